@@ -15,6 +15,10 @@ import { ReverseModeCEditor } from './components/pedals/reverse_mode_c';
 import { MoodMkiiEditor } from './components/pedals/mood_mkii';
 import { BillyStringsWombtoneEditor } from './components/pedals/billy_strings_wombtone';
 import { LossyEditor } from './components/pedals/lossy';
+import { ThermaeEditor } from './components/pedals/thermae';
+import { DarkWorldEditor } from './components/pedals/dark_world';
+import { HabitEditor } from './components/pedals/habit';
+import { ColliderEditor } from './components/pedals/collider';
 import { DeviceMismatchWarning } from './components/DeviceMismatchWarning';
 import { pedalRegistry } from './lib/midi/pedalRegistry';
 import { detectDeviceMismatch } from './lib/midi/deviceMismatchDetection';
@@ -33,6 +37,10 @@ import './lib/midi/pedals/reverse-mode-c';
 import './lib/midi/pedals/mood-mkii';
 import './lib/midi/pedals/billy-strings-wombtone';
 import './lib/midi/pedals/lossy';
+import './lib/midi/pedals/thermae';
+import './lib/midi/pedals/dark-world';
+import './lib/midi/pedals/habit';
+import './lib/midi/pedals/collider';
 
 // Expose MIDI for console testing (dev only)
 import * as midi from './lib/midi/pedals/microcosm';
@@ -144,6 +152,14 @@ function App() {
         return <BillyStringsWombtoneEditor deviceName={connectedDevice.name} />;
       case 'Lossy':
         return <LossyEditor deviceName={connectedDevice.name} />;
+      case 'Thermae':
+        return <ThermaeEditor deviceName={connectedDevice.name} />;
+      case 'DarkWorld':
+        return <DarkWorldEditor deviceName={connectedDevice.name} />;
+      case 'Habit':
+        return <HabitEditor deviceName={connectedDevice.name} />;
+      case 'Collider':
+        return <ColliderEditor deviceName={connectedDevice.name} />;
       default:
         return <ComingSoonEditor pedalType={activePedalType} />;
     }
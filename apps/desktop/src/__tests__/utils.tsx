@@ -32,7 +32,9 @@ export function createMockPreset(overrides = {}) {
     description: 'A test preset',
     parameters: {},
     tags: [],
+    metadata: {},
     isFavorite: false,
+    isArchived: false,
     createdAt: Date.now(),
     updatedAt: Date.now(),
     ...overrides,
@@ -6,12 +6,26 @@
  */
 
 import { useState } from 'react';
-import { requestDeviceIdentity, type DeviceIdentity } from '@/lib/midi/deviceIdentity';
+import { requestDeviceIdentityWithDiagnostics, type DeviceIdentity, type IdentityDiagnostics } from '@/lib/midi/deviceIdentity';
 
 interface DeviceIdentityDebugProps {
   deviceName: string;
 }
 
+function describeFailure(diagnostics: IdentityDiagnostics): string {
+  const attempts = `${diagnostics.attempts} attempt(s)`;
+  if (!diagnostics.output_port) {
+    return `No MIDI output port matched this device after ${attempts}`;
+  }
+  if (!diagnostics.input_port) {
+    return `No MIDI input port matched this device after ${attempts} - replies can't be heard`;
+  }
+  if (diagnostics.other_sysex_count > 0) {
+    return `Device ignored the inquiry after ${attempts} (${diagnostics.other_sysex_count} other SysEx message(s) seen)`;
+  }
+  return `Device ignored the inquiry after ${attempts} (timeout or device does not support identity request)`;
+}
+
 export function DeviceIdentityDebug({ deviceName }: DeviceIdentityDebugProps) {
   const [identity, setIdentity] = useState<DeviceIdentity | null>(null);
   const [isLoading, setIsLoading] = useState(false);
@@ -23,11 +37,11 @@ export function DeviceIdentityDebug({ deviceName }: DeviceIdentityDebugProps) {
     setIdentity(null);
 
     try {
-      const result = await requestDeviceIdentity(deviceName, 3000);
-      if (result) {
-        setIdentity(result);
+      const result = await requestDeviceIdentityWithDiagnostics(deviceName, { timeoutMs: 3000 });
+      if (result.identity) {
+        setIdentity(result.identity);
       } else {
-        setError('No response from device (timeout or device does not support identity request)');
+        setError(describeFailure(result.diagnostics));
       }
     } catch (err) {
       setError(err instanceof Error ? err.message : 'Unknown error');
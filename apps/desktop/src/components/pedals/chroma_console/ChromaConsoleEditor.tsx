@@ -483,6 +483,12 @@ export function ChromaConsoleEditor({ deviceName }: ChromaConsoleEditorProps) {
                   onChange={(v) => editor.setBypassState(v ? 'Bypass' : 'Engaged')}
                   activeColor="green"
                 />
+                <Toggle
+                  label="Dual Footswitch"
+                  value={editor.bypassMode === 'Dual'}
+                  onChange={(v) => editor.setBypassMode(v ? 'Dual' : 'Standard')}
+                  activeColor="blue"
+                />
                 <UtilityDivider />
                 <TapButton
                   label="TAP Tempo"
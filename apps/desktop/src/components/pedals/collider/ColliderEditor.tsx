@@ -0,0 +1,296 @@
+// Source Audio Collider Editor Component
+
+import { useState } from 'react';
+import { useColliderEditor } from '@/hooks/pedals/collider/useColliderEditor';
+import { Knob } from '@/components/common/Knob';
+import { Toggle } from '@/components/common/Toggle';
+import { VerticalSelector } from '@/components/common/VerticalSelector';
+import { PedalUtilityCard } from '@/components/common/PedalUtilityCard';
+import { PresetManagementCard } from '@/components/common/PresetManagementCard';
+import { SaveToLibraryDialog } from '@/components/presets/SaveToLibraryDialog';
+import { PresetDrawer } from '@/components/presets/PresetDrawer';
+import { Save, Library, RotateCcw } from 'lucide-react';
+import { MAX_DELAY_TIME } from '@/lib/midi/pedals/collider';
+import type { ColliderState, EffectRouting } from '@/lib/midi/pedals/collider';
+
+interface ColliderEditorProps {
+  deviceName: string;
+}
+
+export function ColliderEditor({ deviceName }: ColliderEditorProps) {
+  const editor = useColliderEditor(deviceName);
+  const {
+    state, isLoading, error,
+    setDelayTimeA, setDelayRepeatsA, setDelayMixA,
+    setDelayTimeB, setDelayRepeatsB, setDelayMixB,
+    setReverbSizeA, setReverbDecayA, setReverbMixA,
+    setReverbSizeB, setReverbDecayB, setReverbMixB,
+    setRouting, setBypass, setExpression,
+    loadPreset, activePreset, isDirty, resetToPreset, resetToPedalDefault, clearActivePreset,
+  } = editor;
+
+  const [libraryDialogOpen, setLibraryDialogOpen] = useState(false);
+  const [managerOpen, setManagerOpen] = useState(false);
+  const [updating, setUpdating] = useState(false);
+
+  if (isLoading) {
+    return (
+      <div className="flex items-center justify-center h-full">
+        <div className="text-lg">Loading Collider...</div>
+      </div>
+    );
+  }
+
+  if (error) {
+    return (
+      <div className="flex items-center justify-center h-full">
+        <div className="text-lg text-red-500">Error: {error}</div>
+      </div>
+    );
+  }
+
+  if (!state) {
+    return (
+      <div className="flex items-center justify-center h-full">
+        <div className="text-lg">No state available</div>
+      </div>
+    );
+  }
+
+  const handleLibrarySaved = async (presetId: string, presetName: string) => {
+    if (state) await loadPreset(state, presetId, presetName);
+  };
+
+  const handleUpdatePreset = async () => {
+    if (!activePreset || !state) return;
+    try {
+      setUpdating(true);
+      const { updatePreset, savePresetToBank, getBankState } = await import('@/lib/presets');
+      await updatePreset({ id: activePreset.id, parameters: state });
+      const bankState = await getBankState('Collider');
+      const assignedBanks = bankState.filter((slot) => slot.preset?.id === activePreset.id);
+      for (const bank of assignedBanks) {
+        await savePresetToBank(deviceName, activePreset.id, bank.bankNumber);
+      }
+      await loadPreset(state, activePreset.id, activePreset.name);
+    } finally {
+      setUpdating(false);
+    }
+  };
+
+  const handleLoadPreset = async (presetState: ColliderState, presetId?: string, presetName?: string, skipMidiSend?: boolean) => {
+    loadPreset(presetState, presetId, presetName, skipMidiSend);
+    setManagerOpen(false);
+  };
+
+  const delayColor = '#3b82f6';
+  const reverbColor = '#f97316';
+  const routingColors = ['#6b7280', '#3b82f6', '#f97316'];
+
+  const routingOptions = [
+    { value: 'DelayIntoReverb', label: 'D > R' },
+    { value: 'ReverbIntoDelay', label: 'R > D' },
+    { value: 'Parallel', label: 'PARALLEL' },
+  ];
+
+  return (
+    <div className="h-full overflow-y-auto bg-gradient-to-br from-gray-900 to-gray-800">
+      <button
+        onClick={() => setManagerOpen(true)}
+        className="fixed top-4 right-4 z-50 p-2 bg-card-bg hover:bg-control-hover rounded-md border border-control-border transition-colors shadow-lg"
+        title="Open Preset Manager"
+        aria-label="Open Preset Manager"
+      >
+        <Library className="w-5 h-5 text-text-primary" />
+      </button>
+
+      <div className="max-w-4xl mx-auto p-4 space-y-4">
+        {/* Header */}
+        <div className="text-center mb-4">
+          <h1 className="text-xl font-bold text-white mb-1">Source Audio Collider</h1>
+          {activePreset && (
+            <div className="text-sm">
+              <span className="text-gray-400">Active: </span>
+              <span className="text-white font-semibold">{activePreset.name}</span>
+              {isDirty && <span className="ml-2 text-yellow-400">●</span>}
+            </div>
+          )}
+        </div>
+
+        {/* Utility Cards */}
+        <div className="grid grid-cols-1 md:grid-cols-2 gap-3 mb-4">
+          <PedalUtilityCard>
+            <div className="flex items-center gap-4 flex-wrap">
+              <Toggle label="Bypass" value={state.bypass} onChange={setBypass} activeColor="green" />
+            </div>
+          </PedalUtilityCard>
+          <PresetManagementCard
+            activePreset={activePreset ? { name: activePreset.name, isDirty } : null}
+          >
+            <div className="grid grid-cols-2 gap-2 w-full">
+              {activePreset ? (
+                isDirty ? (
+                  <>
+                    <button
+                      onClick={handleUpdatePreset}
+                      disabled={updating}
+                      className="flex items-center justify-center gap-1.5 px-3 py-2 text-xs font-medium rounded-md transition-all disabled:opacity-50"
+                      style={{ backgroundColor: '#10b981', color: '#ffffff' }}
+                    >
+                      <Save className="w-3 h-3" />
+                      {updating ? 'Updating...' : 'Update'}
+                    </button>
+                    <button
+                      onClick={() => setLibraryDialogOpen(true)}
+                      className="flex items-center justify-center gap-1.5 px-3 py-2 text-xs font-medium rounded-md bg-accent-blue/10 hover:bg-accent-blue/20 border border-accent-blue/30 text-accent-blue transition-all"
+                    >
+                      <Library className="w-3 h-3" />
+                      Save to Library
+                    </button>
+                    <button
+                      onClick={resetToPreset}
+                      className="flex items-center justify-center gap-1.5 px-3 py-2 text-xs font-medium border border-control-border rounded-md bg-card-bg text-text-primary hover:bg-control-hover transition-all"
+                    >
+                      <RotateCcw className="w-3 h-3" />
+                      Reset to Preset
+                    </button>
+                    <button
+                      onClick={() => { resetToPedalDefault(); clearActivePreset(); }}
+                      className="flex items-center justify-center gap-1.5 px-3 py-2 text-xs font-medium border border-control-border rounded-md bg-card-bg text-text-primary hover:bg-control-hover transition-all"
+                    >
+                      <RotateCcw className="w-3 h-3" />
+                      Pedal Default
+                    </button>
+                  </>
+                ) : (
+                  <button
+                    onClick={() => { resetToPedalDefault(); clearActivePreset(); }}
+                    className="col-span-2 flex items-center justify-center gap-1.5 px-3 py-2 text-xs font-medium border border-control-border rounded-md bg-card-bg text-text-primary hover:bg-control-hover transition-all"
+                  >
+                    <RotateCcw className="w-3 h-3" />
+                    Pedal Default
+                  </button>
+                )
+              ) : (
+                <button
+                  onClick={() => setLibraryDialogOpen(true)}
+                  className="col-span-2 flex items-center justify-center gap-1.5 px-3 py-2 text-xs font-medium rounded-md bg-accent-blue/10 hover:bg-accent-blue/20 border border-accent-blue/30 text-accent-blue transition-all"
+                >
+                  <Library className="w-3 h-3" />
+                  Save Preset
+                </button>
+              )}
+            </div>
+          </PresetManagementCard>
+        </div>
+
+        {/* Pedal Body */}
+        <div
+          className="relative rounded-xl p-6 shadow-xl border-4"
+          style={{
+            background: 'linear-gradient(135deg, #0a0f1c 0%, #111827 50%, #1c1208 100%)',
+            borderColor: delayColor,
+          }}
+        >
+          <div className="grid grid-cols-1 md:grid-cols-2 gap-6 mb-6">
+            {/* Delays */}
+            <div className="space-y-4">
+              <div className="text-[10px] font-bold uppercase tracking-widest text-center" style={{ color: delayColor }}>Delay</div>
+              <div className="flex justify-center items-end gap-4 flex-wrap">
+                <Knob label="TIME A" value={state.delay_a.time} max={MAX_DELAY_TIME} onChange={setDelayTimeA} color={delayColor} size={64} />
+                <Knob label="REPEATS A" value={state.delay_a.repeats} onChange={setDelayRepeatsA} color={delayColor} size={64} />
+                <Knob label="MIX A" value={state.delay_a.mix} onChange={setDelayMixA} color={delayColor} size={64} />
+              </div>
+              <div className="flex justify-center items-end gap-4 flex-wrap">
+                <Knob label="TIME B" value={state.delay_b.time} max={MAX_DELAY_TIME} onChange={setDelayTimeB} color={delayColor} size={64} />
+                <Knob label="REPEATS B" value={state.delay_b.repeats} onChange={setDelayRepeatsB} color={delayColor} size={64} />
+                <Knob label="MIX B" value={state.delay_b.mix} onChange={setDelayMixB} color={delayColor} size={64} />
+              </div>
+            </div>
+
+            {/* Reverbs */}
+            <div className="space-y-4">
+              <div className="text-[10px] font-bold uppercase tracking-widest text-center" style={{ color: reverbColor }}>Reverb</div>
+              <div className="flex justify-center items-end gap-4 flex-wrap">
+                <Knob label="SIZE A" value={state.reverb_a.size} onChange={setReverbSizeA} color={reverbColor} size={64} />
+                <Knob label="DECAY A" value={state.reverb_a.decay} onChange={setReverbDecayA} color={reverbColor} size={64} />
+                <Knob label="MIX A" value={state.reverb_a.mix} onChange={setReverbMixA} color={reverbColor} size={64} />
+              </div>
+              <div className="flex justify-center items-end gap-4 flex-wrap">
+                <Knob label="SIZE B" value={state.reverb_b.size} onChange={setReverbSizeB} color={reverbColor} size={64} />
+                <Knob label="DECAY B" value={state.reverb_b.decay} onChange={setReverbDecayB} color={reverbColor} size={64} />
+                <Knob label="MIX B" value={state.reverb_b.mix} onChange={setReverbMixB} color={reverbColor} size={64} />
+              </div>
+            </div>
+          </div>
+
+          {/* Divider */}
+          <div className="w-full h-px mb-5" style={{ backgroundColor: `${delayColor}60` }} />
+
+          {/* Shared Controls */}
+          <div className="mb-5">
+            <div className="text-[10px] font-bold uppercase tracking-widest mb-3 text-center" style={{ color: delayColor }}>Shared</div>
+            <div className="flex justify-center items-end gap-5 flex-wrap">
+              <VerticalSelector
+                label="ROUTING"
+                value={state.routing}
+                options={routingOptions}
+                onChange={(v) => setRouting(v as EffectRouting)}
+                optionColors={routingColors}
+              />
+              <Knob label="EXPRESSION" value={state.expression} onChange={setExpression} color={delayColor} size={64} />
+            </div>
+          </div>
+
+          {/* Logo */}
+          <div className="mt-5 text-center">
+            <div className="text-lg font-bold tracking-widest" style={{ color: `${delayColor}cc` }}>
+              SOURCE AUDIO
+            </div>
+            <div className="text-xs text-gray-400 font-semibold tracking-wide">COLLIDER</div>
+          </div>
+        </div>
+
+        {/* Control Reference */}
+        <div className="bg-gray-800/50 border border-gray-700 rounded-lg p-4">
+          <h3 className="text-base font-semibold text-white mb-2">Control Reference</h3>
+          <div className="grid grid-cols-1 md:grid-cols-2 gap-4 text-sm text-gray-300">
+            <div>
+              <h4 className="font-semibold mb-2" style={{ color: delayColor }}>Delay Time</h4>
+              <ul className="space-y-1 text-xs">
+                <li>Sent as 14-bit NRPN for fine steps (0-16383)</li>
+              </ul>
+            </div>
+            <div>
+              <h4 className="font-semibold mb-2" style={{ color: reverbColor }}>Routing</h4>
+              <ul className="space-y-1 text-xs">
+                <li><strong>D &gt; R:</strong> Delays feed the reverbs</li>
+                <li><strong>R &gt; D:</strong> Reverbs feed the delays</li>
+                <li><strong>Parallel:</strong> Both sections summed</li>
+              </ul>
+            </div>
+          </div>
+        </div>
+      </div>
+
+      <SaveToLibraryDialog
+        isOpen={libraryDialogOpen}
+        onClose={() => setLibraryDialogOpen(false)}
+        pedalType="Collider"
+        currentState={state}
+        onSaved={handleLibrarySaved}
+      />
+
+      <PresetDrawer
+        isOpen={managerOpen}
+        onClose={() => setManagerOpen(false)}
+        deviceName={deviceName}
+        pedalType="Collider"
+        currentState={state}
+        activePresetId={activePreset?.id}
+        onLoadPreset={handleLoadPreset}
+        onPresetSaved={handleLibrarySaved}
+      />
+    </div>
+  );
+}
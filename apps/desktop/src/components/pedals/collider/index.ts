@@ -0,0 +1 @@
+export { ColliderEditor } from './ColliderEditor';
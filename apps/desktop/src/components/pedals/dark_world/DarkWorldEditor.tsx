@@ -0,0 +1,331 @@
+// Chase Bliss Dark World Editor Component
+
+import { useState } from 'react';
+import { useDarkWorldEditor } from '@/hooks/pedals/dark_world/useDarkWorldEditor';
+import { Knob } from '@/components/common/Knob';
+import { Toggle } from '@/components/common/Toggle';
+import { VerticalSelector } from '@/components/common/VerticalSelector';
+import { PedalUtilityCard } from '@/components/common/PedalUtilityCard';
+import { PresetManagementCard } from '@/components/common/PresetManagementCard';
+import { SaveToLibraryDialog } from '@/components/presets/SaveToLibraryDialog';
+import { PresetDrawer } from '@/components/presets/PresetDrawer';
+import { Save, Library, RotateCcw } from 'lucide-react';
+import type {
+  DarkWorldState,
+  DarkReverbType,
+  WorldReverbType,
+  EngineRouting,
+} from '@/lib/midi/pedals/dark-world';
+
+interface DarkWorldEditorProps {
+  deviceName: string;
+}
+
+export function DarkWorldEditor({ deviceName }: DarkWorldEditorProps) {
+  const editor = useDarkWorldEditor(deviceName);
+  const {
+    state, isLoading, error,
+    setSideAReverbType, setSideADecay, setSideAMix, setSideATone, setSideAModDepth, setSideAEngaged,
+    setSideBReverbType, setSideBDecay, setSideBMix, setSideBTone, setSideBModDepth, setSideBEngaged,
+    setRouting, setBypass, setExpression,
+    loadPreset, activePreset, isDirty, resetToPreset, resetToPedalDefault, clearActivePreset,
+  } = editor;
+
+  const [libraryDialogOpen, setLibraryDialogOpen] = useState(false);
+  const [managerOpen, setManagerOpen] = useState(false);
+  const [updating, setUpdating] = useState(false);
+
+  if (isLoading) {
+    return (
+      <div className="flex items-center justify-center h-full">
+        <div className="text-lg">Loading Dark World...</div>
+      </div>
+    );
+  }
+
+  if (error) {
+    return (
+      <div className="flex items-center justify-center h-full">
+        <div className="text-lg text-red-500">Error: {error}</div>
+      </div>
+    );
+  }
+
+  if (!state) {
+    return (
+      <div className="flex items-center justify-center h-full">
+        <div className="text-lg">No state available</div>
+      </div>
+    );
+  }
+
+  const handleLibrarySaved = async (presetId: string, presetName: string) => {
+    if (state) await loadPreset(state, presetId, presetName);
+  };
+
+  const handleUpdatePreset = async () => {
+    if (!activePreset || !state) return;
+    try {
+      setUpdating(true);
+      const { updatePreset, savePresetToBank, getBankState } = await import('@/lib/presets');
+      await updatePreset({ id: activePreset.id, parameters: state });
+      const bankState = await getBankState('DarkWorld');
+      const assignedBanks = bankState.filter((slot) => slot.preset?.id === activePreset.id);
+      for (const bank of assignedBanks) {
+        await savePresetToBank(deviceName, activePreset.id, bank.bankNumber);
+      }
+      await loadPreset(state, activePreset.id, activePreset.name);
+    } finally {
+      setUpdating(false);
+    }
+  };
+
+  const handleLoadPreset = async (presetState: DarkWorldState, presetId?: string, presetName?: string, skipMidiSend?: boolean) => {
+    loadPreset(presetState, presetId, presetName, skipMidiSend);
+    setManagerOpen(false);
+  };
+
+  const darkColor = '#8b5cf6';
+  const worldColor = '#38bdf8';
+  const darkSelectorColors = ['#6b7280', '#8b5cf6', '#5b21b6'];
+  const worldSelectorColors = ['#6b7280', '#38bdf8', '#0369a1'];
+
+  const darkTypeOptions = [
+    { value: 'Mod', label: 'MOD' },
+    { value: 'Shimmer', label: 'SHIM' },
+    { value: 'Black', label: 'BLACK' },
+  ];
+  const worldTypeOptions = [
+    { value: 'Hall', label: 'HALL' },
+    { value: 'Plate', label: 'PLATE' },
+    { value: 'Spring', label: 'SPRING' },
+  ];
+  const routingOptions = [
+    { value: 'Series', label: 'SERIES' },
+    { value: 'Split', label: 'SPLIT' },
+    { value: 'Combine', label: 'COMBINE' },
+  ];
+
+  return (
+    <div className="h-full overflow-y-auto bg-gradient-to-br from-gray-900 to-gray-800">
+      <button
+        onClick={() => setManagerOpen(true)}
+        className="fixed top-4 right-4 z-50 p-2 bg-card-bg hover:bg-control-hover rounded-md border border-control-border transition-colors shadow-lg"
+        title="Open Preset Manager"
+        aria-label="Open Preset Manager"
+      >
+        <Library className="w-5 h-5 text-text-primary" />
+      </button>
+
+      <div className="max-w-4xl mx-auto p-4 space-y-4">
+        {/* Header */}
+        <div className="text-center mb-4">
+          <h1 className="text-xl font-bold text-white mb-1">Chase Bliss Dark World</h1>
+          {activePreset && (
+            <div className="text-sm">
+              <span className="text-gray-400">Active: </span>
+              <span className="text-white font-semibold">{activePreset.name}</span>
+              {isDirty && <span className="ml-2 text-yellow-400">●</span>}
+            </div>
+          )}
+        </div>
+
+        {/* Utility Cards */}
+        <div className="grid grid-cols-1 md:grid-cols-2 gap-3 mb-4">
+          <PedalUtilityCard>
+            <div className="flex items-center gap-4 flex-wrap">
+              <Toggle label="Bypass" value={state.bypass} onChange={setBypass} activeColor="green" />
+              <Toggle label="Dark" value={state.side_a.engaged} onChange={setSideAEngaged} activeColor="purple" />
+              <Toggle label="World" value={state.side_b.engaged} onChange={setSideBEngaged} activeColor="blue" />
+            </div>
+          </PedalUtilityCard>
+          <PresetManagementCard
+            activePreset={activePreset ? { name: activePreset.name, isDirty } : null}
+          >
+            <div className="grid grid-cols-2 gap-2 w-full">
+              {activePreset ? (
+                isDirty ? (
+                  <>
+                    <button
+                      onClick={handleUpdatePreset}
+                      disabled={updating}
+                      className="flex items-center justify-center gap-1.5 px-3 py-2 text-xs font-medium rounded-md transition-all disabled:opacity-50"
+                      style={{ backgroundColor: '#10b981', color: '#ffffff' }}
+                    >
+                      <Save className="w-3 h-3" />
+                      {updating ? 'Updating...' : 'Update'}
+                    </button>
+                    <button
+                      onClick={() => setLibraryDialogOpen(true)}
+                      className="flex items-center justify-center gap-1.5 px-3 py-2 text-xs font-medium rounded-md bg-accent-blue/10 hover:bg-accent-blue/20 border border-accent-blue/30 text-accent-blue transition-all"
+                    >
+                      <Library className="w-3 h-3" />
+                      Save to Library
+                    </button>
+                    <button
+                      onClick={resetToPreset}
+                      className="flex items-center justify-center gap-1.5 px-3 py-2 text-xs font-medium border border-control-border rounded-md bg-card-bg text-text-primary hover:bg-control-hover transition-all"
+                    >
+                      <RotateCcw className="w-3 h-3" />
+                      Reset to Preset
+                    </button>
+                    <button
+                      onClick={() => { resetToPedalDefault(); clearActivePreset(); }}
+                      className="flex items-center justify-center gap-1.5 px-3 py-2 text-xs font-medium border border-control-border rounded-md bg-card-bg text-text-primary hover:bg-control-hover transition-all"
+                    >
+                      <RotateCcw className="w-3 h-3" />
+                      Pedal Default
+                    </button>
+                  </>
+                ) : (
+                  <button
+                    onClick={() => { resetToPedalDefault(); clearActivePreset(); }}
+                    className="col-span-2 flex items-center justify-center gap-1.5 px-3 py-2 text-xs font-medium border border-control-border rounded-md bg-card-bg text-text-primary hover:bg-control-hover transition-all"
+                  >
+                    <RotateCcw className="w-3 h-3" />
+                    Pedal Default
+                  </button>
+                )
+              ) : (
+                <button
+                  onClick={() => setLibraryDialogOpen(true)}
+                  className="col-span-2 flex items-center justify-center gap-1.5 px-3 py-2 text-xs font-medium rounded-md bg-accent-blue/10 hover:bg-accent-blue/20 border border-accent-blue/30 text-accent-blue transition-all"
+                >
+                  <Library className="w-3 h-3" />
+                  Save Preset
+                </button>
+              )}
+            </div>
+          </PresetManagementCard>
+        </div>
+
+        {/* Pedal Body */}
+        <div
+          className="relative rounded-xl p-6 shadow-xl border-4"
+          style={{
+            background: 'linear-gradient(135deg, #0b0614 0%, #140c24 50%, #0a1622 100%)',
+            borderColor: darkColor,
+          }}
+        >
+          <div className="grid grid-cols-1 md:grid-cols-2 gap-6 mb-6">
+            {/* Side A - Dark */}
+            <div className="space-y-4">
+              <div className="text-[10px] font-bold uppercase tracking-widest text-center" style={{ color: darkColor }}>Dark</div>
+              <div className="flex justify-center items-end gap-4 flex-wrap">
+                <Knob label="DECAY" value={state.side_a.decay} onChange={setSideADecay} color={darkColor} size={64} />
+                <Knob label="MIX" value={state.side_a.mix} onChange={setSideAMix} color={darkColor} size={64} />
+                <Knob label="TONE" value={state.side_a.tone} onChange={setSideATone} color={darkColor} size={64} />
+                <Knob label="MOD" value={state.side_a.mod_depth} onChange={setSideAModDepth} color={darkColor} size={64} />
+              </div>
+              <div className="flex justify-center">
+                <VerticalSelector
+                  label="DARK TYPE"
+                  value={state.side_a.reverb_type}
+                  options={darkTypeOptions}
+                  onChange={(v) => setSideAReverbType(v as DarkReverbType)}
+                  optionColors={darkSelectorColors}
+                />
+              </div>
+            </div>
+
+            {/* Side B - World */}
+            <div className="space-y-4">
+              <div className="text-[10px] font-bold uppercase tracking-widest text-center" style={{ color: worldColor }}>World</div>
+              <div className="flex justify-center items-end gap-4 flex-wrap">
+                <Knob label="DECAY" value={state.side_b.decay} onChange={setSideBDecay} color={worldColor} size={64} />
+                <Knob label="MIX" value={state.side_b.mix} onChange={setSideBMix} color={worldColor} size={64} />
+                <Knob label="TONE" value={state.side_b.tone} onChange={setSideBTone} color={worldColor} size={64} />
+                <Knob label="MOD" value={state.side_b.mod_depth} onChange={setSideBModDepth} color={worldColor} size={64} />
+              </div>
+              <div className="flex justify-center">
+                <VerticalSelector
+                  label="WORLD TYPE"
+                  value={state.side_b.reverb_type}
+                  options={worldTypeOptions}
+                  onChange={(v) => setSideBReverbType(v as WorldReverbType)}
+                  optionColors={worldSelectorColors}
+                />
+              </div>
+            </div>
+          </div>
+
+          {/* Divider */}
+          <div className="w-full h-px mb-5" style={{ backgroundColor: `${darkColor}60` }} />
+
+          {/* Shared Controls */}
+          <div className="mb-5">
+            <div className="text-[10px] font-bold uppercase tracking-widest mb-3 text-center" style={{ color: darkColor }}>Shared</div>
+            <div className="flex justify-center items-end gap-5 flex-wrap">
+              <VerticalSelector
+                label="ROUTING"
+                value={state.routing}
+                options={routingOptions}
+                onChange={(v) => setRouting(v as EngineRouting)}
+                optionColors={darkSelectorColors}
+              />
+              <Knob label="EXPRESSION" value={state.expression} onChange={setExpression} color={darkColor} size={64} />
+            </div>
+          </div>
+
+          {/* Logo */}
+          <div className="mt-5 text-center">
+            <div className="text-lg font-bold tracking-widest" style={{ color: `${darkColor}cc` }}>
+              CHASE BLISS AUDIO
+            </div>
+            <div className="text-xs text-gray-400 font-semibold tracking-wide">DARK WORLD</div>
+          </div>
+        </div>
+
+        {/* Control Reference */}
+        <div className="bg-gray-800/50 border border-gray-700 rounded-lg p-4">
+          <h3 className="text-base font-semibold text-white mb-2">Control Reference</h3>
+          <div className="grid grid-cols-1 md:grid-cols-3 gap-4 text-sm text-gray-300">
+            <div>
+              <h4 className="font-semibold mb-2" style={{ color: darkColor }}>Dark</h4>
+              <ul className="space-y-1 text-xs">
+                <li><strong>Mod:</strong> Modulated reverb</li>
+                <li><strong>Shimmer:</strong> Pitch-shifted reverb</li>
+                <li><strong>Black:</strong> Dark, swelling reverb</li>
+              </ul>
+            </div>
+            <div>
+              <h4 className="font-semibold mb-2" style={{ color: worldColor }}>World</h4>
+              <ul className="space-y-1 text-xs">
+                <li><strong>Hall:</strong> Large room</li>
+                <li><strong>Plate:</strong> Bright, dense plate</li>
+                <li><strong>Spring:</strong> Spring tank</li>
+              </ul>
+            </div>
+            <div>
+              <h4 className="font-semibold mb-2" style={{ color: darkColor }}>Routing</h4>
+              <ul className="space-y-1 text-xs">
+                <li><strong>Series:</strong> Dark feeds World</li>
+                <li><strong>Split:</strong> Dark left, World right</li>
+                <li><strong>Combine:</strong> Both in parallel</li>
+              </ul>
+            </div>
+          </div>
+        </div>
+      </div>
+
+      <SaveToLibraryDialog
+        isOpen={libraryDialogOpen}
+        onClose={() => setLibraryDialogOpen(false)}
+        pedalType="DarkWorld"
+        currentState={state}
+        onSaved={handleLibrarySaved}
+      />
+
+      <PresetDrawer
+        isOpen={managerOpen}
+        onClose={() => setManagerOpen(false)}
+        deviceName={deviceName}
+        pedalType="DarkWorld"
+        currentState={state}
+        activePresetId={activePreset?.id}
+        onLoadPreset={handleLoadPreset}
+        onPresetSaved={handleLibrarySaved}
+      />
+    </div>
+  );
+}
@@ -0,0 +1 @@
+export { DarkWorldEditor } from './DarkWorldEditor';
@@ -0,0 +1,408 @@
+// Chase Bliss Habit Editor Component
+
+import { useState } from 'react';
+import { useHabitEditor } from '@/hooks/pedals/habit/useHabitEditor';
+import { Knob } from '@/components/common/Knob';
+import { Toggle } from '@/components/common/Toggle';
+import { DipSwitch } from '@/components/common/DipSwitch';
+import { VerticalSelector } from '@/components/common/VerticalSelector';
+import { PedalUtilityCard } from '@/components/common/PedalUtilityCard';
+import { PresetManagementCard } from '@/components/common/PresetManagementCard';
+import { SaveToLibraryDialog } from '@/components/presets/SaveToLibraryDialog';
+import { PresetDrawer } from '@/components/presets/PresetDrawer';
+import { Save, Library, RotateCcw, Eraser } from 'lucide-react';
+import type {
+  HabitState,
+  Modifier,
+  ScanMode,
+  CollectMode,
+} from '@/lib/midi/pedals/habit';
+
+interface HabitEditorProps {
+  deviceName: string;
+}
+
+export function HabitEditor({ deviceName }: HabitEditorProps) {
+  const editor = useHabitEditor(deviceName);
+  const {
+    state, isLoading, error,
+    setLevel, setRepeats, setSize, setScan, setSpread, setModify, setRampSpeed,
+    setModifier, setScanMode, setCollectMode,
+    setBypass, setMemory, setAltMode, setOverdub, setMemoryHold, clearMemory,
+    setDipLevel, setDipRepeats, setDipSize, setDipScan, setDipSpread, setDipBounce,
+    setDipSweep, setDipPolarity,
+    setDipMiso, setDipStereo, setDipLatch, setDipSync,
+    setDipTrails, setDipNoDub, setDipFeedbackLoop, setDipManual,
+    setMidiClockIgnore, setRampBounce, setExpression,
+    loadPreset, activePreset, isDirty, resetToPreset, resetToPedalDefault, clearActivePreset,
+  } = editor;
+
+  const [libraryDialogOpen, setLibraryDialogOpen] = useState(false);
+  const [managerOpen, setManagerOpen] = useState(false);
+  const [updating, setUpdating] = useState(false);
+
+  if (isLoading) {
+    return (
+      <div className="flex items-center justify-center h-full">
+        <div className="text-lg">Loading Habit...</div>
+      </div>
+    );
+  }
+
+  if (error) {
+    return (
+      <div className="flex items-center justify-center h-full">
+        <div className="text-lg text-red-500">Error: {error}</div>
+      </div>
+    );
+  }
+
+  if (!state) {
+    return (
+      <div className="flex items-center justify-center h-full">
+        <div className="text-lg">No state available</div>
+      </div>
+    );
+  }
+
+  const handleLibrarySaved = async (presetId: string, presetName: string) => {
+    if (state) await loadPreset(state, presetId, presetName);
+  };
+
+  const handleUpdatePreset = async () => {
+    if (!activePreset || !state) return;
+    try {
+      setUpdating(true);
+      const { updatePreset, savePresetToBank, getBankState } = await import('@/lib/presets');
+      await updatePreset({ id: activePreset.id, parameters: state });
+      const bankState = await getBankState('Habit');
+      const assignedBanks = bankState.filter((slot) => slot.preset?.id === activePreset.id);
+      for (const bank of assignedBanks) {
+        await savePresetToBank(deviceName, activePreset.id, bank.bankNumber);
+      }
+      await loadPreset(state, activePreset.id, activePreset.name);
+    } finally {
+      setUpdating(false);
+    }
+  };
+
+  const handleLoadPreset = async (presetState: HabitState, presetId?: string, presetName?: string, skipMidiSend?: boolean) => {
+    loadPreset(presetState, presetId, presetName, skipMidiSend);
+    setManagerOpen(false);
+  };
+
+  const knobColor = '#22c55e';
+  const dipColor = '#22c55e';
+  const selectorColors = ['#6b7280', '#22c55e', '#15803d'];
+
+  const modifierOptions = [
+    { value: 'Stretch', label: 'STRETCH' },
+    { value: 'Pitch', label: 'PITCH' },
+    { value: 'Filter', label: 'FILTER' },
+  ];
+  const scanModeOptions = [
+    { value: 'Off', label: 'OFF' },
+    { value: 'Manual', label: 'MANUAL' },
+    { value: 'Auto', label: 'AUTO' },
+  ];
+  const collectModeOptions = [
+    { value: 'In', label: 'IN' },
+    { value: 'Feed', label: 'FEED' },
+    { value: 'Out', label: 'OUT' },
+  ];
+
+  return (
+    <div className="h-full overflow-y-auto bg-gradient-to-br from-gray-900 to-gray-800">
+      <button
+        onClick={() => setManagerOpen(true)}
+        className="fixed top-4 right-4 z-50 p-2 bg-card-bg hover:bg-control-hover rounded-md border border-control-border transition-colors shadow-lg"
+        title="Open Preset Manager"
+        aria-label="Open Preset Manager"
+      >
+        <Library className="w-5 h-5 text-text-primary" />
+      </button>
+
+      <div className="max-w-4xl mx-auto p-4 space-y-4">
+        {/* Header */}
+        <div className="text-center mb-4">
+          <h1 className="text-xl font-bold text-white mb-1">Chase Bliss Habit</h1>
+          {activePreset && (
+            <div className="text-sm">
+              <span className="text-gray-400">Active: </span>
+              <span className="text-white font-semibold">{activePreset.name}</span>
+              {isDirty && <span className="ml-2 text-yellow-400">●</span>}
+            </div>
+          )}
+        </div>
+
+        {/* Utility Cards */}
+        <div className="grid grid-cols-1 md:grid-cols-2 gap-3 mb-4">
+          <PedalUtilityCard>
+            <div className="flex items-center gap-4 flex-wrap">
+              <Toggle label="Bypass" value={state.bypass} onChange={setBypass} activeColor="green" />
+              <Toggle label="Memory" value={state.memory} onChange={setMemory} activeColor="blue" />
+              <Toggle label="Alt" value={state.alt_mode} onChange={setAltMode} activeColor="purple" />
+              <Toggle label="Overdub" value={state.overdub} onChange={setOverdub} activeColor="orange" />
+              <Toggle label="Hold" value={state.memory_hold} onChange={setMemoryHold} activeColor="blue" />
+              <button
+                onClick={clearMemory}
+                className="flex items-center gap-1.5 px-3 py-2 text-xs font-medium border border-control-border rounded-md bg-card-bg text-text-primary hover:bg-control-hover transition-all"
+                title="Erase the pedal's memory"
+              >
+                <Eraser className="w-3 h-3" />
+                Clear Memory
+              </button>
+            </div>
+          </PedalUtilityCard>
+          <PresetManagementCard
+            activePreset={activePreset ? { name: activePreset.name, isDirty } : null}
+          >
+            <div className="grid grid-cols-2 gap-2 w-full">
+              {activePreset ? (
+                isDirty ? (
+                  <>
+                    <button
+                      onClick={handleUpdatePreset}
+                      disabled={updating}
+                      className="flex items-center justify-center gap-1.5 px-3 py-2 text-xs font-medium rounded-md transition-all disabled:opacity-50"
+                      style={{ backgroundColor: '#10b981', color: '#ffffff' }}
+                    >
+                      <Save className="w-3 h-3" />
+                      {updating ? 'Updating...' : 'Update'}
+                    </button>
+                    <button
+                      onClick={() => setLibraryDialogOpen(true)}
+                      className="flex items-center justify-center gap-1.5 px-3 py-2 text-xs font-medium rounded-md bg-accent-blue/10 hover:bg-accent-blue/20 border border-accent-blue/30 text-accent-blue transition-all"
+                    >
+                      <Library className="w-3 h-3" />
+                      Save to Library
+                    </button>
+                    <button
+                      onClick={resetToPreset}
+                      className="flex items-center justify-center gap-1.5 px-3 py-2 text-xs font-medium border border-control-border rounded-md bg-card-bg text-text-primary hover:bg-control-hover transition-all"
+                    >
+                      <RotateCcw className="w-3 h-3" />
+                      Reset to Preset
+                    </button>
+                    <button
+                      onClick={() => { resetToPedalDefault(); clearActivePreset(); }}
+                      className="flex items-center justify-center gap-1.5 px-3 py-2 text-xs font-medium border border-control-border rounded-md bg-card-bg text-text-primary hover:bg-control-hover transition-all"
+                    >
+                      <RotateCcw className="w-3 h-3" />
+                      Pedal Default
+                    </button>
+                  </>
+                ) : (
+                  <button
+                    onClick={() => { resetToPedalDefault(); clearActivePreset(); }}
+                    className="col-span-2 flex items-center justify-center gap-1.5 px-3 py-2 text-xs font-medium border border-control-border rounded-md bg-card-bg text-text-primary hover:bg-control-hover transition-all"
+                  >
+                    <RotateCcw className="w-3 h-3" />
+                    Pedal Default
+                  </button>
+                )
+              ) : (
+                <button
+                  onClick={() => setLibraryDialogOpen(true)}
+                  className="col-span-2 flex items-center justify-center gap-1.5 px-3 py-2 text-xs font-medium rounded-md bg-accent-blue/10 hover:bg-accent-blue/20 border border-accent-blue/30 text-accent-blue transition-all"
+                >
+                  <Library className="w-3 h-3" />
+                  Save Preset
+                </button>
+              )}
+            </div>
+          </PresetManagementCard>
+        </div>
+
+        {/* Pedal Body */}
+        <div
+          className="relative rounded-xl p-6 shadow-xl border-4"
+          style={{
+            background: 'linear-gradient(135deg, #001a0a 0%, #082a14 50%, #103a1e 100%)',
+            borderColor: knobColor,
+          }}
+        >
+          {/* DIP Switches */}
+          <div className="mb-6">
+            <div className="text-[10px] font-bold uppercase tracking-widest mb-4 text-center" style={{ color: knobColor }}>
+              DIP Switches
+            </div>
+            <div className="grid grid-cols-2 gap-x-8 gap-y-0">
+              {/* Left Bank */}
+              <div>
+                <div className="text-[9px] font-semibold text-gray-400 uppercase tracking-wider mb-2">Left Bank</div>
+                <div className="space-y-2.5">
+                  <DipSwitch horizontal label="Level" description="Ramp the level knob" value={state.dip_level} onChange={setDipLevel} activeColor={dipColor} />
+                  <DipSwitch horizontal label="Repeats" description="Ramp the repeats knob" value={state.dip_repeats} onChange={setDipRepeats} activeColor={dipColor} />
+                  <DipSwitch horizontal label="Size" description="Ramp the size knob" value={state.dip_size} onChange={setDipSize} activeColor={dipColor} />
+                  <DipSwitch horizontal label="Scan" description="Ramp the scan knob" value={state.dip_scan} onChange={setDipScan} activeColor={dipColor} />
+                  <DipSwitch horizontal label="Spread" description="Ramp the spread knob" value={state.dip_spread} onChange={setDipSpread} activeColor={dipColor} />
+                  <DipSwitch horizontal label="Bounce" description="Ramp bounces back and forth" value={state.dip_bounce} onChange={setDipBounce} activeColor={dipColor} />
+                  <DipSwitch
+                    horizontal
+                    label="Sweep"
+                    description={state.dip_sweep === 'Top' ? 'Sweep from top' : 'Sweep from bottom'}
+                    value={state.dip_sweep === 'Top'}
+                    onChange={(v) => setDipSweep(v ? 'Top' : 'Bottom')}
+                    activeColor={dipColor}
+                  />
+                  <DipSwitch
+                    horizontal
+                    label="Polarity"
+                    description={state.dip_polarity === 'Reverse' ? 'Reversed' : 'Normal'}
+                    value={state.dip_polarity === 'Reverse'}
+                    onChange={(v) => setDipPolarity(v ? 'Reverse' : 'Forward')}
+                    activeColor={dipColor}
+                  />
+                </div>
+              </div>
+
+              {/* Right Bank */}
+              <div>
+                <div className="text-[9px] font-semibold text-gray-400 uppercase tracking-wider mb-2">Right Bank</div>
+                <div className="space-y-2.5">
+                  <DipSwitch horizontal label="MISO" description="Mono in, stereo out" value={state.dip_miso} onChange={setDipMiso} activeColor={dipColor} />
+                  <DipSwitch horizontal label="Stereo" description="Stereo in and out" value={state.dip_stereo} onChange={setDipStereo} activeColor={dipColor} />
+                  <DipSwitch horizontal label="Latch" description="Latch footswitch" value={state.dip_latch} onChange={setDipLatch} activeColor={dipColor} />
+                  <DipSwitch horizontal label="Sync" description="Sync to MIDI clock" value={state.dip_sync} onChange={setDipSync} activeColor={dipColor} />
+                  <DipSwitch horizontal label="Trails" description="Trails on bypass" value={state.dip_trails} onChange={setDipTrails} activeColor={dipColor} />
+                  <DipSwitch horizontal label="No Dub" description="Overdub replaces memory" value={state.dip_no_dub} onChange={setDipNoDub} activeColor={dipColor} />
+                  <DipSwitch horizontal label="FB Loop" description="Memory in the feedback loop" value={state.dip_feedback_loop} onChange={setDipFeedbackLoop} activeColor={dipColor} />
+                  <DipSwitch horizontal label="Manual" description="Manual memory collection" value={state.dip_manual} onChange={setDipManual} activeColor={dipColor} />
+                </div>
+              </div>
+            </div>
+          </div>
+
+          {/* Divider */}
+          <div className="w-full h-px mb-5" style={{ backgroundColor: `${knobColor}60` }} />
+
+          {/* Main Knobs */}
+          <div className="mb-6 space-y-4">
+            <div className="text-[10px] font-bold uppercase tracking-widest text-center" style={{ color: knobColor }}>Main</div>
+            <div className="flex justify-center items-end gap-4 flex-wrap">
+              <Knob label="LEVEL" value={state.level} onChange={setLevel} color={knobColor} size={72} />
+              <Knob label="REPEATS" value={state.repeats} onChange={setRepeats} color={knobColor} size={72} />
+              <Knob label="SIZE" value={state.size} onChange={setSize} color={knobColor} size={72} />
+              <Knob label="SCAN" value={state.scan} onChange={setScan} color={knobColor} size={72} />
+              <Knob label="SPREAD" value={state.spread} onChange={setSpread} color={knobColor} size={72} />
+              <Knob label="MODIFY" value={state.modify} onChange={setModify} color={knobColor} size={72} />
+              <Knob label="RAMP" value={state.ramp_speed} onChange={setRampSpeed} color={knobColor} size={72} />
+            </div>
+
+            <div className="text-[10px] font-bold uppercase tracking-widest text-center pt-2" style={{ color: knobColor }}>Utility</div>
+            <div className="flex justify-center items-end gap-4 flex-wrap">
+              <Knob label="EXPRESSION" value={state.expression} onChange={setExpression} color={knobColor} size={72} />
+              <Toggle label="RAMP/BNC" value={state.ramp_bounce} onChange={setRampBounce} activeColor="green" />
+              <Toggle label="IGNORE CLOCK" value={state.midi_clock_ignore} onChange={setMidiClockIgnore} activeColor="green" />
+            </div>
+          </div>
+
+          {/* Divider */}
+          <div className="w-full h-px mb-5" style={{ backgroundColor: `${knobColor}60` }} />
+
+          {/* Toggle Selectors */}
+          <div className="mb-5">
+            <div className="text-[10px] font-bold uppercase tracking-widest mb-3 text-center" style={{ color: knobColor }}>Controls</div>
+            <div className="flex justify-center items-start gap-5 flex-wrap">
+              <VerticalSelector
+                label="MODIFIER"
+                value={state.modifier}
+                options={modifierOptions}
+                onChange={(v) => setModifier(v as Modifier)}
+                optionColors={selectorColors}
+              />
+              <VerticalSelector
+                label="SCAN"
+                value={state.scan_mode}
+                options={scanModeOptions}
+                onChange={(v) => setScanMode(v as ScanMode)}
+                optionColors={selectorColors}
+              />
+              <VerticalSelector
+                label="COLLECT"
+                value={state.collect_mode}
+                options={collectModeOptions}
+                onChange={(v) => setCollectMode(v as CollectMode)}
+                optionColors={selectorColors}
+              />
+            </div>
+          </div>
+
+          {/* Logo */}
+          <div className="mt-5 text-center">
+            <div className="text-lg font-bold tracking-widest" style={{ color: `${knobColor}cc` }}>
+              CHASE BLISS AUDIO
+            </div>
+            <div className="text-xs text-gray-400 font-semibold tracking-wide">HABIT</div>
+          </div>
+        </div>
+
+        {/* Control Reference */}
+        <div className="bg-gray-800/50 border border-gray-700 rounded-lg p-4">
+          <h3 className="text-base font-semibold text-white mb-2">Control Reference</h3>
+          <div className="grid grid-cols-1 md:grid-cols-3 gap-4 text-sm text-gray-300">
+            <div>
+              <h4 className="font-semibold mb-2" style={{ color: knobColor }}>Main Knobs</h4>
+              <ul className="space-y-1 text-xs">
+                <li><strong>Level:</strong> Volume of the echoes</li>
+                <li><strong>Repeats:</strong> Echo feedback</li>
+                <li><strong>Size:</strong> Echo time</li>
+                <li><strong>Scan:</strong> How far back into memory the pedal reads</li>
+                <li><strong>Spread:</strong> Spacing between memory reads</li>
+                <li><strong>Modify:</strong> Amount of the selected modifier</li>
+                <li><strong>Ramp:</strong> Ramp modulation speed</li>
+              </ul>
+            </div>
+            <div>
+              <h4 className="font-semibold mb-2" style={{ color: knobColor }}>Toggles</h4>
+              <ul className="space-y-1 text-xs">
+                <li><strong>Modifier:</strong> Stretch/Pitch/Filter</li>
+                <li><strong>Scan:</strong> Off/Manual/Auto memory reads</li>
+                <li><strong>Collect:</strong> Record from input, feedback or output</li>
+              </ul>
+              <h4 className="font-semibold mb-2 mt-3" style={{ color: knobColor }}>Footswitches</h4>
+              <ul className="space-y-1 text-xs">
+                <li><strong>Bypass:</strong> Effect on/off</li>
+                <li><strong>Memory:</strong> Play from memory</li>
+                <li><strong>Overdub:</strong> Write echoes back into memory</li>
+                <li><strong>Hold:</strong> Stop collecting into memory</li>
+                <li><strong>Clear Memory:</strong> Erase everything collected</li>
+              </ul>
+            </div>
+            <div>
+              <h4 className="font-semibold mb-2" style={{ color: knobColor }}>DIP Switches</h4>
+              <ul className="space-y-1 text-xs">
+                <li><strong>MISO:</strong> Mono in, stereo out</li>
+                <li><strong>Stereo:</strong> Stereo in and out</li>
+                <li><strong>Sync:</strong> Follow MIDI clock</li>
+                <li><strong>No Dub:</strong> Overdub replaces instead of layering</li>
+                <li><strong>FB Loop:</strong> Memory reads feed the echo loop</li>
+                <li><strong>Manual:</strong> Collect only while held</li>
+              </ul>
+            </div>
+          </div>
+        </div>
+      </div>
+
+      <SaveToLibraryDialog
+        isOpen={libraryDialogOpen}
+        onClose={() => setLibraryDialogOpen(false)}
+        pedalType="Habit"
+        currentState={state}
+        onSaved={handleLibrarySaved}
+      />
+
+      <PresetDrawer
+        isOpen={managerOpen}
+        onClose={() => setManagerOpen(false)}
+        deviceName={deviceName}
+        pedalType="Habit"
+        currentState={state}
+        activePresetId={activePreset?.id}
+        onLoadPreset={handleLoadPreset}
+        onPresetSaved={handleLibrarySaved}
+      />
+    </div>
+  );
+}
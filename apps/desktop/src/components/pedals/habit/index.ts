@@ -0,0 +1 @@
+export { HabitEditor } from './HabitEditor';
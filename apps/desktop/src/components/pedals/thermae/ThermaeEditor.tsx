@@ -0,0 +1,416 @@
+// Chase Bliss Thermae Editor Component
+
+import { useState } from 'react';
+import { useThermaeEditor } from '@/hooks/pedals/thermae/useThermaeEditor';
+import { Knob } from '@/components/common/Knob';
+import { Toggle } from '@/components/common/Toggle';
+import { DipSwitch } from '@/components/common/DipSwitch';
+import { VerticalSelector } from '@/components/common/VerticalSelector';
+import { PedalUtilityCard } from '@/components/common/PedalUtilityCard';
+import { PresetManagementCard } from '@/components/common/PresetManagementCard';
+import { SaveToLibraryDialog } from '@/components/presets/SaveToLibraryDialog';
+import { PresetDrawer } from '@/components/presets/PresetDrawer';
+import { Save, Library, RotateCcw } from 'lucide-react';
+import { intervalToSemitones, semitonesToInterval, formatInterval, INTERVAL_SEMITONE_RANGE } from '@/lib/midi/pedals/thermae';
+import type {
+  ThermaeState,
+  Division,
+  GlideMode,
+  ExtSwitchMode,
+} from '@/lib/midi/pedals/thermae';
+
+interface ThermaeEditorProps {
+  deviceName: string;
+}
+
+export function ThermaeEditor({ deviceName }: ThermaeEditorProps) {
+  const editor = useThermaeEditor(deviceName);
+  const {
+    state, isLoading, error,
+    setMix, setLowpass, setRegen, setGlide, setInterval1, setInterval2, setRampSpeed,
+    setDivision, setGlideMode, setExtSwitchMode,
+    setTime, setModRate, setRampRange,
+    setBypass, setHold, setSlow,
+    setDipMix, setDipLowpass, setDipRegen, setDipGlide, setDipInterval1, setDipInterval2,
+    setDipSweep, setDipPolarity,
+    setDipMiso, setDipSpread, setDipTrails, setDipLatch, setDipSync,
+    setDipHalfSpeed, setDipInvert, setDipAllWet,
+    setRampBounce, setExpression,
+    loadPreset, activePreset, isDirty, resetToPreset, resetToPedalDefault, clearActivePreset,
+  } = editor;
+
+  const [libraryDialogOpen, setLibraryDialogOpen] = useState(false);
+  const [managerOpen, setManagerOpen] = useState(false);
+  const [updating, setUpdating] = useState(false);
+
+  if (isLoading) {
+    return (
+      <div className="flex items-center justify-center h-full">
+        <div className="text-lg">Loading Thermae...</div>
+      </div>
+    );
+  }
+
+  if (error) {
+    return (
+      <div className="flex items-center justify-center h-full">
+        <div className="text-lg text-red-500">Error: {error}</div>
+      </div>
+    );
+  }
+
+  if (!state) {
+    return (
+      <div className="flex items-center justify-center h-full">
+        <div className="text-lg">No state available</div>
+      </div>
+    );
+  }
+
+  const handleLibrarySaved = async (presetId: string, presetName: string) => {
+    if (state) await loadPreset(state, presetId, presetName);
+  };
+
+  const handleUpdatePreset = async () => {
+    if (!activePreset || !state) return;
+    try {
+      setUpdating(true);
+      const { updatePreset, savePresetToBank, getBankState } = await import('@/lib/presets');
+      await updatePreset({ id: activePreset.id, parameters: state });
+      const bankState = await getBankState('Thermae');
+      const assignedBanks = bankState.filter((slot) => slot.preset?.id === activePreset.id);
+      for (const bank of assignedBanks) {
+        await savePresetToBank(deviceName, activePreset.id, bank.bankNumber);
+      }
+      await loadPreset(state, activePreset.id, activePreset.name);
+    } finally {
+      setUpdating(false);
+    }
+  };
+
+  const handleLoadPreset = async (presetState: ThermaeState, presetId?: string, presetName?: string, skipMidiSend?: boolean) => {
+    loadPreset(presetState, presetId, presetName, skipMidiSend);
+    setManagerOpen(false);
+  };
+
+  const knobColor = '#f97316';
+  const dipColor = '#f97316';
+  const selectorColors = ['#6b7280', '#f97316', '#c2410c'];
+
+  const divisionOptions = [
+    { value: 'Half', label: '1/2' },
+    { value: 'Quarter', label: '1/4' },
+    { value: 'Eighth', label: '1/8' },
+  ];
+  const glideModeOptions = [
+    { value: 'Off', label: 'OFF' },
+    { value: 'Rise', label: 'RISE' },
+    { value: 'Fall', label: 'FALL' },
+  ];
+  const extSwitchModeOptions = [
+    { value: 'Tap', label: 'TAP' },
+    { value: 'Hold', label: 'HOLD' },
+    { value: 'Slow', label: 'SLOW' },
+  ];
+
+  return (
+    <div className="h-full overflow-y-auto bg-gradient-to-br from-gray-900 to-gray-800">
+      <button
+        onClick={() => setManagerOpen(true)}
+        className="fixed top-4 right-4 z-50 p-2 bg-card-bg hover:bg-control-hover rounded-md border border-control-border transition-colors shadow-lg"
+        title="Open Preset Manager"
+        aria-label="Open Preset Manager"
+      >
+        <Library className="w-5 h-5 text-text-primary" />
+      </button>
+
+      <div className="max-w-4xl mx-auto p-4 space-y-4">
+        {/* Header */}
+        <div className="text-center mb-4">
+          <h1 className="text-xl font-bold text-white mb-1">Chase Bliss Thermae</h1>
+          {activePreset && (
+            <div className="text-sm">
+              <span className="text-gray-400">Active: </span>
+              <span className="text-white font-semibold">{activePreset.name}</span>
+              {isDirty && <span className="ml-2 text-yellow-400">●</span>}
+            </div>
+          )}
+        </div>
+
+        {/* Utility Cards */}
+        <div className="grid grid-cols-1 md:grid-cols-2 gap-3 mb-4">
+          <PedalUtilityCard>
+            <div className="flex items-center gap-4 flex-wrap">
+              <Toggle label="Bypass" value={state.bypass} onChange={setBypass} activeColor="green" />
+              <Toggle label="Hold" value={state.hold} onChange={setHold} activeColor="blue" />
+              <Toggle label="Slow" value={state.slow} onChange={setSlow} activeColor="purple" />
+            </div>
+          </PedalUtilityCard>
+          <PresetManagementCard
+            activePreset={activePreset ? { name: activePreset.name, isDirty } : null}
+          >
+            <div className="grid grid-cols-2 gap-2 w-full">
+              {activePreset ? (
+                isDirty ? (
+                  <>
+                    <button
+                      onClick={handleUpdatePreset}
+                      disabled={updating}
+                      className="flex items-center justify-center gap-1.5 px-3 py-2 text-xs font-medium rounded-md transition-all disabled:opacity-50"
+                      style={{ backgroundColor: '#10b981', color: '#ffffff' }}
+                    >
+                      <Save className="w-3 h-3" />
+                      {updating ? 'Updating...' : 'Update'}
+                    </button>
+                    <button
+                      onClick={() => setLibraryDialogOpen(true)}
+                      className="flex items-center justify-center gap-1.5 px-3 py-2 text-xs font-medium rounded-md bg-accent-blue/10 hover:bg-accent-blue/20 border border-accent-blue/30 text-accent-blue transition-all"
+                    >
+                      <Library className="w-3 h-3" />
+                      Save to Library
+                    </button>
+                    <button
+                      onClick={resetToPreset}
+                      className="flex items-center justify-center gap-1.5 px-3 py-2 text-xs font-medium border border-control-border rounded-md bg-card-bg text-text-primary hover:bg-control-hover transition-all"
+                    >
+                      <RotateCcw className="w-3 h-3" />
+                      Reset to Preset
+                    </button>
+                    <button
+                      onClick={() => { resetToPedalDefault(); clearActivePreset(); }}
+                      className="flex items-center justify-center gap-1.5 px-3 py-2 text-xs font-medium border border-control-border rounded-md bg-card-bg text-text-primary hover:bg-control-hover transition-all"
+                    >
+                      <RotateCcw className="w-3 h-3" />
+                      Pedal Default
+                    </button>
+                  </>
+                ) : (
+                  <button
+                    onClick={() => { resetToPedalDefault(); clearActivePreset(); }}
+                    className="col-span-2 flex items-center justify-center gap-1.5 px-3 py-2 text-xs font-medium border border-control-border rounded-md bg-card-bg text-text-primary hover:bg-control-hover transition-all"
+                  >
+                    <RotateCcw className="w-3 h-3" />
+                    Pedal Default
+                  </button>
+                )
+              ) : (
+                <button
+                  onClick={() => setLibraryDialogOpen(true)}
+                  className="col-span-2 flex items-center justify-center gap-1.5 px-3 py-2 text-xs font-medium rounded-md bg-accent-blue/10 hover:bg-accent-blue/20 border border-accent-blue/30 text-accent-blue transition-all"
+                >
+                  <Library className="w-3 h-3" />
+                  Save Preset
+                </button>
+              )}
+            </div>
+          </PresetManagementCard>
+        </div>
+
+        {/* Pedal Body */}
+        <div
+          className="relative rounded-xl p-6 shadow-xl border-4"
+          style={{
+            background: 'linear-gradient(135deg, #1a0d00 0%, #2a1608 50%, #3a2010 100%)',
+            borderColor: knobColor,
+          }}
+        >
+          {/* DIP Switches */}
+          <div className="mb-6">
+            <div className="text-[10px] font-bold uppercase tracking-widest mb-4 text-center" style={{ color: knobColor }}>
+              DIP Switches
+            </div>
+            <div className="grid grid-cols-2 gap-x-8 gap-y-0">
+              {/* Left Bank */}
+              <div>
+                <div className="text-[9px] font-semibold text-gray-400 uppercase tracking-wider mb-2">Left Bank</div>
+                <div className="space-y-2.5">
+                  <DipSwitch horizontal label="Mix" description="Ramp the mix knob" value={state.dip_mix} onChange={setDipMix} activeColor={dipColor} />
+                  <DipSwitch horizontal label="Lowpass" description="Ramp the lowpass knob" value={state.dip_lowpass} onChange={setDipLowpass} activeColor={dipColor} />
+                  <DipSwitch horizontal label="Regen" description="Ramp the regen knob" value={state.dip_regen} onChange={setDipRegen} activeColor={dipColor} />
+                  <DipSwitch horizontal label="Glide" description="Ramp the glide knob" value={state.dip_glide} onChange={setDipGlide} activeColor={dipColor} />
+                  <DipSwitch horizontal label="Int 1" description="Ramp interval 1" value={state.dip_interval_1} onChange={setDipInterval1} activeColor={dipColor} />
+                  <DipSwitch horizontal label="Int 2" description="Ramp interval 2" value={state.dip_interval_2} onChange={setDipInterval2} activeColor={dipColor} />
+                  <DipSwitch
+                    horizontal
+                    label="Sweep"
+                    description={state.dip_sweep === 'Top' ? 'Sweep from top' : 'Sweep from bottom'}
+                    value={state.dip_sweep === 'Top'}
+                    onChange={(v) => setDipSweep(v ? 'Top' : 'Bottom')}
+                    activeColor={dipColor}
+                  />
+                  <DipSwitch
+                    horizontal
+                    label="Polarity"
+                    description={state.dip_polarity === 'Reverse' ? 'Reversed' : 'Normal'}
+                    value={state.dip_polarity === 'Reverse'}
+                    onChange={(v) => setDipPolarity(v ? 'Reverse' : 'Forward')}
+                    activeColor={dipColor}
+                  />
+                </div>
+              </div>
+
+              {/* Right Bank */}
+              <div>
+                <div className="text-[9px] font-semibold text-gray-400 uppercase tracking-wider mb-2">Right Bank</div>
+                <div className="space-y-2.5">
+                  <DipSwitch horizontal label="MISO" description="Mono in, stereo out" value={state.dip_miso} onChange={setDipMiso} activeColor={dipColor} />
+                  <DipSwitch horizontal label="Spread" description="Stereo spread" value={state.dip_spread} onChange={setDipSpread} activeColor={dipColor} />
+                  <DipSwitch horizontal label="Trails" description="Trails on bypass" value={state.dip_trails} onChange={setDipTrails} activeColor={dipColor} />
+                  <DipSwitch horizontal label="Latch" description="Latch footswitch" value={state.dip_latch} onChange={setDipLatch} activeColor={dipColor} />
+                  <DipSwitch horizontal label="Sync" description="Sync to MIDI clock" value={state.dip_sync} onChange={setDipSync} activeColor={dipColor} />
+                  <DipSwitch horizontal label="Half Speed" description="Half-speed slow mode" value={state.dip_half_speed} onChange={setDipHalfSpeed} activeColor={dipColor} />
+                  <DipSwitch horizontal label="Invert" description="Invert ramp polarity" value={state.dip_invert} onChange={setDipInvert} activeColor={dipColor} />
+                  <DipSwitch horizontal label="All Wet" description="Full wet signal" value={state.dip_all_wet} onChange={setDipAllWet} activeColor={dipColor} />
+                </div>
+              </div>
+            </div>
+          </div>
+
+          {/* Divider */}
+          <div className="w-full h-px mb-5" style={{ backgroundColor: `${knobColor}60` }} />
+
+          {/* Main Knobs */}
+          <div className="mb-6 space-y-4">
+            <div className="text-[10px] font-bold uppercase tracking-widest text-center" style={{ color: knobColor }}>Main</div>
+            <div className="flex justify-center items-end gap-4 flex-wrap">
+              <Knob label="MIX" value={state.mix} onChange={setMix} color={knobColor} size={72} />
+              <Knob label="LOWPASS" value={state.lowpass} onChange={setLowpass} color={knobColor} size={72} />
+              <Knob label="REGEN" value={state.regen} onChange={setRegen} color={knobColor} size={72} />
+              <Knob label="GLIDE" value={state.glide} onChange={setGlide} color={knobColor} size={72} />
+              <Knob
+                label={`INT 1 (${formatInterval(state.interval_1)})`}
+                value={intervalToSemitones(state.interval_1)}
+                min={-INTERVAL_SEMITONE_RANGE}
+                max={INTERVAL_SEMITONE_RANGE}
+                onChange={(v) => setInterval1(semitonesToInterval(v))}
+                color={knobColor}
+                size={72}
+              />
+              <Knob
+                label={`INT 2 (${formatInterval(state.interval_2)})`}
+                value={intervalToSemitones(state.interval_2)}
+                min={-INTERVAL_SEMITONE_RANGE}
+                max={INTERVAL_SEMITONE_RANGE}
+                onChange={(v) => setInterval2(semitonesToInterval(v))}
+                color={knobColor}
+                size={72}
+              />
+              <Knob label="RAMP" value={state.ramp_speed} onChange={setRampSpeed} color={knobColor} size={72} />
+            </div>
+
+            <div className="text-[10px] font-bold uppercase tracking-widest text-center pt-2" style={{ color: knobColor }}>Alt / Hidden Menu</div>
+            <div className="flex justify-center items-end gap-4 flex-wrap">
+              <Knob label="TIME" value={state.time} onChange={setTime} color={knobColor} size={72} />
+              <Knob label="MOD RATE" value={state.mod_rate} onChange={setModRate} color={knobColor} size={72} />
+              <Knob label="RAMP RANGE" value={state.ramp_range} onChange={setRampRange} color={knobColor} size={72} />
+              <Knob label="EXPRESSION" value={state.expression} onChange={setExpression} color={knobColor} size={72} />
+              <Toggle label="RAMP/BNC" value={state.ramp_bounce} onChange={setRampBounce} activeColor="orange" />
+            </div>
+          </div>
+
+          {/* Divider */}
+          <div className="w-full h-px mb-5" style={{ backgroundColor: `${knobColor}60` }} />
+
+          {/* Toggle Selectors */}
+          <div className="mb-5">
+            <div className="text-[10px] font-bold uppercase tracking-widest mb-3 text-center" style={{ color: knobColor }}>Controls</div>
+            <div className="flex justify-center items-start gap-5 flex-wrap">
+              <VerticalSelector
+                label="DIVISION"
+                value={state.division}
+                options={divisionOptions}
+                onChange={(v) => setDivision(v as Division)}
+                optionColors={selectorColors}
+              />
+              <VerticalSelector
+                label="GLIDE MODE"
+                value={state.glide_mode}
+                options={glideModeOptions}
+                onChange={(v) => setGlideMode(v as GlideMode)}
+                optionColors={selectorColors}
+              />
+              <VerticalSelector
+                label="EXT SWITCH"
+                value={state.ext_switch_mode}
+                options={extSwitchModeOptions}
+                onChange={(v) => setExtSwitchMode(v as ExtSwitchMode)}
+                optionColors={selectorColors}
+              />
+            </div>
+          </div>
+
+          {/* Logo */}
+          <div className="mt-5 text-center">
+            <div className="text-lg font-bold tracking-widest" style={{ color: `${knobColor}cc` }}>
+              CHASE BLISS AUDIO
+            </div>
+            <div className="text-xs text-gray-400 font-semibold tracking-wide">THERMAE</div>
+          </div>
+        </div>
+
+        {/* Control Reference */}
+        <div className="bg-gray-800/50 border border-gray-700 rounded-lg p-4">
+          <h3 className="text-base font-semibold text-white mb-2">Control Reference</h3>
+          <div className="grid grid-cols-1 md:grid-cols-3 gap-4 text-sm text-gray-300">
+            <div>
+              <h4 className="font-semibold mb-2" style={{ color: knobColor }}>Main Knobs</h4>
+              <ul className="space-y-1 text-xs">
+                <li><strong>Mix:</strong> Dry/wet balance</li>
+                <li><strong>Lowpass:</strong> Filter on the repeats</li>
+                <li><strong>Regen:</strong> Number of repeats</li>
+                <li><strong>Glide:</strong> Time taken to slide between intervals</li>
+                <li><strong>Int 1 / Int 2:</strong> Pitch interval per step, -24 to +24 semitones</li>
+                <li><strong>Ramp:</strong> Ramp modulation speed</li>
+              </ul>
+            </div>
+            <div>
+              <h4 className="font-semibold mb-2" style={{ color: knobColor }}>Toggles</h4>
+              <ul className="space-y-1 text-xs">
+                <li><strong>Division:</strong> 1/2, 1/4 or 1/8 of the tapped time</li>
+                <li><strong>Glide Mode:</strong> Off/Rise/Fall</li>
+                <li><strong>Ext Switch:</strong> Tap/Hold/Slow on the external switch</li>
+              </ul>
+              <h4 className="font-semibold mb-2 mt-3" style={{ color: knobColor }}>Footswitches</h4>
+              <ul className="space-y-1 text-xs">
+                <li><strong>Bypass:</strong> Effect on/off</li>
+                <li><strong>Hold:</strong> Hold the current repeats</li>
+                <li><strong>Slow:</strong> Half-speed repeats</li>
+              </ul>
+            </div>
+            <div>
+              <h4 className="font-semibold mb-2" style={{ color: knobColor }}>DIP Switches</h4>
+              <ul className="space-y-1 text-xs">
+                <li><strong>MISO:</strong> Mono in, stereo out</li>
+                <li><strong>Spread:</strong> Stereo spread</li>
+                <li><strong>Trails:</strong> Delay trails on bypass</li>
+                <li><strong>Sync:</strong> Follow MIDI clock</li>
+                <li><strong>Half Speed:</strong> Slow footswitch drops an octave</li>
+                <li><strong>Invert:</strong> Invert ramp polarity</li>
+                <li><strong>All Wet:</strong> Full wet output</li>
+              </ul>
+            </div>
+          </div>
+        </div>
+      </div>
+
+      <SaveToLibraryDialog
+        isOpen={libraryDialogOpen}
+        onClose={() => setLibraryDialogOpen(false)}
+        pedalType="Thermae"
+        currentState={state}
+        onSaved={handleLibrarySaved}
+      />
+
+      <PresetDrawer
+        isOpen={managerOpen}
+        onClose={() => setManagerOpen(false)}
+        deviceName={deviceName}
+        pedalType="Thermae"
+        currentState={state}
+        activePresetId={activePreset?.id}
+        onLoadPreset={handleLoadPreset}
+        onPresetSaved={handleLibrarySaved}
+      />
+    </div>
+  );
+}
@@ -0,0 +1 @@
+export { ThermaeEditor } from './ThermaeEditor';
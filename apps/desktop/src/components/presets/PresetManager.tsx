@@ -6,6 +6,8 @@ import { getBankState, savePreset, savePresetToBank, deletePreset, clearBank } f
 import type { BankSlot, Preset, BankConfig } from '@/lib/presets/types';
 import { recallMicrocosmPreset } from '@/lib/midi/pedals/microcosm/api';
 import { pedalRegistry } from '@/lib/midi/pedalRegistry';
+import { recordDeviceStateSnapshot } from '@/lib/midi/api';
+import { confirmRecall } from '@/lib/midi';
 import { ConfirmModal } from './ConfirmModal';
 import { LibraryDrawer } from './LibraryDrawer';
 
@@ -192,10 +194,13 @@ export function PresetManager({
     presetId: string,
     presetName: string,
   ) => {
+    await recordDeviceStateSnapshot(deviceName).catch((err) =>
+      console.warn('[PresetManager] Failed to record undo snapshot:', err)
+    );
     if (onLoadPreset) {
       await onLoadPreset(state, presetId, presetName, false); // Always send MIDI from library
     } else {
-      await recallMicrocosmPreset(deviceName, state);
+      await confirmRecall((ack) => recallMicrocosmPreset(deviceName, state, ack));
     }
     // Close the preset manager too
     onClose();
@@ -312,17 +317,20 @@ export function PresetManager({
       } else {
         // Loading from app library: Send all parameters via CCs
         console.log('[PresetManager] Loading from app library (sending all CCs)');
+        await recordDeviceStateSnapshot(deviceName).catch((err) =>
+          console.warn('[PresetManager] Failed to record undo snapshot:', err)
+        );
         if (onLoadPreset) {
           await onLoadPreset(state, preset.id, preset.name, false);
         } else {
           if (pedalType === 'ChromaConsole') {
             const { recallChromaConsolePreset } = await import('@/lib/midi/pedals/chroma_console/api');
-            await recallChromaConsolePreset(deviceName, state);
+            await confirmRecall((ack) => recallChromaConsolePreset(deviceName, state, ack));
           } else if (pedalType === 'Microcosm') {
-            await recallMicrocosmPreset(deviceName, state);
+            await confirmRecall((ack) => recallMicrocosmPreset(deviceName, state, ack));
           } else if (pedalType === 'PreampMk2') {
             const { recallPreampMk2Preset } = await import('@/lib/midi/pedals/preamp_mk2/api');
-            await recallPreampMk2Preset(deviceName, state);
+            await confirmRecall((ack) => recallPreampMk2Preset(deviceName, state, ack));
           } else {
             throw new Error(`Unsupported pedal type for recall: ${pedalType}`);
           }
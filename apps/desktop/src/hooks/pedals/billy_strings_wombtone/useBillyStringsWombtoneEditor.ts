@@ -8,6 +8,7 @@ import {
 } from '@/lib/midi/pedals/billy-strings-wombtone';
 import type { BillyStringsWombtoneState } from '@/lib/midi/pedals/billy-strings-wombtone';
 import { useMIDIInput, type MidiCCEvent } from '@/hooks/useMIDIInput';
+import { confirmRecall } from '@/lib/midi';
 
 interface UseBillyStringsWombtoneEditorReturn {
   state: BillyStringsWombtoneState | null;
@@ -143,15 +144,16 @@ export function useBillyStringsWombtoneEditor(deviceName: string): UseBillyStrin
     skipMidiSend?: boolean,
   ) => {
     try {
+      // Confirm loud parameter jumps before the pedal or the UI changes
+      if (!skipMidiSend && !(await confirmRecall((ack) => recallBillyStringsWombtonePreset(deviceName, newState, ack)))) {
+        return;
+      }
       setState(newState);
       if (presetId && presetName) {
         setActivePreset({ id: presetId, name: presetName });
         setOriginalPresetState(JSON.parse(JSON.stringify(newState)));
       }
       setError(null);
-      if (!skipMidiSend) {
-        await recallBillyStringsWombtonePreset(deviceName, newState);
-      }
     } catch (err) {
       console.error('Failed to load preset:', err);
       setError(err instanceof Error ? err.message : 'Failed to load preset');
@@ -15,6 +15,7 @@ import type {
   Polarity,
 } from '@/lib/midi/pedals/brothers-am';
 import { useMIDIInput, type MidiCCEvent } from '@/hooks/useMIDIInput';
+import { confirmRecall } from '@/lib/midi';
 
 interface UseBrothersAmEditorReturn {
   state: BrothersAmState | null;
@@ -376,15 +377,16 @@ export function useBrothersAmEditor(deviceName: string): UseBrothersAmEditorRetu
     skipMidiSend?: boolean,
   ) => {
     try {
+      // Confirm loud parameter jumps before the pedal or the UI changes
+      if (!skipMidiSend && !(await confirmRecall((ack) => recallBrothersAmPreset(deviceName, newState, ack)))) {
+        return;
+      }
       setState(newState);
       if (presetId && presetName) {
         setActivePreset({ id: presetId, name: presetName });
         setOriginalPresetState(JSON.parse(JSON.stringify(newState)));
       }
       setError(null);
-      if (!skipMidiSend) {
-        await recallBrothersAmPreset(deviceName, newState);
-      }
     } catch (err) {
       console.error('Failed to load preset:', err);
       setError(err instanceof Error ? err.message : 'Failed to load preset');
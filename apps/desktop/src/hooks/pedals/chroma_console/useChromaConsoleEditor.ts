@@ -5,12 +5,14 @@ import {
   getChromaConsoleState,
   sendChromaConsoleParameter,
   recallChromaConsolePreset,
+  setChromaConsoleBypassMode,
   type ChromaConsoleState,
   type CharacterModule,
   type MovementModule,
   type DiffusionModule,
   type TextureModule,
   type BypassState,
+  type BypassMode,
   type GestureMode,
   type CaptureMode,
   type CaptureRouting,
@@ -22,6 +24,8 @@ import {
   statesEqual,
 } from '@/lib/midi/pedals/chroma_console';
 import { useMIDIInput, type MidiCCEvent } from '@/hooks/useMIDIInput';
+import { getChromaBypassModeForDevice, saveChromaBypassModeForDevice } from '@/lib/midi/deviceProfiles';
+import { confirmRecall } from '@/lib/midi';
 
 interface UseChromaConsoleEditorReturn {
   state: ChromaConsoleState | null;
@@ -55,6 +59,8 @@ interface UseChromaConsoleEditorReturn {
   setTextureModule: (module: TextureModule) => Promise<void>;
   
   // Bypass controls
+  bypassMode: BypassMode;
+  setBypassMode: (mode: BypassMode) => Promise<void>;
   setBypassState: (state: BypassState) => Promise<void>;
   setCharacterBypass: (bypass: boolean) => Promise<void>;
   setMovementBypass: (bypass: boolean) => Promise<void>;
@@ -86,6 +92,7 @@ export function useChromaConsoleEditor(deviceName: string): UseChromaConsoleEdit
   const [state, setState] = useState<ChromaConsoleState | null>(null);
   const [isLoading, setIsLoading] = useState(true);
   const [error, setError] = useState<string | null>(null);
+  const [bypassMode, setBypassModeState] = useState<BypassMode>(() => getChromaBypassModeForDevice(deviceName));
   
   // Preset tracking
   const [activePreset, setActivePreset] = useState<{ id: string; name: string } | null>(null);
@@ -173,6 +180,11 @@ export function useChromaConsoleEditor(deviceName: string): UseChromaConsoleEdit
         case 91: 
           newState.bypass_state = event.value < 64 ? 'Engaged' : 'Bypass';
           break;
+        case 92: // Dual footswitch mode: 0-31 = bypass, 32-63 = dual bypass, 64-127 = engaged
+          newState.bypass_state =
+            event.value < 32 ? 'Bypass' :
+            event.value < 64 ? 'DualBypass' : 'Engaged';
+          break;
         case 103: newState.character_bypass = event.value < 64; break;
         case 104: newState.movement_bypass = event.value < 64; break;
         case 105: newState.diffusion_bypass = event.value < 64; break;
@@ -363,6 +375,18 @@ export function useChromaConsoleEditor(deviceName: string): UseChromaConsoleEdit
   }, [setParameter]);
 
   // Bypass controls
+  const setBypassMode = useCallback(async (mode: BypassMode) => {
+    try {
+      await setChromaConsoleBypassMode(deviceName, mode);
+      saveChromaBypassModeForDevice(deviceName, mode);
+      setBypassModeState(mode);
+      setError(null);
+    } catch (err) {
+      console.error('Failed to set bypass mode:', err);
+      setError(err instanceof Error ? err.message : 'Failed to set bypass mode');
+    }
+  }, [deviceName]);
+
   const setBypassState = useCallback(async (bypassState: BypassState) => {
     setState(prev => prev ? { ...prev, bypass_state: bypassState } : null);
     await setParameter({ BypassState: bypassState });
@@ -486,7 +510,9 @@ export function useChromaConsoleEditor(deviceName: string): UseChromaConsoleEdit
     };
     
     // Send defaults to pedal via MIDI
-    await recallChromaConsolePreset(deviceName, pedalDefaultState);
+    if (!(await confirmRecall((ack) => recallChromaConsolePreset(deviceName, pedalDefaultState, ack)))) {
+      return;
+    }
     
     // Update UI state
     setState(pedalDefaultState);
@@ -535,6 +561,8 @@ export function useChromaConsoleEditor(deviceName: string): UseChromaConsoleEdit
     setTextureModule,
     
     // Bypass controls
+    bypassMode,
+    setBypassMode,
     setBypassState,
     setCharacterBypass,
     setMovementBypass,
@@ -17,6 +17,7 @@ import type {
   Polarity,
 } from '@/lib/midi/pedals/clean';
 import { useMIDIInput, type MidiCCEvent } from '@/hooks/useMIDIInput';
+import { confirmRecall } from '@/lib/midi';
 
 interface UseCleanEditorReturn {
   state: CleanState | null;
@@ -520,15 +521,16 @@ export function useCleanEditor(deviceName: string): UseCleanEditorReturn {
     skipMidiSend?: boolean,
   ) => {
     try {
+      // Confirm loud parameter jumps before the pedal or the UI changes
+      if (!skipMidiSend && !(await confirmRecall((ack) => recallCleanPreset(deviceName, newState, ack)))) {
+        return;
+      }
       setState(newState);
       if (presetId && presetName) {
         setActivePreset({ id: presetId, name: presetName });
         setOriginalPresetState(JSON.parse(JSON.stringify(newState)));
       }
       setError(null);
-      if (!skipMidiSend) {
-        await recallCleanPreset(deviceName, newState);
-      }
     } catch (err) {
       console.error('Failed to load preset:', err);
       setError(err instanceof Error ? err.message : 'Failed to load preset');
@@ -0,0 +1,236 @@
+// React hook for managing Source Audio Collider editor state
+
+import { useState, useEffect, useCallback } from 'react';
+import {
+  getColliderState,
+  sendColliderParameter,
+  recallColliderPreset,
+} from '@/lib/midi/pedals/collider';
+import type {
+  ColliderState,
+  ColliderParameter,
+  EffectRouting,
+} from '@/lib/midi/pedals/collider';
+import { useMIDIInput, type MidiCCEvent } from '@/hooks/useMIDIInput';
+import { confirmRecall } from '@/lib/midi';
+
+interface UseColliderEditorReturn {
+  state: ColliderState | null;
+  isLoading: boolean;
+  error: string | null;
+
+  // Delay A
+  setDelayTimeA: (value: number) => Promise<void>;
+  setDelayRepeatsA: (value: number) => Promise<void>;
+  setDelayMixA: (value: number) => Promise<void>;
+
+  // Delay B
+  setDelayTimeB: (value: number) => Promise<void>;
+  setDelayRepeatsB: (value: number) => Promise<void>;
+  setDelayMixB: (value: number) => Promise<void>;
+
+  // Reverb A
+  setReverbSizeA: (value: number) => Promise<void>;
+  setReverbDecayA: (value: number) => Promise<void>;
+  setReverbMixA: (value: number) => Promise<void>;
+
+  // Reverb B
+  setReverbSizeB: (value: number) => Promise<void>;
+  setReverbDecayB: (value: number) => Promise<void>;
+  setReverbMixB: (value: number) => Promise<void>;
+
+  // Shared
+  setRouting: (v: EffectRouting) => Promise<void>;
+  setBypass: (v: boolean) => Promise<void>;
+  setExpression: (value: number) => Promise<void>;
+
+  // Preset management
+  loadPreset: (state: ColliderState, presetId?: string, presetName?: string, skipMidiSend?: boolean) => Promise<void>;
+  activePreset: { id: string; name: string } | null;
+  isDirty: boolean;
+  resetToPreset: () => void;
+  resetToPedalDefault: () => void;
+  clearActivePreset: () => void;
+}
+
+function routingFromCC(value: number): EffectRouting {
+  if (value === 2) return 'ReverbIntoDelay';
+  if (value === 3) return 'Parallel';
+  return 'DelayIntoReverb';
+}
+
+function createDefaultState(): ColliderState {
+  return {
+    delay_a: { time: 8192, repeats: 48, mix: 64 },
+    delay_b: { time: 8192, repeats: 48, mix: 64 },
+    reverb_a: { size: 64, decay: 64, mix: 48 },
+    reverb_b: { size: 64, decay: 64, mix: 48 },
+    routing: 'DelayIntoReverb',
+    bypass: false,
+    expression: 0,
+  };
+}
+
+export function useColliderEditor(deviceName: string): UseColliderEditorReturn {
+  const [state, setState] = useState<ColliderState | null>(null);
+  const [isLoading, setIsLoading] = useState(true);
+  const [error, setError] = useState<string | null>(null);
+
+  const [activePreset, setActivePreset] = useState<{ id: string; name: string } | null>(null);
+  const [originalPresetState, setOriginalPresetState] = useState<ColliderState | null>(null);
+  const [isDirty, setIsDirty] = useState(false);
+
+  // Delay times arrive as NRPN (CCs 99/98/6/38) and are not followed here
+  const handleMidiCC = useCallback((event: MidiCCEvent) => {
+    setState(prev => {
+      if (!prev) return null;
+      const s = {
+        ...prev,
+        delay_a: { ...prev.delay_a },
+        delay_b: { ...prev.delay_b },
+        reverb_a: { ...prev.reverb_a },
+        reverb_b: { ...prev.reverb_b },
+      };
+
+      switch (event.cc_number) {
+        case 14: s.delay_a.repeats = event.value; break;
+        case 15: s.delay_a.mix = event.value; break;
+        case 16: s.delay_b.repeats = event.value; break;
+        case 17: s.delay_b.mix = event.value; break;
+        case 18: s.reverb_a.size = event.value; break;
+        case 19: s.reverb_a.decay = event.value; break;
+        case 20: s.reverb_a.mix = event.value; break;
+        case 21: s.reverb_b.size = event.value; break;
+        case 22: s.reverb_b.decay = event.value; break;
+        case 23: s.reverb_b.mix = event.value; break;
+        case 24: s.routing = routingFromCC(event.value); break;
+        case 100: s.expression = event.value; break;
+        case 102: s.bypass = event.value >= 64; break;
+        default: return prev;
+      }
+
+      return s;
+    });
+  }, []);
+
+  useMIDIInput(handleMidiCC, deviceName);
+
+  useEffect(() => {
+    let mounted = true;
+
+    async function loadState() {
+      try {
+        setIsLoading(true);
+        const initialState = await getColliderState(deviceName);
+        if (mounted) {
+          setState(initialState);
+          setError(null);
+        }
+      } catch (err) {
+        console.error('Failed to load Collider state:', err);
+        if (mounted) {
+          setError(err instanceof Error ? err.message : 'Failed to load state');
+          setState(createDefaultState());
+        }
+      } finally {
+        if (mounted) setIsLoading(false);
+      }
+    }
+
+    loadState();
+    return () => { mounted = false; };
+  }, [deviceName]);
+
+  useEffect(() => {
+    if (state && originalPresetState && activePreset) {
+      setIsDirty(JSON.stringify(state) !== JSON.stringify(originalPresetState));
+    } else {
+      setIsDirty(false);
+    }
+  }, [state, originalPresetState, activePreset]);
+
+  const sendParam = useCallback(async (param: ColliderParameter) => {
+    try {
+      await sendColliderParameter(deviceName, param);
+      setError(null);
+    } catch (err) {
+      console.error('Failed to send parameter:', err);
+      setError(err instanceof Error ? err.message : 'Failed to send parameter');
+    }
+  }, [deviceName]);
+
+  // Delay A
+  const setDelayTimeA = useCallback(async (v: number) => { setState(p => p ? { ...p, delay_a: { ...p.delay_a, time: v } } : null); await sendParam({ DelayTimeA: v }); }, [sendParam]);
+  const setDelayRepeatsA = useCallback(async (v: number) => { setState(p => p ? { ...p, delay_a: { ...p.delay_a, repeats: v } } : null); await sendParam({ DelayRepeatsA: v }); }, [sendParam]);
+  const setDelayMixA = useCallback(async (v: number) => { setState(p => p ? { ...p, delay_a: { ...p.delay_a, mix: v } } : null); await sendParam({ DelayMixA: v }); }, [sendParam]);
+
+  // Delay B
+  const setDelayTimeB = useCallback(async (v: number) => { setState(p => p ? { ...p, delay_b: { ...p.delay_b, time: v } } : null); await sendParam({ DelayTimeB: v }); }, [sendParam]);
+  const setDelayRepeatsB = useCallback(async (v: number) => { setState(p => p ? { ...p, delay_b: { ...p.delay_b, repeats: v } } : null); await sendParam({ DelayRepeatsB: v }); }, [sendParam]);
+  const setDelayMixB = useCallback(async (v: number) => { setState(p => p ? { ...p, delay_b: { ...p.delay_b, mix: v } } : null); await sendParam({ DelayMixB: v }); }, [sendParam]);
+
+  // Reverb A
+  const setReverbSizeA = useCallback(async (v: number) => { setState(p => p ? { ...p, reverb_a: { ...p.reverb_a, size: v } } : null); await sendParam({ ReverbSizeA: v }); }, [sendParam]);
+  const setReverbDecayA = useCallback(async (v: number) => { setState(p => p ? { ...p, reverb_a: { ...p.reverb_a, decay: v } } : null); await sendParam({ ReverbDecayA: v }); }, [sendParam]);
+  const setReverbMixA = useCallback(async (v: number) => { setState(p => p ? { ...p, reverb_a: { ...p.reverb_a, mix: v } } : null); await sendParam({ ReverbMixA: v }); }, [sendParam]);
+
+  // Reverb B
+  const setReverbSizeB = useCallback(async (v: number) => { setState(p => p ? { ...p, reverb_b: { ...p.reverb_b, size: v } } : null); await sendParam({ ReverbSizeB: v }); }, [sendParam]);
+  const setReverbDecayB = useCallback(async (v: number) => { setState(p => p ? { ...p, reverb_b: { ...p.reverb_b, decay: v } } : null); await sendParam({ ReverbDecayB: v }); }, [sendParam]);
+  const setReverbMixB = useCallback(async (v: number) => { setState(p => p ? { ...p, reverb_b: { ...p.reverb_b, mix: v } } : null); await sendParam({ ReverbMixB: v }); }, [sendParam]);
+
+  // Shared
+  const setRouting = useCallback(async (v: EffectRouting) => { setState(p => p ? { ...p, routing: v } : null); await sendParam({ Routing: v }); }, [sendParam]);
+  const setBypass = useCallback(async (v: boolean) => { setState(p => p ? { ...p, bypass: v } : null); await sendParam({ Bypass: v }); }, [sendParam]);
+  const setExpression = useCallback(async (v: number) => { setState(p => p ? { ...p, expression: v } : null); await sendParam({ Expression: v }); }, [sendParam]);
+
+  // Preset management
+  const loadPreset = useCallback(async (
+    newState: ColliderState,
+    presetId?: string,
+    presetName?: string,
+    skipMidiSend?: boolean,
+  ) => {
+    try {
+      // Confirm loud parameter jumps before the pedal or the UI changes
+      if (!skipMidiSend && !(await confirmRecall((ack) => recallColliderPreset(deviceName, newState, ack)))) {
+        return;
+      }
+      setState(newState);
+      if (presetId && presetName) {
+        setActivePreset({ id: presetId, name: presetName });
+        setOriginalPresetState(JSON.parse(JSON.stringify(newState)));
+      }
+      setError(null);
+    } catch (err) {
+      console.error('Failed to load preset:', err);
+      setError(err instanceof Error ? err.message : 'Failed to load preset');
+    }
+  }, [deviceName]);
+
+  const resetToPreset = useCallback(() => {
+    if (originalPresetState) {
+      loadPreset(originalPresetState, activePreset?.id, activePreset?.name);
+    }
+  }, [originalPresetState, activePreset, loadPreset]);
+
+  const resetToPedalDefault = useCallback(() => {
+    loadPreset(createDefaultState());
+  }, [loadPreset]);
+
+  const clearActivePreset = useCallback(() => {
+    setActivePreset(null);
+    setOriginalPresetState(null);
+    setIsDirty(false);
+  }, []);
+
+  return {
+    state, isLoading, error,
+    setDelayTimeA, setDelayRepeatsA, setDelayMixA,
+    setDelayTimeB, setDelayRepeatsB, setDelayMixB,
+    setReverbSizeA, setReverbDecayA, setReverbMixA,
+    setReverbSizeB, setReverbDecayB, setReverbMixB,
+    setRouting, setBypass, setExpression,
+    loadPreset, activePreset, isDirty, resetToPreset, resetToPedalDefault, clearActivePreset,
+  };
+}
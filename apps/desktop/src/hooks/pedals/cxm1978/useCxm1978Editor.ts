@@ -13,6 +13,7 @@ import {
   type Clock,
 } from '@/lib/midi/pedals/cxm1978';
 import { useMIDIInput, type MidiCCEvent } from '@/hooks/useMIDIInput';
+import { confirmRecall } from '@/lib/midi';
 
 interface UseCxm1978EditorReturn {
   state: Cxm1978State | null;
@@ -252,15 +253,16 @@ export function useCxm1978Editor(deviceName: string): UseCxm1978EditorReturn {
     skipMidiSend?: boolean,
   ) => {
     try {
+      // Confirm loud parameter jumps before the pedal or the UI changes
+      if (!skipMidiSend && !(await confirmRecall((ack) => recallCxm1978Preset(deviceName, newState, ack)))) {
+        return;
+      }
       setState(newState);
       if (presetId && presetName) {
         setActivePreset({ id: presetId, name: presetName });
         setOriginalPresetState(JSON.parse(JSON.stringify(newState)));
       }
       setError(null);
-      if (!skipMidiSend) {
-        await recallCxm1978Preset(deviceName, newState);
-      }
     } catch (err) {
       console.error('Failed to load preset:', err);
       setError(err instanceof Error ? err.message : 'Failed to load preset');
@@ -0,0 +1,233 @@
+// React hook for managing Chase Bliss Dark World editor state
+
+import { useState, useEffect, useCallback } from 'react';
+import {
+  getDarkWorldState,
+  sendDarkWorldParameter,
+  recallDarkWorldPreset,
+} from '@/lib/midi/pedals/dark-world';
+import type {
+  DarkWorldState,
+  DarkWorldParameter,
+  DarkReverbType,
+  WorldReverbType,
+  EngineRouting,
+} from '@/lib/midi/pedals/dark-world';
+import { useMIDIInput, type MidiCCEvent } from '@/hooks/useMIDIInput';
+import { confirmRecall } from '@/lib/midi';
+
+interface UseDarkWorldEditorReturn {
+  state: DarkWorldState | null;
+  isLoading: boolean;
+  error: string | null;
+
+  // Side A (Dark)
+  setSideAReverbType: (v: DarkReverbType) => Promise<void>;
+  setSideADecay: (value: number) => Promise<void>;
+  setSideAMix: (value: number) => Promise<void>;
+  setSideATone: (value: number) => Promise<void>;
+  setSideAModDepth: (value: number) => Promise<void>;
+  setSideAEngaged: (v: boolean) => Promise<void>;
+
+  // Side B (World)
+  setSideBReverbType: (v: WorldReverbType) => Promise<void>;
+  setSideBDecay: (value: number) => Promise<void>;
+  setSideBMix: (value: number) => Promise<void>;
+  setSideBTone: (value: number) => Promise<void>;
+  setSideBModDepth: (value: number) => Promise<void>;
+  setSideBEngaged: (v: boolean) => Promise<void>;
+
+  // Shared
+  setRouting: (v: EngineRouting) => Promise<void>;
+  setBypass: (v: boolean) => Promise<void>;
+  setExpression: (value: number) => Promise<void>;
+
+  // Preset management
+  loadPreset: (state: DarkWorldState, presetId?: string, presetName?: string, skipMidiSend?: boolean) => Promise<void>;
+  activePreset: { id: string; name: string } | null;
+  isDirty: boolean;
+  resetToPreset: () => void;
+  resetToPedalDefault: () => void;
+  clearActivePreset: () => void;
+}
+
+function darkReverbTypeFromCC(value: number): DarkReverbType {
+  if (value === 2) return 'Shimmer';
+  if (value === 3) return 'Black';
+  return 'Mod';
+}
+
+function worldReverbTypeFromCC(value: number): WorldReverbType {
+  if (value === 2) return 'Plate';
+  if (value === 3) return 'Spring';
+  return 'Hall';
+}
+
+function routingFromCC(value: number): EngineRouting {
+  if (value === 2) return 'Split';
+  if (value === 3) return 'Combine';
+  return 'Series';
+}
+
+function createDefaultState(): DarkWorldState {
+  return {
+    side_a: { reverb_type: 'Mod', decay: 64, mix: 64, tone: 64, mod_depth: 32, engaged: true },
+    side_b: { reverb_type: 'Hall', decay: 64, mix: 64, tone: 64, mod_depth: 32, engaged: true },
+    routing: 'Series',
+    bypass: false,
+    expression: 0,
+  };
+}
+
+export function useDarkWorldEditor(deviceName: string): UseDarkWorldEditorReturn {
+  const [state, setState] = useState<DarkWorldState | null>(null);
+  const [isLoading, setIsLoading] = useState(true);
+  const [error, setError] = useState<string | null>(null);
+
+  const [activePreset, setActivePreset] = useState<{ id: string; name: string } | null>(null);
+  const [originalPresetState, setOriginalPresetState] = useState<DarkWorldState | null>(null);
+  const [isDirty, setIsDirty] = useState(false);
+
+  const handleMidiCC = useCallback((event: MidiCCEvent) => {
+    setState(prev => {
+      if (!prev) return null;
+      const s = { ...prev, side_a: { ...prev.side_a }, side_b: { ...prev.side_b } };
+
+      switch (event.cc_number) {
+        case 14: s.side_a.decay = event.value; break;
+        case 15: s.side_a.mix = event.value; break;
+        case 16: s.side_a.tone = event.value; break;
+        case 17: s.side_a.mod_depth = event.value; break;
+        case 21: s.side_a.reverb_type = darkReverbTypeFromCC(event.value); break;
+        case 22: s.side_b.reverb_type = worldReverbTypeFromCC(event.value); break;
+        case 23: s.routing = routingFromCC(event.value); break;
+        case 24: s.side_b.decay = event.value; break;
+        case 25: s.side_b.mix = event.value; break;
+        case 26: s.side_b.tone = event.value; break;
+        case 27: s.side_b.mod_depth = event.value; break;
+        case 100: s.expression = event.value; break;
+        case 102: s.bypass = event.value >= 64; break;
+        case 103: s.side_a.engaged = event.value >= 64; break;
+        case 104: s.side_b.engaged = event.value >= 64; break;
+        default: return prev;
+      }
+
+      return s;
+    });
+  }, []);
+
+  useMIDIInput(handleMidiCC, deviceName);
+
+  useEffect(() => {
+    let mounted = true;
+
+    async function loadState() {
+      try {
+        setIsLoading(true);
+        const initialState = await getDarkWorldState(deviceName);
+        if (mounted) {
+          setState(initialState);
+          setError(null);
+        }
+      } catch (err) {
+        console.error('Failed to load Dark World state:', err);
+        if (mounted) {
+          setError(err instanceof Error ? err.message : 'Failed to load state');
+          setState(createDefaultState());
+        }
+      } finally {
+        if (mounted) setIsLoading(false);
+      }
+    }
+
+    loadState();
+    return () => { mounted = false; };
+  }, [deviceName]);
+
+  useEffect(() => {
+    if (state && originalPresetState && activePreset) {
+      setIsDirty(JSON.stringify(state) !== JSON.stringify(originalPresetState));
+    } else {
+      setIsDirty(false);
+    }
+  }, [state, originalPresetState, activePreset]);
+
+  const sendParam = useCallback(async (param: DarkWorldParameter) => {
+    try {
+      await sendDarkWorldParameter(deviceName, param);
+      setError(null);
+    } catch (err) {
+      console.error('Failed to send parameter:', err);
+      setError(err instanceof Error ? err.message : 'Failed to send parameter');
+    }
+  }, [deviceName]);
+
+  // Side A (Dark)
+  const setSideAReverbType = useCallback(async (v: DarkReverbType) => { setState(p => p ? { ...p, side_a: { ...p.side_a, reverb_type: v } } : null); await sendParam({ SideAReverbType: v }); }, [sendParam]);
+  const setSideADecay = useCallback(async (v: number) => { setState(p => p ? { ...p, side_a: { ...p.side_a, decay: v } } : null); await sendParam({ SideADecay: v }); }, [sendParam]);
+  const setSideAMix = useCallback(async (v: number) => { setState(p => p ? { ...p, side_a: { ...p.side_a, mix: v } } : null); await sendParam({ SideAMix: v }); }, [sendParam]);
+  const setSideATone = useCallback(async (v: number) => { setState(p => p ? { ...p, side_a: { ...p.side_a, tone: v } } : null); await sendParam({ SideATone: v }); }, [sendParam]);
+  const setSideAModDepth = useCallback(async (v: number) => { setState(p => p ? { ...p, side_a: { ...p.side_a, mod_depth: v } } : null); await sendParam({ SideAModDepth: v }); }, [sendParam]);
+  const setSideAEngaged = useCallback(async (v: boolean) => { setState(p => p ? { ...p, side_a: { ...p.side_a, engaged: v } } : null); await sendParam({ SideAEngaged: v }); }, [sendParam]);
+
+  // Side B (World)
+  const setSideBReverbType = useCallback(async (v: WorldReverbType) => { setState(p => p ? { ...p, side_b: { ...p.side_b, reverb_type: v } } : null); await sendParam({ SideBReverbType: v }); }, [sendParam]);
+  const setSideBDecay = useCallback(async (v: number) => { setState(p => p ? { ...p, side_b: { ...p.side_b, decay: v } } : null); await sendParam({ SideBDecay: v }); }, [sendParam]);
+  const setSideBMix = useCallback(async (v: number) => { setState(p => p ? { ...p, side_b: { ...p.side_b, mix: v } } : null); await sendParam({ SideBMix: v }); }, [sendParam]);
+  const setSideBTone = useCallback(async (v: number) => { setState(p => p ? { ...p, side_b: { ...p.side_b, tone: v } } : null); await sendParam({ SideBTone: v }); }, [sendParam]);
+  const setSideBModDepth = useCallback(async (v: number) => { setState(p => p ? { ...p, side_b: { ...p.side_b, mod_depth: v } } : null); await sendParam({ SideBModDepth: v }); }, [sendParam]);
+  const setSideBEngaged = useCallback(async (v: boolean) => { setState(p => p ? { ...p, side_b: { ...p.side_b, engaged: v } } : null); await sendParam({ SideBEngaged: v }); }, [sendParam]);
+
+  // Shared
+  const setRouting = useCallback(async (v: EngineRouting) => { setState(p => p ? { ...p, routing: v } : null); await sendParam({ Routing: v }); }, [sendParam]);
+  const setBypass = useCallback(async (v: boolean) => { setState(p => p ? { ...p, bypass: v } : null); await sendParam({ Bypass: v }); }, [sendParam]);
+  const setExpression = useCallback(async (v: number) => { setState(p => p ? { ...p, expression: v } : null); await sendParam({ Expression: v }); }, [sendParam]);
+
+  // Preset management
+  const loadPreset = useCallback(async (
+    newState: DarkWorldState,
+    presetId?: string,
+    presetName?: string,
+    skipMidiSend?: boolean,
+  ) => {
+    try {
+      // Confirm loud parameter jumps before the pedal or the UI changes
+      if (!skipMidiSend && !(await confirmRecall((ack) => recallDarkWorldPreset(deviceName, newState, ack)))) {
+        return;
+      }
+      setState(newState);
+      if (presetId && presetName) {
+        setActivePreset({ id: presetId, name: presetName });
+        setOriginalPresetState(JSON.parse(JSON.stringify(newState)));
+      }
+      setError(null);
+    } catch (err) {
+      console.error('Failed to load preset:', err);
+      setError(err instanceof Error ? err.message : 'Failed to load preset');
+    }
+  }, [deviceName]);
+
+  const resetToPreset = useCallback(() => {
+    if (originalPresetState) {
+      loadPreset(originalPresetState, activePreset?.id, activePreset?.name);
+    }
+  }, [originalPresetState, activePreset, loadPreset]);
+
+  const resetToPedalDefault = useCallback(() => {
+    loadPreset(createDefaultState());
+  }, [loadPreset]);
+
+  const clearActivePreset = useCallback(() => {
+    setActivePreset(null);
+    setOriginalPresetState(null);
+    setIsDirty(false);
+  }, []);
+
+  return {
+    state, isLoading, error,
+    setSideAReverbType, setSideADecay, setSideAMix, setSideATone, setSideAModDepth, setSideAEngaged,
+    setSideBReverbType, setSideBDecay, setSideBMix, setSideBTone, setSideBModDepth, setSideBEngaged,
+    setRouting, setBypass, setExpression,
+    loadPreset, activePreset, isDirty, resetToPreset, resetToPedalDefault, clearActivePreset,
+  };
+}
@@ -18,6 +18,7 @@ import type {
   DspBypassMode,
 } from '@/lib/midi/pedals/gen-loss-mkii';
 import { useMIDIInput, type MidiCCEvent } from '@/hooks/useMIDIInput';
+import { confirmRecall } from '@/lib/midi';
 
 interface UseGenLossMkiiEditorReturn {
   state: GenLossMkiiState | null;
@@ -133,6 +134,7 @@ function createDefaultState(): GenLossMkiiState {
     mechanical_noise: 32, crinkle_pop: 32,
     input_gain: 'InstrumentLevel', dsp_bypass: 'TrueBypass',
     ramp_bounce: false,
+    version: 2,
   };
 }
 
@@ -465,15 +467,16 @@ export function useGenLossMkiiEditor(deviceName: string): UseGenLossMkiiEditorRe
     skipMidiSend?: boolean,
   ) => {
     try {
+      // Confirm loud parameter jumps before the pedal or the UI changes
+      if (!skipMidiSend && !(await confirmRecall((ack) => recallGenLossPreset(deviceName, newState, ack)))) {
+        return;
+      }
       setState(newState);
       if (presetId && presetName) {
         setActivePreset({ id: presetId, name: presetName });
         setOriginalPresetState(JSON.parse(JSON.stringify(newState)));
       }
       setError(null);
-      if (!skipMidiSend) {
-        await recallGenLossPreset(deviceName, newState);
-      }
     } catch (err) {
       console.error('Failed to load preset:', err);
       setError(err instanceof Error ? err.message : 'Failed to load preset');
@@ -0,0 +1,320 @@
+// React hook for managing Chase Bliss Habit editor state
+
+import { useState, useEffect, useCallback } from 'react';
+import {
+  getHabitState,
+  sendHabitParameter,
+  recallHabitPreset,
+} from '@/lib/midi/pedals/habit';
+import type {
+  HabitState,
+  HabitParameter,
+  Modifier,
+  ScanMode,
+  CollectMode,
+  SweepDirection,
+  Polarity,
+} from '@/lib/midi/pedals/habit';
+import { useMIDIInput, type MidiCCEvent } from '@/hooks/useMIDIInput';
+import { confirmRecall } from '@/lib/midi';
+
+interface UseHabitEditorReturn {
+  state: HabitState | null;
+  isLoading: boolean;
+  error: string | null;
+
+  // Main knobs
+  setLevel: (value: number) => Promise<void>;
+  setRepeats: (value: number) => Promise<void>;
+  setSize: (value: number) => Promise<void>;
+  setScan: (value: number) => Promise<void>;
+  setSpread: (value: number) => Promise<void>;
+  setModify: (value: number) => Promise<void>;
+  setRampSpeed: (value: number) => Promise<void>;
+
+  // Toggles
+  setModifier: (v: Modifier) => Promise<void>;
+  setScanMode: (v: ScanMode) => Promise<void>;
+  setCollectMode: (v: CollectMode) => Promise<void>;
+
+  // Footswitches and memory
+  setBypass: (v: boolean) => Promise<void>;
+  setMemory: (v: boolean) => Promise<void>;
+  setAltMode: (v: boolean) => Promise<void>;
+  setOverdub: (v: boolean) => Promise<void>;
+  setMemoryHold: (v: boolean) => Promise<void>;
+  clearMemory: () => Promise<void>;
+
+  // DIP switches - Left bank
+  setDipLevel: (v: boolean) => Promise<void>;
+  setDipRepeats: (v: boolean) => Promise<void>;
+  setDipSize: (v: boolean) => Promise<void>;
+  setDipScan: (v: boolean) => Promise<void>;
+  setDipSpread: (v: boolean) => Promise<void>;
+  setDipBounce: (v: boolean) => Promise<void>;
+  setDipSweep: (v: SweepDirection) => Promise<void>;
+  setDipPolarity: (v: Polarity) => Promise<void>;
+
+  // DIP switches - Right bank
+  setDipMiso: (v: boolean) => Promise<void>;
+  setDipStereo: (v: boolean) => Promise<void>;
+  setDipLatch: (v: boolean) => Promise<void>;
+  setDipSync: (v: boolean) => Promise<void>;
+  setDipTrails: (v: boolean) => Promise<void>;
+  setDipNoDub: (v: boolean) => Promise<void>;
+  setDipFeedbackLoop: (v: boolean) => Promise<void>;
+  setDipManual: (v: boolean) => Promise<void>;
+
+  // Utility
+  setMidiClockIgnore: (v: boolean) => Promise<void>;
+  setRampBounce: (v: boolean) => Promise<void>;
+  setExpression: (value: number) => Promise<void>;
+
+  // Preset management
+  loadPreset: (state: HabitState, presetId?: string, presetName?: string, skipMidiSend?: boolean) => Promise<void>;
+  activePreset: { id: string; name: string } | null;
+  isDirty: boolean;
+  resetToPreset: () => void;
+  resetToPedalDefault: () => void;
+  clearActivePreset: () => void;
+}
+
+function modifierFromCC(value: number): Modifier | null {
+  if (value === 1) return 'Stretch';
+  if (value === 2) return 'Pitch';
+  if (value === 3) return 'Filter';
+  return null;
+}
+
+function scanModeFromCC(value: number): ScanMode | null {
+  if (value === 1) return 'Off';
+  if (value === 2) return 'Manual';
+  if (value === 3) return 'Auto';
+  return null;
+}
+
+function collectModeFromCC(value: number): CollectMode | null {
+  if (value === 1) return 'In';
+  if (value === 2) return 'Feed';
+  if (value === 3) return 'Out';
+  return null;
+}
+
+function createDefaultState(): HabitState {
+  return {
+    level: 64, repeats: 64, size: 64, scan: 0, spread: 64, modify: 0, ramp_speed: 64,
+    modifier: 'Stretch', scan_mode: 'Off', collect_mode: 'In',
+    bypass: false, memory: false, alt_mode: false, overdub: false, memory_hold: false,
+    dip_level: false, dip_repeats: false, dip_size: false, dip_scan: false,
+    dip_spread: false, dip_bounce: false,
+    dip_sweep: 'Bottom', dip_polarity: 'Forward',
+    dip_miso: false, dip_stereo: false, dip_latch: false, dip_sync: false,
+    dip_trails: false, dip_no_dub: false, dip_feedback_loop: false, dip_manual: false,
+    midi_clock_ignore: false, ramp_bounce: false, expression: 0,
+  };
+}
+
+export function useHabitEditor(deviceName: string): UseHabitEditorReturn {
+  const [state, setState] = useState<HabitState | null>(null);
+  const [isLoading, setIsLoading] = useState(true);
+  const [error, setError] = useState<string | null>(null);
+
+  const [activePreset, setActivePreset] = useState<{ id: string; name: string } | null>(null);
+  const [originalPresetState, setOriginalPresetState] = useState<HabitState | null>(null);
+  const [isDirty, setIsDirty] = useState(false);
+
+  // Mirrors HabitState::update_from_cc: invalid toggle positions are ignored
+  const handleMidiCC = useCallback((event: MidiCCEvent) => {
+    setState(prev => {
+      if (!prev) return null;
+      const s = { ...prev };
+      const on = event.value >= 64;
+
+      switch (event.cc_number) {
+        case 14: s.level = event.value; break;
+        case 15: s.repeats = event.value; break;
+        case 16: s.size = event.value; break;
+        case 17: s.scan = event.value; break;
+        case 18: s.spread = event.value; break;
+        case 19: s.modify = event.value; break;
+        case 20: s.ramp_speed = event.value; break;
+        case 21: s.modifier = modifierFromCC(event.value) ?? s.modifier; break;
+        case 22: s.scan_mode = scanModeFromCC(event.value) ?? s.scan_mode; break;
+        case 23: s.collect_mode = collectModeFromCC(event.value) ?? s.collect_mode; break;
+        case 51: s.midi_clock_ignore = on; break;
+        case 52: s.ramp_bounce = on; break;
+        case 61: s.dip_level = on; break;
+        case 62: s.dip_repeats = on; break;
+        case 63: s.dip_size = on; break;
+        case 64: s.dip_scan = on; break;
+        case 65: s.dip_spread = on; break;
+        case 66: s.dip_bounce = on; break;
+        case 67: s.dip_sweep = on ? 'Top' : 'Bottom'; break;
+        case 68: s.dip_polarity = on ? 'Reverse' : 'Forward'; break;
+        case 71: s.dip_miso = on; break;
+        case 72: s.dip_stereo = on; break;
+        case 73: s.dip_latch = on; break;
+        case 74: s.dip_sync = on; break;
+        case 75: s.dip_trails = on; break;
+        case 76: s.dip_no_dub = on; break;
+        case 77: s.dip_feedback_loop = on; break;
+        case 78: s.dip_manual = on; break;
+        case 100: s.expression = event.value; break;
+        case 102: s.bypass = on; break;
+        case 103: s.memory = on; break;
+        case 104: s.alt_mode = on; break;
+        case 105: s.overdub = on; break;
+        case 106: s.memory_hold = on; break;
+        default: return prev;
+      }
+
+      return s;
+    });
+  }, []);
+
+  useMIDIInput(handleMidiCC, deviceName);
+
+  useEffect(() => {
+    let mounted = true;
+
+    async function loadState() {
+      try {
+        setIsLoading(true);
+        const initialState = await getHabitState(deviceName);
+        if (mounted) {
+          setState(initialState);
+          setError(null);
+        }
+      } catch (err) {
+        console.error('Failed to load Habit state:', err);
+        if (mounted) {
+          setError(err instanceof Error ? err.message : 'Failed to load state');
+          setState(createDefaultState());
+        }
+      } finally {
+        if (mounted) setIsLoading(false);
+      }
+    }
+
+    loadState();
+    return () => { mounted = false; };
+  }, [deviceName]);
+
+  useEffect(() => {
+    if (state && originalPresetState && activePreset) {
+      setIsDirty(JSON.stringify(state) !== JSON.stringify(originalPresetState));
+    } else {
+      setIsDirty(false);
+    }
+  }, [state, originalPresetState, activePreset]);
+
+  const sendParam = useCallback(async (param: HabitParameter) => {
+    try {
+      await sendHabitParameter(deviceName, param);
+      setError(null);
+    } catch (err) {
+      console.error('Failed to send parameter:', err);
+      setError(err instanceof Error ? err.message : 'Failed to send parameter');
+    }
+  }, [deviceName]);
+
+  // Main knobs
+  const setLevel = useCallback(async (v: number) => { setState(p => p ? { ...p, level: v } : null); await sendParam({ Level: v }); }, [sendParam]);
+  const setRepeats = useCallback(async (v: number) => { setState(p => p ? { ...p, repeats: v } : null); await sendParam({ Repeats: v }); }, [sendParam]);
+  const setSize = useCallback(async (v: number) => { setState(p => p ? { ...p, size: v } : null); await sendParam({ Size: v }); }, [sendParam]);
+  const setScan = useCallback(async (v: number) => { setState(p => p ? { ...p, scan: v } : null); await sendParam({ Scan: v }); }, [sendParam]);
+  const setSpread = useCallback(async (v: number) => { setState(p => p ? { ...p, spread: v } : null); await sendParam({ Spread: v }); }, [sendParam]);
+  const setModify = useCallback(async (v: number) => { setState(p => p ? { ...p, modify: v } : null); await sendParam({ Modify: v }); }, [sendParam]);
+  const setRampSpeed = useCallback(async (v: number) => { setState(p => p ? { ...p, ramp_speed: v } : null); await sendParam({ RampSpeed: v }); }, [sendParam]);
+
+  // Toggles
+  const setModifier = useCallback(async (v: Modifier) => { setState(p => p ? { ...p, modifier: v } : null); await sendParam({ Modifier: v }); }, [sendParam]);
+  const setScanMode = useCallback(async (v: ScanMode) => { setState(p => p ? { ...p, scan_mode: v } : null); await sendParam({ ScanMode: v }); }, [sendParam]);
+  const setCollectMode = useCallback(async (v: CollectMode) => { setState(p => p ? { ...p, collect_mode: v } : null); await sendParam({ CollectMode: v }); }, [sendParam]);
+
+  // Footswitches and memory
+  const setBypass = useCallback(async (v: boolean) => { setState(p => p ? { ...p, bypass: v } : null); await sendParam({ Bypass: v }); }, [sendParam]);
+  const setMemory = useCallback(async (v: boolean) => { setState(p => p ? { ...p, memory: v } : null); await sendParam({ Memory: v }); }, [sendParam]);
+  const setAltMode = useCallback(async (v: boolean) => { setState(p => p ? { ...p, alt_mode: v } : null); await sendParam({ AltMode: v }); }, [sendParam]);
+  const setOverdub = useCallback(async (v: boolean) => { setState(p => p ? { ...p, overdub: v } : null); await sendParam({ Overdub: v }); }, [sendParam]);
+  const setMemoryHold = useCallback(async (v: boolean) => { setState(p => p ? { ...p, memory_hold: v } : null); await sendParam({ MemoryHold: v }); }, [sendParam]);
+  const clearMemory = useCallback(async () => { await sendParam('ClearMemory'); }, [sendParam]);
+
+  // DIP switches - Left bank
+  const setDipLevel = useCallback(async (v: boolean) => { setState(p => p ? { ...p, dip_level: v } : null); await sendParam({ DipLevel: v }); }, [sendParam]);
+  const setDipRepeats = useCallback(async (v: boolean) => { setState(p => p ? { ...p, dip_repeats: v } : null); await sendParam({ DipRepeats: v }); }, [sendParam]);
+  const setDipSize = useCallback(async (v: boolean) => { setState(p => p ? { ...p, dip_size: v } : null); await sendParam({ DipSize: v }); }, [sendParam]);
+  const setDipScan = useCallback(async (v: boolean) => { setState(p => p ? { ...p, dip_scan: v } : null); await sendParam({ DipScan: v }); }, [sendParam]);
+  const setDipSpread = useCallback(async (v: boolean) => { setState(p => p ? { ...p, dip_spread: v } : null); await sendParam({ DipSpread: v }); }, [sendParam]);
+  const setDipBounce = useCallback(async (v: boolean) => { setState(p => p ? { ...p, dip_bounce: v } : null); await sendParam({ DipBounce: v }); }, [sendParam]);
+  const setDipSweep = useCallback(async (v: SweepDirection) => { setState(p => p ? { ...p, dip_sweep: v } : null); await sendParam({ DipSweep: v }); }, [sendParam]);
+  const setDipPolarity = useCallback(async (v: Polarity) => { setState(p => p ? { ...p, dip_polarity: v } : null); await sendParam({ DipPolarity: v }); }, [sendParam]);
+
+  // DIP switches - Right bank
+  const setDipMiso = useCallback(async (v: boolean) => { setState(p => p ? { ...p, dip_miso: v } : null); await sendParam({ DipMiso: v }); }, [sendParam]);
+  const setDipStereo = useCallback(async (v: boolean) => { setState(p => p ? { ...p, dip_stereo: v } : null); await sendParam({ DipStereo: v }); }, [sendParam]);
+  const setDipLatch = useCallback(async (v: boolean) => { setState(p => p ? { ...p, dip_latch: v } : null); await sendParam({ DipLatch: v }); }, [sendParam]);
+  const setDipSync = useCallback(async (v: boolean) => { setState(p => p ? { ...p, dip_sync: v } : null); await sendParam({ DipSync: v }); }, [sendParam]);
+  const setDipTrails = useCallback(async (v: boolean) => { setState(p => p ? { ...p, dip_trails: v } : null); await sendParam({ DipTrails: v }); }, [sendParam]);
+  const setDipNoDub = useCallback(async (v: boolean) => { setState(p => p ? { ...p, dip_no_dub: v } : null); await sendParam({ DipNoDub: v }); }, [sendParam]);
+  const setDipFeedbackLoop = useCallback(async (v: boolean) => { setState(p => p ? { ...p, dip_feedback_loop: v } : null); await sendParam({ DipFeedbackLoop: v }); }, [sendParam]);
+  const setDipManual = useCallback(async (v: boolean) => { setState(p => p ? { ...p, dip_manual: v } : null); await sendParam({ DipManual: v }); }, [sendParam]);
+
+  // Utility
+  const setMidiClockIgnore = useCallback(async (v: boolean) => { setState(p => p ? { ...p, midi_clock_ignore: v } : null); await sendParam({ MidiClockIgnore: v }); }, [sendParam]);
+  const setRampBounce = useCallback(async (v: boolean) => { setState(p => p ? { ...p, ramp_bounce: v } : null); await sendParam({ RampBounce: v }); }, [sendParam]);
+  const setExpression = useCallback(async (v: number) => { setState(p => p ? { ...p, expression: v } : null); await sendParam({ Expression: v }); }, [sendParam]);
+
+  // Preset management
+  const loadPreset = useCallback(async (
+    newState: HabitState,
+    presetId?: string,
+    presetName?: string,
+    skipMidiSend?: boolean,
+  ) => {
+    try {
+      // Confirm loud parameter jumps before the pedal or the UI changes
+      if (!skipMidiSend && !(await confirmRecall((ack) => recallHabitPreset(deviceName, newState, ack)))) {
+        return;
+      }
+      setState(newState);
+      if (presetId && presetName) {
+        setActivePreset({ id: presetId, name: presetName });
+        setOriginalPresetState(JSON.parse(JSON.stringify(newState)));
+      }
+      setError(null);
+    } catch (err) {
+      console.error('Failed to load preset:', err);
+      setError(err instanceof Error ? err.message : 'Failed to load preset');
+    }
+  }, [deviceName]);
+
+  const resetToPreset = useCallback(() => {
+    if (originalPresetState) {
+      loadPreset(originalPresetState, activePreset?.id, activePreset?.name);
+    }
+  }, [originalPresetState, activePreset, loadPreset]);
+
+  const resetToPedalDefault = useCallback(() => {
+    loadPreset(createDefaultState());
+  }, [loadPreset]);
+
+  const clearActivePreset = useCallback(() => {
+    setActivePreset(null);
+    setOriginalPresetState(null);
+    setIsDirty(false);
+  }, []);
+
+  return {
+    state, isLoading, error,
+    setLevel, setRepeats, setSize, setScan, setSpread, setModify, setRampSpeed,
+    setModifier, setScanMode, setCollectMode,
+    setBypass, setMemory, setAltMode, setOverdub, setMemoryHold, clearMemory,
+    setDipLevel, setDipRepeats, setDipSize, setDipScan, setDipSpread, setDipBounce,
+    setDipSweep, setDipPolarity,
+    setDipMiso, setDipStereo, setDipLatch, setDipSync,
+    setDipTrails, setDipNoDub, setDipFeedbackLoop, setDipManual,
+    setMidiClockIgnore, setRampBounce, setExpression,
+    loadPreset, activePreset, isDirty, resetToPreset, resetToPedalDefault, clearActivePreset,
+  };
+}
@@ -1,5 +1,6 @@
 import { useState, useCallback, useEffect, useRef } from 'react';
 import * as midi from '../../../lib/midi/pedals/microcosm';
+import { confirmRecall } from '../../../lib/midi';
 import type { MicrocosmState, MicrocosmParameter, EffectType, EffectVariation } from '../../../lib/midi/pedals/microcosm';
 
 export function useMicrocosmEditor(deviceName: string) {
@@ -323,8 +324,8 @@ export function useMicrocosmEditor(deviceName: string) {
   ) => {
     try {
       // Send to pedal (unless we're loading from pedal-bank where PC already sent)
-      if (!skipMidiSend) {
-        await midi.recallMicrocosmPreset(deviceName, presetState);
+      if (!skipMidiSend && !(await confirmRecall((ack) => midi.recallMicrocosmPreset(deviceName, presetState, ack)))) {
+        return;
       }
       // Update local state
       setState(presetState);
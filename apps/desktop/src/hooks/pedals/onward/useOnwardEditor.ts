@@ -16,6 +16,7 @@ import type {
   Polarity,
 } from '@/lib/midi/pedals/onward';
 import { useMIDIInput, type MidiCCEvent } from '@/hooks/useMIDIInput';
+import { confirmRecall } from '@/lib/midi';
 
 interface UseOnwardEditorReturn {
   state: OnwardState | null;
@@ -551,15 +552,16 @@ export function useOnwardEditor(deviceName: string): UseOnwardEditorReturn {
     skipMidiSend?: boolean,
   ) => {
     try {
+      // Confirm loud parameter jumps before the pedal or the UI changes
+      if (!skipMidiSend && !(await confirmRecall((ack) => recallOnwardPreset(deviceName, newState, ack)))) {
+        return;
+      }
       setState(newState);
       if (presetId && presetName) {
         setActivePreset({ id: presetId, name: presetName });
         setOriginalPresetState(JSON.parse(JSON.stringify(newState)));
       }
       setHookError(null);
-      if (!skipMidiSend) {
-        await recallOnwardPreset(deviceName, newState);
-      }
     } catch (err) {
       console.error('Failed to load preset:', err);
       setHookError(err instanceof Error ? err.message : 'Failed to load preset');
@@ -13,6 +13,7 @@ import {
   type FuzzMode,
 } from '@/lib/midi/pedals/preamp_mk2';
 import { useMIDIInput, type MidiCCEvent } from '@/hooks/useMIDIInput';
+import { confirmRecall } from '@/lib/midi';
 
 interface UsePreampMk2EditorReturn {
   state: PreampMk2State | null;
@@ -254,17 +255,16 @@ export function usePreampMk2Editor(deviceName: string): UsePreampMk2EditorReturn
     skipMidiSend?: boolean,
   ) => {
     try {
-      // Update UI immediately so the preset appears selected before faders move
+      // Confirm loud parameter jumps before the pedal or the UI changes
+      if (!skipMidiSend && !(await confirmRecall((ack) => recallPreampMk2Preset(deviceName, newState, ack)))) {
+        return;
+      }
       setState(newState);
       if (presetId && presetName) {
         setActivePreset({ id: presetId, name: presetName });
         setOriginalPresetState(JSON.parse(JSON.stringify(newState)));
       }
       setError(null);
-      // Send MIDI after UI update — motorized faders move to match the preset
-      if (!skipMidiSend) {
-        await recallPreampMk2Preset(deviceName, newState);
-      }
     } catch (err) {
       console.error('Failed to load preset:', err);
       setError(err instanceof Error ? err.message : 'Failed to load preset');
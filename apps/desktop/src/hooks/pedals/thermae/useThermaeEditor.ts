@@ -0,0 +1,321 @@
+// React hook for managing Chase Bliss Thermae editor state
+
+import { useState, useEffect, useCallback } from 'react';
+import {
+  getThermaeState,
+  sendThermaeParameter,
+  recallThermaePreset,
+  semitonesToInterval,
+} from '@/lib/midi/pedals/thermae';
+import type {
+  ThermaeState,
+  Division,
+  GlideMode,
+  ExtSwitchMode,
+  SweepDirection,
+  Polarity,
+} from '@/lib/midi/pedals/thermae';
+import { useMIDIInput, type MidiCCEvent } from '@/hooks/useMIDIInput';
+import { confirmRecall } from '@/lib/midi';
+
+interface UseThermaeEditorReturn {
+  state: ThermaeState | null;
+  isLoading: boolean;
+  error: string | null;
+
+  // Main knobs
+  setMix: (value: number) => Promise<void>;
+  setLowpass: (value: number) => Promise<void>;
+  setRegen: (value: number) => Promise<void>;
+  setGlide: (value: number) => Promise<void>;
+  setInterval1: (value: number) => Promise<void>;
+  setInterval2: (value: number) => Promise<void>;
+  setRampSpeed: (value: number) => Promise<void>;
+
+  // Toggles
+  setDivision: (v: Division) => Promise<void>;
+  setGlideMode: (v: GlideMode) => Promise<void>;
+  setExtSwitchMode: (v: ExtSwitchMode) => Promise<void>;
+
+  // Hidden knobs
+  setTime: (value: number) => Promise<void>;
+  setModRate: (value: number) => Promise<void>;
+  setRampRange: (value: number) => Promise<void>;
+
+  // Footswitches
+  setBypass: (v: boolean) => Promise<void>;
+  setHold: (v: boolean) => Promise<void>;
+  setSlow: (v: boolean) => Promise<void>;
+
+  // DIP switches - Left bank
+  setDipMix: (v: boolean) => Promise<void>;
+  setDipLowpass: (v: boolean) => Promise<void>;
+  setDipRegen: (v: boolean) => Promise<void>;
+  setDipGlide: (v: boolean) => Promise<void>;
+  setDipInterval1: (v: boolean) => Promise<void>;
+  setDipInterval2: (v: boolean) => Promise<void>;
+  setDipSweep: (v: SweepDirection) => Promise<void>;
+  setDipPolarity: (v: Polarity) => Promise<void>;
+
+  // DIP switches - Right bank
+  setDipMiso: (v: boolean) => Promise<void>;
+  setDipSpread: (v: boolean) => Promise<void>;
+  setDipTrails: (v: boolean) => Promise<void>;
+  setDipLatch: (v: boolean) => Promise<void>;
+  setDipSync: (v: boolean) => Promise<void>;
+  setDipHalfSpeed: (v: boolean) => Promise<void>;
+  setDipInvert: (v: boolean) => Promise<void>;
+  setDipAllWet: (v: boolean) => Promise<void>;
+
+  // Advanced
+  setRampBounce: (v: boolean) => Promise<void>;
+  setExpression: (value: number) => Promise<void>;
+
+  // Preset management
+  loadPreset: (state: ThermaeState, presetId?: string, presetName?: string, skipMidiSend?: boolean) => Promise<void>;
+  activePreset: { id: string; name: string } | null;
+  isDirty: boolean;
+  resetToPreset: () => void;
+  resetToPedalDefault: () => void;
+  clearActivePreset: () => void;
+}
+
+function divisionFromCC(value: number): Division {
+  if (value === 1) return 'Half';
+  if (value === 3) return 'Eighth';
+  return 'Quarter';
+}
+
+function glideModeFromCC(value: number): GlideMode {
+  if (value === 2) return 'Rise';
+  if (value === 3) return 'Fall';
+  return 'Off';
+}
+
+function extSwitchModeFromCC(value: number): ExtSwitchMode {
+  if (value === 2) return 'Hold';
+  if (value === 3) return 'Slow';
+  return 'Tap';
+}
+
+function createDefaultState(): ThermaeState {
+  return {
+    mix: 64, lowpass: 127, regen: 64, glide: 0,
+    interval_1: semitonesToInterval(12), interval_2: semitonesToInterval(7),
+    ramp_speed: 64,
+    division: 'Quarter', glide_mode: 'Off', ext_switch_mode: 'Tap',
+    time: 64, mod_rate: 64, ramp_range: 127,
+    bypass: false, hold: false, slow: false,
+    dip_mix: false, dip_lowpass: false, dip_regen: false, dip_glide: false,
+    dip_interval_1: false, dip_interval_2: false,
+    dip_sweep: 'Bottom', dip_polarity: 'Forward',
+    dip_miso: false, dip_spread: false, dip_trails: false, dip_latch: false,
+    dip_sync: false, dip_half_speed: false, dip_invert: false, dip_all_wet: false,
+    ramp_bounce: false, expression: 0,
+  };
+}
+
+export function useThermaeEditor(deviceName: string): UseThermaeEditorReturn {
+  const [state, setState] = useState<ThermaeState | null>(null);
+  const [isLoading, setIsLoading] = useState(true);
+  const [error, setError] = useState<string | null>(null);
+
+  const [activePreset, setActivePreset] = useState<{ id: string; name: string } | null>(null);
+  const [originalPresetState, setOriginalPresetState] = useState<ThermaeState | null>(null);
+  const [isDirty, setIsDirty] = useState(false);
+
+  const handleMidiCC = useCallback((event: MidiCCEvent) => {
+    setState(prev => {
+      if (!prev) return null;
+      const s = { ...prev };
+
+      switch (event.cc_number) {
+        case 14: s.mix = event.value; break;
+        case 15: s.lowpass = event.value; break;
+        case 16: s.regen = event.value; break;
+        case 17: s.glide = event.value; break;
+        case 18: s.interval_1 = event.value; break;
+        case 19: s.interval_2 = event.value; break;
+        case 20: s.ramp_speed = event.value; break;
+        case 21: s.division = divisionFromCC(event.value); break;
+        case 22: s.glide_mode = glideModeFromCC(event.value); break;
+        case 23: s.ext_switch_mode = extSwitchModeFromCC(event.value); break;
+        case 24: s.time = event.value; break;
+        case 25: s.mod_rate = event.value; break;
+        case 26: s.ramp_range = event.value; break;
+        case 52: s.ramp_bounce = event.value >= 64; break;
+        case 61: s.dip_mix = event.value >= 64; break;
+        case 62: s.dip_lowpass = event.value >= 64; break;
+        case 63: s.dip_regen = event.value >= 64; break;
+        case 64: s.dip_glide = event.value >= 64; break;
+        case 65: s.dip_interval_1 = event.value >= 64; break;
+        case 66: s.dip_interval_2 = event.value >= 64; break;
+        case 67: s.dip_sweep = event.value < 64 ? 'Bottom' : 'Top'; break;
+        case 68: s.dip_polarity = event.value < 64 ? 'Forward' : 'Reverse'; break;
+        case 71: s.dip_miso = event.value >= 64; break;
+        case 72: s.dip_spread = event.value >= 64; break;
+        case 73: s.dip_trails = event.value >= 64; break;
+        case 74: s.dip_latch = event.value >= 64; break;
+        case 75: s.dip_sync = event.value >= 64; break;
+        case 76: s.dip_half_speed = event.value >= 64; break;
+        case 77: s.dip_invert = event.value >= 64; break;
+        case 78: s.dip_all_wet = event.value >= 64; break;
+        case 100: s.expression = event.value; break;
+        case 102: s.bypass = event.value >= 64; break;
+        case 103: s.hold = event.value >= 64; break;
+        case 104: s.slow = event.value >= 64; break;
+        default: return prev;
+      }
+
+      return s;
+    });
+  }, []);
+
+  useMIDIInput(handleMidiCC, deviceName);
+
+  useEffect(() => {
+    let mounted = true;
+
+    async function loadState() {
+      try {
+        setIsLoading(true);
+        const initialState = await getThermaeState(deviceName);
+        if (mounted) {
+          setState(initialState);
+          setError(null);
+        }
+      } catch (err) {
+        console.error('Failed to load Thermae state:', err);
+        if (mounted) {
+          setError(err instanceof Error ? err.message : 'Failed to load state');
+          setState(createDefaultState());
+        }
+      } finally {
+        if (mounted) setIsLoading(false);
+      }
+    }
+
+    loadState();
+    return () => { mounted = false; };
+  }, [deviceName]);
+
+  useEffect(() => {
+    if (state && originalPresetState && activePreset) {
+      setIsDirty(JSON.stringify(state) !== JSON.stringify(originalPresetState));
+    } else {
+      setIsDirty(false);
+    }
+  }, [state, originalPresetState, activePreset]);
+
+  const sendParam = useCallback(async (param: any) => {
+    try {
+      await sendThermaeParameter(deviceName, param);
+      setError(null);
+    } catch (err) {
+      console.error('Failed to send parameter:', err);
+      setError(err instanceof Error ? err.message : 'Failed to send parameter');
+    }
+  }, [deviceName]);
+
+  // Main knobs
+  const setMix = useCallback(async (v: number) => { setState(p => p ? { ...p, mix: v } : null); await sendParam({ Mix: v }); }, [sendParam]);
+  const setLowpass = useCallback(async (v: number) => { setState(p => p ? { ...p, lowpass: v } : null); await sendParam({ Lowpass: v }); }, [sendParam]);
+  const setRegen = useCallback(async (v: number) => { setState(p => p ? { ...p, regen: v } : null); await sendParam({ Regen: v }); }, [sendParam]);
+  const setGlide = useCallback(async (v: number) => { setState(p => p ? { ...p, glide: v } : null); await sendParam({ Glide: v }); }, [sendParam]);
+  const setInterval1 = useCallback(async (v: number) => { setState(p => p ? { ...p, interval_1: v } : null); await sendParam({ Interval1: v }); }, [sendParam]);
+  const setInterval2 = useCallback(async (v: number) => { setState(p => p ? { ...p, interval_2: v } : null); await sendParam({ Interval2: v }); }, [sendParam]);
+  const setRampSpeed = useCallback(async (v: number) => { setState(p => p ? { ...p, ramp_speed: v } : null); await sendParam({ RampSpeed: v }); }, [sendParam]);
+
+  // Toggles
+  const setDivision = useCallback(async (v: Division) => { setState(p => p ? { ...p, division: v } : null); await sendParam({ Division: v }); }, [sendParam]);
+  const setGlideMode = useCallback(async (v: GlideMode) => { setState(p => p ? { ...p, glide_mode: v } : null); await sendParam({ GlideMode: v }); }, [sendParam]);
+  const setExtSwitchMode = useCallback(async (v: ExtSwitchMode) => { setState(p => p ? { ...p, ext_switch_mode: v } : null); await sendParam({ ExtSwitchMode: v }); }, [sendParam]);
+
+  // Hidden knobs
+  const setTime = useCallback(async (v: number) => { setState(p => p ? { ...p, time: v } : null); await sendParam({ Time: v }); }, [sendParam]);
+  const setModRate = useCallback(async (v: number) => { setState(p => p ? { ...p, mod_rate: v } : null); await sendParam({ ModRate: v }); }, [sendParam]);
+  const setRampRange = useCallback(async (v: number) => { setState(p => p ? { ...p, ramp_range: v } : null); await sendParam({ RampRange: v }); }, [sendParam]);
+
+  // Footswitches
+  const setBypass = useCallback(async (v: boolean) => { setState(p => p ? { ...p, bypass: v } : null); await sendParam({ Bypass: v }); }, [sendParam]);
+  const setHold = useCallback(async (v: boolean) => { setState(p => p ? { ...p, hold: v } : null); await sendParam({ Hold: v }); }, [sendParam]);
+  const setSlow = useCallback(async (v: boolean) => { setState(p => p ? { ...p, slow: v } : null); await sendParam({ Slow: v }); }, [sendParam]);
+
+  // DIP switches - Left bank
+  const setDipMix = useCallback(async (v: boolean) => { setState(p => p ? { ...p, dip_mix: v } : null); await sendParam({ DipMix: v }); }, [sendParam]);
+  const setDipLowpass = useCallback(async (v: boolean) => { setState(p => p ? { ...p, dip_lowpass: v } : null); await sendParam({ DipLowpass: v }); }, [sendParam]);
+  const setDipRegen = useCallback(async (v: boolean) => { setState(p => p ? { ...p, dip_regen: v } : null); await sendParam({ DipRegen: v }); }, [sendParam]);
+  const setDipGlide = useCallback(async (v: boolean) => { setState(p => p ? { ...p, dip_glide: v } : null); await sendParam({ DipGlide: v }); }, [sendParam]);
+  const setDipInterval1 = useCallback(async (v: boolean) => { setState(p => p ? { ...p, dip_interval_1: v } : null); await sendParam({ DipInterval1: v }); }, [sendParam]);
+  const setDipInterval2 = useCallback(async (v: boolean) => { setState(p => p ? { ...p, dip_interval_2: v } : null); await sendParam({ DipInterval2: v }); }, [sendParam]);
+  const setDipSweep = useCallback(async (v: SweepDirection) => { setState(p => p ? { ...p, dip_sweep: v } : null); await sendParam({ DipSweep: v }); }, [sendParam]);
+  const setDipPolarity = useCallback(async (v: Polarity) => { setState(p => p ? { ...p, dip_polarity: v } : null); await sendParam({ DipPolarity: v }); }, [sendParam]);
+
+  // DIP switches - Right bank
+  const setDipMiso = useCallback(async (v: boolean) => { setState(p => p ? { ...p, dip_miso: v } : null); await sendParam({ DipMiso: v }); }, [sendParam]);
+  const setDipSpread = useCallback(async (v: boolean) => { setState(p => p ? { ...p, dip_spread: v } : null); await sendParam({ DipSpread: v }); }, [sendParam]);
+  const setDipTrails = useCallback(async (v: boolean) => { setState(p => p ? { ...p, dip_trails: v } : null); await sendParam({ DipTrails: v }); }, [sendParam]);
+  const setDipLatch = useCallback(async (v: boolean) => { setState(p => p ? { ...p, dip_latch: v } : null); await sendParam({ DipLatch: v }); }, [sendParam]);
+  const setDipSync = useCallback(async (v: boolean) => { setState(p => p ? { ...p, dip_sync: v } : null); await sendParam({ DipSync: v }); }, [sendParam]);
+  const setDipHalfSpeed = useCallback(async (v: boolean) => { setState(p => p ? { ...p, dip_half_speed: v } : null); await sendParam({ DipHalfSpeed: v }); }, [sendParam]);
+  const setDipInvert = useCallback(async (v: boolean) => { setState(p => p ? { ...p, dip_invert: v } : null); await sendParam({ DipInvert: v }); }, [sendParam]);
+  const setDipAllWet = useCallback(async (v: boolean) => { setState(p => p ? { ...p, dip_all_wet: v } : null); await sendParam({ DipAllWet: v }); }, [sendParam]);
+
+  // Advanced
+  const setRampBounce = useCallback(async (v: boolean) => { setState(p => p ? { ...p, ramp_bounce: v } : null); await sendParam({ RampBounce: v }); }, [sendParam]);
+  const setExpression = useCallback(async (v: number) => { setState(p => p ? { ...p, expression: v } : null); await sendParam({ Expression: v }); }, [sendParam]);
+
+  // Preset management
+  const loadPreset = useCallback(async (
+    newState: ThermaeState,
+    presetId?: string,
+    presetName?: string,
+    skipMidiSend?: boolean,
+  ) => {
+    try {
+      // Confirm loud parameter jumps before the pedal or the UI changes
+      if (!skipMidiSend && !(await confirmRecall((ack) => recallThermaePreset(deviceName, newState, ack)))) {
+        return;
+      }
+      setState(newState);
+      if (presetId && presetName) {
+        setActivePreset({ id: presetId, name: presetName });
+        setOriginalPresetState(JSON.parse(JSON.stringify(newState)));
+      }
+      setError(null);
+    } catch (err) {
+      console.error('Failed to load preset:', err);
+      setError(err instanceof Error ? err.message : 'Failed to load preset');
+    }
+  }, [deviceName]);
+
+  const resetToPreset = useCallback(() => {
+    if (originalPresetState) {
+      loadPreset(originalPresetState, activePreset?.id, activePreset?.name);
+    }
+  }, [originalPresetState, activePreset, loadPreset]);
+
+  const resetToPedalDefault = useCallback(() => {
+    loadPreset(createDefaultState());
+  }, [loadPreset]);
+
+  const clearActivePreset = useCallback(() => {
+    setActivePreset(null);
+    setOriginalPresetState(null);
+    setIsDirty(false);
+  }, []);
+
+  return {
+    state, isLoading, error,
+    setMix, setLowpass, setRegen, setGlide, setInterval1, setInterval2, setRampSpeed,
+    setDivision, setGlideMode, setExtSwitchMode,
+    setTime, setModRate, setRampRange,
+    setBypass, setHold, setSlow,
+    setDipMix, setDipLowpass, setDipRegen, setDipGlide, setDipInterval1, setDipInterval2,
+    setDipSweep, setDipPolarity,
+    setDipMiso, setDipSpread, setDipTrails, setDipLatch, setDipSync,
+    setDipHalfSpeed, setDipInvert, setDipAllWet,
+    setRampBounce, setExpression,
+    loadPreset, activePreset, isDirty, resetToPreset, resetToPedalDefault, clearActivePreset,
+  };
+}
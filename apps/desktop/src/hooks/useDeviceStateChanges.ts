@@ -0,0 +1,44 @@
+// Hook for listening to device state diffs emitted by the backend
+// Lets editors animate just the controls that moved after a recall or undo
+
+import { useEffect, useCallback } from 'react';
+import { listen, UnlistenFn } from '@tauri-apps/api/event';
+import type { DeviceStateChangedEvent } from '@/lib/midi/types';
+
+export type DeviceStateChangedCallback = (event: DeviceStateChangedEvent) => void;
+
+/**
+ * Hook to listen for changes to a device's believed state
+ * @param callback Function to call with the changed fields
+ * @param deviceName Optional device name to filter events for a specific device
+ */
+export function useDeviceStateChanges(callback: DeviceStateChangedCallback, deviceName?: string) {
+  const handleStateChanged = useCallback((event: DeviceStateChangedEvent) => {
+    if (deviceName && event.device_name !== deviceName) {
+      return;
+    }
+    callback(event);
+  }, [callback, deviceName]);
+
+  useEffect(() => {
+    let unlisten: UnlistenFn | null = null;
+
+    const setupListener = async () => {
+      try {
+        unlisten = await listen<DeviceStateChangedEvent>('device-state-changed', (event) => {
+          handleStateChanged(event.payload);
+        });
+      } catch (error) {
+        console.error('❌ Failed to setup device state listener:', error);
+      }
+    };
+
+    setupListener();
+
+    return () => {
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, [handleStateChanged]);
+}
@@ -2,7 +2,8 @@ import { useState, useCallback } from 'react';
 import * as midiCommon from '../lib/midi';
 import { connectMicrocosm } from '../lib/midi/pedals/microcosm';
 import { connectGenLossMkii } from '../lib/midi/pedals/gen-loss-mkii';
-import { connectChromaConsole } from '../lib/midi/pedals/chroma_console';
+import { connectChromaConsole, setChromaConsoleBypassMode } from '../lib/midi/pedals/chroma_console';
+import { getChromaBypassModeForDevice } from '../lib/midi/deviceProfiles';
 import { connectPreampMk2 } from '../lib/midi/pedals/preamp_mk2';
 import { connectCxm1978 } from '../lib/midi/pedals/cxm1978';
 import { connectClean } from '../lib/midi/pedals/clean';
@@ -12,6 +13,10 @@ import { connectReverseModeC } from '../lib/midi/pedals/reverse-mode-c';
 import { connectMoodMkii } from '../lib/midi/pedals/mood-mkii';
 import { connectBillyStringsWombtone } from '../lib/midi/pedals/billy-strings-wombtone';
 import { connectLossy } from '../lib/midi/pedals/lossy';
+import { connectThermae } from '../lib/midi/pedals/thermae';
+import { connectDarkWorld } from '../lib/midi/pedals/dark-world';
+import { connectHabit } from '../lib/midi/pedals/habit';
+import { connectCollider } from '../lib/midi/pedals/collider';
 import type { DeviceInfo, PedalType } from '../lib/midi';
 
 export function useMIDIConnection() {
@@ -26,7 +31,7 @@ export function useMIDIConnection() {
     setError(null);
     
     try {
-      const deviceList = await midiCommon.listMidiDevices();
+      const { devices: deviceList } = await midiCommon.listMidiDevices();
       setDevices(deviceList);
     } catch (err) {
       setError(err instanceof Error ? err.message : 'Failed to list MIDI devices');
@@ -51,6 +56,8 @@ export function useMIDIConnection() {
           break;
         case 'ChromaConsole':
           await connectChromaConsole(deviceName, channel);
+          // Footswitch configuration is a device setting - restore it on every connect
+          await setChromaConsoleBypassMode(deviceName, getChromaBypassModeForDevice(deviceName));
           break;
         case 'PreampMk2':
           await connectPreampMk2(deviceName, channel);
@@ -79,6 +86,18 @@ export function useMIDIConnection() {
         case 'Lossy':
           await connectLossy(deviceName, channel);
           break;
+        case 'Thermae':
+          await connectThermae(deviceName, channel);
+          break;
+        case 'DarkWorld':
+          await connectDarkWorld(deviceName, channel);
+          break;
+        case 'Habit':
+          await connectHabit(deviceName, channel);
+          break;
+        case 'Collider':
+          await connectCollider(deviceName, channel);
+          break;
         default:
           throw new Error(`Unknown pedal type: ${pedalType}`);
       }
@@ -89,6 +108,18 @@ export function useMIDIConnection() {
       
       if (device) {
         setConnectedDevice(device);
+
+        // Check the channel in the background; a mismatch surfaces as a
+        // channel-mismatch-detected event, so failures here are only logged
+        if (midiCommon.isChannelVerificationEnabled()) {
+          midiCommon.verifyDeviceChannel(deviceName)
+            .then(channelVerified => {
+              setConnectedDevice(current =>
+                current?.name === deviceName ? { ...current, channel_verified: channelVerified } : current
+              );
+            })
+            .catch(err => console.warn('Channel verification failed:', err));
+        }
       } else {
         throw new Error('Device connected but not found in connected devices list');
       }
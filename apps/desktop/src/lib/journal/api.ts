@@ -0,0 +1,73 @@
+// Session journal API - wrappers for Tauri commands
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * Path of the journal file currently being written
+ */
+export async function getSessionJournalPath(): Promise<string> {
+  return invoke<string>('get_session_journal_path');
+}
+
+/**
+ * Frontend settings as stored in localStorage (values parsed as JSON where possible)
+ */
+function collectSettings(): Record<string, unknown> {
+  const settings: Record<string, unknown> = {};
+  for (let i = 0; i < localStorage.length; i++) {
+    const key = localStorage.key(i);
+    if (key === null) continue;
+    const raw = localStorage.getItem(key);
+    try {
+      settings[key] = raw === null ? null : JSON.parse(raw);
+    } catch {
+      settings[key] = raw;
+    }
+  }
+  return settings;
+}
+
+export type CheckStatus = 'pass' | 'warn' | 'fail';
+
+export interface SelfTestCheck {
+  name: string;
+  status: CheckStatus;
+  detail: string;
+}
+
+/** Whether a pedal type is wired up everywhere it needs to be */
+export interface PedalCoverage {
+  pedalType: string;
+  bankConfig: boolean;
+  schema: boolean;
+  missingCommands: string[];
+}
+
+export interface SelfTestReport {
+  /** No check failed (warnings allowed) */
+  passed: boolean;
+  checks: SelfTestCheck[];
+  inputPorts: number;
+  outputPorts: number;
+  timerResolutionMs: number;
+  pedals: PedalCoverage[];
+}
+
+/**
+ * Check what this install can do: MIDI, database, app data dir, virtual ports,
+ * timer resolution and each pedal's wiring
+ */
+export async function runSelfTest(): Promise<SelfTestReport> {
+  return invoke<SelfTestReport>('run_self_test');
+}
+
+/**
+ * Zip recent journals, settings, library stats and a self-test report into a support bundle at `path`.
+ * Full presets are only included when `includePresets` is set.
+ */
+export async function exportSupportBundle(path: string, includePresets: boolean): Promise<void> {
+  return invoke('export_support_bundle', {
+    path,
+    settings: collectSettings(),
+    includePresets,
+  });
+}
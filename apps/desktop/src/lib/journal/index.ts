@@ -0,0 +1,2 @@
+// Session journal module exports
+export * from './api';
@@ -0,0 +1,34 @@
+// Library server API - wrappers for Tauri commands
+import { invoke } from '@tauri-apps/api/core';
+
+/** Which interfaces the server listens on */
+export type BindScope = 'localhost' | 'lan';
+
+/** User settings for the read-only library server */
+export interface LibraryServerConfig {
+  enabled: boolean;
+  bind: BindScope;
+  /** 0 picks a free port */
+  port: number;
+}
+
+/** Settings plus, while running, the URL and bearer token for companion devices */
+export interface LibraryServerInfo {
+  config: LibraryServerConfig;
+  url: string | null;
+  token: string | null;
+}
+
+/**
+ * Library server settings, URL and this session's bearer token
+ */
+export async function getLibraryServerInfo(): Promise<LibraryServerInfo> {
+  return invoke<LibraryServerInfo>('get_library_server_info');
+}
+
+/**
+ * Save library server settings; the server starts, restarts or stops to match
+ */
+export async function setLibraryServerConfig(config: LibraryServerConfig): Promise<LibraryServerInfo> {
+  return invoke<LibraryServerInfo>('set_library_server_config', { config });
+}
@@ -0,0 +1,2 @@
+// Library server module exports
+export * from './api';
@@ -1,5 +1,7 @@
 // Generic MIDI API — shared operations not tied to a specific pedal type
 import { invoke } from '@tauri-apps/api/core';
+import type { FirmwareWarning, PedalType, RecallOutcome } from './types';
+import type { EffectType, EffectVariation } from './pedals/microcosm/types';
 
 /**
  * Send a Program Change on the given channel to the named MIDI device without
@@ -13,3 +15,403 @@ export async function assignChannelPc(
 ): Promise<void> {
   return invoke('assign_channel_pc', { deviceName, channel });
 }
+
+/**
+ * CC/PC received on Librarian's virtual MIDI port.
+ * Emitted as the `virtual-port-message` event.
+ */
+export type VirtualPortEvent = { port_name: string } & (
+  | { kind: 'control_change'; channel: number; cc_number: number; value: number }
+  | { kind: 'program_change'; channel: number; program: number }
+);
+
+/**
+ * Whether this platform can create virtual MIDI ports (not available on Windows).
+ */
+export async function virtualPortSupported(): Promise<boolean> {
+  return invoke('virtual_port_supported');
+}
+
+/**
+ * Expose Librarian as a virtual MIDI device so other gear can find it via
+ * Device Inquiry and drive the app with CC/PC. Defaults to the name "Librarian".
+ */
+export async function enableVirtualPort(name?: string): Promise<void> {
+  return invoke('enable_virtual_port', { name });
+}
+
+/**
+ * Close the virtual MIDI port.
+ */
+export async function disableVirtualPort(): Promise<void> {
+  return invoke('disable_virtual_port');
+}
+
+/**
+ * What a remapped program change does: recall a library preset, jump a
+ * Microcosm to an effect/variation, or send one parameter (e.g. a looper trigger).
+ */
+export type PcAction =
+  | { RecallPreset: string }
+  | { MicrocosmProgram: [EffectType, EffectVariation] }
+  | { Trigger: PedalParameter };
+
+export interface PcMapping {
+  program: number;
+  deviceName: string;
+  action: PcAction;
+}
+
+/** A monitored input's remap table */
+export interface InputRemap {
+  inputPort: string;
+  bypassed: boolean;
+  mappings: PcMapping[]; // sorted by program
+}
+
+/** Payload of `pc-remap-executed`, emitted when a mapped PC runs its action */
+export interface PcRemapExecutedEvent {
+  inputPort: string;
+  channel: number;
+  mapping: PcMapping;
+  error: string | null;
+}
+
+/**
+ * Payload of `monitored-pc-received`: a PC a monitored input passed on
+ * because it isn't mapped or the input's table is bypassed.
+ */
+export interface MonitoredPcEvent {
+  inputPort: string;
+  channel: number;
+  program: number;
+}
+
+/**
+ * Watch an input port (e.g. a foot controller) for program changes to remap.
+ */
+export async function monitorMidiInput(inputPort: string): Promise<void> {
+  return invoke('monitor_midi_input', { inputPort });
+}
+
+export async function stopMonitoringMidiInput(inputPort: string): Promise<void> {
+  return invoke('stop_monitoring_midi_input', { inputPort });
+}
+
+export async function listMonitoredInputs(): Promise<string[]> {
+  return invoke('list_monitored_inputs');
+}
+
+/**
+ * Map PC `program` on an input to an action on a connected device.
+ * Rejects if that PC is already mapped on the input.
+ */
+export async function createPcMapping(
+  inputPort: string,
+  program: number,
+  deviceName: string,
+  action: PcAction
+): Promise<PcMapping> {
+  return invoke('create_pc_mapping', { inputPort, program, deviceName, action });
+}
+
+export async function listPcMappings(): Promise<InputRemap[]> {
+  return invoke('list_pc_mappings');
+}
+
+export async function deletePcMapping(inputPort: string, program: number): Promise<void> {
+  return invoke('delete_pc_mapping', { inputPort, program });
+}
+
+/**
+ * Pass every PC on an input through untouched, keeping its table for later.
+ */
+export async function setPcRemapBypassed(inputPort: string, bypassed: boolean): Promise<void> {
+  return invoke('set_pc_remap_bypassed', { inputPort, bypassed });
+}
+
+/**
+ * A connected device's state tagged with its pedal type.
+ * `state` has the same shape save_preset takes as parameters.
+ */
+export interface DeviceState {
+  pedal_type: string;
+  state: Record<string, unknown>;
+  firmware: string | null;
+}
+
+/**
+ * Get any connected device's state without knowing its pedal type.
+ */
+export async function getDeviceState(deviceName: string): Promise<DeviceState> {
+  return invoke('get_device_state', { deviceName });
+}
+
+/**
+ * Get the state of every connected device, keyed by device name.
+ * Cheap way to resync the UI, e.g. when the window regains focus.
+ */
+export async function getAllDeviceStates(): Promise<Record<string, DeviceState>> {
+  return invoke('get_all_device_states');
+}
+
+/**
+ * Save the device's current state so the next change can be undone.
+ * Call before recalling a preset; the last 10 snapshots are kept per device.
+ */
+export async function recordDeviceStateSnapshot(deviceName: string): Promise<number> {
+  return invoke('record_device_state_snapshot', { deviceName });
+}
+
+/**
+ * Restore the device state saved before the last change.
+ */
+export async function undoDeviceState(deviceName: string): Promise<void> {
+  return invoke('undo_device_state', { deviceName });
+}
+
+/** One of the two compare buffers */
+export type AbSlot = 'A' | 'B';
+
+/**
+ * Compare buffer state for a device. `matching` is the buffer equal to the current
+ * state (A if both are); `active` is the buffer last stored or recalled.
+ */
+export interface AbStatus {
+  a_stored: boolean;
+  b_stored: boolean;
+  matching: AbSlot | null;
+  active: AbSlot | null;
+}
+
+/**
+ * Store the device's current state in compare buffer A or B.
+ * Buffers are kept until the device disconnects.
+ */
+export async function storeAb(deviceName: string, slot: AbSlot): Promise<AbStatus> {
+  return invoke('store_ab', { deviceName, slot });
+}
+
+/**
+ * Recall compare buffer A or B. Only CCs that differ from the current state are sent.
+ * A `glideMs` above 0 (at most 2 s) ramps `glideCcs` (mix and level controls by default) to their targets over that long.
+ */
+export async function recallAb(
+  deviceName: string,
+  slot: AbSlot,
+  glideMs: number = 0,
+  glideCcs?: number[]
+): Promise<AbStatus> {
+  return invoke('recall_ab', { deviceName, slot, glideMs, glideCcs });
+}
+
+/**
+ * Flip to whichever compare buffer isn't active (A if neither has been used).
+ */
+export async function swapAb(deviceName: string): Promise<AbStatus> {
+  return invoke('swap_ab', { deviceName });
+}
+
+export async function getAbStatus(deviceName: string): Promise<AbStatus> {
+  return invoke('get_ab_status', { deviceName });
+}
+
+/**
+ * A parameter tagged with its pedal type, as sent by each pedal's own
+ * `send_*_parameter` command (e.g. `{ pedal_type: 'GenLossMkii', param: { Wow: 64 } }`).
+ */
+export interface PedalParameter {
+  pedal_type: PedalType;
+  param: Record<string, unknown>;
+}
+
+/**
+ * Send several parameters to one device as a single ordered batch, for macro controls.
+ * Rejects without sending anything if any parameter is for a different pedal type.
+ * Emits one `device-state-changed` event covering every field that changed.
+ * Resolves to any CCs the device's firmware predates (sent or skipped per settings).
+ * Pass `userInitiated` for an explicit user action, which sends even before the device is armed.
+ */
+export async function sendParametersBatch(
+  deviceName: string,
+  params: PedalParameter[],
+  userInitiated = false
+): Promise<FirmwareWarning[]> {
+  return invoke('send_parameters_batch', { deviceName, params, userInitiated });
+}
+
+/**
+ * Send one parameter to any device. Rejects without sending if the parameter
+ * is for a different pedal type. Resolves to any CCs the firmware predates.
+ */
+export async function sendPedalParameter(
+  deviceName: string,
+  param: PedalParameter
+): Promise<FirmwareWarning[]> {
+  return invoke('send_pedal_parameter', { deviceName, param });
+}
+
+/**
+ * A full state for any pedal, tagged with its pedal type.
+ * Same shape as `DeviceState` without the firmware.
+ */
+export interface PedalState {
+  pedal_type: PedalType;
+  state: Record<string, unknown>;
+}
+
+export async function getPedalState(deviceName: string): Promise<PedalState> {
+  return invoke('get_pedal_state', { deviceName });
+}
+
+/**
+ * Ramp continuous controls during a recall instead of jumping them.
+ * `glideCcs` defaults to the pedal's mix and level controls.
+ */
+export interface GlideOptions {
+  glideMs?: number; // 0 or missing = no glide
+  glideCcs?: number[];
+}
+
+/**
+ * Recall any pedal's state on a device of the same type.
+ * Loud parameter jumps block the recall until `acknowledgeWarnings` is set.
+ */
+export async function recallPedalState(
+  deviceName: string,
+  state: PedalState,
+  acknowledgeWarnings = false,
+  glide: GlideOptions = {}
+): Promise<RecallOutcome> {
+  return invoke('recall_pedal_state', { deviceName, state, acknowledgeWarnings, ...glide });
+}
+
+/** What a recall would recall: a library preset, or a state like recallPedalState takes */
+export type RecallSource =
+  | { type: 'preset'; presetId: string }
+  | { type: 'state'; state: PedalState };
+
+export interface RecallOperation extends GlideOptions {
+  source: RecallSource;
+  diffOnly?: boolean; // Only CCs that differ from the device's current state
+}
+
+export interface StepEstimate {
+  ccNumber: number;
+  value: number;
+  startsAtMs: number;
+  delayMs: number; // Pause after this step
+}
+
+export interface PlanEstimate {
+  messageCount: number;
+  durationMs: number;
+  steps: StepEstimate[];
+}
+
+/** Payload of `send-progress`, emitted after each step of a plan goes out */
+export interface SendProgressEvent {
+  deviceName: string;
+  step: number; // Steps sent so far, out of `total`
+  total: number;
+  elapsedMs: number;
+  remainingMs: number;
+}
+
+/**
+ * How many messages a recall would send and how long it would take, without sending.
+ * `send-progress` events during the recall count through the same steps.
+ */
+export async function estimateOperation(deviceName: string, operation: RecallOperation): Promise<PlanEstimate> {
+  return invoke('estimate_operation', { deviceName, operation });
+}
+
+/**
+ * Play a note on a device: Note On, a `durationMs` pause, then Note Off. Pauses past 10 s are refused.
+ * Note and velocity must be 0-127. `userInitiated` sends even before the device is armed.
+ */
+export async function sendMidiNote(
+  deviceName: string,
+  note: number,
+  velocity: number,
+  durationMs: number,
+  userInitiated = false
+): Promise<void> {
+  return invoke('send_midi_note', { deviceName, note, velocity, durationMs, userInitiated });
+}
+
+/** Outcome of bypassing or restoring one device */
+export interface BypassResult {
+  device_name: string;
+  success: boolean;
+  error: string | null;
+}
+
+/**
+ * Bypass every connected device, remembering each one's bypass state.
+ * Also emits a `bypass-result` event per device.
+ */
+export async function bypassAll(): Promise<BypassResult[]> {
+  return invoke('bypass_all');
+}
+
+/**
+ * Restore the bypass states recorded by the last `bypassAll`.
+ * Devices that disconnected in between come back with `success: false`.
+ */
+export async function restoreBypassStates(): Promise<BypassResult[]> {
+  return invoke('restore_bypass_states');
+}
+
+/**
+ * One entry in a device's parameter history.
+ * Recalls, program changes and batches are single composite entries.
+ */
+export type HistoryEntry = { device_name: string; timestamp: number } & (
+  | {
+      kind: 'parameter';
+      parameter: string;
+      cc_number: number;
+      previous_value: number | null;
+      new_value: number;
+    }
+  | {
+      kind: 'nrpn';
+      parameter: string;
+      nrpn_parameter: number;
+      previous_value: number | null;
+      new_value: number;
+    }
+  | { kind: 'recall' }
+  | { kind: 'program_change'; program: number }
+  | { kind: 'batch'; parameters: string[] }
+);
+
+/**
+ * Undo/redo history for a device. `undo` is oldest first; the next redo is last in `redo`.
+ */
+export interface UndoStack {
+  undo: HistoryEntry[];
+  redo: HistoryEntry[];
+}
+
+/**
+ * Undo the last parameter change, recall or program change. Resolves to null if there was nothing to undo.
+ */
+export async function undoParameterChange(deviceName: string): Promise<HistoryEntry | null> {
+  return invoke('undo_parameter_change', { deviceName });
+}
+
+/**
+ * Redo the last undone change. Resolves to null if there was nothing to redo.
+ */
+export async function redoParameterChange(deviceName: string): Promise<HistoryEntry | null> {
+  return invoke('redo_parameter_change', { deviceName });
+}
+
+/**
+ * Get a device's undo/redo history for display.
+ */
+export async function getUndoStack(deviceName: string): Promise<UndoStack> {
+  return invoke('get_undo_stack', { deviceName });
+}
@@ -17,6 +17,29 @@ export interface DeviceIdentity {
   description: string;
 }
 
+/**
+ * What an identity request saw - tells "no matching input port" apart from
+ * "the device ignored the inquiry"
+ */
+export interface IdentityDiagnostics {
+  output_port: string | null;
+  input_port: string | null;
+  bytes_sent: number;
+  other_sysex_count: number; // SysEx heard that wasn't an identity reply
+  attempts: number;
+}
+
+export interface DeviceIdentityInfo {
+  identity: DeviceIdentity | null;
+  diagnostics: IdentityDiagnostics;
+}
+
+export interface IdentityRequestOptions {
+  timeoutMs?: number; // Per attempt
+  attempts?: number;
+  backoffMs?: number; // Before the first retry, doubling after that
+}
+
 /**
  * Known manufacturer IDs mapped to pedal types
  * We'll discover these empirically by testing with real devices
@@ -56,11 +79,43 @@ const KNOWN_DEVICES: ManufacturerMapping[] = [
   },
 ];
 
+/** A pedal recognized by `autoDiscoverPedals` */
+export interface AutoDiscoveryResult {
+  device_name: string;
+  pedal_type: PedalType;
+  midi_channel: number;
+  confidence: number; // 1.0 unambiguous; lower when several pedals share an identity
+}
+
+/**
+ * Send an identity request to every MIDI port (500 ms each) and return the
+ * pedals recognized - backs the "Connect All Detected" button.
+ */
+export async function autoDiscoverPedals(): Promise<AutoDiscoveryResult[]> {
+  return invoke<AutoDiscoveryResult[]>('auto_discover_pedals');
+}
+
+/**
+ * Request device identity along with diagnostics about the ports and replies seen.
+ * Retries (defaults on the Rust side) cover pedals that were only just plugged in.
+ */
+export async function requestDeviceIdentityWithDiagnostics(
+  deviceName: string,
+  options: IdentityRequestOptions = {}
+): Promise<DeviceIdentityInfo> {
+  return invoke<DeviceIdentityInfo>('request_midi_device_identity', {
+    deviceName,
+    timeoutMs: options.timeoutMs,
+    attempts: options.attempts,
+    backoffMs: options.backoffMs,
+  });
+}
+
 /**
  * Request device identity from a MIDI device using Universal Device Inquiry
  * 
  * @param deviceName - Name of the MIDI device to query
- * @param timeoutMs - Timeout in milliseconds (default: 2000)
+ * @param timeoutMs - Timeout per attempt in milliseconds (default: 2000)
  * @returns Device identity information or null if no response
  */
 export async function requestDeviceIdentity(
@@ -70,10 +125,7 @@ export async function requestDeviceIdentity(
   try {
     console.log(`🔍 Requesting device identity from: ${deviceName}`);
     
-    const identity = await invoke<DeviceIdentity | null>(
-      'request_midi_device_identity',
-      { deviceName, timeoutMs }
-    );
+    const { identity, diagnostics } = await requestDeviceIdentityWithDiagnostics(deviceName, { timeoutMs });
     
     if (identity) {
       console.log('✅ Received device identity:', identity);
@@ -81,8 +133,10 @@ export async function requestDeviceIdentity(
       console.log(`   ID: [${identity.manufacturer_id.map(b => `0x${b.toString(16).padStart(2, '0')}`).join(', ')}]`);
       console.log(`   Family: 0x${identity.device_family.toString(16).padStart(4, '0')}`);
       console.log(`   Model: 0x${identity.device_model.toString(16).padStart(4, '0')}`);
+    } else if (!diagnostics.output_port || !diagnostics.input_port) {
+      console.log(`⚠️ No matching MIDI port after ${diagnostics.attempts} attempt(s)`, diagnostics);
     } else {
-      console.log('⏱️ No response from device (timeout or device does not support identity request)');
+      console.log('⏱️ No response from device (timeout or device does not support identity request)', diagnostics);
     }
     
     return identity;
@@ -7,6 +7,7 @@
  */
 
 import type { PedalType } from './types';
+import type { BypassMode } from './pedals/chroma_console/types';
 
 interface DeviceProfile {
   interfaceName: string;
@@ -14,6 +15,7 @@ interface DeviceProfile {
   midiChannel?: number;
   nickname?: string;
   notes?: string;
+  chromaBypassMode?: BypassMode;
   createdAt: string;
 }
 
@@ -62,6 +64,34 @@ export function getMidiChannelForDevice(interfaceName: string): number | null {
   return profile?.midiChannel ?? null;
 }
 
+/**
+ * Get the saved Chroma Console footswitch bypass mode for a device interface
+ */
+export function getChromaBypassModeForDevice(interfaceName: string): BypassMode {
+  const profile = getDeviceProfile(interfaceName);
+  return profile?.chromaBypassMode ?? 'Standard';
+}
+
+/**
+ * Save the Chroma Console footswitch bypass mode for a device interface
+ */
+export function saveChromaBypassModeForDevice(interfaceName: string, bypassMode: BypassMode): void {
+  const profiles = loadDeviceProfiles();
+  const existing = profiles.find(p => p.interfaceName === interfaceName);
+  const filtered = profiles.filter(p => p.interfaceName !== interfaceName);
+  
+  filtered.push({
+    ...(existing ?? {
+      interfaceName,
+      pedalType: 'ChromaConsole',
+      createdAt: new Date().toISOString(),
+    }),
+    chromaBypassMode: bypassMode,
+  });
+  
+  saveDeviceProfiles(filtered);
+}
+
 /**
  * Save a device profile mapping
  */
@@ -73,17 +103,19 @@ export function saveDeviceProfile(
   notes?: string
 ): void {
   const profiles = loadDeviceProfiles();
+  const existing = profiles.find(p => p.interfaceName === interfaceName);
   
   // Remove existing profile for this interface if any
   const filtered = profiles.filter(p => p.interfaceName !== interfaceName);
   
-  // Add new profile
+  // Add new profile (device settings made elsewhere are kept)
   filtered.push({
     interfaceName,
     pedalType,
     midiChannel,
     nickname,
     notes,
+    chromaBypassMode: existing?.chromaBypassMode,
     createdAt: new Date().toISOString(),
   });
   
@@ -2,19 +2,35 @@
 // This file provides type-safe access to the Rust MIDI backend
 
 import { invoke } from '@tauri-apps/api/core';
-import type { DeviceInfo, PedalType } from './types';
+import type { DeviceInfo, MidiPortList, PedalType, ChannelMismatchEvent, SafetyWarning, RecallOutcome, SafetyThresholds, ExitActionSettings, ModeNames, ScheduleSpec, ScheduledRecall, ScheduledRecallEvent, RetryOptions, ConnectOutcome, InputChannelMode } from './types';
 
 // ============================================================================
 // Common MIDI Manager API
 // ============================================================================
 
 /**
- * List all available MIDI devices
+ * List all available MIDI devices. Ports that would loop Librarian back into
+ * itself (its own ports, the IAC bus) come back in `virtual_ports` instead.
  */
-export async function listMidiDevices(): Promise<string[]> {
+export async function listMidiDevices(): Promise<MidiPortList> {
   return invoke('list_midi_devices');
 }
 
+/**
+ * Connect a pedal daisy-chained behind another device's MIDI thru.
+ * `deviceName` names the pedal; `portName` is the physical port the chain shares.
+ */
+export async function connectDeviceOnPort(
+  deviceName: string,
+  portName: string,
+  pedalType: PedalType,
+  midiChannel: number,
+  retry: RetryOptions = {},
+  inputMode?: InputChannelMode
+): Promise<ConnectOutcome> {
+  return invoke('connect_device_on_port', { deviceName, portName, pedalType, midiChannel, inputMode, ...retry });
+}
+
 /**
  * Disconnect from a device
  */
@@ -22,6 +38,104 @@ export async function disconnectDevice(deviceName: string): Promise<void> {
   return invoke('disconnect_device', { deviceName });
 }
 
+/**
+ * Disconnect every device; resolves to the names of those disconnected
+ */
+export async function disconnectAllDevices(): Promise<string[]> {
+  return invoke('disconnect_all_devices');
+}
+
+/**
+ * Turn demo mode on or off. On, a fake pedal of every type is listed by
+ * `listMidiDevices` and connects like hardware (no MIDI interface needed).
+ * Also enabled at startup by LIBRARIAN_DEMO_MODE=1.
+ */
+export async function setDemoMode(enabled: boolean): Promise<void> {
+  return invoke('set_demo_mode', { enabled });
+}
+
+/**
+ * Drop (true) or just warn about (false) CCs a device's firmware predates.
+ * Firmware is learned from `requestMidiDeviceIdentity`.
+ */
+export async function setSkipUnsupportedCcs(skip: boolean): Promise<void> {
+  return invoke('set_skip_unsupported_ccs', { skip });
+}
+
+/**
+ * Whether CCs a device's firmware predates are dropped
+ */
+export async function getSkipUnsupportedCcs(): Promise<boolean> {
+  return invoke('get_skip_unsupported_ccs');
+}
+
+/**
+ * Arm devices as they connect (true), or send nothing to a new connection until
+ * `armDevice` is called (false) - for connecting to pedals mid-performance
+ */
+export async function setAutoArmDevices(autoArm: boolean): Promise<void> {
+  return invoke('set_auto_arm_devices', { autoArm });
+}
+
+/**
+ * Whether devices are armed as they connect
+ */
+export async function getAutoArmDevices(): Promise<boolean> {
+  return invoke('get_auto_arm_devices');
+}
+
+/**
+ * Let sends go out to a device connected while auto-arm was off.
+ * Until then, sends to it reject with "Device not armed".
+ */
+export async function armDevice(deviceName: string): Promise<void> {
+  return invoke('arm_device', { deviceName });
+}
+
+/**
+ * Change which incoming channels a connected device listens on, without reconnecting
+ */
+export async function setInputChannelMode(deviceName: string, mode: InputChannelMode): Promise<void> {
+  return invoke('set_input_channel_mode', { deviceName, mode });
+}
+
+/**
+ * Whether demo mode is on
+ */
+export async function getDemoMode(): Promise<boolean> {
+  return invoke('get_demo_mode');
+}
+
+/**
+ * Connect a fake pedal on its own in-memory port
+ */
+export async function connectFakeDevice(
+  deviceName: string,
+  pedalType: PedalType,
+  midiChannel: number
+): Promise<void> {
+  return invoke('connect_fake_device', { deviceName, pedalType, midiChannel });
+}
+
+/** One message written to a fake device */
+export interface FakeLogEntry {
+  bytes: number[];
+  channel: number | null;
+  message:
+    | { kind: 'control_change'; cc_number: number; value: number }
+    | { kind: 'program_change'; program: number }
+    | { kind: 'note_on'; note: number; velocity: number }
+    | { kind: 'note_off'; note: number }
+    | null;
+}
+
+/**
+ * Everything "sent" to a fake device, oldest first (for debugging)
+ */
+export async function getFakeDeviceLog(deviceName: string): Promise<FakeLogEntry[]> {
+  return invoke('get_fake_device_log', { deviceName });
+}
+
 /**
  * List all currently connected devices
  */
@@ -29,6 +143,14 @@ export async function listConnectedDevices(): Promise<DeviceInfo[]> {
   return invoke('list_connected_devices');
 }
 
+/**
+ * Give a connected device a friendlier name than its MIDI port.
+ * An empty label goes back to showing the port name.
+ */
+export async function setDeviceLabel(deviceName: string, label: string): Promise<void> {
+  return invoke('set_device_label', { deviceName, label });
+}
+
 /**
  * Check if a device is currently connected
  */
@@ -36,8 +158,175 @@ export async function isDeviceConnected(deviceName: string): Promise<boolean> {
   return invoke('is_device_connected', { deviceName });
 }
 
+/**
+ * Pedal type a connected device was connected as; rejects if it isn't connected
+ */
+export async function getPedalTypeForDevice(deviceName: string): Promise<PedalType> {
+  return invoke('get_pedal_type_for_device', { deviceName });
+}
+
+// ============================================================================
+// Channel Verification
+// ============================================================================
+
+const VERIFY_CHANNEL_KEY = 'librarian_verify_channel_on_connect';
+
+/**
+ * Whether to verify the MIDI channel after connecting (on by default)
+ */
+export function isChannelVerificationEnabled(): boolean {
+  return localStorage.getItem(VERIFY_CHANNEL_KEY) !== 'false';
+}
+
+/**
+ * Turn post-connect channel verification on or off
+ */
+export function setChannelVerificationEnabled(enabled: boolean): void {
+  localStorage.setItem(VERIFY_CHANNEL_KEY, String(enabled));
+}
+
+/**
+ * Probe a connected device and listen briefly for traffic on its channel.
+ * Emits `channel-mismatch-detected` if the device is heard on another channel.
+ * @returns true if the device was heard on its configured channel
+ */
+export async function verifyDeviceChannel(deviceName: string, timeoutMs?: number): Promise<boolean> {
+  return invoke('verify_device_channel', { deviceName, timeoutMs });
+}
+
+/**
+ * Reconnect a device on a different MIDI channel (one-click fix for a mismatch)
+ */
+export async function setDeviceChannel(deviceName: string, midiChannel: number): Promise<void> {
+  return invoke('set_device_channel', { deviceName, midiChannel });
+}
+
+// ============================================================================
+// Connection Test
+// ============================================================================
+
+export interface ConnectionTestResult {
+  /** Time until the device sent a CC back, or null if it never did */
+  round_trip_ms: number | null;
+  messages_received: number;
+  is_healthy: boolean;
+}
+
+/**
+ * Send CC 0 (Bank Select MSB, harmless on most pedals) and time the device's reply.
+ * A device that stays silent for 2 seconds resolves as unhealthy rather than rejecting.
+ */
+export async function testMidiConnection(deviceName: string): Promise<ConnectionTestResult> {
+  return invoke('test_midi_connection', { deviceName });
+}
+
+// ============================================================================
+// Loud-Parameter Safety
+// ============================================================================
+
+/**
+ * Loud-parameter thresholds used to warn before a recall
+ */
+export async function getSafetyThresholds(): Promise<SafetyThresholds> {
+  return invoke('get_safety_thresholds');
+}
+
+/**
+ * Replace the loud-parameter thresholds (persisted in the app data dir)
+ */
+export async function setSafetyThresholds(thresholds: SafetyThresholds): Promise<void> {
+  return invoke('set_safety_thresholds', { thresholds });
+}
+
+// ============================================================================
+// Exit Actions
+// ============================================================================
+
+/**
+ * What each pedal type does when the app quits
+ */
+export async function getExitActions(): Promise<ExitActionSettings> {
+  return invoke('get_exit_actions');
+}
+
+/**
+ * Replace the exit actions (persisted in the app data dir)
+ */
+export async function setExitActions(settings: ExitActionSettings): Promise<void> {
+  return invoke('set_exit_actions', { settings });
+}
+
+/**
+ * Loud parameters that recalling a library preset onto a device would raise
+ */
+export async function checkPresetSafety(presetId: string, deviceName: string): Promise<SafetyWarning[]> {
+  return invoke('check_preset_safety', { presetId, deviceName });
+}
+
+/**
+ * Recall a library preset on a device later. Beats need the MIDI clock running.
+ * Emits `scheduled-fired`, or `scheduled-missed` if the device is gone by then.
+ * Loud parameter jumps reject the schedule unless `acknowledgeWarnings` is set.
+ */
+export async function scheduleRecall(
+  deviceName: string,
+  presetId: string,
+  at: ScheduleSpec,
+  acknowledgeWarnings: boolean = false
+): Promise<ScheduledRecall> {
+  return invoke('schedule_recall', { deviceName, presetId, at, acknowledgeWarnings });
+}
+
+/**
+ * Cancel a scheduled recall; false if it already fired
+ */
+export async function cancelScheduled(id: number): Promise<boolean> {
+  return invoke('cancel_scheduled', { id });
+}
+
+/**
+ * Recalls waiting to fire, oldest first
+ */
+export async function listScheduled(): Promise<ScheduledRecall[]> {
+  return invoke('list_scheduled');
+}
+
+/**
+ * Arcade button position labels for a pedal type (empty for pedals without them)
+ */
+export async function getPedalModeNames(pedalType: PedalType): Promise<ModeNames[]> {
+  return invoke('get_pedal_mode_names', { pedalType });
+}
+
+/**
+ * State fields whose meaning depends on other settings (e.g. Chroma Console tilt vs filter mode)
+ */
+export async function getContextDependentParameters(pedalType: PedalType): Promise<string[]> {
+  return invoke('get_context_dependent_parameters', { pedalType });
+}
+
+function describeWarnings(warnings: SafetyWarning[]): string {
+  const lines = warnings.map(
+    (w) => `${w.severity === 'danger' ? '⚠️' : '•'} ${w.parameter}: ${String(w.current)} → ${String(w.target)}`
+  );
+  return `This preset will make the pedal louder:\n\n${lines.join('\n')}\n\nRecall anyway?`;
+}
+
+/**
+ * Run a recall, asking the user to confirm if it would raise a loud parameter.
+ * `recall` is called with `acknowledgeWarnings`; returns whether the preset was sent.
+ */
+export async function confirmRecall(
+  recall: (acknowledgeWarnings: boolean) => Promise<RecallOutcome>
+): Promise<boolean> {
+  const outcome = await recall(false);
+  if (outcome.recalled) return true;
+  if (!window.confirm(describeWarnings(outcome.warnings))) return false;
+  return (await recall(true)).recalled;
+}
+
 // ============================================================================
 // Re-export types
 // ============================================================================
 
-export type { DeviceInfo, PedalType };
+export type { DeviceInfo, MidiPortList, PedalType, ChannelMismatchEvent, SafetyWarning, RecallOutcome, SafetyThresholds, ExitActionSettings, ModeNames, ScheduleSpec, ScheduledRecall, ScheduledRecallEvent };
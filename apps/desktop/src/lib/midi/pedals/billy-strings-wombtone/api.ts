@@ -1,12 +1,14 @@
 // Billy Strings Wombtone API - Tauri command wrappers
 import { invoke } from '@tauri-apps/api/core';
 import type { BillyStringsWombtoneParameter, BillyStringsWombtoneState } from './types';
+import type { RecallOutcome, RetryOptions, ConnectOutcome } from '../../types';
 
 export async function connectBillyStringsWombtone(
   deviceName: string,
-  midiChannel: number = 2
-): Promise<void> {
-  return invoke('connect_billy_strings_wombtone', { deviceName, midiChannel });
+  midiChannel: number = 2,
+  retry: RetryOptions = {}
+): Promise<ConnectOutcome> {
+  return invoke('connect_billy_strings_wombtone', { deviceName, midiChannel, ...retry });
 }
 
 export async function sendBillyStringsWombtoneParameter(
@@ -22,9 +24,12 @@ export async function getBillyStringsWombtoneState(deviceName: string): Promise<
 
 export async function recallBillyStringsWombtonePreset(
   deviceName: string,
-  state: BillyStringsWombtoneState
-): Promise<void> {
-  return invoke('recall_billy_strings_wombtone_preset', { deviceName, state });
+  state: BillyStringsWombtoneState,
+  acknowledgeWarnings: boolean = false,
+  glideMs: number = 0,
+  glideCcs?: number[]
+): Promise<RecallOutcome> {
+  return invoke('recall_billy_strings_wombtone_preset', { deviceName, state, acknowledgeWarnings, glideMs, glideCcs });
 }
 
 export async function saveBillyStringsWombtonePreset(
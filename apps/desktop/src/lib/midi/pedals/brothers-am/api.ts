@@ -1,15 +1,17 @@
 // Brothers AM API - Tauri command wrappers
 import { invoke } from '@tauri-apps/api/core';
 import type { BrothersAmParameter, BrothersAmState } from './types';
+import type { RecallOutcome, RetryOptions, ConnectOutcome } from '../../types';
 
 /**
  * Connect to a Brothers AM pedal
  */
 export async function connectBrothersAm(
   deviceName: string,
-  midiChannel: number = 2
-): Promise<void> {
-  return invoke('connect_brothers_am', { deviceName, midiChannel });
+  midiChannel: number = 2,
+  retry: RetryOptions = {}
+): Promise<ConnectOutcome> {
+  return invoke('connect_brothers_am', { deviceName, midiChannel, ...retry });
 }
 
 /**
@@ -31,12 +33,17 @@ export async function getBrothersAmState(deviceName: string): Promise<BrothersAm
 
 /**
  * Recall a preset on the Brothers AM (sends all parameters)
+ * Loud parameter jumps block the recall until acknowledged (see confirmRecall)
+ * A `glideMs` above 0 (at most 2 s) ramps `glideCcs` to their targets over that long
  */
 export async function recallBrothersAmPreset(
   deviceName: string,
-  state: BrothersAmState
-): Promise<void> {
-  return invoke('recall_brothers_am_preset', { deviceName, state });
+  state: BrothersAmState,
+  acknowledgeWarnings: boolean = false,
+  glideMs: number = 0,
+  glideCcs?: number[]
+): Promise<RecallOutcome> {
+  return invoke('recall_brothers_am_preset', { deviceName, state, acknowledgeWarnings, glideMs, glideCcs });
 }
 
 /**
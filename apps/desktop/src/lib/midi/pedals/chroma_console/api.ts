@@ -1,16 +1,18 @@
 // Chroma Console-specific API calls to the Rust backend
 
 import { invoke } from '@tauri-apps/api/core';
-import type { ChromaConsoleState, ChromaConsoleParameter } from './types';
+import type { ChromaConsoleState, ChromaConsoleParameter, BypassMode, CaptureRouting, ParameterSemantics } from './types';
+import type { RecallOutcome, RetryOptions, ConnectOutcome } from '../../types';
 
 /**
  * Connect to a Hologram Chroma Console pedal
  */
 export async function connectChromaConsole(
   deviceName: string,
-  midiChannel: number = 1
-): Promise<void> {
-  return invoke('connect_chroma_console', { deviceName, midiChannel });
+  midiChannel: number = 1,
+  retry: RetryOptions = {}
+): Promise<ConnectOutcome> {
+  return invoke('connect_chroma_console', { deviceName, midiChannel, ...retry });
 }
 
 /**
@@ -40,12 +42,64 @@ export async function getChromaConsoleState(deviceName: string): Promise<ChromaC
   return invoke('get_chroma_console_state', { deviceName });
 }
 
+/**
+ * What Tilt, Rate and Time currently mean, given the filter mode and selected modules
+ */
+export async function getEffectiveParameterInfo(deviceName: string): Promise<ParameterSemantics[]> {
+  return invoke('get_effective_parameter_info', { deviceName });
+}
+
 /**
  * Recall a preset on a Chroma Console (sends all parameters)
+ * Loud parameter jumps block the recall until acknowledged (see confirmRecall)
+ * A `glideMs` above 0 (at most 2 s) ramps `glideCcs` (mix and level controls by default) to their targets over that long
  */
 export async function recallChromaConsolePreset(
   deviceName: string,
-  state: ChromaConsoleState
+  state: ChromaConsoleState,
+  acknowledgeWarnings: boolean = false,
+  glideMs: number = 0,
+  glideCcs?: number[]
+): Promise<RecallOutcome> {
+  return invoke('recall_chroma_console_preset', { deviceName, state, acknowledgeWarnings, glideMs, glideCcs });
+}
+
+/**
+ * Load the current state into a user preset slot (40-79). The pedal has no
+ * MIDI save, so this resolves to the footswitch instructions for saving it.
+ */
+export async function saveChromaConsolePreset(
+  deviceName: string,
+  slot: number
+): Promise<string> {
+  return invoke('save_chroma_console_preset', { deviceName, slot });
+}
+
+/**
+ * Set the Chroma Console's footswitch bypass mode (Standard = CC 91, Dual = CC 92)
+ */
+export async function setChromaConsoleBypassMode(
+  deviceName: string,
+  bypassMode: BypassMode
+): Promise<void> {
+  return invoke('set_chroma_console_bypass_mode', { deviceName, bypassMode });
+}
+
+/**
+ * Record a capture for `durationMs`, then switch it to playback.
+ * Starting another one-shot on the same device cancels the pending switch.
+ */
+export async function chromaCaptureOneShot(
+  deviceName: string,
+  durationMs: number,
+  routing: CaptureRouting = 'PostFx'
 ): Promise<void> {
-  return invoke('recall_chroma_console_preset', { deviceName, state });
+  return invoke('chroma_capture_one_shot', { deviceName, durationMs, routing });
+}
+
+/**
+ * Stop capture, aborting a pending one-shot
+ */
+export async function chromaCaptureStop(deviceName: string): Promise<void> {
+  return invoke('chroma_capture_stop', { deviceName });
 }
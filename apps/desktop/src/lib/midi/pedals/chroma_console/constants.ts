@@ -102,6 +102,9 @@ export const PRESETS_PER_BANK = 20;
 /** Total number of presets */
 export const TOTAL_PRESETS = 80;
 
+/** First user preset - PC 0-39 are factory presets, 40-79 user presets */
+export const USER_PRESET_START = 40;
+
 /**
  * Get preset bank and number from program change (0-79)
  */
@@ -10,13 +10,14 @@ export const chromaConsoleDefinition: PedalDefinition = {
   color: '#06b6d4',
   hasEditor: true,
   defaultMidiChannel: 1,
+  // PC 0-39 are factory presets; bank slots are the user presets (banks C and D)
   bankConfig: {
-    programChangeStart: 0,
+    programChangeStart: 40,
     programChangeEnd: 79,
-    numBanks: 4,
+    numBanks: 2,
     slotsPerBank: 20,
-    bankLabels: ['A', 'B', 'C', 'D'],
-    bankColors: ['red', 'orange', 'green', 'blue'],
+    bankLabels: ['C', 'D'],
+    bankColors: ['green', 'blue'],
     midiSave: {
       type: 'manualOnly',
       instructions: 'Press and hold the footswitch to save the preset to the pedal\'s internal memory',
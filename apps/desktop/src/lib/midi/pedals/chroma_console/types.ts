@@ -40,12 +40,27 @@ export type TextureModule =
 
 export type BypassState = 'Bypass' | 'Engaged' | 'DualBypass';
 
+// Footswitch configuration: Standard sends bypass on CC# 91, Dual on CC# 92
+export type BypassMode = 'Standard' | 'Dual';
+
 export type GestureMode = 'Play' | 'Record';
 
 export type CaptureMode = 'Stop' | 'Play' | 'Record';
 
 export type CaptureRouting = 'PostFx' | 'PreFx';
 
+// Capture (micro-looper) status as tracked by the app - the pedal doesn't echo it
+export type CaptureTransport = 'Empty' | 'Recording' | 'Playing' | 'Stopped';
+
+/**
+ * Emitted as `chroma-capture-started` / `chroma-capture-stopped` around a one-shot capture
+ */
+export interface ChromaCaptureEvent {
+  device_name: string;
+  capture_transport: CaptureTransport;
+  duration_ms: number | null; // Set on start
+}
+
 export type FilterMode = 'Lpf' | 'Tilt' | 'Hpf';
 
 export type CalibrationLevel = 'Low' | 'Medium' | 'High' | 'VeryHigh';
@@ -95,6 +110,9 @@ export interface ChromaConsoleState {
   filter_mode: FilterMode;             // CC# 84
   calibration_level: CalibrationLevel; // CC# 94
   
+  // Capture transport (believed state)
+  capture_transport?: CaptureTransport;
+  
   // Signal path order (app-only metadata, not sent to pedal)
   signal_path: ModuleSlot[];
 }
@@ -142,3 +160,12 @@ export type ChromaConsoleParameter =
   | { FilterMode: FilterMode }
   | { CalibrationLevel: CalibrationLevel }
   | { CalibrationEnter: boolean };
+
+/** What a context-dependent knob (tilt, rate, time) currently does */
+export interface ParameterSemantics {
+  parameter: string; // State field of the knob, e.g. 'tilt'
+  depends_on: string; // State field that decides its meaning, e.g. 'filter_mode'
+  label: string; // e.g. 'LPF cutoff'
+  interpretation: string; // How to read the knob's value in this context
+  active: boolean; // False while the module it controls is off
+}
@@ -1,15 +1,17 @@
 // Clean API - Tauri command wrappers
 import { invoke } from '@tauri-apps/api/core';
 import type { CleanParameter, CleanState } from './types';
+import type { RecallOutcome, RetryOptions, ConnectOutcome } from '../../types';
 
 /**
  * Connect to a Clean pedal
  */
 export async function connectClean(
   deviceName: string,
-  midiChannel: number = 2
-): Promise<void> {
-  return invoke('connect_clean', { deviceName, midiChannel });
+  midiChannel: number = 2,
+  retry: RetryOptions = {}
+): Promise<ConnectOutcome> {
+  return invoke('connect_clean', { deviceName, midiChannel, ...retry });
 }
 
 /**
@@ -31,12 +33,17 @@ export async function getCleanState(deviceName: string): Promise<CleanState> {
 
 /**
  * Recall a preset on the Clean (sends all parameters)
+ * Loud parameter jumps block the recall until acknowledged (see confirmRecall)
+ * A `glideMs` above 0 (at most 2 s) ramps `glideCcs` to their targets over that long
  */
 export async function recallCleanPreset(
   deviceName: string,
-  state: CleanState
-): Promise<void> {
-  return invoke('recall_clean_preset', { deviceName, state });
+  state: CleanState,
+  acknowledgeWarnings: boolean = false,
+  glideMs: number = 0,
+  glideCcs?: number[]
+): Promise<RecallOutcome> {
+  return invoke('recall_clean_preset', { deviceName, state, acknowledgeWarnings, glideMs, glideCcs });
 }
 
 /**
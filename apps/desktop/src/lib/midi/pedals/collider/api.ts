@@ -0,0 +1,40 @@
+// Collider API - Tauri command wrappers
+import { invoke } from '@tauri-apps/api/core';
+import type { ColliderParameter, ColliderState } from './types';
+import type { RecallOutcome, RetryOptions, ConnectOutcome } from '../../types';
+
+export async function connectCollider(
+  deviceName: string,
+  midiChannel: number = 1,
+  retry: RetryOptions = {}
+): Promise<ConnectOutcome> {
+  return invoke('connect_collider', { deviceName, midiChannel, ...retry });
+}
+
+export async function sendColliderParameter(
+  deviceName: string,
+  parameter: ColliderParameter
+): Promise<void> {
+  return invoke('send_collider_parameter', { deviceName, param: parameter });
+}
+
+export async function getColliderState(deviceName: string): Promise<ColliderState> {
+  return invoke('get_collider_state', { deviceName });
+}
+
+export async function recallColliderPreset(
+  deviceName: string,
+  state: ColliderState,
+  acknowledgeWarnings: boolean = false,
+  glideMs: number = 0,
+  glideCcs?: number[]
+): Promise<RecallOutcome> {
+  return invoke('recall_collider_preset', { deviceName, state, acknowledgeWarnings, glideMs, glideCcs });
+}
+
+export async function sendColliderProgramChange(
+  deviceName: string,
+  program: number
+): Promise<void> {
+  return invoke('send_collider_program_change', { deviceName, program });
+}
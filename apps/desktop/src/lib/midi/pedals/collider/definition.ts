@@ -0,0 +1,25 @@
+// Collider pedal definition
+
+import type { PedalDefinition } from '../../pedalRegistry';
+
+export const colliderDefinition: PedalDefinition = {
+  type: 'Collider',
+  name: 'Collider',
+  manufacturer: 'Source Audio',
+  icon: '💥',
+  color: '#3b82f6',
+  hasEditor: true,
+  defaultMidiChannel: 1,
+  bankConfig: {
+    programChangeStart: 0,
+    programChangeEnd: 127,
+    numBanks: 1,
+    slotsPerBank: 128,
+    bankLabels: ['Preset'],
+    bankColors: ['blue'],
+    midiSave: {
+      type: 'manualOnly',
+      instructions: 'Press and hold both footswitches until the preset LED flashes to save to the current preset.',
+    },
+  },
+};
@@ -0,0 +1,10 @@
+// Collider module - re-exports all Collider-specific functionality
+
+import { colliderDefinition } from './definition';
+
+// Not registered: the backend refuses the Collider's CC map until it's confirmed against
+// Source Audio's MIDI documentation, so it isn't offered as a supported pedal yet
+
+export * from './types';
+export * from './api';
+export { colliderDefinition };
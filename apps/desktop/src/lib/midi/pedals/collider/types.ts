@@ -0,0 +1,51 @@
+// Collider types and enums — mirrors tauri/src/midi/pedals/source_audio_collider/types.rs
+
+export type EffectRouting = 'DelayIntoReverb' | 'ReverbIntoDelay' | 'Parallel';
+
+/** Largest delay time; times are 14-bit and sent as NRPN */
+export const MAX_DELAY_TIME = 16383;
+
+export interface DelayEngineState {
+  time: number; // 0-16383
+  repeats: number;
+  mix: number;
+}
+
+export interface ReverbEngineState {
+  size: number;
+  decay: number;
+  mix: number;
+}
+
+export interface ColliderState {
+  delay_a: DelayEngineState;
+  delay_b: DelayEngineState;
+  reverb_a: ReverbEngineState;
+  reverb_b: ReverbEngineState;
+  routing: EffectRouting;
+  bypass: boolean;
+  expression: number;
+}
+
+// All possible Collider parameters (Rust tagged enum serialization)
+export type ColliderParameter =
+  // Delay A
+  | { DelayTimeA: number }
+  | { DelayRepeatsA: number }
+  | { DelayMixA: number }
+  // Delay B
+  | { DelayTimeB: number }
+  | { DelayRepeatsB: number }
+  | { DelayMixB: number }
+  // Reverb A
+  | { ReverbSizeA: number }
+  | { ReverbDecayA: number }
+  | { ReverbMixA: number }
+  // Reverb B
+  | { ReverbSizeB: number }
+  | { ReverbDecayB: number }
+  | { ReverbMixB: number }
+  // Shared
+  | { Routing: EffectRouting }
+  | { Bypass: boolean }
+  | { Expression: number };
@@ -2,15 +2,17 @@
 
 import { invoke } from '@tauri-apps/api/core';
 import type { Cxm1978State, Cxm1978Parameter } from './types';
+import type { RecallOutcome, RetryOptions, ConnectOutcome } from '../../types';
 
 /**
  * Connect to a Chase Bliss / Meris CXM 1978 pedal
  */
 export async function connectCxm1978(
   deviceName: string,
-  midiChannel: number = 2
-): Promise<void> {
-  return invoke('connect_cxm1978', { deviceName, midiChannel });
+  midiChannel: number = 2,
+  retry: RetryOptions = {}
+): Promise<ConnectOutcome> {
+  return invoke('connect_cxm1978', { deviceName, midiChannel, ...retry });
 }
 
 /**
@@ -30,14 +32,27 @@ export async function getCxm1978State(deviceName: string): Promise<Cxm1978State>
   return invoke('get_cxm1978_state', { deviceName });
 }
 
+/**
+ * Approximate reverb tail (RT60, in seconds) for the pedal's current settings.
+ * roomSize runs from 0 (small) to 1 (very large).
+ */
+export async function getCxm1978ReverbEstimate(deviceName: string, roomSize: number): Promise<number> {
+  return invoke('get_cxm1978_reverb_estimate', { deviceName, roomSize });
+}
+
 /**
  * Recall a preset on a CXM 1978 (sends all parameters)
+ * Loud parameter jumps block the recall until acknowledged (see confirmRecall)
+ * A `glideMs` above 0 (at most 2 s) ramps `glideCcs` (mix and level controls by default) to their targets over that long
  */
 export async function recallCxm1978Preset(
   deviceName: string,
-  state: Cxm1978State
-): Promise<void> {
-  return invoke('recall_cxm1978_preset', { deviceName, state });
+  state: Cxm1978State,
+  acknowledgeWarnings: boolean = false,
+  glideMs: number = 0,
+  glideCcs?: number[]
+): Promise<RecallOutcome> {
+  return invoke('recall_cxm1978_preset', { deviceName, state, acknowledgeWarnings, glideMs, glideCcs });
 }
 
 /**
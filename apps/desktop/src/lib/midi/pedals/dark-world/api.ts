@@ -0,0 +1,40 @@
+// Dark World API - Tauri command wrappers
+import { invoke } from '@tauri-apps/api/core';
+import type { DarkWorldParameter, DarkWorldState } from './types';
+import type { RecallOutcome, RetryOptions, ConnectOutcome } from '../../types';
+
+export async function connectDarkWorld(
+  deviceName: string,
+  midiChannel: number = 2,
+  retry: RetryOptions = {}
+): Promise<ConnectOutcome> {
+  return invoke('connect_dark_world', { deviceName, midiChannel, ...retry });
+}
+
+export async function sendDarkWorldParameter(
+  deviceName: string,
+  parameter: DarkWorldParameter
+): Promise<void> {
+  return invoke('send_dark_world_parameter', { deviceName, param: parameter });
+}
+
+export async function getDarkWorldState(deviceName: string): Promise<DarkWorldState> {
+  return invoke('get_dark_world_state', { deviceName });
+}
+
+export async function recallDarkWorldPreset(
+  deviceName: string,
+  state: DarkWorldState,
+  acknowledgeWarnings: boolean = false,
+  glideMs: number = 0,
+  glideCcs?: number[]
+): Promise<RecallOutcome> {
+  return invoke('recall_dark_world_preset', { deviceName, state, acknowledgeWarnings, glideMs, glideCcs });
+}
+
+export async function saveDarkWorldPreset(
+  deviceName: string,
+  slot: number
+): Promise<void> {
+  return invoke('save_dark_world_preset', { deviceName, slot });
+}
@@ -0,0 +1,26 @@
+// Dark World pedal definition
+
+import type { PedalDefinition } from '../../pedalRegistry';
+
+export const darkWorldDefinition: PedalDefinition = {
+  type: 'DarkWorld',
+  name: 'Dark World',
+  manufacturer: 'Chase Bliss Audio',
+  icon: '🌑',
+  color: '#8b5cf6',
+  hasEditor: true,
+  defaultMidiChannel: 2,
+  bankConfig: {
+    programChangeStart: 1,
+    programChangeEnd: 122,
+    numBanks: 1,
+    slotsPerBank: 122,
+    bankLabels: ['Preset'],
+    bankColors: ['purple'],
+    midiSave: {
+      type: 'supported',
+      ccNumber: 111,
+      description: 'CC 111 - Preset Save (value 1-122 selects slot)',
+    },
+  },
+};
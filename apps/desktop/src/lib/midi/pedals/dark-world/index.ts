@@ -0,0 +1,11 @@
+// Dark World module - re-exports all Dark World-specific functionality
+
+import { pedalRegistry } from '../../pedalRegistry';
+import { darkWorldDefinition } from './definition';
+
+// Self-register on module import
+pedalRegistry.register(darkWorldDefinition);
+
+export * from './types';
+export * from './api';
+export { darkWorldDefinition };
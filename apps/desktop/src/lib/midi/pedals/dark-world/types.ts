@@ -0,0 +1,55 @@
+// Dark World types and enums — mirrors tauri/src/midi/pedals/dark_world/types.rs
+
+export type DarkReverbType = 'Mod' | 'Shimmer' | 'Black';
+export type WorldReverbType = 'Hall' | 'Plate' | 'Spring';
+export type EngineRouting = 'Series' | 'Split' | 'Combine';
+
+// Side A - the Dark engine
+export interface SideAState {
+  reverb_type: DarkReverbType;
+  decay: number;
+  mix: number;
+  tone: number;
+  mod_depth: number;
+  engaged: boolean;
+}
+
+// Side B - the World engine
+export interface SideBState {
+  reverb_type: WorldReverbType;
+  decay: number;
+  mix: number;
+  tone: number;
+  mod_depth: number;
+  engaged: boolean;
+}
+
+export interface DarkWorldState {
+  side_a: SideAState;
+  side_b: SideBState;
+  routing: EngineRouting;
+  bypass: boolean;
+  expression: number;
+}
+
+// All possible Dark World parameters (Rust tagged enum serialization)
+export type DarkWorldParameter =
+  // Side A (Dark)
+  | { SideAReverbType: DarkReverbType }
+  | { SideADecay: number }
+  | { SideAMix: number }
+  | { SideATone: number }
+  | { SideAModDepth: number }
+  | { SideAEngaged: boolean }
+  // Side B (World)
+  | { SideBReverbType: WorldReverbType }
+  | { SideBDecay: number }
+  | { SideBMix: number }
+  | { SideBTone: number }
+  | { SideBModDepth: number }
+  | { SideBEngaged: boolean }
+  // Shared
+  | { Routing: EngineRouting }
+  | { Bypass: boolean }
+  | { Expression: number }
+  | { PresetSave: number };
@@ -1,15 +1,17 @@
 // Gen Loss MKII API - Tauri command wrappers
 import { invoke } from '@tauri-apps/api/core';
-import type { GenLossMkiiParameter, GenLossMkiiState } from './types';
+import type { CoherenceWarning, DipProfile, GenLossDipSwitches, GenLossMkiiParameter, GenLossMkiiState } from './types';
+import type { RecallOutcome, RetryOptions, ConnectOutcome } from '../../types';
 
 /**
  * Connect to a Gen Loss MKII pedal
  */
 export async function connectGenLossMkii(
   deviceName: string,
-  midiChannel: number = 2
-): Promise<void> {
-  return invoke('connect_gen_loss_mkii', { deviceName, midiChannel });
+  midiChannel: number = 2,
+  retry: RetryOptions = {}
+): Promise<ConnectOutcome> {
+  return invoke('connect_gen_loss_mkii', { deviceName, midiChannel, ...retry });
 }
 
 /**
@@ -29,14 +31,63 @@ export async function getGenLossState(deviceName: string): Promise<GenLossMkiiSt
   return invoke('get_gen_loss_state', { deviceName });
 }
 
+/**
+ * DIP switch combinations in `state` known to misbehave together (check before saving)
+ */
+export async function validateGenLossState(state: GenLossMkiiState): Promise<CoherenceWarning[]> {
+  return invoke('validate_gen_loss_state', { state });
+}
+
 /**
  * Recall a preset on the Gen Loss MKII (sends all parameters)
+ * Loud parameter jumps block the recall until acknowledged (see confirmRecall).
+ * The pedal keeps its DIP switches unless includeDipSwitches is set.
+ * A `glideMs` above 0 (at most 2 s) ramps `glideCcs` to their targets over that long
  */
 export async function recallGenLossPreset(
   deviceName: string,
-  state: GenLossMkiiState
-): Promise<void> {
-  return invoke('recall_gen_loss_preset', { deviceName, state });
+  state: GenLossMkiiState,
+  acknowledgeWarnings: boolean = false,
+  includeDipSwitches: boolean = false,
+  glideMs: number = 0,
+  glideCcs?: number[]
+): Promise<RecallOutcome> {
+  return invoke('recall_gen_loss_preset', { deviceName, state, acknowledgeWarnings, includeDipSwitches, glideMs, glideCcs });
+}
+
+/**
+ * Saved DIP switch profiles, sorted by name
+ */
+export async function listGenLossDipProfiles(): Promise<DipProfile[]> {
+  return invoke('list_gen_loss_dip_profiles');
+}
+
+/**
+ * One saved DIP switch profile
+ */
+export async function getGenLossDipProfile(name: string): Promise<DipProfile> {
+  return invoke('get_gen_loss_dip_profile', { name });
+}
+
+/**
+ * Save a DIP switch profile, replacing any with the same name
+ */
+export async function saveGenLossDipProfile(name: string, dips: GenLossDipSwitches): Promise<DipProfile> {
+  return invoke('save_gen_loss_dip_profile', { name, dips });
+}
+
+/**
+ * Delete a saved DIP switch profile
+ */
+export async function deleteGenLossDipProfile(name: string): Promise<void> {
+  return invoke('delete_gen_loss_dip_profile', { name });
+}
+
+/**
+ * Set the pedal's 16 DIP switches from a saved profile
+ */
+export async function applyGenLossDipProfile(deviceName: string, name: string): Promise<DipProfile> {
+  return invoke('apply_gen_loss_dip_profile', { deviceName, name });
 }
 
 /**
@@ -48,3 +99,16 @@ export async function saveGenLossPreset(
 ): Promise<void> {
   return invoke('save_gen_loss_preset', { deviceName, slot });
 }
+
+/**
+ * Save current state to a preset slot (1-122) and update the bank grid: with
+ * `presetId` the slot is assigned that preset, otherwise its old assignment is
+ * cleared unless the pedal already held it. The overwrite can be undone from bank history.
+ */
+export async function saveGenLossPresetToSlot(
+  deviceName: string,
+  slot: number,
+  presetId?: string
+): Promise<void> {
+  return invoke('save_gen_loss_preset_to_slot', { deviceName, slot, presetId });
+}
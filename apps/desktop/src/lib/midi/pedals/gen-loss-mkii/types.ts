@@ -114,6 +114,8 @@ export interface GenLossMkiiState {
   input_gain: InputGain;
   dsp_bypass: DspBypassMode;
   ramp_bounce: boolean;
+
+  version: number; // State layout; the backend migrates older presets on load
 }
 
 // All possible Gen Loss MKII parameters (Rust tagged enum serialization)
@@ -166,3 +168,26 @@ export type GenLossMkiiParameter =
   | { DspBypass: DspBypassMode }
   | { PresetSave: number }
   | { RampBounce: boolean };
+
+/** How much a coherence warning matters */
+export type WarningSeverity = 'info' | 'danger';
+
+/** A DIP switch combination known to misbehave, from `validateGenLossState` */
+export interface CoherenceWarning {
+  dips: string[]; // State field names, e.g. 'dip_bounce'
+  description: string;
+  severity: WarningSeverity;
+}
+
+/** The 16 DIP switch positions - rig configuration, kept apart from sound presets */
+export type GenLossDipSwitches = Pick<GenLossMkiiState,
+  | 'dip_wow' | 'dip_flutter' | 'dip_sat_gen' | 'dip_failure_hp'
+  | 'dip_model_lp' | 'dip_bounce' | 'dip_random' | 'dip_sweep'
+  | 'dip_polarity' | 'dip_classic' | 'dip_miso' | 'dip_spread'
+  | 'dip_dry_type' | 'dip_drop_byp' | 'dip_snag_byp' | 'dip_hum_byp'>;
+
+/** A named, saved set of DIP switch positions */
+export interface DipProfile {
+  name: string;
+  dips: GenLossDipSwitches;
+}
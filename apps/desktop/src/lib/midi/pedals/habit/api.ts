@@ -0,0 +1,40 @@
+// Habit API - Tauri command wrappers
+import { invoke } from '@tauri-apps/api/core';
+import type { HabitParameter, HabitState } from './types';
+import type { RecallOutcome, RetryOptions, ConnectOutcome } from '../../types';
+
+export async function connectHabit(
+  deviceName: string,
+  midiChannel: number = 2,
+  retry: RetryOptions = {}
+): Promise<ConnectOutcome> {
+  return invoke('connect_habit', { deviceName, midiChannel, ...retry });
+}
+
+export async function sendHabitParameter(
+  deviceName: string,
+  parameter: HabitParameter
+): Promise<void> {
+  return invoke('send_habit_parameter', { deviceName, param: parameter });
+}
+
+export async function getHabitState(deviceName: string): Promise<HabitState> {
+  return invoke('get_habit_state', { deviceName });
+}
+
+export async function recallHabitPreset(
+  deviceName: string,
+  state: HabitState,
+  acknowledgeWarnings: boolean = false,
+  glideMs: number = 0,
+  glideCcs?: number[]
+): Promise<RecallOutcome> {
+  return invoke('recall_habit_preset', { deviceName, state, acknowledgeWarnings, glideMs, glideCcs });
+}
+
+export async function saveHabitPreset(
+  deviceName: string,
+  slot: number
+): Promise<void> {
+  return invoke('save_habit_preset', { deviceName, slot });
+}
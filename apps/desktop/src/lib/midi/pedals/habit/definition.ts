@@ -0,0 +1,26 @@
+// Habit pedal definition
+
+import type { PedalDefinition } from '../../pedalRegistry';
+
+export const habitDefinition: PedalDefinition = {
+  type: 'Habit',
+  name: 'Habit',
+  manufacturer: 'Chase Bliss Audio',
+  icon: '🔁',
+  color: '#22c55e',
+  hasEditor: true,
+  defaultMidiChannel: 2,
+  bankConfig: {
+    programChangeStart: 1,
+    programChangeEnd: 122,
+    numBanks: 1,
+    slotsPerBank: 122,
+    bankLabels: ['Preset'],
+    bankColors: ['green'],
+    midiSave: {
+      type: 'supported',
+      ccNumber: 111,
+      description: 'CC 111 - Preset Save (value 1-122 selects slot)',
+    },
+  },
+};
@@ -0,0 +1,11 @@
+// Habit module - re-exports all Habit-specific functionality
+
+import { pedalRegistry } from '../../pedalRegistry';
+import { habitDefinition } from './definition';
+
+// Self-register on module import
+pedalRegistry.register(habitDefinition);
+
+export * from './types';
+export * from './api';
+export { habitDefinition };
@@ -0,0 +1,100 @@
+// Habit types and enums — mirrors tauri/src/midi/pedals/habit/types.rs
+
+export type Modifier = 'Stretch' | 'Pitch' | 'Filter';
+export type ScanMode = 'Off' | 'Manual' | 'Auto';
+export type CollectMode = 'In' | 'Feed' | 'Out';
+export type SweepDirection = 'Bottom' | 'Top';
+export type Polarity = 'Forward' | 'Reverse';
+
+export interface HabitState {
+  // Main knobs
+  level: number;
+  repeats: number;
+  size: number;
+  scan: number;
+  spread: number;
+  modify: number;
+  ramp_speed: number;
+
+  // Three-position toggles
+  modifier: Modifier;
+  scan_mode: ScanMode;
+  collect_mode: CollectMode;
+
+  // Footswitches and memory
+  bypass: boolean;
+  memory: boolean;
+  alt_mode: boolean;
+  overdub: boolean;
+  memory_hold: boolean;
+
+  // DIP switches - Left bank
+  dip_level: boolean;
+  dip_repeats: boolean;
+  dip_size: boolean;
+  dip_scan: boolean;
+  dip_spread: boolean;
+  dip_bounce: boolean;
+  dip_sweep: SweepDirection;
+  dip_polarity: Polarity;
+
+  // DIP switches - Right bank
+  dip_miso: boolean;
+  dip_stereo: boolean;
+  dip_latch: boolean;
+  dip_sync: boolean;
+  dip_trails: boolean;
+  dip_no_dub: boolean;
+  dip_feedback_loop: boolean;
+  dip_manual: boolean;
+
+  // Utility
+  midi_clock_ignore: boolean;
+  ramp_bounce: boolean;
+  expression: number;
+}
+
+// All possible Habit parameters (Rust tagged enum serialization)
+export type HabitParameter =
+  // Main knobs
+  | { Level: number }
+  | { Repeats: number }
+  | { Size: number }
+  | { Scan: number }
+  | { Spread: number }
+  | { Modify: number }
+  | { RampSpeed: number }
+  // Toggles
+  | { Modifier: Modifier }
+  | { ScanMode: ScanMode }
+  | { CollectMode: CollectMode }
+  // Footswitches and memory
+  | { Bypass: boolean }
+  | { Memory: boolean }
+  | { AltMode: boolean }
+  | { Overdub: boolean }
+  | { MemoryHold: boolean }
+  | 'ClearMemory'
+  // DIP switches - Left bank
+  | { DipLevel: boolean }
+  | { DipRepeats: boolean }
+  | { DipSize: boolean }
+  | { DipScan: boolean }
+  | { DipSpread: boolean }
+  | { DipBounce: boolean }
+  | { DipSweep: SweepDirection }
+  | { DipPolarity: Polarity }
+  // DIP switches - Right bank
+  | { DipMiso: boolean }
+  | { DipStereo: boolean }
+  | { DipLatch: boolean }
+  | { DipSync: boolean }
+  | { DipTrails: boolean }
+  | { DipNoDub: boolean }
+  | { DipFeedbackLoop: boolean }
+  | { DipManual: boolean }
+  // Utility
+  | { MidiClockIgnore: boolean }
+  | { RampBounce: boolean }
+  | { Expression: number }
+  | { PresetSave: number };
@@ -1,12 +1,14 @@
 // Lossy API - Tauri command wrappers
 import { invoke } from '@tauri-apps/api/core';
 import type { LossyParameter, LossyState } from './types';
+import type { RecallOutcome, RetryOptions, ConnectOutcome } from '../../types';
 
 export async function connectLossy(
   deviceName: string,
-  midiChannel: number = 2
-): Promise<void> {
-  return invoke('connect_lossy', { deviceName, midiChannel });
+  midiChannel: number = 2,
+  retry: RetryOptions = {}
+): Promise<ConnectOutcome> {
+  return invoke('connect_lossy', { deviceName, midiChannel, ...retry });
 }
 
 export async function sendLossyParameter(
@@ -22,9 +24,12 @@ export async function getLossyState(deviceName: string): Promise<LossyState> {
 
 export async function recallLossyPreset(
   deviceName: string,
-  state: LossyState
-): Promise<void> {
-  return invoke('recall_lossy_preset', { deviceName, state });
+  state: LossyState,
+  acknowledgeWarnings: boolean = false,
+  glideMs: number = 0,
+  glideCcs?: number[]
+): Promise<RecallOutcome> {
+  return invoke('recall_lossy_preset', { deviceName, state, acknowledgeWarnings, glideMs, glideCcs });
 }
 
 export async function saveLossyPreset(
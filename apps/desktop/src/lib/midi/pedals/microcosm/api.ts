@@ -1,16 +1,18 @@
 // Microcosm-specific API calls to the Rust backend
 
 import { invoke } from '@tauri-apps/api/core';
-import type { MicrocosmState, MicrocosmParameter } from './types';
+import type { MicrocosmState, MicrocosmParameter, BankDumpSummary } from './types';
+import type { RecallOutcome, RetryOptions, ConnectOutcome } from '../../types';
 
 /**
  * Connect to a Hologram Microcosm pedal
  */
 export async function connectMicrocosm(
   deviceName: string,
-  midiChannel: number = 1
-): Promise<void> {
-  return invoke('connect_microcosm', { deviceName, midiChannel });
+  midiChannel: number = 1,
+  retry: RetryOptions = {}
+): Promise<ConnectOutcome> {
+  return invoke('connect_microcosm', { deviceName, midiChannel, ...retry });
 }
 
 /**
@@ -42,10 +44,33 @@ export async function getMicrocosmState(deviceName: string): Promise<MicrocosmSt
 
 /**
  * Recall a preset on a Microcosm (sends all parameters)
+ * Loud parameter jumps block the recall until acknowledged (see confirmRecall)
+ * A `glideMs` above 0 (at most 2 s) ramps `glideCcs` (mix and level controls by default) to their targets over that long
  */
 export async function recallMicrocosmPreset(
   deviceName: string,
-  state: MicrocosmState
-): Promise<void> {
-  return invoke('recall_microcosm_preset', { deviceName, state });
+  state: MicrocosmState,
+  acknowledgeWarnings: boolean = false,
+  glideMs: number = 0,
+  glideCcs?: number[]
+): Promise<RecallOutcome> {
+  return invoke('recall_microcosm_preset', { deviceName, state, acknowledgeWarnings, glideMs, glideCcs });
+}
+
+/**
+ * Archive all 16 user banks as presets tagged "hardware-dump".
+ * Listen for `microcosm-bank-dump-progress` for per-bank progress.
+ */
+export async function dumpMicrocosmBanks(
+  deviceName: string,
+  settleMs?: number
+): Promise<BankDumpSummary> {
+  return invoke('dump_microcosm_banks', { deviceName, settleMs });
+}
+
+/**
+ * Cancel a running bank dump. Resolves to false if none was running.
+ */
+export async function cancelMicrocosmBankDump(deviceName: string): Promise<boolean> {
+  return invoke('cancel_microcosm_bank_dump', { deviceName });
 }
@@ -22,6 +22,10 @@ export type PlaybackDirection = 'Forward' | 'Reverse';
 
 export type LooperRouting = 'PostFX' | 'PreFX';
 
+// Believed looper status - tracked from the transport triggers we send,
+// the pedal doesn't echo transport over MIDI
+export type LooperTransport = 'Empty' | 'Recording' | 'Playing' | 'Overdubbing' | 'Stopped';
+
 // Effect selection types (matching Rust enums)
 export type EffectCategory = 'MicroLoop' | 'MultiDelay' | 'Granules' | 'MultiPass';
 
@@ -123,4 +127,36 @@ export type MicrocosmParameter =
   | 'LooperErase'
   | 'LooperUndo'
   | 'PresetCopy'
-  | 'PresetSave';
+  | 'PresetSave'
+  | { EffectSelect: [EffectType, EffectVariation] };
+
+/**
+ * A user bank archived by a bank dump
+ */
+export interface ArchivedBank {
+  bank_number: number; // 1-16
+  program: number;
+  preset_id: string;
+  echoed: boolean; // false if the pedal sent nothing back for this bank
+}
+
+/**
+ * Payload of `microcosm-bank-dump-progress`, emitted after each bank
+ */
+export interface BankDumpProgress {
+  device_name: string;
+  bank: ArchivedBank;
+  completed: number;
+  total: number;
+}
+
+/**
+ * Outcome of a bank dump
+ */
+export interface BankDumpSummary {
+  device_name: string;
+  archived: ArchivedBank[];
+  silent_banks: number[]; // snapshots for these banks are unreliable
+  cancelled: boolean;
+  restored_program: number;
+}
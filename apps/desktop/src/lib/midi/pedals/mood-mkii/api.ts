@@ -1,12 +1,14 @@
 // Mood MkII API - Tauri command wrappers
 import { invoke } from '@tauri-apps/api/core';
 import type { MoodMkiiParameter, MoodMkiiState } from './types';
+import type { RecallOutcome, RetryOptions, ConnectOutcome } from '../../types';
 
 export async function connectMoodMkii(
   deviceName: string,
-  midiChannel: number = 2
-): Promise<void> {
-  return invoke('connect_mood_mkii', { deviceName, midiChannel });
+  midiChannel: number = 2,
+  retry: RetryOptions = {}
+): Promise<ConnectOutcome> {
+  return invoke('connect_mood_mkii', { deviceName, midiChannel, ...retry });
 }
 
 export async function sendMoodMkiiParameter(
@@ -22,9 +24,12 @@ export async function getMoodMkiiState(deviceName: string): Promise<MoodMkiiStat
 
 export async function recallMoodMkiiPreset(
   deviceName: string,
-  state: MoodMkiiState
-): Promise<void> {
-  return invoke('recall_mood_mkii_preset', { deviceName, state });
+  state: MoodMkiiState,
+  acknowledgeWarnings: boolean = false,
+  glideMs: number = 0,
+  glideCcs?: number[]
+): Promise<RecallOutcome> {
+  return invoke('recall_mood_mkii_preset', { deviceName, state, acknowledgeWarnings, glideMs, glideCcs });
 }
 
 export async function saveMoodMkiiPreset(
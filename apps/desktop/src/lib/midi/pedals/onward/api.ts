@@ -1,15 +1,17 @@
 // Onward API - Tauri command wrappers
 import { invoke } from '@tauri-apps/api/core';
 import type { OnwardParameter, OnwardState } from './types';
+import type { RecallOutcome, RetryOptions, ConnectOutcome } from '../../types';
 
 /**
  * Connect to an Onward pedal
  */
 export async function connectOnward(
   deviceName: string,
-  midiChannel: number = 2
-): Promise<void> {
-  return invoke('connect_onward', { deviceName, midiChannel });
+  midiChannel: number = 2,
+  retry: RetryOptions = {}
+): Promise<ConnectOutcome> {
+  return invoke('connect_onward', { deviceName, midiChannel, ...retry });
 }
 
 /**
@@ -31,12 +33,17 @@ export async function getOnwardState(deviceName: string): Promise<OnwardState> {
 
 /**
  * Recall a preset on the Onward (sends all parameters)
+ * Loud parameter jumps block the recall until acknowledged (see confirmRecall)
+ * A `glideMs` above 0 (at most 2 s) ramps `glideCcs` to their targets over that long
  */
 export async function recallOnwardPreset(
   deviceName: string,
-  state: OnwardState
-): Promise<void> {
-  return invoke('recall_onward_preset', { deviceName, state });
+  state: OnwardState,
+  acknowledgeWarnings: boolean = false,
+  glideMs: number = 0,
+  glideCcs?: number[]
+): Promise<RecallOutcome> {
+  return invoke('recall_onward_preset', { deviceName, state, acknowledgeWarnings, glideMs, glideCcs });
 }
 
 /**
@@ -2,15 +2,20 @@
 
 import { invoke } from '@tauri-apps/api/core';
 import type { PreampMk2State, PreampMk2Parameter } from './types';
+import type { RecallOutcome, RetryOptions, ConnectOutcome } from '../../types';
 
 /**
- * Connect to a Chase Bliss Preamp MK II pedal
+ * Connect to a Chase Bliss Preamp MK II pedal.
+ * `strictFaderRange` (default true) sends fader values of 0 and 127 as 2 and 125,
+ * the travel the motors can reach; turn it off to send them as asked.
  */
 export async function connectPreampMk2(
   deviceName: string,
-  midiChannel: number = 2
-): Promise<void> {
-  return invoke('connect_preamp_mk2', { deviceName, midiChannel });
+  midiChannel: number = 2,
+  retry: RetryOptions = {},
+  strictFaderRange = true
+): Promise<ConnectOutcome> {
+  return invoke('connect_preamp_mk2', { deviceName, midiChannel, strictFaderRange, ...retry });
 }
 
 /**
@@ -32,12 +37,17 @@ export async function getPreampMk2State(deviceName: string): Promise<PreampMk2St
 
 /**
  * Recall a preset on a Preamp MK II (sends all parameters)
+ * Loud parameter jumps block the recall until acknowledged (see confirmRecall)
+ * A `glideMs` above 0 (at most 2 s) ramps `glideCcs` to their targets over that long
  */
 export async function recallPreampMk2Preset(
   deviceName: string,
-  state: PreampMk2State
-): Promise<void> {
-  return invoke('recall_preamp_mk2_preset', { deviceName, state });
+  state: PreampMk2State,
+  acknowledgeWarnings: boolean = false,
+  glideMs: number = 0,
+  glideCcs?: number[]
+): Promise<RecallOutcome> {
+  return invoke('recall_preamp_mk2_preset', { deviceName, state, acknowledgeWarnings, glideMs, glideCcs });
 }
 
 /**
@@ -60,3 +70,23 @@ export async function savePreampMk2Preset(
 ): Promise<void> {
   return invoke('save_preamp_mk2_preset', { deviceName, slot });
 }
+
+/**
+ * Get the motorized fader positions as a compact display string
+ * e.g. "Vol: 72% | Treble: 45% | Mids: 60% | Freq: 38% | Bass: 55% | Gain: 80%"
+ */
+export async function getPreampMk2FaderDisplay(deviceName: string): Promise<string> {
+  return invoke('get_preamp_mk2_fader_display', { deviceName });
+}
+
+/**
+ * Turn motorized fader movement off or on. The pedal has no MIDI command for this, so
+ * while it's off Librarian holds fader CCs back and sends the latest ones when it's
+ * turned back on.
+ */
+export async function setPreampMk2MotorizedFadersEnabled(
+  deviceName: string,
+  enabled: boolean
+): Promise<void> {
+  return invoke('set_preamp_mk2_motorized_faders_enabled', { deviceName, enabled });
+}
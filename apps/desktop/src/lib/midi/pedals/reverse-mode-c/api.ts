@@ -1,15 +1,17 @@
 // Reverse Mode C API - Tauri command wrappers
 import { invoke } from '@tauri-apps/api/core';
 import type { ReverseModeCParameter, ReverseModeCState } from './types';
+import type { RecallOutcome, RetryOptions, ConnectOutcome } from '../../types';
 
 /**
  * Connect to a Reverse Mode C pedal
  */
 export async function connectReverseModeC(
   deviceName: string,
-  midiChannel: number = 2
-): Promise<void> {
-  return invoke('connect_reverse_mode_c', { deviceName, midiChannel });
+  midiChannel: number = 2,
+  retry: RetryOptions = {}
+): Promise<ConnectOutcome> {
+  return invoke('connect_reverse_mode_c', { deviceName, midiChannel, ...retry });
 }
 
 /**
@@ -31,12 +33,17 @@ export async function getReverseModeCState(deviceName: string): Promise<ReverseM
 
 /**
  * Recall a preset on the Reverse Mode C (sends all parameters)
+ * Loud parameter jumps block the recall until acknowledged (see confirmRecall)
+ * A `glideMs` above 0 (at most 2 s) ramps `glideCcs` to their targets over that long
  */
 export async function recallReverseModeCPreset(
   deviceName: string,
-  state: ReverseModeCState
-): Promise<void> {
-  return invoke('recall_reverse_mode_c_preset', { deviceName, state });
+  state: ReverseModeCState,
+  acknowledgeWarnings: boolean = false,
+  glideMs: number = 0,
+  glideCcs?: number[]
+): Promise<RecallOutcome> {
+  return invoke('recall_reverse_mode_c_preset', { deviceName, state, acknowledgeWarnings, glideMs, glideCcs });
 }
 
 /**
@@ -0,0 +1,40 @@
+// Thermae API - Tauri command wrappers
+import { invoke } from '@tauri-apps/api/core';
+import type { ThermaeParameter, ThermaeState } from './types';
+import type { RecallOutcome, RetryOptions, ConnectOutcome } from '../../types';
+
+export async function connectThermae(
+  deviceName: string,
+  midiChannel: number = 2,
+  retry: RetryOptions = {}
+): Promise<ConnectOutcome> {
+  return invoke('connect_thermae', { deviceName, midiChannel, ...retry });
+}
+
+export async function sendThermaeParameter(
+  deviceName: string,
+  parameter: ThermaeParameter
+): Promise<void> {
+  return invoke('send_thermae_parameter', { deviceName, param: parameter });
+}
+
+export async function getThermaeState(deviceName: string): Promise<ThermaeState> {
+  return invoke('get_thermae_state', { deviceName });
+}
+
+export async function recallThermaePreset(
+  deviceName: string,
+  state: ThermaeState,
+  acknowledgeWarnings: boolean = false,
+  glideMs: number = 0,
+  glideCcs?: number[]
+): Promise<RecallOutcome> {
+  return invoke('recall_thermae_preset', { deviceName, state, acknowledgeWarnings, glideMs, glideCcs });
+}
+
+export async function saveThermaePreset(
+  deviceName: string,
+  slot: number
+): Promise<void> {
+  return invoke('save_thermae_preset', { deviceName, slot });
+}
@@ -0,0 +1,11 @@
+// Thermae module - re-exports all Thermae-specific functionality
+
+import { pedalRegistry } from '../../pedalRegistry';
+import { thermaeDefinition } from './definition';
+
+// Self-register on module import
+pedalRegistry.register(thermaeDefinition);
+
+export * from './types';
+export * from './api';
+export { thermaeDefinition };
@@ -0,0 +1,120 @@
+// Thermae types and enums — mirrors tauri/src/midi/pedals/thermae/types.rs
+
+export type Division = 'Half' | 'Quarter' | 'Eighth';
+export type GlideMode = 'Off' | 'Rise' | 'Fall';
+export type ExtSwitchMode = 'Tap' | 'Hold' | 'Slow';
+export type SweepDirection = 'Bottom' | 'Top';
+export type Polarity = 'Forward' | 'Reverse';
+
+export interface ThermaeState {
+  // Main knobs
+  mix: number;
+  lowpass: number;
+  regen: number;
+  glide: number;
+  interval_1: number;
+  interval_2: number;
+  ramp_speed: number;
+
+  // Three-position toggles
+  division: Division;
+  glide_mode: GlideMode;
+  ext_switch_mode: ExtSwitchMode;
+
+  // Hidden options
+  time: number;
+  mod_rate: number;
+  ramp_range: number;
+
+  // Footswitches
+  bypass: boolean;
+  hold: boolean;
+  slow: boolean;
+
+  // DIP switches - Left bank
+  dip_mix: boolean;
+  dip_lowpass: boolean;
+  dip_regen: boolean;
+  dip_glide: boolean;
+  dip_interval_1: boolean;
+  dip_interval_2: boolean;
+  dip_sweep: SweepDirection;
+  dip_polarity: Polarity;
+
+  // DIP switches - Right bank
+  dip_miso: boolean;
+  dip_spread: boolean;
+  dip_trails: boolean;
+  dip_latch: boolean;
+  dip_sync: boolean;
+  dip_half_speed: boolean;
+  dip_invert: boolean;
+  dip_all_wet: boolean;
+
+  // Advanced
+  ramp_bounce: boolean;
+  expression: number;
+}
+
+// All possible Thermae parameters (Rust tagged enum serialization)
+export type ThermaeParameter =
+  // Main knobs
+  | { Mix: number }
+  | { Lowpass: number }
+  | { Regen: number }
+  | { Glide: number }
+  | { Interval1: number }
+  | { Interval2: number }
+  | { RampSpeed: number }
+  // Toggles
+  | { Division: Division }
+  | { GlideMode: GlideMode }
+  | { ExtSwitchMode: ExtSwitchMode }
+  // Hidden knobs
+  | { Time: number }
+  | { ModRate: number }
+  | { RampRange: number }
+  // Footswitches
+  | { Bypass: boolean }
+  | { Hold: boolean }
+  | { Slow: boolean }
+  // DIP switches - Left bank
+  | { DipMix: boolean }
+  | { DipLowpass: boolean }
+  | { DipRegen: boolean }
+  | { DipGlide: boolean }
+  | { DipInterval1: boolean }
+  | { DipInterval2: boolean }
+  | { DipSweep: SweepDirection }
+  | { DipPolarity: Polarity }
+  // DIP switches - Right bank
+  | { DipMiso: boolean }
+  | { DipSpread: boolean }
+  | { DipTrails: boolean }
+  | { DipLatch: boolean }
+  | { DipSync: boolean }
+  | { DipHalfSpeed: boolean }
+  | { DipInvert: boolean }
+  | { DipAllWet: boolean }
+  // Advanced
+  | { RampBounce: boolean }
+  | { Expression: number }
+  | { PresetSave: number };
+
+// Interval knobs are chromatic: CC 0-127 maps linearly to -24..+24 semitones
+export const INTERVAL_SEMITONE_RANGE = 24;
+
+export function intervalToSemitones(ccValue: number): number {
+  const cc = Math.max(0, Math.min(127, Math.round(ccValue)));
+  return Math.floor((cc * INTERVAL_SEMITONE_RANGE * 2 + 63) / 127) - INTERVAL_SEMITONE_RANGE;
+}
+
+export function semitonesToInterval(semitones: number): number {
+  const st = Math.max(-INTERVAL_SEMITONE_RANGE, Math.min(INTERVAL_SEMITONE_RANGE, Math.round(semitones)));
+  return Math.floor(((st + INTERVAL_SEMITONE_RANGE) * 127 + INTERVAL_SEMITONE_RANGE) / (INTERVAL_SEMITONE_RANGE * 2));
+}
+
+export function formatInterval(ccValue: number): string {
+  const semitones = intervalToSemitones(ccValue);
+  return semitones > 0 ? `+${semitones} st` : `${semitones} st`;
+}
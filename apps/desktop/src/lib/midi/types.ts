@@ -1,9 +1,163 @@
 // Common MIDI types shared across all pedals
 
-export type PedalType = 'Microcosm' | 'GenLossMkii' | 'ChromaConsole' | 'PreampMk2' | 'Cxm1978' | 'Clean' | 'Onward' | 'BrothersAm' | 'ReverseModeC' | 'MoodMkii' | 'BillyStringsWombtone' | 'Lossy';
+export type PedalType = 'Microcosm' | 'GenLossMkii' | 'ChromaConsole' | 'PreampMk2' | 'Cxm1978' | 'Clean' | 'Onward' | 'BrothersAm' | 'ReverseModeC' | 'MoodMkii' | 'BillyStringsWombtone' | 'Lossy' | 'Thermae' | 'DarkWorld' | 'Habit' | 'Collider';
 
 export interface DeviceInfo {
   name: string;
   pedal_type: PedalType;
+  port_name: string; // Physical port, shared by pedals chained over MIDI thru
   midi_channel: number;
+  channel_verified: boolean; // Device has been heard on midi_channel
+  display_label: string; // User-given label, or `name` when unset
+  firmware: string | null; // e.g. '1.1.0', once the pedal has answered an identity request
+  armed: boolean; // False while sends are held back until armDevice
+  input_mode: InputChannelMode;
+}
+
+/**
+ * Which incoming channels a device listens on. `exact` takes only its own channel;
+ * `omni` and `channels` also take channels no other device on the port claims.
+ */
+export type InputChannelMode =
+  | { type: 'exact' }
+  | { type: 'omni' }
+  | { type: 'channels'; channels: number[] }; // 1-16
+
+/** A CC sent to a pedal whose firmware predates it */
+export interface FirmwareWarning {
+  cc_number: number;
+  required: string;
+  installed: string;
+  skipped: boolean; // Dropped rather than sent anyway
+}
+
+/** Arcade button position labels, from `get_pedal_mode_names` */
+export interface ModeNames {
+  parameter: string; // State field the button sets, e.g. 'jump'
+  names: string[]; // One label per position, in CC value order (1, 2, 3)
+}
+
+/** Result of `list_midi_devices` */
+export interface MidiPortList {
+  devices: string[];
+  virtual_ports: string[]; // Librarian's own ports and loopback buses - connecting causes feedback
+  device_info: MidiDeviceInfo[]; // One per `devices` entry, same order
+}
+
+/** What the OS MIDI stack reports about a port's device (USB IDs currently macOS only) */
+export interface MidiDeviceInfo {
+  index: number | null; // midir's port index; null for demo-mode fake ports
+  name: string;
+  is_input: boolean;
+  vendor_id: number | null;
+  product_id: number | null;
+  manufacturer: string | null;
+  is_usb: boolean;
+}
+
+/**
+ * Emitted as `channel-mismatch-detected` when a device is heard on a
+ * different channel than the one it was connected with.
+ */
+export interface ChannelMismatchEvent {
+  device_name: string;
+  configured_channel: number;
+  observed_channel: number;
+}
+
+/** One state field that changed, with its serialized values */
+export interface FieldChange {
+  field: string;
+  old: unknown;
+  new: unknown;
+}
+
+/**
+ * Emitted as `device-state-changed` after a parameter send, recall, program
+ * change or undo/redo. Only fields that actually changed are included.
+ */
+export interface DeviceStateChangedEvent {
+  device_name: string;
+  pedal_type: PedalType;
+  operation_id: string | null;
+  changes: FieldChange[];
+}
+
+/** A loud parameter a preset recall would raise */
+export interface SafetyWarning {
+  parameter: string;
+  current: unknown;
+  target: unknown;
+  severity: 'caution' | 'danger'; // danger: goes from a safe setting to a loud one
+}
+
+/** When a scheduled recall fires: milliseconds from now, or beats on the running MIDI clock */
+export type ScheduleSpec = { millis: number } | { beats: number };
+
+/** A recall waiting to fire, from `scheduleRecall` / `listScheduled` */
+export interface ScheduledRecall {
+  id: number;
+  device_name: string;
+  preset_id: string;
+  preset_name: string;
+  spec: ScheduleSpec;
+  target_tick: number | null; // Clock tick a beat-scheduled recall fires on
+}
+
+/** Payload of `scheduled-fired` and `scheduled-missed` */
+export interface ScheduledRecallEvent extends ScheduledRecall {
+  reason: string | null; // Why a missed recall didn't go out
+}
+
+/**
+ * How connect commands retry a port that isn't there yet or won't open
+ * (defaults: 3 retries, backing off from 250 ms and doubling)
+ */
+export interface RetryOptions {
+  retries?: number;
+  initialBackoffMs?: number;
+}
+
+/** A successful connect: attempts taken and why the earlier ones failed */
+export interface ConnectOutcome {
+  attempts: number;
+  failedAttempts: string[];
+}
+
+/** Payload of `connection-attempt`, emitted for every connect attempt */
+export interface ConnectionAttemptEvent {
+  deviceName: string;
+  attempt: number; // 1-based
+  maxAttempts: number;
+  status: 'connected' | 'retrying' | 'failed';
+  error: string | null;
+  retryInMs: number | null;
+}
+
+/**
+ * Result of a recall command. With unacknowledged warnings nothing is sent
+ * and `recalled` is false; retry with `acknowledgeWarnings` to go ahead.
+ */
+export interface RecallOutcome {
+  recalled: boolean;
+  warnings: SafetyWarning[];
+}
+
+/**
+ * Loud thresholds per pedal type, keyed by state field: a number means
+ * "at or above this value", a list names loud enum settings.
+ */
+export type SafetyThresholds = Record<string, Record<string, number | string[]>>;
+
+/**
+ * What a pedal type does when the app quits
+ */
+export type ExitAction = 'Nothing' | 'Bypass' | { RecallPreset: string };
+
+/**
+ * Exit actions keyed by pedal type. Nothing is sent unless `enabled`.
+ */
+export interface ExitActionSettings {
+  enabled: boolean;
+  actions: Partial<Record<PedalType, ExitAction>>;
 }
@@ -0,0 +1,86 @@
+// Pedalboard API - wrappers for Tauri commands
+import { invoke } from '@tauri-apps/api/core';
+import type { Pedalboard, PedalboardMember, PedalboardActivation, PedalboardScene, SceneRecallReport, UpdatePedalboardParams } from './types';
+
+/**
+ * Create a new pedalboard
+ */
+export async function createPedalboard(name: string, members: PedalboardMember[]): Promise<Pedalboard> {
+  return invoke<Pedalboard>('create_pedalboard', { name, members });
+}
+
+/**
+ * Update a pedalboard's name and/or members
+ */
+export async function updatePedalboard(params: UpdatePedalboardParams): Promise<Pedalboard> {
+  return invoke<Pedalboard>('update_pedalboard', {
+    id: params.id,
+    name: params.name,
+    members: params.members,
+  });
+}
+
+/**
+ * Delete a pedalboard
+ */
+export async function deletePedalboard(id: string): Promise<void> {
+  return invoke<void>('delete_pedalboard', { id });
+}
+
+/**
+ * Get a pedalboard by ID
+ */
+export async function getPedalboard(id: string): Promise<Pedalboard> {
+  return invoke<Pedalboard>('get_pedalboard', { id });
+}
+
+/**
+ * List all pedalboards
+ */
+export async function listPedalboards(): Promise<Pedalboard[]> {
+  return invoke<Pedalboard[]>('list_pedalboards');
+}
+
+/**
+ * Get the active pedalboard, if any
+ */
+export async function getActivePedalboard(): Promise<Pedalboard | null> {
+  return invoke<Pedalboard | null>('get_active_pedalboard');
+}
+
+/**
+ * Connect every pedal on a pedalboard and make it active.
+ * Check each result - pedals that aren't plugged in fail individually.
+ */
+export async function activatePedalboard(id: string): Promise<PedalboardActivation> {
+  return invoke<PedalboardActivation>('activate_pedalboard', { id });
+}
+
+/**
+ * Disconnect the pedals the active pedalboard connected (ones connected before it was
+ * activated stay connected)
+ */
+export async function deactivatePedalboard(): Promise<Pedalboard | null> {
+  return invoke<Pedalboard | null>('deactivate_pedalboard');
+}
+
+/**
+ * Add a scene to a pedalboard, replacing any scene with the same name
+ */
+export async function savePedalboardScene(id: string, scene: PedalboardScene): Promise<Pedalboard> {
+  return invoke<Pedalboard>('save_pedalboard_scene', { id, scene });
+}
+
+/**
+ * Remove a scene from a pedalboard
+ */
+export async function deletePedalboardScene(id: string, name: string): Promise<Pedalboard> {
+  return invoke<Pedalboard>('delete_pedalboard_scene', { id, name });
+}
+
+/**
+ * Recall one of the active pedalboard's scenes. Check each result - pedals fail individually.
+ */
+export async function recallPedalboardScene(name: string): Promise<SceneRecallReport> {
+  return invoke<SceneRecallReport>('recall_pedalboard_scene', { name });
+}
@@ -0,0 +1,3 @@
+// Pedalboard module exports
+export * from './types';
+export * from './api';
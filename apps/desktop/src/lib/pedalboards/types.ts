@@ -0,0 +1,66 @@
+// TypeScript types for pedalboards (groups of pedals with saved port/channel setup)
+
+export interface PedalboardMember {
+  pedalType: string;
+  alias?: string;
+  portMatch: string; // Substring of the MIDI port name
+  midiChannel: number;
+}
+
+export interface Pedalboard {
+  id: string;
+  name: string;
+  members: PedalboardMember[];
+  isActive: boolean;
+  createdAt: number; // Unix timestamp
+  updatedAt: number; // Unix timestamp
+  connectedByActivation: string[]; // Port matches deactivating will disconnect
+  scenes: PedalboardScene[];
+}
+
+/** A named set of presets recalled together across a board's pedals */
+export interface PedalboardScene {
+  name: string;
+  recalls: SceneRecall[];
+}
+
+export interface SceneRecall {
+  portMatch: string; // A member's port match
+  presetId: string;
+}
+
+export interface SceneRecallResult extends SceneRecall {
+  recalled: boolean;
+  error?: string;
+}
+
+/**
+ * Result of recalling a scene - one entry per pedal.
+ * Also the payload of the "pedalboard-scene-recalled" event.
+ */
+export interface SceneRecallReport {
+  pedalboardId: string;
+  scene: string;
+  results: SceneRecallResult[];
+}
+
+export interface MemberConnectResult extends PedalboardMember {
+  connected: boolean;
+  alreadyConnected: boolean; // Left connected when the board is deactivated
+  error?: string;
+}
+
+/**
+ * Result of activating a pedalboard - one entry per member.
+ * Also the payload of the "pedalboard-activated" event.
+ */
+export interface PedalboardActivation {
+  pedalboardId: string;
+  results: MemberConnectResult[];
+}
+
+export interface UpdatePedalboardParams {
+  id: string;
+  name?: string;
+  members?: PedalboardMember[];
+}
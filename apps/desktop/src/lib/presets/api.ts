@@ -1,6 +1,6 @@
 // Preset management API - wrappers for Tauri commands
 import { invoke } from '@tauri-apps/api/core';
-import type { Preset, PresetWithBanks, BankSlot, PresetFilter, SavePresetParams, UpdatePresetParams, SaveToBankResult } from './types';
+import type { Preset, PresetSummary, PresetWithBanks, DeletedPreset, RestoredPreset, BankSlot, BankLayout, PresetFilter, SavePresetParams, UpdatePresetParams, SaveToBankResult, SavePresetAndAssignResult, BankHistoryEntry, AutofillReport, FillStrategy, BulkPresetResult, BulkDeleteResult, DuplicateGroup, MaintenanceReport, PageRequest, PresetListResult } from './types';
 
 /**
  * Save a new preset to the library
@@ -12,6 +12,7 @@ export async function savePreset(params: SavePresetParams): Promise<Preset> {
     description: params.description,
     parameters: params.parameters,
     tags: params.tags,
+    metadata: params.metadata,
   });
 }
 
@@ -26,6 +27,7 @@ export async function updatePreset(params: UpdatePresetParams): Promise<Preset>
     tags: params.tags,
     isFavorite: params.isFavorite,
     parameters: params.parameters,
+    metadata: params.metadata,
   });
 }
 
@@ -45,16 +47,173 @@ export async function listPresets(filter?: PresetFilter): Promise<Preset[]> {
     tags: filter?.tags,
     isFavorite: filter?.isFavorite,
     searchQuery: filter?.searchQuery,
+    createdAfterTs: filter?.createdAfter,
+    createdBeforeTs: filter?.createdBefore,
+    isArchived: filter?.isArchived,
+    metadataKey: filter?.metadataKey,
+    excludeIds: filter?.excludeIds,
   });
 }
 
 /**
- * Delete a preset from the library
+ * One page of presets plus the total match count, for virtualized lists.
+ * Parameters are null unless `includeParameters` - the detail view uses getPreset.
+ */
+export async function listPresetsPage(
+  filter?: PresetFilter,
+  page?: PageRequest,
+  includeParameters: boolean = false
+): Promise<PresetListResult> {
+  return invoke<PresetListResult>('list_presets', {
+    pedalType: filter?.pedalType,
+    tags: filter?.tags,
+    isFavorite: filter?.isFavorite,
+    searchQuery: filter?.searchQuery,
+    createdAfterTs: filter?.createdAfter,
+    createdBeforeTs: filter?.createdBefore,
+    isArchived: filter?.isArchived,
+    metadataKey: filter?.metadataKey,
+    excludeIds: filter?.excludeIds,
+    limit: page?.limit,
+    offset: page?.offset,
+    includeParameters,
+  });
+}
+
+/**
+ * Search preset names, descriptions and tags (every word as a prefix), best match first
+ */
+export async function searchPresetsFulltext(query: string): Promise<Preset[]> {
+  return invoke<Preset[]>('search_presets_fulltext', { query });
+}
+
+/**
+ * List preset metadata only (no parameters) - use when the full state isn't needed
+ */
+export async function listPresetSummaries(filter?: PresetFilter, page?: PageRequest): Promise<PresetSummary[]> {
+  return invoke<PresetSummary[]>('list_preset_summaries', {
+    pedalType: filter?.pedalType,
+    tags: filter?.tags,
+    isFavorite: filter?.isFavorite,
+    searchQuery: filter?.searchQuery,
+    createdAfterTs: filter?.createdAfter,
+    createdBeforeTs: filter?.createdBefore,
+    isArchived: filter?.isArchived,
+    metadataKey: filter?.metadataKey,
+    excludeIds: filter?.excludeIds,
+    limit: page?.limit,
+    offset: page?.offset,
+  });
+}
+
+/**
+ * Move a preset to the trash (restorable until purged).
+ * Archived presets are removed for good instead.
  */
 export async function deletePreset(id: string): Promise<void> {
   return invoke<void>('delete_preset', { id });
 }
 
+/**
+ * Delete several presets in one transaction (same rules as deletePreset).
+ * Unless `partial`, a missing id rejects and nothing is deleted; with it, missing or
+ * already-trashed ids are reported in `notFound`.
+ */
+export async function deletePresets(ids: string[], partial = false): Promise<BulkDeleteResult> {
+  return invoke<BulkDeleteResult>('delete_presets', { ids, partial });
+}
+
+/**
+ * Find groups of presets for a pedal whose parameters are identical
+ * (key order, enum casing and descriptions don't matter)
+ */
+export async function findDuplicatePresets(pedalType: string): Promise<DuplicateGroup[]> {
+  return invoke<DuplicateGroup[]>('find_duplicate_presets', { pedalType });
+}
+
+/**
+ * Merge duplicates into `keepId`: bank slots and tags move over, the rest go to the trash
+ */
+export async function mergePresets(keepId: string, removeIds: string[]): Promise<Preset> {
+  return invoke<Preset>('merge_presets', { keepId, removeIds });
+}
+
+/**
+ * Favorite or unfavorite several presets in one transaction.
+ * Unless `partial`, a missing id rejects and nothing changes.
+ */
+export async function setFavoriteBulk(ids: string[], isFavorite: boolean, partial = false): Promise<BulkPresetResult[]> {
+  return invoke<BulkPresetResult[]>('set_favorite_bulk', { ids, isFavorite, partial });
+}
+
+/**
+ * Archive a preset - hidden from lists, but its data and bank slots are kept
+ */
+export async function archivePreset(id: string): Promise<Preset> {
+  return invoke<Preset>('archive_preset', { id });
+}
+
+/**
+ * Bring an archived preset back into lists
+ */
+export async function unarchivePreset(id: string): Promise<Preset> {
+  return invoke<Preset>('unarchive_preset', { id });
+}
+
+/**
+ * Remove a preset immediately, skipping the trash
+ */
+export async function permanentlyDeletePreset(id: string): Promise<void> {
+  return invoke<void>('permanently_delete_preset', { id });
+}
+
+/**
+ * List presets in the trash, most recently deleted first
+ */
+export async function listDeletedPresets(): Promise<DeletedPreset[]> {
+  return invoke<DeletedPreset[]>('list_deleted_presets');
+}
+
+/**
+ * Restore a preset from the trash.
+ * Its old bank slots are returned (not re-assigned) so the UI can offer to put it back.
+ */
+export async function restorePreset(id: string): Promise<RestoredPreset> {
+  return invoke<RestoredPreset>('restore_preset', { id });
+}
+
+/**
+ * Permanently remove presets that have been in the trash for at least `olderThanDays`
+ * @returns number of presets purged
+ */
+export async function purgeDeleted(olderThanDays: number): Promise<number> {
+  return invoke<number>('purge_deleted', { olderThanDays });
+}
+
+/**
+ * Remove bank assignments whose preset was deleted outside the library
+ * @returns number of bank assignments removed
+ */
+export async function repairDatabase(): Promise<number> {
+  return invoke<number>('repair_database');
+}
+
+/**
+ * Compact the preset database (checkpoint, VACUUM, ANALYZE).
+ * Rejects with "Database is busy" while another library operation is running.
+ */
+export async function runPresetMaintenance(): Promise<MaintenanceReport> {
+  return invoke<MaintenanceReport>('run_preset_maintenance');
+}
+
+/**
+ * Back up the preset database to `destination` while the app keeps using it.
+ * Safe mid-write, unlike copying the file; an existing file at `destination` is replaced.
+ */
+export async function backupPresetDatabase(destination: string): Promise<void> {
+  return invoke('backup_preset_database', { destination });
+}
+
 /**
  * Toggle favorite status of a preset
  */
@@ -62,6 +221,14 @@ export async function toggleFavorite(id: string): Promise<Preset> {
   return invoke<Preset>('toggle_favorite', { id });
 }
 
+/**
+ * Add tags to a batch of presets, keeping their existing tags
+ * @returns number of presets updated
+ */
+export async function bulkTagPresets(ids: string[], tags: string[]): Promise<number> {
+  return invoke<number>('bulk_tag_presets', { ids, tags });
+}
+
 /**
  * Get the state of all pedal banks for a specific pedal type
  */
@@ -70,17 +237,22 @@ export async function getBankState(pedalType: string): Promise<BankSlot[]> {
 }
 
 /**
- * Assign a preset to a specific bank (tracking only, doesn't send to pedal)
+ * Assign a preset to a specific bank (tracking only, doesn't send to pedal).
+ * On pedals where a preset may only sit in one bank (`allowMultiBank: false`), this
+ * rejects if the preset is already in another bank, unless `moveIfAssigned` is set -
+ * then the old slot is cleared.
  */
 export async function assignToBank(
   pedalType: string,
   bankNumber: number,
-  presetId: string
+  presetId: string,
+  moveIfAssigned = false
 ): Promise<void> {
   return invoke<void>('assign_to_bank', {
     pedalType,
     bankNumber,
     presetId,
+    moveIfAssigned,
   });
 }
 
@@ -126,3 +298,127 @@ export async function savePresetToBank(
     bankNumber,
   });
 }
+
+/**
+ * Save a new preset and put it in a bank in one transaction, so the library and the
+ * bank grid never disagree. With `deviceName`, the pedal's save sequence then stores
+ * it on the hardware too; that step failing leaves the library change in place.
+ */
+export async function savePresetAndAssign(
+  params: SavePresetParams,
+  bankNumber: number,
+  deviceName?: string
+): Promise<SavePresetAndAssignResult> {
+  return invoke<SavePresetAndAssignResult>('save_preset_and_assign', {
+    name: params.name,
+    pedalType: params.pedalType,
+    description: params.description,
+    parameters: params.parameters,
+    tags: params.tags,
+    bankNumber,
+    deviceName,
+  });
+}
+
+/**
+ * Get the previous contents of a bank, newest first
+ */
+export async function getBankHistory(
+  pedalType: string,
+  bankNumber: number
+): Promise<BankHistoryEntry[]> {
+  return invoke<BankHistoryEntry[]>('get_bank_history', { pedalType, bankNumber });
+}
+
+/**
+ * Write a bank's previous contents back to the pedal and the library
+ */
+export async function restoreBankFromHistory(
+  entryId: number,
+  deviceName: string
+): Promise<SaveToBankResult> {
+  return invoke<SaveToBankResult>('restore_bank_from_history', { entryId, deviceName });
+}
+
+/**
+ * Fill a pedal's banks from the presets matching `filter`.
+ * With `dryRun` only the plan is returned. When `deviceName` is connected and the pedal
+ * has a MIDI save, each preset is stored on the pedal too; listen for
+ * `bank-autofill-progress` (AutofillProgress) to follow along.
+ */
+export async function autofillBanks(
+  pedalType: string,
+  filter: Omit<PresetFilter, 'pedalType'>,
+  strategy: FillStrategy,
+  options: { deviceName?: string; dryRun?: boolean } = {}
+): Promise<AutofillReport> {
+  return invoke<AutofillReport>('autofill_banks', {
+    pedalType,
+    deviceName: options.deviceName,
+    filter,
+    strategy,
+    dryRun: options.dryRun,
+  });
+}
+
+/**
+ * Find a preset by name and recall it on a device in one call; resolves to the recalled preset.
+ * Rejects if loud parameter jumps would result, unless `acknowledgeWarnings` is set.
+ * A `glideMs` above 0 (at most 2 s) ramps `glideCcs` (mix and level controls by default) to their targets over that long.
+ */
+export async function recallPresetByName(
+  pedalType: string,
+  deviceName: string,
+  presetName: string,
+  acknowledgeWarnings = false,
+  glideMs = 0,
+  glideCcs?: number[]
+): Promise<Preset> {
+  return invoke<Preset>('recall_preset_by_name', {
+    pedalType,
+    deviceName,
+    presetName,
+    acknowledgeWarnings,
+    glideMs,
+    glideCcs,
+  });
+}
+
+/**
+ * Copy one pedal's bank slots to another, matching presets by name
+ * (slots with no same-named preset on the target pedal are skipped)
+ */
+export async function cloneBankLayout(fromPedal: string, toPedal: string): Promise<void> {
+  return invoke<void>('clone_bank_layout', { fromPedal, toPedal });
+}
+
+/**
+ * Export bank assignments across all pedals (for documenting a pedalboard)
+ */
+export async function exportBankLayout(): Promise<BankLayout> {
+  return invoke<BankLayout>('export_bank_layout');
+}
+
+/**
+ * Export bank assignments across all pedals as a Markdown table
+ */
+export async function exportBankLayoutMarkdown(): Promise<string> {
+  return invoke<string>('export_bank_layout_markdown');
+}
+
+/**
+ * Write a preset's recall as a type 0 MIDI file (program change first where the pedal
+ * uses one, then CCs spaced as a live recall sends them) for dropping into a DAW.
+ * Uses the connected pedal's channel, or channel 1, unless `channelOverride` is given.
+ */
+export async function exportPresetAsMidiFile(
+  presetId: string,
+  path: string,
+  channelOverride?: number
+): Promise<void> {
+  return invoke<void>('export_preset_as_midifile', {
+    presetId,
+    path,
+    channelOverride: channelOverride ?? null,
+  });
+}
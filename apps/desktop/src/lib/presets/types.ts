@@ -14,6 +14,8 @@ export interface BankConfig {
   bankLabels: string[];
   bankColors: string[];
   midiSave: MidiSaveCapability;
+  /** False when a preset may only occupy one bank slot at a time */
+  allowMultiBank: boolean;
 }
 
 export interface SaveToBankResult {
@@ -23,6 +25,121 @@ export interface SaveToBankResult {
   instructions?: string;
 }
 
+export interface SavePresetAndAssignResult {
+  preset: Preset;
+  bankSlot: BankSlot;
+  hardwareSave?: SaveToBankResult; // Present when a device name was given
+}
+
+/**
+ * Order matching presets take when a smart bank fill lays them out
+ */
+export type FillOrder =
+  | { type: 'mostRecent' }
+  | { type: 'alphabetical' }
+  | { type: 'manual'; ids: string[] }; // Matches not listed are left out
+
+export interface FillStrategy {
+  order: FillOrder;
+  clearRemainder: boolean; // Clear banks past the last match instead of leaving them
+}
+
+export interface AutofillSlot {
+  bankNumber: number;
+  presetId?: string; // Absent clears the slot
+  presetName?: string;
+}
+
+export interface AutofillPlan {
+  pedalType: string;
+  slots: AutofillSlot[];
+  truncated: number; // Matches left out because the pedal ran out of banks
+}
+
+export type AutofillSlotStatus =
+  | { type: 'assigned' }
+  | { type: 'savedToPedal' }
+  | { type: 'cleared' }
+  | { type: 'failed'; error: string }
+  | { type: 'skipped' }; // An earlier slot failed
+
+export interface AutofillReport {
+  plan: AutofillPlan;
+  dryRun: boolean;
+  results: { bankNumber: number; presetId?: string; status: AutofillSlotStatus }[];
+}
+
+export interface AutofillProgress {
+  pedalType: string;
+  bankNumber: number;
+  index: number;
+  total: number;
+}
+
+/**
+ * A bank's previous contents, captured before a save overwrote it
+ */
+export interface BankHistoryEntry {
+  id: number;
+  pedalType: string;
+  bankNumber: number;
+  deviceName: string;
+  presetId: string;
+  presetName: string;
+  parameters: Record<string, unknown>;
+  overwrittenAt: number; // Unix timestamp
+}
+
+/**
+ * Per-preset outcome of a bulk operation
+ */
+export interface BulkPresetResult {
+  id: string;
+  status: 'applied' | 'notFound';
+}
+
+/**
+ * Outcome of deleting a batch of presets (live ones to the trash, archived ones for good)
+ */
+export interface BulkDeleteResult {
+  deleted: number;
+  notFound: string[];
+  hadBankAssignments: string[]; // Deleted presets whose bank slots are now empty
+}
+
+/**
+ * Result of compacting the preset database (also the `preset-maintenance-complete` payload)
+ */
+export interface MaintenanceReport {
+  sizeBefore: number; // Bytes, database plus write-ahead log
+  sizeAfter: number;
+  durationMs: number;
+}
+
+/**
+ * What changed in the library (the `preset-changed` payload).
+ * `deleted` presets' bank slots are empty; refetch anything showing them.
+ * `merged` presets were folded into `into` and are followed by their `deleted`.
+ */
+export type PresetChangeEvent =
+  | { created: string }
+  | { updated: string }
+  | { deleted: string }
+  | { merged: { from: string; into: string } }
+  | { bankChanged: { pedalType: string; bankNumber: number } };
+
+/**
+ * Presets with identical parameters, oldest first
+ */
+export interface DuplicateGroup {
+  presets: {
+    id: string;
+    name: string;
+    createdAt: number;
+    updatedAt: number;
+  }[];
+}
+
 export interface Preset {
   id: string;
   name: string;
@@ -30,11 +147,19 @@ export interface Preset {
   description?: string;
   parameters: MicrocosmState | any; // Can be any pedal state
   tags: string[];
+  metadata: Record<string, unknown>; // Free-form (tempo, key, artist...), max 8KB
   isFavorite: boolean;
+  isArchived: boolean; // Hidden from lists by default, data kept
   createdAt: number; // Unix timestamp
   updatedAt: number; // Unix timestamp
+  warnings?: string[]; // Problems found on save (e.g. unknown parameter fields) - not stored
 }
 
+/**
+ * Preset metadata without parameters - lighter for list views
+ */
+export type PresetSummary = Omit<Preset, 'parameters'>;
+
 export interface BankSlot {
   bankNumber: number; // 45-60 for Microcosm
   bankLabel: string; // "Bank 1A", "Bank 2C", etc.
@@ -48,6 +173,22 @@ export interface PresetFilter {
   tags?: string[];
   isFavorite?: boolean;
   searchQuery?: string;
+  createdAfter?: number;   // Unix timestamp (seconds), inclusive
+  createdBefore?: number;  // Unix timestamp (seconds), inclusive
+  isArchived?: boolean;    // Omit to hide archived presets
+  metadataKey?: string;    // Only presets whose metadata has this top-level key
+  excludeIds?: string[];   // Left out of the results (e.g. the loaded preset)
+}
+
+export interface PageRequest {
+  limit?: number;   // Page size (backend default when omitted)
+  offset?: number;  // Matches to skip before the page starts
+}
+
+/** One page of a preset listing */
+export interface PresetListResult {
+  presets: Preset[];
+  totalCount: number;  // Matches across all pages
 }
 
 export interface SavePresetParams {
@@ -56,6 +197,7 @@ export interface SavePresetParams {
   description?: string;
   parameters: MicrocosmState | any;
   tags: string[];
+  metadata?: Record<string, unknown>;
 }
 
 export interface UpdatePresetParams {
@@ -65,6 +207,7 @@ export interface UpdatePresetParams {
   tags?: string[];
   isFavorite?: boolean;
   parameters?: any; // Updated pedal state
+  metadata?: Record<string, unknown>; // Replaces the whole object
 }
 
 /**
@@ -74,3 +217,35 @@ export interface UpdatePresetParams {
 export interface PresetWithBanks extends Preset {
   bankNumbers: number[]; // Bank slots this preset is assigned to (45-60)
 }
+
+/**
+ * Preset sitting in the trash (soft-deleted).
+ * Purged automatically 30 days after deletion.
+ */
+export interface DeletedPreset extends Preset {
+  deletedAt: number; // Unix timestamp
+  previousBankNumbers: number[]; // Bank slots it held when deleted
+}
+
+/**
+ * Preset restored from the trash, with the bank slots it can be re-assigned to.
+ */
+export interface RestoredPreset extends Preset {
+  previousBankNumbers: number[];
+}
+
+/**
+ * One assigned bank slot in an exported bank layout
+ */
+export interface BankSlotDetail {
+  bankNumber: number;
+  bankLabel: string;
+  presetId: string;
+  presetName: string;
+  syncedAt?: number; // Unix timestamp
+}
+
+/**
+ * Bank assignments across every pedal, keyed by pedal type (empty array if none assigned)
+ */
+export type BankLayout = Record<string, BankSlotDetail[]>;
@@ -7,12 +7,19 @@
 //!   cargo run --bin sysex-discovery -- "Chroma Console"
 //!   cargo run --bin sysex-discovery -- "Chroma Console" --full-scan
 //!   cargo run --bin sysex-discovery -- "Chroma Console" --custom "F0 00 02 4D 40 F7"
+//!   cargo run --bin sysex-discovery -- "Chroma Console" --decode ChromaConsole
+//!
+//! Decoding:
+//!   --decode <pedal-type> reads each response as (CC#, value) pairs and names them
+//!   using the pedal's known CC map, to help reverse engineer state dumps.
 //!
 //! Safety:
 //!   - Starts with read-only query commands
 //!   - Rate-limited to avoid flooding the device
 //!   - Saves all responses to files for analysis
 
+use librarian_lib::midi::cc_decode::{decode_payload, decode_supported};
+use librarian_lib::midi::PedalType;
 use midir::{MidiInput, MidiOutput};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
@@ -41,12 +48,31 @@ fn main() {
     let full_scan = args.contains(&"--full-scan".to_string());
     let custom = args.iter().position(|a| a == "--custom");
     
+    let decode = match args.iter().position(|a| a == "--decode") {
+        Some(idx) => match args.get(idx + 1).map(|name| (name, PedalType::from_name(name))) {
+            Some((_, Some(pedal_type))) if decode_supported(&pedal_type) => Some(pedal_type),
+            Some((name, _)) => {
+                println!("❌ Can't decode for pedal type: {}", name);
+                println!("   Supported: ChromaConsole, PreampMk2, Cxm1978");
+                return;
+            }
+            None => {
+                print_usage();
+                return;
+            }
+        },
+        None => None,
+    };
+    
     println!("╔═══════════════════════════════════════════════════════════╗");
     println!("║          SysEx Command Discovery Tool v1.0                ║");
     println!("╚═══════════════════════════════════════════════════════════╝");
     println!();
     println!("🎯 Target Device: {}", device_name);
     println!("📂 Results will be saved to: ./sysex-discovery-results/");
+    if let Some(pedal_type) = &decode {
+        println!("🔎 Decoding responses as {:?} CC states", pedal_type);
+    }
     println!();
     
     // Create results directory
@@ -55,7 +81,7 @@ fn main() {
     
     if let Some(idx) = custom {
         if let Some(hex_string) = args.get(idx + 1) {
-            test_custom_command(device_name, hex_string, &results_dir);
+            test_custom_command(device_name, hex_string, &results_dir, decode.as_ref());
             return;
         }
     }
@@ -64,11 +90,11 @@ fn main() {
         println!("⚠️  FULL SCAN MODE - This will test 128 commands");
         println!("⚠️  Press Ctrl+C to abort\n");
         std::thread::sleep(Duration::from_secs(2));
-        full_command_scan(device_name, &results_dir);
+        full_command_scan(device_name, &results_dir, decode.as_ref());
     } else {
         println!("🔍 Quick scan mode (testing common patterns)");
         println!("   Use --full-scan to test all possible commands\n");
-        quick_scan(device_name, &results_dir);
+        quick_scan(device_name, &results_dir, decode.as_ref());
     }
 }
 
@@ -79,14 +105,16 @@ fn print_usage() {
     println!("  sysex-discovery <device-name>                  # Quick scan");
     println!("  sysex-discovery <device-name> --full-scan      # Test all 128 commands");
     println!("  sysex-discovery <device-name> --custom <hex>   # Test custom message");
+    println!("  sysex-discovery <device-name> --decode <type>  # Decode responses as CC states");
     println!();
     println!("Examples:");
     println!("  sysex-discovery \"Chroma Console\"");
     println!("  sysex-discovery \"Chroma Console\" --full-scan");
     println!("  sysex-discovery \"Chroma Console\" --custom \"F0 00 02 4D 40 F7\"");
+    println!("  sysex-discovery \"Chroma Console\" --decode ChromaConsole");
 }
 
-fn quick_scan(device_name: &str, results_dir: &PathBuf) {
+fn quick_scan(device_name: &str, results_dir: &PathBuf, decode: Option<&PedalType>) {
     let patterns = get_common_patterns();
     
     println!("Testing {} common command patterns...\n", patterns.len());
@@ -105,6 +133,9 @@ fn quick_scan(device_name: &str, results_dir: &PathBuf) {
                 println!("   Data: {:02X?}", response);
                 
                 save_response(results_dir, i, pattern, &response);
+                if let Some(pedal_type) = decode {
+                    print_decoded(pedal_type, &response);
+                }
                 successes.push((pattern, response));
             }
             Ok(None) => {
@@ -122,7 +153,7 @@ fn quick_scan(device_name: &str, results_dir: &PathBuf) {
     print_summary(&successes);
 }
 
-fn full_command_scan(device_name: &str, results_dir: &PathBuf) {
+fn full_command_scan(device_name: &str, results_dir: &PathBuf, decode: Option<&PedalType>) {
     println!("Testing all command bytes (0x00 - 0x7F)...\n");
     
     let mut successes = Vec::new();
@@ -153,6 +184,9 @@ fn full_command_scan(device_name: &str, results_dir: &PathBuf) {
                 println!("✅");
                 successes.push((cmd, response.clone()));
                 save_response(results_dir, cmd as usize, &pattern, &response);
+                if let Some(pedal_type) = decode {
+                    print_decoded(pedal_type, &response);
+                }
             }
             Ok(None) => {
                 print!(".");
@@ -178,7 +212,7 @@ fn full_command_scan(device_name: &str, results_dir: &PathBuf) {
     }
 }
 
-fn test_custom_command(device_name: &str, hex_string: &str, results_dir: &PathBuf) {
+fn test_custom_command(device_name: &str, hex_string: &str, results_dir: &PathBuf, decode: Option<&PedalType>) {
     println!("Testing custom command: {}\n", hex_string);
     
     // Parse hex string
@@ -203,6 +237,9 @@ fn test_custom_command(device_name: &str, hex_string: &str, results_dir: &PathBu
                         category: "User-defined",
                     };
                     save_response(results_dir, 999, &pattern, &response);
+                    if let Some(pedal_type) = decode {
+                        print_decoded(pedal_type, &response);
+                    }
                 }
                 Ok(None) => {
                     println!("❌ No response (timeout)");
@@ -330,6 +367,28 @@ fn save_response(results_dir: &PathBuf, test_num: usize, pattern: &CommandPatter
     println!("   💾 Saved to: {}", filepath.display());
 }
 
+/// Print a response as (CC#, value) pairs matched against the pedal's CC map
+fn print_decoded(pedal_type: &PedalType, response: &[u8]) {
+    let decoded = decode_payload(pedal_type, response);
+    
+    println!();
+    println!("   🔎 Decoded as {:?} CC pairs (skipped {} header byte(s), {}/{} known)",
+        pedal_type, decoded.header_len, decoded.known_count(), decoded.entries.len());
+    println!("    CC# | Known Parameter Name         | Value | Decoded Meaning");
+    println!("   {}", "-".repeat(62));
+    
+    for entry in &decoded.entries {
+        println!(
+            "   {:>4} | {:<28} | {:>5} | {}",
+            entry.cc_number,
+            entry.parameter.as_deref().unwrap_or("?"),
+            entry.value,
+            entry.meaning.as_deref().unwrap_or("-"),
+        );
+    }
+    println!();
+}
+
 fn hex_dump(data: &[u8]) -> String {
     let mut result = String::new();
     for (i, chunk) in data.chunks(16).enumerate() {
@@ -0,0 +1,342 @@
+// Command registry - every command registered with the Tauri invoke handler, listed once.
+// Tauri doesn't expose its handler list at runtime, so `app_commands!` expands the one list
+// below into both the `invoke_handler!` lib.rs registers and the names the self-test checks
+// each pedal's wiring against; the two can't drift apart.
+
+use crate::midi::PedalType;
+
+/// Expands the command list into `REGISTERED_COMMANDS`, `pedal_commands` and `invoke_handler!`.
+/// Each pedal lists the four commands the editor and library need first (connect, send a
+/// parameter, read state, recall a preset), then its other commands.
+macro_rules! app_commands {
+    (
+        general: [$($general:ident),* $(,)?],
+        pedals: {
+            $($pedal:ident => [$connect:ident, $send:ident, $get:ident, $recall:ident $(, $extra:ident)* $(,)?]),* $(,)?
+        } $(,)?
+    ) => {
+        /// Every command the invoke handler registers, in registration order
+        pub const REGISTERED_COMMANDS: &[&str] = &[
+            $(stringify!($general),)*
+            $(stringify!($connect), stringify!($send), stringify!($get), stringify!($recall), $(stringify!($extra),)*)*
+        ];
+
+        /// Commands a pedal needs for the editor and library to work with it:
+        /// connect, send a parameter, read state and recall a preset
+        pub fn pedal_commands(pedal_type: &PedalType) -> [&'static str; 4] {
+            match pedal_type {
+                $(PedalType::$pedal => [stringify!($connect), stringify!($send), stringify!($get), stringify!($recall)],)*
+            }
+        }
+
+        /// The Tauri invoke handler for every command above
+        macro_rules! invoke_handler {
+            () => {
+                tauri::generate_handler![
+                    $(crate::commands::$general,)*
+                    $(
+                        crate::commands::$connect,
+                        crate::commands::$send,
+                        crate::commands::$get,
+                        crate::commands::$recall,
+                        $(crate::commands::$extra,)*
+                    )*
+                ]
+            };
+        }
+        pub(crate) use invoke_handler;
+    };
+}
+
+app_commands! {
+    general: [
+        list_midi_devices,
+        connect_device_on_port,
+        disconnect_device,
+        disconnect_all_devices,
+        set_demo_mode,
+        get_demo_mode,
+        connect_fake_device,
+        get_fake_device_log,
+        list_connected_devices,
+        set_device_label,
+        get_skip_unsupported_ccs,
+        set_skip_unsupported_ccs,
+        get_auto_arm_devices,
+        set_auto_arm_devices,
+        arm_device,
+        set_input_channel_mode,
+        get_safety_thresholds,
+        set_safety_thresholds,
+        get_exit_actions,
+        set_exit_actions,
+        check_preset_safety,
+        request_midi_device_identity,
+        auto_discover_pedals,
+        get_library_server_info,
+        set_library_server_config,
+        get_effective_parameter_info,
+        is_device_connected,
+        get_pedal_type_for_device,
+        assign_channel_pc,
+        verify_device_channel,
+        test_midi_connection,
+        set_device_channel,
+        get_device_state,
+        get_all_device_states,
+        record_device_state_snapshot,
+        undo_device_state,
+        store_ab,
+        recall_ab,
+        swap_ab,
+        get_ab_status,
+        send_parameters_batch,
+        send_pedal_parameter,
+        get_pedal_state,
+        recall_pedal_state,
+        estimate_operation,
+        send_midi_note,
+        bypass_all,
+        restore_bypass_states,
+        undo_parameter_change,
+        redo_parameter_change,
+        get_undo_stack,
+        virtual_port_supported,
+        enable_virtual_port,
+        disable_virtual_port,
+        monitor_midi_input,
+        stop_monitoring_midi_input,
+        list_monitored_inputs,
+        create_pc_mapping,
+        list_pc_mappings,
+        delete_pc_mapping,
+        set_pc_remap_bypassed,
+        save_preset,
+        update_preset,
+        get_preset,
+        list_presets,
+        search_presets_fulltext,
+        list_preset_summaries,
+        delete_preset,
+        delete_presets,
+        find_duplicate_presets,
+        merge_presets,
+        set_favorite_bulk,
+        archive_preset,
+        unarchive_preset,
+        permanently_delete_preset,
+        list_deleted_presets,
+        restore_preset,
+        purge_deleted,
+        repair_database,
+        backup_preset_database,
+        run_self_test,
+        run_preset_maintenance,
+        toggle_favorite,
+        bulk_tag_presets,
+        get_bank_state,
+        assign_to_bank,
+        clear_bank,
+        get_presets_with_banks,
+        export_bank_layout,
+        clone_bank_layout,
+        export_bank_layout_markdown,
+        export_preset_as_midifile,
+        get_session_journal_path,
+        export_support_bundle,
+        create_pedalboard,
+        update_pedalboard,
+        delete_pedalboard,
+        get_pedalboard,
+        list_pedalboards,
+        get_active_pedalboard,
+        activate_pedalboard,
+        deactivate_pedalboard,
+        save_pedalboard_scene,
+        delete_pedalboard_scene,
+        recall_pedalboard_scene,
+        save_preset_to_bank,
+        save_preset_and_assign,
+        get_bank_history,
+        restore_bank_from_history,
+        autofill_banks,
+        recall_preset_by_name,
+        schedule_recall,
+        cancel_scheduled,
+        list_scheduled,
+        get_bank_config,
+        get_pedal_mode_names,
+        get_context_dependent_parameters,
+    ],
+    pedals: {
+        Microcosm => [
+            connect_microcosm,
+            send_microcosm_parameter,
+            get_microcosm_state,
+            recall_microcosm_preset,
+            send_microcosm_program_change,
+            dump_microcosm_banks,
+            cancel_microcosm_bank_dump,
+        ],
+        GenLossMkii => [
+            connect_gen_loss_mkii,
+            send_gen_loss_parameter,
+            get_gen_loss_state,
+            recall_gen_loss_preset,
+            validate_gen_loss_state,
+            list_gen_loss_dip_profiles,
+            get_gen_loss_dip_profile,
+            save_gen_loss_dip_profile,
+            delete_gen_loss_dip_profile,
+            apply_gen_loss_dip_profile,
+            save_gen_loss_preset,
+            save_gen_loss_preset_to_slot,
+            send_gen_loss_program_change,
+        ],
+        ChromaConsole => [
+            connect_chroma_console,
+            send_chroma_console_parameter,
+            get_chroma_console_state,
+            recall_chroma_console_preset,
+            save_chroma_console_preset,
+            send_chroma_console_program_change,
+            set_chroma_console_bypass_mode,
+            chroma_capture_one_shot,
+            chroma_capture_stop,
+        ],
+        PreampMk2 => [
+            connect_preamp_mk2,
+            send_preamp_mk2_parameter,
+            get_preamp_mk2_state,
+            recall_preamp_mk2_preset,
+            save_preamp_mk2_preset,
+            send_preamp_mk2_program_change,
+            get_preamp_mk2_fader_display,
+            set_preamp_mk2_motorized_faders_enabled,
+        ],
+        Cxm1978 => [
+            connect_cxm1978,
+            send_cxm1978_parameter,
+            get_cxm1978_state,
+            recall_cxm1978_preset,
+            get_cxm1978_reverb_estimate,
+            save_cxm1978_preset,
+            send_cxm1978_program_change,
+        ],
+        MoodMkii => [
+            connect_mood_mkii,
+            send_mood_mkii_parameter,
+            get_mood_mkii_state,
+            recall_mood_mkii_preset,
+            save_mood_mkii_preset,
+            send_mood_mkii_program_change,
+        ],
+        BillyStringsWombtone => [
+            connect_billy_strings_wombtone,
+            send_billy_strings_wombtone_parameter,
+            get_billy_strings_wombtone_state,
+            recall_billy_strings_wombtone_preset,
+            save_billy_strings_wombtone_preset,
+            send_billy_strings_wombtone_program_change,
+        ],
+        Lossy => [
+            connect_lossy,
+            send_lossy_parameter,
+            get_lossy_state,
+            recall_lossy_preset,
+            save_lossy_preset,
+            send_lossy_program_change,
+        ],
+        BrothersAm => [
+            connect_brothers_am,
+            send_brothers_am_parameter,
+            get_brothers_am_state,
+            recall_brothers_am_preset,
+            save_brothers_am_preset,
+            send_brothers_am_program_change,
+        ],
+        ReverseModeC => [
+            connect_reverse_mode_c,
+            send_reverse_mode_c_parameter,
+            get_reverse_mode_c_state,
+            recall_reverse_mode_c_preset,
+            save_reverse_mode_c_preset,
+            send_reverse_mode_c_program_change,
+        ],
+        Clean => [
+            connect_clean,
+            send_clean_parameter,
+            get_clean_state,
+            recall_clean_preset,
+            save_clean_preset,
+            send_clean_program_change,
+        ],
+        Onward => [
+            connect_onward,
+            send_onward_parameter,
+            get_onward_state,
+            recall_onward_preset,
+            save_onward_preset,
+            send_onward_program_change,
+        ],
+        Thermae => [
+            connect_thermae,
+            send_thermae_parameter,
+            get_thermae_state,
+            recall_thermae_preset,
+            save_thermae_preset,
+            send_thermae_program_change,
+        ],
+        DarkWorld => [
+            connect_dark_world,
+            send_dark_world_parameter,
+            get_dark_world_state,
+            recall_dark_world_preset,
+            save_dark_world_preset,
+            send_dark_world_program_change,
+        ],
+        Habit => [
+            connect_habit,
+            send_habit_parameter,
+            get_habit_state,
+            recall_habit_preset,
+            save_habit_preset,
+            send_habit_program_change,
+        ],
+        Collider => [
+            connect_collider,
+            send_collider_parameter,
+            get_collider_state,
+            recall_collider_preset,
+            send_collider_program_change,
+        ],
+    },
+}
+
+/// Whether `name` is registered with the invoke handler
+pub fn is_registered(name: &str) -> bool {
+    REGISTERED_COMMANDS.contains(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registered_commands_are_unique() {
+        let mut names = REGISTERED_COMMANDS.to_vec();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), REGISTERED_COMMANDS.len());
+    }
+
+    // A pedal block copied from another and only half renamed would share its commands
+    #[test]
+    fn test_pedals_dont_share_commands() {
+        let mut commands: Vec<&str> = PedalType::ALL.iter().flat_map(pedal_commands).collect();
+        let count = commands.len();
+        commands.sort_unstable();
+        commands.dedup();
+        assert_eq!(commands.len(), count);
+    }
+}
@@ -2,7 +2,15 @@
 // Per-pedal commands live in each pedal's commands.rs and are re-exported here.
 // Shared/cross-pedal commands are defined directly in this file.
 
-use crate::midi::{SharedMidiManager, ConnectedDevice, PedalType, request_device_identity, DeviceIdentity};
+use crate::midi::{self, AutoDiscoveryResult, SharedMidiManager, ConnectedDevice, MidiPortList, PedalType, request_device_identity, DeviceIdentity, IdentityDiagnostics, IdentityRequestOptions, IdentityRequestResult, virtual_ports_supported, HistoryEntry, UndoStack, DeviceState, AbSlot, AbStatus, PedalParameter, BypassResult, FakeLogEntry, FirmwareWarning, ScheduleSpec, ScheduledRecall, PresetSends, PedalState, InputChannelMode};
+use crate::midi::exit_actions::ExitActionSettings;
+use crate::midi::safety::{RecallOutcome, SafetyThresholds, SafetyWarning};
+use crate::midi::scheduler::RecallTarget;
+use crate::midi::send_plan::PlanEstimate;
+use crate::midi::connection_test::{self, ConnectionTestResult};
+use crate::midi::connect_retry::{connect_with_retry, ConnectOutcome, RetryOptions};
+use crate::midi::pc_remap::{InputRemap, PcAction, PcMapping};
+use crate::midi::pedals::ModeNames;
 use crate::midi::pedals::microcosm::MicrocosmParameter;
 use crate::midi::pedals::microcosm::MicrocosmState;
 use crate::midi::pedals::chroma_console::ChromaConsoleState;
@@ -15,9 +23,18 @@ use crate::midi::pedals::billy_strings_wombtone::BillyStringsWombtoneState;
 use crate::midi::pedals::lossy::LossyState;
 use crate::midi::pedals::clean::CleanState;
 use crate::midi::pedals::onward::OnwardState;
-use crate::presets::{self, SharedPresetLibrary, Preset, PresetId, PresetFilter, BankSlot, PresetWithBanks, MidiSaveCapability};
+use crate::midi::pedals::thermae::ThermaeState;
+use crate::midi::pedals::dark_world::DarkWorldState;
+use crate::midi::pedals::habit::HabitState;
+use crate::midi::pedals::source_audio_collider::ColliderState;
+use crate::presets::{self, SharedPresetLibrary, Preset, PresetId, PresetFilter, PresetSummary, PresetListResult, BulkPresetResult, BulkDeleteResult, DuplicateGroup, BankSlot, BankLayout, BankHistoryEntry, PresetError, PresetWithBanks, DeletedPreset, RestoredPreset, BankConfig, MidiSaveCapability, MaintenanceReport};
+use crate::pedalboards::{self, SharedPedalboardLibrary, Pedalboard, PedalboardId, PedalboardMember, PedalboardActivation, PedalboardScene, SceneRecallReport, SceneStep};
+use crate::journal::{self, SessionJournal, LibraryStats};
+use crate::library_server::{SharedLibraryServer, LibraryServerConfig, LibraryServerInfo};
+use crate::self_test::{self, SelfTestReport};
 use serde::{Deserialize, Serialize};
-use tauri::State;
+use std::collections::HashMap;
+use tauri::{Emitter, Manager, State};
 
 // Re-export per-pedal commands so lib.rs references (commands::connect_microcosm, etc.)
 // continue to work without modification.
@@ -33,6 +50,10 @@ pub use crate::midi::pedals::billy_strings_wombtone::commands::*;
 pub use crate::midi::pedals::lossy::commands::*;
 pub use crate::midi::pedals::clean::commands::*;
 pub use crate::midi::pedals::onward::commands::*;
+pub use crate::midi::pedals::thermae::commands::*;
+pub use crate::midi::pedals::dark_world::commands::*;
+pub use crate::midi::pedals::habit::commands::*;
+pub use crate::midi::pedals::source_audio_collider::commands::*;
 
 // ===== Shared Device Commands =====
 
@@ -41,41 +62,68 @@ pub use crate::midi::pedals::onward::commands::*;
 pub struct DeviceInfo {
     pub name: String,
     pub pedal_type: String,
+    pub port_name: String,
     pub midi_channel: u8,
+    pub channel_verified: bool,
+    pub display_label: String,
+    /// Installed firmware (e.g. "1.1.0"), once the pedal has answered an identity request
+    pub firmware: Option<String>,
+    /// False while sends are held back (see `arm_device`)
+    pub armed: bool,
+    /// Channels incoming messages are accepted on (see `set_input_channel_mode`)
+    pub input_mode: InputChannelMode,
 }
 
 impl From<ConnectedDevice> for DeviceInfo {
     fn from(device: ConnectedDevice) -> Self {
         Self {
             name: device.device_name,
-            pedal_type: match device.pedal_type {
-                PedalType::Microcosm => "Microcosm".to_string(),
-                PedalType::GenLossMkii => "GenLossMkii".to_string(),
-                PedalType::ChromaConsole => "ChromaConsole".to_string(),
-                PedalType::PreampMk2 => "PreampMk2".to_string(),
-                PedalType::Cxm1978 => "Cxm1978".to_string(),
-                PedalType::MoodMkii => "MoodMkii".to_string(),
-                PedalType::BillyStringsWombtone => "BillyStringsWombtone".to_string(),
-                PedalType::Lossy => "Lossy".to_string(),
-                PedalType::BrothersAm => "BrothersAm".to_string(),
-                PedalType::ReverseModeC => "ReverseModeC".to_string(),
-                PedalType::Clean => "Clean".to_string(),
-                PedalType::Onward => "Onward".to_string(),
-            },
+            pedal_type: device.pedal_type.as_str().to_string(),
+            port_name: device.port_name,
             midi_channel: device.midi_channel,
+            channel_verified: device.channel_verified,
+            display_label: device.display_label,
+            firmware: device.firmware.map(|version| version.to_string()),
+            armed: device.armed,
+            input_mode: device.input_mode,
         }
     }
 }
 
-/// List all available MIDI devices
+/// List all available MIDI devices, with loop/virtual ports in `virtual_ports`
 #[tauri::command]
 pub async fn list_midi_devices(
     manager: State<'_, SharedMidiManager>,
-) -> Result<Vec<String>, String> {
+) -> Result<MidiPortList, String> {
     let manager = manager.lock().map_err(|e| e.to_string())?;
     manager.list_devices().map_err(|e| e.to_string())
 }
 
+/// Connect a pedal reached through another device's MIDI thru: `device_name` names the
+/// pedal, `port_name` is the physical port it shares with the rest of the chain
+/// (retried like the per-pedal connect commands)
+#[tauri::command]
+pub async fn connect_device_on_port(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    port_name: String,
+    pedal_type: String,
+    midi_channel: u8,
+    input_mode: Option<InputChannelMode>,
+    retries: Option<u32>,
+    initial_backoff_ms: Option<u64>,
+) -> Result<ConnectOutcome, String> {
+    let pedal_type = PedalType::from_name(&pedal_type)
+        .ok_or_else(|| format!("Unsupported pedal type: {}", pedal_type))?;
+    let options = RetryOptions::from_params(retries, initial_backoff_ms);
+    connect_with_retry(manager.inner(), &device_name, "connect_device_on_port", options, |manager| {
+        manager.connect_with_input_mode(&device_name, input_mode.as_ref(), |manager| {
+            manager.connect_on_port(pedal_type.clone(), &device_name, &port_name, midi_channel)
+        })
+    })
+    .map_err(|e| e.to_string())
+}
+
 /// Disconnect from a device
 #[tauri::command]
 pub async fn disconnect_device(
@@ -83,9 +131,208 @@ pub async fn disconnect_device(
     device_name: String,
 ) -> Result<(), String> {
     let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("disconnect_device");
     manager.disconnect(&device_name).map_err(|e| e.to_string())
 }
 
+/// Disconnect every device; returns the names of those disconnected
+#[tauri::command]
+pub async fn disconnect_all_devices(
+    manager: State<'_, SharedMidiManager>,
+) -> Result<Vec<String>, String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("disconnect_all_devices");
+    Ok(manager.disconnect_all())
+}
+
+/// Turn demo mode (fake pedals on in-memory ports) on or off
+#[tauri::command]
+pub async fn set_demo_mode(
+    manager: State<'_, SharedMidiManager>,
+    enabled: bool,
+) -> Result<(), String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.set_demo_mode(enabled);
+    Ok(())
+}
+
+/// Whether demo mode is on
+#[tauri::command]
+pub async fn get_demo_mode(
+    manager: State<'_, SharedMidiManager>,
+) -> Result<bool, String> {
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    Ok(manager.demo_mode())
+}
+
+/// Connect a fake pedal on its own in-memory port (no MIDI hardware needed)
+#[tauri::command]
+pub async fn connect_fake_device(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    pedal_type: String,
+    midi_channel: u8,
+) -> Result<(), String> {
+    let pedal_type = PedalType::from_name(&pedal_type)
+        .ok_or_else(|| format!("Unsupported pedal type: {}", pedal_type))?;
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("connect_fake_device");
+    manager
+        .connect_fake(pedal_type, &device_name, midi_channel)
+        .map_err(|e| e.to_string())
+}
+
+/// Everything "sent" to a fake device, oldest first
+#[tauri::command]
+pub async fn get_fake_device_log(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+) -> Result<Vec<FakeLogEntry>, String> {
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    manager
+        .fake_device_log(&device_name)
+        .map_err(|e| e.to_string())
+}
+
+/// Whether CCs a device's firmware predates are dropped instead of sent with a warning
+#[tauri::command]
+pub async fn get_skip_unsupported_ccs(
+    manager: State<'_, SharedMidiManager>,
+) -> Result<bool, String> {
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    Ok(manager.skip_unsupported_ccs())
+}
+
+/// Drop (true) or just warn about (false) CCs a device's firmware predates
+#[tauri::command]
+pub async fn set_skip_unsupported_ccs(
+    manager: State<'_, SharedMidiManager>,
+    skip: bool,
+) -> Result<(), String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.set_skip_unsupported_ccs(skip);
+    Ok(())
+}
+
+/// Whether devices are armed as they connect
+#[tauri::command]
+pub async fn get_auto_arm_devices(
+    manager: State<'_, SharedMidiManager>,
+) -> Result<bool, String> {
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    Ok(manager.auto_arm())
+}
+
+/// Arm devices as they connect (true), or send nothing to a new connection until it's armed (false)
+#[tauri::command]
+pub async fn set_auto_arm_devices(
+    manager: State<'_, SharedMidiManager>,
+    auto_arm: bool,
+) -> Result<(), String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.set_auto_arm(auto_arm);
+    Ok(())
+}
+
+/// Let sends go out to a device connected while auto-arm was off
+#[tauri::command]
+pub async fn arm_device(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+) -> Result<(), String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager
+        .arm_device(&device_name)
+        .map_err(|e| e.to_string())
+}
+
+/// Accept a connected device's incoming messages on its own channel only (`exact`), on
+/// any channel (`omni`), or on a list of channels. Applies without reconnecting.
+#[tauri::command]
+pub async fn set_input_channel_mode(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    mode: InputChannelMode,
+) -> Result<(), String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager
+        .set_input_channel_mode(&device_name, mode)
+        .map_err(|e| e.to_string())
+}
+
+/// Give a connected device a friendlier name than its MIDI port (empty label clears it)
+#[tauri::command]
+pub async fn set_device_label(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    label: String,
+) -> Result<(), String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager
+        .set_device_label(&device_name, &label)
+        .map_err(|e| e.to_string())
+}
+
+/// Loud-parameter thresholds used to warn before a recall
+#[tauri::command]
+pub async fn get_safety_thresholds(
+    manager: State<'_, SharedMidiManager>,
+) -> Result<SafetyThresholds, String> {
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    Ok(manager.safety_thresholds().clone())
+}
+
+/// Replace the loud-parameter thresholds
+#[tauri::command]
+pub async fn set_safety_thresholds(
+    manager: State<'_, SharedMidiManager>,
+    thresholds: SafetyThresholds,
+) -> Result<(), String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager
+        .set_safety_thresholds(thresholds)
+        .map_err(|e| e.to_string())
+}
+
+/// What each pedal type does when the app quits
+#[tauri::command]
+pub async fn get_exit_actions(
+    manager: State<'_, SharedMidiManager>,
+) -> Result<ExitActionSettings, String> {
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    Ok(manager.exit_actions().clone())
+}
+
+/// Replace the exit actions
+#[tauri::command]
+pub async fn set_exit_actions(
+    manager: State<'_, SharedMidiManager>,
+    settings: ExitActionSettings,
+) -> Result<(), String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager
+        .set_exit_actions(settings)
+        .map_err(|e| e.to_string())
+}
+
+/// Loud parameters that recalling a library preset onto a device would raise
+#[tauri::command]
+pub async fn check_preset_safety(
+    library: State<'_, SharedPresetLibrary>,
+    manager: State<'_, SharedMidiManager>,
+    preset_id: String,
+    device_name: String,
+) -> Result<Vec<SafetyWarning>, String> {
+    let preset = {
+        let library = library.lock().map_err(|e| e.to_string())?;
+        library.get_preset(&PresetId::new(preset_id)).map_err(|e| e.to_string())?
+    };
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    manager
+        .recall_safety_warnings(&device_name, &preset.parameters)
+        .map_err(|e| e.to_string())
+}
+
 /// List all connected devices
 #[tauri::command]
 pub async fn list_connected_devices(
@@ -99,9 +346,9 @@ pub async fn list_connected_devices(
         .collect())
 }
 
-/// Device identity information for frontend
+/// Identity reply fields for frontend
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DeviceIdentityInfo {
+pub struct IdentityReplyInfo {
     pub manufacturer_id: Vec<u8>,
     pub manufacturer_name: Option<String>,
     pub device_family: u16,
@@ -110,7 +357,7 @@ pub struct DeviceIdentityInfo {
     pub description: String,
 }
 
-impl From<DeviceIdentity> for DeviceIdentityInfo {
+impl From<DeviceIdentity> for IdentityReplyInfo {
     fn from(identity: DeviceIdentity) -> Self {
         Self {
             manufacturer_id: identity.manufacturer_id.clone(),
@@ -123,24 +370,69 @@ impl From<DeviceIdentity> for DeviceIdentityInfo {
     }
 }
 
+/// Device identity information for frontend.
+/// `identity` is None when nothing answered - `diagnostics` says whether the ports were found.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceIdentityInfo {
+    pub identity: Option<IdentityReplyInfo>,
+    pub diagnostics: IdentityDiagnostics,
+}
+
+impl From<IdentityRequestResult> for DeviceIdentityInfo {
+    fn from(result: IdentityRequestResult) -> Self {
+        Self {
+            identity: result.identity.map(IdentityReplyInfo::from),
+            diagnostics: result.diagnostics,
+        }
+    }
+}
+
+/// Query every MIDI port for its identity and report the pedals recognized
+#[tauri::command]
+pub async fn auto_discover_pedals() -> Result<Vec<AutoDiscoveryResult>, String> {
+    // Each port gets a blocking identity exchange, so keep them off the async runtime
+    tokio::task::spawn_blocking(midi::auto_discover_pedals)
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
 /// Request device identity using MIDI Universal Device Inquiry
 #[tauri::command]
 pub async fn request_midi_device_identity(
+    manager: State<'_, SharedMidiManager>,
     device_name: String,
     timeout_ms: Option<u64>,
-) -> Result<Option<DeviceIdentityInfo>, String> {
-    let timeout = timeout_ms.unwrap_or(2000); // Default 2 second timeout
+    attempts: Option<u32>,
+    backoff_ms: Option<u64>,
+) -> Result<DeviceIdentityInfo, String> {
+    let defaults = IdentityRequestOptions::default();
+    let options = IdentityRequestOptions {
+        timeout_ms: timeout_ms.unwrap_or(defaults.timeout_ms),
+        attempts: attempts.unwrap_or(defaults.attempts),
+        backoff_ms: backoff_ms.unwrap_or(defaults.backoff_ms),
+    };
 
     println!("🔍 Frontend requested device identity for: {}", device_name);
 
-    match request_device_identity(&device_name, timeout) {
-        Ok(Some(identity)) => {
-            println!("✅ Got device identity: {}", identity.description());
-            Ok(Some(DeviceIdentityInfo::from(identity)))
-        }
-        Ok(None) => {
-            println!("⏱️ No response from device (timeout)");
-            Ok(None)
+    // Retries sleep between attempts, so keep them off the async runtime
+    let port_name = device_name.clone();
+    let result = tokio::task::spawn_blocking(move || request_device_identity(&port_name, options))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    match result {
+        Ok(result) => {
+            match &result.identity {
+                Some(identity) => {
+                    println!("✅ Got device identity: {}", identity.description());
+                    // Connected devices on the port pick up the firmware for CC gating
+                    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+                    manager.record_device_identity(&device_name, identity);
+                }
+                None => println!("⏱️ No identity reply: {:?}", result.diagnostics),
+            }
+            Ok(DeviceIdentityInfo::from(result))
         }
         Err(e) => {
             eprintln!("❌ Error requesting device identity: {}", e);
@@ -159,6 +451,509 @@ pub async fn is_device_connected(
     Ok(manager.is_connected(&device_name))
 }
 
+/// Pedal type a connected device was connected as (e.g. "Microcosm"), for restoring
+/// the editor after a restart
+#[tauri::command]
+pub async fn get_pedal_type_for_device(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+) -> Result<String, String> {
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    manager
+        .device_pedal_type(&device_name)
+        .map(|pedal_type| pedal_type.as_str().to_string())
+        .map_err(|e| e.to_string())
+}
+
+/// Check that a connected device is actually talking on its configured channel.
+/// Sends a benign probe, listens briefly without holding the manager lock, and emits
+/// `channel-mismatch-detected` if the device was heard on a different channel.
+#[tauri::command]
+pub async fn verify_device_channel(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    timeout_ms: Option<u64>,
+) -> Result<bool, String> {
+    {
+        let mut manager = manager.lock().map_err(|e| e.to_string())?;
+        manager.begin_journal_operation("verify_device_channel");
+        manager
+            .begin_channel_verification(&device_name)
+            .map_err(|e| e.to_string())?;
+    }
+    
+    let timeout = timeout_ms.unwrap_or(crate::midi::channel_verification::DEFAULT_VERIFY_TIMEOUT_MS);
+    tokio::time::sleep(tokio::time::Duration::from_millis(timeout)).await;
+    
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    manager
+        .finish_channel_verification(&device_name)
+        .map_err(|e| e.to_string())
+}
+
+/// Check the MIDI connection both ways by sending CC 0 (Bank Select MSB) and timing the
+/// device's reply. A device that doesn't answer within 2 seconds is reported unhealthy,
+/// not as an error. The manager lock isn't held while waiting.
+#[tauri::command]
+pub async fn test_midi_connection(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+) -> Result<ConnectionTestResult, String> {
+    let probe = {
+        let mut manager = manager.lock().map_err(|e| e.to_string())?;
+        manager.begin_journal_operation("test_midi_connection");
+        manager
+            .begin_connection_test(&device_name)
+            .map_err(|e| e.to_string())?
+    };
+    
+    loop {
+        if let Some(result) = probe.poll() {
+            return Ok(result);
+        }
+        if probe.expired(connection_test::ECHO_TIMEOUT) {
+            return Ok(probe.timed_out());
+        }
+        tokio::time::sleep(connection_test::POLL_INTERVAL).await;
+    }
+}
+
+/// Reconnect a device on a different MIDI channel (e.g. after a channel mismatch)
+#[tauri::command]
+pub async fn set_device_channel(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    midi_channel: u8,
+) -> Result<(), String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("set_device_channel");
+    manager
+        .set_device_channel(&device_name, midi_channel)
+        .map_err(|e| e.to_string())
+}
+
+/// Get any connected device's state tagged with its pedal type.
+/// `state` is in the same shape save_preset takes as parameters.
+#[tauri::command]
+pub async fn get_device_state(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+) -> Result<DeviceState, String> {
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    manager
+        .get_device_state(&device_name)
+        .map_err(|e| e.to_string())
+}
+
+/// Get the state of every connected device, keyed by device name
+#[tauri::command]
+pub async fn get_all_device_states(
+    manager: State<'_, SharedMidiManager>,
+) -> Result<HashMap<String, DeviceState>, String> {
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    manager
+        .get_all_device_states()
+        .map_err(|e| e.to_string())
+}
+
+/// Save the device's current state so the next change can be undone
+/// Returns how many CC values were captured.
+#[tauri::command]
+pub async fn record_device_state_snapshot(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+) -> Result<usize, String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("record_device_state_snapshot");
+    manager
+        .record_state_snapshot(&device_name)
+        .map(|snapshot| snapshot.cc_map.len())
+        .map_err(|e| e.to_string())
+}
+
+/// Restore the device state saved before the last change
+#[tauri::command]
+pub async fn undo_device_state(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+) -> Result<(), String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("undo_device_state");
+    manager
+        .undo_state_change(&device_name)
+        .map_err(|e| e.to_string())
+}
+
+/// Store the device's current state in compare buffer A or B
+#[tauri::command]
+pub async fn store_ab(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    slot: AbSlot,
+) -> Result<AbStatus, String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.store_ab(&device_name, slot).map_err(|e| e.to_string())
+}
+
+/// Recall compare buffer A or B, sending only the CCs that change.
+/// A `glide_ms` above 0 (at most 2 s) ramps `glide_ccs` (the pedal's mix and level
+/// controls by default) to their targets over that long.
+#[tauri::command]
+pub async fn recall_ab(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    slot: AbSlot,
+    glide_ms: Option<u64>,
+    glide_ccs: Option<Vec<u8>>,
+) -> Result<AbStatus, String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("recall_ab");
+    let glide = manager.device_pedal_type(&device_name).map_err(|e| e.to_string())?.glide(glide_ms, glide_ccs);
+    manager
+        .gliding(&device_name, glide, |manager| manager.recall_ab(&device_name, slot))
+        .map_err(|e| e.to_string())
+}
+
+/// Flip to whichever compare buffer isn't active
+#[tauri::command]
+pub async fn swap_ab(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+) -> Result<AbStatus, String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("swap_ab");
+    manager.swap_ab(&device_name).map_err(|e| e.to_string())
+}
+
+/// Which compare buffers are stored and which matches the current state
+#[tauri::command]
+pub async fn get_ab_status(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+) -> Result<AbStatus, String> {
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.get_ab_status(&device_name).map_err(|e| e.to_string())
+}
+
+/// Send several parameters to one device as a single batch (macro controls)
+/// All parameters must be for the device's pedal type. If the send fails part-way the
+/// error names the parameters that already went out. `user_initiated` sends even if the
+/// device isn't armed yet.
+#[tauri::command]
+pub async fn send_parameters_batch(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    params: Vec<PedalParameter>,
+    user_initiated: Option<bool>,
+) -> Result<Vec<FirmwareWarning>, String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("send_parameters_batch");
+    let send = |manager: &mut midi::MidiManager| manager.send_parameters_batch(&device_name, params);
+    let result = if user_initiated.unwrap_or(false) {
+        manager.user_initiated(&device_name, send)
+    } else {
+        send(&mut manager)
+    };
+    result.map_err(|e| e.to_string())?;
+    Ok(manager.take_firmware_warnings(&device_name))
+}
+
+/// Send one parameter to any device; rejected if it's for a different pedal type.
+/// Resolves to any CCs the device's firmware predates.
+#[tauri::command]
+pub async fn send_pedal_parameter(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    param: PedalParameter,
+) -> Result<Vec<FirmwareWarning>, String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("send_pedal_parameter");
+    manager.send_parameter(&device_name, param).map_err(|e| e.to_string())?;
+    Ok(manager.take_firmware_warnings(&device_name))
+}
+
+/// Get any connected device's state, tagged with its pedal type
+#[tauri::command]
+pub async fn get_pedal_state(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+) -> Result<PedalState, String> {
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.get_state(&device_name).map_err(|e| e.to_string())
+}
+
+/// Recall any pedal's state on a device of the same type
+/// Loud parameter jumps block the recall until `acknowledge_warnings` is set.
+/// A `glide_ms` above 0 (at most 2 s) ramps `glide_ccs` (the pedal's mix and level
+/// controls by default) to their targets over that long.
+#[tauri::command]
+pub async fn recall_pedal_state(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    state: PedalState,
+    acknowledge_warnings: bool,
+    glide_ms: Option<u64>,
+    glide_ccs: Option<Vec<u8>>,
+) -> Result<RecallOutcome, String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    let target = state.to_json().map_err(|e| e.to_string())?;
+    let warnings = manager
+        .recall_safety_warnings(&device_name, &target)
+        .map_err(|e| e.to_string())?;
+    if !warnings.is_empty() && !acknowledge_warnings {
+        return Ok(RecallOutcome::blocked(warnings));
+    }
+    manager.begin_journal_operation("recall_pedal_state");
+    let glide = state.pedal_type().glide(glide_ms, glide_ccs);
+    manager
+        .gliding(&device_name, glide, |manager| manager.recall_state(&device_name, state))
+        .map_err(|e| e.to_string())?;
+    Ok(RecallOutcome::recalled(warnings))
+}
+
+/// What a recall would recall: a library preset, or a state like `recall_pedal_state` takes
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum RecallSource {
+    Preset { preset_id: String },
+    State { state: PedalState },
+}
+
+/// A recall to estimate, with the options it would be sent with
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecallOperation {
+    pub source: RecallSource,
+    /// Only send CCs that differ from the device's current state
+    #[serde(default)]
+    pub diff_only: bool,
+    pub glide_ms: Option<u64>,
+    pub glide_ccs: Option<Vec<u8>>,
+}
+
+/// How many messages a recall would send and how long it would take, step by step,
+/// without sending anything. `send-progress` events during the recall count the same steps.
+#[tauri::command]
+pub async fn estimate_operation(
+    library: State<'_, SharedPresetLibrary>,
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    operation: RecallOperation,
+) -> Result<PlanEstimate, String> {
+    let (state, include_config) = match operation.source {
+        RecallSource::Preset { preset_id } => {
+            let preset = {
+                let library = library.lock().map_err(|e| e.to_string())?;
+                library.get_preset(&PresetId::new(preset_id)).map_err(|e| e.to_string())?
+            };
+            let pedal_type = PedalType::from_name(&preset.pedal_type)
+                .ok_or_else(|| format!("Unknown pedal type: {}", preset.pedal_type))?;
+            (PedalState::from_json(&pedal_type, preset.parameters).map_err(|e| e.to_string())?, false)
+        }
+        RecallSource::State { state } => (state, true),
+    };
+    let glide = state.pedal_type().glide(operation.glide_ms, operation.glide_ccs);
+
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager
+        .estimate_recall(&device_name, state, include_config, operation.diff_only, glide.as_ref())
+        .map_err(|e| e.to_string())
+}
+
+/// Play a note on a device: Note On, hold for `duration_ms`, then Note Off. A duration
+/// past `MAX_NOTE_DURATION` is refused. The manager isn't locked while the note is held.
+/// `user_initiated` sends even if the device isn't armed yet.
+#[tauri::command]
+pub async fn send_midi_note(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    note: u8,
+    velocity: u8,
+    duration_ms: u64,
+    user_initiated: Option<bool>,
+) -> Result<(), String> {
+    let duration = midi::manager::note_duration(duration_ms).map_err(|e| e.to_string())?;
+    {
+        let mut manager = manager.lock().map_err(|e| e.to_string())?;
+        manager.begin_journal_operation("send_midi_note");
+        let start = |manager: &mut midi::MidiManager| manager.start_midi_note(&device_name, note, velocity);
+        let result = if user_initiated.unwrap_or(false) {
+            manager.user_initiated(&device_name, start)
+        } else {
+            start(&mut manager)
+        };
+        result.map_err(|e| e.to_string())?;
+    }
+
+    tokio::time::sleep(duration).await;
+
+    // Once the note is on, its Note Off goes out whatever happened to the lock meanwhile
+    let mut manager = manager.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+    manager.end_midi_note(&device_name, note).map_err(|e| e.to_string())
+}
+
+/// Bypass every connected device, remembering their bypass states for `restore_bypass_states`
+/// Each device's outcome is also emitted as a `bypass-result` event.
+#[tauri::command]
+pub async fn bypass_all(
+    manager: State<'_, SharedMidiManager>,
+) -> Result<Vec<BypassResult>, String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("bypass_all");
+    Ok(manager.bypass_all())
+}
+
+/// Put back the bypass states recorded by the last `bypass_all`
+#[tauri::command]
+pub async fn restore_bypass_states(
+    manager: State<'_, SharedMidiManager>,
+) -> Result<Vec<BypassResult>, String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("restore_bypass_states");
+    Ok(manager.restore_bypass_states())
+}
+
+/// Undo the last parameter change, recall or program change on a device
+/// Returns the undone entry, or null if there was nothing to undo.
+#[tauri::command]
+pub async fn undo_parameter_change(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+) -> Result<Option<HistoryEntry>, String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("undo_parameter_change");
+    manager
+        .undo_parameter_change(&device_name)
+        .map_err(|e| e.to_string())
+}
+
+/// Redo the last undone change on a device
+#[tauri::command]
+pub async fn redo_parameter_change(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+) -> Result<Option<HistoryEntry>, String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("redo_parameter_change");
+    manager
+        .redo_parameter_change(&device_name)
+        .map_err(|e| e.to_string())
+}
+
+/// Get a device's undo/redo history
+#[tauri::command]
+pub async fn get_undo_stack(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+) -> Result<UndoStack, String> {
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    manager
+        .get_undo_stack(&device_name)
+        .map_err(|e| e.to_string())
+}
+
+/// Whether this platform supports the virtual "Librarian" MIDI port
+#[tauri::command]
+pub async fn virtual_port_supported() -> Result<bool, String> {
+    Ok(virtual_ports_supported())
+}
+
+/// Expose Librarian as a virtual MIDI device (answers Device Inquiry, forwards CC/PC as events)
+#[tauri::command]
+pub async fn enable_virtual_port(
+    manager: State<'_, SharedMidiManager>,
+    name: Option<String>,
+) -> Result<(), String> {
+    let name = name.unwrap_or_else(|| crate::midi::virtual_port::DEFAULT_VIRTUAL_PORT_NAME.to_string());
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("enable_virtual_port");
+    manager.enable_virtual_port(&name).map_err(|e| e.to_string())
+}
+
+/// Close the virtual MIDI port
+#[tauri::command]
+pub async fn disable_virtual_port(
+    manager: State<'_, SharedMidiManager>,
+) -> Result<(), String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("disable_virtual_port");
+    manager.disable_virtual_port();
+    Ok(())
+}
+
+/// Watch an input port (e.g. a foot controller) for program changes to remap
+#[tauri::command]
+pub async fn monitor_midi_input(
+    manager: State<'_, SharedMidiManager>,
+    input_port: String,
+) -> Result<(), String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.monitor_input(&input_port).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn stop_monitoring_midi_input(
+    manager: State<'_, SharedMidiManager>,
+    input_port: String,
+) -> Result<(), String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.stop_monitoring_input(&input_port).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn list_monitored_inputs(
+    manager: State<'_, SharedMidiManager>,
+) -> Result<Vec<String>, String> {
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    Ok(manager.monitored_inputs())
+}
+
+/// Map PC `program` on a monitored input to an action on a connected device
+#[tauri::command]
+pub async fn create_pc_mapping(
+    manager: State<'_, SharedMidiManager>,
+    input_port: String,
+    program: u8,
+    device_name: String,
+    action: PcAction,
+) -> Result<PcMapping, String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager
+        .pc_remap_mut()
+        .add(&input_port, PcMapping { program, device_name, action })
+        .map_err(|e| e.to_string())
+}
+
+/// Every input's PC remap table
+#[tauri::command]
+pub async fn list_pc_mappings(
+    manager: State<'_, SharedMidiManager>,
+) -> Result<Vec<InputRemap>, String> {
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    Ok(manager.pc_remap().list().to_vec())
+}
+
+#[tauri::command]
+pub async fn delete_pc_mapping(
+    manager: State<'_, SharedMidiManager>,
+    input_port: String,
+    program: u8,
+) -> Result<(), String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.pc_remap_mut().delete(&input_port, program).map_err(|e| e.to_string())
+}
+
+/// Pass every PC on an input through untouched, keeping its table for later
+#[tauri::command]
+pub async fn set_pc_remap_bypassed(
+    manager: State<'_, SharedMidiManager>,
+    input_port: String,
+    bypassed: bool,
+) -> Result<(), String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.pc_remap_mut().set_bypassed(&input_port, bypassed).map_err(|e| e.to_string())
+}
+
 /// Send a Program Change on a specific channel to a device (no persistent connection).
 /// Used to trigger MIDI channel reassignment on pedals that accept the first received
 /// PC to set their new channel.
@@ -168,7 +963,8 @@ pub async fn assign_channel_pc(
     device_name: String,
     channel: u8,
 ) -> Result<(), String> {
-    let manager = manager.lock().map_err(|e| e.to_string())?;
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("assign_channel_pc");
     manager
         .send_channel_assignment_pc(&device_name, channel)
         .map_err(|e| e.to_string())
@@ -185,15 +981,24 @@ pub async fn save_preset(
     description: Option<String>,
     parameters: serde_json::Value,
     tags: Vec<String>,
+    metadata: Option<serde_json::Value>,
 ) -> Result<Preset, String> {
     let library = library.lock().map_err(|e| e.to_string())?;
     library
-        .save_preset(name, pedal_type, description, parameters, tags)
+        .save_preset_with_metadata(
+            name,
+            pedal_type,
+            description,
+            parameters,
+            tags,
+            metadata.unwrap_or_else(|| serde_json::json!({})),
+        )
         .map_err(|e| e.to_string())
 }
 
 /// Update an existing preset
 #[tauri::command]
+#[allow(clippy::too_many_arguments)] // One optional argument per editable field
 pub async fn update_preset(
     library: State<'_, SharedPresetLibrary>,
     id: String,
@@ -202,11 +1007,12 @@ pub async fn update_preset(
     tags: Option<Vec<String>>,
     is_favorite: Option<bool>,
     parameters: Option<serde_json::Value>,
+    metadata: Option<serde_json::Value>,
 ) -> Result<Preset, String> {
     let library = library.lock().map_err(|e| e.to_string())?;
     let preset_id = PresetId::new(id);
     library
-        .update_preset(&preset_id, name, description, tags, is_favorite, parameters)
+        .update_preset(&preset_id, name, description, tags, is_favorite, parameters, metadata)
         .map_err(|e| e.to_string())
 }
 
@@ -221,26 +1027,104 @@ pub async fn get_preset(
     library.get_preset(&preset_id).map_err(|e| e.to_string())
 }
 
-/// List presets with optional filtering
+/// `list_presets` result: every match, or one page when paging was asked for
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum PresetListResponse {
+    All(Vec<Preset>),
+    Page(PresetListResult),
+}
+
+/// List presets with optional filtering. Without `limit`, `offset` or `include_parameters`
+/// every match comes back with parameters, as before; with any of them, one page
+/// (default `DEFAULT_PAGE_SIZE`) plus the total count, parameters only if asked for.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)] // One argument per filter field
 pub async fn list_presets(
     library: State<'_, SharedPresetLibrary>,
     pedal_type: Option<String>,
     tags: Option<Vec<String>>,
     is_favorite: Option<bool>,
     search_query: Option<String>,
+    created_after_ts: Option<i64>,
+    created_before_ts: Option<i64>,
+    is_archived: Option<bool>,
+    metadata_key: Option<String>,
+    exclude_ids: Option<Vec<String>>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    include_parameters: Option<bool>,
+) -> Result<PresetListResponse, String> {
+    let library = library.lock().map_err(|e| e.to_string())?;
+    let paged = limit.is_some() || offset.is_some() || include_parameters.is_some();
+    let filter = PresetFilter {
+        pedal_type,
+        tags: tags.unwrap_or_default(),
+        is_favorite,
+        search_query,
+        created_after: created_after_ts,
+        created_before: created_before_ts,
+        is_archived,
+        metadata_key,
+        exclude_ids: exclude_ids.unwrap_or_default().into_iter().map(PresetId::new).collect(),
+        limit: if paged { Some(limit.unwrap_or(presets::DEFAULT_PAGE_SIZE)) } else { None },
+        offset,
+    };
+    let response = if paged {
+        library
+            .list_presets_page(filter, include_parameters.unwrap_or(false))
+            .map(PresetListResponse::Page)
+    } else {
+        library.list_presets(filter).map(PresetListResponse::All)
+    };
+    response.map_err(|e| e.to_string())
+}
+
+/// Search preset names, descriptions and tags, best match first
+#[tauri::command]
+pub async fn search_presets_fulltext(
+    library: State<'_, SharedPresetLibrary>,
+    query: String,
 ) -> Result<Vec<Preset>, String> {
+    let library = library.lock().map_err(|e| e.to_string())?;
+    library.search_fulltext(&query).map_err(|e| e.to_string())
+}
+
+/// List preset metadata (no parameters) with optional filtering - cheaper for list views
+#[tauri::command]
+#[allow(clippy::too_many_arguments)] // One argument per filter field
+pub async fn list_preset_summaries(
+    library: State<'_, SharedPresetLibrary>,
+    pedal_type: Option<String>,
+    tags: Option<Vec<String>>,
+    is_favorite: Option<bool>,
+    search_query: Option<String>,
+    created_after_ts: Option<i64>,
+    created_before_ts: Option<i64>,
+    is_archived: Option<bool>,
+    metadata_key: Option<String>,
+    exclude_ids: Option<Vec<String>>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+) -> Result<Vec<PresetSummary>, String> {
     let library = library.lock().map_err(|e| e.to_string())?;
     let filter = PresetFilter {
         pedal_type,
         tags: tags.unwrap_or_default(),
         is_favorite,
         search_query,
+        created_after: created_after_ts,
+        created_before: created_before_ts,
+        is_archived,
+        metadata_key,
+        exclude_ids: exclude_ids.unwrap_or_default().into_iter().map(PresetId::new).collect(),
+        limit,
+        offset,
     };
-    library.list_presets(filter).map_err(|e| e.to_string())
+    library.list_preset_summaries(filter).map_err(|e| e.to_string())
 }
 
-/// Delete a preset
+/// Delete a preset (to the trash, or for good if it was archived)
 #[tauri::command]
 pub async fn delete_preset(
     library: State<'_, SharedPresetLibrary>,
@@ -253,6 +1137,179 @@ pub async fn delete_preset(
         .map_err(|e| e.to_string())
 }
 
+/// Delete a batch of presets in one transaction, live ones to the trash and archived ones
+/// for good. Unless `partial`, any missing id fails the whole batch. Reports missing ids and
+/// which deleted presets were assigned to banks.
+#[tauri::command]
+pub async fn delete_presets(
+    library: State<'_, SharedPresetLibrary>,
+    ids: Vec<String>,
+    partial: Option<bool>,
+) -> Result<BulkDeleteResult, String> {
+    let library = library.lock().map_err(|e| e.to_string())?;
+    let preset_ids: Vec<PresetId> = ids.into_iter().map(PresetId::new).collect();
+    library
+        .delete_presets(&preset_ids, partial.unwrap_or(false))
+        .map_err(|e| e.to_string())
+}
+
+/// Find groups of presets for a pedal whose parameters are identical
+#[tauri::command]
+pub async fn find_duplicate_presets(
+    library: State<'_, SharedPresetLibrary>,
+    pedal_type: String,
+) -> Result<Vec<DuplicateGroup>, String> {
+    let library = library.lock().map_err(|e| e.to_string())?;
+    library
+        .find_duplicate_presets(&pedal_type)
+        .map_err(|e| e.to_string())
+}
+
+/// Merge duplicates into one preset: bank slots and tags move to `keep_id`,
+/// the removed presets go to the trash
+#[tauri::command]
+pub async fn merge_presets(
+    library: State<'_, SharedPresetLibrary>,
+    keep_id: String,
+    remove_ids: Vec<String>,
+) -> Result<Preset, String> {
+    let library = library.lock().map_err(|e| e.to_string())?;
+    let remove_ids: Vec<PresetId> = remove_ids.into_iter().map(PresetId::new).collect();
+    library
+        .merge_presets(&PresetId::new(keep_id), &remove_ids)
+        .map_err(|e| e.to_string())
+}
+
+/// Favorite or unfavorite a batch of presets in one transaction.
+/// Unless `partial`, any missing id fails the whole batch.
+#[tauri::command]
+pub async fn set_favorite_bulk(
+    library: State<'_, SharedPresetLibrary>,
+    ids: Vec<String>,
+    is_favorite: bool,
+    partial: Option<bool>,
+) -> Result<Vec<BulkPresetResult>, String> {
+    let library = library.lock().map_err(|e| e.to_string())?;
+    let preset_ids: Vec<PresetId> = ids.into_iter().map(PresetId::new).collect();
+    library
+        .set_favorite_bulk(&preset_ids, is_favorite, partial.unwrap_or(false))
+        .map_err(|e| e.to_string())
+}
+
+/// Archive a preset (hidden from lists, data and bank slots kept)
+#[tauri::command]
+pub async fn archive_preset(
+    library: State<'_, SharedPresetLibrary>,
+    id: String,
+) -> Result<Preset, String> {
+    let library = library.lock().map_err(|e| e.to_string())?;
+    let preset_id = PresetId::new(id);
+    library
+        .archive_preset(&preset_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Bring an archived preset back into lists
+#[tauri::command]
+pub async fn unarchive_preset(
+    library: State<'_, SharedPresetLibrary>,
+    id: String,
+) -> Result<Preset, String> {
+    let library = library.lock().map_err(|e| e.to_string())?;
+    let preset_id = PresetId::new(id);
+    library
+        .unarchive_preset(&preset_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Remove a preset immediately, skipping the trash
+#[tauri::command]
+pub async fn permanently_delete_preset(
+    library: State<'_, SharedPresetLibrary>,
+    id: String,
+) -> Result<(), String> {
+    let library = library.lock().map_err(|e| e.to_string())?;
+    let preset_id = PresetId::new(id);
+    library
+        .permanently_delete_preset(&preset_id)
+        .map_err(|e| e.to_string())
+}
+
+/// List presets in the trash
+#[tauri::command]
+pub async fn list_deleted_presets(
+    library: State<'_, SharedPresetLibrary>,
+) -> Result<Vec<DeletedPreset>, String> {
+    let library = library.lock().map_err(|e| e.to_string())?;
+    library
+        .list_deleted_presets()
+        .map_err(|e| e.to_string())
+}
+
+/// Restore a preset from the trash (returns the bank slots it held so the UI can offer to re-assign)
+#[tauri::command]
+pub async fn restore_preset(
+    library: State<'_, SharedPresetLibrary>,
+    id: String,
+) -> Result<RestoredPreset, String> {
+    let library = library.lock().map_err(|e| e.to_string())?;
+    let preset_id = PresetId::new(id);
+    library
+        .restore_preset(&preset_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Permanently remove trashed presets older than the given number of days
+#[tauri::command]
+pub async fn purge_deleted(
+    library: State<'_, SharedPresetLibrary>,
+    older_than_days: u32,
+) -> Result<usize, String> {
+    let library = library.lock().map_err(|e| e.to_string())?;
+    library
+        .purge_deleted(older_than_days)
+        .map_err(|e| e.to_string())
+}
+
+/// Remove bank assignments whose preset was deleted outside the library
+#[tauri::command]
+pub async fn repair_database(
+    library: State<'_, SharedPresetLibrary>,
+) -> Result<usize, String> {
+    let library = library.lock().map_err(|e| e.to_string())?;
+    library
+        .repair_orphaned_banks()
+        .map_err(|e| e.to_string())
+}
+
+/// Compact the preset database and report the space reclaimed.
+/// Refuses while another library operation holds the library; emits `preset-maintenance-complete` when done.
+#[tauri::command]
+pub async fn run_preset_maintenance(
+    app: tauri::AppHandle,
+    library: State<'_, SharedPresetLibrary>,
+) -> Result<MaintenanceReport, String> {
+    let library = library.try_lock()
+        .map_err(|_| PresetError::Busy { reason: "another library operation is in progress".to_string() }.to_string())?;
+    let report = library.run_maintenance().map_err(|e| e.to_string())?;
+    if let Err(e) = app.emit(presets::MAINTENANCE_COMPLETE_EVENT, &report) {
+        eprintln!("Failed to emit maintenance event: {}", e);
+    }
+    Ok(report)
+}
+
+/// Back up the preset database to `destination` without closing it
+#[tauri::command]
+pub async fn backup_preset_database(
+    library: State<'_, SharedPresetLibrary>,
+    destination: String,
+) -> Result<(), String> {
+    let library = library.lock().map_err(|e| e.to_string())?;
+    library
+        .backup_to_file(std::path::PathBuf::from(destination))
+        .map_err(|e| e.to_string())
+}
+
 /// Toggle favorite status
 #[tauri::command]
 pub async fn toggle_favorite(
@@ -266,6 +1323,21 @@ pub async fn toggle_favorite(
         .map_err(|e| e.to_string())
 }
 
+/// Add tags to a batch of presets, keeping their existing tags
+/// Returns how many presets were updated.
+#[tauri::command]
+pub async fn bulk_tag_presets(
+    library: State<'_, SharedPresetLibrary>,
+    ids: Vec<String>,
+    tags: Vec<String>,
+) -> Result<usize, String> {
+    let library = library.lock().map_err(|e| e.to_string())?;
+    let preset_ids: Vec<PresetId> = ids.into_iter().map(PresetId::new).collect();
+    library
+        .tag_preset_collection(&preset_ids, tags)
+        .map_err(|e| e.to_string())
+}
+
 /// Get the state of all pedal banks
 #[tauri::command]
 pub async fn get_bank_state(
@@ -279,19 +1351,24 @@ pub async fn get_bank_state(
     Ok(result)
 }
 
-/// Assign a preset to a specific bank
+/// Assign a preset to a specific bank. On pedals that allow a preset in only one bank,
+/// `move_if_assigned` clears the preset's old slot instead of failing.
 #[tauri::command]
 pub async fn assign_to_bank(
     library: State<'_, SharedPresetLibrary>,
     pedal_type: String,
     bank_number: u8,
     preset_id: String,
+    move_if_assigned: Option<bool>,
 ) -> Result<(), String> {
     let library = library.lock().map_err(|e| e.to_string())?;
     let id = PresetId::new(preset_id);
-    library
-        .assign_to_bank(&pedal_type, bank_number, &id)
-        .map_err(|e| e.to_string())
+    if move_if_assigned.unwrap_or(false) {
+        library.move_to_bank(&pedal_type, bank_number, &id)
+    } else {
+        library.assign_to_bank(&pedal_type, bank_number, &id)
+    }
+    .map_err(|e| e.to_string())
 }
 
 /// Clear a bank slot (unassign preset from slot without deleting preset)
@@ -319,52 +1396,337 @@ pub async fn get_presets_with_banks(
         .map_err(|e| e.to_string())
 }
 
-/// Get the bank configuration for a specific pedal type
+/// Export bank assignments across all pedals (JSON-serializable; see BankLayout::to_markdown_table)
+#[tauri::command]
+pub async fn export_bank_layout(
+    library: State<'_, SharedPresetLibrary>,
+) -> Result<BankLayout, String> {
+    let library = library.lock().map_err(|e| e.to_string())?;
+    library.export_bank_layout().map_err(|e| e.to_string())
+}
+
+/// Copy bank slots from one pedal type to another, matching presets by name
+#[tauri::command]
+pub async fn clone_bank_layout(
+    library: State<'_, SharedPresetLibrary>,
+    from_pedal: String,
+    to_pedal: String,
+) -> Result<(), String> {
+    let library = library.lock().map_err(|e| e.to_string())?;
+    library
+        .clone_bank_layout(&from_pedal, &to_pedal)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Export bank assignments across all pedals as a Markdown table
+#[tauri::command]
+pub async fn export_bank_layout_markdown(
+    library: State<'_, SharedPresetLibrary>,
+) -> Result<String, String> {
+    let library = library.lock().map_err(|e| e.to_string())?;
+    library
+        .export_bank_layout()
+        .map(|layout| layout.to_markdown_table())
+        .map_err(|e| e.to_string())
+}
+
+/// Write a preset's recall as a type 0 MIDI file for dropping into a DAW. Uses
+/// `channel_override`, else the channel of a connected pedal of the preset's type, else 1.
+#[tauri::command]
+pub async fn export_preset_as_midifile(
+    library: State<'_, SharedPresetLibrary>,
+    manager: State<'_, SharedMidiManager>,
+    preset_id: String,
+    path: String,
+    channel_override: Option<u8>,
+) -> Result<(), String> {
+    let preset = {
+        let library = library.lock().map_err(|e| e.to_string())?;
+        library.get_preset(&PresetId::new(preset_id)).map_err(|e| e.to_string())?
+    };
+    let pedal_type = PedalType::from_name(&preset.pedal_type)
+        .ok_or_else(|| format!("Unknown pedal type: {}", preset.pedal_type))?;
+    let midi_channel = match channel_override {
+        Some(channel) => channel,
+        None => {
+            let manager = manager.lock().map_err(|e| e.to_string())?;
+            manager
+                .connected_devices()
+                .into_iter()
+                .find(|device| device.pedal_type == pedal_type)
+                .map_or(1, |device| device.midi_channel)
+        }
+    };
+    PresetSends::for_preset(pedal_type, preset.parameters)
+        .and_then(|sends| sends.write_midi_file(std::path::Path::new(&path), midi_channel, &preset.name))
+        .map_err(|e| e.to_string())
+}
+
+// ===== Session Journal Commands =====
+
+/// Path of the journal file currently being written
+#[tauri::command]
+pub async fn get_session_journal_path(
+    session_journal: State<'_, SessionJournal>,
+) -> Result<String, String> {
+    Ok(session_journal.current_path().display().to_string())
+}
+
+/// Check what this install can do: MIDI backend and ports, database integrity, app data
+/// directory, virtual ports, timer resolution and each pedal's bank config, schema and commands
+#[tauri::command]
+pub async fn run_self_test(
+    app: tauri::AppHandle,
+    library: State<'_, SharedPresetLibrary>,
+) -> Result<SelfTestReport, String> {
+    let app_data_dir = app.path().app_data_dir().ok();
+    let library = library.lock().map_err(|e| e.to_string())?;
+    Ok(self_test::run_self_test(&library, app_data_dir.as_deref()))
+}
+
+/// Zip recent journals, frontend settings, library stats and a self-test report into a support bundle.
+/// Full presets are only included when `include_presets` is set.
+#[tauri::command]
+pub async fn export_support_bundle(
+    app: tauri::AppHandle,
+    session_journal: State<'_, SessionJournal>,
+    library: State<'_, SharedPresetLibrary>,
+    pedalboards: State<'_, SharedPedalboardLibrary>,
+    path: String,
+    settings: Option<serde_json::Value>,
+    include_presets: bool,
+) -> Result<(), String> {
+    session_journal.flush().map_err(|e| e.to_string())?;
+    let journal_files = session_journal.journal_files().map_err(|e| e.to_string())?;
+
+    let app_data_dir = app.path().app_data_dir().ok();
+    let (stats, self_test, presets) = {
+        let library = library.lock().map_err(|e| e.to_string())?;
+        let pedalboards = pedalboards.lock().map_err(|e| e.to_string())?;
+        let stats = LibraryStats::collect(&library, &pedalboards).map_err(|e| e.to_string())?;
+        let self_test = self_test::run_self_test(&library, app_data_dir.as_deref());
+        let presets = if include_presets {
+            Some(library.list_presets(PresetFilter::default()).map_err(|e| e.to_string())?)
+        } else {
+            None
+        };
+        (stats, self_test, presets)
+    };
+
+    let entries = journal::bundle_entries(&journal_files, settings.as_ref(), &stats, Some(&self_test), presets.as_deref())
+        .map_err(|e| e.to_string())?;
+    journal::write_support_bundle(std::path::Path::new(&path), &entries).map_err(|e| e.to_string())
+}
+
+/// Get the bank configuration for a specific pedal type, with its slot range and count
+#[tauri::command]
+pub async fn get_bank_config(pedal_type: String) -> Result<serde_json::Value, String> {
+    let config = presets::bank_config::get_bank_config(&pedal_type)
+        .ok_or_else(|| format!("No bank configuration for pedal type: {}", pedal_type))?;
+    serde_json::to_value(presets::BankConfigInfo::from(&config)).map_err(|e| e.to_string())
+}
+
+/// Get the arcade button position labels for a specific pedal type
+#[tauri::command]
+pub async fn get_pedal_mode_names(pedal_type: String) -> Result<Vec<ModeNames>, String> {
+    PedalType::from_name(&pedal_type)
+        .map(|pedal_type| midi::pedals::mode_names(&pedal_type))
+        .ok_or_else(|| format!("Unknown pedal type: {}", pedal_type))
+}
+
+/// State fields of a pedal type whose meaning depends on other settings
 #[tauri::command]
-pub async fn get_bank_config(pedal_type: String) -> Result<presets::BankConfig, String> {
-    presets::bank_config::get_bank_config(&pedal_type)
-        .ok_or_else(|| format!("No bank configuration for pedal type: {}", pedal_type))
+pub async fn get_context_dependent_parameters(pedal_type: String) -> Result<Vec<String>, String> {
+    PedalType::from_name(&pedal_type)
+        .map(|pedal_type| {
+            midi::pedals::context_dependent_parameters(&pedal_type)
+                .iter()
+                .map(|parameter| parameter.to_string())
+                .collect()
+        })
+        .ok_or_else(|| format!("Unknown pedal type: {}", pedal_type))
 }
 
 /// Result of saving a preset to a bank - includes save capability info for UI feedback
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct SaveToBankResult {
-    pub success: bool,
-    pub saved_via_midi: bool,
-    pub manual_save_required: bool,
-    pub instructions: Option<String>,
+pub struct SaveToBankResult {
+    pub success: bool,
+    pub saved_via_midi: bool,
+    pub manual_save_required: bool,
+    pub instructions: Option<String>,
+}
+
+impl SaveToBankResult {
+    /// Result of a completed save sequence for a pedal with the given save capability
+    fn for_capability(midi_save: &MidiSaveCapability) -> Self {
+        match midi_save {
+            MidiSaveCapability::Supported { .. } => SaveToBankResult {
+                success: true,
+                saved_via_midi: true,
+                manual_save_required: false,
+                instructions: None,
+            },
+            MidiSaveCapability::ManualOnly { instructions } => SaveToBankResult {
+                success: true,
+                saved_via_midi: false,
+                manual_save_required: true,
+                instructions: Some(instructions.clone()),
+            },
+            MidiSaveCapability::AutoSave => SaveToBankResult {
+                success: true,
+                saved_via_midi: true,
+                manual_save_required: false,
+                instructions: None,
+            },
+        }
+    }
+
+    /// `for_capability`, with instructions naming the slot for pedals that save whichever
+    /// slot is active
+    fn for_bank(bank_config: &BankConfig, bank_number: u8) -> Self {
+        let mut result = Self::for_capability(&bank_config.midi_save);
+        if bank_config.pedal_type == "ChromaConsole" {
+            result.instructions = Some(midi::pedals::chroma_console::manual_save_instructions(bank_number));
+        }
+        result
+    }
+}
+
+/// Save a preset to a specific pedal bank (universal for all pedals)
+#[tauri::command]
+pub async fn save_preset_to_bank(
+    midi_manager: State<'_, SharedMidiManager>,
+    library: State<'_, SharedPresetLibrary>,
+    device_name: String,
+    preset_id: String,
+    bank_number: u8,
+) -> Result<SaveToBankResult, String> {
+    // Get the preset
+    let id = PresetId::new(preset_id.clone());
+    let preset = {
+        let library = library.lock().map_err(|e| e.to_string())?;
+        library.get_preset(&id).map_err(|e| e.to_string())?
+    };
+
+    midi_manager
+        .lock()
+        .map_err(|e| e.to_string())?
+        .begin_journal_operation("save_preset_to_bank");
+
+    // Get bank config to determine save capability
+    let bank_config = presets::bank_config::get_bank_config(&preset.pedal_type)
+        .ok_or_else(|| {
+            format!(
+                "No bank configuration for pedal type: {}",
+                preset.pedal_type
+            )
+        })?;
+
+    // Remember what the bank held, in case this overwrite needs undoing
+    let snapshot = {
+        let library = library.lock().map_err(|e| e.to_string())?;
+        library
+            .snapshot_bank(&preset.pedal_type, bank_number)
+            .map_err(|e| e.to_string())?
+    };
+
+    run_bank_save_sequence(&midi_manager, &device_name, &preset.pedal_type, &preset.parameters, bank_number).await?;
+
+    // Sequence completed - record the overwrite and update bank assignment in database
+    {
+        let library = library.lock().map_err(|e| e.to_string())?;
+        library
+            .record_bank_overwrite(&snapshot, &device_name, &preset.parameters)
+            .map_err(|e| e.to_string())?;
+        // The pedal now holds the preset here, so this slot wins over any earlier one
+        library
+            .move_to_bank(&preset.pedal_type, bank_number, &id)
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(SaveToBankResult::for_bank(&bank_config, bank_number))
+}
+
+/// Result of saving a new preset straight into a bank
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SavePresetAndAssignResult {
+    pub preset: Preset,
+    pub bank_slot: BankSlot,
+    /// Present when the preset was also stored on a pedal
+    pub hardware_save: Option<SaveToBankResult>,
 }
 
-/// Save a preset to a specific pedal bank (universal for all pedals)
+/// Save the current state as a new preset and put it in `bank_number` (one transaction).
+/// With `device_name`, the pedal's save sequence then stores it on the hardware too.
 #[tauri::command]
-pub async fn save_preset_to_bank(
+#[allow(clippy::too_many_arguments)] // One argument per preset field, plus the target
+pub async fn save_preset_and_assign(
     midi_manager: State<'_, SharedMidiManager>,
     library: State<'_, SharedPresetLibrary>,
-    device_name: String,
-    preset_id: String,
+    name: String,
+    pedal_type: String,
+    description: Option<String>,
+    parameters: serde_json::Value,
+    tags: Vec<String>,
     bank_number: u8,
-) -> Result<SaveToBankResult, String> {
-    // Get the preset
-    let id = PresetId::new(preset_id.clone());
-    let preset = {
+    device_name: Option<String>,
+) -> Result<SavePresetAndAssignResult, String> {
+    // Remember what the bank held, in case a hardware overwrite needs undoing
+    let (preset, bank_slot, snapshot) = {
         let library = library.lock().map_err(|e| e.to_string())?;
-        library.get_preset(&id).map_err(|e| e.to_string())?
+        let snapshot = library
+            .snapshot_bank(&pedal_type, bank_number)
+            .map_err(|e| e.to_string())?;
+        let (preset, bank_slot) = library
+            .save_preset_and_assign(name, pedal_type, description, parameters, tags, bank_number)
+            .map_err(|e| e.to_string())?;
+        (preset, bank_slot, snapshot)
     };
 
-    // Get bank config to determine save capability
+    let Some(device_name) = device_name else {
+        return Ok(SavePresetAndAssignResult { preset, bank_slot, hardware_save: None });
+    };
+
+    midi_manager
+        .lock()
+        .map_err(|e| e.to_string())?
+        .begin_journal_operation("save_preset_and_assign");
     let bank_config = presets::bank_config::get_bank_config(&preset.pedal_type)
-        .ok_or_else(|| {
-            format!(
-                "No bank configuration for pedal type: {}",
-                preset.pedal_type
-            )
-        })?;
+        .ok_or_else(|| format!("No bank configuration for pedal type: {}", preset.pedal_type))?;
+
+    // The library already holds the preset in this bank; only the pedal can be behind
+    run_bank_save_sequence(&midi_manager, &device_name, &preset.pedal_type, &preset.parameters, bank_number)
+        .await
+        .map_err(|e| format!("Saved '{}' to bank {}, but storing it on the pedal failed: {}", preset.name, bank_number, e))?;
+    {
+        let library = library.lock().map_err(|e| e.to_string())?;
+        library
+            .record_bank_overwrite(&snapshot, &device_name, &preset.parameters)
+            .map_err(|e| e.to_string())?;
+    }
 
-    match preset.pedal_type.as_str() {
+    Ok(SavePresetAndAssignResult {
+        preset,
+        bank_slot,
+        hardware_save: Some(SaveToBankResult::for_bank(&bank_config, bank_number)),
+    })
+}
+
+/// Send a pedal state to the device and store it in `bank_number` using the pedal's save sequence
+async fn run_bank_save_sequence(
+    midi_manager: &SharedMidiManager,
+    device_name: &str,
+    pedal_type: &str,
+    parameters: &serde_json::Value,
+    bank_number: u8,
+) -> Result<(), String> {
+    match pedal_type {
         "Microcosm" => {
             let _state: MicrocosmState =
-                serde_json::from_value(preset.parameters.clone())
+                serde_json::from_value(parameters.clone())
                     .map_err(|e| format!("Failed to deserialize preset: {}", e))?;
 
             let midi_program = bank_number - 1;
@@ -384,7 +1746,7 @@ pub async fn save_preset_to_bank(
                 let mut manager = midi_manager.lock().map_err(|e| e.to_string())?;
                 println!("[Save to Bank] Copy (CC 45)");
                 manager
-                    .send_microcosm_parameter(&device_name, MicrocosmParameter::PresetCopy)
+                    .send_microcosm_parameter(device_name, MicrocosmParameter::PresetCopy)
                     .map_err(|e| e.to_string())?;
             }
             tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
@@ -397,7 +1759,7 @@ pub async fn save_preset_to_bank(
                     bank_number, midi_program
                 );
                 manager
-                    .send_microcosm_program_change(&device_name, midi_program)
+                    .send_microcosm_program_change(device_name, midi_program)
                     .map_err(|e| e.to_string())?;
             }
             tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
@@ -407,7 +1769,7 @@ pub async fn save_preset_to_bank(
                 let mut manager = midi_manager.lock().map_err(|e| e.to_string())?;
                 println!("[Save to Bank] Save (CC 46)");
                 manager
-                    .send_microcosm_parameter(&device_name, MicrocosmParameter::PresetSave)
+                    .send_microcosm_parameter(device_name, MicrocosmParameter::PresetSave)
                     .map_err(|e| e.to_string())?;
             }
             tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
@@ -419,33 +1781,43 @@ pub async fn save_preset_to_bank(
             {
                 let mut manager = midi_manager.lock().map_err(|e| e.to_string())?;
                 manager
-                    .send_chroma_console_program_change(&device_name, bank_number)
+                    .send_chroma_console_program_change(device_name, bank_number)
                     .map_err(|e| e.to_string())?;
             }
             tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
 
             // Recall preset (send all parameters)
             let state: ChromaConsoleState =
-                serde_json::from_value(preset.parameters.clone())
+                serde_json::from_value(parameters.clone())
                     .map_err(|e| format!("Failed to deserialize preset: {}", e))?;
 
             {
                 let mut manager = midi_manager.lock().map_err(|e| e.to_string())?;
                 manager
-                    .recall_chroma_console_preset(&device_name, &state)
+                    .recall_chroma_console_preset(device_name, &state)
                     .map_err(|e| e.to_string())?;
             }
 
-            // No MIDI save command - user must manually save
+            // No MIDI save command - the result tells the user how to save on the pedal
         }
         "PreampMk2" => {
             let state: PreampMk2State =
-                serde_json::from_value(preset.parameters.clone())
+                serde_json::from_value(parameters.clone())
                     .map_err(|e| format!("Failed to deserialize preset: {}", e))?;
+
+            // Navigate to the target slot first so the save lands on the active preset
+            {
+                let mut manager = midi_manager.lock().map_err(|e| e.to_string())?;
+                manager
+                    .send_preamp_mk2_program_change(device_name, bank_number)
+                    .map_err(|e| e.to_string())?;
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+
             {
                 let mut manager = midi_manager.lock().map_err(|e| e.to_string())?;
                 manager
-                    .recall_preamp_mk2_preset(&device_name, &state)
+                    .recall_preamp_mk2_preset(device_name, &state)
                     .map_err(|e| e.to_string())?;
             }
             // Give the pedal time to apply recalled CC values before issuing save CC.
@@ -454,25 +1826,24 @@ pub async fn save_preset_to_bank(
             {
                 let mut manager = midi_manager.lock().map_err(|e| e.to_string())?;
                 manager
-                    .save_preamp_mk2_preset(&device_name, bank_number)
+                    .save_preamp_mk2_preset(device_name, bank_number)
                     .map_err(|e| e.to_string())?;
             }
         }
         "GenLossMkii" => {
-            let state: GenLossMkiiState =
-                serde_json::from_value(preset.parameters.clone())
-                    .map_err(|e| format!("Failed to deserialize preset: {}", e))?;
+            let state = GenLossMkiiState::from_json_preset(parameters)
+                .map_err(|e| format!("Failed to deserialize preset: {}", e))?;
             {
                 let mut manager = midi_manager.lock().map_err(|e| e.to_string())?;
                 manager
-                    .recall_gen_loss_preset(&device_name, &state)
+                    .recall_gen_loss_preset(device_name, &state, false)
                     .map_err(|e| e.to_string())?;
             }
             tokio::time::sleep(tokio::time::Duration::from_millis(250)).await;
             {
                 let mut manager = midi_manager.lock().map_err(|e| e.to_string())?;
                 manager
-                    .save_gen_loss_preset(&device_name, bank_number)
+                    .save_gen_loss_preset(device_name, bank_number)
                     .map_err(|e| e.to_string())?;
             }
             // Navigate to the saved slot so the pedal's indicator confirms the preset is active
@@ -480,107 +1851,585 @@ pub async fn save_preset_to_bank(
             {
                 let mut manager = midi_manager.lock().map_err(|e| e.to_string())?;
                 manager
-                    .send_gen_loss_program_change(&device_name, bank_number)
+                    .send_gen_loss_program_change(device_name, bank_number)
                     .map_err(|e| e.to_string())?;
             }
         }
         "BrothersAm" => {
-            let state: BrothersAmState = serde_json::from_value(preset.parameters.clone())
+            let state: BrothersAmState = serde_json::from_value(parameters.clone())
                 .map_err(|e| format!("Failed to deserialize preset: {}", e))?;
-            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.recall_brothers_am_preset(&device_name, &state).map_err(|e| e.to_string())?; }
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.recall_brothers_am_preset(device_name, &state).map_err(|e| e.to_string())?; }
             tokio::time::sleep(tokio::time::Duration::from_millis(250)).await;
-            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.save_brothers_am_preset(&device_name, bank_number).map_err(|e| e.to_string())?; }
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.save_brothers_am_preset(device_name, bank_number).map_err(|e| e.to_string())?; }
             tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.send_brothers_am_program_change(&device_name, bank_number).map_err(|e| e.to_string())?; }
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.send_brothers_am_program_change(device_name, bank_number).map_err(|e| e.to_string())?; }
         }
         "ReverseModeC" => {
-            let state: ReverseModeCState = serde_json::from_value(preset.parameters.clone())
+            let state: ReverseModeCState = serde_json::from_value(parameters.clone())
                 .map_err(|e| format!("Failed to deserialize preset: {}", e))?;
-            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.recall_reverse_mode_c_preset(&device_name, &state).map_err(|e| e.to_string())?; }
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.recall_reverse_mode_c_preset(device_name, &state).map_err(|e| e.to_string())?; }
             tokio::time::sleep(tokio::time::Duration::from_millis(250)).await;
-            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.save_reverse_mode_c_preset(&device_name, bank_number).map_err(|e| e.to_string())?; }
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.save_reverse_mode_c_preset(device_name, bank_number).map_err(|e| e.to_string())?; }
             tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.send_reverse_mode_c_program_change(&device_name, bank_number).map_err(|e| e.to_string())?; }
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.send_reverse_mode_c_program_change(device_name, bank_number).map_err(|e| e.to_string())?; }
         }
         "MoodMkii" => {
-            let state: MoodMkiiState = serde_json::from_value(preset.parameters.clone())
+            let state: MoodMkiiState = serde_json::from_value(parameters.clone())
                 .map_err(|e| format!("Failed to deserialize preset: {}", e))?;
-            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.recall_mood_mkii_preset(&device_name, &state).map_err(|e| e.to_string())?; }
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.recall_mood_mkii_preset(device_name, &state).map_err(|e| e.to_string())?; }
             tokio::time::sleep(tokio::time::Duration::from_millis(250)).await;
-            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.save_mood_mkii_preset(&device_name, bank_number).map_err(|e| e.to_string())?; }
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.save_mood_mkii_preset(device_name, bank_number).map_err(|e| e.to_string())?; }
             tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.send_mood_mkii_program_change(&device_name, bank_number).map_err(|e| e.to_string())?; }
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.send_mood_mkii_program_change(device_name, bank_number).map_err(|e| e.to_string())?; }
         }
         "BillyStringsWombtone" => {
-            let state: BillyStringsWombtoneState = serde_json::from_value(preset.parameters.clone())
+            let state: BillyStringsWombtoneState = serde_json::from_value(parameters.clone())
                 .map_err(|e| format!("Failed to deserialize preset: {}", e))?;
-            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.recall_billy_strings_wombtone_preset(&device_name, &state).map_err(|e| e.to_string())?; }
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.recall_billy_strings_wombtone_preset(device_name, &state).map_err(|e| e.to_string())?; }
             tokio::time::sleep(tokio::time::Duration::from_millis(250)).await;
-            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.save_billy_strings_wombtone_preset(&device_name, bank_number).map_err(|e| e.to_string())?; }
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.save_billy_strings_wombtone_preset(device_name, bank_number).map_err(|e| e.to_string())?; }
             tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.send_billy_strings_wombtone_program_change(&device_name, bank_number).map_err(|e| e.to_string())?; }
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.send_billy_strings_wombtone_program_change(device_name, bank_number).map_err(|e| e.to_string())?; }
         }
         "Lossy" => {
-            let state: LossyState = serde_json::from_value(preset.parameters.clone())
+            let state: LossyState = serde_json::from_value(parameters.clone())
                 .map_err(|e| format!("Failed to deserialize preset: {}", e))?;
-            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.recall_lossy_preset(&device_name, &state).map_err(|e| e.to_string())?; }
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.recall_lossy_preset(device_name, &state).map_err(|e| e.to_string())?; }
             tokio::time::sleep(tokio::time::Duration::from_millis(250)).await;
-            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.save_lossy_preset(&device_name, bank_number).map_err(|e| e.to_string())?; }
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.save_lossy_preset(device_name, bank_number).map_err(|e| e.to_string())?; }
             tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.send_lossy_program_change(&device_name, bank_number).map_err(|e| e.to_string())?; }
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.send_lossy_program_change(device_name, bank_number).map_err(|e| e.to_string())?; }
         }
         "Clean" => {
-            let state: CleanState = serde_json::from_value(preset.parameters.clone())
+            let state: CleanState = serde_json::from_value(parameters.clone())
                 .map_err(|e| format!("Failed to deserialize preset: {}", e))?;
-            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.recall_clean_preset(&device_name, &state).map_err(|e| e.to_string())?; }
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.recall_clean_preset(device_name, &state).map_err(|e| e.to_string())?; }
             tokio::time::sleep(tokio::time::Duration::from_millis(250)).await;
-            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.save_clean_preset(&device_name, bank_number).map_err(|e| e.to_string())?; }
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.save_clean_preset(device_name, bank_number).map_err(|e| e.to_string())?; }
             tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.send_clean_program_change(&device_name, bank_number).map_err(|e| e.to_string())?; }
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.send_clean_program_change(device_name, bank_number).map_err(|e| e.to_string())?; }
         }
         "Onward" => {
-            let state: OnwardState = serde_json::from_value(preset.parameters.clone())
+            let state: OnwardState = serde_json::from_value(parameters.clone())
+                .map_err(|e| format!("Failed to deserialize preset: {}", e))?;
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.recall_onward_preset(device_name, &state).map_err(|e| e.to_string())?; }
+            tokio::time::sleep(tokio::time::Duration::from_millis(250)).await;
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.save_onward_preset(device_name, bank_number).map_err(|e| e.to_string())?; }
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.send_onward_program_change(device_name, bank_number).map_err(|e| e.to_string())?; }
+        }
+        "Thermae" => {
+            let state: ThermaeState = serde_json::from_value(parameters.clone())
+                .map_err(|e| format!("Failed to deserialize preset: {}", e))?;
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.recall_thermae_preset(device_name, &state).map_err(|e| e.to_string())?; }
+            tokio::time::sleep(tokio::time::Duration::from_millis(250)).await;
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.save_thermae_preset(device_name, bank_number).map_err(|e| e.to_string())?; }
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.send_thermae_program_change(device_name, bank_number).map_err(|e| e.to_string())?; }
+        }
+        "DarkWorld" => {
+            let state: DarkWorldState = serde_json::from_value(parameters.clone())
+                .map_err(|e| format!("Failed to deserialize preset: {}", e))?;
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.recall_dark_world_preset(device_name, &state).map_err(|e| e.to_string())?; }
+            tokio::time::sleep(tokio::time::Duration::from_millis(250)).await;
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.save_dark_world_preset(device_name, bank_number).map_err(|e| e.to_string())?; }
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.send_dark_world_program_change(device_name, bank_number).map_err(|e| e.to_string())?; }
+        }
+        "Habit" => {
+            let state: HabitState = serde_json::from_value(parameters.clone())
                 .map_err(|e| format!("Failed to deserialize preset: {}", e))?;
-            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.recall_onward_preset(&device_name, &state).map_err(|e| e.to_string())?; }
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.recall_habit_preset(device_name, &state).map_err(|e| e.to_string())?; }
             tokio::time::sleep(tokio::time::Duration::from_millis(250)).await;
-            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.save_onward_preset(&device_name, bank_number).map_err(|e| e.to_string())?; }
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.save_habit_preset(device_name, bank_number).map_err(|e| e.to_string())?; }
             tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.send_onward_program_change(&device_name, bank_number).map_err(|e| e.to_string())?; }
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.send_habit_program_change(device_name, bank_number).map_err(|e| e.to_string())?; }
+        }
+        "Collider" => {
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.send_collider_program_change(device_name, bank_number).map_err(|e| e.to_string())?; }
+            tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+            let state: ColliderState = serde_json::from_value(parameters.clone())
+                .map_err(|e| format!("Failed to deserialize preset: {}", e))?;
+            { let mut manager = midi_manager.lock().map_err(|e| e.to_string())?; manager.recall_collider_preset(device_name, &state).map_err(|e| e.to_string())?; }
+            // No MIDI save command - the result tells the user how to save on the pedal
         }
         _ => {
-            return Err(format!("Unsupported pedal type: {}", preset.pedal_type));
+            return Err(format!("Unsupported pedal type: {}", pedal_type));
         }
     }
 
-    // Update bank assignment in database
+    Ok(())
+}
+
+/// Overwrite history for a bank - what it held before each recent save, newest first
+#[tauri::command]
+pub async fn get_bank_history(
+    library: State<'_, SharedPresetLibrary>,
+    pedal_type: String,
+    bank_number: u8,
+) -> Result<Vec<BankHistoryEntry>, String> {
+    let library = library.lock().map_err(|e| e.to_string())?;
+    library
+        .get_bank_history(&pedal_type, bank_number)
+        .map_err(|e| e.to_string())
+}
+
+/// Look up a preset by name and recall it on a device in one step, returning the preset.
+/// Loud parameter jumps fail the recall unless `acknowledge_warnings` is set.
+/// A `glide_ms` above 0 (at most 2 s) ramps `glide_ccs` (the pedal's mix and level
+/// controls by default) to their targets over that long.
+#[tauri::command]
+pub async fn recall_preset_by_name(
+    midi_manager: State<'_, SharedMidiManager>,
+    library: State<'_, SharedPresetLibrary>,
+    pedal_type: String,
+    device_name: String,
+    preset_name: String,
+    acknowledge_warnings: Option<bool>,
+    glide_ms: Option<u64>,
+    glide_ccs: Option<Vec<u8>>,
+) -> Result<Preset, String> {
+    let preset = {
+        let library = library.lock().map_err(|e| e.to_string())?;
+        library
+            .get_preset_by_name(&pedal_type, &preset_name)
+            .map_err(|e| e.to_string())?
+    };
+    let pedal = PedalType::from_name(&pedal_type)
+        .ok_or_else(|| format!("Unsupported pedal type: {}", pedal_type))?;
+
+    let mut manager = midi_manager.lock().map_err(|e| e.to_string())?;
+    let warnings = manager
+        .recall_safety_warnings(&device_name, &preset.parameters)
+        .map_err(|e| e.to_string())?;
+    if !warnings.is_empty() && !acknowledge_warnings.unwrap_or(false) {
+        let parameters: Vec<&str> = warnings.iter().map(|w| w.parameter.as_str()).collect();
+        return Err(format!(
+            "Recall of '{}' blocked by loud parameter jumps: {}",
+            preset.name,
+            parameters.join(", ")
+        ));
+    }
+    manager.begin_journal_operation("recall_preset_by_name");
+    let glide = pedal.glide(glide_ms, glide_ccs);
+    manager
+        .gliding(&device_name, glide, |manager| manager.recall_preset_json(&device_name, pedal, preset.parameters.clone()))
+        .map_err(|e| e.to_string())?;
+    Ok(preset)
+}
+
+/// Recall a library preset on a device later: after `at` milliseconds, or on the MIDI
+/// clock after `at` beats. Emits scheduled-fired or scheduled-missed when it comes due.
+#[tauri::command]
+pub async fn schedule_recall(
+    midi_manager: State<'_, SharedMidiManager>,
+    library: State<'_, SharedPresetLibrary>,
+    device_name: String,
+    preset_id: String,
+    at: ScheduleSpec,
+    acknowledge_warnings: Option<bool>,
+) -> Result<ScheduledRecall, String> {
+    let preset = {
+        let library = library.lock().map_err(|e| e.to_string())?;
+        library.get_preset(&PresetId::new(preset_id)).map_err(|e| e.to_string())?
+    };
+    let pedal = PedalType::from_name(&preset.pedal_type)
+        .ok_or_else(|| format!("Unsupported pedal type: {}", preset.pedal_type))?;
+
+    let shared = SharedMidiManager::clone(&midi_manager);
+    let mut manager = midi_manager.lock().map_err(|e| e.to_string())?;
+    // Checked now: nobody is there to acknowledge a warning when the recall fires
+    let warnings = manager
+        .recall_safety_warnings(&device_name, &preset.parameters)
+        .map_err(|e| e.to_string())?;
+    if !warnings.is_empty() && !acknowledge_warnings.unwrap_or(false) {
+        let parameters: Vec<&str> = warnings.iter().map(|w| w.parameter.as_str()).collect();
+        return Err(format!(
+            "Recall of '{}' blocked by loud parameter jumps: {}",
+            preset.name,
+            parameters.join(", ")
+        ));
+    }
+    let target = RecallTarget { pedal_type: pedal, parameters: preset.parameters };
+    manager
+        .schedule_recall(&device_name, preset.id.as_str(), &preset.name, at, target, move |id| {
+            let Ok(mut manager) = shared.lock() else { return };
+            manager.begin_journal_operation("schedule_recall");
+            if let Err(e) = manager.fire_scheduled_recall(id) {
+                eprintln!("❌ Scheduled recall {} failed: {}", id, e);
+            }
+        })
+        .map_err(|e| e.to_string())
+}
+
+/// Cancel a scheduled recall; false if it already fired or was cancelled
+#[tauri::command]
+pub async fn cancel_scheduled(
+    manager: State<'_, SharedMidiManager>,
+    id: u64,
+) -> Result<bool, String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    Ok(manager.cancel_scheduled(id))
+}
+
+/// Recalls waiting to fire, oldest first
+#[tauri::command]
+pub async fn list_scheduled(
+    manager: State<'_, SharedMidiManager>,
+) -> Result<Vec<ScheduledRecall>, String> {
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    Ok(manager.list_scheduled())
+}
+
+/// Put a bank back the way it was before an overwrite: recall the old state and re-run the save sequence
+#[tauri::command]
+pub async fn restore_bank_from_history(
+    midi_manager: State<'_, SharedMidiManager>,
+    library: State<'_, SharedPresetLibrary>,
+    entry_id: i64,
+    device_name: String,
+) -> Result<SaveToBankResult, String> {
+    let (entry, snapshot) = {
+        let library = library.lock().map_err(|e| e.to_string())?;
+        let entry = library.get_bank_history_entry(entry_id).map_err(|e| e.to_string())?;
+        // The restore is itself an overwrite, so it can be undone the same way
+        let snapshot = library
+            .snapshot_bank(&entry.pedal_type, entry.bank_number)
+            .map_err(|e| e.to_string())?;
+        (entry, snapshot)
+    };
+
+    let bank_config = presets::bank_config::get_bank_config(&entry.pedal_type)
+        .ok_or_else(|| format!("No bank configuration for pedal type: {}", entry.pedal_type))?;
+    let pedal_type = PedalType::from_name(&entry.pedal_type)
+        .ok_or_else(|| format!("Unsupported pedal type: {}", entry.pedal_type))?;
+
+    // Some save sequences store whatever the pedal currently holds, so recall the old state first
+    {
+        let mut manager = midi_manager.lock().map_err(|e| e.to_string())?;
+        manager.begin_journal_operation("restore_bank_from_history");
+        manager
+            .recall_preset_json(&device_name, pedal_type, entry.parameters.clone())
+            .map_err(|e| e.to_string())?;
+    }
+    tokio::time::sleep(tokio::time::Duration::from_millis(250)).await;
+
+    run_bank_save_sequence(&midi_manager, &device_name, &entry.pedal_type, &entry.parameters, entry.bank_number).await?;
+
     {
         let library = library.lock().map_err(|e| e.to_string())?;
         library
-            .assign_to_bank(&preset.pedal_type, bank_number, &id)
+            .record_bank_overwrite(&snapshot, &device_name, &entry.parameters)
             .map_err(|e| e.to_string())?;
+        // The old preset may have been deleted since - then the bank holds an unlisted state
+        match library.move_to_bank(&entry.pedal_type, entry.bank_number, &entry.preset_id) {
+            Err(PresetError::NotFound { .. }) => library.clear_bank(&entry.pedal_type, entry.bank_number),
+            other => other,
+        }
+        .map_err(|e| e.to_string())?;
+    }
+
+    Ok(SaveToBankResult::for_bank(&bank_config, entry.bank_number))
+}
+
+/// Fill `pedal_type`'s banks from the presets matching `filter`, ordered and truncated per
+/// `strategy`. With `dry_run` only the plan is returned. When `device_name` is connected and
+/// the pedal has a MIDI save, each preset is also stored on the pedal with its save sequence.
+/// Emits `bank-autofill-progress` before each slot; stops at the first failed slot, leaving
+/// the slots before it written. The library stays locked for the whole fill so nothing else
+/// reassigns the banks part way through.
+#[tauri::command]
+pub async fn autofill_banks(
+    app: tauri::AppHandle,
+    midi_manager: State<'_, SharedMidiManager>,
+    library: State<'_, SharedPresetLibrary>,
+    pedal_type: String,
+    device_name: Option<String>,
+    filter: PresetFilter,
+    strategy: presets::FillStrategy,
+    dry_run: Option<bool>,
+) -> Result<presets::AutofillReport, String> {
+    let plan = {
+        let library = library.lock().map_err(|e| e.to_string())?;
+        library
+            .plan_autofill(&pedal_type, filter, &strategy)
+            .map_err(|e| e.to_string())?
+    };
+    if dry_run.unwrap_or(false) {
+        return Ok(presets::AutofillReport::dry_run(plan));
     }
 
-    // Return result based on save capability
-    let result = match &bank_config.midi_save {
-        MidiSaveCapability::Supported { .. } => SaveToBankResult {
-            success: true,
-            saved_via_midi: true,
-            manual_save_required: false,
-            instructions: None,
-        },
-        MidiSaveCapability::ManualOnly { instructions } => SaveToBankResult {
-            success: true,
-            saved_via_midi: false,
-            manual_save_required: true,
-            instructions: Some(instructions.clone()),
-        },
-        MidiSaveCapability::AutoSave => SaveToBankResult {
-            success: true,
-            saved_via_midi: true,
-            manual_save_required: false,
-            instructions: None,
-        },
+    let bank_config = presets::bank_config::get_bank_config(&pedal_type)
+        .ok_or_else(|| format!("No bank configuration for pedal type: {}", pedal_type))?;
+    let device_name = {
+        let mut manager = midi_manager.lock().map_err(|e| e.to_string())?;
+        let connected = device_name.filter(|name| manager.is_connected(name));
+        if connected.is_some() {
+            manager.begin_journal_operation("autofill_banks");
+        }
+        connected
     };
+    let save_device = device_name.filter(|_| !matches!(bank_config.midi_save, MidiSaveCapability::ManualOnly { .. }));
 
-    Ok(result)
+    let midi_manager = midi_manager.inner().clone();
+    let library = library.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let library = library.lock().map_err(|e| e.to_string())?;
+        let total = plan.slots.len();
+        Ok(library.apply_autofill_with(plan, |library, index, slot| {
+            let progress = presets::AutofillProgress { pedal_type: pedal_type.clone(), bank_number: slot.bank_number, index, total };
+            if let Err(e) = app.emit(presets::AUTOFILL_PROGRESS_EVENT, &progress) {
+                eprintln!("Failed to emit autofill progress event: {}", e);
+            }
+            let (Some(device_name), Some(preset_id)) = (save_device.as_deref(), &slot.preset_id) else {
+                return Ok(false);
+            };
+            save_autofill_slot(&midi_manager, library, device_name, &pedal_type, preset_id, slot.bank_number)?;
+            Ok(true)
+        }))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Store an autofill slot's preset on `device_name` with the pedal's save sequence, keeping
+/// what the bank held before in its history. Runs on a blocking thread.
+fn save_autofill_slot(
+    midi_manager: &SharedMidiManager,
+    library: &presets::PresetLibrary,
+    device_name: &str,
+    pedal_type: &str,
+    preset_id: &PresetId,
+    bank_number: u8,
+) -> Result<(), String> {
+    let preset = library.get_preset(preset_id).map_err(|e| e.to_string())?;
+    let snapshot = library.snapshot_bank(pedal_type, bank_number).map_err(|e| e.to_string())?;
+
+    tauri::async_runtime::block_on(run_bank_save_sequence(
+        midi_manager,
+        device_name,
+        pedal_type,
+        &preset.parameters,
+        bank_number,
+    ))?;
+
+    library
+        .record_bank_overwrite(&snapshot, device_name, &preset.parameters)
+        .map_err(|e| e.to_string())
+}
+
+// ===== Library Server Commands =====
+
+/// Library server settings, plus its URL and bearer token while it's running
+#[tauri::command]
+pub async fn get_library_server_info(
+    library_server: State<'_, SharedLibraryServer>,
+) -> Result<LibraryServerInfo, String> {
+    let library_server = library_server.lock().map_err(|e| e.to_string())?;
+    Ok(library_server.info())
+}
+
+/// Save library server settings and start, restart or stop it to match
+#[tauri::command]
+pub async fn set_library_server_config(
+    library_server: State<'_, SharedLibraryServer>,
+    config: LibraryServerConfig,
+) -> Result<LibraryServerInfo, String> {
+    let mut library_server = library_server.lock().map_err(|e| e.to_string())?;
+    library_server.set_config(config).map_err(|e| e.to_string())?;
+    Ok(library_server.info())
+}
+
+// ===== Pedalboard Commands =====
+
+/// Create a new pedalboard
+#[tauri::command]
+pub async fn create_pedalboard(
+    library: State<'_, SharedPedalboardLibrary>,
+    name: String,
+    members: Vec<PedalboardMember>,
+) -> Result<Pedalboard, String> {
+    let library = library.lock().map_err(|e| e.to_string())?;
+    library
+        .create_pedalboard(name, members)
+        .map_err(|e| e.to_string())
+}
+
+/// Update a pedalboard's name and/or members
+#[tauri::command]
+pub async fn update_pedalboard(
+    library: State<'_, SharedPedalboardLibrary>,
+    id: String,
+    name: Option<String>,
+    members: Option<Vec<PedalboardMember>>,
+) -> Result<Pedalboard, String> {
+    let library = library.lock().map_err(|e| e.to_string())?;
+    let pedalboard_id = PedalboardId::new(id);
+    library
+        .update_pedalboard(&pedalboard_id, name, members)
+        .map_err(|e| e.to_string())
+}
+
+/// Delete a pedalboard
+#[tauri::command]
+pub async fn delete_pedalboard(
+    library: State<'_, SharedPedalboardLibrary>,
+    id: String,
+) -> Result<(), String> {
+    let library = library.lock().map_err(|e| e.to_string())?;
+    let pedalboard_id = PedalboardId::new(id);
+    library
+        .delete_pedalboard(&pedalboard_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Get a pedalboard by ID
+#[tauri::command]
+pub async fn get_pedalboard(
+    library: State<'_, SharedPedalboardLibrary>,
+    id: String,
+) -> Result<Pedalboard, String> {
+    let library = library.lock().map_err(|e| e.to_string())?;
+    let pedalboard_id = PedalboardId::new(id);
+    library
+        .get_pedalboard(&pedalboard_id)
+        .map_err(|e| e.to_string())
+}
+
+/// List all pedalboards
+#[tauri::command]
+pub async fn list_pedalboards(
+    library: State<'_, SharedPedalboardLibrary>,
+) -> Result<Vec<Pedalboard>, String> {
+    let library = library.lock().map_err(|e| e.to_string())?;
+    library.list_pedalboards().map_err(|e| e.to_string())
+}
+
+/// Get the active pedalboard, if any
+#[tauri::command]
+pub async fn get_active_pedalboard(
+    library: State<'_, SharedPedalboardLibrary>,
+) -> Result<Option<Pedalboard>, String> {
+    let library = library.lock().map_err(|e| e.to_string())?;
+    library.get_active_pedalboard().map_err(|e| e.to_string())
+}
+
+/// Connect every pedal on a pedalboard and make it the active board.
+/// Deactivates the previously active board first. Emits "pedalboard-activated".
+#[tauri::command]
+pub async fn activate_pedalboard(
+    app: tauri::AppHandle,
+    library: State<'_, SharedPedalboardLibrary>,
+    midi_manager: State<'_, SharedMidiManager>,
+    id: String,
+) -> Result<PedalboardActivation, String> {
+    midi_manager
+        .lock()
+        .map_err(|e| e.to_string())?
+        .begin_journal_operation("activate_pedalboard");
+    let pedalboard_id = PedalboardId::new(id);
+
+    let (previous, activation) = pedalboards::activate_shared(library.inner().as_ref(), midi_manager.inner().as_ref(), &pedalboard_id)
+        .map_err(|e| e.to_string())?;
+
+    if let Some(previous) = previous {
+        let _ = app.emit("pedalboard-deactivated", &previous);
+    }
+    if let Err(e) = app.emit("pedalboard-activated", &activation) {
+        eprintln!("Failed to emit pedalboard-activated event: {}", e);
+    }
+
+    Ok(activation)
+}
+
+/// Disconnect the pedals the active pedalboard connected. Emits "pedalboard-deactivated".
+#[tauri::command]
+pub async fn deactivate_pedalboard(
+    app: tauri::AppHandle,
+    library: State<'_, SharedPedalboardLibrary>,
+    midi_manager: State<'_, SharedMidiManager>,
+) -> Result<Option<Pedalboard>, String> {
+    midi_manager
+        .lock()
+        .map_err(|e| e.to_string())?
+        .begin_journal_operation("deactivate_pedalboard");
+
+    let deactivated = pedalboards::deactivate_shared(library.inner().as_ref(), midi_manager.inner().as_ref())
+        .map_err(|e| e.to_string())?;
+
+    if let Some(pedalboard) = &deactivated {
+        if let Err(e) = app.emit("pedalboard-deactivated", pedalboard) {
+            eprintln!("Failed to emit pedalboard-deactivated event: {}", e);
+        }
+    }
+
+    Ok(deactivated)
+}
+
+/// Add a scene to a pedalboard, replacing any scene with the same name
+#[tauri::command]
+pub async fn save_pedalboard_scene(
+    library: State<'_, SharedPedalboardLibrary>,
+    id: String,
+    scene: PedalboardScene,
+) -> Result<Pedalboard, String> {
+    let library = library.lock().map_err(|e| e.to_string())?;
+    library
+        .save_scene(&PedalboardId::new(id), scene)
+        .map_err(|e| e.to_string())
+}
+
+/// Remove a scene from a pedalboard
+#[tauri::command]
+pub async fn delete_pedalboard_scene(
+    library: State<'_, SharedPedalboardLibrary>,
+    id: String,
+    name: String,
+) -> Result<Pedalboard, String> {
+    let library = library.lock().map_err(|e| e.to_string())?;
+    library
+        .delete_scene(&PedalboardId::new(id), &name)
+        .map_err(|e| e.to_string())
+}
+
+/// Recall one of the active pedalboard's scenes: each pedal in it recalls its preset.
+/// A pedal that fails doesn't stop the others. Emits "pedalboard-scene-recalled".
+#[tauri::command]
+pub async fn recall_pedalboard_scene(
+    app: tauri::AppHandle,
+    pedalboard_library: State<'_, SharedPedalboardLibrary>,
+    library: State<'_, SharedPresetLibrary>,
+    midi_manager: State<'_, SharedMidiManager>,
+    name: String,
+) -> Result<SceneRecallReport, String> {
+    // One lock at a time: the board, then the presets, then the pedals
+    let (pedalboard, scene) = {
+        let pedalboard_library = pedalboard_library.lock().map_err(|e| e.to_string())?;
+        pedalboard_library.active_scene(&name).map_err(|e| e.to_string())?
+    };
+    let steps: Vec<SceneStep> = {
+        let library = library.lock().map_err(|e| e.to_string())?;
+        scene
+            .recalls
+            .iter()
+            .map(|recall| SceneStep {
+                recall: recall.clone(),
+                parameters: library
+                    .get_preset(&PresetId::new(recall.preset_id.clone()))
+                    .map(|preset| preset.parameters)
+                    .map_err(|e| e.to_string()),
+            })
+            .collect()
+    };
+    let report = {
+        let mut manager = midi_manager.lock().map_err(|e| e.to_string())?;
+        manager.begin_journal_operation("recall_pedalboard_scene");
+        pedalboards::recall_scene(&pedalboard, &scene.name, steps, &mut *manager)
+    };
+
+    if let Err(e) = app.emit("pedalboard-scene-recalled", &report) {
+        eprintln!("Failed to emit pedalboard-scene-recalled event: {}", e);
+    }
+
+    Ok(report)
 }
@@ -0,0 +1,383 @@
+// Session journal - append-only record of everything sent to hardware
+// One JSONL file per app session in the app data dir, so a support request can show
+// exactly what the app sent. Sends only push onto a channel; a writer thread owns the file.
+
+mod support_bundle;
+
+pub use support_bundle::{bundle_entries, write_support_bundle, LibraryStats};
+
+use crate::midi::cc_decode;
+use crate::midi::PedalType;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Journal directory inside the app data dir
+pub const JOURNAL_DIR: &str = "journals";
+
+/// Size and retention limits for journal files
+#[derive(Debug, Clone, Copy)]
+pub struct JournalLimits {
+    /// A session's file rolls over to a new part past this size
+    pub max_file_bytes: u64,
+    /// Oldest journal files are deleted once all of them together exceed this
+    pub max_total_bytes: u64,
+}
+
+impl Default for JournalLimits {
+    fn default() -> Self {
+        Self {
+            max_file_bytes: 1024 * 1024,
+            max_total_bytes: 10 * 1024 * 1024,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum JournalError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Zip error: {0}")]
+    Zip(#[from] zip::result::ZipError),
+
+    #[error("Library error: {0}")]
+    Library(String),
+
+    #[error("Journal writer has stopped")]
+    WriterStopped,
+}
+
+pub type Result<T> = std::result::Result<T, JournalError>;
+
+/// MIDI message that was sent
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum JournalMessage {
+    ControlChange { cc_number: u8, value: u8 },
+    ProgramChange { program: u8 },
+    NoteOn { note: u8, velocity: u8 },
+    NoteOff { note: u8 },
+}
+
+/// One outbound MIDI message
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub timestamp: i64,  // Unix timestamp (ms)
+    pub operation_id: Option<String>,
+    pub operation: Option<String>,  // Command that caused the send
+    pub device_name: String,
+    pub pedal_type: Option<String>,  // None for sends outside a connection (channel assignment)
+    pub channel: u8,
+    #[serde(flatten)]
+    pub message: JournalMessage,
+    pub meaning: Option<String>,  // Filled in by the writer thread
+}
+
+/// Command currently sending, stamped onto each entry
+#[derive(Debug, Clone)]
+struct Operation {
+    id: String,
+    name: String,
+}
+
+#[derive(Debug)]
+enum WriterCommand {
+    Entry(Box<JournalEntry>),
+    Flush(Sender<()>),
+}
+
+/// Handle to the session journal. Clones share one writer thread and one file.
+#[derive(Debug, Clone)]
+pub struct SessionJournal {
+    sender: Sender<WriterCommand>,
+    operation: Arc<Mutex<Option<Operation>>>,
+    dir: PathBuf,
+    current_path: Arc<Mutex<PathBuf>>,
+}
+
+impl SessionJournal {
+    /// Start a new session journal in `dir` with the default limits
+    pub fn start(dir: PathBuf) -> Result<Self> {
+        Self::start_with_limits(dir, JournalLimits::default())
+    }
+
+    pub fn start_with_limits(dir: PathBuf, limits: JournalLimits) -> Result<Self> {
+        fs::create_dir_all(&dir)?;
+
+        let session = chrono::Utc::now().format("%Y%m%d-%H%M%S").to_string();
+        let writer = JournalWriter::open(dir.clone(), session, limits)?;
+        let current_path = Arc::clone(&writer.current_path);
+
+        let (sender, receiver) = mpsc::channel();
+        thread::Builder::new()
+            .name("session-journal".to_string())
+            .spawn(move || writer.run(receiver))?;
+
+        Ok(Self {
+            sender,
+            operation: Arc::new(Mutex::new(None)),
+            dir,
+            current_path,
+        })
+    }
+
+    /// Mark the start of a command; following sends are tagged with a fresh operation id
+    pub fn begin_operation(&self, name: &str) -> String {
+        let id = uuid::Uuid::new_v4().to_string();
+        if let Ok(mut operation) = self.operation.lock() {
+            *operation = Some(Operation {
+                id: id.clone(),
+                name: name.to_string(),
+            });
+        }
+        id
+    }
+
+    /// Queue an outbound message. Never blocks on disk.
+    pub fn record(&self, device_name: &str, pedal_type: Option<&PedalType>, channel: u8, message: JournalMessage) {
+        let operation = self.operation.lock().ok().and_then(|o| o.clone());
+        let entry = JournalEntry {
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            operation_id: operation.as_ref().map(|o| o.id.clone()),
+            operation: operation.map(|o| o.name),
+            device_name: device_name.to_string(),
+            pedal_type: pedal_type.map(|p| p.as_str().to_string()),
+            channel,
+            message,
+            meaning: None,
+        };
+
+        // The writer only stops if its thread died - sends must carry on regardless
+        let _ = self.sender.send(WriterCommand::Entry(Box::new(entry)));
+    }
+
+    /// Wait until everything queued so far is on disk
+    pub fn flush(&self) -> Result<()> {
+        let (reply, done) = mpsc::channel();
+        self.sender
+            .send(WriterCommand::Flush(reply))
+            .map_err(|_| JournalError::WriterStopped)?;
+        done.recv().map_err(|_| JournalError::WriterStopped)
+    }
+
+    /// File the current session is writing to
+    pub fn current_path(&self) -> PathBuf {
+        self.current_path
+            .lock()
+            .map(|p| p.clone())
+            .unwrap_or_default()
+    }
+
+    /// All journal files still on disk, oldest first
+    pub fn journal_files(&self) -> Result<Vec<PathBuf>> {
+        journal_files(&self.dir)
+    }
+}
+
+/// Owns the session's file on the writer thread
+struct JournalWriter {
+    dir: PathBuf,
+    session: String,
+    limits: JournalLimits,
+    part: u32,
+    file: BufWriter<File>,
+    written: u64,
+    current_path: Arc<Mutex<PathBuf>>,
+}
+
+impl JournalWriter {
+    fn open(dir: PathBuf, session: String, limits: JournalLimits) -> Result<Self> {
+        let path = part_path(&dir, &session, 0);
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let written = file.metadata()?.len();
+        prune(&dir, &limits, &path)?;
+
+        Ok(Self {
+            dir,
+            session,
+            limits,
+            part: 0,
+            file: BufWriter::new(file),
+            written,
+            current_path: Arc::new(Mutex::new(path)),
+        })
+    }
+
+    fn run(mut self, receiver: Receiver<WriterCommand>) {
+        // Handle a burst of messages, then flush once
+        while let Ok(command) = receiver.recv() {
+            self.handle(command);
+            while let Ok(command) = receiver.try_recv() {
+                self.handle(command);
+            }
+            if let Err(e) = self.file.flush() {
+                eprintln!("❌ [Journal] Failed to flush: {}", e);
+            }
+        }
+    }
+
+    fn handle(&mut self, command: WriterCommand) {
+        match command {
+            WriterCommand::Entry(mut entry) => {
+                entry.meaning = describe(&entry);
+                if let Err(e) = self.write(&entry) {
+                    eprintln!("❌ [Journal] Failed to write entry: {}", e);
+                }
+            }
+            WriterCommand::Flush(reply) => {
+                if let Err(e) = self.file.flush() {
+                    eprintln!("❌ [Journal] Failed to flush: {}", e);
+                }
+                let _ = reply.send(());
+            }
+        }
+    }
+
+    fn write(&mut self, entry: &JournalEntry) -> Result<()> {
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+
+        if self.written > 0 && self.written + line.len() as u64 > self.limits.max_file_bytes {
+            self.rotate()?;
+        }
+
+        self.file.write_all(line.as_bytes())?;
+        self.written += line.len() as u64;
+        Ok(())
+    }
+
+    /// Continue the session in a new part file and enforce the total size cap
+    fn rotate(&mut self) -> Result<()> {
+        self.file.flush()?;
+        self.part += 1;
+
+        let path = part_path(&self.dir, &self.session, self.part);
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        self.file = BufWriter::new(file);
+        self.written = 0;
+
+        prune(&self.dir, &self.limits, &path)?;
+        if let Ok(mut current) = self.current_path.lock() {
+            *current = path;
+        }
+        Ok(())
+    }
+}
+
+/// Parts are zero-padded so file names sort oldest first
+fn part_path(dir: &Path, session: &str, part: u32) -> PathBuf {
+    dir.join(format!("session-{}-{:03}.jsonl", session, part))
+}
+
+fn journal_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "jsonl"))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Delete the oldest journals (never the current one) until they fit in `max_total_bytes`
+/// with room left for the current file to grow to `max_file_bytes`
+fn prune(dir: &Path, limits: &JournalLimits, current: &Path) -> Result<()> {
+    let max_total_bytes = limits.max_total_bytes.saturating_sub(limits.max_file_bytes);
+    let files = journal_files(dir)?;
+    let sizes: Vec<u64> = files
+        .iter()
+        .map(|path| fs::metadata(path).map(|m| m.len()).unwrap_or(0))
+        .collect();
+    let mut total: u64 = sizes.iter().sum();
+
+    for (path, size) in files.iter().zip(sizes) {
+        if total <= max_total_bytes {
+            break;
+        }
+        if path == current {
+            continue;
+        }
+        fs::remove_file(path)?;
+        total -= size;
+    }
+    Ok(())
+}
+
+/// Human-readable meaning of a sent message, where the pedal's CCs are known
+fn describe(entry: &JournalEntry) -> Option<String> {
+    match entry.message {
+        JournalMessage::ProgramChange { program } => Some(format!("Program Change {}", program)),
+        JournalMessage::NoteOn { note, velocity } => Some(format!("Note On {} (velocity {})", note, velocity)),
+        JournalMessage::NoteOff { note } => Some(format!("Note Off {}", note)),
+        JournalMessage::ControlChange { cc_number, value } => {
+            let pedal_type = PedalType::from_name(entry.pedal_type.as_deref()?)?;
+            let decoded = cc_decode::decode_cc(&pedal_type, cc_number, value);
+            Some(format!("{} = {}", decoded.parameter?, decoded.meaning?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn read_entries(path: &Path) -> Vec<JournalEntry> {
+        fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_records_entries_with_operation() {
+        let temp_dir = TempDir::new().unwrap();
+        let journal = SessionJournal::start(temp_dir.path().to_path_buf()).unwrap();
+
+        let operation_id = journal.begin_operation("send_chroma_console_parameter");
+        journal.record("Chroma Console", Some(&PedalType::ChromaConsole), 2, JournalMessage::ControlChange { cc_number: 64, value: 100 });
+        journal.record("Chroma Console", Some(&PedalType::ChromaConsole), 2, JournalMessage::ProgramChange { program: 12 });
+        journal.flush().unwrap();
+
+        let entries = read_entries(&journal.current_path());
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].operation_id.as_deref(), Some(operation_id.as_str()));
+        assert_eq!(entries[0].operation.as_deref(), Some("send_chroma_console_parameter"));
+        assert_eq!(entries[0].pedal_type.as_deref(), Some("ChromaConsole"));
+        assert_eq!(entries[0].meaning.as_deref(), Some("tilt = 100"));
+        assert_eq!(entries[1].meaning.as_deref(), Some("Program Change 12"));
+    }
+
+    #[test]
+    fn test_rotates_and_caps_total_size() {
+        let temp_dir = TempDir::new().unwrap();
+        let limits = JournalLimits {
+            max_file_bytes: 400,
+            max_total_bytes: 1000,
+        };
+        let journal = SessionJournal::start_with_limits(temp_dir.path().to_path_buf(), limits).unwrap();
+
+        for value in 0..60 {
+            journal.record("Lossy", Some(&PedalType::Lossy), 1, JournalMessage::ControlChange { cc_number: 14, value });
+        }
+        journal.flush().unwrap();
+
+        let files = journal.journal_files().unwrap();
+        assert!(files.len() > 1);
+        let total: u64 = files.iter().map(|f| fs::metadata(f).unwrap().len()).sum();
+        assert!(total <= limits.max_total_bytes);
+
+        // Newest entries survive; the current file is the last part
+        assert_eq!(files.last(), Some(&journal.current_path()));
+        let last = read_entries(&journal.current_path());
+        assert_eq!(last.last().unwrap().message, JournalMessage::ControlChange { cc_number: 14, value: 59 });
+    }
+}
@@ -0,0 +1,132 @@
+// Support bundle - zip of recent journals, frontend settings, library stats and a self-test report
+// Full preset parameters are only included when the user opts in.
+
+use super::{JournalError, Result};
+use crate::pedalboards::PedalboardLibrary;
+use crate::presets::{Preset, PresetFilter, PresetLibrary};
+use crate::self_test::SelfTestReport;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+
+/// Library counts - enough to reason about a report without anyone's presets
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryStats {
+    pub preset_count: usize,
+    pub presets_by_pedal: BTreeMap<String, usize>,
+    pub trashed_count: usize,
+    pub bank_assignment_count: usize,
+    pub pedalboard_count: usize,
+}
+
+impl LibraryStats {
+    pub fn collect(presets: &PresetLibrary, pedalboards: &PedalboardLibrary) -> Result<Self> {
+        let summaries = presets
+            .list_preset_summaries(PresetFilter::default())
+            .map_err(library_error)?;
+        let mut presets_by_pedal = BTreeMap::new();
+        for summary in &summaries {
+            *presets_by_pedal.entry(summary.pedal_type.clone()).or_insert(0) += 1;
+        }
+
+        let bank_assignment_count = presets
+            .export_bank_layout()
+            .map_err(library_error)?
+            .pedals
+            .values()
+            .map(Vec::len)
+            .sum();
+
+        Ok(Self {
+            preset_count: summaries.len(),
+            presets_by_pedal,
+            trashed_count: presets.list_deleted_presets().map_err(library_error)?.len(),
+            bank_assignment_count,
+            pedalboard_count: pedalboards.list_pedalboards().map_err(library_error)?.len(),
+        })
+    }
+}
+
+fn library_error(e: impl std::fmt::Display) -> JournalError {
+    JournalError::Library(e.to_string())
+}
+
+/// Files that go into a support bundle, as (path inside the zip, contents)
+pub fn bundle_entries(
+    journal_files: &[PathBuf],
+    settings: Option<&serde_json::Value>,
+    stats: &LibraryStats,
+    self_test: Option<&SelfTestReport>,
+    presets: Option<&[Preset]>,
+) -> Result<Vec<(String, Vec<u8>)>> {
+    let mut entries = Vec::new();
+
+    for path in journal_files {
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        entries.push((format!("journals/{}", name), fs::read(path)?));
+    }
+
+    if let Some(settings) = settings {
+        entries.push(("settings.json".to_string(), serde_json::to_vec_pretty(settings)?));
+    }
+
+    entries.push(("library-stats.json".to_string(), serde_json::to_vec_pretty(stats)?));
+
+    if let Some(self_test) = self_test {
+        entries.push(("self-test.json".to_string(), serde_json::to_vec_pretty(self_test)?));
+    }
+
+    // Opt-in only - presets are the user's work
+    if let Some(presets) = presets {
+        entries.push(("presets.json".to_string(), serde_json::to_vec_pretty(presets)?));
+    }
+
+    Ok(entries)
+}
+
+/// Write a zip containing `entries` to `path`
+pub fn write_support_bundle(path: &Path, entries: &[(String, Vec<u8>)]) -> Result<()> {
+    let mut zip = zip::ZipWriter::new(File::create(path)?);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    for (name, contents) in entries {
+        zip.start_file(name.as_str(), options)?;
+        zip.write_all(contents)?;
+    }
+
+    zip.finish()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_bundle_excludes_presets_unless_opted_in() {
+        let temp_dir = TempDir::new().unwrap();
+        let journal = temp_dir.path().join("session-20260101-120000-000.jsonl");
+        fs::write(&journal, "{}\n").unwrap();
+
+        let settings = serde_json::json!({ "theme": "dark" });
+        let stats = LibraryStats { preset_count: 3, ..Default::default() };
+
+        let entries = bundle_entries(std::slice::from_ref(&journal), Some(&settings), &stats, None, None).unwrap();
+        let names: Vec<&str> = entries.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["journals/session-20260101-120000-000.jsonl", "settings.json", "library-stats.json"]);
+
+        let stats_json: serde_json::Value = serde_json::from_slice(&entries[2].1).unwrap();
+        assert_eq!(stats_json["presetCount"], 3);
+
+        let entries = bundle_entries(&[], None, &stats, None, Some(&[])).unwrap();
+        let names: Vec<&str> = entries.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["library-stats.json", "presets.json"]);
+    }
+}
@@ -4,14 +4,29 @@ pub mod midi;
 // Preset management module
 pub mod presets;
 
+// Pedalboard (device group) module
+pub mod pedalboards;
+
+// Session journal of outbound MIDI and support bundles
+pub mod journal;
+
+// Read-only HTTP library browsing for companion devices
+pub mod library_server;
+
 // Tauri commands for frontend integration
 pub mod commands;
 
+// The registered commands, listed once for the invoke handler and the self-test
+pub mod command_registry;
+
+// Startup self-test of MIDI, storage, timing and pedal wiring
+pub mod self_test;
+
 // Test utilities module
 #[cfg(test)]
 pub mod test_utils;
 
-use tauri::Manager;
+use tauri::{Emitter, Manager};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -34,6 +49,12 @@ pub fn run() {
             if let Ok(mut manager) = midi_manager.lock() {
                 manager.set_app_handle(app.handle().clone());
                 println!("✅ MIDI Manager configured for bidirectional communication");
+                
+                // Fake pedals for development and screenshots
+                if midi::fake_device::demo_mode_from_env() {
+                    manager.set_demo_mode(true);
+                    println!("🎭 Demo mode: fake pedals available");
+                }
             }
             
             // Initialize preset library with proper app data directory
@@ -42,105 +63,170 @@ pub fn run() {
             std::fs::create_dir_all(&app_data_dir)
                 .expect("Failed to create app data directory");
             let db_path = app_data_dir.join("presets.db");
-            let preset_library = presets::create_shared_library(db_path)
+            let preset_library = presets::create_shared_library(db_path.clone())
                 .expect("Failed to create preset library");
-            app.manage(preset_library);
+            app.manage(preset_library.clone());
+            
+            // Re-emit library changes so open views can refetch what they show
+            if let Ok(library) = preset_library.lock() {
+                let mut changes = library.watch_changes();
+                let changes_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    use tokio::sync::broadcast::error::RecvError;
+                    loop {
+                        match changes.recv().await {
+                            Ok(event) => {
+                                let _ = changes_handle.emit(presets::PRESET_CHANGED_EVENT, &event);
+                            }
+                            Err(RecvError::Lagged(skipped)) => {
+                                eprintln!("⚠️ Dropped {} preset change event(s)", skipped);
+                            }
+                            Err(RecvError::Closed) => break,
+                        }
+                    }
+                });
+            }
+            
+            // Reclaim space left by heavy deletes, off the startup path and on a connection of
+            // its own, so library commands issued meanwhile aren't stuck behind the VACUUM
+            let maintenance_path = db_path;
+            let maintenance_handle = app.handle().clone();
+            std::thread::spawn(move || {
+                match presets::maintain_database(&maintenance_path, presets::MAINTENANCE_FREE_PAGE_RATIO) {
+                    Ok(None) => {}
+                    Ok(Some(report)) => {
+                        let _ = maintenance_handle.emit(presets::MAINTENANCE_COMPLETE_EVENT, &report);
+                    }
+                    Err(e) => eprintln!("⚠️ Preset database maintenance failed: {}", e),
+                }
+            });
+            
+            // Pedalboards share the preset database file
+            let pedalboard_library = pedalboards::create_shared_library(app_data_dir.join("presets.db"))
+                .expect("Failed to create pedalboard library");
+            app.manage(pedalboard_library.clone());
+
+            // Keep pedalboard scenes on presets that still exist as the library changes
+            if let Ok(library) = preset_library.lock() {
+                let mut changes = library.watch_changes();
+                let scene_boards = pedalboard_library.clone();
+                tauri::async_runtime::spawn(async move {
+                    use tokio::sync::broadcast::error::RecvError;
+                    loop {
+                        match changes.recv().await {
+                            Ok(event) => {
+                                let result = match scene_boards.lock() {
+                                    Ok(boards) => boards.apply_preset_change(&event),
+                                    Err(_) => break,
+                                };
+                                if let Err(e) = result {
+                                    eprintln!("⚠️ Failed to update pedalboard scenes: {}", e);
+                                }
+                            }
+                            Err(RecvError::Lagged(skipped)) => {
+                                eprintln!("⚠️ Pedalboard scenes missed {} preset change event(s)", skipped);
+                            }
+                            Err(RecvError::Closed) => break,
+                        }
+                    }
+                });
+            }
+
+            // Journal every outbound MIDI message for bug reports
+            let session_journal = journal::SessionJournal::start(app_data_dir.join(journal::JOURNAL_DIR))
+                .expect("Failed to start session journal");
+            if let Ok(mut manager) = midi_manager.lock() {
+                manager.set_journal(session_journal.clone());
+            }
+            app.manage(session_journal);
+
+            // Device labels and safety thresholds live next to the rest of the app data
+            if let Ok(mut manager) = midi_manager.lock() {
+                if let Err(e) = manager.set_device_labels_path(app_data_dir.join(midi::device_labels::DEVICE_LABELS_FILE)) {
+                    eprintln!("⚠️ Failed to load device labels: {}", e);
+                }
+                if let Err(e) = manager.set_safety_thresholds_path(app_data_dir.join(midi::safety::SAFETY_THRESHOLDS_FILE)) {
+                    eprintln!("⚠️ Failed to load safety thresholds: {}", e);
+                }
+                if let Err(e) = manager.set_exit_actions_path(app_data_dir.join(midi::exit_actions::EXIT_ACTIONS_FILE)) {
+                    eprintln!("⚠️ Failed to load exit actions: {}", e);
+                }
+                if let Err(e) = manager.set_gen_loss_dip_profiles_path(app_data_dir.join(midi::pedals::gen_loss_mkii::DIP_PROFILES_FILE)) {
+                    eprintln!("⚠️ Failed to load Gen Loss DIP profiles: {}", e);
+                }
+                if let Err(e) = manager.set_pc_remap_path(app_data_dir.join(midi::pc_remap::PC_REMAP_FILE)) {
+                    eprintln!("⚠️ Failed to load PC remap tables: {}", e);
+                }
+                if let Err(e) = manager.set_monitored_inputs_path(app_data_dir.join(midi::pc_remap::MONITORED_INPUTS_FILE)) {
+                    eprintln!("⚠️ Failed to load monitored inputs: {}", e);
+                }
+            }
+
+            // Companion-device library browsing, started now if enabled in settings
+            let server_config_path = app_data_dir.join(library_server::LIBRARY_SERVER_CONFIG_FILE);
+            let library_server = library_server::LibraryServerController::new(
+                server_config_path.clone(),
+                preset_library.clone(),
+                midi_manager.inner().clone(),
+            )
+            .unwrap_or_else(|e| {
+                eprintln!("⚠️ Failed to start library server: {}", e);
+                library_server::LibraryServerController::stopped(server_config_path, preset_library, midi_manager.inner().clone())
+            });
+            app.manage(std::sync::Arc::new(std::sync::Mutex::new(library_server)));
+
+            // The active pedalboard stays active across restarts - bring its pedals back,
+            // off the startup path since opening ports can be slow
+            let reconnect_manager = midi_manager.inner().clone();
+            let reconnect_handle = app.handle().clone();
+            std::thread::spawn(move || {
+                match pedalboards::reconnect_active_shared(&pedalboard_library, &*reconnect_manager) {
+                    Ok(Some(activation)) => {
+                        let _ = reconnect_handle.emit("pedalboard-activated", &activation);
+                    }
+                    Ok(None) => {}
+                    Err(e) => eprintln!("⚠️ Failed to reconnect the active pedalboard: {}", e),
+                }
+            });
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![
-            commands::list_midi_devices,
-            commands::connect_microcosm,
-            commands::connect_gen_loss_mkii,
-            commands::connect_chroma_console,
-            commands::disconnect_device,
-            commands::list_connected_devices,
-            commands::request_midi_device_identity,
-            commands::send_microcosm_parameter,
-            commands::send_microcosm_program_change,
-            commands::send_gen_loss_parameter,
-            commands::send_chroma_console_parameter,
-            commands::send_chroma_console_program_change,
-            commands::connect_preamp_mk2,
-            commands::send_preamp_mk2_parameter,
-            commands::get_preamp_mk2_state,
-            commands::recall_preamp_mk2_preset,
-            commands::save_preamp_mk2_preset,
-            commands::send_preamp_mk2_program_change,
-            commands::connect_cxm1978,
-            commands::send_cxm1978_parameter,
-            commands::get_cxm1978_state,
-            commands::recall_cxm1978_preset,
-            commands::save_cxm1978_preset,
-            commands::send_cxm1978_program_change,
-            commands::connect_mood_mkii,
-            commands::send_mood_mkii_parameter,
-            commands::get_mood_mkii_state,
-            commands::recall_mood_mkii_preset,
-            commands::save_mood_mkii_preset,
-            commands::send_mood_mkii_program_change,
-            commands::connect_billy_strings_wombtone,
-            commands::send_billy_strings_wombtone_parameter,
-            commands::get_billy_strings_wombtone_state,
-            commands::recall_billy_strings_wombtone_preset,
-            commands::save_billy_strings_wombtone_preset,
-            commands::send_billy_strings_wombtone_program_change,
-            commands::connect_lossy,
-            commands::send_lossy_parameter,
-            commands::get_lossy_state,
-            commands::recall_lossy_preset,
-            commands::save_lossy_preset,
-            commands::send_lossy_program_change,
-            commands::get_microcosm_state,
-            commands::get_gen_loss_state,
-            commands::get_chroma_console_state,
-            commands::recall_microcosm_preset,
-            commands::recall_gen_loss_preset,
-            commands::save_gen_loss_preset,
-            commands::send_gen_loss_program_change,
-            commands::recall_chroma_console_preset,
-            commands::is_device_connected,
-            commands::assign_channel_pc,
-            commands::save_preset,
-            commands::update_preset,
-            commands::get_preset,
-            commands::list_presets,
-            commands::delete_preset,
-            commands::toggle_favorite,
-            commands::get_bank_state,
-            commands::assign_to_bank,
-            commands::clear_bank,
-            commands::get_presets_with_banks,
-            commands::save_preset_to_bank,
-            commands::get_bank_config,
-            commands::connect_brothers_am,
-            commands::send_brothers_am_parameter,
-            commands::get_brothers_am_state,
-            commands::recall_brothers_am_preset,
-            commands::save_brothers_am_preset,
-            commands::send_brothers_am_program_change,
-            commands::connect_reverse_mode_c,
-            commands::send_reverse_mode_c_parameter,
-            commands::get_reverse_mode_c_state,
-            commands::recall_reverse_mode_c_preset,
-            commands::save_reverse_mode_c_preset,
-            commands::send_reverse_mode_c_program_change,
-            commands::connect_clean,
-            commands::send_clean_parameter,
-            commands::get_clean_state,
-            commands::recall_clean_preset,
-            commands::save_clean_preset,
-            commands::send_clean_program_change,
-            commands::connect_onward,
-            commands::send_onward_parameter,
-            commands::get_onward_state,
-            commands::recall_onward_preset,
-            commands::save_onward_preset,
-            commands::send_onward_program_change,
-        ]);
-
-    // Run the app with context
-    if let Err(e) = builder.run(tauri::generate_context!()) {
-        eprintln!("Failed to run Tauri application: {}", e);
-        std::process::exit(1);
-    }
+        .on_window_event(|window, event| {
+            // Run the exit actions and release every MIDI port when the window goes away
+            if let tauri::WindowEvent::Destroyed = event {
+                if let (Some(manager), Some(library)) = (
+                    window.try_state::<midi::SharedMidiManager>(),
+                    window.try_state::<presets::SharedPresetLibrary>(),
+                ) {
+                    midi::exit_actions::shut_down(&manager, &library);
+                }
+            }
+        })
+        .invoke_handler(command_registry::invoke_handler!());
+
+    // Build the app with context
+    let app = match builder.build(tauri::generate_context!()) {
+        Ok(app) => app,
+        Err(e) => {
+            eprintln!("Failed to run Tauri application: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    app.run(|app_handle, event| {
+        if let tauri::RunEvent::Exit = event {
+            // Normally done when the window went away; covers exits that skip that
+            if let (Some(manager), Some(library)) = (
+                app_handle.try_state::<midi::SharedMidiManager>(),
+                app_handle.try_state::<presets::SharedPresetLibrary>(),
+            ) {
+                midi::exit_actions::shut_down(&manager, &library);
+            }
+            // Close the library server's socket before the process goes away
+            if let Some(library_server) = app_handle.try_state::<library_server::SharedLibraryServer>() {
+                if let Ok(mut controller) = library_server.lock() {
+                    controller.shutdown();
+                }
+            }
+        }
+    });
 }
@@ -0,0 +1,120 @@
+// The parts of a tiny_http request the endpoints read (method, decoded path and query,
+// headers) and the JSON responses they give back.
+
+use std::collections::HashMap;
+use std::io::Cursor;
+
+/// A request's method, path, query and headers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub query: Vec<(String, String)>,
+    /// Header names lowercased
+    pub headers: HashMap<String, String>,
+}
+
+impl Request {
+    pub fn from_tiny(request: &tiny_http::Request) -> Self {
+        Self::parse(
+            request.method().as_str(),
+            request.url(),
+            request
+                .headers()
+                .iter()
+                .map(|header| (header.field.as_str().as_str(), header.value.as_str())),
+        )
+    }
+
+    /// Split `url` into a decoded path and query; `+` in the query is a space
+    fn parse<'a>(method: &str, url: &str, headers: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        Self {
+            method: method.to_string(),
+            path: percent_encoding::percent_decode_str(path).decode_utf8_lossy().into_owned(),
+            query: form_urlencoded::parse(query.as_bytes()).into_owned().collect(),
+            headers: headers
+                .into_iter()
+                .map(|(name, value)| (name.to_ascii_lowercase(), value.to_string()))
+                .collect(),
+        }
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).map(String::as_str)
+    }
+
+    /// Last value of a query parameter
+    pub fn query_param(&self, name: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .rev()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Every value of a repeatable query parameter (`?tags=a&tags=b`)
+    pub fn query_params(&self, name: &str) -> Vec<String> {
+        self.query
+            .iter()
+            .filter(|(key, _)| key == name)
+            .map(|(_, value)| value.clone())
+            .collect()
+    }
+}
+
+/// A JSON response
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    pub fn json(status: u16, body: String) -> Self {
+        Self { status, body }
+    }
+
+    /// `{"error": message}`
+    pub fn error(status: u16, message: &str) -> Self {
+        Self::json(status, serde_json::json!({ "error": message }).to_string())
+    }
+
+    pub fn into_tiny(self) -> tiny_http::Response<Cursor<Vec<u8>>> {
+        let content_type = tiny_http::Header::from_bytes("Content-Type", "application/json")
+            .expect("a valid static header");
+        tiny_http::Response::from_data(self.body)
+            .with_status_code(self.status)
+            .with_header(content_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request_with_query_and_headers() {
+        let request = Request::parse(
+            "GET",
+            "/presets?pedal_type=Microcosm&tags=ambient&tags=pad&search_query=big+room%21",
+            [("Host", "localhost"), ("Authorization", "Bearer abc")],
+        );
+
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.path, "/presets");
+        assert_eq!(request.query_param("pedal_type"), Some("Microcosm"));
+        assert_eq!(request.query_params("tags"), vec!["ambient", "pad"]);
+        assert_eq!(request.query_param("search_query"), Some("big room!"));
+        assert_eq!(request.header("authorization"), Some("Bearer abc"));
+    }
+
+    #[test]
+    fn test_path_is_decoded_and_malformed_escapes_are_kept() {
+        let request = Request::parse("GET", "/banks/Gen%20Loss?q=100%&r=%zz", []);
+
+        assert_eq!(request.path, "/banks/Gen Loss");
+        assert_eq!(request.query_param("q"), Some("100%"));
+        assert_eq!(request.query_param("r"), Some("%zz"));
+    }
+}
@@ -0,0 +1,414 @@
+// Read-only library browsing over HTTP for companion devices (e.g. a tablet side-stage)
+// Opt-in and off by default. Serves JSON for presets, bank layouts and connected devices
+// from the same shared state the app uses, guarded by a bearer token generated each session.
+
+mod http;
+
+use crate::commands::DeviceInfo;
+use crate::midi::SharedMidiManager;
+use crate::presets::{PresetError, PresetFilter, PresetId, SharedPresetLibrary};
+use http::{Request, Response};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener, TcpStream, UdpSocket};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Server settings file inside the app data dir
+pub const LIBRARY_SERVER_CONFIG_FILE: &str = "library_server.json";
+
+/// Requests served at once; more are turned away with 503 until one finishes
+pub const MAX_CONCURRENT_REQUESTS: usize = 8;
+
+/// How long `stop` waits for the port to be released
+const STOP_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, thiserror::Error)]
+pub enum LibraryServerError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+pub type Result<T> = std::result::Result<T, LibraryServerError>;
+
+/// Which interfaces the server listens on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BindScope {
+    /// This computer only
+    #[default]
+    Localhost,
+    /// Every network interface, so devices on the LAN can connect
+    Lan,
+}
+
+/// User settings for the library server
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LibraryServerConfig {
+    pub enabled: bool,
+    pub bind: BindScope,
+    /// 0 picks a free port
+    pub port: u16,
+}
+
+impl Default for LibraryServerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind: BindScope::Localhost,
+            port: 8470,
+        }
+    }
+}
+
+impl LibraryServerConfig {
+    fn socket_addr(&self) -> SocketAddr {
+        let ip = match self.bind {
+            BindScope::Localhost => Ipv4Addr::LOCALHOST,
+            BindScope::Lan => Ipv4Addr::UNSPECIFIED,
+        };
+        SocketAddr::from((ip, self.port))
+    }
+}
+
+/// Read server settings; a missing file means the defaults (disabled)
+pub fn load_config(path: &Path) -> Result<LibraryServerConfig> {
+    if !path.exists() {
+        return Ok(LibraryServerConfig::default());
+    }
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+/// Write server settings, replacing the previous file
+pub fn save_config(path: &Path, config: &LibraryServerConfig) -> Result<()> {
+    fs::write(path, serde_json::to_string_pretty(config)?)?;
+    Ok(())
+}
+
+/// What the settings screen shows
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LibraryServerInfo {
+    pub config: LibraryServerConfig,
+    /// Base URL to enter on the companion device, while running
+    pub url: Option<String>,
+    /// Bearer token for this session, while running
+    pub token: Option<String>,
+}
+
+/// Server settings and the running server, as app state
+pub struct LibraryServerController {
+    config: LibraryServerConfig,
+    config_path: PathBuf,
+    library: SharedPresetLibrary,
+    manager: SharedMidiManager,
+    server: Option<LibraryServer>,
+}
+
+pub type SharedLibraryServer = Arc<Mutex<LibraryServerController>>;
+
+impl LibraryServerController {
+    /// Load settings from `config_path` and start the server if it's enabled
+    pub fn new(config_path: PathBuf, library: SharedPresetLibrary, manager: SharedMidiManager) -> Result<Self> {
+        let mut controller = Self {
+            config: load_config(&config_path)?,
+            config_path,
+            library,
+            manager,
+            server: None,
+        };
+        controller.apply()?;
+        Ok(controller)
+    }
+
+    /// A controller that isn't serving, for when `new` fails (unreadable settings,
+    /// port in use). Settings still load if they can, so the screen shows them.
+    pub fn stopped(config_path: PathBuf, library: SharedPresetLibrary, manager: SharedMidiManager) -> Self {
+        Self {
+            config: load_config(&config_path).unwrap_or_default(),
+            config_path,
+            library,
+            manager,
+            server: None,
+        }
+    }
+
+    pub fn info(&self) -> LibraryServerInfo {
+        LibraryServerInfo {
+            config: self.config,
+            url: self.server.as_ref().map(|server| format!("http://{}", display_addr(server.addr()))),
+            token: self.server.as_ref().map(|server| server.token().to_string()),
+        }
+    }
+
+    /// Save new settings and restart (or stop) the server to match
+    pub fn set_config(&mut self, config: LibraryServerConfig) -> Result<()> {
+        save_config(&self.config_path, &config)?;
+        self.config = config;
+        self.apply()
+    }
+
+    /// Stop the server without changing the settings (app exit)
+    pub fn shutdown(&mut self) {
+        self.server = None;
+    }
+
+    fn apply(&mut self) -> Result<()> {
+        // Drop the old server first so a restart can rebind the same port
+        self.server = None;
+        if self.config.enabled {
+            self.server = Some(LibraryServer::start(
+                &self.config,
+                Arc::clone(&self.library),
+                Arc::clone(&self.manager),
+            )?);
+        }
+        Ok(())
+    }
+}
+
+/// Address to show the user. A LAN-wide listener is shown at this machine's LAN IP.
+fn display_addr(addr: SocketAddr) -> SocketAddr {
+    if !addr.ip().is_unspecified() {
+        return addr;
+    }
+    // Connecting a UDP socket picks the outbound interface without sending anything
+    let lan_ip = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+        .and_then(|socket| {
+            socket.connect((Ipv4Addr::new(192, 0, 2, 1), 80))?;
+            socket.local_addr()
+        })
+        .map(|local| local.ip())
+        .unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST));
+    SocketAddr::new(lan_ip, addr.port())
+}
+
+/// Shared state the endpoints read from
+#[derive(Clone)]
+struct Endpoints {
+    token: String,
+    library: SharedPresetLibrary,
+    manager: SharedMidiManager,
+}
+
+/// A running server. Stops when dropped.
+pub struct LibraryServer {
+    addr: SocketAddr,
+    token: String,
+    server: Option<Arc<tiny_http::Server>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl LibraryServer {
+    /// Start listening per `config` with a freshly generated bearer token
+    pub fn start(
+        config: &LibraryServerConfig,
+        library: SharedPresetLibrary,
+        manager: SharedMidiManager,
+    ) -> Result<Self> {
+        let listener = TcpListener::bind(config.socket_addr())?;
+        let addr = listener.local_addr()?;
+        let server = Arc::new(tiny_http::Server::from_listener(listener, None).map_err(io::Error::other)?);
+        let token = uuid::Uuid::new_v4().simple().to_string();
+
+        let endpoints = Endpoints {
+            token: token.clone(),
+            library,
+            manager,
+        };
+        let thread_server = Arc::clone(&server);
+        let thread = thread::Builder::new()
+            .name("library-server".to_string())
+            .spawn(move || serve(&thread_server, endpoints))?;
+
+        println!("🌐 Library server listening on {}", addr);
+        Ok(Self {
+            addr,
+            token,
+            server: Some(server),
+            thread: Some(thread),
+        })
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Bearer token clients must send (`Authorization: Bearer <token>`)
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// Stop taking requests, wait for the server thread to finish and release the port
+    pub fn stop(&mut self) {
+        let (Some(server), Some(thread)) = (self.server.take(), self.thread.take()) else { return };
+        server.unblock();
+        let _ = thread.join();
+        drop(server);
+
+        // tiny_http closes the listener from its own accept thread once that wakes up to
+        // find the server dropped; connecting wakes it, and refusal means the port is free
+        let started = Instant::now();
+        while started.elapsed() < STOP_TIMEOUT
+            && TcpStream::connect_timeout(&self.wake_addr(), Duration::from_millis(500)).is_ok()
+        {
+            thread::sleep(Duration::from_millis(10));
+        }
+        println!("🌐 Library server stopped");
+    }
+
+    /// An address that reaches the listener even when bound to every interface
+    fn wake_addr(&self) -> SocketAddr {
+        if self.addr.ip().is_unspecified() {
+            SocketAddr::from((Ipv4Addr::LOCALHOST, self.addr.port()))
+        } else {
+            self.addr
+        }
+    }
+}
+
+impl Drop for LibraryServer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn serve(server: &tiny_http::Server, endpoints: Endpoints) {
+    let active = Arc::new(AtomicUsize::new(0));
+    // Ends once `stop` unblocks the server
+    for request in server.incoming_requests() {
+        let Some(slot) = RequestSlot::claim(&active) else {
+            let _ = request.respond(Response::error(503, "Too many requests, try again shortly").into_tiny());
+            continue;
+        };
+        // A client slow to take its response would otherwise hold up everyone else
+        let endpoints = endpoints.clone();
+        let spawned = thread::Builder::new()
+            .name("library-server-request".to_string())
+            .spawn(move || {
+                let _slot = slot;
+                let response = endpoints.respond(&Request::from_tiny(&request));
+                if let Err(e) = request.respond(response.into_tiny()) {
+                    eprintln!("⚠️ Library server connection error: {}", e);
+                }
+            });
+        if let Err(e) = spawned {
+            eprintln!("⚠️ Library server couldn't start a request thread: {}", e);
+        }
+    }
+}
+
+/// One of the `MAX_CONCURRENT_REQUESTS` places, given back when dropped
+struct RequestSlot(Arc<AtomicUsize>);
+
+impl RequestSlot {
+    fn claim(active: &Arc<AtomicUsize>) -> Option<Self> {
+        active
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| {
+                (count < MAX_CONCURRENT_REQUESTS).then_some(count + 1)
+            })
+            .ok()
+            .map(|_| Self(Arc::clone(active)))
+    }
+}
+
+impl Drop for RequestSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Compare a presented token with the session's without returning early on the first
+/// differing byte, so response timing doesn't reveal how much of a guess was right
+fn token_matches(presented: &str, token: &str) -> bool {
+    let (presented, token) = (presented.as_bytes(), token.as_bytes());
+    presented.len() == token.len()
+        && presented.iter().zip(token).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+impl Endpoints {
+    fn respond(&self, request: &Request) -> Response {
+        let authorized = request
+            .header("authorization")
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|token| token_matches(token, &self.token));
+        if !authorized {
+            return Response::error(401, "Missing or invalid bearer token");
+        }
+        if request.method != "GET" {
+            return Response::error(405, "Only GET is supported");
+        }
+
+        let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+        let result = match segments.as_slice() {
+            ["presets"] => self.list_presets(request),
+            ["presets", id] => self.get_preset(id),
+            ["banks", pedal_type] => self.get_banks(pedal_type),
+            ["devices"] => self.list_devices(),
+            _ => Err(Response::error(404, "Not found")),
+        };
+        result.unwrap_or_else(|error| error)
+    }
+
+    fn list_presets(&self, request: &Request) -> std::result::Result<Response, Response> {
+        let filter = PresetFilter {
+            pedal_type: request.query_param("pedal_type").map(str::to_string),
+            tags: request.query_params("tags"),
+            is_favorite: parse_param(request, "is_favorite")?,
+            search_query: request.query_param("search_query").map(str::to_string),
+            created_after: parse_param(request, "created_after_ts")?,
+            created_before: parse_param(request, "created_before_ts")?,
+            is_archived: parse_param(request, "is_archived")?,
+            metadata_key: request.query_param("metadata_key").map(str::to_string),
+            exclude_ids: request.query_params("exclude_ids").into_iter().map(PresetId::new).collect(),
+            limit: parse_param(request, "limit")?,
+            offset: parse_param(request, "offset")?,
+        };
+        let library = self.library.lock().map_err(|e| Response::error(500, &e.to_string()))?;
+        json(library.list_presets(filter).map_err(preset_error)?)
+    }
+
+    fn get_preset(&self, id: &str) -> std::result::Result<Response, Response> {
+        let library = self.library.lock().map_err(|e| Response::error(500, &e.to_string()))?;
+        json(library.get_preset(&PresetId::new(id.to_string())).map_err(preset_error)?)
+    }
+
+    fn get_banks(&self, pedal_type: &str) -> std::result::Result<Response, Response> {
+        let library = self.library.lock().map_err(|e| Response::error(500, &e.to_string()))?;
+        json(library.get_bank_state(pedal_type).map_err(preset_error)?)
+    }
+
+    fn list_devices(&self) -> std::result::Result<Response, Response> {
+        let manager = self.manager.lock().map_err(|e| Response::error(500, &e.to_string()))?;
+        let devices: Vec<DeviceInfo> = manager.connected_devices().into_iter().map(DeviceInfo::from).collect();
+        json(devices)
+    }
+}
+
+/// Optional typed query parameter; a value that doesn't parse is a 400
+fn parse_param<T: std::str::FromStr>(request: &Request, name: &str) -> std::result::Result<Option<T>, Response> {
+    request
+        .query_param(name)
+        .map(|value| value.parse().map_err(|_| Response::error(400, &format!("Invalid {}: {}", name, value))))
+        .transpose()
+}
+
+fn json(value: impl Serialize) -> std::result::Result<Response, Response> {
+    serde_json::to_string(&value)
+        .map(|body| Response::json(200, body))
+        .map_err(|e| Response::error(500, &e.to_string()))
+}
+
+fn preset_error(error: PresetError) -> Response {
+    match error {
+        PresetError::NotFound { .. } => Response::error(404, &error.to_string()),
+        PresetError::NoBankConfig { .. } => Response::error(400, &error.to_string()),
+        _ => Response::error(500, &error.to_string()),
+    }
+}
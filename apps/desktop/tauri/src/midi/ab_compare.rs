@@ -0,0 +1,128 @@
+// A/B compare buffers
+// Store the current state of a device in A or B, keep tweaking, then flip between the two
+// by ear. Buffers hold the believed state serialized like a preset, are untouched by later
+// parameter edits, and are dropped when the device disconnects.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// One of the two compare buffers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AbSlot {
+    A,
+    B,
+}
+
+impl AbSlot {
+    pub fn other(self) -> Self {
+        match self {
+            AbSlot::A => AbSlot::B,
+            AbSlot::B => AbSlot::A,
+        }
+    }
+}
+
+impl fmt::Display for AbSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AbSlot::A => write!(f, "A"),
+            AbSlot::B => write!(f, "B"),
+        }
+    }
+}
+
+/// What the frontend needs to draw the A/B toggle
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AbStatus {
+    pub a_stored: bool,
+    pub b_stored: bool,
+    /// Buffer equal to the current state - A if both are
+    pub matching: Option<AbSlot>,
+    /// Buffer last stored or recalled
+    pub active: Option<AbSlot>,
+}
+
+/// The A and B buffers for one device
+#[derive(Debug, Clone, Default)]
+pub struct AbBuffers {
+    a: Option<serde_json::Value>,
+    b: Option<serde_json::Value>,
+    active: Option<AbSlot>,
+}
+
+impl AbBuffers {
+    pub fn store(&mut self, slot: AbSlot, state: serde_json::Value) {
+        *self.slot_mut(slot) = Some(state);
+        self.active = Some(slot);
+    }
+
+    pub fn get(&self, slot: AbSlot) -> Option<&serde_json::Value> {
+        match slot {
+            AbSlot::A => self.a.as_ref(),
+            AbSlot::B => self.b.as_ref(),
+        }
+    }
+
+    /// Mark `slot` as the one the device was last set to
+    pub fn set_active(&mut self, slot: AbSlot) {
+        self.active = Some(slot);
+    }
+
+    /// Buffer a swap should recall: the other side from the active one, or A to start
+    pub fn swap_target(&self) -> AbSlot {
+        self.active.map(AbSlot::other).unwrap_or(AbSlot::A)
+    }
+
+    pub fn status(&self, current: &serde_json::Value) -> AbStatus {
+        let matches = |slot| self.get(slot) == Some(current);
+        AbStatus {
+            a_stored: self.a.is_some(),
+            b_stored: self.b.is_some(),
+            matching: [AbSlot::A, AbSlot::B].into_iter().find(|slot| matches(*slot)),
+            active: self.active,
+        }
+    }
+
+    fn slot_mut(&mut self, slot: AbSlot) -> &mut Option<serde_json::Value> {
+        match slot {
+            AbSlot::A => &mut self.a,
+            AbSlot::B => &mut self.b,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_status_reports_stored_and_matching_buffers() {
+        let mut buffers = AbBuffers::default();
+        assert_eq!(buffers.status(&json!({"mix": 1})), AbStatus {
+            a_stored: false,
+            b_stored: false,
+            matching: None,
+            active: None,
+        });
+
+        buffers.store(AbSlot::A, json!({"mix": 1}));
+        buffers.store(AbSlot::B, json!({"mix": 2}));
+
+        let status = buffers.status(&json!({"mix": 2}));
+        assert!(status.a_stored && status.b_stored);
+        assert_eq!(status.matching, Some(AbSlot::B));
+        assert_eq!(buffers.status(&json!({"mix": 3})).matching, None);
+    }
+
+    #[test]
+    fn test_swap_alternates_from_the_active_buffer() {
+        let mut buffers = AbBuffers::default();
+        assert_eq!(buffers.swap_target(), AbSlot::A);
+
+        buffers.store(AbSlot::A, json!({}));
+        assert_eq!(buffers.swap_target(), AbSlot::B);
+        buffers.set_active(AbSlot::B);
+        assert_eq!(buffers.swap_target(), AbSlot::A);
+    }
+}
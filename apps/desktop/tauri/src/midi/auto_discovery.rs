@@ -0,0 +1,196 @@
+// One-click pedal discovery
+// Sends a Universal Device Identity Request to every MIDI port and matches the
+// replies against the identities of supported pedals. Pedals that share an identity
+// (Hologram reports the same one for Microcosm and Chroma Console) are told apart by
+// port name when possible, and reported with lower confidence when not.
+
+use crate::midi::device_detection::is_loop_port;
+use crate::midi::error::MidiResult;
+use crate::midi::identity::{request_identity_with, DeviceIdentity, IdentityRequestOptions, IdentityTransport, MidirTransport};
+use crate::midi::manager::PedalType;
+use serde::Serialize;
+
+/// How long each port gets to answer
+pub const DISCOVERY_TIMEOUT_MS: u64 = 500;
+
+/// An identity reply shared by one or more supported pedals
+struct KnownIdentity {
+    manufacturer_id: &'static [u8],
+    device_family: u16,
+    device_model: u16,
+    /// Most likely first
+    pedal_types: &'static [PedalType],
+}
+
+const KNOWN_IDENTITIES: &[KnownIdentity] = &[
+    // Hologram Electronics - both pedals report family 0x048F, model 0
+    KnownIdentity {
+        manufacturer_id: &[0x00, 0x02, 0x4D],
+        device_family: 0x048F,
+        device_model: 0x0000,
+        pedal_types: &[PedalType::Microcosm, PedalType::ChromaConsole],
+    },
+];
+
+/// A pedal found on a port
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AutoDiscoveryResult {
+    pub device_name: String,
+    pub pedal_type: String,
+    pub midi_channel: u8,
+    /// 1.0 for an unambiguous identity, lower when several pedals share it
+    pub confidence: f32,
+}
+
+/// Pedal type and confidence for an identity reply heard on `port_name`
+fn match_identity(identity: &DeviceIdentity, port_name: &str) -> Option<(PedalType, f32)> {
+    let known = KNOWN_IDENTITIES.iter().find(|known| {
+        known.manufacturer_id == identity.manufacturer_id.as_slice()
+            && known.device_family == identity.device_family
+            && known.device_model == identity.device_model
+    })?;
+
+    match known.pedal_types {
+        [] => None,
+        [only] => Some((only.clone(), 1.0)),
+        candidates => {
+            let port = normalize(port_name);
+            match candidates.iter().find(|pedal_type| port.contains(&normalize(pedal_type.as_str()))) {
+                Some(named) => Some((named.clone(), 0.9)),
+                None => Some((candidates[0].clone(), 1.0 / candidates.len() as f32)),
+            }
+        }
+    }
+}
+
+/// Lowercase alphanumerics only, so "Chroma Console MIDI" contains "ChromaConsole"
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// Pedals usually answer with their MIDI channel as the SysEx device ID
+fn channel_from_device_id(device_id: u8) -> u8 {
+    if device_id < 16 {
+        device_id + 1
+    } else {
+        1
+    }
+}
+
+/// Query every port (except Librarian's own and loopback ports) and report recognized pedals
+pub fn auto_discover_pedals() -> MidiResult<Vec<AutoDiscoveryResult>> {
+    discover_with(&mut MidirTransport)
+}
+
+pub(crate) fn discover_with(transport: &mut impl IdentityTransport) -> MidiResult<Vec<AutoDiscoveryResult>> {
+    let options = IdentityRequestOptions {
+        timeout_ms: DISCOVERY_TIMEOUT_MS,
+        attempts: 1,
+        backoff_ms: 0,
+    };
+
+    let ports = transport.output_port_names()?;
+    let mut results = Vec::new();
+    for port in ports.iter().filter(|port| !is_loop_port(port, &[])) {
+        // One busy or vanished port shouldn't hide the pedals on the others
+        let identity = match request_identity_with(transport, port, options) {
+            Ok(result) => result.identity,
+            Err(e) => {
+                eprintln!("⚠️ Skipping '{}' during discovery: {}", port, e);
+                None
+            }
+        };
+        let Some(identity) = identity else { continue };
+        if let Some((pedal_type, confidence)) = match_identity(&identity, port) {
+            println!("🎯 Discovered {} on '{}' ({:.0}% confidence)", pedal_type.as_str(), port, confidence * 100.0);
+            results.push(AutoDiscoveryResult {
+                device_name: port.clone(),
+                pedal_type: pedal_type.as_str().to_string(),
+                midi_channel: channel_from_device_id(identity.device_id),
+                confidence,
+            });
+        }
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockIdentityTransport;
+
+    /// Hologram identity reply from SysEx device ID `device_id`
+    fn hologram_reply(device_id: u8) -> Vec<u8> {
+        vec![0xF0, 0x7E, device_id, 0x06, 0x02, 0x00, 0x02, 0x4D, 0x0F, 0x09, 0x00, 0x00, 0x01, 0x00, 0xF7]
+    }
+
+    fn transport(ports: &[&str]) -> MockIdentityTransport {
+        let ports: Vec<String> = ports.iter().map(|port| port.to_string()).collect();
+        MockIdentityTransport {
+            output_ports: ports.clone(),
+            input_ports: ports,
+            ..MockIdentityTransport::default()
+        }
+    }
+
+    #[test]
+    fn test_discovers_pedals_by_identity() {
+        let mut transport = transport(&["Chroma Console", "WIDI Jack", "Librarian Output"]);
+        transport.replies_by_port.insert("Chroma Console".to_string(), vec![hologram_reply(2)]);
+        transport.replies_by_port.insert("WIDI Jack".to_string(), vec![hologram_reply(0)]);
+
+        let results = discover_with(&mut transport).unwrap();
+
+        assert_eq!(
+            results,
+            vec![
+                AutoDiscoveryResult {
+                    device_name: "Chroma Console".to_string(),
+                    pedal_type: "ChromaConsole".to_string(),
+                    midi_channel: 3,
+                    confidence: 0.9,
+                },
+                AutoDiscoveryResult {
+                    device_name: "WIDI Jack".to_string(),
+                    pedal_type: "Microcosm".to_string(),
+                    midi_channel: 1,
+                    confidence: 0.5,
+                },
+            ]
+        );
+        // Librarian's own port is never queried
+        assert_eq!(transport.sent.len(), 2);
+    }
+
+    #[test]
+    fn test_unknown_or_silent_ports_are_skipped() {
+        let mut transport = transport(&["Roland Synth", "Silent Pedal"]);
+        transport.replies_by_port.insert(
+            "Roland Synth".to_string(),
+            vec![vec![0xF0, 0x7E, 0x00, 0x06, 0x02, 0x41, 0x00, 0x01, 0x00, 0x02, 0x00, 0x00, 0xF7]],
+        );
+
+        assert!(discover_with(&mut transport).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_failing_port_is_skipped() {
+        let mut transport = transport(&["Busy Interface", "Chroma Console"]);
+        transport.failing_ports.push("Busy Interface".to_string());
+        transport.replies_by_port.insert("Chroma Console".to_string(), vec![hologram_reply(2)]);
+
+        let results = discover_with(&mut transport).unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].device_name, "Chroma Console");
+    }
+
+    #[test]
+    fn test_broadcast_device_id_defaults_to_channel_one() {
+        assert_eq!(channel_from_device_id(0x7F), 1);
+        assert_eq!(channel_from_device_id(15), 16);
+    }
+}
@@ -0,0 +1,272 @@
+// Bypass-all master switch
+// Pedals disagree on what "bypassed" looks like: most Chase Bliss pedals keep an engaged flag
+// that sends 127 for on, the Preamp MK II and CXM 1978 keep a bypass flag that sends 0 to
+// bypass, and the Chroma Console sends 127 on CC 91 to bypass. Each pedal says which of its
+// parameters make up its bypass state, so the manager can snapshot, bypass and restore any
+// mix of connected pedals as ordinary parameter batches.
+
+use crate::midi::pedals::billy_strings_wombtone::BillyStringsWombtoneParameter;
+use crate::midi::pedals::brothers_am::BrothersAmParameter;
+use crate::midi::pedals::chroma_console::{BypassState, ChromaConsoleParameter};
+use crate::midi::pedals::clean::CleanParameter;
+use crate::midi::pedals::cxm1978::Cxm1978Parameter;
+use crate::midi::pedals::dark_world::DarkWorldParameter;
+use crate::midi::pedals::gen_loss_mkii::GenLossMkiiParameter;
+use crate::midi::pedals::habit::HabitParameter;
+use crate::midi::pedals::lossy::LossyParameter;
+use crate::midi::pedals::microcosm::MicrocosmParameter;
+use crate::midi::pedals::mood_mkii::MoodMkiiParameter;
+use crate::midi::pedals::onward::OnwardParameter;
+use crate::midi::pedals::preamp_mk2::PreampMk2Parameter;
+use crate::midi::pedals::reverse_mode_c::ReverseModeCParameter;
+use crate::midi::pedals::source_audio_collider::ColliderParameter;
+use crate::midi::pedals::thermae::ThermaeParameter;
+use crate::midi::pedals::{
+    BillyStringsWombtone, BrothersAm, ChromaConsole, Clean, Collider, Cxm1978, DarkWorld, GenLossMkii, Habit, Lossy,
+    Microcosm, MoodMkii, Onward, PreampMk2, ReverseModeC, Thermae,
+};
+use crate::midi::PedalParameter;
+use serde::Serialize;
+
+/// How a pedal is bypassed and how its current bypass state is put back
+pub trait BypassControl {
+    /// Parameters that re-send the pedal's current bypass-related state
+    fn bypass_snapshot(&self) -> Vec<PedalParameter>;
+
+    /// Parameters that bypass the pedal
+    fn bypass_parameters(&self) -> Vec<PedalParameter>;
+}
+
+/// Outcome of bypassing or restoring one device
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BypassResult {
+    pub device_name: String,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+impl BypassResult {
+    pub fn new(device_name: &str, result: Result<(), String>) -> Self {
+        Self {
+            device_name: device_name.to_string(),
+            success: result.is_ok(),
+            error: result.err(),
+        }
+    }
+}
+
+impl BypassControl for Microcosm {
+    fn bypass_snapshot(&self) -> Vec<PedalParameter> {
+        vec![PedalParameter::Microcosm(MicrocosmParameter::Bypass(self.state.bypass))]
+    }
+
+    fn bypass_parameters(&self) -> Vec<PedalParameter> {
+        vec![PedalParameter::Microcosm(MicrocosmParameter::Bypass(false))]
+    }
+}
+
+impl BypassControl for GenLossMkii {
+    fn bypass_snapshot(&self) -> Vec<PedalParameter> {
+        vec![PedalParameter::GenLossMkii(GenLossMkiiParameter::Bypass(self.state.bypass))]
+    }
+
+    fn bypass_parameters(&self) -> Vec<PedalParameter> {
+        vec![PedalParameter::GenLossMkii(GenLossMkiiParameter::Bypass(false))]
+    }
+}
+
+impl BypassControl for ChromaConsole {
+    fn bypass_snapshot(&self) -> Vec<PedalParameter> {
+        vec![PedalParameter::ChromaConsole(ChromaConsoleParameter::BypassState(self.state.bypass_state))]
+    }
+
+    // Inverted: in standard footswitch mode this sends 127 on CC 91
+    fn bypass_parameters(&self) -> Vec<PedalParameter> {
+        vec![PedalParameter::ChromaConsole(ChromaConsoleParameter::BypassState(BypassState::Bypass))]
+    }
+}
+
+// The Preamp MK II and CXM 1978 flags mean "bypassed" (true sends 0)
+impl BypassControl for PreampMk2 {
+    fn bypass_snapshot(&self) -> Vec<PedalParameter> {
+        vec![PedalParameter::PreampMk2(PreampMk2Parameter::Bypass(self.state.bypass))]
+    }
+
+    fn bypass_parameters(&self) -> Vec<PedalParameter> {
+        vec![PedalParameter::PreampMk2(PreampMk2Parameter::Bypass(true))]
+    }
+}
+
+impl BypassControl for Cxm1978 {
+    fn bypass_snapshot(&self) -> Vec<PedalParameter> {
+        vec![PedalParameter::Cxm1978(Cxm1978Parameter::Bypass(self.state.bypass))]
+    }
+
+    fn bypass_parameters(&self) -> Vec<PedalParameter> {
+        vec![PedalParameter::Cxm1978(Cxm1978Parameter::Bypass(true))]
+    }
+}
+
+impl BypassControl for MoodMkii {
+    fn bypass_snapshot(&self) -> Vec<PedalParameter> {
+        vec![
+            PedalParameter::MoodMkii(MoodMkiiParameter::BypassLeft(self.state.bypass_left)),
+            PedalParameter::MoodMkii(MoodMkiiParameter::BypassRight(self.state.bypass_right)),
+        ]
+    }
+
+    fn bypass_parameters(&self) -> Vec<PedalParameter> {
+        vec![
+            PedalParameter::MoodMkii(MoodMkiiParameter::BypassLeft(false)),
+            PedalParameter::MoodMkii(MoodMkiiParameter::BypassRight(false)),
+        ]
+    }
+}
+
+impl BypassControl for BillyStringsWombtone {
+    fn bypass_snapshot(&self) -> Vec<PedalParameter> {
+        vec![PedalParameter::BillyStringsWombtone(BillyStringsWombtoneParameter::Bypass(self.state.bypass))]
+    }
+
+    fn bypass_parameters(&self) -> Vec<PedalParameter> {
+        vec![PedalParameter::BillyStringsWombtone(BillyStringsWombtoneParameter::Bypass(false))]
+    }
+}
+
+impl BypassControl for Lossy {
+    fn bypass_snapshot(&self) -> Vec<PedalParameter> {
+        vec![PedalParameter::Lossy(LossyParameter::Bypass(self.state.bypass))]
+    }
+
+    fn bypass_parameters(&self) -> Vec<PedalParameter> {
+        vec![PedalParameter::Lossy(LossyParameter::Bypass(false))]
+    }
+}
+
+impl BypassControl for BrothersAm {
+    fn bypass_snapshot(&self) -> Vec<PedalParameter> {
+        vec![
+            PedalParameter::BrothersAm(BrothersAmParameter::Channel1Bypass(self.state.channel1_bypass)),
+            PedalParameter::BrothersAm(BrothersAmParameter::Channel2Bypass(self.state.channel2_bypass)),
+        ]
+    }
+
+    fn bypass_parameters(&self) -> Vec<PedalParameter> {
+        vec![
+            PedalParameter::BrothersAm(BrothersAmParameter::Channel1Bypass(false)),
+            PedalParameter::BrothersAm(BrothersAmParameter::Channel2Bypass(false)),
+        ]
+    }
+}
+
+impl BypassControl for ReverseModeC {
+    fn bypass_snapshot(&self) -> Vec<PedalParameter> {
+        vec![PedalParameter::ReverseModeC(ReverseModeCParameter::Bypass(self.state.bypass))]
+    }
+
+    fn bypass_parameters(&self) -> Vec<PedalParameter> {
+        vec![PedalParameter::ReverseModeC(ReverseModeCParameter::Bypass(false))]
+    }
+}
+
+impl BypassControl for Clean {
+    fn bypass_snapshot(&self) -> Vec<PedalParameter> {
+        vec![PedalParameter::Clean(CleanParameter::Bypass(self.state.bypass))]
+    }
+
+    fn bypass_parameters(&self) -> Vec<PedalParameter> {
+        vec![PedalParameter::Clean(CleanParameter::Bypass(false))]
+    }
+}
+
+impl BypassControl for Onward {
+    fn bypass_snapshot(&self) -> Vec<PedalParameter> {
+        vec![
+            PedalParameter::Onward(OnwardParameter::FreezeBypass(self.state.freeze_bypass)),
+            PedalParameter::Onward(OnwardParameter::GlitchBypass(self.state.glitch_bypass)),
+        ]
+    }
+
+    fn bypass_parameters(&self) -> Vec<PedalParameter> {
+        vec![
+            PedalParameter::Onward(OnwardParameter::FreezeBypass(false)),
+            PedalParameter::Onward(OnwardParameter::GlitchBypass(false)),
+        ]
+    }
+}
+
+impl BypassControl for Thermae {
+    fn bypass_snapshot(&self) -> Vec<PedalParameter> {
+        vec![PedalParameter::Thermae(ThermaeParameter::Bypass(self.state.bypass))]
+    }
+
+    fn bypass_parameters(&self) -> Vec<PedalParameter> {
+        vec![PedalParameter::Thermae(ThermaeParameter::Bypass(false))]
+    }
+}
+
+impl BypassControl for DarkWorld {
+    fn bypass_snapshot(&self) -> Vec<PedalParameter> {
+        vec![PedalParameter::DarkWorld(DarkWorldParameter::Bypass(self.state.bypass))]
+    }
+
+    fn bypass_parameters(&self) -> Vec<PedalParameter> {
+        vec![PedalParameter::DarkWorld(DarkWorldParameter::Bypass(false))]
+    }
+}
+
+impl BypassControl for Habit {
+    fn bypass_snapshot(&self) -> Vec<PedalParameter> {
+        vec![PedalParameter::Habit(HabitParameter::Bypass(self.state.bypass))]
+    }
+
+    fn bypass_parameters(&self) -> Vec<PedalParameter> {
+        vec![PedalParameter::Habit(HabitParameter::Bypass(false))]
+    }
+}
+
+impl BypassControl for Collider {
+    fn bypass_snapshot(&self) -> Vec<PedalParameter> {
+        vec![PedalParameter::Collider(ColliderParameter::Bypass(self.state.bypass))]
+    }
+
+    fn bypass_parameters(&self) -> Vec<PedalParameter> {
+        vec![PedalParameter::Collider(ColliderParameter::Bypass(true))]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::midi::pedals::chroma_console::BypassMode;
+
+    fn cc(param: &PedalParameter) -> (u8, u8) {
+        match param {
+            PedalParameter::ChromaConsole(p) => p.to_cc_message_for_mode(BypassMode::Standard).unwrap(),
+            PedalParameter::PreampMk2(p) => (p.cc_number(), p.cc_value()),
+            PedalParameter::Microcosm(p) => (p.cc_number(), p.cc_value()),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_chroma_bypass_uses_inverted_values() {
+        let mut chroma = ChromaConsole::new(1);
+        chroma.state.bypass_state = BypassState::Engaged;
+
+        assert_eq!(cc(&chroma.bypass_parameters()[0]), (91, 127));
+        assert_eq!(cc(&chroma.bypass_snapshot()[0]), (91, 0));
+    }
+
+    #[test]
+    fn test_bypass_values_follow_each_pedals_polarity() {
+        let mut microcosm = Microcosm::new(1);
+        microcosm.state.bypass = true;
+        assert_eq!(cc(&microcosm.bypass_parameters()[0]), (102, 0));
+        assert_eq!(cc(&microcosm.bypass_snapshot()[0]), (102, 127));
+
+        let preamp = PreampMk2::new(1);
+        assert_eq!(cc(&preamp.bypass_parameters()[0]), (102, 0));
+        assert_eq!(cc(&preamp.bypass_snapshot()[0]), (102, 127));
+    }
+}
@@ -0,0 +1,196 @@
+// CC decoding for SysEx reverse engineering
+// Interprets raw bytes as (CC number, value) pairs and names them using a pedal's
+// CC -> state mapping. Used by the sysex-discovery tool to make sense of state dumps.
+
+use crate::midi::manager::PedalType;
+use crate::midi::pedals::chroma_console::ChromaConsoleState;
+use crate::midi::pedals::cxm1978::Cxm1978State;
+use crate::midi::pedals::mode_names;
+use crate::midi::pedals::preamp_mk2::PreampMk2State;
+use serde::Serialize;
+
+/// Header bytes tried when aligning a payload to CC pairs
+const MAX_HEADER_BYTES: usize = 8;
+
+/// One (CC number, value) pair from a payload
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedCc {
+    pub cc_number: u8,
+    pub value: u8,
+    /// State field(s) the CC controls, if known for the pedal
+    pub parameter: Option<String>,
+    /// The field's value after applying the CC
+    pub meaning: Option<String>,
+}
+
+/// Payload decoded at the alignment that matched the most known CCs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedPayload {
+    pub header_len: usize,
+    pub entries: Vec<DecodedCc>,
+}
+
+impl DecodedPayload {
+    pub fn known_count(&self) -> usize {
+        self.entries.iter().filter(|e| e.parameter.is_some()).count()
+    }
+}
+
+/// Pedals whose state can be rebuilt from incoming CCs
+pub fn decode_supported(pedal_type: &PedalType) -> bool {
+    matches!(
+        pedal_type,
+        PedalType::ChromaConsole | PedalType::PreampMk2 | PedalType::Cxm1978
+    )
+}
+
+/// Decode one CC for a pedal. Returns an unnamed entry for unsupported pedals or unknown CCs.
+pub fn decode_cc(pedal_type: &PedalType, cc_number: u8, value: u8) -> DecodedCc {
+    let (parameter, meaning) = match pedal_type {
+        PedalType::ChromaConsole => describe(cc_number, value, ChromaConsoleState::update_from_cc),
+        PedalType::PreampMk2 => describe(cc_number, value, PreampMk2State::update_from_cc),
+        PedalType::Cxm1978 => describe(cc_number, value, Cxm1978State::update_from_cc),
+        _ => (None, None),
+    };
+    // Arcade buttons read as the manual's labels ("Jump to 5", not "Five")
+    let meaning = match (&parameter, value) {
+        (Some(field), 1..=3) => mode_names(pedal_type)
+            .into_iter()
+            .find(|names| names.parameter == field)
+            .map(|names| names.names[value as usize - 1].to_string())
+            .or(meaning),
+        _ => meaning,
+    };
+
+    DecodedCc {
+        cc_number,
+        value,
+        parameter,
+        meaning,
+    }
+}
+
+/// Interpret a SysEx message as CC pairs. F0/F7 are stripped, then each header length
+/// up to `MAX_HEADER_BYTES` is tried and the best-matching alignment wins.
+pub fn decode_payload(pedal_type: &PedalType, message: &[u8]) -> DecodedPayload {
+    let body = message.strip_prefix(&[0xF0]).unwrap_or(message);
+    let body = body.strip_suffix(&[0xF7]).unwrap_or(body);
+
+    let mut best: Option<DecodedPayload> = None;
+    for header_len in 0..=MAX_HEADER_BYTES.min(body.len()) {
+        let entries = body[header_len..]
+            .chunks_exact(2)
+            .map(|pair| decode_cc(pedal_type, pair[0], pair[1]))
+            .collect();
+        let candidate = DecodedPayload { header_len, entries };
+
+        // Most known CCs wins; on a tie, the alignment with fewer unknown pairs
+        let score = |p: &DecodedPayload| (p.known_count(), std::cmp::Reverse(p.entries.len() - p.known_count()));
+        if best.as_ref().is_none_or(|b| score(&candidate) > score(b)) {
+            best = Some(candidate);
+        }
+    }
+
+    best.unwrap_or(DecodedPayload {
+        header_len: 0,
+        entries: Vec::new(),
+    })
+}
+
+/// Apply a CC to a default state and report which fields changed.
+/// If the value happens to match the default, probe with a different value to find the field.
+fn describe<S: Default + Serialize>(
+    cc_number: u8,
+    value: u8,
+    update_from_cc: fn(&mut S, u8, u8),
+) -> (Option<String>, Option<String>) {
+    let apply = |v: u8| {
+        let mut state = S::default();
+        update_from_cc(&mut state, cc_number, v);
+        serde_json::to_value(&state).unwrap_or_default()
+    };
+
+    let base = serde_json::to_value(S::default()).unwrap_or_default();
+    let after = apply(value);
+
+    let mut fields = changed_fields(&base, &after);
+    if fields.is_empty() {
+        let probe = apply(if value < 64 { 127 } else { 0 });
+        fields = changed_fields(&base, &probe);
+    }
+
+    if fields.is_empty() {
+        return (None, None);
+    }
+
+    let meaning = fields
+        .iter()
+        .map(|field| match &after[field.as_str()] {
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    (Some(fields.join(", ")), Some(meaning))
+}
+
+fn changed_fields(before: &serde_json::Value, after: &serde_json::Value) -> Vec<String> {
+    let (Some(before), Some(after)) = (before.as_object(), after.as_object()) else {
+        return Vec::new();
+    };
+
+    after
+        .iter()
+        .filter(|(key, value)| before.get(*key) != Some(value))
+        .map(|(key, _)| key.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CC_TILT: u8 = 64;  // Chroma Console Tilt
+
+    #[test]
+    fn test_decode_known_cc() {
+        let decoded = decode_cc(&PedalType::ChromaConsole, CC_TILT, 100);
+        assert_eq!(decoded.parameter.as_deref(), Some("tilt"));
+        assert_eq!(decoded.meaning.as_deref(), Some("100"));
+    }
+
+    #[test]
+    fn test_decode_value_matching_default_still_named() {
+        let default_tilt = ChromaConsoleState::default().tilt;
+        let decoded = decode_cc(&PedalType::ChromaConsole, CC_TILT, default_tilt);
+        assert_eq!(decoded.parameter.as_deref(), Some("tilt"));
+        assert_eq!(decoded.meaning, Some(default_tilt.to_string()));
+    }
+
+    #[test]
+    fn test_arcade_buttons_decode_to_labels() {
+        let decoded = decode_cc(&PedalType::PreampMk2, 25, 3);
+        assert_eq!(decoded.parameter.as_deref(), Some("diode_clipping"));
+        assert_eq!(decoded.meaning.as_deref(), Some("Germanium"));
+        assert_eq!(decode_cc(&PedalType::Cxm1978, 22, 3).meaning.as_deref(), Some("Jump to 5"));
+    }
+
+    #[test]
+    fn test_unknown_cc_and_unsupported_pedal() {
+        assert_eq!(decode_cc(&PedalType::ChromaConsole, 127, 1).parameter, None);
+        assert_eq!(decode_cc(&PedalType::Microcosm, 6, 64).parameter, None);
+        assert!(!decode_supported(&PedalType::Microcosm));
+    }
+
+    #[test]
+    fn test_payload_alignment_skips_header() {
+        // F0, 3-byte manufacturer ID, command byte, then two CC pairs
+        let message = [0xF0, 0x00, 0x20, 0x33, 0x01, CC_TILT, 90, CC_TILT, 20, 0xF7];
+        let decoded = decode_payload(&PedalType::ChromaConsole, &message);
+
+        assert_eq!(decoded.header_len, 4);
+        assert_eq!(decoded.known_count(), 2);
+        assert_eq!(decoded.entries[0].value, 90);
+    }
+}
@@ -0,0 +1,92 @@
+// Validated MIDI CC data byte
+// A CC value is 7 bits. A plain u8 let 128-255 through from the frontend, and the high bit
+// then turned the data byte into a status byte on the wire. CcValue can only hold 0-127:
+// construction is checked, and deserializing anything larger fails with the value named.
+
+use crate::midi::error::{MidiError, MidiResult};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+
+/// A MIDI CC value, 0-127
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(transparent)]
+pub struct CcValue(u8);
+
+impl CcValue {
+    pub const MIN: CcValue = CcValue(0);
+    pub const MAX: CcValue = CcValue(127);
+
+    /// `value` if it is 0-127
+    pub fn new(value: u8) -> MidiResult<Self> {
+        if value > Self::MAX.0 {
+            return Err(MidiError::InvalidValue {
+                expected: "a CC value (0-127)".to_string(),
+                actual: value.into(),
+            });
+        }
+        Ok(Self(value))
+    }
+
+    /// `value`, with anything above 127 treated as 127
+    pub fn clamped(value: u8) -> Self {
+        Self(value.min(Self::MAX.0))
+    }
+
+    pub fn get(self) -> u8 {
+        self.0
+    }
+}
+
+impl TryFrom<u8> for CcValue {
+    type Error = MidiError;
+
+    fn try_from(value: u8) -> MidiResult<Self> {
+        Self::new(value)
+    }
+}
+
+impl From<CcValue> for u8 {
+    fn from(value: CcValue) -> u8 {
+        value.0
+    }
+}
+
+impl fmt::Display for CcValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<'de> Deserialize<'de> for CcValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Read wider than u8 so 200 and 300 get the same message, not a u8 overflow error
+        let value = u64::deserialize(deserializer)?;
+        u8::try_from(value)
+            .ok()
+            .filter(|value| *value <= Self::MAX.0)
+            .map(Self)
+            .ok_or_else(|| serde::de::Error::custom(format!("CC value {} is out of range (0-127)", value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_construction_is_checked() {
+        assert_eq!(CcValue::new(127).unwrap().get(), 127);
+        assert!(matches!(CcValue::new(128), Err(MidiError::InvalidValue { actual: 128, .. })));
+        assert_eq!(CcValue::clamped(200), CcValue::MAX);
+    }
+
+    #[test]
+    fn test_out_of_range_values_are_rejected_on_deserialize() {
+        assert_eq!(serde_json::from_str::<CcValue>("64").unwrap().get(), 64);
+
+        let err = serde_json::from_str::<CcValue>("200").unwrap_err().to_string();
+        assert!(err.contains("CC value 200 is out of range (0-127)"), "{}", err);
+        assert!(serde_json::from_str::<CcValue>("300").is_err());
+        assert!(serde_json::from_str::<CcValue>("-1").is_err());
+    }
+}
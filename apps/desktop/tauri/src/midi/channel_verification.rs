@@ -0,0 +1,211 @@
+// MIDI channel verification
+// Connecting on the wrong channel is the most common setup mistake and fails silently,
+// so after connecting we watch the device's input briefly to see which channel it talks on.
+
+use super::routing::SharedInputMode;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// How long to listen for traffic after the probe is sent
+pub const DEFAULT_VERIFY_TIMEOUT_MS: u64 = 500;
+
+#[derive(Debug, Default)]
+struct ActivityLog {
+    configured_seen: bool,
+    other_channel: Option<u8>,
+    /// Control Changes heard on an accepted channel since connecting
+    configured_cc_count: u64,
+    /// Channel voice messages of any kind heard on an accepted channel since connecting
+    configured_message_count: u64,
+}
+
+/// Channel traffic observed on a device's input port (shared with the input callback)
+#[derive(Debug, Clone)]
+pub struct ChannelActivity {
+    configured_channel: u8,
+    input_mode: SharedInputMode,
+    log: Arc<Mutex<ActivityLog>>,
+}
+
+impl ChannelActivity {
+    pub fn new(configured_channel: u8) -> Self {
+        Self::with_input_mode(configured_channel, SharedInputMode::default())
+    }
+
+    /// Activity for a device whose input mode may accept more than its configured channel.
+    /// Traffic on any accepted channel counts as the device answering.
+    pub(crate) fn with_input_mode(configured_channel: u8, input_mode: SharedInputMode) -> Self {
+        Self {
+            configured_channel,
+            input_mode,
+            log: Arc::new(Mutex::new(ActivityLog::default())),
+        }
+    }
+
+    pub fn configured_channel(&self) -> u8 {
+        self.configured_channel
+    }
+
+    /// Record an incoming message by status byte. Only channel voice messages count.
+    pub fn record(&self, status: u8) {
+        if !(0x80..=0xEF).contains(&status) {
+            return;
+        }
+
+        let channel = (status & 0x0F) + 1;
+        let accepted = self.input_mode.get().accepts(self.configured_channel, channel);
+        let mut log = self.log.lock().unwrap();
+        if accepted {
+            log.configured_seen = true;
+            log.configured_message_count += 1;
+            if status & 0xF0 == 0xB0 {
+                log.configured_cc_count += 1;
+            }
+        } else {
+            log.other_channel = Some(channel);
+        }
+    }
+
+    /// Start a new verification window. A channel already confirmed stays confirmed.
+    pub fn begin_window(&self) {
+        self.log.lock().unwrap().other_channel = None;
+    }
+
+    /// Has the device sent anything on a channel its input mode accepts?
+    pub fn verified(&self) -> bool {
+        self.log.lock().unwrap().configured_seen
+    }
+
+    /// How many CCs the device has sent on accepted channels. Compare two readings
+    /// to tell whether the pedal answered something in between.
+    pub fn cc_count(&self) -> u64 {
+        self.log.lock().unwrap().configured_cc_count
+    }
+
+    /// How many channel voice messages of any kind the device has sent on accepted channels
+    pub fn message_count(&self) -> u64 {
+        self.log.lock().unwrap().configured_message_count
+    }
+
+    /// Channel the device seems to be on instead, if it never used an accepted one
+    pub fn observed_mismatch(&self) -> Option<u8> {
+        let log = self.log.lock().unwrap();
+        if log.configured_seen {
+            None
+        } else {
+            log.other_channel
+        }
+    }
+}
+
+/// Channel mismatch payload for frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelMismatchEvent {
+    pub device_name: String,
+    pub configured_channel: u8,
+    pub observed_channel: u8,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::midi::routing::InputChannelMode;
+
+    #[test]
+    fn test_configured_channel_verifies() {
+        let activity = ChannelActivity::new(2);
+        assert!(!activity.verified());
+
+        activity.record(0xB1); // CC on channel 2
+        assert!(activity.verified());
+        assert_eq!(activity.observed_mismatch(), None);
+    }
+
+    #[test]
+    fn test_other_channel_reported_as_mismatch() {
+        let activity = ChannelActivity::new(1);
+        activity.record(0xC3); // PC on channel 4
+
+        assert!(!activity.verified());
+        assert_eq!(activity.observed_mismatch(), Some(4));
+
+        // New window forgets the stray traffic
+        activity.begin_window();
+        assert_eq!(activity.observed_mismatch(), None);
+    }
+
+    #[test]
+    fn test_system_messages_ignored() {
+        let activity = ChannelActivity::new(1);
+        activity.record(0xF0); // SysEx
+        activity.record(0xF8); // Clock
+
+        assert!(!activity.verified());
+        assert_eq!(activity.observed_mismatch(), None);
+    }
+
+    #[test]
+    fn test_cc_count_only_counts_configured_channel_ccs() {
+        let activity = ChannelActivity::new(1);
+        activity.record(0xB0); // CC on channel 1
+        activity.record(0xC0); // PC on channel 1
+        activity.record(0xB3); // CC on channel 4
+        activity.record(0xB0);
+
+        assert_eq!(activity.cc_count(), 2);
+        assert_eq!(activity.message_count(), 3);
+    }
+
+    #[test]
+    fn test_verified_is_sticky_across_windows() {
+        let activity = ChannelActivity::new(1);
+        activity.record(0xB0);
+        activity.begin_window();
+        activity.record(0xB5);
+
+        assert!(activity.verified());
+        assert_eq!(activity.observed_mismatch(), None);
+    }
+
+    #[test]
+    fn test_accepted_channels_count_as_configured() {
+        let mode = SharedInputMode::default();
+        mode.set(InputChannelMode::Channels { channels: vec![3, 5] });
+        let activity = ChannelActivity::with_input_mode(1, mode.clone());
+
+        activity.record(0xB2); // CC on channel 3
+        activity.record(0xC4); // PC on channel 5
+        assert!(activity.verified());
+        assert_eq!(activity.cc_count(), 1);
+        assert_eq!(activity.message_count(), 2);
+        assert_eq!(activity.observed_mismatch(), None);
+
+        // Channel 1 isn't in the list, so it's stray traffic like any other
+        activity.record(0xB0);
+        assert_eq!(activity.message_count(), 2);
+    }
+
+    #[test]
+    fn test_omni_never_reports_mismatch() {
+        let mode = SharedInputMode::default();
+        mode.set(InputChannelMode::Omni);
+        let activity = ChannelActivity::with_input_mode(1, mode);
+
+        activity.record(0xB9);
+        assert!(activity.verified());
+        assert_eq!(activity.observed_mismatch(), None);
+    }
+
+    #[test]
+    fn test_mode_change_applies_to_later_messages() {
+        let mode = SharedInputMode::default();
+        let activity = ChannelActivity::with_input_mode(1, mode.clone());
+
+        activity.record(0xB3);
+        assert_eq!(activity.observed_mismatch(), Some(4));
+
+        mode.set(InputChannelMode::Omni);
+        activity.record(0xB3);
+        assert!(activity.verified());
+    }
+}
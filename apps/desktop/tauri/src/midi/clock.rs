@@ -0,0 +1,31 @@
+// Cancellable waits
+// Timers that outlive the command that started them (one-shot captures, scheduled
+// recalls, bank dumps) wait through a `Clock`, so tests can swap in one that only
+// moves when told to.
+
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+/// Waits out a duration, waking early if it is cancelled
+pub trait Clock: Send + Sync {
+    /// Returns true if the full duration passed, false if cancelled first
+    fn wait(&self, duration: Duration, cancel: &Receiver<()>) -> bool;
+
+    /// Time since a fixed starting point, for deadlines that outlive one wait
+    fn now(&self) -> Duration;
+}
+
+/// Wall-clock waits
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn wait(&self, duration: Duration, cancel: &Receiver<()>) -> bool {
+        matches!(cancel.recv_timeout(duration), Err(RecvTimeoutError::Timeout))
+    }
+
+    fn now(&self) -> Duration {
+        static STARTED: OnceLock<Instant> = OnceLock::new();
+        STARTED.get_or_init(Instant::now).elapsed()
+    }
+}
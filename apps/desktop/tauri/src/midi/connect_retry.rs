@@ -0,0 +1,247 @@
+// Connect with retries
+// On macOS a hot-plugged pedal's CoreMIDI port can take a second or two to appear, so the
+// first connect fails with DeviceNotFound. Connect commands retry those failures with
+// exponential backoff, locking the manager only for each attempt - never while sleeping -
+// and report every attempt as a "connection-attempt" event so the UI can show "retrying…".
+
+use crate::midi::error::{MidiError, MidiResult};
+use crate::midi::manager::{MidiManager, SharedMidiManager};
+use serde::Serialize;
+use std::thread;
+use std::time::Duration;
+
+/// Retries after the first attempt when a connect command doesn't say
+pub const DEFAULT_CONNECT_RETRIES: u32 = 3;
+
+/// Pause before the first retry when a connect command doesn't say; doubles each retry
+pub const DEFAULT_INITIAL_BACKOFF_MS: u64 = 250;
+
+/// Event emitted for each connect attempt
+pub const CONNECTION_ATTEMPT_EVENT: &str = "connection-attempt";
+
+/// How hard a connect tries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryOptions {
+    /// Attempts after the first; 0 tries once
+    pub retries: u32,
+    pub initial_backoff: Duration,
+}
+
+impl RetryOptions {
+    /// Options from a command's optional parameters, defaulting whatever is missing
+    pub fn from_params(retries: Option<u32>, initial_backoff_ms: Option<u64>) -> Self {
+        Self {
+            retries: retries.unwrap_or(DEFAULT_CONNECT_RETRIES),
+            initial_backoff: Duration::from_millis(initial_backoff_ms.unwrap_or(DEFAULT_INITIAL_BACKOFF_MS)),
+        }
+    }
+
+    /// Pause before retry `retry` (1-based): the initial backoff, doubled each time
+    fn backoff(&self, retry: u32) -> Duration {
+        self.initial_backoff.saturating_mul(1 << (retry - 1).min(16))
+    }
+}
+
+impl Default for RetryOptions {
+    fn default() -> Self {
+        Self::from_params(None, None)
+    }
+}
+
+/// Where a connect attempt left things
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AttemptStatus {
+    Connected,
+    /// Failed, another attempt follows after `retry_in_ms`
+    Retrying,
+    /// Failed for good
+    Failed,
+}
+
+/// Payload of a "connection-attempt" event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionAttempt {
+    pub device_name: String,
+    /// 1-based
+    pub attempt: u32,
+    pub max_attempts: u32,
+    pub status: AttemptStatus,
+    pub error: Option<String>,
+    pub retry_in_ms: Option<u64>,
+}
+
+/// A successful connect: how many attempts it took and why the earlier ones failed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectOutcome {
+    pub attempts: u32,
+    pub failed_attempts: Vec<String>,
+}
+
+/// Failures worth waiting out - the port not being there (yet) or refusing to open
+pub fn is_retryable(error: &MidiError) -> bool {
+    matches!(error, MidiError::DeviceNotFound(_) | MidiError::ConnectionFailed(_))
+}
+
+/// Run `connect` against the shared manager until it succeeds, fails with an error that
+/// isn't retryable, or runs out of retries. The manager is locked only for each attempt
+/// (each one journaled as `operation`). A final failure names the number of attempts.
+pub fn connect_with_retry(
+    manager: &SharedMidiManager,
+    device_name: &str,
+    operation: &str,
+    options: RetryOptions,
+    mut connect: impl FnMut(&mut MidiManager) -> MidiResult<()>,
+) -> MidiResult<ConnectOutcome> {
+    let max_attempts = options.retries.saturating_add(1);
+    let mut failed_attempts = Vec::new();
+
+    for attempt in 1..=max_attempts {
+        let backoff = {
+            let mut manager = manager.lock().map_err(|e| MidiError::Other(e.to_string()))?;
+            manager.begin_journal_operation(operation);
+            let result = connect(&mut manager);
+            let mut event = ConnectionAttempt {
+                device_name: device_name.to_string(),
+                attempt,
+                max_attempts,
+                status: AttemptStatus::Connected,
+                error: None,
+                retry_in_ms: None,
+            };
+
+            match result {
+                Ok(()) => {
+                    manager.emit_connection_attempt(&event);
+                    return Ok(ConnectOutcome { attempts: attempt, failed_attempts });
+                }
+                Err(e) if attempt < max_attempts && is_retryable(&e) => {
+                    let backoff = options.backoff(attempt);
+                    println!("⏳ Connecting '{}' failed ({}), retrying in {:?}", device_name, e, backoff);
+                    event.status = AttemptStatus::Retrying;
+                    event.error = Some(e.to_string());
+                    event.retry_in_ms = Some(backoff.as_millis() as u64);
+                    manager.emit_connection_attempt(&event);
+                    failed_attempts.push(e.to_string());
+                    backoff
+                }
+                Err(e) => {
+                    event.status = AttemptStatus::Failed;
+                    event.error = Some(e.to_string());
+                    manager.emit_connection_attempt(&event);
+                    if attempt == 1 {
+                        return Err(e);
+                    }
+                    return Err(MidiError::ConnectionFailed(format!("{} (after {} attempts)", e, attempt)));
+                }
+            }
+        };
+        // The lock is released - other commands keep working while we wait
+        tokio::task::block_in_place(|| thread::sleep(backoff));
+    }
+    unreachable!("the last attempt always returns")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::midi::manager::PedalType;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    fn shared_manager() -> SharedMidiManager {
+        Arc::new(Mutex::new(MidiManager::new().unwrap()))
+    }
+
+    fn options(retries: u32, backoff_ms: u64) -> RetryOptions {
+        RetryOptions { retries, initial_backoff: Duration::from_millis(backoff_ms) }
+    }
+
+    #[test]
+    fn test_connects_once_the_port_appears_on_the_third_poll() {
+        let manager = shared_manager();
+        let mut polls = 0;
+        let outcome = connect_with_retry(&manager, "Microcosm", "connect_microcosm", options(3, 1), |manager| {
+            polls += 1;
+            if polls < 3 {
+                return Err(MidiError::DeviceNotFound("Microcosm".to_string()));
+            }
+            manager.connect_fake(PedalType::Microcosm, "Microcosm", 1)
+        })
+        .unwrap();
+
+        assert_eq!(outcome.attempts, 3);
+        assert_eq!(outcome.failed_attempts.len(), 2);
+        assert!(manager.lock().unwrap().device_pedal_type("Microcosm").is_ok());
+    }
+
+    #[test]
+    fn test_gives_up_after_the_last_retry() {
+        let manager = shared_manager();
+        let mut polls = 0;
+        let error = connect_with_retry(&manager, "Microcosm", "connect_microcosm", options(2, 1), |_| {
+            polls += 1;
+            Err(MidiError::ConnectionFailed("port busy".to_string()))
+        })
+        .unwrap_err();
+
+        assert_eq!(polls, 3);
+        assert!(error.to_string().contains("after 3 attempts"), "{}", error);
+    }
+
+    #[test]
+    fn test_other_errors_are_not_retried() {
+        let manager = shared_manager();
+        let mut polls = 0;
+        let error = connect_with_retry(&manager, "Microcosm", "connect_microcosm", options(3, 1), |manager| {
+            polls += 1;
+            manager.connect_fake(PedalType::Microcosm, "Microcosm", 17)
+        })
+        .unwrap_err();
+
+        assert_eq!(polls, 1);
+        assert!(matches!(error, MidiError::InvalidChannel(17)));
+    }
+
+    #[test]
+    fn test_manager_is_unlocked_while_backing_off() {
+        let manager = shared_manager();
+        let locked_during_backoff = Arc::new(AtomicBool::new(false));
+        let first_attempt_done = Arc::new(AtomicBool::new(false));
+        let other = {
+            let manager = manager.clone();
+            let locked_during_backoff = locked_during_backoff.clone();
+            let first_attempt_done = first_attempt_done.clone();
+            thread::spawn(move || {
+                while !first_attempt_done.load(Ordering::SeqCst) {
+                    thread::yield_now();
+                }
+                let _manager = manager.lock().unwrap();
+                locked_during_backoff.store(true, Ordering::SeqCst);
+            })
+        };
+
+        let mut polls = 0;
+        connect_with_retry(&manager, "Microcosm", "connect_microcosm", options(1, 200), |_| {
+            polls += 1;
+            if polls == 1 {
+                first_attempt_done.store(true, Ordering::SeqCst);
+                return Err(MidiError::DeviceNotFound("Microcosm".to_string()));
+            }
+            assert!(locked_during_backoff.load(Ordering::SeqCst));
+            Ok(())
+        })
+        .unwrap();
+        other.join().unwrap();
+    }
+
+    #[test]
+    fn test_backoff_doubles() {
+        let options = options(3, 250);
+        let backoffs: Vec<u128> = (1..=3).map(|retry| options.backoff(retry).as_millis()).collect();
+        assert_eq!(backoffs, vec![250, 500, 1000]);
+        assert_eq!(RetryOptions::default(), RetryOptions::from_params(Some(3), Some(250)));
+    }
+}
@@ -0,0 +1,112 @@
+// MIDI connection test
+// Checks the cable both ways without touching a sound: send Bank Select MSB (CC 0), which
+// pedals that don't use banks ignore, and time how long until the pedal sends a CC back.
+// No reply isn't an error - plenty of pedals don't echo - so it's reported as unhealthy.
+
+use crate::midi::channel_verification::ChannelActivity;
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+/// Bank Select MSB - safe to send on most pedals
+pub const PROBE_CC: u8 = 0;
+pub const PROBE_VALUE: u8 = 0;
+
+/// How long to wait for the pedal to answer
+pub const ECHO_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How often to look for the answer while waiting
+pub const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Outcome of a connection test
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConnectionTestResult {
+    /// Time from sending the probe to the first CC back, if one came
+    pub round_trip_ms: Option<u64>,
+    /// Messages heard from the device on its channel during the test
+    pub messages_received: u32,
+    pub is_healthy: bool,
+}
+
+/// A probe in flight, with the device's traffic counts from just before it was sent
+pub struct ConnectionProbe {
+    activity: ChannelActivity,
+    cc_count: u64,
+    message_count: u64,
+    sent_at: Instant,
+}
+
+impl ConnectionProbe {
+    /// Start timing against `activity` - call right before sending the probe
+    pub fn start(activity: ChannelActivity) -> Self {
+        Self {
+            cc_count: activity.cc_count(),
+            message_count: activity.message_count(),
+            activity,
+            sent_at: Instant::now(),
+        }
+    }
+
+    /// The result if the device has answered, None while still waiting
+    pub fn poll(&self) -> Option<ConnectionTestResult> {
+        if self.activity.cc_count() == self.cc_count {
+            return None;
+        }
+        let round_trip = self.sent_at.elapsed().as_millis() as u64;
+        Some(self.result(Some(round_trip)))
+    }
+
+    /// Whether `timeout` has passed since the probe was sent
+    pub fn expired(&self, timeout: Duration) -> bool {
+        self.sent_at.elapsed() >= timeout
+    }
+
+    /// The result when the device never answered
+    pub fn timed_out(&self) -> ConnectionTestResult {
+        self.result(None)
+    }
+
+    fn result(&self, round_trip_ms: Option<u64>) -> ConnectionTestResult {
+        let received = self.activity.message_count() - self.message_count;
+        ConnectionTestResult {
+            round_trip_ms,
+            messages_received: u32::try_from(received).unwrap_or(u32::MAX),
+            is_healthy: round_trip_ms.is_some(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_echo_makes_the_test_healthy() {
+        let activity = ChannelActivity::new(1);
+        activity.record(0xB0); // traffic from before the test doesn't count
+        let probe = ConnectionProbe::start(activity.clone());
+        assert!(probe.poll().is_none());
+
+        activity.record(0xC0); // a program change isn't the echo
+        assert!(probe.poll().is_none());
+        activity.record(0xB0);
+
+        let result = probe.poll().unwrap();
+        assert!(result.is_healthy);
+        assert!(result.round_trip_ms.is_some());
+        assert_eq!(result.messages_received, 2);
+    }
+
+    #[test]
+    fn test_silence_is_unhealthy_not_an_error() {
+        let activity = ChannelActivity::new(1);
+        let probe = ConnectionProbe::start(activity.clone());
+        activity.record(0xB3); // another device's channel
+
+        assert!(probe.expired(Duration::ZERO));
+        assert_eq!(probe.timed_out(), ConnectionTestResult {
+            round_trip_ms: None,
+            messages_received: 0,
+            is_healthy: false,
+        });
+    }
+}
@@ -1,15 +1,85 @@
 // MIDI device detection and enumeration using midir
 // This module provides functions to list available MIDI input/output ports
 
+use crate::midi::port_metadata::{port_metadata, PortMetadata};
 use midir::{MidiInput, MidiOutput};
+use serde::Serialize;
 use std::error::Error;
 
+/// Port names that loop Librarian back into itself: its own ports, and the macOS
+/// IAC bus that forwards anything sent to it straight back out (infinite feedback)
+const LOOP_PORT_PATTERNS: &[&str] = &["Librarian", "IAC Driver Bus 1"];
+
 /// Information about a detected MIDI device
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct MidiDeviceInfo {
-    pub index: usize,
+    /// The port's index among midir's ports when it was listed. None for ports midir doesn't
+    /// know (demo-mode fake ports).
+    pub index: Option<usize>,
     pub name: String,
     pub is_input: bool,
+    /// USB IDs and manufacturer, on platforms whose MIDI stack reports them
+    pub vendor_id: Option<u16>,
+    pub product_id: Option<u16>,
+    pub manufacturer: Option<String>,
+    pub is_usb: bool,
+}
+
+impl MidiDeviceInfo {
+    /// Port details, with whatever the OS knows about the device behind it
+    pub fn new(index: usize, name: &str, is_input: bool) -> Self {
+        Self::with_metadata(Some(index), name, is_input, port_metadata(name))
+    }
+
+    pub fn with_metadata(index: Option<usize>, name: &str, is_input: bool, metadata: PortMetadata) -> Self {
+        Self {
+            index,
+            name: name.to_string(),
+            is_input,
+            vendor_id: metadata.vendor_id,
+            product_id: metadata.product_id,
+            manufacturer: metadata.manufacturer,
+            is_usb: metadata.is_usb,
+        }
+    }
+}
+
+/// Output ports, with loop/virtual ports set aside so they can't be picked by accident
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct MidiPortList {
+    pub devices: Vec<String>,
+    pub virtual_ports: Vec<String>,
+    /// One entry per `devices` name, in the same order
+    pub device_info: Vec<MidiDeviceInfo>,
+}
+
+/// Whether connecting to this port would feed Librarian's output back into itself.
+/// `own_ports` are ports Librarian currently has open under user-chosen names.
+pub fn is_loop_port(name: &str, own_ports: &[&str]) -> bool {
+    LOOP_PORT_PATTERNS.iter().any(|pattern| name.contains(pattern))
+        || own_ports.contains(&name)
+}
+
+/// Split port names into real devices and loop/virtual ports
+pub fn partition_ports(names: impl IntoIterator<Item = String>, own_ports: &[&str]) -> MidiPortList {
+    let (virtual_ports, devices) = names
+        .into_iter()
+        .partition(|name| is_loop_port(name, own_ports));
+    MidiPortList { devices, virtual_ports, device_info: Vec::new() }
+}
+
+/// The midir index of each device in `devices`, from the (index, name) of every port midir
+/// listed. Indices are taken in order, so two ports with the same name keep their own.
+/// None for names midir didn't list.
+pub fn midir_indices(devices: &[String], midir_ports: &[(usize, String)]) -> Vec<Option<usize>> {
+    let mut unused: Vec<&(usize, String)> = midir_ports.iter().collect();
+    devices
+        .iter()
+        .map(|name| {
+            let position = unused.iter().position(|(_, port_name)| port_name == name)?;
+            Some(unused.remove(position).0)
+        })
+        .collect()
 }
 
 /// Lists all available MIDI input and output devices
@@ -27,11 +97,7 @@ pub fn list_midi_devices() -> Result<(Vec<MidiDeviceInfo>, Vec<MidiDeviceInfo>),
         match midi_in.port_name(port) {
             Ok(name) => {
                 println!("  [{}] {}", i, name);
-                input_devices.push(MidiDeviceInfo {
-                    index: i,
-                    name: name.clone(),
-                    is_input: true,
-                });
+                input_devices.push(MidiDeviceInfo::new(i, &name, true));
             }
             Err(e) => {
                 eprintln!("  [{}] Error getting port name: {}", i, e);
@@ -51,11 +117,7 @@ pub fn list_midi_devices() -> Result<(Vec<MidiDeviceInfo>, Vec<MidiDeviceInfo>),
         match midi_out.port_name(port) {
             Ok(name) => {
                 println!("  [{}] {}", i, name);
-                output_devices.push(MidiDeviceInfo {
-                    index: i,
-                    name: name.clone(),
-                    is_input: false,
-                });
+                output_devices.push(MidiDeviceInfo::new(i, &name, false));
             }
             Err(e) => {
                 eprintln!("  [{}] Error getting port name: {}", i, e);
@@ -101,6 +163,40 @@ pub fn find_device_by_name(
 mod tests {
     use super::*;
 
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_loop_ports_are_set_aside() {
+        let ports = partition_ports(
+            names(&["Microcosm", "Librarian Output", "IAC Driver Bus 1", "Chroma Console", "Pedalboard Bus"]),
+            &["Pedalboard Bus"],
+        );
+
+        assert_eq!(ports.devices, names(&["Microcosm", "Chroma Console"]));
+        assert_eq!(ports.virtual_ports, names(&["Librarian Output", "IAC Driver Bus 1", "Pedalboard Bus"]));
+    }
+
+    #[test]
+    fn test_midir_indices_skip_filtered_ports() {
+        let midir_ports = vec![
+            (0, "IAC Driver Bus 1".to_string()),
+            (1, "Microcosm".to_string()),
+            (2, "Chroma Console".to_string()),
+            (3, "Chroma Console".to_string()),
+        ];
+        let devices = names(&["Microcosm", "Chroma Console", "Chroma Console", "Fake Pedal"]);
+
+        assert_eq!(midir_indices(&devices, &midir_ports), vec![Some(1), Some(2), Some(3), None]);
+    }
+
+    #[test]
+    fn test_other_iac_buses_are_devices() {
+        assert!(!is_loop_port("IAC Driver Bus 2", &[]));
+        assert!(is_loop_port("Librarian", &[]));
+    }
+
     #[test]
     fn test_list_devices() {
         // This test will only pass if MIDI devices are connected
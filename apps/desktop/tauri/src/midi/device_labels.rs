@@ -0,0 +1,65 @@
+// User-friendly labels for MIDI ports
+// Port names like "iCM4+ USB MIDI 1" say nothing about which pedal is plugged in, so the
+// user can give each one an alias. Labels are keyed by the raw port name and stored as a
+// JSON object in the app data dir, so they survive reconnects and restarts.
+
+use crate::midi::error::{MidiError, MidiResult};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Label file inside the app data dir
+pub const DEVICE_LABELS_FILE: &str = "device_labels.json";
+
+/// Read the label map; a missing file means no labels yet
+pub fn load_labels(path: &Path) -> MidiResult<HashMap<String, String>> {
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    let contents = fs::read_to_string(path)
+        .map_err(|e| MidiError::Other(format!("Failed to read device labels: {}", e)))?;
+    serde_json::from_str(&contents)
+        .map_err(|e| MidiError::Other(format!("Failed to parse device labels: {}", e)))
+}
+
+/// Write the label map, replacing the previous file
+pub fn save_labels(path: &Path, labels: &HashMap<String, String>) -> MidiResult<()> {
+    let contents = serde_json::to_string_pretty(labels)
+        .map_err(|e| MidiError::Other(format!("Failed to serialize device labels: {}", e)))?;
+    fs::write(path, contents)
+        .map_err(|e| MidiError::Other(format!("Failed to write device labels: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_missing_file_is_empty() {
+        let dir = TempDir::new().unwrap();
+        let labels = load_labels(&dir.path().join(DEVICE_LABELS_FILE)).unwrap();
+        assert!(labels.is_empty());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(DEVICE_LABELS_FILE);
+
+        let mut labels = HashMap::new();
+        labels.insert("iCM4+ USB MIDI 1".to_string(), "Board left".to_string());
+        save_labels(&path, &labels).unwrap();
+
+        assert_eq!(load_labels(&path).unwrap(), labels);
+    }
+
+    #[test]
+    fn test_corrupt_file_is_an_error() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(DEVICE_LABELS_FILE);
+        fs::write(&path, "not json").unwrap();
+
+        assert!(load_labels(&path).is_err());
+    }
+}
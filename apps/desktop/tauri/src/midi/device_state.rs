@@ -0,0 +1,104 @@
+// Tagged device state for the frontend
+// One shape for every pedal so the UI doesn't need to pick a per-pedal getter.
+// `state` is serialized exactly like preset parameters, so it can go straight to save_preset.
+
+use crate::midi::error::{MidiError, MidiResult};
+use crate::midi::firmware::FirmwareVersion;
+use crate::midi::manager::PedalType;
+use serde::Serialize;
+
+/// Believed state of a connected device, tagged with its pedal type
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DeviceState {
+    pub pedal_type: String,  // PedalType name, as presets store it (e.g. "GenLossMkii")
+    pub state: serde_json::Value,
+    /// Installed firmware, once the pedal has answered an identity request
+    pub firmware: Option<FirmwareVersion>,
+}
+
+impl DeviceState {
+    pub fn new<S: Serialize>(pedal_type: &PedalType, state: &S) -> MidiResult<Self> {
+        let state = serde_json::to_value(state)
+            .map_err(|e| MidiError::Other(format!("Failed to serialize state: {}", e)))?;
+
+        Ok(Self {
+            pedal_type: pedal_type.as_str().to_string(),
+            state,
+            firmware: None,
+        })
+    }
+
+    /// Deserialize the state as a specific pedal's state type
+    pub fn parse<T: serde::de::DeserializeOwned>(self, expected: &PedalType) -> MidiResult<T> {
+        if self.pedal_type != expected.as_str() {
+            return Err(MidiError::Other(format!(
+                "Device is a {}, not a {}",
+                self.pedal_type,
+                expected.as_str()
+            )));
+        }
+
+        serde_json::from_value(self.state)
+            .map_err(|e| MidiError::Other(format!("Failed to read state: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::midi::pedals::billy_strings_wombtone::BillyStringsWombtoneState;
+    use crate::midi::pedals::brothers_am::BrothersAmState;
+    use crate::midi::pedals::chroma_console::ChromaConsoleState;
+    use crate::midi::pedals::clean::CleanState;
+    use crate::midi::pedals::cxm1978::Cxm1978State;
+    use crate::midi::pedals::dark_world::DarkWorldState;
+    use crate::midi::pedals::gen_loss_mkii::GenLossMkiiState;
+    use crate::midi::pedals::habit::HabitState;
+    use crate::midi::pedals::lossy::LossyState;
+    use crate::midi::pedals::microcosm::MicrocosmState;
+    use crate::midi::pedals::mood_mkii::MoodMkiiState;
+    use crate::midi::pedals::onward::OnwardState;
+    use crate::midi::pedals::preamp_mk2::PreampMk2State;
+    use crate::midi::pedals::reverse_mode_c::ReverseModeCState;
+    use crate::midi::pedals::source_audio_collider::ColliderState;
+    use crate::midi::pedals::thermae::ThermaeState;
+    use serde::de::DeserializeOwned;
+
+    fn assert_tagged<S: Default + Serialize + DeserializeOwned>(pedal_type: PedalType, name: &str) {
+        let device_state = DeviceState::new(&pedal_type, &S::default()).unwrap();
+
+        let json = serde_json::to_value(&device_state).unwrap();
+        assert_eq!(json["pedal_type"], name);
+        assert_eq!(json["state"], serde_json::to_value(S::default()).unwrap());
+
+        // The tag parses back, and the state reads as preset parameters would
+        assert_eq!(PedalType::from_name(&device_state.pedal_type), Some(pedal_type.clone()));
+        device_state.parse::<S>(&pedal_type).unwrap();
+    }
+
+    #[test]
+    fn test_tagged_output_for_each_pedal_type() {
+        assert_tagged::<MicrocosmState>(PedalType::Microcosm, "Microcosm");
+        assert_tagged::<GenLossMkiiState>(PedalType::GenLossMkii, "GenLossMkii");
+        assert_tagged::<ChromaConsoleState>(PedalType::ChromaConsole, "ChromaConsole");
+        assert_tagged::<PreampMk2State>(PedalType::PreampMk2, "PreampMk2");
+        assert_tagged::<Cxm1978State>(PedalType::Cxm1978, "Cxm1978");
+        assert_tagged::<MoodMkiiState>(PedalType::MoodMkii, "MoodMkii");
+        assert_tagged::<BillyStringsWombtoneState>(PedalType::BillyStringsWombtone, "BillyStringsWombtone");
+        assert_tagged::<LossyState>(PedalType::Lossy, "Lossy");
+        assert_tagged::<BrothersAmState>(PedalType::BrothersAm, "BrothersAm");
+        assert_tagged::<ReverseModeCState>(PedalType::ReverseModeC, "ReverseModeC");
+        assert_tagged::<CleanState>(PedalType::Clean, "Clean");
+        assert_tagged::<OnwardState>(PedalType::Onward, "Onward");
+        assert_tagged::<ThermaeState>(PedalType::Thermae, "Thermae");
+        assert_tagged::<DarkWorldState>(PedalType::DarkWorld, "DarkWorld");
+        assert_tagged::<HabitState>(PedalType::Habit, "Habit");
+        assert_tagged::<ColliderState>(PedalType::Collider, "Collider");
+    }
+
+    #[test]
+    fn test_parse_rejects_other_pedal_type() {
+        let device_state = DeviceState::new(&PedalType::Lossy, &LossyState::default()).unwrap();
+        assert!(device_state.parse::<CleanState>(&PedalType::Clean).is_err());
+    }
+}
@@ -11,7 +11,7 @@ pub enum MidiError {
 
     /// Invalid parameter value provided
     #[error("Invalid parameter value: expected {expected}, got {actual}")]
-    InvalidValue { expected: String, actual: u8 },
+    InvalidValue { expected: String, actual: u64 },
 
     /// MIDI communication error
     #[error("MIDI communication error: {0}")]
@@ -37,15 +37,35 @@ pub enum MidiError {
     #[error("Device already connected: {0}")]
     AlreadyConnected(String),
 
+    /// Device was connected but hasn't been armed, so nothing may be sent to it yet
+    #[error("Device not armed: {0} (arm it before sending)")]
+    DeviceNotArmed(String),
+
     /// Invalid MIDI channel (must be 1-16)
     #[error("Invalid MIDI channel: {0} (must be 1-16)")]
     InvalidChannel(u8),
 
+    /// Operation not available on this platform
+    #[error("Unsupported on this platform: {0}")]
+    Unsupported(String),
+
+    /// A batch send stopped part-way - the parameters in `sent` went out, `failed` didn't
+    #[error("Batch stopped at {failed} ({reason}); already sent: {}", sent_list(.sent))]
+    BatchIncomplete { sent: Vec<String>, failed: String, reason: String },
+
     /// Generic MIDI error
     #[error("MIDI error: {0}")]
     Other(String),
 }
 
+fn sent_list(sent: &[String]) -> String {
+    if sent.is_empty() {
+        "nothing".to_string()
+    } else {
+        sent.join(", ")
+    }
+}
+
 /// Result type for MIDI operations
 pub type MidiResult<T> = Result<T, MidiError>;
 
@@ -0,0 +1,155 @@
+// What to leave each pedal doing when the app quits
+// Closing Librarian mid-gig shouldn't leave a pedal in whatever wild state the last edit
+// put it in. Each pedal type can be set to do nothing, bypass, or recall a "shutdown
+// preset" from the library on exit. The whole pass gets a fixed time budget so quitting
+// never hangs: a device whose action wouldn't fit in what's left is skipped, not started.
+// Settings are kept as JSON in the app data dir.
+
+use crate::midi::error::{MidiError, MidiResult};
+use crate::midi::manager::{PedalType, SharedMidiManager};
+use crate::presets::{PresetId, SharedPresetLibrary};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// Settings file inside the app data dir
+pub const EXIT_ACTIONS_FILE: &str = "exit_actions.json";
+
+/// How long the whole exit pass may take
+pub const EXIT_BUDGET: Duration = Duration::from_secs(2);
+
+/// What one pedal type does on exit
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExitAction {
+    #[default]
+    Nothing,
+    Bypass,
+    /// Recall this library preset (the shutdown preset)
+    RecallPreset(String),
+}
+
+/// Exit actions per pedal type, plus a global switch
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExitActionSettings {
+    /// Off, nothing is sent on exit whatever the per-pedal actions say
+    pub enabled: bool,
+    /// Keyed by pedal type name; pedal types not listed do nothing
+    pub actions: BTreeMap<String, ExitAction>,
+}
+
+impl ExitActionSettings {
+    pub fn action_for(&self, pedal_type: &PedalType) -> &ExitAction {
+        static NOTHING: ExitAction = ExitAction::Nothing;
+        self.actions.get(pedal_type.as_str()).unwrap_or(&NOTHING)
+    }
+
+    /// Every shutdown preset ID the settings refer to
+    pub fn shutdown_preset_ids(&self) -> Vec<&str> {
+        self.actions.values().filter_map(|action| match action {
+            ExitAction::RecallPreset(preset_id) => Some(preset_id.as_str()),
+            _ => None,
+        }).collect()
+    }
+}
+
+/// Read settings, or the defaults (everything off) when the file doesn't exist yet
+pub fn load_settings(path: &Path) -> MidiResult<ExitActionSettings> {
+    if !path.exists() {
+        return Ok(ExitActionSettings::default());
+    }
+    let contents = fs::read_to_string(path)
+        .map_err(|e| MidiError::Other(format!("Failed to read exit actions: {}", e)))?;
+    serde_json::from_str(&contents)
+        .map_err(|e| MidiError::Other(format!("Failed to parse exit actions: {}", e)))
+}
+
+/// Write settings, replacing the previous file
+pub fn save_settings(path: &Path, settings: &ExitActionSettings) -> MidiResult<()> {
+    let contents = serde_json::to_string_pretty(settings)
+        .map_err(|e| MidiError::Other(format!("Failed to serialize exit actions: {}", e)))?;
+    fs::write(path, contents)
+        .map_err(|e| MidiError::Other(format!("Failed to write exit actions: {}", e)))
+}
+
+/// A shutdown preset looked up ahead of time, so the exit pass needs only the manager
+#[derive(Debug, Clone)]
+pub struct ShutdownPreset {
+    pub pedal_type: PedalType,
+    pub parameters: serde_json::Value,
+}
+
+/// How one device's exit action went
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExitStatus {
+    Done,
+    /// Its action wouldn't have finished inside the budget
+    Skipped,
+    Failed,
+    /// Its pedal type's action is `Nothing`
+    Ignored,
+}
+
+/// One device's line in the exit log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExitActionResult {
+    pub device_name: String,
+    pub action: ExitAction,
+    pub status: ExitStatus,
+    pub error: Option<String>,
+}
+
+/// Run the exit actions against the shared manager within `EXIT_BUDGET`, then release every
+/// port. Shutdown presets are read from the library first, so the manager and library are
+/// never locked together; one that can't be read fails only the devices that wanted it.
+pub fn shut_down(manager: &SharedMidiManager, library: &SharedPresetLibrary) -> Vec<ExitActionResult> {
+    let Ok(settings) = manager.lock().map(|manager| manager.exit_actions().clone()) else { return Vec::new() };
+    if !settings.enabled {
+        return Vec::new();
+    }
+
+    let mut presets = HashMap::new();
+    if let Ok(library) = library.lock() {
+        for preset_id in settings.shutdown_preset_ids() {
+            let Ok(preset) = library.get_preset(&PresetId::new(preset_id.to_string())) else { continue };
+            let Some(pedal_type) = PedalType::from_name(&preset.pedal_type) else { continue };
+            presets.insert(preset_id.to_string(), ShutdownPreset { pedal_type, parameters: preset.parameters });
+        }
+    }
+
+    let Ok(mut manager) = manager.lock() else { return Vec::new() };
+    manager.begin_journal_operation("exit_actions");
+    manager.shut_down(&presets, EXIT_BUDGET)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_settings_round_trip_and_default_to_nothing() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(EXIT_ACTIONS_FILE);
+        assert_eq!(load_settings(&path).unwrap(), ExitActionSettings::default());
+
+        let settings = ExitActionSettings {
+            enabled: true,
+            actions: BTreeMap::from([
+                ("Microcosm".to_string(), ExitAction::Bypass),
+                ("ChromaConsole".to_string(), ExitAction::RecallPreset("quiet".to_string())),
+            ]),
+        };
+        save_settings(&path, &settings).unwrap();
+        let loaded = load_settings(&path).unwrap();
+
+        assert_eq!(loaded, settings);
+        assert_eq!(loaded.action_for(&PedalType::Microcosm), &ExitAction::Bypass);
+        assert_eq!(loaded.action_for(&PedalType::Lossy), &ExitAction::Nothing);
+        assert_eq!(loaded.shutdown_preset_ids(), vec!["quiet"]);
+    }
+}
@@ -0,0 +1,150 @@
+// Fake devices for demo mode
+// Contributors without the hardware (and the screenshot pipeline) still need a working app.
+// A fake port is an ordinary output port as far as routing is concerned: devices connect to
+// it like any other, so sends, recalls and state tracking take the same paths they do with
+// a pedal. Everything written to it is logged, and an optional responder echoes CCs back
+// after a delay the way a pedal reports its knobs, which exercises the input path too.
+
+use crate::journal::JournalMessage;
+use crate::midi::error::MidiResult;
+use crate::midi::manager::PedalType;
+use crate::midi::routing::OutputPort;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Set to `1` or `true` to start with demo mode on
+pub const DEMO_MODE_ENV: &str = "LIBRARIAN_DEMO_MODE";
+
+/// How long the fake pedals take to echo a CC back
+pub const DEFAULT_ECHO_DELAY: Duration = Duration::from_millis(30);
+
+/// Oldest entries are dropped past this many per port
+const LOG_LIMIT: usize = 1000;
+
+/// Whether the environment asks for demo mode
+pub fn demo_mode_from_env() -> bool {
+    std::env::var(DEMO_MODE_ENV).is_ok_and(|value| matches!(value.trim(), "1" | "true"))
+}
+
+/// Port name of the fake pedal demo mode offers for `pedal_type`. Not "Librarian ...":
+/// those are taken for loop ports and hidden from the device list.
+pub fn demo_port_name(pedal_type: &PedalType) -> String {
+    format!("Demo {}", pedal_type.as_str())
+}
+
+/// One message written to a fake port
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FakeLogEntry {
+    pub bytes: Vec<u8>,
+    /// 1-16, for channel voice messages
+    pub channel: Option<u8>,
+    /// Decoded CC / PC / note, where recognized
+    pub message: Option<JournalMessage>,
+}
+
+impl FakeLogEntry {
+    fn parse(bytes: &[u8]) -> Self {
+        let status = bytes.first().copied().unwrap_or(0);
+        let channel = (0x80..=0xEF).contains(&status).then_some((status & 0x0F) + 1);
+        let message = match (status & 0xF0, bytes) {
+            (0xB0, [_, cc_number, value, ..]) => Some(JournalMessage::ControlChange { cc_number: *cc_number, value: *value }),
+            (0xC0, [_, program, ..]) => Some(JournalMessage::ProgramChange { program: *program }),
+            (0x90, [_, note, velocity, ..]) => Some(JournalMessage::NoteOn { note: *note, velocity: *velocity }),
+            (0x80, [_, note, ..]) => Some(JournalMessage::NoteOff { note: *note }),
+            _ => None,
+        };
+        Self { bytes: bytes.to_vec(), channel, message }
+    }
+}
+
+/// Called with each echoed message, as if it had arrived on the port's input
+pub(crate) type Responder = Arc<dyn Fn(&[u8]) + Send + Sync>;
+
+/// In-memory output port standing in for a pedal. Clones share one log and responder.
+#[derive(Clone)]
+pub(crate) struct FakePort {
+    log: Arc<Mutex<Vec<FakeLogEntry>>>,
+    responder: Arc<Mutex<Option<Responder>>>,
+    echo_delay: Option<Duration>,
+}
+
+impl FakePort {
+    /// A port that echoes CCs back after `echo_delay`, or never if `None`
+    pub fn new(echo_delay: Option<Duration>) -> Self {
+        Self {
+            log: Arc::default(),
+            responder: Arc::default(),
+            echo_delay,
+        }
+    }
+
+    /// Where echoed messages are delivered (the port's input listeners)
+    pub fn respond_with(&self, responder: Responder) {
+        *self.responder.lock().unwrap() = Some(responder);
+    }
+
+    /// Messages written so far, oldest first
+    pub fn log(&self) -> Vec<FakeLogEntry> {
+        self.log.lock().unwrap().clone()
+    }
+}
+
+impl OutputPort for FakePort {
+    fn send(&mut self, message: &[u8]) -> MidiResult<()> {
+        let entry = FakeLogEntry::parse(message);
+        let is_cc = matches!(entry.message, Some(JournalMessage::ControlChange { .. }));
+        {
+            let mut log = self.log.lock().unwrap();
+            if log.len() >= LOG_LIMIT {
+                log.remove(0);
+            }
+            log.push(entry);
+        }
+
+        let responder = self.responder.lock().unwrap().clone();
+        if let (true, Some(delay), Some(responder)) = (is_cc, self.echo_delay, responder) {
+            let message = message.to_vec();
+            thread::spawn(move || {
+                thread::sleep(delay);
+                responder(&message);
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[test]
+    fn test_log_decodes_channel_voice_messages() {
+        let mut port = FakePort::new(None);
+        port.send(&[0xB2, 14, 100]).unwrap();
+        port.send(&[0xC0, 5]).unwrap();
+        port.send(&[0xF0, 0x7E, 0x7F, 0x06, 0x01, 0xF7]).unwrap();
+
+        let log = port.log();
+        assert_eq!(log[0].channel, Some(3));
+        assert_eq!(log[0].message, Some(JournalMessage::ControlChange { cc_number: 14, value: 100 }));
+        assert_eq!(log[1].message, Some(JournalMessage::ProgramChange { program: 5 }));
+        assert_eq!((log[2].channel, log[2].message.clone()), (None, None));
+    }
+
+    #[test]
+    fn test_responder_echoes_only_ccs() {
+        let mut port = FakePort::new(Some(Duration::ZERO));
+        let (tx, rx) = mpsc::channel();
+        let tx = Mutex::new(tx);
+        port.respond_with(Arc::new(move |message| tx.lock().unwrap().send(message.to_vec()).unwrap()));
+
+        port.send(&[0xC0, 5]).unwrap();
+        port.send(&[0xB0, 14, 100]).unwrap();
+
+        assert_eq!(rx.recv_timeout(Duration::from_secs(1)).unwrap(), vec![0xB0, 14, 100]);
+        assert!(rx.recv_timeout(Duration::from_millis(50)).is_err());
+    }
+}
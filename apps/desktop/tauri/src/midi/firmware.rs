@@ -0,0 +1,122 @@
+// Firmware versions and version-specific CCs
+// Pedals pick up CCs in firmware updates. The identity reply carries the installed version,
+// so a send of a CC the pedal doesn't know yet can be flagged (or dropped) instead of
+// silently doing nothing on the hardware.
+
+use crate::midi::manager::PedalType;
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+/// Firmware version from an identity reply's software revision bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FirmwareVersion([u8; 4]);
+
+impl FirmwareVersion {
+    pub const fn new(major: u8, minor: u8, patch: u8) -> Self {
+        Self([major, minor, patch, 0])
+    }
+
+    /// Read the identity reply's software revision (up to 4 bytes, most significant first)
+    pub fn from_identity_bytes(bytes: &[u8]) -> Self {
+        let mut parts = [0; 4];
+        for (part, byte) in parts.iter_mut().zip(bytes) {
+            *part = *byte;
+        }
+        Self(parts)
+    }
+}
+
+impl fmt::Display for FirmwareVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [major, minor, patch, build] = self.0;
+        write!(f, "{}.{}.{}", major, minor, patch)?;
+        if build != 0 {
+            write!(f, ".{}", build)?;
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for FirmwareVersion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// CCs the Gen Loss MKII only answers from a given firmware on
+const GEN_LOSS_MKII_CC_FIRMWARE: &[(u8, FirmwareVersion)] = &[
+    (52, FirmwareVersion::new(1, 1, 0)), // Ramp/bounce
+];
+
+/// Firmware a pedal needs before it responds to `cc_number`, or None if every version does
+pub fn cc_min_firmware(pedal_type: &PedalType, cc_number: u8) -> Option<FirmwareVersion> {
+    let table = match pedal_type {
+        PedalType::GenLossMkii => GEN_LOSS_MKII_CC_FIRMWARE,
+        _ => return None,
+    };
+    table
+        .iter()
+        .find(|(cc, _)| *cc == cc_number)
+        .map(|(_, version)| *version)
+}
+
+/// A CC sent to a pedal whose firmware predates it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FirmwareWarning {
+    pub cc_number: u8,
+    pub required: FirmwareVersion,
+    pub installed: FirmwareVersion,
+    /// True when the CC was dropped rather than sent anyway
+    pub skipped: bool,
+}
+
+/// What a connection checks its CCs against
+#[derive(Debug, Clone)]
+pub(crate) struct FirmwareGate {
+    pub pedal_type: PedalType,
+    pub installed: FirmwareVersion,
+    pub skip_unsupported: bool,
+}
+
+impl FirmwareGate {
+    /// The warning for sending `cc_number`, if the installed firmware predates it
+    pub fn check(&self, cc_number: u8) -> Option<FirmwareWarning> {
+        let required = cc_min_firmware(&self.pedal_type, cc_number)?;
+        (self.installed < required).then_some(FirmwareWarning {
+            cc_number,
+            required,
+            installed: self.installed,
+            skipped: self.skip_unsupported,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_from_identity_bytes() {
+        let version = FirmwareVersion::from_identity_bytes(&[1, 2]);
+        assert_eq!(version, FirmwareVersion::new(1, 2, 0));
+        assert_eq!(version.to_string(), "1.2.0");
+        assert_eq!(FirmwareVersion::from_identity_bytes(&[2, 0, 1, 7]).to_string(), "2.0.1.7");
+        assert!(FirmwareVersion::new(1, 0, 9) < FirmwareVersion::new(1, 1, 0));
+    }
+
+    #[test]
+    fn test_gate_flags_only_newer_ccs() {
+        let gate = FirmwareGate {
+            pedal_type: PedalType::GenLossMkii,
+            installed: FirmwareVersion::new(1, 0, 0),
+            skip_unsupported: false,
+        };
+        let warning = gate.check(52).unwrap();
+        assert_eq!(warning.required, FirmwareVersion::new(1, 1, 0));
+        assert!(!warning.skipped);
+        assert_eq!(gate.check(14), None);
+
+        let current = FirmwareGate { installed: FirmwareVersion::new(1, 1, 0), ..gate };
+        assert_eq!(current.check(52), None);
+    }
+}
@@ -2,7 +2,8 @@
 // Implements the MIDI specification for device identification via SysEx
 
 use crate::midi::error::{MidiError, MidiResult};
-use midir::{MidiInput, MidiOutput};
+use midir::{Ignore, MidiInput, MidiOutput};
+use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
@@ -20,6 +21,8 @@ const IDENTITY_REQUEST: [u8; 6] = [
 /// Parsed device identity information
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DeviceIdentity {
+    /// SysEx device ID the reply came from (pedals usually use their MIDI channel, 0-based)
+    pub device_id: u8,
     pub manufacturer_id: Vec<u8>,
     pub device_family: u16,
     pub device_model: u16,
@@ -103,7 +106,7 @@ impl DeviceIdentity {
 
 /// Parse an Identity Reply SysEx message
 /// Format: F0 7E [device] 06 02 [mfg] [family LSB] [family MSB] [model LSB] [model MSB] [version...] F7
-fn parse_identity_reply(message: &[u8]) -> MidiResult<DeviceIdentity> {
+pub(crate) fn parse_identity_reply(message: &[u8]) -> MidiResult<DeviceIdentity> {
     // Minimum valid message: F0 7E [dev] 06 02 [mfg] [fam] [fam] [mod] [mod] F7 = 11 bytes
     if message.len() < 11 {
         return Err(MidiError::Other(format!(
@@ -161,6 +164,7 @@ fn parse_identity_reply(message: &[u8]) -> MidiResult<DeviceIdentity> {
     }
 
     Ok(DeviceIdentity {
+        device_id: message[2],
         manufacturer_id,
         device_family,
         device_model,
@@ -168,144 +172,284 @@ fn parse_identity_reply(message: &[u8]) -> MidiResult<DeviceIdentity> {
     })
 }
 
+/// Retry settings for an identity request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdentityRequestOptions {
+    /// How long to listen for a reply on each attempt
+    pub timeout_ms: u64,
+    /// Total attempts (at least one is always made)
+    pub attempts: u32,
+    /// Wait before the first retry; doubles after each further attempt
+    pub backoff_ms: u64,
+}
+
+impl Default for IdentityRequestOptions {
+    fn default() -> Self {
+        Self {
+            timeout_ms: 2000,
+            attempts: 3,
+            backoff_ms: 250,
+        }
+    }
+}
+
+/// What an identity request actually saw, so "no matching input port" can be told
+/// apart from "the device ignored the inquiry"
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdentityDiagnostics {
+    /// Output port the inquiry was sent on (last attempt), if one matched
+    pub output_port: Option<String>,
+    /// Input port listened on (last attempt), if one matched
+    pub input_port: Option<String>,
+    /// Inquiry bytes sent across all attempts
+    pub bytes_sent: usize,
+    /// SysEx messages heard that were not identity replies
+    pub other_sysex_count: usize,
+    /// Attempts made
+    pub attempts: u32,
+}
+
+/// Outcome of an identity request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdentityRequestResult {
+    pub identity: Option<DeviceIdentity>,
+    pub diagnostics: IdentityDiagnostics,
+}
+
+/// Port access for identity requests - midir in the app, a mock in tests
+pub(crate) trait IdentityTransport {
+    fn output_port_names(&mut self) -> MidiResult<Vec<String>>;
+
+    fn input_port_names(&mut self) -> MidiResult<Vec<String>>;
+
+    /// Send `request` and return every SysEx message heard on the input port until an
+    /// identity reply arrives (plus a short grace period for more replies) or the timeout passes
+    fn exchange(
+        &mut self,
+        output_port: &str,
+        input_port: &str,
+        request: &[u8],
+        timeout: Duration,
+    ) -> MidiResult<Vec<Vec<u8>>>;
+}
+
+fn is_identity_reply(message: &[u8]) -> bool {
+    message.len() >= 5
+        && message[0] == 0xF0
+        && message[1] == 0x7E
+        && message[3] == 0x06
+        && message[4] == 0x02
+}
+
+/// Real MIDI ports through midir
+pub(crate) struct MidirTransport;
+
+impl IdentityTransport for MidirTransport {
+    fn output_port_names(&mut self) -> MidiResult<Vec<String>> {
+        let midi_out = MidiOutput::new("Librarian Identity Request")
+            .map_err(|e| MidiError::Other(e.to_string()))?;
+        Ok(midi_out
+            .ports()
+            .iter()
+            .filter_map(|p| midi_out.port_name(p).ok())
+            .collect())
+    }
+
+    fn input_port_names(&mut self) -> MidiResult<Vec<String>> {
+        let midi_in = MidiInput::new("Librarian Identity Listener")
+            .map_err(|e| MidiError::Other(e.to_string()))?;
+        Ok(midi_in
+            .ports()
+            .iter()
+            .filter_map(|p| midi_in.port_name(p).ok())
+            .collect())
+    }
+
+    fn exchange(
+        &mut self,
+        output_port: &str,
+        input_port: &str,
+        request: &[u8],
+        timeout: Duration,
+    ) -> MidiResult<Vec<Vec<u8>>> {
+        let midi_out = MidiOutput::new("Librarian Identity Request")
+            .map_err(|e| MidiError::Other(e.to_string()))?;
+        let out_ports = midi_out.ports();
+        let out_port = out_ports
+            .iter()
+            .find(|p| midi_out.port_name(p).map(|name| name == output_port).unwrap_or(false))
+            .ok_or_else(|| MidiError::DeviceNotFound(output_port.to_string()))?;
+
+        let mut midi_in = MidiInput::new("Librarian Identity Listener")
+            .map_err(|e| MidiError::Other(e.to_string()))?;
+        // Non-identity SysEx is counted in the diagnostics, so don't filter any
+        midi_in.ignore(Ignore::None);
+        let in_ports = midi_in.ports();
+        let in_port = in_ports
+            .iter()
+            .find(|p| midi_in.port_name(p).map(|name| name == input_port).unwrap_or(false))
+            .ok_or_else(|| MidiError::DeviceNotFound(format!("{} (input)", input_port)))?;
+
+        // Shared state for capturing responses (may receive multiple)
+        let messages = Arc::new(Mutex::new(Vec::new()));
+        let messages_clone = Arc::clone(&messages);
+
+        let _conn_in = midi_in
+            .connect(
+                in_port,
+                "identity-listener",
+                move |_timestamp, message, _| {
+                    if message.first() == Some(&0xF0) {
+                        println!("📥 Received SysEx: {:02X?}", message);
+                        messages_clone.lock().unwrap().push(message.to_vec());
+                    }
+                },
+                (),
+            )
+            .map_err(|e| MidiError::ConnectionFailed(e.to_string()))?;
+
+        let mut conn_out = midi_out
+            .connect(out_port, "identity-requester")
+            .map_err(|e| MidiError::ConnectionFailed(e.to_string()))?;
+
+        println!("📤 Sending Identity Request: {:02X?}", request);
+        conn_out
+            .send(request)
+            .map_err(|e| MidiError::SendFailed(e.to_string()))?;
+
+        println!("⏳ Waiting for response (timeout: {}ms)...", timeout.as_millis());
+
+        let start = std::time::Instant::now();
+        while start.elapsed() < timeout {
+            if messages.lock().unwrap().iter().any(|m| is_identity_reply(m)) {
+                // Keep collecting responses for a bit longer (100ms) to get all of them
+                std::thread::sleep(Duration::from_millis(100));
+                break;
+            }
+            // Small sleep to avoid busy-waiting
+            std::thread::sleep(Duration::from_millis(50));
+        }
+
+        let messages = messages.lock().unwrap().clone();
+        Ok(messages)
+    }
+}
+
 /// Request device identity from a MIDI device
-/// Sends an Identity Request and waits for replies (may get multiple)
-/// Returns the first pedal response (non-interface), or the first response if all are interfaces
+/// Sends an Identity Request and waits for replies (may get multiple), retrying with backoff
+/// while the ports are missing or nothing answers.
+/// The identity is the first pedal response (non-interface), or the first response if all are interfaces
 pub fn request_device_identity(
     device_name: &str,
-    timeout_ms: u64,
-) -> MidiResult<Option<DeviceIdentity>> {
+    options: IdentityRequestOptions,
+) -> MidiResult<IdentityRequestResult> {
+    request_identity_with(&mut MidirTransport, device_name, options)
+}
+
+pub(crate) fn request_identity_with(
+    transport: &mut impl IdentityTransport,
+    device_name: &str,
+    options: IdentityRequestOptions,
+) -> MidiResult<IdentityRequestResult> {
     println!("🔍 Requesting device identity from: {}", device_name);
 
-    // Find the output port
-    let midi_out = MidiOutput::new("Librarian Identity Request")
-        .map_err(|e| MidiError::Other(e.to_string()))?;
-    
-    let out_ports = midi_out.ports();
-    let out_port = out_ports
-        .iter()
-        .find(|p| {
-            midi_out
-                .port_name(p)
-                .map(|name| name == device_name)
-                .unwrap_or(false)
-        })
-        .ok_or_else(|| MidiError::DeviceNotFound(device_name.to_string()))?;
-
-    // Find the input port
-    let midi_in = MidiInput::new("Librarian Identity Listener")
-        .map_err(|e| MidiError::Other(e.to_string()))?;
-    
-    let in_ports = midi_in.ports();
-    let in_port = in_ports
-        .iter()
-        .find(|p| {
-            midi_in
-                .port_name(p)
-                .map(|name| name == device_name)
-                .unwrap_or(false)
-        })
-        .ok_or_else(|| MidiError::DeviceNotFound(format!("{} (input)", device_name)))?;
-
-    // Shared state for capturing responses (may receive multiple)
-    let responses = Arc::new(Mutex::new(Vec::new()));
-    let responses_clone = Arc::clone(&responses);
-
-    // Connect to input port with callback
-    let _conn_in = midi_in
-        .connect(
-            in_port,
-            "identity-listener",
-            move |_timestamp, message, _| {
-                // Check if this is an Identity Reply
-                if message.len() >= 5
-                    && message[0] == 0xF0
-                    && message[1] == 0x7E
-                    && message[3] == 0x06
-                    && message[4] == 0x02
-                {
-                    println!("📥 Received Identity Reply: {} bytes", message.len());
-                    println!("   Raw: {:02X?}", message);
-                    
-                    match parse_identity_reply(message) {
-                        Ok(identity) => {
-                            println!("✅ Parsed identity: {}", identity.description());
-                            let mut resps = responses_clone.lock().unwrap();
-                            resps.push(identity);
-                        }
-                        Err(e) => {
-                            eprintln!("❌ Failed to parse identity reply: {}", e);
-                        }
-                    }
+    let mut diagnostics = IdentityDiagnostics::default();
+    let attempts = options.attempts.max(1);
+    let mut backoff = Duration::from_millis(options.backoff_ms);
+
+    for attempt in 1..=attempts {
+        if attempt > 1 {
+            println!("🔁 Retrying identity request ({}/{}) in {}ms", attempt, attempts, backoff.as_millis());
+            std::thread::sleep(backoff);
+            backoff *= 2;
+        }
+        diagnostics.attempts = attempt;
+
+        // Ports are looked up again every attempt - the OS may still be enumerating
+        diagnostics.output_port = transport
+            .output_port_names()?
+            .into_iter()
+            .find(|name| name == device_name);
+        diagnostics.input_port = transport
+            .input_port_names()?
+            .into_iter()
+            .find(|name| name == device_name);
+
+        let (Some(output_port), Some(input_port)) = (&diagnostics.output_port, &diagnostics.input_port) else {
+            println!("⚠️ No matching port yet (output: {:?}, input: {:?})", diagnostics.output_port, diagnostics.input_port);
+            continue;
+        };
+
+        let messages = transport.exchange(
+            output_port,
+            input_port,
+            &IDENTITY_REQUEST,
+            Duration::from_millis(options.timeout_ms),
+        )?;
+        diagnostics.bytes_sent += IDENTITY_REQUEST.len();
+
+        let mut identities = Vec::new();
+        for message in &messages {
+            if !is_identity_reply(message) {
+                diagnostics.other_sysex_count += 1;
+                continue;
+            }
+            match parse_identity_reply(message) {
+                Ok(identity) => {
+                    println!("✅ Parsed identity: {}", identity.description());
+                    identities.push(identity);
                 }
-            },
-            (),
-        )
-        .map_err(|e| MidiError::ConnectionFailed(e.to_string()))?;
-
-    // Connect to output port
-    let mut conn_out = midi_out
-        .connect(out_port, "identity-requester")
-        .map_err(|e| MidiError::ConnectionFailed(e.to_string()))?;
-
-    // Send Identity Request
-    println!("📤 Sending Identity Request: {:02X?}", IDENTITY_REQUEST);
-    conn_out
-        .send(&IDENTITY_REQUEST)
-        .map_err(|e| MidiError::SendFailed(e.to_string()))?;
-    
-    println!("⏳ Waiting for response (timeout: {}ms)...", timeout_ms);
-
-    // Wait for responses with timeout
-    let start = std::time::Instant::now();
-    let timeout = Duration::from_millis(timeout_ms);
-    
-    while start.elapsed() < timeout {
-        // Check if we got any responses
-        let resps = responses.lock().unwrap();
-        if !resps.is_empty() {
-            // Keep collecting responses for a bit longer (100ms) to get all of them
-            drop(resps);
-            std::thread::sleep(Duration::from_millis(100));
-            break;
+                Err(e) => {
+                    eprintln!("❌ Failed to parse identity reply: {}", e);
+                }
+            }
         }
-        drop(resps);
-        
-        // Small sleep to avoid busy-waiting
-        std::thread::sleep(Duration::from_millis(50));
-    }
 
-    let all_responses = responses.lock().unwrap();
-    
-    if all_responses.is_empty() {
+        if let Some(identity) = select_identity(identities) {
+            return Ok(IdentityRequestResult {
+                identity: Some(identity),
+                diagnostics,
+            });
+        }
         println!("⏱️ Timeout waiting for Identity Reply");
-        return Ok(None);
     }
-    
-    println!("📊 Received {} identity response(s)", all_responses.len());
-    
+
+    Ok(IdentityRequestResult {
+        identity: None,
+        diagnostics,
+    })
+}
+
+/// Prefer an actual pedal over a MIDI interface that answered too
+fn select_identity(identities: Vec<DeviceIdentity>) -> Option<DeviceIdentity> {
+    if identities.is_empty() {
+        return None;
+    }
+    println!("📊 Received {} identity response(s)", identities.len());
+
     // Known MIDI interface IDs (WIDI Jack, etc.)
-    let interface_ids = vec![
+    let interface_ids = [
         vec![0x00, 0x20, 0x63], // Central Music Co. (CME) - WIDI Jack
     ];
-    
+
     // Prioritize non-interface responses (actual pedals)
-    for identity in all_responses.iter() {
-        let is_interface = interface_ids.iter().any(|id| id == &identity.manufacturer_id);
-        if !is_interface {
-            println!("🎯 Returning pedal identity (non-interface): {}", identity.description());
-            return Ok(Some(identity.clone()));
-        }
+    if let Some(identity) = identities
+        .iter()
+        .find(|identity| !interface_ids.iter().any(|id| id == &identity.manufacturer_id))
+    {
+        println!("🎯 Returning pedal identity (non-interface): {}", identity.description());
+        return Some(identity.clone());
     }
-    
+
     // If all responses are interfaces, return the first one
     println!("🔌 All responses are MIDI interfaces, returning first one");
-    Ok(Some(all_responses[0].clone()))
+    identities.into_iter().next()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::MockIdentityTransport;
 
     #[test]
     fn test_parse_identity_reply_single_byte_mfg() {
@@ -346,6 +490,81 @@ mod tests {
         assert_eq!(result.device_model, (2 << 7) | 0);
     }
 
+    fn options(attempts: u32) -> IdentityRequestOptions {
+        IdentityRequestOptions {
+            timeout_ms: 0,
+            attempts,
+            backoff_ms: 0,
+        }
+    }
+
+    const HOLOGRAM_REPLY: [u8; 15] = [
+        0xF0, 0x7E, 0x00, 0x06, 0x02, 0x00, 0x02, 0x4D, 0x0F, 0x09, 0x00, 0x00, 0x01, 0x00, 0xF7,
+    ];
+
+    #[test]
+    fn test_no_matching_input_port() {
+        let mut transport = MockIdentityTransport {
+            output_ports: vec!["Microcosm".to_string()],
+            input_ports: vec!["Microcosm MIDI In".to_string()],
+            ..MockIdentityTransport::default()
+        };
+
+        let result = request_identity_with(&mut transport, "Microcosm", options(2)).unwrap();
+
+        assert_eq!(result.identity, None);
+        assert_eq!(result.diagnostics.output_port.as_deref(), Some("Microcosm"));
+        assert_eq!(result.diagnostics.input_port, None);
+        assert_eq!(result.diagnostics.bytes_sent, 0);
+        assert_eq!(result.diagnostics.attempts, 2);
+        assert!(transport.sent.is_empty());
+    }
+
+    #[test]
+    fn test_device_ignores_inquiry() {
+        let mut transport = MockIdentityTransport::with_port("Microcosm");
+        // Something else on the port chatters, but nothing answers the inquiry
+        transport.replies = vec![vec![0xF0, 0x00, 0x02, 0x4D, 0x10, 0xF7]];
+
+        let result = request_identity_with(&mut transport, "Microcosm", options(3)).unwrap();
+
+        assert_eq!(result.identity, None);
+        assert_eq!(result.diagnostics.input_port.as_deref(), Some("Microcosm"));
+        assert_eq!(result.diagnostics.bytes_sent, 3 * IDENTITY_REQUEST.len());
+        assert_eq!(result.diagnostics.other_sysex_count, 3);
+        assert_eq!(transport.sent, vec![IDENTITY_REQUEST.to_vec(); 3]);
+    }
+
+    #[test]
+    fn test_retries_until_input_port_appears() {
+        let mut transport = MockIdentityTransport::with_port("Microcosm");
+        transport.input_ports_missing_for = 1;
+        transport.silent_exchanges = 1;
+        transport.replies = vec![HOLOGRAM_REPLY.to_vec()];
+
+        let result = request_identity_with(&mut transport, "Microcosm", options(3)).unwrap();
+
+        let identity = result.identity.unwrap();
+        assert_eq!(identity.manufacturer_name(), Some("Hologram Electronics LLC"));
+        assert_eq!(result.diagnostics.attempts, 3);
+        assert_eq!(result.diagnostics.bytes_sent, 2 * IDENTITY_REQUEST.len());
+        assert_eq!(result.diagnostics.other_sysex_count, 0);
+    }
+
+    #[test]
+    fn test_prefers_pedal_over_interface() {
+        let widi_reply = vec![
+            0xF0, 0x7E, 0x00, 0x06, 0x02, 0x00, 0x20, 0x63, 0x48, 0x45, 0x43, 0x43, 0x00, 0xF7,
+        ];
+        let mut transport = MockIdentityTransport::with_port("WIDI Jack");
+        transport.replies = vec![widi_reply, HOLOGRAM_REPLY.to_vec()];
+
+        let result = request_identity_with(&mut transport, "WIDI Jack", options(1)).unwrap();
+
+        assert_eq!(result.identity.unwrap().manufacturer_id, vec![0x00, 0x02, 0x4D]);
+        assert_eq!(result.diagnostics.attempts, 1);
+    }
+
     #[test]
     fn test_identity_request_format() {
         assert_eq!(
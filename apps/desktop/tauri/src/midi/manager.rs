@@ -1,13 +1,42 @@
 // MIDI Manager - Central hub for all MIDI communication
 // Handles device connections, message sending, and state management
 
+use crate::journal::{JournalMessage, SessionJournal};
+use crate::midi::ab_compare::{AbBuffers, AbSlot, AbStatus};
+use crate::midi::bypass::{BypassControl, BypassResult};
+use crate::midi::cc_value::CcValue;
+use crate::midi::channel_verification::{ChannelActivity, ChannelMismatchEvent};
+use crate::midi::connection_test::{self, ConnectionProbe};
+use crate::midi::device_state::DeviceState;
+use crate::midi::device_labels;
 use crate::midi::error::{MidiError, MidiResult};
-use crate::midi::pedals::{Microcosm, GenLossMkii, ChromaConsole, PreampMk2, Cxm1978, MoodMkii, BillyStringsWombtone, Lossy, BrothersAm, ReverseModeC, Clean, Onward};
-use crate::midi::pedals::microcosm::{MicrocosmParameter, MicrocosmState};
-use crate::midi::pedals::gen_loss_mkii::{GenLossMkiiParameter, GenLossMkiiState, CC_PRESET_SAVE as GEN_LOSS_CC_PRESET_SAVE};
-use crate::midi::pedals::chroma_console::{ChromaConsoleParameter, ChromaConsoleState};
-use crate::midi::pedals::preamp_mk2::{PreampMk2Parameter, PreampMk2State, CC_PRESET_SAVE as PREAMP_CC_PRESET_SAVE};
-use crate::midi::pedals::cxm1978::{Cxm1978Parameter, Cxm1978State, CC_PRESET_SAVE as CXM_CC_PRESET_SAVE};
+use crate::midi::exit_actions::{self, ExitAction, ExitActionResult, ExitActionSettings, ExitStatus, ShutdownPreset};
+use crate::midi::fake_device::{self, FakeLogEntry, FakePort};
+use crate::midi::firmware::{FirmwareGate, FirmwareVersion, FirmwareWarning};
+use crate::midi::identity::DeviceIdentity;
+use crate::midi::nrpn::Nrpn;
+use crate::midi::parameter_batch::PedalParameter;
+use crate::midi::pedal_state::{self, PedalState};
+use crate::midi::parameter_history::{HistoryChange, HistoryEntry, HistoryStep, ParameterHistory, UndoStack};
+use crate::midi::safety::{self, SafetyThresholds, SafetyWarning};
+use crate::midi::scheduler::{BeatClock, RecallScheduler, RecallTarget, ScheduleSpec, ScheduledRecall};
+use crate::midi::send_plan::{Glide, PlanEstimate, SendPlan, SendProgress, SendProgressEvent, DEFAULT_SEND_DELAY, SEND_PROGRESS_EVENT};
+use crate::midi::connect_retry::{ConnectionAttempt, CONNECTION_ATTEMPT_EVENT};
+use crate::midi::pc_remap::{self, PcRemapOutcome, PcRemapStore, MONITORED_PC_EVENT, PC_REMAP_EXECUTED_EVENT};
+use crate::midi::routing::{ChannelDemux, InputChannelMode, OutputPort, OutputRouter, Route, RoutedOutput, SharedInputMode};
+use crate::midi::state_diff::{diff_json, FieldChange};
+use crate::midi::state_snapshot::{SnapshotStack, StateSnapshot};
+use crate::midi::virtual_port::{VirtualPort, VirtualPortMessage};
+use crate::midi::device_detection::{self, MidiDeviceInfo, MidiPortList};
+use crate::midi::port_metadata::PortMetadata;
+use crate::midi::pedals::{Microcosm, GenLossMkii, ChromaConsole, PreampMk2, Cxm1978, MoodMkii, BillyStringsWombtone, Lossy, BrothersAm, ReverseModeC, Clean, Onward, Thermae, DarkWorld, Habit, Collider};
+use crate::midi::pedals::microcosm::{LooperTransport, MicrocosmParameter, MicrocosmState, GLIDE_CCS as MICROCOSM_GLIDE_CCS};
+use crate::midi::pedals::microcosm::bank_dump::BankDumps;
+use crate::midi::pedals::gen_loss_mkii::{DipProfile, DipProfileStore, GenLossDipSwitches, GenLossMkiiParameter, GenLossMkiiState, CC_PRESET_SAVE as GEN_LOSS_CC_PRESET_SAVE};
+use crate::midi::pedals::chroma_console::{self, BypassMode, BypassState, CaptureMode, CaptureRouting, CaptureTransport, ChromaConsoleParameter, ChromaConsoleState, GLIDE_CCS as CHROMA_CONSOLE_GLIDE_CCS, USER_PRESET_START as CHROMA_USER_PRESET_START};
+use crate::midi::pedals::chroma_console::capture::CaptureTimers;
+use crate::midi::pedals::preamp_mk2::{reachable_fader_value, PreampMk2Parameter, PreampMk2State, CC_PRESET_SAVE as PREAMP_CC_PRESET_SAVE, FADER_CCS as PREAMP_FADER_CCS};
+use crate::midi::pedals::cxm1978::{Cxm1978Parameter, Cxm1978State, CC_PRESET_SAVE as CXM_CC_PRESET_SAVE, GLIDE_CCS as CXM_GLIDE_CCS};
 use crate::midi::pedals::mood_mkii::{MoodMkiiParameter, MoodMkiiState, CC_PRESET_SAVE as MOOD_CC_PRESET_SAVE};
 use crate::midi::pedals::billy_strings_wombtone::{BillyStringsWombtoneParameter, BillyStringsWombtoneState, CC_PRESET_SAVE as BSW_CC_PRESET_SAVE};
 use crate::midi::pedals::lossy::{LossyParameter, LossyState, CC_PRESET_SAVE as LOSSY_CC_PRESET_SAVE};
@@ -15,15 +44,36 @@ use crate::midi::pedals::brothers_am::{BrothersAmParameter, BrothersAmState, CC_
 use crate::midi::pedals::reverse_mode_c::{ReverseModeCParameter, ReverseModeCState, CC_PRESET_SAVE as REVERSE_MODE_C_CC_PRESET_SAVE};
 use crate::midi::pedals::clean::{CleanParameter, CleanState, CC_PRESET_SAVE as CLEAN_CC_PRESET_SAVE};
 use crate::midi::pedals::onward::{OnwardParameter, OnwardState, CC_PRESET_SAVE as ONWARD_CC_PRESET_SAVE};
+use crate::midi::pedals::thermae::{ThermaeParameter, ThermaeState, CC_PRESET_SAVE as THERMAE_CC_PRESET_SAVE};
+use crate::midi::pedals::dark_world::{DarkWorldParameter, DarkWorldState, CC_PRESET_SAVE as DARK_WORLD_CC_PRESET_SAVE};
+use crate::midi::pedals::habit::{HabitParameter, HabitState, CC_PRESET_SAVE as HABIT_CC_PRESET_SAVE};
+use crate::midi::pedals::source_audio_collider::{self, ColliderParameter, ColliderState};
 use serde::{Serialize, Deserialize};
-use tauri::Emitter;
+use tauri::{Emitter, Manager};
 
-use midir::{MidiOutput, MidiOutputConnection, MidiInput, MidiInputConnection, Ignore};
-use std::collections::HashMap;
+use midir::{MidiOutput, MidiInput, MidiInputConnection, Ignore};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::PathBuf;
+use std::sync::mpsc::Receiver;
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 
+/// Longest a played note is held before its Note Off
+pub const MAX_NOTE_DURATION: Duration = Duration::from_secs(10);
+
+/// How long to hold a note of `duration_ms`, refusing anything past `MAX_NOTE_DURATION`
+pub fn note_duration(duration_ms: u64) -> MidiResult<Duration> {
+    let duration = Duration::from_millis(duration_ms);
+    if duration > MAX_NOTE_DURATION {
+        return Err(MidiError::InvalidValue {
+            expected: format!("a note duration of at most {} ms", MAX_NOTE_DURATION.as_millis()),
+            actual: duration_ms,
+        });
+    }
+    Ok(duration)
+}
+
 /// MIDI CC message event payload for frontend
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MidiCCEvent {
@@ -34,6 +84,48 @@ pub struct MidiCCEvent {
     pub value: u8,
 }
 
+/// Microcosm looper transport change payload for frontend
+/// Believed state only - the pedal doesn't echo looper transport over MIDI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LooperStateEvent {
+    pub device_name: String,
+    pub looper_transport: LooperTransport,
+}
+
+/// Chroma Console one-shot capture start/stop payload for frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChromaCaptureEvent {
+    pub device_name: String,
+    pub capture_transport: CaptureTransport,
+    pub duration_ms: Option<u64>,
+}
+
+/// A scheduled recall that fired, or was missed, for frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduledRecallEvent {
+    #[serde(flatten)]
+    pub recall: ScheduledRecall,
+    /// Why a missed recall didn't go out
+    pub reason: Option<String>,
+}
+
+/// CC/PC received on Librarian's virtual port, for frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VirtualPortEvent {
+    pub port_name: String,
+    #[serde(flatten)]
+    pub message: VirtualPortMessage,
+}
+
+/// Fields of a device's believed state that changed, for frontend animation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeviceStateChangedEvent {
+    pub device_name: String,
+    pub pedal_type: String,
+    pub operation_id: Option<String>,
+    pub changes: Vec<FieldChange>,
+}
+
 /// Type of pedal device
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum PedalType {
@@ -49,6 +141,95 @@ pub enum PedalType {
     ReverseModeC,
     Clean,
     Onward,
+    Thermae,
+    DarkWorld,
+    Habit,
+    Collider,
+}
+
+impl PedalType {
+    /// Every supported pedal, in the order they were added. The Collider isn't listed until
+    /// its CC map is confirmed (see `source_audio_collider::CC_MAP_CONFIRMED`).
+    pub const ALL: &'static [PedalType] = &[
+        PedalType::Microcosm,
+        PedalType::GenLossMkii,
+        PedalType::ChromaConsole,
+        PedalType::PreampMk2,
+        PedalType::Cxm1978,
+        PedalType::MoodMkii,
+        PedalType::BillyStringsWombtone,
+        PedalType::Lossy,
+        PedalType::BrothersAm,
+        PedalType::ReverseModeC,
+        PedalType::Clean,
+        PedalType::Onward,
+        PedalType::Thermae,
+        PedalType::DarkWorld,
+        PedalType::Habit,
+    ];
+    
+    /// Parse the pedal type name used by the frontend and presets (e.g. "GenLossMkii")
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "Microcosm" => Some(PedalType::Microcosm),
+            "GenLossMkii" => Some(PedalType::GenLossMkii),
+            "ChromaConsole" => Some(PedalType::ChromaConsole),
+            "PreampMk2" => Some(PedalType::PreampMk2),
+            "Cxm1978" => Some(PedalType::Cxm1978),
+            "MoodMkii" => Some(PedalType::MoodMkii),
+            "BillyStringsWombtone" => Some(PedalType::BillyStringsWombtone),
+            "Lossy" => Some(PedalType::Lossy),
+            "BrothersAm" => Some(PedalType::BrothersAm),
+            "ReverseModeC" => Some(PedalType::ReverseModeC),
+            "Clean" => Some(PedalType::Clean),
+            "Onward" => Some(PedalType::Onward),
+            "Thermae" => Some(PedalType::Thermae),
+            "DarkWorld" => Some(PedalType::DarkWorld),
+            "Habit" => Some(PedalType::Habit),
+            "Collider" => Some(PedalType::Collider),
+            _ => None,
+        }
+    }
+    
+    /// Name used by the frontend, presets and events - the inverse of `from_name`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PedalType::Microcosm => "Microcosm",
+            PedalType::GenLossMkii => "GenLossMkii",
+            PedalType::ChromaConsole => "ChromaConsole",
+            PedalType::PreampMk2 => "PreampMk2",
+            PedalType::Cxm1978 => "Cxm1978",
+            PedalType::MoodMkii => "MoodMkii",
+            PedalType::BillyStringsWombtone => "BillyStringsWombtone",
+            PedalType::Lossy => "Lossy",
+            PedalType::BrothersAm => "BrothersAm",
+            PedalType::ReverseModeC => "ReverseModeC",
+            PedalType::Clean => "Clean",
+            PedalType::Onward => "Onward",
+            PedalType::Thermae => "Thermae",
+            PedalType::DarkWorld => "DarkWorld",
+            PedalType::Habit => "Habit",
+            PedalType::Collider => "Collider",
+        }
+    }
+    
+    /// CCs a gliding recall ramps unless told otherwise; none for pedals without
+    /// a mix or level control worth smoothing
+    pub fn default_glide_ccs(&self) -> &'static [u8] {
+        match self {
+            PedalType::Microcosm => &MICROCOSM_GLIDE_CCS,
+            PedalType::ChromaConsole => &CHROMA_CONSOLE_GLIDE_CCS,
+            PedalType::Cxm1978 => &CXM_GLIDE_CCS,
+            _ => &[],
+        }
+    }
+    
+    /// The glide a recall command asked for: `glide_ccs`, or this pedal's defaults,
+    /// ramped over `glide_ms`. None (jump) when `glide_ms` is missing or 0.
+    pub fn glide(&self, glide_ms: Option<u64>, glide_ccs: Option<Vec<u8>>) -> Option<Glide> {
+        let glide_ccs = glide_ccs.unwrap_or_else(|| self.default_glide_ccs().to_vec());
+        Glide::from_millis(glide_ms.unwrap_or(0), glide_ccs)
+    }
 }
 
 /// Information about a connected device
@@ -56,47 +237,318 @@ pub enum PedalType {
 pub struct ConnectedDevice {
     pub device_name: String,
     pub pedal_type: PedalType,
+    /// Physical port - shared by devices daisy-chained over MIDI thru
+    pub port_name: String,
     pub midi_channel: u8,
+    pub channel_verified: bool,
+    /// User-given label, or the port name when there is none
+    pub display_label: String,
+    /// From the pedal's identity reply; None until it has answered one
+    pub firmware: Option<FirmwareVersion>,
+    /// Whether sends may go out (see `MidiManager::arm_device`)
+    pub armed: bool,
+    /// Channels incoming messages are accepted on
+    pub input_mode: InputChannelMode,
 }
 
-/// Active MIDI connection with bidirectional capability
+/// A logical device's route to its pedal. Devices daisy-chained on one port share the
+/// port's output; the channel comes from the route and is stamped on at send time.
 struct MidiConnection {
-    output: MidiOutputConnection,
-    #[allow(dead_code)]
-    input: Option<MidiInputConnection<()>>,
-    midi_channel: u8,
+    output: RoutedOutput,
+    journal: Option<ConnectionJournal>,
+    /// Set once the pedal's firmware is known
+    firmware: Option<FirmwareGate>,
+    /// CCs this operation sent (or dropped) that the firmware predates
+    firmware_warnings: Vec<FirmwareWarning>,
+    /// Until armed, every send fails with DeviceNotArmed
+    armed: bool,
+    /// CCs held back instead of sent (Preamp MK II faders while their motors are off)
+    held_ccs: Vec<u8>,
+    /// Latest value each held CC would have sent, released in CC number order
+    pending_ccs: BTreeMap<u8, u8>,
+    /// Keep Preamp MK II fader CCs within the motors' reach, whichever path sends them
+    /// (single sends, batches, glide ramps, undo)
+    strict_fader_range: bool,
+    /// While a gliding recall runs: the glide and the CC values it ramps from
+    glide: Option<(Glide, HashMap<u8, u8>)>,
+    /// Channels incoming messages are accepted on, shared with the input listener
+    input_mode: SharedInputMode,
+    /// Told how far each plan has got after every step
+    progress: Option<ProgressSink>,
+    /// The pedal's CC map is unconfirmed: CCs (and NRPNs, which are CCs) are refused with
+    /// an error rather than sent
+    cc_map_unconfirmed: bool,
+}
+
+/// Receives a connection's progress through the plans it sends
+type ProgressSink = Arc<dyn Fn(&SendProgress) + Send + Sync>;
+
+/// Where a connection's outbound messages are journaled
+struct ConnectionJournal {
+    journal: SessionJournal,
+    device_name: String,
+    pedal_type: PedalType,
+}
+
+impl ConnectionJournal {
+    fn record(&self, channel: u8, message: JournalMessage) {
+        self.journal.record(&self.device_name, Some(&self.pedal_type), channel, message);
+    }
 }
 
 impl MidiConnection {
-    /// Send a Control Change message
+    fn new(output: RoutedOutput) -> Self {
+        Self {
+            output,
+            journal: None,
+            firmware: None,
+            firmware_warnings: Vec::new(),
+            armed: true,
+            held_ccs: Vec::new(),
+            pending_ccs: BTreeMap::new(),
+            strict_fader_range: false,
+            glide: None,
+            input_mode: SharedInputMode::default(),
+            progress: None,
+            cc_map_unconfirmed: false,
+        }
+    }
+    
+    fn midi_channel(&self) -> u8 {
+        self.output.route.channel
+    }
+    
+    fn port_name(&self) -> &str {
+        &self.output.route.port_name
+    }
+    
+    /// Refuse to send until the device is armed
+    fn check_armed(&self) -> MidiResult<()> {
+        if self.armed {
+            return Ok(());
+        }
+        Err(MidiError::DeviceNotArmed(format!(
+            "'{}' on MIDI channel {}", self.port_name(), self.midi_channel()
+        )))
+    }
+    
+    /// Refuse CCs when the pedal's CC map hasn't been confirmed
+    fn check_cc_map(&self) -> MidiResult<()> {
+        if !self.cc_map_unconfirmed {
+            return Ok(());
+        }
+        Err(MidiError::Other(format!("The CC map for '{}' is unconfirmed", self.port_name())))
+    }
+    
+    /// Send a Control Change message on this device's channel
     fn send_cc(&mut self, cc_number: u8, value: u8) -> MidiResult<()> {
-        // MIDI CC message format: [Status byte, CC number, Value]
-        // Status byte = 0xB0 + (channel - 1)
-        let status = 0xB0 + (self.midi_channel - 1);
-        let message = [status, cc_number, value];
+        self.check_armed()?;
+        self.check_cc_map()?;
+        if let Some(warning) = self.firmware.as_ref().and_then(|gate| gate.check(cc_number)) {
+            eprintln!(
+                "⚠️ CC {} needs firmware {} but '{}' has {}{}",
+                cc_number, warning.required, self.port_name(), warning.installed,
+                if warning.skipped { ", not sending" } else { "" }
+            );
+            let skipped = warning.skipped;
+            if !self.firmware_warnings.contains(&warning) {
+                self.firmware_warnings.push(warning);
+            }
+            if skipped {
+                return Ok(());
+            }
+        }
+        let value = if self.strict_fader_range { reachable_fader_value(cc_number, value) } else { value };
+        if self.held_ccs.contains(&cc_number) {
+            self.pending_ccs.insert(cc_number, value);
+            return Ok(());
+        }
+        self.output.send_cc(cc_number, value)?;
         
-        self.output
-            .send(&message)
-            .map_err(|e| MidiError::SendFailed(e.to_string()))?;
+        if let Some(journal) = &self.journal {
+            journal.record(self.midi_channel(), JournalMessage::ControlChange { cc_number, value });
+        }
         
         Ok(())
     }
     
-    /// Send a Program Change message
+    /// Send a 14-bit NRPN write as its four CCs
+    fn send_nrpn(&mut self, nrpn: &Nrpn) -> MidiResult<()> {
+        for (cc_number, value) in nrpn.cc_messages() {
+            self.send_cc(cc_number, value)?;
+        }
+        Ok(())
+    }
+    
+    /// Send a recall plan's CCs in order, pausing after each so the pedal keeps up
+    fn send_plan(&mut self, plan: &SendPlan) -> MidiResult<()> {
+        self.send_plan_tracked(plan).map_err(|(_, e)| e)
+    }
+    
+    /// `send_plan`, but a failure also says how many steps went out before it
+    fn send_plan_tracked(&mut self, plan: &SendPlan) -> Result<(), (usize, MidiError)> {
+        // Check every value up front so a bad one can't leave the pedal half-recalled
+        if let Some(step) = plan.steps().iter().find(|step| CcValue::new(step.value).is_err()) {
+            return Err((0, MidiError::InvalidValue {
+                expected: format!("a CC value (0-127) for CC {}", step.cc_number),
+                actual: step.value.into(),
+            }));
+        }
+        let glided;
+        let plan = match &self.glide {
+            Some((glide, from)) => {
+                glided = plan.clone().with_glide(glide, from);
+                &glided
+            }
+            None => plan,
+        };
+        let estimate = plan.estimate();
+        for (sent, step) in plan.steps().iter().enumerate() {
+            self.send_cc(step.cc_number, step.value).map_err(|e| (sent, e))?;
+            tokio::task::block_in_place(|| thread::sleep(step.delay()));
+            if let Some(progress) = &self.progress {
+                progress(&estimate.progress(sent + 1));
+            }
+        }
+        Ok(())
+    }
+    
+    /// Send a Program Change message on this device's channel
     fn send_program_change(&mut self, program: u8) -> MidiResult<()> {
-        // MIDI Program Change format: [Status byte, Program number]
-        // Status byte = 0xC0 + (channel - 1)
-        let status = 0xC0 + (self.midi_channel - 1);
-        let message = [status, program];
+        self.check_armed()?;
+        self.output.send_program_change(program)?;
         
-        self.output
-            .send(&message)
-            .map_err(|e| MidiError::SendFailed(e.to_string()))?;
+        if let Some(journal) = &self.journal {
+            journal.record(self.midi_channel(), JournalMessage::ProgramChange { program });
+        }
+        
+        Ok(())
+    }
+    
+    /// Send a Note On message on this device's channel
+    fn send_note_on(&mut self, note: u8, velocity: u8) -> MidiResult<()> {
+        self.check_armed()?;
+        self.output.send_note_on(note, velocity)?;
+        
+        if let Some(journal) = &self.journal {
+            journal.record(self.midi_channel(), JournalMessage::NoteOn { note, velocity });
+        }
+        
+        Ok(())
+    }
+    
+    /// Send a Note Off message on this device's channel. Not gated on arming: a device
+    /// disarmed while a note was held still has to let go of it.
+    fn send_note_off(&mut self, note: u8) -> MidiResult<()> {
+        self.output.send_note_off(note)?;
+        
+        if let Some(journal) = &self.journal {
+            journal.record(self.midi_channel(), JournalMessage::NoteOff { note });
+        }
         
         Ok(())
     }
 }
 
+/// Logical device listening on a (possibly shared) input port
+#[derive(Clone)]
+struct InputListener {
+    device_name: String,
+    pedal_type: PedalType,
+    channel: u8,
+    activity: ChannelActivity,
+    input_mode: SharedInputMode,
+    /// Applies the CCs the pedal sends to its believed state, for pedals that track them
+    state_updates: Option<OrderedWorker<(u8, u8)>>,
+}
+
+/// Runs queued items one at a time, in the order they arrived, on its own thread - off
+/// the MIDI thread, since applying them needs the manager lock and disconnecting drops
+/// an input connection while the manager is locked. One task per message could run out
+/// of order and leave a knob sweep on a stale value. The thread ends once every clone
+/// of the worker is gone.
+struct OrderedWorker<T> {
+    sender: std::sync::mpsc::Sender<T>,
+}
+
+impl<T> Clone for OrderedWorker<T> {
+    fn clone(&self) -> Self {
+        Self { sender: self.sender.clone() }
+    }
+}
+
+impl<T: Send + 'static> OrderedWorker<T> {
+    fn spawn(mut apply: impl FnMut(T) + Send + 'static) -> Self {
+        let (sender, receiver) = std::sync::mpsc::channel::<T>();
+        thread::spawn(move || {
+            for item in receiver {
+                apply(item);
+            }
+        });
+        Self { sender }
+    }
+    
+    fn push(&self, item: T) {
+        let _ = self.sender.send(item);
+    }
+}
+
+impl OrderedWorker<(u8, u8)> {
+    /// A worker keeping a device's believed state in step with the CCs its pedal sends,
+    /// or None for pedals whose state isn't tracked from their output. Microcosm bank
+    /// dumps snapshot that state, so it has to end on the pedal's last value.
+    fn for_device(app_handle: tauri::AppHandle, device_name: String, pedal_type: PedalType) -> Option<Self> {
+        if !matches!(pedal_type, PedalType::Cxm1978 | PedalType::Microcosm) {
+            return None;
+        }
+        Some(Self::spawn(move |(cc_number, value)| {
+            let manager = app_handle.state::<SharedMidiManager>();
+            let Ok(mut manager) = manager.lock() else { return };
+            let result = match pedal_type {
+                PedalType::Cxm1978 => manager.update_cxm1978_state_from_cc(&device_name, cc_number, value).map(|_| ()),
+                _ => manager.update_microcosm_state_from_cc(&device_name, cc_number, value),
+            };
+            if let Err(e) = result {
+                eprintln!("❌ Failed to update state from MIDI CC: {}", e);
+            }
+        }))
+    }
+}
+
+impl InputListener {
+    /// Whether a message on `channel` is for this device under its input mode
+    fn accepts(&self, channel: u8) -> bool {
+        self.input_mode.get().accepts(self.channel, channel)
+    }
+
+    /// The frontend event for an incoming message, if it is a Control Change on a channel
+    /// this device accepts. Reports the channel it actually arrived on.
+    fn cc_event(&self, message: &[u8]) -> Option<MidiCCEvent> {
+        let [status, cc_number, value, ..] = *message else { return None };
+        if !(0xB0..=0xBF).contains(&status) {
+            return None;
+        }
+        let channel = (status & 0x0F) + 1;
+        if !self.accepts(channel) {
+            return None;
+        }
+        Some(MidiCCEvent {
+            device_name: self.device_name.clone(),
+            pedal_type: self.pedal_type.as_str().to_string(),
+            channel,
+            cc_number,
+            value,
+        })
+    }
+}
+
+/// Input connection for one physical port, shared by the logical devices on it
+struct PortInput {
+    /// None for fake ports, whose echoes are delivered by the port itself
+    _connection: Option<MidiInputConnection<()>>,
+    demux: ChannelDemux<InputListener>,
+}
+
 /// Device-specific connection wrapper
 enum DeviceConnection {
     Microcosm {
@@ -147,25 +599,463 @@ enum DeviceConnection {
         connection: MidiConnection,
         state: Onward,
     },
+    Thermae {
+        connection: MidiConnection,
+        state: Thermae,
+    },
+    DarkWorld {
+        connection: MidiConnection,
+        state: DarkWorld,
+    },
+    Habit {
+        connection: MidiConnection,
+        state: Habit,
+    },
+    Collider {
+        connection: MidiConnection,
+        state: Collider,
+    },
+}
+
+impl DeviceConnection {
+    /// A freshly connected device of `pedal_type` in its default state
+    fn open(pedal_type: PedalType, connection: MidiConnection) -> Self {
+        let midi_channel = connection.midi_channel();
+        match pedal_type {
+            PedalType::Microcosm => DeviceConnection::Microcosm { connection, state: Microcosm::new(midi_channel) },
+            PedalType::GenLossMkii => DeviceConnection::GenLossMkii { connection, state: GenLossMkii::new(midi_channel) },
+            PedalType::ChromaConsole => DeviceConnection::ChromaConsole { connection, state: ChromaConsole::new(midi_channel) },
+            PedalType::PreampMk2 => {
+                let state = PreampMk2::new(midi_channel);
+                let mut connection = connection;
+                connection.strict_fader_range = state.strict_fader_range;
+                DeviceConnection::PreampMk2 { connection, state }
+            }
+            PedalType::Cxm1978 => DeviceConnection::Cxm1978 { connection, state: Cxm1978::new(midi_channel) },
+            PedalType::MoodMkii => DeviceConnection::MoodMkii { connection, state: MoodMkii::new(midi_channel) },
+            PedalType::BillyStringsWombtone => DeviceConnection::BillyStringsWombtone { connection, state: BillyStringsWombtone::new(midi_channel) },
+            PedalType::Lossy => DeviceConnection::Lossy { connection, state: Lossy::new(midi_channel) },
+            PedalType::BrothersAm => DeviceConnection::BrothersAm { connection, state: BrothersAm::new(midi_channel) },
+            PedalType::ReverseModeC => DeviceConnection::ReverseModeC { connection, state: ReverseModeC::new(midi_channel) },
+            PedalType::Clean => DeviceConnection::Clean { connection, state: Clean::new(midi_channel) },
+            PedalType::Onward => DeviceConnection::Onward { connection, state: Onward::new(midi_channel) },
+            PedalType::Thermae => DeviceConnection::Thermae { connection, state: Thermae::new(midi_channel) },
+            PedalType::DarkWorld => DeviceConnection::DarkWorld { connection, state: DarkWorld::new(midi_channel) },
+            PedalType::Habit => DeviceConnection::Habit { connection, state: Habit::new(midi_channel) },
+            PedalType::Collider => {
+                let mut connection = connection;
+                connection.cc_map_unconfirmed = !source_audio_collider::CC_MAP_CONFIRMED;
+                DeviceConnection::Collider { connection, state: Collider::new(midi_channel) }
+            }
+        }
+    }
+    
+    fn pedal_type(&self) -> PedalType {
+        match self {
+            DeviceConnection::Microcosm { .. } => PedalType::Microcosm,
+            DeviceConnection::GenLossMkii { .. } => PedalType::GenLossMkii,
+            DeviceConnection::ChromaConsole { .. } => PedalType::ChromaConsole,
+            DeviceConnection::PreampMk2 { .. } => PedalType::PreampMk2,
+            DeviceConnection::Cxm1978 { .. } => PedalType::Cxm1978,
+            DeviceConnection::MoodMkii { .. } => PedalType::MoodMkii,
+            DeviceConnection::BillyStringsWombtone { .. } => PedalType::BillyStringsWombtone,
+            DeviceConnection::Lossy { .. } => PedalType::Lossy,
+            DeviceConnection::BrothersAm { .. } => PedalType::BrothersAm,
+            DeviceConnection::ReverseModeC { .. } => PedalType::ReverseModeC,
+            DeviceConnection::Clean { .. } => PedalType::Clean,
+            DeviceConnection::Onward { .. } => PedalType::Onward,
+            DeviceConnection::Thermae { .. } => PedalType::Thermae,
+            DeviceConnection::DarkWorld { .. } => PedalType::DarkWorld,
+            DeviceConnection::Habit { .. } => PedalType::Habit,
+            DeviceConnection::Collider { .. } => PedalType::Collider,
+        }
+    }
+    
+    fn connection(&self) -> &MidiConnection {
+        match self {
+            DeviceConnection::Microcosm { connection, .. } => connection,
+            DeviceConnection::GenLossMkii { connection, .. } => connection,
+            DeviceConnection::ChromaConsole { connection, .. } => connection,
+            DeviceConnection::PreampMk2 { connection, .. } => connection,
+            DeviceConnection::Cxm1978 { connection, .. } => connection,
+            DeviceConnection::MoodMkii { connection, .. } => connection,
+            DeviceConnection::BillyStringsWombtone { connection, .. } => connection,
+            DeviceConnection::Lossy { connection, .. } => connection,
+            DeviceConnection::BrothersAm { connection, .. } => connection,
+            DeviceConnection::ReverseModeC { connection, .. } => connection,
+            DeviceConnection::Clean { connection, .. } => connection,
+            DeviceConnection::Onward { connection, .. } => connection,
+            DeviceConnection::Thermae { connection, .. } => connection,
+            DeviceConnection::DarkWorld { connection, .. } => connection,
+            DeviceConnection::Habit { connection, .. } => connection,
+            DeviceConnection::Collider { connection, .. } => connection,
+        }
+    }
+    
+    fn connection_mut(&mut self) -> &mut MidiConnection {
+        match self {
+            DeviceConnection::Microcosm { connection, .. } => connection,
+            DeviceConnection::GenLossMkii { connection, .. } => connection,
+            DeviceConnection::ChromaConsole { connection, .. } => connection,
+            DeviceConnection::PreampMk2 { connection, .. } => connection,
+            DeviceConnection::Cxm1978 { connection, .. } => connection,
+            DeviceConnection::MoodMkii { connection, .. } => connection,
+            DeviceConnection::BillyStringsWombtone { connection, .. } => connection,
+            DeviceConnection::Lossy { connection, .. } => connection,
+            DeviceConnection::BrothersAm { connection, .. } => connection,
+            DeviceConnection::ReverseModeC { connection, .. } => connection,
+            DeviceConnection::Clean { connection, .. } => connection,
+            DeviceConnection::Onward { connection, .. } => connection,
+            DeviceConnection::Thermae { connection, .. } => connection,
+            DeviceConnection::DarkWorld { connection, .. } => connection,
+            DeviceConnection::Habit { connection, .. } => connection,
+            DeviceConnection::Collider { connection, .. } => connection,
+        }
+    }
+    
+    /// Current believed state as CC number -> value
+    fn cc_map(&self) -> HashMap<u8, u8> {
+        match self {
+            DeviceConnection::Microcosm { state, .. } => state.state_as_cc_map(),
+            DeviceConnection::GenLossMkii { state, .. } => state.state_as_cc_map(),
+            DeviceConnection::ChromaConsole { state, .. } => state.state_as_cc_map(),
+            DeviceConnection::PreampMk2 { state, .. } => state.state_as_cc_map(),
+            DeviceConnection::Cxm1978 { state, .. } => state.state_as_cc_map(),
+            DeviceConnection::MoodMkii { state, .. } => state.state_as_cc_map(),
+            DeviceConnection::BillyStringsWombtone { state, .. } => state.state_as_cc_map(),
+            DeviceConnection::Lossy { state, .. } => state.state_as_cc_map(),
+            DeviceConnection::BrothersAm { state, .. } => state.state_as_cc_map(),
+            DeviceConnection::ReverseModeC { state, .. } => state.state_as_cc_map(),
+            DeviceConnection::Clean { state, .. } => state.state_as_cc_map(),
+            DeviceConnection::Onward { state, .. } => state.state_as_cc_map(),
+            DeviceConnection::Thermae { state, .. } => state.state_as_cc_map(),
+            DeviceConnection::DarkWorld { state, .. } => state.state_as_cc_map(),
+            DeviceConnection::Habit { state, .. } => state.state_as_cc_map(),
+            DeviceConnection::Collider { state, .. } => state.state_as_cc_map(),
+        }
+    }
+    
+    /// Current believed state, typed
+    fn pedal_state(&self) -> PedalState {
+        match self {
+            DeviceConnection::Microcosm { state, .. } => PedalState::Microcosm(state.state.clone()),
+            DeviceConnection::GenLossMkii { state, .. } => PedalState::GenLossMkii(state.state.clone()),
+            DeviceConnection::ChromaConsole { state, .. } => PedalState::ChromaConsole(state.state.clone()),
+            DeviceConnection::PreampMk2 { state, .. } => PedalState::PreampMk2(state.state.clone()),
+            DeviceConnection::Cxm1978 { state, .. } => PedalState::Cxm1978(state.state.clone()),
+            DeviceConnection::MoodMkii { state, .. } => PedalState::MoodMkii(state.state.clone()),
+            DeviceConnection::BillyStringsWombtone { state, .. } => PedalState::BillyStringsWombtone(state.state.clone()),
+            DeviceConnection::Lossy { state, .. } => PedalState::Lossy(state.state.clone()),
+            DeviceConnection::BrothersAm { state, .. } => PedalState::BrothersAm(state.state.clone()),
+            DeviceConnection::ReverseModeC { state, .. } => PedalState::ReverseModeC(state.state.clone()),
+            DeviceConnection::Clean { state, .. } => PedalState::Clean(state.state.clone()),
+            DeviceConnection::Onward { state, .. } => PedalState::Onward(state.state.clone()),
+            DeviceConnection::Thermae { state, .. } => PedalState::Thermae(state.state.clone()),
+            DeviceConnection::DarkWorld { state, .. } => PedalState::DarkWorld(state.state.clone()),
+            DeviceConnection::Habit { state, .. } => PedalState::Habit(state.state.clone()),
+            DeviceConnection::Collider { state, .. } => PedalState::Collider(state.state.clone()),
+        }
+    }
+    
+    /// Current believed state tagged with the pedal type
+    fn device_state(&self) -> MidiResult<DeviceState> {
+        match self {
+            DeviceConnection::Microcosm { state, .. } => DeviceState::new(&PedalType::Microcosm, &state.state),
+            DeviceConnection::GenLossMkii { state, .. } => DeviceState::new(&PedalType::GenLossMkii, &state.state),
+            DeviceConnection::ChromaConsole { state, .. } => DeviceState::new(&PedalType::ChromaConsole, &state.state),
+            DeviceConnection::PreampMk2 { state, .. } => DeviceState::new(&PedalType::PreampMk2, &state.state),
+            DeviceConnection::Cxm1978 { state, .. } => DeviceState::new(&PedalType::Cxm1978, &state.state),
+            DeviceConnection::MoodMkii { state, .. } => DeviceState::new(&PedalType::MoodMkii, &state.state),
+            DeviceConnection::BillyStringsWombtone { state, .. } => DeviceState::new(&PedalType::BillyStringsWombtone, &state.state),
+            DeviceConnection::Lossy { state, .. } => DeviceState::new(&PedalType::Lossy, &state.state),
+            DeviceConnection::BrothersAm { state, .. } => DeviceState::new(&PedalType::BrothersAm, &state.state),
+            DeviceConnection::ReverseModeC { state, .. } => DeviceState::new(&PedalType::ReverseModeC, &state.state),
+            DeviceConnection::Clean { state, .. } => DeviceState::new(&PedalType::Clean, &state.state),
+            DeviceConnection::Onward { state, .. } => DeviceState::new(&PedalType::Onward, &state.state),
+            DeviceConnection::Thermae { state, .. } => DeviceState::new(&PedalType::Thermae, &state.state),
+            DeviceConnection::DarkWorld { state, .. } => DeviceState::new(&PedalType::DarkWorld, &state.state),
+            DeviceConnection::Habit { state, .. } => DeviceState::new(&PedalType::Habit, &state.state),
+            DeviceConnection::Collider { state, .. } => DeviceState::new(&PedalType::Collider, &state.state),
+        }
+    }
+    
+    /// Current believed state serialized the same way presets store it
+    fn state_json(&self) -> MidiResult<serde_json::Value> {
+        self.device_state().map(|device_state| device_state.state)
+    }
+    
+    /// Replace the believed state without sending anything
+    fn set_state_json(&mut self, value: serde_json::Value) -> MidiResult<()> {
+        fn parse<T: serde::de::DeserializeOwned>(value: serde_json::Value) -> MidiResult<T> {
+            serde_json::from_value(value)
+                .map_err(|e| MidiError::Other(format!("Failed to restore state: {}", e)))
+        }
+        
+        match self {
+            DeviceConnection::Microcosm { state, .. } => state.state = parse(value)?,
+            DeviceConnection::GenLossMkii { state, .. } => state.state = GenLossMkiiState::from_json_preset(&value)?,
+            DeviceConnection::ChromaConsole { state, .. } => state.state = parse(value)?,
+            DeviceConnection::PreampMk2 { state, .. } => state.state = parse(value)?,
+            DeviceConnection::Cxm1978 { state, .. } => state.state = parse(value)?,
+            DeviceConnection::MoodMkii { state, .. } => state.state = parse(value)?,
+            DeviceConnection::BillyStringsWombtone { state, .. } => state.state = parse(value)?,
+            DeviceConnection::Lossy { state, .. } => state.state = parse(value)?,
+            DeviceConnection::BrothersAm { state, .. } => state.state = parse(value)?,
+            DeviceConnection::ReverseModeC { state, .. } => state.state = parse(value)?,
+            DeviceConnection::Clean { state, .. } => state.state = parse(value)?,
+            DeviceConnection::Onward { state, .. } => state.state = parse(value)?,
+            DeviceConnection::Thermae { state, .. } => state.state = parse(value)?,
+            DeviceConnection::DarkWorld { state, .. } => state.state = parse(value)?,
+            DeviceConnection::Habit { state, .. } => state.state = parse(value)?,
+            DeviceConnection::Collider { state, .. } => state.state = parse(value)?,
+        }
+        Ok(())
+    }
+    
+    /// Replace the believed state the way a recall would, keeping live transport state,
+    /// which a recall never sends
+    fn load_state_json(&mut self, value: serde_json::Value) -> MidiResult<()> {
+        match self {
+            DeviceConnection::ChromaConsole { state, .. } => {
+                let live = state.state.clone();
+                self.set_state_json(value)?;
+                if let DeviceConnection::ChromaConsole { state, .. } = self {
+                    state.state.gesture_mode = live.gesture_mode;
+                    state.state.capture_mode = live.capture_mode;
+                    state.state.capture_transport = live.capture_transport;
+                }
+            }
+            _ => self.set_state_json(value)?,
+        }
+        Ok(())
+    }
+    
+    /// What recalling the believed state sends. Without `include_config` it's what a
+    /// library preset recall sends, which leaves Gen Loss MKII DIP switches alone.
+    fn recall_plan_for(&self, include_config: bool) -> SendPlan {
+        match self {
+            DeviceConnection::GenLossMkii { state, .. } if !include_config => state.state.sound_recall_plan(),
+            _ => self.recall_plan(),
+        }
+    }
+    
+    /// Current believed state as CCs in recall order
+    fn recall_plan(&self) -> SendPlan {
+        match self {
+            DeviceConnection::Microcosm { state, .. } => state.recall_plan(),
+            DeviceConnection::GenLossMkii { state, .. } => state.recall_plan(),
+            DeviceConnection::ChromaConsole { state, .. } => state.recall_plan(),
+            DeviceConnection::PreampMk2 { state, .. } => state.recall_plan(),
+            DeviceConnection::Cxm1978 { state, .. } => state.recall_plan(),
+            DeviceConnection::MoodMkii { state, .. } => state.recall_plan(),
+            DeviceConnection::BillyStringsWombtone { state, .. } => state.recall_plan(),
+            DeviceConnection::Lossy { state, .. } => state.recall_plan(),
+            DeviceConnection::BrothersAm { state, .. } => state.recall_plan(),
+            DeviceConnection::ReverseModeC { state, .. } => state.recall_plan(),
+            DeviceConnection::Clean { state, .. } => state.recall_plan(),
+            DeviceConnection::Onward { state, .. } => state.recall_plan(),
+            DeviceConnection::Thermae { state, .. } => state.recall_plan(),
+            DeviceConnection::DarkWorld { state, .. } => state.recall_plan(),
+            DeviceConnection::Habit { state, .. } => state.recall_plan(),
+            DeviceConnection::Collider { state, .. } => state.recall_plan(),
+        }
+    }
+    
+    /// A parameter as this device sends it: Preamp MK II fader values within reach under
+    /// a strict fader range, anything else as is
+    fn within_range(&self, param: PedalParameter) -> PedalParameter {
+        match (self, param) {
+            (DeviceConnection::PreampMk2 { state, .. }, PedalParameter::PreampMk2(p)) if state.strict_fader_range => {
+                PedalParameter::PreampMk2(p.clamped_to_fader_range())
+            }
+            (_, param) => param,
+        }
+    }
+    
+    /// Bring restored believed state within what the device is set to send
+    fn keep_state_within_range(&mut self) {
+        if let DeviceConnection::PreampMk2 { state, .. } = self {
+            if state.strict_fader_range {
+                state.state = state.state.clamped_to_fader_range();
+            }
+        }
+    }
+    
+    /// The CCs a batch parameter sends, checking it's for this pedal. One CC per
+    /// parameter, except NRPN parameters, which are their four CCs.
+    fn batch_ccs(&self, param: &PedalParameter) -> MidiResult<Vec<(u8, u8)>> {
+        let (cc_number, cc_value) = match (self, param) {
+            (DeviceConnection::Microcosm { .. }, PedalParameter::Microcosm(p)) => {
+                if p.program_number().is_some() {
+                    return Err(MidiError::Other(format!(
+                        "{} is a program change and can't be sent in a batch", p.name()
+                    )));
+                }
+                (p.cc_number(), p.cc_value())
+            }
+            (DeviceConnection::GenLossMkii { .. }, PedalParameter::GenLossMkii(p)) => (p.cc_number(), p.cc_value()),
+            (DeviceConnection::ChromaConsole { state, .. }, PedalParameter::ChromaConsole(p)) => {
+                p.to_cc_message_for_mode(state.bypass_mode).ok_or_else(|| MidiError::Other(format!(
+                    "{} can't be sent in {:?} bypass mode", p.name(), state.bypass_mode
+                )))?
+            }
+            (DeviceConnection::PreampMk2 { .. }, PedalParameter::PreampMk2(p)) => (p.cc_number(), p.cc_value()),
+            (DeviceConnection::Cxm1978 { .. }, PedalParameter::Cxm1978(p)) => (p.cc_number(), p.cc_value()),
+            (DeviceConnection::MoodMkii { .. }, PedalParameter::MoodMkii(p)) => (p.cc_number(), p.cc_value()),
+            (DeviceConnection::BillyStringsWombtone { .. }, PedalParameter::BillyStringsWombtone(p)) => (p.cc_number(), p.cc_value()),
+            (DeviceConnection::Lossy { .. }, PedalParameter::Lossy(p)) => (p.cc_number(), p.cc_value()),
+            (DeviceConnection::BrothersAm { .. }, PedalParameter::BrothersAm(p)) => (p.cc_number(), p.cc_value()),
+            (DeviceConnection::ReverseModeC { .. }, PedalParameter::ReverseModeC(p)) => (p.cc_number(), p.cc_value()),
+            (DeviceConnection::Clean { .. }, PedalParameter::Clean(p)) => (p.cc_number(), p.cc_value()),
+            (DeviceConnection::Onward { .. }, PedalParameter::Onward(p)) => (p.cc_number(), p.cc_value()),
+            (DeviceConnection::Thermae { .. }, PedalParameter::Thermae(p)) => (p.cc_number(), p.cc_value()),
+            (DeviceConnection::DarkWorld { .. }, PedalParameter::DarkWorld(p)) => (p.cc_number(), p.cc_value()),
+            (DeviceConnection::Habit { .. }, PedalParameter::Habit(p)) => (p.cc_number(), p.cc_value()),
+            (DeviceConnection::Collider { connection, .. }, PedalParameter::Collider(p)) => {
+                connection.check_cc_map()?;
+                if let Some(nrpn) = p.nrpn()? {
+                    return Ok(nrpn.cc_messages().to_vec());
+                }
+                (p.cc_number(), p.cc_value())
+            }
+            _ => {
+                return Err(MidiError::Other(format!(
+                    "{} is a {} parameter, not {}",
+                    param.name(), param.pedal_type().as_str(), self.pedal_type().as_str()
+                )))
+            }
+        };
+        CcValue::new(cc_value)?;
+        Ok(vec![(cc_number, cc_value)])
+    }
+    
+    /// Apply a sent batch parameter to the believed state (parameters for other pedals are ignored)
+    fn apply_parameter(&mut self, param: &PedalParameter) {
+        match (self, param) {
+            (DeviceConnection::Microcosm { state, .. }, PedalParameter::Microcosm(p)) => state.update_state(p),
+            (DeviceConnection::GenLossMkii { state, .. }, PedalParameter::GenLossMkii(p)) => state.update_state(p),
+            (DeviceConnection::ChromaConsole { state, .. }, PedalParameter::ChromaConsole(p)) => state.update_state(p),
+            (DeviceConnection::PreampMk2 { state, .. }, PedalParameter::PreampMk2(p)) => state.update_state(p),
+            (DeviceConnection::Cxm1978 { state, .. }, PedalParameter::Cxm1978(p)) => state.update_state(p),
+            (DeviceConnection::MoodMkii { state, .. }, PedalParameter::MoodMkii(p)) => state.update_state(p),
+            (DeviceConnection::BillyStringsWombtone { state, .. }, PedalParameter::BillyStringsWombtone(p)) => state.update_state(p),
+            (DeviceConnection::Lossy { state, .. }, PedalParameter::Lossy(p)) => state.update_state(p),
+            (DeviceConnection::BrothersAm { state, .. }, PedalParameter::BrothersAm(p)) => state.update_state(p),
+            (DeviceConnection::ReverseModeC { state, .. }, PedalParameter::ReverseModeC(p)) => state.update_state(p),
+            (DeviceConnection::Clean { state, .. }, PedalParameter::Clean(p)) => state.update_state(p),
+            (DeviceConnection::Onward { state, .. }, PedalParameter::Onward(p)) => state.update_state(p),
+            (DeviceConnection::Thermae { state, .. }, PedalParameter::Thermae(p)) => state.update_state(p),
+            (DeviceConnection::DarkWorld { state, .. }, PedalParameter::DarkWorld(p)) => state.update_state(p),
+            (DeviceConnection::Habit { state, .. }, PedalParameter::Habit(p)) => state.update_state(p),
+            (DeviceConnection::Collider { state, .. }, PedalParameter::Collider(p)) => state.update_state(p),
+            _ => {}
+        }
+    }
+    
+    /// How this pedal is bypassed
+    fn bypass_control(&self) -> &dyn BypassControl {
+        match self {
+            DeviceConnection::Microcosm { state, .. } => state,
+            DeviceConnection::GenLossMkii { state, .. } => state,
+            DeviceConnection::ChromaConsole { state, .. } => state,
+            DeviceConnection::PreampMk2 { state, .. } => state,
+            DeviceConnection::Cxm1978 { state, .. } => state,
+            DeviceConnection::MoodMkii { state, .. } => state,
+            DeviceConnection::BillyStringsWombtone { state, .. } => state,
+            DeviceConnection::Lossy { state, .. } => state,
+            DeviceConnection::BrothersAm { state, .. } => state,
+            DeviceConnection::ReverseModeC { state, .. } => state,
+            DeviceConnection::Clean { state, .. } => state,
+            DeviceConnection::Onward { state, .. } => state,
+            DeviceConnection::Thermae { state, .. } => state,
+            DeviceConnection::DarkWorld { state, .. } => state,
+            DeviceConnection::Habit { state, .. } => state,
+            DeviceConnection::Collider { state, .. } => state,
+        }
+    }
+}
+
+/// Device state captured before a tracked change
+struct HistoryCheckpoint {
+    cc_map: HashMap<u8, u8>,
+    state: serde_json::Value,
 }
 
 /// Central MIDI Manager for all device communication
 pub struct MidiManager {
     connections: HashMap<String, DeviceConnection>,
-    midi_output: Option<MidiOutput>,
+    outputs: OutputRouter,
+    inputs: HashMap<String, PortInput>,
     app_handle: Option<tauri::AppHandle>,
+    virtual_port: Option<VirtualPort>,
+    channel_activity: HashMap<String, ChannelActivity>,
+    snapshots: HashMap<String, SnapshotStack>,
+    histories: HashMap<String, ParameterHistory>,
+    history_paused: bool,
+    journal: Option<SessionJournal>,
+    operation_id: Option<String>,
+    device_labels: HashMap<String, String>,
+    device_labels_path: Option<PathBuf>,
+    safety_thresholds: SafetyThresholds,
+    safety_thresholds_path: Option<PathBuf>,
+    exit_actions: ExitActionSettings,
+    exit_actions_path: Option<PathBuf>,
+    capture_timers: CaptureTimers,
+    recall_scheduler: RecallScheduler,
+    bank_dumps: BankDumps,
+    ab_buffers: HashMap<String, AbBuffers>,
+    bypass_snapshots: HashMap<String, Vec<PedalParameter>>,
+    /// In-memory ports standing in for pedals, by port name
+    fake_ports: HashMap<String, FakePort>,
+    demo_mode: bool,
+    /// Drop CCs a device's firmware predates instead of sending them anyway
+    skip_unsupported_ccs: bool,
+    /// Arm devices as they connect; off, nothing is sent until `arm_device`
+    auto_arm: bool,
+    gen_loss_dip_profiles: DipProfileStore,
+    pc_remap: PcRemapStore,
+    /// Inputs watched for PCs to remap (foot controllers), by port name
+    monitored_inputs: HashMap<String, MidiInputConnection<()>>,
+    /// Inputs to monitor again on the next launch, including any not open right now
+    saved_monitored_inputs: BTreeSet<String>,
+    monitored_inputs_path: Option<PathBuf>,
 }
 
 impl MidiManager {
     /// Create a new MIDI Manager
     pub fn new() -> MidiResult<Self> {
-        let midi_output = MidiOutput::new("Librarian Output")
-            .map_err(|e| MidiError::Other(e.to_string()))?;
-        
         Ok(Self {
             connections: HashMap::new(),
-            midi_output: Some(midi_output),
+            outputs: OutputRouter::default(),
+            inputs: HashMap::new(),
             app_handle: None,
+            virtual_port: None,
+            channel_activity: HashMap::new(),
+            snapshots: HashMap::new(),
+            histories: HashMap::new(),
+            history_paused: false,
+            journal: None,
+            operation_id: None,
+            device_labels: HashMap::new(),
+            device_labels_path: None,
+            safety_thresholds: SafetyThresholds::default(),
+            safety_thresholds_path: None,
+            exit_actions: ExitActionSettings::default(),
+            exit_actions_path: None,
+            capture_timers: CaptureTimers::default(),
+            recall_scheduler: RecallScheduler::default(),
+            bank_dumps: BankDumps::default(),
+            ab_buffers: HashMap::new(),
+            bypass_snapshots: HashMap::new(),
+            fake_ports: HashMap::new(),
+            demo_mode: false,
+            skip_unsupported_ccs: false,
+            auto_arm: true,
+            gen_loss_dip_profiles: DipProfileStore::default(),
+            pc_remap: PcRemapStore::default(),
+            monitored_inputs: HashMap::new(),
+            saved_monitored_inputs: BTreeSet::new(),
+            monitored_inputs_path: None,
         })
     }
     
@@ -174,317 +1064,514 @@ impl MidiManager {
         self.app_handle = Some(handle);
     }
     
-    /// Setup MIDI input listener for a device
-    fn setup_midi_input(
-        &self,
-        device_name: &str,
-        pedal_type: PedalType,
-        midi_channel: u8,
-    ) -> MidiResult<Option<MidiInputConnection<()>>> {
-        // Only setup input if we have an app handle
-        if self.app_handle.is_none() {
-            println!("⚠️  No app handle available, skipping MIDI input setup");
-            return Ok(None);
+    /// Record everything sent to hardware in the session journal
+    pub fn set_journal(&mut self, journal: SessionJournal) {
+        self.journal = Some(journal);
+        let device_names: Vec<String> = self.connections.keys().cloned().collect();
+        for device_name in device_names {
+            self.attach_journal(&device_name);
         }
-        
-        let mut midi_in = MidiInput::new("Librarian Input")
-            .map_err(|e| MidiError::Other(e.to_string()))?;
-        midi_in.ignore(Ignore::None);
-        
-        // List all available input ports (debug mode only)
-        #[cfg(debug_assertions)]
-        {
-            println!("🔍 Available MIDI input ports:");
-            for port in midi_in.ports().iter() {
-                if let Ok(name) = midi_in.port_name(port) {
-                    println!("   - {}", name);
-                }
-            }
+    }
+    
+    /// Load device labels from (and save future changes to) this file
+    pub fn set_device_labels_path(&mut self, path: PathBuf) -> MidiResult<()> {
+        self.device_labels = device_labels::load_labels(&path)?;
+        self.device_labels_path = Some(path);
+        Ok(())
+    }
+    
+    /// Give a connected device a display label. An empty label goes back to the port name.
+    pub fn set_device_label(&mut self, device_name: &str, label: &str) -> MidiResult<()> {
+        if !self.connections.contains_key(device_name) {
+            return Err(MidiError::NotConnected(device_name.to_string()));
         }
         
-        // Find the matching input port
-        let in_ports = midi_in.ports();
-        let port_opt = in_ports.into_iter()
-            .find(|p| {
-                midi_in.port_name(p)
-                    .map(|name| {
-                        let matches = name.to_lowercase().contains(&device_name.to_lowercase());
-                        if matches {
-                            println!("✅ Found matching input port: {}", name);
-                        }
-                        matches
-                    })
-                    .unwrap_or(false)
-            });
-        
-        if let Some(port) = port_opt {
-            let device_name_clone = device_name.to_string();
-            let pedal_type_str = match pedal_type {
-                PedalType::Microcosm => "Microcosm".to_string(),
-                PedalType::GenLossMkii => "GenLossMkii".to_string(),
-                PedalType::ChromaConsole => "ChromaConsole".to_string(),
-                PedalType::PreampMk2 => "PreampMk2".to_string(),
-                PedalType::Cxm1978 => "Cxm1978".to_string(),
-                PedalType::MoodMkii => "MoodMkii".to_string(),
-                PedalType::BillyStringsWombtone => "BillyStringsWombtone".to_string(),
-                PedalType::Lossy => "Lossy".to_string(),
-                PedalType::BrothersAm => "BrothersAm".to_string(),
-                PedalType::ReverseModeC => "ReverseModeC".to_string(),
-                PedalType::Clean => "Clean".to_string(),
-                PedalType::Onward => "Onward".to_string(),
-            };
-            let app_handle = self.app_handle.as_ref().unwrap().clone();
-            
-            let conn_in = midi_in.connect(
-                &port,
-                "librarian-listener",
-                move |_stamp, message, _| {
-                    if message.is_empty() {
-                        return;
-                    }
-                    
-                    let status = message[0];
-                    
-                    // Filter out System Real-Time messages (0xF8-0xFF)
-                    // 0xF8 = MIDI Clock (sent 24 times per quarter note)
-                    // 0xFA = Start, 0xFB = Continue, 0xFC = Stop
-                    // 0xFE = Active Sensing, 0xFF = System Reset
-                    if status >= 0xF8 {
-                        // Silently ignore timing/sync messages
-                        return;
-                    }
-                    
-                    // Parse CC messages (need at least 3 bytes)
-                    if message.len() >= 3 {
-                        let data1 = message[1];
-                        let data2 = message[2];
-                        
-                        // Check if it's a Control Change message (0xB0-0xBF)
-                        if status >= 0xB0 && status <= 0xBF {
-                            let channel = (status & 0x0F) + 1;
-                            
-                            // Process messages on the correct channel
-                            if channel == midi_channel {
-                                let event = MidiCCEvent {
-                                    device_name: device_name_clone.clone(),
-                                    pedal_type: pedal_type_str.clone(),
-                                    channel,
-                                    cc_number: data1,
-                                    value: data2,
-                                };
-                                
-                                println!("📥 MIDI CC: {}, CC#={}, Value={}", 
-                                    event.device_name, event.cc_number, event.value);
-                                
-                                // Emit event to frontend
-                                if let Err(e) = app_handle.emit("midi-cc-received", &event) {
-                                    eprintln!("❌ Failed to emit MIDI event: {}", e);
-                                }
-                            }
-                        }
-                    }
-                },
-                (),
-            ).map_err(|e| MidiError::ConnectionFailed(e.to_string()))?;
-            
-            println!("✅ MIDI input listener setup for: {}", device_name);
-            Ok(Some(conn_in))
+        let label = label.trim();
+        if label.is_empty() {
+            self.device_labels.remove(device_name);
         } else {
-            println!("⚠️  No MIDI input port found for: {}", device_name);
-            Ok(None)
+            self.device_labels.insert(device_name.to_string(), label.to_string());
         }
-    }
-    
-    /// List all available MIDI output devices
-    pub fn list_devices(&self) -> MidiResult<Vec<String>> {
-        let midi_out = MidiOutput::new("Librarian Scanner")
-            .map_err(|e| MidiError::Other(e.to_string()))?;
         
-        let mut devices = Vec::new();
-        for port in midi_out.ports().iter() {
-            if let Ok(name) = midi_out.port_name(port) {
-                devices.push(name);
-            }
+        if let Some(path) = &self.device_labels_path {
+            device_labels::save_labels(path, &self.device_labels)?;
         }
-        
-        Ok(devices)
+        Ok(())
     }
-
-    /// Send a Program Change on a specific channel to a device without establishing
-    /// a persistent connection. Used to trigger channel reassignment on pedals that
-    /// accept the first received PC to set their new MIDI channel.
-    pub fn send_channel_assignment_pc(&self, device_name: &str, channel: u8) -> MidiResult<()> {
-        if channel < 1 || channel > 16 {
-            return Err(MidiError::InvalidChannel(channel));
+    
+    /// Display label for a port - the user's label if set, otherwise the port name
+    pub fn device_label(&self, device_name: &str) -> String {
+        self.device_labels
+            .get(device_name)
+            .cloned()
+            .unwrap_or_else(|| device_name.to_string())
+    }
+    
+    /// Load loud-parameter thresholds from (and save future changes to) this file
+    pub fn set_safety_thresholds_path(&mut self, path: PathBuf) -> MidiResult<()> {
+        self.safety_thresholds = safety::load_thresholds(&path)?;
+        self.safety_thresholds_path = Some(path);
+        Ok(())
+    }
+    
+    pub fn safety_thresholds(&self) -> &SafetyThresholds {
+        &self.safety_thresholds
+    }
+    
+    /// Replace the loud-parameter thresholds
+    pub fn set_safety_thresholds(&mut self, thresholds: SafetyThresholds) -> MidiResult<()> {
+        if let Some(path) = &self.safety_thresholds_path {
+            safety::save_thresholds(path, &thresholds)?;
         }
-
-        let midi_out = MidiOutput::new("Librarian Channel Assign")
-            .map_err(|e| MidiError::Other(e.to_string()))?;
-
-        let ports = midi_out.ports();
-        let port = ports
-            .iter()
-            .find(|p| {
-                midi_out
-                    .port_name(p)
-                    .map(|name| name.to_lowercase().contains(&device_name.to_lowercase()))
-                    .unwrap_or(false)
+        self.safety_thresholds = thresholds;
+        Ok(())
+    }
+    
+    /// Load exit actions from (and save future changes to) this file
+    pub fn set_exit_actions_path(&mut self, path: PathBuf) -> MidiResult<()> {
+        self.exit_actions = exit_actions::load_settings(&path)?;
+        self.exit_actions_path = Some(path);
+        Ok(())
+    }
+    
+    pub fn exit_actions(&self) -> &ExitActionSettings {
+        &self.exit_actions
+    }
+    
+    /// Replace the exit actions
+    pub fn set_exit_actions(&mut self, settings: ExitActionSettings) -> MidiResult<()> {
+        if let Some(path) = &self.exit_actions_path {
+            exit_actions::save_settings(path, &settings)?;
+        }
+        self.exit_actions = settings;
+        Ok(())
+    }
+    
+    /// Loud parameters that recalling `target` (a state serialized like a preset) would raise
+    pub fn recall_safety_warnings<S: Serialize>(&self, device_name: &str, target: &S) -> MidiResult<Vec<SafetyWarning>> {
+        let current = self.get_device_state(device_name)?;
+        let target = serde_json::to_value(target)
+            .map_err(|e| MidiError::Other(format!("Failed to serialize state: {}", e)))?;
+        Ok(safety::check_safety(&self.safety_thresholds, &current.pedal_type, &current.state, &target))
+    }
+    
+    /// Record the firmware an identity reply on `port_name` reported. Devices daisy-chained
+    /// on the port only take it if the reply's device ID matches their channel.
+    /// Returns the devices updated.
+    pub fn record_device_identity(&mut self, port_name: &str, identity: &DeviceIdentity) -> Vec<String> {
+        let on_port: Vec<String> = self.connections.iter()
+            .filter(|(_, device)| device.connection().port_name() == port_name)
+            .map(|(name, _)| name.clone())
+            .collect();
+        let version = FirmwareVersion::from_identity_bytes(&identity.software_version);
+        let updated: Vec<String> = on_port.iter()
+            .filter(|name| {
+                on_port.len() == 1 || self.connections[name.as_str()].connection().midi_channel() == identity.device_id + 1
             })
-            .ok_or_else(|| MidiError::DeviceNotFound(device_name.to_string()))?;
-
-        let mut output = midi_out
-            .connect(port, "Librarian Channel Assign")
-            .map_err(|e| MidiError::ConnectionFailed(e.to_string()))?;
-
-        // Send PC 1 — same value used to recall the first bank slot.
-        // Program 0 is outside the valid range on CB pedals (1–122), so using 1
-        // ensures the pedal treats this as a valid PC and reads the channel from
-        // the status byte to complete the channel assignment.
-        let status = 0xC0 + (channel - 1);
-        output
-            .send(&[status, 1])
-            .map_err(|e| MidiError::SendFailed(e.to_string()))?;
-
-        println!(
-            "✅ Sent channel assignment PC on channel {} to '{}'",
-            channel, device_name
-        );
-
+            .cloned()
+            .collect();
+        for name in &updated {
+            let _ = self.set_device_firmware(name, version);
+        }
+        updated
+    }
+    
+    /// Record a device's firmware, gating the CCs it predates
+    pub fn set_device_firmware(&mut self, device_name: &str, version: FirmwareVersion) -> MidiResult<()> {
+        let skip_unsupported = self.skip_unsupported_ccs;
+        let device = self.connections.get_mut(device_name)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        let pedal_type = device.pedal_type();
+        device.connection_mut().firmware = Some(FirmwareGate { pedal_type, installed: version, skip_unsupported });
         Ok(())
     }
     
-    /// Connect to a Microcosm pedal
-    pub fn connect_microcosm(
+    /// Firmware the device reported, if it has been identified
+    pub fn device_firmware(&self, device_name: &str) -> Option<FirmwareVersion> {
+        self.connections.get(device_name)?
+            .connection()
+            .firmware
+            .as_ref()
+            .map(|gate| gate.installed)
+    }
+    
+    /// Whether CCs a device's firmware predates are dropped (true) or sent with a warning
+    pub fn skip_unsupported_ccs(&self) -> bool {
+        self.skip_unsupported_ccs
+    }
+    
+    pub fn set_skip_unsupported_ccs(&mut self, skip: bool) {
+        self.skip_unsupported_ccs = skip;
+        for device in self.connections.values_mut() {
+            if let Some(gate) = &mut device.connection_mut().firmware {
+                gate.skip_unsupported = skip;
+            }
+        }
+    }
+    
+    /// Firmware warnings from the current operation's sends to a device
+    pub fn take_firmware_warnings(&mut self, device_name: &str) -> Vec<FirmwareWarning> {
+        self.connections.get_mut(device_name)
+            .map(|device| std::mem::take(&mut device.connection_mut().firmware_warnings))
+            .unwrap_or_default()
+    }
+    
+    /// Whether devices are armed as they connect
+    pub fn auto_arm(&self) -> bool {
+        self.auto_arm
+    }
+    
+    /// Arm devices as they connect (true), or hold every new connection silent until
+    /// `arm_device` so connecting never touches a pedal mid-performance (false).
+    /// Devices already connected keep their armed state.
+    pub fn set_auto_arm(&mut self, auto_arm: bool) {
+        self.auto_arm = auto_arm;
+    }
+    
+    /// Allow sends to a device connected while auto-arm was off
+    pub fn arm_device(&mut self, device_name: &str) -> MidiResult<()> {
+        let device = self.connections.get_mut(device_name)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        device.connection_mut().armed = true;
+        Ok(())
+    }
+    
+    /// Change which channels a connected device's incoming messages are accepted on.
+    /// Takes effect on the next message; the device stays connected.
+    pub fn set_input_channel_mode(&mut self, device_name: &str, mode: InputChannelMode) -> MidiResult<()> {
+        mode.validate()?;
+        let device = self.connections.get(device_name)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        device.connection().input_mode.set(mode);
+        Ok(())
+    }
+    
+    pub fn input_channel_mode(&self, device_name: &str) -> MidiResult<InputChannelMode> {
+        self.connections
+            .get(device_name)
+            .map(|device| device.connection().input_mode.get())
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))
+    }
+    
+    /// Run `connect`, then apply `input_mode` to the device it connected. The mode is
+    /// checked first, so a bad channel list fails before anything is connected.
+    pub fn connect_with_input_mode(
         &mut self,
         device_name: &str,
-        midi_channel: u8,
+        input_mode: Option<&InputChannelMode>,
+        connect: impl FnOnce(&mut Self) -> MidiResult<()>,
     ) -> MidiResult<()> {
-        // Validate channel (1-16)
-        if midi_channel < 1 || midi_channel > 16 {
-            return Err(MidiError::InvalidChannel(midi_channel));
+        if let Some(mode) = input_mode {
+            mode.validate()?;
         }
-        
-        // Check if already connected
-        if self.connections.contains_key(device_name) {
-            return Err(MidiError::AlreadyConnected(device_name.to_string()));
+        connect(self)?;
+        match input_mode {
+            Some(mode) => self.set_input_channel_mode(device_name, mode.clone()),
+            None => Ok(()),
         }
+    }
+    
+    pub fn is_armed(&self, device_name: &str) -> bool {
+        self.connections
+            .get(device_name)
+            .is_some_and(|device| device.connection().armed)
+    }
+    
+    /// Run `send` with the arm guard lifted for one device - for sends the user asked
+    /// for explicitly. The device stays unarmed afterwards.
+    pub fn user_initiated<T>(&mut self, device_name: &str, send: impl FnOnce(&mut Self) -> MidiResult<T>) -> MidiResult<T> {
+        let was_armed = self.is_armed(device_name);
+        if let Some(device) = self.connections.get_mut(device_name) {
+            device.connection_mut().armed = true;
+        }
+        let result = send(self);
+        if let Some(device) = self.connections.get_mut(device_name) {
+            device.connection_mut().armed = was_armed;
+        }
+        result
+    }
+    
+    /// Run `recall` with `glide` ramping its continuous CCs from the device's believed
+    /// values instead of jumping them. No glide runs `recall` as is.
+    pub fn gliding<T>(&mut self, device_name: &str, glide: Option<Glide>, recall: impl FnOnce(&mut Self) -> MidiResult<T>) -> MidiResult<T> {
+        let Some(glide) = glide else { return recall(self) };
+        if let Some(device) = self.connections.get_mut(device_name) {
+            let from = device.cc_map();
+            device.connection_mut().glide = Some((glide, from));
+        }
+        let result = recall(self);
+        if let Some(device) = self.connections.get_mut(device_name) {
+            device.connection_mut().glide = None;
+        }
+        result
+    }
+    
+    /// Emit `send-progress` as each of the device's plans goes out
+    fn attach_progress(&mut self, device_name: &str) {
+        let Some(app_handle) = self.app_handle.clone() else { return };
+        if let Some(device) = self.connections.get_mut(device_name) {
+            let device_name = device_name.to_string();
+            device.connection_mut().progress = Some(Arc::new(move |progress: &SendProgress| {
+                let event = SendProgressEvent { device_name: device_name.clone(), progress: *progress };
+                if let Err(e) = app_handle.emit(SEND_PROGRESS_EVENT, &event) {
+                    eprintln!("❌ Failed to emit send progress: {}", e);
+                }
+            }));
+        }
+    }
+    
+    /// Tag the following sends and state change events with a new operation (the command that caused them)
+    pub fn begin_journal_operation(&mut self, name: &str) {
+        let operation_id = match &self.journal {
+            Some(journal) => journal.begin_operation(name),
+            None => uuid::Uuid::new_v4().to_string(),
+        };
+        self.operation_id = Some(operation_id);
+        for device in self.connections.values_mut() {
+            device.connection_mut().firmware_warnings.clear();
+        }
+    }
+    
+    fn attach_journal(&mut self, device_name: &str) {
+        let Some(journal) = &self.journal else { return };
+        if let Some(device) = self.connections.get_mut(device_name) {
+            let pedal_type = device.pedal_type();
+            device.connection_mut().journal = Some(ConnectionJournal {
+                journal: journal.clone(),
+                device_name: device_name.to_string(),
+                pedal_type,
+            });
+        }
+    }
+    
+    /// Open (or reopen under a new name) the virtual "Librarian" port pair.
+    /// Incoming CC/PC is re-emitted to the frontend as `virtual-port-message`.
+    pub fn enable_virtual_port(&mut self, name: &str) -> MidiResult<()> {
+        // Close any existing pair first so the name can be reused
+        self.virtual_port = None;
         
-        // Find the MIDI port
-        let midi_out = self.midi_output.take()
-            .ok_or_else(|| MidiError::Other("MIDI output not initialized".to_string()))?;
+        let app_handle = self.app_handle.clone();
+        let port_name = name.to_string();
+        let port = VirtualPort::open(name, move |message| {
+            println!("📥 [Virtual Port] {:?}", message);
+            if let Some(app_handle) = &app_handle {
+                let event = VirtualPortEvent {
+                    port_name: port_name.clone(),
+                    message,
+                };
+                if let Err(e) = app_handle.emit("virtual-port-message", &event) {
+                    eprintln!("❌ Failed to emit virtual port event: {}", e);
+                }
+            }
+        })?;
         
-        // Find the matching port by iterating and collecting the port we need
-        let port_opt = {
-            let ports = midi_out.ports();
-            ports.into_iter()
-                .find(|p| {
-                    midi_out.port_name(p)
-                        .map(|name| name.to_lowercase().contains(&device_name.to_lowercase()))
-                        .unwrap_or(false)
-                })
+        self.virtual_port = Some(port);
+        Ok(())
+    }
+    
+    /// Close the virtual port pair, if open
+    pub fn disable_virtual_port(&mut self) {
+        if let Some(port) = self.virtual_port.take() {
+            println!("🔌 [Virtual Port] Closed \"{}\"", port.name());
+        }
+    }
+    
+    /// Listen for a logical device's incoming MIDI. Devices on the same port share one
+    /// input connection and are told apart by channel.
+    fn listen_on_port(
+        &mut self,
+        device_name: &str,
+        port_name: &str,
+        pedal_type: PedalType,
+        midi_channel: u8,
+        input_mode: SharedInputMode,
+    ) -> MidiResult<()> {
+        let state_updates = self.app_handle.clone().and_then(|app_handle| {
+            OrderedWorker::for_device(app_handle, device_name.to_string(), pedal_type.clone())
+        });
+        let listener = InputListener {
+            device_name: device_name.to_string(),
+            pedal_type,
+            channel: midi_channel,
+            activity: ChannelActivity::with_input_mode(midi_channel, input_mode.clone()),
+            input_mode,
+            state_updates,
         };
         
-        let port = port_opt.ok_or_else(|| MidiError::DeviceNotFound(device_name.to_string()))?;
-        
-        // Connect to the port
-        let output = midi_out.connect(&port, "Librarian")
-            .map_err(|e| MidiError::ConnectionFailed(e.to_string()))?;
+        if let Some(input) = self.inputs.get(port_name) {
+            input.demux.add(midi_channel, listener.clone())?;
+            self.channel_activity.insert(device_name.to_string(), listener.activity);
+            println!("✅ MIDI input listener shared on '{}' for: {}", port_name, device_name);
+            return Ok(());
+        }
         
-        // Setup MIDI input for bidirectional communication
-        let input = self.setup_midi_input(device_name, PedalType::Microcosm, midi_channel)?;
+        let demux = ChannelDemux::default();
+        demux.add(midi_channel, listener.clone())?;
+        let connection = if let Some(fake) = self.fake_ports.get(port_name) {
+            let app_handle = self.app_handle.clone();
+            let echo_demux = demux.clone();
+            let beat_clock = self.recall_scheduler.beat_clock();
+            let input_port = port_name.to_string();
+            fake.respond_with(Arc::new(move |message| {
+                Self::dispatch_incoming(app_handle.as_ref(), &echo_demux, &beat_clock, &input_port, message)
+            }));
+            None
+        } else {
+            let Some(connection) = self.open_input_port(port_name, demux.clone())? else {
+                return Ok(());
+            };
+            Some(connection)
+        };
         
-        // Create connection and device state
-        let connection = MidiConnection {
-            output,
-            input,
-            midi_channel,
+        self.inputs.insert(port_name.to_string(), PortInput { _connection: connection, demux });
+        self.channel_activity.insert(device_name.to_string(), listener.activity);
+        println!("✅ MIDI input listener setup for: {}", device_name);
+        Ok(())
+    }
+    
+    /// Open an input port and dispatch its messages to the listening logical devices
+    fn open_input_port(
+        &self,
+        port_name: &str,
+        demux: ChannelDemux<InputListener>,
+    ) -> MidiResult<Option<MidiInputConnection<()>>> {
+        // Only setup input if we have an app handle
+        let Some(app_handle) = self.app_handle.clone() else {
+            println!("⚠️  No app handle available, skipping MIDI input setup");
+            return Ok(None);
         };
         
-        let state = Microcosm::new(midi_channel);
+        let mut midi_in = MidiInput::new("Librarian Input")
+            .map_err(|e| MidiError::Other(e.to_string()))?;
+        midi_in.ignore(Ignore::None);
         
-        self.connections.insert(
-            device_name.to_string(),
-            DeviceConnection::Microcosm { connection, state },
-        );
+        // List all available input ports (debug mode only)
+        #[cfg(debug_assertions)]
+        {
+            println!("🔍 Available MIDI input ports:");
+            for port in midi_in.ports().iter() {
+                if let Ok(name) = midi_in.port_name(port) {
+                    println!("   - {}", name);
+                }
+            }
+        }
+        
+        // Find the matching input port
+        let in_ports = midi_in.ports();
+        let port_opt = in_ports.into_iter()
+            .find(|p| {
+                midi_in.port_name(p)
+                    .map(|name| {
+                        let matches = name.to_lowercase().contains(&port_name.to_lowercase());
+                        if matches {
+                            println!("✅ Found matching input port: {}", name);
+                        }
+                        matches
+                    })
+                    .unwrap_or(false)
+            });
         
-        println!("✅ Connected to Microcosm: '{}' on MIDI Channel {}", device_name, midi_channel);
+        let Some(port) = port_opt else {
+            println!("⚠️  No MIDI input port found for: {}", port_name);
+            return Ok(None);
+        };
         
-        // Reinitialize MIDI output for future connections
-        self.midi_output = Some(MidiOutput::new("Librarian Output")
-            .map_err(|e| MidiError::Other(e.to_string()))?);
+        let beat_clock = self.recall_scheduler.beat_clock();
+        let input_port = port_name.to_string();
+        let conn_in = midi_in.connect(
+            &port,
+            "librarian-listener",
+            move |_stamp, message, _| {
+                Self::dispatch_incoming(Some(&app_handle), &demux, &beat_clock, &input_port, message)
+            },
+            (),
+        ).map_err(|e| MidiError::ConnectionFailed(e.to_string()))?;
         
-        Ok(())
+        Ok(Some(conn_in))
     }
     
-    /// Connect to a Gen Loss MKII pedal
-    pub fn connect_gen_loss_mkii(
-        &mut self,
-        device_name: &str,
-        midi_channel: u8,
-    ) -> MidiResult<()> {
-        // Validate channel (1-16)
-        if midi_channel < 1 || midi_channel > 16 {
-            return Err(MidiError::InvalidChannel(midi_channel));
+    /// Deliver a message heard on an input port to the logical device it is for: note the
+    /// channel it came in on, tell the frontend about CCs, and keep believed state in step.
+    /// MIDI clock goes to the beat clock scheduled recalls count on.
+    fn dispatch_incoming(
+        app_handle: Option<&tauri::AppHandle>,
+        demux: &ChannelDemux<InputListener>,
+        beat_clock: &BeatClock,
+        input_port: &str,
+        message: &[u8],
+    ) {
+        if message.is_empty() {
+            return;
         }
         
-        // Check if already connected
-        if self.connections.contains_key(device_name) {
-            return Err(MidiError::AlreadyConnected(device_name.to_string()));
-        }
+        let status = message[0];
         
-        // Find the MIDI port
-        let midi_out = self.midi_output.take()
-            .ok_or_else(|| MidiError::Other("MIDI output not initialized".to_string()))?;
+        // System Real-Time messages (0xF8-0xFF) belong to no device
+        // 0xF8 = MIDI Clock (sent 24 times per quarter note)
+        // 0xFA = Start, 0xFB = Continue, 0xFC = Stop
+        // 0xFE = Active Sensing, 0xFF = System Reset
+        if status >= 0xF8 {
+            // Clock and transport drive beat-scheduled recalls; the rest is ignored
+            beat_clock.handle(input_port, status);
+            return;
+        }
         
-        // Find the matching port by iterating and collecting the port we need
-        let port_opt = {
-            let ports = midi_out.ports();
-            ports.into_iter()
-                .find(|p| {
-                    midi_out.port_name(p)
-                        .map(|name| name.to_lowercase().contains(&device_name.to_lowercase()))
-                        .unwrap_or(false)
-                })
-        };
+        // Which logical device on this port the message is for
+        let Some(listener) = demux.route_with_fallback(status, InputListener::accepts) else { return };
         
-        let port = port_opt.ok_or_else(|| MidiError::DeviceNotFound(device_name.to_string()))?;
+        // Note which channel the device is talking on (for channel verification)
+        listener.activity.record(status);
         
-        // Connect to the port
-        let output = midi_out.connect(&port, "Librarian")
-            .map_err(|e| MidiError::ConnectionFailed(e.to_string()))?;
+        // Without an app handle there is nobody to tell (and no manager to update)
+        let Some(app_handle) = app_handle else { return };
         
-        // Setup MIDI input for bidirectional communication
-        let input = self.setup_midi_input(device_name, PedalType::GenLossMkii, midi_channel)?;
+        // Process CCs on a channel the device's input mode accepts
+        let Some(event) = listener.cc_event(message) else { return };
         
-        // Create connection and device state
-        let connection = MidiConnection {
-            output,
-            input,
-            midi_channel,
-        };
+        println!("📥 MIDI CC: {}, CC#={}, Value={}", 
+            event.device_name, event.cc_number, event.value);
         
-        let state = GenLossMkii::new(midi_channel);
+        // Emit event to frontend
+        if let Err(e) = app_handle.emit("midi-cc-received", &event) {
+            eprintln!("❌ Failed to emit MIDI event: {}", e);
+        }
         
-        self.connections.insert(
-            device_name.to_string(),
-            DeviceConnection::GenLossMkii { connection, state },
-        );
+        // Keep the backend's believed state in step with the pedal
+        if let Some(state_updates) = &listener.state_updates {
+            state_updates.push((event.cc_number, event.value));
+        }
+    }
+    
+    /// Open a physical output port by (partial) name
+    fn open_output_port(port_name: &str) -> MidiResult<Box<dyn OutputPort>> {
+        let midi_out = MidiOutput::new("Librarian Output")
+            .map_err(|e| MidiError::Other(e.to_string()))?;
         
-        // Reinitialize MIDI output for future connections
-        self.midi_output = Some(MidiOutput::new("Librarian Output")
-            .map_err(|e| MidiError::Other(e.to_string()))?);
+        let port = midi_out.ports().into_iter()
+            .find(|p| {
+                midi_out.port_name(p)
+                    .map(|name| name.to_lowercase().contains(&port_name.to_lowercase()))
+                    .unwrap_or(false)
+            })
+            .ok_or_else(|| MidiError::DeviceNotFound(port_name.to_string()))?;
         
-        Ok(())
+        let output = midi_out.connect(&port, "Librarian")
+            .map_err(|e| MidiError::ConnectionFailed(e.to_string()))?;
+        Ok(Box::new(output))
     }
     
-    /// Connect to a Chroma Console pedal
-    pub fn connect_chroma_console(
+    /// Connect a logical device: a pedal of `pedal_type` reached through `port_name` on
+    /// `midi_channel`. Several devices can share a port (MIDI thru chains) as long as
+    /// each has its own channel; the port is opened once and shared.
+    pub fn connect_on_port(
         &mut self,
+        pedal_type: PedalType,
         device_name: &str,
+        port_name: &str,
         midi_channel: u8,
     ) -> MidiResult<()> {
         // Validate channel (1-16)
-        if midi_channel < 1 || midi_channel > 16 {
+        if !(1..=16).contains(&midi_channel) {
             return Err(MidiError::InvalidChannel(midi_channel));
         }
         
@@ -493,1229 +1580,1911 @@ impl MidiManager {
             return Err(MidiError::AlreadyConnected(device_name.to_string()));
         }
         
-        // Find the MIDI port
-        let midi_out = self.midi_output.take()
-            .ok_or_else(|| MidiError::Other("MIDI output not initialized".to_string()))?;
+        // One device per channel on a port - they'd all answer the same messages
+        if self.connections.values().any(|d| {
+            d.connection().port_name() == port_name && d.connection().midi_channel() == midi_channel
+        }) {
+            return Err(MidiError::Other(format!(
+                "Another device on '{}' already uses MIDI channel {}", port_name, midi_channel
+            )));
+        }
         
-        // Find the matching port by iterating and collecting the port we need
-        let port_opt = {
-            let ports = midi_out.ports();
-            ports.into_iter()
-                .find(|p| {
-                    midi_out.port_name(p)
-                        .map(|name| name.to_lowercase().contains(&device_name.to_lowercase()))
-                        .unwrap_or(false)
-                })
+        let route = Route {
+            port_name: port_name.to_string(),
+            channel: midi_channel,
         };
-        
-        let port = port_opt.ok_or_else(|| MidiError::DeviceNotFound(device_name.to_string()))?;
-        
-        // Connect to the port
-        let output = midi_out.connect(&port, "Librarian")
-            .map_err(|e| MidiError::ConnectionFailed(e.to_string()))?;
+        let fake = self.fake_ports.get(port_name).cloned();
+        let output = self.outputs.acquire(route, || match fake {
+            Some(fake) => Ok(Box::new(fake) as Box<dyn OutputPort>),
+            None => Self::open_output_port(port_name),
+        })?;
         
         // Setup MIDI input for bidirectional communication
-        let input = self.setup_midi_input(device_name, PedalType::ChromaConsole, midi_channel)?;
-        
-        // Create connection and device state
-        let connection = MidiConnection {
-            output,
-            input,
-            midi_channel,
-        };
-        
-        let state = ChromaConsole::new(midi_channel);
+        let mut connection = MidiConnection::new(output);
+        if let Err(e) = self.listen_on_port(device_name, port_name, pedal_type.clone(), midi_channel, connection.input_mode.clone()) {
+            self.outputs.release(port_name);
+            return Err(e);
+        }
         
+        connection.armed = self.auto_arm;
         self.connections.insert(
             device_name.to_string(),
-            DeviceConnection::ChromaConsole { connection, state },
+            DeviceConnection::open(pedal_type.clone(), connection),
         );
+        self.attach_journal(device_name);
+        self.attach_progress(device_name);
         
-        println!("✅ Connected to Chroma Console: '{}' on MIDI Channel {}", device_name, midi_channel);
-        
-        // Reinitialize MIDI output for future connections
-        self.midi_output = Some(MidiOutput::new("Librarian Output")
-            .map_err(|e| MidiError::Other(e.to_string()))?);
+        println!(
+            "✅ Connected to {}: '{}' via '{}' on MIDI Channel {}",
+            pedal_type.as_str(), device_name, port_name, midi_channel
+        );
         
         Ok(())
     }
     
-    /// Disconnect from a device
-    pub fn disconnect(&mut self, device_name: &str) -> MidiResult<()> {
-        self.connections.remove(device_name)
-            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
-        Ok(())
+    /// Turn demo mode on or off. On, every pedal type gets a fake port (see `fake_device`)
+    /// that shows up in `list_devices` and connects like real hardware. Off, fake ports
+    /// nothing is connected to are removed; connected fake devices keep working.
+    pub fn set_demo_mode(&mut self, enabled: bool) {
+        self.demo_mode = enabled;
+        if enabled {
+            for pedal_type in PedalType::ALL {
+                self.fake_ports
+                    .entry(fake_device::demo_port_name(pedal_type))
+                    .or_insert_with(|| FakePort::new(Some(fake_device::DEFAULT_ECHO_DELAY)));
+            }
+        } else {
+            let outputs = &self.outputs;
+            self.fake_ports.retain(|port_name, _| outputs.is_open(port_name));
+        }
     }
     
-    /// Send a parameter change to a Microcosm
-    pub fn send_microcosm_parameter(
-        &mut self,
-        device_name: &str,
-        param: MicrocosmParameter,
-    ) -> MidiResult<()> {
-        let device = self.connections.get_mut(device_name)
-            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
-        
-        match device {
-            DeviceConnection::Microcosm { connection, state } => {
-                let cc_number = param.cc_number();
-                let cc_value = param.cc_value();
-                
-                connection.send_cc(cc_number, cc_value)?;
-                state.update_state(&param);
-                
-                Ok(())
-            }
-            _ => Err(MidiError::Other("Device is not a Microcosm".to_string())),
-        }
+    pub fn demo_mode(&self) -> bool {
+        self.demo_mode
     }
     
-    /// Send a program change to a Microcosm (select effect/preset)
-    pub fn send_microcosm_program_change(
-        &mut self,
-        device_name: &str,
-        program: u8,
-    ) -> MidiResult<()> {
-        let device = self.connections.get_mut(device_name)
-            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
-        
-        match device {
-            DeviceConnection::Microcosm { connection, state } => {
-                connection.send_program_change(program)?;
-                state.set_current_preset(program);
-                Ok(())
-            }
-            _ => Err(MidiError::Other("Device is not a Microcosm".to_string())),
-        }
+    /// Connect `pedal_type` as `device_name` through an in-memory fake port of the same name.
+    /// The device behaves like hardware: sends are logged (see `fake_device_log`) and CCs are
+    /// echoed back through the input path.
+    pub fn connect_fake(&mut self, pedal_type: PedalType, device_name: &str, midi_channel: u8) -> MidiResult<()> {
+        self.fake_ports
+            .entry(device_name.to_string())
+            .or_insert_with(|| FakePort::new(Some(fake_device::DEFAULT_ECHO_DELAY)));
+        self.connect_on_port(pedal_type, device_name, device_name, midi_channel)
     }
     
-    /// Send a parameter change to a Gen Loss MKII
-    pub fn send_gen_loss_parameter(
-        &mut self,
-        device_name: &str,
-        param: GenLossMkiiParameter,
-    ) -> MidiResult<()> {
-        let device = self.connections.get_mut(device_name)
-            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+    /// Everything sent to a fake device on its channel, oldest first
+    pub fn fake_device_log(&self, device_name: &str) -> MidiResult<Vec<FakeLogEntry>> {
+        let connection = self.connections.get(device_name)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?
+            .connection();
+        let fake = self.fake_ports.get(connection.port_name())
+            .ok_or_else(|| MidiError::Other(format!("'{}' is not a fake device", device_name)))?;
+        let channel = connection.midi_channel();
+        Ok(fake.log().into_iter().filter(|entry| entry.channel == Some(channel)).collect())
+    }
+    
+    /// List all available MIDI output devices. Ports that would loop Librarian back
+    /// into itself (its own ports, the IAC bus, the virtual port) are listed separately.
+    pub fn list_devices(&self) -> MidiResult<MidiPortList> {
+        // Demo mode has to work on machines with no MIDI system at all
+        let midir_ports = match MidiOutput::new("Librarian Scanner") {
+            Ok(midi_out) => midi_out.ports().iter().enumerate()
+                .filter_map(|(index, port)| midi_out.port_name(port).ok().map(|name| (index, name)))
+                .collect::<Vec<_>>(),
+            Err(_) if !self.fake_ports.is_empty() => Vec::new(),
+            Err(e) => return Err(MidiError::Other(e.to_string())),
+        };
+        let mut names: Vec<String> = midir_ports.iter().map(|(_, name)| name.clone()).collect();
+        let mut fake_names: Vec<String> = self.fake_ports.keys().cloned().collect();
+        fake_names.sort();
+        names.extend(fake_names);
+        let own_ports = self.virtual_port.iter().map(|port| port.name()).collect::<Vec<_>>();
         
-        match device {
-            DeviceConnection::GenLossMkii { connection, state } => {
-                let cc_number = param.cc_number();
-                let cc_value = param.cc_value();
-                
-                connection.send_cc(cc_number, cc_value)?;
-                state.update_state(&param);
-                
-                Ok(())
+        let mut ports = device_detection::partition_ports(names, &own_ports);
+        let indices = device_detection::midir_indices(&ports.devices, &midir_ports);
+        ports.device_info = ports.devices.iter().zip(indices)
+            .map(|(name, index)| match (self.fake_ports.contains_key(name), index) {
+                (false, Some(index)) => MidiDeviceInfo::new(index, name, false),
+                _ => MidiDeviceInfo::with_metadata(None, name, false, PortMetadata::default()),
+            })
+            .collect();
+        Ok(ports)
+    }
+
+    /// Send a Program Change on a specific channel to a device without establishing
+    /// a persistent connection. Used to trigger channel reassignment on pedals that
+    /// accept the first received PC to set their new MIDI channel.
+    pub fn send_channel_assignment_pc(&self, device_name: &str, channel: u8) -> MidiResult<()> {
+        if channel < 1 || channel > 16 {
+            return Err(MidiError::InvalidChannel(channel));
+        }
+
+        if let Some(fake) = self.fake_ports.get(device_name) {
+            fake.clone().send(&[0xC0 + (channel - 1), 1])?;
+            if let Some(journal) = &self.journal {
+                journal.record(device_name, None, channel, JournalMessage::ProgramChange { program: 1 });
             }
-            _ => Err(MidiError::Other("Device is not a Gen Loss MKII".to_string())),
+            return Ok(());
+        }
+
+        let midi_out = MidiOutput::new("Librarian Channel Assign")
+            .map_err(|e| MidiError::Other(e.to_string()))?;
+
+        let ports = midi_out.ports();
+        let port = ports
+            .iter()
+            .find(|p| {
+                midi_out
+                    .port_name(p)
+                    .map(|name| name.to_lowercase().contains(&device_name.to_lowercase()))
+                    .unwrap_or(false)
+            })
+            .ok_or_else(|| MidiError::DeviceNotFound(device_name.to_string()))?;
+
+        let mut output = midi_out
+            .connect(port, "Librarian Channel Assign")
+            .map_err(|e| MidiError::ConnectionFailed(e.to_string()))?;
+
+        // Send PC 1 — same value used to recall the first bank slot.
+        // Program 0 is outside the valid range on CB pedals (1–122), so using 1
+        // ensures the pedal treats this as a valid PC and reads the channel from
+        // the status byte to complete the channel assignment.
+        let status = 0xC0 + (channel - 1);
+        output
+            .send(&[status, 1])
+            .map_err(|e| MidiError::SendFailed(e.to_string()))?;
+
+        if let Some(journal) = &self.journal {
+            journal.record(device_name, None, channel, JournalMessage::ProgramChange { program: 1 });
         }
+
+        println!(
+            "✅ Sent channel assignment PC on channel {} to '{}'",
+            channel, device_name
+        );
+
+        Ok(())
     }
     
-    /// Recall a preset on a Microcosm (send all parameters)
-    pub fn recall_microcosm_preset(
+    /// Connect to a Microcosm pedal
+    pub fn connect_microcosm(
         &mut self,
         device_name: &str,
-        state: &MicrocosmState,
+        midi_channel: u8,
     ) -> MidiResult<()> {
-        let device = self.connections.get_mut(device_name)
-            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
-        
-        match device {
-            DeviceConnection::Microcosm { connection, state: device_state } => {
-                // Get all CC values from the preset state
-                let temp_microcosm = Microcosm {
-                    state: state.clone(),
-                    midi_channel: connection.midi_channel,
-                };
-                let cc_map = temp_microcosm.state_as_cc_map();
-                
-                println!("[Microcosm] Recalling preset: sending {} CC messages", cc_map.len());
-                
-                // Send all CC messages with increased throttling to prevent buffer overflow
-                for (cc_number, value) in cc_map.iter() {
-                    connection.send_cc(*cc_number, *value)?;
-                    println!("[Microcosm] Sent CC#{}: {}", cc_number, value);
-                    tokio::task::block_in_place(|| thread::sleep(Duration::from_millis(20)));
-                }
-                
-                println!("[Microcosm] Preset recall complete");
-                
-                // Update device state
-                *device_state = temp_microcosm;
-                
-                Ok(())
-            }
-            _ => Err(MidiError::Other("Device is not a Microcosm".to_string())),
-        }
+        self.connect_on_port(PedalType::Microcosm, device_name, device_name, midi_channel)
     }
     
-    /// Recall a preset on a Gen Loss MKII (send all parameters)
-    pub fn recall_gen_loss_preset(
+    /// Connect to a Gen Loss MKII pedal
+    pub fn connect_gen_loss_mkii(
         &mut self,
         device_name: &str,
-        state: &GenLossMkiiState,
+        midi_channel: u8,
     ) -> MidiResult<()> {
-        let device = self.connections.get_mut(device_name)
-            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
-        
-        match device {
-            DeviceConnection::GenLossMkii { connection, state: device_state } => {
-                // Get all CC values from the preset state
-                let temp_gen_loss = GenLossMkii {
-                    state: state.clone(),
-                    midi_channel: connection.midi_channel,
-                };
-                let cc_map = temp_gen_loss.state_as_cc_map();
-                
-                println!("[Gen Loss MKII] Recalling preset: sending {} CC messages", cc_map.len());
-                
-                // Send all CC messages with increased throttling to prevent buffer overflow
-                for (cc_number, value) in cc_map.iter() {
-                    connection.send_cc(*cc_number, *value)?;
-                    println!("[Gen Loss MKII] Sent CC#{}: {}", cc_number, value);
-                    tokio::task::block_in_place(|| thread::sleep(Duration::from_millis(20)));
-                }
-                
-                println!("[Gen Loss MKII] Preset recall complete");
-                
-                // Update device state
-                *device_state = temp_gen_loss;
-                
-                Ok(())
-            }
-            _ => Err(MidiError::Other("Device is not a Gen Loss MKII".to_string())),
-        }
+        self.connect_on_port(PedalType::GenLossMkii, device_name, device_name, midi_channel)
     }
     
-    /// Save current state to a Gen Loss MKII preset slot (1-122) using CC 111
-    pub fn save_gen_loss_preset(
+    /// Connect to a Chroma Console pedal
+    pub fn connect_chroma_console(
         &mut self,
         device_name: &str,
-        slot: u8,
+        midi_channel: u8,
     ) -> MidiResult<()> {
-        if slot < 1 || slot > 122 {
-            return Err(MidiError::Other(format!("Invalid preset slot: {}. Must be 1-122", slot)));
-        }
-        
-        let device = self.connections.get_mut(device_name)
+        self.connect_on_port(PedalType::ChromaConsole, device_name, device_name, midi_channel)
+    }
+    
+    /// Disconnect from a device
+    pub fn disconnect(&mut self, device_name: &str) -> MidiResult<()> {
+        let device = self.connections.remove(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        self.capture_timers.cancel(device_name);
         
-        match device {
-            DeviceConnection::GenLossMkii { connection, .. } => {
-                connection.send_cc(GEN_LOSS_CC_PRESET_SAVE, slot)?;
-                println!("[Gen Loss MKII] Saved current state to preset slot {}", slot);
-                
-                Ok(())
+        // The port itself stays open while other devices on it are connected
+        let port_name = device.connection().port_name().to_string();
+        self.outputs.release(&port_name);
+        if !self.outputs.is_open(&port_name) {
+            println!("🔌 Closed MIDI port '{}'", port_name);
+        }
+        if let Some(input) = self.inputs.get(&port_name) {
+            if !input.demux.remove(device.connection().midi_channel()) {
+                self.inputs.remove(&port_name);
             }
-            _ => Err(MidiError::Other("Device is not a Gen Loss MKII".to_string())),
         }
+        self.channel_activity.remove(device_name);
+        self.snapshots.remove(device_name);
+        self.histories.remove(device_name);
+        self.ab_buffers.remove(device_name);
+        Ok(())
     }
     
-    /// Send a program change to a Gen Loss MKII (navigate to preset slot 1-122)
-    pub fn send_gen_loss_program_change(
-        &mut self,
-        device_name: &str,
-        program: u8,
-    ) -> MidiResult<()> {
-        let device = self.connections.get_mut(device_name)
-            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
-
-        match device {
-            DeviceConnection::GenLossMkii { connection, .. } => {
-                connection.send_program_change(program)?;
-                println!("[Gen Loss MKII] Sent PC {} (navigated to preset slot {})", program, program);
-                Ok(())
+    /// Disconnect every device, closing all their ports. Returns the devices disconnected.
+    pub fn disconnect_all(&mut self) -> Vec<String> {
+        let mut device_names: Vec<String> = self.connections.keys().cloned().collect();
+        device_names.sort();
+        for device_name in &device_names {
+            if let Err(e) = self.disconnect(device_name) {
+                eprintln!("⚠️ Failed to disconnect {}: {}", device_name, e);
             }
-            _ => Err(MidiError::Other("Device is not a Gen Loss MKII".to_string())),
         }
+        device_names
     }
-
-    /// Get the current state of a Microcosm
-    pub fn get_microcosm_state(&self, device_name: &str) -> MidiResult<MicrocosmState> {
+    
+    /// Push the device's current state onto its undo stack (call before overwriting it)
+    pub fn record_state_snapshot(&mut self, device_name: &str) -> MidiResult<StateSnapshot> {
         let device = self.connections.get(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
         
-        match device {
-            DeviceConnection::Microcosm { state, .. } => Ok(state.state.clone()),
-            _ => Err(MidiError::Other("Device is not a Microcosm".to_string())),
-        }
+        let snapshot = StateSnapshot {
+            device_name: device_name.to_string(),
+            pedal_type: device.pedal_type(),
+            timestamp: std::time::Instant::now(),
+            cc_map: device.cc_map(),
+            state: device.state_json()?,
+        };
+        
+        self.snapshots
+            .entry(device_name.to_string())
+            .or_default()
+            .push(snapshot.clone());
+        
+        Ok(snapshot)
     }
     
-    /// Get the current state of a Gen Loss MKII
-    pub fn get_gen_loss_state(&self, device_name: &str) -> MidiResult<GenLossMkiiState> {
-        let device = self.connections.get(device_name)
-            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+    /// Recall the most recent snapshot on the device, taking it off the stack once the
+    /// recall succeeds. A failed recall (e.g. device not armed) leaves it to retry.
+    pub fn undo_state_change(&mut self, device_name: &str) -> MidiResult<()> {
+        if !self.connections.contains_key(device_name) {
+            return Err(MidiError::NotConnected(device_name.to_string()));
+        }
         
-        match device {
-            DeviceConnection::GenLossMkii { state, .. } => Ok(state.state.clone()),
-            _ => Err(MidiError::Other("Device is not a Gen Loss MKII".to_string())),
+        let snapshot = self.snapshots
+            .get(device_name)
+            .and_then(|stack| stack.peek())
+            .cloned()
+            .ok_or_else(|| MidiError::Other(format!("Nothing to undo for {}", device_name)))?;
+        
+        println!("[Undo] Restoring {} ({} CCs)", device_name, snapshot.cc_map.len());
+        
+        self.recall_state_json(device_name, snapshot.pedal_type, snapshot.state)?;
+        if let Some(stack) = self.snapshots.get_mut(device_name) {
+            stack.pop();
         }
+        Ok(())
     }
     
-    /// Send a parameter change to a Chroma Console
-    pub fn send_chroma_console_parameter(
-        &mut self,
-        device_name: &str,
-        param: ChromaConsoleParameter,
-    ) -> MidiResult<()> {
-        let device = self.connections.get_mut(device_name)
-            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
-        
-        match device {
-            DeviceConnection::ChromaConsole { connection, state } => {
-                let cc_number = param.cc_number();
-                let cc_value = param.cc_value();
-                
-                connection.send_cc(cc_number, cc_value)?;
-                state.update_state(&param);
-                
-                Ok(())
-            }
-            _ => Err(MidiError::Other("Device is not a Chroma Console".to_string())),
+    /// Recall a full pedal state (serialized like a preset) via the pedal's recall method
+    pub fn recall_state_json(&mut self, device_name: &str, pedal_type: PedalType, state: serde_json::Value) -> MidiResult<()> {
+        self.recall_json(device_name, pedal_type, state, true)
+    }
+    
+    /// Recall a library preset: like `recall_state_json`, but device configuration a preset
+    /// shouldn't change (Gen Loss MKII DIP switches) stays as it is
+    pub fn recall_preset_json(&mut self, device_name: &str, pedal_type: PedalType, state: serde_json::Value) -> MidiResult<()> {
+        self.recall_json(device_name, pedal_type, state, false)
+    }
+    
+    fn recall_json(&mut self, device_name: &str, pedal_type: PedalType, state: serde_json::Value, include_config: bool) -> MidiResult<()> {
+        self.recall_pedal_state(device_name, PedalState::from_json(&pedal_type, state)?, include_config)
+    }
+    
+    /// Recall any pedal's state on a device of the same type (Gen Loss MKII DIP switches included)
+    pub fn recall_state(&mut self, device_name: &str, state: PedalState) -> MidiResult<()> {
+        self.recall_pedal_state(device_name, state, true)
+    }
+    
+    fn recall_pedal_state(&mut self, device_name: &str, state: PedalState, include_config: bool) -> MidiResult<()> {
+        self.ensure_pedal_type(device_name, &state.pedal_type())?;
+        match state {
+            PedalState::Microcosm(state) => self.recall_microcosm_preset(device_name, &state),
+            PedalState::GenLossMkii(state) => self.recall_gen_loss_preset(device_name, &state, include_config),
+            PedalState::ChromaConsole(state) => self.recall_chroma_console_preset(device_name, &state),
+            PedalState::PreampMk2(state) => self.recall_preamp_mk2_preset(device_name, &state),
+            PedalState::Cxm1978(state) => self.recall_cxm1978_preset(device_name, &state),
+            PedalState::MoodMkii(state) => self.recall_mood_mkii_preset(device_name, &state),
+            PedalState::BillyStringsWombtone(state) => self.recall_billy_strings_wombtone_preset(device_name, &state),
+            PedalState::Lossy(state) => self.recall_lossy_preset(device_name, &state),
+            PedalState::BrothersAm(state) => self.recall_brothers_am_preset(device_name, &state),
+            PedalState::ReverseModeC(state) => self.recall_reverse_mode_c_preset(device_name, &state),
+            PedalState::Clean(state) => self.recall_clean_preset(device_name, &state),
+            PedalState::Onward(state) => self.recall_onward_preset(device_name, &state),
+            PedalState::Thermae(state) => self.recall_thermae_preset(device_name, &state),
+            PedalState::DarkWorld(state) => self.recall_dark_world_preset(device_name, &state),
+            PedalState::Habit(state) => self.recall_habit_preset(device_name, &state),
+            PedalState::Collider(state) => self.recall_collider_preset(device_name, &state),
         }
     }
     
-    /// Send a program change to a Chroma Console (0-79 for 80 user presets)
-    pub fn send_chroma_console_program_change(
+    /// What recalling `state` would send and how long it would take, worked out the way
+    /// the recall itself would without sending anything. `include_config` is as for
+    /// `recall_state` (true) vs `recall_preset_json` (false); `diff_only` leaves out CCs
+    /// already at their target, as an A/B recall does; `glide` ramps from the believed values.
+    pub fn estimate_recall(
         &mut self,
         device_name: &str,
-        program: u8,
-    ) -> MidiResult<()> {
+        state: PedalState,
+        include_config: bool,
+        diff_only: bool,
+        glide: Option<&Glide>,
+    ) -> MidiResult<PlanEstimate> {
+        self.ensure_pedal_type(device_name, &state.pedal_type())?;
+        let target = state.to_json()?;
         let device = self.connections.get_mut(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
         
-        match device {
-            DeviceConnection::ChromaConsole { connection, state } => {
-                connection.send_program_change(program)?;
-                state.load_preset(program);
-                Ok(())
-            }
-            _ => Err(MidiError::Other("Device is not a Chroma Console".to_string())),
+        let before_cc_map = device.cc_map();
+        let before = device.state_json()?;
+        device.load_state_json(target)?;
+        let mut plan = device.recall_plan_for(include_config);
+        device.set_state_json(before)?;
+        
+        if diff_only {
+            plan = plan.without_unchanged(&before_cc_map);
+        }
+        if let Some(glide) = glide {
+            plan = plan.with_glide(glide, &before_cc_map);
         }
+        Ok(plan.estimate())
     }
     
-    /// Recall a preset on a Chroma Console (send all parameters)
-    pub fn recall_chroma_console_preset(
-        &mut self,
-        device_name: &str,
-        state: &ChromaConsoleState,
-    ) -> MidiResult<()> {
+    /// Fail unless `device_name` is connected and is a `expected`
+    pub(crate) fn ensure_pedal_type(&self, device_name: &str, expected: &PedalType) -> MidiResult<()> {
+        let actual = self.device_pedal_type(device_name)?;
+        if &actual != expected {
+            return Err(pedal_state::type_mismatch(&actual, expected));
+        }
+        Ok(())
+    }
+    
+    /// Send one parameter for any pedal, dispatched to that pedal's own send. A parameter
+    /// for a different pedal type is rejected before anything is sent.
+    pub fn send_parameter(&mut self, device_name: &str, param: PedalParameter) -> MidiResult<()> {
+        self.ensure_pedal_type(device_name, &param.pedal_type())?;
+        match param {
+            PedalParameter::Microcosm(param) => self.send_microcosm_parameter(device_name, param),
+            PedalParameter::GenLossMkii(param) => self.send_gen_loss_parameter(device_name, param),
+            PedalParameter::ChromaConsole(param) => self.send_chroma_console_parameter(device_name, param),
+            PedalParameter::PreampMk2(param) => self.send_preamp_mk2_parameter(device_name, param),
+            PedalParameter::Cxm1978(param) => self.send_cxm1978_parameter(device_name, param),
+            PedalParameter::MoodMkii(param) => self.send_mood_mkii_parameter(device_name, param),
+            PedalParameter::BillyStringsWombtone(param) => self.send_billy_strings_wombtone_parameter(device_name, param),
+            PedalParameter::Lossy(param) => self.send_lossy_parameter(device_name, param),
+            PedalParameter::BrothersAm(param) => self.send_brothers_am_parameter(device_name, param),
+            PedalParameter::ReverseModeC(param) => self.send_reverse_mode_c_parameter(device_name, param),
+            PedalParameter::Clean(param) => self.send_clean_parameter(device_name, param),
+            PedalParameter::Onward(param) => self.send_onward_parameter(device_name, param),
+            PedalParameter::Thermae(param) => self.send_thermae_parameter(device_name, param),
+            PedalParameter::DarkWorld(param) => self.send_dark_world_parameter(device_name, param),
+            PedalParameter::Habit(param) => self.send_habit_parameter(device_name, param),
+            PedalParameter::Collider(param) => self.send_collider_parameter(device_name, param),
+        }
+    }
+    
+    /// Send a raw Control Change to a device. The believed state is not updated, so
+    /// prefer the pedal's parameter commands for anything the editor shows.
+    pub fn send_cc(&mut self, device_name: &str, cc_number: u8, value: u8) -> MidiResult<()> {
+        let cc_number = CcValue::new(cc_number).map_err(|_| MidiError::InvalidValue {
+            expected: "a CC number (0-127)".to_string(),
+            actual: cc_number.into(),
+        })?;
+        let device = self.connections.get_mut(device_name)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        device.connection_mut().send_cc(cc_number.get(), value)
+    }
+    
+    /// Send a raw Program Change to a device without touching its believed state
+    pub fn send_program_change(&mut self, device_name: &str, program: u8) -> MidiResult<()> {
+        let program = CcValue::new(program).map_err(|_| MidiError::InvalidValue {
+            expected: "a program number (0-127)".to_string(),
+            actual: program.into(),
+        })?;
+        let device = self.connections.get_mut(device_name)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        device.connection_mut().send_program_change(program.get())
+    }
+    
+    /// Play a note on a device: Note On, a `duration_ms` pause, then Note Off.
+    /// Blocks for the whole duration, like a recall's pacing does; callers that shouldn't
+    /// hold the manager that long use `start_midi_note` and `end_midi_note` instead.
+    pub fn send_midi_note(&mut self, device_name: &str, note: u8, velocity: u8, duration_ms: u64) -> MidiResult<()> {
+        let duration = note_duration(duration_ms)?;
+        self.start_midi_note(device_name, note, velocity)?;
+        tokio::task::block_in_place(|| thread::sleep(duration));
+        self.end_midi_note(device_name, note)
+    }
+    
+    /// Start a note on a device. The caller holds it without the manager lock and ends
+    /// it with `end_midi_note`; see `MAX_NOTE_DURATION`.
+    pub fn start_midi_note(&mut self, device_name: &str, note: u8, velocity: u8) -> MidiResult<()> {
+        let note = CcValue::new(note).map_err(|_| MidiError::InvalidValue {
+            expected: "a note number (0-127)".to_string(),
+            actual: note.into(),
+        })?;
+        let velocity = CcValue::new(velocity).map_err(|_| MidiError::InvalidValue {
+            expected: "a velocity (0-127)".to_string(),
+            actual: velocity.into(),
+        })?;
+        let device = self.connections.get_mut(device_name)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        device.connection_mut().send_note_on(note.get(), velocity.get())
+    }
+    
+    /// End a note started with `start_midi_note`
+    pub fn end_midi_note(&mut self, device_name: &str, note: u8) -> MidiResult<()> {
+        let note = CcValue::new(note).map_err(|_| MidiError::InvalidValue {
+            expected: "a note number (0-127)".to_string(),
+            actual: note.into(),
+        })?;
+        let device = self.connections.get_mut(device_name)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        device.connection_mut().send_note_off(note.get())
+    }
+    
+    /// Send several parameters to one device as a single ordered, paced batch (a macro
+    /// control). Nothing is sent unless every parameter is for the device's pedal type.
+    /// The believed state follows whatever went out, with one history entry and one
+    /// `device-state-changed` event covering every field.
+    pub fn send_parameters_batch(&mut self, device_name: &str, params: Vec<PedalParameter>) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
         let device = self.connections.get_mut(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
         
-        match device {
-            DeviceConnection::ChromaConsole { connection, state: device_state } => {
-                // Get all CC values from the preset state
-                let temp_chroma = ChromaConsole {
-                    state: state.clone(),
-                    midi_channel: connection.midi_channel,
-                };
-                let cc_map = temp_chroma.state_as_cc_map();
-                
-                println!("[Chroma Console] Recalling preset: sending {} CC messages", cc_map.len());
-                
-                // Send all CC messages with increased throttling to prevent buffer overflow
-                for (cc_number, value) in cc_map.iter() {
-                    connection.send_cc(*cc_number, *value)?;
-                    println!("[Chroma Console] Sent CC#{}: {}", cc_number, value);
-                    tokio::task::block_in_place(|| thread::sleep(Duration::from_millis(20)));
+        // Believed state follows the values that actually go out
+        let params: Vec<PedalParameter> = params.into_iter().map(|param| device.within_range(param)).collect();
+        let planned = params
+            .iter()
+            .map(|param| device.batch_ccs(param))
+            .collect::<MidiResult<Vec<_>>>()?;
+        let result = device.connection_mut().send_plan_tracked(&SendPlan::in_order(planned.iter().flatten().copied()));
+        
+        // A parameter counts as sent once every one of its CCs went out
+        let sent_count = match &result {
+            Ok(()) => params.len(),
+            Err((sent_ccs, _)) => planned
+                .iter()
+                .scan(0, |total, ccs| {
+                    *total += ccs.len();
+                    Some(*total)
+                })
+                .take_while(|total| total <= sent_ccs)
+                .count(),
+        };
+        let sent = &params[..sent_count];
+        for param in sent {
+            device.apply_parameter(param);
+        }
+        
+        let sent_names: Vec<String> = sent.iter().map(|param| param.name().to_string()).collect();
+        if !sent.is_empty() {
+            self.record_history(device_name, checkpoint, HistoryChange::Batch { parameters: sent_names.clone() });
+        }
+        
+        result.map_err(|(_, e)| MidiError::BatchIncomplete {
+            sent: sent_names,
+            failed: params[sent_count].name().to_string(),
+            reason: e.to_string(),
+        })
+    }
+    
+    /// Bypass every connected device, remembering each one's bypass state for `restore_bypass_states`.
+    /// Devices already in the snapshot keep their first recorded state, so bypassing twice
+    /// doesn't lose what to restore.
+    pub fn bypass_all(&mut self) -> Vec<BypassResult> {
+        let mut device_names: Vec<String> = self.connections.keys().cloned().collect();
+        device_names.sort();
+        
+        device_names.iter().map(|device_name| {
+            let device = &self.connections[device_name];
+            let snapshot = device.bypass_control().bypass_snapshot();
+            let params = device.bypass_control().bypass_parameters();
+            self.bypass_snapshots.entry(device_name.clone()).or_insert(snapshot);
+            
+            let result = BypassResult::new(device_name, self.send_parameters_batch(device_name, params).map_err(|e| e.to_string()));
+            self.emit_bypass_result(&result);
+            result
+        }).collect()
+    }
+    
+    /// Re-send the bypass states recorded by `bypass_all` and clear the snapshot.
+    /// Devices that have since disconnected are reported as failed.
+    pub fn restore_bypass_states(&mut self) -> Vec<BypassResult> {
+        let mut snapshots: Vec<(String, Vec<PedalParameter>)> = self.bypass_snapshots.drain().collect();
+        snapshots.sort_by(|a, b| a.0.cmp(&b.0));
+        
+        snapshots.into_iter().map(|(device_name, params)| {
+            let result = BypassResult::new(&device_name, self.send_parameters_batch(&device_name, params).map_err(|e| e.to_string()));
+            self.emit_bypass_result(&result);
+            result
+        }).collect()
+    }
+    
+    /// Run each connected device's exit action (in name order) unless they're switched off.
+    /// `presets` holds the shutdown presets by ID. A device is skipped when the time spent so
+    /// far plus what its action should take (one send delay per CC) would overrun `budget`.
+    pub fn run_exit_actions(&mut self, presets: &HashMap<String, ShutdownPreset>, budget: Duration) -> Vec<ExitActionResult> {
+        if !self.exit_actions.enabled {
+            return Vec::new();
+        }
+        let started = std::time::Instant::now();
+        let mut device_names: Vec<String> = self.connections.keys().cloned().collect();
+        device_names.sort();
+        
+        let results: Vec<ExitActionResult> = device_names.into_iter().map(|device_name| {
+            let device = &self.connections[&device_name];
+            let action = self.exit_actions.action_for(&device.pedal_type()).clone();
+            let expected_sends = match &action {
+                ExitAction::Nothing => {
+                    return ExitActionResult { device_name, action, status: ExitStatus::Ignored, error: None };
                 }
-                
-                println!("[Chroma Console] Preset recall complete");
-                
-                // Update device state
-                *device_state = temp_chroma;
-                
-                Ok(())
+                ExitAction::Bypass => device.bypass_control().bypass_parameters().len(),
+                ExitAction::RecallPreset(_) => device.cc_map().len(),
+            };
+            if started.elapsed() + DEFAULT_SEND_DELAY * expected_sends as u32 > budget {
+                println!("⏭️ Exit: skipped '{}', {:?} isn't enough time left", device_name, budget.saturating_sub(started.elapsed()));
+                return ExitActionResult { device_name, action, status: ExitStatus::Skipped, error: None };
             }
-            _ => Err(MidiError::Other("Device is not a Chroma Console".to_string())),
+            
+            let result = match &action {
+                ExitAction::Nothing => Ok(()),
+                ExitAction::Bypass => {
+                    let params = device.bypass_control().bypass_parameters();
+                    self.send_parameters_batch(&device_name, params)
+                }
+                ExitAction::RecallPreset(preset_id) => match presets.get(preset_id) {
+                    Some(preset) => self.recall_preset_json(&device_name, preset.pedal_type.clone(), preset.parameters.clone()),
+                    None => Err(MidiError::Other(format!("Shutdown preset {} isn't in the library", preset_id))),
+                },
+            };
+            match result {
+                Ok(()) => {
+                    println!("✅ Exit: {:?} on '{}'", action, device_name);
+                    ExitActionResult { device_name, action, status: ExitStatus::Done, error: None }
+                }
+                Err(e) => {
+                    eprintln!("❌ Exit: {:?} on '{}' failed: {}", action, device_name, e);
+                    ExitActionResult { device_name, action, status: ExitStatus::Failed, error: Some(e.to_string()) }
+                }
+            }
+        }).collect();
+        
+        println!("[Exit] Ran exit actions on {} device(s) in {:?}", results.len(), started.elapsed());
+        results
+    }
+    
+    /// Leave every device as its exit action says, then release all ports. The actions
+    /// have to go first: once `disconnect_all` runs there is nothing left to send them to.
+    pub fn shut_down(&mut self, presets: &HashMap<String, ShutdownPreset>, budget: Duration) -> Vec<ExitActionResult> {
+        let results = self.run_exit_actions(presets, budget);
+        let disconnected = self.disconnect_all();
+        if !disconnected.is_empty() {
+            println!("🔌 Disconnected {} device(s) on shutdown", disconnected.len());
         }
+        results
     }
     
-    /// Get the current state of a Chroma Console
-    pub fn get_chroma_console_state(&self, device_name: &str) -> MidiResult<ChromaConsoleState> {
-        let device = self.connections.get(device_name)
-            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
-        
-        match device {
-            DeviceConnection::ChromaConsole { state, .. } => Ok(state.state.clone()),
-            _ => Err(MidiError::Other("Device is not a Chroma Console".to_string())),
+    /// Tell the frontend how bypassing or restoring one device went
+    fn emit_bypass_result(&self, result: &BypassResult) {
+        let Some(app_handle) = &self.app_handle else { return };
+        if let Err(e) = app_handle.emit("bypass-result", result) {
+            eprintln!("❌ Failed to emit bypass result: {}", e);
         }
     }
     
-    // ========================================================================
-    // Chase Bliss Preamp MK II Methods
-    // ========================================================================
+    /// Tell the frontend how a connect attempt went (see `connect_retry`)
+    pub(crate) fn emit_connection_attempt(&self, attempt: &ConnectionAttempt) {
+        let Some(app_handle) = &self.app_handle else { return };
+        if let Err(e) = app_handle.emit(CONNECTION_ATTEMPT_EVENT, attempt) {
+            eprintln!("❌ Failed to emit connection attempt: {}", e);
+        }
+    }
     
-    /// Connect to a Chase Bliss Preamp MK II
-    pub fn connect_preamp_mk2(
-        &mut self,
-        device_name: &str,
-        midi_channel: u8,
-    ) -> MidiResult<()> {
-        // Validate channel (1-16)
-        if midi_channel < 1 || midi_channel > 16 {
-            return Err(MidiError::InvalidChannel(midi_channel));
+    /// Tell the frontend what a monitored input did with a PC (see `pc_remap`)
+    pub(crate) fn emit_pc_remap_outcome(&self, outcome: &PcRemapOutcome) {
+        let Some(app_handle) = &self.app_handle else { return };
+        let result = match outcome {
+            PcRemapOutcome::Executed(execution) => app_handle.emit(PC_REMAP_EXECUTED_EVENT, execution),
+            PcRemapOutcome::Forwarded(pc) => app_handle.emit(MONITORED_PC_EVENT, pc),
+        };
+        if let Err(e) = result {
+            eprintln!("❌ Failed to emit PC remap outcome: {}", e);
         }
+    }
+    
+    /// Store the device's current state in an A/B compare buffer
+    pub fn store_ab(&mut self, device_name: &str, slot: AbSlot) -> MidiResult<AbStatus> {
+        let device = self.connections.get(device_name)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        let state = device.state_json()?;
         
-        // Check if already connected
-        if self.connections.contains_key(device_name) {
-            return Err(MidiError::AlreadyConnected(device_name.to_string()));
-        }
+        self.ab_buffers.entry(device_name.to_string()).or_default().store(slot, state);
+        self.get_ab_status(device_name)
+    }
+    
+    /// Recall an A/B compare buffer, sending only the CCs that differ from the current state
+    pub fn recall_ab(&mut self, device_name: &str, slot: AbSlot) -> MidiResult<AbStatus> {
+        let target = self.ab_buffers
+            .get(device_name)
+            .and_then(|buffers| buffers.get(slot))
+            .cloned()
+            .ok_or_else(|| MidiError::Other(format!("Compare buffer {} is empty for {}", slot, device_name)))?;
         
-        // Find the MIDI port
-        let midi_out = self.midi_output.take()
-            .ok_or_else(|| MidiError::Other("MIDI output not initialized".to_string()))?;
+        let checkpoint = self.history_checkpoint(device_name);
+        let device = self.connections.get_mut(device_name)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
         
-        // Find the matching port by iterating and collecting the port we need
-        let port_opt = {
-            let ports = midi_out.ports();
-            ports.into_iter()
-                .find(|p| {
-                    midi_out.port_name(p)
-                        .map(|name| name.to_lowercase().contains(&device_name.to_lowercase()))
-                        .unwrap_or(false)
-                })
-        };
+        let before_cc_map = device.cc_map();
+        let before = device.state_json()?;
+        device.load_state_json(target)?;
+        let plan = device.recall_plan().without_unchanged(&before_cc_map);
         
-        let port = port_opt.ok_or_else(|| MidiError::DeviceNotFound(device_name.to_string()))?;
+        println!("[A/B] Recalling {} on {}: sending {} changed CCs", slot, device_name, plan.len());
         
-        // Connect to the output port
-        let output = midi_out
-            .connect(&port, "Librarian")
-            .map_err(|e| MidiError::ConnectionFailed(e.to_string()))?;
+        if let Err(e) = device.connection_mut().send_plan(&plan) {
+            device.set_state_json(before)?;
+            return Err(e);
+        }
         
-        // Setup MIDI input for bidirectional communication
-        let input = self.setup_midi_input(device_name, PedalType::PreampMk2, midi_channel)?;
+        if let Some(buffers) = self.ab_buffers.get_mut(device_name) {
+            buffers.set_active(slot);
+        }
+        self.record_history(device_name, checkpoint, HistoryChange::Recall);
+        self.get_ab_status(device_name)
+    }
+    
+    /// Recall whichever buffer isn't active (A if neither has been used yet)
+    pub fn swap_ab(&mut self, device_name: &str) -> MidiResult<AbStatus> {
+        let slot = self.ab_buffers
+            .get(device_name)
+            .map(AbBuffers::swap_target)
+            .unwrap_or(AbSlot::A);
+        self.recall_ab(device_name, slot)
+    }
+    
+    /// Which A/B buffers are stored and which matches the device's current state
+    pub fn get_ab_status(&self, device_name: &str) -> MidiResult<AbStatus> {
+        let device = self.connections.get(device_name)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        let current = device.state_json()?;
         
-        // Create connection and device state
-        let connection = MidiConnection {
-            output,
-            input,
-            midi_channel,
+        Ok(self.ab_buffers
+            .get(device_name)
+            .map(|buffers| buffers.status(&current))
+            .unwrap_or_else(|| AbBuffers::default().status(&current)))
+    }
+    
+    /// Capture state before a change so it can be diffed and recorded in the device's history
+    fn history_checkpoint(&self, device_name: &str) -> Option<HistoryCheckpoint> {
+        let device = self.connections.get(device_name)?;
+        Some(HistoryCheckpoint {
+            cc_map: device.cc_map(),
+            state: device.state_json().ok()?,
+        })
+    }
+    
+    /// Record a completed change against the state captured before it
+    fn record_history(&mut self, device_name: &str, checkpoint: Option<HistoryCheckpoint>, mut change: HistoryChange) {
+        let Some(checkpoint) = checkpoint else { return };
+        let Some(after) = self.connections.get(device_name).and_then(|d| d.state_json().ok()) else {
+            return;
         };
         
-        let state = PreampMk2::new(midi_channel);
+        self.emit_state_changed(device_name, &checkpoint.state, &after);
+        if self.history_paused {
+            return;
+        }
         
-        self.connections.insert(
-            device_name.to_string(),
-            DeviceConnection::PreampMk2 { connection, state },
-        );
+        // Re-sending an unchanged value (e.g. while dragging) isn't worth an undo step
+        if !change.is_composite() && checkpoint.state == after {
+            return;
+        }
+        if let HistoryChange::Parameter { cc_number, previous_value, .. } = &mut change {
+            *previous_value = checkpoint.cc_map.get(cc_number).copied();
+        }
+        
+        self.histories
+            .entry(device_name.to_string())
+            .or_default()
+            .record(HistoryEntry {
+                device_name: device_name.to_string(),
+                change,
+                timestamp: chrono::Utc::now().timestamp_millis(),
+                before: checkpoint.state,
+                after,
+            });
+    }
+    
+    /// Build the `device-state-changed` payload, or None if nothing changed
+    pub(crate) fn state_changed_event(
+        &self,
+        device_name: &str,
+        before: &serde_json::Value,
+        after: &serde_json::Value,
+    ) -> Option<DeviceStateChangedEvent> {
+        let device = self.connections.get(device_name)?;
+        let changes = diff_json(before, after);
+        if changes.is_empty() {
+            return None;
+        }
+        
+        Some(DeviceStateChangedEvent {
+            device_name: device_name.to_string(),
+            pedal_type: device.pedal_type().as_str().to_string(),
+            operation_id: self.operation_id.clone(),
+            changes,
+        })
+    }
+    
+    /// Tell the frontend which fields of a device's state just changed
+    fn emit_state_changed(&self, device_name: &str, before: &serde_json::Value, after: &serde_json::Value) {
+        let Some(app_handle) = &self.app_handle else { return };
+        if let Some(event) = self.state_changed_event(device_name, before, after) {
+            if let Err(e) = app_handle.emit("device-state-changed", &event) {
+                eprintln!("❌ Failed to emit device state event: {}", e);
+            }
+        }
+    }
+    
+    /// Move a device to one side of a history entry
+    fn apply_history_step(&mut self, device_name: &str, step: HistoryStep<'_>) -> MidiResult<()> {
+        let device = self.connections.get_mut(device_name)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
         
-        println!("✅ Connected to Preamp MK II: '{}' on MIDI Channel {}", device_name, midi_channel);
+        match step.ccs {
+            Some(ccs) => {
+                // The same plan path every send takes, so value checks, the Preamp fader
+                // clamp and the firmware gate apply to replayed CCs too
+                device.connection_mut().send_plan(&SendPlan::in_order(ccs))?;
+                let before = device.state_json()?;
+                device.set_state_json(step.state.clone())?;
+                device.keep_state_within_range();
+                let after = device.state_json()?;
+                self.emit_state_changed(device_name, &before, &after);
+                Ok(())
+            }
+            None => {
+                let pedal_type = device.pedal_type();
+                self.history_paused = true;
+                let result = self.recall_state_json(device_name, pedal_type, step.state.clone());
+                self.history_paused = false;
+                result
+            }
+        }
+    }
+    
+    /// Undo the last parameter change, recall or program change on a device
+    pub fn undo_parameter_change(&mut self, device_name: &str) -> MidiResult<Option<HistoryEntry>> {
+        if !self.connections.contains_key(device_name) {
+            return Err(MidiError::NotConnected(device_name.to_string()));
+        }
         
-        // Reinitialize MIDI output for future connections
-        self.midi_output = Some(MidiOutput::new("Librarian Output")
-            .map_err(|e| MidiError::Other(e.to_string()))?);
+        let mut history = self.histories.remove(device_name).unwrap_or_default();
+        let result = history.undo(|step| self.apply_history_step(device_name, step));
+        self.histories.insert(device_name.to_string(), history);
+        result
+    }
+    
+    /// Redo the last undone change on a device
+    pub fn redo_parameter_change(&mut self, device_name: &str) -> MidiResult<Option<HistoryEntry>> {
+        if !self.connections.contains_key(device_name) {
+            return Err(MidiError::NotConnected(device_name.to_string()));
+        }
         
-        Ok(())
+        let mut history = self.histories.remove(device_name).unwrap_or_default();
+        let result = history.redo(|step| self.apply_history_step(device_name, step));
+        self.histories.insert(device_name.to_string(), history);
+        result
     }
     
-    /// Send a parameter change to a Preamp MK II
-    pub fn send_preamp_mk2_parameter(
+    /// Undo/redo history for a device
+    pub fn get_undo_stack(&self, device_name: &str) -> MidiResult<UndoStack> {
+        if !self.connections.contains_key(device_name) {
+            return Err(MidiError::NotConnected(device_name.to_string()));
+        }
+        
+        Ok(self.histories
+            .get(device_name)
+            .map(ParameterHistory::stack)
+            .unwrap_or(UndoStack { undo: vec![], redo: vec![] }))
+    }
+    
+    /// Send a parameter change to a Microcosm
+    pub fn send_microcosm_parameter(
         &mut self,
         device_name: &str,
-        param: PreampMk2Parameter,
+        param: MicrocosmParameter,
     ) -> MidiResult<()> {
+        if let Some(program) = param.program_number() {
+            return self.send_microcosm_program_change(device_name, program);
+        }
+        
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::parameter(param.name(), param.cc_number(), param.cc_value());
         let device = self.connections.get_mut(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
         
         match device {
-            DeviceConnection::PreampMk2 { connection, state } => {
+            DeviceConnection::Microcosm { connection, state } => {
                 let cc_number = param.cc_number();
                 let cc_value = param.cc_value();
                 
-                #[cfg(debug_assertions)]
-                println!("[Preamp MK II] Sending CC#{} = {} (ch {})", cc_number, cc_value, connection.midi_channel);
-                
                 connection.send_cc(cc_number, cc_value)?;
+                
+                let previous_transport = state.looper_transport;
                 state.update_state(&param);
                 
+                if state.looper_transport != previous_transport {
+                    if let Some(app_handle) = &self.app_handle {
+                        let event = LooperStateEvent {
+                            device_name: device_name.to_string(),
+                            looper_transport: state.looper_transport,
+                        };
+                        if let Err(e) = app_handle.emit("looper-state-changed", &event) {
+                            eprintln!("❌ Failed to emit looper state event: {}", e);
+                        }
+                    }
+                }
+                
+                self.record_history(device_name, checkpoint, change);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a Preamp MK II".to_string())),
+            _ => Err(MidiError::Other("Device is not a Microcosm".to_string())),
         }
     }
     
-    /// Send a Program Change to a Preamp MK II to recall a preset (PC 0-29 → presets 0-29)
-    pub fn send_preamp_mk2_program_change(
+    /// Send a program change to a Microcosm (select effect/preset)
+    pub fn send_microcosm_program_change(
         &mut self,
         device_name: &str,
         program: u8,
     ) -> MidiResult<()> {
-        if program > 29 {
-            return Err(MidiError::Other(format!("Invalid preset slot: {}. Must be 0-29", program)));
-        }
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::ProgramChange { program };
         let device = self.connections.get_mut(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
-
+        
         match device {
-            DeviceConnection::PreampMk2 { connection, .. } => {
+            DeviceConnection::Microcosm { connection, state } => {
                 connection.send_program_change(program)?;
-                println!("[Preamp MK II] Sent Program Change {} to recall preset {}", program, program);
+                state.set_current_preset(program);
+                self.record_history(device_name, checkpoint, change);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a Preamp MK II".to_string())),
+            _ => Err(MidiError::Other("Device is not a Microcosm".to_string())),
         }
     }
-
-    /// Recall a preset by sending all parameters to the Preamp MK II
-    pub fn recall_preamp_mk2_preset(
+    
+    /// Apply a CC received from a Microcosm to the believed state (nothing is sent).
+    /// Called from the MIDI input callback, e.g. when the pedal echoes a preset it just loaded.
+    pub fn update_microcosm_state_from_cc(
         &mut self,
         device_name: &str,
-        state: &PreampMk2State,
+        cc_number: u8,
+        value: u8,
     ) -> MidiResult<()> {
         let device = self.connections.get_mut(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
         
         match device {
-            DeviceConnection::PreampMk2 { connection, state: device_state } => {
-                // Get all CC values from the preset state
-                let temp_preamp = PreampMk2 {
-                    state: state.clone(),
-                    midi_channel: connection.midi_channel,
-                };
-                let cc_map = temp_preamp.state_as_cc_map();
-                
-                println!("[Preamp MK II] Recalling preset: sending {} CC messages", cc_map.len());
-                
-                // Send all CC messages with throttling
-                for (cc_number, value) in cc_map.iter() {
-                    connection.send_cc(*cc_number, *value)?;
-                    println!("[Preamp MK II] Sent CC#{}: {}", cc_number, value);
-                    tokio::task::block_in_place(|| thread::sleep(Duration::from_millis(20)));
-                }
-                
-                println!("[Preamp MK II] Preset recall complete");
-                
-                // Update device state
-                *device_state = temp_preamp;
-                
+            DeviceConnection::Microcosm { state, .. } => {
+                state.state.update_from_cc(cc_number, value);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a Preamp MK II".to_string())),
+            _ => Err(MidiError::Other("Device is not a Microcosm".to_string())),
         }
     }
     
-    /// Save current state to a preset slot (0-29) using CC 27
-    pub fn save_preamp_mk2_preset(
+    /// Program a Microcosm is believed to be on
+    pub fn get_microcosm_program(&self, device_name: &str) -> MidiResult<u8> {
+        match self.connections.get(device_name) {
+            Some(DeviceConnection::Microcosm { state, .. }) => Ok(state.current_program()),
+            Some(_) => Err(MidiError::Other("Device is not a Microcosm".to_string())),
+            None => Err(MidiError::NotConnected(device_name.to_string())),
+        }
+    }
+    
+    /// Start a bank dump on a Microcosm. Returns the dump's id (for `finish_microcosm_bank_dump`)
+    /// and a receiver that disconnects when the dump is cancelled.
+    pub fn begin_microcosm_bank_dump(&mut self, device_name: &str) -> MidiResult<(u64, Receiver<()>)> {
+        self.get_microcosm_program(device_name)?;
+        if !self.channel_activity.contains_key(device_name) {
+            return Err(MidiError::Other(format!(
+                "No MIDI input for '{}' - a bank dump needs the pedal's replies", device_name
+            )));
+        }
+        self.bank_dumps
+            .begin(device_name)
+            .ok_or_else(|| MidiError::Other(format!("A bank dump is already running on '{}'", device_name)))
+    }
+    
+    /// Cancel a running bank dump; returns whether there was one
+    pub fn cancel_microcosm_bank_dump(&mut self, device_name: &str) -> bool {
+        self.bank_dumps.cancel(device_name)
+    }
+    
+    /// Mark a bank dump as done so another can start
+    pub fn finish_microcosm_bank_dump(&mut self, device_name: &str, dump_id: u64) {
+        self.bank_dumps.finish(device_name, dump_id);
+    }
+    
+    /// Send a parameter change to a Gen Loss MKII
+    pub fn send_gen_loss_parameter(
         &mut self,
         device_name: &str,
-        slot: u8,
+        param: GenLossMkiiParameter,
     ) -> MidiResult<()> {
-        // Validate slot (0-29 for 30 presets)
-        if slot > 29 {
-            return Err(MidiError::Other(format!("Invalid preset slot: {}. Must be 0-29", slot)));
-        }
-        
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::parameter(param.name(), param.cc_number(), param.cc_value());
         let device = self.connections.get_mut(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
         
         match device {
-            DeviceConnection::PreampMk2 { connection, .. } => {
-                // Send CC 27 with slot number (0-29)
-                connection.send_cc(PREAMP_CC_PRESET_SAVE, slot)?;
-                println!("[Preamp MK II] Saved current state to preset slot {}", slot);
+            DeviceConnection::GenLossMkii { connection, state } => {
+                let cc_number = param.cc_number();
+                let cc_value = param.cc_value();
                 
+                connection.send_cc(cc_number, cc_value)?;
+                state.update_state(&param);
+                
+                self.record_history(device_name, checkpoint, change);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a Preamp MK II".to_string())),
+            _ => Err(MidiError::Other("Device is not a Gen Loss MKII".to_string())),
         }
     }
     
-    /// Get the current state of a Preamp MK II
-    pub fn get_preamp_mk2_state(&self, device_name: &str) -> MidiResult<PreampMk2State> {
-        let device = self.connections.get(device_name)
-            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
-        
-        match device {
-            DeviceConnection::PreampMk2 { state, .. } => Ok(state.state.clone()),
-            _ => Err(MidiError::Other("Device is not a Preamp MK II".to_string())),
+    /// Recall a preset on a Microcosm (send all parameters)
+    pub fn recall_microcosm_preset(
+        &mut self,
+        device_name: &str,
+        state: &MicrocosmState,
+    ) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::Recall;
+        let device = self.connections.get_mut(device_name)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        
+        match device {
+            DeviceConnection::Microcosm { connection, state: device_state } => {
+                // Get all CC values from the preset state
+                let temp_microcosm = Microcosm {
+                    state: state.clone(),
+                    midi_channel: connection.midi_channel(),
+                    program: device_state.program,
+                    looper_transport: device_state.looper_transport,
+                };
+                let plan = temp_microcosm.recall_plan();
+                
+                println!("[Microcosm] Recalling preset: sending {} CC messages", plan.len());
+                
+                connection.send_plan(&plan)?;
+                
+                println!("[Microcosm] Preset recall complete");
+                
+                // Update device state
+                *device_state = temp_microcosm;
+                
+                self.record_history(device_name, checkpoint, change);
+                Ok(())
+            }
+            _ => Err(MidiError::Other("Device is not a Microcosm".to_string())),
         }
     }
     
-    // ========================================================================
-    // Chase Bliss / Meris CXM 1978 Methods
-    // ========================================================================
-
-    /// Connect to a Chase Bliss / Meris CXM 1978
-    pub fn connect_cxm1978(
+    /// Recall a preset on a Gen Loss MKII (send all parameters)
+    /// DIP switches are configuration rather than sound, so unless `include_dip_switches`
+    /// is set they aren't sent and the device keeps its current ones.
+    pub fn recall_gen_loss_preset(
         &mut self,
         device_name: &str,
-        midi_channel: u8,
+        state: &GenLossMkiiState,
+        include_dip_switches: bool,
     ) -> MidiResult<()> {
-        if midi_channel < 1 || midi_channel > 16 {
-            return Err(MidiError::InvalidChannel(midi_channel));
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::Recall;
+        let device = self.connections.get_mut(device_name)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        
+        match device {
+            DeviceConnection::GenLossMkii { connection, state: device_state } => {
+                // Get all CC values from the preset state
+                let mut temp_gen_loss = GenLossMkii {
+                    state: state.clone(),
+                    midi_channel: connection.midi_channel(),
+                };
+                let plan = if include_dip_switches {
+                    temp_gen_loss.recall_plan()
+                } else {
+                    GenLossDipSwitches::from(&device_state.state).apply_to(&mut temp_gen_loss.state);
+                    temp_gen_loss.state.sound_recall_plan()
+                };
+                
+                println!("[Gen Loss MKII] Recalling preset: sending {} CC messages", plan.len());
+                
+                connection.send_plan(&plan)?;
+                
+                println!("[Gen Loss MKII] Preset recall complete");
+                
+                // Update device state
+                *device_state = temp_gen_loss;
+                
+                self.record_history(device_name, checkpoint, change);
+                Ok(())
+            }
+            _ => Err(MidiError::Other("Device is not a Gen Loss MKII".to_string())),
         }
-
-        if self.connections.contains_key(device_name) {
-            return Err(MidiError::AlreadyConnected(device_name.to_string()));
+    }
+    
+    /// Saved Gen Loss MKII DIP profiles
+    pub fn gen_loss_dip_profiles(&self) -> &DipProfileStore {
+        &self.gen_loss_dip_profiles
+    }
+    
+    pub fn gen_loss_dip_profiles_mut(&mut self) -> &mut DipProfileStore {
+        &mut self.gen_loss_dip_profiles
+    }
+    
+    /// Load DIP profiles from (and save future changes to) this file
+    pub fn set_gen_loss_dip_profiles_path(&mut self, path: PathBuf) -> MidiResult<()> {
+        self.gen_loss_dip_profiles = DipProfileStore::load(path)?;
+        Ok(())
+    }
+    
+    /// Program change remap tables for monitored inputs
+    pub fn pc_remap(&self) -> &PcRemapStore {
+        &self.pc_remap
+    }
+    
+    pub fn pc_remap_mut(&mut self) -> &mut PcRemapStore {
+        &mut self.pc_remap
+    }
+    
+    /// Load PC remap tables from (and save future changes to) this file
+    pub fn set_pc_remap_path(&mut self, path: PathBuf) -> MidiResult<()> {
+        self.pc_remap = PcRemapStore::load(path)?;
+        Ok(())
+    }
+    
+    /// Watch (and save future changes to) the inputs listed in this file. Saved ports that
+    /// can't be opened now - a foot controller that isn't plugged in - are logged and kept,
+    /// so they come back on a later launch.
+    pub fn set_monitored_inputs_path(&mut self, path: PathBuf) -> MidiResult<()> {
+        let ports = pc_remap::load_monitored_inputs(&path)?;
+        self.saved_monitored_inputs = ports.into_iter().collect();
+        self.monitored_inputs_path = Some(path);
+        
+        let ports: Vec<String> = self.saved_monitored_inputs.iter().cloned().collect();
+        for port_name in ports {
+            if let Err(e) = self.monitor_input(&port_name) {
+                eprintln!("⚠️ Failed to monitor '{}': {}", port_name, e);
+            }
         }
-
-        let midi_out = self.midi_output.take()
-            .ok_or_else(|| MidiError::Other("MIDI output not initialized".to_string()))?;
-
-        let port_opt = {
-            let ports = midi_out.ports();
-            ports.into_iter()
-                .find(|p| {
-                    midi_out.port_name(p)
-                        .map(|name| name.to_lowercase().contains(&device_name.to_lowercase()))
-                        .unwrap_or(false)
-                })
-        };
-
-        let port = port_opt.ok_or_else(|| MidiError::DeviceNotFound(device_name.to_string()))?;
-
-        let output = midi_out
-            .connect(&port, "Librarian")
-            .map_err(|e| MidiError::ConnectionFailed(e.to_string()))?;
-
-        let input = self.setup_midi_input(device_name, PedalType::Cxm1978, midi_channel)?;
-
-        let connection = MidiConnection {
-            output,
-            input,
-            midi_channel,
+        Ok(())
+    }
+    
+    /// Start watching an input port (e.g. a foot controller) for PCs to remap. PCs are
+    /// handled in the order they arrive by one worker per input, off the MIDI thread,
+    /// since running their actions needs the manager lock.
+    pub fn monitor_input(&mut self, port_name: &str) -> MidiResult<()> {
+        if self.monitored_inputs.contains_key(port_name) {
+            return Ok(());
+        }
+        let Some(app_handle) = self.app_handle.clone() else {
+            return Err(MidiError::Other("Input monitoring needs the app running".to_string()));
         };
-
-        let state = Cxm1978::new(midi_channel);
-
-        self.connections.insert(
-            device_name.to_string(),
-            DeviceConnection::Cxm1978 { connection, state },
-        );
-
-        println!("✅ Connected to CXM 1978: '{}' on MIDI Channel {}", device_name, midi_channel);
-
-        self.midi_output = Some(MidiOutput::new("Librarian Output")
-            .map_err(|e| MidiError::Other(e.to_string()))?);
-
+        
+        let mut midi_in = MidiInput::new("Librarian Monitor")
+            .map_err(|e| MidiError::Other(e.to_string()))?;
+        midi_in.ignore(Ignore::All);
+        let port = midi_in.ports().into_iter()
+            .find(|p| midi_in.port_name(p).map(|name| name == port_name).unwrap_or(false))
+            .ok_or_else(|| MidiError::DeviceNotFound(port_name.to_string()))?;
+        
+        let input_port = port_name.to_string();
+        let worker = OrderedWorker::spawn(move |message: Vec<u8>| {
+            let manager = app_handle.state::<SharedMidiManager>();
+            let library = app_handle.state::<crate::presets::SharedPresetLibrary>();
+            pc_remap::handle_monitored_message(&manager, &library, &input_port, &message);
+        });
+        let connection = midi_in.connect(
+            &port,
+            "librarian-monitor",
+            move |_stamp, message, _| {
+                if matches!(message.first(), Some(0xC0..=0xCF)) {
+                    worker.push(message.to_vec());
+                }
+            },
+            (),
+        ).map_err(|e| MidiError::ConnectionFailed(e.to_string()))?;
+        
+        self.monitored_inputs.insert(port_name.to_string(), connection);
+        if self.saved_monitored_inputs.insert(port_name.to_string()) {
+            self.persist_monitored_inputs()?;
+        }
+        println!("👂 Monitoring '{}' for program changes", port_name);
         Ok(())
     }
-
-    /// Send a parameter change to a CXM 1978
-    pub fn send_cxm1978_parameter(
+    
+    pub fn stop_monitoring_input(&mut self, port_name: &str) -> MidiResult<()> {
+        let connection = self.monitored_inputs.remove(port_name)
+            .ok_or_else(|| MidiError::Other(format!("'{}' isn't being monitored", port_name)))?;
+        connection.close();
+        self.saved_monitored_inputs.remove(port_name);
+        self.persist_monitored_inputs()
+    }
+    
+    fn persist_monitored_inputs(&self) -> MidiResult<()> {
+        let Some(path) = &self.monitored_inputs_path else { return Ok(()) };
+        let ports: Vec<String> = self.saved_monitored_inputs.iter().cloned().collect();
+        pc_remap::save_monitored_inputs(path, &ports)
+    }
+    
+    /// Input ports being monitored, sorted
+    pub fn monitored_inputs(&self) -> Vec<String> {
+        let mut ports: Vec<String> = self.monitored_inputs.keys().cloned().collect();
+        ports.sort();
+        ports
+    }
+    
+    /// Set a Gen Loss MKII's 16 DIP switches from a saved profile, as one batch
+    pub fn apply_gen_loss_dip_profile(&mut self, device_name: &str, profile_name: &str) -> MidiResult<DipProfile> {
+        let profile = self.gen_loss_dip_profiles.get(profile_name)?.clone();
+        let params = profile.dips
+            .parameters()
+            .into_iter()
+            .map(PedalParameter::GenLossMkii)
+            .collect();
+        self.send_parameters_batch(device_name, params)?;
+        Ok(profile)
+    }
+    
+    /// Save current state to a Gen Loss MKII preset slot (1-122) using CC 111
+    pub fn save_gen_loss_preset(
         &mut self,
         device_name: &str,
-        param: Cxm1978Parameter,
+        slot: u8,
     ) -> MidiResult<()> {
+        if slot < 1 || slot > 122 {
+            return Err(MidiError::Other(format!("Invalid preset slot: {}. Must be 1-122", slot)));
+        }
+        
         let device = self.connections.get_mut(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
-
+        
         match device {
-            DeviceConnection::Cxm1978 { connection, state } => {
-                let cc_number = param.cc_number();
-                let cc_value = param.cc_value();
-
-                #[cfg(debug_assertions)]
-                println!("[CXM 1978] Sending CC#{} = {} (ch {})", cc_number, cc_value, connection.midi_channel);
-
-                connection.send_cc(cc_number, cc_value)?;
-                state.update_state(&param);
-
+            DeviceConnection::GenLossMkii { connection, .. } => {
+                connection.send_cc(GEN_LOSS_CC_PRESET_SAVE, slot)?;
+                println!("[Gen Loss MKII] Saved current state to preset slot {}", slot);
+                
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a CXM 1978".to_string())),
+            _ => Err(MidiError::Other("Device is not a Gen Loss MKII".to_string())),
         }
     }
-
-    /// Send a Program Change to recall a CXM 1978 preset (PC 0-29)
-    pub fn send_cxm1978_program_change(
+    
+    /// Send a program change to a Gen Loss MKII (navigate to preset slot 1-122)
+    pub fn send_gen_loss_program_change(
         &mut self,
         device_name: &str,
         program: u8,
     ) -> MidiResult<()> {
-        if program > 29 {
-            return Err(MidiError::Other(format!("Invalid preset slot: {}. Must be 0-29", program)));
-        }
-
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::ProgramChange { program };
         let device = self.connections.get_mut(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
 
         match device {
-            DeviceConnection::Cxm1978 { connection, .. } => {
+            DeviceConnection::GenLossMkii { connection, .. } => {
                 connection.send_program_change(program)?;
-                println!("[CXM 1978] Sent Program Change {} to recall preset {}", program, program);
+                println!("[Gen Loss MKII] Sent PC {} (navigated to preset slot {})", program, program);
+                self.record_history(device_name, checkpoint, change);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a CXM 1978".to_string())),
+            _ => Err(MidiError::Other("Device is not a Gen Loss MKII".to_string())),
         }
     }
 
-    /// Recall a CXM 1978 preset by sending all parameters
-    pub fn recall_cxm1978_preset(
+    /// Get any connected device's state, tagged with its pedal type
+    pub fn get_device_state(&self, device_name: &str) -> MidiResult<DeviceState> {
+        let mut state = self.connections.get(device_name)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?
+            .device_state()?;
+        state.firmware = self.device_firmware(device_name);
+        Ok(state)
+    }
+    
+    /// Get any connected device's state, typed
+    pub fn get_state(&self, device_name: &str) -> MidiResult<PedalState> {
+        self.connections.get(device_name)
+            .map(DeviceConnection::pedal_state)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))
+    }
+    
+    /// Get the state of every connected device, keyed by device name
+    pub fn get_all_device_states(&self) -> MidiResult<HashMap<String, DeviceState>> {
+        self.connections.keys()
+            .map(|name| Ok((name.clone(), self.get_device_state(name)?)))
+            .collect()
+    }
+    
+    /// Get the current state of a Microcosm
+    pub fn get_microcosm_state(&self, device_name: &str) -> MidiResult<MicrocosmState> {
+        self.get_device_state(device_name)?.parse(&PedalType::Microcosm)
+    }
+    
+    /// Get the current state of a Gen Loss MKII
+    pub fn get_gen_loss_state(&self, device_name: &str) -> MidiResult<GenLossMkiiState> {
+        self.get_device_state(device_name)?.parse(&PedalType::GenLossMkii)
+    }
+    
+    /// Send a parameter change to a Chroma Console
+    pub fn send_chroma_console_parameter(
         &mut self,
         device_name: &str,
-        state: &Cxm1978State,
+        param: ChromaConsoleParameter,
     ) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
         let device = self.connections.get_mut(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
-
+        
         match device {
-            DeviceConnection::Cxm1978 { connection, state: device_state } => {
-                let temp_cxm = Cxm1978 {
-                    state: state.clone(),
-                    midi_channel: connection.midi_channel,
-                };
-                let cc_map = temp_cxm.state_as_cc_map();
-
-                // Sort by CC number for deterministic order
-                let mut cc_pairs: Vec<(u8, u8)> = cc_map.into_iter().collect();
-                cc_pairs.sort_by_key(|(cc, _)| *cc);
-
-                for (cc_number, cc_value) in cc_pairs {
-                    connection.send_cc(cc_number, cc_value)?;
-                    tokio::task::block_in_place(|| thread::sleep(Duration::from_millis(20)));
-                }
-
-                device_state.state = state.clone();
-                println!("[CXM 1978] Recalled preset state for '{}'", device_name);
+            DeviceConnection::ChromaConsole { connection, state } => {
+                // Overall bypass goes out on CC 91 or 92 depending on the footswitch configuration
+                let (cc_number, cc_value) = param.to_cc_message_for_mode(state.bypass_mode)
+                    .ok_or_else(|| MidiError::Other(format!(
+                        "{} can't be sent in {:?} bypass mode", param.name(), state.bypass_mode
+                    )))?;
+                
+                connection.send_cc(cc_number, cc_value)?;
+                state.update_state(&param);
+                
+                let change = HistoryChange::parameter(param.name(), cc_number, cc_value);
+                self.record_history(device_name, checkpoint, change);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a CXM 1978".to_string())),
+            _ => Err(MidiError::Other("Device is not a Chroma Console".to_string())),
         }
     }
-
-    /// Save current state to a CXM 1978 preset slot (0-29)
-    pub fn save_cxm1978_preset(
+    
+    /// Send a program change to a Chroma Console (0-79 for 80 user presets)
+    pub fn send_chroma_console_program_change(
         &mut self,
         device_name: &str,
-        slot: u8,
+        program: u8,
     ) -> MidiResult<()> {
-        if slot > 29 {
-            return Err(MidiError::Other(format!("Invalid preset slot: {}. Must be 0-29", slot)));
-        }
-
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::ProgramChange { program };
         let device = self.connections.get_mut(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
-
+        
         match device {
-            DeviceConnection::Cxm1978 { connection, .. } => {
-                connection.send_cc(CXM_CC_PRESET_SAVE, slot)?;
-                println!("[CXM 1978] Saved current state to preset slot {}", slot);
+            DeviceConnection::ChromaConsole { connection, state } => {
+                connection.send_program_change(program)?;
+                state.load_preset(program);
+                self.record_history(device_name, checkpoint, change);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a CXM 1978".to_string())),
+            _ => Err(MidiError::Other("Device is not a Chroma Console".to_string())),
         }
     }
-
-    /// Get the current state of a CXM 1978
-    pub fn get_cxm1978_state(&self, device_name: &str) -> MidiResult<Cxm1978State> {
-        let device = self.connections.get(device_name)
-            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
-
-        match device {
-            DeviceConnection::Cxm1978 { state, .. } => Ok(state.state.clone()),
-            _ => Err(MidiError::Other("Device is not a CXM 1978".to_string())),
+    
+    /// Put the current state into a Chroma Console user preset slot (PC 40-79) ready to be
+    /// saved. The pedal has no MIDI save, so this jumps to the slot, re-sends the state the
+    /// program change just replaced, and returns what to do on the pedal to store it.
+    pub fn save_chroma_console_preset(&mut self, device_name: &str, slot: u8) -> MidiResult<String> {
+        if !(CHROMA_USER_PRESET_START..ChromaConsole::preset_count()).contains(&slot) {
+            return Err(MidiError::Other(format!(
+                "Invalid preset slot: {}. Must be {}-{}", slot, CHROMA_USER_PRESET_START, ChromaConsole::preset_count() - 1
+            )));
         }
+        let state = self.get_chroma_console_state(device_name)?;
+        self.send_chroma_console_program_change(device_name, slot)?;
+        self.recall_chroma_console_preset(device_name, &state)?;
+        println!("[Chroma Console] Loaded current state into PC {} - waiting for a manual save", slot);
+        Ok(chroma_console::manual_save_instructions(slot))
     }
-
-    // ========================================================================
-    // Chase Bliss Audio Mood MkII Methods
-    // ========================================================================
-
-    /// Connect to a Chase Bliss Audio Mood MkII
-    pub fn connect_mood_mkii(
+    
+    /// Recall a preset on a Chroma Console (send all parameters)
+    pub fn recall_chroma_console_preset(
         &mut self,
         device_name: &str,
-        midi_channel: u8,
-    ) -> MidiResult<()> {
-        if midi_channel < 1 || midi_channel > 16 {
-            return Err(MidiError::InvalidChannel(midi_channel));
-        }
-        if self.connections.contains_key(device_name) {
-            return Err(MidiError::AlreadyConnected(device_name.to_string()));
-        }
-        let midi_out = self.midi_output.take()
-            .ok_or_else(|| MidiError::Other("MIDI output not initialized".to_string()))?;
-        let port_opt = {
-            let ports = midi_out.ports();
-            ports.into_iter()
-                .find(|p| {
-                    midi_out.port_name(p)
-                        .map(|name| name.to_lowercase().contains(&device_name.to_lowercase()))
-                        .unwrap_or(false)
-                })
-        };
-        let port = port_opt.ok_or_else(|| MidiError::DeviceNotFound(device_name.to_string()))?;
-        let output = midi_out.connect(&port, "Librarian")
-            .map_err(|e| MidiError::ConnectionFailed(e.to_string()))?;
-        let input = self.setup_midi_input(device_name, PedalType::MoodMkii, midi_channel)?;
-        let connection = MidiConnection { output, input, midi_channel };
-        let state = MoodMkii::new(midi_channel);
-        self.connections.insert(device_name.to_string(), DeviceConnection::MoodMkii { connection, state });
-        println!("✅ Connected to Mood MkII: '{}' on MIDI Channel {}", device_name, midi_channel);
-        self.midi_output = Some(MidiOutput::new("Librarian Output")
-            .map_err(|e| MidiError::Other(e.to_string()))?);
-        Ok(())
-    }
-
-    /// Send a parameter change to a Mood MkII
-    pub fn send_mood_mkii_parameter(
-        &mut self,
-        device_name: &str,
-        param: MoodMkiiParameter,
+        state: &ChromaConsoleState,
     ) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::Recall;
         let device = self.connections.get_mut(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        
         match device {
-            DeviceConnection::MoodMkii { connection, state } => {
-                let cc_number = param.cc_number();
-                let cc_value = param.cc_value();
-                #[cfg(debug_assertions)]
-                println!("[Mood MkII] Sending CC#{} = {} (ch {})", cc_number, cc_value, connection.midi_channel);
-                connection.send_cc(cc_number, cc_value)?;
-                state.update_state(&param);
+            DeviceConnection::ChromaConsole { connection, state: device_state } => {
+                // Dual bypass only exists when the pedal's footswitches are in dual mode
+                if state.bypass_state == BypassState::DualBypass && device_state.bypass_mode != BypassMode::Dual {
+                    return Err(MidiError::Other(
+                        "Preset uses dual bypass, but the Chroma Console isn't in dual bypass mode".to_string()
+                    ));
+                }
+                
+                // Get all CC values from the preset state
+                let mut temp_chroma = ChromaConsole {
+                    state: state.clone(),
+                    midi_channel: connection.midi_channel(),
+                    bypass_mode: device_state.bypass_mode,
+                };
+                // Gesture and capture are live transport state - the recall plan never sends them
+                temp_chroma.state.gesture_mode = device_state.state.gesture_mode;
+                temp_chroma.state.capture_mode = device_state.state.capture_mode;
+                temp_chroma.state.capture_transport = device_state.state.capture_transport;
+                let plan = temp_chroma.recall_plan();
+                
+                println!("[Chroma Console] Recalling preset: sending {} CC messages", plan.len());
+                
+                connection.send_plan(&plan)?;
+                
+                println!("[Chroma Console] Preset recall complete");
+                
+                // Update device state
+                *device_state = temp_chroma;
+                
+                self.record_history(device_name, checkpoint, change);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a Mood MkII".to_string())),
-        }
-    }
-
-    /// Get the current state of a Mood MkII
-    pub fn get_mood_mkii_state(&self, device_name: &str) -> MidiResult<MoodMkiiState> {
-        let device = self.connections.get(device_name)
-            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
-        match device {
-            DeviceConnection::MoodMkii { state, .. } => Ok(state.state.clone()),
-            _ => Err(MidiError::Other("Device is not a Mood MkII".to_string())),
+            _ => Err(MidiError::Other("Device is not a Chroma Console".to_string())),
         }
     }
-
-    /// Recall a preset on a Mood MkII (send all parameters)
-    pub fn recall_mood_mkii_preset(
+    
+    /// Set how a Chroma Console's footswitches are configured for bypass.
+    /// Nothing is sent - this only changes which CC bypass changes go out on.
+    pub fn set_chroma_console_bypass_mode(
         &mut self,
         device_name: &str,
-        state: &MoodMkiiState,
+        bypass_mode: BypassMode,
     ) -> MidiResult<()> {
         let device = self.connections.get_mut(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        
         match device {
-            DeviceConnection::MoodMkii { connection, state: device_state } => {
-                let temp = MoodMkii { state: state.clone(), midi_channel: connection.midi_channel };
-                let cc_map = temp.state_as_cc_map();
-                println!("[Mood MkII] Recalling preset: sending {} CC messages", cc_map.len());
-                for (cc_number, value) in cc_map.iter() {
-                    connection.send_cc(*cc_number, *value)?;
-                    tokio::task::block_in_place(|| thread::sleep(Duration::from_millis(20)));
-                }
-                println!("[Mood MkII] Preset recall complete");
-                *device_state = temp;
+            DeviceConnection::ChromaConsole { state, .. } => {
+                state.bypass_mode = bypass_mode;
+                println!("[Chroma Console] Bypass mode set to {:?}", bypass_mode);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a Mood MkII".to_string())),
+            _ => Err(MidiError::Other("Device is not a Chroma Console".to_string())),
         }
     }
-
-    /// Save current state to a Mood MkII preset slot (1-122)
-    pub fn save_mood_mkii_preset(
+    
+    /// Get the current state of a Chroma Console
+    pub fn get_chroma_console_state(&self, device_name: &str) -> MidiResult<ChromaConsoleState> {
+        self.get_device_state(device_name)?.parse(&PedalType::ChromaConsole)
+    }
+    
+    /// Start recording a Chroma Console capture that switches to playback after `duration`.
+    /// `on_elapsed` runs on a worker thread when the time is up and should hand its timer id
+    /// to `finish_capture_one_shot`. A one-shot already running on the device is cancelled.
+    pub fn start_capture_one_shot(
         &mut self,
         device_name: &str,
-        slot: u8,
+        duration: Duration,
+        routing: CaptureRouting,
+        on_elapsed: impl FnOnce(u64) + Send + 'static,
     ) -> MidiResult<()> {
-        if slot < 1 || slot > 122 {
-            return Err(MidiError::Other(format!("Invalid preset slot: {}. Must be 1-122", slot)));
-        }
-        let device = self.connections.get_mut(device_name)
-            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
-        match device {
-            DeviceConnection::MoodMkii { connection, .. } => {
-                connection.send_cc(MOOD_CC_PRESET_SAVE, slot)?;
-                println!("[Mood MkII] Saved current state to preset slot {}", slot);
-                Ok(())
-            }
-            _ => Err(MidiError::Other("Device is not a Mood MkII".to_string())),
+        self.capture_timers.cancel(device_name);
+        self.send_chroma_console_parameter(device_name, ChromaConsoleParameter::CaptureRouting(routing))?;
+        self.send_chroma_console_parameter(device_name, ChromaConsoleParameter::CaptureMode(CaptureMode::Record))?;
+        self.capture_timers.start(device_name, duration, on_elapsed);
+        self.emit_capture_event("chroma-capture-started", device_name, Some(duration.as_millis() as u64));
+        Ok(())
+    }
+    
+    /// Switch a one-shot capture to playback. Does nothing if the one-shot was
+    /// stopped or replaced since its timer started.
+    pub fn finish_capture_one_shot(&mut self, device_name: &str, timer_id: u64) -> MidiResult<()> {
+        if !self.capture_timers.finish(device_name, timer_id) {
+            return Ok(());
         }
+        self.send_chroma_console_parameter(device_name, ChromaConsoleParameter::CaptureMode(CaptureMode::Play))?;
+        self.emit_capture_event("chroma-capture-stopped", device_name, None);
+        Ok(())
     }
-
-    /// Send a program change to a Mood MkII (navigate to preset slot 1-122)
-    pub fn send_mood_mkii_program_change(
+    
+    /// Stop capture on a Chroma Console, aborting any pending one-shot
+    pub fn stop_capture(&mut self, device_name: &str) -> MidiResult<()> {
+        self.capture_timers.cancel(device_name);
+        self.send_chroma_console_parameter(device_name, ChromaConsoleParameter::CaptureMode(CaptureMode::Stop))?;
+        self.emit_capture_event("chroma-capture-stopped", device_name, None);
+        Ok(())
+    }
+    
+    /// Schedule `target` to be recalled on a device later, in milliseconds or in beats of
+    /// the MIDI clock heard on an input port. `on_due` runs on the scheduler's worker
+    /// thread with the schedule's id; it should call `fire_scheduled_recall` with it.
+    pub fn schedule_recall(
         &mut self,
         device_name: &str,
-        program: u8,
-    ) -> MidiResult<()> {
-        let device = self.connections.get_mut(device_name)
+        preset_id: &str,
+        preset_name: &str,
+        spec: ScheduleSpec,
+        target: RecallTarget,
+        on_due: impl FnOnce(u64) + Send + 'static,
+    ) -> MidiResult<ScheduledRecall> {
+        let device = self.connections.get(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
-        match device {
-            DeviceConnection::MoodMkii { connection, .. } => {
-                connection.send_program_change(program)?;
-                println!("[Mood MkII] Sent PC {} (navigated to preset slot {})", program, program);
+        if device.pedal_type() != target.pedal_type {
+            return Err(MidiError::Other(format!(
+                "Device is a {}, not a {}",
+                device.pedal_type().as_str(),
+                target.pedal_type.as_str()
+            )));
+        }
+        self.recall_scheduler.schedule(device_name, preset_id, preset_name, spec, target, on_due)
+    }
+    
+    /// Recall a schedule that came due, emitting scheduled-fired, or scheduled-missed if its
+    /// device has gone. Does nothing if it was cancelled meanwhile.
+    pub fn fire_scheduled_recall(&mut self, id: u64) -> MidiResult<()> {
+        let Some((recall, target)) = self.recall_scheduler.finish(id) else { return Ok(()) };
+        if !self.is_connected(&recall.device_name) {
+            self.emit_scheduled_event("scheduled-missed", recall, Some("Device disconnected".to_string()));
+            return Ok(());
+        }
+        match self.recall_preset_json(&recall.device_name, target.pedal_type, target.parameters) {
+            Ok(()) => {
+                self.emit_scheduled_event("scheduled-fired", recall, None);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a Mood MkII".to_string())),
+            Err(e) => {
+                self.emit_scheduled_event("scheduled-missed", recall, Some(e.to_string()));
+                Err(e)
+            }
         }
     }
-
+    
+    /// Cancel a scheduled recall; returns whether it was still pending
+    pub fn cancel_scheduled(&mut self, id: u64) -> bool {
+        self.recall_scheduler.cancel(id)
+    }
+    
+    /// Pending scheduled recalls, oldest first
+    pub fn list_scheduled(&self) -> Vec<ScheduledRecall> {
+        self.recall_scheduler.list()
+    }
+    
+    fn emit_scheduled_event(&self, event_name: &str, recall: ScheduledRecall, reason: Option<String>) {
+        let Some(app_handle) = &self.app_handle else { return };
+        let event = ScheduledRecallEvent { recall, reason };
+        if let Err(e) = app_handle.emit(event_name, &event) {
+            eprintln!("❌ Failed to emit {} event: {}", event_name, e);
+        }
+    }
+    
+    fn emit_capture_event(&self, event_name: &str, device_name: &str, duration_ms: Option<u64>) {
+        let Some(app_handle) = &self.app_handle else { return };
+        let Ok(state) = self.get_chroma_console_state(device_name) else { return };
+        let event = ChromaCaptureEvent {
+            device_name: device_name.to_string(),
+            capture_transport: state.capture_transport,
+            duration_ms,
+        };
+        if let Err(e) = app_handle.emit(event_name, &event) {
+            eprintln!("❌ Failed to emit capture event: {}", e);
+        }
+    }
+    
     // ========================================================================
-    // Chase Bliss Audio Billy Strings Wombtone Methods
+    // Chase Bliss Preamp MK II Methods
     // ========================================================================
-
-    /// Connect to a Chase Bliss Audio Billy Strings Wombtone
-    pub fn connect_billy_strings_wombtone(
+    
+    /// Connect to a Chase Bliss Preamp MK II
+    pub fn connect_preamp_mk2(
         &mut self,
         device_name: &str,
         midi_channel: u8,
     ) -> MidiResult<()> {
-        if midi_channel < 1 || midi_channel > 16 {
-            return Err(MidiError::InvalidChannel(midi_channel));
-        }
-        if self.connections.contains_key(device_name) {
-            return Err(MidiError::AlreadyConnected(device_name.to_string()));
-        }
-        let midi_out = self.midi_output.take()
-            .ok_or_else(|| MidiError::Other("MIDI output not initialized".to_string()))?;
-        let port_opt = {
-            let ports = midi_out.ports();
-            ports.into_iter()
-                .find(|p| {
-                    midi_out.port_name(p)
-                        .map(|name| name.to_lowercase().contains(&device_name.to_lowercase()))
-                        .unwrap_or(false)
-                })
-        };
-        let port = port_opt.ok_or_else(|| MidiError::DeviceNotFound(device_name.to_string()))?;
-        let output = midi_out.connect(&port, "Librarian")
-            .map_err(|e| MidiError::ConnectionFailed(e.to_string()))?;
-        let input = self.setup_midi_input(device_name, PedalType::BillyStringsWombtone, midi_channel)?;
-        let connection = MidiConnection { output, input, midi_channel };
-        let state = BillyStringsWombtone::new(midi_channel);
-        self.connections.insert(device_name.to_string(), DeviceConnection::BillyStringsWombtone { connection, state });
-        println!("✅ Connected to Billy Strings Wombtone: '{}' on MIDI Channel {}", device_name, midi_channel);
-        self.midi_output = Some(MidiOutput::new("Librarian Output")
-            .map_err(|e| MidiError::Other(e.to_string()))?);
-        Ok(())
+        self.connect_on_port(PedalType::PreampMk2, device_name, device_name, midi_channel)
     }
-
-    /// Send a parameter change to a Billy Strings Wombtone
-    pub fn send_billy_strings_wombtone_parameter(
+    
+    /// Send a parameter change to a Preamp MK II. With a strict fader range (the default),
+    /// fader values beyond what the motors can reach are clamped, with a warning logged.
+    pub fn send_preamp_mk2_parameter(
         &mut self,
         device_name: &str,
-        param: BillyStringsWombtoneParameter,
+        param: PreampMk2Parameter,
     ) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
         let device = self.connections.get_mut(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        
         match device {
-            DeviceConnection::BillyStringsWombtone { connection, state } => {
+            DeviceConnection::PreampMk2 { connection, state } => {
+                let requested = param.cc_value();
+                let param = if state.strict_fader_range { param.clamped_to_fader_range() } else { param };
+                if param.cc_value() != requested {
+                    eprintln!(
+                        "⚠️ [Preamp MK II] {} = {} is beyond fader travel; sending {}",
+                        param.name(), requested, param.cc_value()
+                    );
+                }
+                let change = HistoryChange::parameter(param.name(), param.cc_number(), param.cc_value());
                 let cc_number = param.cc_number();
                 let cc_value = param.cc_value();
+                
                 #[cfg(debug_assertions)]
-                println!("[Billy Strings Wombtone] Sending CC#{} = {} (ch {})", cc_number, cc_value, connection.midi_channel);
+                println!("[Preamp MK II] Sending CC#{} = {} (ch {})", cc_number, cc_value, connection.midi_channel());
+                
                 connection.send_cc(cc_number, cc_value)?;
                 state.update_state(&param);
+                
+                self.record_history(device_name, checkpoint, change);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a Billy Strings Wombtone".to_string())),
+            _ => Err(MidiError::Other("Device is not a Preamp MK II".to_string())),
         }
     }
-
-    /// Get the current state of a Billy Strings Wombtone
-    pub fn get_billy_strings_wombtone_state(&self, device_name: &str) -> MidiResult<BillyStringsWombtoneState> {
-        let device = self.connections.get(device_name)
+    
+    /// Send a Program Change to a Preamp MK II to recall a preset (PC 0-29 → presets 0-29)
+    pub fn send_preamp_mk2_program_change(
+        &mut self,
+        device_name: &str,
+        program: u8,
+    ) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::ProgramChange { program };
+        if program > 29 {
+            return Err(MidiError::Other(format!("Invalid preset slot: {}. Must be 0-29", program)));
+        }
+        let device = self.connections.get_mut(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+
         match device {
-            DeviceConnection::BillyStringsWombtone { state, .. } => Ok(state.state.clone()),
-            _ => Err(MidiError::Other("Device is not a Billy Strings Wombtone".to_string())),
+            DeviceConnection::PreampMk2 { connection, .. } => {
+                connection.send_program_change(program)?;
+                println!("[Preamp MK II] Sent Program Change {} to recall preset {}", program, program);
+                self.record_history(device_name, checkpoint, change);
+                Ok(())
+            }
+            _ => Err(MidiError::Other("Device is not a Preamp MK II".to_string())),
         }
     }
 
-    /// Recall a preset on a Billy Strings Wombtone (send all parameters)
-    pub fn recall_billy_strings_wombtone_preset(
+    /// Recall a preset by sending all parameters to the Preamp MK II
+    pub fn recall_preamp_mk2_preset(
         &mut self,
         device_name: &str,
-        state: &BillyStringsWombtoneState,
+        state: &PreampMk2State,
     ) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::Recall;
         let device = self.connections.get_mut(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        
         match device {
-            DeviceConnection::BillyStringsWombtone { connection, state: device_state } => {
-                let temp = BillyStringsWombtone { state: state.clone(), midi_channel: connection.midi_channel };
-                let cc_map = temp.state_as_cc_map();
-                println!("[Billy Strings Wombtone] Recalling preset: sending {} CC messages", cc_map.len());
-                for (cc_number, value) in cc_map.iter() {
-                    connection.send_cc(*cc_number, *value)?;
-                    tokio::task::block_in_place(|| thread::sleep(Duration::from_millis(20)));
-                }
-                println!("[Billy Strings Wombtone] Preset recall complete");
-                *device_state = temp;
+            DeviceConnection::PreampMk2 { connection, state: device_state } => {
+                // Get all CC values from the preset state
+                let strict_fader_range = device_state.strict_fader_range;
+                let temp_preamp = PreampMk2 {
+                    state: if strict_fader_range { state.clamped_to_fader_range() } else { state.clone() },
+                    midi_channel: connection.midi_channel(),
+                    strict_fader_range,
+                };
+                let plan = temp_preamp.recall_plan();
+                
+                println!("[Preamp MK II] Recalling preset: sending {} CC messages", plan.len());
+                
+                connection.send_plan(&plan)?;
+                
+                println!("[Preamp MK II] Preset recall complete");
+                
+                // Update device state
+                *device_state = temp_preamp;
+                
+                self.record_history(device_name, checkpoint, change);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a Billy Strings Wombtone".to_string())),
+            _ => Err(MidiError::Other("Device is not a Preamp MK II".to_string())),
         }
     }
-
-    /// Save current state to a Billy Strings Wombtone preset slot (1-122)
-    pub fn save_billy_strings_wombtone_preset(
+    
+    /// Save current state to a preset slot (0-29) using CC 27
+    pub fn save_preamp_mk2_preset(
         &mut self,
         device_name: &str,
         slot: u8,
     ) -> MidiResult<()> {
-        if slot < 1 || slot > 122 {
-            return Err(MidiError::Other(format!("Invalid preset slot: {}. Must be 1-122", slot)));
+        // Validate slot (0-29 for 30 presets)
+        if slot > 29 {
+            return Err(MidiError::Other(format!("Invalid preset slot: {}. Must be 0-29", slot)));
         }
+        
         let device = self.connections.get_mut(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        
         match device {
-            DeviceConnection::BillyStringsWombtone { connection, .. } => {
-                connection.send_cc(BSW_CC_PRESET_SAVE, slot)?;
-                println!("[Billy Strings Wombtone] Saved current state to preset slot {}", slot);
+            DeviceConnection::PreampMk2 { connection, .. } => {
+                // Send CC 27 with slot number (0-29)
+                connection.send_cc(PREAMP_CC_PRESET_SAVE, slot)?;
+                println!("[Preamp MK II] Saved current state to preset slot {}", slot);
+                
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a Billy Strings Wombtone".to_string())),
+            _ => Err(MidiError::Other("Device is not a Preamp MK II".to_string())),
         }
     }
-
-    /// Send a program change to a Billy Strings Wombtone (navigate to preset slot 1-122)
-    pub fn send_billy_strings_wombtone_program_change(
+    
+    /// Turn Preamp MK II fader movement off or back on. The pedal has no MIDI command for
+    /// this, so while it's off fader CCs are held here instead of sent - the believed state
+    /// still follows them - and turning it back on sends the latest held value of each fader.
+    /// A reconnected pedal starts with movement on.
+    pub fn set_preamp_mk2_motorized_faders_enabled(
         &mut self,
         device_name: &str,
-        program: u8,
+        enabled: bool,
     ) -> MidiResult<()> {
         let device = self.connections.get_mut(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        
         match device {
-            DeviceConnection::BillyStringsWombtone { connection, .. } => {
-                connection.send_program_change(program)?;
-                println!("[Billy Strings Wombtone] Sent PC {} (navigated to preset slot {})", program, program);
+            DeviceConnection::PreampMk2 { connection, .. } => {
+                if !enabled {
+                    connection.held_ccs = PREAMP_FADER_CCS.to_vec();
+                    return Ok(());
+                }
+                connection.held_ccs.clear();
+                let held = std::mem::take(&mut connection.pending_ccs);
+                if !held.is_empty() {
+                    println!("[Preamp MK II] Fader movement enabled: sending {} held fader CCs", held.len());
+                }
+                connection.send_plan(&SendPlan::in_order(held))
+            }
+            _ => Err(MidiError::Other("Device is not a Preamp MK II".to_string())),
+        }
+    }
+    
+    /// Whether Preamp MK II fader CCs are being sent (see `set_preamp_mk2_motorized_faders_enabled`)
+    pub fn preamp_mk2_motorized_faders_enabled(&self, device_name: &str) -> MidiResult<bool> {
+        match self.connections.get(device_name) {
+            Some(DeviceConnection::PreampMk2 { connection, .. }) => Ok(connection.held_ccs.is_empty()),
+            Some(_) => Err(MidiError::Other("Device is not a Preamp MK II".to_string())),
+            None => Err(MidiError::NotConnected(device_name.to_string())),
+        }
+    }
+    
+    /// Clamp Preamp MK II fader CCs to the reachable range (on), or send them as asked (off).
+    /// A reconnected pedal starts strict.
+    pub fn set_preamp_mk2_strict_fader_range(&mut self, device_name: &str, strict: bool) -> MidiResult<()> {
+        match self.connections.get_mut(device_name) {
+            Some(DeviceConnection::PreampMk2 { connection, state }) => {
+                state.strict_fader_range = strict;
+                connection.strict_fader_range = strict;
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a Billy Strings Wombtone".to_string())),
+            Some(_) => Err(MidiError::Other("Device is not a Preamp MK II".to_string())),
+            None => Err(MidiError::NotConnected(device_name.to_string())),
         }
     }
-
+    
+    /// Get the current state of a Preamp MK II
+    pub fn get_preamp_mk2_state(&self, device_name: &str) -> MidiResult<PreampMk2State> {
+        self.get_device_state(device_name)?.parse(&PedalType::PreampMk2)
+    }
+    
     // ========================================================================
-    // Chase Bliss Audio Lossy Methods
+    // Chase Bliss / Meris CXM 1978 Methods
     // ========================================================================
 
-    /// Connect to a Chase Bliss Audio Lossy
-    pub fn connect_lossy(
+    /// Connect to a Chase Bliss / Meris CXM 1978
+    pub fn connect_cxm1978(
         &mut self,
         device_name: &str,
         midi_channel: u8,
     ) -> MidiResult<()> {
-        if midi_channel < 1 || midi_channel > 16 {
-            return Err(MidiError::InvalidChannel(midi_channel));
-        }
-        if self.connections.contains_key(device_name) {
-            return Err(MidiError::AlreadyConnected(device_name.to_string()));
-        }
-        let midi_out = self.midi_output.take()
-            .ok_or_else(|| MidiError::Other("MIDI output not initialized".to_string()))?;
-        let port_opt = {
-            let ports = midi_out.ports();
-            ports.into_iter()
-                .find(|p| {
-                    midi_out.port_name(p)
-                        .map(|name| name.to_lowercase().contains(&device_name.to_lowercase()))
-                        .unwrap_or(false)
-                })
-        };
-        let port = port_opt.ok_or_else(|| MidiError::DeviceNotFound(device_name.to_string()))?;
-        let output = midi_out.connect(&port, "Librarian")
-            .map_err(|e| MidiError::ConnectionFailed(e.to_string()))?;
-        let input = self.setup_midi_input(device_name, PedalType::Lossy, midi_channel)?;
-        let connection = MidiConnection { output, input, midi_channel };
-        let state = Lossy::new(midi_channel);
-        self.connections.insert(device_name.to_string(), DeviceConnection::Lossy { connection, state });
-        println!("✅ Connected to Lossy: '{}' on MIDI Channel {}", device_name, midi_channel);
-        self.midi_output = Some(MidiOutput::new("Librarian Output")
-            .map_err(|e| MidiError::Other(e.to_string()))?);
-        Ok(())
+        self.connect_on_port(PedalType::Cxm1978, device_name, device_name, midi_channel)
     }
 
-    /// Send a parameter change to a Lossy
-    pub fn send_lossy_parameter(
+    /// Send a parameter change to a CXM 1978
+    pub fn send_cxm1978_parameter(
         &mut self,
         device_name: &str,
-        param: LossyParameter,
+        param: Cxm1978Parameter,
     ) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::parameter(param.name(), param.cc_number(), param.cc_value());
         let device = self.connections.get_mut(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+
         match device {
-            DeviceConnection::Lossy { connection, state } => {
+            DeviceConnection::Cxm1978 { connection, state } => {
                 let cc_number = param.cc_number();
                 let cc_value = param.cc_value();
+
                 #[cfg(debug_assertions)]
-                println!("[Lossy] Sending CC#{} = {} (ch {})", cc_number, cc_value, connection.midi_channel);
+                println!("[CXM 1978] Sending CC#{} = {} (ch {})", cc_number, cc_value, connection.midi_channel());
+
                 connection.send_cc(cc_number, cc_value)?;
                 state.update_state(&param);
+
+                self.record_history(device_name, checkpoint, change);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a Lossy".to_string())),
+            _ => Err(MidiError::Other("Device is not a CXM 1978".to_string())),
         }
     }
 
-    /// Get the current state of a Lossy
-    pub fn get_lossy_state(&self, device_name: &str) -> MidiResult<LossyState> {
-        let device = self.connections.get(device_name)
+    /// Apply a CC received from a CXM 1978 to the believed state (nothing is sent).
+    /// Called from the MIDI input callback so state follows the pedal's own faders and buttons.
+    /// Returns the parsed parameter, or None if the CC isn't one we track.
+    pub fn update_cxm1978_state_from_cc(
+        &mut self,
+        device_name: &str,
+        cc_number: u8,
+        value: u8,
+    ) -> MidiResult<Option<Cxm1978Parameter>> {
+        let device = self.connections.get_mut(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+
         match device {
-            DeviceConnection::Lossy { state, .. } => Ok(state.state.clone()),
-            _ => Err(MidiError::Other("Device is not a Lossy".to_string())),
+            DeviceConnection::Cxm1978 { state, .. } => {
+                let param = Cxm1978Parameter::from_cc(cc_number, value);
+                if let Some(param) = &param {
+                    state.update_state(param);
+                }
+                Ok(param)
+            }
+            _ => Err(MidiError::Other("Device is not a CXM 1978".to_string())),
         }
     }
 
-    /// Recall a preset on a Lossy (send all parameters)
-    pub fn recall_lossy_preset(
+    /// Send a Program Change to recall a CXM 1978 preset (PC 0-29)
+    pub fn send_cxm1978_program_change(
         &mut self,
         device_name: &str,
-        state: &LossyState,
+        program: u8,
     ) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::ProgramChange { program };
+        if program > 29 {
+            return Err(MidiError::Other(format!("Invalid preset slot: {}. Must be 0-29", program)));
+        }
+
         let device = self.connections.get_mut(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+
         match device {
-            DeviceConnection::Lossy { connection, state: device_state } => {
-                let temp = Lossy { state: state.clone(), midi_channel: connection.midi_channel };
-                let cc_map = temp.state_as_cc_map();
-                println!("[Lossy] Recalling preset: sending {} CC messages", cc_map.len());
-                for (cc_number, value) in cc_map.iter() {
-                    connection.send_cc(*cc_number, *value)?;
-                    tokio::task::block_in_place(|| thread::sleep(Duration::from_millis(20)));
-                }
-                println!("[Lossy] Preset recall complete");
-                *device_state = temp;
+            DeviceConnection::Cxm1978 { connection, .. } => {
+                connection.send_program_change(program)?;
+                println!("[CXM 1978] Sent Program Change {} to recall preset {}", program, program);
+                self.record_history(device_name, checkpoint, change);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a Lossy".to_string())),
+            _ => Err(MidiError::Other("Device is not a CXM 1978".to_string())),
         }
     }
 
-    /// Save current state to a Lossy preset slot (1-122)
-    pub fn save_lossy_preset(
+    /// Recall a CXM 1978 preset by sending all parameters
+    pub fn recall_cxm1978_preset(
         &mut self,
         device_name: &str,
-        slot: u8,
+        state: &Cxm1978State,
     ) -> MidiResult<()> {
-        if slot < 1 || slot > 122 {
-            return Err(MidiError::Other(format!("Invalid preset slot: {}. Must be 1-122", slot)));
-        }
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::Recall;
         let device = self.connections.get_mut(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+
         match device {
-            DeviceConnection::Lossy { connection, .. } => {
-                connection.send_cc(LOSSY_CC_PRESET_SAVE, slot)?;
-                println!("[Lossy] Saved current state to preset slot {}", slot);
+            DeviceConnection::Cxm1978 { connection, state: device_state } => {
+                let temp_cxm = Cxm1978 {
+                    state: state.clone(),
+                    midi_channel: connection.midi_channel(),
+                };
+                let plan = temp_cxm.recall_plan();
+
+                connection.send_plan(&plan)?;
+
+                device_state.state = state.clone();
+                println!("[CXM 1978] Recalled preset state for '{}'", device_name);
+                self.record_history(device_name, checkpoint, change);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a Lossy".to_string())),
+            _ => Err(MidiError::Other("Device is not a CXM 1978".to_string())),
         }
     }
 
-    /// Send a program change to a Lossy (navigate to preset slot 1-122)
-    pub fn send_lossy_program_change(
+    /// Save current state to a CXM 1978 preset slot (0-29)
+    pub fn save_cxm1978_preset(
         &mut self,
         device_name: &str,
-        program: u8,
+        slot: u8,
     ) -> MidiResult<()> {
+        if slot > 29 {
+            return Err(MidiError::Other(format!("Invalid preset slot: {}. Must be 0-29", slot)));
+        }
+
         let device = self.connections.get_mut(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+
         match device {
-            DeviceConnection::Lossy { connection, .. } => {
-                connection.send_program_change(program)?;
-                println!("[Lossy] Sent PC {} (navigated to preset slot {})", program, program);
+            DeviceConnection::Cxm1978 { connection, .. } => {
+                connection.send_cc(CXM_CC_PRESET_SAVE, slot)?;
+                println!("[CXM 1978] Saved current state to preset slot {}", slot);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a Lossy".to_string())),
+            _ => Err(MidiError::Other("Device is not a CXM 1978".to_string())),
         }
     }
 
+    /// Get the current state of a CXM 1978
+    pub fn get_cxm1978_state(&self, device_name: &str) -> MidiResult<Cxm1978State> {
+        self.get_device_state(device_name)?.parse(&PedalType::Cxm1978)
+    }
+
     // ========================================================================
-    // Chase Bliss Audio Brothers AM Methods
+    // Chase Bliss Audio Mood MkII Methods
     // ========================================================================
 
-    /// Connect to a Chase Bliss Brothers AM pedal
-    pub fn connect_brothers_am(
+    /// Connect to a Chase Bliss Audio Mood MkII
+    pub fn connect_mood_mkii(
         &mut self,
         device_name: &str,
         midi_channel: u8,
     ) -> MidiResult<()> {
-        if midi_channel < 1 || midi_channel > 16 {
-            return Err(MidiError::InvalidChannel(midi_channel));
-        }
-        if self.connections.contains_key(device_name) {
-            return Err(MidiError::AlreadyConnected(device_name.to_string()));
-        }
-        let midi_out = self.midi_output.take()
-            .ok_or_else(|| MidiError::Other("MIDI output not initialized".to_string()))?;
-        let port_opt = {
-            let ports = midi_out.ports();
-            ports.into_iter().find(|p| {
-                midi_out.port_name(p)
-                    .map(|name| name.to_lowercase().contains(&device_name.to_lowercase()))
-                    .unwrap_or(false)
-            })
-        };
-        let port = port_opt.ok_or_else(|| MidiError::DeviceNotFound(device_name.to_string()))?;
-        let output = midi_out.connect(&port, "Librarian")
-            .map_err(|e| MidiError::ConnectionFailed(e.to_string()))?;
-        let input = self.setup_midi_input(device_name, PedalType::BrothersAm, midi_channel)?;
-        let connection = MidiConnection { output, input, midi_channel };
-        let state = BrothersAm::new(midi_channel);
-        self.connections.insert(
-            device_name.to_string(),
-            DeviceConnection::BrothersAm { connection, state },
-        );
-        println!("✅ Connected to Brothers AM: '{}' on MIDI Channel {}", device_name, midi_channel);
-        self.midi_output = Some(MidiOutput::new("Librarian Output")
-            .map_err(|e| MidiError::Other(e.to_string()))?);
-        Ok(())
+        self.connect_on_port(PedalType::MoodMkii, device_name, device_name, midi_channel)
     }
 
-    /// Send a parameter change to a Brothers AM
-    pub fn send_brothers_am_parameter(
+    /// Send a parameter change to a Mood MkII
+    pub fn send_mood_mkii_parameter(
         &mut self,
         device_name: &str,
-        param: BrothersAmParameter,
+        param: MoodMkiiParameter,
     ) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::parameter(param.name(), param.cc_number(), param.cc_value());
         let device = self.connections.get_mut(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
         match device {
-            DeviceConnection::BrothersAm { connection, state } => {
+            DeviceConnection::MoodMkii { connection, state } => {
                 let cc_number = param.cc_number();
                 let cc_value = param.cc_value();
                 #[cfg(debug_assertions)]
-                println!("[Brothers AM] Sending CC#{} = {} (ch {})", cc_number, cc_value, connection.midi_channel);
+                println!("[Mood MkII] Sending CC#{} = {} (ch {})", cc_number, cc_value, connection.midi_channel());
                 connection.send_cc(cc_number, cc_value)?;
                 state.update_state(&param);
+                self.record_history(device_name, checkpoint, change);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a Brothers AM".to_string())),
+            _ => Err(MidiError::Other("Device is not a Mood MkII".to_string())),
         }
     }
 
-    /// Recall a preset on a Brothers AM (send all parameters)
-    pub fn recall_brothers_am_preset(
+    /// Get the current state of a Mood MkII
+    pub fn get_mood_mkii_state(&self, device_name: &str) -> MidiResult<MoodMkiiState> {
+        self.get_device_state(device_name)?.parse(&PedalType::MoodMkii)
+    }
+
+    /// Recall a preset on a Mood MkII (send all parameters)
+    pub fn recall_mood_mkii_preset(
         &mut self,
         device_name: &str,
-        state: &BrothersAmState,
+        state: &MoodMkiiState,
     ) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::Recall;
         let device = self.connections.get_mut(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
         match device {
-            DeviceConnection::BrothersAm { connection, state: device_state } => {
-                let temp = BrothersAm {
-                    state: state.clone(),
-                    midi_channel: connection.midi_channel,
-                };
-                let cc_map = temp.state_as_cc_map();
-                println!("[Brothers AM] Recalling preset: sending {} CC messages", cc_map.len());
-                let mut cc_pairs: Vec<(u8, u8)> = cc_map.into_iter().collect();
-                cc_pairs.sort_by_key(|(cc, _)| *cc);
-                for (cc_number, value) in cc_pairs {
-                    connection.send_cc(cc_number, value)?;
-                    tokio::task::block_in_place(|| thread::sleep(Duration::from_millis(20)));
-                }
-                println!("[Brothers AM] Preset recall complete");
+            DeviceConnection::MoodMkii { connection, state: device_state } => {
+                let temp = MoodMkii { state: state.clone(), midi_channel: connection.midi_channel() };
+                let plan = temp.recall_plan();
+                println!("[Mood MkII] Recalling preset: sending {} CC messages", plan.len());
+                connection.send_plan(&plan)?;
+                println!("[Mood MkII] Preset recall complete");
                 *device_state = temp;
+                self.record_history(device_name, checkpoint, change);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a Brothers AM".to_string())),
+            _ => Err(MidiError::Other("Device is not a Mood MkII".to_string())),
         }
     }
 
-    /// Save current state to a Brothers AM preset slot (1-122) using CC 111
-    pub fn save_brothers_am_preset(
+    /// Save current state to a Mood MkII preset slot (1-122)
+    pub fn save_mood_mkii_preset(
         &mut self,
         device_name: &str,
         slot: u8,
@@ -1726,139 +3495,106 @@ impl MidiManager {
         let device = self.connections.get_mut(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
         match device {
-            DeviceConnection::BrothersAm { connection, .. } => {
-                connection.send_cc(BROTHERS_AM_CC_PRESET_SAVE, slot)?;
-                println!("[Brothers AM] Saved current state to preset slot {}", slot);
+            DeviceConnection::MoodMkii { connection, .. } => {
+                connection.send_cc(MOOD_CC_PRESET_SAVE, slot)?;
+                println!("[Mood MkII] Saved current state to preset slot {}", slot);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a Brothers AM".to_string())),
+            _ => Err(MidiError::Other("Device is not a Mood MkII".to_string())),
         }
     }
 
-    /// Send a program change to a Brothers AM (navigate to preset slot 1-122)
-    pub fn send_brothers_am_program_change(
+    /// Send a program change to a Mood MkII (navigate to preset slot 1-122)
+    pub fn send_mood_mkii_program_change(
         &mut self,
         device_name: &str,
         program: u8,
     ) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::ProgramChange { program };
         let device = self.connections.get_mut(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
         match device {
-            DeviceConnection::BrothersAm { connection, .. } => {
+            DeviceConnection::MoodMkii { connection, .. } => {
                 connection.send_program_change(program)?;
-                println!("[Brothers AM] Sent PC {} (navigated to preset slot {})", program, program);
+                println!("[Mood MkII] Sent PC {} (navigated to preset slot {})", program, program);
+                self.record_history(device_name, checkpoint, change);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a Brothers AM".to_string())),
-        }
-    }
-
-    /// Get the current state of a Brothers AM
-    pub fn get_brothers_am_state(&self, device_name: &str) -> MidiResult<BrothersAmState> {
-        let device = self.connections.get(device_name)
-            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
-        match device {
-            DeviceConnection::BrothersAm { state, .. } => Ok(state.state.clone()),
-            _ => Err(MidiError::Other("Device is not a Brothers AM".to_string())),
+            _ => Err(MidiError::Other("Device is not a Mood MkII".to_string())),
         }
     }
 
     // ========================================================================
-    // Chase Bliss Audio Reverse Mode C Methods
+    // Chase Bliss Audio Billy Strings Wombtone Methods
     // ========================================================================
 
-    /// Connect to a Chase Bliss Reverse Mode C pedal
-    pub fn connect_reverse_mode_c(
+    /// Connect to a Chase Bliss Audio Billy Strings Wombtone
+    pub fn connect_billy_strings_wombtone(
         &mut self,
         device_name: &str,
         midi_channel: u8,
     ) -> MidiResult<()> {
-        if midi_channel < 1 || midi_channel > 16 {
-            return Err(MidiError::InvalidChannel(midi_channel));
-        }
-        if self.connections.contains_key(device_name) {
-            return Err(MidiError::AlreadyConnected(device_name.to_string()));
-        }
-        let midi_out = self.midi_output.take()
-            .ok_or_else(|| MidiError::Other("MIDI output not initialized".to_string()))?;
-        let port_opt = {
-            let ports = midi_out.ports();
-            ports.into_iter().find(|p| {
-                midi_out.port_name(p)
-                    .map(|name| name.to_lowercase().contains(&device_name.to_lowercase()))
-                    .unwrap_or(false)
-            })
-        };
-        let port = port_opt.ok_or_else(|| MidiError::DeviceNotFound(device_name.to_string()))?;
-        let output = midi_out.connect(&port, "Librarian")
-            .map_err(|e| MidiError::ConnectionFailed(e.to_string()))?;
-        let input = self.setup_midi_input(device_name, PedalType::ReverseModeC, midi_channel)?;
-        let connection = MidiConnection { output, input, midi_channel };
-        let state = ReverseModeC::new(midi_channel);
-        self.connections.insert(
-            device_name.to_string(),
-            DeviceConnection::ReverseModeC { connection, state },
-        );
-        println!("✅ Connected to Reverse Mode C: '{}' on MIDI Channel {}", device_name, midi_channel);
-        self.midi_output = Some(MidiOutput::new("Librarian Output")
-            .map_err(|e| MidiError::Other(e.to_string()))?);
-        Ok(())
+        self.connect_on_port(PedalType::BillyStringsWombtone, device_name, device_name, midi_channel)
     }
 
-    /// Send a parameter change to a Reverse Mode C
-    pub fn send_reverse_mode_c_parameter(
+    /// Send a parameter change to a Billy Strings Wombtone
+    pub fn send_billy_strings_wombtone_parameter(
         &mut self,
         device_name: &str,
-        param: ReverseModeCParameter,
+        param: BillyStringsWombtoneParameter,
     ) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::parameter(param.name(), param.cc_number(), param.cc_value());
         let device = self.connections.get_mut(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
         match device {
-            DeviceConnection::ReverseModeC { connection, state } => {
+            DeviceConnection::BillyStringsWombtone { connection, state } => {
                 let cc_number = param.cc_number();
                 let cc_value = param.cc_value();
                 #[cfg(debug_assertions)]
-                println!("[Reverse Mode C] Sending CC#{} = {} (ch {})", cc_number, cc_value, connection.midi_channel);
+                println!("[Billy Strings Wombtone] Sending CC#{} = {} (ch {})", cc_number, cc_value, connection.midi_channel());
                 connection.send_cc(cc_number, cc_value)?;
                 state.update_state(&param);
+                self.record_history(device_name, checkpoint, change);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a Reverse Mode C".to_string())),
+            _ => Err(MidiError::Other("Device is not a Billy Strings Wombtone".to_string())),
         }
     }
 
-    /// Recall a preset on a Reverse Mode C (send all parameters)
-    pub fn recall_reverse_mode_c_preset(
+    /// Get the current state of a Billy Strings Wombtone
+    pub fn get_billy_strings_wombtone_state(&self, device_name: &str) -> MidiResult<BillyStringsWombtoneState> {
+        self.get_device_state(device_name)?.parse(&PedalType::BillyStringsWombtone)
+    }
+
+    /// Recall a preset on a Billy Strings Wombtone (send all parameters)
+    pub fn recall_billy_strings_wombtone_preset(
         &mut self,
         device_name: &str,
-        state: &ReverseModeCState,
+        state: &BillyStringsWombtoneState,
     ) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::Recall;
         let device = self.connections.get_mut(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
         match device {
-            DeviceConnection::ReverseModeC { connection, state: device_state } => {
-                let temp = ReverseModeC {
-                    state: state.clone(),
-                    midi_channel: connection.midi_channel,
-                };
-                let cc_map = temp.state_as_cc_map();
-                println!("[Reverse Mode C] Recalling preset: sending {} CC messages", cc_map.len());
-                let mut cc_pairs: Vec<(u8, u8)> = cc_map.into_iter().collect();
-                cc_pairs.sort_by_key(|(cc, _)| *cc);
-                for (cc_number, value) in cc_pairs {
-                    connection.send_cc(cc_number, value)?;
-                    tokio::task::block_in_place(|| thread::sleep(Duration::from_millis(20)));
-                }
-                println!("[Reverse Mode C] Preset recall complete");
-                device_state.state = state.clone();
+            DeviceConnection::BillyStringsWombtone { connection, state: device_state } => {
+                let temp = BillyStringsWombtone { state: state.clone(), midi_channel: connection.midi_channel() };
+                let plan = temp.recall_plan();
+                println!("[Billy Strings Wombtone] Recalling preset: sending {} CC messages", plan.len());
+                connection.send_plan(&plan)?;
+                println!("[Billy Strings Wombtone] Preset recall complete");
+                *device_state = temp;
+                self.record_history(device_name, checkpoint, change);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a Reverse Mode C".to_string())),
+            _ => Err(MidiError::Other("Device is not a Billy Strings Wombtone".to_string())),
         }
     }
 
-    /// Save current state to a Reverse Mode C preset slot (1-122) using CC 111
-    pub fn save_reverse_mode_c_preset(
+    /// Save current state to a Billy Strings Wombtone preset slot (1-122)
+    pub fn save_billy_strings_wombtone_preset(
         &mut self,
         device_name: &str,
         slot: u8,
@@ -1869,305 +3605,2368 @@ impl MidiManager {
         let device = self.connections.get_mut(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
         match device {
-            DeviceConnection::ReverseModeC { connection, .. } => {
-                connection.send_cc(REVERSE_MODE_C_CC_PRESET_SAVE, slot)?;
-                println!("[Reverse Mode C] Saved current state to preset slot {}", slot);
+            DeviceConnection::BillyStringsWombtone { connection, .. } => {
+                connection.send_cc(BSW_CC_PRESET_SAVE, slot)?;
+                println!("[Billy Strings Wombtone] Saved current state to preset slot {}", slot);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a Reverse Mode C".to_string())),
+            _ => Err(MidiError::Other("Device is not a Billy Strings Wombtone".to_string())),
         }
     }
 
-    /// Send a program change to a Reverse Mode C (navigate to preset slot 1-122)
-    pub fn send_reverse_mode_c_program_change(
+    /// Send a program change to a Billy Strings Wombtone (navigate to preset slot 1-122)
+    pub fn send_billy_strings_wombtone_program_change(
         &mut self,
         device_name: &str,
         program: u8,
     ) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::ProgramChange { program };
         let device = self.connections.get_mut(device_name)
             .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
         match device {
-            DeviceConnection::ReverseModeC { connection, .. } => {
+            DeviceConnection::BillyStringsWombtone { connection, .. } => {
                 connection.send_program_change(program)?;
-                println!("[Reverse Mode C] Sent PC {} (navigated to preset slot {})", program, program);
+                println!("[Billy Strings Wombtone] Sent PC {} (navigated to preset slot {})", program, program);
+                self.record_history(device_name, checkpoint, change);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a Reverse Mode C".to_string())),
-        }
-    }
-
-    /// Get the current state of a Reverse Mode C
-    pub fn get_reverse_mode_c_state(&self, device_name: &str) -> MidiResult<ReverseModeCState> {
-        let device = self.connections.get(device_name)
-            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
-        match device {
-            DeviceConnection::ReverseModeC { state, .. } => Ok(state.state.clone()),
-            _ => Err(MidiError::Other("Device is not a Reverse Mode C".to_string())),
+            _ => Err(MidiError::Other("Device is not a Billy Strings Wombtone".to_string())),
         }
     }
 
     // ========================================================================
-    // Chase Bliss Audio Clean Methods
+    // Chase Bliss Audio Lossy Methods
     // ========================================================================
 
-    /// Connect to a Chase Bliss Audio Clean
-    pub fn connect_clean(&mut self, device_name: &str, midi_channel: u8) -> MidiResult<()> {
-        if midi_channel < 1 || midi_channel > 16 {
-            return Err(MidiError::InvalidChannel(midi_channel));
-        }
-        if self.connections.contains_key(device_name) {
-            return Err(MidiError::AlreadyConnected(device_name.to_string()));
-        }
-        let midi_out = self.midi_output.take()
-            .ok_or_else(|| MidiError::Other("MIDI output not initialized".to_string()))?;
-        let port_opt = {
-            let ports = midi_out.ports();
-            ports.into_iter().find(|p| midi_out.port_name(p).map(|name| name.to_lowercase().contains(&device_name.to_lowercase())).unwrap_or(false))
-        };
-        let port = port_opt.ok_or_else(|| MidiError::DeviceNotFound(device_name.to_string()))?;
-        let output = midi_out.connect(&port, "Librarian").map_err(|e| MidiError::ConnectionFailed(e.to_string()))?;
-        let input = self.setup_midi_input(device_name, PedalType::Clean, midi_channel)?;
-        let connection = MidiConnection { output, input, midi_channel };
-        let state = Clean::new(midi_channel);
-        self.connections.insert(device_name.to_string(), DeviceConnection::Clean { connection, state });
-        println!("✅ Connected to Clean: '{}' on MIDI Channel {}", device_name, midi_channel);
-        self.midi_output = Some(MidiOutput::new("Librarian Output").map_err(|e| MidiError::Other(e.to_string()))?);
-        Ok(())
+    /// Connect to a Chase Bliss Audio Lossy
+    pub fn connect_lossy(
+        &mut self,
+        device_name: &str,
+        midi_channel: u8,
+    ) -> MidiResult<()> {
+        self.connect_on_port(PedalType::Lossy, device_name, device_name, midi_channel)
     }
 
-    /// Send a parameter change to a Clean
-    pub fn send_clean_parameter(&mut self, device_name: &str, param: CleanParameter) -> MidiResult<()> {
-        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+    /// Send a parameter change to a Lossy
+    pub fn send_lossy_parameter(
+        &mut self,
+        device_name: &str,
+        param: LossyParameter,
+    ) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::parameter(param.name(), param.cc_number(), param.cc_value());
+        let device = self.connections.get_mut(device_name)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
         match device {
-            DeviceConnection::Clean { connection, state } => {
-                connection.send_cc(param.cc_number(), param.cc_value())?;
+            DeviceConnection::Lossy { connection, state } => {
+                let cc_number = param.cc_number();
+                let cc_value = param.cc_value();
+                #[cfg(debug_assertions)]
+                println!("[Lossy] Sending CC#{} = {} (ch {})", cc_number, cc_value, connection.midi_channel());
+                connection.send_cc(cc_number, cc_value)?;
                 state.update_state(&param);
+                self.record_history(device_name, checkpoint, change);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a Clean".to_string())),
+            _ => Err(MidiError::Other("Device is not a Lossy".to_string())),
         }
     }
 
-    /// Get current state of a Clean
-    pub fn get_clean_state(&self, device_name: &str) -> MidiResult<CleanState> {
-        let device = self.connections.get(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
-        match device {
-            DeviceConnection::Clean { state, .. } => Ok(state.state.clone()),
-            _ => Err(MidiError::Other("Device is not a Clean".to_string())),
-        }
+    /// Get the current state of a Lossy
+    pub fn get_lossy_state(&self, device_name: &str) -> MidiResult<LossyState> {
+        self.get_device_state(device_name)?.parse(&PedalType::Lossy)
     }
 
-    /// Recall a preset on a Clean (send all parameters)
-    pub fn recall_clean_preset(&mut self, device_name: &str, state: &CleanState) -> MidiResult<()> {
-        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+    /// Recall a preset on a Lossy (send all parameters)
+    pub fn recall_lossy_preset(
+        &mut self,
+        device_name: &str,
+        state: &LossyState,
+    ) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::Recall;
+        let device = self.connections.get_mut(device_name)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
         match device {
-            DeviceConnection::Clean { connection, state: device_state } => {
-                let temp = Clean { state: state.clone(), midi_channel: connection.midi_channel };
-                let cc_map = temp.state_as_cc_map();
-                for (cc_number, value) in cc_map.iter() {
-                    connection.send_cc(*cc_number, *value)?;
-                    tokio::task::block_in_place(|| thread::sleep(Duration::from_millis(20)));
-                }
+            DeviceConnection::Lossy { connection, state: device_state } => {
+                let temp = Lossy { state: state.clone(), midi_channel: connection.midi_channel() };
+                let plan = temp.recall_plan();
+                println!("[Lossy] Recalling preset: sending {} CC messages", plan.len());
+                connection.send_plan(&plan)?;
+                println!("[Lossy] Preset recall complete");
                 *device_state = temp;
+                self.record_history(device_name, checkpoint, change);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a Clean".to_string())),
+            _ => Err(MidiError::Other("Device is not a Lossy".to_string())),
         }
     }
 
-    /// Save current state to a Clean preset slot (1-122)
-    pub fn save_clean_preset(&mut self, device_name: &str, slot: u8) -> MidiResult<()> {
+    /// Save current state to a Lossy preset slot (1-122)
+    pub fn save_lossy_preset(
+        &mut self,
+        device_name: &str,
+        slot: u8,
+    ) -> MidiResult<()> {
         if slot < 1 || slot > 122 {
             return Err(MidiError::Other(format!("Invalid preset slot: {}. Must be 1-122", slot)));
         }
-        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        let device = self.connections.get_mut(device_name)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
         match device {
-            DeviceConnection::Clean { connection, .. } => {
-                connection.send_cc(CLEAN_CC_PRESET_SAVE, slot)?;
-                println!("[Clean] Saved current state to preset slot {}", slot);
+            DeviceConnection::Lossy { connection, .. } => {
+                connection.send_cc(LOSSY_CC_PRESET_SAVE, slot)?;
+                println!("[Lossy] Saved current state to preset slot {}", slot);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a Clean".to_string())),
+            _ => Err(MidiError::Other("Device is not a Lossy".to_string())),
         }
     }
 
-    /// Send a program change to a Clean (navigate to preset slot 1-122)
-    pub fn send_clean_program_change(&mut self, device_name: &str, program: u8) -> MidiResult<()> {
-        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+    /// Send a program change to a Lossy (navigate to preset slot 1-122)
+    pub fn send_lossy_program_change(
+        &mut self,
+        device_name: &str,
+        program: u8,
+    ) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::ProgramChange { program };
+        let device = self.connections.get_mut(device_name)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
         match device {
-            DeviceConnection::Clean { connection, .. } => {
+            DeviceConnection::Lossy { connection, .. } => {
                 connection.send_program_change(program)?;
-                println!("[Clean] Sent PC {} (navigated to preset slot {})", program, program);
+                println!("[Lossy] Sent PC {} (navigated to preset slot {})", program, program);
+                self.record_history(device_name, checkpoint, change);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not a Clean".to_string())),
+            _ => Err(MidiError::Other("Device is not a Lossy".to_string())),
         }
     }
 
     // ========================================================================
-    // Chase Bliss Audio Onward Methods
+    // Chase Bliss Audio Brothers AM Methods
     // ========================================================================
 
-    /// Connect to a Chase Bliss Audio Onward
-    pub fn connect_onward(&mut self, device_name: &str, midi_channel: u8) -> MidiResult<()> {
-        if midi_channel < 1 || midi_channel > 16 {
-            return Err(MidiError::InvalidChannel(midi_channel));
-        }
-        if self.connections.contains_key(device_name) {
-            return Err(MidiError::AlreadyConnected(device_name.to_string()));
-        }
-        let midi_out = self.midi_output.take()
-            .ok_or_else(|| MidiError::Other("MIDI output not initialized".to_string()))?;
-        let port_opt = {
-            let ports = midi_out.ports();
-            ports.into_iter().find(|p| midi_out.port_name(p).map(|name| name.to_lowercase().contains(&device_name.to_lowercase())).unwrap_or(false))
-        };
-        let port = port_opt.ok_or_else(|| MidiError::DeviceNotFound(device_name.to_string()))?;
-        let output = midi_out.connect(&port, "Librarian").map_err(|e| MidiError::ConnectionFailed(e.to_string()))?;
-        let input = self.setup_midi_input(device_name, PedalType::Onward, midi_channel)?;
-        let connection = MidiConnection { output, input, midi_channel };
-        let state = Onward::new(midi_channel);
-        self.connections.insert(device_name.to_string(), DeviceConnection::Onward { connection, state });
-        println!("✅ Connected to Onward: '{}' on MIDI Channel {}", device_name, midi_channel);
-        self.midi_output = Some(MidiOutput::new("Librarian Output").map_err(|e| MidiError::Other(e.to_string()))?);
-        Ok(())
+    /// Connect to a Chase Bliss Brothers AM pedal
+    pub fn connect_brothers_am(
+        &mut self,
+        device_name: &str,
+        midi_channel: u8,
+    ) -> MidiResult<()> {
+        self.connect_on_port(PedalType::BrothersAm, device_name, device_name, midi_channel)
     }
 
-    /// Send a parameter change to an Onward
-    pub fn send_onward_parameter(&mut self, device_name: &str, param: OnwardParameter) -> MidiResult<()> {
-        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+    /// Send a parameter change to a Brothers AM
+    pub fn send_brothers_am_parameter(
+        &mut self,
+        device_name: &str,
+        param: BrothersAmParameter,
+    ) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::parameter(param.name(), param.cc_number(), param.cc_value());
+        let device = self.connections.get_mut(device_name)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
         match device {
-            DeviceConnection::Onward { connection, state } => {
-                connection.send_cc(param.cc_number(), param.cc_value())?;
+            DeviceConnection::BrothersAm { connection, state } => {
+                let cc_number = param.cc_number();
+                let cc_value = param.cc_value();
+                #[cfg(debug_assertions)]
+                println!("[Brothers AM] Sending CC#{} = {} (ch {})", cc_number, cc_value, connection.midi_channel());
+                connection.send_cc(cc_number, cc_value)?;
                 state.update_state(&param);
+                self.record_history(device_name, checkpoint, change);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not an Onward".to_string())),
-        }
-    }
-
-    /// Get current state of an Onward
-    pub fn get_onward_state(&self, device_name: &str) -> MidiResult<OnwardState> {
-        let device = self.connections.get(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
-        match device {
-            DeviceConnection::Onward { state, .. } => Ok(state.state.clone()),
-            _ => Err(MidiError::Other("Device is not an Onward".to_string())),
+            _ => Err(MidiError::Other("Device is not a Brothers AM".to_string())),
         }
     }
 
-    /// Recall a preset on an Onward (send all parameters)
-    pub fn recall_onward_preset(&mut self, device_name: &str, state: &OnwardState) -> MidiResult<()> {
-        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+    /// Recall a preset on a Brothers AM (send all parameters)
+    pub fn recall_brothers_am_preset(
+        &mut self,
+        device_name: &str,
+        state: &BrothersAmState,
+    ) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::Recall;
+        let device = self.connections.get_mut(device_name)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
         match device {
-            DeviceConnection::Onward { connection, state: device_state } => {
-                let temp = Onward { state: state.clone(), midi_channel: connection.midi_channel };
-                let cc_map = temp.state_as_cc_map();
-                for (cc_number, value) in cc_map.iter() {
-                    connection.send_cc(*cc_number, *value)?;
-                    tokio::task::block_in_place(|| thread::sleep(Duration::from_millis(20)));
-                }
+            DeviceConnection::BrothersAm { connection, state: device_state } => {
+                let temp = BrothersAm {
+                    state: state.clone(),
+                    midi_channel: connection.midi_channel(),
+                };
+                let plan = temp.recall_plan();
+                println!("[Brothers AM] Recalling preset: sending {} CC messages", plan.len());
+                connection.send_plan(&plan)?;
+                println!("[Brothers AM] Preset recall complete");
                 *device_state = temp;
+                self.record_history(device_name, checkpoint, change);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not an Onward".to_string())),
+            _ => Err(MidiError::Other("Device is not a Brothers AM".to_string())),
         }
     }
 
-    /// Save current state to an Onward preset slot (1-122)
-    pub fn save_onward_preset(&mut self, device_name: &str, slot: u8) -> MidiResult<()> {
+    /// Save current state to a Brothers AM preset slot (1-122) using CC 111
+    pub fn save_brothers_am_preset(
+        &mut self,
+        device_name: &str,
+        slot: u8,
+    ) -> MidiResult<()> {
         if slot < 1 || slot > 122 {
             return Err(MidiError::Other(format!("Invalid preset slot: {}. Must be 1-122", slot)));
         }
-        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        let device = self.connections.get_mut(device_name)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
         match device {
-            DeviceConnection::Onward { connection, .. } => {
-                connection.send_cc(ONWARD_CC_PRESET_SAVE, slot)?;
-                println!("[Onward] Saved current state to preset slot {}", slot);
+            DeviceConnection::BrothersAm { connection, .. } => {
+                connection.send_cc(BROTHERS_AM_CC_PRESET_SAVE, slot)?;
+                println!("[Brothers AM] Saved current state to preset slot {}", slot);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not an Onward".to_string())),
+            _ => Err(MidiError::Other("Device is not a Brothers AM".to_string())),
         }
     }
 
-    /// Send a program change to an Onward (navigate to preset slot 1-122)
-    pub fn send_onward_program_change(&mut self, device_name: &str, program: u8) -> MidiResult<()> {
-        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+    /// Send a program change to a Brothers AM (navigate to preset slot 1-122)
+    pub fn send_brothers_am_program_change(
+        &mut self,
+        device_name: &str,
+        program: u8,
+    ) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::ProgramChange { program };
+        let device = self.connections.get_mut(device_name)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
         match device {
-            DeviceConnection::Onward { connection, .. } => {
+            DeviceConnection::BrothersAm { connection, .. } => {
                 connection.send_program_change(program)?;
-                println!("[Onward] Sent PC {} (navigated to preset slot {})", program, program);
+                println!("[Brothers AM] Sent PC {} (navigated to preset slot {})", program, program);
+                self.record_history(device_name, checkpoint, change);
                 Ok(())
             }
-            _ => Err(MidiError::Other("Device is not an Onward".to_string())),
+            _ => Err(MidiError::Other("Device is not a Brothers AM".to_string())),
         }
     }
 
-    /// List all connected devices
-    pub fn connected_devices(&self) -> Vec<ConnectedDevice> {
-        self.connections.iter().map(|(name, device)| {
-            let (pedal_type, midi_channel) = match device {
-                DeviceConnection::Microcosm { connection, .. } => {
-                    (PedalType::Microcosm, connection.midi_channel)
-                }
-                DeviceConnection::GenLossMkii { connection, .. } => {
-                    (PedalType::GenLossMkii, connection.midi_channel)
-                }
-                DeviceConnection::ChromaConsole { connection, .. } => {
-                    (PedalType::ChromaConsole, connection.midi_channel)
-                }
-                DeviceConnection::PreampMk2 { connection, .. } => {
-                    (PedalType::PreampMk2, connection.midi_channel)
-                }
-                DeviceConnection::Cxm1978 { connection, .. } => {
-                    (PedalType::Cxm1978, connection.midi_channel)
-                }
-                DeviceConnection::MoodMkii { connection, .. } => {
-                    (PedalType::MoodMkii, connection.midi_channel)
-                }
-                DeviceConnection::BillyStringsWombtone { connection, .. } => {
-                    (PedalType::BillyStringsWombtone, connection.midi_channel)
-                }
-                DeviceConnection::Lossy { connection, .. } => {
-                    (PedalType::Lossy, connection.midi_channel)
-                }
-                DeviceConnection::BrothersAm { connection, .. } => {
-                    (PedalType::BrothersAm, connection.midi_channel)
-                }
-                DeviceConnection::ReverseModeC { connection, .. } => {
-                    (PedalType::ReverseModeC, connection.midi_channel)
-                }
-                DeviceConnection::Clean { connection, .. } => {
-                    (PedalType::Clean, connection.midi_channel)
-                }
-                DeviceConnection::Onward { connection, .. } => {
-                    (PedalType::Onward, connection.midi_channel)
-                }
-            };
-            
-            ConnectedDevice {
-                device_name: name.clone(),
-                pedal_type,
-                midi_channel,
-            }
-        }).collect()
-    }
-    
-    /// Check if a device is connected
-    pub fn is_connected(&self, device_name: &str) -> bool {
-        self.connections.contains_key(device_name)
+    /// Get the current state of a Brothers AM
+    pub fn get_brothers_am_state(&self, device_name: &str) -> MidiResult<BrothersAmState> {
+        self.get_device_state(device_name)?.parse(&PedalType::BrothersAm)
     }
-}
 
-impl Default for MidiManager {
-    fn default() -> Self {
-        Self::new().expect("Failed to create MIDI Manager")
-    }
-}
+    // ========================================================================
+    // Chase Bliss Audio Reverse Mode C Methods
+    // ========================================================================
 
-// Thread-safe wrapper for use with Tauri State
-pub type SharedMidiManager = Arc<Mutex<MidiManager>>;
+    /// Connect to a Chase Bliss Reverse Mode C pedal
+    pub fn connect_reverse_mode_c(
+        &mut self,
+        device_name: &str,
+        midi_channel: u8,
+    ) -> MidiResult<()> {
+        self.connect_on_port(PedalType::ReverseModeC, device_name, device_name, midi_channel)
+    }
 
-/// Create a new shared MIDI Manager for use with Tauri
-pub fn create_shared_manager() -> MidiResult<SharedMidiManager> {
-    Ok(Arc::new(Mutex::new(MidiManager::new()?)))
+    /// Send a parameter change to a Reverse Mode C
+    pub fn send_reverse_mode_c_parameter(
+        &mut self,
+        device_name: &str,
+        param: ReverseModeCParameter,
+    ) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::parameter(param.name(), param.cc_number(), param.cc_value());
+        let device = self.connections.get_mut(device_name)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        match device {
+            DeviceConnection::ReverseModeC { connection, state } => {
+                let cc_number = param.cc_number();
+                let cc_value = param.cc_value();
+                #[cfg(debug_assertions)]
+                println!("[Reverse Mode C] Sending CC#{} = {} (ch {})", cc_number, cc_value, connection.midi_channel());
+                connection.send_cc(cc_number, cc_value)?;
+                state.update_state(&param);
+                self.record_history(device_name, checkpoint, change);
+                Ok(())
+            }
+            _ => Err(MidiError::Other("Device is not a Reverse Mode C".to_string())),
+        }
+    }
+
+    /// Recall a preset on a Reverse Mode C (send all parameters)
+    pub fn recall_reverse_mode_c_preset(
+        &mut self,
+        device_name: &str,
+        state: &ReverseModeCState,
+    ) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::Recall;
+        let device = self.connections.get_mut(device_name)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        match device {
+            DeviceConnection::ReverseModeC { connection, state: device_state } => {
+                let temp = ReverseModeC {
+                    state: state.clone(),
+                    midi_channel: connection.midi_channel(),
+                };
+                let plan = temp.recall_plan();
+                println!("[Reverse Mode C] Recalling preset: sending {} CC messages", plan.len());
+                connection.send_plan(&plan)?;
+                println!("[Reverse Mode C] Preset recall complete");
+                device_state.state = state.clone();
+                self.record_history(device_name, checkpoint, change);
+                Ok(())
+            }
+            _ => Err(MidiError::Other("Device is not a Reverse Mode C".to_string())),
+        }
+    }
+
+    /// Save current state to a Reverse Mode C preset slot (1-122) using CC 111
+    pub fn save_reverse_mode_c_preset(
+        &mut self,
+        device_name: &str,
+        slot: u8,
+    ) -> MidiResult<()> {
+        if slot < 1 || slot > 122 {
+            return Err(MidiError::Other(format!("Invalid preset slot: {}. Must be 1-122", slot)));
+        }
+        let device = self.connections.get_mut(device_name)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        match device {
+            DeviceConnection::ReverseModeC { connection, .. } => {
+                connection.send_cc(REVERSE_MODE_C_CC_PRESET_SAVE, slot)?;
+                println!("[Reverse Mode C] Saved current state to preset slot {}", slot);
+                Ok(())
+            }
+            _ => Err(MidiError::Other("Device is not a Reverse Mode C".to_string())),
+        }
+    }
+
+    /// Send a program change to a Reverse Mode C (navigate to preset slot 1-122)
+    pub fn send_reverse_mode_c_program_change(
+        &mut self,
+        device_name: &str,
+        program: u8,
+    ) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::ProgramChange { program };
+        let device = self.connections.get_mut(device_name)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        match device {
+            DeviceConnection::ReverseModeC { connection, .. } => {
+                connection.send_program_change(program)?;
+                println!("[Reverse Mode C] Sent PC {} (navigated to preset slot {})", program, program);
+                self.record_history(device_name, checkpoint, change);
+                Ok(())
+            }
+            _ => Err(MidiError::Other("Device is not a Reverse Mode C".to_string())),
+        }
+    }
+
+    /// Get the current state of a Reverse Mode C
+    pub fn get_reverse_mode_c_state(&self, device_name: &str) -> MidiResult<ReverseModeCState> {
+        self.get_device_state(device_name)?.parse(&PedalType::ReverseModeC)
+    }
+
+    // ========================================================================
+    // Chase Bliss Audio Clean Methods
+    // ========================================================================
+
+    /// Connect to a Chase Bliss Audio Clean
+    pub fn connect_clean(&mut self, device_name: &str, midi_channel: u8) -> MidiResult<()> {
+        self.connect_on_port(PedalType::Clean, device_name, device_name, midi_channel)
+    }
+
+    /// Send a parameter change to a Clean
+    pub fn send_clean_parameter(&mut self, device_name: &str, param: CleanParameter) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::parameter(param.name(), param.cc_number(), param.cc_value());
+        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        match device {
+            DeviceConnection::Clean { connection, state } => {
+                connection.send_cc(param.cc_number(), param.cc_value())?;
+                state.update_state(&param);
+                self.record_history(device_name, checkpoint, change);
+                Ok(())
+            }
+            _ => Err(MidiError::Other("Device is not a Clean".to_string())),
+        }
+    }
+
+    /// Get current state of a Clean
+    pub fn get_clean_state(&self, device_name: &str) -> MidiResult<CleanState> {
+        self.get_device_state(device_name)?.parse(&PedalType::Clean)
+    }
+
+    /// Recall a preset on a Clean (send all parameters)
+    pub fn recall_clean_preset(&mut self, device_name: &str, state: &CleanState) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::Recall;
+        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        match device {
+            DeviceConnection::Clean { connection, state: device_state } => {
+                let temp = Clean { state: state.clone(), midi_channel: connection.midi_channel() };
+                let plan = temp.recall_plan();
+                connection.send_plan(&plan)?;
+                *device_state = temp;
+                self.record_history(device_name, checkpoint, change);
+                Ok(())
+            }
+            _ => Err(MidiError::Other("Device is not a Clean".to_string())),
+        }
+    }
+
+    /// Save current state to a Clean preset slot (1-122)
+    pub fn save_clean_preset(&mut self, device_name: &str, slot: u8) -> MidiResult<()> {
+        if slot < 1 || slot > 122 {
+            return Err(MidiError::Other(format!("Invalid preset slot: {}. Must be 1-122", slot)));
+        }
+        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        match device {
+            DeviceConnection::Clean { connection, .. } => {
+                connection.send_cc(CLEAN_CC_PRESET_SAVE, slot)?;
+                println!("[Clean] Saved current state to preset slot {}", slot);
+                Ok(())
+            }
+            _ => Err(MidiError::Other("Device is not a Clean".to_string())),
+        }
+    }
+
+    /// Send a program change to a Clean (navigate to preset slot 1-122)
+    pub fn send_clean_program_change(&mut self, device_name: &str, program: u8) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::ProgramChange { program };
+        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        match device {
+            DeviceConnection::Clean { connection, .. } => {
+                connection.send_program_change(program)?;
+                println!("[Clean] Sent PC {} (navigated to preset slot {})", program, program);
+                self.record_history(device_name, checkpoint, change);
+                Ok(())
+            }
+            _ => Err(MidiError::Other("Device is not a Clean".to_string())),
+        }
+    }
+
+    // ========================================================================
+    // Chase Bliss Audio Onward Methods
+    // ========================================================================
+
+    /// Connect to a Chase Bliss Audio Onward
+    pub fn connect_onward(&mut self, device_name: &str, midi_channel: u8) -> MidiResult<()> {
+        self.connect_on_port(PedalType::Onward, device_name, device_name, midi_channel)
+    }
+
+    /// Send a parameter change to an Onward
+    pub fn send_onward_parameter(&mut self, device_name: &str, param: OnwardParameter) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::parameter(param.name(), param.cc_number(), param.cc_value());
+        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        match device {
+            DeviceConnection::Onward { connection, state } => {
+                connection.send_cc(param.cc_number(), param.cc_value())?;
+                state.update_state(&param);
+                self.record_history(device_name, checkpoint, change);
+                Ok(())
+            }
+            _ => Err(MidiError::Other("Device is not an Onward".to_string())),
+        }
+    }
+
+    /// Get current state of an Onward
+    pub fn get_onward_state(&self, device_name: &str) -> MidiResult<OnwardState> {
+        self.get_device_state(device_name)?.parse(&PedalType::Onward)
+    }
+
+    /// Recall a preset on an Onward (send all parameters)
+    pub fn recall_onward_preset(&mut self, device_name: &str, state: &OnwardState) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::Recall;
+        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        match device {
+            DeviceConnection::Onward { connection, state: device_state } => {
+                let temp = Onward { state: state.clone(), midi_channel: connection.midi_channel() };
+                let plan = temp.recall_plan();
+                connection.send_plan(&plan)?;
+                *device_state = temp;
+                self.record_history(device_name, checkpoint, change);
+                Ok(())
+            }
+            _ => Err(MidiError::Other("Device is not an Onward".to_string())),
+        }
+    }
+
+    /// Save current state to an Onward preset slot (1-122)
+    pub fn save_onward_preset(&mut self, device_name: &str, slot: u8) -> MidiResult<()> {
+        if slot < 1 || slot > 122 {
+            return Err(MidiError::Other(format!("Invalid preset slot: {}. Must be 1-122", slot)));
+        }
+        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        match device {
+            DeviceConnection::Onward { connection, .. } => {
+                connection.send_cc(ONWARD_CC_PRESET_SAVE, slot)?;
+                println!("[Onward] Saved current state to preset slot {}", slot);
+                Ok(())
+            }
+            _ => Err(MidiError::Other("Device is not an Onward".to_string())),
+        }
+    }
+
+    /// Send a program change to an Onward (navigate to preset slot 1-122)
+    pub fn send_onward_program_change(&mut self, device_name: &str, program: u8) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::ProgramChange { program };
+        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        match device {
+            DeviceConnection::Onward { connection, .. } => {
+                connection.send_program_change(program)?;
+                println!("[Onward] Sent PC {} (navigated to preset slot {})", program, program);
+                self.record_history(device_name, checkpoint, change);
+                Ok(())
+            }
+            _ => Err(MidiError::Other("Device is not an Onward".to_string())),
+        }
+    }
+
+    // ========================================================================
+    // Chase Bliss Audio Thermae Methods
+    // ========================================================================
+
+    /// Connect to a Chase Bliss Audio Thermae
+    pub fn connect_thermae(&mut self, device_name: &str, midi_channel: u8) -> MidiResult<()> {
+        self.connect_on_port(PedalType::Thermae, device_name, device_name, midi_channel)
+    }
+
+    /// Send a parameter change to a Thermae
+    pub fn send_thermae_parameter(&mut self, device_name: &str, param: ThermaeParameter) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::parameter(param.name(), param.cc_number(), param.cc_value());
+        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        match device {
+            DeviceConnection::Thermae { connection, state } => {
+                connection.send_cc(param.cc_number(), param.cc_value())?;
+                state.update_state(&param);
+                self.record_history(device_name, checkpoint, change);
+                Ok(())
+            }
+            _ => Err(MidiError::Other("Device is not a Thermae".to_string())),
+        }
+    }
+
+    /// Get current state of a Thermae
+    pub fn get_thermae_state(&self, device_name: &str) -> MidiResult<ThermaeState> {
+        self.get_device_state(device_name)?.parse(&PedalType::Thermae)
+    }
+
+    /// Recall a preset on a Thermae (send all parameters)
+    pub fn recall_thermae_preset(&mut self, device_name: &str, state: &ThermaeState) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::Recall;
+        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        match device {
+            DeviceConnection::Thermae { connection, state: device_state } => {
+                let temp = Thermae { state: state.clone(), midi_channel: connection.midi_channel() };
+                let plan = temp.recall_plan();
+                connection.send_plan(&plan)?;
+                *device_state = temp;
+                self.record_history(device_name, checkpoint, change);
+                Ok(())
+            }
+            _ => Err(MidiError::Other("Device is not a Thermae".to_string())),
+        }
+    }
+
+    /// Save current state to a Thermae preset slot (1-122)
+    pub fn save_thermae_preset(&mut self, device_name: &str, slot: u8) -> MidiResult<()> {
+        if !(1..=122).contains(&slot) {
+            return Err(MidiError::Other(format!("Invalid preset slot: {}. Must be 1-122", slot)));
+        }
+        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        match device {
+            DeviceConnection::Thermae { connection, .. } => {
+                connection.send_cc(THERMAE_CC_PRESET_SAVE, slot)?;
+                println!("[Thermae] Saved current state to preset slot {}", slot);
+                Ok(())
+            }
+            _ => Err(MidiError::Other("Device is not a Thermae".to_string())),
+        }
+    }
+
+    /// Send a program change to a Thermae (navigate to preset slot 1-122)
+    pub fn send_thermae_program_change(&mut self, device_name: &str, program: u8) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::ProgramChange { program };
+        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        match device {
+            DeviceConnection::Thermae { connection, .. } => {
+                connection.send_program_change(program)?;
+                println!("[Thermae] Sent PC {} (navigated to preset slot {})", program, program);
+                self.record_history(device_name, checkpoint, change);
+                Ok(())
+            }
+            _ => Err(MidiError::Other("Device is not a Thermae".to_string())),
+        }
+    }
+
+    // ========================================================================
+    // Chase Bliss Audio Dark World Methods
+    // ========================================================================
+
+    /// Connect to a Chase Bliss Audio Dark World
+    pub fn connect_dark_world(&mut self, device_name: &str, midi_channel: u8) -> MidiResult<()> {
+        self.connect_on_port(PedalType::DarkWorld, device_name, device_name, midi_channel)
+    }
+
+    /// Send a parameter change to a Dark World
+    pub fn send_dark_world_parameter(&mut self, device_name: &str, param: DarkWorldParameter) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::parameter(param.name(), param.cc_number(), param.cc_value());
+        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        match device {
+            DeviceConnection::DarkWorld { connection, state } => {
+                connection.send_cc(param.cc_number(), param.cc_value())?;
+                state.update_state(&param);
+                self.record_history(device_name, checkpoint, change);
+                Ok(())
+            }
+            _ => Err(MidiError::Other("Device is not a Dark World".to_string())),
+        }
+    }
+
+    /// Get current state of a Dark World
+    pub fn get_dark_world_state(&self, device_name: &str) -> MidiResult<DarkWorldState> {
+        self.get_device_state(device_name)?.parse(&PedalType::DarkWorld)
+    }
+
+    /// Recall a preset on a Dark World (send all parameters)
+    pub fn recall_dark_world_preset(&mut self, device_name: &str, state: &DarkWorldState) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::Recall;
+        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        match device {
+            DeviceConnection::DarkWorld { connection, state: device_state } => {
+                let temp = DarkWorld { state: state.clone(), midi_channel: connection.midi_channel() };
+                let plan = temp.recall_plan();
+                connection.send_plan(&plan)?;
+                *device_state = temp;
+                self.record_history(device_name, checkpoint, change);
+                Ok(())
+            }
+            _ => Err(MidiError::Other("Device is not a Dark World".to_string())),
+        }
+    }
+
+    /// Save current state to a Dark World preset slot (1-122)
+    pub fn save_dark_world_preset(&mut self, device_name: &str, slot: u8) -> MidiResult<()> {
+        if !(1..=122).contains(&slot) {
+            return Err(MidiError::Other(format!("Invalid preset slot: {}. Must be 1-122", slot)));
+        }
+        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        match device {
+            DeviceConnection::DarkWorld { connection, .. } => {
+                connection.send_cc(DARK_WORLD_CC_PRESET_SAVE, slot)?;
+                println!("[Dark World] Saved current state to preset slot {}", slot);
+                Ok(())
+            }
+            _ => Err(MidiError::Other("Device is not a Dark World".to_string())),
+        }
+    }
+
+    /// Send a program change to a Dark World (navigate to preset slot 1-122)
+    pub fn send_dark_world_program_change(&mut self, device_name: &str, program: u8) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::ProgramChange { program };
+        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        match device {
+            DeviceConnection::DarkWorld { connection, .. } => {
+                connection.send_program_change(program)?;
+                println!("[Dark World] Sent PC {} (navigated to preset slot {})", program, program);
+                self.record_history(device_name, checkpoint, change);
+                Ok(())
+            }
+            _ => Err(MidiError::Other("Device is not a Dark World".to_string())),
+        }
+    }
+
+    // ========================================================================
+    // Chase Bliss Audio Habit Methods
+    // ========================================================================
+
+    /// Connect to a Chase Bliss Audio Habit
+    pub fn connect_habit(&mut self, device_name: &str, midi_channel: u8) -> MidiResult<()> {
+        self.connect_on_port(PedalType::Habit, device_name, device_name, midi_channel)
+    }
+
+    /// Send a parameter change to a Habit
+    pub fn send_habit_parameter(&mut self, device_name: &str, param: HabitParameter) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::parameter(param.name(), param.cc_number(), param.cc_value());
+        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        match device {
+            DeviceConnection::Habit { connection, state } => {
+                connection.send_cc(param.cc_number(), param.cc_value())?;
+                state.update_state(&param);
+                self.record_history(device_name, checkpoint, change);
+                Ok(())
+            }
+            _ => Err(MidiError::Other("Device is not a Habit".to_string())),
+        }
+    }
+
+    /// Get current state of a Habit
+    pub fn get_habit_state(&self, device_name: &str) -> MidiResult<HabitState> {
+        self.get_device_state(device_name)?.parse(&PedalType::Habit)
+    }
+
+    /// Recall a preset on a Habit (send all parameters)
+    pub fn recall_habit_preset(&mut self, device_name: &str, state: &HabitState) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::Recall;
+        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        match device {
+            DeviceConnection::Habit { connection, state: device_state } => {
+                let temp = Habit { state: state.clone(), midi_channel: connection.midi_channel() };
+                let plan = temp.recall_plan();
+                connection.send_plan(&plan)?;
+                *device_state = temp;
+                self.record_history(device_name, checkpoint, change);
+                Ok(())
+            }
+            _ => Err(MidiError::Other("Device is not a Habit".to_string())),
+        }
+    }
+
+    /// Save current state to a Habit preset slot (1-122)
+    pub fn save_habit_preset(&mut self, device_name: &str, slot: u8) -> MidiResult<()> {
+        if !(1..=122).contains(&slot) {
+            return Err(MidiError::Other(format!("Invalid preset slot: {}. Must be 1-122", slot)));
+        }
+        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        match device {
+            DeviceConnection::Habit { connection, .. } => {
+                connection.send_cc(HABIT_CC_PRESET_SAVE, slot)?;
+                println!("[Habit] Saved current state to preset slot {}", slot);
+                Ok(())
+            }
+            _ => Err(MidiError::Other("Device is not a Habit".to_string())),
+        }
+    }
+
+    /// Send a program change to a Habit (navigate to preset slot 1-122)
+    pub fn send_habit_program_change(&mut self, device_name: &str, program: u8) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::ProgramChange { program };
+        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        match device {
+            DeviceConnection::Habit { connection, .. } => {
+                connection.send_program_change(program)?;
+                println!("[Habit] Sent PC {} (navigated to preset slot {})", program, program);
+                self.record_history(device_name, checkpoint, change);
+                Ok(())
+            }
+            _ => Err(MidiError::Other("Device is not a Habit".to_string())),
+        }
+    }
+
+    // ========================================================================
+    // Source Audio Collider Methods
+    // ========================================================================
+
+    /// Connect to a Source Audio Collider
+    pub fn connect_collider(&mut self, device_name: &str, midi_channel: u8) -> MidiResult<()> {
+        self.connect_on_port(PedalType::Collider, device_name, device_name, midi_channel)
+    }
+
+    /// Send a parameter change to a Collider. Delay times go out as a 14-bit NRPN write;
+    /// their history entry re-sends the whole prior state on undo.
+    pub fn send_collider_parameter(&mut self, device_name: &str, param: ColliderParameter) -> MidiResult<()> {
+        let nrpn = param.nrpn()?;
+        let checkpoint = self.history_checkpoint(device_name);
+        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        match device {
+            DeviceConnection::Collider { connection, state } => {
+                let change = match &nrpn {
+                    Some(nrpn) => {
+                        connection.send_nrpn(nrpn)?;
+                        HistoryChange::Nrpn {
+                            parameter: param.name().to_string(),
+                            nrpn_parameter: nrpn.parameter,
+                            previous_value: state.state.delay_time_nrpns()
+                                .iter()
+                                .find(|previous| previous.parameter == nrpn.parameter)
+                                .map(|previous| previous.value),
+                            new_value: nrpn.value,
+                        }
+                    }
+                    None => {
+                        connection.send_cc(param.cc_number(), param.cc_value())?;
+                        HistoryChange::parameter(param.name(), param.cc_number(), param.cc_value())
+                    }
+                };
+                state.update_state(&param);
+                self.record_history(device_name, checkpoint, change);
+                Ok(())
+            }
+            _ => Err(MidiError::Other("Device is not a Collider".to_string())),
+        }
+    }
+
+    /// Get current state of a Collider
+    pub fn get_collider_state(&self, device_name: &str) -> MidiResult<ColliderState> {
+        self.get_device_state(device_name)?.parse(&PedalType::Collider)
+    }
+
+    /// Recall a preset on a Collider (send all parameters, delay times last)
+    pub fn recall_collider_preset(&mut self, device_name: &str, state: &ColliderState) -> MidiResult<()> {
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::Recall;
+        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        match device {
+            DeviceConnection::Collider { connection, state: device_state } => {
+                let temp = Collider { state: state.clone(), midi_channel: connection.midi_channel() };
+                let plan = temp.recall_plan();
+                connection.send_plan(&plan)?;
+                *device_state = temp;
+                self.record_history(device_name, checkpoint, change);
+                Ok(())
+            }
+            _ => Err(MidiError::Other("Device is not a Collider".to_string())),
+        }
+    }
+
+    /// Send a program change to a Collider (load preset 0-127)
+    pub fn send_collider_program_change(&mut self, device_name: &str, program: u8) -> MidiResult<()> {
+        if program >= Collider::preset_count() {
+            return Err(MidiError::Other(format!(
+                "Invalid program: {}. Must be 0-{}", program, Collider::preset_count() - 1
+            )));
+        }
+        let checkpoint = self.history_checkpoint(device_name);
+        let change = HistoryChange::ProgramChange { program };
+        let device = self.connections.get_mut(device_name).ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        match device {
+            DeviceConnection::Collider { connection, .. } => {
+                connection.send_program_change(program)?;
+                println!("[Collider] Sent PC {} (loaded preset {})", program, program);
+                self.record_history(device_name, checkpoint, change);
+                Ok(())
+            }
+            _ => Err(MidiError::Other("Device is not a Collider".to_string())),
+        }
+    }
+
+    /// List all connected devices
+    pub fn connected_devices(&self) -> Vec<ConnectedDevice> {
+        self.connections.iter().map(|(name, device)| {
+            ConnectedDevice {
+                device_name: name.clone(),
+                pedal_type: device.pedal_type(),
+                port_name: device.connection().port_name().to_string(),
+                midi_channel: device.connection().midi_channel(),
+                channel_verified: self.channel_activity
+                    .get(name)
+                    .map(|activity| activity.verified())
+                    .unwrap_or(false),
+                display_label: self.device_label(name),
+                firmware: self.device_firmware(name),
+                armed: device.connection().armed,
+                input_mode: device.connection().input_mode.get(),
+            }
+        }).collect()
+    }
+    
+    /// Check if a device is connected
+    pub fn is_connected(&self, device_name: &str) -> bool {
+        self.connections.contains_key(device_name)
+    }
+    
+    /// The pedal type a connected device was connected as
+    pub fn device_pedal_type(&self, device_name: &str) -> MidiResult<PedalType> {
+        self.connections
+            .get(device_name)
+            .map(|device| device.pedal_type())
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))
+    }
+    
+    /// Start a channel verification window: send the connection test's probe (Bank Select
+    /// MSB, which changes nothing on pedals without banks) so pedals that echo have
+    /// something to answer. Call finish_channel_verification after a short wait (without
+    /// holding the manager lock in between).
+    pub fn begin_channel_verification(&mut self, device_name: &str) -> MidiResult<()> {
+        let device = self.connections.get_mut(device_name)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        
+        let activity = self.channel_activity.get(device_name)
+            .ok_or_else(|| MidiError::Other(format!("No MIDI input for '{}', can't verify channel", device_name)))?;
+        activity.begin_window();
+        
+        // Not a parameter the believed state holds - re-sending one of those could
+        // overwrite the pedal's real setting right after connecting
+        device.connection_mut().send_cc(connection_test::PROBE_CC, connection_test::PROBE_VALUE)
+
+    }
+    
+    /// Send the connection test probe (CC 0) and start timing the device's reply
+    pub fn begin_connection_test(&mut self, device_name: &str) -> MidiResult<ConnectionProbe> {
+        let device = self.connections.get_mut(device_name)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        let activity = self.channel_activity.get(device_name)
+            .ok_or_else(|| MidiError::Other(format!("No MIDI input for '{}', can't test the connection", device_name)))?;
+        
+        let probe = ConnectionProbe::start(activity.clone());
+        device.connection_mut().send_cc(connection_test::PROBE_CC, connection_test::PROBE_VALUE)?;
+        Ok(probe)
+    }
+    
+    /// CCs heard from a device on its channel so far (see `ChannelActivity::cc_count`)
+    pub fn received_cc_count(&self, device_name: &str) -> MidiResult<u64> {
+        self.channel_activity
+            .get(device_name)
+            .map(ChannelActivity::cc_count)
+            .ok_or_else(|| MidiError::Other(format!("No MIDI input for '{}'", device_name)))
+    }
+    
+    /// End a channel verification window. Returns whether traffic was seen on the configured
+    /// channel; if the device was heard on another channel, emits `channel-mismatch-detected`.
+    pub fn finish_channel_verification(&self, device_name: &str) -> MidiResult<bool> {
+        if !self.connections.contains_key(device_name) {
+            return Err(MidiError::NotConnected(device_name.to_string()));
+        }
+        let activity = self.channel_activity.get(device_name)
+            .ok_or_else(|| MidiError::Other(format!("No MIDI input for '{}', can't verify channel", device_name)))?;
+        
+        if let Some(observed_channel) = activity.observed_mismatch() {
+            let configured_channel = activity.configured_channel();
+            println!(
+                "⚠️  '{}' configured for channel {} but heard on channel {}",
+                device_name, configured_channel, observed_channel
+            );
+            
+            if let Some(app_handle) = &self.app_handle {
+                let event = ChannelMismatchEvent {
+                    device_name: device_name.to_string(),
+                    configured_channel,
+                    observed_channel,
+                };
+                if let Err(e) = app_handle.emit("channel-mismatch-detected", &event) {
+                    eprintln!("❌ Failed to emit channel mismatch event: {}", e);
+                }
+            }
+        }
+        
+        Ok(activity.verified())
+    }
+    
+    /// Reconnect a device on a different MIDI channel (believed state resets to defaults).
+    /// The new route is opened before the old one is torn down, so if it can't be opened
+    /// the device stays connected as it was.
+    pub fn set_device_channel(&mut self, device_name: &str, midi_channel: u8) -> MidiResult<()> {
+        if !(1..=16).contains(&midi_channel) {
+            return Err(MidiError::InvalidChannel(midi_channel));
+        }
+        
+        let old = self.connections.remove(device_name)
+            .ok_or_else(|| MidiError::NotConnected(device_name.to_string()))?;
+        let old_channel = old.connection().midi_channel();
+        if old_channel == midi_channel {
+            self.connections.insert(device_name.to_string(), old);
+            return Ok(());
+        }
+        let pedal_type = old.pedal_type();
+        let port_name = old.connection().port_name().to_string();
+        let old_activity = self.channel_activity.remove(device_name);
+        let input_mode = old.connection().input_mode.get();
+        
+        if let Err(e) = self.connect_on_port(pedal_type, device_name, &port_name, midi_channel) {
+            self.connections.insert(device_name.to_string(), old);
+            if let Some(activity) = old_activity {
+                self.channel_activity.insert(device_name.to_string(), activity);
+            }
+            return Err(e);
+        }
+        // Input mode is the user's setting, not the connection's - it outlives the channel
+        if let Some(connection) = self.connections.get(device_name) {
+            connection.connection().input_mode.set(input_mode);
+        }
+        
+        // The new route is up - release the old one. The port stays open for the new route.
+        self.capture_timers.cancel(device_name);
+        self.outputs.release(&port_name);
+        if let Some(input) = self.inputs.get(&port_name) {
+            input.demux.remove(old_channel);
+        }
+        self.snapshots.remove(device_name);
+        self.histories.remove(device_name);
+        self.ab_buffers.remove(device_name);
+        Ok(())
+    }
+    
+    /// Connect to a pedal of any type (dispatches to the pedal-specific connect method)
+    pub fn connect(&mut self, pedal_type: PedalType, device_name: &str, midi_channel: u8) -> MidiResult<()> {
+        match pedal_type {
+            PedalType::Microcosm => self.connect_microcosm(device_name, midi_channel),
+            PedalType::GenLossMkii => self.connect_gen_loss_mkii(device_name, midi_channel),
+            PedalType::ChromaConsole => self.connect_chroma_console(device_name, midi_channel),
+            PedalType::PreampMk2 => self.connect_preamp_mk2(device_name, midi_channel),
+            PedalType::Cxm1978 => self.connect_cxm1978(device_name, midi_channel),
+            PedalType::MoodMkii => self.connect_mood_mkii(device_name, midi_channel),
+            PedalType::BillyStringsWombtone => self.connect_billy_strings_wombtone(device_name, midi_channel),
+            PedalType::Lossy => self.connect_lossy(device_name, midi_channel),
+            PedalType::BrothersAm => self.connect_brothers_am(device_name, midi_channel),
+            PedalType::ReverseModeC => self.connect_reverse_mode_c(device_name, midi_channel),
+            PedalType::Clean => self.connect_clean(device_name, midi_channel),
+            PedalType::Onward => self.connect_onward(device_name, midi_channel),
+            PedalType::Thermae => self.connect_thermae(device_name, midi_channel),
+            PedalType::DarkWorld => self.connect_dark_world(device_name, midi_channel),
+            PedalType::Habit => self.connect_habit(device_name, midi_channel),
+            PedalType::Collider => self.connect_collider(device_name, midi_channel),
+        }
+    }
+}
+
+impl Default for MidiManager {
+    fn default() -> Self {
+        Self::new().expect("Failed to create MIDI Manager")
+    }
+}
+
+// Thread-safe wrapper for use with Tauri State
+pub type SharedMidiManager = Arc<Mutex<MidiManager>>;
+
+/// Create a new shared MIDI Manager for use with Tauri
+pub fn create_shared_manager() -> MidiResult<SharedMidiManager> {
+    Ok(Arc::new(Mutex::new(MidiManager::new()?)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::midi::CcValue;
+    use crate::midi::clock::Clock;
+    use crate::test_utils::{MockClock, MockOutputPort};
+    use crate::midi::pedals::DIP_SWITCH_CCS;
+    use std::sync::mpsc;
+
+    /// A manager with one Microcosm on a mock port
+    fn manager_with_microcosm(port: &MockOutputPort) -> MidiManager {
+        let mut manager = MidiManager::new().unwrap();
+        connect_mock(&mut manager, port, PedalType::Microcosm, "Microcosm", 1);
+        manager
+    }
+
+    /// Connect `pedal_type` as `device_name` on its own mock port
+    fn connect_mock(manager: &mut MidiManager, port: &MockOutputPort, pedal_type: PedalType, device_name: &str, channel: u8) {
+        let route = Route { port_name: device_name.to_string(), channel };
+        let output = manager.outputs.acquire(route, || Ok(Box::new(port.clone()))).unwrap();
+        let connection = MidiConnection::new(output);
+        manager.connections.insert(device_name.to_string(), DeviceConnection::open(pedal_type, connection));
+    }
+
+    fn set(manager: &mut MidiManager, param: MicrocosmParameter) {
+        set_on(manager, "Microcosm", param);
+    }
+
+    fn set_on(manager: &mut MidiManager, device_name: &str, param: MicrocosmParameter) {
+        manager.send_microcosm_parameter(device_name, param).unwrap();
+    }
+
+    #[test]
+    fn test_ab_flip_restores_state_and_sends_only_changes() {
+        let port = MockOutputPort::default();
+        let mut manager = manager_with_microcosm(&port);
+
+        let status = manager.store_ab("Microcosm", AbSlot::A).unwrap();
+        assert_eq!(status.matching, Some(AbSlot::A));
+        let a_state = manager.connections["Microcosm"].state_json().unwrap();
+
+        set(&mut manager, MicrocosmParameter::Mix(CcValue::new(100).unwrap()));
+        set(&mut manager, MicrocosmParameter::Time(CcValue::new(20).unwrap()));
+        manager.store_ab("Microcosm", AbSlot::B).unwrap();
+
+        // Edits after storing don't touch the buffers
+        set(&mut manager, MicrocosmParameter::Activity(CcValue::new(5).unwrap()));
+        assert_eq!(manager.get_ab_status("Microcosm").unwrap().matching, None);
+
+        let sent_before = port.sent().len();
+        let status = manager.swap_ab("Microcosm").unwrap();
+        assert_eq!(status.matching, Some(AbSlot::A));
+        assert_eq!(manager.connections["Microcosm"].state_json().unwrap(), a_state);
+        assert_eq!(port.sent().len() - sent_before, 3);
+
+        // Second flip only sends mix and time
+        let sent_before = port.sent().len();
+        let status = manager.swap_ab("Microcosm").unwrap();
+        assert_eq!(status.matching, Some(AbSlot::B));
+        let sent = &port.sent()[sent_before..];
+        assert_eq!(sent.len(), 2);
+        assert!(sent.contains(&vec![0xB0, 9, 100]));
+        assert!(sent.contains(&vec![0xB0, 10, 20]));
+
+        manager.swap_ab("Microcosm").unwrap();
+        assert_eq!(manager.connections["Microcosm"].state_json().unwrap(), a_state);
+    }
+
+    #[test]
+    fn test_ab_buffers_dropped_on_disconnect() {
+        let port = MockOutputPort::default();
+        let mut manager = manager_with_microcosm(&port);
+
+        assert!(manager.recall_ab("Microcosm", AbSlot::B).is_err());
+        manager.store_ab("Microcosm", AbSlot::B).unwrap();
+        manager.disconnect("Microcosm").unwrap();
+
+        assert!(manager.ab_buffers.is_empty());
+        assert!(matches!(manager.get_ab_status("Microcosm"), Err(MidiError::NotConnected(_))));
+    }
+
+    fn cc(value: u8) -> CcValue {
+        CcValue::new(value).unwrap()
+    }
+
+    #[test]
+    fn test_looper_transport_stays_on_the_connection_across_recalls() {
+        let port = MockOutputPort::default();
+        let mut manager = manager_with_microcosm(&port);
+        set(&mut manager, MicrocosmParameter::LooperRecord);
+
+        let saved = manager.get_device_state("Microcosm").unwrap().state;
+        assert!(saved.get("looper_transport").is_none());
+
+        manager.recall_microcosm_preset("Microcosm", &MicrocosmState::default()).unwrap();
+        let DeviceConnection::Microcosm { state, .. } = &manager.connections["Microcosm"] else { unreachable!() };
+        assert_eq!(state.looper_transport, LooperTransport::Recording);
+    }
+
+    #[test]
+    fn test_midi_note_sends_note_on_then_off() {
+        let port = MockOutputPort::default();
+        let mut manager = MidiManager::new().unwrap();
+        connect_mock(&mut manager, &port, PedalType::Microcosm, "Microcosm", 3);
+
+        manager.start_midi_note("Microcosm", 60, 100).unwrap();
+        manager.end_midi_note("Microcosm", 60).unwrap();
+        assert_eq!(port.sent(), vec![vec![0x92, 60, 100], vec![0x82, 60, 0]]);
+
+        let err = manager.start_midi_note("Microcosm", 60, 128).unwrap_err().to_string();
+        assert!(err.contains("velocity"), "{}", err);
+        assert_eq!(port.sent().len(), 2);
+
+        manager.send_midi_note("Microcosm", 62, 90, 0).unwrap();
+        assert_eq!(port.sent()[2..], [vec![0x92, 62, 90], vec![0x82, 62, 0]]);
+    }
+
+    #[test]
+    fn test_midi_note_longer_than_the_cap_is_refused() {
+        let port = MockOutputPort::default();
+        let mut manager = MidiManager::new().unwrap();
+        connect_mock(&mut manager, &port, PedalType::Microcosm, "Microcosm", 3);
+
+        let too_long = MAX_NOTE_DURATION.as_millis() as u64 + 1;
+        let result = manager.send_midi_note("Microcosm", 60, 100, too_long);
+        assert!(matches!(result, Err(MidiError::InvalidValue { actual, .. }) if actual == too_long));
+        assert!(port.sent().is_empty());
+        assert_eq!(note_duration(MAX_NOTE_DURATION.as_millis() as u64).unwrap(), MAX_NOTE_DURATION);
+    }
+
+    #[test]
+    fn test_gen_loss_recall_leaves_dips_unless_asked() {
+        let port = MockOutputPort::default();
+        let mut manager = MidiManager::new().unwrap();
+        connect_mock(&mut manager, &port, PedalType::GenLossMkii, "Gen Loss", 1);
+        let preset = GenLossMkiiState { wow: 90, dip_wow: true, ..Default::default() };
+        let sent_ccs = |port: &MockOutputPort| -> Vec<u8> { port.sent().iter().map(|message| message[1]).collect() };
+
+        manager.recall_gen_loss_preset("Gen Loss", &preset, false).unwrap();
+        assert!(sent_ccs(&port).iter().all(|cc| !DIP_SWITCH_CCS.contains(cc)));
+        let state = manager.get_gen_loss_state("Gen Loss").unwrap();
+        assert_eq!((state.wow, state.dip_wow), (90, false));
+
+        manager.recall_gen_loss_preset("Gen Loss", &preset, true).unwrap();
+        assert!(sent_ccs(&port).contains(&61));
+        assert!(manager.get_gen_loss_state("Gen Loss").unwrap().dip_wow);
+
+        // Profiles set all 16 switches and nothing else
+        let dips = GenLossDipSwitches { dip_spread: true, ..GenLossDipSwitches::from(&GenLossMkiiState::default()) };
+        manager.gen_loss_dip_profiles_mut().save("Stereo", dips).unwrap();
+        let before = port.sent().len();
+        manager.apply_gen_loss_dip_profile("Gen Loss", "Stereo").unwrap();
+        let applied = &sent_ccs(&port)[before..];
+        assert_eq!(applied, DIP_SWITCH_CCS);
+        let state = manager.get_gen_loss_state("Gen Loss").unwrap();
+        assert_eq!(GenLossDipSwitches::from(&state), dips);
+        assert_eq!(state.wow, 90);
+    }
+
+    #[test]
+    fn test_old_firmware_gates_newer_ccs() {
+        let port = MockOutputPort::default();
+        let mut manager = MidiManager::new().unwrap();
+        connect_mock(&mut manager, &port, PedalType::GenLossMkii, "Gen Loss", 1);
+        let identity = DeviceIdentity {
+            device_id: 0,
+            manufacturer_id: vec![0x00, 0x02, 0x4D],
+            device_family: 0,
+            device_model: 0,
+            software_version: vec![1, 0, 0, 0],
+        };
+        assert_eq!(manager.record_device_identity("Gen Loss", &identity), vec!["Gen Loss".to_string()]);
+        assert_eq!(manager.get_device_state("Gen Loss").unwrap().firmware, Some(FirmwareVersion::new(1, 0, 0)));
+
+        // Warn but send
+        manager.begin_journal_operation("test");
+        manager.send_gen_loss_parameter("Gen Loss", GenLossMkiiParameter::RampBounce(true)).unwrap();
+        manager.send_gen_loss_parameter("Gen Loss", GenLossMkiiParameter::Wow(cc(64))).unwrap();
+        assert_eq!(port.sent(), vec![vec![0xB0, 52, 127], vec![0xB0, 14, 64]]);
+        let warnings = manager.take_firmware_warnings("Gen Loss");
+        assert_eq!(warnings.len(), 1);
+        assert_eq!((warnings[0].cc_number, warnings[0].skipped), (52, false));
+
+        // Skip
+        manager.set_skip_unsupported_ccs(true);
+        manager.begin_journal_operation("test");
+        manager.send_gen_loss_parameter("Gen Loss", GenLossMkiiParameter::RampBounce(false)).unwrap();
+        assert_eq!(port.sent().len(), 2);
+        assert!(manager.take_firmware_warnings("Gen Loss")[0].skipped);
+    }
+
+    #[test]
+    fn test_scheduled_recall_skips_disconnected_device() {
+        let port = MockOutputPort::default();
+        let mut manager = manager_with_microcosm(&port);
+        let state = serde_json::to_value(MicrocosmState { mix: 99, ..Default::default() }).unwrap();
+        let target = RecallTarget { pedal_type: PedalType::Microcosm, parameters: state };
+        let hour = ScheduleSpec::Millis(3_600_000);
+
+        // Fired by hand here; the worker is still waiting out its hour
+        let fired = manager.schedule_recall("Microcosm", "p1", "Verse", hour, target.clone(), |_| {}).unwrap();
+        let missed = manager.schedule_recall("Microcosm", "p1", "Verse", hour, target, |_| {}).unwrap();
+        assert_eq!(manager.list_scheduled().len(), 2);
+
+        manager.fire_scheduled_recall(fired.id).unwrap();
+        assert_eq!(manager.get_microcosm_state("Microcosm").unwrap().mix, 99);
+        let sent = port.sent().len();
+
+        manager.disconnect("Microcosm").unwrap();
+        manager.fire_scheduled_recall(missed.id).unwrap();
+        assert_eq!(port.sent().len(), sent);
+        assert!(manager.list_scheduled().is_empty());
+    }
+
+    #[test]
+    fn test_disconnect_all_releases_every_port() {
+        let port = MockOutputPort::default();
+        let mut manager = manager_with_microcosm(&port);
+        connect_mock(&mut manager, &port, PedalType::GenLossMkii, "Gen Loss", 2);
+
+        assert_eq!(manager.disconnect_all(), vec!["Gen Loss".to_string(), "Microcosm".to_string()]);
+        assert!(manager.connected_devices().is_empty());
+        assert!(!manager.outputs.is_open("Microcosm"));
+        assert!(!manager.outputs.is_open("Gen Loss"));
+        assert!(manager.disconnect_all().is_empty());
+    }
+
+    #[test]
+    fn test_batch_for_another_pedal_sends_nothing() {
+        let port = MockOutputPort::default();
+        let mut manager = manager_with_microcosm(&port);
+        let before = manager.connections["Microcosm"].state_json().unwrap();
+
+        let result = manager.send_parameters_batch("Microcosm", vec![
+            PedalParameter::Microcosm(MicrocosmParameter::Mix(cc(100))),
+            PedalParameter::GenLossMkii(GenLossMkiiParameter::Wow(cc(64))),
+        ]);
+
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("Wow is a GenLossMkii parameter, not Microcosm"), "{}", err);
+        assert!(port.sent().is_empty());
+        assert_eq!(manager.connections["Microcosm"].state_json().unwrap(), before);
+        assert!(manager.get_undo_stack("Microcosm").unwrap().undo.is_empty());
+    }
+
+    #[test]
+    fn test_effect_select_sends_the_program_change() {
+        use crate::midi::pedals::microcosm::{EffectType, EffectVariation};
+
+        let port = MockOutputPort::default();
+        let mut manager = manager_with_microcosm(&port);
+
+        set(&mut manager, MicrocosmParameter::EffectSelect(EffectType::Haze, EffectVariation::C));
+
+        assert_eq!(port.sent(), vec![vec![0xC0, 26]]);
+        let DeviceConnection::Microcosm { state, .. } = &manager.connections["Microcosm"] else { unreachable!() };
+        assert_eq!(state.state.current_effect, EffectType::Haze);
+        assert_eq!(state.state.current_variation, EffectVariation::C);
+        let undo = manager.get_undo_stack("Microcosm").unwrap().undo;
+        assert_eq!(undo[0].change, HistoryChange::ProgramChange { program: 26 });
+
+        let batch = vec![PedalParameter::Microcosm(MicrocosmParameter::EffectSelect(EffectType::Warp, EffectVariation::A))];
+        assert!(manager.send_parameters_batch("Microcosm", batch).is_err());
+        assert_eq!(port.sent().len(), 1);
+    }
+
+    /// A Collider that sends, as it will once its CC map is confirmed
+    fn connect_confirmed_collider(manager: &mut MidiManager, port: &MockOutputPort) {
+        connect_mock(manager, port, PedalType::Collider, "Collider", 1);
+        manager.connections.get_mut("Collider").unwrap().connection_mut().cc_map_unconfirmed = false;
+    }
+
+    #[test]
+    fn test_unconfirmed_collider_map_is_refused() {
+        let port = MockOutputPort::default();
+        let mut manager = MidiManager::new().unwrap();
+        connect_mock(&mut manager, &port, PedalType::Collider, "Collider", 1);
+        let before = manager.get_collider_state("Collider").unwrap();
+
+        assert!(manager.send_collider_parameter("Collider", ColliderParameter::DelayTimeA(10_000)).is_err());
+        assert!(manager.send_collider_parameter("Collider", ColliderParameter::Bypass(true)).is_err());
+        assert!(manager.send_parameters_batch("Collider", vec![
+            PedalParameter::Collider(ColliderParameter::DelayTimeB(12_000)),
+        ]).is_err());
+        let mut target = before.clone();
+        target.bypass = true;
+        assert!(manager.recall_collider_preset("Collider", &target).is_err());
+
+        assert!(port.sent().is_empty());
+        assert_eq!(manager.get_collider_state("Collider").unwrap(), before);
+        assert!(manager.get_undo_stack("Collider").unwrap().undo.is_empty());
+
+        // Program changes are standard MIDI and still go out
+        manager.send_collider_program_change("Collider", 5).unwrap();
+        assert_eq!(port.sent(), vec![vec![0xC0, 5]]);
+    }
+
+    #[test]
+    fn test_collider_delay_time_undo_resends_only_its_nrpn() {
+        let port = MockOutputPort::default();
+        let mut manager = MidiManager::new().unwrap();
+        connect_confirmed_collider(&mut manager, &port);
+        let previous = manager.get_collider_state("Collider").unwrap().delay_a.time;
+
+        manager.send_collider_parameter("Collider", ColliderParameter::DelayTimeA(10_000)).unwrap();
+        let undo = manager.get_undo_stack("Collider").unwrap().undo;
+        assert_eq!(undo[0].change, HistoryChange::Nrpn {
+            parameter: "Delay A Time".to_string(),
+            nrpn_parameter: 1,
+            previous_value: Some(previous),
+            new_value: 10_000,
+        });
+
+        let sent_before_undo = port.sent().len();
+        manager.undo_parameter_change("Collider").unwrap();
+        let resent: Vec<Vec<u8>> = Nrpn::new(1, previous).unwrap()
+            .cc_messages()
+            .iter()
+            .map(|(cc_number, value)| vec![0xB0, *cc_number, *value])
+            .collect();
+        assert_eq!(port.sent()[sent_before_undo..], resent);
+        assert_eq!(manager.get_collider_state("Collider").unwrap().delay_a.time, previous);
+    }
+
+    #[test]
+    fn test_batch_expands_collider_delay_times() {
+        let port = MockOutputPort::default();
+        let mut manager = MidiManager::new().unwrap();
+        connect_confirmed_collider(&mut manager, &port);
+
+        manager.send_parameters_batch("Collider", vec![
+            PedalParameter::Collider(ColliderParameter::DelayTimeB(12_000)),
+            PedalParameter::Collider(ColliderParameter::Bypass(true)),
+        ]).unwrap();
+
+        let mut expected: Vec<Vec<u8>> = Nrpn::new(2, 12_000).unwrap()
+            .cc_messages()
+            .iter()
+            .map(|(cc_number, value)| vec![0xB0, *cc_number, *value])
+            .collect();
+        expected.push(vec![0xB0, 102, 127]);
+        assert_eq!(port.sent(), expected);
+        let state = manager.get_collider_state("Collider").unwrap();
+        assert_eq!(state.delay_b.time, 12_000);
+        assert!(state.bypass);
+    }
+
+    /// A Chroma Console whose capture timers run on `clock`
+    fn manager_with_chroma_capture(port: &MockOutputPort, clock: &Arc<MockClock>) -> MidiManager {
+        let mut manager = MidiManager::new().unwrap();
+        connect_mock(&mut manager, port, PedalType::ChromaConsole, "Chroma", 1);
+        manager.capture_timers = CaptureTimers::with_clock(Arc::clone(clock) as Arc<dyn Clock>);
+        manager
+    }
+
+    fn capture_mode_sent(mode: CaptureMode) -> Vec<u8> {
+        let param = ChromaConsoleParameter::CaptureMode(mode);
+        vec![0xB0, param.cc_number(), param.cc_value()]
+    }
+
+    /// Start a one-shot whose timer reports its id on `elapsed`, once the timer is waiting
+    fn start_one_shot(manager: &mut MidiManager, clock: &MockClock, duration_ms: u64, elapsed: &mpsc::Sender<u64>) {
+        let waiters = clock.waits().len() + 1;
+        let elapsed = elapsed.clone();
+        manager.start_capture_one_shot("Chroma", Duration::from_millis(duration_ms), CaptureRouting::PostFx, move |id| {
+            let _ = elapsed.send(id);
+        }).unwrap();
+        clock.await_waiters(waiters);
+    }
+
+    #[test]
+    fn test_capture_one_shot_records_then_plays_after_duration() {
+        let port = MockOutputPort::default();
+        let clock = Arc::new(MockClock::default());
+        let mut manager = manager_with_chroma_capture(&port, &clock);
+        let (elapsed, fired) = mpsc::channel();
+
+        start_one_shot(&mut manager, &clock, 2000, &elapsed);
+        assert_eq!(port.sent().last(), Some(&capture_mode_sent(CaptureMode::Record)));
+        assert!(manager.capture_timers.is_pending("Chroma"));
+
+        clock.advance(Duration::from_millis(2000));
+        let id = fired.recv().unwrap();
+        manager.finish_capture_one_shot("Chroma", id).unwrap();
+
+        assert_eq!(port.sent().last(), Some(&capture_mode_sent(CaptureMode::Play)));
+        assert!(!manager.capture_timers.is_pending("Chroma"));
+        assert_eq!(clock.waits(), vec![Duration::from_millis(2000)]);
+        let state = manager.get_chroma_console_state("Chroma").unwrap();
+        assert_eq!(state.capture_transport, CaptureTransport::Playing);
+    }
+
+    #[test]
+    fn test_overlapping_capture_one_shot_cancels_previous_timer() {
+        let port = MockOutputPort::default();
+        let clock = Arc::new(MockClock::default());
+        let mut manager = manager_with_chroma_capture(&port, &clock);
+        let (elapsed, fired) = mpsc::channel();
+
+        start_one_shot(&mut manager, &clock, 1000, &elapsed);
+        clock.advance(Duration::from_millis(500));
+        start_one_shot(&mut manager, &clock, 1000, &elapsed);
+
+        // Past the first deadline and the second; only the second timer fires
+        clock.advance(Duration::from_millis(1000));
+        drop(elapsed);
+        let ids: Vec<u64> = fired.iter().collect();
+        assert_eq!(ids.len(), 1);
+
+        // A stale id (e.g. a timer that fired just before being replaced) is ignored
+        let sent = port.sent().len();
+        manager.finish_capture_one_shot("Chroma", ids[0] - 1).unwrap();
+        assert_eq!(port.sent().len(), sent);
+
+        manager.finish_capture_one_shot("Chroma", ids[0]).unwrap();
+        assert_eq!(port.sent().last(), Some(&capture_mode_sent(CaptureMode::Play)));
+    }
+
+    #[test]
+    fn test_stop_capture_aborts_pending_one_shot() {
+        let port = MockOutputPort::default();
+        let clock = Arc::new(MockClock::default());
+        let mut manager = manager_with_chroma_capture(&port, &clock);
+        let (elapsed, fired) = mpsc::channel();
+
+        start_one_shot(&mut manager, &clock, 1000, &elapsed);
+        manager.stop_capture("Chroma").unwrap();
+        clock.advance(Duration::from_millis(1000));
+        drop(elapsed);
+
+        assert_eq!(fired.iter().count(), 0);
+        assert_eq!(port.sent().last(), Some(&capture_mode_sent(CaptureMode::Stop)));
+        assert!(!manager.capture_timers.is_pending("Chroma"));
+    }
+
+    #[test]
+    fn test_batch_sends_in_order_with_one_event_and_history_entry() {
+        let port = MockOutputPort::default();
+        let mut manager = manager_with_microcosm(&port);
+        manager.begin_journal_operation("send_parameters_batch");
+        let before = manager.connections["Microcosm"].state_json().unwrap();
+
+        manager.send_parameters_batch("Microcosm", vec![
+            PedalParameter::Microcosm(MicrocosmParameter::Time(cc(20))),
+            PedalParameter::Microcosm(MicrocosmParameter::Mix(cc(100))),
+            PedalParameter::Microcosm(MicrocosmParameter::Activity(cc(5))),
+        ]).unwrap();
+
+        assert_eq!(port.sent(), vec![vec![0xB0, 10, 20], vec![0xB0, 9, 100], vec![0xB0, 6, 5]]);
+
+        let after = manager.connections["Microcosm"].state_json().unwrap();
+        let event = manager.state_changed_event("Microcosm", &before, &after).unwrap();
+        assert_eq!(event.pedal_type, "Microcosm");
+        assert!(event.operation_id.is_some());
+        let mut fields: Vec<&str> = event.changes.iter().map(|change| change.field.as_str()).collect();
+        fields.sort();
+        assert_eq!(fields, vec!["activity", "mix", "time"]);
+        let mix = event.changes.iter().find(|change| change.field == "mix").unwrap();
+        assert_eq!(mix.new, 100);
+
+        let undo = manager.get_undo_stack("Microcosm").unwrap().undo;
+        assert_eq!(undo.len(), 1);
+        assert_eq!(undo[0].change, HistoryChange::Batch {
+            parameters: vec!["Time".to_string(), "Mix".to_string(), "Activity".to_string()],
+        });
+    }
+
+    // The payload `device-state-changed` carries for a real recall: the fields it moved
+    #[test]
+    fn test_recall_state_changed_payload() {
+        use crate::midi::pedals::thermae::GlideMode;
+
+        let port = MockOutputPort::default();
+        let mut manager = MidiManager::new().unwrap();
+        connect_mock(&mut manager, &port, PedalType::Thermae, "Thermae", 1);
+        let current = manager.get_thermae_state("Thermae").unwrap();
+        let preset = ThermaeState { mix: 100, glide_mode: GlideMode::Rise, hold: true, ..current.clone() };
+
+        manager.recall_thermae_preset("Thermae", &preset).unwrap();
+
+        assert!(!port.sent().is_empty());
+        let undo = manager.get_undo_stack("Thermae").unwrap().undo;
+        assert_eq!(undo[0].change, HistoryChange::Recall);
+        let event = manager.state_changed_event("Thermae", &undo[0].before, &undo[0].after).unwrap();
+        assert_eq!(event.device_name, "Thermae");
+        assert_eq!(event.pedal_type, "Thermae");
+        let before = serde_json::to_value(&current).unwrap();
+        assert_eq!(event.changes, vec![
+            FieldChange { field: "glide_mode".to_string(), old: before["glide_mode"].clone(), new: serde_json::json!("Rise") },
+            FieldChange { field: "hold".to_string(), old: serde_json::json!(false), new: serde_json::json!(true) },
+            FieldChange { field: "mix".to_string(), old: before["mix"].clone(), new: serde_json::json!(100) },
+        ]);
+
+        // Recalling the same preset again moves nothing
+        let after = manager.connections["Thermae"].state_json().unwrap();
+        manager.recall_thermae_preset("Thermae", &preset).unwrap();
+        assert!(manager.state_changed_event("Thermae", &after, &manager.connections["Thermae"].state_json().unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_batch_failure_reports_what_was_sent() {
+        let port = MockOutputPort::failing_after(1);
+        let mut manager = manager_with_microcosm(&port);
+
+        let err = manager.send_parameters_batch("Microcosm", vec![
+            PedalParameter::Microcosm(MicrocosmParameter::Mix(cc(100))),
+            PedalParameter::Microcosm(MicrocosmParameter::Time(cc(20))),
+        ]).unwrap_err();
+
+        match err {
+            MidiError::BatchIncomplete { sent, failed, .. } => {
+                assert_eq!(sent, vec!["Mix"]);
+                assert_eq!(failed, "Time");
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+        // State follows what actually went out
+        let state = manager.get_microcosm_state("Microcosm").unwrap();
+        assert_eq!(state.mix, 100);
+        assert_ne!(state.time, 20);
+    }
+
+    #[test]
+    fn test_bypass_all_sends_inverted_chroma_values() {
+        let port = MockOutputPort::default();
+        let mut manager = MidiManager::new().unwrap();
+        connect_mock(&mut manager, &port, PedalType::ChromaConsole, "Chroma Console", 1);
+        let chroma_param = |state| PedalParameter::ChromaConsole(ChromaConsoleParameter::BypassState(state));
+        manager.send_parameters_batch("Chroma Console", vec![chroma_param(BypassState::Engaged)]).unwrap();
+
+        let sent_before = port.sent().len();
+        let results = manager.bypass_all();
+        assert_eq!(results, vec![BypassResult { device_name: "Chroma Console".to_string(), success: true, error: None }]);
+        // Standard footswitch mode bypasses with 127 on CC 91
+        assert_eq!(port.sent()[sent_before..], [vec![0xB0, 91, 127]]);
+        assert_eq!(manager.get_chroma_console_state("Chroma Console").unwrap().bypass_state, BypassState::Bypass);
+
+        let sent_before = port.sent().len();
+        let results = manager.restore_bypass_states();
+        assert!(results[0].success);
+        assert_eq!(port.sent()[sent_before..], [vec![0xB0, 91, 0]]);
+        assert_eq!(manager.get_chroma_console_state("Chroma Console").unwrap().bypass_state, BypassState::Engaged);
+    }
+
+    #[test]
+    fn test_preamp_faders_are_held_while_motors_are_disabled() {
+        let port = MockOutputPort::default();
+        let mut manager = MidiManager::new().unwrap();
+        connect_mock(&mut manager, &port, PedalType::PreampMk2, "Preamp", 2);
+        manager.set_preamp_mk2_motorized_faders_enabled("Preamp", false).unwrap();
+        assert!(!manager.preamp_mk2_motorized_faders_enabled("Preamp").unwrap());
+
+        manager.send_preamp_mk2_parameter("Preamp", PreampMk2Parameter::Gain(cc(90))).unwrap();
+        manager.send_preamp_mk2_parameter("Preamp", PreampMk2Parameter::Volume(cc(10))).unwrap();
+        manager.send_preamp_mk2_parameter("Preamp", PreampMk2Parameter::Gain(cc(100))).unwrap();
+        manager.send_preamp_mk2_parameter("Preamp", PreampMk2Parameter::Bypass(true)).unwrap();
+        // Only the non-fader CC went out, but the believed state has everything
+        assert_eq!(port.sent(), vec![vec![0xB1, 102, 0]]);
+        let state = manager.get_preamp_mk2_state("Preamp").unwrap();
+        assert_eq!((state.gain, state.volume), (100, 10));
+
+        // Enabling sends the latest value of each held fader, in CC order
+        manager.set_preamp_mk2_motorized_faders_enabled("Preamp", true).unwrap();
+        assert_eq!(port.sent()[1..], [vec![0xB1, 14, 10], vec![0xB1, 19, 100]]);
+        manager.send_preamp_mk2_parameter("Preamp", PreampMk2Parameter::Treble(cc(5))).unwrap();
+        assert_eq!(port.sent().last().unwrap(), &vec![0xB1, 15, 5]);
+        assert!(manager.preamp_mk2_motorized_faders_enabled("Preamp").unwrap());
+    }
+
+    #[test]
+    fn test_restore_tolerates_disconnected_devices() {
+        let microcosm_port = MockOutputPort::default();
+        let preamp_port = MockOutputPort::default();
+        let mut manager = manager_with_microcosm(&microcosm_port);
+        connect_mock(&mut manager, &preamp_port, PedalType::PreampMk2, "Preamp", 2);
+        set(&mut manager, MicrocosmParameter::Bypass(true));
+
+        let results = manager.bypass_all();
+        assert!(results.iter().all(|result| result.success));
+        assert!(!manager.get_microcosm_state("Microcosm").unwrap().bypass);
+        assert!(manager.get_preamp_mk2_state("Preamp").unwrap().bypass);
+
+        // Bypassing again keeps the first snapshot
+        manager.bypass_all();
+        manager.disconnect("Preamp").unwrap();
+
+        let results = manager.restore_bypass_states();
+        assert_eq!(results.len(), 2);
+        assert!(results[0].success);
+        assert_eq!(results[1].device_name, "Preamp");
+        assert!(!results[1].success);
+        assert!(results[1].error.is_some());
+        assert!(manager.get_microcosm_state("Microcosm").unwrap().bypass);
+
+        // The snapshot is spent
+        assert!(manager.restore_bypass_states().is_empty());
+    }
+
+    #[test]
+    fn test_fake_device_sends_recalls_and_logs_like_hardware() {
+        let mut manager = MidiManager::new().unwrap();
+        manager.connect_fake(PedalType::Microcosm, "Demo Microcosm", 2).unwrap();
+
+        set_on(&mut manager, "Demo Microcosm", MicrocosmParameter::Mix(cc(100)));
+        assert_eq!(manager.get_microcosm_state("Demo Microcosm").unwrap().mix, 100);
+
+        let mut state = manager.get_microcosm_state("Demo Microcosm").unwrap();
+        state.time = 20;
+        manager.recall_microcosm_preset("Demo Microcosm", &state).unwrap();
+        assert_eq!(manager.get_microcosm_state("Demo Microcosm").unwrap().time, 20);
+
+        let log = manager.fake_device_log("Demo Microcosm").unwrap();
+        assert!(log.len() > 1);
+        assert!(log.iter().all(|entry| entry.channel == Some(2)));
+        assert_eq!(log[0].bytes, vec![0xB1, MicrocosmParameter::Mix(cc(100)).cc_number(), 100]);
+    }
+
+    #[test]
+    fn test_fake_device_echoes_ccs_through_the_input_path() {
+        let mut manager = MidiManager::new().unwrap();
+        manager.connect_fake(PedalType::Microcosm, "Demo Microcosm", 1).unwrap();
+
+        set_on(&mut manager, "Demo Microcosm", MicrocosmParameter::Mix(cc(90)));
+
+        let deadline = std::time::Instant::now() + Duration::from_secs(2);
+        while manager.received_cc_count("Demo Microcosm").unwrap() == 0 {
+            assert!(std::time::Instant::now() < deadline, "echo never arrived");
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn test_input_channel_mode_changes_without_reconnecting() {
+        let mut manager = MidiManager::new().unwrap();
+        manager.connect_fake(PedalType::PreampMk2, "Demo Preamp", 4).unwrap();
+        assert_eq!(manager.input_channel_mode("Demo Preamp").unwrap(), InputChannelMode::Exact);
+
+        manager.set_input_channel_mode("Demo Preamp", InputChannelMode::Omni).unwrap();
+        assert_eq!(manager.connected_devices()[0].input_mode, InputChannelMode::Omni);
+        assert_eq!(manager.connected_devices()[0].midi_channel, 4);
+
+        // A bad channel list leaves the mode as it was
+        let err = manager
+            .set_input_channel_mode("Demo Preamp", InputChannelMode::Channels { channels: vec![1, 17] })
+            .unwrap_err();
+        assert!(matches!(err, MidiError::InvalidChannel(17)), "{}", err);
+        assert_eq!(manager.input_channel_mode("Demo Preamp").unwrap(), InputChannelMode::Omni);
+
+        assert!(matches!(
+            manager.set_input_channel_mode("Missing", InputChannelMode::Omni),
+            Err(MidiError::NotConnected(_))
+        ));
+    }
+
+    #[test]
+    fn test_connect_with_bad_input_mode_connects_nothing() {
+        let mut manager = MidiManager::new().unwrap();
+        let mode = InputChannelMode::Channels { channels: vec![] };
+
+        let result = manager.connect_with_input_mode("Demo Preamp", Some(&mode), |m| {
+            m.connect_fake(PedalType::PreampMk2, "Demo Preamp", 4)
+        });
+        assert!(result.is_err());
+        assert!(manager.connected_devices().is_empty());
+
+        let mode = InputChannelMode::Channels { channels: vec![1, 4] };
+        manager
+            .connect_with_input_mode("Demo Preamp", Some(&mode), |m| m.connect_fake(PedalType::PreampMk2, "Demo Preamp", 4))
+            .unwrap();
+        assert_eq!(manager.input_channel_mode("Demo Preamp").unwrap(), mode);
+    }
+
+    #[test]
+    fn test_failed_undo_keeps_its_snapshot() {
+        let mut manager = MidiManager::new().unwrap();
+        manager.set_auto_arm(false);
+        manager.connect_fake(PedalType::Microcosm, "Demo Microcosm", 1).unwrap();
+        manager.record_state_snapshot("Demo Microcosm").unwrap();
+
+        // Not armed, so the recall is refused
+        assert!(matches!(manager.undo_state_change("Demo Microcosm"), Err(MidiError::DeviceNotArmed(_))));
+        assert_eq!(manager.snapshots["Demo Microcosm"].len(), 1);
+
+        manager.arm_device("Demo Microcosm").unwrap();
+        manager.undo_state_change("Demo Microcosm").unwrap();
+        assert!(manager.snapshots["Demo Microcosm"].is_empty());
+    }
+
+    #[test]
+    fn test_recall_waits_for_arming_when_auto_arm_is_off() {
+        let mut manager = MidiManager::new().unwrap();
+        manager.set_auto_arm(false);
+        manager.connect_fake(PedalType::Microcosm, "Demo Microcosm", 1).unwrap();
+        assert!(!manager.connected_devices()[0].armed);
+
+        let mut state = manager.get_microcosm_state("Demo Microcosm").unwrap();
+        state.time = 20;
+        let err = manager.recall_microcosm_preset("Demo Microcosm", &state).unwrap_err();
+        assert!(matches!(err, MidiError::DeviceNotArmed(_)), "{}", err);
+        assert!(manager.fake_device_log("Demo Microcosm").unwrap().is_empty());
+
+        // An explicit user send goes out without arming the device
+        // The note is held outside the lock, so its Note Off can't rely on the override
+        manager.user_initiated("Demo Microcosm", |m| m.start_midi_note("Demo Microcosm", 60, 100)).unwrap();
+        manager.end_midi_note("Demo Microcosm", 60).unwrap();
+        assert_eq!(manager.fake_device_log("Demo Microcosm").unwrap().len(), 2);
+        assert!(!manager.is_armed("Demo Microcosm"));
+
+        manager.arm_device("Demo Microcosm").unwrap();
+        assert!(manager.connected_devices()[0].armed);
+        manager.recall_microcosm_preset("Demo Microcosm", &state).unwrap();
+        assert_eq!(manager.get_microcosm_state("Demo Microcosm").unwrap().time, 20);
+    }
+
+    #[test]
+    fn test_device_pedal_type_needs_a_connection() {
+        let port = MockOutputPort::default();
+        let manager = manager_with_microcosm(&port);
+        assert_eq!(manager.device_pedal_type("Microcosm").unwrap(), PedalType::Microcosm);
+        assert!(matches!(manager.device_pedal_type("Lossy"), Err(MidiError::NotConnected(_))));
+    }
+
+    #[test]
+    fn test_device_info_parallels_device_names() {
+        let mut manager = MidiManager::new().unwrap();
+        manager.set_demo_mode(true);
+        let ports = manager.list_devices().unwrap();
+
+        let names: Vec<&String> = ports.device_info.iter().map(|info| &info.name).collect();
+        assert_eq!(names, ports.devices.iter().collect::<Vec<_>>());
+        let lossy = ports.device_info.iter()
+            .find(|info| info.name == fake_device::demo_port_name(&PedalType::Lossy))
+            .unwrap();
+        assert_eq!((lossy.vendor_id, lossy.is_usb, lossy.is_input), (None, false, false));
+    }
+
+    #[test]
+    fn test_demo_mode_lists_fake_ports_and_keeps_connected_ones() {
+        let mut manager = MidiManager::new().unwrap();
+        manager.set_demo_mode(true);
+        let lossy_port = fake_device::demo_port_name(&PedalType::Lossy);
+        assert!(manager.list_devices().unwrap().devices.contains(&lossy_port));
+
+        manager.connect_on_port(PedalType::Lossy, "Lossy", &lossy_port, 1).unwrap();
+        manager.set_demo_mode(false);
+
+        let devices = manager.list_devices().unwrap().devices;
+        assert!(devices.contains(&lossy_port));
+        assert!(!devices.contains(&fake_device::demo_port_name(&PedalType::Clean)));
+        assert!(manager.fake_device_log("Lossy").is_ok());
+    }
+
+    #[test]
+    fn test_fake_device_log_rejects_real_devices() {
+        let port = MockOutputPort::default();
+        let manager = manager_with_microcosm(&port);
+
+        let err = manager.fake_device_log("Microcosm").unwrap_err().to_string();
+        assert!(err.contains("not a fake device"), "{}", err);
+    }
+
+    #[test]
+    fn test_monitored_pcs_run_their_mappings_and_forward_the_rest() {
+        use crate::midi::pc_remap::{handle_monitored_message, PcAction, PcMapping};
+        use crate::midi::pedals::microcosm::{EffectType, EffectVariation};
+        use std::sync::{Arc, Mutex};
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let library = crate::presets::create_shared_library(dir.path().join("presets.db")).unwrap();
+        let port = MockOutputPort::default();
+        let mut manager = manager_with_microcosm(&port);
+        connect_mock(&mut manager, &MockOutputPort::default(), PedalType::ChromaConsole, "Chroma", 2);
+
+        set(&mut manager, MicrocosmParameter::Mix(cc(100)));
+        let state = manager.connections["Microcosm"].state_json().unwrap();
+        let preset = library.lock().unwrap()
+            .save_preset("Wash".to_string(), "Microcosm".to_string(), None, state, Vec::new())
+            .unwrap();
+        set(&mut manager, MicrocosmParameter::Mix(cc(10)));
+
+        let actions = [
+            ("Microcosm", PcAction::MicrocosmProgram(EffectType::Mosaic, EffectVariation::B)),
+            ("Microcosm", PcAction::RecallPreset(preset.id.to_string())),
+            ("Microcosm", PcAction::Trigger(PedalParameter::Microcosm(MicrocosmParameter::LooperRecord))),
+            ("Chroma", PcAction::RecallPreset(preset.id.to_string())),
+        ];
+        for (program, (device_name, action)) in actions.into_iter().enumerate() {
+            let mapping = PcMapping { program: program as u8, device_name: device_name.to_string(), action };
+            manager.pc_remap_mut().add("FC-4", mapping).unwrap();
+        }
+        let manager = Arc::new(Mutex::new(manager));
+        let feed = |message: &[u8]| {
+            let before = port.sent().len();
+            let outcome = handle_monitored_message(&manager, &library, "FC-4", message);
+            (outcome, port.sent()[before..].to_vec())
+        };
+
+        // Clock, a CC and active sensing aren't program changes
+        for message in [&[0xF8][..], &[0xB0, 9, 5], &[0xFE]] {
+            let (outcome, sent) = feed(message);
+            assert!(outcome.is_none() && sent.is_empty());
+        }
+
+        let (outcome, sent) = feed(&[0xC3, 0]);
+        assert!(matches!(outcome, Some(PcRemapOutcome::Executed(ref e)) if e.channel == 4 && e.error.is_none()));
+        assert_eq!(sent, vec![vec![0xC0, EffectType::Mosaic.program_number(EffectVariation::B)]]);
+
+        let (outcome, sent) = feed(&[0xC0, 1]);
+        assert!(matches!(outcome, Some(PcRemapOutcome::Executed(ref e)) if e.error.is_none()));
+        assert!(sent.contains(&vec![0xB0, 9, 100]));
+
+        let (_, sent) = feed(&[0xC0, 2]);
+        assert_eq!(sent, vec![vec![0xB0, 28, 127]]);
+
+        // A Microcosm preset can't be recalled on a Chroma Console
+        let (outcome, _) = feed(&[0xC0, 3]);
+        assert!(matches!(outcome, Some(PcRemapOutcome::Executed(ref e)) if e.error.is_some()));
+
+        let (outcome, sent) = feed(&[0xC0, 7]);
+        assert!(matches!(outcome, Some(PcRemapOutcome::Forwarded(ref pc)) if pc.program == 7));
+        assert!(sent.is_empty());
+
+        manager.lock().unwrap().pc_remap_mut().set_bypassed("FC-4", true).unwrap();
+        let (outcome, sent) = feed(&[0xC0, 0]);
+        assert!(matches!(outcome, Some(PcRemapOutcome::Forwarded(ref pc)) if pc.program == 0 && pc.channel == 1));
+        assert!(sent.is_empty());
+    }
+
+    #[test]
+    fn test_send_parameter_dispatches_on_the_device() {
+        use crate::midi::pedals::clean::CleanParameter;
+        use crate::midi::pedals::microcosm::{EffectType, EffectVariation};
+
+        let microcosm = MockOutputPort::default();
+        let clean = MockOutputPort::default();
+        let mut manager = manager_with_microcosm(&microcosm);
+        connect_mock(&mut manager, &clean, PedalType::Clean, "Clean", 2);
+
+        manager.send_parameter("Microcosm", MicrocosmParameter::Mix(cc(90)).into()).unwrap();
+        manager.send_parameter("Microcosm", MicrocosmParameter::EffectSelect(EffectType::Mosaic, EffectVariation::C).into()).unwrap();
+        manager.send_parameter("Clean", CleanParameter::Wet(cc(33)).into()).unwrap();
+
+        assert_eq!(microcosm.sent(), vec![
+            vec![0xB0, 9, 90],
+            vec![0xC0, EffectType::Mosaic.program_number(EffectVariation::C)],
+        ]);
+        assert_eq!(clean.sent(), vec![vec![0xB1, 16, 33]]);
+        assert_eq!(manager.get_undo_stack("Clean").unwrap().undo.len(), 1);
+    }
+
+    #[test]
+    fn test_mismatched_parameters_and_states_send_nothing() {
+        use crate::midi::pedals::clean::{CleanParameter, CleanState};
+
+        let microcosm = MockOutputPort::default();
+        let clean = MockOutputPort::default();
+        let mut manager = manager_with_microcosm(&microcosm);
+        connect_mock(&mut manager, &clean, PedalType::Clean, "Clean", 2);
+
+        let error = manager.send_parameter("Microcosm", CleanParameter::Wet(cc(33)).into()).unwrap_err();
+        assert_eq!(error.to_string(), "MIDI error: Device is a Microcosm, not a Clean");
+        let error = manager.send_parameter("Clean", MicrocosmParameter::Mix(cc(90)).into()).unwrap_err();
+        assert_eq!(error.to_string(), "MIDI error: Device is a Clean, not a Microcosm");
+
+        let microcosm_state = manager.get_state("Microcosm").unwrap();
+        assert!(manager.recall_state("Clean", microcosm_state).is_err());
+        assert!(manager.recall_state("Microcosm", CleanState::default().into()).is_err());
+        assert!(matches!(
+            manager.send_parameter("Gone", MicrocosmParameter::Mix(cc(1)).into()),
+            Err(MidiError::NotConnected(_))
+        ));
+        assert!(matches!(manager.get_state("Gone"), Err(MidiError::NotConnected(_))));
+
+        assert!(microcosm.sent().is_empty());
+        assert!(clean.sent().is_empty());
+    }
+
+    #[test]
+    fn test_get_and_recall_state_round_trip() {
+        use crate::midi::pedals::clean::{CleanParameter, CleanState};
+
+        let clean = MockOutputPort::default();
+        let mut manager = MidiManager::new().unwrap();
+        connect_mock(&mut manager, &clean, PedalType::Clean, "Clean", 1);
+
+        manager.send_parameter("Clean", CleanParameter::Dynamics(cc(5)).into()).unwrap();
+        let saved = manager.get_state("Clean").unwrap();
+        assert_eq!(saved.pedal_type(), PedalType::Clean);
+        assert_eq!(CleanState::try_from(saved.clone()).unwrap().dynamics, 5);
+
+        manager.send_parameter("Clean", CleanParameter::Dynamics(cc(120)).into()).unwrap();
+        let sent_before = clean.sent().len();
+        manager.recall_state("Clean", saved.clone()).unwrap();
+        assert!(clean.sent()[sent_before..].contains(&vec![0xB0, 14, 5]));
+        assert_eq!(manager.get_state("Clean").unwrap().to_json().unwrap(), saved.to_json().unwrap());
+        assert_eq!(manager.get_device_state("Clean").unwrap().state, saved.to_json().unwrap());
+    }
+
+    #[test]
+    fn test_preamp_fader_values_clamp_unless_range_is_loose() {
+        use crate::midi::pedals::preamp_mk2::PreampMk2Parameter;
+
+        let port = MockOutputPort::default();
+        let mut manager = MidiManager::new().unwrap();
+        connect_mock(&mut manager, &port, PedalType::PreampMk2, "Preamp", 1);
+
+        manager.send_preamp_mk2_parameter("Preamp", PreampMk2Parameter::Gain(cc(127))).unwrap();
+        manager.send_preamp_mk2_parameter("Preamp", PreampMk2Parameter::Volume(cc(0))).unwrap();
+        manager.send_preamp_mk2_parameter("Preamp", PreampMk2Parameter::Expression(cc(0))).unwrap();
+        assert_eq!(port.sent(), vec![vec![0xB0, 19, 125], vec![0xB0, 14, 2], vec![0xB0, 100, 0]]);
+        assert_eq!(manager.get_preamp_mk2_state("Preamp").unwrap().gain, 125);
+
+        manager.set_preamp_mk2_strict_fader_range("Preamp", false).unwrap();
+        manager.send_preamp_mk2_parameter("Preamp", PreampMk2Parameter::Gain(cc(127))).unwrap();
+        assert_eq!(port.sent().last(), Some(&vec![0xB0, 19, 127]));
+        assert!(manager.set_preamp_mk2_strict_fader_range("Gone", false).is_err());
+    }
+
+    #[test]
+    fn test_preamp_fader_range_holds_for_recalls_and_batches() {
+        use crate::midi::pedals::preamp_mk2::PreampMk2Parameter;
+
+        let port = MockOutputPort::default();
+        let mut manager = MidiManager::new().unwrap();
+        connect_mock(&mut manager, &port, PedalType::PreampMk2, "Preamp", 1);
+        // Volume (14) and gain (19) sends from message `from` on
+        let fader_sends = |from: usize| -> Vec<(u8, u8)> {
+            port.sent()[from..].iter().filter(|m| [14, 19].contains(&m[1])).map(|m| (m[1], m[2])).collect()
+        };
+
+        let mut preset = manager.get_preamp_mk2_state("Preamp").unwrap();
+        preset.gain = 127;
+        preset.volume = 1;
+        manager.recall_preamp_mk2_preset("Preamp", &preset).unwrap();
+        assert_eq!(fader_sends(0), vec![(14, 1), (19, 125)]);
+        assert_eq!(manager.get_preamp_mk2_state("Preamp").unwrap().gain, 125);
+
+        let from = port.sent().len();
+        let batch = vec![PedalParameter::PreampMk2(PreampMk2Parameter::Volume(cc(0)))];
+        manager.send_parameters_batch("Preamp", batch).unwrap();
+        assert_eq!(fader_sends(from), vec![(14, 2)]);
+        assert_eq!(manager.get_preamp_mk2_state("Preamp").unwrap().volume, 2);
+
+        // Undo replays a raw CC; it still goes out within reach
+        let from = port.sent().len();
+        manager.set_preamp_mk2_strict_fader_range("Preamp", false).unwrap();
+        manager.send_preamp_mk2_parameter("Preamp", PreampMk2Parameter::Volume(cc(127))).unwrap();
+        manager.set_preamp_mk2_strict_fader_range("Preamp", true).unwrap();
+        manager.undo_parameter_change("Preamp").unwrap();
+        manager.redo_parameter_change("Preamp").unwrap();
+        assert_eq!(fader_sends(from), vec![(14, 127), (14, 2), (14, 125)]);
+        assert_eq!(manager.get_preamp_mk2_state("Preamp").unwrap().volume, 125);
+        manager.undo_parameter_change("Preamp").unwrap();
+        assert_eq!(manager.get_preamp_mk2_state("Preamp").unwrap().volume, 2);
+    }
+
+    #[test]
+    fn test_gliding_recall_ramps_mix_and_jumps_the_rest() {
+        let port = MockOutputPort::default();
+        let mut manager = manager_with_microcosm(&port);
+        set(&mut manager, MicrocosmParameter::Mix(cc(0)));
+        let mut target = manager.get_microcosm_state("Microcosm").unwrap();
+        target.mix = 127;
+        target.time = 3;
+
+        let sent_before = port.sent().len();
+        let glide = Glide::from_millis(50, PedalType::Microcosm.default_glide_ccs().to_vec());
+        manager.gliding("Microcosm", glide, |manager| manager.recall_microcosm_preset("Microcosm", &target)).unwrap();
+        let sent = port.sent()[sent_before..].to_vec();
+
+        let mix: Vec<u8> = sent.iter().filter(|message| message[1] == 9).map(|message| message[2]).collect();
+        assert_eq!(mix, vec![25, 51, 76, 102, 127]);
+        // The mix ramp comes after everything that jumps
+        let first_mix = sent.iter().position(|message| message[1] == 9).unwrap();
+        assert!(sent[..first_mix].contains(&vec![0xB0, 10, 3]));
+        assert_eq!(manager.get_microcosm_state("Microcosm").unwrap().mix, 127);
+
+        // The glide only lasts for that recall
+        let sent_before = port.sent().len();
+        target.mix = 0;
+        manager.recall_microcosm_preset("Microcosm", &target).unwrap();
+        let mix: Vec<u8> = port.sent()[sent_before..].iter().filter(|message| message[1] == 9).map(|message| message[2]).collect();
+        assert_eq!(mix, vec![0]);
+    }
+
+    #[test]
+    fn test_estimate_default_recall_on_every_pedal_matches_send_delay() {
+        for pedal_type in PedalType::ALL {
+            let port = MockOutputPort::default();
+            let mut manager = MidiManager::new().unwrap();
+            connect_mock(&mut manager, &port, pedal_type.clone(), "Pedal", 1);
+            let state = PedalState::from_json(pedal_type, manager.get_device_state("Pedal").unwrap().state).unwrap();
+
+            let estimate = manager.estimate_recall("Pedal", state.clone(), true, false, None).unwrap();
+
+            let plan_len = manager.connections["Pedal"].recall_plan().len();
+            assert!(plan_len > 0, "{}", pedal_type.as_str());
+            assert_eq!(estimate.message_count, plan_len, "{}", pedal_type.as_str());
+            assert_eq!(
+                estimate.duration_ms,
+                (DEFAULT_SEND_DELAY * plan_len as u32).as_millis() as u64,
+                "{}", pedal_type.as_str()
+            );
+            // Recalling what the pedal already has changes nothing
+            let unchanged = manager.estimate_recall("Pedal", state, true, true, None).unwrap();
+            assert_eq!(unchanged.message_count, 0, "{}", pedal_type.as_str());
+            assert!(port.sent().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_estimate_leaves_believed_state_alone() {
+        let port = MockOutputPort::default();
+        let mut manager = manager_with_microcosm(&port);
+        let mut target = manager.get_microcosm_state("Microcosm").unwrap();
+        target.mix = 127;
+        target.time = 3;
+        let before = manager.get_device_state("Microcosm").unwrap().state;
+
+        let estimate = manager.estimate_recall("Microcosm", PedalState::Microcosm(target), true, true, None).unwrap();
+
+        assert_eq!(estimate.message_count, 2);
+        assert_eq!(manager.get_device_state("Microcosm").unwrap().state, before);
+        assert!(port.sent().is_empty());
+    }
+
+    #[test]
+    fn test_send_progress_counts_the_steps_the_estimate_promised() {
+        let port = MockOutputPort::default();
+        let mut manager = manager_with_microcosm(&port);
+        let mut target = manager.get_microcosm_state("Microcosm").unwrap();
+        target.mix = 127;
+        let glide = Glide::from_millis(50, PedalType::Microcosm.default_glide_ccs().to_vec());
+        let estimate = manager
+            .estimate_recall("Microcosm", PedalState::Microcosm(target.clone()), true, false, glide.as_ref())
+            .unwrap();
+
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let sink = reported.clone();
+        manager.connections.get_mut("Microcosm").unwrap().connection_mut().progress =
+            Some(Arc::new(move |progress: &SendProgress| sink.lock().unwrap().push(*progress)));
+        manager.gliding("Microcosm", glide, |manager| manager.recall_microcosm_preset("Microcosm", &target)).unwrap();
+
+        let reported = reported.lock().unwrap();
+        assert_eq!(port.sent().len(), estimate.message_count);
+        assert_eq!(reported.len(), estimate.message_count);
+        assert!(reported.iter().enumerate().all(|(i, progress)| progress.step == i + 1 && progress.total == estimate.message_count));
+        assert_eq!(reported.last().unwrap().elapsed_ms, estimate.duration_ms);
+        assert_eq!(reported.last().unwrap().remaining_ms, 0);
+    }
+
+    #[test]
+    fn test_preamp_and_cxm_state_getters_return_recalled_state() {
+        let port = MockOutputPort::default();
+        let mut manager = MidiManager::new().unwrap();
+        connect_mock(&mut manager, &port, PedalType::PreampMk2, "Preamp", 1);
+        connect_mock(&mut manager, &port, PedalType::Cxm1978, "CXM", 2);
+
+        let preamp = PreampMk2State { gain: 100, treble: 12, ..Default::default() };
+        manager.recall_preamp_mk2_preset("Preamp", &preamp).unwrap();
+        let cxm = Cxm1978State {
+            mix: 90,
+            reverb_type: crate::midi::pedals::cxm1978::ReverbType::Hall,
+            ..Default::default()
+        };
+        manager.recall_cxm1978_preset("CXM", &cxm).unwrap();
+
+        let state = manager.get_preamp_mk2_state("Preamp").unwrap();
+        assert_eq!((state.gain, state.treble), (100, 12));
+        let state = manager.get_cxm1978_state("CXM").unwrap();
+        assert_eq!(state.mix, 90);
+        assert!(matches!(state.reverb_type, crate::midi::pedals::cxm1978::ReverbType::Hall));
+    }
+
+    #[test]
+    fn test_preamp_and_cxm_state_getters_reject_other_devices() {
+        let port = MockOutputPort::default();
+        let mut manager = MidiManager::new().unwrap();
+        connect_mock(&mut manager, &port, PedalType::PreampMk2, "Preamp", 1);
+
+        assert!(manager.get_cxm1978_state("Preamp").is_err());
+        assert!(matches!(manager.get_preamp_mk2_state("CXM"), Err(MidiError::NotConnected(_))));
+        assert!(matches!(manager.get_cxm1978_state("CXM"), Err(MidiError::NotConnected(_))));
+    }
+
+    #[test]
+    fn test_channel_verification_probe_leaves_believed_state_alone() {
+        let port = MockOutputPort::default();
+        let mut manager = manager_with_microcosm(&port);
+        manager.channel_activity.insert("Microcosm".to_string(), ChannelActivity::new(1));
+
+        manager.begin_channel_verification("Microcosm").unwrap();
+
+        assert_eq!(port.sent(), vec![vec![0xB0, connection_test::PROBE_CC, connection_test::PROBE_VALUE]]);
+    }
+
+    #[test]
+    fn test_set_device_channel_keeps_device_when_new_route_fails() {
+        let mut manager = MidiManager::new().unwrap();
+        manager.fake_ports.insert("Chain".to_string(), FakePort::new(None));
+        manager.connect_on_port(PedalType::Microcosm, "Microcosm", "Chain", 1).unwrap();
+        manager.connect_on_port(PedalType::Lossy, "Lossy", "Chain", 2).unwrap();
+
+        // Channel 2 is taken on the shared port
+        assert!(manager.set_device_channel("Microcosm", 2).is_err());
+        assert!(manager.is_connected("Microcosm"));
+        manager.send_microcosm_parameter("Microcosm", MicrocosmParameter::Mix(cc(10))).unwrap();
+        let log = manager.fake_device_log("Microcosm").unwrap();
+        assert_eq!(log.last().unwrap().channel, Some(1));
+
+        manager.set_device_channel("Microcosm", 3).unwrap();
+        manager.send_microcosm_parameter("Microcosm", MicrocosmParameter::Mix(cc(20))).unwrap();
+        assert_eq!(manager.fake_device_log("Microcosm").unwrap().last().unwrap().channel, Some(3));
+        assert!(manager.fake_device_log("Lossy").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_set_device_channel_keeps_input_mode() {
+        let mut manager = MidiManager::new().unwrap();
+        manager.connect_fake(PedalType::PreampMk2, "Demo Preamp", 4).unwrap();
+        manager.set_input_channel_mode("Demo Preamp", InputChannelMode::Omni).unwrap();
+
+        manager.set_device_channel("Demo Preamp", 6).unwrap();
+
+        assert_eq!(manager.input_channel_mode("Demo Preamp").unwrap(), InputChannelMode::Omni);
+        // The new listener follows the carried-over mode too
+        let activity = &manager.channel_activity["Demo Preamp"];
+        activity.record(0xB8);
+        assert!(activity.verified());
+    }
+
+    fn input_listener(device_name: &str, channel: u8, mode: InputChannelMode) -> InputListener {
+        let input_mode = SharedInputMode::default();
+        input_mode.set(mode);
+        InputListener {
+            device_name: device_name.to_string(),
+            pedal_type: PedalType::Microcosm,
+            channel,
+            activity: ChannelActivity::with_input_mode(channel, input_mode.clone()),
+            input_mode,
+            state_updates: None,
+        }
+    }
+
+    #[test]
+    fn test_dispatch_incoming_routes_by_input_mode() {
+        let demux = ChannelDemux::default();
+        let exact = input_listener("Exact", 1, InputChannelMode::Exact);
+        let listed = input_listener("Listed", 2, InputChannelMode::Channels { channels: vec![5] });
+        demux.add(1, exact.clone()).unwrap();
+        demux.add(2, listed.clone()).unwrap();
+        let beat_clock = RecallScheduler::default().beat_clock();
+
+        MidiManager::dispatch_incoming(None, &demux, &beat_clock, "Chain", &[0xB4, 20, 64]); // channel 5
+        MidiManager::dispatch_incoming(None, &demux, &beat_clock, "Chain", &[0xB6, 20, 64]); // channel 7
+        MidiManager::dispatch_incoming(None, &demux, &beat_clock, "Chain", &[0xB0, 20, 64]); // channel 1
+
+        assert_eq!(listed.activity.message_count(), 1);
+        assert_eq!(listed.activity.observed_mismatch(), None);
+        assert_eq!(exact.activity.message_count(), 1);
+
+        // Clock goes to the beat clock, not a device
+        MidiManager::dispatch_incoming(None, &demux, &beat_clock, "Chain", &[0xFA]);
+        MidiManager::dispatch_incoming(None, &demux, &beat_clock, "Chain", &[0xF8]);
+        assert_eq!(beat_clock.tick(), Some(1));
+        assert_eq!(exact.activity.message_count() + listed.activity.message_count(), 2);
+    }
+
+    #[test]
+    fn test_cc_event_reports_the_channel_it_arrived_on() {
+        let omni = input_listener("Omni", 4, InputChannelMode::Omni);
+        let event = omni.cc_event(&[0xB8, 20, 64]).unwrap();
+        assert_eq!(event.channel, 9);
+        assert_eq!((event.cc_number, event.value), (20, 64));
+
+        let exact = input_listener("Exact", 4, InputChannelMode::Exact);
+        assert!(exact.cc_event(&[0xB8, 20, 64]).is_none());
+        assert_eq!(exact.cc_event(&[0xB3, 20, 64]).unwrap().channel, 4);
+
+        // Only complete Control Changes are reported
+        assert!(omni.cc_event(&[0xC3, 2]).is_none());
+        assert!(omni.cc_event(&[0xB3, 20]).is_none());
+    }
+
+    #[test]
+    fn test_ordered_worker_applies_ccs_in_arrival_order() {
+        let applied = Arc::new(Mutex::new(Vec::new()));
+        let sink = applied.clone();
+        let (done, finished) = std::sync::mpsc::channel();
+        let worker = OrderedWorker::spawn(move |(cc_number, value): (u8, u8)| {
+            sink.lock().unwrap().push(value);
+            if value == 127 {
+                done.send(cc_number).unwrap();
+            }
+        });
+
+        // A fast knob sweep
+        for value in 0..=127 {
+            worker.push((18, value));
+        }
+        finished.recv_timeout(Duration::from_secs(5)).unwrap();
+
+        assert_eq!(*applied.lock().unwrap(), (0..=127).collect::<Vec<u8>>());
+    }
+
+    #[test]
+    fn test_gen_loss_preset_save_sends_cc_111_for_valid_slots() {
+        let port = MockOutputPort::default();
+        let mut manager = MidiManager::new().unwrap();
+        connect_mock(&mut manager, &port, PedalType::GenLossMkii, "Gen Loss", 3);
+
+        manager.save_gen_loss_preset("Gen Loss", 1).unwrap();
+        manager.save_gen_loss_preset("Gen Loss", 122).unwrap();
+        assert!(manager.save_gen_loss_preset("Gen Loss", 0).is_err());
+        assert!(manager.save_gen_loss_preset("Gen Loss", 123).is_err());
+        assert_eq!(port.sent(), vec![vec![0xB2, 111, 1], vec![0xB2, 111, 122]]);
+
+        let microcosm = MockOutputPort::default();
+        connect_mock(&mut manager, &microcosm, PedalType::Microcosm, "Microcosm", 1);
+        assert!(manager.save_gen_loss_preset("Microcosm", 5).is_err());
+        assert!(microcosm.sent().is_empty());
+    }
+
+    #[test]
+    fn test_exit_actions_skip_devices_past_the_budget() {
+        use crate::midi::exit_actions::{ExitAction, ExitActionSettings, ExitStatus};
+
+        let port = MockOutputPort::default();
+        let mut manager = MidiManager::new().unwrap();
+        connect_mock(&mut manager, &port, PedalType::Microcosm, "Microcosm A", 1);
+        connect_mock(&mut manager, &port, PedalType::Microcosm, "Microcosm B", 2);
+        connect_mock(&mut manager, &port, PedalType::Lossy, "Lossy", 3);
+        let mut settings = ExitActionSettings {
+            enabled: false,
+            actions: BTreeMap::from([("Microcosm".to_string(), ExitAction::Bypass)]),
+        };
+        manager.set_exit_actions(settings.clone()).unwrap();
+        assert!(manager.run_exit_actions(&HashMap::new(), Duration::from_secs(2)).is_empty());
+        assert!(port.sent().is_empty());
+
+        settings.enabled = true;
+        manager.set_exit_actions(settings).unwrap();
+        // Each bypass is one CC and a send delay, so only the first fits in 30ms
+        let results = manager.run_exit_actions(&HashMap::new(), Duration::from_millis(30));
+        let statuses: Vec<(&str, &ExitStatus)> = results.iter().map(|r| (r.device_name.as_str(), &r.status)).collect();
+        assert_eq!(statuses, vec![
+            ("Lossy", &ExitStatus::Ignored),
+            ("Microcosm A", &ExitStatus::Done),
+            ("Microcosm B", &ExitStatus::Skipped),
+        ]);
+        // Only channel 1 (Microcosm A) heard anything
+        assert_eq!(port.sent().len(), 1);
+        assert_eq!(port.sent()[0][0], 0xB0);
+    }
+
+    #[test]
+    fn test_shut_down_sends_exit_actions_before_closing_ports() {
+        use crate::midi::exit_actions::{ExitAction, ExitActionSettings, ExitStatus};
+
+        let port = MockOutputPort::default();
+        let mut manager = manager_with_microcosm(&port);
+        manager.set_exit_actions(ExitActionSettings {
+            enabled: true,
+            actions: BTreeMap::from([("Microcosm".to_string(), ExitAction::Bypass)]),
+        }).unwrap();
+
+        let results = manager.shut_down(&HashMap::new(), Duration::from_secs(2));
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].status, ExitStatus::Done);
+        assert_eq!(port.sent().len(), 1);
+        assert!(manager.connected_devices().is_empty());
+        assert!(!manager.outputs.is_open("Microcosm"));
+
+        // A second pass (window destroyed, then the app exits) finds nothing left to do
+        assert!(manager.disconnect_all().is_empty());
+        assert!(manager.run_exit_actions(&HashMap::new(), Duration::from_secs(2)).is_empty());
+        assert_eq!(port.sent().len(), 1);
+    }
+
+    #[test]
+    fn test_exit_actions_recall_shutdown_presets() {
+        use crate::midi::exit_actions::{ExitAction, ExitActionSettings, ExitStatus, ShutdownPreset};
+
+        let port = MockOutputPort::default();
+        let mut manager = MidiManager::new().unwrap();
+        connect_mock(&mut manager, &port, PedalType::ChromaConsole, "Chroma Console", 1);
+        connect_mock(&mut manager, &port, PedalType::Cxm1978, "CXM", 2);
+        manager.set_exit_actions(ExitActionSettings {
+            enabled: true,
+            actions: BTreeMap::from([
+                ("ChromaConsole".to_string(), ExitAction::RecallPreset("quiet".to_string())),
+                ("Cxm1978".to_string(), ExitAction::RecallPreset("deleted".to_string())),
+            ]),
+        }).unwrap();
+        let quiet = ChromaConsoleState { output_level: 10, ..ChromaConsoleState::default() };
+        let presets = HashMap::from([("quiet".to_string(), ShutdownPreset {
+            pedal_type: PedalType::ChromaConsole,
+            parameters: serde_json::to_value(&quiet).unwrap(),
+        })]);
+
+        let results = manager.run_exit_actions(&presets, Duration::from_secs(2));
+        assert_eq!(results[0].device_name, "CXM");
+        assert_eq!(results[0].status, ExitStatus::Failed);
+        assert!(results[0].error.as_deref().unwrap().contains("deleted"));
+        assert_eq!(results[1].device_name, "Chroma Console");
+        assert_eq!(results[1].status, ExitStatus::Done);
+        assert_eq!(manager.get_chroma_console_state("Chroma Console").unwrap().output_level, 10);
+    }
+
+    #[test]
+    fn test_chroma_console_save_reloads_the_state_into_the_slot() {
+        let port = MockOutputPort::default();
+        let mut manager = MidiManager::new().unwrap();
+        connect_mock(&mut manager, &port, PedalType::ChromaConsole, "Chroma Console", 1);
+        manager.send_chroma_console_parameter("Chroma Console", ChromaConsoleParameter::Mix(cc(99))).unwrap();
+        assert!(manager.save_chroma_console_preset("Chroma Console", 39).is_err());
+        assert!(manager.save_chroma_console_preset("Chroma Console", 80).is_err());
+
+        let sent_before = port.sent().len();
+        let instructions = manager.save_chroma_console_preset("Chroma Console", 51).unwrap();
+        assert!(instructions.starts_with("After the parameters are loaded, hold the main footswitch for 2 seconds"));
+        assert!(instructions.contains("(slot 12)"), "{}", instructions);
+
+        let sent = &port.sent()[sent_before..];
+        assert_eq!(sent[0], vec![0xC0, 51]);
+        // Mix is CC 70
+        assert!(sent.contains(&vec![0xB0, 70, 99]));
+    }
 }
@@ -0,0 +1,318 @@
+// Standard MIDI File export of a preset recall
+// Renders the same SendPlan a recall sends as a type 0 file, so a preset can be dragged
+// into a DAW as a clip. Pauses between CCs become delta times at a fixed PPQ and tempo,
+// which keeps the pedal's input buffer as safe on playback as it is on a live recall.
+
+use crate::midi::error::{MidiError, MidiResult};
+use crate::midi::pedals::billy_strings_wombtone::BillyStringsWombtoneState;
+use crate::midi::pedals::brothers_am::BrothersAmState;
+use crate::midi::pedals::chroma_console::{BypassMode, BypassState, ChromaConsoleState};
+use crate::midi::pedals::clean::CleanState;
+use crate::midi::pedals::cxm1978::Cxm1978State;
+use crate::midi::pedals::dark_world::DarkWorldState;
+use crate::midi::pedals::gen_loss_mkii::GenLossMkiiState;
+use crate::midi::pedals::habit::HabitState;
+use crate::midi::pedals::lossy::LossyState;
+use crate::midi::pedals::microcosm::MicrocosmState;
+use crate::midi::pedals::mood_mkii::MoodMkiiState;
+use crate::midi::pedals::onward::OnwardState;
+use crate::midi::pedals::preamp_mk2::PreampMk2State;
+use crate::midi::pedals::reverse_mode_c::ReverseModeCState;
+use crate::midi::pedals::source_audio_collider::ColliderState;
+use crate::midi::pedals::thermae::ThermaeState;
+use crate::midi::send_plan::{SendPlan, DEFAULT_SEND_DELAY};
+use crate::midi::{CcValue, PedalType};
+use std::path::Path;
+use std::time::Duration;
+
+/// Ticks per quarter note
+pub const PPQ: u16 = 480;
+
+/// Tempo written to the file; delta times are computed against it
+pub const TEMPO_BPM: u32 = 120;
+
+const MICROS_PER_QUARTER: u32 = 60_000_000 / TEMPO_BPM;
+
+/// What a preset recall sends: an optional program change first, then the plan's CCs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PresetSends {
+    pub program: Option<u8>,
+    pub plan: SendPlan,
+}
+
+impl PresetSends {
+    /// The sends for a library preset's parameters, as `recall_preset_json` would make them.
+    /// Microcosm presets select their effect by program change, so that goes first.
+    pub fn for_preset(pedal_type: PedalType, parameters: serde_json::Value) -> MidiResult<Self> {
+        fn parse<T: serde::de::DeserializeOwned>(value: serde_json::Value) -> MidiResult<T> {
+            serde_json::from_value(value)
+                .map_err(|e| MidiError::Other(format!("Failed to read preset: {}", e)))
+        }
+
+        let (program, plan) = match pedal_type {
+            PedalType::Microcosm => {
+                let state: MicrocosmState = parse(parameters)?;
+                (Some(state.current_effect.program_number(state.current_variation)), state.recall_plan())
+            }
+            // DIP switches are device configuration - a preset recall leaves them alone
+            PedalType::GenLossMkii => (None, parse::<GenLossMkiiState>(parameters)?.sound_recall_plan()),
+            PedalType::ChromaConsole => {
+                // Footswitch mode is a device setting; a dual bypass preset only makes sense in dual mode
+                let state: ChromaConsoleState = parse(parameters)?;
+                let bypass_mode = if state.bypass_state == BypassState::DualBypass {
+                    BypassMode::Dual
+                } else {
+                    BypassMode::default()
+                };
+                (None, state.recall_plan(bypass_mode))
+            }
+            PedalType::PreampMk2 => (None, parse::<PreampMk2State>(parameters)?.recall_plan()),
+            PedalType::Cxm1978 => (None, parse::<Cxm1978State>(parameters)?.recall_plan()),
+            PedalType::MoodMkii => (None, parse::<MoodMkiiState>(parameters)?.recall_plan()),
+            PedalType::BillyStringsWombtone => (None, parse::<BillyStringsWombtoneState>(parameters)?.recall_plan()),
+            PedalType::Lossy => (None, parse::<LossyState>(parameters)?.recall_plan()),
+            PedalType::BrothersAm => (None, parse::<BrothersAmState>(parameters)?.recall_plan()),
+            PedalType::ReverseModeC => (None, parse::<ReverseModeCState>(parameters)?.recall_plan()),
+            PedalType::Clean => (None, parse::<CleanState>(parameters)?.recall_plan()),
+            PedalType::Onward => (None, parse::<OnwardState>(parameters)?.recall_plan()),
+            PedalType::Thermae => (None, parse::<ThermaeState>(parameters)?.recall_plan()),
+            PedalType::DarkWorld => (None, parse::<DarkWorldState>(parameters)?.recall_plan()),
+            PedalType::Habit => (None, parse::<HabitState>(parameters)?.recall_plan()),
+            PedalType::Collider => (None, parse::<ColliderState>(parameters)?.recall_plan()),
+        };
+        Ok(Self { program, plan })
+    }
+
+    /// Render as a type 0 Standard MIDI File on `midi_channel` (1-16)
+    pub fn to_midi_file(&self, midi_channel: u8, track_name: &str) -> MidiResult<Vec<u8>> {
+        if !(1..=16).contains(&midi_channel) {
+            return Err(MidiError::InvalidChannel(midi_channel));
+        }
+        let status_channel = midi_channel - 1;
+
+        let mut track = Vec::new();
+        // Tempo first so the delta times mean what they were computed as
+        write_event(&mut track, 0, &[0xFF, 0x51, 0x03]);
+        track.extend_from_slice(&MICROS_PER_QUARTER.to_be_bytes()[1..]);
+        if !track_name.is_empty() {
+            write_event(&mut track, 0, &[0xFF, 0x03]);
+            write_variable_length(&mut track, track_name.len() as u32);
+            track.extend_from_slice(track_name.as_bytes());
+        }
+
+        let mut delta = 0;
+        if let Some(program) = self.program {
+            let program = checked_data_byte(program, "a program number (0-127)")?;
+            write_event(&mut track, delta, &[0xC0 | status_channel, program]);
+            delta = duration_to_ticks(DEFAULT_SEND_DELAY);
+        }
+        for step in self.plan.steps() {
+            let cc_number = checked_data_byte(step.cc_number, "a CC number (0-127)")?;
+            let value = checked_data_byte(step.value, &format!("a CC value (0-127) for CC {}", step.cc_number))?;
+            write_event(&mut track, delta, &[0xB0 | status_channel, cc_number, value]);
+            delta = duration_to_ticks(step.delay());
+        }
+        // End of track after the last pause, so the clip is as long as the recall
+        write_event(&mut track, delta, &[0xFF, 0x2F, 0x00]);
+
+        let mut bytes = Vec::with_capacity(22 + track.len());
+        bytes.extend_from_slice(b"MThd");
+        bytes.extend_from_slice(&6u32.to_be_bytes());
+        bytes.extend_from_slice(&0u16.to_be_bytes()); // format 0
+        bytes.extend_from_slice(&1u16.to_be_bytes()); // one track
+        bytes.extend_from_slice(&PPQ.to_be_bytes());
+        bytes.extend_from_slice(b"MTrk");
+        bytes.extend_from_slice(&(track.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(&track);
+        Ok(bytes)
+    }
+
+    /// Render and write to `path`
+    pub fn write_midi_file(&self, path: &Path, midi_channel: u8, track_name: &str) -> MidiResult<()> {
+        let bytes = self.to_midi_file(midi_channel, track_name)?;
+        std::fs::write(path, bytes)
+            .map_err(|e| MidiError::Other(format!("Failed to write {}: {}", path.display(), e)))
+    }
+}
+
+/// Ticks for a pause at `TEMPO_BPM`, rounded to the nearest tick
+pub fn duration_to_ticks(duration: Duration) -> u32 {
+    let ticks = duration.as_micros() * u128::from(PPQ) / u128::from(MICROS_PER_QUARTER);
+    let remainder = duration.as_micros() * u128::from(PPQ) % u128::from(MICROS_PER_QUARTER);
+    let rounded = if remainder * 2 >= u128::from(MICROS_PER_QUARTER) { ticks + 1 } else { ticks };
+    u32::try_from(rounded).unwrap_or(u32::MAX)
+}
+
+fn checked_data_byte(value: u8, expected: &str) -> MidiResult<u8> {
+    CcValue::new(value).map(|value| value.get()).map_err(|_| MidiError::InvalidValue {
+        expected: expected.to_string(),
+        actual: value.into(),
+    })
+}
+
+fn write_event(track: &mut Vec<u8>, delta: u32, event: &[u8]) {
+    write_variable_length(track, delta);
+    track.extend_from_slice(event);
+}
+
+/// Big-endian base-128, high bit set on every byte but the last
+fn write_variable_length(out: &mut Vec<u8>, value: u32) {
+    let value = value.min(0x0FFF_FFFF);
+    let mut groups = [0u8; 4];
+    let mut count = 0;
+    let mut rest = value;
+    loop {
+        groups[count] = (rest & 0x7F) as u8;
+        count += 1;
+        rest >>= 7;
+        if rest == 0 {
+            break;
+        }
+    }
+    for index in (0..count).rev() {
+        out.push(if index == 0 { groups[index] } else { groups[index] | 0x80 });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::midi::pedals::microcosm::{EffectType, EffectVariation};
+
+    /// A channel event read back out of a file: (absolute tick, status, data bytes)
+    type Event = (u32, u8, Vec<u8>);
+
+    fn read_variable_length(bytes: &[u8], pos: &mut usize) -> u32 {
+        let mut value = 0u32;
+        loop {
+            let byte = bytes[*pos];
+            *pos += 1;
+            value = (value << 7) | u32::from(byte & 0x7F);
+            if byte & 0x80 == 0 {
+                return value;
+            }
+        }
+    }
+
+    /// Minimal type 0 reader: checks the header and returns the channel events and the end-of-track tick
+    fn read_midi_file(bytes: &[u8]) -> (Vec<Event>, u32) {
+        assert_eq!(&bytes[0..4], b"MThd");
+        assert_eq!(u32::from_be_bytes(bytes[4..8].try_into().unwrap()), 6);
+        assert_eq!(u16::from_be_bytes(bytes[8..10].try_into().unwrap()), 0);
+        assert_eq!(u16::from_be_bytes(bytes[10..12].try_into().unwrap()), 1);
+        assert_eq!(u16::from_be_bytes(bytes[12..14].try_into().unwrap()), PPQ);
+        assert_eq!(&bytes[14..18], b"MTrk");
+        let length = u32::from_be_bytes(bytes[18..22].try_into().unwrap()) as usize;
+        let track = &bytes[22..];
+        assert_eq!(track.len(), length);
+
+        let mut events = Vec::new();
+        let mut pos = 0;
+        let mut tick = 0;
+        loop {
+            tick += read_variable_length(track, &mut pos);
+            let status = track[pos];
+            pos += 1;
+            match status {
+                0xFF => {
+                    let kind = track[pos];
+                    pos += 1;
+                    let length = read_variable_length(track, &mut pos) as usize;
+                    if kind == 0x51 {
+                        assert_eq!(&track[pos..pos + length], &MICROS_PER_QUARTER.to_be_bytes()[1..]);
+                    }
+                    pos += length;
+                    if kind == 0x2F {
+                        assert_eq!(pos, track.len());
+                        return (events, tick);
+                    }
+                }
+                0xC0..=0xCF => {
+                    events.push((tick, status, track[pos..pos + 1].to_vec()));
+                    pos += 1;
+                }
+                0xB0..=0xBF => {
+                    events.push((tick, status, track[pos..pos + 2].to_vec()));
+                    pos += 2;
+                }
+                other => panic!("unexpected status byte {:#04x}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_variable_length_encoding() {
+        for (value, expected) in [
+            (0u32, vec![0x00]),
+            (0x7F, vec![0x7F]),
+            (0x80, vec![0x81, 0x00]),
+            (0x3FFF, vec![0xFF, 0x7F]),
+            (0x0FFF_FFFF, vec![0xFF, 0xFF, 0xFF, 0x7F]),
+        ] {
+            let mut out = Vec::new();
+            write_variable_length(&mut out, value);
+            assert_eq!(out, expected, "{:#x}", value);
+            assert_eq!(read_variable_length(&out, &mut 0), value);
+        }
+    }
+
+    #[test]
+    fn test_plan_round_trips_in_order_with_spacing() {
+        let plan = SendPlan::in_order([(102, 127), (9, 64), (14, 3)]).with_delay(9, Duration::from_millis(100));
+        let sends = PresetSends { program: None, plan };
+
+        let (events, end) = read_midi_file(&sends.to_midi_file(3, "Pad").unwrap());
+        let default_ticks = duration_to_ticks(DEFAULT_SEND_DELAY);
+        let long_ticks = duration_to_ticks(Duration::from_millis(100));
+        assert_eq!(events, vec![
+            (0, 0xB2, vec![102, 127]),
+            (default_ticks, 0xB2, vec![9, 64]),
+            (default_ticks + long_ticks, 0xB2, vec![14, 3]),
+        ]);
+        assert_eq!(end, 2 * default_ticks + long_ticks);
+        // 120 BPM at 480 PPQ is 0.96 ticks per ms
+        assert_eq!(duration_to_ticks(Duration::from_millis(500)), 480);
+        assert_eq!(default_ticks, 19);
+    }
+
+    #[test]
+    fn test_microcosm_preset_sends_program_change_first() {
+        let state = MicrocosmState {
+            current_effect: EffectType::Mosaic,
+            current_variation: EffectVariation::C,
+            ..MicrocosmState::default()
+        };
+        let sends = PresetSends::for_preset(PedalType::Microcosm, serde_json::to_value(&state).unwrap()).unwrap();
+        assert_eq!(sends.plan, state.recall_plan());
+
+        let (events, _) = read_midi_file(&sends.to_midi_file(16, "").unwrap());
+        let program = EffectType::Mosaic.program_number(EffectVariation::C);
+        assert_eq!(events[0], (0, 0xCF, vec![program]));
+        let ccs: Vec<(u8, u8)> = events[1..].iter().map(|(_, status, data)| {
+            assert_eq!(*status, 0xBF);
+            (data[0], data[1])
+        }).collect();
+        let planned: Vec<(u8, u8)> = sends.plan.steps().iter().map(|step| (step.cc_number, step.value)).collect();
+        assert_eq!(ccs, planned);
+    }
+
+    #[test]
+    fn test_gen_loss_export_leaves_dip_switches_out() {
+        let state = GenLossMkiiState::default();
+        let sends = PresetSends::for_preset(PedalType::GenLossMkii, serde_json::to_value(&state).unwrap()).unwrap();
+        assert_eq!(sends.program, None);
+        assert_eq!(sends.plan, state.sound_recall_plan());
+    }
+
+    #[test]
+    fn test_rejects_bad_channel_and_values() {
+        let sends = PresetSends { program: None, plan: SendPlan::in_order([(9, 64)]) };
+        assert!(matches!(sends.to_midi_file(0, ""), Err(MidiError::InvalidChannel(0))));
+        assert!(matches!(sends.to_midi_file(17, ""), Err(MidiError::InvalidChannel(17))));
+
+        let sends = PresetSends { program: None, plan: SendPlan::in_order([(9, 200)]) };
+        assert!(matches!(sends.to_midi_file(1, ""), Err(MidiError::InvalidValue { actual: 200, .. })));
+
+        assert!(PresetSends::for_preset(PedalType::Lossy, serde_json::json!("not a state")).is_err());
+    }
+}
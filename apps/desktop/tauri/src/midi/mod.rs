@@ -1,15 +1,62 @@
 // MIDI module for Librarian
 // Handles MIDI device detection, connection, and communication
 
+pub mod ab_compare;
+pub mod auto_discovery;
+pub mod bypass;
+pub mod cc_decode;
+pub mod cc_value;
+pub mod channel_verification;
+pub mod clock;
+pub mod connect_retry;
+pub mod connection_test;
 pub mod device_detection;
+pub mod device_labels;
+pub mod device_state;
 pub mod error;
+pub mod exit_actions;
+pub mod fake_device;
+pub mod firmware;
 pub mod identity;
 pub mod manager;
+pub mod midi_file;
+pub mod nrpn;
+pub mod parameter_batch;
+pub mod parameter_history;
+pub mod pc_remap;
+pub mod pedal_state;
 pub mod pedals;
+pub mod port_metadata;
+pub mod routing;
+pub mod safety;
+pub mod scheduler;
+pub mod send_plan;
+pub mod state_diff;
+pub mod state_snapshot;
+pub mod virtual_port;
 
 // Re-export commonly used types
-pub use device_detection::{list_midi_devices, MidiDeviceInfo};
+pub use ab_compare::{AbSlot, AbStatus};
+pub use auto_discovery::{auto_discover_pedals, AutoDiscoveryResult};
+pub use bypass::{BypassControl, BypassResult};
+pub use cc_value::CcValue;
+pub use connect_retry::{ConnectOutcome, ConnectionAttempt, RetryOptions};
+pub use device_detection::{list_midi_devices, MidiDeviceInfo, MidiPortList};
+pub use device_state::DeviceState;
 pub use error::{MidiError, MidiResult};
-pub use identity::{request_device_identity, DeviceIdentity};
+pub use fake_device::FakeLogEntry;
+pub use firmware::{FirmwareVersion, FirmwareWarning};
+pub use identity::{request_device_identity, DeviceIdentity, IdentityDiagnostics, IdentityRequestOptions, IdentityRequestResult};
+pub use midi_file::PresetSends;
+pub use nrpn::Nrpn;
 pub use manager::{MidiManager, SharedMidiManager, create_shared_manager, ConnectedDevice, PedalType};
 pub use pedals::{Microcosm, GenLossMkii};
+pub use parameter_batch::PedalParameter;
+pub use pedal_state::PedalState;
+pub use routing::InputChannelMode;
+pub use parameter_history::{HistoryEntry, UndoStack};
+pub use scheduler::{ScheduleSpec, ScheduledRecall};
+pub use send_plan::{Glide, PlanEstimate, PlannedCc, SendPlan, SendProgress};
+pub use state_diff::{FieldChange, StateDiff};
+pub use state_snapshot::StateSnapshot;
+pub use virtual_port::{VirtualPort, VirtualPortMessage, virtual_ports_supported};
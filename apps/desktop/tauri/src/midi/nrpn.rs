@@ -0,0 +1,76 @@
+// Non-Registered Parameter Numbers (NRPN)
+// A CC carries 7 bits; pedals that need finer control (delay times) take 14-bit values as
+// NRPN. One NRPN write is four CCs on the device's channel: parameter number MSB (CC 99)
+// and LSB (CC 98) select the parameter, then data entry MSB (CC 6) and LSB (CC 38) set it.
+// Because it is plain CCs, an NRPN can sit in a SendPlan and go through the usual send path.
+
+use crate::midi::error::{MidiError, MidiResult};
+
+pub const CC_NRPN_MSB: u8 = 99;
+pub const CC_NRPN_LSB: u8 = 98;
+pub const CC_DATA_ENTRY_MSB: u8 = 6;
+pub const CC_DATA_ENTRY_LSB: u8 = 38;
+
+/// Largest 14-bit value (parameter numbers and values alike)
+pub const MAX_14_BIT: u16 = 0x3FFF;
+
+/// One 14-bit parameter write
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Nrpn {
+    pub parameter: u16,
+    pub value: u16,
+}
+
+impl Nrpn {
+    pub fn new(parameter: u16, value: u16) -> MidiResult<Self> {
+        for (what, number) in [("NRPN parameter number", parameter), ("NRPN value", value)] {
+            if number > MAX_14_BIT {
+                return Err(MidiError::Other(format!(
+                    "Invalid {}: {} (must be 0-{})", what, number, MAX_14_BIT
+                )));
+            }
+        }
+        Ok(Self { parameter, value })
+    }
+
+    /// The four CCs that make up this write, in send order
+    pub fn cc_messages(&self) -> [(u8, u8); 4] {
+        let (parameter_msb, parameter_lsb) = split_14_bit(self.parameter);
+        let (value_msb, value_lsb) = split_14_bit(self.value);
+        [
+            (CC_NRPN_MSB, parameter_msb),
+            (CC_NRPN_LSB, parameter_lsb),
+            (CC_DATA_ENTRY_MSB, value_msb),
+            (CC_DATA_ENTRY_LSB, value_lsb),
+        ]
+    }
+}
+
+/// (MSB, LSB) 7-bit halves of a 14-bit value; bits above 14 are dropped
+pub fn split_14_bit(value: u16) -> (u8, u8) {
+    (((value >> 7) & 0x7F) as u8, (value & 0x7F) as u8)
+}
+
+/// The 14-bit value from its 7-bit halves
+pub fn join_14_bit(msb: u8, lsb: u8) -> u16 {
+    (u16::from(msb & 0x7F) << 7) | u16::from(lsb & 0x7F)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cc_messages_split_parameter_and_value() {
+        let nrpn = Nrpn::new(0x0102, 10_000).unwrap();
+        assert_eq!(nrpn.cc_messages(), [(99, 2), (98, 2), (6, 78), (38, 16)]);
+        assert_eq!(join_14_bit(78, 16), 10_000);
+    }
+
+    #[test]
+    fn test_rejects_values_over_14_bits() {
+        assert!(Nrpn::new(1, MAX_14_BIT).is_ok());
+        assert!(Nrpn::new(1, MAX_14_BIT + 1).is_err());
+        assert!(Nrpn::new(MAX_14_BIT + 1, 0).is_err());
+    }
+}
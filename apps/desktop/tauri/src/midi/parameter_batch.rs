@@ -0,0 +1,117 @@
+// Parameter batches for macro controls
+// A macro knob sets several parameters at once. Sent as separate commands, each one took
+// the manager lock in turn and could interleave with other sends. A batch is checked
+// against the device's pedal type up front, then goes out as one ordered, paced run with
+// a single history entry and a single `device-state-changed` event.
+
+use crate::midi::manager::PedalType;
+use crate::midi::pedals::billy_strings_wombtone::BillyStringsWombtoneParameter;
+use crate::midi::pedals::brothers_am::BrothersAmParameter;
+use crate::midi::pedals::chroma_console::ChromaConsoleParameter;
+use crate::midi::pedals::clean::CleanParameter;
+use crate::midi::pedals::cxm1978::Cxm1978Parameter;
+use crate::midi::pedals::gen_loss_mkii::GenLossMkiiParameter;
+use crate::midi::pedals::lossy::LossyParameter;
+use crate::midi::pedals::microcosm::MicrocosmParameter;
+use crate::midi::pedals::mood_mkii::MoodMkiiParameter;
+use crate::midi::pedals::onward::OnwardParameter;
+use crate::midi::pedals::preamp_mk2::PreampMk2Parameter;
+use crate::midi::pedals::reverse_mode_c::ReverseModeCParameter;
+use crate::midi::pedals::thermae::ThermaeParameter;
+use crate::midi::pedals::dark_world::DarkWorldParameter;
+use crate::midi::pedals::habit::HabitParameter;
+use crate::midi::pedals::source_audio_collider::ColliderParameter;
+use serde::{Deserialize, Serialize};
+
+/// A parameter for any pedal, tagged with the pedal type it belongs to
+/// (`{ "pedal_type": "GenLossMkii", "param": { "Wow": 64 } }`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "pedal_type", content = "param")]
+pub enum PedalParameter {
+    Microcosm(MicrocosmParameter),
+    GenLossMkii(GenLossMkiiParameter),
+    ChromaConsole(ChromaConsoleParameter),
+    PreampMk2(PreampMk2Parameter),
+    Cxm1978(Cxm1978Parameter),
+    MoodMkii(MoodMkiiParameter),
+    BillyStringsWombtone(BillyStringsWombtoneParameter),
+    Lossy(LossyParameter),
+    BrothersAm(BrothersAmParameter),
+    ReverseModeC(ReverseModeCParameter),
+    Clean(CleanParameter),
+    Onward(OnwardParameter),
+    Thermae(ThermaeParameter),
+    DarkWorld(DarkWorldParameter),
+    Habit(HabitParameter),
+    Collider(ColliderParameter),
+}
+
+impl PedalParameter {
+    pub fn pedal_type(&self) -> PedalType {
+        match self {
+            PedalParameter::Microcosm(_) => PedalType::Microcosm,
+            PedalParameter::GenLossMkii(_) => PedalType::GenLossMkii,
+            PedalParameter::ChromaConsole(_) => PedalType::ChromaConsole,
+            PedalParameter::PreampMk2(_) => PedalType::PreampMk2,
+            PedalParameter::Cxm1978(_) => PedalType::Cxm1978,
+            PedalParameter::MoodMkii(_) => PedalType::MoodMkii,
+            PedalParameter::BillyStringsWombtone(_) => PedalType::BillyStringsWombtone,
+            PedalParameter::Lossy(_) => PedalType::Lossy,
+            PedalParameter::BrothersAm(_) => PedalType::BrothersAm,
+            PedalParameter::ReverseModeC(_) => PedalType::ReverseModeC,
+            PedalParameter::Clean(_) => PedalType::Clean,
+            PedalParameter::Onward(_) => PedalType::Onward,
+            PedalParameter::Thermae(_) => PedalType::Thermae,
+            PedalParameter::DarkWorld(_) => PedalType::DarkWorld,
+            PedalParameter::Habit(_) => PedalType::Habit,
+            PedalParameter::Collider(_) => PedalType::Collider,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PedalParameter::Microcosm(param) => param.name(),
+            PedalParameter::GenLossMkii(param) => param.name(),
+            PedalParameter::ChromaConsole(param) => param.name(),
+            PedalParameter::PreampMk2(param) => param.name(),
+            PedalParameter::Cxm1978(param) => param.name(),
+            PedalParameter::MoodMkii(param) => param.name(),
+            PedalParameter::BillyStringsWombtone(param) => param.name(),
+            PedalParameter::Lossy(param) => param.name(),
+            PedalParameter::BrothersAm(param) => param.name(),
+            PedalParameter::ReverseModeC(param) => param.name(),
+            PedalParameter::Clean(param) => param.name(),
+            PedalParameter::Onward(param) => param.name(),
+            PedalParameter::Thermae(param) => param.name(),
+            PedalParameter::DarkWorld(param) => param.name(),
+            PedalParameter::Habit(param) => param.name(),
+            PedalParameter::Collider(param) => param.name(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserializes_tagged_by_pedal_type() {
+        let param: PedalParameter = serde_json::from_value(serde_json::json!({
+            "pedal_type": "GenLossMkii",
+            "param": { "Wow": 64 },
+        }))
+        .unwrap();
+
+        assert_eq!(param.pedal_type(), PedalType::GenLossMkii);
+        assert!(matches!(param, PedalParameter::GenLossMkii(GenLossMkiiParameter::Wow(v)) if v.get() == 64));
+    }
+
+    #[test]
+    fn test_out_of_range_values_are_rejected() {
+        let result = serde_json::from_value::<PedalParameter>(serde_json::json!({
+            "pedal_type": "GenLossMkii",
+            "param": { "Wow": 200 },
+        }));
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,334 @@
+// Parameter change history for the live editor
+// Every parameter send, recall and program change is recorded per device so the
+// editor can undo/redo. Entries keep the full believed state on both sides of the
+// change; parameter entries re-send just their CC (or NRPN), composite entries re-send everything.
+
+use crate::midi::nrpn::Nrpn;
+use serde::Serialize;
+use std::collections::VecDeque;
+
+/// Undo entries kept per device - older ones are dropped
+pub const MAX_HISTORY_ENTRIES: usize = 200;
+
+/// What a history entry changed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum HistoryChange {
+    /// Single parameter send
+    Parameter {
+        parameter: String,
+        cc_number: u8,
+        previous_value: Option<u8>,  // None if the CC isn't part of the believed state (triggers)
+        new_value: u8,
+    },
+    /// Single 14-bit parameter send (e.g. a Collider delay time), as one NRPN write
+    Nrpn {
+        parameter: String,
+        nrpn_parameter: u16,
+        previous_value: Option<u16>,
+        new_value: u16,
+    },
+    /// Preset recall - every parameter at once
+    Recall,
+    /// Program change - the pedal loads a stored preset
+    ProgramChange { program: u8 },
+    /// Several parameters sent together (a macro control), named in send order
+    Batch { parameters: Vec<String> },
+}
+
+impl HistoryChange {
+    pub fn parameter(name: &str, cc_number: u8, new_value: u8) -> Self {
+        HistoryChange::Parameter {
+            parameter: name.to_string(),
+            cc_number,
+            previous_value: None,
+            new_value,
+        }
+    }
+
+    /// Composite entries are undone by re-sending the full prior state
+    pub fn is_composite(&self) -> bool {
+        !matches!(self, HistoryChange::Parameter { .. } | HistoryChange::Nrpn { .. })
+    }
+
+    /// The CCs that move the device to one side of this change: `new` picks the new value,
+    /// otherwise the previous one. None for composite changes and missing previous values.
+    fn ccs(&self, new: bool) -> Option<Vec<(u8, u8)>> {
+        match self {
+            HistoryChange::Parameter { cc_number, previous_value, new_value, .. } => {
+                let value = if new { Some(*new_value) } else { *previous_value };
+                value.map(|value| vec![(*cc_number, value)])
+            }
+            HistoryChange::Nrpn { nrpn_parameter, previous_value, new_value, .. } => {
+                let value = if new { Some(*new_value) } else { *previous_value };
+                value.map(|value| Nrpn { parameter: *nrpn_parameter, value }.cc_messages().to_vec())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// One recorded change
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryEntry {
+    pub device_name: String,
+    #[serde(flatten)]
+    pub change: HistoryChange,
+    pub timestamp: i64,  // Unix timestamp (ms)
+    #[serde(skip)]
+    pub(crate) before: serde_json::Value,
+    #[serde(skip)]
+    pub(crate) after: serde_json::Value,
+}
+
+/// What to send to move the device to one side of an entry
+#[derive(Debug, PartialEq)]
+pub struct HistoryStep<'a> {
+    /// CCs to re-send in order (one for a parameter, four for an NRPN), or None to
+    /// re-send the full state
+    pub ccs: Option<Vec<(u8, u8)>>,
+    /// Believed state after the step
+    pub state: &'a serde_json::Value,
+}
+
+/// Undo/redo stacks for the frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct UndoStack {
+    pub undo: Vec<HistoryEntry>,  // Oldest first
+    pub redo: Vec<HistoryEntry>,  // Next redo last
+}
+
+/// Bounded undo/redo history for one device
+#[derive(Debug, Default)]
+pub struct ParameterHistory {
+    undo: VecDeque<HistoryEntry>,
+    redo: Vec<HistoryEntry>,
+}
+
+impl ParameterHistory {
+    /// Record a new change. Clears the redo stack.
+    pub fn record(&mut self, entry: HistoryEntry) {
+        if self.undo.len() == MAX_HISTORY_ENTRIES {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(entry);
+        self.redo.clear();
+    }
+
+    /// Undo the latest change using `apply`. The entry only moves to the redo
+    /// stack if `apply` succeeds. Returns the undone entry, or None if empty.
+    pub fn undo<F, E>(&mut self, apply: F) -> Result<Option<HistoryEntry>, E>
+    where
+        F: FnOnce(HistoryStep<'_>) -> Result<(), E>,
+    {
+        let Some(entry) = self.undo.pop_back() else {
+            return Ok(None);
+        };
+
+        // No previous value to send - restore the state as a whole
+        let ccs = entry.change.ccs(false);
+
+        if let Err(e) = apply(HistoryStep { ccs, state: &entry.before }) {
+            self.undo.push_back(entry);
+            return Err(e);
+        }
+
+        self.redo.push(entry.clone());
+        Ok(Some(entry))
+    }
+
+    /// Redo the latest undone change using `apply`
+    pub fn redo<F, E>(&mut self, apply: F) -> Result<Option<HistoryEntry>, E>
+    where
+        F: FnOnce(HistoryStep<'_>) -> Result<(), E>,
+    {
+        let Some(entry) = self.redo.pop() else {
+            return Ok(None);
+        };
+
+        let ccs = entry.change.ccs(true);
+
+        if let Err(e) = apply(HistoryStep { ccs, state: &entry.after }) {
+            self.redo.push(entry);
+            return Err(e);
+        }
+
+        self.undo.push_back(entry.clone());
+        Ok(Some(entry))
+    }
+
+    pub fn stack(&self) -> UndoStack {
+        UndoStack {
+            undo: self.undo.iter().cloned().collect(),
+            redo: self.redo.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn param_entry(cc_number: u8, previous: u8, new: u8) -> HistoryEntry {
+        HistoryEntry {
+            device_name: "Microcosm".to_string(),
+            change: HistoryChange::Parameter {
+                parameter: "Activity".to_string(),
+                cc_number,
+                previous_value: Some(previous),
+                new_value: new,
+            },
+            timestamp: 0,
+            before: json!({ "activity": previous }),
+            after: json!({ "activity": new }),
+        }
+    }
+
+    fn recall_entry() -> HistoryEntry {
+        HistoryEntry {
+            device_name: "Microcosm".to_string(),
+            change: HistoryChange::Recall,
+            timestamp: 0,
+            before: json!({ "activity": 30, "repeats": 40 }),
+            after: json!({ "activity": 100, "repeats": 110 }),
+        }
+    }
+
+    /// Apply steps to a fake device: (last CC sent, full-state resends, current state)
+    struct FakeDevice {
+        sent_cc: Vec<(u8, u8)>,
+        full_recalls: usize,
+        state: serde_json::Value,
+    }
+
+    impl FakeDevice {
+        fn new() -> Self {
+            Self { sent_cc: vec![], full_recalls: 0, state: json!(null) }
+        }
+
+        fn apply(&mut self, step: HistoryStep<'_>) -> Result<(), String> {
+            match step.ccs {
+                Some(ccs) => self.sent_cc.extend(ccs),
+                None => self.full_recalls += 1,
+            }
+            self.state = step.state.clone();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_interleaved_undo_redo() {
+        let mut history = ParameterHistory::default();
+        let mut device = FakeDevice::new();
+
+        history.record(param_entry(14, 10, 20));
+        history.record(param_entry(14, 20, 30));
+        history.record(param_entry(14, 30, 40));
+
+        // Undo twice: 40 -> 30 -> 20
+        history.undo(|s| device.apply(s)).unwrap();
+        history.undo(|s| device.apply(s)).unwrap();
+        assert_eq!(device.sent_cc, vec![(14, 30), (14, 20)]);
+
+        // Redo once: back to 30
+        history.redo(|s| device.apply(s)).unwrap();
+        assert_eq!(device.sent_cc.last(), Some(&(14, 30)));
+        assert_eq!(device.state, json!({ "activity": 30 }));
+
+        let stack = history.stack();
+        assert_eq!(stack.undo.len(), 2);
+        assert_eq!(stack.redo.len(), 1);
+
+        // A new change drops the remaining redo
+        history.record(param_entry(14, 30, 50));
+        assert!(history.stack().redo.is_empty());
+        assert!(history.redo(|s| device.apply(s)).unwrap().is_none());
+
+        // Undo walks back through the new change, then the older ones
+        history.undo(|s| device.apply(s)).unwrap();
+        history.undo(|s| device.apply(s)).unwrap();
+        history.undo(|s| device.apply(s)).unwrap();
+        assert_eq!(device.sent_cc[device.sent_cc.len() - 3..], [(14, 30), (14, 20), (14, 10)]);
+        assert!(history.undo(|s| device.apply(s)).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_composite_recall_entry_restores_full_state() {
+        let mut history = ParameterHistory::default();
+        let mut device = FakeDevice::new();
+
+        history.record(param_entry(14, 10, 30));
+        history.record(recall_entry());
+
+        let undone = history.undo(|s| device.apply(s)).unwrap().unwrap();
+        assert!(undone.change.is_composite());
+        assert_eq!(device.full_recalls, 1);
+        assert!(device.sent_cc.is_empty());
+        assert_eq!(device.state, json!({ "activity": 30, "repeats": 40 }));
+
+        history.redo(|s| device.apply(s)).unwrap();
+        assert_eq!(device.full_recalls, 2);
+        assert_eq!(device.state, json!({ "activity": 100, "repeats": 110 }));
+    }
+
+    #[test]
+    fn test_nrpn_entry_resends_only_its_nrpn() {
+        let mut history = ParameterHistory::default();
+        let mut device = FakeDevice::new();
+
+        history.record(HistoryEntry {
+            device_name: "Collider".to_string(),
+            change: HistoryChange::Nrpn {
+                parameter: "Delay A Time".to_string(),
+                nrpn_parameter: 1,
+                previous_value: Some(500),
+                new_value: 10_000,
+            },
+            timestamp: 0,
+            before: json!({ "time": 500 }),
+            after: json!({ "time": 10_000 }),
+        });
+
+        let undone = history.undo(|s| device.apply(s)).unwrap().unwrap();
+        assert!(!undone.change.is_composite());
+        assert_eq!(device.full_recalls, 0);
+        assert_eq!(device.sent_cc, Nrpn { parameter: 1, value: 500 }.cc_messages().to_vec());
+
+        history.redo(|s| device.apply(s)).unwrap();
+        assert_eq!(device.sent_cc[4..], Nrpn { parameter: 1, value: 10_000 }.cc_messages());
+        assert_eq!(device.state, json!({ "time": 10_000 }));
+    }
+
+    #[test]
+    fn test_failed_apply_keeps_entry() {
+        let mut history = ParameterHistory::default();
+        history.record(param_entry(14, 10, 20));
+
+        let result = history.undo(|_| Err("send failed".to_string()));
+        assert!(result.is_err());
+        assert_eq!(history.stack().undo.len(), 1);
+        assert!(history.stack().redo.is_empty());
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let mut history = ParameterHistory::default();
+        for i in 0..(MAX_HISTORY_ENTRIES + 5) {
+            history.record(param_entry(14, (i % 128) as u8, ((i + 1) % 128) as u8));
+        }
+
+        let stack = history.stack();
+        assert_eq!(stack.undo.len(), MAX_HISTORY_ENTRIES);
+        // Oldest five were dropped
+        assert_eq!(
+            stack.undo[0].change,
+            HistoryChange::Parameter {
+                parameter: "Activity".to_string(),
+                cc_number: 14,
+                previous_value: Some(5),
+                new_value: 6,
+            }
+        );
+    }
+}
@@ -0,0 +1,342 @@
+// Program change remapping for monitored inputs
+// A foot controller that only sends PCs 0-3 can drive anything Librarian can do: each
+// monitored input port gets a table translating incoming PC n into an action on a connected
+// pedal. Mapped PCs run their action instead of being passed on; unmapped ones (or every
+// PC while the input's table is bypassed) are forwarded to the frontend untouched.
+// Tables are kept as JSON in the app data dir.
+
+use crate::midi::error::{MidiError, MidiResult};
+use crate::midi::manager::{PedalType, SharedMidiManager};
+use crate::midi::parameter_batch::PedalParameter;
+use crate::midi::pedals::microcosm::{EffectType, EffectVariation};
+use crate::presets::{PresetId, SharedPresetLibrary};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Remap file inside the app data dir
+pub const PC_REMAP_FILE: &str = "pc_remap.json";
+
+/// Monitored input list inside the app data dir
+pub const MONITORED_INPUTS_FILE: &str = "monitored_inputs.json";
+
+/// Emitted when a mapped PC runs its action
+pub const PC_REMAP_EXECUTED_EVENT: &str = "pc-remap-executed";
+
+/// Emitted for PCs a monitored input passes on (unmapped, or the table is bypassed)
+pub const MONITORED_PC_EVENT: &str = "monitored-pc-received";
+
+/// What an incoming PC turns into
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PcAction {
+    /// Recall a library preset by ID (looked up when the PC arrives, so edits are picked up)
+    RecallPreset(String),
+    /// Jump a Microcosm to an effect and variation
+    MicrocosmProgram(EffectType, EffectVariation),
+    /// Send one parameter, e.g. a looper trigger
+    Trigger(PedalParameter),
+}
+
+/// One row of an input's table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PcMapping {
+    pub program: u8,
+    /// Connected device the action goes to
+    pub device_name: String,
+    pub action: PcAction,
+}
+
+/// One monitored input's table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InputRemap {
+    pub input_port: String,
+    /// Forward every PC as if nothing were mapped
+    pub bypassed: bool,
+    /// Sorted by program
+    pub mappings: Vec<PcMapping>,
+}
+
+/// Every input's table, sorted by port name. Changes are written through to the file when
+/// there is one.
+#[derive(Debug, Default)]
+pub struct PcRemapStore {
+    path: Option<PathBuf>,
+    inputs: Vec<InputRemap>,
+}
+
+impl PcRemapStore {
+    /// Load tables from (and save future changes to) this file; a missing file means none yet
+    pub fn load(path: PathBuf) -> MidiResult<Self> {
+        let inputs = if path.exists() {
+            let contents = fs::read_to_string(&path)
+                .map_err(|e| MidiError::Other(format!("Failed to read PC remap tables: {}", e)))?;
+            serde_json::from_str(&contents)
+                .map_err(|e| MidiError::Other(format!("Failed to parse PC remap tables: {}", e)))?
+        } else {
+            Vec::new()
+        };
+        Ok(Self { path: Some(path), inputs })
+    }
+
+    pub fn list(&self) -> &[InputRemap] {
+        &self.inputs
+    }
+
+    pub fn get(&self, input_port: &str) -> Option<&InputRemap> {
+        self.inputs.iter().find(|input| input.input_port == input_port)
+    }
+
+    /// Add a mapping. A PC already mapped on that input is rejected, not replaced.
+    pub fn add(&mut self, input_port: &str, mapping: PcMapping) -> MidiResult<PcMapping> {
+        if mapping.program > 127 {
+            return Err(MidiError::InvalidValue {
+                expected: "a program number (0-127)".to_string(),
+                actual: mapping.program.into(),
+            });
+        }
+        let input = self.input_mut(input_port);
+        let index = match input.mappings.binary_search_by_key(&mapping.program, |existing| existing.program) {
+            Ok(_) => {
+                return Err(MidiError::Other(format!(
+                    "PC {} on '{}' is already mapped - delete that mapping first", mapping.program, input_port
+                )))
+            }
+            Err(index) => index,
+        };
+        input.mappings.insert(index, mapping.clone());
+        self.persist()?;
+        Ok(mapping)
+    }
+
+    pub fn delete(&mut self, input_port: &str, program: u8) -> MidiResult<()> {
+        let missing = || MidiError::Other(format!("PC {} on '{}' isn't mapped", program, input_port));
+        let input = self.inputs.iter_mut().find(|input| input.input_port == input_port).ok_or_else(missing)?;
+        let before = input.mappings.len();
+        input.mappings.retain(|mapping| mapping.program != program);
+        if input.mappings.len() == before {
+            return Err(missing());
+        }
+        self.inputs.retain(|input| !input.mappings.is_empty() || input.bypassed);
+        self.persist()
+    }
+
+    pub fn set_bypassed(&mut self, input_port: &str, bypassed: bool) -> MidiResult<()> {
+        self.input_mut(input_port).bypassed = bypassed;
+        self.inputs.retain(|input| !input.mappings.is_empty() || input.bypassed);
+        self.persist()
+    }
+
+    /// The mapping a PC on this input runs, unless the table is bypassed
+    pub fn lookup(&self, input_port: &str, program: u8) -> Option<&PcMapping> {
+        let input = self.get(input_port).filter(|input| !input.bypassed)?;
+        input.mappings.iter().find(|mapping| mapping.program == program)
+    }
+
+    fn input_mut(&mut self, input_port: &str) -> &mut InputRemap {
+        let index = match self.inputs.binary_search_by(|input| input.input_port.as_str().cmp(input_port)) {
+            Ok(index) => index,
+            Err(index) => {
+                self.inputs.insert(index, InputRemap {
+                    input_port: input_port.to_string(),
+                    bypassed: false,
+                    mappings: Vec::new(),
+                });
+                index
+            }
+        };
+        &mut self.inputs[index]
+    }
+
+    fn persist(&self) -> MidiResult<()> {
+        let Some(path) = &self.path else { return Ok(()) };
+        save_tables(path, &self.inputs)
+    }
+}
+
+fn save_tables(path: &Path, inputs: &[InputRemap]) -> MidiResult<()> {
+    let contents = serde_json::to_string_pretty(inputs)
+        .map_err(|e| MidiError::Other(format!("Failed to serialize PC remap tables: {}", e)))?;
+    fs::write(path, contents)
+        .map_err(|e| MidiError::Other(format!("Failed to write PC remap tables: {}", e)))
+}
+
+/// Read the saved list of monitored input ports; a missing file means none yet
+pub fn load_monitored_inputs(path: &Path) -> MidiResult<Vec<String>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let contents = fs::read_to_string(path)
+        .map_err(|e| MidiError::Other(format!("Failed to read monitored inputs: {}", e)))?;
+    serde_json::from_str(&contents)
+        .map_err(|e| MidiError::Other(format!("Failed to parse monitored inputs: {}", e)))
+}
+
+/// Write the list of monitored input ports, replacing the previous file
+pub fn save_monitored_inputs(path: &Path, ports: &[String]) -> MidiResult<()> {
+    let contents = serde_json::to_string_pretty(ports)
+        .map_err(|e| MidiError::Other(format!("Failed to serialize monitored inputs: {}", e)))?;
+    fs::write(path, contents)
+        .map_err(|e| MidiError::Other(format!("Failed to write monitored inputs: {}", e)))
+}
+
+/// A PC a monitored input passed on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitoredPc {
+    pub input_port: String,
+    pub channel: u8,
+    pub program: u8,
+}
+
+/// A mapped PC that ran its action
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PcRemapExecution {
+    pub input_port: String,
+    pub channel: u8,
+    pub mapping: PcMapping,
+    /// Why the action failed, if it did
+    pub error: Option<String>,
+}
+
+/// What a monitored input did with a message
+#[derive(Debug, Clone)]
+pub enum PcRemapOutcome {
+    Executed(PcRemapExecution),
+    Forwarded(MonitoredPc),
+}
+
+/// Handle one raw message heard on a monitored input. Only Program Changes are acted on;
+/// anything else is ignored (None). The manager and library are never locked together.
+pub fn handle_monitored_message(
+    manager: &SharedMidiManager,
+    library: &SharedPresetLibrary,
+    input_port: &str,
+    message: &[u8],
+) -> Option<PcRemapOutcome> {
+    let (&status, data) = message.split_first()?;
+    if !(0xC0..=0xCF).contains(&status) {
+        return None;
+    }
+    let program = *data.first()?;
+    let channel = (status & 0x0F) + 1;
+
+    let mapping = manager.lock().ok()?.pc_remap().lookup(input_port, program).cloned();
+    let outcome = match mapping {
+        Some(mapping) => {
+            let error = execute(manager, library, &mapping).err().map(|e| e.to_string());
+            if let Some(error) = &error {
+                eprintln!("❌ PC {} on '{}' failed: {}", program, input_port, error);
+            }
+            PcRemapOutcome::Executed(PcRemapExecution {
+                input_port: input_port.to_string(),
+                channel,
+                mapping,
+                error,
+            })
+        }
+        None => PcRemapOutcome::Forwarded(MonitoredPc { input_port: input_port.to_string(), channel, program }),
+    };
+
+    if let Ok(manager) = manager.lock() {
+        manager.emit_pc_remap_outcome(&outcome);
+    }
+    Some(outcome)
+}
+
+fn execute(manager: &SharedMidiManager, library: &SharedPresetLibrary, mapping: &PcMapping) -> MidiResult<()> {
+    match &mapping.action {
+        PcAction::RecallPreset(preset_id) => {
+            let preset = {
+                let library = library.lock().map_err(|e| MidiError::Other(e.to_string()))?;
+                library.get_preset(&PresetId::new(preset_id.clone())).map_err(|e| MidiError::Other(e.to_string()))?
+            };
+            let pedal_type = PedalType::from_name(&preset.pedal_type)
+                .ok_or_else(|| MidiError::Other(format!("Unknown pedal type: {}", preset.pedal_type)))?;
+            let mut manager = manager.lock().map_err(|e| MidiError::Other(e.to_string()))?;
+            manager.ensure_pedal_type(&mapping.device_name, &pedal_type)?;
+            manager.begin_journal_operation("pc_remap");
+            manager.recall_preset_json(&mapping.device_name, pedal_type, preset.parameters)
+        }
+        PcAction::MicrocosmProgram(effect, variation) => {
+            let mut manager = manager.lock().map_err(|e| MidiError::Other(e.to_string()))?;
+            manager.begin_journal_operation("pc_remap");
+            manager.send_microcosm_program_change(&mapping.device_name, effect.program_number(*variation))
+        }
+        PcAction::Trigger(param) => {
+            let mut manager = manager.lock().map_err(|e| MidiError::Other(e.to_string()))?;
+            manager.begin_journal_operation("pc_remap");
+            manager.send_parameters_batch(&mapping.device_name, vec![param.clone()])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::midi::pedals::microcosm::MicrocosmParameter;
+    use tempfile::TempDir;
+
+    fn mapping(program: u8) -> PcMapping {
+        PcMapping {
+            program,
+            device_name: "Microcosm".to_string(),
+            action: PcAction::MicrocosmProgram(EffectType::Mosaic, EffectVariation::A),
+        }
+    }
+
+    #[test]
+    fn test_tables_persist_and_reject_collisions() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(PC_REMAP_FILE);
+
+        let mut store = PcRemapStore::load(path.clone()).unwrap();
+        store.add("FC-4", mapping(2)).unwrap();
+        store.add("FC-4", mapping(0)).unwrap();
+        store.add("Board", PcMapping {
+            action: PcAction::Trigger(PedalParameter::Microcosm(MicrocosmParameter::LooperRecord)),
+            ..mapping(0)
+        }).unwrap();
+        assert!(store.add("FC-4", mapping(2)).is_err());
+        assert!(store.add("FC-4", mapping(128)).is_err());
+
+        let reloaded = PcRemapStore::load(path).unwrap();
+        let ports: Vec<&str> = reloaded.list().iter().map(|input| input.input_port.as_str()).collect();
+        assert_eq!(ports, vec!["Board", "FC-4"]);
+        let programs: Vec<u8> = reloaded.get("FC-4").unwrap().mappings.iter().map(|m| m.program).collect();
+        assert_eq!(programs, vec![0, 2]);
+        assert!(matches!(
+            reloaded.lookup("Board", 0).unwrap().action,
+            PcAction::Trigger(PedalParameter::Microcosm(MicrocosmParameter::LooperRecord))
+        ));
+
+        store.delete("Board", 0).unwrap();
+        assert!(store.delete("Board", 0).is_err());
+        assert!(store.get("Board").is_none());
+    }
+
+    #[test]
+    fn test_bypass_hides_mappings() {
+        let mut store = PcRemapStore::default();
+        store.add("FC-4", mapping(1)).unwrap();
+        store.set_bypassed("FC-4", true).unwrap();
+        assert!(store.lookup("FC-4", 1).is_none());
+        store.set_bypassed("FC-4", false).unwrap();
+        assert!(store.lookup("FC-4", 1).is_some());
+        assert!(store.lookup("FC-4", 2).is_none());
+        assert!(store.lookup("Other", 1).is_none());
+    }
+
+    #[test]
+    fn test_monitored_inputs_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(MONITORED_INPUTS_FILE);
+        assert!(load_monitored_inputs(&path).unwrap().is_empty());
+
+        let ports = vec!["Board".to_string(), "FC-4".to_string()];
+        save_monitored_inputs(&path, &ports).unwrap();
+        assert_eq!(load_monitored_inputs(&path).unwrap(), ports);
+    }
+}
@@ -0,0 +1,194 @@
+// Typed state for any pedal
+// `PedalState` is to whole states what `PedalParameter` is to single parameters: one value
+// that can hold any pedal's state, so sending, reading and recalling can each be one
+// manager method that dispatches on the device instead of one per pedal.
+// Serialized like `DeviceState` (`{ "pedal_type": "Lossy", "state": { ... } }`).
+
+use crate::midi::error::{MidiError, MidiResult};
+use crate::midi::manager::PedalType;
+use crate::midi::parameter_batch::PedalParameter;
+use crate::midi::pedals::billy_strings_wombtone::{BillyStringsWombtoneParameter, BillyStringsWombtoneState};
+use crate::midi::pedals::brothers_am::{BrothersAmParameter, BrothersAmState};
+use crate::midi::pedals::chroma_console::{ChromaConsoleParameter, ChromaConsoleState};
+use crate::midi::pedals::clean::{CleanParameter, CleanState};
+use crate::midi::pedals::cxm1978::{Cxm1978Parameter, Cxm1978State};
+use crate::midi::pedals::dark_world::{DarkWorldParameter, DarkWorldState};
+use crate::midi::pedals::gen_loss_mkii::{GenLossMkiiParameter, GenLossMkiiState};
+use crate::midi::pedals::habit::{HabitParameter, HabitState};
+use crate::midi::pedals::lossy::{LossyParameter, LossyState};
+use crate::midi::pedals::microcosm::{MicrocosmParameter, MicrocosmState};
+use crate::midi::pedals::mood_mkii::{MoodMkiiParameter, MoodMkiiState};
+use crate::midi::pedals::onward::{OnwardParameter, OnwardState};
+use crate::midi::pedals::preamp_mk2::{PreampMk2Parameter, PreampMk2State};
+use crate::midi::pedals::reverse_mode_c::{ReverseModeCParameter, ReverseModeCState};
+use crate::midi::pedals::source_audio_collider::{ColliderParameter, ColliderState};
+use crate::midi::pedals::thermae::{ThermaeParameter, ThermaeState};
+use serde::{Deserialize, Serialize};
+
+/// A full state for any pedal, tagged with the pedal type it belongs to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "pedal_type", content = "state")]
+pub enum PedalState {
+    Microcosm(MicrocosmState),
+    GenLossMkii(GenLossMkiiState),
+    ChromaConsole(ChromaConsoleState),
+    PreampMk2(PreampMk2State),
+    Cxm1978(Cxm1978State),
+    MoodMkii(MoodMkiiState),
+    BillyStringsWombtone(BillyStringsWombtoneState),
+    Lossy(LossyState),
+    BrothersAm(BrothersAmState),
+    ReverseModeC(ReverseModeCState),
+    Clean(CleanState),
+    Onward(OnwardState),
+    Thermae(ThermaeState),
+    DarkWorld(DarkWorldState),
+    Habit(HabitState),
+    Collider(ColliderState),
+}
+
+impl PedalState {
+    pub fn pedal_type(&self) -> PedalType {
+        match self {
+            PedalState::Microcosm(_) => PedalType::Microcosm,
+            PedalState::GenLossMkii(_) => PedalType::GenLossMkii,
+            PedalState::ChromaConsole(_) => PedalType::ChromaConsole,
+            PedalState::PreampMk2(_) => PedalType::PreampMk2,
+            PedalState::Cxm1978(_) => PedalType::Cxm1978,
+            PedalState::MoodMkii(_) => PedalType::MoodMkii,
+            PedalState::BillyStringsWombtone(_) => PedalType::BillyStringsWombtone,
+            PedalState::Lossy(_) => PedalType::Lossy,
+            PedalState::BrothersAm(_) => PedalType::BrothersAm,
+            PedalState::ReverseModeC(_) => PedalType::ReverseModeC,
+            PedalState::Clean(_) => PedalType::Clean,
+            PedalState::Onward(_) => PedalType::Onward,
+            PedalState::Thermae(_) => PedalType::Thermae,
+            PedalState::DarkWorld(_) => PedalType::DarkWorld,
+            PedalState::Habit(_) => PedalType::Habit,
+            PedalState::Collider(_) => PedalType::Collider,
+        }
+    }
+
+    /// Read a state serialized the way presets store it
+    pub fn from_json(pedal_type: &PedalType, state: serde_json::Value) -> MidiResult<Self> {
+        if let PedalType::GenLossMkii = pedal_type {
+            return GenLossMkiiState::from_json_preset(&state).map(PedalState::GenLossMkii);
+        }
+        serde_json::from_value(serde_json::json!({ "pedal_type": pedal_type.as_str(), "state": state }))
+            .map_err(|e| MidiError::Other(format!("Failed to read {} state: {}", pedal_type.as_str(), e)))
+    }
+
+    /// The state serialized the way presets store it, without the tag
+    pub fn to_json(&self) -> MidiResult<serde_json::Value> {
+        let mut tagged = serde_json::to_value(self)
+            .map_err(|e| MidiError::Other(format!("Failed to serialize state: {}", e)))?;
+        Ok(tagged["state"].take())
+    }
+}
+
+/// The error for a state or parameter offered to the wrong kind of pedal
+pub(crate) fn type_mismatch(actual: &PedalType, expected: &PedalType) -> MidiError {
+    MidiError::Other(format!("Device is a {}, not a {}", actual.as_str(), expected.as_str()))
+}
+
+// `From` each pedal's own type into the tagged one, and `TryFrom` back out
+macro_rules! tagged_conversions {
+    ($tagged:ident { $($variant:ident($inner:ty)),* $(,)? }) => {
+        $(
+            impl From<$inner> for $tagged {
+                fn from(value: $inner) -> Self {
+                    $tagged::$variant(value)
+                }
+            }
+
+            impl TryFrom<$tagged> for $inner {
+                type Error = MidiError;
+
+                fn try_from(value: $tagged) -> MidiResult<Self> {
+                    match value {
+                        $tagged::$variant(inner) => Ok(inner),
+                        other => Err(type_mismatch(&other.pedal_type(), &PedalType::$variant)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+tagged_conversions!(PedalState {
+    Microcosm(MicrocosmState),
+    GenLossMkii(GenLossMkiiState),
+    ChromaConsole(ChromaConsoleState),
+    PreampMk2(PreampMk2State),
+    Cxm1978(Cxm1978State),
+    MoodMkii(MoodMkiiState),
+    BillyStringsWombtone(BillyStringsWombtoneState),
+    Lossy(LossyState),
+    BrothersAm(BrothersAmState),
+    ReverseModeC(ReverseModeCState),
+    Clean(CleanState),
+    Onward(OnwardState),
+    Thermae(ThermaeState),
+    DarkWorld(DarkWorldState),
+    Habit(HabitState),
+    Collider(ColliderState),
+});
+
+tagged_conversions!(PedalParameter {
+    Microcosm(MicrocosmParameter),
+    GenLossMkii(GenLossMkiiParameter),
+    ChromaConsole(ChromaConsoleParameter),
+    PreampMk2(PreampMk2Parameter),
+    Cxm1978(Cxm1978Parameter),
+    MoodMkii(MoodMkiiParameter),
+    BillyStringsWombtone(BillyStringsWombtoneParameter),
+    Lossy(LossyParameter),
+    BrothersAm(BrothersAmParameter),
+    ReverseModeC(ReverseModeCParameter),
+    Clean(CleanParameter),
+    Onward(OnwardParameter),
+    Thermae(ThermaeParameter),
+    DarkWorld(DarkWorldParameter),
+    Habit(HabitParameter),
+    Collider(ColliderParameter),
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::midi::cc_value::CcValue;
+    use crate::midi::device_state::DeviceState;
+
+    #[test]
+    fn test_serializes_like_device_state() {
+        let state = LossyState::default();
+        let tagged = serde_json::to_value(PedalState::from(state.clone())).unwrap();
+        let device_state = serde_json::to_value(DeviceState::new(&PedalType::Lossy, &state).unwrap()).unwrap();
+
+        assert_eq!(tagged["pedal_type"], device_state["pedal_type"]);
+        assert_eq!(tagged["state"], device_state["state"]);
+    }
+
+    #[test]
+    fn test_json_round_trip_keeps_the_pedal_type() {
+        let json = PedalState::from(Cxm1978State::default()).to_json().unwrap();
+        let state = PedalState::from_json(&PedalType::Cxm1978, json.clone()).unwrap();
+
+        assert_eq!(state.pedal_type(), PedalType::Cxm1978);
+        assert_eq!(state.to_json().unwrap(), json);
+        assert!(PedalState::from_json(&PedalType::Cxm1978, serde_json::json!("not a state")).is_err());
+    }
+
+    #[test]
+    fn test_try_from_rejects_other_pedals() {
+        let state = PedalState::from(CleanState::default());
+        assert!(CleanState::try_from(state.clone()).is_ok());
+        let error = OnwardState::try_from(state).unwrap_err();
+        assert_eq!(error.to_string(), "MIDI error: Device is a Clean, not a Onward");
+
+        let param = PedalParameter::from(LossyParameter::Filter(CcValue::new(10).unwrap()));
+        assert_eq!(param.pedal_type(), PedalType::Lossy);
+        assert!(LossyParameter::try_from(param.clone()).is_ok());
+        assert!(ThermaeParameter::try_from(param).is_err());
+    }
+}
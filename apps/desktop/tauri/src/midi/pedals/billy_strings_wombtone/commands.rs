@@ -1,6 +1,8 @@
 // Tauri commands for Chase Bliss Audio Billy Strings Wombtone
 
-use crate::midi::SharedMidiManager;
+use crate::midi::{InputChannelMode, PedalType, SharedMidiManager};
+use crate::midi::connect_retry::{connect_with_retry, ConnectOutcome, RetryOptions};
+use crate::midi::safety::RecallOutcome;
 use crate::midi::pedals::billy_strings_wombtone::{BillyStringsWombtoneParameter, BillyStringsWombtoneState};
 use tauri::State;
 
@@ -10,11 +12,17 @@ pub async fn connect_billy_strings_wombtone(
     manager: State<'_, SharedMidiManager>,
     device_name: String,
     midi_channel: u8,
-) -> Result<(), String> {
-    let mut manager = manager.lock().map_err(|e| e.to_string())?;
-    manager
-        .connect_billy_strings_wombtone(&device_name, midi_channel)
-        .map_err(|e| e.to_string())
+    input_mode: Option<InputChannelMode>,
+    retries: Option<u32>,
+    initial_backoff_ms: Option<u64>,
+) -> Result<ConnectOutcome, String> {
+    let options = RetryOptions::from_params(retries, initial_backoff_ms);
+    connect_with_retry(manager.inner(), &device_name, "connect_billy_strings_wombtone", options, |manager| {
+        manager.connect_with_input_mode(&device_name, input_mode.as_ref(), |manager| {
+            manager.connect_billy_strings_wombtone(&device_name, midi_channel)
+        })
+    })
+    .map_err(|e| e.to_string())
 }
 
 /// Send a Billy Strings Wombtone parameter change
@@ -25,6 +33,7 @@ pub async fn send_billy_strings_wombtone_parameter(
     param: BillyStringsWombtoneParameter,
 ) -> Result<(), String> {
     let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("send_billy_strings_wombtone_parameter");
     manager
         .send_billy_strings_wombtone_parameter(&device_name, param)
         .map_err(|e| e.to_string())
@@ -43,16 +52,30 @@ pub async fn get_billy_strings_wombtone_state(
 }
 
 /// Recall a Billy Strings Wombtone preset (send all parameters)
+/// Loud parameter jumps block the recall until `acknowledge_warnings` is set.
+/// A `glide_ms` above 0 (at most 2 s) ramps `glide_ccs` to their targets over that long.
 #[tauri::command]
 pub async fn recall_billy_strings_wombtone_preset(
     manager: State<'_, SharedMidiManager>,
     device_name: String,
     state: BillyStringsWombtoneState,
-) -> Result<(), String> {
+    acknowledge_warnings: bool,
+    glide_ms: Option<u64>,
+    glide_ccs: Option<Vec<u8>>,
+) -> Result<RecallOutcome, String> {
     let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    let warnings = manager
+        .recall_safety_warnings(&device_name, &state)
+        .map_err(|e| e.to_string())?;
+    if !warnings.is_empty() && !acknowledge_warnings {
+        return Ok(RecallOutcome::blocked(warnings));
+    }
+    manager.begin_journal_operation("recall_billy_strings_wombtone_preset");
+    let glide = PedalType::BillyStringsWombtone.glide(glide_ms, glide_ccs);
     manager
-        .recall_billy_strings_wombtone_preset(&device_name, &state)
-        .map_err(|e| e.to_string())
+        .gliding(&device_name, glide, |manager| manager.recall_billy_strings_wombtone_preset(&device_name, &state))
+        .map_err(|e| e.to_string())?;
+    Ok(RecallOutcome::recalled(warnings))
 }
 
 /// Save current state to a Billy Strings Wombtone preset slot (1-122)
@@ -63,6 +86,7 @@ pub async fn save_billy_strings_wombtone_preset(
     slot: u8,
 ) -> Result<(), String> {
     let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("save_billy_strings_wombtone_preset");
     manager
         .save_billy_strings_wombtone_preset(&device_name, slot)
         .map_err(|e| e.to_string())
@@ -76,6 +100,7 @@ pub async fn send_billy_strings_wombtone_program_change(
     program: u8,
 ) -> Result<(), String> {
     let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("send_billy_strings_wombtone_program_change");
     manager
         .send_billy_strings_wombtone_program_change(&device_name, program)
         .map_err(|e| e.to_string())
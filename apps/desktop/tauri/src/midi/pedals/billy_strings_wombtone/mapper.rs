@@ -1,6 +1,7 @@
 // Billy Strings Wombtone MIDI CC mapping - infrastructure layer
 
 use super::types::{BillyStringsWombtoneParameter, BillyStringsWombtoneState};
+use crate::midi::send_plan::SendPlan;
 use std::collections::HashMap;
 
 pub const CC_PRESET_SAVE: u8 = 111;
@@ -37,10 +38,10 @@ impl BillyStringsWombtoneParameter {
             | Self::Form(v)
             | Self::RampSpeed(v)
             | Self::Expression(v)
-            | Self::PresetSave(v) => *v,
+            | Self::PresetSave(v) => v.get(),
 
             // Note division: raw value 0-5
-            Self::NoteDivision(v) => *v,
+            Self::NoteDivision(v) => v.get(),
 
             // Bypass: split-range — off=0, on=64
             Self::Bypass(b) => if *b { 64 } else { 0 },
@@ -105,4 +106,10 @@ impl BillyStringsWombtoneState {
 
         map
     }
+
+    /// Recall order: bypass first, then the note division and clock setting, then knobs
+    /// Tap is never recalled.
+    pub fn recall_plan(&self) -> SendPlan {
+        SendPlan::grouped(self.to_cc_map(), &[102], &[21, 51], &[93])
+    }
 }
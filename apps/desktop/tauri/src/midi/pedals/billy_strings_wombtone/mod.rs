@@ -27,18 +27,18 @@ impl BillyStringsWombtone {
     /// Update internal state from a parameter change
     pub fn update_state(&mut self, param: &BillyStringsWombtoneParameter) {
         match param {
-            BillyStringsWombtoneParameter::Feed(v) => self.state.feed = *v,
-            BillyStringsWombtoneParameter::Volume(v) => self.state.volume = *v,
-            BillyStringsWombtoneParameter::Mix(v) => self.state.mix = *v,
-            BillyStringsWombtoneParameter::Rate(v) => self.state.rate = *v,
-            BillyStringsWombtoneParameter::Depth(v) => self.state.depth = *v,
-            BillyStringsWombtoneParameter::Form(v) => self.state.form = *v,
-            BillyStringsWombtoneParameter::RampSpeed(v) => self.state.ramp_speed = *v,
-            BillyStringsWombtoneParameter::NoteDivision(v) => self.state.note_division = *v,
+            BillyStringsWombtoneParameter::Feed(v) => self.state.feed = v.get(),
+            BillyStringsWombtoneParameter::Volume(v) => self.state.volume = v.get(),
+            BillyStringsWombtoneParameter::Mix(v) => self.state.mix = v.get(),
+            BillyStringsWombtoneParameter::Rate(v) => self.state.rate = v.get(),
+            BillyStringsWombtoneParameter::Depth(v) => self.state.depth = v.get(),
+            BillyStringsWombtoneParameter::Form(v) => self.state.form = v.get(),
+            BillyStringsWombtoneParameter::RampSpeed(v) => self.state.ramp_speed = v.get(),
+            BillyStringsWombtoneParameter::NoteDivision(v) => self.state.note_division = v.get(),
             BillyStringsWombtoneParameter::Bypass(v) => self.state.bypass = *v,
             BillyStringsWombtoneParameter::Tap(v) => self.state.tap = *v,
             BillyStringsWombtoneParameter::MidiClockIgnore(v) => self.state.midi_clock_ignore = *v,
-            BillyStringsWombtoneParameter::Expression(v) => self.state.expression = *v,
+            BillyStringsWombtoneParameter::Expression(v) => self.state.expression = v.get(),
             BillyStringsWombtoneParameter::PresetSave(_) => {} // Does not update state
         }
     }
@@ -47,6 +47,11 @@ impl BillyStringsWombtone {
     pub fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
         self.state.to_cc_map()
     }
+
+    /// Get the current state as CCs in the order a recall should send them
+    pub fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.state.recall_plan()
+    }
 }
 
 impl super::PedalCapabilities for BillyStringsWombtone {
@@ -81,4 +86,8 @@ impl super::PedalCapabilities for BillyStringsWombtone {
     fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
         self.state_as_cc_map()
     }
+
+    fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.recall_plan()
+    }
 }
@@ -1,5 +1,6 @@
 // Billy Strings Wombtone domain types
 
+use crate::midi::CcValue;
 use serde::{Deserialize, Serialize};
 
 /// Complete state of all Billy Strings Wombtone parameters.
@@ -51,16 +52,16 @@ impl Default for BillyStringsWombtoneState {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum BillyStringsWombtoneParameter {
     // Main knobs
-    Feed(u8),
-    Volume(u8),
-    Mix(u8),
-    Rate(u8),
-    Depth(u8),
-    Form(u8),
-    RampSpeed(u8),
+    Feed(CcValue),
+    Volume(CcValue),
+    Mix(CcValue),
+    Rate(CcValue),
+    Depth(CcValue),
+    Form(CcValue),
+    RampSpeed(CcValue),
 
     // Note division (raw 0-5)
-    NoteDivision(u8),
+    NoteDivision(CcValue),
 
     // Footswitches
     Bypass(bool),
@@ -68,6 +69,6 @@ pub enum BillyStringsWombtoneParameter {
 
     // Utility
     MidiClockIgnore(bool),
-    Expression(u8),
-    PresetSave(u8),
+    Expression(CcValue),
+    PresetSave(CcValue),
 }
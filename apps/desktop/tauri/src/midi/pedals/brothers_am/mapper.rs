@@ -1,6 +1,8 @@
 // Brothers AM MIDI CC mapping - infrastructure layer
 
 use super::types::{BrothersAmParameter, BrothersAmState};
+use crate::midi::pedals::DIP_SWITCH_CCS;
+use crate::midi::send_plan::SendPlan;
 use std::collections::HashMap;
 
 pub const CC_PRESET_SAVE: u8 = 111;
@@ -68,7 +70,7 @@ impl BrothersAmParameter {
             | BrothersAmParameter::Presence2(v)
             | BrothersAmParameter::Presence1(v)
             | BrothersAmParameter::Expression(v)
-            | BrothersAmParameter::PresetSave(v) => *v,
+            | BrothersAmParameter::PresetSave(v) => v.get(),
 
             // Enum parameters
             BrothersAmParameter::Gain2Type(t) => t.to_cc_value(),
@@ -186,4 +188,10 @@ impl BrothersAmState {
 
         map
     }
+
+    /// Recall order: channel bypasses first, then toggles and DIP switches, then knobs
+    pub fn recall_plan(&self) -> SendPlan {
+        let selections = [&[21, 22, 23][..], DIP_SWITCH_CCS].concat();
+        SendPlan::grouped(self.to_cc_map(), &[102, 103], &selections, &[])
+    }
 }
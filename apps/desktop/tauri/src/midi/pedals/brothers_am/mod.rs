@@ -27,14 +27,14 @@ impl BrothersAm {
     /// Update internal state from a parameter change
     pub fn update_state(&mut self, param: &BrothersAmParameter) {
         match param {
-            BrothersAmParameter::Gain2(v) => self.state.gain2 = *v,
-            BrothersAmParameter::Volume2(v) => self.state.volume2 = *v,
-            BrothersAmParameter::Gain1(v) => self.state.gain1 = *v,
-            BrothersAmParameter::Tone2(v) => self.state.tone2 = *v,
-            BrothersAmParameter::Volume1(v) => self.state.volume1 = *v,
-            BrothersAmParameter::Tone1(v) => self.state.tone1 = *v,
-            BrothersAmParameter::Presence2(v) => self.state.presence2 = *v,
-            BrothersAmParameter::Presence1(v) => self.state.presence1 = *v,
+            BrothersAmParameter::Gain2(v) => self.state.gain2 = v.get(),
+            BrothersAmParameter::Volume2(v) => self.state.volume2 = v.get(),
+            BrothersAmParameter::Gain1(v) => self.state.gain1 = v.get(),
+            BrothersAmParameter::Tone2(v) => self.state.tone2 = v.get(),
+            BrothersAmParameter::Volume1(v) => self.state.volume1 = v.get(),
+            BrothersAmParameter::Tone1(v) => self.state.tone1 = v.get(),
+            BrothersAmParameter::Presence2(v) => self.state.presence2 = v.get(),
+            BrothersAmParameter::Presence1(v) => self.state.presence1 = v.get(),
             BrothersAmParameter::Gain2Type(v) => self.state.gain2_type = *v,
             BrothersAmParameter::TrebleBoost(v) => self.state.treble_boost = *v,
             BrothersAmParameter::Gain1Type(v) => self.state.gain1_type = *v,
@@ -55,7 +55,7 @@ impl BrothersAm {
             BrothersAmParameter::DipPresLink1(v) => self.state.dip_pres_link1 = *v,
             BrothersAmParameter::DipPresLink2(v) => self.state.dip_pres_link2 = *v,
             BrothersAmParameter::DipMaster(v) => self.state.dip_master = *v,
-            BrothersAmParameter::Expression(v) => self.state.expression = *v,
+            BrothersAmParameter::Expression(v) => self.state.expression = v.get(),
             BrothersAmParameter::PresetSave(_) => {} // Does not update persistent state
         }
     }
@@ -64,6 +64,11 @@ impl BrothersAm {
     pub fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
         self.state.to_cc_map()
     }
+
+    /// Get the current state as CCs in the order a recall should send them
+    pub fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.state.recall_plan()
+    }
 }
 
 impl super::PedalCapabilities for BrothersAm {
@@ -98,11 +103,16 @@ impl super::PedalCapabilities for BrothersAm {
     fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
         self.state_as_cc_map()
     }
+
+    fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.recall_plan()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::midi::CcValue;
 
     #[test]
     fn test_brothers_am_new() {
@@ -118,7 +128,7 @@ mod tests {
     #[test]
     fn test_update_state() {
         let mut pedal = BrothersAm::new(1);
-        pedal.update_state(&BrothersAmParameter::Gain1(100));
+        pedal.update_state(&BrothersAmParameter::Gain1(CcValue::new(100).unwrap()));
         assert_eq!(pedal.state.gain1, 100);
         pedal.update_state(&BrothersAmParameter::Channel1Bypass(true));
         assert!(pedal.state.channel1_bypass);
@@ -164,19 +174,19 @@ mod tests {
 
     #[test]
     fn test_parameter_cc_numbers() {
-        assert_eq!(BrothersAmParameter::Gain2(64).cc_number(), 14);
-        assert_eq!(BrothersAmParameter::Volume2(64).cc_number(), 15);
-        assert_eq!(BrothersAmParameter::Gain1(64).cc_number(), 16);
-        assert_eq!(BrothersAmParameter::Tone2(64).cc_number(), 17);
-        assert_eq!(BrothersAmParameter::Volume1(64).cc_number(), 18);
-        assert_eq!(BrothersAmParameter::Tone1(64).cc_number(), 19);
-        assert_eq!(BrothersAmParameter::Presence2(64).cc_number(), 27);
-        assert_eq!(BrothersAmParameter::Presence1(64).cc_number(), 29);
+        assert_eq!(BrothersAmParameter::Gain2(CcValue::new(64).unwrap()).cc_number(), 14);
+        assert_eq!(BrothersAmParameter::Volume2(CcValue::new(64).unwrap()).cc_number(), 15);
+        assert_eq!(BrothersAmParameter::Gain1(CcValue::new(64).unwrap()).cc_number(), 16);
+        assert_eq!(BrothersAmParameter::Tone2(CcValue::new(64).unwrap()).cc_number(), 17);
+        assert_eq!(BrothersAmParameter::Volume1(CcValue::new(64).unwrap()).cc_number(), 18);
+        assert_eq!(BrothersAmParameter::Tone1(CcValue::new(64).unwrap()).cc_number(), 19);
+        assert_eq!(BrothersAmParameter::Presence2(CcValue::new(64).unwrap()).cc_number(), 27);
+        assert_eq!(BrothersAmParameter::Presence1(CcValue::new(64).unwrap()).cc_number(), 29);
         assert_eq!(BrothersAmParameter::Gain2Type(Gain2Type::Boost).cc_number(), 21);
         assert_eq!(BrothersAmParameter::TrebleBoost(TrebleBoost::FullSun).cc_number(), 22);
         assert_eq!(BrothersAmParameter::Gain1Type(Gain1Type::Dist).cc_number(), 23);
         assert_eq!(BrothersAmParameter::Channel1Bypass(false).cc_number(), 102);
         assert_eq!(BrothersAmParameter::Channel2Bypass(false).cc_number(), 103);
-        assert_eq!(BrothersAmParameter::Expression(0).cc_number(), 100);
+        assert_eq!(BrothersAmParameter::Expression(CcValue::new(0).unwrap()).cc_number(), 100);
     }
 }
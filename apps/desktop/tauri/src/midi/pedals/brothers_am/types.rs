@@ -1,5 +1,6 @@
 // Brothers AM domain types - enums, structs, and value objects
 
+use crate::midi::CcValue;
 use crate::midi::error::MidiResult;
 use serde::{Deserialize, Serialize};
 
@@ -96,16 +97,16 @@ impl Default for BrothersAmState {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum BrothersAmParameter {
     // Main knobs - Channel 2
-    Gain2(u8),
-    Volume2(u8),
-    Tone2(u8),
-    Presence2(u8),
+    Gain2(CcValue),
+    Volume2(CcValue),
+    Tone2(CcValue),
+    Presence2(CcValue),
 
     // Main knobs - Channel 1
-    Gain1(u8),
-    Volume1(u8),
-    Tone1(u8),
-    Presence1(u8),
+    Gain1(CcValue),
+    Volume1(CcValue),
+    Tone1(CcValue),
+    Presence1(CcValue),
 
     // Toggles
     Gain2Type(Gain2Type),
@@ -136,8 +137,8 @@ pub enum BrothersAmParameter {
     DipMaster(bool),
 
     // Utility
-    Expression(u8),
-    PresetSave(u8),
+    Expression(CcValue),
+    PresetSave(CcValue),
 }
 
 /// Gain type for channel 2 (CC 21)
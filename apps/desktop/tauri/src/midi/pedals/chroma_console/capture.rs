@@ -0,0 +1,205 @@
+// One-shot capture timing for the Chroma Console
+// "Record for N ms, then play" needs a timer that outlives the command that started
+// it. Each device gets at most one pending timer: starting another one-shot (or
+// stopping capture) cancels it, so a stale timer can never flip the pedal to Play.
+
+use crate::midi::clock::{Clock, SystemClock};
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+struct PendingCapture {
+    id: u64,
+    // Dropping the sender wakes the worker as cancelled
+    _cancel: Sender<()>,
+}
+
+/// Pending one-shot timers, at most one per device
+pub struct CaptureTimers {
+    clock: Arc<dyn Clock>,
+    pending: HashMap<String, PendingCapture>,
+    next_id: u64,
+}
+
+impl Default for CaptureTimers {
+    fn default() -> Self {
+        Self::with_clock(Arc::new(SystemClock))
+    }
+}
+
+impl CaptureTimers {
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
+        Self {
+            clock,
+            pending: HashMap::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Start a timer for `device_name`, cancelling any pending one. `on_elapsed`
+    /// runs on a worker thread with the timer's id once `duration` has passed;
+    /// pass that id to `finish` to check the timer wasn't superseded meanwhile.
+    pub fn start(
+        &mut self,
+        device_name: &str,
+        duration: Duration,
+        on_elapsed: impl FnOnce(u64) + Send + 'static,
+    ) -> u64 {
+        self.next_id += 1;
+        let id = self.next_id;
+        let (cancel, cancelled) = mpsc::channel();
+        self.pending.insert(device_name.to_string(), PendingCapture { id, _cancel: cancel });
+
+        let clock = Arc::clone(&self.clock);
+        thread::spawn(move || {
+            if clock.wait(duration, &cancelled) {
+                on_elapsed(id);
+            }
+        });
+        id
+    }
+
+    /// Cancel the device's pending timer; returns whether there was one
+    pub fn cancel(&mut self, device_name: &str) -> bool {
+        self.pending.remove(device_name).is_some()
+    }
+
+    /// Claim an elapsed timer. False if it was cancelled or replaced after it fired.
+    pub fn finish(&mut self, device_name: &str, id: u64) -> bool {
+        match self.pending.get(device_name) {
+            Some(pending) if pending.id == id => {
+                self.pending.remove(device_name);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn is_pending(&self, device_name: &str) -> bool {
+        self.pending.contains_key(device_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::midi::pedals::chroma_console::{CaptureMode, CaptureTransport};
+    use crate::test_utils::MockClock;
+    use std::sync::Mutex;
+
+    /// A device's capture modes in send order, driven the way the manager drives them
+    #[derive(Clone)]
+    struct Pedal {
+        clock: Arc<MockClock>,
+        sent: Arc<Mutex<Vec<CaptureMode>>>,
+        timers: Arc<Mutex<CaptureTimers>>,
+    }
+
+    impl Pedal {
+        fn new(clock: Arc<MockClock>) -> Self {
+            Self {
+                clock: Arc::clone(&clock),
+                sent: Arc::default(),
+                timers: Arc::new(Mutex::new(CaptureTimers::with_clock(clock))),
+            }
+        }
+
+        fn one_shot(&self, duration_ms: u64) {
+            self.sent.lock().unwrap().push(CaptureMode::Record);
+            let waiters = self.clock.waits().len() + 1;
+            let pedal = self.clone();
+            self.timers.lock().unwrap().start("Chroma Console", Duration::from_millis(duration_ms), move |id| {
+                if pedal.timers.lock().unwrap().finish("Chroma Console", id) {
+                    pedal.sent.lock().unwrap().push(CaptureMode::Play);
+                }
+            });
+            self.clock.await_waiters(waiters);
+        }
+
+        fn stop(&self) {
+            self.timers.lock().unwrap().cancel("Chroma Console");
+            self.sent.lock().unwrap().push(CaptureMode::Stop);
+        }
+
+        fn sent(&self) -> Vec<CaptureMode> {
+            self.sent.lock().unwrap().clone()
+        }
+
+        fn is_pending(&self) -> bool {
+            self.timers.lock().unwrap().is_pending("Chroma Console")
+        }
+    }
+
+    /// Let worker threads observe the clock
+    fn settle() {
+        thread::sleep(Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_one_shot_records_then_plays_after_duration() {
+        let clock = Arc::new(MockClock::default());
+        let pedal = Pedal::new(Arc::clone(&clock));
+
+        pedal.one_shot(2000);
+        assert_eq!(pedal.sent(), vec![CaptureMode::Record]);
+
+        clock.advance(Duration::from_millis(1999));
+        settle();
+        assert_eq!(pedal.sent(), vec![CaptureMode::Record]);
+
+        clock.advance(Duration::from_millis(1));
+        settle();
+        assert_eq!(pedal.sent(), vec![CaptureMode::Record, CaptureMode::Play]);
+        assert!(!pedal.is_pending());
+        assert_eq!(clock.waits(), vec![Duration::from_millis(2000)]);
+    }
+
+    #[test]
+    fn test_overlapping_one_shot_cancels_previous_timer() {
+        let clock = Arc::new(MockClock::default());
+        let pedal = Pedal::new(Arc::clone(&clock));
+
+        pedal.one_shot(1000);
+        clock.advance(Duration::from_millis(500));
+        pedal.one_shot(1000);
+
+        // The first timer's deadline passes, but it was superseded
+        clock.advance(Duration::from_millis(600));
+        settle();
+        assert_eq!(pedal.sent(), vec![CaptureMode::Record, CaptureMode::Record]);
+
+        clock.advance(Duration::from_millis(400));
+        settle();
+        assert_eq!(
+            pedal.sent(),
+            vec![CaptureMode::Record, CaptureMode::Record, CaptureMode::Play]
+        );
+    }
+
+    #[test]
+    fn test_stop_aborts_pending_one_shot() {
+        let clock = Arc::new(MockClock::default());
+        let pedal = Pedal::new(Arc::clone(&clock));
+
+        pedal.one_shot(1000);
+        pedal.stop();
+        clock.advance(Duration::from_millis(1000));
+        settle();
+
+        assert_eq!(pedal.sent(), vec![CaptureMode::Record, CaptureMode::Stop]);
+        assert!(!pedal.is_pending());
+    }
+
+    #[test]
+    fn test_capture_transport_follows_modes() {
+        let transport = CaptureTransport::Empty;
+        assert_eq!(transport.apply(CaptureMode::Play), CaptureTransport::Empty);
+
+        let transport = transport.apply(CaptureMode::Record);
+        assert_eq!(transport, CaptureTransport::Recording);
+        assert_eq!(transport.apply(CaptureMode::Play), CaptureTransport::Playing);
+        assert_eq!(transport.apply(CaptureMode::Stop), CaptureTransport::Stopped);
+    }
+}
@@ -1,7 +1,10 @@
 // Tauri commands for Chase Bliss Chroma Console pedal
 
-use crate::midi::SharedMidiManager;
-use crate::midi::pedals::chroma_console::{ChromaConsoleParameter, ChromaConsoleState};
+use crate::midi::{InputChannelMode, PedalType, SharedMidiManager};
+use crate::midi::connect_retry::{connect_with_retry, ConnectOutcome, RetryOptions};
+use crate::midi::safety::RecallOutcome;
+use crate::midi::pedals::chroma_console::{BypassMode, CaptureRouting, ChromaConsoleParameter, ChromaConsoleState, ParameterSemantics};
+use std::time::Duration;
 use tauri::State;
 
 /// Connect to a Chroma Console pedal
@@ -10,11 +13,17 @@ pub async fn connect_chroma_console(
     manager: State<'_, SharedMidiManager>,
     device_name: String,
     midi_channel: u8,
-) -> Result<(), String> {
-    let mut manager = manager.lock().map_err(|e| e.to_string())?;
-    manager
-        .connect_chroma_console(&device_name, midi_channel)
-        .map_err(|e| e.to_string())
+    input_mode: Option<InputChannelMode>,
+    retries: Option<u32>,
+    initial_backoff_ms: Option<u64>,
+) -> Result<ConnectOutcome, String> {
+    let options = RetryOptions::from_params(retries, initial_backoff_ms);
+    connect_with_retry(manager.inner(), &device_name, "connect_chroma_console", options, |manager| {
+        manager.connect_with_input_mode(&device_name, input_mode.as_ref(), |manager| {
+            manager.connect_chroma_console(&device_name, midi_channel)
+        })
+    })
+    .map_err(|e| e.to_string())
 }
 
 /// Send a Chroma Console parameter change
@@ -25,6 +34,7 @@ pub async fn send_chroma_console_parameter(
     param: ChromaConsoleParameter,
 ) -> Result<(), String> {
     let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("send_chroma_console_parameter");
     manager
         .send_chroma_console_parameter(&device_name, param)
         .map_err(|e| e.to_string())
@@ -38,11 +48,49 @@ pub async fn send_chroma_console_program_change(
     program: u8,
 ) -> Result<(), String> {
     let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("send_chroma_console_program_change");
     manager
         .send_chroma_console_program_change(&device_name, program)
         .map_err(|e| e.to_string())
 }
 
+/// Record a capture for `duration_ms`, then switch it to playback.
+/// Starting another one-shot on the same device cancels the pending switch.
+#[tauri::command]
+pub async fn chroma_capture_one_shot(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    duration_ms: u64,
+    routing: CaptureRouting,
+) -> Result<(), String> {
+    let shared = SharedMidiManager::clone(&manager);
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("chroma_capture_one_shot");
+    let timer_device = device_name.clone();
+    manager
+        .start_capture_one_shot(&device_name, Duration::from_millis(duration_ms), routing, move |timer_id| {
+            let Ok(mut manager) = shared.lock() else { return };
+            manager.begin_journal_operation("chroma_capture_one_shot");
+            if let Err(e) = manager.finish_capture_one_shot(&timer_device, timer_id) {
+                eprintln!("❌ Failed to finish capture on {}: {}", timer_device, e);
+            }
+        })
+        .map_err(|e| e.to_string())
+}
+
+/// Stop capture, aborting a pending one-shot
+#[tauri::command]
+pub async fn chroma_capture_stop(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+) -> Result<(), String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("chroma_capture_stop");
+    manager
+        .stop_capture(&device_name)
+        .map_err(|e| e.to_string())
+}
+
 /// Get current Chroma Console state
 #[tauri::command]
 pub async fn get_chroma_console_state(
@@ -55,15 +103,71 @@ pub async fn get_chroma_console_state(
         .map_err(|e| e.to_string())
 }
 
+/// What Tilt, Rate and Time currently mean on a Chroma Console, given its filter mode and modules
+#[tauri::command]
+pub async fn get_effective_parameter_info(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+) -> Result<Vec<ParameterSemantics>, String> {
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    let state = manager
+        .get_chroma_console_state(&device_name)
+        .map_err(|e| e.to_string())?;
+    Ok(state.effective_semantics())
+}
+
 /// Recall a Chroma Console preset (send all parameters)
+/// Loud parameter jumps block the recall until `acknowledge_warnings` is set.
+/// A `glide_ms` above 0 (at most 2 s) ramps `glide_ccs` (the pedal's mix and level
+/// controls by default) to their targets over that long.
 #[tauri::command]
 pub async fn recall_chroma_console_preset(
     manager: State<'_, SharedMidiManager>,
     device_name: String,
     state: ChromaConsoleState,
+    acknowledge_warnings: bool,
+    glide_ms: Option<u64>,
+    glide_ccs: Option<Vec<u8>>,
+) -> Result<RecallOutcome, String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    let warnings = manager
+        .recall_safety_warnings(&device_name, &state)
+        .map_err(|e| e.to_string())?;
+    if !warnings.is_empty() && !acknowledge_warnings {
+        return Ok(RecallOutcome::blocked(warnings));
+    }
+    manager.begin_journal_operation("recall_chroma_console_preset");
+    let glide = PedalType::ChromaConsole.glide(glide_ms, glide_ccs);
+    manager
+        .gliding(&device_name, glide, |manager| manager.recall_chroma_console_preset(&device_name, &state))
+        .map_err(|e| e.to_string())?;
+    Ok(RecallOutcome::recalled(warnings))
+}
+
+/// Load the current state into a user preset slot (40-79) and return the footswitch
+/// instructions for saving it there - the Chroma Console has no MIDI save
+#[tauri::command]
+pub async fn save_chroma_console_preset(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    slot: u8,
+) -> Result<String, String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("save_chroma_console_preset");
+    manager
+        .save_chroma_console_preset(&device_name, slot)
+        .map_err(|e| e.to_string())
+}
+
+/// Set the Chroma Console's footswitch bypass mode (Standard = CC 91, Dual = CC 92)
+#[tauri::command]
+pub async fn set_chroma_console_bypass_mode(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    bypass_mode: BypassMode,
 ) -> Result<(), String> {
     let mut manager = manager.lock().map_err(|e| e.to_string())?;
     manager
-        .recall_chroma_console_preset(&device_name, &state)
+        .set_chroma_console_bypass_mode(&device_name, bypass_mode)
         .map_err(|e| e.to_string())
 }
@@ -1,6 +1,7 @@
 // Chroma Console MIDI mapper - converts between domain types and MIDI CC messages
 
 use super::types::*;
+use crate::midi::send_plan::SendPlan;
 use std::collections::HashMap;
 
 // ============================================================================
@@ -27,6 +28,9 @@ pub const CC_EFFECT_VOL_MOVEMENT: u8 = 75;
 pub const CC_EFFECT_VOL_DIFFUSION: u8 = 77;
 pub const CC_EFFECT_VOL_TEXTURE: u8 = 79;
 
+/// Continuous CCs a gliding recall ramps
+pub const GLIDE_CCS: [u8; 2] = [CC_MIX, CC_OUTPUT_LEVEL];
+
 // Module controls
 pub const CC_CHARACTER_MODULE: u8 = 16;
 pub const CC_MOVEMENT_MODULE: u8 = 17;
@@ -51,13 +55,58 @@ pub const CC_FILTER_MODE: u8 = 84;
 pub const CC_CALIBRATION_LEVEL: u8 = 94;
 pub const CC_CALIBRATION_ENTER: u8 = 95;
 
+// ============================================================================
+// Bypass Mode Conversion
+// ============================================================================
+
+impl BypassMode {
+    /// CC number that carries the overall bypass state in this mode
+    pub fn cc_number(&self) -> u8 {
+        match self {
+            BypassMode::Standard => CC_STANDARD_BYPASS,
+            BypassMode::Dual => CC_DUAL_BYPASS,
+        }
+    }
+}
+
+impl BypassState {
+    /// CC value for this state in the given mode, or None if the mode can't express it
+    pub fn to_cc_value(&self, mode: BypassMode) -> Option<u8> {
+        match mode {
+            // NOTE: Chroma Console uses INVERTED logic on CC 91: 0 = engaged, 127 = bypassed
+            BypassMode::Standard => match self {
+                BypassState::Bypass => Some(127),
+                BypassState::Engaged => Some(0),
+                BypassState::DualBypass => None,  // Only exists in dual footswitch mode
+            },
+            // CC 92: 0-31 = bypass, 32-63 = dual bypass, 64-127 = engaged
+            BypassMode::Dual => match self {
+                BypassState::Bypass => Some(0),
+                BypassState::DualBypass => Some(48),
+                BypassState::Engaged => Some(127),
+            },
+        }
+    }
+
+    /// Parse a CC 92 value (dual footswitch mode)
+    pub fn from_dual_cc_value(value: u8) -> Self {
+        match value {
+            0..=31 => BypassState::Bypass,
+            32..=63 => BypassState::DualBypass,
+            _ => BypassState::Engaged,
+        }
+    }
+}
+
 // ============================================================================
 // State to CC Map Conversion
 // ============================================================================
 
 impl ChromaConsoleState {
-    /// Convert state to a HashMap of CC numbers and values
-    pub fn to_cc_map(&self) -> HashMap<u8, u8> {
+    /// Convert state to a HashMap of CC numbers and values.
+    /// The overall bypass goes out on the CC for `bypass_mode`, and is left out
+    /// if the state can't be expressed in that mode.
+    pub fn to_cc_map(&self, bypass_mode: BypassMode) -> HashMap<u8, u8> {
         let mut map = HashMap::new();
         
         // Primary controls
@@ -86,14 +135,10 @@ impl ChromaConsoleState {
         map.insert(CC_DIFFUSION_MODULE, self.diffusion_module.to_cc_value());
         map.insert(CC_TEXTURE_MODULE, self.texture_module.to_cc_value());
         
-        // Bypass state (use standard bypass by default)
-        // NOTE: Chroma Console uses INVERTED logic: 0 = engaged, 127 = bypassed
-        let bypass_value = match self.bypass_state {
-            BypassState::Bypass => 127,      // Send 127 to bypass (turn off)
-            BypassState::Engaged => 0,        // Send 0 to engage (turn on)
-            BypassState::DualBypass => 48,    // For dual bypass mode
-        };
-        map.insert(CC_STANDARD_BYPASS, bypass_value);
+        // Bypass state (CC 91 or CC 92, depending on the footswitch configuration)
+        if let Some(bypass_value) = self.bypass_state.to_cc_value(bypass_mode) {
+            map.insert(bypass_mode.cc_number(), bypass_value);
+        }
         
         // Module bypasses (true = bypassed, so send 0; false = engaged, so send 127)
         map.insert(CC_CHARACTER_BYPASS, if self.character_bypass { 0 } else { 127 });
@@ -110,6 +155,31 @@ impl ChromaConsoleState {
         
         map
     }
+
+    /// Recall order: bypasses and capture routing first, then module and mode selections,
+    /// then knobs. Gesture and capture transport are live performance state, never recalled.
+    pub fn recall_plan(&self, bypass_mode: BypassMode) -> SendPlan {
+        SendPlan::grouped(
+            self.to_cc_map(bypass_mode),
+            &[
+                bypass_mode.cc_number(),
+                CC_CHARACTER_BYPASS,
+                CC_MOVEMENT_BYPASS,
+                CC_DIFFUSION_BYPASS,
+                CC_TEXTURE_BYPASS,
+                CC_CAPTURE_ROUTING,
+            ],
+            &[
+                CC_CHARACTER_MODULE,
+                CC_MOVEMENT_MODULE,
+                CC_DIFFUSION_MODULE,
+                CC_TEXTURE_MODULE,
+                CC_FILTER_MODE,
+                CC_CALIBRATION_LEVEL,
+            ],
+            &[CC_GESTURE_PLAY_REC, CC_GESTURE_STOP_ERASE, CC_CAPTURE, CC_TAP_TEMPO, CC_CALIBRATION_ENTER],
+        )
+    }
     
     /// Update state from a CC message
     pub fn update_from_cc(&mut self, cc: u8, value: u8) {
@@ -149,14 +219,7 @@ impl ChromaConsoleState {
                     BypassState::Bypass   // High values = bypassed (off)
                 };
             }
-            CC_DUAL_BYPASS => {
-                // NOTE: Assuming dual bypass follows same inverted logic
-                self.bypass_state = match value {
-                    0..=31 => BypassState::Engaged,      // Low = engaged
-                    32..=63 => BypassState::DualBypass,  // Mid = dual bypass
-                    _ => BypassState::Bypass,            // High = bypassed
-                };
-            }
+            CC_DUAL_BYPASS => self.bypass_state = BypassState::from_dual_cc_value(value),
             // Module bypasses (0-63 = BYPASS = true, 64-127 = ENGAGE = false)
             CC_CHARACTER_BYPASS => self.character_bypass = value < 64,
             CC_MOVEMENT_BYPASS => self.movement_bypass = value < 64,
@@ -247,7 +310,7 @@ impl ChromaConsoleParameter {
             ChromaConsoleParameter::EffectVolCharacter(v) |
             ChromaConsoleParameter::EffectVolMovement(v) |
             ChromaConsoleParameter::EffectVolDiffusion(v) |
-            ChromaConsoleParameter::EffectVolTexture(v) => *v,
+            ChromaConsoleParameter::EffectVolTexture(v) => v.get(),
             
             // Module selections (use to_cc_value)
             ChromaConsoleParameter::CharacterModule(m) => m.to_cc_value(),
@@ -282,29 +345,79 @@ impl ChromaConsoleParameter {
         }
     }
     
+    /// Get a human-readable name for this parameter
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChromaConsoleParameter::Tilt(_) => "Tilt",
+            ChromaConsoleParameter::Rate(_) => "Rate",
+            ChromaConsoleParameter::Time(_) => "Time",
+            ChromaConsoleParameter::Mix(_) => "Mix",
+            ChromaConsoleParameter::AmountCharacter(_) => "Amount (Character)",
+            ChromaConsoleParameter::AmountMovement(_) => "Amount (Movement)",
+            ChromaConsoleParameter::AmountDiffusion(_) => "Amount (Diffusion)",
+            ChromaConsoleParameter::AmountTexture(_) => "Amount (Texture)",
+            ChromaConsoleParameter::Sensitivity(_) => "Sensitivity",
+            ChromaConsoleParameter::DriftMovement(_) => "Drift (Movement)",
+            ChromaConsoleParameter::DriftDiffusion(_) => "Drift (Diffusion)",
+            ChromaConsoleParameter::OutputLevel(_) => "Output Level",
+            ChromaConsoleParameter::EffectVolCharacter(_) => "Effect Vol (Character)",
+            ChromaConsoleParameter::EffectVolMovement(_) => "Effect Vol (Movement)",
+            ChromaConsoleParameter::EffectVolDiffusion(_) => "Effect Vol (Diffusion)",
+            ChromaConsoleParameter::EffectVolTexture(_) => "Effect Vol (Texture)",
+            ChromaConsoleParameter::CharacterModule(_) => "Character Module",
+            ChromaConsoleParameter::MovementModule(_) => "Movement Module",
+            ChromaConsoleParameter::DiffusionModule(_) => "Diffusion Module",
+            ChromaConsoleParameter::TextureModule(_) => "Texture Module",
+            ChromaConsoleParameter::BypassState(_) => "Bypass",
+            ChromaConsoleParameter::CharacterBypass(_) => "Character Bypass",
+            ChromaConsoleParameter::MovementBypass(_) => "Movement Bypass",
+            ChromaConsoleParameter::DiffusionBypass(_) => "Diffusion Bypass",
+            ChromaConsoleParameter::TextureBypass(_) => "Texture Bypass",
+            ChromaConsoleParameter::GestureMode(_) => "Gesture Mode",
+            ChromaConsoleParameter::GestureStop => "Gesture Stop",
+            ChromaConsoleParameter::CaptureMode(_) => "Capture Mode",
+            ChromaConsoleParameter::CaptureRouting(_) => "Capture Routing",
+            ChromaConsoleParameter::TapTempo => "Tap Tempo",
+            ChromaConsoleParameter::FilterMode(_) => "Filter Mode",
+            ChromaConsoleParameter::CalibrationLevel(_) => "Calibration Level",
+            ChromaConsoleParameter::CalibrationEnter(_) => "Calibration Enter",
+        }
+    }
+    
+    /// Convert parameter to (CC number, value) tuple for the given bypass mode.
+    /// Returns None if the overall bypass state can't be sent in that mode.
+    pub fn to_cc_message_for_mode(&self, bypass_mode: BypassMode) -> Option<(u8, u8)> {
+        match self {
+            ChromaConsoleParameter::BypassState(state) => {
+                state.to_cc_value(bypass_mode).map(|value| (bypass_mode.cc_number(), value))
+            }
+            _ => self.to_cc_message(),
+        }
+    }
+
     /// Convert parameter to (CC number, value) tuple
     /// Returns None for trigger-only parameters that need special handling
     pub fn to_cc_message(&self) -> Option<(u8, u8)> {
         match self {
             // Primary controls
-            ChromaConsoleParameter::Tilt(v) => Some((CC_TILT, *v)),
-            ChromaConsoleParameter::Rate(v) => Some((CC_RATE, *v)),
-            ChromaConsoleParameter::Time(v) => Some((CC_TIME, *v)),
-            ChromaConsoleParameter::Mix(v) => Some((CC_MIX, *v)),
-            ChromaConsoleParameter::AmountCharacter(v) => Some((CC_AMOUNT_CHARACTER, *v)),
-            ChromaConsoleParameter::AmountMovement(v) => Some((CC_AMOUNT_MOVEMENT, *v)),
-            ChromaConsoleParameter::AmountDiffusion(v) => Some((CC_AMOUNT_DIFFUSION, *v)),
-            ChromaConsoleParameter::AmountTexture(v) => Some((CC_AMOUNT_TEXTURE, *v)),
+            ChromaConsoleParameter::Tilt(v) => Some((CC_TILT, v.get())),
+            ChromaConsoleParameter::Rate(v) => Some((CC_RATE, v.get())),
+            ChromaConsoleParameter::Time(v) => Some((CC_TIME, v.get())),
+            ChromaConsoleParameter::Mix(v) => Some((CC_MIX, v.get())),
+            ChromaConsoleParameter::AmountCharacter(v) => Some((CC_AMOUNT_CHARACTER, v.get())),
+            ChromaConsoleParameter::AmountMovement(v) => Some((CC_AMOUNT_MOVEMENT, v.get())),
+            ChromaConsoleParameter::AmountDiffusion(v) => Some((CC_AMOUNT_DIFFUSION, v.get())),
+            ChromaConsoleParameter::AmountTexture(v) => Some((CC_AMOUNT_TEXTURE, v.get())),
             
             // Secondary controls
-            ChromaConsoleParameter::Sensitivity(v) => Some((CC_SENSITIVITY, *v)),
-            ChromaConsoleParameter::DriftMovement(v) => Some((CC_DRIFT_MOVEMENT, *v)),
-            ChromaConsoleParameter::DriftDiffusion(v) => Some((CC_DRIFT_DIFFUSION, *v)),
-            ChromaConsoleParameter::OutputLevel(v) => Some((CC_OUTPUT_LEVEL, *v)),
-            ChromaConsoleParameter::EffectVolCharacter(v) => Some((CC_EFFECT_VOL_CHARACTER, *v)),
-            ChromaConsoleParameter::EffectVolMovement(v) => Some((CC_EFFECT_VOL_MOVEMENT, *v)),
-            ChromaConsoleParameter::EffectVolDiffusion(v) => Some((CC_EFFECT_VOL_DIFFUSION, *v)),
-            ChromaConsoleParameter::EffectVolTexture(v) => Some((CC_EFFECT_VOL_TEXTURE, *v)),
+            ChromaConsoleParameter::Sensitivity(v) => Some((CC_SENSITIVITY, v.get())),
+            ChromaConsoleParameter::DriftMovement(v) => Some((CC_DRIFT_MOVEMENT, v.get())),
+            ChromaConsoleParameter::DriftDiffusion(v) => Some((CC_DRIFT_DIFFUSION, v.get())),
+            ChromaConsoleParameter::OutputLevel(v) => Some((CC_OUTPUT_LEVEL, v.get())),
+            ChromaConsoleParameter::EffectVolCharacter(v) => Some((CC_EFFECT_VOL_CHARACTER, v.get())),
+            ChromaConsoleParameter::EffectVolMovement(v) => Some((CC_EFFECT_VOL_MOVEMENT, v.get())),
+            ChromaConsoleParameter::EffectVolDiffusion(v) => Some((CC_EFFECT_VOL_DIFFUSION, v.get())),
+            ChromaConsoleParameter::EffectVolTexture(v) => Some((CC_EFFECT_VOL_TEXTURE, v.get())),
             
             // Module selections
             ChromaConsoleParameter::CharacterModule(m) => Some((CC_CHARACTER_MODULE, m.to_cc_value())),
@@ -315,12 +428,7 @@ impl ChromaConsoleParameter {
             // Bypass controls
             // NOTE: Chroma Console uses INVERTED logic: 0 = engaged, 127 = bypassed
             ChromaConsoleParameter::BypassState(state) => {
-                let value = match state {
-                    BypassState::Bypass => 127,      // Send 127 to bypass (turn off)
-                    BypassState::Engaged => 0,        // Send 0 to engage (turn on)
-                    BypassState::DualBypass => 48,
-                };
-                Some((CC_STANDARD_BYPASS, value))
+                state.to_cc_value(BypassMode::Standard).map(|value| (CC_STANDARD_BYPASS, value))
             }
             // Module bypasses (true = bypassed, so send 0; false = engaged, so send 127)
             ChromaConsoleParameter::CharacterBypass(b) => Some((CC_CHARACTER_BYPASS, if *b { 0 } else { 127 })),
@@ -344,6 +452,7 @@ impl ChromaConsoleParameter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::midi::CcValue;
 
     #[test]
     fn test_character_module_cc_conversion() {
@@ -363,7 +472,7 @@ mod tests {
     #[test]
     fn test_state_to_cc_map() {
         let state = ChromaConsoleState::default();
-        let cc_map = state.to_cc_map();
+        let cc_map = state.to_cc_map(BypassMode::Standard);
         
         assert_eq!(cc_map.get(&CC_TILT), Some(&64));
         assert_eq!(cc_map.get(&CC_MIX), Some(&64));
@@ -372,10 +481,77 @@ mod tests {
 
     #[test]
     fn test_parameter_to_cc_message() {
-        let param = ChromaConsoleParameter::Tilt(100);
+        let param = ChromaConsoleParameter::Tilt(CcValue::new(100).unwrap());
         assert_eq!(param.to_cc_message(), Some((CC_TILT, 100)));
         
         let param = ChromaConsoleParameter::CharacterModule(CharacterModule::Fuzz);
         assert_eq!(param.to_cc_message(), Some((CC_CHARACTER_MODULE, 54)));
     }
+
+    #[test]
+    fn test_standard_bypass_mode_uses_cc_91() {
+        let mut state = ChromaConsoleState {
+            bypass_state: BypassState::Engaged,
+            ..Default::default()
+        };
+        let cc_map = state.to_cc_map(BypassMode::Standard);
+        assert_eq!(cc_map.get(&CC_STANDARD_BYPASS), Some(&0));
+        assert!(!cc_map.contains_key(&CC_DUAL_BYPASS));
+
+        // Dual bypass can't be expressed on CC 91
+        state.bypass_state = BypassState::DualBypass;
+        let cc_map = state.to_cc_map(BypassMode::Standard);
+        assert!(!cc_map.contains_key(&CC_STANDARD_BYPASS));
+        assert_eq!(
+            ChromaConsoleParameter::BypassState(BypassState::DualBypass).to_cc_message_for_mode(BypassMode::Standard),
+            None
+        );
+    }
+
+    #[test]
+    fn test_dual_bypass_mode_uses_cc_92() {
+        let mut state = ChromaConsoleState::default();
+        for (bypass_state, value) in [
+            (BypassState::Bypass, 0),
+            (BypassState::DualBypass, 48),
+            (BypassState::Engaged, 127),
+        ] {
+            state.bypass_state = bypass_state;
+            let cc_map = state.to_cc_map(BypassMode::Dual);
+            assert_eq!(cc_map.get(&CC_DUAL_BYPASS), Some(&value));
+            assert!(!cc_map.contains_key(&CC_STANDARD_BYPASS));
+            assert_eq!(
+                ChromaConsoleParameter::BypassState(bypass_state).to_cc_message_for_mode(BypassMode::Dual),
+                Some((CC_DUAL_BYPASS, value))
+            );
+        }
+
+        // Non-bypass parameters are unaffected by the mode
+        assert_eq!(
+            ChromaConsoleParameter::Tilt(CcValue::new(100).unwrap()).to_cc_message_for_mode(BypassMode::Dual),
+            Some((CC_TILT, 100))
+        );
+    }
+
+    #[test]
+    fn test_incoming_dual_bypass_ranges() {
+        let mut state = ChromaConsoleState::default();
+        for (value, expected) in [
+            (0, BypassState::Bypass),
+            (31, BypassState::Bypass),
+            (32, BypassState::DualBypass),
+            (63, BypassState::DualBypass),
+            (64, BypassState::Engaged),
+            (127, BypassState::Engaged),
+        ] {
+            state.update_from_cc(CC_DUAL_BYPASS, value);
+            assert_eq!(state.bypass_state, expected, "CC 92 value {}", value);
+        }
+
+        // Standard bypass keeps its inverted logic
+        state.update_from_cc(CC_STANDARD_BYPASS, 0);
+        assert_eq!(state.bypass_state, BypassState::Engaged);
+        state.update_from_cc(CC_STANDARD_BYPASS, 127);
+        assert_eq!(state.bypass_state, BypassState::Bypass);
+    }
 }
@@ -3,10 +3,28 @@
 
 mod types;
 mod mapper;
+mod semantics;
+pub mod capture;
 pub mod commands;
 
 // Re-export public types
 pub use types::*;
+pub use semantics::{ParameterSemantics, CONTEXT_DEPENDENT_PARAMETERS};
+pub use mapper::GLIDE_CCS;
+
+/// First user preset's program change. Per Hologram's documentation PC 0-39 are the
+/// factory presets and PC 40-79 are user presets, so only 40-79 are bank slots we write.
+pub const USER_PRESET_START: u8 = 40;
+
+/// What to do on the pedal once a state is loaded into `program`, per the manual. There is
+/// no MIDI save, so holding the footswitch is the only way to store it. The slot is numbered
+/// the way the library shows it (user presets from 1).
+pub fn manual_save_instructions(program: u8) -> String {
+    format!(
+        "After the parameters are loaded, hold the main footswitch for 2 seconds until the LED blinks white to save to the current preset slot (slot {}).",
+        program.saturating_sub(USER_PRESET_START) + 1
+    )
+}
 
 /// Hologram Chroma Console pedal with complete MIDI control
 /// This is the aggregate root for the Chroma Console domain
@@ -14,6 +32,7 @@ pub use types::*;
 pub struct ChromaConsole {
     pub state: ChromaConsoleState,
     pub midi_channel: u8,
+    pub bypass_mode: BypassMode,  // Footswitch configuration - a device setting, not part of presets
 }
 
 impl ChromaConsole {
@@ -22,12 +41,19 @@ impl ChromaConsole {
         Self {
             state: ChromaConsoleState::default(),
             midi_channel,
+            bypass_mode: BypassMode::default(),
         }
     }
     
+    /// Presets the pedal holds (PC 0-79): 40 factory presets, then 40 user presets
+    /// starting at `USER_PRESET_START`
+    pub fn preset_count() -> u8 {
+        80
+    }
+    
     /// Load a preset from program change number (0-79 for 80 presets)
     pub fn load_preset(&mut self, _program: u8) {
-        // Program change 0-79 maps to 80 user presets
+        // Program change 0-39 recalls a factory preset, 40-79 a user preset
         // This doesn't change internal state, just sends the program change
         // The pedal will respond with parameter updates via CC
     }
@@ -36,24 +62,24 @@ impl ChromaConsole {
     pub fn update_state(&mut self, param: &ChromaConsoleParameter) {
         match param {
             // Primary controls
-            ChromaConsoleParameter::Tilt(v) => self.state.tilt = *v,
-            ChromaConsoleParameter::Rate(v) => self.state.rate = *v,
-            ChromaConsoleParameter::Time(v) => self.state.time = *v,
-            ChromaConsoleParameter::Mix(v) => self.state.mix = *v,
-            ChromaConsoleParameter::AmountCharacter(v) => self.state.amount_character = *v,
-            ChromaConsoleParameter::AmountMovement(v) => self.state.amount_movement = *v,
-            ChromaConsoleParameter::AmountDiffusion(v) => self.state.amount_diffusion = *v,
-            ChromaConsoleParameter::AmountTexture(v) => self.state.amount_texture = *v,
+            ChromaConsoleParameter::Tilt(v) => self.state.tilt = v.get(),
+            ChromaConsoleParameter::Rate(v) => self.state.rate = v.get(),
+            ChromaConsoleParameter::Time(v) => self.state.time = v.get(),
+            ChromaConsoleParameter::Mix(v) => self.state.mix = v.get(),
+            ChromaConsoleParameter::AmountCharacter(v) => self.state.amount_character = v.get(),
+            ChromaConsoleParameter::AmountMovement(v) => self.state.amount_movement = v.get(),
+            ChromaConsoleParameter::AmountDiffusion(v) => self.state.amount_diffusion = v.get(),
+            ChromaConsoleParameter::AmountTexture(v) => self.state.amount_texture = v.get(),
             
             // Secondary controls
-            ChromaConsoleParameter::Sensitivity(v) => self.state.sensitivity = *v,
-            ChromaConsoleParameter::DriftMovement(v) => self.state.drift_movement = *v,
-            ChromaConsoleParameter::DriftDiffusion(v) => self.state.drift_diffusion = *v,
-            ChromaConsoleParameter::OutputLevel(v) => self.state.output_level = *v,
-            ChromaConsoleParameter::EffectVolCharacter(v) => self.state.effect_vol_character = *v,
-            ChromaConsoleParameter::EffectVolMovement(v) => self.state.effect_vol_movement = *v,
-            ChromaConsoleParameter::EffectVolDiffusion(v) => self.state.effect_vol_diffusion = *v,
-            ChromaConsoleParameter::EffectVolTexture(v) => self.state.effect_vol_texture = *v,
+            ChromaConsoleParameter::Sensitivity(v) => self.state.sensitivity = v.get(),
+            ChromaConsoleParameter::DriftMovement(v) => self.state.drift_movement = v.get(),
+            ChromaConsoleParameter::DriftDiffusion(v) => self.state.drift_diffusion = v.get(),
+            ChromaConsoleParameter::OutputLevel(v) => self.state.output_level = v.get(),
+            ChromaConsoleParameter::EffectVolCharacter(v) => self.state.effect_vol_character = v.get(),
+            ChromaConsoleParameter::EffectVolMovement(v) => self.state.effect_vol_movement = v.get(),
+            ChromaConsoleParameter::EffectVolDiffusion(v) => self.state.effect_vol_diffusion = v.get(),
+            ChromaConsoleParameter::EffectVolTexture(v) => self.state.effect_vol_texture = v.get(),
             
             // Module selections
             ChromaConsoleParameter::CharacterModule(m) => self.state.character_module = *m,
@@ -70,7 +96,10 @@ impl ChromaConsole {
             
             // Other functions
             ChromaConsoleParameter::GestureMode(m) => self.state.gesture_mode = *m,
-            ChromaConsoleParameter::CaptureMode(m) => self.state.capture_mode = *m,
+            ChromaConsoleParameter::CaptureMode(m) => {
+                self.state.capture_mode = *m;
+                self.state.capture_transport = self.state.capture_transport.apply(*m);
+            }
             ChromaConsoleParameter::CaptureRouting(r) => self.state.capture_routing = *r,
             ChromaConsoleParameter::FilterMode(m) => self.state.filter_mode = *m,
             ChromaConsoleParameter::CalibrationLevel(l) => self.state.calibration_level = *l,
@@ -85,7 +114,12 @@ impl ChromaConsole {
     /// Get the current state as a hashmap of CC numbers to values
     /// Useful for sending the complete state to the pedal
     pub fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
-        self.state.to_cc_map()
+        self.state.to_cc_map(self.bypass_mode)
+    }
+
+    /// Get the current state as CCs in the order a recall should send them
+    pub fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.state.recall_plan(self.bypass_mode)
     }
 }
 
@@ -122,6 +156,10 @@ impl super::PedalCapabilities for ChromaConsole {
     fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
         self.state_as_cc_map()
     }
+
+    fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.recall_plan()
+    }
     
     fn load_preset(&mut self, program: u8) {
         self.load_preset(program);
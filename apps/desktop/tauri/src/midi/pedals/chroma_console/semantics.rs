@@ -0,0 +1,126 @@
+// What the context-dependent knobs currently do
+// Tilt, Rate and Time change meaning with the filter mode and the selected modules. The
+// editor asks for the effective meaning here instead of duplicating these tables.
+
+use super::{ChromaConsoleState, DiffusionModule, FilterMode, MovementModule};
+use serde::Serialize;
+
+/// State fields whose meaning depends on another setting
+pub const CONTEXT_DEPENDENT_PARAMETERS: &[&str] = &["tilt", "rate", "time"];
+
+/// The current meaning of a context-dependent knob
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ParameterSemantics {
+    /// State field of the knob (e.g. "tilt")
+    pub parameter: &'static str,
+    /// State field that decides its meaning (e.g. "filter_mode")
+    pub depends_on: &'static str,
+    pub label: &'static str,
+    /// How to read the knob's value in this context
+    pub interpretation: &'static str,
+    /// False when the module it controls is off
+    pub active: bool,
+}
+
+impl ParameterSemantics {
+    fn new(parameter: &'static str, depends_on: &'static str, (label, interpretation): (&'static str, &'static str)) -> Self {
+        Self { parameter, depends_on, label, interpretation, active: true }
+    }
+
+    fn inactive(mut self) -> Self {
+        self.active = false;
+        self
+    }
+}
+
+fn tilt_meaning(filter_mode: FilterMode) -> (&'static str, &'static str) {
+    match filter_mode {
+        FilterMode::Lpf => ("LPF cutoff", "Lower values roll off more treble"),
+        FilterMode::Tilt => ("Tilt EQ", "Below noon darker, above noon brighter"),
+        FilterMode::Hpf => ("HPF cutoff", "Higher values roll off more bass"),
+    }
+}
+
+fn rate_meaning(module: MovementModule) -> (&'static str, &'static str) {
+    match module {
+        MovementModule::Doubler => ("Doubler drift", "Speed of the doubled voice's wander"),
+        MovementModule::Vibrato => ("Vibrato speed", "Higher values are faster"),
+        MovementModule::Phaser => ("Phaser speed", "Higher values sweep faster"),
+        MovementModule::Tremolo => ("Tremolo speed", "Higher values are faster"),
+        MovementModule::Pitch => ("Pitch interval", "Steps through the shift intervals"),
+        MovementModule::Off => ("Rate", "No effect while Movement is off"),
+    }
+}
+
+fn time_meaning(module: DiffusionModule) -> (&'static str, &'static str) {
+    match module {
+        DiffusionModule::Cascade => ("Delay time", "Spacing of the cascading repeats"),
+        DiffusionModule::Reels => ("Tape delay time", "Longer values space the echoes further apart"),
+        DiffusionModule::Space => ("Reverb decay", "Higher values give a longer tail"),
+        DiffusionModule::Collage => ("Fragment length", "Length of the rearranged snippets"),
+        DiffusionModule::Reverse => ("Reverse window", "Length of each reversed segment"),
+        DiffusionModule::Off => ("Time", "No effect while Diffusion is off"),
+    }
+}
+
+impl ChromaConsoleState {
+    /// Current meaning of each knob in `CONTEXT_DEPENDENT_PARAMETERS`, in that order
+    pub fn effective_semantics(&self) -> Vec<ParameterSemantics> {
+        let rate = ParameterSemantics::new("rate", "movement_module", rate_meaning(self.movement_module));
+        let time = ParameterSemantics::new("time", "diffusion_module", time_meaning(self.diffusion_module));
+        vec![
+            ParameterSemantics::new("tilt", "filter_mode", tilt_meaning(self.filter_mode)),
+            if self.movement_module == MovementModule::Off { rate.inactive() } else { rate },
+            if self.diffusion_module == DiffusionModule::Off { time.inactive() } else { time },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn semantics_for(state: &ChromaConsoleState, parameter: &str) -> ParameterSemantics {
+        state.effective_semantics().into_iter().find(|s| s.parameter == parameter).unwrap()
+    }
+
+    #[test]
+    fn test_tilt_follows_filter_mode() {
+        let mut state = ChromaConsoleState { filter_mode: FilterMode::Lpf, ..Default::default() };
+        assert_eq!(semantics_for(&state, "tilt").label, "LPF cutoff");
+        state.filter_mode = FilterMode::Hpf;
+        assert_eq!(semantics_for(&state, "tilt").label, "HPF cutoff");
+        state.filter_mode = FilterMode::Tilt;
+        assert_eq!(semantics_for(&state, "tilt").depends_on, "filter_mode");
+        assert_eq!(semantics_for(&state, "tilt").label, "Tilt EQ");
+    }
+
+    #[test]
+    fn test_rate_and_time_follow_their_modules() {
+        let state = ChromaConsoleState {
+            movement_module: MovementModule::Pitch,
+            diffusion_module: DiffusionModule::Space,
+            ..Default::default()
+        };
+        assert_eq!(semantics_for(&state, "rate").label, "Pitch interval");
+        assert_eq!(semantics_for(&state, "time").label, "Reverb decay");
+
+        let off = ChromaConsoleState {
+            movement_module: MovementModule::Off,
+            diffusion_module: DiffusionModule::Off,
+            ..state
+        };
+        assert!(!semantics_for(&off, "rate").active);
+        assert!(!semantics_for(&off, "time").active);
+    }
+
+    #[test]
+    fn test_every_context_dependent_parameter_is_described() {
+        let parameters: Vec<&str> = ChromaConsoleState::default()
+            .effective_semantics()
+            .iter()
+            .map(|s| s.parameter)
+            .collect();
+        assert_eq!(parameters, CONTEXT_DEPENDENT_PARAMETERS);
+    }
+}
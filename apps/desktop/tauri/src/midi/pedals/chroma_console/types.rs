@@ -1,5 +1,6 @@
 // Chroma Console domain types - enums, structs, and value objects
 
+use crate::midi::CcValue;
 use serde::{Deserialize, Serialize};
 
 /// Complete state of all Chroma Console parameters
@@ -44,6 +45,10 @@ pub struct ChromaConsoleState {
     pub capture_routing: CaptureRouting,     // CC# 83
     pub filter_mode: FilterMode,             // CC# 84
     pub calibration_level: CalibrationLevel, // CC# 94
+    
+    // Capture transport (believed state - the pedal doesn't echo it)
+    #[serde(default)]
+    pub capture_transport: CaptureTransport,
 }
 
 impl Default for ChromaConsoleState {
@@ -79,6 +84,7 @@ impl Default for ChromaConsoleState {
             capture_routing: CaptureRouting::PostFx,
             filter_mode: FilterMode::Lpf,
             calibration_level: CalibrationLevel::Medium,
+            capture_transport: CaptureTransport::Empty,
         }
     }
 }
@@ -139,6 +145,15 @@ pub enum BypassState {
     DualBypass,   // CC# 92: 32-63 (only for dual bypass mode)
 }
 
+/// How the pedal's footswitches are configured for bypass.
+/// Standard uses CC# 91; dual footswitch mode uses CC# 92's three-way mapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BypassMode {
+    #[default]
+    Standard,
+    Dual,
+}
+
 /// Gesture play/record mode (CC# 80)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GestureMode {
@@ -154,6 +169,30 @@ pub enum CaptureMode {
     Record,  // 88-127
 }
 
+/// Capture (micro-looper) status as tracked by the app, derived from the
+/// capture modes we send
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CaptureTransport {
+    #[default]
+    Empty,
+    Recording,
+    Playing,
+    Stopped,
+}
+
+impl CaptureTransport {
+    /// Transport after sending a capture mode. Play and Stop do nothing until
+    /// something has been recorded.
+    pub fn apply(self, mode: CaptureMode) -> Self {
+        match (self, mode) {
+            (_, CaptureMode::Record) => CaptureTransport::Recording,
+            (CaptureTransport::Empty, _) => CaptureTransport::Empty,
+            (_, CaptureMode::Play) => CaptureTransport::Playing,
+            (_, CaptureMode::Stop) => CaptureTransport::Stopped,
+        }
+    }
+}
+
 /// Capture routing (CC# 83)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CaptureRouting {
@@ -182,24 +221,24 @@ pub enum CalibrationLevel {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ChromaConsoleParameter {
     // Primary controls
-    Tilt(u8),
-    Rate(u8),
-    Time(u8),
-    Mix(u8),
-    AmountCharacter(u8),
-    AmountMovement(u8),
-    AmountDiffusion(u8),
-    AmountTexture(u8),
+    Tilt(CcValue),
+    Rate(CcValue),
+    Time(CcValue),
+    Mix(CcValue),
+    AmountCharacter(CcValue),
+    AmountMovement(CcValue),
+    AmountDiffusion(CcValue),
+    AmountTexture(CcValue),
     
     // Secondary controls
-    Sensitivity(u8),
-    DriftMovement(u8),
-    DriftDiffusion(u8),
-    OutputLevel(u8),
-    EffectVolCharacter(u8),
-    EffectVolMovement(u8),
-    EffectVolDiffusion(u8),
-    EffectVolTexture(u8),
+    Sensitivity(CcValue),
+    DriftMovement(CcValue),
+    DriftDiffusion(CcValue),
+    OutputLevel(CcValue),
+    EffectVolCharacter(CcValue),
+    EffectVolMovement(CcValue),
+    EffectVolDiffusion(CcValue),
+    EffectVolTexture(CcValue),
     
     // Module selections
     CharacterModule(CharacterModule),
@@ -1,6 +1,8 @@
 // Chase Bliss Audio Clean MIDI CC mapping - infrastructure layer
 
 use super::types::{CleanParameter, CleanState};
+use crate::midi::pedals::DIP_SWITCH_CCS;
+use crate::midi::send_plan::SendPlan;
 use std::collections::HashMap;
 
 pub const CC_PRESET_SAVE: u8 = 111;
@@ -80,7 +82,7 @@ impl CleanParameter {
             | CleanParameter::SwellOut(v)
             | CleanParameter::ShiftyMode(v)
             | CleanParameter::Expression(v)
-            | CleanParameter::PresetSave(v) => *v,
+            | CleanParameter::PresetSave(v) => v.get(),
 
             // Enum parameters
             CleanParameter::ReleaseMode(m) => m.to_cc_value(),
@@ -231,4 +233,10 @@ impl CleanState {
 
         map
     }
+
+    /// Recall order: bypass and spread routing first, then toggles, footswitch modes and DIP switches, then knobs
+    pub fn recall_plan(&self) -> SendPlan {
+        let selections = [&[21, 22, 23, 31, 103, 104, 105, 106, 52][..], DIP_SWITCH_CCS].concat();
+        SendPlan::grouped(self.to_cc_map(), &[102, 33], &selections, &[])
+    }
 }
@@ -28,24 +28,24 @@ impl Clean {
     /// Update internal state from a parameter change
     pub fn update_state(&mut self, param: &CleanParameter) {
         match param {
-            CleanParameter::Dynamics(v) => self.state.dynamics = *v,
-            CleanParameter::Sensitivity(v) => self.state.sensitivity = *v,
-            CleanParameter::Wet(v) => self.state.wet = *v,
-            CleanParameter::Attack(v) => self.state.attack = *v,
-            CleanParameter::Eq(v) => self.state.eq = *v,
-            CleanParameter::Dry(v) => self.state.dry = *v,
-            CleanParameter::RampSpeed(v) => self.state.ramp_speed = *v,
+            CleanParameter::Dynamics(v) => self.state.dynamics = v.get(),
+            CleanParameter::Sensitivity(v) => self.state.sensitivity = v.get(),
+            CleanParameter::Wet(v) => self.state.wet = v.get(),
+            CleanParameter::Attack(v) => self.state.attack = v.get(),
+            CleanParameter::Eq(v) => self.state.eq = v.get(),
+            CleanParameter::Dry(v) => self.state.dry = v.get(),
+            CleanParameter::RampSpeed(v) => self.state.ramp_speed = v.get(),
             CleanParameter::ReleaseMode(v) => self.state.release_mode = *v,
             CleanParameter::EffectMode(v) => self.state.effect_mode = *v,
             CleanParameter::PhysicsMode(v) => self.state.physics_mode = *v,
-            CleanParameter::NoiseGateRelease(v) => self.state.noise_gate_release = *v,
-            CleanParameter::NoiseGateSens(v) => self.state.noise_gate_sens = *v,
-            CleanParameter::SwellIn(v) => self.state.swell_in = *v,
-            CleanParameter::UserRelease(v) => self.state.user_release = *v,
-            CleanParameter::BalanceFilter(v) => self.state.balance_filter = *v,
-            CleanParameter::SwellOut(v) => self.state.swell_out = *v,
+            CleanParameter::NoiseGateRelease(v) => self.state.noise_gate_release = v.get(),
+            CleanParameter::NoiseGateSens(v) => self.state.noise_gate_sens = v.get(),
+            CleanParameter::SwellIn(v) => self.state.swell_in = v.get(),
+            CleanParameter::UserRelease(v) => self.state.user_release = v.get(),
+            CleanParameter::BalanceFilter(v) => self.state.balance_filter = v.get(),
+            CleanParameter::SwellOut(v) => self.state.swell_out = v.get(),
             CleanParameter::EnvelopeMode(v) => self.state.envelope_mode = *v,
-            CleanParameter::ShiftyMode(v) => self.state.shifty_mode = *v,
+            CleanParameter::ShiftyMode(v) => self.state.shifty_mode = v.get(),
             CleanParameter::SpreadRouting(v) => self.state.spread_routing = *v,
             CleanParameter::Bypass(v) => self.state.bypass = *v,
             CleanParameter::Swell(v) => self.state.swell = *v,
@@ -69,7 +69,7 @@ impl Clean {
             CleanParameter::DipSwellAux(v) => self.state.dip_swell_aux = *v,
             CleanParameter::DipDusty(v) => self.state.dip_dusty = *v,
             CleanParameter::RampBounce(v) => self.state.ramp_bounce = *v,
-            CleanParameter::Expression(v) => self.state.expression = *v,
+            CleanParameter::Expression(v) => self.state.expression = v.get(),
             CleanParameter::PresetSave(_) => {} // Does not update state
         }
     }
@@ -78,6 +78,11 @@ impl Clean {
     pub fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
         self.state.to_cc_map()
     }
+
+    /// Get the current state as CCs in the order a recall should send them
+    pub fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.state.recall_plan()
+    }
 }
 
 impl super::PedalCapabilities for Clean {
@@ -108,4 +113,8 @@ impl super::PedalCapabilities for Clean {
     fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
         self.state_as_cc_map()
     }
+
+    fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.recall_plan()
+    }
 }
@@ -1,5 +1,6 @@
 // Chase Bliss Audio Clean domain types
 
+use crate::midi::CcValue;
 use crate::midi::error::{MidiError, MidiResult};
 use serde::{Deserialize, Serialize};
 
@@ -122,13 +123,13 @@ impl Default for CleanState {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum CleanParameter {
     // Main knobs
-    Dynamics(u8),
-    Sensitivity(u8),
-    Wet(u8),
-    Attack(u8),
-    Eq(u8),
-    Dry(u8),
-    RampSpeed(u8),
+    Dynamics(CcValue),
+    Sensitivity(CcValue),
+    Wet(CcValue),
+    Attack(CcValue),
+    Eq(CcValue),
+    Dry(CcValue),
+    RampSpeed(CcValue),
 
     // Toggles
     ReleaseMode(ReleaseMode),
@@ -136,14 +137,14 @@ pub enum CleanParameter {
     PhysicsMode(PhysicsMode),
 
     // Hidden options
-    NoiseGateRelease(u8),
-    NoiseGateSens(u8),
-    SwellIn(u8),
-    UserRelease(u8),
-    BalanceFilter(u8),
-    SwellOut(u8),
+    NoiseGateRelease(CcValue),
+    NoiseGateSens(CcValue),
+    SwellIn(CcValue),
+    UserRelease(CcValue),
+    BalanceFilter(CcValue),
+    SwellOut(CcValue),
     EnvelopeMode(EnvelopeMode),
-    ShiftyMode(u8),
+    ShiftyMode(CcValue),
     SpreadRouting(SpreadRouting),
 
     // Footswitches
@@ -175,8 +176,8 @@ pub enum CleanParameter {
 
     // Utility
     RampBounce(bool),
-    Expression(u8),
-    PresetSave(u8),
+    Expression(CcValue),
+    PresetSave(CcValue),
 }
 
 /// Three-position release mode (CC 21)
@@ -203,7 +204,7 @@ impl ReleaseMode {
             3 => Ok(ReleaseMode::Slow),
             _ => Err(MidiError::InvalidValue {
                 expected: "1-3".to_string(),
-                actual: value,
+                actual: value.into(),
             }),
         }
     }
@@ -233,7 +234,7 @@ impl EffectMode {
             3 => Ok(EffectMode::Modulated),
             _ => Err(MidiError::InvalidValue {
                 expected: "1-3".to_string(),
-                actual: value,
+                actual: value.into(),
             }),
         }
     }
@@ -263,7 +264,7 @@ impl PhysicsMode {
             3 => Ok(PhysicsMode::Twitchy),
             _ => Err(MidiError::InvalidValue {
                 expected: "1-3".to_string(),
-                actual: value,
+                actual: value.into(),
             }),
         }
     }
@@ -293,7 +294,7 @@ impl EnvelopeMode {
             3 => Ok(EnvelopeMode::Adaptive),
             _ => Err(MidiError::InvalidValue {
                 expected: "1-3".to_string(),
-                actual: value,
+                actual: value.into(),
             }),
         }
     }
@@ -323,7 +324,7 @@ impl SpreadRouting {
             3 => Ok(SpreadRouting::VolComp),
             _ => Err(MidiError::InvalidValue {
                 expected: "1-3".to_string(),
-                actual: value,
+                actual: value.into(),
             }),
         }
     }
@@ -1,6 +1,8 @@
 // Tauri commands for Chase Bliss / Meris CXM 1978 Automatone
 
-use crate::midi::SharedMidiManager;
+use crate::midi::{InputChannelMode, PedalType, SharedMidiManager};
+use crate::midi::connect_retry::{connect_with_retry, ConnectOutcome, RetryOptions};
+use crate::midi::safety::RecallOutcome;
 use crate::midi::pedals::cxm1978::{Cxm1978Parameter, Cxm1978State};
 use tauri::State;
 
@@ -10,11 +12,17 @@ pub async fn connect_cxm1978(
     manager: State<'_, SharedMidiManager>,
     device_name: String,
     midi_channel: u8,
-) -> Result<(), String> {
-    let mut manager = manager.lock().map_err(|e| e.to_string())?;
-    manager
-        .connect_cxm1978(&device_name, midi_channel)
-        .map_err(|e| e.to_string())
+    input_mode: Option<InputChannelMode>,
+    retries: Option<u32>,
+    initial_backoff_ms: Option<u64>,
+) -> Result<ConnectOutcome, String> {
+    let options = RetryOptions::from_params(retries, initial_backoff_ms);
+    connect_with_retry(manager.inner(), &device_name, "connect_cxm1978", options, |manager| {
+        manager.connect_with_input_mode(&device_name, input_mode.as_ref(), |manager| {
+            manager.connect_cxm1978(&device_name, midi_channel)
+        })
+    })
+    .map_err(|e| e.to_string())
 }
 
 /// Send a parameter change to a CXM 1978
@@ -25,6 +33,7 @@ pub async fn send_cxm1978_parameter(
     param: Cxm1978Parameter,
 ) -> Result<(), String> {
     let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("send_cxm1978_parameter");
     manager
         .send_cxm1978_parameter(&device_name, param)
         .map_err(|e| e.to_string())
@@ -38,6 +47,7 @@ pub async fn send_cxm1978_program_change(
     program: u8,
 ) -> Result<(), String> {
     let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("send_cxm1978_program_change");
     manager
         .send_cxm1978_program_change(&device_name, program)
         .map_err(|e| e.to_string())
@@ -55,17 +65,47 @@ pub async fn get_cxm1978_state(
         .map_err(|e| e.to_string())
 }
 
+/// Approximate reverb tail (RT60, seconds) for a CXM 1978's current settings.
+/// `room_size` is 0.0 (small) to 1.0 (very large).
+#[tauri::command]
+pub async fn get_cxm1978_reverb_estimate(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    room_size: f32,
+) -> Result<f32, String> {
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    let state = manager
+        .get_cxm1978_state(&device_name)
+        .map_err(|e| e.to_string())?;
+    Ok(state.estimated_reverb_time_seconds(room_size))
+}
+
 /// Recall a CXM 1978 preset (send all parameters via CC)
+/// Loud parameter jumps block the recall until `acknowledge_warnings` is set.
+/// A `glide_ms` above 0 (at most 2 s) ramps `glide_ccs` (the pedal's mix and level
+/// controls by default) to their targets over that long.
 #[tauri::command]
 pub async fn recall_cxm1978_preset(
     manager: State<'_, SharedMidiManager>,
     device_name: String,
     state: Cxm1978State,
-) -> Result<(), String> {
+    acknowledge_warnings: bool,
+    glide_ms: Option<u64>,
+    glide_ccs: Option<Vec<u8>>,
+) -> Result<RecallOutcome, String> {
     let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    let warnings = manager
+        .recall_safety_warnings(&device_name, &state)
+        .map_err(|e| e.to_string())?;
+    if !warnings.is_empty() && !acknowledge_warnings {
+        return Ok(RecallOutcome::blocked(warnings));
+    }
+    manager.begin_journal_operation("recall_cxm1978_preset");
+    let glide = PedalType::Cxm1978.glide(glide_ms, glide_ccs);
     manager
-        .recall_cxm1978_preset(&device_name, &state)
-        .map_err(|e| e.to_string())
+        .gliding(&device_name, glide, |manager| manager.recall_cxm1978_preset(&device_name, &state))
+        .map_err(|e| e.to_string())?;
+    Ok(RecallOutcome::recalled(warnings))
 }
 
 /// Save current state to a CXM 1978 preset slot (0-29)
@@ -76,6 +116,7 @@ pub async fn save_cxm1978_preset(
     slot: u8,
 ) -> Result<(), String> {
     let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("save_cxm1978_preset");
     manager
         .save_cxm1978_preset(&device_name, slot)
         .map_err(|e| e.to_string())
@@ -1,6 +1,8 @@
 // CXM 1978 MIDI mapper — converts between domain types and MIDI CC messages
 
 use super::types::*;
+use crate::midi::send_plan::SendPlan;
+use crate::midi::CcValue;
 use std::collections::HashMap;
 
 // ============================================================================
@@ -15,6 +17,9 @@ pub const CC_TREBLE: u8 = 17;
 pub const CC_MIX: u8 = 18;
 pub const CC_PRE_DLY: u8 = 19;
 
+/// Continuous CCs a gliding recall ramps
+pub const GLIDE_CCS: [u8; 1] = [CC_MIX];
+
 // Arcade buttons (1-3)
 pub const CC_JUMP: u8 = 22;
 pub const CC_REVERB_TYPE: u8 = 23;
@@ -60,6 +65,11 @@ impl Cxm1978State {
         map
     }
 
+    /// Recall order: arcade buttons first, then faders (bypass and expression aren't recalled)
+    pub fn recall_plan(&self) -> SendPlan {
+        SendPlan::grouped(self.to_cc_map(), &[], &[CC_JUMP, CC_REVERB_TYPE, CC_DIFFUSION, CC_TANK_MOD, CC_CLOCK], &[])
+    }
+
     /// Update state from an incoming CC message
     pub fn update_from_cc(&mut self, cc: u8, value: u8) {
         match cc {
@@ -121,7 +131,7 @@ impl Cxm1978Parameter {
             | Cxm1978Parameter::Treble(v)
             | Cxm1978Parameter::Mix(v)
             | Cxm1978Parameter::PreDly(v)
-            | Cxm1978Parameter::Expression(v) => *v,
+            | Cxm1978Parameter::Expression(v) => v.get(),
 
             // Arcade buttons
             Cxm1978Parameter::Jump(j) => j.to_cc_value(),
@@ -135,6 +145,38 @@ impl Cxm1978Parameter {
         }
     }
 
+    /// Parse an incoming CC message into a parameter.
+    /// Arcade buttons use the same value matching as their `from_cc_value`.
+    /// Returns None for CCs that aren't part of the parameter set (e.g. preset save),
+    /// or for a value that isn't a valid CC data byte.
+    pub fn from_cc(cc: u8, value: u8) -> Option<Self> {
+        let cc_value = CcValue::new(value).ok()?;
+        let param = match cc {
+            // Faders
+            CC_BASS => Cxm1978Parameter::Bass(cc_value),
+            CC_MIDS => Cxm1978Parameter::Mids(cc_value),
+            CC_CROSS => Cxm1978Parameter::Cross(cc_value),
+            CC_TREBLE => Cxm1978Parameter::Treble(cc_value),
+            CC_MIX => Cxm1978Parameter::Mix(cc_value),
+            CC_PRE_DLY => Cxm1978Parameter::PreDly(cc_value),
+
+            // Arcade buttons
+            CC_JUMP => Cxm1978Parameter::Jump(Jump::from_cc_value(value)),
+            CC_REVERB_TYPE => Cxm1978Parameter::ReverbType(ReverbType::from_cc_value(value)),
+            CC_DIFFUSION => Cxm1978Parameter::Diffusion(Diffusion::from_cc_value(value)),
+            CC_TANK_MOD => Cxm1978Parameter::TankMod(TankMod::from_cc_value(value)),
+            CC_CLOCK => Cxm1978Parameter::Clock(Clock::from_cc_value(value)),
+
+            // Other controls
+            CC_EXPRESSION => Cxm1978Parameter::Expression(cc_value),
+            CC_BYPASS => Cxm1978Parameter::Bypass(value == 0), // 0 = bypass, 1-127 = engage
+
+            _ => return None,
+        };
+
+        Some(param)
+    }
+
     /// Convert parameter to (CC number, value) tuple
     pub fn to_cc_message(&self) -> Option<(u8, u8)> {
         Some((self.cc_number(), self.cc_value()))
@@ -163,6 +205,7 @@ impl Cxm1978Parameter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::midi::error::MidiResult;
 
     #[test]
     fn test_reverb_type_cc_conversion() {
@@ -181,6 +224,62 @@ mod tests {
         assert_eq!(Jump::from_cc_value(3), Jump::Five);
     }
 
+    #[test]
+    fn test_diffusion_cc_conversion() {
+        assert_eq!(Diffusion::from_cc_value(1), Diffusion::Low);
+        assert_eq!(Diffusion::from_cc_value(2), Diffusion::Med);
+        assert_eq!(Diffusion::from_cc_value(3), Diffusion::High);
+        assert_eq!(Diffusion::High.to_cc_value(), 3);
+    }
+
+    #[test]
+    fn test_tank_mod_cc_conversion() {
+        assert_eq!(TankMod::from_cc_value(1), TankMod::Low);
+        assert_eq!(TankMod::from_cc_value(2), TankMod::Med);
+        assert_eq!(TankMod::from_cc_value(3), TankMod::High);
+        assert_eq!(TankMod::Low.to_cc_value(), 1);
+    }
+
+    #[test]
+    fn test_clock_cc_conversion() {
+        assert_eq!(Clock::from_cc_value(1), Clock::HiFi);
+        assert_eq!(Clock::from_cc_value(2), Clock::Standard);
+        assert_eq!(Clock::from_cc_value(3), Clock::LoFi);
+        assert_eq!(Clock::LoFi.to_cc_value(), 3);
+    }
+
+    /// Every arcade position's label and CC value, so UI strings can't drift from the MIDI mapping
+    #[test]
+    fn test_arcade_labels_match_cc_values() {
+        fn check<T: Copy + PartialEq + std::fmt::Debug>(
+            cases: &[(T, &str, u8)],
+            name: fn(&T) -> &'static str,
+            to_cc: fn(&T) -> u8,
+            try_from_cc: fn(u8) -> MidiResult<T>,
+        ) {
+            for (variant, label, cc) in cases {
+                assert_eq!(name(variant), *label);
+                assert_eq!(to_cc(variant), *cc);
+                assert_eq!(try_from_cc(*cc).unwrap(), *variant);
+            }
+        }
+        check(&[(Jump::Off, "Off", 1), (Jump::Zero, "Jump to 0", 2), (Jump::Five, "Jump to 5", 3)], Jump::name, Jump::to_cc_value, Jump::try_from_cc_value);
+        check(&[(ReverbType::Room, "Room", 1), (ReverbType::Plate, "Plate", 2), (ReverbType::Hall, "Hall", 3)], ReverbType::name, ReverbType::to_cc_value, ReverbType::try_from_cc_value);
+        check(&[(Diffusion::Low, "Low", 1), (Diffusion::Med, "Med", 2), (Diffusion::High, "High", 3)], Diffusion::name, Diffusion::to_cc_value, Diffusion::try_from_cc_value);
+        check(&[(TankMod::Low, "Low", 1), (TankMod::Med, "Med", 2), (TankMod::High, "High", 3)], TankMod::name, TankMod::to_cc_value, TankMod::try_from_cc_value);
+        check(&[(Clock::HiFi, "HiFi", 1), (Clock::Standard, "Standard", 2), (Clock::LoFi, "LoFi", 3)], Clock::name, Clock::to_cc_value, Clock::try_from_cc_value);
+    }
+
+    #[test]
+    fn test_out_of_range_arcade_values() {
+        assert!(ReverbType::try_from_cc_value(0).is_err());
+        assert!(Clock::try_from_cc_value(4).is_err());
+        // Live input keeps the lenient fallback
+        assert_eq!(ReverbType::from_cc_value(0), ReverbType::Room);
+        assert_eq!(Diffusion::from_cc_value(127), Diffusion::Med);
+        assert_eq!(Clock::from_cc_value(4), Clock::Standard);
+    }
+
     #[test]
     fn test_state_to_cc_map() {
         let state = Cxm1978State::default();
@@ -196,9 +295,41 @@ mod tests {
         assert!(!cc_map.contains_key(&CC_BYPASS));
     }
 
+    #[test]
+    fn test_update_from_cc_round_trips_state() {
+        let mut state = Cxm1978State::default();
+        for (cc, value) in [
+            (CC_TREBLE, 30),
+            (CC_PRE_DLY, 90),
+            (CC_JUMP, 3),
+            (CC_REVERB_TYPE, 2),
+            (CC_DIFFUSION, 1),
+            (CC_TANK_MOD, 3),
+            (CC_CLOCK, 1),
+            (CC_EXPRESSION, 40),
+            (CC_BYPASS, 0),
+        ] {
+            state.update_from_cc(cc, value);
+        }
+
+        assert_eq!(state.jump, Jump::Five);
+        assert_eq!(state.reverb_type, ReverbType::Plate);
+        assert_eq!(state.diffusion, Diffusion::Low);
+        assert_eq!(state.tank_mod, TankMod::High);
+        assert_eq!(state.clock, Clock::HiFi);
+        assert_eq!(state.expression, 40);
+        assert!(state.bypass);
+
+        let mut restored = Cxm1978State::default();
+        for (cc, value) in state.to_cc_map() {
+            restored.update_from_cc(cc, value);
+        }
+        assert_eq!(restored.to_cc_map(), state.to_cc_map());
+    }
+
     #[test]
     fn test_parameter_to_cc_message() {
-        let param = Cxm1978Parameter::Mix(100);
+        let param = Cxm1978Parameter::Mix(CcValue::new(100).unwrap());
         assert_eq!(param.to_cc_message(), Some((CC_MIX, 100)));
 
         let param = Cxm1978Parameter::ReverbType(ReverbType::Hall);
@@ -207,4 +338,49 @@ mod tests {
         let param = Cxm1978Parameter::Bypass(false);
         assert_eq!(param.to_cc_message(), Some((CC_BYPASS, 127)));
     }
+
+    #[test]
+    fn test_parameter_from_cc() {
+        assert!(matches!(Cxm1978Parameter::from_cc(CC_MIX, 100), Some(Cxm1978Parameter::Mix(v)) if v.get() == 100));
+        assert!(matches!(
+            Cxm1978Parameter::from_cc(CC_REVERB_TYPE, 3),
+            Some(Cxm1978Parameter::ReverbType(ReverbType::Hall))
+        ));
+        // Out-of-range button values fall back like from_cc_value does
+        assert!(matches!(
+            Cxm1978Parameter::from_cc(CC_DIFFUSION, 90),
+            Some(Cxm1978Parameter::Diffusion(Diffusion::Med))
+        ));
+        assert!(matches!(Cxm1978Parameter::from_cc(CC_BYPASS, 0), Some(Cxm1978Parameter::Bypass(true))));
+        assert!(Cxm1978Parameter::from_cc(CC_PRESET_SAVE, 127).is_none());
+        assert!(Cxm1978Parameter::from_cc(99, 64).is_none());
+    }
+
+    #[test]
+    fn test_from_cc_round_trips_parameters() {
+        let params = [
+            Cxm1978Parameter::Treble(CcValue::new(12).unwrap()),
+            Cxm1978Parameter::Jump(Jump::Five),
+            Cxm1978Parameter::TankMod(TankMod::High),
+            Cxm1978Parameter::Clock(Clock::HiFi),
+            Cxm1978Parameter::Bypass(false),
+        ];
+
+        for param in params {
+            let (cc, value) = param.to_cc_message().unwrap();
+            let parsed = Cxm1978Parameter::from_cc(cc, value).unwrap();
+            assert_eq!(parsed.to_cc_message(), Some((cc, value)));
+        }
+    }
+
+    #[test]
+    fn test_mode_names() {
+        let state = Cxm1978State { clock: Clock::LoFi, ..Cxm1978State::default() };
+        assert_eq!(state.clock_name(), "LoFi");
+
+        let mode_names = Cxm1978State::mode_names();
+        let clock = mode_names.iter().find(|modes| modes.parameter == "clock").unwrap();
+        assert_eq!(clock.names, vec!["HiFi", "Standard", "LoFi"]);
+        assert_eq!(mode_names.len(), 5);
+    }
 }
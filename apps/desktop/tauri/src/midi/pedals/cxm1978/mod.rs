@@ -3,10 +3,11 @@
 
 mod types;
 mod mapper;
+mod reverb_estimate;
 pub mod commands;
 
 pub use types::*;
-pub use mapper::CC_PRESET_SAVE;
+pub use mapper::{CC_PRESET_SAVE, GLIDE_CCS};
 
 /// Chase Bliss CXM 1978 pedal with complete MIDI control
 /// This is the aggregate root for the CXM 1978 domain
@@ -29,12 +30,12 @@ impl Cxm1978 {
     pub fn update_state(&mut self, param: &Cxm1978Parameter) {
         match param {
             // Faders
-            Cxm1978Parameter::Bass(v) => self.state.bass = *v,
-            Cxm1978Parameter::Mids(v) => self.state.mids = *v,
-            Cxm1978Parameter::Cross(v) => self.state.cross = *v,
-            Cxm1978Parameter::Treble(v) => self.state.treble = *v,
-            Cxm1978Parameter::Mix(v) => self.state.mix = *v,
-            Cxm1978Parameter::PreDly(v) => self.state.pre_dly = *v,
+            Cxm1978Parameter::Bass(v) => self.state.bass = v.get(),
+            Cxm1978Parameter::Mids(v) => self.state.mids = v.get(),
+            Cxm1978Parameter::Cross(v) => self.state.cross = v.get(),
+            Cxm1978Parameter::Treble(v) => self.state.treble = v.get(),
+            Cxm1978Parameter::Mix(v) => self.state.mix = v.get(),
+            Cxm1978Parameter::PreDly(v) => self.state.pre_dly = v.get(),
 
             // Arcade buttons
             Cxm1978Parameter::Jump(j) => self.state.jump = *j,
@@ -44,7 +45,7 @@ impl Cxm1978 {
             Cxm1978Parameter::Clock(c) => self.state.clock = *c,
 
             // Other controls
-            Cxm1978Parameter::Expression(v) => self.state.expression = *v,
+            Cxm1978Parameter::Expression(v) => self.state.expression = v.get(),
             Cxm1978Parameter::Bypass(b) => self.state.bypass = *b,
         }
     }
@@ -53,6 +54,11 @@ impl Cxm1978 {
     pub fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
         self.state.to_cc_map()
     }
+
+    /// Get the current state as CCs in the order a recall should send them
+    pub fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.state.recall_plan()
+    }
 }
 
 impl super::PedalCapabilities for Cxm1978 {
@@ -88,6 +94,10 @@ impl super::PedalCapabilities for Cxm1978 {
         self.state_as_cc_map()
     }
 
+    fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.recall_plan()
+    }
+
     fn load_preset(&mut self, _program: u8) {
         // CXM 1978 doesn't support loading presets via MIDI CC.
         // Presets are recalled on the pedal via Program Change or footswitches.
@@ -0,0 +1,81 @@
+// Approximate reverb tail length
+// The CXM doesn't report its decay, so this models RT60 from the faders and algorithm the
+// way the hardware tends to behave: Mids sets the main decay, Bass scales the decay below
+// the Cross frequency, and Treble darkens (and so audibly shortens) the rest.
+
+use super::{Cxm1978State, ReverbType};
+
+impl ReverbType {
+    /// RT60 in seconds with every fader centred in a medium room
+    fn base_decay_seconds(&self) -> f32 {
+        match self {
+            ReverbType::Room => 1.2,
+            ReverbType::Plate => 2.0,
+            ReverbType::Hall => 3.2,
+        }
+    }
+}
+
+/// A fader's position as 0.0-1.0
+fn fader(value: u8) -> f32 {
+    f32::from(value.min(127)) / 127.0
+}
+
+impl Cxm1978State {
+    /// Approximate RT60 in seconds for the current settings. `room_size` runs from 0.0
+    /// (small) to 1.0 (very large) and is clamped to that range.
+    pub fn estimated_reverb_time_seconds(&self, room_size: f32) -> f32 {
+        let room_size = if room_size.is_nan() { 0.5 } else { room_size.clamp(0.0, 1.0) };
+        let size_scale = 0.4 + 1.2 * room_size;
+        let mids_scale = 0.25 + 1.5 * fader(self.mids);
+
+        // Cross decides how much of the spectrum follows the bass decay instead of the mids
+        let bass_weight = fader(self.cross);
+        let bass_scale = 0.5 + fader(self.bass);
+        let treble_scale = 0.85 + 0.3 * fader(self.treble);
+        let band_scale = bass_weight * bass_scale + (1.0 - bass_weight) * treble_scale;
+
+        self.reverb_type.base_decay_seconds() * size_scale * mids_scale * band_scale
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_type(reverb_type: ReverbType) -> Cxm1978State {
+        Cxm1978State { reverb_type, ..Default::default() }
+    }
+
+    #[test]
+    fn test_algorithms_order_by_tail_length() {
+        let room = with_type(ReverbType::Room).estimated_reverb_time_seconds(0.5);
+        let plate = with_type(ReverbType::Plate).estimated_reverb_time_seconds(0.5);
+        let hall = with_type(ReverbType::Hall).estimated_reverb_time_seconds(0.5);
+        assert!(room < plate && plate < hall, "{} {} {}", room, plate, hall);
+        assert!((1.0..1.5).contains(&room), "{}", room);
+    }
+
+    #[test]
+    fn test_room_size_lengthens_and_clamps() {
+        let state = Cxm1978State::default();
+        let small = state.estimated_reverb_time_seconds(0.0);
+        let large = state.estimated_reverb_time_seconds(1.0);
+        assert!(small < large);
+        assert_eq!(state.estimated_reverb_time_seconds(-3.0), small);
+        assert_eq!(state.estimated_reverb_time_seconds(9.0), large);
+    }
+
+    #[test]
+    fn test_bass_decay_only_matters_with_cross_up() {
+        let short_bass = Cxm1978State { bass: 0, cross: 0, ..Default::default() };
+        let long_bass = Cxm1978State { bass: 127, ..short_bass.clone() };
+        assert_eq!(short_bass.estimated_reverb_time_seconds(0.5), long_bass.estimated_reverb_time_seconds(0.5));
+
+        let (short_bass, long_bass) = (
+            Cxm1978State { cross: 127, ..short_bass },
+            Cxm1978State { cross: 127, ..long_bass },
+        );
+        assert!(short_bass.estimated_reverb_time_seconds(0.5) < long_bass.estimated_reverb_time_seconds(0.5));
+    }
+}
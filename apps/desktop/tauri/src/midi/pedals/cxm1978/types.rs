@@ -1,5 +1,8 @@
 // Chase Bliss / Meris CXM 1978 Automatone domain types
 
+use crate::midi::CcValue;
+use crate::midi::error::{MidiError, MidiResult};
+use crate::midi::pedals::{knob_position, ModeNames};
 use serde::{Deserialize, Serialize};
 
 /// Complete state of all CXM 1978 parameters
@@ -45,6 +48,47 @@ impl Default for Cxm1978State {
     }
 }
 
+/// Positions of the CXM 1978's six motorized faders, each 0.0 (down) to 1.0 (up)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct KnobPositions {
+    pub bass: f32,
+    pub mids: f32,
+    pub cross: f32,
+    pub treble: f32,
+    pub mix: f32,
+    pub pre_dly: f32,
+}
+
+impl Cxm1978State {
+    /// Motorized fader positions for drawing the pedal's face
+    pub fn to_knob_positions(&self) -> KnobPositions {
+        KnobPositions {
+            bass: knob_position(self.bass),
+            mids: knob_position(self.mids),
+            cross: knob_position(self.cross),
+            treble: knob_position(self.treble),
+            mix: knob_position(self.mix),
+            pre_dly: knob_position(self.pre_dly),
+        }
+    }
+
+    /// Label of the current Clock position (e.g. "HiFi")
+    pub fn clock_name(&self) -> &'static str {
+        self.clock.name()
+    }
+
+    /// Labels for every arcade button position
+    pub fn mode_names() -> Vec<ModeNames> {
+        vec![
+            ModeNames::new("jump", |value| Jump::from_cc_value(value).name()),
+            ModeNames::new("reverb_type", |value| ReverbType::from_cc_value(value).name()),
+            ModeNames::new("diffusion", |value| Diffusion::from_cc_value(value).name()),
+            ModeNames::new("tank_mod", |value| TankMod::from_cc_value(value).name()),
+            ModeNames::new("clock", |value| Clock::from_cc_value(value).name()),
+        ]
+    }
+}
+
 // ============================================================================
 // Value Objects — Enums representing domain concepts
 // ============================================================================
@@ -93,12 +137,12 @@ pub enum Clock {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Cxm1978Parameter {
     // Faders
-    Bass(u8),
-    Mids(u8),
-    Cross(u8),
-    Treble(u8),
-    Mix(u8),
-    PreDly(u8),
+    Bass(CcValue),
+    Mids(CcValue),
+    Cross(CcValue),
+    Treble(CcValue),
+    Mix(CcValue),
+    PreDly(CcValue),
 
     // Arcade buttons
     Jump(Jump),
@@ -108,7 +152,7 @@ pub enum Cxm1978Parameter {
     Clock(Clock),
 
     // Other controls
-    Expression(u8),
+    Expression(CcValue),
     Bypass(bool),
 }
 
@@ -125,14 +169,24 @@ impl Jump {
         }
     }
 
-    pub fn from_cc_value(value: u8) -> Self {
+    /// Position for a CC value, or an error outside 1-3
+    pub fn try_from_cc_value(value: u8) -> MidiResult<Self> {
         match value {
-            2 => Jump::Zero,
-            3 => Jump::Five,
-            _ => Jump::Off,
+            1 => Ok(Jump::Off),
+            2 => Ok(Jump::Zero),
+            3 => Ok(Jump::Five),
+            _ => Err(MidiError::InvalidValue {
+                expected: "1-3".to_string(),
+                actual: value.into(),
+            }),
         }
     }
 
+    /// Position for a CC value, falling back to Off outside 1-3 (for live input)
+    pub fn from_cc_value(value: u8) -> Self {
+        Self::try_from_cc_value(value).unwrap_or(Jump::Off)
+    }
+
     pub fn name(&self) -> &'static str {
         match self {
             Jump::Off => "Off",
@@ -151,14 +205,24 @@ impl ReverbType {
         }
     }
 
-    pub fn from_cc_value(value: u8) -> Self {
+    /// Position for a CC value, or an error outside 1-3
+    pub fn try_from_cc_value(value: u8) -> MidiResult<Self> {
         match value {
-            2 => ReverbType::Plate,
-            3 => ReverbType::Hall,
-            _ => ReverbType::Room,
+            1 => Ok(ReverbType::Room),
+            2 => Ok(ReverbType::Plate),
+            3 => Ok(ReverbType::Hall),
+            _ => Err(MidiError::InvalidValue {
+                expected: "1-3".to_string(),
+                actual: value.into(),
+            }),
         }
     }
 
+    /// Position for a CC value, falling back to Room outside 1-3 (for live input)
+    pub fn from_cc_value(value: u8) -> Self {
+        Self::try_from_cc_value(value).unwrap_or(ReverbType::Room)
+    }
+
     pub fn name(&self) -> &'static str {
         match self {
             ReverbType::Room => "Room",
@@ -177,14 +241,24 @@ impl Diffusion {
         }
     }
 
-    pub fn from_cc_value(value: u8) -> Self {
+    /// Position for a CC value, or an error outside 1-3
+    pub fn try_from_cc_value(value: u8) -> MidiResult<Self> {
         match value {
-            1 => Diffusion::Low,
-            3 => Diffusion::High,
-            _ => Diffusion::Med,
+            1 => Ok(Diffusion::Low),
+            2 => Ok(Diffusion::Med),
+            3 => Ok(Diffusion::High),
+            _ => Err(MidiError::InvalidValue {
+                expected: "1-3".to_string(),
+                actual: value.into(),
+            }),
         }
     }
 
+    /// Position for a CC value, falling back to Med outside 1-3 (for live input)
+    pub fn from_cc_value(value: u8) -> Self {
+        Self::try_from_cc_value(value).unwrap_or(Diffusion::Med)
+    }
+
     pub fn name(&self) -> &'static str {
         match self {
             Diffusion::Low => "Low",
@@ -203,14 +277,24 @@ impl TankMod {
         }
     }
 
-    pub fn from_cc_value(value: u8) -> Self {
+    /// Position for a CC value, or an error outside 1-3
+    pub fn try_from_cc_value(value: u8) -> MidiResult<Self> {
         match value {
-            1 => TankMod::Low,
-            3 => TankMod::High,
-            _ => TankMod::Med,
+            1 => Ok(TankMod::Low),
+            2 => Ok(TankMod::Med),
+            3 => Ok(TankMod::High),
+            _ => Err(MidiError::InvalidValue {
+                expected: "1-3".to_string(),
+                actual: value.into(),
+            }),
         }
     }
 
+    /// Position for a CC value, falling back to Med outside 1-3 (for live input)
+    pub fn from_cc_value(value: u8) -> Self {
+        Self::try_from_cc_value(value).unwrap_or(TankMod::Med)
+    }
+
     pub fn name(&self) -> &'static str {
         match self {
             TankMod::Low => "Low",
@@ -229,14 +313,24 @@ impl Clock {
         }
     }
 
-    pub fn from_cc_value(value: u8) -> Self {
+    /// Position for a CC value, or an error outside 1-3
+    pub fn try_from_cc_value(value: u8) -> MidiResult<Self> {
         match value {
-            1 => Clock::HiFi,
-            3 => Clock::LoFi,
-            _ => Clock::Standard,
+            1 => Ok(Clock::HiFi),
+            2 => Ok(Clock::Standard),
+            3 => Ok(Clock::LoFi),
+            _ => Err(MidiError::InvalidValue {
+                expected: "1-3".to_string(),
+                actual: value.into(),
+            }),
         }
     }
 
+    /// Position for a CC value, falling back to Standard outside 1-3 (for live input)
+    pub fn from_cc_value(value: u8) -> Self {
+        Self::try_from_cc_value(value).unwrap_or(Clock::Standard)
+    }
+
     pub fn name(&self) -> &'static str {
         match self {
             Clock::HiFi => "HiFi",
@@ -0,0 +1,107 @@
+// Tauri commands for Chase Bliss Audio Dark World
+
+use crate::midi::{InputChannelMode, PedalType, SharedMidiManager};
+use crate::midi::connect_retry::{connect_with_retry, ConnectOutcome, RetryOptions};
+use crate::midi::safety::RecallOutcome;
+use crate::midi::pedals::dark_world::{DarkWorldParameter, DarkWorldState};
+use tauri::State;
+
+/// Connect to a Dark World pedal
+#[tauri::command]
+pub async fn connect_dark_world(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    midi_channel: u8,
+    input_mode: Option<InputChannelMode>,
+    retries: Option<u32>,
+    initial_backoff_ms: Option<u64>,
+) -> Result<ConnectOutcome, String> {
+    let options = RetryOptions::from_params(retries, initial_backoff_ms);
+    connect_with_retry(manager.inner(), &device_name, "connect_dark_world", options, |manager| {
+        manager.connect_with_input_mode(&device_name, input_mode.as_ref(), |manager| {
+            manager.connect_dark_world(&device_name, midi_channel)
+        })
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Send a Dark World parameter change
+#[tauri::command]
+pub async fn send_dark_world_parameter(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    param: DarkWorldParameter,
+) -> Result<(), String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("send_dark_world_parameter");
+    manager
+        .send_dark_world_parameter(&device_name, param)
+        .map_err(|e| e.to_string())
+}
+
+/// Get the current Dark World state
+#[tauri::command]
+pub async fn get_dark_world_state(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+) -> Result<DarkWorldState, String> {
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    manager
+        .get_dark_world_state(&device_name)
+        .map_err(|e| e.to_string())
+}
+
+/// Recall a Dark World preset (send all parameters)
+/// Loud parameter jumps block the recall until `acknowledge_warnings` is set.
+/// A `glide_ms` above 0 (at most 2 s) ramps `glide_ccs` to their targets over that long.
+#[tauri::command]
+pub async fn recall_dark_world_preset(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    state: DarkWorldState,
+    acknowledge_warnings: bool,
+    glide_ms: Option<u64>,
+    glide_ccs: Option<Vec<u8>>,
+) -> Result<RecallOutcome, String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    let warnings = manager
+        .recall_safety_warnings(&device_name, &state)
+        .map_err(|e| e.to_string())?;
+    if !warnings.is_empty() && !acknowledge_warnings {
+        return Ok(RecallOutcome::blocked(warnings));
+    }
+    manager.begin_journal_operation("recall_dark_world_preset");
+    let glide = PedalType::DarkWorld.glide(glide_ms, glide_ccs);
+    manager
+        .gliding(&device_name, glide, |manager| manager.recall_dark_world_preset(&device_name, &state))
+        .map_err(|e| e.to_string())?;
+    Ok(RecallOutcome::recalled(warnings))
+}
+
+/// Save current state to a Dark World preset slot (1-122)
+#[tauri::command]
+pub async fn save_dark_world_preset(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    slot: u8,
+) -> Result<(), String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("save_dark_world_preset");
+    manager
+        .save_dark_world_preset(&device_name, slot)
+        .map_err(|e| e.to_string())
+}
+
+/// Send a program change to a Dark World (navigate to preset slot 1-122)
+#[tauri::command]
+pub async fn send_dark_world_program_change(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    program: u8,
+) -> Result<(), String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("send_dark_world_program_change");
+    manager
+        .send_dark_world_program_change(&device_name, program)
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,156 @@
+// Dark World MIDI CC mapping - infrastructure layer
+// Each engine has its own CC range: side A knobs on 14-17, side B knobs on 24-27.
+// The three toggles (side A type, side B type, routing) sit on 21-23.
+
+use super::types::{DarkWorldParameter, DualEngineState};
+use crate::midi::send_plan::SendPlan;
+use std::collections::HashMap;
+
+pub const CC_PRESET_SAVE: u8 = 111;
+
+impl DarkWorldParameter {
+    /// Get the CC number for this parameter
+    pub fn cc_number(&self) -> u8 {
+        match self {
+            // Side A (Dark)
+            Self::SideADecay(_) => 14,
+            Self::SideAMix(_) => 15,
+            Self::SideATone(_) => 16,
+            Self::SideAModDepth(_) => 17,
+            Self::SideAReverbType(_) => 21,
+            Self::SideAEngaged(_) => 103,
+
+            // Side B (World)
+            Self::SideBDecay(_) => 24,
+            Self::SideBMix(_) => 25,
+            Self::SideBTone(_) => 26,
+            Self::SideBModDepth(_) => 27,
+            Self::SideBReverbType(_) => 22,
+            Self::SideBEngaged(_) => 104,
+
+            // Shared
+            Self::Routing(_) => 23,
+            Self::Expression(_) => 100,
+            Self::Bypass(_) => 102,
+            Self::PresetSave(_) => CC_PRESET_SAVE,
+        }
+    }
+
+    /// Get the CC value for this parameter
+    pub fn cc_value(&self) -> u8 {
+        match self {
+            // Continuous
+            Self::SideADecay(v)
+            | Self::SideAMix(v)
+            | Self::SideATone(v)
+            | Self::SideAModDepth(v)
+            | Self::SideBDecay(v)
+            | Self::SideBMix(v)
+            | Self::SideBTone(v)
+            | Self::SideBModDepth(v)
+            | Self::Expression(v)
+            | Self::PresetSave(v) => v.get(),
+
+            // Three-position enums
+            Self::SideAReverbType(v) => v.to_cc_value(),
+            Self::SideBReverbType(v) => v.to_cc_value(),
+            Self::Routing(v) => v.to_cc_value(),
+
+            // Binary (bool → 0 or 127)
+            Self::SideAEngaged(b)
+            | Self::SideBEngaged(b)
+            | Self::Bypass(b) => if *b { 127 } else { 0 },
+        }
+    }
+
+    /// Get a human-readable name for this parameter
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::SideAReverbType(_) => "Dark Type",
+            Self::SideADecay(_) => "Dark Decay",
+            Self::SideAMix(_) => "Dark Mix",
+            Self::SideATone(_) => "Dark Tone",
+            Self::SideAModDepth(_) => "Dark Mod Depth",
+            Self::SideAEngaged(_) => "Dark",
+            Self::SideBReverbType(_) => "World Type",
+            Self::SideBDecay(_) => "World Decay",
+            Self::SideBMix(_) => "World Mix",
+            Self::SideBTone(_) => "World Tone",
+            Self::SideBModDepth(_) => "World Mod Depth",
+            Self::SideBEngaged(_) => "World",
+            Self::Routing(_) => "Routing",
+            Self::Bypass(_) => "Bypass",
+            Self::Expression(_) => "Expression",
+            Self::PresetSave(_) => "Preset Save",
+        }
+    }
+}
+
+impl DualEngineState {
+    /// Convert the current state to a map of CC numbers → CC values.
+    /// Used when recalling a full preset (sending all parameters at once).
+    pub fn to_cc_map(&self) -> HashMap<u8, u8> {
+        let mut map = HashMap::new();
+
+        // Side A (Dark)
+        map.insert(14, self.side_a.decay);
+        map.insert(15, self.side_a.mix);
+        map.insert(16, self.side_a.tone);
+        map.insert(17, self.side_a.mod_depth);
+        map.insert(21, self.side_a.reverb_type.to_cc_value());
+        map.insert(103, if self.side_a.engaged { 127 } else { 0 });
+
+        // Side B (World)
+        map.insert(24, self.side_b.decay);
+        map.insert(25, self.side_b.mix);
+        map.insert(26, self.side_b.tone);
+        map.insert(27, self.side_b.mod_depth);
+        map.insert(22, self.side_b.reverb_type.to_cc_value());
+        map.insert(104, if self.side_b.engaged { 127 } else { 0 });
+
+        // Shared
+        map.insert(23, self.routing.to_cc_value());
+        map.insert(100, self.expression);
+        map.insert(102, if self.bypass { 127 } else { 0 });
+
+        map
+    }
+
+    /// Recall order: bypass and routing first, then reverb types and engine footswitches, then knobs
+    pub fn recall_plan(&self) -> SendPlan {
+        SendPlan::grouped(self.to_cc_map(), &[102, 23], &[21, 22, 103, 104], &[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::midi::CcValue;
+    use crate::midi::pedals::dark_world::{DarkReverbType, EngineRouting, WorldReverbType};
+
+    #[test]
+    fn test_sides_use_separate_cc_ranges() {
+        let value = CcValue::new(10).unwrap();
+        assert_eq!(DarkWorldParameter::SideAMix(value).cc_number(), 15);
+        assert_eq!(DarkWorldParameter::SideBMix(value).cc_number(), 25);
+        assert_eq!(DarkWorldParameter::SideAModDepth(value).cc_number(), 17);
+        assert_eq!(DarkWorldParameter::SideBModDepth(value).cc_number(), 27);
+    }
+
+    #[test]
+    fn test_state_to_cc_map() {
+        let mut state = DualEngineState::default();
+        state.side_a.reverb_type = DarkReverbType::Black;
+        state.side_b.reverb_type = WorldReverbType::Spring;
+        state.side_b.decay = 100;
+        state.routing = EngineRouting::Split;
+
+        let map = state.to_cc_map();
+        assert_eq!(map.get(&21), Some(&3));
+        assert_eq!(map.get(&22), Some(&3));
+        assert_eq!(map.get(&23), Some(&2));
+        assert_eq!(map.get(&24), Some(&100));
+        assert_eq!(map.get(&14), Some(&64));
+        assert!(!map.contains_key(&CC_PRESET_SAVE));
+    }
+}
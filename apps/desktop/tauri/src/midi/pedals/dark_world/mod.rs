@@ -0,0 +1,140 @@
+// Chase Bliss Audio Dark World MIDI implementation
+// Two independent reverbs in one pedal: side A (Dark) and side B (World), each with its
+// own reverb type and knobs, joined in series, split across the outputs, or combined.
+
+mod mapper;
+mod types;
+pub mod commands;
+
+pub use types::*;
+pub use mapper::CC_PRESET_SAVE;
+
+/// Chase Bliss Audio Dark World pedal with complete MIDI control.
+/// This is the aggregate root for the Dark World domain.
+#[derive(Debug)]
+pub struct DarkWorld {
+    pub state: DarkWorldState,
+    pub midi_channel: u8,
+}
+
+impl DarkWorld {
+    /// Create a new Dark World instance with default state
+    pub fn new(midi_channel: u8) -> Self {
+        Self {
+            state: DarkWorldState::default(),
+            midi_channel,
+        }
+    }
+
+    /// Update internal state from a parameter change
+    pub fn update_state(&mut self, param: &DarkWorldParameter) {
+        let side_a = &mut self.state.side_a;
+        let side_b = &mut self.state.side_b;
+        match param {
+            DarkWorldParameter::SideAReverbType(v) => side_a.reverb_type = *v,
+            DarkWorldParameter::SideADecay(v) => side_a.decay = v.get(),
+            DarkWorldParameter::SideAMix(v) => side_a.mix = v.get(),
+            DarkWorldParameter::SideATone(v) => side_a.tone = v.get(),
+            DarkWorldParameter::SideAModDepth(v) => side_a.mod_depth = v.get(),
+            DarkWorldParameter::SideAEngaged(v) => side_a.engaged = *v,
+            DarkWorldParameter::SideBReverbType(v) => side_b.reverb_type = *v,
+            DarkWorldParameter::SideBDecay(v) => side_b.decay = v.get(),
+            DarkWorldParameter::SideBMix(v) => side_b.mix = v.get(),
+            DarkWorldParameter::SideBTone(v) => side_b.tone = v.get(),
+            DarkWorldParameter::SideBModDepth(v) => side_b.mod_depth = v.get(),
+            DarkWorldParameter::SideBEngaged(v) => side_b.engaged = *v,
+            DarkWorldParameter::Routing(v) => self.state.routing = *v,
+            DarkWorldParameter::Bypass(v) => self.state.bypass = *v,
+            DarkWorldParameter::Expression(v) => self.state.expression = v.get(),
+            DarkWorldParameter::PresetSave(_) => {} // Does not update state
+        }
+    }
+
+    /// Get the current state as a hashmap of CC numbers to values
+    pub fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
+        self.state.to_cc_map()
+    }
+
+    /// Get the current state as CCs in the order a recall should send them
+    pub fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.state.recall_plan()
+    }
+}
+
+impl super::PedalCapabilities for DarkWorld {
+    type State = DarkWorldState;
+    type Parameter = DarkWorldParameter;
+
+    fn metadata(&self) -> super::PedalMetadata {
+        super::PedalMetadata {
+            name: "Dark World",
+            manufacturer: "Chase Bliss Audio",
+            supports_editor: true,
+            supports_preset_library: true,
+        }
+    }
+
+    fn supports_program_change(&self) -> bool {
+        true
+    }
+
+    fn midi_channel(&self) -> u8 {
+        self.midi_channel
+    }
+
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+
+    fn update_state(&mut self, param: &Self::Parameter) {
+        self.update_state(param)
+    }
+
+    fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
+        self.state_as_cc_map()
+    }
+
+    fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.recall_plan()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::midi::CcValue;
+
+    #[test]
+    fn test_update_state_touches_only_its_side() {
+        let mut dark_world = DarkWorld::new(1);
+        dark_world.update_state(&DarkWorldParameter::SideBDecay(CcValue::new(120).unwrap()));
+        dark_world.update_state(&DarkWorldParameter::SideAReverbType(DarkReverbType::Shimmer));
+        dark_world.update_state(&DarkWorldParameter::Routing(EngineRouting::Combine));
+
+        assert_eq!(dark_world.state.side_b.decay, 120);
+        assert_eq!(dark_world.state.side_a.decay, SideAState::default().decay);
+        assert_eq!(dark_world.state.side_a.reverb_type, DarkReverbType::Shimmer);
+        assert_eq!(dark_world.state.side_b.reverb_type, WorldReverbType::Hall);
+        assert_eq!(dark_world.state.routing, EngineRouting::Combine);
+    }
+
+    #[test]
+    fn test_state_serializes_with_nested_sides() {
+        let json = serde_json::to_value(DarkWorldState::default()).unwrap();
+        assert_eq!(json["side_a"]["reverb_type"], "Mod");
+        assert_eq!(json["side_b"]["reverb_type"], "Hall");
+        assert_eq!(json["routing"], "Series");
+
+        let restored: DarkWorldState = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, DarkWorldState::default());
+    }
+
+    #[test]
+    fn test_toggle_cc_values_round_trip() {
+        for routing in [EngineRouting::Series, EngineRouting::Split, EngineRouting::Combine] {
+            assert_eq!(EngineRouting::from_cc_value(routing.to_cc_value()).unwrap(), routing);
+        }
+        assert!(DarkReverbType::from_cc_value(0).is_err());
+        assert_eq!(WorldReverbType::from_cc_value(2).unwrap(), WorldReverbType::Plate);
+    }
+}
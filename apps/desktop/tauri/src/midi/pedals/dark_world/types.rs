@@ -0,0 +1,202 @@
+// Dark World domain types
+
+use crate::midi::CcValue;
+use crate::midi::error::{MidiError, MidiResult};
+use serde::{Deserialize, Serialize};
+
+/// Side A - the Dark engine
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SideAState {
+    pub reverb_type: DarkReverbType,
+    pub decay: u8,
+    pub mix: u8,
+    pub tone: u8,
+    pub mod_depth: u8,
+    /// Left footswitch
+    pub engaged: bool,
+}
+
+impl Default for SideAState {
+    fn default() -> Self {
+        Self {
+            reverb_type: DarkReverbType::Mod,
+            decay: 64,
+            mix: 64,
+            tone: 64,
+            mod_depth: 32,
+            engaged: true,
+        }
+    }
+}
+
+/// Side B - the World engine
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SideBState {
+    pub reverb_type: WorldReverbType,
+    pub decay: u8,
+    pub mix: u8,
+    pub tone: u8,
+    pub mod_depth: u8,
+    /// Right footswitch
+    pub engaged: bool,
+}
+
+impl Default for SideBState {
+    fn default() -> Self {
+        Self {
+            reverb_type: WorldReverbType::Hall,
+            decay: 64,
+            mix: 64,
+            tone: 64,
+            mod_depth: 32,
+            engaged: true,
+        }
+    }
+}
+
+/// Complete state of both Dark World engines and the controls they share
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DualEngineState {
+    pub side_a: SideAState,
+    pub side_b: SideBState,
+
+    /// How the two engines are combined (CC 23)
+    pub routing: EngineRouting,
+
+    pub bypass: bool,
+    pub expression: u8,
+}
+
+/// The Dark World's pedal state - both engines plus shared controls
+pub type DarkWorldState = DualEngineState;
+
+impl Default for DualEngineState {
+    fn default() -> Self {
+        Self {
+            side_a: SideAState::default(),
+            side_b: SideBState::default(),
+            routing: EngineRouting::Series,
+            bypass: false,
+            expression: 0,
+        }
+    }
+}
+
+/// All possible Dark World parameters with their values
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum DarkWorldParameter {
+    // Side A (Dark)
+    SideAReverbType(DarkReverbType),
+    SideADecay(CcValue),
+    SideAMix(CcValue),
+    SideATone(CcValue),
+    SideAModDepth(CcValue),
+    SideAEngaged(bool),
+
+    // Side B (World)
+    SideBReverbType(WorldReverbType),
+    SideBDecay(CcValue),
+    SideBMix(CcValue),
+    SideBTone(CcValue),
+    SideBModDepth(CcValue),
+    SideBEngaged(bool),
+
+    // Shared
+    Routing(EngineRouting),
+    Bypass(bool),
+    Expression(CcValue),
+    PresetSave(CcValue),
+}
+
+/// Side A reverb type toggle (CC 21)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DarkReverbType {
+    Mod,     // 1
+    Shimmer, // 2
+    Black,   // 3
+}
+
+impl DarkReverbType {
+    pub fn to_cc_value(self) -> u8 {
+        match self {
+            Self::Mod => 1,
+            Self::Shimmer => 2,
+            Self::Black => 3,
+        }
+    }
+
+    pub fn from_cc_value(value: u8) -> MidiResult<Self> {
+        match value {
+            1 => Ok(Self::Mod),
+            2 => Ok(Self::Shimmer),
+            3 => Ok(Self::Black),
+            _ => Err(MidiError::InvalidValue {
+                expected: "1-3".to_string(),
+                actual: value.into(),
+            }),
+        }
+    }
+}
+
+/// Side B reverb type toggle (CC 22)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WorldReverbType {
+    Hall,   // 1
+    Plate,  // 2
+    Spring, // 3
+}
+
+impl WorldReverbType {
+    pub fn to_cc_value(self) -> u8 {
+        match self {
+            Self::Hall => 1,
+            Self::Plate => 2,
+            Self::Spring => 3,
+        }
+    }
+
+    pub fn from_cc_value(value: u8) -> MidiResult<Self> {
+        match value {
+            1 => Ok(Self::Hall),
+            2 => Ok(Self::Plate),
+            3 => Ok(Self::Spring),
+            _ => Err(MidiError::InvalidValue {
+                expected: "1-3".to_string(),
+                actual: value.into(),
+            }),
+        }
+    }
+}
+
+/// Engine routing toggle (CC 23)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EngineRouting {
+    /// Side A feeds side B
+    Series, // 1
+    /// Side A on the left output, side B on the right
+    Split, // 2
+    /// Both engines in parallel, summed to both outputs
+    Combine, // 3
+}
+
+impl EngineRouting {
+    pub fn to_cc_value(self) -> u8 {
+        match self {
+            Self::Series => 1,
+            Self::Split => 2,
+            Self::Combine => 3,
+        }
+    }
+
+    pub fn from_cc_value(value: u8) -> MidiResult<Self> {
+        match value {
+            1 => Ok(Self::Series),
+            2 => Ok(Self::Split),
+            3 => Ok(Self::Combine),
+            _ => Err(MidiError::InvalidValue {
+                expected: "1-3".to_string(),
+                actual: value.into(),
+            }),
+        }
+    }
+}
@@ -0,0 +1,99 @@
+// DIP switch combinations that behave unexpectedly together
+// Each DIP is harmless on its own; these are the interactions worth a look before saving.
+
+use super::GenLossMkiiState;
+use serde::{Deserialize, Serialize};
+
+/// Wow above this with both the wow and bounce DIPs on can run away into feedback
+const RUNAWAY_WOW_THRESHOLD: u8 = 96;
+
+/// How much a coherence warning matters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningSeverity {
+    /// Does nothing useful, but does no harm
+    Info,
+    /// Can get loud or unstable
+    Danger,
+}
+
+/// A known-problematic DIP combination found in a state
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoherenceWarning {
+    /// State field names of the DIPs involved
+    pub dips: Vec<String>,
+    pub description: String,
+    pub severity: WarningSeverity,
+}
+
+impl CoherenceWarning {
+    fn new(dips: &[&str], description: &str, severity: WarningSeverity) -> Self {
+        Self {
+            dips: dips.iter().map(|dip| dip.to_string()).collect(),
+            description: description.to_string(),
+            severity,
+        }
+    }
+}
+
+impl GenLossMkiiState {
+    /// Check the DIP switches for combinations known to misbehave together
+    pub fn validate_coherence(&self) -> Vec<CoherenceWarning> {
+        let mut warnings = Vec::new();
+
+        if self.dip_wow && self.dip_bounce && self.wow > RUNAWAY_WOW_THRESHOLD {
+            warnings.push(CoherenceWarning::new(
+                &["dip_wow", "dip_bounce"],
+                "Bouncing the wow ramp with Wow this high can run away into feedback",
+                WarningSeverity::Danger,
+            ));
+        }
+
+        // Bounce and random shape the ramp, which only moves the knobs the left bank selects
+        let ramps_a_knob = self.dip_wow || self.dip_flutter || self.dip_sat_gen || self.dip_failure_hp || self.dip_model_lp;
+        let shapers: Vec<&str> = [("dip_bounce", self.dip_bounce), ("dip_random", self.dip_random)]
+            .into_iter()
+            .filter_map(|(dip, on)| on.then_some(dip))
+            .collect();
+        if !ramps_a_knob && !shapers.is_empty() {
+            warnings.push(CoherenceWarning::new(
+                &shapers,
+                "No knob DIP is on, so there is no ramp for this to shape",
+                WarningSeverity::Info,
+            ));
+        }
+
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_state_is_coherent() {
+        assert!(GenLossMkiiState::default().validate_coherence().is_empty());
+    }
+
+    #[test]
+    fn test_wow_bounce_runaway_needs_high_wow() {
+        let mut state = GenLossMkiiState { dip_wow: true, dip_bounce: true, wow: 64, ..Default::default() };
+        assert!(state.validate_coherence().is_empty());
+
+        state.wow = 120;
+        let warnings = state.validate_coherence();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].dips, vec!["dip_wow", "dip_bounce"]);
+        assert_eq!(warnings[0].severity, WarningSeverity::Danger);
+    }
+
+    #[test]
+    fn test_ramp_shapers_without_knob_dip() {
+        let state = GenLossMkiiState { dip_bounce: true, dip_random: true, ..Default::default() };
+        let warnings = state.validate_coherence();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].dips, vec!["dip_bounce", "dip_random"]);
+        assert_eq!(warnings[0].severity, WarningSeverity::Info);
+    }
+}
@@ -1,7 +1,10 @@
 // Tauri commands for Chase Bliss Generation Loss MKII pedal
 
-use crate::midi::SharedMidiManager;
-use crate::midi::pedals::gen_loss_mkii::{GenLossMkiiParameter, GenLossMkiiState};
+use crate::midi::{InputChannelMode, PedalType, SharedMidiManager};
+use crate::presets::{PresetId, SharedPresetLibrary};
+use crate::midi::connect_retry::{connect_with_retry, ConnectOutcome, RetryOptions};
+use crate::midi::safety::RecallOutcome;
+use crate::midi::pedals::gen_loss_mkii::{CoherenceWarning, DipProfile, GenLossDipSwitches, GenLossMkiiParameter, GenLossMkiiState};
 use tauri::State;
 
 /// Connect to a Gen Loss MKII pedal
@@ -10,11 +13,17 @@ pub async fn connect_gen_loss_mkii(
     manager: State<'_, SharedMidiManager>,
     device_name: String,
     midi_channel: u8,
-) -> Result<(), String> {
-    let mut manager = manager.lock().map_err(|e| e.to_string())?;
-    manager
-        .connect_gen_loss_mkii(&device_name, midi_channel)
-        .map_err(|e| e.to_string())
+    input_mode: Option<InputChannelMode>,
+    retries: Option<u32>,
+    initial_backoff_ms: Option<u64>,
+) -> Result<ConnectOutcome, String> {
+    let options = RetryOptions::from_params(retries, initial_backoff_ms);
+    connect_with_retry(manager.inner(), &device_name, "connect_gen_loss_mkii", options, |manager| {
+        manager.connect_with_input_mode(&device_name, input_mode.as_ref(), |manager| {
+            manager.connect_gen_loss_mkii(&device_name, midi_channel)
+        })
+    })
+    .map_err(|e| e.to_string())
 }
 
 /// Send a Gen Loss MKII parameter change
@@ -25,6 +34,7 @@ pub async fn send_gen_loss_parameter(
     param: GenLossMkiiParameter,
 ) -> Result<(), String> {
     let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("send_gen_loss_parameter");
     manager
         .send_gen_loss_parameter(&device_name, param)
         .map_err(|e| e.to_string())
@@ -42,16 +52,103 @@ pub async fn get_gen_loss_state(
         .map_err(|e| e.to_string())
 }
 
+/// DIP switch combinations in `state` known to misbehave together, for a check before saving
+#[tauri::command]
+pub async fn validate_gen_loss_state(state: GenLossMkiiState) -> Result<Vec<CoherenceWarning>, String> {
+    Ok(state.validate_coherence())
+}
+
 /// Recall a Gen Loss MKII preset (send all parameters)
+/// Loud parameter jumps block the recall until `acknowledge_warnings` is set.
+/// DIP switches stay as they are unless `include_dip_switches` is set.
+/// A `glide_ms` above 0 (at most 2 s) ramps `glide_ccs` to their targets over that long.
 #[tauri::command]
 pub async fn recall_gen_loss_preset(
     manager: State<'_, SharedMidiManager>,
     device_name: String,
     state: GenLossMkiiState,
+    acknowledge_warnings: bool,
+    include_dip_switches: Option<bool>,
+    glide_ms: Option<u64>,
+    glide_ccs: Option<Vec<u8>>,
+) -> Result<RecallOutcome, String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    let warnings = manager
+        .recall_safety_warnings(&device_name, &state)
+        .map_err(|e| e.to_string())?;
+    if !warnings.is_empty() && !acknowledge_warnings {
+        return Ok(RecallOutcome::blocked(warnings));
+    }
+    manager.begin_journal_operation("recall_gen_loss_preset");
+    let glide = PedalType::GenLossMkii.glide(glide_ms, glide_ccs);
+    let include_dip_switches = include_dip_switches.unwrap_or(false);
+    manager
+        .gliding(&device_name, glide, |manager| manager.recall_gen_loss_preset(&device_name, &state, include_dip_switches))
+        .map_err(|e| e.to_string())?;
+    Ok(RecallOutcome::recalled(warnings))
+}
+
+/// Saved DIP switch profiles, by name
+#[tauri::command]
+pub async fn list_gen_loss_dip_profiles(
+    manager: State<'_, SharedMidiManager>,
+) -> Result<Vec<DipProfile>, String> {
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    Ok(manager.gen_loss_dip_profiles().list().to_vec())
+}
+
+/// One saved DIP switch profile
+#[tauri::command]
+pub async fn get_gen_loss_dip_profile(
+    manager: State<'_, SharedMidiManager>,
+    name: String,
+) -> Result<DipProfile, String> {
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    manager
+        .gen_loss_dip_profiles()
+        .get(&name)
+        .cloned()
+        .map_err(|e| e.to_string())
+}
+
+/// Save a DIP switch profile, replacing any with the same name
+#[tauri::command]
+pub async fn save_gen_loss_dip_profile(
+    manager: State<'_, SharedMidiManager>,
+    name: String,
+    dips: GenLossDipSwitches,
+) -> Result<DipProfile, String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager
+        .gen_loss_dip_profiles_mut()
+        .save(&name, dips)
+        .map_err(|e| e.to_string())
+}
+
+/// Delete a saved DIP switch profile
+#[tauri::command]
+pub async fn delete_gen_loss_dip_profile(
+    manager: State<'_, SharedMidiManager>,
+    name: String,
 ) -> Result<(), String> {
     let mut manager = manager.lock().map_err(|e| e.to_string())?;
     manager
-        .recall_gen_loss_preset(&device_name, &state)
+        .gen_loss_dip_profiles_mut()
+        .delete(&name)
+        .map_err(|e| e.to_string())
+}
+
+/// Set a Gen Loss MKII's DIP switches from a saved profile
+#[tauri::command]
+pub async fn apply_gen_loss_dip_profile(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    name: String,
+) -> Result<DipProfile, String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("apply_gen_loss_dip_profile");
+    manager
+        .apply_gen_loss_dip_profile(&device_name, &name)
         .map_err(|e| e.to_string())
 }
 
@@ -63,11 +160,50 @@ pub async fn save_gen_loss_preset(
     slot: u8,
 ) -> Result<(), String> {
     let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("save_gen_loss_preset");
     manager
         .save_gen_loss_preset(&device_name, slot)
         .map_err(|e| e.to_string())
 }
 
+/// Save the pedal's current state to preset slot 1-122 and keep the bank grid in step:
+/// with `preset_id` the slot is assigned that preset (moved from any other slot), otherwise
+/// the slot's old assignment is cleared unless it's what the pedal already holds. What the
+/// slot held before is kept in bank history, as any bank overwrite is.
+#[tauri::command]
+pub async fn save_gen_loss_preset_to_slot(
+    manager: State<'_, SharedMidiManager>,
+    library: State<'_, SharedPresetLibrary>,
+    device_name: String,
+    slot: u8,
+    preset_id: Option<String>,
+) -> Result<(), String> {
+    const PEDAL_TYPE: &str = "GenLossMkii";
+    let snapshot = {
+        let library = library.lock().map_err(|e| e.to_string())?;
+        library.snapshot_bank(PEDAL_TYPE, slot).map_err(|e| e.to_string())?
+    };
+
+    let saved_state = {
+        let mut manager = manager.lock().map_err(|e| e.to_string())?;
+        manager.begin_journal_operation("save_gen_loss_preset_to_slot");
+        let state = manager.get_device_state(&device_name).map_err(|e| e.to_string())?.state;
+        manager.save_gen_loss_preset(&device_name, slot).map_err(|e| e.to_string())?;
+        state
+    };
+
+    let library = library.lock().map_err(|e| e.to_string())?;
+    library
+        .record_bank_overwrite(&snapshot, &device_name, &saved_state)
+        .map_err(|e| e.to_string())?;
+    match preset_id {
+        Some(preset_id) => library.move_to_bank(PEDAL_TYPE, slot, &PresetId::new(preset_id)),
+        None if snapshot.previous.is_some_and(|previous| previous.parameters == saved_state) => Ok(()),
+        None => library.clear_bank(PEDAL_TYPE, slot),
+    }
+    .map_err(|e| e.to_string())
+}
+
 /// Send a program change to a Gen Loss MKII (navigate to preset slot 1-122)
 #[tauri::command]
 pub async fn send_gen_loss_program_change(
@@ -76,6 +212,7 @@ pub async fn send_gen_loss_program_change(
     program: u8,
 ) -> Result<(), String> {
     let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("send_gen_loss_program_change");
     manager
         .send_gen_loss_program_change(&device_name, program)
         .map_err(|e| e.to_string())
@@ -0,0 +1,222 @@
+// DIP switch profiles
+// The DIP switches configure how the pedal sits in a rig (what the ramp moves, bypass
+// behaviour, stereo routing) rather than how it sounds. Presets recall without them by
+// default; DIP settings are kept as named profiles in the app data dir and applied on purpose.
+
+use super::{GenLossMkiiParameter, GenLossMkiiState, Polarity, SweepDirection};
+use crate::midi::error::{MidiError, MidiResult};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Profile file inside the app data dir
+pub const DIP_PROFILES_FILE: &str = "gen_loss_dip_profiles.json";
+
+/// The 16 DIP switch positions, as on GenLossMkiiState
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenLossDipSwitches {
+    // Left bank
+    pub dip_wow: bool,
+    pub dip_flutter: bool,
+    pub dip_sat_gen: bool,
+    pub dip_failure_hp: bool,
+    pub dip_model_lp: bool,
+    pub dip_bounce: bool,
+    pub dip_random: bool,
+    pub dip_sweep: SweepDirection,
+
+    // Right bank
+    pub dip_polarity: Polarity,
+    pub dip_classic: bool,
+    pub dip_miso: bool,
+    pub dip_spread: bool,
+    pub dip_dry_type: bool,
+    pub dip_drop_byp: bool,
+    pub dip_snag_byp: bool,
+    pub dip_hum_byp: bool,
+}
+
+impl From<&GenLossMkiiState> for GenLossDipSwitches {
+    fn from(state: &GenLossMkiiState) -> Self {
+        Self {
+            dip_wow: state.dip_wow,
+            dip_flutter: state.dip_flutter,
+            dip_sat_gen: state.dip_sat_gen,
+            dip_failure_hp: state.dip_failure_hp,
+            dip_model_lp: state.dip_model_lp,
+            dip_bounce: state.dip_bounce,
+            dip_random: state.dip_random,
+            dip_sweep: state.dip_sweep,
+            dip_polarity: state.dip_polarity,
+            dip_classic: state.dip_classic,
+            dip_miso: state.dip_miso,
+            dip_spread: state.dip_spread,
+            dip_dry_type: state.dip_dry_type,
+            dip_drop_byp: state.dip_drop_byp,
+            dip_snag_byp: state.dip_snag_byp,
+            dip_hum_byp: state.dip_hum_byp,
+        }
+    }
+}
+
+impl GenLossDipSwitches {
+    /// Each switch as a parameter, left bank then right bank
+    pub fn parameters(&self) -> Vec<GenLossMkiiParameter> {
+        vec![
+            GenLossMkiiParameter::DipWow(self.dip_wow),
+            GenLossMkiiParameter::DipFlutter(self.dip_flutter),
+            GenLossMkiiParameter::DipSatGen(self.dip_sat_gen),
+            GenLossMkiiParameter::DipFailureHp(self.dip_failure_hp),
+            GenLossMkiiParameter::DipModelLp(self.dip_model_lp),
+            GenLossMkiiParameter::DipBounce(self.dip_bounce),
+            GenLossMkiiParameter::DipRandom(self.dip_random),
+            GenLossMkiiParameter::DipSweep(self.dip_sweep),
+            GenLossMkiiParameter::DipPolarity(self.dip_polarity),
+            GenLossMkiiParameter::DipClassic(self.dip_classic),
+            GenLossMkiiParameter::DipMiso(self.dip_miso),
+            GenLossMkiiParameter::DipSpread(self.dip_spread),
+            GenLossMkiiParameter::DipDryType(self.dip_dry_type),
+            GenLossMkiiParameter::DipDropByp(self.dip_drop_byp),
+            GenLossMkiiParameter::DipSnagByp(self.dip_snag_byp),
+            GenLossMkiiParameter::DipHumByp(self.dip_hum_byp),
+        ]
+    }
+
+    /// Set these positions on `state`, leaving everything else alone
+    pub fn apply_to(&self, state: &mut GenLossMkiiState) {
+        state.dip_wow = self.dip_wow;
+        state.dip_flutter = self.dip_flutter;
+        state.dip_sat_gen = self.dip_sat_gen;
+        state.dip_failure_hp = self.dip_failure_hp;
+        state.dip_model_lp = self.dip_model_lp;
+        state.dip_bounce = self.dip_bounce;
+        state.dip_random = self.dip_random;
+        state.dip_sweep = self.dip_sweep;
+        state.dip_polarity = self.dip_polarity;
+        state.dip_classic = self.dip_classic;
+        state.dip_miso = self.dip_miso;
+        state.dip_spread = self.dip_spread;
+        state.dip_dry_type = self.dip_dry_type;
+        state.dip_drop_byp = self.dip_drop_byp;
+        state.dip_snag_byp = self.dip_snag_byp;
+        state.dip_hum_byp = self.dip_hum_byp;
+    }
+}
+
+/// A named set of DIP switch positions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DipProfile {
+    pub name: String,
+    pub dips: GenLossDipSwitches,
+}
+
+/// Saved DIP profiles, sorted by name. Changes are written through to the file when
+/// there is one.
+#[derive(Debug, Default)]
+pub struct DipProfileStore {
+    path: Option<PathBuf>,
+    profiles: Vec<DipProfile>,
+}
+
+impl DipProfileStore {
+    /// Load profiles from (and save future changes to) this file; a missing file means none yet
+    pub fn load(path: PathBuf) -> MidiResult<Self> {
+        let profiles = if path.exists() {
+            let contents = fs::read_to_string(&path)
+                .map_err(|e| MidiError::Other(format!("Failed to read DIP profiles: {}", e)))?;
+            serde_json::from_str(&contents)
+                .map_err(|e| MidiError::Other(format!("Failed to parse DIP profiles: {}", e)))?
+        } else {
+            Vec::new()
+        };
+        Ok(Self { path: Some(path), profiles })
+    }
+
+    pub fn list(&self) -> &[DipProfile] {
+        &self.profiles
+    }
+
+    pub fn get(&self, name: &str) -> MidiResult<&DipProfile> {
+        self.profiles
+            .iter()
+            .find(|profile| profile.name == name)
+            .ok_or_else(|| MidiError::Other(format!("No DIP profile named '{}'", name)))
+    }
+
+    /// Create a profile, or replace the one with the same name
+    pub fn save(&mut self, name: &str, dips: GenLossDipSwitches) -> MidiResult<DipProfile> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(MidiError::Other("DIP profile name can't be empty".to_string()));
+        }
+        let profile = DipProfile { name: name.to_string(), dips };
+        match self.profiles.binary_search_by(|existing| existing.name.as_str().cmp(name)) {
+            Ok(index) => self.profiles[index] = profile.clone(),
+            Err(index) => self.profiles.insert(index, profile.clone()),
+        }
+        self.persist()?;
+        Ok(profile)
+    }
+
+    pub fn delete(&mut self, name: &str) -> MidiResult<()> {
+        let before = self.profiles.len();
+        self.profiles.retain(|profile| profile.name != name);
+        if self.profiles.len() == before {
+            return Err(MidiError::Other(format!("No DIP profile named '{}'", name)));
+        }
+        self.persist()
+    }
+
+    fn persist(&self) -> MidiResult<()> {
+        let Some(path) = &self.path else { return Ok(()) };
+        save_profiles(path, &self.profiles)
+    }
+}
+
+fn save_profiles(path: &Path, profiles: &[DipProfile]) -> MidiResult<()> {
+    let contents = serde_json::to_string_pretty(profiles)
+        .map_err(|e| MidiError::Other(format!("Failed to serialize DIP profiles: {}", e)))?;
+    fs::write(path, contents)
+        .map_err(|e| MidiError::Other(format!("Failed to write DIP profiles: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_apply_round_trips_through_state() {
+        let dips = GenLossDipSwitches {
+            dip_bounce: true,
+            dip_polarity: Polarity::Reverse,
+            ..GenLossDipSwitches::from(&GenLossMkiiState::default())
+        };
+        let mut state = GenLossMkiiState { wow: 99, ..Default::default() };
+        dips.apply_to(&mut state);
+        assert_eq!(GenLossDipSwitches::from(&state), dips);
+        assert_eq!(state.wow, 99);
+    }
+
+    #[test]
+    fn test_profiles_persist_sorted_by_name() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(DIP_PROFILES_FILE);
+        let dips = GenLossDipSwitches::from(&GenLossMkiiState::default());
+
+        let mut store = DipProfileStore::load(path.clone()).unwrap();
+        store.save("Stereo rig", dips).unwrap();
+        store.save("Mono rig", dips).unwrap();
+        store.save("Stereo rig", GenLossDipSwitches { dip_spread: true, ..dips }).unwrap();
+        assert!(store.save("  ", dips).is_err());
+
+        let reloaded = DipProfileStore::load(path).unwrap();
+        let names: Vec<&str> = reloaded.list().iter().map(|profile| profile.name.as_str()).collect();
+        assert_eq!(names, vec!["Mono rig", "Stereo rig"]);
+        assert!(reloaded.get("Stereo rig").unwrap().dips.dip_spread);
+
+        store.delete("Mono rig").unwrap();
+        assert!(store.delete("Mono rig").is_err());
+        assert_eq!(store.list().len(), 1);
+    }
+}
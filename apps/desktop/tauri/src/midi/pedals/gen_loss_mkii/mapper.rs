@@ -1,12 +1,18 @@
 // Gen Loss MKII MIDI CC mapping - infrastructure layer
 
+use super::dip_profiles::GenLossDipSwitches;
 use super::types::{
     GenLossMkiiParameter, GenLossMkiiState,
 };
+use crate::midi::pedals::DIP_SWITCH_CCS;
+use crate::midi::send_plan::SendPlan;
 use std::collections::HashMap;
 
 pub const CC_PRESET_SAVE: u8 = 111;
 
+/// Model, toggles, switches and ramp/bounce - sent after bypass, before the knobs
+const SOUND_SELECTION_CCS: &[u8] = &[16, 21, 22, 23, 32, 103, 104, 105, 106, 107, 52];
+
 impl GenLossMkiiParameter {
     /// Get the CC number for this parameter
     pub fn cc_number(&self) -> u8 {
@@ -76,7 +82,7 @@ impl GenLossMkiiParameter {
             | GenLossMkiiParameter::HissLevel(v)
             | GenLossMkiiParameter::MechanicalNoise(v)
             | GenLossMkiiParameter::CrinklePop(v)
-            | GenLossMkiiParameter::PresetSave(v) => *v,
+            | GenLossMkiiParameter::PresetSave(v) => v.get(),
 
             // Enum parameters
             GenLossMkiiParameter::Model(m) => m.to_cc_value(),
@@ -167,6 +173,13 @@ impl GenLossMkiiState {
     /// Convert the current state to a map of CC numbers → CC values.
     /// Used when recalling a full preset (sending all parameters at once).
     pub fn to_cc_map(&self) -> HashMap<u8, u8> {
+        let mut map = self.to_sound_cc_map();
+        map.extend(self.to_config_cc_map());
+        map
+    }
+
+    /// Everything but the DIP switches: knobs, toggles, switches and advanced noise settings
+    pub fn to_sound_cc_map(&self) -> HashMap<u8, u8> {
         let mut map = HashMap::new();
 
         // Main knobs
@@ -191,26 +204,6 @@ impl GenLossMkiiState {
         map.insert(106, if self.center_switch { 127 } else { 0 });
         map.insert(107, if self.right_switch { 127 } else { 0 });
 
-        // DIP switches - Left bank
-        map.insert(61, if self.dip_wow { 127 } else { 0 });
-        map.insert(62, if self.dip_flutter { 127 } else { 0 });
-        map.insert(63, if self.dip_sat_gen { 127 } else { 0 });
-        map.insert(64, if self.dip_failure_hp { 127 } else { 0 });
-        map.insert(65, if self.dip_model_lp { 127 } else { 0 });
-        map.insert(66, if self.dip_bounce { 127 } else { 0 });
-        map.insert(67, if self.dip_random { 127 } else { 0 });
-        map.insert(68, self.dip_sweep.to_cc_value());
-
-        // DIP switches - Right bank
-        map.insert(71, self.dip_polarity.to_cc_value());
-        map.insert(72, if self.dip_classic { 127 } else { 0 });
-        map.insert(73, if self.dip_miso { 127 } else { 0 });
-        map.insert(74, if self.dip_spread { 127 } else { 0 });
-        map.insert(75, if self.dip_dry_type { 127 } else { 0 });
-        map.insert(76, if self.dip_drop_byp { 127 } else { 0 });
-        map.insert(77, if self.dip_snag_byp { 127 } else { 0 });
-        map.insert(78, if self.dip_hum_byp { 127 } else { 0 });
-
         // Advanced
         map.insert(100, self.expression);
         map.insert(24, self.aux_onset_time);
@@ -223,4 +216,24 @@ impl GenLossMkiiState {
 
         map
     }
+
+    /// The 16 DIP switches, which configure the pedal for a rig rather than shape the sound
+    pub fn to_config_cc_map(&self) -> HashMap<u8, u8> {
+        GenLossDipSwitches::from(self)
+            .parameters()
+            .iter()
+            .map(|param| (param.cc_number(), param.cc_value()))
+            .collect()
+    }
+
+    /// Recall order: bypass and DSP bypass first, then model, toggles, switches and DIP switches, then knobs
+    pub fn recall_plan(&self) -> SendPlan {
+        let selections = [SOUND_SELECTION_CCS, DIP_SWITCH_CCS].concat();
+        SendPlan::grouped(self.to_cc_map(), &[102, 26], &selections, &[])
+    }
+
+    /// `recall_plan` without the DIP switches, for recalling a preset onto the pedal's own configuration
+    pub fn sound_recall_plan(&self) -> SendPlan {
+        SendPlan::grouped(self.to_sound_cc_map(), &[102, 26], SOUND_SELECTION_CCS, &[])
+    }
 }
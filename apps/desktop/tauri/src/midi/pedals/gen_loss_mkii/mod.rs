@@ -1,12 +1,16 @@
 // Chase Bliss Generation Loss MKII MIDI implementation
 // 41 MIDI-controllable parameters
 
+mod coherence;
+mod dip_profiles;
 mod mapper;
 mod types;
 pub mod commands;
 
 // Re-export public types
 pub use types::*;
+pub use coherence::{CoherenceWarning, WarningSeverity};
+pub use dip_profiles::{DipProfile, DipProfileStore, GenLossDipSwitches, DIP_PROFILES_FILE};
 pub use mapper::CC_PRESET_SAVE;
 
 /// Chase Bliss Generation Loss MKII pedal with complete MIDI control
@@ -29,13 +33,13 @@ impl GenLossMkii {
     /// Update internal state from a parameter change
     pub fn update_state(&mut self, param: &GenLossMkiiParameter) {
         match param {
-            GenLossMkiiParameter::Wow(v) => self.state.wow = *v,
-            GenLossMkiiParameter::Volume(v) => self.state.volume = *v,
+            GenLossMkiiParameter::Wow(v) => self.state.wow = v.get(),
+            GenLossMkiiParameter::Volume(v) => self.state.volume = v.get(),
             GenLossMkiiParameter::Model(v) => self.state.model = *v,
-            GenLossMkiiParameter::Flutter(v) => self.state.flutter = *v,
-            GenLossMkiiParameter::Saturate(v) => self.state.saturate = *v,
-            GenLossMkiiParameter::Failure(v) => self.state.failure = *v,
-            GenLossMkiiParameter::RampSpeed(v) => self.state.ramp_speed = *v,
+            GenLossMkiiParameter::Flutter(v) => self.state.flutter = v.get(),
+            GenLossMkiiParameter::Saturate(v) => self.state.saturate = v.get(),
+            GenLossMkiiParameter::Failure(v) => self.state.failure = v.get(),
+            GenLossMkiiParameter::RampSpeed(v) => self.state.ramp_speed = v.get(),
             GenLossMkiiParameter::DryMode(v) => self.state.dry_mode = *v,
             GenLossMkiiParameter::NoiseMode(v) => self.state.noise_mode = *v,
             GenLossMkiiParameter::AuxMode(v) => self.state.aux_mode = *v,
@@ -61,11 +65,11 @@ impl GenLossMkii {
             GenLossMkiiParameter::DipDropByp(v) => self.state.dip_drop_byp = *v,
             GenLossMkiiParameter::DipSnagByp(v) => self.state.dip_snag_byp = *v,
             GenLossMkiiParameter::DipHumByp(v) => self.state.dip_hum_byp = *v,
-            GenLossMkiiParameter::Expression(v) => self.state.expression = *v,
-            GenLossMkiiParameter::AuxOnsetTime(v) => self.state.aux_onset_time = *v,
-            GenLossMkiiParameter::HissLevel(v) => self.state.hiss_level = *v,
-            GenLossMkiiParameter::MechanicalNoise(v) => self.state.mechanical_noise = *v,
-            GenLossMkiiParameter::CrinklePop(v) => self.state.crinkle_pop = *v,
+            GenLossMkiiParameter::Expression(v) => self.state.expression = v.get(),
+            GenLossMkiiParameter::AuxOnsetTime(v) => self.state.aux_onset_time = v.get(),
+            GenLossMkiiParameter::HissLevel(v) => self.state.hiss_level = v.get(),
+            GenLossMkiiParameter::MechanicalNoise(v) => self.state.mechanical_noise = v.get(),
+            GenLossMkiiParameter::CrinklePop(v) => self.state.crinkle_pop = v.get(),
             GenLossMkiiParameter::InputGain(v) => self.state.input_gain = *v,
             GenLossMkiiParameter::DspBypass(v) => self.state.dsp_bypass = *v,
             GenLossMkiiParameter::RampBounce(v) => self.state.ramp_bounce = *v,
@@ -77,6 +81,16 @@ impl GenLossMkii {
     pub fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
         self.state.to_cc_map()
     }
+
+    /// Get the current state as CCs in the order a recall should send them
+    pub fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.state.recall_plan()
+    }
+
+    /// Current knob positions (0.0-1.0) for drawing the pedal's face
+    pub fn to_knob_positions(&self) -> KnobPositions {
+        self.state.to_knob_positions()
+    }
 }
 
 // Implement PedalCapabilities trait for compile-time enforcement
@@ -112,11 +126,16 @@ impl super::PedalCapabilities for GenLossMkii {
     fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
         self.state_as_cc_map()
     }
+
+    fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.recall_plan()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::midi::CcValue;
 
     #[test]
     fn test_tape_model_to_cc() {
@@ -138,6 +157,18 @@ mod tests {
         assert_eq!(TapeModel::from_cc_value(8), TapeModel::CPR3300Gen1);
     }
 
+    #[test]
+    fn test_knob_positions() {
+        let mut pedal = GenLossMkii::new(1);
+        pedal.state.wow = 0;
+        pedal.state.failure = 127;
+
+        let knobs = pedal.to_knob_positions();
+        assert_eq!(knobs.wow, 0.0);
+        assert_eq!(knobs.failure, 1.0);
+        assert!((0.0..=1.0).contains(&knobs.volume));
+    }
+
     #[test]
     fn test_tape_model_names() {
         assert_eq!(TapeModel::None.name(), "None");
@@ -262,18 +293,18 @@ mod tests {
 
     #[test]
     fn test_parameter_cc_numbers() {
-        assert_eq!(GenLossMkiiParameter::Wow(64).cc_number(), 14);
-        assert_eq!(GenLossMkiiParameter::Volume(64).cc_number(), 15);
+        assert_eq!(GenLossMkiiParameter::Wow(CcValue::new(64).unwrap()).cc_number(), 14);
+        assert_eq!(GenLossMkiiParameter::Volume(CcValue::new(64).unwrap()).cc_number(), 15);
         assert_eq!(GenLossMkiiParameter::Model(TapeModel::None).cc_number(), 16);
-        assert_eq!(GenLossMkiiParameter::Flutter(64).cc_number(), 17);
+        assert_eq!(GenLossMkiiParameter::Flutter(CcValue::new(64).unwrap()).cc_number(), 17);
         assert_eq!(GenLossMkiiParameter::Bypass(true).cc_number(), 102);
     }
 
     #[test]
     fn test_parameter_cc_values() {
-        assert_eq!(GenLossMkiiParameter::Wow(64).cc_value(), 64);
-        assert_eq!(GenLossMkiiParameter::Volume(127).cc_value(), 127);
-        assert_eq!(GenLossMkiiParameter::Flutter(0).cc_value(), 0);
+        assert_eq!(GenLossMkiiParameter::Wow(CcValue::new(64).unwrap()).cc_value(), 64);
+        assert_eq!(GenLossMkiiParameter::Volume(CcValue::new(127).unwrap()).cc_value(), 127);
+        assert_eq!(GenLossMkiiParameter::Flutter(CcValue::new(0).unwrap()).cc_value(), 0);
         assert_eq!(GenLossMkiiParameter::Bypass(true).cc_value(), 127);
         assert_eq!(GenLossMkiiParameter::Bypass(false).cc_value(), 0);
         assert_eq!(GenLossMkiiParameter::AuxSwitch(true).cc_value(), 127);
@@ -290,8 +321,8 @@ mod tests {
 
     #[test]
     fn test_parameter_names() {
-        assert_eq!(GenLossMkiiParameter::Wow(64).name(), "Wow");
-        assert_eq!(GenLossMkiiParameter::Volume(64).name(), "Volume");
+        assert_eq!(GenLossMkiiParameter::Wow(CcValue::new(64).unwrap()).name(), "Wow");
+        assert_eq!(GenLossMkiiParameter::Volume(CcValue::new(64).unwrap()).name(), "Volume");
         assert_eq!(
             GenLossMkiiParameter::Model(TapeModel::None).name(),
             "Model"
@@ -312,7 +343,7 @@ mod tests {
     #[test]
     fn test_update_state() {
         let mut gen_loss = GenLossMkii::new(1);
-        gen_loss.update_state(&GenLossMkiiParameter::Wow(100));
+        gen_loss.update_state(&GenLossMkiiParameter::Wow(CcValue::new(100).unwrap()));
         assert_eq!(gen_loss.state.wow, 100);
         gen_loss.update_state(&GenLossMkiiParameter::Bypass(true));
         assert!(gen_loss.state.bypass);
@@ -365,6 +396,7 @@ mod tests {
                 input_gain: InputGain::LineLevel,
                 dsp_bypass: DspBypassMode::DspBypass,
                 ramp_bounce: false,
+                version: GEN_LOSS_STATE_VERSION,
             },
         };
 
@@ -382,6 +414,25 @@ mod tests {
         assert!(cc_map.contains_key(&102));
     }
 
+    #[test]
+    fn test_sound_and_config_maps_partition_the_ccs() {
+        let state = GenLossMkiiState::default();
+        let mut sound: Vec<u8> = state.to_sound_cc_map().into_keys().collect();
+        let mut config: Vec<u8> = state.to_config_cc_map().into_keys().collect();
+        sound.sort();
+        config.sort();
+
+        assert_eq!(config, crate::midi::pedals::DIP_SWITCH_CCS);
+        assert_eq!(sound, vec![14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 26, 27, 28, 29, 32, 52, 100, 102, 103, 104, 105, 106, 107]);
+
+        let mut all: Vec<u8> = state.to_cc_map().into_keys().collect();
+        all.sort();
+        let mut both = [sound, config].concat();
+        both.sort();
+        assert_eq!(all, both);
+        assert_eq!(state.sound_recall_plan().len(), state.to_sound_cc_map().len());
+    }
+
     #[test]
     fn test_tape_model_round_trip() {
         let models = vec![TapeModel::None, TapeModel::CPR3300Gen1, TapeModel::MPEX];
@@ -399,4 +450,50 @@ mod tests {
             assert_eq!(mode.to_cc_value(), i);
         }
     }
+
+    #[test]
+    fn test_from_json_preset_fills_fields_missing_from_version_1() {
+        let mut json = serde_json::to_value(GenLossMkiiState { wow: 90, ..Default::default() }).unwrap();
+        let fields = json.as_object_mut().unwrap();
+        for field in ["version", "expression", "aux_onset_time", "hiss_level", "mechanical_noise", "crinkle_pop", "input_gain", "dsp_bypass", "ramp_bounce"] {
+            fields.remove(field);
+        }
+
+        assert!(serde_json::from_value::<GenLossMkiiState>(json.clone()).is_ok());
+        let state = GenLossMkiiState::from_json_preset(&json).unwrap();
+
+        assert_eq!(state.wow, 90);
+        assert_eq!(state.version, GEN_LOSS_STATE_VERSION);
+        let defaults = GenLossMkiiState::default();
+        assert_eq!(state.aux_onset_time, defaults.aux_onset_time);
+        assert_eq!(state.hiss_level, defaults.hiss_level);
+        assert_eq!(state.input_gain, defaults.input_gain);
+        assert_eq!(state.dsp_bypass, defaults.dsp_bypass);
+        assert!(!state.ramp_bounce);
+    }
+
+    #[test]
+    fn test_from_json_preset_keeps_current_version_as_saved() {
+        let saved = GenLossMkiiState { ramp_bounce: true, dsp_bypass: DspBypassMode::DspBypass, ..Default::default() };
+        let json = serde_json::to_value(&saved).unwrap();
+        assert_eq!(json["version"], GEN_LOSS_STATE_VERSION);
+
+        let state = GenLossMkiiState::from_json_preset(&json).unwrap();
+        assert!(state.ramp_bounce);
+        assert_eq!(state.dsp_bypass, DspBypassMode::DspBypass);
+    }
+
+    #[test]
+    fn test_from_json_preset_rejects_newer_version() {
+        let mut json = serde_json::to_value(GenLossMkiiState::default()).unwrap();
+        json["version"] = serde_json::json!(GEN_LOSS_STATE_VERSION + 1);
+        assert!(GenLossMkiiState::from_json_preset(&json).is_err());
+    }
+
+    #[test]
+    fn test_from_json_preset_still_requires_original_fields() {
+        let mut json = serde_json::to_value(GenLossMkiiState::default()).unwrap();
+        json.as_object_mut().unwrap().remove("wow");
+        assert!(GenLossMkiiState::from_json_preset(&json).is_err());
+    }
 }
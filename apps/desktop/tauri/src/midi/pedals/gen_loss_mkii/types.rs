@@ -1,6 +1,8 @@
 // Gen Loss MKII domain types - enums, structs, and value objects
 
+use crate::midi::CcValue;
 use crate::midi::error::{MidiError, MidiResult};
+use crate::midi::pedals::knob_position;
 use serde::{Deserialize, Serialize};
 
 /// Complete state of all Gen Loss MKII parameters
@@ -50,15 +52,104 @@ pub struct GenLossMkiiState {
     pub dip_snag_byp: bool,
     pub dip_hum_byp: bool,
 
-    // Advanced parameters
+    // Advanced parameters (version 2)
+    #[serde(default)]
     pub expression: u8,
+    #[serde(default = "default_aux_onset_time")]
     pub aux_onset_time: u8,
+    #[serde(default = "default_noise_level")]
     pub hiss_level: u8,
+    #[serde(default = "default_noise_level")]
     pub mechanical_noise: u8,
+    #[serde(default = "default_noise_level")]
     pub crinkle_pop: u8,
+    #[serde(default = "default_input_gain")]
     pub input_gain: InputGain,
+    #[serde(default = "default_dsp_bypass")]
     pub dsp_bypass: DspBypassMode,
+    #[serde(default)]
     pub ramp_bounce: bool,
+
+    // Layout of this state; presets saved before it was stored are version 1
+    #[serde(default = "default_version")]
+    pub version: u8,
+}
+
+/// Version written by this build. Older states are migrated on load by `from_json_preset`.
+pub const GEN_LOSS_STATE_VERSION: u8 = 2;
+
+// Serde defaults for presets saved before the advanced parameters existed
+fn default_aux_onset_time() -> u8 {
+    64
+}
+
+fn default_noise_level() -> u8 {
+    32
+}
+
+fn default_input_gain() -> InputGain {
+    InputGain::InstrumentLevel
+}
+
+fn default_dsp_bypass() -> DspBypassMode {
+    DspBypassMode::TrueBypass
+}
+
+fn default_version() -> u8 {
+    1
+}
+
+/// Positions of the Gen Loss MKII's continuous knobs, each 0.0-1.0
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct KnobPositions {
+    pub wow: f32,
+    pub volume: f32,
+    pub flutter: f32,
+    pub saturate: f32,
+    pub failure: f32,
+    pub ramp_speed: f32,
+}
+
+impl GenLossMkiiState {
+    /// Read a state from preset JSON saved by this or any older build. Fields an older
+    /// build didn't write take their defaults, and the result is migrated to the current version.
+    pub fn from_json_preset(json: &serde_json::Value) -> MidiResult<Self> {
+        let mut state: Self = serde_json::from_value(json.clone())
+            .map_err(|e| MidiError::Other(format!("Failed to read GenLossMkii preset: {}", e)))?;
+
+        if state.version > GEN_LOSS_STATE_VERSION {
+            return Err(MidiError::Other(format!(
+                "GenLossMkii preset version {} is newer than this app supports ({})",
+                state.version, GEN_LOSS_STATE_VERSION
+            )));
+        }
+
+        while state.version < GEN_LOSS_STATE_VERSION {
+            state.migrate_one_version();
+        }
+
+        Ok(state)
+    }
+
+    fn migrate_one_version(&mut self) {
+        match self.version {
+            // Version 1 had no advanced parameters; serde already filled them with defaults
+            0 | 1 => self.version = 2,
+            _ => {}
+        }
+    }
+
+    /// Knob positions for drawing the pedal's face
+    pub fn to_knob_positions(&self) -> KnobPositions {
+        KnobPositions {
+            wow: knob_position(self.wow),
+            volume: knob_position(self.volume),
+            flutter: knob_position(self.flutter),
+            saturate: knob_position(self.saturate),
+            failure: knob_position(self.failure),
+            ramp_speed: knob_position(self.ramp_speed),
+        }
+    }
 }
 
 impl Default for GenLossMkiiState {
@@ -113,6 +204,8 @@ impl Default for GenLossMkiiState {
             input_gain: InputGain::InstrumentLevel,
             dsp_bypass: DspBypassMode::TrueBypass,
             ramp_bounce: false,
+
+            version: GEN_LOSS_STATE_VERSION,
         }
     }
 }
@@ -121,13 +214,13 @@ impl Default for GenLossMkiiState {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum GenLossMkiiParameter {
     // Main knobs
-    Wow(u8),
-    Volume(u8),
+    Wow(CcValue),
+    Volume(CcValue),
     Model(TapeModel),
-    Flutter(u8),
-    Saturate(u8),
-    Failure(u8),
-    RampSpeed(u8),
+    Flutter(CcValue),
+    Saturate(CcValue),
+    Failure(CcValue),
+    RampSpeed(CcValue),
 
     // Toggles
     DryMode(DryMode),
@@ -165,14 +258,14 @@ pub enum GenLossMkiiParameter {
     DipHumByp(bool),
 
     // Advanced
-    Expression(u8),
-    AuxOnsetTime(u8),
-    HissLevel(u8),
-    MechanicalNoise(u8),
-    CrinklePop(u8),
+    Expression(CcValue),
+    AuxOnsetTime(CcValue),
+    HissLevel(CcValue),
+    MechanicalNoise(CcValue),
+    CrinklePop(CcValue),
     InputGain(InputGain),
     DspBypass(DspBypassMode),
-    PresetSave(u8), // 1-122
+    PresetSave(CcValue), // 1-122
     RampBounce(bool),
 }
 
@@ -275,7 +368,7 @@ impl DryMode {
             3 => Ok(DryMode::Dry3),
             _ => Err(MidiError::InvalidValue {
                 expected: "1-3".to_string(),
-                actual: value,
+                actual: value.into(),
             }),
         }
     }
@@ -305,7 +398,7 @@ impl NoiseMode {
             3 => Ok(NoiseMode::Noise3),
             _ => Err(MidiError::InvalidValue {
                 expected: "1-3".to_string(),
-                actual: value,
+                actual: value.into(),
             }),
         }
     }
@@ -335,7 +428,7 @@ impl AuxMode {
             3 => Ok(AuxMode::Aux3),
             _ => Err(MidiError::InvalidValue {
                 expected: "1-3".to_string(),
-                actual: value,
+                actual: value.into(),
             }),
         }
     }
@@ -413,7 +506,7 @@ impl InputGain {
             3 => Ok(InputGain::HighGain),
             _ => Err(MidiError::InvalidValue {
                 expected: "1-3".to_string(),
-                actual: value,
+                actual: value.into(),
             }),
         }
     }
@@ -0,0 +1,269 @@
+// Chase Bliss Audio Habit MIDI CC mapping - infrastructure layer
+
+use super::types::{CollectMode, HabitParameter, HabitState, Modifier, Polarity, ScanMode, SweepDirection};
+use crate::midi::pedals::DIP_SWITCH_CCS;
+use crate::midi::send_plan::SendPlan;
+use std::collections::HashMap;
+
+pub const CC_PRESET_SAVE: u8 = 111;
+pub const CC_CLEAR_MEMORY: u8 = 108;
+
+impl HabitParameter {
+    /// Get the CC number for this parameter
+    pub fn cc_number(&self) -> u8 {
+        match self {
+            HabitParameter::Level(_) => 14,
+            HabitParameter::Repeats(_) => 15,
+            HabitParameter::Size(_) => 16,
+            HabitParameter::Scan(_) => 17,
+            HabitParameter::Spread(_) => 18,
+            HabitParameter::Modify(_) => 19,
+            HabitParameter::RampSpeed(_) => 20,
+
+            HabitParameter::Modifier(_) => 21,
+            HabitParameter::ScanMode(_) => 22,
+            HabitParameter::CollectMode(_) => 23,
+
+            HabitParameter::Bypass(_) => 102,
+            HabitParameter::Memory(_) => 103,
+            HabitParameter::AltMode(_) => 104,
+            HabitParameter::Overdub(_) => 105,
+            HabitParameter::MemoryHold(_) => 106,
+            HabitParameter::ClearMemory => CC_CLEAR_MEMORY,
+
+            HabitParameter::DipLevel(_) => 61,
+            HabitParameter::DipRepeats(_) => 62,
+            HabitParameter::DipSize(_) => 63,
+            HabitParameter::DipScan(_) => 64,
+            HabitParameter::DipSpread(_) => 65,
+            HabitParameter::DipBounce(_) => 66,
+            HabitParameter::DipSweep(_) => 67,
+            HabitParameter::DipPolarity(_) => 68,
+
+            HabitParameter::DipMiso(_) => 71,
+            HabitParameter::DipStereo(_) => 72,
+            HabitParameter::DipLatch(_) => 73,
+            HabitParameter::DipSync(_) => 74,
+            HabitParameter::DipTrails(_) => 75,
+            HabitParameter::DipNoDub(_) => 76,
+            HabitParameter::DipFeedbackLoop(_) => 77,
+            HabitParameter::DipManual(_) => 78,
+
+            HabitParameter::MidiClockIgnore(_) => 51,
+            HabitParameter::RampBounce(_) => 52,
+            HabitParameter::Expression(_) => 100,
+            HabitParameter::PresetSave(_) => CC_PRESET_SAVE,
+        }
+    }
+
+    /// Get the CC value for this parameter
+    pub fn cc_value(&self) -> u8 {
+        match self {
+            // Continuous parameters
+            HabitParameter::Level(v)
+            | HabitParameter::Repeats(v)
+            | HabitParameter::Size(v)
+            | HabitParameter::Scan(v)
+            | HabitParameter::Spread(v)
+            | HabitParameter::Modify(v)
+            | HabitParameter::RampSpeed(v)
+            | HabitParameter::Expression(v)
+            | HabitParameter::PresetSave(v) => v.get(),
+
+            // Enum parameters
+            HabitParameter::Modifier(m) => m.to_cc_value(),
+            HabitParameter::ScanMode(m) => m.to_cc_value(),
+            HabitParameter::CollectMode(m) => m.to_cc_value(),
+            HabitParameter::DipSweep(d) => d.to_cc_value(),
+            HabitParameter::DipPolarity(p) => p.to_cc_value(),
+
+            // Trigger
+            HabitParameter::ClearMemory => 127,
+
+            // Binary parameters
+            HabitParameter::Bypass(b)
+            | HabitParameter::Memory(b)
+            | HabitParameter::AltMode(b)
+            | HabitParameter::Overdub(b)
+            | HabitParameter::MemoryHold(b)
+            | HabitParameter::DipLevel(b)
+            | HabitParameter::DipRepeats(b)
+            | HabitParameter::DipSize(b)
+            | HabitParameter::DipScan(b)
+            | HabitParameter::DipSpread(b)
+            | HabitParameter::DipBounce(b)
+            | HabitParameter::DipMiso(b)
+            | HabitParameter::DipStereo(b)
+            | HabitParameter::DipLatch(b)
+            | HabitParameter::DipSync(b)
+            | HabitParameter::DipTrails(b)
+            | HabitParameter::DipNoDub(b)
+            | HabitParameter::DipFeedbackLoop(b)
+            | HabitParameter::DipManual(b)
+            | HabitParameter::MidiClockIgnore(b)
+            | HabitParameter::RampBounce(b) => {
+                if *b { 127 } else { 0 }
+            }
+        }
+    }
+
+    /// Get a human-readable name for this parameter
+    pub fn name(&self) -> &'static str {
+        match self {
+            HabitParameter::Level(_) => "Level",
+            HabitParameter::Repeats(_) => "Repeats",
+            HabitParameter::Size(_) => "Size",
+            HabitParameter::Scan(_) => "Scan",
+            HabitParameter::Spread(_) => "Spread",
+            HabitParameter::Modify(_) => "Modify",
+            HabitParameter::RampSpeed(_) => "Ramp Speed",
+            HabitParameter::Modifier(_) => "Modifier",
+            HabitParameter::ScanMode(_) => "Scan Mode",
+            HabitParameter::CollectMode(_) => "Collect Mode",
+            HabitParameter::Bypass(_) => "Bypass",
+            HabitParameter::Memory(_) => "Memory",
+            HabitParameter::AltMode(_) => "Alt Mode",
+            HabitParameter::Overdub(_) => "Overdub",
+            HabitParameter::MemoryHold(_) => "Memory Hold",
+            HabitParameter::ClearMemory => "Clear Memory",
+            HabitParameter::DipLevel(_) => "DIP: Level",
+            HabitParameter::DipRepeats(_) => "DIP: Repeats",
+            HabitParameter::DipSize(_) => "DIP: Size",
+            HabitParameter::DipScan(_) => "DIP: Scan",
+            HabitParameter::DipSpread(_) => "DIP: Spread",
+            HabitParameter::DipBounce(_) => "DIP: Bounce",
+            HabitParameter::DipSweep(_) => "DIP: Sweep",
+            HabitParameter::DipPolarity(_) => "DIP: Polarity",
+            HabitParameter::DipMiso(_) => "DIP: Miso",
+            HabitParameter::DipStereo(_) => "DIP: Stereo",
+            HabitParameter::DipLatch(_) => "DIP: Latch",
+            HabitParameter::DipSync(_) => "DIP: Sync",
+            HabitParameter::DipTrails(_) => "DIP: Trails",
+            HabitParameter::DipNoDub(_) => "DIP: No Dub",
+            HabitParameter::DipFeedbackLoop(_) => "DIP: Feedback Loop",
+            HabitParameter::DipManual(_) => "DIP: Manual",
+            HabitParameter::MidiClockIgnore(_) => "MIDI Clock Ignore",
+            HabitParameter::RampBounce(_) => "Ramp/Bounce",
+            HabitParameter::Expression(_) => "Expression",
+            HabitParameter::PresetSave(_) => "Preset Save",
+        }
+    }
+}
+
+impl HabitState {
+    /// Convert the current state to a map of CC numbers → CC values.
+    /// Used when recalling a full preset (sending all parameters at once).
+    pub fn to_cc_map(&self) -> HashMap<u8, u8> {
+        let mut map = HashMap::new();
+
+        // Main knobs
+        map.insert(14, self.level);
+        map.insert(15, self.repeats);
+        map.insert(16, self.size);
+        map.insert(17, self.scan);
+        map.insert(18, self.spread);
+        map.insert(19, self.modify);
+        map.insert(20, self.ramp_speed);
+
+        // Toggles
+        map.insert(21, self.modifier.to_cc_value());
+        map.insert(22, self.scan_mode.to_cc_value());
+        map.insert(23, self.collect_mode.to_cc_value());
+
+        // Footswitches and memory
+        map.insert(102, if self.bypass { 127 } else { 0 });
+        map.insert(103, if self.memory { 127 } else { 0 });
+        map.insert(104, if self.alt_mode { 127 } else { 0 });
+        map.insert(105, if self.overdub { 127 } else { 0 });
+        map.insert(106, if self.memory_hold { 127 } else { 0 });
+
+        // DIP switches - Left bank
+        map.insert(61, if self.dip_level { 127 } else { 0 });
+        map.insert(62, if self.dip_repeats { 127 } else { 0 });
+        map.insert(63, if self.dip_size { 127 } else { 0 });
+        map.insert(64, if self.dip_scan { 127 } else { 0 });
+        map.insert(65, if self.dip_spread { 127 } else { 0 });
+        map.insert(66, if self.dip_bounce { 127 } else { 0 });
+        map.insert(67, self.dip_sweep.to_cc_value());
+        map.insert(68, self.dip_polarity.to_cc_value());
+
+        // DIP switches - Right bank
+        map.insert(71, if self.dip_miso { 127 } else { 0 });
+        map.insert(72, if self.dip_stereo { 127 } else { 0 });
+        map.insert(73, if self.dip_latch { 127 } else { 0 });
+        map.insert(74, if self.dip_sync { 127 } else { 0 });
+        map.insert(75, if self.dip_trails { 127 } else { 0 });
+        map.insert(76, if self.dip_no_dub { 127 } else { 0 });
+        map.insert(77, if self.dip_feedback_loop { 127 } else { 0 });
+        map.insert(78, if self.dip_manual { 127 } else { 0 });
+
+        // Utility
+        map.insert(51, if self.midi_clock_ignore { 127 } else { 0 });
+        map.insert(52, if self.ramp_bounce { 127 } else { 0 });
+        map.insert(100, self.expression);
+
+        map
+    }
+
+    /// Apply one incoming CC to the state. Unknown CCs, triggers and out-of-range
+    /// toggle positions are ignored.
+    pub fn update_from_cc(&mut self, cc: u8, value: u8) {
+        let on = value >= 64;
+        match cc {
+            // Main knobs
+            14 => self.level = value,
+            15 => self.repeats = value,
+            16 => self.size = value,
+            17 => self.scan = value,
+            18 => self.spread = value,
+            19 => self.modify = value,
+            20 => self.ramp_speed = value,
+
+            // Toggles
+            21 => if let Ok(m) = Modifier::from_cc_value(value) { self.modifier = m },
+            22 => if let Ok(m) = ScanMode::from_cc_value(value) { self.scan_mode = m },
+            23 => if let Ok(m) = CollectMode::from_cc_value(value) { self.collect_mode = m },
+
+            // Footswitches and memory
+            102 => self.bypass = on,
+            103 => self.memory = on,
+            104 => self.alt_mode = on,
+            105 => self.overdub = on,
+            106 => self.memory_hold = on,
+
+            // DIP switches - Left bank
+            61 => self.dip_level = on,
+            62 => self.dip_repeats = on,
+            63 => self.dip_size = on,
+            64 => self.dip_scan = on,
+            65 => self.dip_spread = on,
+            66 => self.dip_bounce = on,
+            67 => self.dip_sweep = SweepDirection::from_cc_value(value),
+            68 => self.dip_polarity = Polarity::from_cc_value(value),
+
+            // DIP switches - Right bank
+            71 => self.dip_miso = on,
+            72 => self.dip_stereo = on,
+            73 => self.dip_latch = on,
+            74 => self.dip_sync = on,
+            75 => self.dip_trails = on,
+            76 => self.dip_no_dub = on,
+            77 => self.dip_feedback_loop = on,
+            78 => self.dip_manual = on,
+
+            // Utility
+            51 => self.midi_clock_ignore = on,
+            52 => self.ramp_bounce = on,
+            100 => self.expression = value,
+
+            _ => {}
+        }
+    }
+
+    /// Recall order: bypass first, then toggles, footswitch modes and DIP switches, then knobs.
+    /// Clear memory is a trigger and is never in the map.
+    pub fn recall_plan(&self) -> SendPlan {
+        let selections = [&[21, 22, 23, 103, 104, 105, 106, 51, 52][..], DIP_SWITCH_CCS].concat();
+        SendPlan::grouped(self.to_cc_map(), &[102], &selections, &[])
+    }
+}
@@ -0,0 +1,320 @@
+// Chase Bliss Audio Habit MIDI implementation
+// An echo with a three-minute memory: the echo is always collecting into memory, and the
+// Scan controls read back through it. Knobs, toggles, DIP switches and the memory
+// footswitches are all CCs; 122 presets are saved with CC 111 and recalled by Program Change.
+
+mod mapper;
+mod types;
+pub mod commands;
+
+// Re-export public types
+pub use types::*;
+pub use mapper::{CC_CLEAR_MEMORY, CC_PRESET_SAVE};
+
+/// Chase Bliss Audio Habit pedal with complete MIDI control.
+/// This is the aggregate root for the Habit domain.
+#[derive(Debug)]
+pub struct Habit {
+    pub state: HabitState,
+    pub midi_channel: u8,
+}
+
+impl Habit {
+    /// Create a new Habit instance with default state
+    pub fn new(midi_channel: u8) -> Self {
+        Self {
+            state: HabitState::default(),
+            midi_channel,
+        }
+    }
+
+    /// Update internal state from a parameter change
+    pub fn update_state(&mut self, param: &HabitParameter) {
+        match param {
+            HabitParameter::Level(v) => self.state.level = v.get(),
+            HabitParameter::Repeats(v) => self.state.repeats = v.get(),
+            HabitParameter::Size(v) => self.state.size = v.get(),
+            HabitParameter::Scan(v) => self.state.scan = v.get(),
+            HabitParameter::Spread(v) => self.state.spread = v.get(),
+            HabitParameter::Modify(v) => self.state.modify = v.get(),
+            HabitParameter::RampSpeed(v) => self.state.ramp_speed = v.get(),
+            HabitParameter::Modifier(v) => self.state.modifier = *v,
+            HabitParameter::ScanMode(v) => self.state.scan_mode = *v,
+            HabitParameter::CollectMode(v) => self.state.collect_mode = *v,
+            HabitParameter::Bypass(v) => self.state.bypass = *v,
+            HabitParameter::Memory(v) => self.state.memory = *v,
+            HabitParameter::AltMode(v) => self.state.alt_mode = *v,
+            HabitParameter::Overdub(v) => self.state.overdub = *v,
+            HabitParameter::MemoryHold(v) => self.state.memory_hold = *v,
+            HabitParameter::ClearMemory => {} // Trigger - does not update state
+            HabitParameter::DipLevel(v) => self.state.dip_level = *v,
+            HabitParameter::DipRepeats(v) => self.state.dip_repeats = *v,
+            HabitParameter::DipSize(v) => self.state.dip_size = *v,
+            HabitParameter::DipScan(v) => self.state.dip_scan = *v,
+            HabitParameter::DipSpread(v) => self.state.dip_spread = *v,
+            HabitParameter::DipBounce(v) => self.state.dip_bounce = *v,
+            HabitParameter::DipSweep(v) => self.state.dip_sweep = *v,
+            HabitParameter::DipPolarity(v) => self.state.dip_polarity = *v,
+            HabitParameter::DipMiso(v) => self.state.dip_miso = *v,
+            HabitParameter::DipStereo(v) => self.state.dip_stereo = *v,
+            HabitParameter::DipLatch(v) => self.state.dip_latch = *v,
+            HabitParameter::DipSync(v) => self.state.dip_sync = *v,
+            HabitParameter::DipTrails(v) => self.state.dip_trails = *v,
+            HabitParameter::DipNoDub(v) => self.state.dip_no_dub = *v,
+            HabitParameter::DipFeedbackLoop(v) => self.state.dip_feedback_loop = *v,
+            HabitParameter::DipManual(v) => self.state.dip_manual = *v,
+            HabitParameter::MidiClockIgnore(v) => self.state.midi_clock_ignore = *v,
+            HabitParameter::RampBounce(v) => self.state.ramp_bounce = *v,
+            HabitParameter::Expression(v) => self.state.expression = v.get(),
+            HabitParameter::PresetSave(_) => {} // Does not update state
+        }
+    }
+
+    /// Get the current state as a hashmap of CC numbers to values
+    pub fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
+        self.state.to_cc_map()
+    }
+
+    /// Get the current state as CCs in the order a recall should send them
+    pub fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.state.recall_plan()
+    }
+}
+
+impl super::PedalCapabilities for Habit {
+    type State = HabitState;
+    type Parameter = HabitParameter;
+
+    fn metadata(&self) -> super::PedalMetadata {
+        super::PedalMetadata {
+            name: "Habit",
+            manufacturer: "Chase Bliss Audio",
+            supports_editor: true,
+            supports_preset_library: true,
+        }
+    }
+
+    fn midi_channel(&self) -> u8 {
+        self.midi_channel
+    }
+
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+
+    fn update_state(&mut self, param: &Self::Parameter) {
+        self.update_state(param)
+    }
+
+    fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
+        self.state_as_cc_map()
+    }
+
+    fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.recall_plan()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::midi::CcValue;
+
+    #[test]
+    fn test_default_state() {
+        let state = HabitState::default();
+        assert_eq!(state.level, 64);
+        assert_eq!(state.scan, 0);
+        assert_eq!(state.modifier, Modifier::Stretch);
+        assert_eq!(state.scan_mode, ScanMode::Off);
+        assert_eq!(state.collect_mode, CollectMode::In);
+        assert!(!state.bypass);
+        assert!(!state.memory);
+        assert!(!state.overdub);
+        assert_eq!(state.dip_sweep, SweepDirection::Bottom);
+        assert_eq!(state.dip_polarity, Polarity::Forward);
+    }
+
+    #[test]
+    fn test_modifier_to_cc() {
+        assert_eq!(Modifier::Stretch.to_cc_value(), 1);
+        assert_eq!(Modifier::Pitch.to_cc_value(), 2);
+        assert_eq!(Modifier::Filter.to_cc_value(), 3);
+    }
+
+    #[test]
+    fn test_modifier_from_cc() {
+        assert_eq!(Modifier::from_cc_value(1).unwrap(), Modifier::Stretch);
+        assert_eq!(Modifier::from_cc_value(2).unwrap(), Modifier::Pitch);
+        assert_eq!(Modifier::from_cc_value(3).unwrap(), Modifier::Filter);
+
+        // Test boundary values
+        assert!(Modifier::from_cc_value(0).is_err());
+        assert!(Modifier::from_cc_value(4).is_err());
+        assert!(Modifier::from_cc_value(127).is_err());
+    }
+
+    #[test]
+    fn test_scan_mode_from_cc() {
+        assert_eq!(ScanMode::from_cc_value(1).unwrap(), ScanMode::Off);
+        assert_eq!(ScanMode::from_cc_value(2).unwrap(), ScanMode::Manual);
+        assert_eq!(ScanMode::from_cc_value(3).unwrap(), ScanMode::Auto);
+
+        // Test boundary values
+        assert!(ScanMode::from_cc_value(0).is_err());
+        assert!(ScanMode::from_cc_value(4).is_err());
+    }
+
+    #[test]
+    fn test_collect_mode_from_cc() {
+        assert_eq!(CollectMode::from_cc_value(1).unwrap(), CollectMode::In);
+        assert_eq!(CollectMode::from_cc_value(2).unwrap(), CollectMode::Feed);
+        assert_eq!(CollectMode::from_cc_value(3).unwrap(), CollectMode::Out);
+
+        // Test boundary values
+        assert!(CollectMode::from_cc_value(0).is_err());
+        assert!(CollectMode::from_cc_value(4).is_err());
+    }
+
+    #[test]
+    fn test_toggle_round_trips() {
+        for i in 1..=3 {
+            assert_eq!(Modifier::from_cc_value(i).unwrap().to_cc_value(), i);
+            assert_eq!(ScanMode::from_cc_value(i).unwrap().to_cc_value(), i);
+            assert_eq!(CollectMode::from_cc_value(i).unwrap().to_cc_value(), i);
+        }
+    }
+
+    #[test]
+    fn test_sweep_and_polarity_from_cc() {
+        assert_eq!(SweepDirection::from_cc_value(63), SweepDirection::Bottom);
+        assert_eq!(SweepDirection::from_cc_value(64), SweepDirection::Top);
+        assert_eq!(Polarity::from_cc_value(63), Polarity::Forward);
+        assert_eq!(Polarity::from_cc_value(64), Polarity::Reverse);
+    }
+
+    #[test]
+    fn test_parameter_cc_numbers() {
+        assert_eq!(HabitParameter::Level(CcValue::new(64).unwrap()).cc_number(), 14);
+        assert_eq!(HabitParameter::Modify(CcValue::new(64).unwrap()).cc_number(), 19);
+        assert_eq!(HabitParameter::CollectMode(CollectMode::Feed).cc_number(), 23);
+        assert_eq!(HabitParameter::Bypass(true).cc_number(), 102);
+        assert_eq!(HabitParameter::Overdub(true).cc_number(), 105);
+        assert_eq!(HabitParameter::ClearMemory.cc_number(), CC_CLEAR_MEMORY);
+        assert_eq!(HabitParameter::PresetSave(CcValue::new(1).unwrap()).cc_number(), CC_PRESET_SAVE);
+    }
+
+    #[test]
+    fn test_parameter_cc_values() {
+        assert_eq!(HabitParameter::Repeats(CcValue::new(127).unwrap()).cc_value(), 127);
+        assert_eq!(HabitParameter::Memory(true).cc_value(), 127);
+        assert_eq!(HabitParameter::Memory(false).cc_value(), 0);
+        assert_eq!(HabitParameter::ScanMode(ScanMode::Auto).cc_value(), 3);
+        assert_eq!(HabitParameter::DipSweep(SweepDirection::Top).cc_value(), 127);
+        assert_eq!(HabitParameter::ClearMemory.cc_value(), 127);
+    }
+
+    #[test]
+    fn test_parameter_names() {
+        assert_eq!(HabitParameter::Scan(CcValue::new(64).unwrap()).name(), "Scan");
+        assert_eq!(HabitParameter::MemoryHold(true).name(), "Memory Hold");
+        assert_eq!(HabitParameter::DipNoDub(true).name(), "DIP: No Dub");
+    }
+
+    #[test]
+    fn test_update_state() {
+        let mut habit = Habit::new(1);
+        habit.update_state(&HabitParameter::Size(CcValue::new(100).unwrap()));
+        assert_eq!(habit.state.size, 100);
+        habit.update_state(&HabitParameter::Overdub(true));
+        assert!(habit.state.overdub);
+        habit.update_state(&HabitParameter::Modifier(Modifier::Pitch));
+        assert_eq!(habit.state.modifier, Modifier::Pitch);
+
+        let before = habit.state.clone();
+        habit.update_state(&HabitParameter::ClearMemory);
+        habit.update_state(&HabitParameter::PresetSave(CcValue::new(5).unwrap()));
+        assert_eq!(habit.state, before);
+    }
+
+    #[test]
+    fn test_state_as_cc_map() {
+        let habit = Habit {
+            midi_channel: 1,
+            state: HabitState {
+                level: 90,
+                scan_mode: ScanMode::Manual,
+                collect_mode: CollectMode::Out,
+                memory: true,
+                dip_scan: true,
+                dip_polarity: Polarity::Reverse,
+                ..Default::default()
+            },
+        };
+
+        let cc_map = habit.state_as_cc_map();
+        assert_eq!(cc_map.get(&14), Some(&90)); // level
+        assert_eq!(cc_map.get(&22), Some(&2)); // scan_mode (Manual)
+        assert_eq!(cc_map.get(&23), Some(&3)); // collect_mode (Out)
+        assert_eq!(cc_map.get(&103), Some(&127)); // memory (true)
+        assert_eq!(cc_map.get(&64), Some(&127)); // dip_scan (true)
+        assert_eq!(cc_map.get(&68), Some(&127)); // dip_polarity (Reverse)
+        assert!(!cc_map.contains_key(&CC_CLEAR_MEMORY));
+        assert!(!cc_map.contains_key(&CC_PRESET_SAVE));
+
+        let mut ccs: Vec<u8> = cc_map.into_keys().collect();
+        ccs.sort();
+        let mut expected = [
+            &[14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 51, 52, 100, 102, 103, 104, 105, 106][..],
+            crate::midi::pedals::DIP_SWITCH_CCS,
+        ]
+        .concat();
+        expected.sort();
+        assert_eq!(ccs, expected);
+    }
+
+    #[test]
+    fn test_cc_map_round_trips_through_update_from_cc() {
+        let state = HabitState {
+            repeats: 12,
+            modifier: Modifier::Filter,
+            scan_mode: ScanMode::Auto,
+            collect_mode: CollectMode::Feed,
+            bypass: true,
+            alt_mode: true,
+            memory_hold: true,
+            dip_sweep: SweepDirection::Top,
+            dip_feedback_loop: true,
+            ramp_bounce: true,
+            expression: 33,
+            ..Default::default()
+        };
+
+        let mut rebuilt = HabitState::default();
+        for (cc, value) in state.to_cc_map() {
+            rebuilt.update_from_cc(cc, value);
+        }
+        assert_eq!(rebuilt, state);
+    }
+
+    #[test]
+    fn test_update_from_cc_ignores_invalid_toggle_positions() {
+        let mut state = HabitState::default();
+        state.update_from_cc(21, 0);
+        state.update_from_cc(22, 4);
+        state.update_from_cc(CC_CLEAR_MEMORY, 127);
+        assert_eq!(state, HabitState::default());
+
+        state.update_from_cc(21, 3);
+        assert_eq!(state.modifier, Modifier::Filter);
+    }
+
+    #[test]
+    fn test_state_serialization_round_trip() {
+        let state = HabitState { scan: 127, overdub: true, ..Default::default() };
+        let json = serde_json::to_value(&state).unwrap();
+        assert_eq!(json["collect_mode"], "In");
+        let restored: HabitState = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, state);
+    }
+}
@@ -0,0 +1,296 @@
+// Chase Bliss Audio Habit domain types
+
+use crate::midi::CcValue;
+use crate::midi::error::{MidiError, MidiResult};
+use serde::{Deserialize, Serialize};
+
+/// Complete state of all Habit parameters
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HabitState {
+    // Main control knobs
+    pub level: u8,
+    pub repeats: u8,
+    pub size: u8,
+    pub scan: u8,
+    pub spread: u8,
+    pub modify: u8,
+    pub ramp_speed: u8,
+
+    // Three-position toggles
+    pub modifier: Modifier,
+    pub scan_mode: ScanMode,
+    pub collect_mode: CollectMode,
+
+    // Footswitches and memory
+    pub bypass: bool,
+    /// Play from memory instead of the live echo
+    pub memory: bool,
+    pub alt_mode: bool,
+    /// Write the echo back into memory
+    pub overdub: bool,
+    /// Stop collecting into memory
+    pub memory_hold: bool,
+
+    // DIP switches - Left bank
+    pub dip_level: bool,
+    pub dip_repeats: bool,
+    pub dip_size: bool,
+    pub dip_scan: bool,
+    pub dip_spread: bool,
+    pub dip_bounce: bool,
+    pub dip_sweep: SweepDirection,
+    pub dip_polarity: Polarity,
+
+    // DIP switches - Right bank
+    pub dip_miso: bool,
+    pub dip_stereo: bool,
+    pub dip_latch: bool,
+    pub dip_sync: bool,
+    pub dip_trails: bool,
+    pub dip_no_dub: bool,
+    pub dip_feedback_loop: bool,
+    pub dip_manual: bool,
+
+    // Utility
+    pub midi_clock_ignore: bool,
+    pub ramp_bounce: bool,
+    pub expression: u8,
+}
+
+impl Default for HabitState {
+    fn default() -> Self {
+        Self {
+            level: 64,
+            repeats: 64,
+            size: 64,
+            scan: 0,
+            spread: 64,
+            modify: 0,
+            ramp_speed: 64,
+
+            modifier: Modifier::Stretch,
+            scan_mode: ScanMode::Off,
+            collect_mode: CollectMode::In,
+
+            bypass: false,
+            memory: false,
+            alt_mode: false,
+            overdub: false,
+            memory_hold: false,
+
+            dip_level: false,
+            dip_repeats: false,
+            dip_size: false,
+            dip_scan: false,
+            dip_spread: false,
+            dip_bounce: false,
+            dip_sweep: SweepDirection::Bottom,
+            dip_polarity: Polarity::Forward,
+
+            dip_miso: false,
+            dip_stereo: false,
+            dip_latch: false,
+            dip_sync: false,
+            dip_trails: false,
+            dip_no_dub: false,
+            dip_feedback_loop: false,
+            dip_manual: false,
+
+            midi_clock_ignore: false,
+            ramp_bounce: false,
+            expression: 0,
+        }
+    }
+}
+
+/// All possible Habit parameters with their values
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum HabitParameter {
+    // Main knobs (CC 14-20)
+    Level(CcValue),     // 14
+    Repeats(CcValue),   // 15
+    Size(CcValue),      // 16
+    Scan(CcValue),      // 17
+    Spread(CcValue),    // 18
+    Modify(CcValue),    // 19
+    RampSpeed(CcValue), // 20
+
+    // Toggles (CC 21-23)
+    Modifier(Modifier),       // 21
+    ScanMode(ScanMode),       // 22
+    CollectMode(CollectMode), // 23
+
+    // Footswitches and memory (CC 102-108)
+    Bypass(bool),     // 102
+    Memory(bool),     // 103
+    AltMode(bool),    // 104
+    Overdub(bool),    // 105
+    MemoryHold(bool), // 106
+    /// Erase the memory (CC 108). A trigger: never stored or recalled.
+    ClearMemory,
+
+    // DIP switches - Left bank (CC 61-68)
+    DipLevel(bool),
+    DipRepeats(bool),
+    DipSize(bool),
+    DipScan(bool),
+    DipSpread(bool),
+    DipBounce(bool),
+    DipSweep(SweepDirection),
+    DipPolarity(Polarity),
+
+    // DIP switches - Right bank (CC 71-78)
+    DipMiso(bool),
+    DipStereo(bool),
+    DipLatch(bool),
+    DipSync(bool),
+    DipTrails(bool),
+    DipNoDub(bool),
+    DipFeedbackLoop(bool),
+    DipManual(bool),
+
+    // Utility
+    MidiClockIgnore(bool), // 51
+    RampBounce(bool),      // 52
+    Expression(CcValue),   // 100
+    /// Save the current state to a preset slot (CC 111, value 1-122)
+    PresetSave(CcValue),
+}
+
+/// Three-position modifier selector (CC 21) - what the Modify knob does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Modifier {
+    Stretch, // 1
+    Pitch,   // 2
+    Filter,  // 3
+}
+
+impl Modifier {
+    pub fn to_cc_value(self) -> u8 {
+        match self {
+            Modifier::Stretch => 1,
+            Modifier::Pitch => 2,
+            Modifier::Filter => 3,
+        }
+    }
+
+    pub fn from_cc_value(value: u8) -> MidiResult<Self> {
+        match value {
+            1 => Ok(Modifier::Stretch),
+            2 => Ok(Modifier::Pitch),
+            3 => Ok(Modifier::Filter),
+            _ => Err(MidiError::InvalidValue {
+                expected: "1-3".to_string(),
+                actual: value.into(),
+            }),
+        }
+    }
+}
+
+/// Three-position scan mode (CC 22) - how the pedal reads back through memory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScanMode {
+    Off,    // 1
+    Manual, // 2
+    Auto,   // 3
+}
+
+impl ScanMode {
+    pub fn to_cc_value(self) -> u8 {
+        match self {
+            ScanMode::Off => 1,
+            ScanMode::Manual => 2,
+            ScanMode::Auto => 3,
+        }
+    }
+
+    pub fn from_cc_value(value: u8) -> MidiResult<Self> {
+        match value {
+            1 => Ok(ScanMode::Off),
+            2 => Ok(ScanMode::Manual),
+            3 => Ok(ScanMode::Auto),
+            _ => Err(MidiError::InvalidValue {
+                expected: "1-3".to_string(),
+                actual: value.into(),
+            }),
+        }
+    }
+}
+
+/// Three-position collect mode (CC 23) - where memory records from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CollectMode {
+    In,   // 1 - the dry input
+    Feed, // 2 - input and repeats
+    Out,  // 3 - the pedal's output
+}
+
+impl CollectMode {
+    pub fn to_cc_value(self) -> u8 {
+        match self {
+            CollectMode::In => 1,
+            CollectMode::Feed => 2,
+            CollectMode::Out => 3,
+        }
+    }
+
+    pub fn from_cc_value(value: u8) -> MidiResult<Self> {
+        match value {
+            1 => Ok(CollectMode::In),
+            2 => Ok(CollectMode::Feed),
+            3 => Ok(CollectMode::Out),
+            _ => Err(MidiError::InvalidValue {
+                expected: "1-3".to_string(),
+                actual: value.into(),
+            }),
+        }
+    }
+}
+
+/// DIP switch sweep direction (CC 67)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SweepDirection {
+    Bottom, // 0-63
+    Top,    // 64-127
+}
+
+impl SweepDirection {
+    pub fn to_cc_value(self) -> u8 {
+        match self {
+            SweepDirection::Bottom => 0,
+            SweepDirection::Top => 127,
+        }
+    }
+
+    pub fn from_cc_value(value: u8) -> Self {
+        if value < 64 {
+            SweepDirection::Bottom
+        } else {
+            SweepDirection::Top
+        }
+    }
+}
+
+/// DIP switch polarity (CC 68)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Polarity {
+    Forward, // 0-63
+    Reverse, // 64-127
+}
+
+impl Polarity {
+    pub fn to_cc_value(self) -> u8 {
+        match self {
+            Polarity::Forward => 0,
+            Polarity::Reverse => 127,
+        }
+    }
+
+    pub fn from_cc_value(value: u8) -> Self {
+        if value < 64 {
+            Polarity::Forward
+        } else {
+            Polarity::Reverse
+        }
+    }
+}
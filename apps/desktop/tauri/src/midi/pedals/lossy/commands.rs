@@ -1,6 +1,8 @@
 // Tauri commands for Chase Bliss Audio Lossy
 
-use crate::midi::SharedMidiManager;
+use crate::midi::{InputChannelMode, PedalType, SharedMidiManager};
+use crate::midi::connect_retry::{connect_with_retry, ConnectOutcome, RetryOptions};
+use crate::midi::safety::RecallOutcome;
 use crate::midi::pedals::lossy::{LossyParameter, LossyState};
 use tauri::State;
 
@@ -10,11 +12,17 @@ pub async fn connect_lossy(
     manager: State<'_, SharedMidiManager>,
     device_name: String,
     midi_channel: u8,
-) -> Result<(), String> {
-    let mut manager = manager.lock().map_err(|e| e.to_string())?;
-    manager
-        .connect_lossy(&device_name, midi_channel)
-        .map_err(|e| e.to_string())
+    input_mode: Option<InputChannelMode>,
+    retries: Option<u32>,
+    initial_backoff_ms: Option<u64>,
+) -> Result<ConnectOutcome, String> {
+    let options = RetryOptions::from_params(retries, initial_backoff_ms);
+    connect_with_retry(manager.inner(), &device_name, "connect_lossy", options, |manager| {
+        manager.connect_with_input_mode(&device_name, input_mode.as_ref(), |manager| {
+            manager.connect_lossy(&device_name, midi_channel)
+        })
+    })
+    .map_err(|e| e.to_string())
 }
 
 /// Send a Lossy parameter change
@@ -25,6 +33,7 @@ pub async fn send_lossy_parameter(
     param: LossyParameter,
 ) -> Result<(), String> {
     let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("send_lossy_parameter");
     manager
         .send_lossy_parameter(&device_name, param)
         .map_err(|e| e.to_string())
@@ -43,16 +52,30 @@ pub async fn get_lossy_state(
 }
 
 /// Recall a Lossy preset (send all parameters)
+/// Loud parameter jumps block the recall until `acknowledge_warnings` is set.
+/// A `glide_ms` above 0 (at most 2 s) ramps `glide_ccs` to their targets over that long.
 #[tauri::command]
 pub async fn recall_lossy_preset(
     manager: State<'_, SharedMidiManager>,
     device_name: String,
     state: LossyState,
-) -> Result<(), String> {
+    acknowledge_warnings: bool,
+    glide_ms: Option<u64>,
+    glide_ccs: Option<Vec<u8>>,
+) -> Result<RecallOutcome, String> {
     let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    let warnings = manager
+        .recall_safety_warnings(&device_name, &state)
+        .map_err(|e| e.to_string())?;
+    if !warnings.is_empty() && !acknowledge_warnings {
+        return Ok(RecallOutcome::blocked(warnings));
+    }
+    manager.begin_journal_operation("recall_lossy_preset");
+    let glide = PedalType::Lossy.glide(glide_ms, glide_ccs);
     manager
-        .recall_lossy_preset(&device_name, &state)
-        .map_err(|e| e.to_string())
+        .gliding(&device_name, glide, |manager| manager.recall_lossy_preset(&device_name, &state))
+        .map_err(|e| e.to_string())?;
+    Ok(RecallOutcome::recalled(warnings))
 }
 
 /// Save current state to a Lossy preset slot (1-122)
@@ -63,6 +86,7 @@ pub async fn save_lossy_preset(
     slot: u8,
 ) -> Result<(), String> {
     let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("save_lossy_preset");
     manager
         .save_lossy_preset(&device_name, slot)
         .map_err(|e| e.to_string())
@@ -76,6 +100,7 @@ pub async fn send_lossy_program_change(
     program: u8,
 ) -> Result<(), String> {
     let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("send_lossy_program_change");
     manager
         .send_lossy_program_change(&device_name, program)
         .map_err(|e| e.to_string())
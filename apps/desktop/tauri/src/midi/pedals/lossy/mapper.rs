@@ -1,6 +1,8 @@
 // Lossy MIDI CC mapping - infrastructure layer
 
 use super::types::{LossyParameter, LossyState};
+use crate::midi::pedals::DIP_SWITCH_CCS;
+use crate::midi::send_plan::SendPlan;
 use std::collections::HashMap;
 
 pub const CC_PRESET_SAVE: u8 = 111;
@@ -85,7 +87,7 @@ impl LossyParameter {
             | Self::AutoGain(v)
             | Self::LossGain(v)
             | Self::Expression(v)
-            | Self::PresetSave(v) => *v,
+            | Self::PresetSave(v) => v.get(),
 
             // Three-position enums
             Self::FilterSlope(v) => v.to_cc_value(),
@@ -234,4 +236,10 @@ impl LossyState {
 
         map
     }
+
+    /// Recall order: bypass and dry kill first, then toggles, footswitch modes and DIP switches, then knobs
+    pub fn recall_plan(&self) -> SendPlan {
+        let selections = [&[21, 22, 23, 33, 103, 104, 105, 106, 52][..], DIP_SWITCH_CCS].concat();
+        SendPlan::grouped(self.to_cc_map(), &[102, 57], &selections, &[])
+    }
 }
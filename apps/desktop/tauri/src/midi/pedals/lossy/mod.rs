@@ -27,22 +27,22 @@ impl Lossy {
     /// Update internal state from a parameter change
     pub fn update_state(&mut self, param: &LossyParameter) {
         match param {
-            LossyParameter::Filter(v) => self.state.filter = *v,
-            LossyParameter::Global(v) => self.state.global = *v,
-            LossyParameter::Reverb(v) => self.state.reverb = *v,
-            LossyParameter::Freq(v) => self.state.freq = *v,
-            LossyParameter::Speed(v) => self.state.speed = *v,
-            LossyParameter::Loss(v) => self.state.loss = *v,
-            LossyParameter::RampSpeed(v) => self.state.ramp_speed = *v,
+            LossyParameter::Filter(v) => self.state.filter = v.get(),
+            LossyParameter::Global(v) => self.state.global = v.get(),
+            LossyParameter::Reverb(v) => self.state.reverb = v.get(),
+            LossyParameter::Freq(v) => self.state.freq = v.get(),
+            LossyParameter::Speed(v) => self.state.speed = v.get(),
+            LossyParameter::Loss(v) => self.state.loss = v.get(),
+            LossyParameter::RampSpeed(v) => self.state.ramp_speed = v.get(),
             LossyParameter::FilterSlope(v) => self.state.filter_slope = *v,
             LossyParameter::PacketMode(v) => self.state.packet_mode = *v,
             LossyParameter::LossEffect(v) => self.state.loss_effect = *v,
-            LossyParameter::Gate(v) => self.state.gate = *v,
-            LossyParameter::Freezer(v) => self.state.freezer = *v,
-            LossyParameter::VerbDecay(v) => self.state.verb_decay = *v,
-            LossyParameter::LimiterThreshold(v) => self.state.limiter_threshold = *v,
-            LossyParameter::AutoGain(v) => self.state.auto_gain = *v,
-            LossyParameter::LossGain(v) => self.state.loss_gain = *v,
+            LossyParameter::Gate(v) => self.state.gate = v.get(),
+            LossyParameter::Freezer(v) => self.state.freezer = v.get(),
+            LossyParameter::VerbDecay(v) => self.state.verb_decay = v.get(),
+            LossyParameter::LimiterThreshold(v) => self.state.limiter_threshold = v.get(),
+            LossyParameter::AutoGain(v) => self.state.auto_gain = v.get(),
+            LossyParameter::LossGain(v) => self.state.loss_gain = v.get(),
             LossyParameter::Weighting(v) => self.state.weighting = *v,
             LossyParameter::Bypass(v) => self.state.bypass = *v,
             LossyParameter::FreezeSlushie(v) => self.state.freeze_slushie = *v,
@@ -67,7 +67,7 @@ impl Lossy {
             LossyParameter::DipAllWet(v) => self.state.dip_all_wet = *v,
             LossyParameter::RampBounce(v) => self.state.ramp_bounce = *v,
             LossyParameter::DryKill(v) => self.state.dry_kill = *v,
-            LossyParameter::Expression(v) => self.state.expression = *v,
+            LossyParameter::Expression(v) => self.state.expression = v.get(),
             LossyParameter::PresetSave(_) => {} // Does not update state
         }
     }
@@ -76,6 +76,11 @@ impl Lossy {
     pub fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
         self.state.to_cc_map()
     }
+
+    /// Get the current state as CCs in the order a recall should send them
+    pub fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.state.recall_plan()
+    }
 }
 
 impl super::PedalCapabilities for Lossy {
@@ -110,4 +115,8 @@ impl super::PedalCapabilities for Lossy {
     fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
         self.state_as_cc_map()
     }
+
+    fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.recall_plan()
+    }
 }
@@ -1,5 +1,6 @@
 // Lossy domain types
 
+use crate::midi::CcValue;
 use crate::midi::error::{MidiError, MidiResult};
 use serde::{Deserialize, Serialize};
 
@@ -114,13 +115,13 @@ impl Default for LossyState {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum LossyParameter {
     // Main knobs
-    Filter(u8),
-    Global(u8),
-    Reverb(u8),
-    Freq(u8),
-    Speed(u8),
-    Loss(u8),
-    RampSpeed(u8),
+    Filter(CcValue),
+    Global(CcValue),
+    Reverb(CcValue),
+    Freq(CcValue),
+    Speed(CcValue),
+    Loss(CcValue),
+    RampSpeed(CcValue),
 
     // Three-position toggles
     FilterSlope(FilterSlope),
@@ -128,12 +129,12 @@ pub enum LossyParameter {
     LossEffect(LossEffect),
 
     // Hidden options
-    Gate(u8),
-    Freezer(u8),
-    VerbDecay(u8),
-    LimiterThreshold(u8),
-    AutoGain(u8),
-    LossGain(u8),
+    Gate(CcValue),
+    Freezer(CcValue),
+    VerbDecay(CcValue),
+    LimiterThreshold(CcValue),
+    AutoGain(CcValue),
+    LossGain(CcValue),
     Weighting(Weighting),
 
     // Footswitches
@@ -166,8 +167,8 @@ pub enum LossyParameter {
     // Utility
     RampBounce(bool),
     DryKill(bool),
-    Expression(u8),
-    PresetSave(u8),
+    Expression(CcValue),
+    PresetSave(CcValue),
 }
 
 /// Filter slope toggle (CC 21)
@@ -194,7 +195,7 @@ impl FilterSlope {
             3 => Ok(Self::Db96),
             _ => Err(MidiError::InvalidValue {
                 expected: "1-3".to_string(),
-                actual: value,
+                actual: value.into(),
             }),
         }
     }
@@ -224,7 +225,7 @@ impl PacketMode {
             3 => Ok(Self::LossMode),
             _ => Err(MidiError::InvalidValue {
                 expected: "1-3".to_string(),
-                actual: value,
+                actual: value.into(),
             }),
         }
     }
@@ -254,7 +255,7 @@ impl LossEffect {
             3 => Ok(Self::Jitter),
             _ => Err(MidiError::InvalidValue {
                 expected: "1-3".to_string(),
-                actual: value,
+                actual: value.into(),
             }),
         }
     }
@@ -284,7 +285,7 @@ impl Weighting {
             3 => Ok(Self::Bright),
             _ => Err(MidiError::InvalidValue {
                 expected: "1-3".to_string(),
-                actual: value,
+                actual: value.into(),
             }),
         }
     }
@@ -0,0 +1,345 @@
+// Microcosm user-bank dump
+// The Microcosm can't send its stored presets on request, but it echoes a preset's CCs
+// when one is loaded. Walking the 16 user banks (PC 45-60) with a pause after each program
+// change lets input->state routing rebuild each bank, which is then archived as a preset.
+// Banks that stay silent are still archived but reported, since their snapshot is only
+// whatever state the previous bank left behind.
+
+use crate::midi::error::MidiError;
+use crate::midi::clock::Clock;
+use crate::midi::pedals::microcosm::MicrocosmState;
+use crate::presets::{PresetError, PresetId};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::Duration;
+
+/// Program changes for user banks 1-16
+pub const USER_BANK_PROGRAMS: RangeInclusive<u8> = 45..=60;
+
+/// How long to wait for the pedal to echo a bank after selecting it
+pub const DEFAULT_SETTLE_MS: u64 = 500;
+
+/// Tag on every archived bank
+pub const DUMP_TAG: &str = "hardware-dump";
+
+/// Event emitted after each bank
+pub const PROGRESS_EVENT: &str = "microcosm-bank-dump-progress";
+
+#[derive(Debug, thiserror::Error)]
+pub enum BankDumpError {
+    #[error(transparent)]
+    Midi(#[from] MidiError),
+
+    #[error(transparent)]
+    Preset(#[from] PresetError),
+}
+
+pub type Result<T> = std::result::Result<T, BankDumpError>;
+
+/// The pedal and library a dump works against
+pub trait DumpTarget {
+    /// Program the pedal is on before the dump, to return to afterwards
+    fn current_program(&self) -> Result<u8>;
+    fn select_program(&self, program: u8) -> Result<()>;
+    /// Running count of CCs heard from the pedal
+    fn received_cc_count(&self) -> Result<u64>;
+    /// The pedal's state as rebuilt from what it has sent
+    fn snapshot(&self) -> Result<MicrocosmState>;
+    fn save_preset(&self, name: &str, description: &str, state: &MicrocosmState) -> Result<PresetId>;
+}
+
+/// One bank archived
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ArchivedBank {
+    /// 1-16
+    pub bank_number: u8,
+    pub program: u8,
+    pub preset_id: PresetId,
+    /// False if the pedal sent nothing after the bank was selected
+    pub echoed: bool,
+}
+
+/// Progress payload, sent after each bank
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BankDumpProgress {
+    pub device_name: String,
+    pub bank: ArchivedBank,
+    pub completed: usize,
+    pub total: usize,
+}
+
+/// Outcome of a dump
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BankDumpSummary {
+    pub device_name: String,
+    pub archived: Vec<ArchivedBank>,
+    /// Banks that produced no echo - their archived snapshots are unreliable
+    pub silent_banks: Vec<u8>,
+    pub cancelled: bool,
+    /// Program selected again once the dump ended
+    pub restored_program: u8,
+}
+
+/// Archive every user bank, calling `on_progress` after each. Stops early (keeping what
+/// was archived) if `cancel` disconnects. The original program is selected again at the
+/// end, including after a failure part-way through.
+pub fn dump_banks(
+    device_name: &str,
+    target: &impl DumpTarget,
+    clock: &dyn Clock,
+    cancel: &Receiver<()>,
+    settle: Duration,
+    date: &str,
+    mut on_progress: impl FnMut(&BankDumpProgress),
+) -> Result<BankDumpSummary> {
+    let original_program = target.current_program()?;
+    let mut summary = BankDumpSummary {
+        device_name: device_name.to_string(),
+        archived: Vec::new(),
+        silent_banks: Vec::new(),
+        cancelled: false,
+        restored_program: original_program,
+    };
+
+    let walked = walk_banks(target, clock, cancel, settle, date, &mut summary, &mut on_progress);
+    let restored = target.select_program(original_program);
+    walked?;
+    restored?;
+    Ok(summary)
+}
+
+fn walk_banks(
+    target: &impl DumpTarget,
+    clock: &dyn Clock,
+    cancel: &Receiver<()>,
+    settle: Duration,
+    date: &str,
+    summary: &mut BankDumpSummary,
+    on_progress: &mut impl FnMut(&BankDumpProgress),
+) -> Result<()> {
+    let total = USER_BANK_PROGRAMS.len();
+    for program in USER_BANK_PROGRAMS {
+        let bank_number = program - USER_BANK_PROGRAMS.start() + 1;
+        let heard_before = target.received_cc_count()?;
+        target.select_program(program)?;
+        if !clock.wait(settle, cancel) {
+            summary.cancelled = true;
+            return Ok(());
+        }
+        let echoed = target.received_cc_count()? > heard_before;
+
+        let description = if echoed {
+            format!("Snapshot of user bank {} (PC {})", bank_number, program)
+        } else {
+            format!(
+                "Snapshot of user bank {} (PC {}). The pedal sent nothing back, so this may not match the bank.",
+                bank_number, program
+            )
+        };
+        let preset_id = save_unique(target, &format!("Bank {} archive {}", bank_number, date), &description)?;
+
+        if !echoed {
+            summary.silent_banks.push(bank_number);
+        }
+        summary.archived.push(ArchivedBank {
+            bank_number,
+            program,
+            preset_id,
+            echoed,
+        });
+        on_progress(&BankDumpProgress {
+            device_name: summary.device_name.clone(),
+            bank: summary.archived[summary.archived.len() - 1].clone(),
+            completed: summary.archived.len(),
+            total,
+        });
+    }
+    Ok(())
+}
+
+/// Save under `name`, or `name (2)`, `name (3)`... if an earlier dump today took it
+fn save_unique(target: &impl DumpTarget, name: &str, description: &str) -> Result<PresetId> {
+    let state = target.snapshot()?;
+    let mut attempt = 1;
+    loop {
+        let candidate = if attempt == 1 { name.to_string() } else { format!("{} ({})", name, attempt) };
+        match target.save_preset(&candidate, description, &state) {
+            Err(BankDumpError::Preset(PresetError::DuplicateName { .. })) => attempt += 1,
+            result => return result,
+        }
+    }
+}
+
+struct RunningDump {
+    id: u64,
+    // Dropping the sender cancels the dump
+    _cancel: Sender<()>,
+}
+
+/// Running dumps, at most one per device
+#[derive(Default)]
+pub struct BankDumps {
+    running: HashMap<String, RunningDump>,
+    next_id: u64,
+}
+
+impl BankDumps {
+    /// Register a dump for `device_name`. None if one is already running there.
+    pub fn begin(&mut self, device_name: &str) -> Option<(u64, Receiver<()>)> {
+        if self.running.contains_key(device_name) {
+            return None;
+        }
+        self.next_id += 1;
+        let (cancel, cancelled) = mpsc::channel();
+        self.running.insert(device_name.to_string(), RunningDump { id: self.next_id, _cancel: cancel });
+        Some((self.next_id, cancelled))
+    }
+
+    /// Cancel the device's dump; returns whether one was running
+    pub fn cancel(&mut self, device_name: &str) -> bool {
+        self.running.remove(device_name).is_some()
+    }
+
+    /// Forget a finished dump (a no-op if it was cancelled and another has started since)
+    pub fn finish(&mut self, device_name: &str, id: u64) {
+        if self.running.get(device_name).is_some_and(|dump| dump.id == id) {
+            self.running.remove(device_name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+    use std::sync::mpsc::TryRecvError;
+
+    /// Waits finish immediately unless already cancelled
+    struct InstantClock;
+
+    impl Clock for InstantClock {
+        fn wait(&self, _duration: Duration, cancel: &Receiver<()>) -> bool {
+            matches!(cancel.try_recv(), Err(TryRecvError::Empty))
+        }
+
+        fn now(&self) -> Duration {
+            Duration::ZERO
+        }
+    }
+
+    /// A pedal that echoes every bank except `silent` ones by setting `mix` to the program
+    #[derive(Default)]
+    struct FakePedal {
+        silent: Vec<u8>,
+        selected: RefCell<Vec<u8>>,
+        state: RefCell<MicrocosmState>,
+        cc_count: Cell<u64>,
+        saved: RefCell<Vec<String>>,
+        taken_names: Vec<String>,
+        /// Dropped when this program is selected, cancelling the dump
+        cancel_at: Option<(u8, RefCell<Option<Sender<()>>>)>,
+    }
+
+    impl DumpTarget for FakePedal {
+        fn current_program(&self) -> Result<u8> {
+            Ok(7)
+        }
+
+        fn select_program(&self, program: u8) -> Result<()> {
+            self.selected.borrow_mut().push(program);
+            if let Some((cancel_program, cancel)) = &self.cancel_at {
+                if *cancel_program == program {
+                    cancel.borrow_mut().take();
+                }
+            }
+            if USER_BANK_PROGRAMS.contains(&program) && !self.silent.contains(&program) {
+                self.state.borrow_mut().mix = program;
+                self.cc_count.set(self.cc_count.get() + 1);
+            }
+            Ok(())
+        }
+
+        fn received_cc_count(&self) -> Result<u64> {
+            Ok(self.cc_count.get())
+        }
+
+        fn snapshot(&self) -> Result<MicrocosmState> {
+            Ok(self.state.borrow().clone())
+        }
+
+        fn save_preset(&self, name: &str, _description: &str, _state: &MicrocosmState) -> Result<PresetId> {
+            if self.taken_names.iter().any(|taken| taken == name) {
+                return Err(PresetError::DuplicateName { name: name.to_string() }.into());
+            }
+            self.saved.borrow_mut().push(name.to_string());
+            Ok(PresetId::new(name.to_string()))
+        }
+    }
+
+    fn dump(pedal: &FakePedal, cancel: &Receiver<()>) -> (BankDumpSummary, usize) {
+        let mut progress_events = 0;
+        let summary = dump_banks("Microcosm", pedal, &InstantClock, cancel, Duration::ZERO, "2026-10-15", |_| {
+            progress_events += 1;
+        })
+        .unwrap();
+        (summary, progress_events)
+    }
+
+    #[test]
+    fn test_walks_every_bank_and_reports_silent_ones() {
+        let pedal = FakePedal {
+            silent: vec![47, 60],
+            taken_names: vec!["Bank 1 archive 2026-10-15".to_string()],
+            ..FakePedal::default()
+        };
+        let (_cancel, cancelled) = mpsc::channel();
+
+        let (summary, progress_events) = dump(&pedal, &cancelled);
+
+        assert_eq!(summary.archived.len(), 16);
+        assert_eq!(progress_events, 16);
+        assert_eq!(summary.silent_banks, vec![3, 16]);
+        assert!(!summary.cancelled);
+        assert_eq!(pedal.saved.borrow()[0], "Bank 1 archive 2026-10-15 (2)");
+        assert_eq!(pedal.saved.borrow()[15], "Bank 16 archive 2026-10-15");
+
+        // Every user bank in order, then back to where the pedal started
+        let selected = pedal.selected.borrow();
+        assert_eq!(selected[..16], USER_BANK_PROGRAMS.collect::<Vec<_>>()[..]);
+        assert_eq!(selected.last(), Some(&7));
+        assert_eq!(summary.restored_program, 7);
+    }
+
+    #[test]
+    fn test_cancel_stops_and_restores_program() {
+        let (cancel, cancelled) = mpsc::channel();
+        // Cancel while bank 3 is settling
+        let pedal = FakePedal {
+            cancel_at: Some((47, RefCell::new(Some(cancel)))),
+            ..FakePedal::default()
+        };
+
+        let (summary, _) = dump(&pedal, &cancelled);
+
+        assert!(summary.cancelled);
+        assert_eq!(summary.archived.len(), 2);
+        assert_eq!(pedal.selected.borrow().as_slice(), &[45, 46, 47, 7]);
+    }
+
+    #[test]
+    fn test_one_dump_per_device() {
+        let mut dumps = BankDumps::default();
+        let (first, _cancelled) = dumps.begin("Microcosm").unwrap();
+        assert!(dumps.begin("Microcosm").is_none());
+
+        assert!(dumps.cancel("Microcosm"));
+        let (second, _cancelled) = dumps.begin("Microcosm").unwrap();
+        // The cancelled dump finishing late doesn't clear the new one
+        dumps.finish("Microcosm", first);
+        assert!(dumps.begin("Microcosm").is_none());
+        dumps.finish("Microcosm", second);
+        assert!(dumps.begin("Microcosm").is_some());
+    }
+}
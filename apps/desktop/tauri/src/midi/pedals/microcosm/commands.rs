@@ -1,8 +1,15 @@
 // Tauri commands for Hologram Microcosm pedal
 
-use crate::midi::SharedMidiManager;
+use crate::midi::{InputChannelMode, PedalType, SharedMidiManager};
+use crate::midi::connect_retry::{connect_with_retry, ConnectOutcome, RetryOptions};
+use crate::midi::error::MidiError;
+use crate::midi::safety::RecallOutcome;
+use crate::midi::clock::SystemClock;
 use crate::midi::pedals::microcosm::{MicrocosmParameter, MicrocosmState};
-use tauri::State;
+use crate::midi::pedals::microcosm::bank_dump::{self, BankDumpSummary, DumpTarget, DEFAULT_SETTLE_MS, DUMP_TAG, PROGRESS_EVENT};
+use crate::presets::{PresetId, SharedPresetLibrary};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, State};
 
 /// Connect to a Microcosm pedal
 #[tauri::command]
@@ -10,11 +17,17 @@ pub async fn connect_microcosm(
     manager: State<'_, SharedMidiManager>,
     device_name: String,
     midi_channel: u8,
-) -> Result<(), String> {
-    let mut manager = manager.lock().map_err(|e| e.to_string())?;
-    manager
-        .connect_microcosm(&device_name, midi_channel)
-        .map_err(|e| e.to_string())
+    input_mode: Option<InputChannelMode>,
+    retries: Option<u32>,
+    initial_backoff_ms: Option<u64>,
+) -> Result<ConnectOutcome, String> {
+    let options = RetryOptions::from_params(retries, initial_backoff_ms);
+    connect_with_retry(manager.inner(), &device_name, "connect_microcosm", options, |manager| {
+        manager.connect_with_input_mode(&device_name, input_mode.as_ref(), |manager| {
+            manager.connect_microcosm(&device_name, midi_channel)
+        })
+    })
+    .map_err(|e| e.to_string())
 }
 
 /// Send a Microcosm parameter change
@@ -25,6 +38,7 @@ pub async fn send_microcosm_parameter(
     param: MicrocosmParameter,
 ) -> Result<(), String> {
     let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("send_microcosm_parameter");
     manager
         .send_microcosm_parameter(&device_name, param)
         .map_err(|e| e.to_string())
@@ -38,6 +52,7 @@ pub async fn send_microcosm_program_change(
     program: u8,
 ) -> Result<(), String> {
     let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("send_microcosm_program_change");
     manager
         .send_microcosm_program_change(&device_name, program)
         .map_err(|e| e.to_string())
@@ -56,14 +71,124 @@ pub async fn get_microcosm_state(
 }
 
 /// Recall a Microcosm preset (send all parameters)
+/// Loud parameter jumps block the recall until `acknowledge_warnings` is set.
+/// A `glide_ms` above 0 (at most 2 s) ramps `glide_ccs` (the pedal's mix and level
+/// controls by default) to their targets over that long.
 #[tauri::command]
 pub async fn recall_microcosm_preset(
     manager: State<'_, SharedMidiManager>,
     device_name: String,
     state: MicrocosmState,
-) -> Result<(), String> {
+    acknowledge_warnings: bool,
+    glide_ms: Option<u64>,
+    glide_ccs: Option<Vec<u8>>,
+) -> Result<RecallOutcome, String> {
     let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    let warnings = manager
+        .recall_safety_warnings(&device_name, &state)
+        .map_err(|e| e.to_string())?;
+    if !warnings.is_empty() && !acknowledge_warnings {
+        return Ok(RecallOutcome::blocked(warnings));
+    }
+    manager.begin_journal_operation("recall_microcosm_preset");
+    let glide = PedalType::Microcosm.glide(glide_ms, glide_ccs);
     manager
-        .recall_microcosm_preset(&device_name, &state)
-        .map_err(|e| e.to_string())
+        .gliding(&device_name, glide, |manager| manager.recall_microcosm_preset(&device_name, &state))
+        .map_err(|e| e.to_string())?;
+    Ok(RecallOutcome::recalled(warnings))
+}
+
+/// A connected Microcosm and the preset library, for a bank dump.
+/// Each call takes its lock briefly so echoed CCs can update state between calls.
+struct AppDumpTarget {
+    manager: SharedMidiManager,
+    library: SharedPresetLibrary,
+    device_name: String,
+}
+
+impl AppDumpTarget {
+    fn manager(&self) -> bank_dump::Result<std::sync::MutexGuard<'_, crate::midi::MidiManager>> {
+        self.manager.lock().map_err(|e| MidiError::Other(e.to_string()).into())
+    }
+}
+
+impl DumpTarget for AppDumpTarget {
+    fn current_program(&self) -> bank_dump::Result<u8> {
+        Ok(self.manager()?.get_microcosm_program(&self.device_name)?)
+    }
+
+    fn select_program(&self, program: u8) -> bank_dump::Result<()> {
+        Ok(self.manager()?.send_microcosm_program_change(&self.device_name, program)?)
+    }
+
+    fn received_cc_count(&self) -> bank_dump::Result<u64> {
+        Ok(self.manager()?.received_cc_count(&self.device_name)?)
+    }
+
+    fn snapshot(&self) -> bank_dump::Result<MicrocosmState> {
+        Ok(self.manager()?.get_microcosm_state(&self.device_name)?)
+    }
+
+    fn save_preset(&self, name: &str, description: &str, state: &MicrocosmState) -> bank_dump::Result<PresetId> {
+        let parameters = serde_json::to_value(state).map_err(|e| MidiError::Other(e.to_string()))?;
+        let library = self.library.lock().map_err(|e| MidiError::Other(e.to_string()))?;
+        let preset = library.save_preset(
+            name.to_string(),
+            "Microcosm".to_string(),
+            Some(description.to_string()),
+            parameters,
+            vec![DUMP_TAG.to_string()],
+        )?;
+        Ok(preset.id)
+    }
+}
+
+/// Archive all 16 user banks (PC 45-60) as presets tagged "hardware-dump", waiting
+/// `settle_ms` after each for the pedal's echo. Emits `microcosm-bank-dump-progress` per
+/// bank and reselects the original program at the end. Cancel with `cancel_microcosm_bank_dump`.
+#[tauri::command]
+pub async fn dump_microcosm_banks(
+    app: AppHandle,
+    manager: State<'_, SharedMidiManager>,
+    library: State<'_, SharedPresetLibrary>,
+    device_name: String,
+    settle_ms: Option<u64>,
+) -> Result<BankDumpSummary, String> {
+    let (dump_id, cancelled) = {
+        let mut manager = manager.lock().map_err(|e| e.to_string())?;
+        manager.begin_journal_operation("dump_microcosm_banks");
+        manager
+            .begin_microcosm_bank_dump(&device_name)
+            .map_err(|e| e.to_string())?
+    };
+
+    let target = AppDumpTarget {
+        manager: SharedMidiManager::clone(&manager),
+        library: SharedPresetLibrary::clone(&library),
+        device_name: device_name.clone(),
+    };
+    let settle = Duration::from_millis(settle_ms.unwrap_or(DEFAULT_SETTLE_MS));
+    let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let result = tokio::task::block_in_place(|| {
+        bank_dump::dump_banks(&device_name, &target, &SystemClock, &cancelled, settle, &date, |progress| {
+            if let Err(e) = app.emit(PROGRESS_EVENT, progress) {
+                eprintln!("❌ Failed to emit bank dump progress: {}", e);
+            }
+        })
+    });
+
+    if let Ok(mut manager) = manager.lock() {
+        manager.finish_microcosm_bank_dump(&device_name, dump_id);
+    }
+    result.map_err(|e| e.to_string())
+}
+
+/// Stop a running Microcosm bank dump. Banks archived so far are kept.
+#[tauri::command]
+pub async fn cancel_microcosm_bank_dump(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+) -> Result<bool, String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    Ok(manager.cancel_microcosm_bank_dump(&device_name))
 }
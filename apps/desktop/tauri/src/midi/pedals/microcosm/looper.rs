@@ -0,0 +1,157 @@
+// Looper transport state machine for the Microcosm
+// The pedal doesn't echo its looper status, so we track a *believed* state from
+// the transport triggers we send. The transition table is plain data so it can
+// be reviewed against the manual and tested exhaustively.
+
+use super::types::{LooperTransport, LooperTrigger, MicrocosmParameter};
+
+use LooperTransport::*;
+use LooperTrigger::*;
+
+/// (from, trigger, to) - any pair not listed leaves the transport unchanged
+pub const LOOPER_TRANSITIONS: &[(LooperTransport, LooperTrigger, LooperTransport)] = &[
+    // Empty: only Record starts a new loop
+    (Empty, Record, Recording),
+    // Recording: Record/Play closes the loop, Overdub closes and layers
+    (Recording, Record, Playing),
+    (Recording, Play, Playing),
+    (Recording, Overdub, Overdubbing),
+    (Recording, Stop, Stopped),
+    (Recording, Erase, Empty),
+    (Recording, Undo, Empty),
+    // Playing
+    (Playing, Record, Overdubbing),
+    (Playing, Overdub, Overdubbing),
+    (Playing, Stop, Stopped),
+    (Playing, Erase, Empty),
+    // Overdubbing: Record/Overdub toggles back to playback
+    (Overdubbing, Record, Playing),
+    (Overdubbing, Play, Playing),
+    (Overdubbing, Overdub, Playing),
+    (Overdubbing, Stop, Stopped),
+    (Overdubbing, Erase, Empty),
+    (Overdubbing, Undo, Playing),
+    // Stopped: the loop is still in memory
+    (Stopped, Record, Recording),
+    (Stopped, Play, Playing),
+    (Stopped, Overdub, Overdubbing),
+    (Stopped, Erase, Empty),
+];
+
+impl LooperTrigger {
+    /// Map a transport parameter to its trigger (None for non-transport parameters)
+    pub fn from_parameter(param: &MicrocosmParameter) -> Option<Self> {
+        match param {
+            MicrocosmParameter::LooperRecord => Some(Record),
+            MicrocosmParameter::LooperPlay => Some(Play),
+            MicrocosmParameter::LooperOverdub => Some(Overdub),
+            MicrocosmParameter::LooperStop => Some(Stop),
+            MicrocosmParameter::LooperErase => Some(Erase),
+            MicrocosmParameter::LooperUndo => Some(Undo),
+            _ => None,
+        }
+    }
+}
+
+impl LooperTransport {
+    /// Apply a transport trigger and return the resulting (believed) state
+    pub fn apply(self, trigger: LooperTrigger) -> Self {
+        LOOPER_TRANSITIONS
+            .iter()
+            .find(|(from, t, _)| *from == self && *t == trigger)
+            .map(|(_, _, to)| *to)
+            .unwrap_or(self)
+    }
+    
+    /// Human-readable name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Empty => "Empty",
+            Recording => "Recording",
+            Playing => "Playing",
+            Overdubbing => "Overdubbing",
+            Stopped => "Stopped",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::midi::CcValue;
+    
+    const ALL_STATES: [LooperTransport; 5] = [Empty, Recording, Playing, Overdubbing, Stopped];
+    const ALL_TRIGGERS: [LooperTrigger; 6] = [Record, Play, Overdub, Stop, Erase, Undo];
+    
+    #[test]
+    fn test_transition_table_has_no_duplicate_keys() {
+        for (i, (from, trigger, _)) in LOOPER_TRANSITIONS.iter().enumerate() {
+            let dupes = LOOPER_TRANSITIONS[i + 1..]
+                .iter()
+                .filter(|(f, t, _)| f == from && t == trigger)
+                .count();
+            assert_eq!(dupes, 0, "Duplicate transition for {:?} + {:?}", from, trigger);
+        }
+    }
+    
+    #[test]
+    fn test_every_state_trigger_pair() {
+        // Expected result for every (state, trigger) pair, in ALL_TRIGGERS order
+        let expected: [(LooperTransport, [LooperTransport; 6]); 5] = [
+            (Empty, [Recording, Empty, Empty, Empty, Empty, Empty]),
+            (Recording, [Playing, Playing, Overdubbing, Stopped, Empty, Empty]),
+            (Playing, [Overdubbing, Playing, Overdubbing, Stopped, Empty, Playing]),
+            (Overdubbing, [Playing, Playing, Playing, Stopped, Empty, Playing]),
+            (Stopped, [Recording, Playing, Overdubbing, Stopped, Empty, Stopped]),
+        ];
+        
+        for (from, results) in expected {
+            for (trigger, to) in ALL_TRIGGERS.iter().zip(results) {
+                assert_eq!(
+                    from.apply(*trigger),
+                    to,
+                    "{:?} + {:?} should be {:?}",
+                    from,
+                    trigger,
+                    to
+                );
+            }
+        }
+    }
+    
+    #[test]
+    fn test_erase_always_empties() {
+        for state in ALL_STATES {
+            assert_eq!(state.apply(Erase), Empty);
+        }
+    }
+    
+    #[test]
+    fn test_record_play_cycle() {
+        let state = Empty.apply(Record);
+        assert_eq!(state, Recording);
+        let state = state.apply(Record);
+        assert_eq!(state, Playing);
+        let state = state.apply(Record);
+        assert_eq!(state, Overdubbing);
+        let state = state.apply(Record);
+        assert_eq!(state, Playing);
+    }
+    
+    #[test]
+    fn test_trigger_from_parameter() {
+        assert_eq!(LooperTrigger::from_parameter(&MicrocosmParameter::LooperRecord), Some(Record));
+        assert_eq!(LooperTrigger::from_parameter(&MicrocosmParameter::LooperPlay), Some(Play));
+        assert_eq!(LooperTrigger::from_parameter(&MicrocosmParameter::LooperOverdub), Some(Overdub));
+        assert_eq!(LooperTrigger::from_parameter(&MicrocosmParameter::LooperStop), Some(Stop));
+        assert_eq!(LooperTrigger::from_parameter(&MicrocosmParameter::LooperErase), Some(Erase));
+        assert_eq!(LooperTrigger::from_parameter(&MicrocosmParameter::LooperUndo), Some(Undo));
+        assert_eq!(LooperTrigger::from_parameter(&MicrocosmParameter::Mix(CcValue::new(64).unwrap())), None);
+        assert_eq!(LooperTrigger::from_parameter(&MicrocosmParameter::TapTempo), None);
+    }
+    
+    #[test]
+    fn test_default_is_empty() {
+        assert_eq!(LooperTransport::default(), Empty);
+    }
+}
@@ -3,8 +3,12 @@
 
 use super::types::*;
 use crate::midi::error::{MidiError, MidiResult};
+use crate::midi::send_plan::SendPlan;
 use std::collections::HashMap;
 
+/// Continuous CCs a gliding recall ramps: cutoff, mix, space and volume
+pub const GLIDE_CCS: [u8; 4] = [8, 9, 12, 16];
+
 // ============================================================================
 // Value Object <-> MIDI CC Conversions
 // ============================================================================
@@ -31,7 +35,7 @@ impl SubdivisionValue {
             5 => Ok(SubdivisionValue::Octuple),
             _ => Err(MidiError::InvalidValue {
                 expected: "0-5".to_string(),
-                actual: value,
+                actual: value.into(),
             }),
         }
     }
@@ -151,6 +155,8 @@ impl MicrocosmParameter {
             // Preset
             MicrocosmParameter::PresetCopy => 45,
             MicrocosmParameter::PresetSave => 46,
+            // Program change, not a CC - see program_number
+            MicrocosmParameter::EffectSelect(..) => 0,
         }
     }
     
@@ -171,7 +177,7 @@ impl MicrocosmParameter {
             MicrocosmParameter::ReverbTime(v) |
             MicrocosmParameter::LoopLevel(v) |
             MicrocosmParameter::LooperSpeed(v) |
-            MicrocosmParameter::FadeTime(v) => *v,
+            MicrocosmParameter::FadeTime(v) => v.get(),
             
             // Stepped/enum parameters
             MicrocosmParameter::Subdivision(s) |
@@ -199,6 +205,16 @@ impl MicrocosmParameter {
             MicrocosmParameter::LooperUndo |
             MicrocosmParameter::PresetCopy |
             MicrocosmParameter::PresetSave => 127,
+            
+            MicrocosmParameter::EffectSelect(effect, variation) => effect.program_number(*variation),
+        }
+    }
+    
+    /// Program change to send instead of a CC (None for CC parameters)
+    pub fn program_number(&self) -> Option<u8> {
+        match self {
+            MicrocosmParameter::EffectSelect(effect, variation) => Some(effect.program_number(*variation)),
+            _ => None,
         }
     }
     
@@ -240,6 +256,7 @@ impl MicrocosmParameter {
             MicrocosmParameter::LooperUndo => "Looper Undo",
             MicrocosmParameter::PresetCopy => "Preset Copy",
             MicrocosmParameter::PresetSave => "Preset Save",
+            MicrocosmParameter::EffectSelect(..) => "Effect Select",
         }
     }
 }
@@ -296,11 +313,74 @@ impl MicrocosmState {
         
         map
     }
+
+    /// Recall order: bypass and routing first, then subdivision, shape and looper modes, then knobs
+    pub fn recall_plan(&self) -> SendPlan {
+        SendPlan::grouped(self.to_cc_map(), &[102, 24, 25], &[5, 7, 18, 22, 23, 26, 27, 47, 48], &[])
+    }
+    
+    /// Apply a CC the pedal sent (e.g. echoing a preset it just loaded) to this state.
+    /// Only preset parameters are tracked; triggers and unknown CCs are ignored.
+    pub fn update_from_cc(&mut self, cc: u8, value: u8) {
+        let on = value >= 64;
+        match cc {
+            // Time
+            5 => {
+                if let Ok(subdivision) = SubdivisionValue::from_cc_value(value) {
+                    self.subdivision = subdivision;
+                }
+            }
+            10 => self.time = value,
+            48 => self.hold_sampler = on,
+            
+            // Special Sauce
+            6 => self.activity = value,
+            11 => self.repeats = value,
+            
+            // Modulation
+            7 => self.shape = WaveformShape::from_cc_value(value),
+            14 => self.frequency = value,
+            19 => self.depth = value,
+            
+            // Filter
+            8 => self.cutoff = value,
+            15 => self.resonance = value,
+            
+            // Effect
+            9 => self.mix = value,
+            16 => self.volume = value,
+            47 => self.reverse_effect = on,
+            102 => self.bypass = on,
+            
+            // Reverb
+            12 => self.space = value,
+            20 => self.reverb_time = value,
+            
+            // Looper
+            13 => self.loop_level = value,
+            17 => self.looper_speed = value,
+            18 => {
+                if let Ok(speed) = SubdivisionValue::from_cc_value(value) {
+                    self.looper_speed_stepped = speed;
+                }
+            }
+            21 => self.fade_time = value,
+            22 => self.looper_enabled = on,
+            23 => self.playback_direction = PlaybackDirection::from_cc_value(value),
+            24 => self.routing = LooperRouting::from_cc_value(value),
+            25 => self.looper_only = on,
+            26 => self.burst_mode = on,
+            27 => self.quantized = on,
+            
+            _ => {}
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::midi::CcValue;
     
     // Test SubdivisionValue conversions
     #[test]
@@ -398,20 +478,30 @@ mod tests {
     // Test MicrocosmParameter CC numbers
     #[test]
     fn test_parameter_cc_numbers() {
-        assert_eq!(MicrocosmParameter::Time(64).cc_number(), 10);
-        assert_eq!(MicrocosmParameter::Activity(64).cc_number(), 6);
-        assert_eq!(MicrocosmParameter::Mix(64).cc_number(), 9);
+        assert_eq!(MicrocosmParameter::Time(CcValue::new(64).unwrap()).cc_number(), 10);
+        assert_eq!(MicrocosmParameter::Activity(CcValue::new(64).unwrap()).cc_number(), 6);
+        assert_eq!(MicrocosmParameter::Mix(CcValue::new(64).unwrap()).cc_number(), 9);
         assert_eq!(MicrocosmParameter::Subdivision(SubdivisionValue::Tap).cc_number(), 5);
         assert_eq!(MicrocosmParameter::TapTempo.cc_number(), 93);
     }
     
+    // Continuous values are checked when a payload is deserialized
+    #[test]
+    fn test_out_of_range_parameter_payload_is_rejected() {
+        let param: MicrocosmParameter = serde_json::from_str(r#"{"Time": 127}"#).unwrap();
+        assert_eq!(param.cc_value(), 127);
+
+        let err = serde_json::from_str::<MicrocosmParameter>(r#"{"Time": 200}"#).unwrap_err().to_string();
+        assert!(err.contains("CC value 200 is out of range (0-127)"), "{}", err);
+    }
+
     // Test MicrocosmParameter CC values
     #[test]
     fn test_parameter_cc_values() {
         // Continuous parameters
-        assert_eq!(MicrocosmParameter::Time(64).cc_value(), 64);
-        assert_eq!(MicrocosmParameter::Activity(127).cc_value(), 127);
-        assert_eq!(MicrocosmParameter::Mix(0).cc_value(), 0);
+        assert_eq!(MicrocosmParameter::Time(CcValue::new(64).unwrap()).cc_value(), 64);
+        assert_eq!(MicrocosmParameter::Activity(CcValue::new(127).unwrap()).cc_value(), 127);
+        assert_eq!(MicrocosmParameter::Mix(CcValue::new(0).unwrap()).cc_value(), 0);
         
         // Binary parameters
         assert_eq!(MicrocosmParameter::Bypass(true).cc_value(), 127);
@@ -437,9 +527,9 @@ mod tests {
     // Test MicrocosmParameter names
     #[test]
     fn test_parameter_names() {
-        assert_eq!(MicrocosmParameter::Time(64).name(), "Time");
-        assert_eq!(MicrocosmParameter::Activity(64).name(), "Activity");
-        assert_eq!(MicrocosmParameter::Mix(64).name(), "Mix");
+        assert_eq!(MicrocosmParameter::Time(CcValue::new(64).unwrap()).name(), "Time");
+        assert_eq!(MicrocosmParameter::Activity(CcValue::new(64).unwrap()).name(), "Activity");
+        assert_eq!(MicrocosmParameter::Mix(CcValue::new(64).unwrap()).name(), "Mix");
         assert_eq!(MicrocosmParameter::TapTempo.name(), "Tap Tempo");
     }
     
@@ -520,4 +610,27 @@ mod tests {
             reverse
         );
     }
+    
+    #[test]
+    fn test_update_from_cc_rebuilds_recalled_state() {
+        let source = MicrocosmState {
+            time: 12,
+            activity: 99,
+            shape: WaveformShape::Saw,
+            subdivision: SubdivisionValue::Octuple,
+            looper_enabled: true,
+            routing: LooperRouting::PreFX,
+            bypass: true,
+            ..MicrocosmState::default()
+        };
+        
+        let mut rebuilt = MicrocosmState::default();
+        for (cc, value) in source.to_cc_map() {
+            rebuilt.update_from_cc(cc, value);
+        }
+        // Triggers don't touch state
+        rebuilt.update_from_cc(28, 127);
+        
+        assert_eq!(serde_json::to_value(&rebuilt).unwrap(), serde_json::to_value(&source).unwrap());
+    }
 }
@@ -3,10 +3,14 @@
 
 mod types;
 mod mapper;
+mod looper;
+pub mod bank_dump;
 pub mod commands;
 
 // Re-export public types
 pub use types::*;
+pub use looper::LOOPER_TRANSITIONS;
+pub use mapper::GLIDE_CCS;
 
 /// Hologram Microcosm pedal with complete MIDI control
 /// This is the aggregate root for the Microcosm domain
@@ -14,6 +18,11 @@ pub use types::*;
 pub struct Microcosm {
     pub state: MicrocosmState,
     pub midi_channel: u8,
+    /// Last program change sent, if any (effect programs or user banks)
+    pub program: Option<u8>,
+    /// Believed looper transport - the pedal doesn't echo it, and it's live pedal state
+    /// rather than part of a preset
+    pub looper_transport: LooperTransport,
 }
 
 impl Microcosm {
@@ -22,11 +31,21 @@ impl Microcosm {
         Self {
             state: MicrocosmState::default(),
             midi_channel,
+            program: None,
+            looper_transport: LooperTransport::Empty,
         }
     }
     
+    /// Program the pedal is believed to be on: the last one sent, or else the
+    /// program for the current effect and variation
+    pub fn current_program(&self) -> u8 {
+        self.program
+            .unwrap_or_else(|| self.state.current_effect.program_number(self.state.current_variation))
+    }
+    
     /// Set the current preset from a program number
     pub fn set_current_preset(&mut self, program: u8) {
+        self.program = Some(program);
         if let Some((effect, variation)) = EffectType::from_program(program) {
             self.state.current_effect = effect;
             self.state.current_variation = variation;
@@ -37,33 +56,41 @@ impl Microcosm {
     pub fn update_state(&mut self, param: &MicrocosmParameter) {
         match param {
             MicrocosmParameter::Subdivision(v) => self.state.subdivision = *v,
-            MicrocosmParameter::Time(v) => self.state.time = *v,
+            MicrocosmParameter::Time(v) => self.state.time = v.get(),
             MicrocosmParameter::HoldSampler(v) => self.state.hold_sampler = *v,
-            MicrocosmParameter::Activity(v) => self.state.activity = *v,
-            MicrocosmParameter::Repeats(v) => self.state.repeats = *v,
+            MicrocosmParameter::Activity(v) => self.state.activity = v.get(),
+            MicrocosmParameter::Repeats(v) => self.state.repeats = v.get(),
             MicrocosmParameter::Shape(v) => self.state.shape = *v,
-            MicrocosmParameter::Frequency(v) => self.state.frequency = *v,
-            MicrocosmParameter::Depth(v) => self.state.depth = *v,
-            MicrocosmParameter::Cutoff(v) => self.state.cutoff = *v,
-            MicrocosmParameter::Resonance(v) => self.state.resonance = *v,
-            MicrocosmParameter::Mix(v) => self.state.mix = *v,
-            MicrocosmParameter::Volume(v) => self.state.volume = *v,
+            MicrocosmParameter::Frequency(v) => self.state.frequency = v.get(),
+            MicrocosmParameter::Depth(v) => self.state.depth = v.get(),
+            MicrocosmParameter::Cutoff(v) => self.state.cutoff = v.get(),
+            MicrocosmParameter::Resonance(v) => self.state.resonance = v.get(),
+            MicrocosmParameter::Mix(v) => self.state.mix = v.get(),
+            MicrocosmParameter::Volume(v) => self.state.volume = v.get(),
             MicrocosmParameter::ReverseEffect(v) => self.state.reverse_effect = *v,
             MicrocosmParameter::Bypass(v) => self.state.bypass = *v,
-            MicrocosmParameter::Space(v) => self.state.space = *v,
-            MicrocosmParameter::ReverbTime(v) => self.state.reverb_time = *v,
-            MicrocosmParameter::LoopLevel(v) => self.state.loop_level = *v,
-            MicrocosmParameter::LooperSpeed(v) => self.state.looper_speed = *v,
+            MicrocosmParameter::Space(v) => self.state.space = v.get(),
+            MicrocosmParameter::ReverbTime(v) => self.state.reverb_time = v.get(),
+            MicrocosmParameter::LoopLevel(v) => self.state.loop_level = v.get(),
+            MicrocosmParameter::LooperSpeed(v) => self.state.looper_speed = v.get(),
             MicrocosmParameter::LooperSpeedStepped(v) => self.state.looper_speed_stepped = *v,
-            MicrocosmParameter::FadeTime(v) => self.state.fade_time = *v,
+            MicrocosmParameter::FadeTime(v) => self.state.fade_time = v.get(),
             MicrocosmParameter::LooperEnabled(v) => self.state.looper_enabled = *v,
             MicrocosmParameter::PlaybackDirection(v) => self.state.playback_direction = *v,
             MicrocosmParameter::Routing(v) => self.state.routing = *v,
             MicrocosmParameter::LooperOnly(v) => self.state.looper_only = *v,
             MicrocosmParameter::BurstMode(v) => self.state.burst_mode = *v,
             MicrocosmParameter::Quantized(v) => self.state.quantized = *v,
-            // Trigger actions don't update state
-            _ => {}
+            MicrocosmParameter::EffectSelect(effect, variation) => {
+                self.set_current_preset(effect.program_number(*variation))
+            }
+            // Looper transport triggers drive the believed looper state,
+            // other trigger actions don't update state
+            _ => {
+                if let Some(trigger) = LooperTrigger::from_parameter(param) {
+                    self.looper_transport = self.looper_transport.apply(trigger);
+                }
+            }
         }
     }
     
@@ -72,6 +99,11 @@ impl Microcosm {
     pub fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
         self.state.to_cc_map()
     }
+
+    /// Get the current state as CCs in the order a recall should send them
+    pub fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.state.recall_plan()
+    }
 }
 
 // Implement PedalCapabilities trait for compile-time enforcement
@@ -107,6 +139,10 @@ impl super::PedalCapabilities for Microcosm {
     fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
         self.state_as_cc_map()
     }
+
+    fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.recall_plan()
+    }
     
     fn load_preset(&mut self, program: u8) {
         self.set_current_preset(program);
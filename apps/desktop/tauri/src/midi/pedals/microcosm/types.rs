@@ -1,5 +1,7 @@
 // Microcosm domain types - enums, structs, and value objects
 
+use crate::midi::CcValue;
+use crate::midi::pedals::knob_position;
 use serde::{Deserialize, Serialize};
 
 /// Complete state of all Microcosm parameters
@@ -55,6 +57,42 @@ pub struct MicrocosmState {
     pub quantized: bool,
 }
 
+/// Positions of the Microcosm's front-panel knobs, each 0.0-1.0.
+/// Hidden options (reverb time, fade, looper speed) have no knob of their own.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct KnobPositions {
+    pub time: f32,
+    pub activity: f32,
+    pub repeats: f32,
+    pub frequency: f32,
+    pub depth: f32,
+    pub cutoff: f32,
+    pub resonance: f32,
+    pub mix: f32,
+    pub volume: f32,
+    pub space: f32,
+    pub loop_level: f32,
+}
+
+impl MicrocosmState {
+    /// Knob positions for drawing the pedal's face
+    pub fn to_knob_positions(&self) -> KnobPositions {
+        KnobPositions {
+            time: knob_position(self.time),
+            activity: knob_position(self.activity),
+            repeats: knob_position(self.repeats),
+            frequency: knob_position(self.frequency),
+            depth: knob_position(self.depth),
+            cutoff: knob_position(self.cutoff),
+            resonance: knob_position(self.resonance),
+            mix: knob_position(self.mix),
+            volume: knob_position(self.volume),
+            space: knob_position(self.space),
+            loop_level: knob_position(self.loop_level),
+        }
+    }
+}
+
 impl Default for MicrocosmState {
     fn default() -> Self {
         Self {
@@ -129,6 +167,30 @@ pub enum LooperRouting {
     PreFX,   // 64-127
 }
 
+/// Looper transport status as tracked by the app.
+/// This is *believed* state derived from the transport triggers we send -
+/// the pedal never echoes its looper status back over MIDI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LooperTransport {
+    #[default]
+    Empty,
+    Recording,
+    Playing,
+    Overdubbing,
+    Stopped,
+}
+
+/// Looper transport triggers (CC 28-31, 34, 35)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LooperTrigger {
+    Record,
+    Play,
+    Overdub,
+    Stop,
+    Erase,
+    Undo,
+}
+
 /// Microcosm effect categories (in pedal order)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EffectCategory {
@@ -172,38 +234,38 @@ pub enum EffectVariation {
 pub enum MicrocosmParameter {
     // Time
     Subdivision(SubdivisionValue),
-    Time(u8),
+    Time(CcValue),
     HoldSampler(bool),
     TapTempo,  // Trigger only
     
     // Special Sauce
-    Activity(u8),
-    Repeats(u8),
+    Activity(CcValue),
+    Repeats(CcValue),
     
     // Modulation
     Shape(WaveformShape),
-    Frequency(u8),
-    Depth(u8),
+    Frequency(CcValue),
+    Depth(CcValue),
     
     // Filter
-    Cutoff(u8),
-    Resonance(u8),
+    Cutoff(CcValue),
+    Resonance(CcValue),
     
     // Effect
-    Mix(u8),
-    Volume(u8),
+    Mix(CcValue),
+    Volume(CcValue),
     ReverseEffect(bool),  // Binary: 0-63 = Forward, 64-127 = Reverse
     Bypass(bool),
     
     // Reverb
-    Space(u8),
-    ReverbTime(u8),
+    Space(CcValue),
+    ReverbTime(CcValue),
     
     // Looper
-    LoopLevel(u8),
-    LooperSpeed(u8),
+    LoopLevel(CcValue),
+    LooperSpeed(CcValue),
     LooperSpeedStepped(SubdivisionValue),
-    FadeTime(u8),
+    FadeTime(CcValue),
     LooperEnabled(bool),
     PlaybackDirection(PlaybackDirection),
     Routing(LooperRouting),
@@ -222,6 +284,7 @@ pub enum MicrocosmParameter {
     // Preset
     PresetCopy,
     PresetSave,
+    EffectSelect(EffectType, EffectVariation),  // Sent as a program change, not a CC
 }
 
 // ============================================================================
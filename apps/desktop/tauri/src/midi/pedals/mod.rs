@@ -12,6 +12,10 @@ pub mod brothers_am;
 pub mod reverse_mode_c;
 pub mod clean;
 pub mod onward;
+pub mod thermae;
+pub mod dark_world;
+pub mod habit;
+pub mod source_audio_collider;
 
 pub use microcosm::Microcosm;
 pub use gen_loss_mkii::GenLossMkii;
@@ -25,7 +29,14 @@ pub use brothers_am::BrothersAm;
 pub use reverse_mode_c::ReverseModeC;
 pub use clean::Clean;
 pub use onward::Onward;
+pub use thermae::Thermae;
+pub use dark_world::DarkWorld;
+pub use habit::Habit;
+pub use source_audio_collider::Collider;
 
+use crate::midi::manager::PedalType;
+use crate::midi::send_plan::SendPlan;
+use serde::Serialize;
 use std::collections::HashMap;
 
 /// Metadata describing a pedal's capabilities
@@ -37,6 +48,52 @@ pub struct PedalMetadata {
     pub supports_preset_library: bool,
 }
 
+/// Chase Bliss DIP switch CCs: left bank 61-68, right bank 71-78
+pub(crate) const DIP_SWITCH_CCS: &[u8] = &[61, 62, 63, 64, 65, 66, 67, 68, 71, 72, 73, 74, 75, 76, 77, 78];
+
+/// Labels for an arcade button's positions, as printed in the pedal manual
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ModeNames {
+    /// State field the button sets (e.g. "jump")
+    pub parameter: &'static str,
+    /// One label per position, in CC value order (1, 2, 3)
+    pub names: Vec<&'static str>,
+}
+
+impl ModeNames {
+    /// Labels for a three-position arcade button, looked up by CC value
+    pub fn new(parameter: &'static str, name_for_cc: impl Fn(u8) -> &'static str) -> Self {
+        Self {
+            parameter,
+            names: (1..=3).map(name_for_cc).collect(),
+        }
+    }
+}
+
+/// A 0-127 CC value as a knob or fader position from 0.0 (fully counter-clockwise / down)
+/// to 1.0 (fully clockwise / up), for drawing the pedal's controls
+pub fn knob_position(cc_value: u8) -> f32 {
+    f32::from(cc_value.min(127)) / 127.0
+}
+
+/// Arcade button labels for a pedal type; empty for pedals without multi-position selectors
+pub fn mode_names(pedal_type: &PedalType) -> Vec<ModeNames> {
+    match pedal_type {
+        PedalType::PreampMk2 => preamp_mk2::PreampMk2State::mode_names(),
+        PedalType::Cxm1978 => cxm1978::Cxm1978State::mode_names(),
+        _ => Vec::new(),
+    }
+}
+
+/// State fields whose meaning changes with other settings (see e.g.
+/// `ChromaConsoleState::effective_semantics`); empty when every knob always means the same thing
+pub fn context_dependent_parameters(pedal_type: &PedalType) -> &'static [&'static str] {
+    match pedal_type {
+        PedalType::ChromaConsole => chroma_console::CONTEXT_DEPENDENT_PARAMETERS,
+        _ => &[],
+    }
+}
+
 /// Trait that all pedal implementations must implement
 /// This enforces a consistent interface across all supported pedals
 pub trait PedalCapabilities {
@@ -65,6 +122,10 @@ pub trait PedalCapabilities {
     /// Used for recalling presets (sending all parameters at once)
     fn state_as_cc_map(&self) -> HashMap<u8, u8>;
     
+    /// Current state as CCs in send order: routing first, selections next, continuous
+    /// values last. Recalls send this rather than the unordered map.
+    fn recall_plan(&self) -> SendPlan;
+    
     /// Load a preset by program change number (if supported)
     fn load_preset(&mut self, program: u8) {
         let _ = program; // Default: no-op
@@ -113,5 +174,120 @@ mod tests {
         let rmc = ReverseModeC::new(7);
         let _metadata = rmc.metadata();
         let _supports_pc = rmc.supports_program_change();
+
+        let thermae = Thermae::new(8);
+        let _metadata = thermae.metadata();
+        let _supports_pc = thermae.supports_program_change();
+
+        let dark_world = DarkWorld::new(9);
+        let _metadata = dark_world.metadata();
+        let _supports_pc = dark_world.supports_program_change();
+
+        let habit = Habit::new(11);
+        let _metadata = habit.metadata();
+        let _supports_pc = habit.supports_program_change();
+
+        let collider = Collider::new(10);
+        let _metadata = collider.metadata();
+        let _supports_pc = collider.supports_program_change();
+    }
+
+    // Recall plan snapshots for each pedal's default state: routing, then selections,
+    // then continuous values. A change here changes what the hardware hears on recall.
+
+    #[test]
+    fn recall_plan_order_hologram() {
+        assert_eq!(
+            microcosm::MicrocosmState::default().recall_plan().cc_numbers(),
+            vec![102, 24, 25, 5, 7, 18, 22, 23, 26, 27, 47, 48, 6, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 19, 20, 21]
+        );
+        assert_eq!(
+            chroma_console::ChromaConsoleState::default()
+                .recall_plan(chroma_console::BypassMode::Standard)
+                .cc_numbers(),
+            vec![91, 103, 104, 105, 106, 83, 16, 17, 18, 19, 84, 94, 64, 65, 66, 67, 68, 69, 70, 71, 72, 73, 74, 75, 76, 77, 78, 79]
+        );
+    }
+
+    #[test]
+    fn recall_plan_order_arcade_button_pedals() {
+        assert_eq!(
+            preamp_mk2::PreampMk2State::default().recall_plan().cc_numbers(),
+            vec![23, 22, 24, 25, 26, 14, 15, 16, 17, 18, 19]
+        );
+        assert_eq!(
+            cxm1978::Cxm1978State::default().recall_plan().cc_numbers(),
+            vec![22, 23, 24, 25, 26, 14, 15, 16, 17, 18, 19]
+        );
+    }
+
+    #[test]
+    fn recall_plan_order_chase_bliss() {
+        assert_eq!(
+            gen_loss_mkii::GenLossMkiiState::default().recall_plan().cc_numbers(),
+            vec![102, 26, 16, 21, 22, 23, 32, 103, 104, 105, 106, 107, 52, 61, 62, 63, 64, 65, 66, 67, 68, 71, 72, 73, 74, 75, 76, 77, 78, 14, 15, 17, 18, 19, 20, 24, 27, 28, 29, 100]
+        );
+        assert_eq!(
+            mood_mkii::MoodMkiiState::default().recall_plan().cc_numbers(),
+            vec![102, 103, 21, 22, 23, 31, 32, 33, 104, 105, 106, 51, 52, 61, 62, 63, 64, 65, 66, 67, 68, 71, 72, 73, 74, 75, 76, 77, 78, 14, 15, 16, 17, 18, 19, 20, 24, 25, 26, 27, 28, 29, 100]
+        );
+        assert_eq!(
+            billy_strings_wombtone::BillyStringsWombtoneState::default().recall_plan().cc_numbers(),
+            vec![102, 21, 51, 14, 15, 16, 17, 18, 19, 20, 100]
+        );
+        assert_eq!(
+            lossy::LossyState::default().recall_plan().cc_numbers(),
+            vec![102, 57, 21, 22, 23, 33, 103, 104, 105, 106, 52, 61, 62, 63, 64, 65, 66, 67, 68, 71, 72, 73, 74, 75, 76, 77, 78, 14, 15, 16, 17, 18, 19, 20, 24, 25, 26, 27, 28, 29, 100]
+        );
+        assert_eq!(
+            brothers_am::BrothersAmState::default().recall_plan().cc_numbers(),
+            vec![102, 103, 21, 22, 23, 61, 62, 63, 64, 65, 66, 67, 68, 71, 72, 73, 74, 75, 76, 77, 14, 15, 16, 17, 18, 19, 27, 29, 100]
+        );
+        assert_eq!(
+            reverse_mode_c::ReverseModeCState::default().recall_plan().cc_numbers(),
+            vec![102, 57, 21, 22, 23, 31, 33, 104, 105, 106, 51, 52, 61, 62, 63, 64, 65, 66, 67, 68, 71, 72, 73, 74, 75, 76, 77, 78, 14, 15, 16, 17, 18, 19, 20, 24, 25, 27, 28, 100]
+        );
+        assert_eq!(
+            clean::CleanState::default().recall_plan().cc_numbers(),
+            vec![102, 33, 21, 22, 23, 31, 103, 104, 105, 106, 52, 61, 62, 63, 64, 65, 66, 67, 68, 71, 72, 73, 74, 75, 76, 77, 78, 14, 15, 16, 17, 18, 19, 20, 24, 25, 26, 27, 28, 29, 32, 100]
+        );
+        assert_eq!(
+            onward::OnwardState::default().recall_plan().cc_numbers(),
+            vec![102, 103, 31, 32, 33, 57, 21, 22, 23, 104, 105, 106, 51, 52, 58, 61, 62, 63, 64, 65, 66, 67, 68, 71, 72, 73, 74, 75, 76, 77, 78, 14, 15, 16, 17, 18, 19, 20, 24, 25, 26, 27, 28, 29, 100]
+        );
+        assert_eq!(
+            thermae::ThermaeState::default().recall_plan().cc_numbers(),
+            vec![102, 21, 22, 23, 103, 104, 52, 61, 62, 63, 64, 65, 66, 67, 68, 71, 72, 73, 74, 75, 76, 77, 78, 14, 15, 16, 17, 18, 19, 20, 24, 25, 26, 100]
+        );
+        assert_eq!(
+            dark_world::DarkWorldState::default().recall_plan().cc_numbers(),
+            vec![102, 23, 21, 22, 103, 104, 14, 15, 16, 17, 24, 25, 26, 27, 100]
+        );
+        assert_eq!(
+            habit::HabitState::default().recall_plan().cc_numbers(),
+            vec![102, 21, 22, 23, 103, 104, 105, 106, 51, 52, 61, 62, 63, 64, 65, 66, 67, 68, 71, 72, 73, 74, 75, 76, 77, 78, 14, 15, 16, 17, 18, 19, 20, 100]
+        );
+    }
+
+    #[test]
+    fn recall_plan_order_source_audio() {
+        // Delay times follow every CC as NRPN writes (99, 98, 6, 38 per engine)
+        assert_eq!(
+            source_audio_collider::ColliderState::default().recall_plan().cc_numbers(),
+            vec![102, 24, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 100, 99, 98, 6, 38, 99, 98, 6, 38]
+        );
+    }
+
+    #[test]
+    fn recall_plans_never_send_triggers() {
+        let tapped = billy_strings_wombtone::BillyStringsWombtoneState { tap: true, ..Default::default() };
+        assert!(!tapped.recall_plan().cc_numbers().contains(&93));
+
+        let chroma = chroma_console::ChromaConsoleState::default().recall_plan(chroma_console::BypassMode::Standard);
+        for trigger in [80, 81, 82, 93, 95] {
+            assert!(!chroma.cc_numbers().contains(&trigger));
+        }
+
+        assert!(!habit::HabitState::default().recall_plan().cc_numbers().contains(&habit::CC_CLEAR_MEMORY));
     }
 }
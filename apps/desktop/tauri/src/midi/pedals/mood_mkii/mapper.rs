@@ -1,6 +1,8 @@
 // Mood MkII MIDI CC mapping - infrastructure layer
 
 use super::types::{MoodMkiiParameter, MoodMkiiState};
+use crate::midi::pedals::DIP_SWITCH_CCS;
+use crate::midi::send_plan::SendPlan;
 use std::collections::HashMap;
 
 pub const CC_PRESET_SAVE: u8 = 111;
@@ -87,7 +89,7 @@ impl MoodMkiiParameter {
             | Self::LevelBalance(v)
             | Self::DirectMicroLoop(v)
             | Self::Expression(v)
-            | Self::PresetSave(v) => *v,
+            | Self::PresetSave(v) => v.get(),
 
             // Three-position enums
             Self::WetChannelRouting(v) => v.to_cc_value(),
@@ -244,4 +246,10 @@ impl MoodMkiiState {
 
         map
     }
+
+    /// Recall order: bypasses and routing first, then toggles, footswitch modes and DIP switches, then knobs
+    pub fn recall_plan(&self) -> SendPlan {
+        let selections = [&[23, 31, 32, 33, 104, 105, 106, 51, 52][..], DIP_SWITCH_CCS].concat();
+        SendPlan::grouped(self.to_cc_map(), &[102, 103, 21, 22], &selections, &[])
+    }
 }
@@ -27,22 +27,22 @@ impl MoodMkii {
     /// Update internal state from a parameter change
     pub fn update_state(&mut self, param: &MoodMkiiParameter) {
         match param {
-            MoodMkiiParameter::Time(v) => self.state.time = *v,
-            MoodMkiiParameter::Mix(v) => self.state.mix = *v,
-            MoodMkiiParameter::Length(v) => self.state.length = *v,
-            MoodMkiiParameter::ModifyWet(v) => self.state.modify_wet = *v,
-            MoodMkiiParameter::Clock(v) => self.state.clock = *v,
-            MoodMkiiParameter::ModifyLooper(v) => self.state.modify_looper = *v,
-            MoodMkiiParameter::RampSpeed(v) => self.state.ramp_speed = *v,
+            MoodMkiiParameter::Time(v) => self.state.time = v.get(),
+            MoodMkiiParameter::Mix(v) => self.state.mix = v.get(),
+            MoodMkiiParameter::Length(v) => self.state.length = v.get(),
+            MoodMkiiParameter::ModifyWet(v) => self.state.modify_wet = v.get(),
+            MoodMkiiParameter::Clock(v) => self.state.clock = v.get(),
+            MoodMkiiParameter::ModifyLooper(v) => self.state.modify_looper = v.get(),
+            MoodMkiiParameter::RampSpeed(v) => self.state.ramp_speed = v.get(),
             MoodMkiiParameter::WetChannelRouting(v) => self.state.wet_channel_routing = *v,
             MoodMkiiParameter::Routing(v) => self.state.routing = *v,
             MoodMkiiParameter::MicroLooper(v) => self.state.micro_looper = *v,
-            MoodMkiiParameter::StereoWidth(v) => self.state.stereo_width = *v,
-            MoodMkiiParameter::RampingWaveform(v) => self.state.ramping_waveform = *v,
-            MoodMkiiParameter::Fade(v) => self.state.fade = *v,
-            MoodMkiiParameter::Tone(v) => self.state.tone = *v,
-            MoodMkiiParameter::LevelBalance(v) => self.state.level_balance = *v,
-            MoodMkiiParameter::DirectMicroLoop(v) => self.state.direct_micro_loop = *v,
+            MoodMkiiParameter::StereoWidth(v) => self.state.stereo_width = v.get(),
+            MoodMkiiParameter::RampingWaveform(v) => self.state.ramping_waveform = v.get(),
+            MoodMkiiParameter::Fade(v) => self.state.fade = v.get(),
+            MoodMkiiParameter::Tone(v) => self.state.tone = v.get(),
+            MoodMkiiParameter::LevelBalance(v) => self.state.level_balance = v.get(),
+            MoodMkiiParameter::DirectMicroLoop(v) => self.state.direct_micro_loop = v.get(),
             MoodMkiiParameter::Sync(v) => self.state.sync = *v,
             MoodMkiiParameter::Spread(v) => self.state.spread = *v,
             MoodMkiiParameter::BufferLength(v) => self.state.buffer_length = *v,
@@ -69,7 +69,7 @@ impl MoodMkii {
             MoodMkiiParameter::DipSmooth(v) => self.state.dip_smooth = *v,
             MoodMkiiParameter::MidiClockIgnore(v) => self.state.midi_clock_ignore = *v,
             MoodMkiiParameter::RampBounce(v) => self.state.ramp_bounce = *v,
-            MoodMkiiParameter::Expression(v) => self.state.expression = *v,
+            MoodMkiiParameter::Expression(v) => self.state.expression = v.get(),
             MoodMkiiParameter::PresetSave(_) => {} // Does not update state
         }
     }
@@ -78,6 +78,11 @@ impl MoodMkii {
     pub fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
         self.state.to_cc_map()
     }
+
+    /// Get the current state as CCs in the order a recall should send them
+    pub fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.state.recall_plan()
+    }
 }
 
 impl super::PedalCapabilities for MoodMkii {
@@ -112,4 +117,8 @@ impl super::PedalCapabilities for MoodMkii {
     fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
         self.state_as_cc_map()
     }
+
+    fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.recall_plan()
+    }
 }
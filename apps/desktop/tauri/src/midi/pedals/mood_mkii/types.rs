@@ -1,5 +1,6 @@
 // Mood MkII domain types
 
+use crate::midi::CcValue;
 use crate::midi::error::{MidiError, MidiResult};
 use serde::{Deserialize, Serialize};
 
@@ -118,13 +119,13 @@ impl Default for MoodMkiiState {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum MoodMkiiParameter {
     // Main knobs
-    Time(u8),
-    Mix(u8),
-    Length(u8),
-    ModifyWet(u8),
-    Clock(u8),
-    ModifyLooper(u8),
-    RampSpeed(u8),
+    Time(CcValue),
+    Mix(CcValue),
+    Length(CcValue),
+    ModifyWet(CcValue),
+    Clock(CcValue),
+    ModifyLooper(CcValue),
+    RampSpeed(CcValue),
 
     // Three-position toggles
     WetChannelRouting(WetChannelRouting),
@@ -132,12 +133,12 @@ pub enum MoodMkiiParameter {
     MicroLooper(MicroLooper),
 
     // Hidden options
-    StereoWidth(u8),
-    RampingWaveform(u8),
-    Fade(u8),
-    Tone(u8),
-    LevelBalance(u8),
-    DirectMicroLoop(u8),
+    StereoWidth(CcValue),
+    RampingWaveform(CcValue),
+    Fade(CcValue),
+    Tone(CcValue),
+    LevelBalance(CcValue),
+    DirectMicroLoop(CcValue),
     Sync(MoodSync),
     Spread(MoodSpread),
     BufferLength(bool),
@@ -172,8 +173,8 @@ pub enum MoodMkiiParameter {
     // Utility
     MidiClockIgnore(bool),
     RampBounce(bool),
-    Expression(u8),
-    PresetSave(u8),
+    Expression(CcValue),
+    PresetSave(CcValue),
 }
 
 /// Wet channel routing toggle (CC 21)
@@ -200,7 +201,7 @@ impl WetChannelRouting {
             3 => Ok(Self::Slip),
             _ => Err(MidiError::InvalidValue {
                 expected: "1-3".to_string(),
-                actual: value,
+                actual: value.into(),
             }),
         }
     }
@@ -230,7 +231,7 @@ impl MoodRouting {
             3 => Ok(Self::Env),
             _ => Err(MidiError::InvalidValue {
                 expected: "1-3".to_string(),
-                actual: value,
+                actual: value.into(),
             }),
         }
     }
@@ -260,7 +261,7 @@ impl MicroLooper {
             3 => Ok(Self::Stretch),
             _ => Err(MidiError::InvalidValue {
                 expected: "1-3".to_string(),
-                actual: value,
+                actual: value.into(),
             }),
         }
     }
@@ -290,7 +291,7 @@ impl MoodSync {
             3 => Ok(Self::Auto),
             _ => Err(MidiError::InvalidValue {
                 expected: "1-3".to_string(),
-                actual: value,
+                actual: value.into(),
             }),
         }
     }
@@ -320,7 +321,7 @@ impl MoodSpread {
             3 => Ok(Self::OnlyAlt),
             _ => Err(MidiError::InvalidValue {
                 expected: "1-3".to_string(),
-                actual: value,
+                actual: value.into(),
             }),
         }
     }
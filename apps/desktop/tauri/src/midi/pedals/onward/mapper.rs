@@ -1,6 +1,8 @@
 // Chase Bliss Audio Onward MIDI CC mapping - infrastructure layer
 
 use super::types::{OnwardParameter, OnwardState};
+use crate::midi::pedals::DIP_SWITCH_CCS;
+use crate::midi::send_plan::SendPlan;
 use std::collections::HashMap;
 
 pub const CC_PRESET_SAVE: u8 = 111;
@@ -84,7 +86,7 @@ impl OnwardParameter {
             | OnwardParameter::UserFade(v)
             | OnwardParameter::Filter(v)
             | OnwardParameter::Expression(v)
-            | OnwardParameter::PresetSave(v) => *v,
+            | OnwardParameter::PresetSave(v) => v.get(),
 
             // Enum parameters
             OnwardParameter::ErrorType(m) => m.to_cc_value(),
@@ -251,4 +253,11 @@ impl OnwardState {
 
         map
     }
+
+    /// Recall order: bypasses and routing first, then toggles, footswitch modes and DIP switches, then knobs
+    /// Retriggers are never recalled.
+    pub fn recall_plan(&self) -> SendPlan {
+        let selections = [&[21, 22, 23, 104, 105, 106, 51, 52, 58][..], DIP_SWITCH_CCS].concat();
+        SendPlan::grouped(self.to_cc_map(), &[102, 103, 31, 32, 33, 57], &selections, &[108, 109])
+    }
 }
@@ -28,22 +28,22 @@ impl Onward {
     /// Update internal state from a parameter change
     pub fn update_state(&mut self, param: &OnwardParameter) {
         match param {
-            OnwardParameter::Size(v) => self.state.size = *v,
-            OnwardParameter::Mix(v) => self.state.mix = *v,
-            OnwardParameter::Octave(v) => self.state.octave = *v,
-            OnwardParameter::Error(v) => self.state.error = *v,
-            OnwardParameter::Sustain(v) => self.state.sustain = *v,
-            OnwardParameter::Texture(v) => self.state.texture = *v,
-            OnwardParameter::RampSpeed(v) => self.state.ramp_speed = *v,
+            OnwardParameter::Size(v) => self.state.size = v.get(),
+            OnwardParameter::Mix(v) => self.state.mix = v.get(),
+            OnwardParameter::Octave(v) => self.state.octave = v.get(),
+            OnwardParameter::Error(v) => self.state.error = v.get(),
+            OnwardParameter::Sustain(v) => self.state.sustain = v.get(),
+            OnwardParameter::Texture(v) => self.state.texture = v.get(),
+            OnwardParameter::RampSpeed(v) => self.state.ramp_speed = v.get(),
             OnwardParameter::ErrorType(v) => self.state.error_type = *v,
             OnwardParameter::FadeMode(v) => self.state.fade_mode = *v,
             OnwardParameter::AnimateMode(v) => self.state.animate_mode = *v,
-            OnwardParameter::Sensitivity(v) => self.state.sensitivity = *v,
-            OnwardParameter::Balance(v) => self.state.balance = *v,
-            OnwardParameter::DuckDepth(v) => self.state.duck_depth = *v,
-            OnwardParameter::ErrorBlend(v) => self.state.error_blend = *v,
-            OnwardParameter::UserFade(v) => self.state.user_fade = *v,
-            OnwardParameter::Filter(v) => self.state.filter = *v,
+            OnwardParameter::Sensitivity(v) => self.state.sensitivity = v.get(),
+            OnwardParameter::Balance(v) => self.state.balance = v.get(),
+            OnwardParameter::DuckDepth(v) => self.state.duck_depth = v.get(),
+            OnwardParameter::ErrorBlend(v) => self.state.error_blend = v.get(),
+            OnwardParameter::UserFade(v) => self.state.user_fade = v.get(),
+            OnwardParameter::Filter(v) => self.state.filter = v.get(),
             OnwardParameter::ErrorRouting(v) => self.state.error_routing = *v,
             OnwardParameter::SustainRouting(v) => self.state.sustain_routing = *v,
             OnwardParameter::EffectsRouting(v) => self.state.effects_routing = *v,
@@ -74,7 +74,7 @@ impl Onward {
             OnwardParameter::RampBounce(v) => self.state.ramp_bounce = *v,
             OnwardParameter::DryKill(v) => self.state.dry_kill = *v,
             OnwardParameter::Trails(v) => self.state.trails = *v,
-            OnwardParameter::Expression(v) => self.state.expression = *v,
+            OnwardParameter::Expression(v) => self.state.expression = v.get(),
             OnwardParameter::PresetSave(_) => {} // Does not update state
         }
     }
@@ -83,6 +83,11 @@ impl Onward {
     pub fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
         self.state.to_cc_map()
     }
+
+    /// Get the current state as CCs in the order a recall should send them
+    pub fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.state.recall_plan()
+    }
 }
 
 impl super::PedalCapabilities for Onward {
@@ -113,4 +118,8 @@ impl super::PedalCapabilities for Onward {
     fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
         self.state_as_cc_map()
     }
+
+    fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.recall_plan()
+    }
 }
@@ -1,5 +1,6 @@
 // Chase Bliss Audio Onward domain types
 
+use crate::midi::CcValue;
 use crate::midi::error::{MidiError, MidiResult};
 use serde::{Deserialize, Serialize};
 
@@ -132,13 +133,13 @@ impl Default for OnwardState {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum OnwardParameter {
     // Main knobs
-    Size(u8),
-    Mix(u8),
-    Octave(u8),
-    Error(u8),
-    Sustain(u8),
-    Texture(u8),
-    RampSpeed(u8),
+    Size(CcValue),
+    Mix(CcValue),
+    Octave(CcValue),
+    Error(CcValue),
+    Sustain(CcValue),
+    Texture(CcValue),
+    RampSpeed(CcValue),
 
     // Toggles
     ErrorType(ErrorType),
@@ -146,12 +147,12 @@ pub enum OnwardParameter {
     AnimateMode(AnimateMode),
 
     // Hidden options
-    Sensitivity(u8),
-    Balance(u8),
-    DuckDepth(u8),
-    ErrorBlend(u8),
-    UserFade(u8),
-    Filter(u8),
+    Sensitivity(CcValue),
+    Balance(CcValue),
+    DuckDepth(CcValue),
+    ErrorBlend(CcValue),
+    UserFade(CcValue),
+    Filter(CcValue),
     ErrorRouting(Routing),
     SustainRouting(Routing),
     EffectsRouting(Routing),
@@ -190,8 +191,8 @@ pub enum OnwardParameter {
     RampBounce(bool),
     DryKill(bool),
     Trails(bool),
-    Expression(u8),
-    PresetSave(u8),
+    Expression(CcValue),
+    PresetSave(CcValue),
 }
 
 /// Three-position error type (CC 21)
@@ -218,7 +219,7 @@ impl ErrorType {
             3 => Ok(ErrorType::Playback),
             _ => Err(MidiError::InvalidValue {
                 expected: "1-3".to_string(),
-                actual: value,
+                actual: value.into(),
             }),
         }
     }
@@ -248,7 +249,7 @@ impl FadeMode {
             3 => Ok(FadeMode::Short),
             _ => Err(MidiError::InvalidValue {
                 expected: "1-3".to_string(),
-                actual: value,
+                actual: value.into(),
             }),
         }
     }
@@ -278,7 +279,7 @@ impl AnimateMode {
             3 => Ok(AnimateMode::Chorus),
             _ => Err(MidiError::InvalidValue {
                 expected: "1-3".to_string(),
-                actual: value,
+                actual: value.into(),
             }),
         }
     }
@@ -308,7 +309,7 @@ impl Routing {
             3 => Ok(Routing::Freeze),
             _ => Err(MidiError::InvalidValue {
                 expected: "1-3".to_string(),
-                actual: value,
+                actual: value.into(),
             }),
         }
     }
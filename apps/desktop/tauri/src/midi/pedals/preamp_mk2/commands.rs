@@ -1,20 +1,31 @@
 // Tauri commands for Chase Bliss Preamp MK II pedal
 
-use crate::midi::SharedMidiManager;
+use crate::midi::{InputChannelMode, PedalType, SharedMidiManager};
+use crate::midi::connect_retry::{connect_with_retry, ConnectOutcome, RetryOptions};
+use crate::midi::safety::RecallOutcome;
 use crate::midi::pedals::preamp_mk2::{PreampMk2Parameter, PreampMk2State};
 use tauri::State;
 
-/// Connect to a Preamp MK II pedal
+/// Connect to a Preamp MK II pedal. `strict_fader_range` (default true) clamps fader
+/// CCs to the travel the motors can reach.
 #[tauri::command]
 pub async fn connect_preamp_mk2(
     manager: State<'_, SharedMidiManager>,
     device_name: String,
     midi_channel: u8,
-) -> Result<(), String> {
-    let mut manager = manager.lock().map_err(|e| e.to_string())?;
-    manager
-        .connect_preamp_mk2(&device_name, midi_channel)
-        .map_err(|e| e.to_string())
+    input_mode: Option<InputChannelMode>,
+    strict_fader_range: Option<bool>,
+    retries: Option<u32>,
+    initial_backoff_ms: Option<u64>,
+) -> Result<ConnectOutcome, String> {
+    let options = RetryOptions::from_params(retries, initial_backoff_ms);
+    connect_with_retry(manager.inner(), &device_name, "connect_preamp_mk2", options, |manager| {
+        manager.connect_with_input_mode(&device_name, input_mode.as_ref(), |manager| {
+            manager.connect_preamp_mk2(&device_name, midi_channel)
+        })?;
+        manager.set_preamp_mk2_strict_fader_range(&device_name, strict_fader_range.unwrap_or(true))
+    })
+    .map_err(|e| e.to_string())
 }
 
 /// Send a parameter change to a Preamp MK II
@@ -25,6 +36,7 @@ pub async fn send_preamp_mk2_parameter(
     param: PreampMk2Parameter,
 ) -> Result<(), String> {
     let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("send_preamp_mk2_parameter");
     manager
         .send_preamp_mk2_parameter(&device_name, param)
         .map_err(|e| e.to_string())
@@ -38,6 +50,7 @@ pub async fn send_preamp_mk2_program_change(
     program: u8,
 ) -> Result<(), String> {
     let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("send_preamp_mk2_program_change");
     manager
         .send_preamp_mk2_program_change(&device_name, program)
         .map_err(|e| e.to_string())
@@ -56,16 +69,30 @@ pub async fn get_preamp_mk2_state(
 }
 
 /// Recall a Preamp MK II preset (send all parameters)
+/// Loud parameter jumps block the recall until `acknowledge_warnings` is set.
+/// A `glide_ms` above 0 (at most 2 s) ramps `glide_ccs` to their targets over that long.
 #[tauri::command]
 pub async fn recall_preamp_mk2_preset(
     manager: State<'_, SharedMidiManager>,
     device_name: String,
     state: PreampMk2State,
-) -> Result<(), String> {
+    acknowledge_warnings: bool,
+    glide_ms: Option<u64>,
+    glide_ccs: Option<Vec<u8>>,
+) -> Result<RecallOutcome, String> {
     let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    let warnings = manager
+        .recall_safety_warnings(&device_name, &state)
+        .map_err(|e| e.to_string())?;
+    if !warnings.is_empty() && !acknowledge_warnings {
+        return Ok(RecallOutcome::blocked(warnings));
+    }
+    manager.begin_journal_operation("recall_preamp_mk2_preset");
+    let glide = PedalType::PreampMk2.glide(glide_ms, glide_ccs);
     manager
-        .recall_preamp_mk2_preset(&device_name, &state)
-        .map_err(|e| e.to_string())
+        .gliding(&device_name, glide, |manager| manager.recall_preamp_mk2_preset(&device_name, &state))
+        .map_err(|e| e.to_string())?;
+    Ok(RecallOutcome::recalled(warnings))
 }
 
 /// Save current state to a Preamp MK II preset slot (0-29)
@@ -76,7 +103,36 @@ pub async fn save_preamp_mk2_preset(
     slot: u8,
 ) -> Result<(), String> {
     let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("save_preamp_mk2_preset");
     manager
         .save_preamp_mk2_preset(&device_name, slot)
         .map_err(|e| e.to_string())
 }
+
+/// Turn Preamp MK II fader movement off or on. Fader changes made while it's off are
+/// held and sent when it's turned back on.
+#[tauri::command]
+pub async fn set_preamp_mk2_motorized_faders_enabled(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    enabled: bool,
+) -> Result<(), String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("set_preamp_mk2_motorized_faders_enabled");
+    manager
+        .set_preamp_mk2_motorized_faders_enabled(&device_name, enabled)
+        .map_err(|e| e.to_string())
+}
+
+/// Get the Preamp MK II fader positions as a compact display string
+#[tauri::command]
+pub async fn get_preamp_mk2_fader_display(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+) -> Result<String, String> {
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    manager
+        .get_preamp_mk2_state(&device_name)
+        .map(|state| state.fader_position_string())
+        .map_err(|e| e.to_string())
+}
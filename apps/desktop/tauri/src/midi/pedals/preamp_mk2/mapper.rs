@@ -1,6 +1,8 @@
 // Preamp MK II MIDI mapper - converts between domain types and MIDI CC messages
 
 use super::types::*;
+use crate::midi::CcValue;
+use crate::midi::send_plan::SendPlan;
 use std::collections::HashMap;
 
 // ============================================================================
@@ -15,6 +17,26 @@ pub const CC_FREQUENCY: u8 = 17;
 pub const CC_BASS: u8 = 18;
 pub const CC_GAIN: u8 = 19;
 
+/// Every CC that moves a motorized fader
+pub const FADER_CCS: [u8; 6] = [CC_VOLUME, CC_TREBLE, CC_MIDS, CC_FREQUENCY, CC_BASS, CC_GAIN];
+
+/// Fader travel the motors can actually reach; asking for more can stall them
+pub const FADER_MIN_REACHABLE: u8 = 2;
+pub const FADER_MAX_REACHABLE: u8 = 125;
+
+/// A fader CC value with the ends of the range (0 and 127) pulled in to the reachable
+/// travel; every other value, and every other CC, is unchanged
+pub fn reachable_fader_value(cc_number: u8, value: u8) -> u8 {
+    if !FADER_CCS.contains(&cc_number) {
+        return value;
+    }
+    match value {
+        0 => FADER_MIN_REACHABLE,
+        127 => FADER_MAX_REACHABLE,
+        other => other,
+    }
+}
+
 // Arcade buttons (1-3)
 pub const CC_JUMP: u8 = 22;
 pub const CC_MIDS_POSITION: u8 = 23;
@@ -59,6 +81,24 @@ impl PreampMk2State {
         
         map
     }
+
+    /// The state with fader values of 0 or 127 pulled in to the reachable travel
+    pub fn clamped_to_fader_range(&self) -> Self {
+        Self {
+            volume: reachable_fader_value(CC_VOLUME, self.volume),
+            treble: reachable_fader_value(CC_TREBLE, self.treble),
+            mids: reachable_fader_value(CC_MIDS, self.mids),
+            frequency: reachable_fader_value(CC_FREQUENCY, self.frequency),
+            bass: reachable_fader_value(CC_BASS, self.bass),
+            gain: reachable_fader_value(CC_GAIN, self.gain),
+            ..self.clone()
+        }
+    }
+
+    /// Recall order: mids position first, then the other arcade buttons, then faders (bypass and expression aren't recalled)
+    pub fn recall_plan(&self) -> SendPlan {
+        SendPlan::grouped(self.to_cc_map(), &[CC_MIDS_POSITION], &[CC_JUMP, CC_Q_RESONANCE, CC_DIODE_CLIPPING, CC_FUZZ_MODE], &[])
+    }
     
     /// Update state from a CC message
     pub fn update_from_cc(&mut self, cc: u8, value: u8) {
@@ -127,7 +167,7 @@ impl PreampMk2Parameter {
             PreampMk2Parameter::Frequency(v) |
             PreampMk2Parameter::Bass(v) |
             PreampMk2Parameter::Gain(v) |
-            PreampMk2Parameter::Expression(v) => *v,
+            PreampMk2Parameter::Expression(v) => v.get(),
             
             // Arcade buttons (use to_cc_value)
             PreampMk2Parameter::Jump(j) => j.to_cc_value(),
@@ -141,16 +181,39 @@ impl PreampMk2Parameter {
         }
     }
     
+    /// Whether this parameter moves a motorized fader
+    pub fn is_fader_parameter(&self) -> bool {
+        FADER_CCS.contains(&self.cc_number())
+    }
+    
+    /// The parameter with a fader value of 0 or 127 pulled in to the reachable travel
+    /// (see `reachable_fader_value`); anything else is unchanged
+    pub fn clamped_to_fader_range(&self) -> Self {
+        if !self.is_fader_parameter() {
+            return self.clone();
+        }
+        let value = CcValue::clamped(reachable_fader_value(self.cc_number(), self.cc_value()));
+        match self {
+            PreampMk2Parameter::Volume(_) => PreampMk2Parameter::Volume(value),
+            PreampMk2Parameter::Treble(_) => PreampMk2Parameter::Treble(value),
+            PreampMk2Parameter::Mids(_) => PreampMk2Parameter::Mids(value),
+            PreampMk2Parameter::Frequency(_) => PreampMk2Parameter::Frequency(value),
+            PreampMk2Parameter::Bass(_) => PreampMk2Parameter::Bass(value),
+            PreampMk2Parameter::Gain(_) => PreampMk2Parameter::Gain(value),
+            other => other.clone(),
+        }
+    }
+    
     /// Convert parameter to (CC number, value) tuple
     pub fn to_cc_message(&self) -> Option<(u8, u8)> {
         match self {
             // Faders
-            PreampMk2Parameter::Volume(v) => Some((CC_VOLUME, *v)),
-            PreampMk2Parameter::Treble(v) => Some((CC_TREBLE, *v)),
-            PreampMk2Parameter::Mids(v) => Some((CC_MIDS, *v)),
-            PreampMk2Parameter::Frequency(v) => Some((CC_FREQUENCY, *v)),
-            PreampMk2Parameter::Bass(v) => Some((CC_BASS, *v)),
-            PreampMk2Parameter::Gain(v) => Some((CC_GAIN, *v)),
+            PreampMk2Parameter::Volume(v) => Some((CC_VOLUME, v.get())),
+            PreampMk2Parameter::Treble(v) => Some((CC_TREBLE, v.get())),
+            PreampMk2Parameter::Mids(v) => Some((CC_MIDS, v.get())),
+            PreampMk2Parameter::Frequency(v) => Some((CC_FREQUENCY, v.get())),
+            PreampMk2Parameter::Bass(v) => Some((CC_BASS, v.get())),
+            PreampMk2Parameter::Gain(v) => Some((CC_GAIN, v.get())),
             
             // Arcade buttons
             PreampMk2Parameter::Jump(j) => Some((CC_JUMP, j.to_cc_value())),
@@ -160,7 +223,7 @@ impl PreampMk2Parameter {
             PreampMk2Parameter::FuzzMode(f) => Some((CC_FUZZ_MODE, f.to_cc_value())),
             
             // Other controls
-            PreampMk2Parameter::Expression(v) => Some((CC_EXPRESSION, *v)),
+            PreampMk2Parameter::Expression(v) => Some((CC_EXPRESSION, v.get())),
             PreampMk2Parameter::Bypass(b) => Some((CC_BYPASS, if *b { 0 } else { 127 })),
         }
     }
@@ -188,6 +251,29 @@ impl PreampMk2Parameter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::midi::error::MidiResult;
+
+    #[test]
+    fn test_fader_values_clamp_to_reachable_range() {
+        let volume = |v| PreampMk2Parameter::Volume(CcValue::new(v).unwrap());
+        assert!(volume(0).is_fader_parameter());
+        assert!(!PreampMk2Parameter::Expression(CcValue::new(0).unwrap()).is_fader_parameter());
+        assert!(!PreampMk2Parameter::Bypass(false).is_fader_parameter());
+
+        assert_eq!(volume(0).clamped_to_fader_range().cc_value(), 2);
+        assert_eq!(volume(127).clamped_to_fader_range().cc_value(), 125);
+        assert_eq!(volume(64).clamped_to_fader_range().cc_value(), 64);
+        // Only the ends of the range move; 1 and 126 are reachable enough
+        assert_eq!(volume(1).clamped_to_fader_range().cc_value(), 1);
+        let gain = PreampMk2Parameter::Gain(CcValue::new(126).unwrap()).clamped_to_fader_range();
+        assert_eq!(gain.to_cc_message(), Some((CC_GAIN, 126)));
+        let gain = PreampMk2Parameter::Gain(CcValue::new(127).unwrap()).clamped_to_fader_range();
+        assert_eq!(gain.to_cc_message(), Some((CC_GAIN, 125)));
+        // Only faders move; expression can still reach 0 and bypass 127
+        let expression = PreampMk2Parameter::Expression(CcValue::new(0).unwrap());
+        assert_eq!(expression.clamped_to_fader_range().cc_value(), 0);
+        assert_eq!(PreampMk2Parameter::Bypass(false).clamped_to_fader_range().cc_value(), 127);
+    }
 
     #[test]
     fn test_jump_cc_conversion() {
@@ -206,6 +292,65 @@ mod tests {
         assert_eq!(MidsPosition::from_cc_value(3), MidsPosition::Post);
     }
 
+    #[test]
+    fn test_arcade_buttons_round_trip() {
+        for jump in [Jump::Off, Jump::Zero, Jump::Five] {
+            assert_eq!(Jump::from_cc_value(jump.to_cc_value()), jump);
+        }
+        for position in [MidsPosition::Off, MidsPosition::Pre, MidsPosition::Post] {
+            assert_eq!(MidsPosition::from_cc_value(position.to_cc_value()), position);
+        }
+        for q in [QResonance::Low, QResonance::Mid, QResonance::High] {
+            assert_eq!(QResonance::from_cc_value(q.to_cc_value()), q);
+        }
+        for diode in [DiodeClipping::Off, DiodeClipping::Silicon, DiodeClipping::Germanium] {
+            assert_eq!(DiodeClipping::from_cc_value(diode.to_cc_value()), diode);
+        }
+        for fuzz in [FuzzMode::Off, FuzzMode::Open, FuzzMode::Gated] {
+            assert_eq!(FuzzMode::from_cc_value(fuzz.to_cc_value()), fuzz);
+        }
+    }
+
+    #[test]
+    fn test_out_of_range_arcade_values_fall_back() {
+        assert_eq!(Jump::from_cc_value(0), Jump::Off);
+        assert_eq!(MidsPosition::from_cc_value(64), MidsPosition::Off);
+        assert_eq!(QResonance::from_cc_value(127), QResonance::Mid);
+        assert_eq!(DiodeClipping::from_cc_value(4), DiodeClipping::Off);
+        assert_eq!(FuzzMode::from_cc_value(0), FuzzMode::Off);
+    }
+
+    /// Every arcade position's label and CC value, so UI strings can't drift from the MIDI mapping
+    #[test]
+    fn test_arcade_labels_match_cc_values() {
+        fn check<T: Copy + PartialEq + std::fmt::Debug>(
+            cases: &[(T, &str, u8)],
+            name: fn(&T) -> &'static str,
+            to_cc: fn(&T) -> u8,
+            try_from_cc: fn(u8) -> MidiResult<T>,
+        ) {
+            for (variant, label, cc) in cases {
+                assert_eq!(name(variant), *label);
+                assert_eq!(to_cc(variant), *cc);
+                assert_eq!(try_from_cc(*cc).unwrap(), *variant);
+            }
+        }
+        check(&[(Jump::Off, "Off", 1), (Jump::Zero, "Jump to 0", 2), (Jump::Five, "Jump to 5", 3)], Jump::name, Jump::to_cc_value, Jump::try_from_cc_value);
+        check(&[(MidsPosition::Off, "Off", 1), (MidsPosition::Pre, "Pre", 2), (MidsPosition::Post, "Post", 3)], MidsPosition::name, MidsPosition::to_cc_value, MidsPosition::try_from_cc_value);
+        check(&[(QResonance::Low, "Low", 1), (QResonance::Mid, "Mid", 2), (QResonance::High, "High", 3)], QResonance::name, QResonance::to_cc_value, QResonance::try_from_cc_value);
+        check(&[(DiodeClipping::Off, "Off", 1), (DiodeClipping::Silicon, "Silicon", 2), (DiodeClipping::Germanium, "Germanium", 3)], DiodeClipping::name, DiodeClipping::to_cc_value, DiodeClipping::try_from_cc_value);
+        check(&[(FuzzMode::Off, "Off", 1), (FuzzMode::Open, "Open", 2), (FuzzMode::Gated, "Gated", 3)], FuzzMode::name, FuzzMode::to_cc_value, FuzzMode::try_from_cc_value);
+    }
+
+    #[test]
+    fn test_strict_arcade_conversion_rejects_out_of_range() {
+        assert!(Jump::try_from_cc_value(0).is_err());
+        assert!(MidsPosition::try_from_cc_value(4).is_err());
+        assert!(QResonance::try_from_cc_value(127).is_err());
+        assert!(DiodeClipping::try_from_cc_value(0).is_err());
+        assert!(FuzzMode::try_from_cc_value(64).is_err());
+    }
+
     #[test]
     fn test_state_to_cc_map() {
         let state = PreampMk2State::default();
@@ -218,7 +363,7 @@ mod tests {
 
     #[test]
     fn test_parameter_to_cc_message() {
-        let param = PreampMk2Parameter::Volume(100);
+        let param = PreampMk2Parameter::Volume(CcValue::new(100).unwrap());
         assert_eq!(param.to_cc_message(), Some((CC_VOLUME, 100)));
         
         let param = PreampMk2Parameter::Jump(Jump::Five);
@@ -227,4 +372,41 @@ mod tests {
         let param = PreampMk2Parameter::Bypass(false);
         assert_eq!(param.to_cc_message(), Some((CC_BYPASS, 127)));
     }
+
+    #[test]
+    fn test_fader_position_string() {
+        let state = PreampMk2State {
+            volume: 127,
+            treble: 0,
+            mids: 64,
+            frequency: 48,
+            bass: 70,
+            gain: 102,
+            ..PreampMk2State::default()
+        };
+        assert_eq!(
+            state.fader_position_string(),
+            "Vol: 100% | Treble: 0% | Mids: 50% | Freq: 38% | Bass: 55% | Gain: 80%"
+        );
+    }
+
+    #[test]
+    fn test_knob_positions() {
+        let state = PreampMk2State { volume: 127, treble: 0, ..PreampMk2State::default() };
+        let knobs = state.to_knob_positions();
+        assert_eq!(knobs.volume, 1.0);
+        assert_eq!(knobs.treble, 0.0);
+        assert!((knobs.mids - 64.0 / 127.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_mode_names() {
+        let state = PreampMk2State { jump: Jump::Five, ..PreampMk2State::default() };
+        assert_eq!(state.jump_mode_name(), "Jump to 5");
+
+        let mode_names = PreampMk2State::mode_names();
+        assert_eq!(mode_names[0].parameter, "jump");
+        assert_eq!(mode_names[0].names, vec!["Off", "Jump to 0", "Jump to 5"]);
+        assert_eq!(mode_names[4].names, vec!["Off", "Open", "Gated"]);
+    }
 }
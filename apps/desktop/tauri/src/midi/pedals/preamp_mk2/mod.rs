@@ -7,7 +7,7 @@ pub mod commands;
 
 // Re-export public types
 pub use types::*;
-pub use mapper::CC_PRESET_SAVE;
+pub use mapper::{reachable_fader_value, CC_PRESET_SAVE, FADER_CCS, FADER_MAX_REACHABLE, FADER_MIN_REACHABLE};
 
 /// Chase Bliss Preamp MK II pedal with complete MIDI control
 /// This is the aggregate root for the Preamp MK II domain
@@ -15,6 +15,8 @@ pub use mapper::CC_PRESET_SAVE;
 pub struct PreampMk2 {
     pub state: PreampMk2State,
     pub midi_channel: u8,
+    /// Keep fader CCs within the range the motors can reach (on by default)
+    pub strict_fader_range: bool,
 }
 
 impl PreampMk2 {
@@ -23,9 +25,15 @@ impl PreampMk2 {
         Self {
             state: PreampMk2State::default(),
             midi_channel,
+            strict_fader_range: true,
         }
     }
     
+    /// Whether presets can be written to the pedal over MIDI (CC 27 with the slot number)
+    pub fn supports_preset_save_via_midi() -> bool {
+        true
+    }
+    
     /// Save current state to a preset slot (0-29)
     /// This sends CC 27 with the slot number
     /// Note: The pedal saves the current knob positions, not our state
@@ -38,12 +46,12 @@ impl PreampMk2 {
     pub fn update_state(&mut self, param: &PreampMk2Parameter) {
         match param {
             // Faders
-            PreampMk2Parameter::Volume(v) => self.state.volume = *v,
-            PreampMk2Parameter::Treble(v) => self.state.treble = *v,
-            PreampMk2Parameter::Mids(v) => self.state.mids = *v,
-            PreampMk2Parameter::Frequency(v) => self.state.frequency = *v,
-            PreampMk2Parameter::Bass(v) => self.state.bass = *v,
-            PreampMk2Parameter::Gain(v) => self.state.gain = *v,
+            PreampMk2Parameter::Volume(v) => self.state.volume = v.get(),
+            PreampMk2Parameter::Treble(v) => self.state.treble = v.get(),
+            PreampMk2Parameter::Mids(v) => self.state.mids = v.get(),
+            PreampMk2Parameter::Frequency(v) => self.state.frequency = v.get(),
+            PreampMk2Parameter::Bass(v) => self.state.bass = v.get(),
+            PreampMk2Parameter::Gain(v) => self.state.gain = v.get(),
             
             // Arcade buttons
             PreampMk2Parameter::Jump(j) => self.state.jump = *j,
@@ -53,7 +61,7 @@ impl PreampMk2 {
             PreampMk2Parameter::FuzzMode(f) => self.state.fuzz_mode = *f,
             
             // Other controls
-            PreampMk2Parameter::Expression(v) => self.state.expression = *v,
+            PreampMk2Parameter::Expression(v) => self.state.expression = v.get(),
             PreampMk2Parameter::Bypass(b) => self.state.bypass = *b,
         }
     }
@@ -63,6 +71,16 @@ impl PreampMk2 {
     pub fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
         self.state.to_cc_map()
     }
+
+    /// Get the current state as CCs in the order a recall should send them, with fader
+    /// values kept within reach under a strict fader range
+    pub fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        if self.strict_fader_range {
+            self.state.clamped_to_fader_range().recall_plan()
+        } else {
+            self.state.recall_plan()
+        }
+    }
 }
 
 // Implement PedalCapabilities trait for compile-time enforcement
@@ -98,6 +116,10 @@ impl super::PedalCapabilities for PreampMk2 {
     fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
         self.state_as_cc_map()
     }
+
+    fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.recall_plan()
+    }
     
     fn load_preset(&mut self, _program: u8) {
         // Preamp MK II doesn't support loading presets via MIDI
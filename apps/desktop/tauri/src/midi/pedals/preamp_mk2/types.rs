@@ -1,5 +1,8 @@
 // Chase Bliss Preamp MK II domain types - enums, structs, and value objects
 
+use crate::midi::CcValue;
+use crate::midi::error::{MidiError, MidiResult};
+use crate::midi::pedals::{knob_position, ModeNames};
 use serde::{Deserialize, Serialize};
 
 /// Complete state of all Preamp MK II parameters
@@ -45,6 +48,66 @@ impl Default for PreampMk2State {
     }
 }
 
+impl PreampMk2State {
+    /// Motorized fader positions as percentages, for a compact status display
+    /// e.g. "Vol: 72% | Treble: 45% | Mids: 60% | Freq: 38% | Bass: 55% | Gain: 80%"
+    pub fn fader_position_string(&self) -> String {
+        format!(
+            "Vol: {}% | Treble: {}% | Mids: {}% | Freq: {}% | Bass: {}% | Gain: {}%",
+            fader_percent(self.volume),
+            fader_percent(self.treble),
+            fader_percent(self.mids),
+            fader_percent(self.frequency),
+            fader_percent(self.bass),
+            fader_percent(self.gain),
+        )
+    }
+
+    /// Motorized fader positions for drawing the pedal's face
+    pub fn to_knob_positions(&self) -> KnobPositions {
+        KnobPositions {
+            volume: knob_position(self.volume),
+            treble: knob_position(self.treble),
+            mids: knob_position(self.mids),
+            frequency: knob_position(self.frequency),
+            bass: knob_position(self.bass),
+            gain: knob_position(self.gain),
+        }
+    }
+
+    /// Label of the current Jump position (e.g. "Jump to 5")
+    pub fn jump_mode_name(&self) -> &'static str {
+        self.jump.name()
+    }
+
+    /// Labels for every arcade button position
+    pub fn mode_names() -> Vec<ModeNames> {
+        vec![
+            ModeNames::new("jump", |value| Jump::from_cc_value(value).name()),
+            ModeNames::new("mids_position", |value| MidsPosition::from_cc_value(value).name()),
+            ModeNames::new("q_resonance", |value| QResonance::from_cc_value(value).name()),
+            ModeNames::new("diode_clipping", |value| DiodeClipping::from_cc_value(value).name()),
+            ModeNames::new("fuzz_mode", |value| FuzzMode::from_cc_value(value).name()),
+        ]
+    }
+}
+
+/// Normalize a fader CC value (0-127) to a rounded percentage
+/// Positions of the Preamp MK II's six motorized faders, each 0.0 (down) to 1.0 (up)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct KnobPositions {
+    pub volume: f32,
+    pub treble: f32,
+    pub mids: f32,
+    pub frequency: f32,
+    pub bass: f32,
+    pub gain: f32,
+}
+
+fn fader_percent(value: u8) -> u32 {
+    (u32::from(value.min(127)) * 100 + 63) / 127
+}
+
 // ============================================================================
 // Value Objects - Enums representing domain concepts
 // ============================================================================
@@ -93,12 +156,12 @@ pub enum FuzzMode {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PreampMk2Parameter {
     // Faders
-    Volume(u8),
-    Treble(u8),
-    Mids(u8),
-    Frequency(u8),
-    Bass(u8),
-    Gain(u8),
+    Volume(CcValue),
+    Treble(CcValue),
+    Mids(CcValue),
+    Frequency(CcValue),
+    Bass(CcValue),
+    Gain(CcValue),
     
     // Arcade buttons
     Jump(Jump),
@@ -108,7 +171,7 @@ pub enum PreampMk2Parameter {
     FuzzMode(FuzzMode),
     
     // Other controls
-    Expression(u8),
+    Expression(CcValue),
     Bypass(bool),
 }
 
@@ -125,15 +188,24 @@ impl Jump {
         }
     }
     
-    pub fn from_cc_value(value: u8) -> Self {
+    /// Position for a CC value, or an error outside 1-3
+    pub fn try_from_cc_value(value: u8) -> MidiResult<Self> {
         match value {
-            1 => Jump::Off,
-            2 => Jump::Zero,
-            3 => Jump::Five,
-            _ => Jump::Off, // Default to Off for out-of-range values
+            1 => Ok(Jump::Off),
+            2 => Ok(Jump::Zero),
+            3 => Ok(Jump::Five),
+            _ => Err(MidiError::InvalidValue {
+                expected: "1-3".to_string(),
+                actual: value.into(),
+            }),
         }
     }
     
+    /// Position for a CC value, falling back to Off outside 1-3 (for live input)
+    pub fn from_cc_value(value: u8) -> Self {
+        Self::try_from_cc_value(value).unwrap_or(Jump::Off)
+    }
+    
     pub fn name(&self) -> &'static str {
         match self {
             Jump::Off => "Off",
@@ -152,15 +224,24 @@ impl MidsPosition {
         }
     }
     
-    pub fn from_cc_value(value: u8) -> Self {
+    /// Position for a CC value, or an error outside 1-3
+    pub fn try_from_cc_value(value: u8) -> MidiResult<Self> {
         match value {
-            1 => MidsPosition::Off,
-            2 => MidsPosition::Pre,
-            3 => MidsPosition::Post,
-            _ => MidsPosition::Off,
+            1 => Ok(MidsPosition::Off),
+            2 => Ok(MidsPosition::Pre),
+            3 => Ok(MidsPosition::Post),
+            _ => Err(MidiError::InvalidValue {
+                expected: "1-3".to_string(),
+                actual: value.into(),
+            }),
         }
     }
     
+    /// Position for a CC value, falling back to Off outside 1-3 (for live input)
+    pub fn from_cc_value(value: u8) -> Self {
+        Self::try_from_cc_value(value).unwrap_or(MidsPosition::Off)
+    }
+    
     pub fn name(&self) -> &'static str {
         match self {
             MidsPosition::Off => "Off",
@@ -179,15 +260,24 @@ impl QResonance {
         }
     }
     
-    pub fn from_cc_value(value: u8) -> Self {
+    /// Position for a CC value, or an error outside 1-3
+    pub fn try_from_cc_value(value: u8) -> MidiResult<Self> {
         match value {
-            1 => QResonance::Low,
-            2 => QResonance::Mid,
-            3 => QResonance::High,
-            _ => QResonance::Mid,
+            1 => Ok(QResonance::Low),
+            2 => Ok(QResonance::Mid),
+            3 => Ok(QResonance::High),
+            _ => Err(MidiError::InvalidValue {
+                expected: "1-3".to_string(),
+                actual: value.into(),
+            }),
         }
     }
     
+    /// Position for a CC value, falling back to Mid outside 1-3 (for live input)
+    pub fn from_cc_value(value: u8) -> Self {
+        Self::try_from_cc_value(value).unwrap_or(QResonance::Mid)
+    }
+    
     pub fn name(&self) -> &'static str {
         match self {
             QResonance::Low => "Low",
@@ -206,15 +296,24 @@ impl DiodeClipping {
         }
     }
     
-    pub fn from_cc_value(value: u8) -> Self {
+    /// Position for a CC value, or an error outside 1-3
+    pub fn try_from_cc_value(value: u8) -> MidiResult<Self> {
         match value {
-            1 => DiodeClipping::Off,
-            2 => DiodeClipping::Silicon,
-            3 => DiodeClipping::Germanium,
-            _ => DiodeClipping::Off,
+            1 => Ok(DiodeClipping::Off),
+            2 => Ok(DiodeClipping::Silicon),
+            3 => Ok(DiodeClipping::Germanium),
+            _ => Err(MidiError::InvalidValue {
+                expected: "1-3".to_string(),
+                actual: value.into(),
+            }),
         }
     }
     
+    /// Position for a CC value, falling back to Off outside 1-3 (for live input)
+    pub fn from_cc_value(value: u8) -> Self {
+        Self::try_from_cc_value(value).unwrap_or(DiodeClipping::Off)
+    }
+    
     pub fn name(&self) -> &'static str {
         match self {
             DiodeClipping::Off => "Off",
@@ -233,15 +332,24 @@ impl FuzzMode {
         }
     }
     
-    pub fn from_cc_value(value: u8) -> Self {
+    /// Position for a CC value, or an error outside 1-3
+    pub fn try_from_cc_value(value: u8) -> MidiResult<Self> {
         match value {
-            1 => FuzzMode::Off,
-            2 => FuzzMode::Open,
-            3 => FuzzMode::Gated,
-            _ => FuzzMode::Off,
+            1 => Ok(FuzzMode::Off),
+            2 => Ok(FuzzMode::Open),
+            3 => Ok(FuzzMode::Gated),
+            _ => Err(MidiError::InvalidValue {
+                expected: "1-3".to_string(),
+                actual: value.into(),
+            }),
         }
     }
     
+    /// Position for a CC value, falling back to Off outside 1-3 (for live input)
+    pub fn from_cc_value(value: u8) -> Self {
+        Self::try_from_cc_value(value).unwrap_or(FuzzMode::Off)
+    }
+    
     pub fn name(&self) -> &'static str {
         match self {
             FuzzMode::Off => "Off",
@@ -4,6 +4,8 @@ use super::types::{
     ReverseModeCParameter, ReverseModeCState,
     sequence_spacing_to_cc,
 };
+use crate::midi::pedals::DIP_SWITCH_CCS;
+use crate::midi::send_plan::SendPlan;
 use std::collections::HashMap;
 
 pub const CC_PRESET_SAVE: u8 = 111;
@@ -86,7 +88,7 @@ impl ReverseModeCParameter {
             | ReverseModeCParameter::ModDepth(v)
             | ReverseModeCParameter::ModRate(v)
             | ReverseModeCParameter::Expression(v)
-            | ReverseModeCParameter::PresetSave(v) => *v,
+            | ReverseModeCParameter::PresetSave(v) => v.get(),
 
             // Enum parameters
             ReverseModeCParameter::ModSync(m) => m.to_cc_value(),
@@ -239,4 +241,11 @@ impl ReverseModeCState {
 
         map
     }
+
+    /// Recall order: bypass and dry kill first, then toggles, footswitch modes and DIP switches, then knobs
+    /// Tap is never recalled.
+    pub fn recall_plan(&self) -> SendPlan {
+        let selections = [&[21, 22, 23, 31, 33, 104, 105, 106, 51, 52][..], DIP_SWITCH_CCS].concat();
+        SendPlan::grouped(self.to_cc_map(), &[102, 57], &selections, &[103])
+    }
 }
@@ -27,20 +27,20 @@ impl ReverseModeC {
     /// Update internal state from a parameter change
     pub fn update_state(&mut self, param: &ReverseModeCParameter) {
         match param {
-            ReverseModeCParameter::Time(v) => self.state.time = *v,
-            ReverseModeCParameter::Mix(v) => self.state.mix = *v,
-            ReverseModeCParameter::Feedback(v) => self.state.feedback = *v,
-            ReverseModeCParameter::Offset(v) => self.state.offset = *v,
-            ReverseModeCParameter::Balance(v) => self.state.balance = *v,
-            ReverseModeCParameter::Filter(v) => self.state.filter = *v,
-            ReverseModeCParameter::RampSpeed(v) => self.state.ramp_speed = *v,
+            ReverseModeCParameter::Time(v) => self.state.time = v.get(),
+            ReverseModeCParameter::Mix(v) => self.state.mix = v.get(),
+            ReverseModeCParameter::Feedback(v) => self.state.feedback = v.get(),
+            ReverseModeCParameter::Offset(v) => self.state.offset = v.get(),
+            ReverseModeCParameter::Balance(v) => self.state.balance = v.get(),
+            ReverseModeCParameter::Filter(v) => self.state.filter = v.get(),
+            ReverseModeCParameter::RampSpeed(v) => self.state.ramp_speed = v.get(),
             ReverseModeCParameter::ModSync(v) => self.state.mod_sync = *v,
             ReverseModeCParameter::ModType(v) => self.state.mod_type = *v,
             ReverseModeCParameter::SequenceMode(v) => self.state.sequence_mode = *v,
-            ReverseModeCParameter::SequencerSubdivision(v) => self.state.sequencer_subdivision = *v,
-            ReverseModeCParameter::RampingWaveform(v) => self.state.ramping_waveform = *v,
-            ReverseModeCParameter::ModDepth(v) => self.state.mod_depth = *v,
-            ReverseModeCParameter::ModRate(v) => self.state.mod_rate = *v,
+            ReverseModeCParameter::SequencerSubdivision(v) => self.state.sequencer_subdivision = v.get(),
+            ReverseModeCParameter::RampingWaveform(v) => self.state.ramping_waveform = v.get(),
+            ReverseModeCParameter::ModDepth(v) => self.state.mod_depth = v.get(),
+            ReverseModeCParameter::ModRate(v) => self.state.mod_rate = v.get(),
             ReverseModeCParameter::OctaveType(v) => self.state.octave_type = *v,
             ReverseModeCParameter::SequenceSpacing(v) => self.state.sequence_spacing = *v,
             ReverseModeCParameter::Bypass(v) => self.state.bypass = *v,
@@ -67,7 +67,7 @@ impl ReverseModeC {
             ReverseModeCParameter::MidiClockIgnore(v) => self.state.midi_clock_ignore = *v,
             ReverseModeCParameter::RampBounce(v) => self.state.ramp_bounce = *v,
             ReverseModeCParameter::DryKill(v) => self.state.dry_kill = *v,
-            ReverseModeCParameter::Expression(v) => self.state.expression = *v,
+            ReverseModeCParameter::Expression(v) => self.state.expression = v.get(),
             ReverseModeCParameter::PresetSave(_) => {} // Does not update persistent state
         }
     }
@@ -76,6 +76,11 @@ impl ReverseModeC {
     pub fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
         self.state.to_cc_map()
     }
+
+    /// Get the current state as CCs in the order a recall should send them
+    pub fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.state.recall_plan()
+    }
 }
 
 impl super::PedalCapabilities for ReverseModeC {
@@ -110,11 +115,16 @@ impl super::PedalCapabilities for ReverseModeC {
     fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
         self.state_as_cc_map()
     }
+
+    fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.recall_plan()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::midi::CcValue;
 
     #[test]
     fn test_reverse_mode_c_new() {
@@ -130,7 +140,7 @@ mod tests {
     #[test]
     fn test_update_state() {
         let mut pedal = ReverseModeC::new(1);
-        pedal.update_state(&ReverseModeCParameter::Time(100));
+        pedal.update_state(&ReverseModeCParameter::Time(CcValue::new(100).unwrap()));
         assert_eq!(pedal.state.time, 100);
         pedal.update_state(&ReverseModeCParameter::Bypass(true));
         assert!(pedal.state.bypass);
@@ -1,5 +1,6 @@
 // Reverse Mode C domain types - enums, structs, and value objects
 
+use crate::midi::CcValue;
 use crate::midi::error::MidiResult;
 use serde::{Deserialize, Serialize};
 
@@ -120,13 +121,13 @@ impl Default for ReverseModeCState {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ReverseModeCParameter {
     // Main knobs
-    Time(u8),
-    Mix(u8),
-    Feedback(u8),
-    Offset(u8),
-    Balance(u8),
-    Filter(u8),
-    RampSpeed(u8),
+    Time(CcValue),
+    Mix(CcValue),
+    Feedback(CcValue),
+    Offset(CcValue),
+    Balance(CcValue),
+    Filter(CcValue),
+    RampSpeed(CcValue),
 
     // Toggles
     ModSync(ModSync),
@@ -134,10 +135,10 @@ pub enum ReverseModeCParameter {
     SequenceMode(SequenceMode),
 
     // Hidden options
-    SequencerSubdivision(u8),
-    RampingWaveform(u8),
-    ModDepth(u8),
-    ModRate(u8),
+    SequencerSubdivision(CcValue),
+    RampingWaveform(CcValue),
+    ModDepth(CcValue),
+    ModRate(CcValue),
     OctaveType(OctaveType),
     SequenceSpacing(bool),
 
@@ -172,8 +173,8 @@ pub enum ReverseModeCParameter {
     MidiClockIgnore(bool),
     RampBounce(bool),
     DryKill(bool),
-    Expression(u8),
-    PresetSave(u8),
+    Expression(CcValue),
+    PresetSave(CcValue),
 }
 
 /// Modulation sync mode (CC 21)
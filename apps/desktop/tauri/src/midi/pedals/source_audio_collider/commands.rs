@@ -0,0 +1,93 @@
+// Tauri commands for Source Audio Collider
+
+use crate::midi::{InputChannelMode, PedalType, SharedMidiManager};
+use crate::midi::connect_retry::{connect_with_retry, ConnectOutcome, RetryOptions};
+use crate::midi::safety::RecallOutcome;
+use crate::midi::pedals::source_audio_collider::{ColliderParameter, ColliderState};
+use tauri::State;
+
+/// Connect to a Collider pedal
+#[tauri::command]
+pub async fn connect_collider(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    midi_channel: u8,
+    input_mode: Option<InputChannelMode>,
+    retries: Option<u32>,
+    initial_backoff_ms: Option<u64>,
+) -> Result<ConnectOutcome, String> {
+    let options = RetryOptions::from_params(retries, initial_backoff_ms);
+    connect_with_retry(manager.inner(), &device_name, "connect_collider", options, |manager| {
+        manager.connect_with_input_mode(&device_name, input_mode.as_ref(), |manager| {
+            manager.connect_collider(&device_name, midi_channel)
+        })
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Send a Collider parameter change (delay times go out as NRPN)
+#[tauri::command]
+pub async fn send_collider_parameter(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    param: ColliderParameter,
+) -> Result<(), String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("send_collider_parameter");
+    manager
+        .send_collider_parameter(&device_name, param)
+        .map_err(|e| e.to_string())
+}
+
+/// Get the current Collider state
+#[tauri::command]
+pub async fn get_collider_state(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+) -> Result<ColliderState, String> {
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    manager
+        .get_collider_state(&device_name)
+        .map_err(|e| e.to_string())
+}
+
+/// Recall a Collider preset (send all parameters)
+/// Loud parameter jumps block the recall until `acknowledge_warnings` is set.
+/// A `glide_ms` above 0 (at most 2 s) ramps `glide_ccs` to their targets over that long.
+#[tauri::command]
+pub async fn recall_collider_preset(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    state: ColliderState,
+    acknowledge_warnings: bool,
+    glide_ms: Option<u64>,
+    glide_ccs: Option<Vec<u8>>,
+) -> Result<RecallOutcome, String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    let warnings = manager
+        .recall_safety_warnings(&device_name, &state)
+        .map_err(|e| e.to_string())?;
+    if !warnings.is_empty() && !acknowledge_warnings {
+        return Ok(RecallOutcome::blocked(warnings));
+    }
+    manager.begin_journal_operation("recall_collider_preset");
+    let glide = PedalType::Collider.glide(glide_ms, glide_ccs);
+    manager
+        .gliding(&device_name, glide, |manager| manager.recall_collider_preset(&device_name, &state))
+        .map_err(|e| e.to_string())?;
+    Ok(RecallOutcome::recalled(warnings))
+}
+
+/// Send a program change to a Collider (load preset 0-127)
+#[tauri::command]
+pub async fn send_collider_program_change(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    program: u8,
+) -> Result<(), String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("send_collider_program_change");
+    manager
+        .send_collider_program_change(&device_name, program)
+        .map_err(|e| e.to_string())
+}
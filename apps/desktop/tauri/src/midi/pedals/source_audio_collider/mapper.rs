@@ -0,0 +1,196 @@
+// Collider MIDI mapping - infrastructure layer
+// UNCONFIRMED: this CC/NRPN map hasn't been checked against Source Audio's Collider MIDI
+// documentation or a physical pedal, so none of it is sent (see `CC_MAP_CONFIRMED`) - edits,
+// batches and recalls fail with an error instead. Program changes are standard and still go out.
+// Knobs are plain CCs: delay A on 14-15, delay B on 16-17, reverb A on 18-20, reverb B on
+// 21-23, routing on 24, expression on 100, bypass on 102. The two delay times are 14-bit and
+// go out as NRPN 1 (A) and 2 (B); a recall sends them after every CC.
+
+use super::types::{ColliderParameter, ColliderState};
+use crate::midi::error::MidiResult;
+use crate::midi::nrpn::{self, Nrpn, MAX_14_BIT};
+use crate::midi::send_plan::SendPlan;
+use std::collections::HashMap;
+
+/// Whether the map below has been checked. While it hasn't, sending its CCs or NRPNs is an
+/// error and the Collider stays out of `PedalType::ALL`.
+pub const CC_MAP_CONFIRMED: bool = false;
+
+pub const NRPN_DELAY_TIME_A: u16 = 1;
+pub const NRPN_DELAY_TIME_B: u16 = 2;
+
+impl ColliderParameter {
+    /// Get the CC number for this parameter. Delay times are NRPN (see `nrpn`); for them this
+    /// is the data entry MSB, their coarse half.
+    pub fn cc_number(&self) -> u8 {
+        match self {
+            // NRPN
+            Self::DelayTimeA(_) | Self::DelayTimeB(_) => nrpn::CC_DATA_ENTRY_MSB,
+
+            // Delay A
+            Self::DelayRepeatsA(_) => 14,
+            Self::DelayMixA(_) => 15,
+
+            // Delay B
+            Self::DelayRepeatsB(_) => 16,
+            Self::DelayMixB(_) => 17,
+
+            // Reverb A
+            Self::ReverbSizeA(_) => 18,
+            Self::ReverbDecayA(_) => 19,
+            Self::ReverbMixA(_) => 20,
+
+            // Reverb B
+            Self::ReverbSizeB(_) => 21,
+            Self::ReverbDecayB(_) => 22,
+            Self::ReverbMixB(_) => 23,
+
+            // Shared
+            Self::Routing(_) => 24,
+            Self::Expression(_) => 100,
+            Self::Bypass(_) => 102,
+        }
+    }
+
+    /// Get the CC value for this parameter (the MSB for delay times)
+    pub fn cc_value(&self) -> u8 {
+        match self {
+            Self::DelayTimeA(v) | Self::DelayTimeB(v) => nrpn::split_14_bit(*v).0,
+
+            // Continuous
+            Self::DelayRepeatsA(v)
+            | Self::DelayMixA(v)
+            | Self::DelayRepeatsB(v)
+            | Self::DelayMixB(v)
+            | Self::ReverbSizeA(v)
+            | Self::ReverbDecayA(v)
+            | Self::ReverbMixA(v)
+            | Self::ReverbSizeB(v)
+            | Self::ReverbDecayB(v)
+            | Self::ReverbMixB(v)
+            | Self::Expression(v) => v.get(),
+
+            // Three-position enum
+            Self::Routing(v) => v.to_cc_value(),
+
+            // Binary (bool → 0 or 127)
+            Self::Bypass(b) => if *b { 127 } else { 0 },
+        }
+    }
+
+    /// The NRPN write for a delay time, or None for parameters sent as a single CC.
+    /// Fails for times over 14 bits.
+    pub fn nrpn(&self) -> MidiResult<Option<Nrpn>> {
+        match self {
+            Self::DelayTimeA(v) => Nrpn::new(NRPN_DELAY_TIME_A, *v).map(Some),
+            Self::DelayTimeB(v) => Nrpn::new(NRPN_DELAY_TIME_B, *v).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Get a human-readable name for this parameter
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::DelayTimeA(_) => "Delay A Time",
+            Self::DelayRepeatsA(_) => "Delay A Repeats",
+            Self::DelayMixA(_) => "Delay A Mix",
+            Self::DelayTimeB(_) => "Delay B Time",
+            Self::DelayRepeatsB(_) => "Delay B Repeats",
+            Self::DelayMixB(_) => "Delay B Mix",
+            Self::ReverbSizeA(_) => "Reverb A Size",
+            Self::ReverbDecayA(_) => "Reverb A Decay",
+            Self::ReverbMixA(_) => "Reverb A Mix",
+            Self::ReverbSizeB(_) => "Reverb B Size",
+            Self::ReverbDecayB(_) => "Reverb B Decay",
+            Self::ReverbMixB(_) => "Reverb B Mix",
+            Self::Routing(_) => "Routing",
+            Self::Bypass(_) => "Bypass",
+            Self::Expression(_) => "Expression",
+        }
+    }
+}
+
+impl ColliderState {
+    /// Convert the current state to a map of CC numbers → CC values.
+    /// Delay times are NRPN and not in the map; `recall_plan` sends them.
+    pub fn to_cc_map(&self) -> HashMap<u8, u8> {
+        let mut map = HashMap::new();
+
+        // Delays
+        map.insert(14, self.delay_a.repeats);
+        map.insert(15, self.delay_a.mix);
+        map.insert(16, self.delay_b.repeats);
+        map.insert(17, self.delay_b.mix);
+
+        // Reverbs
+        map.insert(18, self.reverb_a.size);
+        map.insert(19, self.reverb_a.decay);
+        map.insert(20, self.reverb_a.mix);
+        map.insert(21, self.reverb_b.size);
+        map.insert(22, self.reverb_b.decay);
+        map.insert(23, self.reverb_b.mix);
+
+        // Shared
+        map.insert(24, self.routing.to_cc_value());
+        map.insert(100, self.expression);
+        map.insert(102, if self.bypass { 127 } else { 0 });
+
+        map
+    }
+
+    /// The delay time NRPN writes, A then B. Times over 14 bits are clamped.
+    pub fn delay_time_nrpns(&self) -> [Nrpn; 2] {
+        [
+            Nrpn { parameter: NRPN_DELAY_TIME_A, value: self.delay_a.time.min(MAX_14_BIT) },
+            Nrpn { parameter: NRPN_DELAY_TIME_B, value: self.delay_b.time.min(MAX_14_BIT) },
+        ]
+    }
+
+    /// Recall order: bypass and routing first, then knobs, then the delay time NRPNs
+    pub fn recall_plan(&self) -> SendPlan {
+        SendPlan::grouped(self.to_cc_map(), &[102, 24], &[], &[])
+            .followed_by(self.delay_time_nrpns().iter().flat_map(Nrpn::cc_messages))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::midi::pedals::source_audio_collider::EffectRouting;
+
+    #[test]
+    fn test_delay_times_are_nrpn() {
+        let nrpn = ColliderParameter::DelayTimeB(10_000).nrpn().unwrap().unwrap();
+        assert_eq!(nrpn.cc_messages(), [(99, 0), (98, 2), (6, 78), (38, 16)]);
+        assert!(ColliderParameter::DelayTimeA(MAX_14_BIT + 1).nrpn().is_err());
+        assert!(ColliderParameter::Routing(EffectRouting::Parallel).nrpn().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_state_to_cc_map() {
+        let mut state = ColliderState::default();
+        state.reverb_b.decay = 100;
+        state.routing = EffectRouting::ReverbIntoDelay;
+        state.bypass = true;
+
+        let map = state.to_cc_map();
+        assert_eq!(map.get(&22), Some(&100));
+        assert_eq!(map.get(&24), Some(&2));
+        assert_eq!(map.get(&102), Some(&127));
+        assert!(!map.contains_key(&nrpn::CC_DATA_ENTRY_MSB));
+    }
+
+    #[test]
+    fn test_recall_plan_ends_with_both_delay_times() {
+        let mut state = ColliderState::default();
+        state.delay_a.time = 300;
+        state.delay_b.time = MAX_14_BIT;
+
+        let steps: Vec<(u8, u8)> = state.recall_plan().steps().iter().map(|step| (step.cc_number, step.value)).collect();
+        assert_eq!(&steps[..2], &[(102, 0), (24, 1)]);
+        assert_eq!(
+            &steps[steps.len() - 8..],
+            &[(99, 0), (98, 1), (6, 2), (38, 44), (99, 0), (98, 2), (6, 127), (38, 127)]
+        );
+    }
+}
@@ -0,0 +1,142 @@
+// Source Audio Collider MIDI implementation
+// A delay + reverb pedal with two delay engines (time, repeats, mix) and two reverb engines
+// (size, decay, mix), chained by a routing switch. 128 presets are recalled by Program Change.
+// Delay times are 14-bit and sent as NRPN rather than CC, so they move in fine steps.
+
+mod mapper;
+mod types;
+pub mod commands;
+
+pub use types::*;
+pub use mapper::{CC_MAP_CONFIRMED, NRPN_DELAY_TIME_A, NRPN_DELAY_TIME_B};
+
+/// Source Audio Collider pedal with complete MIDI control.
+/// This is the aggregate root for the Collider domain.
+#[derive(Debug)]
+pub struct Collider {
+    pub state: ColliderState,
+    pub midi_channel: u8,
+}
+
+impl Collider {
+    /// Create a new Collider instance with default state
+    pub fn new(midi_channel: u8) -> Self {
+        Self {
+            state: ColliderState::default(),
+            midi_channel,
+        }
+    }
+
+    /// Update internal state from a parameter change
+    pub fn update_state(&mut self, param: &ColliderParameter) {
+        let state = &mut self.state;
+        match param {
+            ColliderParameter::DelayTimeA(v) => state.delay_a.time = *v,
+            ColliderParameter::DelayRepeatsA(v) => state.delay_a.repeats = v.get(),
+            ColliderParameter::DelayMixA(v) => state.delay_a.mix = v.get(),
+            ColliderParameter::DelayTimeB(v) => state.delay_b.time = *v,
+            ColliderParameter::DelayRepeatsB(v) => state.delay_b.repeats = v.get(),
+            ColliderParameter::DelayMixB(v) => state.delay_b.mix = v.get(),
+            ColliderParameter::ReverbSizeA(v) => state.reverb_a.size = v.get(),
+            ColliderParameter::ReverbDecayA(v) => state.reverb_a.decay = v.get(),
+            ColliderParameter::ReverbMixA(v) => state.reverb_a.mix = v.get(),
+            ColliderParameter::ReverbSizeB(v) => state.reverb_b.size = v.get(),
+            ColliderParameter::ReverbDecayB(v) => state.reverb_b.decay = v.get(),
+            ColliderParameter::ReverbMixB(v) => state.reverb_b.mix = v.get(),
+            ColliderParameter::Routing(v) => state.routing = *v,
+            ColliderParameter::Bypass(v) => state.bypass = *v,
+            ColliderParameter::Expression(v) => state.expression = v.get(),
+        }
+    }
+
+    /// Get the current state as a hashmap of CC numbers to values (delay times excluded)
+    pub fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
+        self.state.to_cc_map()
+    }
+
+    /// Get the current state as CCs in the order a recall should send them
+    pub fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.state.recall_plan()
+    }
+
+    /// Number of preset slots (PC 0-127)
+    pub const fn preset_count() -> u8 {
+        128
+    }
+}
+
+impl super::PedalCapabilities for Collider {
+    type State = ColliderState;
+    type Parameter = ColliderParameter;
+
+    fn metadata(&self) -> super::PedalMetadata {
+        super::PedalMetadata {
+            name: "Collider",
+            manufacturer: "Source Audio",
+            supports_editor: true,
+            supports_preset_library: true,
+        }
+    }
+
+    fn supports_program_change(&self) -> bool {
+        true
+    }
+
+    fn midi_channel(&self) -> u8 {
+        self.midi_channel
+    }
+
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+
+    fn update_state(&mut self, param: &Self::Parameter) {
+        self.update_state(param)
+    }
+
+    fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
+        self.state_as_cc_map()
+    }
+
+    fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.recall_plan()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::midi::CcValue;
+
+    #[test]
+    fn test_update_state_touches_only_its_engine() {
+        let mut collider = Collider::new(1);
+        collider.update_state(&ColliderParameter::DelayTimeB(12_000));
+        collider.update_state(&ColliderParameter::ReverbDecayA(CcValue::new(120).unwrap()));
+        collider.update_state(&ColliderParameter::Routing(EffectRouting::Parallel));
+
+        assert_eq!(collider.state.delay_b.time, 12_000);
+        assert_eq!(collider.state.delay_a.time, DelayEngineState::default().time);
+        assert_eq!(collider.state.reverb_a.decay, 120);
+        assert_eq!(collider.state.reverb_b.decay, ReverbEngineState::default().decay);
+        assert_eq!(collider.state.routing, EffectRouting::Parallel);
+    }
+
+    #[test]
+    fn test_state_serializes_with_nested_engines() {
+        let json = serde_json::to_value(ColliderState::default()).unwrap();
+        assert_eq!(json["delay_a"]["time"], 8192);
+        assert_eq!(json["routing"], "DelayIntoReverb");
+
+        let restored: ColliderState = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, ColliderState::default());
+    }
+
+    #[test]
+    fn test_routing_cc_values_round_trip() {
+        for routing in [EffectRouting::DelayIntoReverb, EffectRouting::ReverbIntoDelay, EffectRouting::Parallel] {
+            assert_eq!(EffectRouting::from_cc_value(routing.to_cc_value()).unwrap(), routing);
+        }
+        assert!(EffectRouting::from_cc_value(0).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+// Collider domain types
+
+use crate::midi::CcValue;
+use crate::midi::error::{MidiError, MidiResult};
+use crate::midi::nrpn::MAX_14_BIT;
+use serde::{Deserialize, Serialize};
+
+/// One of the two delay engines
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DelayEngineState {
+    /// 14-bit (0-16383), sent as NRPN - shortest to longest delay
+    pub time: u16,
+    pub repeats: u8,
+    pub mix: u8,
+}
+
+impl Default for DelayEngineState {
+    fn default() -> Self {
+        Self {
+            time: MAX_14_BIT / 2 + 1,
+            repeats: 48,
+            mix: 64,
+        }
+    }
+}
+
+/// One of the two reverb engines
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReverbEngineState {
+    pub size: u8,
+    pub decay: u8,
+    pub mix: u8,
+}
+
+impl Default for ReverbEngineState {
+    fn default() -> Self {
+        Self {
+            size: 64,
+            decay: 64,
+            mix: 48,
+        }
+    }
+}
+
+/// Complete state of the Collider's four engines and the controls they share
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColliderState {
+    pub delay_a: DelayEngineState,
+    pub delay_b: DelayEngineState,
+    pub reverb_a: ReverbEngineState,
+    pub reverb_b: ReverbEngineState,
+
+    /// Order of the delay and reverb sections (CC 24)
+    pub routing: EffectRouting,
+
+    /// True when the pedal is bypassed
+    pub bypass: bool,
+    pub expression: u8,
+}
+
+impl Default for ColliderState {
+    fn default() -> Self {
+        Self {
+            delay_a: DelayEngineState::default(),
+            delay_b: DelayEngineState::default(),
+            reverb_a: ReverbEngineState::default(),
+            reverb_b: ReverbEngineState::default(),
+            routing: EffectRouting::DelayIntoReverb,
+            bypass: false,
+            expression: 0,
+        }
+    }
+}
+
+/// All possible Collider parameters with their values
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ColliderParameter {
+    // Delay A
+    /// 14-bit, sent as NRPN
+    DelayTimeA(u16),
+    DelayRepeatsA(CcValue),
+    DelayMixA(CcValue),
+
+    // Delay B
+    /// 14-bit, sent as NRPN
+    DelayTimeB(u16),
+    DelayRepeatsB(CcValue),
+    DelayMixB(CcValue),
+
+    // Reverb A
+    ReverbSizeA(CcValue),
+    ReverbDecayA(CcValue),
+    ReverbMixA(CcValue),
+
+    // Reverb B
+    ReverbSizeB(CcValue),
+    ReverbDecayB(CcValue),
+    ReverbMixB(CcValue),
+
+    // Shared
+    Routing(EffectRouting),
+    Bypass(bool),
+    Expression(CcValue),
+}
+
+/// How the delay and reverb sections are chained (CC 24)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EffectRouting {
+    /// Delays feed the reverbs
+    DelayIntoReverb, // 1
+    /// Reverbs feed the delays
+    ReverbIntoDelay, // 2
+    /// Both sections in parallel, summed
+    Parallel, // 3
+}
+
+impl EffectRouting {
+    pub fn to_cc_value(self) -> u8 {
+        match self {
+            Self::DelayIntoReverb => 1,
+            Self::ReverbIntoDelay => 2,
+            Self::Parallel => 3,
+        }
+    }
+
+    pub fn from_cc_value(value: u8) -> MidiResult<Self> {
+        match value {
+            1 => Ok(Self::DelayIntoReverb),
+            2 => Ok(Self::ReverbIntoDelay),
+            3 => Ok(Self::Parallel),
+            _ => Err(MidiError::InvalidValue {
+                expected: "1-3".to_string(),
+                actual: value.into(),
+            }),
+        }
+    }
+}
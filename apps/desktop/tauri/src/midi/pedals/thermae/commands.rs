@@ -0,0 +1,107 @@
+// Tauri commands for Chase Bliss Audio Thermae
+
+use crate::midi::{InputChannelMode, PedalType, SharedMidiManager};
+use crate::midi::connect_retry::{connect_with_retry, ConnectOutcome, RetryOptions};
+use crate::midi::safety::RecallOutcome;
+use crate::midi::pedals::thermae::{ThermaeParameter, ThermaeState};
+use tauri::State;
+
+/// Connect to a Thermae pedal
+#[tauri::command]
+pub async fn connect_thermae(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    midi_channel: u8,
+    input_mode: Option<InputChannelMode>,
+    retries: Option<u32>,
+    initial_backoff_ms: Option<u64>,
+) -> Result<ConnectOutcome, String> {
+    let options = RetryOptions::from_params(retries, initial_backoff_ms);
+    connect_with_retry(manager.inner(), &device_name, "connect_thermae", options, |manager| {
+        manager.connect_with_input_mode(&device_name, input_mode.as_ref(), |manager| {
+            manager.connect_thermae(&device_name, midi_channel)
+        })
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Send a Thermae parameter change
+#[tauri::command]
+pub async fn send_thermae_parameter(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    param: ThermaeParameter,
+) -> Result<(), String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("send_thermae_parameter");
+    manager
+        .send_thermae_parameter(&device_name, param)
+        .map_err(|e| e.to_string())
+}
+
+/// Get the current Thermae state
+#[tauri::command]
+pub async fn get_thermae_state(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+) -> Result<ThermaeState, String> {
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    manager
+        .get_thermae_state(&device_name)
+        .map_err(|e| e.to_string())
+}
+
+/// Recall a Thermae preset (send all parameters)
+/// Loud parameter jumps block the recall until `acknowledge_warnings` is set.
+/// A `glide_ms` above 0 (at most 2 s) ramps `glide_ccs` to their targets over that long.
+#[tauri::command]
+pub async fn recall_thermae_preset(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    state: ThermaeState,
+    acknowledge_warnings: bool,
+    glide_ms: Option<u64>,
+    glide_ccs: Option<Vec<u8>>,
+) -> Result<RecallOutcome, String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    let warnings = manager
+        .recall_safety_warnings(&device_name, &state)
+        .map_err(|e| e.to_string())?;
+    if !warnings.is_empty() && !acknowledge_warnings {
+        return Ok(RecallOutcome::blocked(warnings));
+    }
+    manager.begin_journal_operation("recall_thermae_preset");
+    let glide = PedalType::Thermae.glide(glide_ms, glide_ccs);
+    manager
+        .gliding(&device_name, glide, |manager| manager.recall_thermae_preset(&device_name, &state))
+        .map_err(|e| e.to_string())?;
+    Ok(RecallOutcome::recalled(warnings))
+}
+
+/// Save current state to a Thermae preset slot (1-122)
+#[tauri::command]
+pub async fn save_thermae_preset(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    slot: u8,
+) -> Result<(), String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("save_thermae_preset");
+    manager
+        .save_thermae_preset(&device_name, slot)
+        .map_err(|e| e.to_string())
+}
+
+/// Send a program change to a Thermae (navigate to preset slot 1-122)
+#[tauri::command]
+pub async fn send_thermae_program_change(
+    manager: State<'_, SharedMidiManager>,
+    device_name: String,
+    program: u8,
+) -> Result<(), String> {
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.begin_journal_operation("send_thermae_program_change");
+    manager
+        .send_thermae_program_change(&device_name, program)
+        .map_err(|e| e.to_string())
+}
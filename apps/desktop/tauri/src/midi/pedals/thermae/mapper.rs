@@ -0,0 +1,217 @@
+// Thermae MIDI CC mapping - infrastructure layer
+
+use super::types::{ThermaeParameter, ThermaeState};
+use crate::midi::pedals::DIP_SWITCH_CCS;
+use crate::midi::send_plan::SendPlan;
+use std::collections::HashMap;
+
+pub const CC_PRESET_SAVE: u8 = 111;
+
+impl ThermaeParameter {
+    /// Get the CC number for this parameter
+    pub fn cc_number(&self) -> u8 {
+        match self {
+            // Main knobs
+            Self::Mix(_) => 14,
+            Self::Lowpass(_) => 15,
+            Self::Regen(_) => 16,
+            Self::Glide(_) => 17,
+            Self::Interval1(_) => 18,
+            Self::Interval2(_) => 19,
+            Self::RampSpeed(_) => 20,
+
+            // Three-position toggles
+            Self::Division(_) => 21,
+            Self::GlideMode(_) => 22,
+            Self::ExtSwitchMode(_) => 23,
+
+            // Hidden options
+            Self::Time(_) => 24,
+            Self::ModRate(_) => 25,
+            Self::RampRange(_) => 26,
+
+            // Utility
+            Self::RampBounce(_) => 52,
+
+            // Left DIP bank
+            Self::DipMix(_) => 61,
+            Self::DipLowpass(_) => 62,
+            Self::DipRegen(_) => 63,
+            Self::DipGlide(_) => 64,
+            Self::DipInterval1(_) => 65,
+            Self::DipInterval2(_) => 66,
+            Self::DipSweep(_) => 67,
+            Self::DipPolarity(_) => 68,
+
+            // Right DIP bank
+            Self::DipMiso(_) => 71,
+            Self::DipSpread(_) => 72,
+            Self::DipTrails(_) => 73,
+            Self::DipLatch(_) => 74,
+            Self::DipSync(_) => 75,
+            Self::DipHalfSpeed(_) => 76,
+            Self::DipInvert(_) => 77,
+            Self::DipAllWet(_) => 78,
+
+            // Expression / footswitches / preset
+            Self::Expression(_) => 100,
+            Self::Bypass(_) => 102,
+            Self::Hold(_) => 103,
+            Self::Slow(_) => 104,
+            Self::PresetSave(_) => 111,
+        }
+    }
+
+    /// Get the CC value for this parameter
+    pub fn cc_value(&self) -> u8 {
+        match self {
+            // Continuous
+            Self::Mix(v)
+            | Self::Lowpass(v)
+            | Self::Regen(v)
+            | Self::Glide(v)
+            | Self::Interval1(v)
+            | Self::Interval2(v)
+            | Self::RampSpeed(v)
+            | Self::Time(v)
+            | Self::ModRate(v)
+            | Self::RampRange(v)
+            | Self::Expression(v)
+            | Self::PresetSave(v) => v.get(),
+
+            // Three-position enums
+            Self::Division(v) => v.to_cc_value(),
+            Self::GlideMode(v) => v.to_cc_value(),
+            Self::ExtSwitchMode(v) => v.to_cc_value(),
+
+            // Two-position enums
+            Self::DipSweep(v) => v.to_cc_value(),
+            Self::DipPolarity(v) => v.to_cc_value(),
+
+            // Binary (bool → 0 or 127)
+            Self::Bypass(b)
+            | Self::Hold(b)
+            | Self::Slow(b)
+            | Self::DipMix(b)
+            | Self::DipLowpass(b)
+            | Self::DipRegen(b)
+            | Self::DipGlide(b)
+            | Self::DipInterval1(b)
+            | Self::DipInterval2(b)
+            | Self::DipMiso(b)
+            | Self::DipSpread(b)
+            | Self::DipTrails(b)
+            | Self::DipLatch(b)
+            | Self::DipSync(b)
+            | Self::DipHalfSpeed(b)
+            | Self::DipInvert(b)
+            | Self::DipAllWet(b)
+            | Self::RampBounce(b) => if *b { 127 } else { 0 },
+        }
+    }
+
+    /// Get a human-readable name for this parameter
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Mix(_) => "Mix",
+            Self::Lowpass(_) => "Lowpass",
+            Self::Regen(_) => "Regen",
+            Self::Glide(_) => "Glide",
+            Self::Interval1(_) => "Interval 1",
+            Self::Interval2(_) => "Interval 2",
+            Self::RampSpeed(_) => "Ramp Speed",
+            Self::Division(_) => "Division",
+            Self::GlideMode(_) => "Glide Mode",
+            Self::ExtSwitchMode(_) => "Ext Switch Mode",
+            Self::Time(_) => "Time",
+            Self::ModRate(_) => "Mod Rate",
+            Self::RampRange(_) => "Ramp Range",
+            Self::Bypass(_) => "Bypass",
+            Self::Hold(_) => "Hold",
+            Self::Slow(_) => "Slow",
+            Self::DipMix(_) => "DIP: Mix",
+            Self::DipLowpass(_) => "DIP: Lowpass",
+            Self::DipRegen(_) => "DIP: Regen",
+            Self::DipGlide(_) => "DIP: Glide",
+            Self::DipInterval1(_) => "DIP: Interval 1",
+            Self::DipInterval2(_) => "DIP: Interval 2",
+            Self::DipSweep(_) => "DIP: Sweep",
+            Self::DipPolarity(_) => "DIP: Polarity",
+            Self::DipMiso(_) => "DIP: Miso",
+            Self::DipSpread(_) => "DIP: Spread",
+            Self::DipTrails(_) => "DIP: Trails",
+            Self::DipLatch(_) => "DIP: Latch",
+            Self::DipSync(_) => "DIP: Sync",
+            Self::DipHalfSpeed(_) => "DIP: Half Speed",
+            Self::DipInvert(_) => "DIP: Invert",
+            Self::DipAllWet(_) => "DIP: All Wet",
+            Self::RampBounce(_) => "Ramp/Bounce",
+            Self::Expression(_) => "Expression",
+            Self::PresetSave(_) => "Preset Save",
+        }
+    }
+}
+
+impl ThermaeState {
+    /// Convert the current state to a map of CC numbers → CC values.
+    /// Used when recalling a full preset (sending all parameters at once).
+    pub fn to_cc_map(&self) -> HashMap<u8, u8> {
+        let mut map = HashMap::new();
+
+        // Main knobs
+        map.insert(14, self.mix);
+        map.insert(15, self.lowpass);
+        map.insert(16, self.regen);
+        map.insert(17, self.glide);
+        map.insert(18, self.interval_1);
+        map.insert(19, self.interval_2);
+        map.insert(20, self.ramp_speed);
+
+        // Three-position toggles
+        map.insert(21, self.division.to_cc_value());
+        map.insert(22, self.glide_mode.to_cc_value());
+        map.insert(23, self.ext_switch_mode.to_cc_value());
+
+        // Hidden options
+        map.insert(24, self.time);
+        map.insert(25, self.mod_rate);
+        map.insert(26, self.ramp_range);
+
+        // Utility
+        map.insert(52, if self.ramp_bounce { 127 } else { 0 });
+
+        // Left DIP bank
+        map.insert(61, if self.dip_mix { 127 } else { 0 });
+        map.insert(62, if self.dip_lowpass { 127 } else { 0 });
+        map.insert(63, if self.dip_regen { 127 } else { 0 });
+        map.insert(64, if self.dip_glide { 127 } else { 0 });
+        map.insert(65, if self.dip_interval_1 { 127 } else { 0 });
+        map.insert(66, if self.dip_interval_2 { 127 } else { 0 });
+        map.insert(67, self.dip_sweep.to_cc_value());
+        map.insert(68, self.dip_polarity.to_cc_value());
+
+        // Right DIP bank
+        map.insert(71, if self.dip_miso { 127 } else { 0 });
+        map.insert(72, if self.dip_spread { 127 } else { 0 });
+        map.insert(73, if self.dip_trails { 127 } else { 0 });
+        map.insert(74, if self.dip_latch { 127 } else { 0 });
+        map.insert(75, if self.dip_sync { 127 } else { 0 });
+        map.insert(76, if self.dip_half_speed { 127 } else { 0 });
+        map.insert(77, if self.dip_invert { 127 } else { 0 });
+        map.insert(78, if self.dip_all_wet { 127 } else { 0 });
+
+        // Expression / footswitches
+        map.insert(100, self.expression);
+        map.insert(102, if self.bypass { 127 } else { 0 });
+        map.insert(103, if self.hold { 127 } else { 0 });
+        map.insert(104, if self.slow { 127 } else { 0 });
+
+        map
+    }
+
+    /// Recall order: bypass first, then toggles, footswitch modes and DIP switches, then knobs
+    pub fn recall_plan(&self) -> SendPlan {
+        let selections = [&[21, 22, 23, 103, 104, 52][..], DIP_SWITCH_CCS].concat();
+        SendPlan::grouped(self.to_cc_map(), &[102], &selections, &[])
+    }
+}
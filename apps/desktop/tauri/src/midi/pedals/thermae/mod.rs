@@ -0,0 +1,183 @@
+// Chase Bliss Audio Thermae MIDI implementation
+
+mod mapper;
+mod types;
+pub mod commands;
+
+pub use types::*;
+pub use mapper::CC_PRESET_SAVE;
+
+/// Chase Bliss Audio Thermae pedal with complete MIDI control.
+/// This is the aggregate root for the Thermae domain.
+#[derive(Debug)]
+pub struct Thermae {
+    pub state: ThermaeState,
+    pub midi_channel: u8,
+}
+
+impl Thermae {
+    /// Create a new Thermae instance with default state
+    pub fn new(midi_channel: u8) -> Self {
+        Self {
+            state: ThermaeState::default(),
+            midi_channel,
+        }
+    }
+
+    /// Update internal state from a parameter change
+    pub fn update_state(&mut self, param: &ThermaeParameter) {
+        match param {
+            ThermaeParameter::Mix(v) => self.state.mix = v.get(),
+            ThermaeParameter::Lowpass(v) => self.state.lowpass = v.get(),
+            ThermaeParameter::Regen(v) => self.state.regen = v.get(),
+            ThermaeParameter::Glide(v) => self.state.glide = v.get(),
+            ThermaeParameter::Interval1(v) => self.state.interval_1 = v.get(),
+            ThermaeParameter::Interval2(v) => self.state.interval_2 = v.get(),
+            ThermaeParameter::RampSpeed(v) => self.state.ramp_speed = v.get(),
+            ThermaeParameter::Division(v) => self.state.division = *v,
+            ThermaeParameter::GlideMode(v) => self.state.glide_mode = *v,
+            ThermaeParameter::ExtSwitchMode(v) => self.state.ext_switch_mode = *v,
+            ThermaeParameter::Time(v) => self.state.time = v.get(),
+            ThermaeParameter::ModRate(v) => self.state.mod_rate = v.get(),
+            ThermaeParameter::RampRange(v) => self.state.ramp_range = v.get(),
+            ThermaeParameter::Bypass(v) => self.state.bypass = *v,
+            ThermaeParameter::Hold(v) => self.state.hold = *v,
+            ThermaeParameter::Slow(v) => self.state.slow = *v,
+            ThermaeParameter::DipMix(v) => self.state.dip_mix = *v,
+            ThermaeParameter::DipLowpass(v) => self.state.dip_lowpass = *v,
+            ThermaeParameter::DipRegen(v) => self.state.dip_regen = *v,
+            ThermaeParameter::DipGlide(v) => self.state.dip_glide = *v,
+            ThermaeParameter::DipInterval1(v) => self.state.dip_interval_1 = *v,
+            ThermaeParameter::DipInterval2(v) => self.state.dip_interval_2 = *v,
+            ThermaeParameter::DipSweep(v) => self.state.dip_sweep = *v,
+            ThermaeParameter::DipPolarity(v) => self.state.dip_polarity = *v,
+            ThermaeParameter::DipMiso(v) => self.state.dip_miso = *v,
+            ThermaeParameter::DipSpread(v) => self.state.dip_spread = *v,
+            ThermaeParameter::DipTrails(v) => self.state.dip_trails = *v,
+            ThermaeParameter::DipLatch(v) => self.state.dip_latch = *v,
+            ThermaeParameter::DipSync(v) => self.state.dip_sync = *v,
+            ThermaeParameter::DipHalfSpeed(v) => self.state.dip_half_speed = *v,
+            ThermaeParameter::DipInvert(v) => self.state.dip_invert = *v,
+            ThermaeParameter::DipAllWet(v) => self.state.dip_all_wet = *v,
+            ThermaeParameter::RampBounce(v) => self.state.ramp_bounce = *v,
+            ThermaeParameter::Expression(v) => self.state.expression = v.get(),
+            ThermaeParameter::PresetSave(_) => {} // Does not update state
+        }
+    }
+
+    /// Get the current state as a hashmap of CC numbers to values
+    pub fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
+        self.state.to_cc_map()
+    }
+
+    /// Get the current state as CCs in the order a recall should send them
+    pub fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.state.recall_plan()
+    }
+}
+
+impl super::PedalCapabilities for Thermae {
+    type State = ThermaeState;
+    type Parameter = ThermaeParameter;
+
+    fn metadata(&self) -> super::PedalMetadata {
+        super::PedalMetadata {
+            name: "Thermae",
+            manufacturer: "Chase Bliss Audio",
+            supports_editor: true,
+            supports_preset_library: true,
+        }
+    }
+
+    fn supports_program_change(&self) -> bool {
+        true
+    }
+
+    fn midi_channel(&self) -> u8 {
+        self.midi_channel
+    }
+
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+
+    fn update_state(&mut self, param: &Self::Parameter) {
+        self.update_state(param)
+    }
+
+    fn state_as_cc_map(&self) -> std::collections::HashMap<u8, u8> {
+        self.state_as_cc_map()
+    }
+
+    fn recall_plan(&self) -> crate::midi::send_plan::SendPlan {
+        self.recall_plan()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::midi::CcValue;
+
+    #[test]
+    fn test_interval_to_semitones_endpoints() {
+        assert_eq!(interval_to_semitones(0), -24);
+        assert_eq!(interval_to_semitones(64), 0);
+        assert_eq!(interval_to_semitones(127), 24);
+    }
+
+    #[test]
+    fn test_interval_mapping_is_monotonic() {
+        let semitones: Vec<i8> = (0..=127).map(interval_to_semitones).collect();
+        assert!(semitones.windows(2).all(|pair| pair[1] - pair[0] <= 1 && pair[1] >= pair[0]));
+    }
+
+    #[test]
+    fn test_semitones_round_trip() {
+        for semitones in -24..=24 {
+            assert_eq!(interval_to_semitones(semitones_to_interval(semitones)), semitones);
+        }
+        assert_eq!(semitones_to_interval(-24), 0);
+        assert_eq!(semitones_to_interval(24), 127);
+        assert_eq!(semitones_to_interval(36), 127);
+    }
+
+    #[test]
+    fn test_default_intervals() {
+        let state = ThermaeState::default();
+        assert_eq!(state.interval_1_semitones(), 12);
+        assert_eq!(state.interval_2_semitones(), 7);
+    }
+
+    #[test]
+    fn test_parameter_cc_numbers() {
+        assert_eq!(ThermaeParameter::Mix(CcValue::new(0).unwrap()).cc_number(), 14);
+        assert_eq!(ThermaeParameter::Interval1(CcValue::new(0).unwrap()).cc_number(), 18);
+        assert_eq!(ThermaeParameter::Interval2(CcValue::new(0).unwrap()).cc_number(), 19);
+        assert_eq!(ThermaeParameter::ExtSwitchMode(ExtSwitchMode::Tap).cc_number(), 23);
+        assert_eq!(ThermaeParameter::PresetSave(CcValue::new(1).unwrap()).cc_number(), CC_PRESET_SAVE);
+    }
+
+    #[test]
+    fn test_update_state() {
+        let mut thermae = Thermae::new(1);
+        thermae.update_state(&ThermaeParameter::Interval1(CcValue::new(semitones_to_interval(-5)).unwrap()));
+        thermae.update_state(&ThermaeParameter::GlideMode(GlideMode::Rise));
+        thermae.update_state(&ThermaeParameter::Hold(true));
+
+        assert_eq!(thermae.state.interval_1_semitones(), -5);
+        assert_eq!(thermae.state.glide_mode, GlideMode::Rise);
+        assert!(thermae.state.hold);
+    }
+
+    #[test]
+    fn test_state_as_cc_map() {
+        let thermae = Thermae::new(1);
+        let cc_map = thermae.state_as_cc_map();
+
+        assert_eq!(cc_map.get(&18), Some(&semitones_to_interval(12)));
+        assert_eq!(cc_map.get(&21), Some(&Division::Quarter.to_cc_value()));
+        assert_eq!(cc_map.get(&102), Some(&0));
+        assert!(!cc_map.contains_key(&CC_PRESET_SAVE));
+    }
+}
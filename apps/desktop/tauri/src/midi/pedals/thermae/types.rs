@@ -0,0 +1,312 @@
+// Thermae domain types
+
+use crate::midi::CcValue;
+use crate::midi::error::{MidiError, MidiResult};
+use serde::{Deserialize, Serialize};
+
+/// Widest interval either Interval knob can reach, in semitones
+pub const INTERVAL_SEMITONE_RANGE: i8 = 24;
+
+/// Convert an Interval CC value (0-127) to semitones (-24 to +24).
+/// The mapping is linear: 0 = -24, 64 = unison, 127 = +24.
+pub fn interval_to_semitones(cc_value: u8) -> i8 {
+    let range = INTERVAL_SEMITONE_RANGE as i32;
+    let cc_value = cc_value.min(127) as i32;
+    ((cc_value * range * 2 + 63) / 127 - range) as i8
+}
+
+/// Convert semitones (-24 to +24) to the Interval CC value that selects them.
+/// Out-of-range intervals are clamped.
+pub fn semitones_to_interval(semitones: i8) -> u8 {
+    let range = INTERVAL_SEMITONE_RANGE as i32;
+    let semitones = (semitones as i32).clamp(-range, range);
+    (((semitones + range) * 127 + range) / (range * 2)) as u8
+}
+
+/// Complete state of all Thermae parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThermaeState {
+    // Main knobs
+    pub mix: u8,
+    pub lowpass: u8,
+    pub regen: u8,
+    pub glide: u8,
+    pub interval_1: u8, // Chromatic, see `interval_to_semitones`
+    pub interval_2: u8, // Chromatic, see `interval_to_semitones`
+    pub ramp_speed: u8,
+
+    // Three-position toggles
+    pub division: Division,
+    pub glide_mode: GlideMode,
+    pub ext_switch_mode: ExtSwitchMode,
+
+    // Hidden options
+    pub time: u8,
+    pub mod_rate: u8,
+    pub ramp_range: u8,
+
+    // Footswitches
+    pub bypass: bool,
+    pub hold: bool,
+    pub slow: bool,
+
+    // Left DIP bank (CC 61-68)
+    pub dip_mix: bool,
+    pub dip_lowpass: bool,
+    pub dip_regen: bool,
+    pub dip_glide: bool,
+    pub dip_interval_1: bool,
+    pub dip_interval_2: bool,
+    pub dip_sweep: SweepDirection,
+    pub dip_polarity: Polarity,
+
+    // Right DIP bank (CC 71-78)
+    pub dip_miso: bool,
+    pub dip_spread: bool,
+    pub dip_trails: bool,
+    pub dip_latch: bool,
+    pub dip_sync: bool,
+    pub dip_half_speed: bool,
+    pub dip_invert: bool,
+    pub dip_all_wet: bool,
+
+    // Utility
+    pub ramp_bounce: bool,
+    pub expression: u8,
+}
+
+impl ThermaeState {
+    /// First interval in semitones (-24 to +24)
+    pub fn interval_1_semitones(&self) -> i8 {
+        interval_to_semitones(self.interval_1)
+    }
+
+    /// Second interval in semitones (-24 to +24)
+    pub fn interval_2_semitones(&self) -> i8 {
+        interval_to_semitones(self.interval_2)
+    }
+}
+
+impl Default for ThermaeState {
+    fn default() -> Self {
+        Self {
+            mix: 64,
+            lowpass: 127,
+            regen: 64,
+            glide: 0,
+            interval_1: semitones_to_interval(12),
+            interval_2: semitones_to_interval(7),
+            ramp_speed: 64,
+            division: Division::Quarter,
+            glide_mode: GlideMode::Off,
+            ext_switch_mode: ExtSwitchMode::Tap,
+            time: 64,
+            mod_rate: 64,
+            ramp_range: 127,
+            bypass: false,
+            hold: false,
+            slow: false,
+            dip_mix: false,
+            dip_lowpass: false,
+            dip_regen: false,
+            dip_glide: false,
+            dip_interval_1: false,
+            dip_interval_2: false,
+            dip_sweep: SweepDirection::Bottom,
+            dip_polarity: Polarity::Forward,
+            dip_miso: false,
+            dip_spread: false,
+            dip_trails: false,
+            dip_latch: false,
+            dip_sync: false,
+            dip_half_speed: false,
+            dip_invert: false,
+            dip_all_wet: false,
+            ramp_bounce: false,
+            expression: 0,
+        }
+    }
+}
+
+/// All possible Thermae parameters with their values
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ThermaeParameter {
+    // Main knobs
+    Mix(CcValue),
+    Lowpass(CcValue),
+    Regen(CcValue),
+    Glide(CcValue),
+    Interval1(CcValue),
+    Interval2(CcValue),
+    RampSpeed(CcValue),
+
+    // Three-position toggles
+    Division(Division),
+    GlideMode(GlideMode),
+    ExtSwitchMode(ExtSwitchMode),
+
+    // Hidden options
+    Time(CcValue),
+    ModRate(CcValue),
+    RampRange(CcValue),
+
+    // Footswitches
+    Bypass(bool),
+    Hold(bool),
+    Slow(bool),
+
+    // Left DIP bank
+    DipMix(bool),
+    DipLowpass(bool),
+    DipRegen(bool),
+    DipGlide(bool),
+    DipInterval1(bool),
+    DipInterval2(bool),
+    DipSweep(SweepDirection),
+    DipPolarity(Polarity),
+
+    // Right DIP bank
+    DipMiso(bool),
+    DipSpread(bool),
+    DipTrails(bool),
+    DipLatch(bool),
+    DipSync(bool),
+    DipHalfSpeed(bool),
+    DipInvert(bool),
+    DipAllWet(bool),
+
+    // Utility
+    RampBounce(bool),
+    Expression(CcValue),
+    PresetSave(CcValue),
+}
+
+/// Delay time division toggle (CC 21)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Division {
+    Half,    // 1
+    Quarter, // 2
+    Eighth,  // 3
+}
+
+impl Division {
+    pub fn to_cc_value(self) -> u8 {
+        match self {
+            Self::Half => 1,
+            Self::Quarter => 2,
+            Self::Eighth => 3,
+        }
+    }
+
+    pub fn from_cc_value(value: u8) -> MidiResult<Self> {
+        match value {
+            1 => Ok(Self::Half),
+            2 => Ok(Self::Quarter),
+            3 => Ok(Self::Eighth),
+            _ => Err(MidiError::InvalidValue {
+                expected: "1-3".to_string(),
+                actual: value.into(),
+            }),
+        }
+    }
+}
+
+/// Glide mode toggle (CC 22)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GlideMode {
+    Off,  // 1
+    Rise, // 2
+    Fall, // 3
+}
+
+impl GlideMode {
+    pub fn to_cc_value(self) -> u8 {
+        match self {
+            Self::Off => 1,
+            Self::Rise => 2,
+            Self::Fall => 3,
+        }
+    }
+
+    pub fn from_cc_value(value: u8) -> MidiResult<Self> {
+        match value {
+            1 => Ok(Self::Off),
+            2 => Ok(Self::Rise),
+            3 => Ok(Self::Fall),
+            _ => Err(MidiError::InvalidValue {
+                expected: "1-3".to_string(),
+                actual: value.into(),
+            }),
+        }
+    }
+}
+
+/// External switch mode toggle (CC 23)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExtSwitchMode {
+    Tap,  // 1
+    Hold, // 2
+    Slow, // 3
+}
+
+impl ExtSwitchMode {
+    pub fn to_cc_value(self) -> u8 {
+        match self {
+            Self::Tap => 1,
+            Self::Hold => 2,
+            Self::Slow => 3,
+        }
+    }
+
+    pub fn from_cc_value(value: u8) -> MidiResult<Self> {
+        match value {
+            1 => Ok(Self::Tap),
+            2 => Ok(Self::Hold),
+            3 => Ok(Self::Slow),
+            _ => Err(MidiError::InvalidValue {
+                expected: "1-3".to_string(),
+                actual: value.into(),
+            }),
+        }
+    }
+}
+
+/// DIP sweep direction (CC 67)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SweepDirection {
+    Bottom, // 0-63
+    Top,    // 64-127
+}
+
+impl SweepDirection {
+    pub fn to_cc_value(self) -> u8 {
+        match self {
+            Self::Bottom => 0,
+            Self::Top => 127,
+        }
+    }
+
+    pub fn from_cc_value(value: u8) -> Self {
+        if value < 64 { Self::Bottom } else { Self::Top }
+    }
+}
+
+/// DIP polarity (CC 68)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Polarity {
+    Forward, // 0-63
+    Reverse, // 64-127
+}
+
+impl Polarity {
+    pub fn to_cc_value(self) -> u8 {
+        match self {
+            Self::Forward => 0,
+            Self::Reverse => 127,
+        }
+    }
+
+    pub fn from_cc_value(value: u8) -> Self {
+        if value < 64 { Self::Forward } else { Self::Reverse }
+    }
+}
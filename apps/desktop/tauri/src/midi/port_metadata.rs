@@ -0,0 +1,103 @@
+// Hardware details behind a MIDI port
+// midir only gives port names. Where the OS MIDI stack knows more (which USB device a
+// port belongs to, who made it), it helps tell two identical interfaces apart and
+// recognize a pedal before its identity reply comes back.
+//
+// macOS: CoreMIDI device properties. Windows: WinMM's caps only carry MMREG IDs, which
+// aren't USB IDs, so nothing is reported. Linux (ALSA): nothing yet.
+
+/// What the OS reports about the device behind a port
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PortMetadata {
+    pub vendor_id: Option<u16>,
+    pub product_id: Option<u16>,
+    pub manufacturer: Option<String>,
+    pub is_usb: bool,
+}
+
+/// Look up the device behind a port by its midir name. Devices name their input and
+/// output ports alike, so the lookup goes through the output (destination) side.
+pub fn port_metadata(port_name: &str) -> PortMetadata {
+    #[cfg(target_os = "macos")]
+    {
+        macos::port_metadata(port_name)
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = port_name;
+        PortMetadata::default()
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use super::PortMetadata;
+    use core_foundation_sys::base::CFRelease;
+    use core_foundation_sys::string::{
+        kCFStringEncodingUTF8, CFStringGetCString, CFStringGetLength, CFStringGetMaximumSizeForEncoding, CFStringRef,
+    };
+    use coremidi_sys::{
+        kMIDIPropertyDisplayName, kMIDIPropertyManufacturer, kMIDIPropertyUSBLocationID, kMIDIPropertyUSBVendorProduct,
+        MIDIDeviceRef, MIDIEndpointGetEntity, MIDIEntityGetDevice, MIDIEntityRef, MIDIGetDestination,
+        MIDIGetNumberOfDestinations, MIDIObjectGetIntegerProperty, MIDIObjectGetStringProperty, MIDIObjectRef,
+    };
+    use std::ffi::CStr;
+
+    pub fn port_metadata(port_name: &str) -> PortMetadata {
+        // midir names CoreMIDI ports by their display name
+        let Some(device) = destination_device(port_name) else {
+            return PortMetadata::default();
+        };
+        // Packed as (vendor << 16) | product
+        let vendor_product = integer_property(device, unsafe { kMIDIPropertyUSBVendorProduct });
+        PortMetadata {
+            vendor_id: vendor_product.map(|packed| (packed >> 16) as u16),
+            product_id: vendor_product.map(|packed| packed as u16),
+            manufacturer: string_property(device, unsafe { kMIDIPropertyManufacturer }),
+            is_usb: integer_property(device, unsafe { kMIDIPropertyUSBLocationID }).is_some() || vendor_product.is_some(),
+        }
+    }
+
+    fn destination_device(port_name: &str) -> Option<MIDIDeviceRef> {
+        let count = unsafe { MIDIGetNumberOfDestinations() };
+        let endpoint = (0..count)
+            .map(|index| unsafe { MIDIGetDestination(index) })
+            .find(|&endpoint| string_property(endpoint, unsafe { kMIDIPropertyDisplayName }).as_deref() == Some(port_name))?;
+
+        let mut entity: MIDIEntityRef = 0;
+        let mut device: MIDIDeviceRef = 0;
+        // Virtual endpoints have no entity, and so no device
+        if unsafe { MIDIEndpointGetEntity(endpoint, &mut entity) } != 0 || entity == 0 {
+            return None;
+        }
+        if unsafe { MIDIEntityGetDevice(entity, &mut device) } != 0 || device == 0 {
+            return None;
+        }
+        Some(device)
+    }
+
+    fn integer_property(object: MIDIObjectRef, property: CFStringRef) -> Option<i32> {
+        let mut value = 0;
+        (unsafe { MIDIObjectGetIntegerProperty(object, property, &mut value) } == 0).then_some(value)
+    }
+
+    fn string_property(object: MIDIObjectRef, property: CFStringRef) -> Option<String> {
+        let mut value: CFStringRef = std::ptr::null();
+        if unsafe { MIDIObjectGetStringProperty(object, property, &mut value) } != 0 || value.is_null() {
+            return None;
+        }
+        let string = unsafe { cf_string_to_string(value) };
+        unsafe { CFRelease(value.cast()) };
+        string
+    }
+
+    /// Copy a CFString out as UTF-8
+    unsafe fn cf_string_to_string(string: CFStringRef) -> Option<String> {
+        let capacity = CFStringGetMaximumSizeForEncoding(CFStringGetLength(string), kCFStringEncodingUTF8) + 1;
+        let mut buffer = vec![0u8; capacity as usize];
+        if CFStringGetCString(string, buffer.as_mut_ptr().cast(), capacity, kCFStringEncodingUTF8) == 0 {
+            return None;
+        }
+        CStr::from_bytes_until_nul(&buffer).ok().map(|s| s.to_string_lossy().into_owned())
+    }
+}
@@ -0,0 +1,453 @@
+// Outbound routing for logical devices
+// Pedals daisy-chained over 5-pin MIDI thru share one physical port, each on its own
+// channel. A logical device is a (port, channel) route: every device on a port sends
+// through one shared output connection, and the channel is stamped onto each message
+// at send time. Incoming messages on a shared port are demultiplexed by channel.
+// A device can also accept input on other channels (Omni, or a list) for pedals that
+// echo on a channel other than the one they're addressed on.
+
+use crate::midi::cc_value::CcValue;
+use crate::midi::error::{MidiError, MidiResult};
+use midir::MidiOutputConnection;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Raw byte sink for one physical output port
+pub(crate) trait OutputPort: Send {
+    fn send(&mut self, message: &[u8]) -> MidiResult<()>;
+}
+
+impl OutputPort for MidiOutputConnection {
+    fn send(&mut self, message: &[u8]) -> MidiResult<()> {
+        MidiOutputConnection::send(self, message).map_err(|e| MidiError::SendFailed(e.to_string()))
+    }
+}
+
+/// One physical output, shared by every logical device routed through it
+pub(crate) type SharedOutput = Arc<Mutex<Box<dyn OutputPort>>>;
+
+/// Where a logical device's messages go
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    pub port_name: String,
+    pub channel: u8,
+}
+
+/// A logical device's handle on its port's shared output
+#[derive(Clone)]
+pub(crate) struct RoutedOutput {
+    output: SharedOutput,
+    pub route: Route,
+}
+
+impl RoutedOutput {
+    /// Control Change stamped with this route's channel. A value above 127 is rejected
+    /// rather than sent, since its high bit would turn it into a status byte.
+    pub fn send_cc(&self, cc_number: u8, value: u8) -> MidiResult<()> {
+        let value = CcValue::new(value)?;
+        self.send(&[0xB0 | (self.route.channel - 1), cc_number, value.get()])
+    }
+
+    /// Program Change stamped with this route's channel
+    pub fn send_program_change(&self, program: u8) -> MidiResult<()> {
+        self.send(&[0xC0 | (self.route.channel - 1), program])
+    }
+
+    /// Note On stamped with this route's channel
+    pub fn send_note_on(&self, note: u8, velocity: u8) -> MidiResult<()> {
+        self.send(&[0x90 | (self.route.channel - 1), note, velocity])
+    }
+
+    /// Note Off stamped with this route's channel (release velocity 0)
+    pub fn send_note_off(&self, note: u8) -> MidiResult<()> {
+        self.send(&[0x80 | (self.route.channel - 1), note, 0])
+    }
+
+    fn send(&self, message: &[u8]) -> MidiResult<()> {
+        self.output
+            .lock()
+            .map_err(|e| MidiError::SendFailed(e.to_string()))?
+            .send(message)
+    }
+}
+
+struct OpenPort {
+    output: SharedOutput,
+    devices: usize,
+}
+
+/// Physical output connections keyed by port name, held open while any logical device uses them
+#[derive(Default)]
+pub(crate) struct OutputRouter {
+    ports: HashMap<String, OpenPort>,
+}
+
+impl OutputRouter {
+    /// Route a logical device through `route.port_name`, opening the port with `open`
+    /// only if no other device is already using it
+    pub fn acquire(
+        &mut self,
+        route: Route,
+        open: impl FnOnce() -> MidiResult<Box<dyn OutputPort>>,
+    ) -> MidiResult<RoutedOutput> {
+        if !(1..=16).contains(&route.channel) {
+            return Err(MidiError::InvalidChannel(route.channel));
+        }
+
+        let port = match self.ports.entry(route.port_name.clone()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(OpenPort {
+                output: Arc::new(Mutex::new(open()?)),
+                devices: 0,
+            }),
+        };
+        port.devices += 1;
+
+        Ok(RoutedOutput {
+            output: Arc::clone(&port.output),
+            route,
+        })
+    }
+
+    /// A logical device stopped using a port; the port closes with its last device
+    pub fn release(&mut self, port_name: &str) {
+        if let Some(port) = self.ports.get_mut(port_name) {
+            port.devices -= 1;
+            if port.devices == 0 {
+                self.ports.remove(port_name);
+            }
+        }
+    }
+
+    pub fn is_open(&self, port_name: &str) -> bool {
+        self.ports.contains_key(port_name)
+    }
+}
+
+/// Which channels a device's incoming messages are accepted on
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum InputChannelMode {
+    /// Only the channel the device was connected on
+    #[default]
+    Exact,
+    /// Any channel
+    Omni,
+    /// Any of these channels (the connected channel is not implied)
+    Channels { channels: Vec<u8> },
+}
+
+impl InputChannelMode {
+    /// Channel lists must be non-empty and within 1-16
+    pub fn validate(&self) -> MidiResult<()> {
+        match self {
+            InputChannelMode::Channels { channels } => {
+                if channels.is_empty() {
+                    return Err(MidiError::Other("Input channel list is empty".to_string()));
+                }
+                match channels.iter().find(|channel| !(1..=16).contains(*channel)) {
+                    Some(channel) => Err(MidiError::InvalidChannel(*channel)),
+                    None => Ok(()),
+                }
+            }
+            InputChannelMode::Exact | InputChannelMode::Omni => Ok(()),
+        }
+    }
+
+    /// Whether a message on `channel` is for a device connected on `connected_channel`
+    pub fn accepts(&self, connected_channel: u8, channel: u8) -> bool {
+        match self {
+            InputChannelMode::Exact => channel == connected_channel,
+            InputChannelMode::Omni => true,
+            InputChannelMode::Channels { channels } => channels.contains(&channel),
+        }
+    }
+}
+
+/// A device's input mode, shared by its connection and its input listener so it can be
+/// changed without reconnecting
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SharedInputMode(Arc<Mutex<InputChannelMode>>);
+
+impl SharedInputMode {
+    pub fn get(&self) -> InputChannelMode {
+        self.0.lock().unwrap().clone()
+    }
+
+    pub fn set(&self, mode: InputChannelMode) {
+        *self.0.lock().unwrap() = mode;
+    }
+}
+
+/// Logical devices listening on one input port, by channel
+#[derive(Debug)]
+pub(crate) struct ChannelDemux<T> {
+    listeners: Arc<Mutex<HashMap<u8, T>>>,
+}
+
+impl<T> Clone for ChannelDemux<T> {
+    fn clone(&self) -> Self {
+        Self {
+            listeners: Arc::clone(&self.listeners),
+        }
+    }
+}
+
+impl<T> Default for ChannelDemux<T> {
+    fn default() -> Self {
+        Self {
+            listeners: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+impl<T: Clone> ChannelDemux<T> {
+    /// Listen on a channel. Fails if another logical device on the port already has it.
+    pub fn add(&self, channel: u8, listener: T) -> MidiResult<()> {
+        let mut listeners = self.listeners.lock().unwrap();
+        if listeners.contains_key(&channel) {
+            return Err(MidiError::Other(format!("Another device on this port already uses channel {}", channel)));
+        }
+        listeners.insert(channel, listener);
+        Ok(())
+    }
+
+    /// Stop listening on a channel; returns whether anyone is still listening on the port
+    pub fn remove(&self, channel: u8) -> bool {
+        let mut listeners = self.listeners.lock().unwrap();
+        listeners.remove(&channel);
+        !listeners.is_empty()
+    }
+
+    /// Listener for a message's status byte. A lone device on a port hears everything, so
+    /// traffic on the wrong channel still reaches it (that's how channel mismatches show up).
+    pub fn route(&self, status: u8) -> Option<T> {
+        self.route_with_fallback(status, |_, _| false)
+    }
+
+    /// `route`, but a message on a channel no device was connected on goes to the first
+    /// listener (lowest connected channel) that `accepts` it
+    pub fn route_with_fallback(&self, status: u8, accepts: impl Fn(&T, u8) -> bool) -> Option<T> {
+        let listeners = self.listeners.lock().unwrap();
+        if listeners.len() == 1 {
+            return listeners.values().next().cloned();
+        }
+        if !(0x80..=0xEF).contains(&status) {
+            return None;
+        }
+        let channel = (status & 0x0F) + 1;
+        if let Some(listener) = listeners.get(&channel) {
+            return Some(listener.clone());
+        }
+
+        let mut connected: Vec<_> = listeners.iter().collect();
+        connected.sort_by_key(|(connected_channel, _)| **connected_channel);
+        connected
+            .into_iter()
+            .map(|(_, listener)| listener)
+            .find(|listener| accepts(listener, channel))
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockOutputPort;
+
+    fn route(channel: u8) -> Route {
+        Route {
+            port_name: "Microcosm MIDI Thru".to_string(),
+            channel,
+        }
+    }
+
+    #[test]
+    fn test_daisy_chained_devices_share_one_port() {
+        let mut router = OutputRouter::default();
+        let port = MockOutputPort::default();
+        let mut opened = 0;
+
+        let mut acquire = |channel| {
+            router
+                .acquire(route(channel), || {
+                    opened += 1;
+                    Ok(Box::new(port.clone()))
+                })
+                .unwrap()
+        };
+        let microcosm = acquire(1);
+        let chroma_console = acquire(2);
+        let thermae = acquire(3);
+        assert_eq!(opened, 1);
+
+        microcosm.send_cc(14, 64).unwrap();
+        chroma_console.send_cc(14, 100).unwrap();
+        thermae.send_program_change(5).unwrap();
+        microcosm.send_program_change(45).unwrap();
+
+        assert_eq!(
+            port.sent(),
+            vec![
+                vec![0xB0, 14, 64],
+                vec![0xB1, 14, 100],
+                vec![0xC2, 5],
+                vec![0xC0, 45],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_out_of_range_cc_value_is_not_sent() {
+        let mut router = OutputRouter::default();
+        let port = MockOutputPort::default();
+        let microcosm = router.acquire(route(1), || Ok(Box::new(port.clone()))).unwrap();
+
+        assert!(matches!(microcosm.send_cc(14, 200), Err(MidiError::InvalidValue { actual: 200, .. })));
+        assert!(port.sent().is_empty());
+    }
+
+    #[test]
+    fn test_port_closes_with_last_device() {
+        let mut router = OutputRouter::default();
+        let port = MockOutputPort::default();
+
+        router.acquire(route(1), || Ok(Box::new(port.clone()))).unwrap();
+        router.acquire(route(2), || Ok(Box::new(port.clone()))).unwrap();
+
+        router.release("Microcosm MIDI Thru");
+        assert!(router.is_open("Microcosm MIDI Thru"));
+        router.release("Microcosm MIDI Thru");
+        assert!(!router.is_open("Microcosm MIDI Thru"));
+    }
+
+    #[test]
+    fn test_invalid_channel_does_not_open_port() {
+        let mut router = OutputRouter::default();
+        let result = router.acquire(route(17), || panic!("port should not be opened"));
+
+        assert!(matches!(result, Err(MidiError::InvalidChannel(17))));
+        assert!(!router.is_open("Microcosm MIDI Thru"));
+    }
+
+    #[test]
+    fn test_demux_routes_by_channel() {
+        let demux = ChannelDemux::default();
+        demux.add(1, "Microcosm").unwrap();
+        demux.add(2, "Chroma Console").unwrap();
+        demux.add(3, "Thermae").unwrap();
+
+        assert_eq!(demux.route(0xB0), Some("Microcosm"));
+        assert_eq!(demux.route(0xB1), Some("Chroma Console"));
+        assert_eq!(demux.route(0xC2), Some("Thermae"));
+        assert_eq!(demux.route(0xB5), None);
+        assert!(demux.add(2, "Clean").is_err());
+    }
+
+    /// Listener connected on `channel` with `mode`, as the manager's input callback sees it
+    fn listener(name: &'static str, channel: u8, mode: InputChannelMode) -> (&'static str, u8, SharedInputMode) {
+        let shared = SharedInputMode::default();
+        shared.set(mode);
+        (name, channel, shared)
+    }
+
+    fn accepts(listener: &(&'static str, u8, SharedInputMode), channel: u8) -> bool {
+        listener.2.get().accepts(listener.1, channel)
+    }
+
+    #[test]
+    fn test_exact_mode_accepts_only_connected_channel() {
+        let mode = InputChannelMode::Exact;
+        assert!(mode.accepts(4, 4));
+        assert!(!mode.accepts(4, 1));
+    }
+
+    #[test]
+    fn test_omni_mode_accepts_every_channel() {
+        assert!((1..=16).all(|channel| InputChannelMode::Omni.accepts(4, channel)));
+    }
+
+    #[test]
+    fn test_channel_list_mode_accepts_listed_channels_only() {
+        let mode = InputChannelMode::Channels { channels: vec![1, 4] };
+        assert!(mode.accepts(4, 1));
+        assert!(mode.accepts(4, 4));
+        assert!(!mode.accepts(4, 2));
+        // The connected channel is only accepted when listed
+        assert!(!InputChannelMode::Channels { channels: vec![1] }.accepts(4, 4));
+    }
+
+    #[test]
+    fn test_channel_list_validation() {
+        assert!(InputChannelMode::Channels { channels: vec![1, 16] }.validate().is_ok());
+        assert!(matches!(
+            InputChannelMode::Channels { channels: vec![0] }.validate(),
+            Err(MidiError::InvalidChannel(0))
+        ));
+        assert!(matches!(
+            InputChannelMode::Channels { channels: vec![3, 17] }.validate(),
+            Err(MidiError::InvalidChannel(17))
+        ));
+        assert!(InputChannelMode::Channels { channels: vec![] }.validate().is_err());
+        assert!(InputChannelMode::Omni.validate().is_ok());
+    }
+
+    #[test]
+    fn test_input_channel_mode_serde() {
+        assert_eq!(serde_json::to_value(InputChannelMode::Omni).unwrap(), serde_json::json!({"type": "omni"}));
+        let mode: InputChannelMode = serde_json::from_value(serde_json::json!({"type": "channels", "channels": [1, 4]})).unwrap();
+        assert_eq!(mode, InputChannelMode::Channels { channels: vec![1, 4] });
+    }
+
+    #[test]
+    fn test_shared_port_falls_back_to_omni_listener() {
+        let demux = ChannelDemux::default();
+        demux.add(4, listener("Preamp MK II", 4, InputChannelMode::Omni)).unwrap();
+        demux.add(2, listener("Chroma Console", 2, InputChannelMode::Exact)).unwrap();
+        let name = |status| demux.route_with_fallback(status, accepts).map(|listener| listener.0);
+
+        // A device's own channel still goes to that device
+        assert_eq!(name(0xB1), Some("Chroma Console"));
+        assert_eq!(name(0xB3), Some("Preamp MK II"));
+        // Unclaimed channels go to the Omni device
+        assert_eq!(name(0xB0), Some("Preamp MK II"));
+        assert_eq!(name(0xBF), Some("Preamp MK II"));
+    }
+
+    #[test]
+    fn test_shared_port_drops_unclaimed_channels_without_omni() {
+        let demux = ChannelDemux::default();
+        demux.add(4, listener("Preamp MK II", 4, InputChannelMode::Channels { channels: vec![1, 4] })).unwrap();
+        demux.add(2, listener("Chroma Console", 2, InputChannelMode::Exact)).unwrap();
+        let name = |status| demux.route_with_fallback(status, accepts).map(|listener| listener.0);
+
+        assert_eq!(name(0xB0), Some("Preamp MK II"));
+        assert_eq!(name(0xB5), None);
+    }
+
+    #[test]
+    fn test_mode_change_applies_without_re_adding_listener() {
+        let demux = ChannelDemux::default();
+        let preamp = listener("Preamp MK II", 4, InputChannelMode::Exact);
+        let mode = preamp.2.clone();
+        demux.add(4, preamp).unwrap();
+        demux.add(2, listener("Chroma Console", 2, InputChannelMode::Exact)).unwrap();
+        let name = |status| demux.route_with_fallback(status, accepts).map(|listener| listener.0);
+
+        assert_eq!(name(0xB0), None);
+        mode.set(InputChannelMode::Omni);
+        assert_eq!(name(0xB0), Some("Preamp MK II"));
+    }
+
+    #[test]
+    fn test_lone_listener_hears_every_channel() {
+        let demux = ChannelDemux::default();
+        demux.add(1, "Microcosm").unwrap();
+
+        assert_eq!(demux.route(0xB4), Some("Microcosm"));
+        assert!(!demux.remove(1));
+        assert_eq!(demux.route(0xB0), None);
+    }
+}
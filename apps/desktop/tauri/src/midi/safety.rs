@@ -0,0 +1,258 @@
+// Loud-output safety checks for preset recall
+// Recalling a preset jumps every parameter at once, so a preset saved with Volume at 127
+// can blast monitors that were fine a second ago. Each pedal has a few "loud" parameters
+// with thresholds; a recall that raises one of them past its threshold is flagged so the
+// user can confirm first. Thresholds are user-editable and stored in the app data dir.
+
+use crate::midi::error::{MidiError, MidiResult};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+/// Threshold file inside the app data dir
+pub const SAFETY_THRESHOLDS_FILE: &str = "safety_thresholds.json";
+
+/// When a parameter counts as loud
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Threshold {
+    /// Knob values at or above this
+    AtLeast(u8),
+    /// Enum settings with one of these names (e.g. "HighGain")
+    OneOf(Vec<String>),
+}
+
+impl Threshold {
+    fn is_loud(&self, value: &serde_json::Value) -> bool {
+        match self {
+            Threshold::AtLeast(threshold) => value.as_u64().is_some_and(|v| v >= u64::from(*threshold)),
+            Threshold::OneOf(names) => value.as_str().is_some_and(|v| names.iter().any(|name| name == v)),
+        }
+    }
+}
+
+/// Loud parameters per pedal type, keyed by state field name
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SafetyThresholds(pub HashMap<String, BTreeMap<String, Threshold>>);
+
+impl Default for SafetyThresholds {
+    fn default() -> Self {
+        let knobs = |fields: &[&str]| -> BTreeMap<String, Threshold> {
+            fields.iter().map(|field| (field.to_string(), Threshold::AtLeast(100))).collect()
+        };
+
+        let mut gen_loss = knobs(&["volume", "hiss_level"]);
+        gen_loss.insert("input_gain".to_string(), Threshold::OneOf(vec!["HighGain".to_string()]));
+
+        Self(HashMap::from([
+            ("Microcosm".to_string(), knobs(&["volume"])),
+            ("GenLossMkii".to_string(), gen_loss),
+            ("ChromaConsole".to_string(), knobs(&["output_level"])),
+            ("PreampMk2".to_string(), knobs(&["volume", "gain"])),
+            ("BillyStringsWombtone".to_string(), knobs(&["volume"])),
+            ("BrothersAm".to_string(), knobs(&["volume1", "volume2", "gain1", "gain2"])),
+        ]))
+    }
+}
+
+/// How alarming a warning is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// Already loud, and getting louder
+    Caution,
+    /// Going from a safe setting to a loud one
+    Danger,
+}
+
+/// A loud parameter a recall would raise
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SafetyWarning {
+    pub parameter: String,
+    pub current: serde_json::Value,
+    pub target: serde_json::Value,
+    pub severity: Severity,
+}
+
+/// What a recall command did: nothing is sent while unacknowledged warnings exist
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RecallOutcome {
+    pub recalled: bool,
+    pub warnings: Vec<SafetyWarning>,
+}
+
+impl RecallOutcome {
+    pub fn recalled(warnings: Vec<SafetyWarning>) -> Self {
+        Self { recalled: true, warnings }
+    }
+
+    pub fn blocked(warnings: Vec<SafetyWarning>) -> Self {
+        Self { recalled: false, warnings }
+    }
+}
+
+/// Warnings for moving a pedal from `current` to `target` (both serialized like presets).
+/// Parameters that don't change, or only get quieter, never warn.
+pub fn check_safety(
+    thresholds: &SafetyThresholds,
+    pedal_type: &str,
+    current: &serde_json::Value,
+    target: &serde_json::Value,
+) -> Vec<SafetyWarning> {
+    let Some(parameters) = thresholds.0.get(pedal_type) else {
+        return Vec::new();
+    };
+
+    parameters
+        .iter()
+        .filter_map(|(parameter, threshold)| {
+            let target = target.get(parameter)?;
+            let current = current.get(parameter).cloned().unwrap_or(serde_json::Value::Null);
+            if current == *target || !threshold.is_loud(target) {
+                return None;
+            }
+
+            let severity = if !threshold.is_loud(&current) {
+                Severity::Danger
+            } else {
+                // Already loud - a knob only matters if it goes up further
+                match threshold {
+                    Threshold::AtLeast(_) if target.as_u64() <= current.as_u64() => return None,
+                    _ => Severity::Caution,
+                }
+            };
+
+            Some(SafetyWarning {
+                parameter: parameter.clone(),
+                current,
+                target: target.clone(),
+                severity,
+            })
+        })
+        .collect()
+}
+
+/// Read thresholds; a missing file means the defaults
+pub fn load_thresholds(path: &Path) -> MidiResult<SafetyThresholds> {
+    if !path.exists() {
+        return Ok(SafetyThresholds::default());
+    }
+    let contents = fs::read_to_string(path)
+        .map_err(|e| MidiError::Other(format!("Failed to read safety thresholds: {}", e)))?;
+    serde_json::from_str(&contents)
+        .map_err(|e| MidiError::Other(format!("Failed to parse safety thresholds: {}", e)))
+}
+
+/// Write thresholds, replacing the previous file
+pub fn save_thresholds(path: &Path, thresholds: &SafetyThresholds) -> MidiResult<()> {
+    let contents = serde_json::to_string_pretty(thresholds)
+        .map_err(|e| MidiError::Other(format!("Failed to serialize safety thresholds: {}", e)))?;
+    fs::write(path, contents)
+        .map_err(|e| MidiError::Other(format!("Failed to write safety thresholds: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::midi::pedals::gen_loss_mkii::{GenLossMkiiState, InputGain};
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn gen_loss(volume: u8, input_gain: InputGain) -> serde_json::Value {
+        serde_json::to_value(GenLossMkiiState {
+            volume,
+            input_gain,
+            ..GenLossMkiiState::default()
+        })
+        .unwrap()
+    }
+
+    fn check(current: &serde_json::Value, target: &serde_json::Value) -> Vec<SafetyWarning> {
+        check_safety(&SafetyThresholds::default(), "GenLossMkii", current, target)
+    }
+
+    #[test]
+    fn test_loud_recall_from_safe_state_is_danger() {
+        let warnings = check(
+            &gen_loss(64, InputGain::InstrumentLevel),
+            &gen_loss(127, InputGain::HighGain),
+        );
+
+        assert_eq!(
+            warnings,
+            vec![
+                SafetyWarning {
+                    parameter: "input_gain".to_string(),
+                    current: json!("InstrumentLevel"),
+                    target: json!("HighGain"),
+                    severity: Severity::Danger,
+                },
+                SafetyWarning {
+                    parameter: "volume".to_string(),
+                    current: json!(64),
+                    target: json!(127),
+                    severity: Severity::Danger,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unchanged_loud_parameter_does_not_warn() {
+        let state = gen_loss(127, InputGain::HighGain);
+        assert!(check(&state, &state).is_empty());
+    }
+
+    #[test]
+    fn test_threshold_boundary() {
+        let current = gen_loss(99, InputGain::InstrumentLevel);
+        assert!(check(&current, &gen_loss(99, InputGain::InstrumentLevel)).is_empty());
+
+        let warnings = check(&current, &gen_loss(100, InputGain::InstrumentLevel));
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].severity, Severity::Danger);
+    }
+
+    #[test]
+    fn test_louder_when_already_loud_is_caution() {
+        let warnings = check(
+            &gen_loss(110, InputGain::InstrumentLevel),
+            &gen_loss(127, InputGain::InstrumentLevel),
+        );
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].severity, Severity::Caution);
+
+        // Turning down never warns
+        assert!(check(
+            &gen_loss(127, InputGain::InstrumentLevel),
+            &gen_loss(110, InputGain::InstrumentLevel),
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_pedal_without_thresholds_never_warns() {
+        let warnings = check_safety(
+            &SafetyThresholds::default(),
+            "Thermae",
+            &json!({ "mix": 0 }),
+            &json!({ "mix": 127 }),
+        );
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_thresholds_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(SAFETY_THRESHOLDS_FILE);
+        assert_eq!(load_thresholds(&path).unwrap(), SafetyThresholds::default());
+
+        let mut thresholds = SafetyThresholds::default();
+        thresholds.0.get_mut("Microcosm").unwrap().insert("volume".to_string(), Threshold::AtLeast(80));
+        save_thresholds(&path, &thresholds).unwrap();
+
+        assert_eq!(load_thresholds(&path).unwrap(), thresholds);
+    }
+}
@@ -0,0 +1,433 @@
+// Scheduled preset recalls
+// "Recall X on Y in 4 beats" or "in 1500 ms" for synchronized live changes. Every
+// scheduled recall waits on one worker thread per scheduler, woken whenever a recall is
+// scheduled or cancelled and on every MIDI clock tick. A recall the worker finds due runs
+// `on_due`, which claims its entry with `finish`, so a recall cancelled just as it came
+// due never fires.
+// Beats are counted by a `BeatClock` fed the System Real-Time messages heard on input
+// ports: Start zeroes it, each Timing Clock (0xF8) adds a tick, Stop stops it.
+
+use crate::midi::error::{MidiError, MidiResult};
+use crate::midi::manager::PedalType;
+use crate::midi::clock::{Clock, SystemClock};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// MIDI clock resolution (pulses per quarter note)
+pub const TICKS_PER_BEAT: u64 = 24;
+
+/// System Real-Time messages the beat clock follows
+const TIMING_CLOCK: u8 = 0xF8;
+const START: u8 = 0xFA;
+const CONTINUE: u8 = 0xFB;
+const STOP: u8 = 0xFC;
+
+/// How long the worker sleeps with nothing due; anything new wakes it sooner
+const IDLE_WAIT: Duration = Duration::from_secs(3600);
+
+/// When a scheduled recall fires
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduleSpec {
+    /// This many milliseconds from now
+    Millis(u64),
+    /// This many beats from now, counted on the running MIDI clock
+    Beats(u32),
+}
+
+/// Tick counter for the MIDI clock an input port receives, for beat-quantized scheduling.
+/// Ticks are only counted from one port at a time: the last to send Start, or the first
+/// to tick while no clock is running (a clock already going when the port was opened).
+#[derive(Clone)]
+pub struct BeatClock {
+    state: Arc<Mutex<BeatClockState>>,
+    /// Wakes the scheduler's worker so beat schedules fire on the tick they are due
+    wake: Sender<()>,
+}
+
+#[derive(Default)]
+struct BeatClockState {
+    source: Option<String>,
+    tick: Option<u64>,
+}
+
+impl BeatClock {
+    /// Ticks since the clock started, or None while it is stopped
+    pub fn tick(&self) -> Option<u64> {
+        self.state.lock().map(|state| state.tick).unwrap_or(None)
+    }
+
+    /// Follow a message heard on `port_name`; anything but clock, Start, Continue and
+    /// Stop is ignored
+    pub fn handle(&self, port_name: &str, status: u8) {
+        let Ok(mut state) = self.state.lock() else { return };
+        let from_source = state.source.as_deref() == Some(port_name);
+        match status {
+            START => {
+                state.source = Some(port_name.to_string());
+                state.tick = Some(0);
+            }
+            CONTINUE if !from_source || state.tick.is_none() => {
+                state.source = Some(port_name.to_string());
+                state.tick = Some(0);
+            }
+            TIMING_CLOCK if from_source => {
+                state.tick = state.tick.map(|tick| tick + 1);
+            }
+            TIMING_CLOCK if state.tick.is_none() => {
+                state.source = Some(port_name.to_string());
+                state.tick = Some(0);
+            }
+            STOP if from_source => {
+                state.tick = None;
+            }
+            _ => return,
+        }
+        drop(state);
+        let _ = self.wake.send(());
+    }
+}
+
+/// A pending recall, as listed for the frontend
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ScheduledRecall {
+    pub id: u64,
+    pub device_name: String,
+    pub preset_id: String,
+    pub preset_name: String,
+    pub spec: ScheduleSpec,
+    /// Clock tick a beat-scheduled recall fires on
+    pub target_tick: Option<u64>,
+}
+
+/// What to recall once a schedule comes due
+#[derive(Debug, Clone)]
+pub struct RecallTarget {
+    pub pedal_type: PedalType,
+    pub parameters: serde_json::Value,
+}
+
+struct PendingRecall {
+    recall: ScheduledRecall,
+    target: RecallTarget,
+}
+
+/// When a job on the worker comes due
+enum Due {
+    /// Once the clock reads this
+    At(Duration),
+    /// Once the beat clock reaches this tick; dropped if the clock stops first
+    Tick(u64),
+}
+
+struct Job {
+    id: u64,
+    due: Due,
+    on_due: Box<dyn FnOnce(u64) + Send>,
+}
+
+enum WorkerEvent {
+    Schedule(Job),
+    Cancel(u64),
+    Shutdown,
+    #[cfg(test)]
+    CaughtUp(Sender<()>),
+}
+
+/// Pending scheduled recalls, by id
+pub struct RecallScheduler {
+    clock: Arc<dyn Clock>,
+    beat_clock: BeatClock,
+    events: Sender<WorkerEvent>,
+    pending: HashMap<u64, PendingRecall>,
+    next_id: u64,
+}
+
+impl Default for RecallScheduler {
+    fn default() -> Self {
+        Self::with_clock(Arc::new(SystemClock))
+    }
+}
+
+impl RecallScheduler {
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
+        let (events, event_receiver) = mpsc::channel();
+        let (wake, wake_receiver) = mpsc::channel();
+        let beat_clock = BeatClock { state: Arc::default(), wake };
+        let worker_clock = Arc::clone(&clock);
+        let worker_beats = beat_clock.clone();
+        thread::spawn(move || run_worker(worker_clock, worker_beats, event_receiver, wake_receiver));
+        Self {
+            clock,
+            beat_clock,
+            events,
+            pending: HashMap::new(),
+            next_id: 0,
+        }
+    }
+
+    /// The clock beat-based schedules count on; input ports feed it what they hear
+    pub fn beat_clock(&self) -> BeatClock {
+        self.beat_clock.clone()
+    }
+
+    /// Schedule a recall. `on_due` runs on the worker thread with the schedule's id once
+    /// it comes due; pass that id to `finish` to claim the recall.
+    pub fn schedule(
+        &mut self,
+        device_name: &str,
+        preset_id: &str,
+        preset_name: &str,
+        spec: ScheduleSpec,
+        target: RecallTarget,
+        on_due: impl FnOnce(u64) + Send + 'static,
+    ) -> MidiResult<ScheduledRecall> {
+        // Beats count from the clock's current tick, so the recall lands on a tick boundary
+        let due = match spec {
+            ScheduleSpec::Millis(ms) => Due::At(self.clock.now() + Duration::from_millis(ms)),
+            ScheduleSpec::Beats(beats) => {
+                let now = self.beat_clock.tick()
+                    .ok_or_else(|| MidiError::Other("Scheduling in beats needs the MIDI clock running".to_string()))?;
+                Due::Tick(now + beats as u64 * TICKS_PER_BEAT)
+            }
+        };
+
+        self.next_id += 1;
+        let id = self.next_id;
+        let recall = ScheduledRecall {
+            id,
+            device_name: device_name.to_string(),
+            preset_id: preset_id.to_string(),
+            preset_name: preset_name.to_string(),
+            spec,
+            target_tick: match due {
+                Due::Tick(tick) => Some(tick),
+                Due::At(_) => None,
+            },
+        };
+        self.pending.insert(id, PendingRecall { recall: recall.clone(), target });
+        self.send(WorkerEvent::Schedule(Job { id, due, on_due: Box::new(on_due) }));
+        Ok(recall)
+    }
+
+    /// Cancel a pending recall; returns whether there was one
+    pub fn cancel(&mut self, id: u64) -> bool {
+        let cancelled = self.pending.remove(&id).is_some();
+        if cancelled {
+            self.send(WorkerEvent::Cancel(id));
+        }
+        cancelled
+    }
+
+    /// Claim a recall that came due. None if it was cancelled meanwhile.
+    pub fn finish(&mut self, id: u64) -> Option<(ScheduledRecall, RecallTarget)> {
+        self.pending.remove(&id).map(|pending| (pending.recall, pending.target))
+    }
+
+    /// Pending recalls, oldest first
+    pub fn list(&self) -> Vec<ScheduledRecall> {
+        let mut recalls: Vec<ScheduledRecall> = self.pending.values().map(|pending| pending.recall.clone()).collect();
+        recalls.sort_by_key(|recall| recall.id);
+        recalls
+    }
+
+    /// Resolves once the worker has handled everything sent before it, including
+    /// running whatever came due
+    #[cfg(test)]
+    fn caught_up(&self) -> Receiver<()> {
+        let (done, caught_up) = mpsc::channel();
+        self.send(WorkerEvent::CaughtUp(done));
+        caught_up
+    }
+
+    fn send(&self, event: WorkerEvent) {
+        let _ = self.events.send(event);
+        let _ = self.beat_clock.wake.send(());
+    }
+}
+
+impl Drop for RecallScheduler {
+    fn drop(&mut self) {
+        self.send(WorkerEvent::Shutdown);
+    }
+}
+
+/// Run jobs as they come due until the scheduler is dropped
+fn run_worker(clock: Arc<dyn Clock>, beat_clock: BeatClock, events: Receiver<WorkerEvent>, wake: Receiver<()>) {
+    let mut jobs: Vec<Job> = Vec::new();
+    loop {
+        #[cfg(test)]
+        let mut caught_up = Vec::new();
+        loop {
+            match events.try_recv() {
+                Ok(WorkerEvent::Schedule(job)) => jobs.push(job),
+                Ok(WorkerEvent::Cancel(id)) => jobs.retain(|job| job.id != id),
+                Ok(WorkerEvent::Shutdown) => return,
+                #[cfg(test)]
+                Ok(WorkerEvent::CaughtUp(done)) => caught_up.push(done),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => return,
+            }
+        }
+
+        let now = clock.now();
+        let tick = beat_clock.tick();
+        // A beat schedule ends with the clock it was counting on
+        jobs.retain(|job| !matches!(job.due, Due::Tick(_)) || tick.is_some());
+        let (due, waiting): (Vec<Job>, Vec<Job>) = std::mem::take(&mut jobs).into_iter().partition(|job| match job.due {
+            Due::At(at) => at <= now,
+            Due::Tick(target) => tick.is_some_and(|tick| tick >= target),
+        });
+        jobs = waiting;
+        for job in due {
+            (job.on_due)(job.id);
+        }
+        #[cfg(test)]
+        for done in caught_up {
+            let _ = done.send(());
+        }
+
+        let next = jobs.iter()
+            .filter_map(|job| match job.due {
+                Due::At(at) => Some(at.saturating_sub(now)),
+                Due::Tick(_) => None,
+            })
+            .min()
+            .unwrap_or(IDLE_WAIT);
+        clock.wait(next, &wake);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockClock;
+
+    fn target() -> RecallTarget {
+        RecallTarget { pedal_type: PedalType::Microcosm, parameters: serde_json::json!({}) }
+    }
+
+    /// Ids of claimed recalls, with the beat clock's tick when each was claimed
+    type Fired = Arc<Mutex<Vec<(u64, Option<u64>)>>>;
+
+    /// Schedule on `scheduler`, recording the beat clock's tick whenever the recall is claimed
+    fn schedule(scheduler: &Arc<Mutex<RecallScheduler>>, spec: ScheduleSpec, fired: &Fired) -> MidiResult<ScheduledRecall> {
+        let mut locked = scheduler.lock().unwrap();
+        let (worker_scheduler, beat_clock, fired) = (Arc::clone(scheduler), locked.beat_clock(), Arc::clone(fired));
+        locked.schedule("Microcosm", "preset-1", "Verse", spec, target(), move |id| {
+            if worker_scheduler.lock().unwrap().finish(id).is_some() {
+                fired.lock().unwrap().push((id, beat_clock.tick()));
+            }
+        })
+    }
+
+    fn scheduler_with(clock: Arc<MockClock>) -> Arc<Mutex<RecallScheduler>> {
+        Arc::new(Mutex::new(RecallScheduler::with_clock(clock)))
+    }
+
+    /// Wait for the worker to handle every clock change and tick so far
+    fn caught_up(scheduler: &Arc<Mutex<RecallScheduler>>) {
+        let done = scheduler.lock().unwrap().caught_up();
+        done.recv_timeout(Duration::from_secs(5)).unwrap();
+    }
+
+    fn ticks(beat_clock: &BeatClock, count: u64) {
+        for _ in 0..count {
+            beat_clock.handle("Clock", TIMING_CLOCK);
+        }
+    }
+
+    #[test]
+    fn test_beats_quantize_to_clock_ticks() {
+        let scheduler = scheduler_with(Arc::new(MockClock::default()));
+        let beat_clock = scheduler.lock().unwrap().beat_clock();
+        beat_clock.handle("Clock", START);
+        ticks(&beat_clock, 10);
+        let fired = Arc::default();
+
+        let recall = schedule(&scheduler, ScheduleSpec::Beats(4), &fired).unwrap();
+        assert_eq!(recall.target_tick, Some(10 + 4 * TICKS_PER_BEAT));
+
+        ticks(&beat_clock, 4 * TICKS_PER_BEAT - 1);
+        caught_up(&scheduler);
+        assert!(fired.lock().unwrap().is_empty());
+
+        ticks(&beat_clock, 1);
+        caught_up(&scheduler);
+        assert_eq!(*fired.lock().unwrap(), vec![(recall.id, recall.target_tick)]);
+        assert!(scheduler.lock().unwrap().list().is_empty());
+    }
+
+    #[test]
+    fn test_beats_need_running_clock() {
+        let scheduler = scheduler_with(Arc::new(MockClock::default()));
+
+        let err = schedule(&scheduler, ScheduleSpec::Beats(4), &Arc::default()).unwrap_err();
+        assert!(err.to_string().contains("clock"), "{}", err);
+        assert!(scheduler.lock().unwrap().list().is_empty());
+    }
+
+    #[test]
+    fn test_stopped_clock_drops_beat_schedules() {
+        let scheduler = scheduler_with(Arc::new(MockClock::default()));
+        let beat_clock = scheduler.lock().unwrap().beat_clock();
+        beat_clock.handle("Clock", START);
+        let fired = Arc::default();
+
+        schedule(&scheduler, ScheduleSpec::Beats(1), &fired).unwrap();
+        beat_clock.handle("Clock", STOP);
+        caught_up(&scheduler);
+        beat_clock.handle("Clock", START);
+        ticks(&beat_clock, TICKS_PER_BEAT);
+        caught_up(&scheduler);
+        assert!(fired.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_beat_clock_counts_one_port() {
+        let scheduler = RecallScheduler::with_clock(Arc::new(MockClock::default()));
+        let beat_clock = scheduler.beat_clock();
+        assert_eq!(beat_clock.tick(), None);
+
+        // A clock already running when its port was opened counts from its first tick
+        ticks(&beat_clock, 3);
+        assert_eq!(beat_clock.tick(), Some(2));
+
+        // Another port's clock is ignored until it sends Start
+        beat_clock.handle("Other", TIMING_CLOCK);
+        beat_clock.handle("Other", STOP);
+        assert_eq!(beat_clock.tick(), Some(2));
+        beat_clock.handle("Other", START);
+        beat_clock.handle("Other", TIMING_CLOCK);
+        beat_clock.handle("Clock", TIMING_CLOCK);
+        assert_eq!(beat_clock.tick(), Some(1));
+
+        beat_clock.handle("Other", STOP);
+        assert_eq!(beat_clock.tick(), None);
+    }
+
+    #[test]
+    fn test_millis_fire_after_delay_unless_cancelled() {
+        let clock = Arc::new(MockClock::default());
+        let scheduler = scheduler_with(Arc::clone(&clock));
+        let fired = Arc::default();
+
+        let kept = schedule(&scheduler, ScheduleSpec::Millis(1000), &fired).unwrap();
+        let cancelled = schedule(&scheduler, ScheduleSpec::Millis(1000), &fired).unwrap();
+        assert_eq!(scheduler.lock().unwrap().list().len(), 2);
+        assert!(scheduler.lock().unwrap().cancel(cancelled.id));
+
+        clock.advance(Duration::from_millis(999));
+        caught_up(&scheduler);
+        assert!(fired.lock().unwrap().is_empty());
+
+        clock.advance(Duration::from_millis(1));
+        caught_up(&scheduler);
+        let fired: Vec<u64> = fired.lock().unwrap().iter().map(|(id, _)| *id).collect();
+        assert_eq!(fired, vec![kept.id]);
+    }
+}
@@ -0,0 +1,363 @@
+// Ordered CC sends for recalling a full pedal state
+// `to_cc_map` is a HashMap, so sending straight from it went out in a different order
+// every run. A SendPlan fixes the order: routing and bypass first so the signal path is
+// settled, then mode/enum selections, then continuous values. Momentary triggers (tap,
+// capture, retrigger) are never part of a recall.
+// A recall can also glide: chosen continuous CCs (mix, volume…) ramp from where the pedal
+// is to the target in a few steps instead of jumping, so the change doesn't click.
+// A plan's estimate is its timeline before anything is sent; progress while sending is
+// read off the same estimate, so "step 12 of 37" is the step the estimate promised.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Emitted with a `SendProgressEvent` after each step of a plan goes out
+pub const SEND_PROGRESS_EVENT: &str = "send-progress";
+
+/// Pause after each CC unless a step overrides it - keeps pedal input buffers from overflowing
+pub const DEFAULT_SEND_DELAY: Duration = Duration::from_millis(20);
+
+/// Steps a glide takes when the recall doesn't say
+pub const DEFAULT_GLIDE_STEPS: u32 = 5;
+
+/// Longest a glide may take. The device stays locked while its recall runs, so a
+/// typo'd `glide_ms` mustn't hold every other send for minutes.
+pub const MAX_GLIDE: Duration = Duration::from_secs(2);
+
+/// How a recall ramps continuous CCs instead of jumping them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Glide {
+    /// From the first ramp value to the target
+    pub duration: Duration,
+    pub steps: u32,
+    /// CCs to ramp; every other CC is sent once, before the ramps start
+    pub ccs: Vec<u8>,
+}
+
+impl Glide {
+    /// A glide over `glide_ms` (capped at `MAX_GLIDE`), or None for 0 (jump straight
+    /// to the target)
+    pub fn from_millis(glide_ms: u64, ccs: Vec<u8>) -> Option<Self> {
+        let duration = Duration::from_millis(glide_ms).min(MAX_GLIDE);
+        (glide_ms > 0).then(|| Self { duration, steps: DEFAULT_GLIDE_STEPS, ccs })
+    }
+}
+
+/// One CC in a send plan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlannedCc {
+    pub cc_number: u8,
+    pub value: u8,
+    /// Pause after sending, if it differs from `DEFAULT_SEND_DELAY`
+    pub delay: Option<Duration>,
+}
+
+impl PlannedCc {
+    /// How long to wait after sending this CC
+    pub fn delay(&self) -> Duration {
+        self.delay.unwrap_or(DEFAULT_SEND_DELAY)
+    }
+}
+
+/// CCs to send, in order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SendPlan {
+    steps: Vec<PlannedCc>,
+}
+
+impl SendPlan {
+    /// Order `cc_map` for sending: `routing` CCs first, then `selections`, each in the
+    /// order given; every other CC follows as a continuous value in CC number order.
+    /// `triggers` are dropped. CCs listed but missing from the map are skipped.
+    pub fn grouped(mut cc_map: HashMap<u8, u8>, routing: &[u8], selections: &[u8], triggers: &[u8]) -> Self {
+        for cc_number in triggers {
+            cc_map.remove(cc_number);
+        }
+
+        let mut steps = Vec::with_capacity(cc_map.len());
+        for cc_number in routing.iter().chain(selections) {
+            if let Some(value) = cc_map.remove(cc_number) {
+                steps.push(PlannedCc { cc_number: *cc_number, value, delay: None });
+            }
+        }
+
+        let mut continuous: Vec<(u8, u8)> = cc_map.into_iter().collect();
+        continuous.sort_by_key(|(cc_number, _)| *cc_number);
+        steps.extend(
+            continuous
+                .into_iter()
+                .map(|(cc_number, value)| PlannedCc { cc_number, value, delay: None }),
+        );
+
+        Self { steps }
+    }
+
+    /// Send `ccs` exactly in the order given, with the default pause after each
+    pub fn in_order(ccs: impl IntoIterator<Item = (u8, u8)>) -> Self {
+        Self {
+            steps: ccs
+                .into_iter()
+                .map(|(cc_number, value)| PlannedCc { cc_number, value, delay: None })
+                .collect(),
+        }
+    }
+
+    /// Send `ccs` after everything already planned, in the order given. Unlike `grouped`,
+    /// a CC may appear more than once (an NRPN write reuses CCs 98/99/6/38 each time).
+    pub fn followed_by(mut self, ccs: impl IntoIterator<Item = (u8, u8)>) -> Self {
+        self.steps.extend(Self::in_order(ccs).steps);
+        self
+    }
+
+    /// Wait `delay` after `cc_number` instead of the default (no-op if it isn't planned)
+    pub fn with_delay(mut self, cc_number: u8, delay: Duration) -> Self {
+        if let Some(step) = self.steps.iter_mut().find(|step| step.cc_number == cc_number) {
+            step.delay = Some(delay);
+        }
+        self
+    }
+
+    /// Only the steps whose value differs from `current` (CCs missing from it are kept)
+    pub fn without_unchanged(mut self, current: &HashMap<u8, u8>) -> Self {
+        self.steps.retain(|step| current.get(&step.cc_number) != Some(&step.value));
+        self
+    }
+
+    /// Ramp the glide's CCs from their `current` values: the other steps go first, as
+    /// planned, then `glide.steps` rounds each move every ramping CC a step closer, the
+    /// last round landing on the target. A round takes `duration / steps`, split evenly
+    /// between its CCs. CCs with no current value, or already at the target, aren't ramped.
+    pub fn with_glide(self, glide: &Glide, current: &HashMap<u8, u8>) -> Self {
+        let rounds = glide.steps.max(1);
+        let (ramps, mut steps): (Vec<PlannedCc>, Vec<PlannedCc>) = self.steps.into_iter().partition(|step| {
+            glide.ccs.contains(&step.cc_number)
+                && current.get(&step.cc_number).is_some_and(|from| *from != step.value)
+        });
+        if ramps.is_empty() {
+            return Self { steps };
+        }
+
+        let delay = glide.duration / rounds / ramps.len() as u32;
+        for round in 1..=rounds {
+            for ramp in &ramps {
+                let from = u32::from(current[&ramp.cc_number]);
+                let to = u32::from(ramp.value);
+                // Rounded linear interpolation; the last round is exactly `to`
+                let value = (from * (rounds - round) + to * round + rounds / 2) / rounds;
+                steps.push(PlannedCc { cc_number: ramp.cc_number, value: value as u8, delay: Some(delay) });
+            }
+        }
+        Self { steps }
+    }
+
+    pub fn steps(&self) -> &[PlannedCc] {
+        &self.steps
+    }
+
+    /// CC numbers in send order
+    pub fn cc_numbers(&self) -> Vec<u8> {
+        self.steps.iter().map(|step| step.cc_number).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// How many messages this plan sends and how long it takes, step by step
+    pub fn estimate(&self) -> PlanEstimate {
+        let mut starts_at = Duration::ZERO;
+        let steps: Vec<StepEstimate> = self
+            .steps
+            .iter()
+            .map(|step| {
+                let estimate = StepEstimate {
+                    cc_number: step.cc_number,
+                    value: step.value,
+                    starts_at_ms: starts_at.as_millis() as u64,
+                    delay_ms: step.delay().as_millis() as u64,
+                };
+                starts_at += step.delay();
+                estimate
+            })
+            .collect();
+
+        PlanEstimate {
+            message_count: steps.len(),
+            duration_ms: starts_at.as_millis() as u64,
+            steps,
+        }
+    }
+}
+
+/// One step of an estimated plan
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepEstimate {
+    pub cc_number: u8,
+    pub value: u8,
+    /// When the CC goes out, from the start of the plan
+    pub starts_at_ms: u64,
+    /// Pause after it before the next step
+    pub delay_ms: u64,
+}
+
+/// A plan's timeline, worked out without sending anything
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanEstimate {
+    pub message_count: usize,
+    /// Every step's send and pause, end to end
+    pub duration_ms: u64,
+    pub steps: Vec<StepEstimate>,
+}
+
+impl PlanEstimate {
+    /// Where the plan stands once `sent` steps (and their pauses) are done
+    pub fn progress(&self, sent: usize) -> SendProgress {
+        let elapsed_ms = match self.steps.get(sent) {
+            Some(next) => next.starts_at_ms,
+            None => self.duration_ms,
+        };
+        SendProgress {
+            step: sent.min(self.message_count),
+            total: self.message_count,
+            elapsed_ms,
+            remaining_ms: self.duration_ms - elapsed_ms,
+        }
+    }
+}
+
+/// How far a plan being sent has got, by its estimate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SendProgress {
+    /// Steps sent so far, 1-based once the first has gone out
+    pub step: usize,
+    pub total: usize,
+    pub elapsed_ms: u64,
+    pub remaining_ms: u64,
+}
+
+/// Payload of `send-progress`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SendProgressEvent {
+    pub device_name: String,
+    #[serde(flatten)]
+    pub progress: SendProgress,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cc_map(pairs: &[(u8, u8)]) -> HashMap<u8, u8> {
+        pairs.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_groups_go_out_in_order() {
+        let plan = SendPlan::grouped(
+            cc_map(&[(14, 1), (9, 2), (102, 127), (21, 3), (93, 127), (5, 4)]),
+            &[102],
+            &[21, 5],
+            &[93],
+        );
+
+        assert_eq!(plan.cc_numbers(), vec![102, 21, 5, 9, 14]);
+        assert_eq!(plan.steps()[0].value, 127);
+    }
+
+    #[test]
+    fn test_missing_group_members_are_skipped() {
+        let plan = SendPlan::grouped(cc_map(&[(14, 1)]), &[102, 103], &[21], &[]);
+        assert_eq!(plan.cc_numbers(), vec![14]);
+    }
+
+    #[test]
+    fn test_without_unchanged_keeps_order() {
+        let plan = SendPlan::grouped(cc_map(&[(9, 1), (14, 2), (21, 3)]), &[], &[21], &[])
+            .without_unchanged(&cc_map(&[(9, 1), (14, 5)]));
+        assert_eq!(plan.cc_numbers(), vec![21, 14]);
+    }
+
+    #[test]
+    fn test_in_order_keeps_duplicates_and_order() {
+        let plan = SendPlan::in_order([(21, 1), (9, 2), (21, 3)]);
+        assert_eq!(plan.cc_numbers(), vec![21, 9, 21]);
+        assert_eq!(plan.steps()[2].value, 3);
+    }
+
+    #[test]
+    fn test_glide_ramps_0_to_127_in_5_steps() {
+        let glide = Glide::from_millis(150, vec![9]).unwrap();
+        let plan = SendPlan::grouped(cc_map(&[(9, 127), (5, 3)]), &[], &[5], &[])
+            .with_glide(&glide, &cc_map(&[(9, 0), (5, 1)]));
+
+        let sent: Vec<(u8, u8)> = plan.steps().iter().map(|step| (step.cc_number, step.value)).collect();
+        assert_eq!(sent, vec![(5, 3), (9, 25), (9, 51), (9, 76), (9, 102), (9, 127)]);
+        assert_eq!(plan.steps()[0].delay(), DEFAULT_SEND_DELAY);
+        assert!(plan.steps()[1..].iter().all(|step| step.delay() == Duration::from_millis(30)));
+    }
+
+    #[test]
+    fn test_glide_interleaves_ccs_and_skips_ones_it_cant_ramp() {
+        let glide = Glide { duration: Duration::from_millis(100), steps: 2, ccs: vec![9, 16, 12, 8] };
+        // 12 is already at its target, 8 has no known current value
+        let plan = SendPlan::grouped(cc_map(&[(9, 100), (16, 0), (12, 50), (8, 70)]), &[], &[], &[])
+            .with_glide(&glide, &cc_map(&[(9, 0), (16, 100), (12, 50)]));
+
+        let sent: Vec<(u8, u8)> = plan.steps().iter().map(|step| (step.cc_number, step.value)).collect();
+        assert_eq!(sent, vec![(8, 70), (12, 50), (9, 50), (16, 50), (9, 100), (16, 0)]);
+        assert_eq!(plan.steps()[2].delay(), Duration::from_millis(25));
+        assert!(Glide::from_millis(0, vec![9]).is_none());
+        assert_eq!(Glide::from_millis(600_000, vec![9]).unwrap().duration, MAX_GLIDE);
+    }
+
+    #[test]
+    fn test_estimate_adds_up_every_pause() {
+        let plan = SendPlan::in_order([(102, 127), (9, 64), (14, 3)]).with_delay(9, Duration::from_millis(100));
+        let estimate = plan.estimate();
+
+        assert_eq!(estimate.message_count, 3);
+        assert_eq!(estimate.duration_ms, 20 + 100 + 20);
+        let starts: Vec<u64> = estimate.steps.iter().map(|step| step.starts_at_ms).collect();
+        assert_eq!(starts, vec![0, 20, 120]);
+        assert_eq!(estimate.steps[1].delay_ms, 100);
+    }
+
+    #[test]
+    fn test_estimate_includes_glide_rounds() {
+        let glide = Glide::from_millis(150, vec![9]).unwrap();
+        let plan = SendPlan::grouped(cc_map(&[(9, 127), (5, 3)]), &[], &[5], &[])
+            .with_glide(&glide, &cc_map(&[(9, 0), (5, 1)]));
+        let estimate = plan.estimate();
+
+        assert_eq!(estimate.message_count, 6);
+        assert_eq!(estimate.duration_ms, 20 + 150);
+    }
+
+    #[test]
+    fn test_progress_follows_estimate() {
+        let estimate = SendPlan::in_order([(1, 0), (2, 0), (3, 0)]).with_delay(2, Duration::from_millis(50)).estimate();
+
+        assert_eq!(estimate.progress(0), SendProgress { step: 0, total: 3, elapsed_ms: 0, remaining_ms: 90 });
+        assert_eq!(estimate.progress(2), SendProgress { step: 2, total: 3, elapsed_ms: 70, remaining_ms: 20 });
+        assert_eq!(estimate.progress(3), SendProgress { step: 3, total: 3, elapsed_ms: 90, remaining_ms: 0 });
+        assert_eq!(SendPlan::default().estimate().progress(0).total, 0);
+    }
+
+    #[test]
+    fn test_delay_override() {
+        let plan = SendPlan::grouped(cc_map(&[(14, 1), (16, 2)]), &[], &[], &[])
+            .with_delay(16, Duration::from_millis(80));
+
+        assert_eq!(plan.steps()[0].delay(), DEFAULT_SEND_DELAY);
+        assert_eq!(plan.steps()[1].delay(), Duration::from_millis(80));
+    }
+}
@@ -0,0 +1,104 @@
+// Field-level diffs between two pedal states
+// Recalls and program changes replace a device's state wholesale. Comparing the
+// serialized states before and after tells the frontend which controls actually
+// moved, and the same comparison works for any two presets of one pedal type.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// One field whose value differs between two states
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Value,
+    pub new: Value,
+}
+
+/// Compare two values field by field
+pub trait StateDiff {
+    /// Fields that differ going from `self` to `other`, in field order
+    fn diff(&self, other: &Self) -> Vec<FieldChange>;
+}
+
+impl<T: Serialize> StateDiff for T {
+    fn diff(&self, other: &Self) -> Vec<FieldChange> {
+        match (serde_json::to_value(self), serde_json::to_value(other)) {
+            (Ok(before), Ok(after)) => diff_json(&before, &after),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Diff two serialized states by their top-level fields. A field missing on one side
+/// shows up as null there; non-object values are compared as a single "" field.
+pub fn diff_json(before: &Value, after: &Value) -> Vec<FieldChange> {
+    let (Value::Object(before_fields), Value::Object(after_fields)) = (before, after) else {
+        if before == after {
+            return Vec::new();
+        }
+        return vec![FieldChange {
+            field: String::new(),
+            old: before.clone(),
+            new: after.clone(),
+        }];
+    };
+
+    let mut fields: Vec<&String> = before_fields.keys().chain(after_fields.keys()).collect();
+    fields.sort();
+    fields.dedup();
+
+    fields
+        .into_iter()
+        .filter_map(|field| {
+            let old = before_fields.get(field).cloned().unwrap_or(Value::Null);
+            let new = after_fields.get(field).cloned().unwrap_or(Value::Null);
+            (old != new).then(|| FieldChange {
+                field: field.clone(),
+                old,
+                new,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Serialize)]
+    struct Knobs {
+        mix: u8,
+        time: u8,
+        hold: bool,
+    }
+
+    #[test]
+    fn test_identical_states_have_no_changes() {
+        let knobs = Knobs { mix: 64, time: 10, hold: false };
+        assert!(knobs.diff(&Knobs { mix: 64, time: 10, hold: false }).is_empty());
+    }
+
+    #[test]
+    fn test_diff_reports_only_changed_fields() {
+        let before = Knobs { mix: 64, time: 10, hold: false };
+        let after = Knobs { mix: 100, time: 10, hold: true };
+
+        assert_eq!(
+            before.diff(&after),
+            vec![
+                FieldChange { field: "hold".to_string(), old: json!(false), new: json!(true) },
+                FieldChange { field: "mix".to_string(), old: json!(64), new: json!(100) },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_missing_fields_diff_against_null() {
+        let changes = diff_json(&json!({ "mix": 1 }), &json!({ "mix": 1, "tone": 5 }));
+        assert_eq!(
+            changes,
+            vec![FieldChange { field: "tone".to_string(), old: Value::Null, new: json!(5) }]
+        );
+    }
+}
@@ -0,0 +1,98 @@
+// Device state snapshots for undo
+// Recalling a preset overwrites whatever the pedal was set to, so we keep a short
+// per-device history of the believed state that can be recalled again.
+
+use crate::midi::manager::PedalType;
+use std::collections::{HashMap, VecDeque};
+use std::time::Instant;
+
+/// Snapshots kept per device - older ones are dropped
+pub const MAX_SNAPSHOTS: usize = 10;
+
+/// Believed state of a device at a point in time
+#[derive(Debug, Clone)]
+pub struct StateSnapshot {
+    pub device_name: String,
+    pub pedal_type: PedalType,
+    pub timestamp: Instant,
+    pub cc_map: HashMap<u8, u8>,
+    /// Full pedal state (serialized like a preset) so non-CC fields restore too
+    pub(crate) state: serde_json::Value,
+}
+
+/// Bounded LIFO of snapshots for one device
+#[derive(Debug, Default)]
+pub struct SnapshotStack {
+    snapshots: VecDeque<StateSnapshot>,
+}
+
+impl SnapshotStack {
+    /// Push a snapshot, dropping the oldest one when full
+    pub fn push(&mut self, snapshot: StateSnapshot) {
+        if self.snapshots.len() == MAX_SNAPSHOTS {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(snapshot);
+    }
+
+    /// The most recent snapshot, left on the stack
+    pub fn peek(&self) -> Option<&StateSnapshot> {
+        self.snapshots.back()
+    }
+
+    /// Take the most recent snapshot
+    pub fn pop(&mut self) -> Option<StateSnapshot> {
+        self.snapshots.pop_back()
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(value: u8) -> StateSnapshot {
+        StateSnapshot {
+            device_name: "Microcosm".to_string(),
+            pedal_type: PedalType::Microcosm,
+            timestamp: Instant::now(),
+            cc_map: HashMap::from([(14, value)]),
+            state: serde_json::json!({}),
+        }
+    }
+
+    #[test]
+    fn test_pop_returns_most_recent_first() {
+        let mut stack = SnapshotStack::default();
+        stack.push(snapshot(1));
+        stack.push(snapshot(2));
+
+        assert_eq!(stack.pop().unwrap().cc_map[&14], 2);
+        assert_eq!(stack.pop().unwrap().cc_map[&14], 1);
+        assert!(stack.pop().is_none());
+    }
+
+    #[test]
+    fn test_oldest_dropped_when_full() {
+        let mut stack = SnapshotStack::default();
+        for value in 0..(MAX_SNAPSHOTS as u8 + 3) {
+            stack.push(snapshot(value));
+        }
+
+        assert_eq!(stack.len(), MAX_SNAPSHOTS);
+
+        let mut oldest = None;
+        while let Some(s) = stack.pop() {
+            oldest = Some(s.cc_map[&14]);
+        }
+        assert_eq!(oldest, Some(3));
+        assert!(stack.is_empty());
+    }
+}
@@ -0,0 +1,188 @@
+// Virtual "Librarian" MIDI port
+// Lets other gear see Librarian as a MIDI device: answers Universal Device Inquiry
+// and forwards incoming CC/PC so external controllers can drive the app.
+
+use crate::midi::error::MidiResult;
+use serde::{Deserialize, Serialize};
+
+/// Default name for the virtual port pair
+pub const DEFAULT_VIRTUAL_PORT_NAME: &str = "Librarian";
+
+/// Manufacturer ID 0x7D is reserved for non-commercial/educational use
+const LIBRARIAN_MANUFACTURER_ID: u8 = 0x7D;
+
+/// Device ID used in our Identity Reply (0x7F = "all call", we don't have a configurable ID)
+const LIBRARIAN_DEVICE_ID: u8 = 0x7F;
+
+/// Whether this platform can create virtual MIDI ports
+/// CoreMIDI (macOS), ALSA and JACK support them; WinMM on Windows does not.
+pub fn virtual_ports_supported() -> bool {
+    cfg!(unix)
+}
+
+/// CC/PC received on the virtual port
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum VirtualPortMessage {
+    ControlChange { channel: u8, cc_number: u8, value: u8 },
+    ProgramChange { channel: u8, program: u8 },
+}
+
+impl VirtualPortMessage {
+    /// Parse a channel voice message (channel is 1-16). Anything other than CC/PC is ignored.
+    pub fn parse(message: &[u8]) -> Option<Self> {
+        let status = *message.first()?;
+        let channel = (status & 0x0F) + 1;
+
+        match status & 0xF0 {
+            0xB0 if message.len() >= 3 => Some(Self::ControlChange {
+                channel,
+                cc_number: message[1],
+                value: message[2],
+            }),
+            0xC0 if message.len() >= 2 => Some(Self::ProgramChange {
+                channel,
+                program: message[1],
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Check for an Identity Request addressed to any device ID: F0 7E nn 06 01 F7
+pub fn is_identity_request(message: &[u8]) -> bool {
+    matches!(message, [0xF0, 0x7E, _, 0x06, 0x01, 0xF7])
+}
+
+/// Build Librarian's Identity Reply
+/// Format: F0 7E [device] 06 02 [mfg] [family LSB] [family MSB] [model LSB] [model MSB] [version x4] F7
+/// Software version carries the crate's major/minor/patch.
+pub fn identity_reply() -> Vec<u8> {
+    let mut version = env!("CARGO_PKG_VERSION")
+        .split('.')
+        .map(|part| part.parse::<u8>().unwrap_or(0) & 0x7F);
+
+    vec![
+        0xF0, 0x7E, LIBRARIAN_DEVICE_ID, 0x06, 0x02,
+        LIBRARIAN_MANUFACTURER_ID,
+        0x01, 0x00, // Family
+        0x01, 0x00, // Model
+        version.next().unwrap_or(0),
+        version.next().unwrap_or(0),
+        version.next().unwrap_or(0),
+        0x00,
+        0xF7,
+    ]
+}
+
+/// Open virtual input/output port pair
+pub struct VirtualPort {
+    name: String,
+    #[allow(dead_code)]
+    input: midir::MidiInputConnection<()>,
+    #[allow(dead_code)]
+    output: std::sync::Arc<std::sync::Mutex<midir::MidiOutputConnection>>,
+}
+
+impl VirtualPort {
+    /// Create the virtual port pair. Identity Requests are answered on the output;
+    /// CC/PC messages are handed to `on_message`.
+    #[cfg(unix)]
+    pub fn open<F>(name: &str, on_message: F) -> MidiResult<Self>
+    where
+        F: Fn(VirtualPortMessage) + Send + 'static,
+    {
+        use midir::os::unix::{VirtualInput, VirtualOutput};
+        use midir::{Ignore, MidiInput, MidiOutput};
+        use std::sync::{Arc, Mutex};
+
+        let midi_out = MidiOutput::new("Librarian Virtual Output")?;
+        let output = Arc::new(Mutex::new(midi_out.create_virtual(name)?));
+
+        let mut midi_in = MidiInput::new("Librarian Virtual Input")?;
+        midi_in.ignore(Ignore::None);
+
+        let reply_output = Arc::clone(&output);
+        let input = midi_in.create_virtual(
+            name,
+            move |_stamp, message, _| {
+                if is_identity_request(message) {
+                    println!("📥 [Virtual Port] Identity Request, replying");
+                    if let Ok(mut out) = reply_output.lock() {
+                        if let Err(e) = out.send(&identity_reply()) {
+                            eprintln!("❌ [Virtual Port] Failed to send Identity Reply: {}", e);
+                        }
+                    }
+                } else if let Some(msg) = VirtualPortMessage::parse(message) {
+                    on_message(msg);
+                }
+            },
+            (),
+        )?;
+
+        println!("✅ [Virtual Port] Opened \"{}\"", name);
+        Ok(Self {
+            name: name.to_string(),
+            input,
+            output,
+        })
+    }
+
+    /// Virtual ports aren't available on this platform
+    #[cfg(not(unix))]
+    pub fn open<F>(_name: &str, _on_message: F) -> MidiResult<Self>
+    where
+        F: Fn(VirtualPortMessage) + Send + 'static,
+    {
+        Err(crate::midi::error::MidiError::Unsupported(
+            "Virtual MIDI ports are not supported on this platform".to_string(),
+        ))
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::midi::identity::parse_identity_reply;
+
+    #[test]
+    fn test_is_identity_request_any_device_id() {
+        assert!(is_identity_request(&[0xF0, 0x7E, 0x7F, 0x06, 0x01, 0xF7]));
+        assert!(is_identity_request(&[0xF0, 0x7E, 0x03, 0x06, 0x01, 0xF7]));
+        // Identity Reply, not a request
+        assert!(!is_identity_request(&[0xF0, 0x7E, 0x7F, 0x06, 0x02, 0xF7]));
+        assert!(!is_identity_request(&[0xB0, 0x07, 0x40]));
+    }
+
+    #[test]
+    fn test_identity_reply_parses() {
+        let reply = identity_reply();
+        let identity = parse_identity_reply(&reply).unwrap();
+        assert_eq!(identity.manufacturer_id, vec![LIBRARIAN_MANUFACTURER_ID]);
+        assert_eq!(identity.device_family, 1);
+        assert_eq!(identity.device_model, 1);
+        assert_eq!(identity.software_version.len(), 4);
+        assert!(reply.iter().skip(1).take(reply.len() - 2).all(|b| *b < 0x80));
+    }
+
+    #[test]
+    fn test_parse_cc_and_pc() {
+        assert_eq!(
+            VirtualPortMessage::parse(&[0xB2, 14, 100]),
+            Some(VirtualPortMessage::ControlChange { channel: 3, cc_number: 14, value: 100 })
+        );
+        assert_eq!(
+            VirtualPortMessage::parse(&[0xCF, 45]),
+            Some(VirtualPortMessage::ProgramChange { channel: 16, program: 45 })
+        );
+        // Note On, truncated CC, clock
+        assert_eq!(VirtualPortMessage::parse(&[0x90, 60, 100]), None);
+        assert_eq!(VirtualPortMessage::parse(&[0xB0, 14]), None);
+        assert_eq!(VirtualPortMessage::parse(&[0xF8]), None);
+        assert_eq!(VirtualPortMessage::parse(&[]), None);
+    }
+}
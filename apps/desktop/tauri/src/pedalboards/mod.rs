@@ -0,0 +1,468 @@
+// Pedalboards bounded context - aggregate root
+// Groups pedals with their saved port/channel setup so a whole rig connects in one step.
+// The active board is the one launch reconnects and whose scenes can be recalled.
+
+mod types;
+mod repository;
+
+pub use types::*;
+use repository::PedalboardRepository;
+
+use crate::midi::error::MidiError;
+use crate::midi::{MidiManager, PedalType};
+use crate::presets::PresetChangeEvent;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// Connects and disconnects pedals for pedalboard activation
+/// Implemented by `MidiManager`; tests substitute their own.
+pub trait DeviceConnector {
+    /// Connect a pedal; Ok(false) means it was already connected
+    fn connect_device(&mut self, pedal_type: &str, port_match: &str, midi_channel: u8) -> std::result::Result<bool, String>;
+    fn disconnect_device(&mut self, port_match: &str) -> std::result::Result<(), String>;
+}
+
+impl DeviceConnector for MidiManager {
+    fn connect_device(&mut self, pedal_type: &str, port_match: &str, midi_channel: u8) -> std::result::Result<bool, String> {
+        let pedal_type = PedalType::from_name(pedal_type)
+            .ok_or_else(|| format!("Unknown pedal type: {}", pedal_type))?;
+
+        match self.connect(pedal_type, port_match, midi_channel) {
+            Ok(()) => Ok(true),
+            Err(MidiError::AlreadyConnected(_)) => Ok(false),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    fn disconnect_device(&mut self, port_match: &str) -> std::result::Result<(), String> {
+        match self.disconnect(port_match) {
+            Ok(()) | Err(MidiError::NotConnected(_)) => Ok(()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+/// Recalls library presets on connected pedals for scenes
+/// Implemented by `MidiManager`; tests substitute their own.
+pub trait PresetRecaller {
+    fn recall_preset(&mut self, device_name: &str, pedal_type: &str, parameters: serde_json::Value) -> std::result::Result<(), String>;
+}
+
+impl PresetRecaller for MidiManager {
+    fn recall_preset(&mut self, device_name: &str, pedal_type: &str, parameters: serde_json::Value) -> std::result::Result<(), String> {
+        let pedal_type = PedalType::from_name(pedal_type)
+            .ok_or_else(|| format!("Unknown pedal type: {}", pedal_type))?;
+        self.recall_preset_json(device_name, pedal_type, parameters)
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Pedalboard library - aggregate root for pedalboard management
+pub struct PedalboardLibrary {
+    repository: PedalboardRepository,
+}
+
+impl PedalboardLibrary {
+    /// Create a new pedalboard library with the given database path
+    pub fn new(db_path: PathBuf) -> Result<Self> {
+        Ok(Self {
+            repository: PedalboardRepository::new(db_path)?,
+        })
+    }
+
+    /// Create a new pedalboard
+    pub fn create_pedalboard(&self, name: String, members: Vec<PedalboardMember>) -> Result<Pedalboard> {
+        let name = Self::validate_name(&name)?;
+        Self::validate_members(&members)?;
+
+        if self.repository.find_by_name(&name)?.is_some() {
+            return Err(PedalboardError::DuplicateName { name });
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        let pedalboard = Pedalboard {
+            id: PedalboardId::generate(),
+            name,
+            members,
+            is_active: false,
+            created_at: now,
+            updated_at: now,
+            connected_by_activation: Vec::new(),
+            scenes: Vec::new(),
+        };
+
+        self.repository.save(&pedalboard)?;
+
+        Ok(pedalboard)
+    }
+
+    /// Update a pedalboard's name and/or members
+    pub fn update_pedalboard(
+        &self,
+        id: &PedalboardId,
+        name: Option<String>,
+        members: Option<Vec<PedalboardMember>>,
+    ) -> Result<Pedalboard> {
+        let mut pedalboard = self.get_pedalboard(id)?;
+
+        if let Some(name) = name {
+            let name = Self::validate_name(&name)?;
+            if let Some(existing) = self.repository.find_by_name(&name)? {
+                if existing.id != pedalboard.id {
+                    return Err(PedalboardError::DuplicateName { name });
+                }
+            }
+            pedalboard.name = name;
+        }
+
+        if let Some(members) = members {
+            Self::validate_members(&members)?;
+            pedalboard.members = members;
+        }
+
+        pedalboard.updated_at = chrono::Utc::now().timestamp();
+        self.repository.save(&pedalboard)?;
+
+        Ok(pedalboard)
+    }
+
+    /// Delete a pedalboard (does not touch connections; deactivate first if needed)
+    pub fn delete_pedalboard(&self, id: &PedalboardId) -> Result<()> {
+        self.repository.delete(id)
+    }
+
+    /// Get a pedalboard by ID
+    pub fn get_pedalboard(&self, id: &PedalboardId) -> Result<Pedalboard> {
+        self.repository
+            .find_by_id(id)?
+            .ok_or_else(|| PedalboardError::NotFound { id: id.to_string() })
+    }
+
+    /// List all pedalboards
+    pub fn list_pedalboards(&self) -> Result<Vec<Pedalboard>> {
+        self.repository.list()
+    }
+
+    /// Get the currently active pedalboard, if any
+    pub fn get_active_pedalboard(&self) -> Result<Option<Pedalboard>> {
+        Ok(self.repository.list()?.into_iter().find(|p| p.is_active))
+    }
+
+    /// Connect every member of a pedalboard and mark it active.
+    /// A member that fails to connect doesn't stop the others - the result reports each one.
+    pub fn activate_pedalboard(
+        &self,
+        id: &PedalboardId,
+        connector: &mut dyn DeviceConnector,
+    ) -> Result<PedalboardActivation> {
+        let pedalboard = self.get_pedalboard(id)?;
+        let activation = Self::connect_members(&pedalboard, connector);
+        self.mark_active(&activation)?;
+        Ok(activation)
+    }
+
+    /// Connect every member of a pedalboard, reporting each one. Touches no stored state.
+    pub fn connect_members(pedalboard: &Pedalboard, connector: &mut dyn DeviceConnector) -> PedalboardActivation {
+        let results = pedalboard
+            .members
+            .iter()
+            .map(|member| {
+                match connector.connect_device(&member.pedal_type, &member.port_match, member.midi_channel) {
+                    Ok(newly_connected) => MemberConnectResult {
+                        member: member.clone(),
+                        connected: true,
+                        already_connected: !newly_connected,
+                        error: None,
+                    },
+                    Err(e) => {
+                        println!(
+                            "⚠️ [Pedalboards] Failed to connect {} on \"{}\": {}",
+                            member.pedal_type, member.port_match, e
+                        );
+                        MemberConnectResult {
+                            member: member.clone(),
+                            connected: false,
+                            already_connected: false,
+                            error: Some(e),
+                        }
+                    }
+                }
+            })
+            .collect();
+
+        PedalboardActivation {
+            pedalboard_id: pedalboard.id.clone(),
+            results,
+        }
+    }
+
+    /// Make an activation's board the active one, remembering which members it connected
+    pub fn mark_active(&self, activation: &PedalboardActivation) -> Result<()> {
+        self.repository
+            .set_active(Some((&activation.pedalboard_id, &activation.newly_connected())))
+    }
+
+    /// Disconnect the devices the active pedalboard's activation connected and clear the
+    /// active flag. Pedals that were connected before activation stay connected.
+    /// Returns the board that was deactivated, if one was active.
+    pub fn deactivate_pedalboard(&self, connector: &mut dyn DeviceConnector) -> Result<Option<Pedalboard>> {
+        let Some(pedalboard) = self.get_active_pedalboard()? else {
+            return Ok(None);
+        };
+
+        Self::disconnect_members(&pedalboard, connector);
+        self.repository.set_active(None)?;
+
+        Ok(Some(Self::deactivated(pedalboard)))
+    }
+
+    /// Disconnect the members a board's activation connected. Touches no stored state.
+    pub fn disconnect_members(pedalboard: &Pedalboard, connector: &mut dyn DeviceConnector) {
+        for port_match in &pedalboard.connected_by_activation {
+            if let Err(e) = connector.disconnect_device(port_match) {
+                println!(
+                    "⚠️ [Pedalboards] Failed to disconnect \"{}\": {}",
+                    port_match, e
+                );
+            }
+        }
+    }
+
+    fn deactivated(pedalboard: Pedalboard) -> Pedalboard {
+        Pedalboard {
+            is_active: false,
+            connected_by_activation: Vec::new(),
+            ..pedalboard
+        }
+    }
+
+    /// Add a scene to a pedalboard, replacing any scene with the same name
+    pub fn save_scene(&self, id: &PedalboardId, scene: PedalboardScene) -> Result<Pedalboard> {
+        let mut pedalboard = self.get_pedalboard(id)?;
+        let scene = Self::validate_scene(&pedalboard, scene)?;
+
+        match pedalboard.scenes.iter_mut().find(|existing| existing.name == scene.name) {
+            Some(existing) => *existing = scene,
+            None => pedalboard.scenes.push(scene),
+        }
+        pedalboard.updated_at = chrono::Utc::now().timestamp();
+        self.repository.save(&pedalboard)?;
+
+        Ok(pedalboard)
+    }
+
+    /// Remove a scene from a pedalboard
+    pub fn delete_scene(&self, id: &PedalboardId, name: &str) -> Result<Pedalboard> {
+        let mut pedalboard = self.get_pedalboard(id)?;
+        let count = pedalboard.scenes.len();
+        pedalboard.scenes.retain(|scene| scene.name != name);
+        if pedalboard.scenes.len() == count {
+            return Err(PedalboardError::SceneNotFound { name: name.to_string() });
+        }
+
+        pedalboard.updated_at = chrono::Utc::now().timestamp();
+        self.repository.save(&pedalboard)?;
+
+        Ok(pedalboard)
+    }
+
+    /// Keep scene recalls on presets that exist as the preset library changes: a merge moves
+    /// them to the kept preset, a delete drops them. Returns whether any scene changed.
+    pub fn apply_preset_change(&self, event: &PresetChangeEvent) -> Result<bool> {
+        match event {
+            PresetChangeEvent::Merged { from, into } => {
+                self.repository.repoint_scene_recalls(from.as_str(), Some(into.as_str()))
+            }
+            PresetChangeEvent::Deleted(id) => self.repository.repoint_scene_recalls(id.as_str(), None),
+            _ => Ok(false),
+        }
+    }
+
+    /// The active pedalboard and its scene called `name` - scenes recall on the active board only
+    pub fn active_scene(&self, name: &str) -> Result<(Pedalboard, PedalboardScene)> {
+        let pedalboard = self.get_active_pedalboard()?.ok_or(PedalboardError::NoActivePedalboard)?;
+        let scene = pedalboard
+            .scenes
+            .iter()
+            .find(|scene| scene.name == name)
+            .cloned()
+            .ok_or_else(|| PedalboardError::SceneNotFound { name: name.to_string() })?;
+        Ok((pedalboard, scene))
+    }
+
+    fn validate_scene(pedalboard: &Pedalboard, scene: PedalboardScene) -> Result<PedalboardScene> {
+        let name = scene.name.trim().to_string();
+        if name.is_empty() {
+            return Err(PedalboardError::InvalidScene {
+                reason: "Name cannot be empty".to_string(),
+            });
+        }
+
+        for (index, recall) in scene.recalls.iter().enumerate() {
+            if !pedalboard.members.iter().any(|member| member.port_match == recall.port_match) {
+                return Err(PedalboardError::InvalidScene {
+                    reason: format!("\"{}\" is not on this pedalboard", recall.port_match),
+                });
+            }
+            if scene.recalls[..index].iter().any(|earlier| earlier.port_match == recall.port_match) {
+                return Err(PedalboardError::InvalidScene {
+                    reason: format!("\"{}\" has more than one preset", recall.port_match),
+                });
+            }
+        }
+
+        Ok(PedalboardScene { name, ..scene })
+    }
+
+    fn validate_name(name: &str) -> Result<String> {
+        let trimmed = name.trim().to_string();
+        if trimmed.is_empty() {
+            return Err(PedalboardError::InvalidName {
+                reason: "Name cannot be empty".to_string(),
+            });
+        }
+
+        if trimmed.len() > 100 {
+            return Err(PedalboardError::InvalidName {
+                reason: "Name too long (max 100 characters)".to_string(),
+            });
+        }
+
+        Ok(trimmed)
+    }
+
+    fn validate_members(members: &[PedalboardMember]) -> Result<()> {
+        for member in members {
+            if PedalType::from_name(&member.pedal_type).is_none() {
+                return Err(PedalboardError::InvalidMember {
+                    reason: format!("Unknown pedal type: {}", member.pedal_type),
+                });
+            }
+
+            if member.port_match.trim().is_empty() {
+                return Err(PedalboardError::InvalidMember {
+                    reason: format!("Port match for {} cannot be empty", member.pedal_type),
+                });
+            }
+
+            if !(1..=16).contains(&member.midi_channel) {
+                return Err(PedalboardError::InvalidMember {
+                    reason: format!("Invalid MIDI channel: {} (must be 1-16)", member.midi_channel),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Recall a scene's presets on a board's pedals, in the scene's order. One pedal failing
+/// doesn't stop the others - the report covers each one.
+pub fn recall_scene(
+    pedalboard: &Pedalboard,
+    scene_name: &str,
+    steps: Vec<SceneStep>,
+    recaller: &mut dyn PresetRecaller,
+) -> SceneRecallReport {
+    let results = steps
+        .into_iter()
+        .map(|step| {
+            let member = pedalboard
+                .members
+                .iter()
+                .find(|member| member.port_match == step.recall.port_match);
+            let outcome = match (member, step.parameters) {
+                (None, _) => Err(format!("\"{}\" is no longer on this pedalboard", step.recall.port_match)),
+                (_, Err(e)) => Err(e),
+                (Some(member), Ok(parameters)) => {
+                    recaller.recall_preset(&member.port_match, &member.pedal_type, parameters)
+                }
+            };
+            if let Err(e) = &outcome {
+                println!(
+                    "⚠️ [Pedalboards] Scene \"{}\" couldn't recall on \"{}\": {}",
+                    scene_name, step.recall.port_match, e
+                );
+            }
+            SceneRecallResult {
+                recall: step.recall,
+                recalled: outcome.is_ok(),
+                error: outcome.err(),
+            }
+        })
+        .collect();
+
+    SceneRecallReport {
+        pedalboard_id: pedalboard.id.clone(),
+        scene: scene_name.to_string(),
+        results,
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Activate a pedalboard on shared state, deactivating the active one first. The pedalboard
+/// lock and the connector's lock are never held together, so this can't deadlock against
+/// anything that takes them in the other order.
+/// Returns the board that was deactivated, if one was active, and the activation.
+pub fn activate_shared<C: DeviceConnector>(
+    library: &Mutex<PedalboardLibrary>,
+    connector: &Mutex<C>,
+    id: &PedalboardId,
+) -> Result<(Option<Pedalboard>, PedalboardActivation)> {
+    let (pedalboard, previous) = {
+        let library = lock(library);
+        (library.get_pedalboard(id)?, library.get_active_pedalboard()?)
+    };
+
+    let activation = {
+        let mut connector = lock(connector);
+        if let Some(previous) = &previous {
+            PedalboardLibrary::disconnect_members(previous, &mut *connector);
+        }
+        PedalboardLibrary::connect_members(&pedalboard, &mut *connector)
+    };
+
+    lock(library).mark_active(&activation)?;
+    Ok((previous.map(PedalboardLibrary::deactivated), activation))
+}
+
+/// `PedalboardLibrary::deactivate_pedalboard` on shared state, without holding both locks
+pub fn deactivate_shared<C: DeviceConnector>(
+    library: &Mutex<PedalboardLibrary>,
+    connector: &Mutex<C>,
+) -> Result<Option<Pedalboard>> {
+    let Some(pedalboard) = lock(library).get_active_pedalboard()? else {
+        return Ok(None);
+    };
+
+    PedalboardLibrary::disconnect_members(&pedalboard, &mut *lock(connector));
+    lock(library).repository.set_active(None)?;
+
+    Ok(Some(PedalboardLibrary::deactivated(pedalboard)))
+}
+
+/// Reconnect the active pedalboard's pedals - the board stays active across restarts but
+/// its connections don't. Returns None when no board is active.
+pub fn reconnect_active_shared<C: DeviceConnector>(
+    library: &Mutex<PedalboardLibrary>,
+    connector: &Mutex<C>,
+) -> Result<Option<PedalboardActivation>> {
+    let Some(pedalboard) = lock(library).get_active_pedalboard()? else {
+        return Ok(None);
+    };
+
+    let activation = PedalboardLibrary::connect_members(&pedalboard, &mut *lock(connector));
+    lock(library).mark_active(&activation)?;
+    Ok(Some(activation))
+}
+
+/// Create a shared pedalboard library for use in Tauri state management
+pub type SharedPedalboardLibrary = Arc<Mutex<PedalboardLibrary>>;
+
+pub fn create_shared_library(db_path: PathBuf) -> Result<SharedPedalboardLibrary> {
+    let library = PedalboardLibrary::new(db_path)?;
+    Ok(Arc::new(Mutex::new(library)))
+}
@@ -0,0 +1,239 @@
+// Pedalboard repository - SQLite persistence (infrastructure layer)
+use super::types::*;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// How long a write waits for the preset library's connection to finish with the file
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+const SELECT_COLUMNS: &str =
+    "SELECT id, name, members, is_active, created_at, updated_at, connected_by_activation, scenes FROM pedalboards";
+
+/// Repository for pedalboard persistence
+pub struct PedalboardRepository {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl PedalboardRepository {
+    /// Create a new repository with the given database path
+    pub fn new(db_path: PathBuf) -> Result<Self> {
+        let conn = Connection::open(db_path)?;
+
+        // Shares the file with the preset library's connection: WAL lets one read while the
+        // other writes, and the busy timeout waits out the other's writes instead of failing
+        conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get::<_, String>(0))?;
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+
+        let repo = Self {
+            conn: Arc::new(Mutex::new(conn)),
+        };
+        repo.init_schema()?;
+        Ok(repo)
+    }
+
+    /// Initialize database schema (idempotent)
+    fn init_schema(&self) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        // Members are stored as JSON, like preset tags
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS pedalboards (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL UNIQUE,
+                members TEXT NOT NULL,
+                is_active INTEGER NOT NULL DEFAULT 0,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        // Migration: devices activation itself connected, so deactivating leaves the rest
+        let has_connected_by_activation: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('pedalboards') WHERE name = 'connected_by_activation'",
+            [],
+            |row| row.get(0),
+        )?;
+        if has_connected_by_activation == 0 {
+            conn.execute(
+                "ALTER TABLE pedalboards ADD COLUMN connected_by_activation TEXT NOT NULL DEFAULT '[]'",
+                [],
+            )?;
+        }
+
+        // Migration: scenes, stored as JSON like members
+        let has_scenes: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('pedalboards') WHERE name = 'scenes'",
+            [],
+            |row| row.get(0),
+        )?;
+        if has_scenes == 0 {
+            conn.execute("ALTER TABLE pedalboards ADD COLUMN scenes TEXT NOT NULL DEFAULT '[]'", [])?;
+        }
+
+        Ok(())
+    }
+
+    /// Save a pedalboard to the database
+    pub fn save(&self, pedalboard: &Pedalboard) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        let members_json = serde_json::to_string(&pedalboard.members)?;
+        let connected_json = serde_json::to_string(&pedalboard.connected_by_activation)?;
+        let scenes_json = serde_json::to_string(&pedalboard.scenes)?;
+
+        conn.prepare_cached(
+            "INSERT INTO pedalboards (id, name, members, is_active, created_at, updated_at, connected_by_activation, scenes)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+             ON CONFLICT(id) DO UPDATE SET
+                name = excluded.name,
+                members = excluded.members,
+                is_active = excluded.is_active,
+                updated_at = excluded.updated_at,
+                connected_by_activation = excluded.connected_by_activation,
+                scenes = excluded.scenes",
+        )?.execute(params![
+            pedalboard.id.as_str(),
+            pedalboard.name,
+            members_json,
+            if pedalboard.is_active { 1 } else { 0 },
+            pedalboard.created_at,
+            pedalboard.updated_at,
+            connected_json,
+            scenes_json,
+        ])?;
+
+        Ok(())
+    }
+
+    /// Find a pedalboard by ID
+    pub fn find_by_id(&self, id: &PedalboardId) -> Result<Option<Pedalboard>> {
+        let conn = self.conn.lock().unwrap();
+
+        let pedalboard = conn
+            .prepare_cached(&format!("{} WHERE id = ?1", SELECT_COLUMNS))?
+            .query_row(params![id.as_str()], Self::map_row)
+            .optional()?;
+
+        Ok(pedalboard)
+    }
+
+    /// Find a pedalboard by name
+    pub fn find_by_name(&self, name: &str) -> Result<Option<Pedalboard>> {
+        let conn = self.conn.lock().unwrap();
+
+        let pedalboard = conn
+            .prepare_cached(&format!("{} WHERE name = ?1", SELECT_COLUMNS))?
+            .query_row(params![name], Self::map_row)
+            .optional()?;
+
+        Ok(pedalboard)
+    }
+
+    /// List all pedalboards by name
+    pub fn list(&self) -> Result<Vec<Pedalboard>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn.prepare_cached(&format!("{} ORDER BY name", SELECT_COLUMNS))?;
+        let rows = stmt.query_map([], Self::map_row)?;
+
+        let mut pedalboards = Vec::new();
+        for row in rows {
+            pedalboards.push(row?);
+        }
+
+        Ok(pedalboards)
+    }
+
+    /// Delete a pedalboard
+    pub fn delete(&self, id: &PedalboardId) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows_affected = conn
+            .prepare_cached("DELETE FROM pedalboards WHERE id = ?1")?
+            .execute(params![id.as_str()])?;
+
+        if rows_affected == 0 {
+            return Err(PedalboardError::NotFound {
+                id: id.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Point scene recalls of preset `from` at `to` on every board, or drop them when `to` is
+    /// None, in one transaction. Returns whether any board changed.
+    pub fn repoint_scene_recalls(&self, from: &str, to: Option<&str>) -> Result<bool> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        let pedalboards = tx
+            .prepare_cached(SELECT_COLUMNS)?
+            .query_map([], Self::map_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut changed = false;
+        for mut pedalboard in pedalboards {
+            let mut board_changed = false;
+            for scene in &mut pedalboard.scenes {
+                board_changed |= scene.repoint_preset(from, to);
+            }
+            if board_changed {
+                tx.prepare_cached("UPDATE pedalboards SET scenes = ?1 WHERE id = ?2")?
+                    .execute(params![serde_json::to_string(&pedalboard.scenes)?, pedalboard.id.as_str()])?;
+                changed = true;
+            }
+        }
+
+        tx.commit()?;
+        Ok(changed)
+    }
+
+    /// Mark one pedalboard active (or none) - at most one board is active at a time.
+    /// `connected` is the port matches activation itself connected.
+    pub fn set_active(&self, active: Option<(&PedalboardId, &[String])>) -> Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        tx.prepare_cached(
+            "UPDATE pedalboards SET is_active = 0, connected_by_activation = '[]' WHERE is_active = 1",
+        )?
+        .execute([])?;
+
+        if let Some((id, connected)) = active {
+            let rows_affected = tx
+                .prepare_cached("UPDATE pedalboards SET is_active = 1, connected_by_activation = ?2 WHERE id = ?1")?
+                .execute(params![id.as_str(), serde_json::to_string(connected)?])?;
+
+            if rows_affected == 0 {
+                return Err(PedalboardError::NotFound {
+                    id: id.to_string(),
+                });
+            }
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn map_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Pedalboard> {
+        let members_json: String = row.get(2)?;
+        let members: Vec<PedalboardMember> = serde_json::from_str(&members_json).unwrap_or_default();
+        let connected_json: String = row.get(6)?;
+        let scenes_json: String = row.get(7)?;
+
+        Ok(Pedalboard {
+            id: PedalboardId::new(row.get(0)?),
+            name: row.get(1)?,
+            members,
+            is_active: row.get::<_, i32>(3)? != 0,
+            created_at: row.get(4)?,
+            updated_at: row.get(5)?,
+            connected_by_activation: serde_json::from_str(&connected_json).unwrap_or_default(),
+            scenes: serde_json::from_str(&scenes_json).unwrap_or_default(),
+        })
+    }
+}
@@ -0,0 +1,189 @@
+// Pedalboard domain types - entities, value objects, and domain concepts
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Pedalboard entity - a named rig of pedals with their saved port/channel setup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Pedalboard {
+    pub id: PedalboardId,
+    pub name: String,
+    pub members: Vec<PedalboardMember>,
+    pub is_active: bool,
+    pub created_at: i64,  // Unix timestamp
+    pub updated_at: i64,  // Unix timestamp
+    /// Port matches of the members activation itself connected - deactivating disconnects
+    /// only these, leaving pedals that were already connected alone
+    #[serde(default)]
+    pub connected_by_activation: Vec<String>,
+    #[serde(default)]
+    pub scenes: Vec<PedalboardScene>,
+}
+
+/// A named set of presets recalled together across a board's pedals (e.g. "Verse")
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PedalboardScene {
+    pub name: String,
+    pub recalls: Vec<SceneRecall>,
+}
+
+impl PedalboardScene {
+    /// Point recalls of preset `from` at `to`, or drop them when `to` is None (the preset is
+    /// gone). Returns whether anything changed.
+    pub fn repoint_preset(&mut self, from: &str, to: Option<&str>) -> bool {
+        let count = self.recalls.len();
+        match to {
+            Some(to) => {
+                let mut changed = false;
+                for recall in self.recalls.iter_mut().filter(|recall| recall.preset_id == from) {
+                    recall.preset_id = to.to_string();
+                    changed = true;
+                }
+                changed
+            }
+            None => {
+                self.recalls.retain(|recall| recall.preset_id != from);
+                self.recalls.len() != count
+            }
+        }
+    }
+}
+
+/// One pedal's part of a scene
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneRecall {
+    /// The member's port match, which is also its device name once connected
+    pub port_match: String,
+    pub preset_id: String,
+}
+
+/// A scene recall with its preset loaded from the library (or why it couldn't be)
+#[derive(Debug, Clone)]
+pub struct SceneStep {
+    pub recall: SceneRecall,
+    pub parameters: std::result::Result<serde_json::Value, String>,
+}
+
+/// Outcome of recalling one pedal's preset for a scene
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneRecallResult {
+    #[serde(flatten)]
+    pub recall: SceneRecall,
+    pub recalled: bool,
+    pub error: Option<String>,
+}
+
+/// Result of recalling a scene - per-pedal success/failure.
+/// Also the payload of the "pedalboard-scene-recalled" event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneRecallReport {
+    pub pedalboard_id: PedalboardId,
+    pub scene: String,
+    pub results: Vec<SceneRecallResult>,
+}
+
+/// One pedal on a pedalboard
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PedalboardMember {
+    pub pedal_type: String,
+    pub alias: Option<String>,
+    pub port_match: String,  // Substring of the MIDI port name (same matching as connect_*)
+    pub midi_channel: u8,
+}
+
+/// Pedalboard ID - value object ensuring valid IDs
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PedalboardId(String);
+
+impl PedalboardId {
+    pub fn new(id: String) -> Self {
+        Self(id)
+    }
+
+    pub fn generate() -> Self {
+        Self(uuid::Uuid::new_v4().to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for PedalboardId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Outcome of connecting one member during activation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemberConnectResult {
+    #[serde(flatten)]
+    pub member: PedalboardMember,
+    pub connected: bool,
+    /// Connected before activation, so deactivating leaves it connected
+    pub already_connected: bool,
+    pub error: Option<String>,
+}
+
+/// Result of activating a pedalboard - per-device success/failure
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PedalboardActivation {
+    pub pedalboard_id: PedalboardId,
+    pub results: Vec<MemberConnectResult>,
+}
+
+impl PedalboardActivation {
+    pub fn all_connected(&self) -> bool {
+        self.results.iter().all(|r| r.connected)
+    }
+
+    /// Port matches of the members this activation connected
+    pub fn newly_connected(&self) -> Vec<String> {
+        self.results
+            .iter()
+            .filter(|r| r.connected && !r.already_connected)
+            .map(|r| r.member.port_match.clone())
+            .collect()
+    }
+}
+
+/// Domain errors for pedalboard operations
+#[derive(Debug, thiserror::Error)]
+pub enum PedalboardError {
+    #[error("Pedalboard not found: {id}")]
+    NotFound { id: String },
+
+    #[error("Pedalboard name already exists: {name}")]
+    DuplicateName { name: String },
+
+    #[error("Invalid pedalboard name: {reason}")]
+    InvalidName { reason: String },
+
+    #[error("Invalid pedalboard member: {reason}")]
+    InvalidMember { reason: String },
+
+    #[error("Invalid scene: {reason}")]
+    InvalidScene { reason: String },
+
+    #[error("Scene not found on the active pedalboard: {name}")]
+    SceneNotFound { name: String },
+
+    #[error("No pedalboard is active")]
+    NoActivePedalboard,
+
+    #[error("Database error: {0}")]
+    Database(#[from] rusqlite::Error),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+pub type Result<T> = std::result::Result<T, PedalboardError>;
@@ -0,0 +1,209 @@
+// Smart banks - fill a pedal's bank slots from a preset query
+// A fill is planned first: matches are ordered, truncated to the pedal's slot count and laid
+// out lowest bank first. The plan is what a dry run returns, and applying it writes one slot
+// at a time so a failure part way through leaves the slots before it assigned.
+
+use super::types::{Preset, PresetId};
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+
+/// Emitted with an `AutofillProgress` before each slot of an autofill is written
+pub const AUTOFILL_PROGRESS_EVENT: &str = "bank-autofill-progress";
+
+/// Order matching presets take in the banks
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum FillOrder {
+    /// Most recently edited first
+    MostRecent,
+    /// By name, ignoring case
+    Alphabetical,
+    /// In the order of `ids`; matches not listed are left out
+    Manual { ids: Vec<PresetId> },
+}
+
+/// How an autofill orders matches and treats banks it has no preset for.
+/// Matches beyond the pedal's slot count are always dropped.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FillStrategy {
+    pub order: FillOrder,
+    /// Clear banks past the last match instead of leaving them as they are
+    #[serde(default)]
+    pub clear_remainder: bool,
+}
+
+/// One bank slot an autofill will write
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutofillSlot {
+    pub bank_number: u8,
+    /// None clears the slot
+    pub preset_id: Option<PresetId>,
+    pub preset_name: Option<String>,
+}
+
+/// Slots an autofill will write, lowest bank first. Banks not listed are left alone.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutofillPlan {
+    pub pedal_type: String,
+    pub slots: Vec<AutofillSlot>,
+    /// Matches left out because the pedal ran out of banks
+    pub truncated: usize,
+}
+
+/// What happened to one slot of an applied plan
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum AutofillSlotStatus {
+    Assigned,
+    /// Assigned and stored on the pedal with its MIDI save sequence
+    SavedToPedal,
+    Cleared,
+    Failed { error: String },
+    /// Not attempted because an earlier slot failed
+    Skipped,
+}
+
+/// Per-slot result of an applied plan
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutofillSlotResult {
+    pub bank_number: u8,
+    pub preset_id: Option<PresetId>,
+    pub status: AutofillSlotStatus,
+}
+
+/// A plan and, unless it was a dry run, what applying it did to each slot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutofillReport {
+    pub plan: AutofillPlan,
+    pub dry_run: bool,
+    pub results: Vec<AutofillSlotResult>,
+}
+
+/// Sent before each slot is written, so the UI can show how far a fill has got
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutofillProgress {
+    pub pedal_type: String,
+    pub bank_number: u8,
+    /// Zero-based position of this slot in the plan
+    pub index: usize,
+    pub total: usize,
+}
+
+impl AutofillSlotResult {
+    pub fn new(slot: &AutofillSlot, status: AutofillSlotStatus) -> Self {
+        Self {
+            bank_number: slot.bank_number,
+            preset_id: slot.preset_id.clone(),
+            status,
+        }
+    }
+}
+
+impl AutofillReport {
+    /// The plan alone - nothing was written
+    pub fn dry_run(plan: AutofillPlan) -> Self {
+        Self { plan, dry_run: true, results: Vec::new() }
+    }
+
+    /// Whether every slot was written
+    pub fn is_complete(&self) -> bool {
+        !self.dry_run
+            && self.results.len() == self.plan.slots.len()
+            && self
+                .results
+                .iter()
+                .all(|result| !matches!(result.status, AutofillSlotStatus::Failed { .. } | AutofillSlotStatus::Skipped))
+    }
+}
+
+/// `presets` in `order`
+pub(crate) fn order_matches(mut presets: Vec<Preset>, order: &FillOrder) -> Vec<Preset> {
+    match order {
+        FillOrder::MostRecent => {
+            presets.sort_by(|a, b| b.updated_at.cmp(&a.updated_at).then_with(|| a.id.as_str().cmp(b.id.as_str())));
+            presets
+        }
+        FillOrder::Alphabetical => {
+            presets.sort_by_cached_key(|preset| (preset.name.to_lowercase(), preset.id.as_str().to_string()));
+            presets
+        }
+        FillOrder::Manual { ids } => ids
+            .iter()
+            .filter_map(|id| presets.iter().position(|preset| preset.id == *id))
+            .map(|position| presets[position].clone())
+            .collect(),
+    }
+}
+
+/// Lay ordered matches out across `banks`, lowest first
+pub(crate) fn plan_slots(
+    pedal_type: &str,
+    ordered: Vec<Preset>,
+    banks: RangeInclusive<u8>,
+    clear_remainder: bool,
+) -> AutofillPlan {
+    let bank_count = banks.clone().count();
+    let truncated = ordered.len().saturating_sub(bank_count);
+    let mut presets = ordered.into_iter();
+
+    let mut slots = Vec::with_capacity(bank_count);
+    for bank_number in banks {
+        match presets.next() {
+            Some(preset) => slots.push(AutofillSlot {
+                bank_number,
+                preset_id: Some(preset.id),
+                preset_name: Some(preset.name),
+            }),
+            None if clear_remainder => slots.push(AutofillSlot { bank_number, preset_id: None, preset_name: None }),
+            None => break,
+        }
+    }
+
+    AutofillPlan { pedal_type: pedal_type.to_string(), slots, truncated }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::PresetBuilder;
+
+    fn preset(name: &str, updated_at: i64) -> Preset {
+        PresetBuilder::new().name(name).timestamp(updated_at).build()
+    }
+
+    #[test]
+    fn test_order_matches() {
+        let presets = vec![preset("bravo", 20), preset("Alpha", 10), preset("charlie", 30)];
+        let names = |presets: Vec<Preset>| presets.into_iter().map(|p| p.name).collect::<Vec<_>>();
+
+        assert_eq!(names(order_matches(presets.clone(), &FillOrder::MostRecent)), ["charlie", "bravo", "Alpha"]);
+        assert_eq!(names(order_matches(presets.clone(), &FillOrder::Alphabetical)), ["Alpha", "bravo", "charlie"]);
+
+        let ids = vec![presets[2].id.clone(), PresetId::new("missing".to_string()), presets[0].id.clone()];
+        assert_eq!(names(order_matches(presets, &FillOrder::Manual { ids })), ["charlie", "bravo"]);
+    }
+
+    #[test]
+    fn test_plan_slots_truncates_and_clears() {
+        let presets: Vec<Preset> = (0..5).map(|i| preset(&format!("p{}", i), i)).collect();
+
+        let plan = plan_slots("Microcosm", presets.clone(), 45..=48, false);
+        assert_eq!(plan.slots.len(), 4);
+        assert_eq!(plan.truncated, 1);
+        assert_eq!(plan.slots[3].preset_name.as_deref(), Some("p3"));
+
+        let plan = plan_slots("Microcosm", presets[..2].to_vec(), 45..=48, false);
+        assert_eq!(plan.slots.iter().map(|s| s.bank_number).collect::<Vec<_>>(), [45, 46]);
+        assert_eq!(plan.truncated, 0);
+
+        let plan = plan_slots("Microcosm", presets[..2].to_vec(), 45..=48, true);
+        assert_eq!(plan.slots.len(), 4);
+        assert!(plan.slots[2..].iter().all(|s| s.preset_id.is_none()));
+    }
+}
@@ -1,8 +1,9 @@
 // Bank configuration - defines preset bank layouts for different pedal types
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
 
 /// How a pedal saves presets to internal memory via MIDI
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "camelCase")]
 pub enum MidiSaveCapability {
     /// Pedal supports MIDI save via Control Change
@@ -22,9 +23,16 @@ pub enum MidiSaveCapability {
 
 /// Bank configuration for a pedal type
 /// Defines how preset banks are organized and displayed
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BankConfig {
+    /// Pedal type this configuration belongs to (e.g., "Microcosm")
+    pub pedal_type: String,
+    /// Offset added to a program change number to get the number printed on the pedal
+    /// (e.g., -44 for Microcosm where PC 45 is User Bank 1)
+    pub display_offset: i16,
+    /// Whether the pedal recalls bank slots via Program Change
+    pub supports_program_change: bool,
     /// First program change number in the bank range
     pub program_change_start: u8,
     /// Last program change number in the bank range
@@ -39,6 +47,9 @@ pub struct BankConfig {
     pub bank_colors: Vec<String>,
     /// How this pedal saves presets to internal memory
     pub midi_save: MidiSaveCapability,
+    /// Whether one preset may sit in several bank slots at once. When false each preset
+    /// has at most one slot, so there is no doubt which hardware slot is canonical.
+    pub allow_multi_bank: bool,
 }
 
 impl BankConfig {
@@ -47,6 +58,16 @@ impl BankConfig {
         self.num_banks * self.slots_per_bank
     }
     
+    /// Program change numbers of the bank slots
+    pub fn slot_range(&self) -> RangeInclusive<u8> {
+        self.program_change_start..=self.program_change_end
+    }
+    
+    /// Number of bank slots, counted from the program change range
+    pub fn slot_count(&self) -> u8 {
+        self.program_change_end - self.program_change_start + 1
+    }
+    
     /// Calculate the bank index (0-based) from a program change number
     pub fn get_bank_index(&self, program_change: u8) -> Option<usize> {
         if program_change < self.program_change_start || program_change > self.program_change_end {
@@ -82,6 +103,20 @@ impl BankConfig {
         }
     }
     
+    /// Display-friendly slot name (e.g., "User Bank 1" or "Preset 001"), or None if
+    /// `bank_number` isn't one of this pedal's slots
+    pub fn slot_label(&self, bank_number: u8) -> Option<String> {
+        if !self.slot_range().contains(&bank_number) {
+            return None;
+        }
+        let display_number = bank_number as i16 + self.display_offset;
+        Some(if self.num_banks == 1 {
+            format!("{} {:03}", self.bank_labels[0], display_number)
+        } else {
+            format!("User Bank {}", display_number)
+        })
+    }
+    
     /// Get the color for a program change number
     pub fn get_color(&self, program_change: u8) -> Option<&str> {
         let bank_idx = self.get_bank_index(program_change)?;
@@ -89,10 +124,41 @@ impl BankConfig {
     }
 }
 
+/// A bank configuration as the frontend gets it: the stored fields plus the derived
+/// `slotRange` and `slotCount`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BankConfigInfo<'a> {
+    #[serde(flatten)]
+    pub config: &'a BankConfig,
+    pub slot_range: (u8, u8),
+    pub slot_count: u8,
+}
+
+impl<'a> From<&'a BankConfig> for BankConfigInfo<'a> {
+    fn from(config: &'a BankConfig) -> Self {
+        Self {
+            config,
+            slot_range: (config.program_change_start, config.program_change_end),
+            slot_count: config.slot_count(),
+        }
+    }
+}
+
+/// Every pedal type with bank tracking (i.e. get_bank_config returns Some)
+pub const PEDAL_TYPES: &[&str] = &[
+    "Microcosm", "ChromaConsole", "PreampMk2", "Cxm1978", "GenLossMkii", "Clean", "Onward",
+    "BrothersAm", "ReverseModeC", "MoodMkii", "BillyStringsWombtone", "Lossy",
+    "Thermae", "DarkWorld", "Habit", "Collider",
+];
+
 /// Get the bank configuration for a specific pedal type
 pub fn get_bank_config(pedal_type: &str) -> Option<BankConfig> {
     match pedal_type {
         "Microcosm" => Some(BankConfig {
+            pedal_type: "Microcosm".to_string(),
+            display_offset: -44,
+            supports_program_change: true,
             program_change_start: 45,
             program_change_end: 60,
             num_banks: 4,
@@ -113,29 +179,34 @@ pub fn get_bank_config(pedal_type: &str) -> Option<BankConfig> {
                 cc_number: 46,
                 description: "CC 46 - Preset Save".to_string(),
             },
+            allow_multi_bank: true,
         }),
+        // PC 0-39 are factory presets (banks A and B); only the user presets are slots
         "ChromaConsole" => Some(BankConfig {
-            program_change_start: 0,
+            pedal_type: "ChromaConsole".to_string(),
+            display_offset: -39,
+            supports_program_change: true,
+            program_change_start: 40,
             program_change_end: 79,
-            num_banks: 4,
+            num_banks: 2,
             slots_per_bank: 20,
             bank_labels: vec![
-                "A".to_string(),
-                "B".to_string(),
                 "C".to_string(),
                 "D".to_string(),
             ],
             bank_colors: vec![
-                "red".to_string(),
-                "orange".to_string(),
                 "green".to_string(),
                 "blue".to_string(),
             ],
             midi_save: MidiSaveCapability::ManualOnly {
-                instructions: "Press and hold the footswitch to save the preset to the pedal's internal memory".to_string(),
+                instructions: "After the parameters are loaded, hold the main footswitch for 2 seconds until the LED blinks white to save to the current preset slot.".to_string(),
             },
+            allow_multi_bank: true,
         }),
         "PreampMk2" => Some(BankConfig {
+            pedal_type: "PreampMk2".to_string(),
+            display_offset: 1,
+            supports_program_change: true,
             program_change_start: 0,
             program_change_end: 29,
             num_banks: 3,
@@ -154,8 +225,36 @@ pub fn get_bank_config(pedal_type: &str) -> Option<BankConfig> {
                 cc_number: 27,
                 description: "CC 27 with value 0-29 saves to that preset slot".to_string(),
             },
+            allow_multi_bank: true,
+        }),
+        "Cxm1978" => Some(BankConfig {
+            pedal_type: "Cxm1978".to_string(),
+            display_offset: 1,
+            supports_program_change: true,
+            program_change_start: 0,
+            program_change_end: 29,
+            num_banks: 3,
+            slots_per_bank: 10,
+            bank_labels: vec![
+                "1".to_string(),
+                "2".to_string(),
+                "3".to_string(),
+            ],
+            bank_colors: vec![
+                "red".to_string(),
+                "green".to_string(),
+                "blue".to_string(),
+            ],
+            midi_save: MidiSaveCapability::Supported {
+                cc_number: 27,
+                description: "CC 27 with value 0-29 saves to that preset slot".to_string(),
+            },
+            allow_multi_bank: true,
         }),
         "GenLossMkii" => Some(BankConfig {
+            pedal_type: "GenLossMkii".to_string(),
+            display_offset: 0,
+            supports_program_change: true,
             program_change_start: 1,
             program_change_end: 122,
             num_banks: 1,
@@ -166,8 +265,12 @@ pub fn get_bank_config(pedal_type: &str) -> Option<BankConfig> {
                 cc_number: 111,
                 description: "CC 111 with value 1-122 saves to that preset slot".to_string(),
             },
+            allow_multi_bank: false,
         }),
         "Clean" => Some(BankConfig {
+            pedal_type: "Clean".to_string(),
+            display_offset: 0,
+            supports_program_change: true,
             program_change_start: 1,
             program_change_end: 122,
             num_banks: 1,
@@ -178,8 +281,12 @@ pub fn get_bank_config(pedal_type: &str) -> Option<BankConfig> {
                 cc_number: 111,
                 description: "CC 111 with value 1-122 saves to that preset slot".to_string(),
             },
+            allow_multi_bank: false,
         }),
         "Onward" => Some(BankConfig {
+            pedal_type: "Onward".to_string(),
+            display_offset: 0,
+            supports_program_change: true,
             program_change_start: 1,
             program_change_end: 122,
             num_banks: 1,
@@ -190,8 +297,12 @@ pub fn get_bank_config(pedal_type: &str) -> Option<BankConfig> {
                 cc_number: 111,
                 description: "CC 111 with value 1-122 saves to that preset slot".to_string(),
             },
+            allow_multi_bank: false,
         }),
         "BrothersAm" => Some(BankConfig {
+            pedal_type: "BrothersAm".to_string(),
+            display_offset: 0,
+            supports_program_change: true,
             program_change_start: 1,
             program_change_end: 122,
             num_banks: 1,
@@ -202,8 +313,12 @@ pub fn get_bank_config(pedal_type: &str) -> Option<BankConfig> {
                 cc_number: 111,
                 description: "CC 111 with value 1-122 saves to that preset slot".to_string(),
             },
+            allow_multi_bank: false,
         }),
         "ReverseModeC" => Some(BankConfig {
+            pedal_type: "ReverseModeC".to_string(),
+            display_offset: 0,
+            supports_program_change: true,
             program_change_start: 1,
             program_change_end: 122,
             num_banks: 1,
@@ -214,8 +329,12 @@ pub fn get_bank_config(pedal_type: &str) -> Option<BankConfig> {
                 cc_number: 111,
                 description: "CC 111 with value 1-122 saves to that preset slot".to_string(),
             },
+            allow_multi_bank: false,
         }),
         "MoodMkii" => Some(BankConfig {
+            pedal_type: "MoodMkii".to_string(),
+            display_offset: 0,
+            supports_program_change: true,
             program_change_start: 1,
             program_change_end: 122,
             num_banks: 1,
@@ -226,8 +345,12 @@ pub fn get_bank_config(pedal_type: &str) -> Option<BankConfig> {
                 cc_number: 111,
                 description: "CC 111 with value 1-122 saves to that preset slot".to_string(),
             },
+            allow_multi_bank: false,
         }),
         "BillyStringsWombtone" => Some(BankConfig {
+            pedal_type: "BillyStringsWombtone".to_string(),
+            display_offset: 0,
+            supports_program_change: true,
             program_change_start: 1,
             program_change_end: 122,
             num_banks: 1,
@@ -238,8 +361,12 @@ pub fn get_bank_config(pedal_type: &str) -> Option<BankConfig> {
                 cc_number: 111,
                 description: "CC 111 with value 1-122 saves to that preset slot".to_string(),
             },
+            allow_multi_bank: false,
         }),
         "Lossy" => Some(BankConfig {
+            pedal_type: "Lossy".to_string(),
+            display_offset: 0,
+            supports_program_change: true,
             program_change_start: 1,
             program_change_end: 122,
             num_banks: 1,
@@ -250,6 +377,71 @@ pub fn get_bank_config(pedal_type: &str) -> Option<BankConfig> {
                 cc_number: 111,
                 description: "CC 111 with value 1-122 saves to that preset slot".to_string(),
             },
+            allow_multi_bank: false,
+        }),
+        "Thermae" => Some(BankConfig {
+            pedal_type: "Thermae".to_string(),
+            display_offset: 0,
+            supports_program_change: true,
+            program_change_start: 1,
+            program_change_end: 122,
+            num_banks: 1,
+            slots_per_bank: 122,
+            bank_labels: vec!["Preset".to_string()],
+            bank_colors: vec!["orange".to_string()],
+            midi_save: MidiSaveCapability::Supported {
+                cc_number: 111,
+                description: "CC 111 with value 1-122 saves to that preset slot".to_string(),
+            },
+            allow_multi_bank: false,
+        }),
+        "DarkWorld" => Some(BankConfig {
+            pedal_type: "DarkWorld".to_string(),
+            display_offset: 0,
+            supports_program_change: true,
+            program_change_start: 1,
+            program_change_end: 122,
+            num_banks: 1,
+            slots_per_bank: 122,
+            bank_labels: vec!["Preset".to_string()],
+            bank_colors: vec!["purple".to_string()],
+            midi_save: MidiSaveCapability::Supported {
+                cc_number: 111,
+                description: "CC 111 with value 1-122 saves to that preset slot".to_string(),
+            },
+            allow_multi_bank: false,
+        }),
+        "Habit" => Some(BankConfig {
+            pedal_type: "Habit".to_string(),
+            display_offset: 0,
+            supports_program_change: true,
+            program_change_start: 1,
+            program_change_end: 122,
+            num_banks: 1,
+            slots_per_bank: 122,
+            bank_labels: vec!["Preset".to_string()],
+            bank_colors: vec!["green".to_string()],
+            midi_save: MidiSaveCapability::Supported {
+                cc_number: 111,
+                description: "CC 111 with value 1-122 saves to that preset slot".to_string(),
+            },
+            allow_multi_bank: false,
+        }),
+        // No MIDI save: PC 0-127 are shown as presets 1-128
+        "Collider" => Some(BankConfig {
+            pedal_type: "Collider".to_string(),
+            display_offset: 1,
+            supports_program_change: true,
+            program_change_start: 0,
+            program_change_end: 127,
+            num_banks: 1,
+            slots_per_bank: 128,
+            bank_labels: vec!["Preset".to_string()],
+            bank_colors: vec!["blue".to_string()],
+            midi_save: MidiSaveCapability::ManualOnly {
+                instructions: "After the parameters are loaded, press and hold both footswitches until the preset LED flashes to save to the current preset.".to_string(),
+            },
+            allow_multi_bank: false,
         }),
         _ => None,
     }
@@ -282,16 +474,20 @@ mod tests {
     
     #[test]
     fn test_chroma_console_config() {
+        use crate::midi::pedals::chroma_console::{ChromaConsole, USER_PRESET_START};
+
         let config = get_bank_config("ChromaConsole").unwrap();
-        assert_eq!(config.program_change_start, 0);
-        assert_eq!(config.program_change_end, 79);
-        assert_eq!(config.total_slots(), 80);
-        assert_eq!(config.format_label(0), Some("A-1".to_string()));
-        assert_eq!(config.format_label(19), Some("A-20".to_string()));
-        assert_eq!(config.format_label(20), Some("B-1".to_string()));
+        assert_eq!(config.program_change_start, USER_PRESET_START);
+        assert_eq!(config.program_change_end, ChromaConsole::preset_count() - 1);
+        assert_eq!(config.total_slots(), 40);
+        // Factory presets aren't slots
+        assert_eq!(config.format_label(0), None);
+        assert_eq!(config.format_label(39), None);
+        assert_eq!(config.format_label(40), Some("C-1".to_string()));
+        assert_eq!(config.format_label(60), Some("D-1".to_string()));
         assert_eq!(config.format_label(79), Some("D-20".to_string()));
-        assert_eq!(config.get_color(0), Some("red"));
         assert_eq!(config.get_color(40), Some("green"));
+        assert_eq!(config.get_color(79), Some("blue"));
         
         // Verify manual save capability
         match &config.midi_save {
@@ -302,6 +498,83 @@ mod tests {
         }
     }
     
+    #[test]
+    fn test_preamp_mk2_config() {
+        use crate::midi::pedals::preamp_mk2::{PreampMk2, CC_PRESET_SAVE};
+
+        let config = get_bank_config("PreampMk2").unwrap();
+        assert_eq!(config.total_slots(), 30);
+
+        // Save capability must agree with the pedal's CC 27 save
+        assert!(PreampMk2::supports_preset_save_via_midi());
+        match &config.midi_save {
+            MidiSaveCapability::Supported { cc_number, .. } => {
+                assert_eq!(*cc_number, CC_PRESET_SAVE, "Preamp MK II uses CC 27 for save");
+            }
+            _ => panic!("Preamp MK II should support MIDI save"),
+        }
+    }
+    
+    #[test]
+    fn test_slot_label() {
+        let microcosm = get_bank_config("Microcosm").unwrap();
+        assert_eq!(microcosm.slot_label(45).as_deref(), Some("User Bank 1"));
+        assert_eq!(microcosm.slot_label(60).as_deref(), Some("User Bank 16"));
+        assert_eq!(microcosm.slot_label(44), None);
+        assert_eq!(microcosm.slot_label(61), None);
+        
+        let gen_loss = get_bank_config("GenLossMkii").unwrap();
+        assert_eq!(gen_loss.slot_label(1).as_deref(), Some("Preset 001"));
+        assert_eq!(gen_loss.slot_label(122).as_deref(), Some("Preset 122"));
+        assert_eq!(gen_loss.slot_label(0), None);
+        
+        let chroma = get_bank_config("ChromaConsole").unwrap();
+        assert_eq!(chroma.slot_label(40).as_deref(), Some("User Bank 1"));
+        assert_eq!(chroma.slot_label(79).as_deref(), Some("User Bank 40"));
+        assert_eq!(chroma.slot_label(0), None);
+        
+        for pedal_type in ["PreampMk2", "Cxm1978"] {
+            let config = get_bank_config(pedal_type).unwrap();
+            assert_eq!(config.slot_label(0).as_deref(), Some("User Bank 1"));
+            assert_eq!(config.slot_label(29).as_deref(), Some("User Bank 30"));
+            assert_eq!(config.slot_label(30), None);
+        }
+    }
+    
+    // Every pedal's first slot is numbered from 1, the way the pedals print it
+    #[test]
+    fn test_slot_labels_start_at_one() {
+        for pedal_type in PEDAL_TYPES {
+            let config = get_bank_config(pedal_type).unwrap();
+            let first = *config.slot_range().start() as i16 + config.display_offset;
+            assert_eq!(first, 1, "{} numbers its first slot {}", pedal_type, first);
+        }
+    }
+    
+    #[test]
+    fn slot_count_matches_bank_layout() {
+        for pedal_type in PEDAL_TYPES {
+            let config = get_bank_config(pedal_type).unwrap();
+            assert_eq!(config.pedal_type, *pedal_type);
+            assert_eq!(config.slot_count() as usize, config.total_slots(), "{} slot count", pedal_type);
+        }
+    }
+    
+    #[test]
+    fn test_bank_config_round_trips_through_json() {
+        let config = get_bank_config("Microcosm").unwrap();
+        let json = serde_json::to_value(BankConfigInfo::from(&config)).unwrap();
+        assert_eq!(json["slotRange"], serde_json::json!([45, 60]));
+        assert_eq!(json["slotCount"], 16);
+        assert_eq!(json["displayOffset"], -44);
+        assert!(serde_json::to_value(&config).unwrap().get("slotRange").is_none());
+        
+        let restored: BankConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.pedal_type, "Microcosm");
+        assert_eq!(restored.slot_range(), 45..=60);
+        assert_eq!(restored.slot_count(), 16);
+    }
+    
     #[test]
     fn all_pedals_have_save_capability_defined() {
         // Ensure every pedal type has a complete bank config with save capability
@@ -1,5 +1,6 @@
 // Bank tracker - domain service for managing pedal bank assignments
 use super::types::*;
+use super::bank_config::get_bank_config;
 use super::repository::PresetRepository;
 use std::sync::Arc;
 
@@ -13,13 +14,11 @@ impl BankTracker {
         Self { repository }
     }
     
-    /// Get the state of all banks for a pedal type with its bank configuration
-    pub fn get_bank_state(
-        &self, 
-        pedal_type: &str, 
-        bank_range: std::ops::RangeInclusive<u8>,
-        config: &crate::presets::bank_config::BankConfig
-    ) -> Result<Vec<BankSlot>> {
+    /// Get the state of every bank slot in the pedal type's bank configuration
+    pub fn get_bank_state(&self, pedal_type: &str) -> Result<Vec<BankSlot>> {
+        let bank_range = BankNumber::range(pedal_type)
+            .ok_or_else(|| PresetError::NoBankConfig { pedal_type: pedal_type.to_string() })?;
+        
         // Get all bank assignments from database
         let assignments = self.repository.get_bank_assignments(pedal_type)?;
         
@@ -33,7 +32,7 @@ impl BankTracker {
         let mut bank_slots = Vec::new();
         
         for bank_num in bank_range {
-            let bank_number = BankNumber::new(bank_num, config)?;
+            let bank_number = BankNumber::new(bank_num, pedal_type)?;
             
             if let Some((Some(preset_id), Some(synced_at))) = assignment_map.get(&bank_num) {
                 // Bank has an assigned preset - fetch it
@@ -52,8 +51,16 @@ impl BankTracker {
         Ok(bank_slots)
     }
     
-    /// Assign a preset to a specific bank
-    pub fn assign_to_bank(&self, pedal_type: &str, bank_number: u8, preset_id: &PresetId) -> Result<()> {
+    /// Assign a preset to a specific bank. If the pedal allows a preset in only one bank
+    /// and this one already has another, it is moved there when `move_if_assigned` is set
+    /// (clearing the old slot) and rejected with `AlreadyAssigned` otherwise.
+    pub fn assign_to_bank(
+        &self,
+        pedal_type: &str,
+        bank_number: u8,
+        preset_id: &PresetId,
+        move_if_assigned: bool,
+    ) -> Result<()> {
         // Verify the preset exists
         self.repository
             .find_by_id(preset_id)?
@@ -61,6 +68,20 @@ impl BankTracker {
                 id: preset_id.to_string(),
             })?;
         
+        let allow_multi_bank = get_bank_config(pedal_type).is_none_or(|config| config.allow_multi_bank);
+        if !allow_multi_bank {
+            let existing_bank = self.repository
+                .banks_for_preset(pedal_type, preset_id)?
+                .into_iter()
+                .find(|bank| *bank != bank_number);
+            if let Some(existing_bank) = existing_bank {
+                if !move_if_assigned {
+                    return Err(PresetError::AlreadyAssigned { existing_bank });
+                }
+                return self.repository.move_to_bank(pedal_type, bank_number, preset_id);
+            }
+        }
+        
         // Assign to bank
         self.repository.assign_to_bank(pedal_type, bank_number, preset_id)?;
         
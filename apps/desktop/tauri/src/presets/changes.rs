@@ -0,0 +1,57 @@
+// Preset change notifications
+// Every mutating PresetLibrary method publishes what it changed, so views holding a copy
+// of a preset or a bank grid (another window, the library server) know to refetch.
+
+use super::types::PresetId;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+/// Emitted with a `PresetChangeEvent` whenever the library changes
+pub const PRESET_CHANGED_EVENT: &str = "preset-changed";
+
+/// Events buffered per subscriber; one that falls further behind skips the oldest
+pub const CHANGE_CHANNEL_CAPACITY: usize = 256;
+
+/// Something in the library changed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum PresetChangeEvent {
+    /// A new preset, or one restored from the trash
+    Created(PresetId),
+    /// Edited, favorited, tagged, archived or unarchived
+    Updated(PresetId),
+    /// Moved to the trash or removed for good. Any bank slots it held are now empty.
+    Deleted(PresetId),
+    /// Folded into `into` by a merge, which then moves `from` to the trash (a `Deleted`
+    /// follows). Anything that referred to `from` should refer to `into` now.
+    Merged { from: PresetId, into: PresetId },
+    /// A bank slot was assigned or cleared
+    BankChanged { pedal_type: String, bank_number: u8 },
+}
+
+/// Sending half of the library's change feed
+pub(crate) struct ChangeFeed {
+    sender: broadcast::Sender<PresetChangeEvent>,
+}
+
+impl ChangeFeed {
+    pub(crate) fn new() -> Self {
+        let (sender, _) = broadcast::channel(CHANGE_CHANNEL_CAPACITY);
+        Self { sender }
+    }
+
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<PresetChangeEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Publish `event`. Nobody listening is not an error.
+    pub(crate) fn publish(&self, event: PresetChangeEvent) {
+        let _ = self.sender.send(event);
+    }
+
+    pub(crate) fn publish_all(&self, events: impl IntoIterator<Item = PresetChangeEvent>) {
+        for event in events {
+            self.publish(event);
+        }
+    }
+}
@@ -0,0 +1,81 @@
+// Duplicate detection by parameter content
+// Two presets are duplicates when their parameters are the same sound, regardless of how the
+// JSON happens to be written: object keys are sorted, enum strings compared case-insensitively
+// and whole-number floats (`64.0`) treated as integers. Name, description and tags don't count.
+
+use super::types::{DuplicateGroup, DuplicatePreset, Preset};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+/// `parameters` in canonical form, as a string two duplicates share exactly
+pub(crate) fn canonical_parameters(parameters: &Value) -> String {
+    canonicalize(parameters).to_string()
+}
+
+fn canonicalize(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let sorted: BTreeMap<&String, Value> = map.iter().map(|(key, value)| (key, canonicalize(value))).collect();
+            Value::Object(sorted.into_iter().map(|(key, value)| (key.clone(), value)).collect())
+        }
+        Value::Array(items) => Value::Array(items.iter().map(canonicalize).collect()),
+        Value::String(s) => Value::String(s.to_lowercase()),
+        Value::Number(n) => match n.as_f64() {
+            Some(f) if !n.is_i64() && !n.is_u64() && f.fract() == 0.0 && f.abs() < i64::MAX as f64 => {
+                Value::from(f as i64)
+            }
+            _ => value.clone(),
+        },
+        _ => value.clone(),
+    }
+}
+
+/// Group `presets` by canonical parameters, keeping only groups with more than one member.
+/// Each group is oldest first (the natural one to keep); groups are ordered by their oldest preset.
+pub(crate) fn group_duplicates(presets: Vec<Preset>) -> Vec<DuplicateGroup> {
+    let mut groups: HashMap<String, Vec<DuplicatePreset>> = HashMap::new();
+    for preset in presets {
+        groups
+            .entry(canonical_parameters(&preset.parameters))
+            .or_default()
+            .push(DuplicatePreset {
+                id: preset.id,
+                name: preset.name,
+                created_at: preset.created_at,
+                updated_at: preset.updated_at,
+            });
+    }
+
+    let mut groups: Vec<DuplicateGroup> = groups
+        .into_values()
+        .filter(|presets| presets.len() > 1)
+        .map(|mut presets| {
+            presets.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.name.cmp(&b.name)));
+            DuplicateGroup { presets }
+        })
+        .collect();
+    groups.sort_by(|a, b| {
+        let (a, b) = (&a.presets[0], &b.presets[0]);
+        a.created_at.cmp(&b.created_at).then_with(|| a.name.cmp(&b.name))
+    });
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_canonical_form_ignores_key_order_casing_and_float_ints() {
+        let a = json!({"mix": 64, "mode": "Mosaic", "nested": {"b": 1, "a": [2.0, "X"]}});
+        let b = json!({"nested": {"a": [2, "x"], "b": 1.0}, "mode": "MOSAIC", "mix": 64.0});
+        assert_eq!(canonical_parameters(&a), canonical_parameters(&b));
+    }
+
+    #[test]
+    fn test_canonical_form_keeps_real_differences() {
+        assert_ne!(canonical_parameters(&json!({"mix": 64})), canonical_parameters(&json!({"mix": 65})));
+        assert_ne!(canonical_parameters(&json!({"time": 0.5})), canonical_parameters(&json!({"time": 0})));
+    }
+}
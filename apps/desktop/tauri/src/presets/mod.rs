@@ -4,20 +4,32 @@
 mod types;
 mod repository;
 mod bank_tracker;
+mod validation;
+mod duplicates;
+mod autofill;
+mod changes;
 pub mod bank_config;
 
 pub use types::*;
-pub use bank_config::{BankConfig, MidiSaveCapability};
+pub use bank_config::{BankConfig, BankConfigInfo, MidiSaveCapability};
+pub use autofill::{
+    AutofillPlan, AutofillProgress, AutofillReport, AutofillSlot, AutofillSlotResult, AutofillSlotStatus,
+    FillOrder, FillStrategy, AUTOFILL_PROGRESS_EVENT,
+};
+pub use changes::{PresetChangeEvent, CHANGE_CHANNEL_CAPACITY, PRESET_CHANGED_EVENT};
+pub use validation::default_state_validates;
 use repository::PresetRepository;
 use bank_tracker::BankTracker;
+use changes::ChangeFeed;
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
 /// Preset library - aggregate root for preset management
 pub struct PresetLibrary {
     repository: Arc<PresetRepository>,
     bank_tracker: BankTracker,
+    changes: ChangeFeed,
 }
 
 impl PresetLibrary {
@@ -26,10 +38,27 @@ impl PresetLibrary {
         let repository = Arc::new(PresetRepository::new(db_path)?);
         let bank_tracker = BankTracker::new(Arc::clone(&repository));
         
-        Ok(Self {
+        let library = Self {
             repository,
             bank_tracker,
-        })
+            changes: ChangeFeed::new(),
+        };
+        // Rows a slot range change left behind aren't moved (their old slot numbers meant
+        // other presets on the pedal), only reported so they can be reassigned
+        for stranded in library.stranded_bank_assignments()? {
+            eprintln!(
+                "⚠️ {} bank {} holds \"{}\" but is outside the pedal's slots; reassign it",
+                stranded.pedal_type, stranded.bank_number, stranded.preset_name
+            );
+        }
+        Ok(library)
+    }
+    
+    /// Subscribe to every change made through this library from now on.
+    /// A receiver that falls more than `CHANGE_CHANNEL_CAPACITY` events behind gets
+    /// `RecvError::Lagged` and should refetch whatever it shows.
+    pub fn watch_changes(&self) -> tokio::sync::broadcast::Receiver<PresetChangeEvent> {
+        self.changes.subscribe()
     }
     
     /// Save a new preset or update an existing one
@@ -40,6 +69,55 @@ impl PresetLibrary {
         description: Option<String>,
         parameters: serde_json::Value,
         tags: Vec<String>,
+    ) -> Result<Preset> {
+        self.save_preset_with_metadata(name, pedal_type, description, parameters, tags, serde_json::json!({}))
+    }
+    
+    /// Save a new preset carrying free-form metadata (a JSON object of at most 8KB)
+    pub fn save_preset_with_metadata(
+        &self,
+        name: String,
+        pedal_type: String,
+        description: Option<String>,
+        parameters: serde_json::Value,
+        tags: Vec<String>,
+        metadata: serde_json::Value,
+    ) -> Result<Preset> {
+        let preset = self.new_preset(name, pedal_type, description, parameters, tags, metadata)?;
+        self.repository.save(&preset)?;
+        self.changes.publish(PresetChangeEvent::Created(preset.id.clone()));
+        Ok(preset)
+    }
+    
+    /// Save a new preset and assign it to `bank_number` as one transaction, so the library
+    /// and the bank grid can't disagree. Nothing is written if validation or the assignment fails.
+    pub fn save_preset_and_assign(
+        &self,
+        name: String,
+        pedal_type: String,
+        description: Option<String>,
+        parameters: serde_json::Value,
+        tags: Vec<String>,
+        bank_number: u8,
+    ) -> Result<(Preset, BankSlot)> {
+        let bank = BankNumber::new(bank_number, &pedal_type)?;
+        let preset = self.new_preset(name, pedal_type, description, parameters, tags, serde_json::json!({}))?;
+        let synced_at = self.repository.save_and_assign(&preset, &preset.pedal_type, bank_number)?;
+        let slot = BankSlot::with_preset(bank, preset.clone(), synced_at);
+        self.changes.publish(PresetChangeEvent::Created(preset.id.clone()));
+        self.changes.publish(PresetChangeEvent::BankChanged { pedal_type: preset.pedal_type.clone(), bank_number });
+        Ok((preset, slot))
+    }
+    
+    /// Validate and build (but don't store) a new preset
+    fn new_preset(
+        &self,
+        name: String,
+        pedal_type: String,
+        description: Option<String>,
+        parameters: serde_json::Value,
+        tags: Vec<String>,
+        metadata: serde_json::Value,
     ) -> Result<Preset> {
         // Validate name
         let trimmed_name = name.trim().to_string();
@@ -56,12 +134,16 @@ impl PresetLibrary {
         }
         
         // Check for duplicate name
-        if self.repository.find_by_name(&trimmed_name)?.is_some() {
+        if self.repository.find_by_name(&pedal_type, &trimmed_name)?.is_some() {
             return Err(PresetError::DuplicateName {
                 name: trimmed_name,
             });
         }
         
+        // Catch malformed parameters now rather than at recall
+        let warnings = validation::validate_parameters(&pedal_type, &parameters)?;
+        validation::validate_metadata(&metadata)?;
+        
         let now = chrono::Utc::now().timestamp();
         let preset = Preset {
             id: PresetId::generate(),
@@ -70,17 +152,19 @@ impl PresetLibrary {
             description,
             parameters,
             tags,
+            metadata,
             is_favorite: false,
+            is_archived: false,
             created_at: now,
             updated_at: now,
+            warnings,
         };
         
-        self.repository.save(&preset)?;
-        
         Ok(preset)
     }
     
     /// Update an existing preset
+    #[allow(clippy::too_many_arguments)] // One optional argument per editable field
     pub fn update_preset(
         &self,
         id: &PresetId,
@@ -89,6 +173,7 @@ impl PresetLibrary {
         tags: Option<Vec<String>>,
         is_favorite: Option<bool>,
         parameters: Option<serde_json::Value>,
+        metadata: Option<serde_json::Value>,
     ) -> Result<Preset> {
         let mut preset = self
             .repository
@@ -110,7 +195,7 @@ impl PresetLibrary {
             }
             
             // Check for duplicate name (excluding current preset)
-            if let Some(existing) = self.repository.find_by_name(&trimmed_name)? {
+            if let Some(existing) = self.repository.find_by_name(&preset.pedal_type, &trimmed_name)? {
                 if existing.id != *id {
                     return Err(PresetError::DuplicateName {
                         name: trimmed_name,
@@ -133,9 +218,15 @@ impl PresetLibrary {
             preset.is_favorite = fav;
         }
         
+        if let Some(metadata) = metadata {
+            validation::validate_metadata(&metadata)?;
+            preset.metadata = metadata;
+        }
+        
         preset.updated_at = chrono::Utc::now().timestamp();
         
         self.repository.save(&preset)?;
+        self.changes.publish(PresetChangeEvent::Updated(preset.id.clone()));
         
         Ok(preset)
     }
@@ -149,51 +240,412 @@ impl PresetLibrary {
             })
     }
     
+    /// Get a live preset of a pedal type by its exact name (surrounding whitespace ignored)
+    pub fn get_preset_by_name(&self, pedal_type: &str, name: &str) -> Result<Preset> {
+        self.repository
+            .find_by_name(pedal_type, name.trim())?
+            .ok_or_else(|| PresetError::NotFound {
+                id: name.trim().to_string(),
+            })
+    }
+    
     /// List all presets with optional filtering
     pub fn list_presets(&self, filter: PresetFilter) -> Result<Vec<Preset>> {
         self.repository.list(&filter)
     }
     
-    /// Delete a preset
+    /// Search preset names, descriptions and tags, ranked by relevance (BM25)
+    pub fn search_fulltext(&self, query: &str) -> Result<Vec<Preset>> {
+        self.repository.search_fulltext(query)
+    }
+    
+    /// One page of presets plus the total match count. Parameters are left null unless
+    /// `include_parameters` (the detail view loads them with `get_preset`).
+    pub fn list_presets_page(&self, filter: PresetFilter, include_parameters: bool) -> Result<PresetListResult> {
+        Ok(PresetListResult {
+            total_count: self.repository.count(&filter)?,
+            presets: self.repository.list_with(&filter, include_parameters)?,
+        })
+    }
+    
+    /// List preset metadata only (no parameters) with optional filtering
+    pub fn list_preset_summaries(&self, filter: PresetFilter) -> Result<Vec<PresetSummary>> {
+        self.repository.list_summaries(&filter)
+    }
+    
+    /// Delete a preset. A live preset moves to the trash (its bank slots are cleared but
+    /// remembered); an archived one was already put away, so it is removed for good.
     pub fn delete_preset(&self, id: &PresetId) -> Result<()> {
-        self.repository.delete(id)
+        let is_archived = self.get_preset(id)?.is_archived;
+        let banks_before = self.banks_holding(std::slice::from_ref(id))?;
+        if is_archived {
+            self.repository.hard_delete(id)?;
+        } else {
+            self.repository.delete(id)?;
+        }
+        self.changes.publish(PresetChangeEvent::Deleted(id.clone()));
+        self.publish_all_bank_changes(&banks_before)
+    }
+    
+    /// Delete several presets at once, in a single transaction (same rules as delete_preset).
+    /// Unless `partial`, one missing preset fails the whole batch and nothing is deleted; with
+    /// it, ids that aren't live or archived presets (including ones already in the trash) are
+    /// reported as not found. Deleted presets that held bank slots are reported too, so the
+    /// UI can warn that those slots are now empty.
+    pub fn delete_presets(&self, ids: &[PresetId], partial: bool) -> Result<BulkDeleteResult> {
+        let assigned = self.repository.ids_with_bank_assignments(ids)?;
+        let banks_before = self.banks_holding(ids)?;
+        let results = self.repository.delete_many(ids, partial)?;
+        self.changes.publish_all(applied(&results).map(PresetChangeEvent::Deleted));
+        self.publish_all_bank_changes(&banks_before)?;
+        
+        let deleted: Vec<PresetId> = applied(&results).collect();
+        Ok(BulkDeleteResult {
+            deleted: deleted.len(),
+            not_found: results
+                .iter()
+                .filter(|result| result.status == BulkPresetStatus::NotFound)
+                .map(|result| result.id.clone())
+                .collect(),
+            had_bank_assignments: deleted.into_iter().filter(|id| assigned.contains(id.as_str())).collect(),
+        })
+    }
+    
+    /// Groups of live presets for `pedal_type` whose parameters are the same sound
+    /// (see `duplicates`). Archived presets are left out, as in listings.
+    pub fn find_duplicate_presets(&self, pedal_type: &str) -> Result<Vec<DuplicateGroup>> {
+        let presets = self.repository.list(&PresetFilter {
+            pedal_type: Some(pedal_type.to_string()),
+            ..Default::default()
+        })?;
+        Ok(duplicates::group_duplicates(presets))
+    }
+    
+    /// Fold `remove_ids` into `keep_id` in a single transaction: their bank slots point at
+    /// the kept preset, their tags are added to it, and they move to the trash.
+    pub fn merge_presets(&self, keep_id: &PresetId, remove_ids: &[PresetId]) -> Result<Preset> {
+        if remove_ids.contains(keep_id) {
+            return Err(PresetError::InvalidMerge {
+                reason: "the preset being kept is also being removed".to_string(),
+            });
+        }
+        let pedal_type = self.get_preset(keep_id)?.pedal_type;
+        let banks_before = self.get_bank_state(&pedal_type)?;
+        self.repository.merge_into(keep_id, remove_ids, chrono::Utc::now().timestamp())?;
+        
+        self.changes.publish(PresetChangeEvent::Updated(keep_id.clone()));
+        self.changes.publish_all(remove_ids.iter().flat_map(|id| [
+            PresetChangeEvent::Merged { from: id.clone(), into: keep_id.clone() },
+            PresetChangeEvent::Deleted(id.clone()),
+        ]));
+        self.publish_bank_changes(&pedal_type, &banks_before)?;
+        self.get_preset(keep_id)
+    }
+    
+    /// Favorite or unfavorite several presets at once, in a single transaction.
+    /// Unless `partial`, one missing preset fails the whole batch and nothing changes.
+    pub fn set_favorite_bulk(&self, ids: &[PresetId], is_favorite: bool, partial: bool) -> Result<Vec<BulkPresetResult>> {
+        let results = self.repository.set_favorite_many(ids, is_favorite, partial)?;
+        self.changes.publish_all(applied(&results).map(PresetChangeEvent::Updated));
+        Ok(results)
+    }
+    
+    /// Archive a preset: hidden from listings by default, but its data and bank slots are kept
+    pub fn archive_preset(&self, id: &PresetId) -> Result<Preset> {
+        self.repository.set_archived(id, true)?;
+        self.changes.publish(PresetChangeEvent::Updated(id.clone()));
+        self.get_preset(id)
+    }
+    
+    /// Bring an archived preset back into listings
+    pub fn unarchive_preset(&self, id: &PresetId) -> Result<Preset> {
+        self.repository.set_archived(id, false)?;
+        self.changes.publish(PresetChangeEvent::Updated(id.clone()));
+        self.get_preset(id)
+    }
+    
+    /// Remove a preset immediately, skipping the trash (works on trashed presets too)
+    pub fn permanently_delete_preset(&self, id: &PresetId) -> Result<()> {
+        let banks_before = self.banks_holding(std::slice::from_ref(id))?;
+        self.repository.hard_delete(id)?;
+        self.changes.publish(PresetChangeEvent::Deleted(id.clone()));
+        self.publish_all_bank_changes(&banks_before)
+    }
+    
+    /// List presets in the trash, most recently deleted first
+    pub fn list_deleted_presets(&self) -> Result<Vec<DeletedPreset>> {
+        self.repository.list_deleted()
+    }
+    
+    /// Restore a preset from the trash
+    pub fn restore_preset(&self, id: &PresetId) -> Result<RestoredPreset> {
+        let deleted = self
+            .repository
+            .list_deleted()?
+            .into_iter()
+            .find(|d| d.preset.id == *id)
+            .ok_or_else(|| PresetError::NotFound {
+                id: id.to_string(),
+            })?;
+        
+        // A live preset may have taken the name since this one was trashed
+        if self.repository.find_by_name(&deleted.preset.pedal_type, &deleted.preset.name)?.is_some() {
+            return Err(PresetError::DuplicateName {
+                name: deleted.preset.name,
+            });
+        }
+        
+        self.repository.restore(id)?;
+        self.changes.publish(PresetChangeEvent::Created(id.clone()));
+        
+        Ok(RestoredPreset {
+            preset: deleted.preset,
+            previous_bank_numbers: deleted.previous_bank_numbers,
+        })
+    }
+    
+    /// Permanently remove presets that have been in the trash for at least `older_than_days`
+    pub fn purge_deleted(&self, older_than_days: u32) -> Result<usize> {
+        let cutoff = chrono::Utc::now().timestamp() - i64::from(older_than_days) * 24 * 60 * 60;
+        let purged = self.repository.purge_deleted(cutoff)?;
+        let count = purged.len();
+        self.changes.publish_all(purged.into_iter().map(PresetChangeEvent::Deleted));
+        Ok(count)
+    }
+    
+    /// Remove bank assignments left pointing at nothing by presets deleted outside the library.
+    /// Returns how many bank rows were cleaned up.
+    pub fn repair_orphaned_banks(&self) -> Result<usize> {
+        let (nulled, missing) = self.repository.delete_orphaned_banks()?;
+        if !missing.is_empty() {
+            eprintln!("⚠️ Removed {} bank assignment(s) referencing presets that no longer exist", missing.len());
+        }
+        let repaired = nulled.len() + missing.len();
+        self.changes.publish_all(
+            nulled
+                .into_iter()
+                .chain(missing)
+                .map(|(pedal_type, bank_number)| PresetChangeEvent::BankChanged { pedal_type, bank_number }),
+        );
+        Ok(repaired)
+    }
+    
+    /// Bank rows outside their pedal's slot range, which no bank view shows
+    pub fn stranded_bank_assignments(&self) -> Result<Vec<StrandedBankAssignment>> {
+        let mut stranded = Vec::new();
+        for pedal_type in bank_config::PEDAL_TYPES {
+            let Some(config) = bank_config::get_bank_config(pedal_type) else { continue };
+            for (bank_number, preset_id, _) in self.repository.get_bank_assignments(pedal_type)? {
+                if config.slot_range().contains(&bank_number) {
+                    continue;
+                }
+                let Some(preset_id) = preset_id else { continue };
+                let preset_name = self.repository.find_by_id(&preset_id)?
+                    .map(|preset| preset.name)
+                    .unwrap_or_default();
+                stranded.push(StrandedBankAssignment {
+                    pedal_type: pedal_type.to_string(),
+                    bank_number,
+                    preset_id,
+                    preset_name,
+                });
+            }
+        }
+        Ok(stranded)
+    }
+    
+    /// Write a complete, consistent copy of the library database to `path` while it stays in
+    /// use. Unlike copying the file, this can't catch a transaction half-written.
+    pub fn backup_to_file(&self, path: PathBuf) -> Result<()> {
+        self.repository.backup_to(&path)
+    }
+    
+    /// Compact the database: checkpoint, VACUUM, ANALYZE and full-text reindex.
+    /// Fails with `PresetError::Busy` instead of waiting on an in-flight write.
+    pub fn run_maintenance(&self) -> Result<MaintenanceReport> {
+        let size_before = self.repository.file_size()?;
+        timed_maintenance(size_before, || {
+            self.repository.run_maintenance()?;
+            self.repository.file_size()
+        })
+    }
+    
+    /// Problems SQLite's integrity check finds; empty when the database is sound
+    pub fn integrity_check(&self) -> Result<Vec<String>> {
+        self.repository.integrity_check()
+    }
+    
+    /// Whether enough of the database is free pages that maintenance is worth running
+    pub fn needs_maintenance(&self, free_page_ratio: f64) -> Result<bool> {
+        Ok(self.repository.free_page_ratio()? > free_page_ratio)
     }
     
     /// Toggle favorite status
     pub fn toggle_favorite(&self, id: &PresetId) -> Result<Preset> {
         let preset = self.get_preset(id)?;
         self.repository.set_favorite(id, !preset.is_favorite)?;
+        self.changes.publish(PresetChangeEvent::Updated(id.clone()));
         self.get_preset(id)
     }
     
+    /// Add tags to many presets at once (e.g. a freshly imported batch).
+    /// Tags are appended to each preset's existing tags, never replacing them.
+    /// Returns how many presets gained at least one tag.
+    pub fn tag_preset_collection(&self, preset_ids: &[PresetId], tags: Vec<String>) -> Result<usize> {
+        let mut new_tags: Vec<String> = Vec::new();
+        for tag in tags {
+            let tag = tag.trim().to_string();
+            if !tag.is_empty() && !new_tags.contains(&tag) {
+                new_tags.push(tag);
+            }
+        }
+        
+        if preset_ids.is_empty() || new_tags.is_empty() {
+            return Ok(0);
+        }
+        
+        let tagged = self.repository.add_tags(preset_ids, &new_tags, chrono::Utc::now().timestamp())?;
+        let count = tagged.len();
+        self.changes.publish_all(tagged.into_iter().map(PresetChangeEvent::Updated));
+        Ok(count)
+    }
+    
     /// Get the state of all pedal banks
     pub fn get_bank_state(&self, pedal_type: &str) -> Result<Vec<BankSlot>> {
-        // Get pedal-specific bank configuration
-        let config = bank_config::get_bank_config(pedal_type)
-            .ok_or_else(|| PresetError::Midi(format!("No bank configuration for pedal type: {}", pedal_type)))?;
-        
-        let range = config.program_change_start..=config.program_change_end;
-        self.bank_tracker.get_bank_state(pedal_type, range, &config)
+        self.bank_tracker.get_bank_state(pedal_type)
     }
     
-    /// Assign a preset to a specific pedal bank
+    /// Assign a preset to a specific pedal bank. On pedals that allow a preset in only one
+    /// bank, fails with `AlreadyAssigned` if the preset is already in another.
     pub fn assign_to_bank(&self, pedal_type: &str, bank_number: u8, preset_id: &PresetId) -> Result<()> {
-        // Validate bank number against pedal config
-        let config = bank_config::get_bank_config(pedal_type)
-            .ok_or_else(|| PresetError::Midi(format!("No bank configuration for pedal type: {}", pedal_type)))?;
-        
-        let _ = BankNumber::new(bank_number, &config)?; // Validates the bank number
-        self.bank_tracker.assign_to_bank(pedal_type, bank_number, preset_id)
+        BankNumber::new(bank_number, pedal_type)?; // Validates against the pedal's bank config
+        self.bank_tracker.assign_to_bank(pedal_type, bank_number, preset_id, false)?;
+        self.changes.publish(PresetChangeEvent::BankChanged { pedal_type: pedal_type.to_string(), bank_number });
+        Ok(())
+    }
+    
+    /// Like `assign_to_bank`, but on one-bank-per-preset pedals the preset's old slot is
+    /// cleared instead of the assignment failing
+    pub fn move_to_bank(&self, pedal_type: &str, bank_number: u8, preset_id: &PresetId) -> Result<()> {
+        BankNumber::new(bank_number, pedal_type)?;
+        let banks_before = self.get_bank_state(pedal_type)?;
+        self.bank_tracker.assign_to_bank(pedal_type, bank_number, preset_id, true)?;
+        self.publish_bank_changes(pedal_type, &banks_before)
     }
     
     /// Clear a bank assignment
     pub fn clear_bank(&self, pedal_type: &str, bank_number: u8) -> Result<()> {
-        // Validate bank number against pedal config
-        let config = bank_config::get_bank_config(pedal_type)
-            .ok_or_else(|| PresetError::Midi(format!("No bank configuration for pedal type: {}", pedal_type)))?;
+        BankNumber::new(bank_number, pedal_type)?; // Validates against the pedal's bank config
+        self.bank_tracker.clear_bank(pedal_type, bank_number)?;
+        self.changes.publish(PresetChangeEvent::BankChanged { pedal_type: pedal_type.to_string(), bank_number });
+        Ok(())
+    }
+    
+    /// Give `to_pedal` the same bank structure as `from_pedal`. Parameters differ between
+    /// pedals, so each slot is matched by preset name; slots without a same-named preset
+    /// for `to_pedal` (or outside its bank range) are left alone. Returns the slots copied.
+    pub fn clone_bank_layout(&self, from_pedal: &str, to_pedal: &str) -> Result<usize> {
+        BankNumber::range(from_pedal)
+            .ok_or_else(|| PresetError::NoBankConfig { pedal_type: from_pedal.to_string() })?;
+        let banks = BankNumber::range(to_pedal)
+            .ok_or_else(|| PresetError::NoBankConfig { pedal_type: to_pedal.to_string() })?;
+        
+        let banks_before = self.get_bank_state(to_pedal)?;
+        let copied = self.repository.clone_bank_assignments(from_pedal, to_pedal, banks)?;
+        self.publish_bank_changes(to_pedal, &banks_before)?;
+        Ok(copied)
+    }
+    
+    /// Plan filling `pedal_type`'s banks from the presets matching `filter` (always narrowed to
+    /// the pedal type, unpaged). Nothing is written; this is also what a dry run returns.
+    pub fn plan_autofill(&self, pedal_type: &str, filter: PresetFilter, strategy: &FillStrategy) -> Result<AutofillPlan> {
+        let banks = BankNumber::range(pedal_type)
+            .ok_or_else(|| PresetError::NoBankConfig { pedal_type: pedal_type.to_string() })?;
+        let matches = self.repository.list(&PresetFilter {
+            pedal_type: Some(pedal_type.to_string()),
+            limit: None,
+            offset: None,
+            ..filter
+        })?;
+        
+        let ordered = autofill::order_matches(matches, &strategy.order);
+        Ok(autofill::plan_slots(pedal_type, ordered, banks, strategy.clear_remainder))
+    }
+    
+    /// Write one planned slot. Assignments move, so on one-bank-per-preset pedals a preset's
+    /// old slot is cleared rather than the fill failing.
+    pub fn apply_autofill_slot(&self, pedal_type: &str, slot: &AutofillSlot) -> Result<AutofillSlotStatus> {
+        match &slot.preset_id {
+            Some(preset_id) => {
+                self.move_to_bank(pedal_type, slot.bank_number, preset_id)?;
+                Ok(AutofillSlotStatus::Assigned)
+            }
+            None => {
+                self.clear_bank(pedal_type, slot.bank_number)?;
+                Ok(AutofillSlotStatus::Cleared)
+            }
+        }
+    }
+    
+    /// Write a plan's slots in order. Each slot is its own write: on a failure the slots
+    /// before it stay assigned and the rest are skipped.
+    pub fn apply_autofill(&self, plan: AutofillPlan) -> AutofillReport {
+        self.apply_autofill_with(plan, |_, _, _| Ok(false))
+    }
+    
+    /// `apply_autofill`, calling `before_slot` with the slot's index ahead of each library
+    /// write - to report progress and store the preset on the pedal. It returns whether the
+    /// slot was saved to the pedal; an error fails the slot without writing it.
+    pub fn apply_autofill_with(
+        &self,
+        plan: AutofillPlan,
+        mut before_slot: impl FnMut(&Self, usize, &AutofillSlot) -> std::result::Result<bool, String>,
+    ) -> AutofillReport {
+        let mut results = Vec::with_capacity(plan.slots.len());
+        let mut failed = false;
+        for (index, slot) in plan.slots.iter().enumerate() {
+            let status = if failed {
+                AutofillSlotStatus::Skipped
+            } else {
+                before_slot(self, index, slot)
+                    .and_then(|saved_to_pedal| {
+                        let status = self.apply_autofill_slot(&plan.pedal_type, slot).map_err(|e| e.to_string())?;
+                        Ok(if saved_to_pedal { AutofillSlotStatus::SavedToPedal } else { status })
+                    })
+                    .unwrap_or_else(|error| {
+                        failed = true;
+                        AutofillSlotStatus::Failed { error }
+                    })
+            };
+            results.push(AutofillSlotResult::new(slot, status));
+        }
         
-        let _ = BankNumber::new(bank_number, &config)?; // Validates the bank number
-        self.bank_tracker.clear_bank(pedal_type, bank_number)
+        AutofillReport { plan, dry_run: false, results }
+    }
+    
+    /// Export bank assignments for every pedal type (for documenting a pedalboard)
+    pub fn export_bank_layout(&self) -> Result<BankLayout> {
+        let mut layout = BankLayout::default();
+        
+        for pedal_type in bank_config::PEDAL_TYPES {
+            let slots = self
+                .get_bank_state(pedal_type)?
+                .into_iter()
+                .filter_map(|slot| {
+                    let preset = slot.preset?;
+                    Some(BankSlotDetail {
+                        bank_number: slot.bank_number,
+                        bank_label: slot.bank_label,
+                        preset_id: preset.id,
+                        preset_name: preset.name,
+                        synced_at: slot.synced_at,
+                    })
+                })
+                .collect();
+            layout.pedals.insert(pedal_type.to_string(), slots);
+        }
+        
+        Ok(layout)
     }
     
     /// Get all presets with their bank assignments (for library drawer)
@@ -201,21 +653,167 @@ impl PresetLibrary {
         self.repository.find_all_with_banks(pedal_type)
     }
     
+    /// Capture what a bank currently holds, before a save sequence overwrites it
+    pub fn snapshot_bank(&self, pedal_type: &str, bank_number: u8) -> Result<BankSnapshot> {
+        Ok(BankSnapshot {
+            pedal_type: pedal_type.to_string(),
+            bank_number,
+            previous: self.get_bank_preset(pedal_type, bank_number)?,
+        })
+    }
+    
+    /// Record a snapshot once its save sequence has completed.
+    /// Nothing is recorded for an empty bank, or when `incoming` matches what the bank held.
+    pub fn record_bank_overwrite(
+        &self,
+        snapshot: &BankSnapshot,
+        device_name: &str,
+        incoming: &serde_json::Value,
+    ) -> Result<Option<BankHistoryEntry>> {
+        let Some(previous) = &snapshot.previous else {
+            return Ok(None);
+        };
+        if previous.parameters == *incoming {
+            return Ok(None);
+        }
+        
+        self.repository
+            .insert_bank_history(
+                &snapshot.pedal_type,
+                snapshot.bank_number,
+                device_name,
+                previous,
+                chrono::Utc::now().timestamp(),
+                BANK_HISTORY_LIMIT,
+            )
+            .map(Some)
+    }
+    
+    /// What a bank held before each of its recent overwrites, newest first
+    pub fn get_bank_history(&self, pedal_type: &str, bank_number: u8) -> Result<Vec<BankHistoryEntry>> {
+        self.repository.list_bank_history(pedal_type, bank_number)
+    }
+    
+    /// Get a single bank history entry
+    pub fn get_bank_history_entry(&self, id: i64) -> Result<BankHistoryEntry> {
+        self.repository
+            .find_bank_history_entry(id)?
+            .ok_or(PresetError::HistoryEntryNotFound { id })
+    }
+    
     /// Get the preset assigned to a specific bank
     pub fn get_bank_preset(&self, pedal_type: &str, bank_number: u8) -> Result<Option<Preset>> {
-        // Validate bank number against pedal config
-        let config = bank_config::get_bank_config(pedal_type)
-            .ok_or_else(|| PresetError::Midi(format!("No bank configuration for pedal type: {}", pedal_type)))?;
-        
-        let _ = BankNumber::new(bank_number, &config)?; // Validates the bank number
+        BankNumber::new(bank_number, pedal_type)?; // Validates against the pedal's bank config
         self.bank_tracker.get_bank_preset(pedal_type, bank_number)
     }
+    
+    /// Publish `BankChanged` for every slot of `pedal_type` whose preset differs from `before`
+    fn publish_bank_changes(&self, pedal_type: &str, before: &[BankSlot]) -> Result<()> {
+        let preset_id = |slot: &BankSlot| slot.preset.as_ref().map(|preset| preset.id.clone());
+        let after = self.get_bank_state(pedal_type)?;
+        self.changes.publish_all(
+            after
+                .iter()
+                .filter(|slot| {
+                    !before
+                        .iter()
+                        .any(|old| old.bank_number == slot.bank_number && preset_id(old) == preset_id(slot))
+                })
+                .map(|slot| PresetChangeEvent::BankChanged { pedal_type: pedal_type.to_string(), bank_number: slot.bank_number }),
+        );
+        Ok(())
+    }
+    
+    /// Bank grids of the pedal types where live presets in `ids` hold slots, taken before
+    /// they're deleted so `publish_all_bank_changes` can tell which slots emptied
+    fn banks_holding(&self, ids: &[PresetId]) -> Result<Vec<(String, Vec<BankSlot>)>> {
+        let mut banks: Vec<(String, Vec<BankSlot>)> = Vec::new();
+        for id in ids {
+            let Some(preset) = self.repository.find_by_id(id)? else { continue };
+            if banks.iter().any(|(pedal_type, _)| *pedal_type == preset.pedal_type)
+                || self.repository.banks_for_preset(&preset.pedal_type, id)?.is_empty()
+            {
+                continue;
+            }
+            let before = self.get_bank_state(&preset.pedal_type)?;
+            banks.push((preset.pedal_type, before));
+        }
+        Ok(banks)
+    }
+    
+    /// `publish_bank_changes` for each grid from `banks_holding`
+    fn publish_all_bank_changes(&self, banks_before: &[(String, Vec<BankSlot>)]) -> Result<()> {
+        for (pedal_type, before) in banks_before {
+            self.publish_bank_changes(pedal_type, before)?;
+        }
+        Ok(())
+    }
+}
+
+/// Ids a bulk operation was applied to
+fn applied(results: &[BulkPresetResult]) -> impl Iterator<Item = PresetId> + '_ {
+    results
+        .iter()
+        .filter(|result| result.status == BulkPresetStatus::Applied)
+        .map(|result| result.id.clone())
+}
+
+/// Run `maintain` (which compacts the database and returns its new size) and report on it
+fn timed_maintenance(size_before: u64, maintain: impl FnOnce() -> Result<u64>) -> Result<MaintenanceReport> {
+    let started = std::time::Instant::now();
+    let report = MaintenanceReport {
+        size_before,
+        size_after: maintain()?,
+        duration_ms: started.elapsed().as_millis() as u64,
+    };
+    println!(
+        "[Presets] 🧹 Maintenance: {} → {} bytes in {} ms",
+        report.size_before, report.size_after, report.duration_ms
+    );
+    Ok(report)
+}
+
+/// Run maintenance on the database at `db_path` if more than `free_page_ratio` of it is
+/// free pages, on a connection of its own so the shared library stays usable meanwhile.
+/// Returns None when it wasn't needed.
+pub fn maintain_database(db_path: &Path, free_page_ratio: f64) -> Result<Option<MaintenanceReport>> {
+    let conn = PresetRepository::open_for_maintenance(db_path)?;
+    if PresetRepository::free_page_ratio_on(&conn)? <= free_page_ratio {
+        return Ok(None);
+    }
+    let size_before = PresetRepository::file_size_at(db_path)?;
+    timed_maintenance(size_before, || {
+        PresetRepository::run_maintenance_on(&conn)?;
+        PresetRepository::file_size_at(db_path)
+    })
+    .map(Some)
 }
 
 /// Create a shared preset library for use in Tauri state management
 pub type SharedPresetLibrary = Arc<Mutex<PresetLibrary>>;
 
+/// How long deleted presets stay in the trash before being purged on startup
+pub const TRASH_RETENTION_DAYS: u32 = 30;
+
+/// Overwrite history entries kept per bank
+pub const BANK_HISTORY_LIMIT: usize = 10;
+
+/// Run maintenance on startup once more than this fraction of the database is free pages
+pub const MAINTENANCE_FREE_PAGE_RATIO: f64 = 0.25;
+
+/// Page size for a paged listing that doesn't set its own limit
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// Emitted with a `MaintenanceReport` when maintenance finishes
+pub const MAINTENANCE_COMPLETE_EVENT: &str = "preset-maintenance-complete";
+
 pub fn create_shared_library(db_path: PathBuf) -> Result<SharedPresetLibrary> {
     let library = PresetLibrary::new(db_path)?;
+    
+    let purged = library.purge_deleted(TRASH_RETENTION_DAYS)?;
+    if purged > 0 {
+        println!("[Presets] 🗑️ Purged {} preset(s) from the trash", purged);
+    }
+    
     Ok(Arc::new(Mutex::new(library)))
 }
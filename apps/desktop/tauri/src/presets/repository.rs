@@ -1,22 +1,41 @@
 // Preset repository - SQLite persistence (infrastructure layer)
 use super::types::*;
-use rusqlite::{params, Connection, OptionalExtension};
-use std::path::PathBuf;
+use rusqlite::{params, Connection, DatabaseName, OptionalExtension};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
+/// The preset columns `preset_from_row` reads, in the order it reads them
+const PRESET_COLUMNS: [&str; 11] = [
+    "id", "name", "pedal_type", "description", "parameters", "tags",
+    "is_favorite", "created_at", "updated_at", "is_archived", "metadata",
+];
+
 /// Repository for preset persistence
 pub struct PresetRepository {
     conn: Arc<Mutex<Connection>>,
+    db_path: PathBuf,
 }
 
 impl PresetRepository {
     /// Create a new repository with the given database path
     pub fn new(db_path: PathBuf) -> Result<Self> {
-        let conn = Connection::open(db_path)?;
+        let conn = Connection::open(&db_path)?;
+        
+        // WAL lets readers proceed while a write is in progress; NORMAL sync is safe under WAL
+        conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get::<_, String>(0))?;
+        conn.pragma_update(None, "synchronous", "NORMAL")?;
+        // Pedalboards keep their own connection to the same file; wait out its writes
+        conn.busy_timeout(std::time::Duration::from_secs(5))?;
+        conn.set_prepared_statement_cache_capacity(32);
+        
         let repo = Self {
             conn: Arc::new(Mutex::new(conn)),
+            db_path,
         };
         repo.init_schema()?;
+        
+        // After the schema migrations: rebuilding `presets` with enforcement on would null out every bank row
+        repo.conn.lock().unwrap().pragma_update(None, "foreign_keys", "ON")?;
         Ok(repo)
     }
     
@@ -28,18 +47,87 @@ impl PresetRepository {
         conn.execute(
             "CREATE TABLE IF NOT EXISTS presets (
                 id TEXT PRIMARY KEY,
-                name TEXT NOT NULL UNIQUE,
+                name TEXT NOT NULL,
                 pedal_type TEXT NOT NULL,
                 description TEXT,
                 parameters TEXT NOT NULL,
                 tags TEXT,
                 is_favorite INTEGER NOT NULL DEFAULT 0,
                 created_at INTEGER NOT NULL,
-                updated_at INTEGER NOT NULL
+                updated_at INTEGER NOT NULL,
+                deleted_at INTEGER,
+                is_archived INTEGER NOT NULL DEFAULT 0
             )",
             [],
         )?;
         
+        // Migration: soft delete. Older databases have a table-level UNIQUE on name,
+        // which SQLite can't drop in place, so rebuild the table without it.
+        let has_deleted_at: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('presets') WHERE name = 'deleted_at'",
+            [],
+            |row| row.get(0),
+        )?;
+        
+        if has_deleted_at == 0 {
+            conn.execute_batch(
+                "BEGIN;
+                 CREATE TABLE presets_new (
+                    id TEXT PRIMARY KEY,
+                    name TEXT NOT NULL,
+                    pedal_type TEXT NOT NULL,
+                    description TEXT,
+                    parameters TEXT NOT NULL,
+                    tags TEXT,
+                    is_favorite INTEGER NOT NULL DEFAULT 0,
+                    created_at INTEGER NOT NULL,
+                    updated_at INTEGER NOT NULL,
+                    deleted_at INTEGER
+                 );
+                 INSERT INTO presets_new (id, name, pedal_type, description, parameters, tags, is_favorite, created_at, updated_at)
+                    SELECT id, name, pedal_type, description, parameters, tags, is_favorite, created_at, updated_at FROM presets;
+                 DROP TABLE presets;
+                 ALTER TABLE presets_new RENAME TO presets;
+                 COMMIT;",
+            )?;
+        }
+        
+        // Migration: archiving
+        let has_is_archived: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('presets') WHERE name = 'is_archived'",
+            [],
+            |row| row.get(0),
+        )?;
+        
+        if has_is_archived == 0 {
+            conn.execute(
+                "ALTER TABLE presets ADD COLUMN is_archived INTEGER NOT NULL DEFAULT 0",
+                [],
+            )?;
+        }
+        
+        // Migration: free-form metadata
+        let has_metadata: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('presets') WHERE name = 'metadata'",
+            [],
+            |row| row.get(0),
+        )?;
+        
+        if has_metadata == 0 {
+            conn.execute(
+                "ALTER TABLE presets ADD COLUMN metadata TEXT NOT NULL DEFAULT '{}'",
+                [],
+            )?;
+        }
+        
+        // Names only need to be unique among live presets of one pedal type, so a
+        // "Lead" preset can exist for each pedal (older databases were unique across pedals)
+        conn.execute("DROP INDEX IF EXISTS idx_live_name", [])?;
+        conn.execute(
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_live_pedal_name ON presets(pedal_type, name) WHERE deleted_at IS NULL",
+            [],
+        )?;
+        
         // Create indexes
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_pedal_type ON presets(pedal_type)",
@@ -64,38 +152,130 @@ impl PresetRepository {
             [],
         )?;
         
+        // Bank slots a trashed preset held when it was deleted (offered back on restore)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS trashed_bank_assignments (
+                preset_id TEXT NOT NULL,
+                pedal_type TEXT NOT NULL,
+                bank_number INTEGER NOT NULL,
+                PRIMARY KEY (preset_id, pedal_type, bank_number)
+            )",
+            [],
+        )?;
+        
+        // What each bank held before a save overwrote it
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS bank_overwrite_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                pedal_type TEXT NOT NULL,
+                bank_number INTEGER NOT NULL,
+                device_name TEXT NOT NULL,
+                preset_id TEXT NOT NULL,
+                preset_name TEXT NOT NULL,
+                parameters TEXT NOT NULL,
+                overwritten_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+        
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_bank_history ON bank_overwrite_history(pedal_type, bank_number)",
+            [],
+        )?;
+        
+        // Migration: full-text search over name, description and tags. The index holds no
+        // copy of the text (content='presets'); triggers keep it in step with the table.
+        let has_fts: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'presets_fts'",
+            [],
+            |row| row.get(0),
+        )?;
+        
+        conn.execute_batch(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS presets_fts USING fts5(
+                name, description, tags, content='presets', content_rowid='rowid'
+             );
+             CREATE TRIGGER IF NOT EXISTS presets_fts_insert AFTER INSERT ON presets BEGIN
+                INSERT INTO presets_fts(rowid, name, description, tags)
+                    VALUES (new.rowid, new.name, new.description, new.tags);
+             END;
+             CREATE TRIGGER IF NOT EXISTS presets_fts_delete AFTER DELETE ON presets BEGIN
+                INSERT INTO presets_fts(presets_fts, rowid, name, description, tags)
+                    VALUES ('delete', old.rowid, old.name, old.description, old.tags);
+             END;
+             CREATE TRIGGER IF NOT EXISTS presets_fts_update AFTER UPDATE OF name, description, tags ON presets BEGIN
+                INSERT INTO presets_fts(presets_fts, rowid, name, description, tags)
+                    VALUES ('delete', old.rowid, old.name, old.description, old.tags);
+                INSERT INTO presets_fts(rowid, name, description, tags)
+                    VALUES (new.rowid, new.name, new.description, new.tags);
+             END;",
+        )?;
+        
+        // Index presets saved before the table existed
+        if has_fts == 0 {
+            conn.execute("INSERT INTO presets_fts(presets_fts) VALUES ('rebuild')", [])?;
+        }
+        
         Ok(())
     }
     
     /// Save a preset to the database
     pub fn save(&self, preset: &Preset) -> Result<()> {
         let conn = self.conn.lock().unwrap();
+        Self::upsert_preset(&conn, preset)
+    }
+    
+    /// Save a new preset and assign it to a bank in one transaction: if the assignment
+    /// fails, the preset isn't saved either. Returns the slot's sync time.
+    pub fn save_and_assign(&self, preset: &Preset, pedal_type: &str, bank_number: u8) -> Result<i64> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let now = chrono::Utc::now().timestamp();
+        
+        Self::upsert_preset(&tx, preset)?;
+        tx.prepare_cached(
+            "INSERT INTO pedal_banks (pedal_type, bank_number, preset_id, synced_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(pedal_type, bank_number) DO UPDATE SET
+                preset_id = excluded.preset_id,
+                synced_at = excluded.synced_at",
+        )?.execute(params![pedal_type, bank_number, preset.id.as_str(), now])?;
         
+        tx.commit()?;
+        Ok(now)
+    }
+    
+    /// Insert or update one preset row (`conn` may be a transaction)
+    fn upsert_preset(conn: &Connection, preset: &Preset) -> Result<()> {
         let tags_json = serde_json::to_string(&preset.tags)?;
         let parameters_json = serde_json::to_string(&preset.parameters)?;
+        let metadata_json = serde_json::to_string(&preset.metadata)?;
         
-        conn.execute(
-            "INSERT INTO presets (id, name, pedal_type, description, parameters, tags, is_favorite, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+        conn.prepare_cached(
+            "INSERT INTO presets (id, name, pedal_type, description, parameters, tags, is_favorite, created_at, updated_at, is_archived, metadata)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
              ON CONFLICT(id) DO UPDATE SET
                 name = excluded.name,
                 description = excluded.description,
                 parameters = excluded.parameters,
                 tags = excluded.tags,
                 is_favorite = excluded.is_favorite,
-                updated_at = excluded.updated_at",
-            params![
-                preset.id.as_str(),
-                preset.name,
-                preset.pedal_type,
-                preset.description,
-                parameters_json,
-                tags_json,
-                if preset.is_favorite { 1 } else { 0 },
-                preset.created_at,
-                preset.updated_at,
-            ],
-        )?;
+                updated_at = excluded.updated_at,
+                is_archived = excluded.is_archived,
+                metadata = excluded.metadata",
+        )?.execute(params![
+            preset.id.as_str(),
+            preset.name,
+            preset.pedal_type,
+            preset.description,
+            parameters_json,
+            tags_json,
+            if preset.is_favorite { 1 } else { 0 },
+            preset.created_at,
+            preset.updated_at,
+            if preset.is_archived { 1 } else { 0 },
+            metadata_json,
+        ])?;
         
         Ok(())
     }
@@ -105,167 +285,536 @@ impl PresetRepository {
         let conn = self.conn.lock().unwrap();
         
         let preset = conn
-            .query_row(
-                "SELECT id, name, pedal_type, description, parameters, tags, is_favorite, created_at, updated_at
-                 FROM presets WHERE id = ?1",
-                params![id.as_str()],
-                |row| {
-                    let tags_json: String = row.get(5)?;
-                    let tags: Vec<String> = serde_json::from_str(&tags_json).unwrap_or_default();
-                    
-                    let parameters_json: String = row.get(4)?;
-                    let parameters: serde_json::Value = serde_json::from_str(&parameters_json)
-                        .unwrap_or(serde_json::Value::Null);
-                    
-                    Ok(Preset {
-                        id: PresetId::new(row.get(0)?),
-                        name: row.get(1)?,
-                        pedal_type: row.get(2)?,
-                        description: row.get(3)?,
-                        parameters,
-                        tags,
-                        is_favorite: row.get::<_, i32>(6)? != 0,
-                        created_at: row.get(7)?,
-                        updated_at: row.get(8)?,
-                    })
-                },
-            )
+            .prepare_cached(&format!(
+                "SELECT {} FROM presets WHERE id = ?1 AND deleted_at IS NULL",
+                Self::preset_select_list(None, true)
+            ))?
+            .query_row(params![id.as_str()], Self::preset_from_row)
             .optional()?;
         
         Ok(preset)
     }
     
-    /// Find a preset by name
-    pub fn find_by_name(&self, name: &str) -> Result<Option<Preset>> {
+    /// Find a live preset of a pedal type by name
+    pub fn find_by_name(&self, pedal_type: &str, name: &str) -> Result<Option<Preset>> {
         let conn = self.conn.lock().unwrap();
         
         let preset = conn
-            .query_row(
-                "SELECT id, name, pedal_type, description, parameters, tags, is_favorite, created_at, updated_at
-                 FROM presets WHERE name = ?1",
-                params![name],
-                |row| {
-                    let tags_json: String = row.get(5)?;
-                    let tags: Vec<String> = serde_json::from_str(&tags_json).unwrap_or_default();
-                    
-                    let parameters_json: String = row.get(4)?;
-                    let parameters: serde_json::Value = serde_json::from_str(&parameters_json)
-                        .unwrap_or(serde_json::Value::Null);
-                    
-                    Ok(Preset {
-                        id: PresetId::new(row.get(0)?),
-                        name: row.get(1)?,
-                        pedal_type: row.get(2)?,
-                        description: row.get(3)?,
-                        parameters,
-                        tags,
-                        is_favorite: row.get::<_, i32>(6)? != 0,
-                        created_at: row.get(7)?,
-                        updated_at: row.get(8)?,
-                    })
-                },
-            )
+            .prepare_cached(&format!(
+                "SELECT {} FROM presets WHERE pedal_type = ?1 AND name = ?2 AND deleted_at IS NULL",
+                Self::preset_select_list(None, true)
+            ))?
+            .query_row(params![pedal_type, name], Self::preset_from_row)
             .optional()?;
         
         Ok(preset)
     }
     
-    /// List all presets with optional filtering
-    pub fn list(&self, filter: &PresetFilter) -> Result<Vec<Preset>> {
-        let conn = self.conn.lock().unwrap();
+    /// Build the WHERE clause, ordering and page for a filter
+    fn filter_clause(filter: &PresetFilter) -> (String, Vec<rusqlite::types::Value>) {
+        let (mut clause, mut params) = Self::where_clause(filter);
         
-        let mut query = String::from(
-            "SELECT id, name, pedal_type, description, parameters, tags, is_favorite, created_at, updated_at FROM presets WHERE 1=1"
-        );
-
+        // id breaks ties so pages don't overlap or skip when presets share updated_at
+        clause.push_str(" ORDER BY updated_at DESC, id");
+        if filter.limit.is_some() || filter.offset.is_some() {
+            // LIMIT -1 is SQLite for "no limit", needed to use OFFSET alone
+            clause.push_str(" LIMIT ? OFFSET ?");
+            params.push(filter.limit.map_or(-1, |limit| limit as i64).into());
+            params.push((filter.offset.unwrap_or(0) as i64).into());
+        }
+        (clause, params)
+    }
+    
+    /// Build the WHERE clause for a filter
+    fn where_clause(filter: &PresetFilter) -> (String, Vec<rusqlite::types::Value>) {
+        let mut clause = String::from(" WHERE deleted_at IS NULL");
         let mut params: Vec<rusqlite::types::Value> = Vec::new();
 
         if let Some(ref pedal_type) = filter.pedal_type {
-            query.push_str(" AND pedal_type = ?");
+            clause.push_str(" AND pedal_type = ?");
             params.push(pedal_type.clone().into());
         }
 
+        // Any of the tags
+        if !filter.tags.is_empty() {
+            let placeholders = vec!["?"; filter.tags.len()].join(", ");
+            clause.push_str(&format!(" AND EXISTS (SELECT 1 FROM json_each(tags) WHERE value IN ({}))", placeholders));
+            params.extend(filter.tags.iter().map(|tag| tag.clone().into()));
+        }
+
         if let Some(is_favorite) = filter.is_favorite {
-            query.push_str(" AND is_favorite = ?");
+            clause.push_str(" AND is_favorite = ?");
             params.push((if is_favorite { 1i64 } else { 0i64 }).into());
         }
 
+        clause.push_str(" AND is_archived = ?");
+        params.push((if filter.is_archived.unwrap_or(false) { 1i64 } else { 0i64 }).into());
+
         if let Some(ref search) = filter.search_query {
-            query.push_str(" AND (name LIKE ? OR description LIKE ?)");
+            clause.push_str(" AND (name LIKE ? OR description LIKE ?)");
             let pattern = format!("%{}%", search);
             params.push(pattern.clone().into());
             params.push(pattern.into());
         }
 
-        query.push_str(" ORDER BY updated_at DESC");
+        if let Some(created_after) = filter.created_after {
+            clause.push_str(" AND created_at >= ?");
+            params.push(created_after.into());
+        }
+
+        if let Some(created_before) = filter.created_before {
+            clause.push_str(" AND created_at <= ?");
+            params.push(created_before.into());
+        }
+
+        if let Some(ref key) = filter.metadata_key {
+            clause.push_str(" AND EXISTS (SELECT 1 FROM json_each(metadata) WHERE key = ?)");
+            params.push(key.clone().into());
+        }
 
-        let mut stmt = conn.prepare(&query)?;
-        let preset_iter = stmt.query_map(rusqlite::params_from_iter(params.iter()), |row| {
-            let tags_json: String = row.get(5)?;
+        // `NOT IN ()` is a syntax error, so an empty list adds no clause at all
+        if !filter.exclude_ids.is_empty() {
+            let placeholders = vec!["?"; filter.exclude_ids.len()].join(", ");
+            clause.push_str(&format!(" AND id NOT IN ({})", placeholders));
+            params.extend(filter.exclude_ids.iter().map(|id| id.as_str().to_string().into()));
+        }
+
+        (clause, params)
+    }
+    
+    /// Number of presets matching a filter, ignoring its page
+    pub fn count(&self, filter: &PresetFilter) -> Result<usize> {
+        let conn = self.conn.lock().unwrap();
+        let (clause, params) = Self::where_clause(filter);
+        let count: i64 = conn
+            .prepare_cached(&format!("SELECT COUNT(*) FROM presets{}", clause))?
+            .query_row(rusqlite::params_from_iter(params.iter()), |row| row.get(0))?;
+        Ok(count as usize)
+    }
+    
+    /// Live, unarchived presets matching every word of `query` (as a prefix) in their name,
+    /// description or tags, best match first
+    pub fn search_fulltext(&self, query: &str) -> Result<Vec<Preset>> {
+        // Quote each word so FTS5 operators and punctuation in the query are taken literally
+        let match_expr = query
+            .split_whitespace()
+            .map(|word| format!("\"{}\"*", word.replace('"', "\"\"")))
+            .collect::<Vec<_>>()
+            .join(" ");
+        if match_expr.is_empty() {
+            return Ok(Vec::new());
+        }
+        
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(&format!(
+            "SELECT {}
+             FROM presets_fts
+             JOIN presets p ON p.rowid = presets_fts.rowid
+             WHERE presets_fts MATCH ?1 AND p.deleted_at IS NULL AND p.is_archived = 0
+             ORDER BY bm25(presets_fts)",
+            Self::preset_select_list(Some("p"), true)
+        ))?;
+        let rows = stmt.query_map(params![match_expr], Self::preset_from_row)?;
+        
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+    
+    /// List all presets with optional filtering
+    pub fn list(&self, filter: &PresetFilter) -> Result<Vec<Preset>> {
+        self.list_with(filter, true)
+    }
+    
+    /// List presets, leaving `parameters` null unless `include_parameters` - list views
+    /// don't need them, and they are most of each row
+    pub fn list_with(&self, filter: &PresetFilter, include_parameters: bool) -> Result<Vec<Preset>> {
+        let conn = self.conn.lock().unwrap();
+        
+        let (clause, params) = Self::filter_clause(filter);
+        let query = format!(
+            "SELECT {} FROM presets{}",
+            Self::preset_select_list(None, include_parameters),
+            clause
+        );
+
+        let mut stmt = conn.prepare_cached(&query)?;
+        let preset_iter = stmt.query_map(rusqlite::params_from_iter(params.iter()), Self::preset_from_row)?;
+        
+        let mut presets = Vec::new();
+        for preset in preset_iter {
+            presets.push(preset?);
+        }
+        
+        Ok(presets)
+    }
+    
+    /// List preset metadata only - skips reading and parsing the parameters JSON
+    pub fn list_summaries(&self, filter: &PresetFilter) -> Result<Vec<PresetSummary>> {
+        let conn = self.conn.lock().unwrap();
+        
+        let (clause, params) = Self::filter_clause(filter);
+        let query = format!(
+            "SELECT id, name, pedal_type, description, tags, is_favorite, created_at, updated_at, is_archived, metadata FROM presets{}",
+            clause
+        );
+
+        let mut stmt = conn.prepare_cached(&query)?;
+        let summary_iter = stmt.query_map(rusqlite::params_from_iter(params.iter()), |row| {
+            let tags_json: String = row.get(4)?;
             let tags: Vec<String> = serde_json::from_str(&tags_json).unwrap_or_default();
             
-            let parameters_json: String = row.get(4)?;
-            let parameters: serde_json::Value = serde_json::from_str(&parameters_json)
-                .unwrap_or(serde_json::Value::Null);
+            let metadata_json: String = row.get(9)?;
+            let metadata: serde_json::Value = serde_json::from_str(&metadata_json)
+                .unwrap_or_else(|_| serde_json::json!({}));
             
-            Ok(Preset {
+            Ok(PresetSummary {
                 id: PresetId::new(row.get(0)?),
                 name: row.get(1)?,
                 pedal_type: row.get(2)?,
                 description: row.get(3)?,
-                parameters,
                 tags,
-                is_favorite: row.get::<_, i32>(6)? != 0,
-                created_at: row.get(7)?,
-                updated_at: row.get(8)?,
+                metadata,
+                is_favorite: row.get::<_, i32>(5)? != 0,
+                is_archived: row.get::<_, i32>(8)? != 0,
+                created_at: row.get(6)?,
+                updated_at: row.get(7)?,
             })
         })?;
         
-        let mut presets = Vec::new();
-        for preset in preset_iter {
-            presets.push(preset?);
+        let mut summaries = Vec::new();
+        for summary in summary_iter {
+            summaries.push(summary?);
         }
         
-        // Filter by tags if specified (post-query filtering)
-        if !filter.tags.is_empty() {
-            presets.retain(|p| {
-                filter.tags.iter().any(|tag| p.tags.contains(tag))
+        Ok(summaries)
+    }
+    
+    /// Soft-delete a preset: mark it trashed and move its bank assignments aside
+    pub fn delete(&self, id: &PresetId) -> Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        
+        if !Self::trash_in(&tx, id)? {
+            return Err(PresetError::NotFound {
+                id: id.to_string(),
             });
         }
         
-        Ok(presets)
+        tx.commit()?;
+        Ok(())
     }
     
-    /// Delete a preset
-    pub fn delete(&self, id: &PresetId) -> Result<()> {
-        let conn = self.conn.lock().unwrap();
+    /// Trash a live preset within a transaction. Returns false if there was no live preset.
+    fn trash_in(tx: &rusqlite::Transaction, id: &PresetId) -> Result<bool> {
+        let rows_affected = tx.prepare_cached(
+            "UPDATE presets SET deleted_at = ?1 WHERE id = ?2 AND deleted_at IS NULL",
+        )?.execute(params![chrono::Utc::now().timestamp(), id.as_str()])?;
         
-        let rows_affected = conn.execute(
-            "DELETE FROM presets WHERE id = ?1",
-            params![id.as_str()],
+        if rows_affected == 0 {
+            return Ok(false);
+        }
+        
+        tx.prepare_cached(
+            "INSERT OR REPLACE INTO trashed_bank_assignments (preset_id, pedal_type, bank_number)
+             SELECT preset_id, pedal_type, bank_number FROM pedal_banks WHERE preset_id = ?1",
+        )?.execute(params![id.as_str()])?;
+        
+        tx.prepare_cached(
+            "DELETE FROM pedal_banks WHERE preset_id = ?1",
+        )?.execute(params![id.as_str()])?;
+        
+        Ok(true)
+    }
+    
+    /// Delete several presets in one transaction - archived ones for good, the rest to the trash.
+    /// Each step is one statement over the whole batch, bound as `IN (...)`. With `partial`,
+    /// missing presets are reported and skipped; without it, the first one rolls everything back.
+    pub fn delete_many(&self, ids: &[PresetId], partial: bool) -> Result<Vec<BulkPresetResult>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let placeholders = vec!["?"; ids.len()].join(", ");
+        let bound = || rusqlite::params_from_iter(ids.iter().map(PresetId::as_str));
+        let live_in = format!("SELECT id FROM presets WHERE deleted_at IS NULL AND id IN ({})", placeholders);
+        
+        let mut live = tx.prepare(&live_in)?
+            .query_map(bound(), |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<std::collections::HashSet<_>>>()?;
+        // An id listed twice is deleted once; the repeat finds nothing left
+        let results = ids
+            .iter()
+            .map(|id| Self::bulk_result(id, live.remove(id.as_str()), partial))
+            .collect::<Result<Vec<_>>>()?;
+        
+        tx.execute(
+            &format!(
+                "INSERT OR REPLACE INTO trashed_bank_assignments (preset_id, pedal_type, bank_number)
+                 SELECT preset_id, pedal_type, bank_number FROM pedal_banks
+                 WHERE preset_id IN ({} AND is_archived = 0)",
+                live_in
+            ),
+            bound(),
+        )?;
+        tx.execute(&format!("DELETE FROM pedal_banks WHERE preset_id IN ({})", live_in), bound())?;
+        tx.execute(
+            &format!("DELETE FROM trashed_bank_assignments WHERE preset_id IN ({} AND is_archived = 1)", live_in),
+            bound(),
+        )?;
+        tx.execute(
+            &format!("DELETE FROM presets WHERE deleted_at IS NULL AND is_archived = 1 AND id IN ({})", placeholders),
+            bound(),
+        )?;
+        let deleted_at = rusqlite::types::Value::Integer(chrono::Utc::now().timestamp());
+        tx.execute(
+            &format!("UPDATE presets SET deleted_at = ? WHERE deleted_at IS NULL AND id IN ({})", placeholders),
+            rusqlite::params_from_iter(
+                std::iter::once(deleted_at).chain(ids.iter().map(|id| rusqlite::types::Value::Text(id.to_string()))),
+            ),
         )?;
         
+        tx.commit()?;
+        Ok(results)
+    }
+    
+    /// Which of `ids` hold at least one bank slot, bound as one `IN (...)` query
+    pub fn ids_with_bank_assignments(&self, ids: &[PresetId]) -> Result<std::collections::HashSet<String>> {
+        if ids.is_empty() {
+            return Ok(std::collections::HashSet::new());
+        }
+        
+        let conn = self.conn.lock().unwrap();
+        let placeholders = vec!["?"; ids.len()].join(", ");
+        let mut stmt = conn.prepare(&format!(
+            "SELECT DISTINCT preset_id FROM pedal_banks WHERE preset_id IN ({})", placeholders
+        ))?;
+        let assigned = stmt.query_map(
+            rusqlite::params_from_iter(ids.iter().map(PresetId::as_str)),
+            |row| row.get::<_, String>(0),
+        )?;
+        Ok(assigned.collect::<rusqlite::Result<_>>()?)
+    }
+    
+    /// Set the favorite flag on several presets in one transaction (same `partial` rules as delete_many)
+    pub fn set_favorite_many(&self, ids: &[PresetId], is_favorite: bool, partial: bool) -> Result<Vec<BulkPresetResult>> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let now = chrono::Utc::now().timestamp();
+        
+        let mut results = Vec::with_capacity(ids.len());
+        for id in ids {
+            let rows_affected = tx.prepare_cached(
+                "UPDATE presets SET is_favorite = ?1, updated_at = ?2 WHERE id = ?3 AND deleted_at IS NULL",
+            )?.execute(params![if is_favorite { 1 } else { 0 }, now, id.as_str()])?;
+            results.push(Self::bulk_result(id, rows_affected > 0, partial)?);
+        }
+        
+        tx.commit()?;
+        Ok(results)
+    }
+    
+    /// Per-id outcome of a bulk operation; a miss is an error (aborting the transaction) unless partial
+    fn bulk_result(id: &PresetId, found: bool, partial: bool) -> Result<BulkPresetResult> {
+        if !found && !partial {
+            return Err(PresetError::NotFound {
+                id: id.to_string(),
+            });
+        }
+        Ok(BulkPresetResult {
+            id: id.clone(),
+            status: if found { BulkPresetStatus::Applied } else { BulkPresetStatus::NotFound },
+        })
+    }
+    
+    /// List trashed presets with the bank slots they held, most recently deleted first
+    pub fn list_deleted(&self) -> Result<Vec<DeletedPreset>> {
+        let conn = self.conn.lock().unwrap();
+        
+        let mut stmt = conn.prepare_cached(&format!(
+            "SELECT {}, p.deleted_at, GROUP_CONCAT(tb.bank_number) as bank_numbers
+             FROM presets p
+             LEFT JOIN trashed_bank_assignments tb ON p.id = tb.preset_id
+             WHERE p.deleted_at IS NOT NULL
+             GROUP BY p.id
+             ORDER BY p.deleted_at DESC",
+            Self::preset_select_list(Some("p"), true)
+        ))?;
+        
+        let rows = stmt.query_map([], |row| {
+            let bank_numbers_str: Option<String> = row.get(12)?;
+            let mut previous_bank_numbers: Vec<u8> = bank_numbers_str
+                .map(|s| {
+                    s.split(',')
+                        .filter_map(|n| n.trim().parse::<u8>().ok())
+                        .collect()
+                })
+                .unwrap_or_default();
+            previous_bank_numbers.sort_unstable();
+            
+            Ok(DeletedPreset {
+                preset: Self::preset_from_row(row)?,
+                deleted_at: row.get(11)?,
+                previous_bank_numbers,
+            })
+        })?;
+        
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+        
+        Ok(results)
+    }
+    
+    /// Take a preset back out of the trash and forget its remembered bank slots
+    pub fn restore(&self, id: &PresetId) -> Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        
+        let rows_affected = tx.prepare_cached(
+            "UPDATE presets SET deleted_at = NULL WHERE id = ?1 AND deleted_at IS NOT NULL",
+        )?.execute(params![id.as_str()])?;
+        
         if rows_affected == 0 {
             return Err(PresetError::NotFound {
                 id: id.to_string(),
             });
         }
         
+        tx.prepare_cached(
+            "DELETE FROM trashed_bank_assignments WHERE preset_id = ?1",
+        )?.execute(params![id.as_str()])?;
+        
+        tx.commit()?;
+        Ok(())
+    }
+    
+    /// Permanently remove presets trashed at or before the given Unix timestamp.
+    /// Returns the ids removed.
+    pub fn purge_deleted(&self, deleted_before: i64) -> Result<Vec<PresetId>> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        
+        let purged = tx.prepare_cached(
+            "SELECT id FROM presets WHERE deleted_at IS NOT NULL AND deleted_at <= ?1",
+        )?.query_map(params![deleted_before], |row| row.get::<_, String>(0))?
+            .map(|id| id.map(PresetId::new))
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        
+        tx.prepare_cached(
+            "DELETE FROM trashed_bank_assignments WHERE preset_id IN
+                (SELECT id FROM presets WHERE deleted_at IS NOT NULL AND deleted_at <= ?1)",
+        )?.execute(params![deleted_before])?;
+        
+        tx.prepare_cached(
+            "DELETE FROM presets WHERE deleted_at IS NOT NULL AND deleted_at <= ?1",
+        )?.execute(params![deleted_before])?;
+        
+        tx.commit()?;
+        Ok(purged)
+    }
+    
+    /// Append tags to several presets in one transaction, skipping ones each already has.
+    /// Fails without changing anything if any preset is missing or trashed.
+    /// Returns the ids of the presets that gained at least one tag.
+    pub fn add_tags(&self, ids: &[PresetId], tags: &[String], updated_at: i64) -> Result<Vec<PresetId>> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        
+        let mut updated = Vec::new();
+        for id in ids {
+            let tags_json: Option<String> = tx.prepare_cached(
+                "SELECT tags FROM presets WHERE id = ?1 AND deleted_at IS NULL",
+            )?.query_row(params![id.as_str()], |row| row.get(0)).optional()?;
+            
+            let Some(tags_json) = tags_json else {
+                return Err(PresetError::NotFound {
+                    id: id.to_string(),
+                });
+            };
+            
+            let mut preset_tags: Vec<String> = serde_json::from_str(&tags_json).unwrap_or_default();
+            let before = preset_tags.len();
+            for tag in tags {
+                if !preset_tags.contains(tag) {
+                    preset_tags.push(tag.clone());
+                }
+            }
+            
+            if preset_tags.len() == before {
+                continue;
+            }
+            
+            tx.prepare_cached(
+                "UPDATE presets SET tags = ?1, updated_at = ?2 WHERE id = ?3",
+            )?.execute(params![serde_json::to_string(&preset_tags)?, updated_at, id.as_str()])?;
+            updated.push(id.clone());
+        }
+        
+        tx.commit()?;
+        Ok(updated)
+    }
+    
+    /// Merge `remove_ids` into `keep_id` in one transaction: re-point their bank slots, union
+    /// their tags into the kept preset's, then trash them. All must be live and share a pedal
+    /// type; otherwise nothing changes.
+    pub fn merge_into(&self, keep_id: &PresetId, remove_ids: &[PresetId], updated_at: i64) -> Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        
+        let (pedal_type, mut tags) = Self::pedal_type_and_tags_in(&tx, keep_id)?;
+        for id in remove_ids {
+            let (other_pedal_type, other_tags) = Self::pedal_type_and_tags_in(&tx, id)?;
+            if other_pedal_type != pedal_type {
+                return Err(PresetError::InvalidMerge {
+                    reason: format!("{} is a {} preset, not {}", id, other_pedal_type, pedal_type),
+                });
+            }
+            for tag in other_tags {
+                if !tags.contains(&tag) {
+                    tags.push(tag);
+                }
+            }
+            
+            // Re-point before trashing, so trash_in has no slots left to set aside
+            tx.prepare_cached(
+                "UPDATE pedal_banks SET preset_id = ?1 WHERE preset_id = ?2",
+            )?.execute(params![keep_id.as_str(), id.as_str()])?;
+            Self::trash_in(&tx, id)?;
+        }
+        
+        tx.prepare_cached(
+            "UPDATE presets SET tags = ?1, updated_at = ?2 WHERE id = ?3",
+        )?.execute(params![serde_json::to_string(&tags)?, updated_at, keep_id.as_str()])?;
+        
+        tx.commit()?;
         Ok(())
     }
     
+    /// Pedal type and tags of a live preset
+    fn pedal_type_and_tags_in(tx: &rusqlite::Transaction, id: &PresetId) -> Result<(String, Vec<String>)> {
+        let row: Option<(String, String)> = tx.prepare_cached(
+            "SELECT pedal_type, tags FROM presets WHERE id = ?1 AND deleted_at IS NULL",
+        )?.query_row(params![id.as_str()], |row| Ok((row.get(0)?, row.get(1)?))).optional()?;
+        
+        let (pedal_type, tags_json) = row.ok_or_else(|| PresetError::NotFound {
+            id: id.to_string(),
+        })?;
+        Ok((pedal_type, serde_json::from_str(&tags_json).unwrap_or_default()))
+    }
+    
     /// Update preset favorite status
     pub fn set_favorite(&self, id: &PresetId, is_favorite: bool) -> Result<()> {
         let conn = self.conn.lock().unwrap();
         
-        let rows_affected = conn.execute(
-            "UPDATE presets SET is_favorite = ?1, updated_at = ?2 WHERE id = ?3",
-            params![
-                if is_favorite { 1 } else { 0 },
-                chrono::Utc::now().timestamp(),
-                id.as_str()
-            ],
-        )?;
+        let rows_affected = conn.prepare_cached(
+            "UPDATE presets SET is_favorite = ?1, updated_at = ?2 WHERE id = ?3 AND deleted_at IS NULL",
+        )?.execute(params![
+            if is_favorite { 1 } else { 0 },
+            chrono::Utc::now().timestamp(),
+            id.as_str()
+        ])?;
         
         if rows_affected == 0 {
             return Err(PresetError::NotFound {
@@ -276,11 +825,64 @@ impl PresetRepository {
         Ok(())
     }
     
+    /// Archive or unarchive a live preset
+    pub fn set_archived(&self, id: &PresetId, is_archived: bool) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        
+        let rows_affected = conn.prepare_cached(
+            "UPDATE presets SET is_archived = ?1, updated_at = ?2 WHERE id = ?3 AND deleted_at IS NULL",
+        )?.execute(params![
+            if is_archived { 1 } else { 0 },
+            chrono::Utc::now().timestamp(),
+            id.as_str()
+        ])?;
+        
+        if rows_affected == 0 {
+            return Err(PresetError::NotFound {
+                id: id.to_string(),
+            });
+        }
+        
+        Ok(())
+    }
+    
+    /// Remove a preset (live, archived or trashed) and every bank slot it holds or held
+    pub fn hard_delete(&self, id: &PresetId) -> Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        
+        if !Self::hard_delete_in(&tx, id)? {
+            return Err(PresetError::NotFound {
+                id: id.to_string(),
+            });
+        }
+        
+        tx.commit()?;
+        Ok(())
+    }
+    
+    /// Remove a preset row and its bank slots within a transaction. Returns false if there was no such preset.
+    fn hard_delete_in(tx: &rusqlite::Transaction, id: &PresetId) -> Result<bool> {
+        tx.prepare_cached(
+            "DELETE FROM pedal_banks WHERE preset_id = ?1",
+        )?.execute(params![id.as_str()])?;
+        
+        tx.prepare_cached(
+            "DELETE FROM trashed_bank_assignments WHERE preset_id = ?1",
+        )?.execute(params![id.as_str()])?;
+        
+        let rows_affected = tx.prepare_cached(
+            "DELETE FROM presets WHERE id = ?1",
+        )?.execute(params![id.as_str()])?;
+        
+        Ok(rows_affected > 0)
+    }
+    
     /// Get bank assignments for a pedal type
     pub fn get_bank_assignments(&self, pedal_type: &str) -> Result<Vec<(u8, Option<PresetId>, Option<i64>)>> {
         let conn = self.conn.lock().unwrap();
         
-        let mut stmt = conn.prepare(
+        let mut stmt = conn.prepare_cached(
             "SELECT bank_number, preset_id, synced_at FROM pedal_banks WHERE pedal_type = ?1 ORDER BY bank_number"
         )?;
         
@@ -306,42 +908,100 @@ impl PresetRepository {
         let conn = self.conn.lock().unwrap();
         let now = chrono::Utc::now().timestamp();
         
-        conn.execute(
+        conn.prepare_cached(
             "INSERT INTO pedal_banks (pedal_type, bank_number, preset_id, synced_at)
              VALUES (?1, ?2, ?3, ?4)
              ON CONFLICT(pedal_type, bank_number) DO UPDATE SET
                 preset_id = excluded.preset_id,
                 synced_at = excluded.synced_at",
-            params![pedal_type, bank_number, preset_id.as_str(), now],
+        )?.execute(params![pedal_type, bank_number, preset_id.as_str(), now])?;
+        
+        Ok(())
+    }
+    
+    /// Banks of `pedal_type` holding `preset_id`, lowest first
+    pub fn banks_for_preset(&self, pedal_type: &str, preset_id: &PresetId) -> Result<Vec<u8>> {
+        let conn = self.conn.lock().unwrap();
+        
+        let mut stmt = conn.prepare_cached(
+            "SELECT bank_number FROM pedal_banks WHERE pedal_type = ?1 AND preset_id = ?2 ORDER BY bank_number"
         )?;
+        let banks = stmt
+            .query_map(params![pedal_type, preset_id.as_str()], |row| row.get(0))?
+            .collect::<rusqlite::Result<Vec<u8>>>()?;
+        
+        Ok(banks)
+    }
+    
+    /// Assign a preset to a bank and clear any other bank of the pedal type holding it,
+    /// in one transaction
+    pub fn move_to_bank(&self, pedal_type: &str, bank_number: u8, preset_id: &PresetId) -> Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let now = chrono::Utc::now().timestamp();
+        
+        tx.prepare_cached(
+            "DELETE FROM pedal_banks WHERE pedal_type = ?1 AND preset_id = ?2 AND bank_number != ?3",
+        )?.execute(params![pedal_type, preset_id.as_str(), bank_number])?;
+        tx.prepare_cached(
+            "INSERT INTO pedal_banks (pedal_type, bank_number, preset_id, synced_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(pedal_type, bank_number) DO UPDATE SET
+                preset_id = excluded.preset_id,
+                synced_at = excluded.synced_at",
+        )?.execute(params![pedal_type, bank_number, preset_id.as_str(), now])?;
         
+        tx.commit()?;
         Ok(())
     }
     
+    /// Copy one pedal type's bank slots to another, pointing each slot at the target
+    /// pedal's preset with the same name. Slots outside `banks` or with no same-named
+    /// live preset are skipped. Returns the number of slots written.
+    pub fn clone_bank_assignments(
+        &self,
+        from_pedal: &str,
+        to_pedal: &str,
+        banks: std::ops::RangeInclusive<u8>,
+    ) -> Result<usize> {
+        let conn = self.conn.lock().unwrap();
+        let now = chrono::Utc::now().timestamp();
+        
+        let copied = conn.prepare_cached(
+            "INSERT INTO pedal_banks (pedal_type, bank_number, preset_id, synced_at)
+             SELECT ?2, pb.bank_number, target.id, ?5
+             FROM pedal_banks pb
+             JOIN presets source ON source.id = pb.preset_id AND source.deleted_at IS NULL
+             JOIN presets target ON target.name = source.name
+                AND target.pedal_type = ?2
+                AND target.deleted_at IS NULL
+                AND target.is_archived = 0
+             WHERE pb.pedal_type = ?1 AND pb.bank_number BETWEEN ?3 AND ?4
+             ON CONFLICT(pedal_type, bank_number) DO UPDATE SET
+                preset_id = excluded.preset_id,
+                synced_at = excluded.synced_at",
+        )?.execute(params![from_pedal, to_pedal, banks.start(), banks.end(), now])?;
+        
+        Ok(copied)
+    }
+    
     /// Find all presets for a pedal type with their bank assignments
     pub fn find_all_with_banks(&self, pedal_type: &str) -> Result<Vec<PresetWithBanks>> {
         let conn = self.conn.lock().unwrap();
         
         // Get all presets for this pedal type
-        let mut stmt = conn.prepare(
-            "SELECT p.id, p.name, p.pedal_type, p.description, p.parameters, p.tags, p.is_favorite, p.created_at, p.updated_at,
-                    GROUP_CONCAT(pb.bank_number) as bank_numbers
+        let mut stmt = conn.prepare_cached(&format!(
+            "SELECT {}, GROUP_CONCAT(pb.bank_number) as bank_numbers
              FROM presets p
              LEFT JOIN pedal_banks pb ON p.id = pb.preset_id AND pb.pedal_type = ?1
-             WHERE p.pedal_type = ?1
+             WHERE p.pedal_type = ?1 AND p.deleted_at IS NULL AND p.is_archived = 0
              GROUP BY p.id
-             ORDER BY p.updated_at DESC"
-        )?;
+             ORDER BY p.updated_at DESC",
+            Self::preset_select_list(Some("p"), true)
+        ))?;
         
         let rows = stmt.query_map(params![pedal_type], |row| {
-            let tags_json: String = row.get(5)?;
-            let tags: Vec<String> = serde_json::from_str(&tags_json).unwrap_or_default();
-            
-            let parameters_json: String = row.get(4)?;
-            let parameters: serde_json::Value = serde_json::from_str(&parameters_json)
-                .unwrap_or(serde_json::Value::Null);
-            
-            let bank_numbers_str: Option<String> = row.get(9)?;
+            let bank_numbers_str: Option<String> = row.get(11)?;
             let bank_numbers: Vec<u8> = bank_numbers_str
                 .map(|s| {
                     s.split(',')
@@ -351,17 +1011,7 @@ impl PresetRepository {
                 .unwrap_or_default();
             
             Ok(PresetWithBanks {
-                preset: Preset {
-                    id: PresetId::new(row.get(0)?),
-                    name: row.get(1)?,
-                    pedal_type: row.get(2)?,
-                    description: row.get(3)?,
-                    parameters,
-                    tags,
-                    is_favorite: row.get::<_, i32>(6)? != 0,
-                    created_at: row.get(7)?,
-                    updated_at: row.get(8)?,
-                },
+                preset: Self::preset_from_row(row)?,
                 bank_numbers,
             })
         })?;
@@ -378,11 +1028,267 @@ impl PresetRepository {
     pub fn clear_bank(&self, pedal_type: &str, bank_number: u8) -> Result<()> {
         let conn = self.conn.lock().unwrap();
         
-        conn.execute(
+        conn.prepare_cached(
             "DELETE FROM pedal_banks WHERE pedal_type = ?1 AND bank_number = ?2",
-            params![pedal_type, bank_number],
-        )?;
+        )?.execute(params![pedal_type, bank_number])?;
         
         Ok(())
     }
+    
+    /// Copy the whole database to `path` with SQLite's online backup, which stays consistent
+    /// even while other connections write. An existing file at `path` is replaced.
+    pub fn backup_to(&self, path: &Path) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.backup(DatabaseName::Main, path, None)?;
+        Ok(())
+    }
+    
+    /// Problems SQLite's integrity check finds; empty when the database is sound
+    pub fn integrity_check(&self) -> Result<Vec<String>> {
+        let conn = self.conn.lock().unwrap();
+        let rows: Vec<String> = conn.prepare("PRAGMA integrity_check")?
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(rows.into_iter().filter(|row| row != "ok").collect())
+    }
+    
+    /// Bytes on disk: the database file plus its write-ahead log
+    pub fn file_size(&self) -> Result<u64> {
+        Self::file_size_at(&self.db_path)
+    }
+    
+    /// `file_size` for the database at `db_path`
+    pub fn file_size_at(db_path: &Path) -> Result<u64> {
+        let mut wal_path = db_path.to_path_buf().into_os_string();
+        wal_path.push("-wal");
+        let wal_size = match std::fs::metadata(&wal_path) {
+            Ok(metadata) => metadata.len(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => 0,
+            Err(e) => return Err(e.into()),
+        };
+        Ok(std::fs::metadata(db_path)?.len() + wal_size)
+    }
+    
+    /// Fraction of database pages left free by deletes (what VACUUM would reclaim)
+    pub fn free_page_ratio(&self) -> Result<f64> {
+        Self::free_page_ratio_on(&self.conn.lock().unwrap())
+    }
+    
+    /// `free_page_ratio` on any connection to the database
+    pub fn free_page_ratio_on(conn: &Connection) -> Result<f64> {
+        let free: i64 = conn.query_row("PRAGMA freelist_count", [], |row| row.get(0))?;
+        let total: i64 = conn.query_row("PRAGMA page_count", [], |row| row.get(0))?;
+        Ok(if total == 0 { 0.0 } else { free as f64 / total as f64 })
+    }
+    
+    /// Checkpoint the WAL, VACUUM, ANALYZE and rebuild any full-text indexes.
+    /// Refuses rather than waits if a write is in flight, here or on another connection.
+    pub fn run_maintenance(&self) -> Result<()> {
+        let conn = self.conn.try_lock().map_err(|_| PresetError::Busy {
+            reason: "another library operation is in progress".to_string(),
+        })?;
+        Self::run_maintenance_on(&conn)
+    }
+    
+    /// Open a connection of its own to the database at `db_path`, for maintenance that
+    /// shouldn't hold the library's connection while it runs. Writes made meanwhile through
+    /// the library wait out the VACUUM on their busy timeout.
+    pub fn open_for_maintenance(db_path: &Path) -> Result<Connection> {
+        let conn = Connection::open(db_path)?;
+        conn.busy_timeout(std::time::Duration::from_secs(5))?;
+        Ok(conn)
+    }
+    
+    /// `run_maintenance` on any connection to the database
+    pub fn run_maintenance_on(conn: &Connection) -> Result<()> {
+        Self::checkpoint(conn)?;
+        conn.execute_batch("VACUUM; ANALYZE;")?;
+        
+        let fts_tables: Vec<String> = conn.prepare(
+            "SELECT name FROM sqlite_master WHERE type = 'table' AND sql LIKE 'CREATE VIRTUAL TABLE%USING fts%'",
+        )?.query_map([], |row| row.get(0))?.collect::<rusqlite::Result<_>>()?;
+        for table in fts_tables {
+            conn.execute(&format!("INSERT INTO \"{0}\"(\"{0}\") VALUES('rebuild')", table), [])?;
+        }
+        
+        // Under WAL the vacuumed pages land in the log; checkpoint again so the file itself shrinks
+        Self::checkpoint(conn)
+    }
+    
+    fn checkpoint(conn: &Connection) -> Result<()> {
+        let busy: i64 = conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |row| row.get(0))?;
+        if busy != 0 {
+            return Err(PresetError::Busy {
+                reason: "another connection is writing to the database".to_string(),
+            });
+        }
+        Ok(())
+    }
+    
+    /// Delete bank rows that no longer point at a preset: rows nulled by `ON DELETE SET NULL`,
+    /// and rows whose preset is missing altogether (left by deletes made without foreign keys enforced).
+    /// Returns the (pedal type, bank number) slots of the nulled rows and of the missing-preset rows.
+    pub fn delete_orphaned_banks(&self) -> Result<(Vec<(String, u8)>, Vec<(String, u8)>)> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        
+        let slots = |condition: &str| -> Result<Vec<(String, u8)>> {
+            let slots: Vec<(String, u8)> = tx.prepare(&format!("SELECT pedal_type, bank_number FROM pedal_banks WHERE {}", condition))?
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+                .collect::<rusqlite::Result<_>>()?;
+            tx.execute(&format!("DELETE FROM pedal_banks WHERE {}", condition), [])?;
+            Ok(slots)
+        };
+        let nulled = slots("preset_id IS NULL")?;
+        let missing = slots("preset_id NOT IN (SELECT id FROM presets)")?;
+        
+        tx.commit()?;
+        Ok((nulled, missing))
+    }
+    
+    /// Record what a bank held before being overwritten, keeping only the newest `limit` entries for that bank
+    pub fn insert_bank_history(
+        &self,
+        pedal_type: &str,
+        bank_number: u8,
+        device_name: &str,
+        previous: &Preset,
+        overwritten_at: i64,
+        limit: usize,
+    ) -> Result<BankHistoryEntry> {
+        let conn = self.conn.lock().unwrap();
+        
+        conn.prepare_cached(
+            "INSERT INTO bank_overwrite_history
+                (pedal_type, bank_number, device_name, preset_id, preset_name, parameters, overwritten_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        )?.execute(params![
+            pedal_type,
+            bank_number,
+            device_name,
+            previous.id.as_str(),
+            previous.name,
+            serde_json::to_string(&previous.parameters)?,
+            overwritten_at,
+        ])?;
+        let id = conn.last_insert_rowid();
+        
+        conn.prepare_cached(
+            "DELETE FROM bank_overwrite_history
+             WHERE pedal_type = ?1 AND bank_number = ?2 AND id NOT IN (
+                SELECT id FROM bank_overwrite_history
+                WHERE pedal_type = ?1 AND bank_number = ?2
+                ORDER BY overwritten_at DESC, id DESC
+                LIMIT ?3
+             )",
+        )?.execute(params![pedal_type, bank_number, limit as i64])?;
+        
+        Ok(BankHistoryEntry {
+            id,
+            pedal_type: pedal_type.to_string(),
+            bank_number,
+            device_name: device_name.to_string(),
+            preset_id: previous.id.clone(),
+            preset_name: previous.name.clone(),
+            parameters: previous.parameters.clone(),
+            overwritten_at,
+        })
+    }
+    
+    /// Overwrite history for one bank, newest first
+    pub fn list_bank_history(&self, pedal_type: &str, bank_number: u8) -> Result<Vec<BankHistoryEntry>> {
+        let conn = self.conn.lock().unwrap();
+        
+        let mut stmt = conn.prepare_cached(
+            "SELECT id, pedal_type, bank_number, device_name, preset_id, preset_name, parameters, overwritten_at
+             FROM bank_overwrite_history
+             WHERE pedal_type = ?1 AND bank_number = ?2
+             ORDER BY overwritten_at DESC, id DESC",
+        )?;
+        
+        let rows = stmt.query_map(params![pedal_type, bank_number], Self::bank_history_from_row)?;
+        
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row?);
+        }
+        
+        Ok(entries)
+    }
+    
+    /// Find a single overwrite history entry
+    pub fn find_bank_history_entry(&self, id: i64) -> Result<Option<BankHistoryEntry>> {
+        let conn = self.conn.lock().unwrap();
+        
+        let entry = conn
+            .prepare_cached(
+                "SELECT id, pedal_type, bank_number, device_name, preset_id, preset_name, parameters, overwritten_at
+                 FROM bank_overwrite_history WHERE id = ?1",
+            )?
+            .query_row(params![id], Self::bank_history_from_row)
+            .optional()?;
+        
+        Ok(entry)
+    }
+    
+    /// `PRESET_COLUMNS` as a select list, qualified with `table` when a query joins others.
+    /// Without `include_parameters` the parameters are selected as JSON null (see `list_with`).
+    fn preset_select_list(table: Option<&str>, include_parameters: bool) -> String {
+        PRESET_COLUMNS
+            .iter()
+            .map(|&column| match table {
+                _ if column == "parameters" && !include_parameters => "'null'".to_string(),
+                Some(table) => format!("{}.{}", table, column),
+                None => column.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+    
+    /// A preset from the first `PRESET_COLUMNS.len()` columns of a row selected with
+    /// `preset_select_list`; queries add their own columns after those
+    fn preset_from_row(row: &rusqlite::Row) -> rusqlite::Result<Preset> {
+        let tags_json: String = row.get(5)?;
+        let tags: Vec<String> = serde_json::from_str(&tags_json).unwrap_or_default();
+        
+        let parameters_json: String = row.get(4)?;
+        let parameters: serde_json::Value = serde_json::from_str(&parameters_json)
+            .unwrap_or(serde_json::Value::Null);
+        
+        let metadata_json: String = row.get(10)?;
+        let metadata: serde_json::Value = serde_json::from_str(&metadata_json)
+            .unwrap_or_else(|_| serde_json::json!({}));
+        
+        Ok(Preset {
+            id: PresetId::new(row.get(0)?),
+            name: row.get(1)?,
+            pedal_type: row.get(2)?,
+            description: row.get(3)?,
+            parameters,
+            tags,
+            metadata,
+            is_favorite: row.get::<_, i32>(6)? != 0,
+            is_archived: row.get::<_, i32>(9)? != 0,
+            created_at: row.get(7)?,
+            updated_at: row.get(8)?,
+            warnings: Vec::new(),
+        })
+    }
+    
+    fn bank_history_from_row(row: &rusqlite::Row) -> rusqlite::Result<BankHistoryEntry> {
+        let parameters_json: String = row.get(6)?;
+        let parameters: serde_json::Value = serde_json::from_str(&parameters_json)
+            .unwrap_or(serde_json::Value::Null);
+        
+        Ok(BankHistoryEntry {
+            id: row.get(0)?,
+            pedal_type: row.get(1)?,
+            bank_number: row.get(2)?,
+            device_name: row.get(3)?,
+            preset_id: PresetId::new(row.get(4)?),
+            preset_name: row.get(5)?,
+            parameters,
+            overwritten_at: row.get(7)?,
+        })
+    }
 }
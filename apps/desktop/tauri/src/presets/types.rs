@@ -12,9 +12,39 @@ pub struct Preset {
     pub description: Option<String>,
     pub parameters: serde_json::Value, // Stores MicrocosmState, GenLossState, etc. as JSON
     pub tags: Vec<String>,
+    /// Free-form JSON object (tempo, key, artist, ...) - capped at 8KB
+    #[serde(default = "empty_metadata")]
+    pub metadata: serde_json::Value,
     pub is_favorite: bool,
+    /// Hidden from listings but kept intact (and still recallable) until unarchived
+    #[serde(default)]
+    pub is_archived: bool,
     pub created_at: i64,  // Unix timestamp
     pub updated_at: i64,  // Unix timestamp
+    /// Problems found when the preset was saved (e.g. unknown parameter fields). Not stored.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Preset metadata without parameters - for list views over large libraries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresetSummary {
+    pub id: PresetId,
+    pub name: String,
+    pub pedal_type: String,
+    pub description: Option<String>,
+    pub tags: Vec<String>,
+    #[serde(default = "empty_metadata")]
+    pub metadata: serde_json::Value,
+    pub is_favorite: bool,
+    pub is_archived: bool,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+fn empty_metadata() -> serde_json::Value {
+    serde_json::json!({})
 }
 
 /// Preset ID - value object ensuring valid IDs
@@ -50,7 +80,11 @@ pub struct BankNumber {
 }
 
 impl BankNumber {
-    pub fn new(value: u8, config: &crate::presets::bank_config::BankConfig) -> std::result::Result<Self, PresetError> {
+    /// Validate a bank number against the pedal's bank configuration
+    pub fn new(value: u8, pedal_type: &str) -> std::result::Result<Self, PresetError> {
+        let config = crate::presets::bank_config::get_bank_config(pedal_type)
+            .ok_or_else(|| PresetError::NoBankConfig { pedal_type: pedal_type.to_string() })?;
+        
         if value < config.program_change_start || value > config.program_change_end {
             Err(PresetError::InvalidBankNumber {
                 value,
@@ -72,6 +106,12 @@ impl BankNumber {
         }
     }
     
+    /// Valid bank numbers for a pedal type, or None if it has no banks
+    pub fn range(pedal_type: &str) -> Option<std::ops::RangeInclusive<u8>> {
+        crate::presets::bank_config::get_bank_config(pedal_type)
+            .map(|config| config.program_change_start..=config.program_change_end)
+    }
+    
     pub fn value(&self) -> u8 {
         self.value
     }
@@ -129,13 +169,183 @@ pub struct PresetWithBanks {
     pub bank_numbers: Vec<u8>,
 }
 
-/// Preset filter criteria
-#[derive(Debug, Clone, Default)]
+/// Trashed preset - soft-deleted, with the bank slots it held at delete time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeletedPreset {
+    #[serde(flatten)]
+    pub preset: Preset,
+    pub deleted_at: i64,  // Unix timestamp
+    pub previous_bank_numbers: Vec<u8>,
+}
+
+/// Restored preset - the UI offers to re-assign it to its previous bank slots
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoredPreset {
+    #[serde(flatten)]
+    pub preset: Preset,
+    pub previous_bank_numbers: Vec<u8>,
+}
+
+/// What a bulk operation did to one preset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BulkPresetStatus {
+    Applied,
+    NotFound,
+}
+
+/// Per-preset result of a bulk operation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkPresetResult {
+    pub id: PresetId,
+    pub status: BulkPresetStatus,
+}
+
+/// Outcome of deleting a batch of presets (live ones to the trash, archived ones for good)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkDeleteResult {
+    pub deleted: usize,
+    pub not_found: Vec<PresetId>,
+    /// Deleted presets that were assigned to a bank slot, which is now empty
+    pub had_bank_assignments: Vec<PresetId>,
+}
+
+/// A preset in a group of duplicates
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicatePreset {
+    pub id: PresetId,
+    pub name: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// Presets with the same parameters, oldest first
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateGroup {
+    pub presets: Vec<DuplicatePreset>,
+}
+
+/// One assigned bank slot in an exported bank layout
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BankSlotDetail {
+    pub bank_number: u8,
+    pub bank_label: String,
+    pub preset_id: PresetId,
+    pub preset_name: String,
+    pub synced_at: Option<i64>,  // Unix timestamp
+}
+
+/// A bank row outside its pedal's slot range, left by a layout change (e.g. ChromaConsole
+/// slots moving to the user presets at 40-79). The UI doesn't show it and nothing recalls it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StrandedBankAssignment {
+    pub pedal_type: String,
+    pub bank_number: u8,
+    pub preset_id: PresetId,
+    pub preset_name: String,
+}
+
+/// Bank assignments across every pedal, keyed by pedal type (empty if nothing assigned)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct BankLayout {
+    pub pedals: std::collections::BTreeMap<String, Vec<BankSlotDetail>>,
+}
+
+impl BankLayout {
+    /// Human-readable Markdown table, one row per assigned slot
+    pub fn to_markdown_table(&self) -> String {
+        let mut table = String::from("| Pedal | Bank | Preset | Last Synced |\n|---|---|---|---|\n");
+        
+        for (pedal_type, slots) in &self.pedals {
+            if slots.is_empty() {
+                table.push_str(&format!("| {} | - | _(no banks assigned)_ | - |\n", pedal_type));
+                continue;
+            }
+            
+            for slot in slots {
+                let synced = slot.synced_at
+                    .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+                    .map(|dt| dt.format("%Y-%m-%d %H:%M UTC").to_string())
+                    .unwrap_or_else(|| "-".to_string());
+                table.push_str(&format!(
+                    "| {} | {} | {} | {} |\n",
+                    pedal_type,
+                    slot.bank_label,
+                    slot.preset_name.replace('|', "\\|"),
+                    synced
+                ));
+            }
+        }
+        
+        table
+    }
+}
+
+/// What a bank held just before a save overwrote it (a preset may have changed since)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BankHistoryEntry {
+    pub id: i64,
+    pub pedal_type: String,
+    pub bank_number: u8,
+    pub device_name: String,
+    pub preset_id: PresetId,
+    pub preset_name: String,
+    pub parameters: serde_json::Value,
+    pub overwritten_at: i64,  // Unix timestamp
+}
+
+/// The library's belief about a bank, captured before a save sequence runs
+#[derive(Debug, Clone)]
+pub struct BankSnapshot {
+    pub pedal_type: String,
+    pub bank_number: u8,
+    pub previous: Option<Preset>,
+}
+
+/// Outcome of `PresetLibrary::run_maintenance`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceReport {
+    /// Database plus write-ahead log, in bytes
+    pub size_before: u64,
+    pub size_after: u64,
+    pub duration_ms: u64,
+}
+
+/// Preset filter criteria. Every field is optional when it comes from the frontend.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
 pub struct PresetFilter {
     pub pedal_type: Option<String>,
     pub tags: Vec<String>,
     pub is_favorite: Option<bool>,
     pub search_query: Option<String>,
+    pub created_after: Option<i64>,   // Unix timestamp, inclusive
+    pub created_before: Option<i64>,  // Unix timestamp, inclusive
+    pub is_archived: Option<bool>,    // None hides archived presets
+    pub metadata_key: Option<String>, // Only presets whose metadata has this top-level key
+    pub exclude_ids: Vec<PresetId>,   // Left out of the results (e.g. the loaded preset)
+    pub limit: Option<usize>,         // Page size; None returns every match
+    pub offset: Option<usize>,        // Matches to skip before the page starts
+}
+
+/// One page of a preset listing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresetListResult {
+    pub presets: Vec<Preset>,
+    /// Matches across all pages, for sizing a virtualized list
+    pub total_count: usize,
 }
 
 /// Domain errors for preset operations
@@ -153,6 +363,27 @@ pub enum PresetError {
     #[error("Invalid preset name: {reason}")]
     InvalidName { reason: String },
     
+    #[error("Invalid parameters for {pedal_type}: {reason}")]
+    InvalidParameters { pedal_type: String, reason: String },
+    
+    #[error("Invalid preset metadata: {reason}")]
+    InvalidMetadata { reason: String },
+    
+    #[error("Preset is already assigned to bank {existing_bank}")]
+    AlreadyAssigned { existing_bank: u8 },
+    
+    #[error("No bank configuration for pedal type: {pedal_type}")]
+    NoBankConfig { pedal_type: String },
+    
+    #[error("Cannot merge presets: {reason}")]
+    InvalidMerge { reason: String },
+    
+    #[error("Bank history entry not found: {id}")]
+    HistoryEntryNotFound { id: i64 },
+    
+    #[error("Database is busy: {reason}")]
+    Busy { reason: String },
+    
     #[error("Database error: {0}")]
     Database(#[from] rusqlite::Error),
     
@@ -171,7 +402,7 @@ pub type Result<T> = std::result::Result<T, PresetError>;
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::presets::bank_config;
+    use crate::test_utils::PresetBuilder;
     
     #[test]
     fn test_preset_id_generate() {
@@ -210,19 +441,15 @@ mod tests {
     
     #[test]
     fn test_bank_number_valid() {
-        let config = bank_config::get_bank_config("Microcosm").unwrap();
-        
         // Valid bank number in range (45-60)
-        let bank = BankNumber::new(45, &config).unwrap();
+        let bank = BankNumber::new(45, "Microcosm").unwrap();
         assert_eq!(bank.value(), 45);
     }
     
     #[test]
     fn test_bank_number_invalid_too_low() {
-        let config = bank_config::get_bank_config("Microcosm").unwrap();
-        
         // Below minimum (45)
-        let result = BankNumber::new(44, &config);
+        let result = BankNumber::new(44, "Microcosm");
         assert!(result.is_err());
         
         match result {
@@ -237,10 +464,8 @@ mod tests {
     
     #[test]
     fn test_bank_number_invalid_too_high() {
-        let config = bank_config::get_bank_config("Microcosm").unwrap();
-        
         // Above maximum (60)
-        let result = BankNumber::new(61, &config);
+        let result = BankNumber::new(61, "Microcosm");
         assert!(result.is_err());
         
         match result {
@@ -253,11 +478,22 @@ mod tests {
         }
     }
     
+    #[test]
+    fn test_bank_number_unknown_pedal() {
+        let result = BankNumber::new(1, "NotAPedal");
+        assert!(matches!(result, Err(PresetError::NoBankConfig { .. })));
+    }
+    
+    #[test]
+    fn test_bank_number_range() {
+        assert_eq!(BankNumber::range("Microcosm"), Some(45..=60));
+        assert_eq!(BankNumber::range("ChromaConsole"), Some(40..=79));
+        assert_eq!(BankNumber::range("NotAPedal"), None);
+    }
+    
     #[test]
     fn test_bank_number_label() {
-        let config = bank_config::get_bank_config("Microcosm").unwrap();
-        
-        let bank = BankNumber::new(45, &config).unwrap();
+        let bank = BankNumber::new(45, "Microcosm").unwrap();
         let label = bank.label();
         
         // Label should start with "Bank "
@@ -266,17 +502,14 @@ mod tests {
     
     #[test]
     fn test_bank_number_color() {
-        let config = bank_config::get_bank_config("Microcosm").unwrap();
-        
         // Bank 45-48 should be red
-        let bank = BankNumber::new(45, &config).unwrap();
+        let bank = BankNumber::new(45, "Microcosm").unwrap();
         assert_eq!(bank.color(), "red");
     }
     
     #[test]
     fn test_bank_slot_new() {
-        let config = bank_config::get_bank_config("Microcosm").unwrap();
-        let bank_number = BankNumber::new(45, &config).unwrap();
+        let bank_number = BankNumber::new(45, "Microcosm").unwrap();
         
         let slot = BankSlot::new(bank_number);
         
@@ -289,20 +522,9 @@ mod tests {
     
     #[test]
     fn test_bank_slot_with_preset() {
-        let config = bank_config::get_bank_config("Microcosm").unwrap();
-        let bank_number = BankNumber::new(45, &config).unwrap();
+        let bank_number = BankNumber::new(45, "Microcosm").unwrap();
         
-        let preset = Preset {
-            id: PresetId::generate(),
-            name: "Test Preset".to_string(),
-            pedal_type: "microcosm".to_string(),
-            description: None,
-            parameters: serde_json::json!({}),
-            tags: vec![],
-            is_favorite: false,
-            created_at: 0,
-            updated_at: 0,
-        };
+        let preset = PresetBuilder::new().name("Test Preset").timestamp(0).build();
         
         let synced_at = chrono::Utc::now().timestamp();
         let slot = BankSlot::with_preset(bank_number, preset.clone(), synced_at);
@@ -325,17 +547,16 @@ mod tests {
     
     #[test]
     fn test_preset_serialization() {
-        let preset = Preset {
-            id: PresetId::new("test-id".to_string()),
-            name: "Test Preset".to_string(),
-            pedal_type: "microcosm".to_string(),
-            description: Some("A test preset".to_string()),
-            parameters: serde_json::json!({"activity": 64}),
-            tags: vec!["ambient".to_string(), "experimental".to_string()],
-            is_favorite: true,
-            created_at: 1234567890,
-            updated_at: 1234567890,
-        };
+        let preset = PresetBuilder::new()
+            .id("test-id")
+            .name("Test Preset")
+            .description("A test preset")
+            .parameters(serde_json::json!({"activity": 64}))
+            .tags(vec!["ambient".to_string(), "experimental".to_string()])
+            .metadata(serde_json::json!({"bpm": 120}))
+            .favorite()
+            .timestamp(1234567890)
+            .build();
         
         // Serialize to JSON
         let json = serde_json::to_string(&preset).unwrap();
@@ -377,4 +598,25 @@ mod tests {
         };
         assert_eq!(format!("{}", err), "Invalid preset name: Name cannot be empty");
     }
+    
+    #[test]
+    fn test_preset_error_chains_source() {
+        use std::error::Error;
+        
+        // `?` on rusqlite/serde_json errors converts via From and keeps the cause
+        fn parse(json: &str) -> Result<serde_json::Value> {
+            Ok(serde_json::from_str(json)?)
+        }
+        let err = parse("{not json").unwrap_err();
+        assert!(matches!(err, PresetError::Serialization(_)));
+        assert!(err.source().is_some());
+        
+        let err = PresetError::from(rusqlite::Error::QueryReturnedNoRows);
+        assert!(matches!(err, PresetError::Database(_)));
+        assert!(err.source().is_some());
+        
+        // Domain errors have no underlying cause
+        let err = PresetError::NotFound { id: "x".to_string() };
+        assert!(err.source().is_none());
+    }
 }
@@ -0,0 +1,209 @@
+// Preset parameter validation
+// Checks a preset's parameters JSON against its pedal's state struct at save time,
+// so malformed blobs are rejected with a useful message instead of failing at recall.
+// State fields are plain u8s, so values are also range checked here: every number in a
+// pedal state is a CC value, and anything above 127 would be rejected at send time anyway.
+// The exception is fields sent as NRPN (Collider delay times), which are checked as 14-bit.
+
+use super::types::{PresetError, Result};
+use crate::midi::pedals::billy_strings_wombtone::BillyStringsWombtoneState;
+use crate::midi::pedals::brothers_am::BrothersAmState;
+use crate::midi::pedals::chroma_console::ChromaConsoleState;
+use crate::midi::pedals::clean::CleanState;
+use crate::midi::pedals::cxm1978::Cxm1978State;
+use crate::midi::pedals::dark_world::DarkWorldState;
+use crate::midi::pedals::gen_loss_mkii::GenLossMkiiState;
+use crate::midi::pedals::habit::HabitState;
+use crate::midi::pedals::lossy::LossyState;
+use crate::midi::pedals::microcosm::MicrocosmState;
+use crate::midi::pedals::mood_mkii::MoodMkiiState;
+use crate::midi::pedals::onward::OnwardState;
+use crate::midi::pedals::preamp_mk2::PreampMk2State;
+use crate::midi::pedals::reverse_mode_c::ReverseModeCState;
+use crate::midi::pedals::source_audio_collider::ColliderState;
+use crate::midi::pedals::thermae::ThermaeState;
+use crate::midi::nrpn::MAX_14_BIT;
+use crate::midi::{CcValue, PedalType};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Check `parameters` deserialize as `pedal_type`'s state.
+/// Returns warnings for fields the state doesn't know about (they are kept, not rejected).
+/// Pedal types without a state struct are not validated.
+pub fn validate_parameters(pedal_type: &str, parameters: &serde_json::Value) -> Result<Vec<String>> {
+    let Some(pedal) = PedalType::from_name(pedal_type) else {
+        return Ok(Vec::new());
+    };
+
+    match pedal {
+        PedalType::Microcosm => check::<MicrocosmState>(pedal_type, parameters),
+        PedalType::GenLossMkii => check::<GenLossMkiiState>(pedal_type, parameters),
+        PedalType::ChromaConsole => check::<ChromaConsoleState>(pedal_type, parameters),
+        PedalType::PreampMk2 => check::<PreampMk2State>(pedal_type, parameters),
+        PedalType::Cxm1978 => check::<Cxm1978State>(pedal_type, parameters),
+        PedalType::MoodMkii => check::<MoodMkiiState>(pedal_type, parameters),
+        PedalType::BillyStringsWombtone => check::<BillyStringsWombtoneState>(pedal_type, parameters),
+        PedalType::Lossy => check::<LossyState>(pedal_type, parameters),
+        PedalType::BrothersAm => check::<BrothersAmState>(pedal_type, parameters),
+        PedalType::ReverseModeC => check::<ReverseModeCState>(pedal_type, parameters),
+        PedalType::Clean => check::<CleanState>(pedal_type, parameters),
+        PedalType::Onward => check::<OnwardState>(pedal_type, parameters),
+        PedalType::Thermae => check::<ThermaeState>(pedal_type, parameters),
+        PedalType::DarkWorld => check::<DarkWorldState>(pedal_type, parameters),
+        PedalType::Habit => check::<HabitState>(pedal_type, parameters),
+        PedalType::Collider => check_with_14_bit::<ColliderState>(pedal_type, parameters, &["delay_a.time", "delay_b.time"]),
+    }
+}
+
+/// Whether `pedal_type`'s default state passes its own preset validation cleanly,
+/// i.e. a freshly connected pedal's state can be saved as a preset
+pub fn default_state_validates(pedal_type: &PedalType) -> bool {
+    let state = match pedal_type {
+        PedalType::Microcosm => serde_json::to_value(MicrocosmState::default()),
+        PedalType::GenLossMkii => serde_json::to_value(GenLossMkiiState::default()),
+        PedalType::ChromaConsole => serde_json::to_value(ChromaConsoleState::default()),
+        PedalType::PreampMk2 => serde_json::to_value(PreampMk2State::default()),
+        PedalType::Cxm1978 => serde_json::to_value(Cxm1978State::default()),
+        PedalType::MoodMkii => serde_json::to_value(MoodMkiiState::default()),
+        PedalType::BillyStringsWombtone => serde_json::to_value(BillyStringsWombtoneState::default()),
+        PedalType::Lossy => serde_json::to_value(LossyState::default()),
+        PedalType::BrothersAm => serde_json::to_value(BrothersAmState::default()),
+        PedalType::ReverseModeC => serde_json::to_value(ReverseModeCState::default()),
+        PedalType::Clean => serde_json::to_value(CleanState::default()),
+        PedalType::Onward => serde_json::to_value(OnwardState::default()),
+        PedalType::Thermae => serde_json::to_value(ThermaeState::default()),
+        PedalType::DarkWorld => serde_json::to_value(DarkWorldState::default()),
+        PedalType::Habit => serde_json::to_value(HabitState::default()),
+        PedalType::Collider => serde_json::to_value(ColliderState::default()),
+    };
+    state
+        .ok()
+        .and_then(|state| validate_parameters(pedal_type.as_str(), &state).ok())
+        .is_some_and(|warnings| warnings.is_empty())
+}
+
+fn check<S: DeserializeOwned + Serialize>(pedal_type: &str, parameters: &serde_json::Value) -> Result<Vec<String>> {
+    check_with_14_bit::<S>(pedal_type, parameters, &[])
+}
+
+/// `check`, with the dotted field paths in `fourteen_bit` allowed up to 16383
+fn check_with_14_bit<S: DeserializeOwned + Serialize>(
+    pedal_type: &str,
+    parameters: &serde_json::Value,
+    fourteen_bit: &[&str],
+) -> Result<Vec<String>> {
+    // Parse from text rather than the Value so errors carry a line and column
+    let state: S = serde_json::from_str(&parameters.to_string()).map_err(|e| PresetError::InvalidParameters {
+        pedal_type: pedal_type.to_string(),
+        reason: e.to_string(),
+    })?;
+
+    // Anything the state didn't keep on a round trip is a field it doesn't know about
+    let known = serde_json::to_value(&state)?;
+    if let Some((field, value)) = out_of_range_cc_value(&known, "", fourteen_bit) {
+        let limit = if fourteen_bit.contains(&field.as_str()) { "14-bit values are 0-16383" } else { "CC values are 0-127" };
+        return Err(PresetError::InvalidParameters {
+            pedal_type: pedal_type.to_string(),
+            reason: format!("`{}` is {}; {}", field, value, limit),
+        });
+    }
+    let (Some(given), Some(known)) = (parameters.as_object(), known.as_object()) else {
+        return Ok(Vec::new());
+    };
+
+    Ok(given
+        .keys()
+        .filter(|key| !known.contains_key(*key))
+        .map(|key| format!("Unknown field `{}` for {} (ignored on recall)", key, pedal_type))
+        .collect())
+}
+
+/// Largest serialized size accepted for a preset's metadata
+pub const MAX_METADATA_BYTES: usize = 8 * 1024;
+
+/// Check `metadata` is a JSON object no larger than [`MAX_METADATA_BYTES`] once serialized
+pub fn validate_metadata(metadata: &serde_json::Value) -> Result<()> {
+    if !metadata.is_object() {
+        return Err(PresetError::InvalidMetadata {
+            reason: "must be a JSON object".to_string(),
+        });
+    }
+
+    let size = metadata.to_string().len();
+    if size > MAX_METADATA_BYTES {
+        return Err(PresetError::InvalidMetadata {
+            reason: format!("{} bytes exceeds the {} byte limit", size, MAX_METADATA_BYTES),
+        });
+    }
+
+    Ok(())
+}
+
+/// First number above 127 in `value` (above 16383 for paths in `fourteen_bit`),
+/// with its dotted field path
+fn out_of_range_cc_value(value: &serde_json::Value, path: &str, fourteen_bit: &[&str]) -> Option<(String, u64)> {
+    let max = if fourteen_bit.contains(&path) { u64::from(MAX_14_BIT) } else { u64::from(CcValue::MAX.get()) };
+    match value {
+        serde_json::Value::Number(n) => n
+            .as_u64()
+            .filter(|n| *n > max)
+            .map(|n| (path.to_string(), n)),
+        serde_json::Value::Object(map) => map.iter().find_map(|(key, value)| {
+            let path = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+            out_of_range_cc_value(value, &path, fourteen_bit)
+        }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_parameters_have_no_warnings() {
+        let parameters = serde_json::to_value(MicrocosmState::default()).unwrap();
+        assert!(validate_parameters("Microcosm", &parameters).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_unknown_fields_are_warnings() {
+        let mut parameters = serde_json::to_value(LossyState::default()).unwrap();
+        parameters["legacy_knob"] = serde_json::json!(3);
+
+        let warnings = validate_parameters("Lossy", &parameters).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("legacy_knob"));
+    }
+
+    #[test]
+    fn test_out_of_range_cc_values_name_the_field() {
+        let mut parameters = serde_json::to_value(MicrocosmState::default()).unwrap();
+        parameters["time"] = serde_json::json!(200);
+
+        let err = validate_parameters("Microcosm", &parameters).unwrap_err().to_string();
+        assert!(err.contains("`time` is 200"), "{}", err);
+    }
+
+    #[test]
+    fn test_nrpn_fields_are_checked_as_14_bit() {
+        let mut parameters = serde_json::to_value(ColliderState::default()).unwrap();
+        parameters["delay_a"]["time"] = serde_json::json!(16_000);
+        assert!(validate_parameters("Collider", &parameters).unwrap().is_empty());
+
+        parameters["delay_b"]["mix"] = serde_json::json!(200);
+        let err = validate_parameters("Collider", &parameters).unwrap_err().to_string();
+        assert!(err.contains("`delay_b.mix` is 200; CC values are 0-127"), "{}", err);
+    }
+
+    #[test]
+    fn test_metadata_must_be_an_object() {
+        assert!(validate_metadata(&serde_json::json!({"bpm": 120})).is_ok());
+        assert!(validate_metadata(&serde_json::json!(["bpm"])).is_err());
+    }
+
+    #[test]
+    fn test_unrecognized_pedal_type_is_not_validated() {
+        assert!(validate_parameters("SomeFuturePedal", &serde_json::json!({})).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,222 @@
+// Startup self-test - what this install can actually do, in one report the frontend can
+// render and support bundles carry. Every check is independent: one failing is reported,
+// never fatal, so the report is most useful exactly when something is broken.
+
+use crate::command_registry::{is_registered, pedal_commands};
+use crate::midi::{self, PedalType};
+use crate::presets::{self, PresetLibrary};
+use serde::Serialize;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Sleep overshoot (ms) past which scheduled recalls and CC spacing will drift noticeably
+pub const TIMER_RESOLUTION_WARN_MS: f64 = 5.0;
+
+/// Outcome of one check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    /// Works, but with a limitation worth knowing about
+    Warn,
+    Fail,
+}
+
+/// One line of the report
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfTestCheck {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl SelfTestCheck {
+    fn new(name: &'static str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self { name, status, detail: detail.into() }
+    }
+}
+
+/// Whether a pedal type is wired up everywhere it needs to be
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PedalCoverage {
+    pub pedal_type: &'static str,
+    /// Has a bank layout
+    pub bank_config: bool,
+    /// Default state passes preset validation
+    pub schema: bool,
+    /// Required commands missing from the invoke handler
+    pub missing_commands: Vec<&'static str>,
+}
+
+impl PedalCoverage {
+    pub fn for_pedal(pedal_type: &PedalType) -> Self {
+        Self {
+            pedal_type: pedal_type.as_str(),
+            bank_config: presets::bank_config::get_bank_config(pedal_type.as_str()).is_some(),
+            schema: presets::default_state_validates(pedal_type),
+            missing_commands: pedal_commands(pedal_type)
+                .into_iter()
+                .filter(|command| !is_registered(command))
+                .collect(),
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.bank_config && self.schema && self.missing_commands.is_empty()
+    }
+}
+
+/// Everything the self-test found
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfTestReport {
+    /// No check failed (warnings allowed)
+    pub passed: bool,
+    pub checks: Vec<SelfTestCheck>,
+    pub input_ports: usize,
+    pub output_ports: usize,
+    pub timer_resolution_ms: f64,
+    pub pedals: Vec<PedalCoverage>,
+}
+
+/// Run every check. `app_data_dir` is None when the platform couldn't resolve one.
+pub fn run_self_test(library: &PresetLibrary, app_data_dir: Option<&Path>) -> SelfTestReport {
+    let mut checks = Vec::new();
+
+    let (input_ports, output_ports) = match midi::list_midi_devices() {
+        Ok((inputs, outputs)) => {
+            checks.push(SelfTestCheck::new("midi_backend", CheckStatus::Pass, "MIDI backend initialized"));
+            let status = if outputs.is_empty() { CheckStatus::Warn } else { CheckStatus::Pass };
+            checks.push(SelfTestCheck::new(
+                "midi_ports",
+                status,
+                format!("{} input port(s), {} output port(s) visible", inputs.len(), outputs.len()),
+            ));
+            (inputs.len(), outputs.len())
+        }
+        Err(e) => {
+            checks.push(SelfTestCheck::new("midi_backend", CheckStatus::Fail, e.to_string()));
+            (0, 0)
+        }
+    };
+
+    checks.push(match library.integrity_check() {
+        Ok(problems) if problems.is_empty() => SelfTestCheck::new("database", CheckStatus::Pass, "integrity_check ok"),
+        Ok(problems) => SelfTestCheck::new("database", CheckStatus::Fail, problems.join("; ")),
+        Err(e) => SelfTestCheck::new("database", CheckStatus::Fail, e.to_string()),
+    });
+
+    checks.push(match library.stranded_bank_assignments() {
+        Ok(stranded) if stranded.is_empty() => SelfTestCheck::new("bank_slots", CheckStatus::Pass, "every bank row is in its pedal's slots"),
+        Ok(stranded) => SelfTestCheck::new(
+            "bank_slots",
+            CheckStatus::Warn,
+            format!(
+                "outside the pedal's slots, reassign: {}",
+                stranded
+                    .iter()
+                    .map(|row| format!("{} bank {} ({})", row.pedal_type, row.bank_number, row.preset_name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        ),
+        Err(e) => SelfTestCheck::new("bank_slots", CheckStatus::Fail, e.to_string()),
+    });
+
+    checks.push(match app_data_dir {
+        Some(dir) => match probe_writable(dir) {
+            Ok(()) => SelfTestCheck::new("app_data_dir", CheckStatus::Pass, dir.display().to_string()),
+            Err(e) => SelfTestCheck::new("app_data_dir", CheckStatus::Fail, format!("{}: {}", dir.display(), e)),
+        },
+        None => SelfTestCheck::new("app_data_dir", CheckStatus::Fail, "no app data directory on this platform"),
+    });
+
+    checks.push(if midi::virtual_ports_supported() {
+        SelfTestCheck::new("virtual_ports", CheckStatus::Pass, "supported")
+    } else {
+        SelfTestCheck::new("virtual_ports", CheckStatus::Warn, "not supported on this platform")
+    });
+
+    let timer_resolution_ms = measure_timer_resolution().as_secs_f64() * 1000.0;
+    checks.push(SelfTestCheck::new(
+        "timer_resolution",
+        if timer_resolution_ms > TIMER_RESOLUTION_WARN_MS { CheckStatus::Warn } else { CheckStatus::Pass },
+        format!("1 ms sleep takes {:.2} ms", timer_resolution_ms),
+    ));
+
+    let pedals: Vec<PedalCoverage> = PedalType::ALL.iter().map(PedalCoverage::for_pedal).collect();
+    let incomplete: Vec<&str> = pedals.iter().filter(|p| !p.is_complete()).map(|p| p.pedal_type).collect();
+    checks.push(if incomplete.is_empty() {
+        SelfTestCheck::new("pedal_modules", CheckStatus::Pass, format!("{} pedal(s) fully registered", pedals.len()))
+    } else {
+        SelfTestCheck::new("pedal_modules", CheckStatus::Fail, format!("incomplete: {}", incomplete.join(", ")))
+    });
+
+    SelfTestReport {
+        passed: checks.iter().all(|check| check.status != CheckStatus::Fail),
+        checks,
+        input_ports,
+        output_ports,
+        timer_resolution_ms,
+        pedals,
+    }
+}
+
+/// Write and remove a scratch file in `dir`
+fn probe_writable(dir: &Path) -> std::io::Result<()> {
+    let probe = dir.join(".self-test");
+    std::fs::write(&probe, b"ok")?;
+    std::fs::remove_file(&probe)
+}
+
+/// Average time a 1 ms sleep actually takes on a fresh thread, as the scheduler's clock threads see it
+pub fn measure_timer_resolution() -> Duration {
+    const SAMPLES: u32 = 10;
+    thread::spawn(|| {
+        let started = Instant::now();
+        for _ in 0..SAMPLES {
+            thread::sleep(Duration::from_millis(1));
+        }
+        started.elapsed() / SAMPLES
+    })
+    .join()
+    .unwrap_or(Duration::ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_every_pedal_is_fully_registered() {
+        for pedal_type in PedalType::ALL {
+            let coverage = PedalCoverage::for_pedal(pedal_type);
+            assert!(coverage.is_complete(), "{:?}", coverage);
+        }
+    }
+
+    #[test]
+    fn test_report_covers_each_check() {
+        let temp_dir = TempDir::new().unwrap();
+        let library = PresetLibrary::new(temp_dir.path().join("presets.db")).unwrap();
+
+        let report = run_self_test(&library, Some(temp_dir.path()));
+        let database = report.checks.iter().find(|check| check.name == "database").unwrap();
+        assert_eq!(database.status, CheckStatus::Pass);
+        let bank_slots = report.checks.iter().find(|check| check.name == "bank_slots").unwrap();
+        assert_eq!(bank_slots.status, CheckStatus::Pass);
+        let app_data = report.checks.iter().find(|check| check.name == "app_data_dir").unwrap();
+        assert_eq!(app_data.status, CheckStatus::Pass);
+        assert!(!temp_dir.path().join(".self-test").exists());
+        assert_eq!(report.pedals.len(), PedalType::ALL.len());
+
+        let report = run_self_test(&library, Some(&temp_dir.path().join("missing")));
+        let app_data = report.checks.iter().find(|check| check.name == "app_data_dir").unwrap();
+        assert_eq!(app_data.status, CheckStatus::Fail);
+        assert!(!report.passed);
+    }
+}
@@ -5,7 +5,8 @@ use crate::midi::pedals::microcosm::MicrocosmState;
 use crate::midi::pedals::gen_loss_mkii::GenLossMkiiState;
 use crate::midi::pedals::chroma_console::ChromaConsoleState;
 
-/// Builder for creating test Presets
+/// Builder for creating test Presets - unset fields get defaults (a generated ID, the
+/// current time, an untagged Microcosm preset with empty parameters)
 pub struct PresetBuilder {
     id: Option<String>,
     name: String,
@@ -13,6 +14,7 @@ pub struct PresetBuilder {
     description: Option<String>,
     parameters: serde_json::Value,
     tags: Vec<String>,
+    metadata: serde_json::Value,
     is_favorite: bool,
     created_at: i64,
     updated_at: i64,
@@ -20,51 +22,71 @@ pub struct PresetBuilder {
 
 impl PresetBuilder {
     pub fn new() -> Self {
+        let now = chrono::Utc::now().timestamp();
         Self {
             id: None,
             name: "Test Preset".to_string(),
-            pedal_type: "microcosm".to_string(),
+            pedal_type: "Microcosm".to_string(),
             description: None,
             parameters: serde_json::json!({}),
             tags: vec![],
+            metadata: serde_json::json!({}),
             is_favorite: false,
-            created_at: chrono::Utc::now().timestamp(),
-            updated_at: chrono::Utc::now().timestamp(),
+            created_at: now,
+            updated_at: now,
         }
     }
     
-    pub fn with_id(mut self, id: String) -> Self {
-        self.id = Some(id);
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
         self
     }
     
-    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+    pub fn name(mut self, name: impl Into<String>) -> Self {
         self.name = name.into();
         self
     }
     
-    pub fn with_pedal_type(mut self, pedal_type: impl Into<String>) -> Self {
+    pub fn pedal_type(mut self, pedal_type: impl Into<String>) -> Self {
         self.pedal_type = pedal_type.into();
         self
     }
     
-    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+    pub fn description(mut self, description: impl Into<String>) -> Self {
         self.description = Some(description.into());
         self
     }
     
-    pub fn with_parameters(mut self, parameters: serde_json::Value) -> Self {
+    pub fn parameters(mut self, parameters: serde_json::Value) -> Self {
         self.parameters = parameters;
         self
     }
     
-    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+    pub fn tags(mut self, tags: Vec<String>) -> Self {
         self.tags = tags;
         self
     }
     
-    pub fn with_favorite(mut self, is_favorite: bool) -> Self {
-        self.is_favorite = is_favorite;
+    pub fn metadata(mut self, metadata: serde_json::Value) -> Self {
+        self.metadata = metadata;
+        self
+    }
+    
+    pub fn favorite(mut self) -> Self {
+        self.is_favorite = true;
+        self
+    }
+    
+    /// Presets have no rating column, so this goes in metadata as `rating`
+    pub fn rating(mut self, rating: u8) -> Self {
+        self.metadata["rating"] = serde_json::json!(rating);
+        self
+    }
+    
+    /// Set both created_at and updated_at
+    pub fn timestamp(mut self, timestamp: i64) -> Self {
+        self.created_at = timestamp;
+        self.updated_at = timestamp;
         self
     }
     
@@ -76,9 +98,12 @@ impl PresetBuilder {
             description: self.description,
             parameters: self.parameters,
             tags: self.tags,
+            metadata: self.metadata,
             is_favorite: self.is_favorite,
+            is_archived: false,
             created_at: self.created_at,
             updated_at: self.updated_at,
+            warnings: Vec::new(),
         }
     }
 }
@@ -112,25 +137,31 @@ mod tests {
     fn test_preset_builder_defaults() {
         let preset = PresetBuilder::new().build();
         assert_eq!(preset.name, "Test Preset");
-        assert_eq!(preset.pedal_type, "microcosm");
+        assert_eq!(preset.pedal_type, "Microcosm");
         assert_eq!(preset.tags.len(), 0);
         assert!(!preset.is_favorite);
+        assert_eq!(preset.metadata, serde_json::json!({}));
+        assert_eq!(preset.created_at, preset.updated_at);
+        assert_ne!(PresetBuilder::new().build().id, preset.id);
     }
     
     #[test]
     fn test_preset_builder_with_custom_values() {
         let preset = PresetBuilder::new()
-            .with_name("My Preset")
-            .with_pedal_type("gen-loss-mkii")
-            .with_description("A test preset")
-            .with_tags(vec!["ambient".to_string(), "experimental".to_string()])
-            .with_favorite(true)
+            .name("My Preset")
+            .pedal_type("GenLossMkii")
+            .description("A test preset")
+            .parameters(serde_json::to_value(test_gen_loss_state()).unwrap())
+            .tags(vec!["ambient".to_string(), "experimental".to_string()])
+            .favorite()
+            .rating(4)
             .build();
         
         assert_eq!(preset.name, "My Preset");
-        assert_eq!(preset.pedal_type, "gen-loss-mkii");
+        assert_eq!(preset.pedal_type, "GenLossMkii");
         assert_eq!(preset.description, Some("A test preset".to_string()));
         assert_eq!(preset.tags.len(), 2);
         assert!(preset.is_favorite);
+        assert_eq!(preset.metadata["rating"], 4);
     }
 }
@@ -0,0 +1,54 @@
+// Manually advanced clock for testing timers without sleeping
+
+use crate::midi::clock::Clock;
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::sync::{Condvar, Mutex};
+use std::time::Duration;
+
+/// Clock that only moves when a test calls `advance`
+#[derive(Default)]
+pub struct MockClock {
+    now: Mutex<Duration>,
+    waits: Mutex<Vec<Duration>>,
+    ticked: Condvar,
+}
+
+impl MockClock {
+    pub fn advance(&self, by: Duration) {
+        *self.now.lock().unwrap() += by;
+        self.ticked.notify_all();
+    }
+
+    /// Durations waited for, in the order the waits started
+    pub fn waits(&self) -> Vec<Duration> {
+        self.waits.lock().unwrap().clone()
+    }
+
+    /// Block until `count` waits have started, so their deadlines are fixed
+    pub fn await_waiters(&self, count: usize) {
+        while self.waits.lock().unwrap().len() < count {
+            std::thread::yield_now();
+        }
+    }
+}
+
+impl Clock for MockClock {
+    fn wait(&self, duration: Duration, cancel: &Receiver<()>) -> bool {
+        let mut now = self.now.lock().unwrap();
+        let deadline = *now + duration;
+        self.waits.lock().unwrap().push(duration);
+        loop {
+            if !matches!(cancel.try_recv(), Err(TryRecvError::Empty)) {
+                return false;
+            }
+            if *now >= deadline {
+                return true;
+            }
+            now = self.ticked.wait_timeout(now, Duration::from_millis(5)).unwrap().0;
+        }
+    }
+
+    fn now(&self) -> Duration {
+        *self.now.lock().unwrap()
+    }
+}
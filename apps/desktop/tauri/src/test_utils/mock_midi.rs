@@ -1,6 +1,11 @@
 // Mock MIDI infrastructure for testing without hardware
 
+use crate::midi::error::{MidiError, MidiResult};
+use crate::midi::identity::IdentityTransport;
+use crate::midi::routing::OutputPort;
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 /// Mock MIDI message
 #[derive(Debug, Clone, PartialEq)]
@@ -85,6 +90,101 @@ impl MockMidiConnection {
     }
 }
 
+/// Mock physical output port that records raw bytes (clones share the recording)
+#[derive(Debug, Clone, Default)]
+pub struct MockOutputPort {
+    sent: Arc<Mutex<Vec<Vec<u8>>>>,
+    /// Sends fail once this many messages have gone out
+    fail_after: Option<usize>,
+}
+
+impl MockOutputPort {
+    /// A port that accepts `count` messages, then fails every send (a cable pulled mid-send)
+    pub fn failing_after(count: usize) -> Self {
+        Self { fail_after: Some(count), ..Self::default() }
+    }
+    
+    /// Every message sent through the port, in order
+    pub fn sent(&self) -> Vec<Vec<u8>> {
+        self.sent.lock().unwrap().clone()
+    }
+}
+
+impl OutputPort for MockOutputPort {
+    fn send(&mut self, message: &[u8]) -> MidiResult<()> {
+        let mut sent = self.sent.lock().unwrap();
+        if self.fail_after.is_some_and(|count| sent.len() >= count) {
+            return Err(MidiError::SendFailed("mock port disconnected".to_string()));
+        }
+        sent.push(message.to_vec());
+        Ok(())
+    }
+}
+
+/// Mock ports for identity requests - canned port lists and replies, records what was sent
+#[derive(Debug, Clone, Default)]
+pub struct MockIdentityTransport {
+    pub output_ports: Vec<String>,
+    pub input_ports: Vec<String>,
+    /// Input port lookups that come back empty first (OS still enumerating)
+    pub input_ports_missing_for: u32,
+    /// SysEx heard after each inquiry
+    pub replies: Vec<Vec<u8>>,
+    /// Per-output-port replies, used instead of `replies` for ports listed here
+    pub replies_by_port: HashMap<String, Vec<Vec<u8>>>,
+    /// Inquiries that get no reply at all before `replies` start coming back
+    pub silent_exchanges: u32,
+    /// Output ports whose exchange fails outright (busy or unplugged mid-scan)
+    pub failing_ports: Vec<String>,
+    pub sent: Vec<Vec<u8>>,
+}
+
+impl MockIdentityTransport {
+    /// Output and input port both named `port`
+    pub fn with_port(port: &str) -> Self {
+        Self {
+            output_ports: vec![port.to_string()],
+            input_ports: vec![port.to_string()],
+            ..Self::default()
+        }
+    }
+}
+
+impl IdentityTransport for MockIdentityTransport {
+    fn output_port_names(&mut self) -> MidiResult<Vec<String>> {
+        Ok(self.output_ports.clone())
+    }
+
+    fn input_port_names(&mut self) -> MidiResult<Vec<String>> {
+        if self.input_ports_missing_for > 0 {
+            self.input_ports_missing_for -= 1;
+            return Ok(Vec::new());
+        }
+        Ok(self.input_ports.clone())
+    }
+
+    fn exchange(
+        &mut self,
+        output_port: &str,
+        _input_port: &str,
+        request: &[u8],
+        _timeout: Duration,
+    ) -> MidiResult<Vec<Vec<u8>>> {
+        if self.failing_ports.iter().any(|port| port == output_port) {
+            return Err(MidiError::ConnectionFailed(format!("{} is busy", output_port)));
+        }
+        self.sent.push(request.to_vec());
+        if self.silent_exchanges > 0 {
+            self.silent_exchanges -= 1;
+            return Ok(Vec::new());
+        }
+        if let Some(replies) = self.replies_by_port.get(output_port) {
+            return Ok(replies.clone());
+        }
+        Ok(self.replies.clone())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
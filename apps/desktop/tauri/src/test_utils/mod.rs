@@ -6,6 +6,9 @@ pub mod builders;
 #[cfg(test)]
 pub mod db_fixtures;
 
+#[cfg(test)]
+pub mod mock_clock;
+
 #[cfg(test)]
 pub mod mock_midi;
 
@@ -15,4 +18,6 @@ pub use builders::*;
 #[cfg(test)]
 pub use db_fixtures::*;
 #[cfg(test)]
+pub use mock_clock::*;
+#[cfg(test)]
 pub use mock_midi::*;
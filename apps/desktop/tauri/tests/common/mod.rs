@@ -0,0 +1,12 @@
+// Helpers shared by the integration tests (each test file declares `mod common;`)
+
+use librarian_lib::midi::pedals::microcosm::MicrocosmState;
+
+/// Full Microcosm parameters with `overrides` applied - presets are validated on save
+pub fn microcosm_parameters(overrides: serde_json::Value) -> serde_json::Value {
+    let mut parameters = serde_json::to_value(MicrocosmState::default()).unwrap();
+    if let (Some(parameters), Some(overrides)) = (parameters.as_object_mut(), overrides.as_object()) {
+        parameters.extend(overrides.clone());
+    }
+    parameters
+}
@@ -1,6 +1,9 @@
 // E2E test for complete preset lifecycle
 // Simulates a user workflow: create → edit → save to bank → recall → delete
 
+mod common;
+
+use common::microcosm_parameters;
 use librarian_lib::presets::{PresetLibrary, PresetFilter};
 use tempfile::TempDir;
 
@@ -22,12 +25,12 @@ fn test_complete_preset_lifecycle() {
         "Ambient Texture".to_string(),
         "Microcosm".to_string(),
         Some("A lush ambient soundscape".to_string()),
-        serde_json::json!({
+        microcosm_parameters(serde_json::json!({
             "activity": 80,
             "mix": 100,
             "time": 90,
             "space": 127
-        }),
+        })),
         vec!["ambient".to_string(), "texture".to_string()],
     ).unwrap();
     
@@ -52,6 +55,7 @@ fn test_complete_preset_lifecycle() {
             "time": 95,
             "space": 127
         })),
+        None,
     ).unwrap();
     
     println!("✅ Updated preset parameters");
@@ -97,11 +101,11 @@ fn test_complete_preset_lifecycle() {
         "Glitch Pattern".to_string(),
         "Microcosm".to_string(),
         Some("Rhythmic glitchy texture".to_string()),
-        serde_json::json!({
+        microcosm_parameters(serde_json::json!({
             "activity": 100,
             "mix": 90,
             "repeats": 127
-        }),
+        })),
         vec!["glitch".to_string(), "rhythm".to_string()],
     ).unwrap();
     
@@ -203,7 +207,7 @@ fn test_error_recovery_workflow() {
         "".to_string(),
         "Microcosm".to_string(),
         None,
-        serde_json::json!({}),
+        microcosm_parameters(serde_json::json!({})),
         vec![],
     );
     
@@ -217,7 +221,7 @@ fn test_error_recovery_workflow() {
         "Valid Preset".to_string(),
         "Microcosm".to_string(),
         None,
-        serde_json::json!({}),
+        microcosm_parameters(serde_json::json!({})),
         vec![],
     ).unwrap();
     
@@ -230,7 +234,7 @@ fn test_error_recovery_workflow() {
         "Valid Preset".to_string(),
         "Microcosm".to_string(),
         None,
-        serde_json::json!({}),
+        microcosm_parameters(serde_json::json!({})),
         vec![],
     );
     
@@ -261,6 +265,7 @@ fn test_error_recovery_workflow() {
         None,
         None,
         None,
+        None,
     );
     
     assert!(result.is_err());
@@ -276,6 +281,7 @@ fn test_error_recovery_workflow() {
         None,
         None,
         None,
+        None,
     ).unwrap();
     
     assert_eq!(updated.name, "Updated Name");
@@ -0,0 +1,198 @@
+// Integration tests for the read-only library server
+// Starts a real server on a free localhost port against a seeded library
+
+mod common;
+
+use common::microcosm_parameters;
+use librarian_lib::library_server::{BindScope, LibraryServer, LibraryServerConfig, MAX_CONCURRENT_REQUESTS};
+use librarian_lib::midi::create_shared_manager;
+use librarian_lib::presets::{Preset, PresetLibrary, SharedPresetLibrary};
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::sync::{Arc, Mutex};
+use tempfile::TempDir;
+
+/// A running server over a library holding two Microcosm presets, one in bank 45
+fn start_seeded_server() -> (LibraryServer, Vec<Preset>, TempDir) {
+    let (server, _library, presets, temp_dir) = start_seeded_server_with_library();
+    (server, presets, temp_dir)
+}
+
+/// `start_seeded_server`, also handing back the library the server reads
+fn start_seeded_server_with_library() -> (LibraryServer, SharedPresetLibrary, Vec<Preset>, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let library = PresetLibrary::new(temp_dir.path().join("test.db")).unwrap();
+
+    let ambient = library
+        .save_preset(
+            "Ambient Wash".to_string(),
+            "Microcosm".to_string(),
+            None,
+            microcosm_parameters(serde_json::json!({"mix": 100})),
+            vec!["ambient".to_string()],
+        )
+        .unwrap();
+    let glitch = library
+        .save_preset(
+            "Glitch Stutter".to_string(),
+            "Microcosm".to_string(),
+            None,
+            microcosm_parameters(serde_json::json!({"activity": 90})),
+            vec!["glitch".to_string()],
+        )
+        .unwrap();
+    library.assign_to_bank("Microcosm", 45, &ambient.id).unwrap();
+
+    let config = LibraryServerConfig {
+        enabled: true,
+        bind: BindScope::Localhost,
+        port: 0,
+    };
+    let library = Arc::new(Mutex::new(library));
+    let server = LibraryServer::start(&config, Arc::clone(&library), create_shared_manager().unwrap()).unwrap();
+    (server, library, vec![ambient, glitch], temp_dir)
+}
+
+/// GET `path`, returning the status code and JSON body
+fn get(server: &LibraryServer, path: &str, token: Option<&str>) -> (u16, serde_json::Value) {
+    get_from(server.addr(), path, token)
+}
+
+fn get_from(addr: SocketAddr, path: &str, token: Option<&str>) -> (u16, serde_json::Value) {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    let auth = token.map(|token| format!("Authorization: Bearer {}\r\n", token)).unwrap_or_default();
+    write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n{}\r\n", path, auth).unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+    (status, serde_json::from_str(body).unwrap())
+}
+
+#[test]
+fn test_requests_without_token_are_rejected() {
+    let (server, _presets, _temp_dir) = start_seeded_server();
+
+    assert_eq!(get(&server, "/presets", None).0, 401);
+    assert_eq!(get(&server, "/presets", Some("not-the-token")).0, 401);
+}
+
+#[test]
+fn test_idle_connection_does_not_block_other_clients() {
+    let (server, _presets, _temp_dir) = start_seeded_server();
+    let token = server.token().to_string();
+
+    // Connects and never sends a request
+    let _idle = TcpStream::connect(server.addr()).unwrap();
+
+    let started = std::time::Instant::now();
+    assert_eq!(get(&server, "/presets", Some(&token)).0, 200);
+    assert!(started.elapsed() < std::time::Duration::from_secs(2));
+}
+
+#[test]
+fn test_requests_past_the_cap_are_turned_away() {
+    let (server, library, _presets, _temp_dir) = start_seeded_server_with_library();
+    let token = server.token().to_string();
+
+    // Preset requests wait on the library, each holding its place meanwhile. They retry
+    // if turned away while the probe below briefly holds a place.
+    let held = library.lock().unwrap();
+    let waiting: Vec<_> = (0..MAX_CONCURRENT_REQUESTS)
+        .map(|_| {
+            let (addr, token) = (server.addr(), token.clone());
+            std::thread::spawn(move || loop {
+                let status = get_from(addr, "/presets", Some(&token)).0;
+                if status != 503 {
+                    return status;
+                }
+            })
+        })
+        .collect();
+
+    // Devices don't wait on the library, so this is turned away once every place is taken
+    let started = std::time::Instant::now();
+    while get(&server, "/devices", Some(&token)).0 != 503 {
+        assert!(started.elapsed() < std::time::Duration::from_secs(2));
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    // Releasing the library lets the waiting requests finish and frees their places
+    drop(held);
+    for request in waiting {
+        assert_eq!(request.join().unwrap(), 200);
+    }
+    assert_eq!(get(&server, "/devices", Some(&token)).0, 200);
+}
+
+#[test]
+fn test_list_presets_applies_filters() {
+    let (server, _presets, _temp_dir) = start_seeded_server();
+    let token = server.token().to_string();
+
+    let (status, all) = get(&server, "/presets", Some(&token));
+    assert_eq!(status, 200);
+    assert_eq!(all.as_array().unwrap().len(), 2);
+
+    let (status, tagged) = get(&server, "/presets?pedal_type=Microcosm&tags=glitch", Some(&token));
+    assert_eq!(status, 200);
+    let tagged = tagged.as_array().unwrap();
+    assert_eq!(tagged.len(), 1);
+    assert_eq!(tagged[0]["name"], "Glitch Stutter");
+
+    let (status, searched) = get(&server, "/presets?search_query=ambient%20wash", Some(&token));
+    assert_eq!(status, 200);
+    assert_eq!(searched.as_array().unwrap().len(), 1);
+
+    assert_eq!(get(&server, "/presets?is_favorite=maybe", Some(&token)).0, 400);
+}
+
+#[test]
+fn test_get_preset_by_id() {
+    let (server, presets, _temp_dir) = start_seeded_server();
+    let token = server.token().to_string();
+
+    let (status, preset) = get(&server, &format!("/presets/{}", presets[0].id), Some(&token));
+    assert_eq!(status, 200);
+    assert_eq!(preset["name"], "Ambient Wash");
+    assert_eq!(preset["parameters"]["mix"], 100);
+
+    assert_eq!(get(&server, "/presets/missing", Some(&token)).0, 404);
+}
+
+#[test]
+fn test_banks_and_devices() {
+    let (server, presets, _temp_dir) = start_seeded_server();
+    let token = server.token().to_string();
+
+    let (status, banks) = get(&server, "/banks/Microcosm", Some(&token));
+    assert_eq!(status, 200);
+    let assigned: Vec<&serde_json::Value> = banks
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|slot| !slot["preset"].is_null())
+        .collect();
+    assert_eq!(assigned.len(), 1);
+    assert_eq!(assigned[0]["bankNumber"], 45);
+    assert_eq!(assigned[0]["preset"]["id"], presets[0].id.to_string());
+
+    assert_eq!(get(&server, "/banks/NotAPedal", Some(&token)).0, 400);
+
+    let (status, devices) = get(&server, "/devices", Some(&token));
+    assert_eq!(status, 200);
+    assert_eq!(devices, serde_json::json!([]));
+
+    assert_eq!(get(&server, "/nowhere", Some(&token)).0, 404);
+}
+
+#[test]
+fn test_stop_releases_the_port() {
+    let (mut server, _presets, _temp_dir) = start_seeded_server();
+    let addr = server.addr();
+
+    server.stop();
+
+    assert!(TcpStream::connect(addr).is_err());
+}
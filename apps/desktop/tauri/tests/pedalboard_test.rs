@@ -0,0 +1,429 @@
+// Integration tests for pedalboards (CRUD, activation, deactivation)
+
+mod common;
+
+use common::microcosm_parameters;
+use librarian_lib::pedalboards::{
+    self, DeviceConnector, PedalboardError, PedalboardLibrary, PedalboardMember, PedalboardScene, PresetRecaller,
+    SceneRecall, SceneStep,
+};
+use librarian_lib::presets::{PresetChangeEvent, PresetId, PresetLibrary};
+use std::collections::HashSet;
+use std::sync::Mutex;
+use tempfile::TempDir;
+use tokio::sync::broadcast;
+
+/// Helper to create a temporary database for testing
+fn create_test_library() -> (PedalboardLibrary, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let library = PedalboardLibrary::new(db_path).unwrap();
+    (library, temp_dir)
+}
+
+fn member(pedal_type: &str, port_match: &str, midi_channel: u8) -> PedalboardMember {
+    PedalboardMember {
+        pedal_type: pedal_type.to_string(),
+        alias: None,
+        port_match: port_match.to_string(),
+        midi_channel,
+    }
+}
+
+/// Connector that fails for any port it doesn't know about
+#[derive(Default)]
+struct MockConnector {
+    available_ports: HashSet<String>,
+    connected: HashSet<String>,
+}
+
+impl MockConnector {
+    fn with_ports(ports: &[&str]) -> Self {
+        Self {
+            available_ports: ports.iter().map(|p| p.to_string()).collect(),
+            connected: HashSet::new(),
+        }
+    }
+}
+
+impl DeviceConnector for MockConnector {
+    fn connect_device(&mut self, _pedal_type: &str, port_match: &str, _midi_channel: u8) -> Result<bool, String> {
+        if !self.available_ports.contains(port_match) {
+            return Err(format!("Device not found: {}", port_match));
+        }
+        Ok(self.connected.insert(port_match.to_string()))
+    }
+
+    fn disconnect_device(&mut self, port_match: &str) -> Result<(), String> {
+        self.connected.remove(port_match);
+        Ok(())
+    }
+}
+
+/// Recaller that records what it was asked to recall and fails for listed devices
+#[derive(Default)]
+struct MockRecaller {
+    failing: HashSet<String>,
+    recalled: Vec<(String, String, serde_json::Value)>,
+}
+
+impl PresetRecaller for MockRecaller {
+    fn recall_preset(&mut self, device_name: &str, pedal_type: &str, parameters: serde_json::Value) -> Result<(), String> {
+        if self.failing.contains(device_name) {
+            return Err(format!("{} is not armed", device_name));
+        }
+        self.recalled.push((device_name.to_string(), pedal_type.to_string(), parameters));
+        Ok(())
+    }
+}
+
+fn scene(name: &str, recalls: &[(&str, &str)]) -> PedalboardScene {
+    PedalboardScene {
+        name: name.to_string(),
+        recalls: recalls
+            .iter()
+            .map(|(port_match, preset_id)| SceneRecall {
+                port_match: port_match.to_string(),
+                preset_id: preset_id.to_string(),
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn test_create_and_get_pedalboard() {
+    let (library, _temp_dir) = create_test_library();
+
+    let mut microcosm = member("Microcosm", "Microcosm", 1);
+    microcosm.alias = Some("Loops".to_string());
+    let members = vec![microcosm, member("GenLossMkii", "MIDI Interface", 2)];
+
+    let created = library.create_pedalboard("  Studio  ".to_string(), members.clone()).unwrap();
+    assert_eq!(created.name, "Studio");
+    assert!(!created.is_active);
+
+    let fetched = library.get_pedalboard(&created.id).unwrap();
+    assert_eq!(fetched.name, "Studio");
+    assert_eq!(fetched.members, members);
+}
+
+#[test]
+fn test_create_rejects_duplicate_name_and_invalid_members() {
+    let (library, _temp_dir) = create_test_library();
+
+    library.create_pedalboard("Live".to_string(), vec![]).unwrap();
+    let result = library.create_pedalboard("Live".to_string(), vec![]);
+    assert!(matches!(result, Err(PedalboardError::DuplicateName { .. })));
+
+    let result = library.create_pedalboard("Bad Type".to_string(), vec![member("Kazoo", "Port", 1)]);
+    assert!(matches!(result, Err(PedalboardError::InvalidMember { .. })));
+
+    let result = library.create_pedalboard("Bad Channel".to_string(), vec![member("Microcosm", "Port", 17)]);
+    assert!(matches!(result, Err(PedalboardError::InvalidMember { .. })));
+
+    let result = library.create_pedalboard("   ".to_string(), vec![]);
+    assert!(matches!(result, Err(PedalboardError::InvalidName { .. })));
+}
+
+#[test]
+fn test_update_and_delete_pedalboard() {
+    let (library, _temp_dir) = create_test_library();
+
+    let board = library.create_pedalboard("Home".to_string(), vec![member("Microcosm", "Microcosm", 1)]).unwrap();
+    library.create_pedalboard("Gig".to_string(), vec![]).unwrap();
+
+    // Renaming onto another board's name is rejected
+    let result = library.update_pedalboard(&board.id, Some("Gig".to_string()), None);
+    assert!(matches!(result, Err(PedalboardError::DuplicateName { .. })));
+
+    let updated = library
+        .update_pedalboard(&board.id, Some("Home Rig".to_string()), Some(vec![member("Lossy", "Lossy", 3)]))
+        .unwrap();
+    assert_eq!(updated.name, "Home Rig");
+    assert_eq!(updated.members, vec![member("Lossy", "Lossy", 3)]);
+
+    let names: Vec<String> = library.list_pedalboards().unwrap().into_iter().map(|p| p.name).collect();
+    assert_eq!(names, vec!["Gig", "Home Rig"]);
+
+    library.delete_pedalboard(&board.id).unwrap();
+    assert!(matches!(library.get_pedalboard(&board.id), Err(PedalboardError::NotFound { .. })));
+    assert!(matches!(library.delete_pedalboard(&board.id), Err(PedalboardError::NotFound { .. })));
+}
+
+#[test]
+fn test_activate_reports_partial_failure() {
+    let (library, _temp_dir) = create_test_library();
+
+    let board = library.create_pedalboard(
+        "Stage".to_string(),
+        vec![
+            member("Microcosm", "Microcosm", 1),
+            member("ChromaConsole", "Chroma", 2),
+            member("Onward", "Onward", 3),
+        ],
+    ).unwrap();
+
+    // Chroma Console isn't plugged in
+    let mut connector = MockConnector::with_ports(&["Microcosm", "Onward"]);
+    let activation = library.activate_pedalboard(&board.id, &mut connector).unwrap();
+
+    assert!(!activation.all_connected());
+    assert_eq!(activation.results.len(), 3);
+
+    let failed: Vec<&str> = activation.results.iter()
+        .filter(|r| !r.connected)
+        .map(|r| r.member.port_match.as_str())
+        .collect();
+    assert_eq!(failed, vec!["Chroma"]);
+    assert!(activation.results[1].error.as_deref().unwrap().contains("Chroma"));
+    assert!(activation.results[0].error.is_none());
+
+    // The board is active even though one pedal is missing
+    let active = library.get_active_pedalboard().unwrap().unwrap();
+    assert_eq!(active.id, board.id);
+    assert_eq!(connector.connected.len(), 2);
+}
+
+#[test]
+fn test_only_one_board_active() {
+    let (library, _temp_dir) = create_test_library();
+
+    let first = library.create_pedalboard("First".to_string(), vec![member("Microcosm", "Microcosm", 1)]).unwrap();
+    let second = library.create_pedalboard("Second".to_string(), vec![member("Lossy", "Lossy", 2)]).unwrap();
+
+    let mut connector = MockConnector::with_ports(&["Microcosm", "Lossy"]);
+    let activation = library.activate_pedalboard(&first.id, &mut connector).unwrap();
+    assert!(activation.all_connected());
+    library.activate_pedalboard(&second.id, &mut connector).unwrap();
+
+    let active: Vec<_> = library.list_pedalboards().unwrap().into_iter().filter(|p| p.is_active).collect();
+    assert_eq!(active.len(), 1);
+    assert_eq!(active[0].id, second.id);
+}
+
+#[test]
+fn test_deactivate_disconnects_members() {
+    let (library, _temp_dir) = create_test_library();
+
+    let board = library.create_pedalboard(
+        "Stage".to_string(),
+        vec![member("Microcosm", "Microcosm", 1), member("Lossy", "Lossy", 2)],
+    ).unwrap();
+
+    let mut connector = MockConnector::with_ports(&["Microcosm", "Lossy"]);
+    library.activate_pedalboard(&board.id, &mut connector).unwrap();
+    assert_eq!(connector.connected.len(), 2);
+
+    let deactivated = library.deactivate_pedalboard(&mut connector).unwrap().unwrap();
+    assert_eq!(deactivated.id, board.id);
+    assert!(!deactivated.is_active);
+    assert!(connector.connected.is_empty());
+    assert!(library.get_active_pedalboard().unwrap().is_none());
+
+    // Nothing active - nothing to do
+    assert!(library.deactivate_pedalboard(&mut connector).unwrap().is_none());
+}
+
+#[test]
+fn test_deactivate_leaves_devices_connected_before_activation() {
+    let (library, _temp_dir) = create_test_library();
+
+    let board = library.create_pedalboard(
+        "Stage".to_string(),
+        vec![member("Microcosm", "Microcosm", 1), member("Lossy", "Lossy", 2)],
+    ).unwrap();
+
+    // The Microcosm was connected by hand before the board was activated
+    let mut connector = MockConnector::with_ports(&["Microcosm", "Lossy"]);
+    connector.connected.insert("Microcosm".to_string());
+    let activation = library.activate_pedalboard(&board.id, &mut connector).unwrap();
+    assert!(activation.results[0].already_connected);
+    assert!(!activation.results[1].already_connected);
+
+    // Survives a reload
+    let active = library.get_active_pedalboard().unwrap().unwrap();
+    assert_eq!(active.connected_by_activation, vec!["Lossy"]);
+
+    library.deactivate_pedalboard(&mut connector).unwrap();
+    assert_eq!(connector.connected, HashSet::from(["Microcosm".to_string()]));
+}
+
+#[test]
+fn test_shared_activation_swaps_boards_and_reconnects() {
+    let (library, _temp_dir) = create_test_library();
+    let home = library.create_pedalboard("Home".to_string(), vec![member("Microcosm", "Microcosm", 1)]).unwrap();
+    let travel = library.create_pedalboard("Travel".to_string(), vec![member("Lossy", "Lossy", 2)]).unwrap();
+    let library = Mutex::new(library);
+    let connector = Mutex::new(MockConnector::with_ports(&["Microcosm", "Lossy"]));
+
+    pedalboards::activate_shared(&library, &connector, &home.id).unwrap();
+    let (previous, activation) = pedalboards::activate_shared(&library, &connector, &travel.id).unwrap();
+    assert_eq!(previous.unwrap().id, home.id);
+    assert!(activation.all_connected());
+    assert_eq!(connector.lock().unwrap().connected, HashSet::from(["Lossy".to_string()]));
+
+    // After a restart nothing is connected, but the board is still active
+    connector.lock().unwrap().connected.clear();
+    let reconnected = pedalboards::reconnect_active_shared(&library, &connector).unwrap().unwrap();
+    assert_eq!(reconnected.pedalboard_id, travel.id);
+    assert_eq!(connector.lock().unwrap().connected, HashSet::from(["Lossy".to_string()]));
+
+    let deactivated = pedalboards::deactivate_shared(&library, &connector).unwrap().unwrap();
+    assert_eq!(deactivated.id, travel.id);
+    assert!(connector.lock().unwrap().connected.is_empty());
+    assert!(pedalboards::reconnect_active_shared(&library, &connector).unwrap().is_none());
+}
+
+#[test]
+fn test_scenes_are_validated_against_members() {
+    let (library, _temp_dir) = create_test_library();
+    let board = library.create_pedalboard("Stage".to_string(), vec![member("Microcosm", "Microcosm", 1)]).unwrap();
+
+    let result = library.save_scene(&board.id, scene("Verse", &[("Lossy", "preset-1")]));
+    assert!(matches!(result, Err(PedalboardError::InvalidScene { .. })));
+    let result = library.save_scene(&board.id, scene("Verse", &[("Microcosm", "a"), ("Microcosm", "b")]));
+    assert!(matches!(result, Err(PedalboardError::InvalidScene { .. })));
+
+    library.save_scene(&board.id, scene(" Verse ", &[("Microcosm", "a")])).unwrap();
+    let updated = library.save_scene(&board.id, scene("Verse", &[("Microcosm", "b")])).unwrap();
+    assert_eq!(updated.scenes, vec![scene("Verse", &[("Microcosm", "b")])]);
+
+    let updated = library.delete_scene(&board.id, "Verse").unwrap();
+    assert!(updated.scenes.is_empty());
+    assert!(matches!(library.delete_scene(&board.id, "Verse"), Err(PedalboardError::SceneNotFound { .. })));
+}
+
+#[test]
+fn test_scene_recalls_on_active_board_only() {
+    let (library, _temp_dir) = create_test_library();
+    let board = library.create_pedalboard(
+        "Stage".to_string(),
+        vec![member("Microcosm", "Microcosm", 1), member("Lossy", "Lossy", 2), member("Onward", "Onward", 3)],
+    ).unwrap();
+    library.save_scene(&board.id, scene("Chorus", &[("Microcosm", "wash"), ("Lossy", "gone"), ("Onward", "freeze")])).unwrap();
+
+    assert!(matches!(library.active_scene("Chorus"), Err(PedalboardError::NoActivePedalboard)));
+    library.activate_pedalboard(&board.id, &mut MockConnector::with_ports(&["Microcosm", "Lossy", "Onward"])).unwrap();
+    assert!(matches!(library.active_scene("Bridge"), Err(PedalboardError::SceneNotFound { .. })));
+
+    let (active, chorus) = library.active_scene("Chorus").unwrap();
+    let steps = chorus
+        .recalls
+        .iter()
+        .map(|recall| SceneStep {
+            recall: recall.clone(),
+            parameters: match recall.preset_id.as_str() {
+                "gone" => Err("Preset not found: gone".to_string()),
+                id => Ok(serde_json::json!({ "id": id })),
+            },
+        })
+        .collect();
+    let mut recaller = MockRecaller { failing: HashSet::from(["Onward".to_string()]), ..Default::default() };
+    let report = pedalboards::recall_scene(&active, &chorus.name, steps, &mut recaller);
+
+    // The missing preset and the failing pedal don't stop the Microcosm
+    let recalled: Vec<bool> = report.results.iter().map(|r| r.recalled).collect();
+    assert_eq!(recalled, vec![true, false, false]);
+    assert_eq!(
+        recaller.recalled,
+        vec![("Microcosm".to_string(), "Microcosm".to_string(), serde_json::json!({ "id": "wash" }))]
+    );
+    assert!(report.results[2].error.as_deref().unwrap().contains("not armed"));
+}
+
+#[test]
+fn test_shares_database_with_presets() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("presets.db");
+
+    let presets = PresetLibrary::new(db_path.clone()).unwrap();
+    let pedalboards = PedalboardLibrary::new(db_path.clone()).unwrap();
+
+    presets.save_preset("Ambient".to_string(), "Microcosm".to_string(), None, microcosm_parameters(serde_json::json!({})), vec![]).unwrap();
+    pedalboards.create_pedalboard("Studio".to_string(), vec![member("Microcosm", "Microcosm", 1)]).unwrap();
+
+    // Reopen both - data survives and the schemas don't collide
+    let pedalboards = PedalboardLibrary::new(db_path.clone()).unwrap();
+    let presets = PresetLibrary::new(db_path).unwrap();
+    assert_eq!(pedalboards.list_pedalboards().unwrap().len(), 1);
+    assert_eq!(presets.list_presets(Default::default()).unwrap().len(), 1);
+}
+
+/// Load a scene's presets from the library, as the recall command does
+fn scene_steps(presets: &PresetLibrary, scene: &PedalboardScene) -> Vec<SceneStep> {
+    scene
+        .recalls
+        .iter()
+        .map(|recall| SceneStep {
+            recall: recall.clone(),
+            parameters: presets
+                .get_preset(&PresetId::new(recall.preset_id.clone()))
+                .map(|preset| preset.parameters)
+                .map_err(|e| e.to_string()),
+        })
+        .collect()
+}
+
+/// Hand the pedalboards every preset change published so far, as the app's listener does
+fn apply_changes(changes: &mut broadcast::Receiver<PresetChangeEvent>, pedalboards: &PedalboardLibrary) {
+    while let Ok(event) = changes.try_recv() {
+        pedalboards.apply_preset_change(&event).unwrap();
+    }
+}
+
+/// A preset library and an active one-Microcosm board sharing a database
+fn create_shared_libraries() -> (PresetLibrary, PedalboardLibrary, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("presets.db");
+    let presets = PresetLibrary::new(db_path.clone()).unwrap();
+    let pedalboards = PedalboardLibrary::new(db_path).unwrap();
+    let board = pedalboards.create_pedalboard("Stage".to_string(), vec![member("Microcosm", "Microcosm", 1)]).unwrap();
+    pedalboards.activate_pedalboard(&board.id, &mut MockConnector::with_ports(&["Microcosm"])).unwrap();
+    (presets, pedalboards, temp_dir)
+}
+
+#[test]
+fn test_scene_recalls_kept_preset_after_merge() {
+    let (presets, pedalboards, _temp_dir) = create_shared_libraries();
+    let kept = presets.save_preset("Wash".to_string(), "Microcosm".to_string(), None, microcosm_parameters(serde_json::json!({})), vec![]).unwrap();
+    let merged = presets.save_preset("Wash 2".to_string(), "Microcosm".to_string(), None, microcosm_parameters(serde_json::json!({})), vec![]).unwrap();
+    let board = pedalboards.get_active_pedalboard().unwrap().unwrap();
+    pedalboards.save_scene(&board.id, scene("Verse", &[("Microcosm", merged.id.as_str())])).unwrap();
+    let mut changes = presets.watch_changes();
+
+    presets.merge_presets(&kept.id, std::slice::from_ref(&merged.id)).unwrap();
+    apply_changes(&mut changes, &pedalboards);
+
+    let (active, verse) = pedalboards.active_scene("Verse").unwrap();
+    assert_eq!(verse, scene("Verse", &[("Microcosm", kept.id.as_str())]));
+    let mut recaller = MockRecaller::default();
+    let report = pedalboards::recall_scene(&active, &verse.name, scene_steps(&presets, &verse), &mut recaller);
+    assert!(report.results.iter().all(|result| result.recalled));
+    let parameters = presets.get_preset(&kept.id).unwrap().parameters;
+    assert_eq!(recaller.recalled, vec![("Microcosm".to_string(), "Microcosm".to_string(), parameters)]);
+}
+
+#[test]
+fn test_scene_drops_deleted_presets() {
+    let (presets, pedalboards, _temp_dir) = create_shared_libraries();
+    let trashed = presets.save_preset("Wash".to_string(), "Microcosm".to_string(), None, microcosm_parameters(serde_json::json!({})), vec![]).unwrap();
+    let purged = presets.save_preset("Glitch".to_string(), "Microcosm".to_string(), None, microcosm_parameters(serde_json::json!({})), vec![]).unwrap();
+    let board = pedalboards.get_active_pedalboard().unwrap().unwrap();
+    pedalboards.save_scene(&board.id, scene("Verse", &[("Microcosm", trashed.id.as_str())])).unwrap();
+    pedalboards.save_scene(&board.id, scene("Chorus", &[("Microcosm", purged.id.as_str())])).unwrap();
+    let mut changes = presets.watch_changes();
+
+    presets.delete_preset(&trashed.id).unwrap();
+    presets.permanently_delete_preset(&purged.id).unwrap();
+    apply_changes(&mut changes, &pedalboards);
+
+    // Both scenes are left empty rather than naming missing presets
+    for name in ["Verse", "Chorus"] {
+        let (active, found) = pedalboards.active_scene(name).unwrap();
+        assert!(found.recalls.is_empty());
+        let mut recaller = MockRecaller::default();
+        let report = pedalboards::recall_scene(&active, &found.name, scene_steps(&presets, &found), &mut recaller);
+        assert!(report.results.is_empty());
+        assert!(recaller.recalled.is_empty());
+    }
+}
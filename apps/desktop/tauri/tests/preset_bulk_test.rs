@@ -0,0 +1,175 @@
+// Integration tests for bulk preset operations (delete, favorite) and their transactions
+
+use librarian_lib::midi::pedals::microcosm::MicrocosmState;
+use librarian_lib::presets::{BulkDeleteResult, BulkPresetStatus, PresetError, PresetFilter, PresetId, PresetLibrary};
+use tempfile::TempDir;
+
+/// Helper to create a temporary database for testing
+fn create_test_library() -> (PresetLibrary, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let library = PresetLibrary::new(db_path).unwrap();
+    (library, temp_dir)
+}
+
+fn save(library: &PresetLibrary, name: &str) -> PresetId {
+    library.save_preset(
+        name.to_string(),
+        "Microcosm".to_string(),
+        None,
+        serde_json::to_value(MicrocosmState::default()).unwrap(),
+        vec![],
+    ).unwrap().id
+}
+
+fn missing() -> PresetId {
+    PresetId::new("does-not-exist".to_string())
+}
+
+#[test]
+fn test_delete_presets_clears_bank_assignments() {
+    let (library, _temp_dir) = create_test_library();
+
+    let a = save(&library, "A");
+    let b = save(&library, "B");
+    let keep = save(&library, "Keep");
+    library.assign_to_bank("Microcosm", 45, &a).unwrap();
+    library.assign_to_bank("Microcosm", 46, &b).unwrap();
+    library.assign_to_bank("Microcosm", 47, &keep).unwrap();
+
+    let result = library.delete_presets(&[a.clone(), b.clone()], false).unwrap();
+    assert_eq!(result.deleted, 2);
+    assert!(result.not_found.is_empty());
+
+    let remaining = library.list_presets(PresetFilter::default()).unwrap();
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].id, keep);
+
+    let banks = library.get_bank_state("Microcosm").unwrap();
+    let assigned: Vec<u8> = banks.iter().filter(|b| b.preset.is_some()).map(|b| b.bank_number).collect();
+    assert_eq!(assigned, vec![47]);
+
+    // Deleted presets land in the trash like single deletes do
+    assert_eq!(library.list_deleted_presets().unwrap().len(), 2);
+}
+
+#[test]
+fn test_delete_presets_rolls_back_on_missing_id() {
+    let (library, _temp_dir) = create_test_library();
+
+    let a = save(&library, "A");
+    let b = save(&library, "B");
+    library.assign_to_bank("Microcosm", 45, &a).unwrap();
+
+    let result = library.delete_presets(&[a.clone(), missing(), b.clone()], false);
+    assert!(matches!(result, Err(PresetError::NotFound { id }) if id == "does-not-exist"));
+
+    // Nothing was deleted, and the bank assignment survived
+    assert_eq!(library.list_presets(PresetFilter::default()).unwrap().len(), 2);
+    assert!(library.list_deleted_presets().unwrap().is_empty());
+    let banks = library.get_bank_state("Microcosm").unwrap();
+    let slot = banks.iter().find(|b| b.bank_number == 45).unwrap();
+    assert_eq!(slot.preset.as_ref().unwrap().id, a);
+}
+
+#[test]
+fn test_delete_presets_partial_reports_missing() {
+    let (library, _temp_dir) = create_test_library();
+
+    let a = save(&library, "A");
+    let archived = save(&library, "Archived");
+    library.archive_preset(&archived).unwrap();
+
+    let result = library.delete_presets(&[a.clone(), missing(), archived.clone()], true).unwrap();
+    assert_eq!(result.deleted, 2);
+    assert_eq!(result.not_found, vec![missing()]);
+
+    // The live preset is in the trash; the archived one is gone for good
+    let trashed = library.list_deleted_presets().unwrap();
+    assert_eq!(trashed.len(), 1);
+    assert_eq!(trashed[0].preset.id, a);
+    assert!(library.get_preset(&archived).is_err());
+}
+
+#[test]
+fn test_set_favorite_bulk() {
+    let (library, _temp_dir) = create_test_library();
+
+    let a = save(&library, "A");
+    let b = save(&library, "B");
+
+    // All-or-nothing: a missing id leaves both untouched
+    assert!(library.set_favorite_bulk(&[a.clone(), b.clone(), missing()], true, false).is_err());
+    assert!(!library.get_preset(&a).unwrap().is_favorite);
+    assert!(!library.get_preset(&b).unwrap().is_favorite);
+
+    let results = library.set_favorite_bulk(&[a.clone(), missing(), b.clone()], true, true).unwrap();
+    assert_eq!(results[1].status, BulkPresetStatus::NotFound);
+    assert!(library.get_preset(&a).unwrap().is_favorite);
+    assert!(library.get_preset(&b).unwrap().is_favorite);
+
+    library.set_favorite_bulk(std::slice::from_ref(&a), false, false).unwrap();
+    let favorites = library.list_presets(PresetFilter {
+        is_favorite: Some(true),
+        ..Default::default()
+    }).unwrap();
+    assert_eq!(favorites.len(), 1);
+    assert_eq!(favorites[0].id, b);
+}
+
+#[test]
+fn test_delete_presets_reports_missing_and_assigned() {
+    let (library, _temp_dir) = create_test_library();
+
+    let a = save(&library, "A");
+    let b = save(&library, "B");
+    let trashed = save(&library, "Trashed");
+    let keep = save(&library, "Keep");
+    library.assign_to_bank("Microcosm", 45, &b).unwrap();
+    library.assign_to_bank("Microcosm", 46, &keep).unwrap();
+    library.delete_preset(&trashed).unwrap();
+
+    let result = library.delete_presets(&[a.clone(), missing(), b.clone(), trashed.clone()], true).unwrap();
+
+    // Already in the trash counts as not found, and stays in the trash
+    assert_eq!(
+        result,
+        BulkDeleteResult {
+            deleted: 2,
+            not_found: vec![missing(), trashed.clone()],
+            had_bank_assignments: vec![b.clone()],
+        }
+    );
+    assert!(library.get_preset(&a).is_err());
+    let mut in_trash: Vec<_> = library.list_deleted_presets().unwrap().into_iter().map(|d| d.preset.id).collect();
+    in_trash.sort_by(|x, y| x.as_str().cmp(y.as_str()));
+    let mut expected = vec![a.clone(), b.clone(), trashed];
+    expected.sort_by(|x, y| x.as_str().cmp(y.as_str()));
+    assert_eq!(in_trash, expected);
+
+    let banks = library.get_bank_state("Microcosm").unwrap();
+    let assigned: Vec<_> = banks.iter().filter_map(|slot| slot.preset.as_ref()).map(|p| p.id.clone()).collect();
+    assert_eq!(assigned, vec![keep]);
+}
+
+#[test]
+fn test_delete_presets_removes_archived_presets_for_good() {
+    let (library, _temp_dir) = create_test_library();
+    let archived = save(&library, "Archived");
+    library.archive_preset(&archived).unwrap();
+
+    let result = library.delete_presets(std::slice::from_ref(&archived), false).unwrap();
+
+    assert_eq!(result.deleted, 1);
+    assert!(library.get_preset(&archived).is_err());
+    assert!(library.list_deleted_presets().unwrap().is_empty());
+}
+
+#[test]
+fn test_delete_presets_empty_batch() {
+    let (library, _temp_dir) = create_test_library();
+    save(&library, "A");
+
+    assert_eq!(library.delete_presets(&[], false).unwrap(), BulkDeleteResult::default());
+    assert_eq!(library.list_presets(PresetFilter::default()).unwrap().len(), 1);
+}
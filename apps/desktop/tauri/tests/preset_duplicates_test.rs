@@ -0,0 +1,88 @@
+// Integration tests for duplicate preset detection and merging
+
+mod common;
+
+use common::microcosm_parameters;
+use librarian_lib::presets::{PresetError, PresetId, PresetLibrary};
+use tempfile::TempDir;
+
+fn create_test_library() -> (PresetLibrary, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let library = PresetLibrary::new(temp_dir.path().join("test.db")).unwrap();
+    (library, temp_dir)
+}
+
+fn save(library: &PresetLibrary, name: &str, description: Option<&str>, overrides: serde_json::Value, tags: &[&str]) -> PresetId {
+    library.save_preset(
+        name.to_string(),
+        "Microcosm".to_string(),
+        description.map(str::to_string),
+        microcosm_parameters(overrides),
+        tags.iter().map(|tag| tag.to_string()).collect(),
+    ).unwrap().id
+}
+
+#[test]
+fn test_presets_differing_only_in_description_are_duplicates() {
+    let (library, _temp_dir) = create_test_library();
+
+    let a = save(&library, "Wash", Some("big"), serde_json::json!({"mix": 100}), &[]);
+    let b = save(&library, "Wash Copy", Some("bigger"), serde_json::json!({"mix": 100}), &[]);
+    save(&library, "Dry", None, serde_json::json!({"mix": 10}), &[]);
+
+    let groups = library.find_duplicate_presets("Microcosm").unwrap();
+    assert_eq!(groups.len(), 1);
+    let ids: Vec<PresetId> = groups[0].presets.iter().map(|p| p.id.clone()).collect();
+    assert!(ids.contains(&a) && ids.contains(&b));
+    assert_eq!(ids.len(), 2);
+}
+
+#[test]
+fn test_presets_differing_in_one_parameter_are_not_duplicates() {
+    let (library, _temp_dir) = create_test_library();
+
+    save(&library, "A", None, serde_json::json!({"mix": 100}), &[]);
+    save(&library, "B", None, serde_json::json!({"mix": 99}), &[]);
+
+    assert!(library.find_duplicate_presets("Microcosm").unwrap().is_empty());
+}
+
+#[test]
+fn test_merge_moves_banks_and_tags_then_trashes() {
+    let (library, _temp_dir) = create_test_library();
+
+    let keep = save(&library, "Keep", None, serde_json::json!({}), &["ambient"]);
+    let dup = save(&library, "Dup", None, serde_json::json!({}), &["pad", "ambient"]);
+    library.assign_to_bank("Microcosm", 45, &dup).unwrap();
+
+    let merged = library.merge_presets(&keep, std::slice::from_ref(&dup)).unwrap();
+    assert_eq!(merged.tags, vec!["ambient", "pad"]);
+
+    let banks = library.get_bank_state("Microcosm").unwrap();
+    let slot = banks.iter().find(|slot| slot.bank_number == 45).unwrap();
+    assert_eq!(slot.preset.as_ref().unwrap().id, keep);
+
+    let trashed = library.list_deleted_presets().unwrap();
+    assert_eq!(trashed.len(), 1);
+    assert_eq!(trashed[0].preset.id, dup);
+    assert!(trashed[0].previous_bank_numbers.is_empty());
+}
+
+#[test]
+fn test_merge_rolls_back_on_missing_id() {
+    let (library, _temp_dir) = create_test_library();
+
+    let keep = save(&library, "Keep", None, serde_json::json!({}), &[]);
+    let dup = save(&library, "Dup", None, serde_json::json!({}), &["pad"]);
+    let missing = PresetId::new("does-not-exist".to_string());
+
+    let result = library.merge_presets(&keep, &[dup.clone(), missing]);
+    assert!(matches!(result, Err(PresetError::NotFound { .. })));
+    assert!(library.get_preset(&dup).is_ok());
+    assert!(library.get_preset(&keep).unwrap().tags.is_empty());
+
+    assert!(matches!(
+        library.merge_presets(&keep, std::slice::from_ref(&keep)),
+        Err(PresetError::InvalidMerge { .. })
+    ));
+}
@@ -1,7 +1,17 @@
 // Integration tests for PresetLibrary aggregate
 // Tests the full workflow of saving presets and managing bank assignments
 
-use librarian_lib::presets::PresetLibrary;
+mod common;
+
+use common::microcosm_parameters;
+use librarian_lib::midi::pedals::chroma_console::ChromaConsoleState;
+use librarian_lib::midi::pedals::cxm1978::Cxm1978State;
+use librarian_lib::midi::pedals::gen_loss_mkii::GenLossMkiiState;
+use librarian_lib::midi::pedals::lossy::LossyState;
+use librarian_lib::presets::{
+    AutofillSlotStatus, FillOrder, FillStrategy, PresetChangeEvent, PresetError, PresetFilter, PresetLibrary,
+    StrandedBankAssignment, BANK_HISTORY_LIMIT,
+};
 use tempfile::TempDir;
 
 /// Helper to create a temporary database for testing
@@ -21,7 +31,7 @@ fn test_save_preset_workflow() {
         "My Preset".to_string(),
         "Microcosm".to_string(),
         Some("A test preset".to_string()),
-        serde_json::json!({"activity": 64, "mix": 100}),
+        microcosm_parameters(serde_json::json!({"activity": 64, "mix": 100})),
         vec!["ambient".to_string(), "experimental".to_string()],
     ).unwrap();
     
@@ -44,6 +54,7 @@ fn test_save_preset_workflow() {
         None,
         Some(true), // Mark as favorite
         None,
+        None,
     ).unwrap();
     
     assert!(updated.is_favorite);
@@ -67,7 +78,7 @@ fn test_update_preset_workflow() {
         "Original".to_string(),
         "Microcosm".to_string(),
         None,
-        serde_json::json!({"activity": 50}),
+        microcosm_parameters(serde_json::json!({"activity": 50})),
         vec![],
     ).unwrap();
     
@@ -79,6 +90,7 @@ fn test_update_preset_workflow() {
         None,
         None,
         None,
+        None,
     ).unwrap();
     assert_eq!(updated.name, "Modified");
     
@@ -90,6 +102,7 @@ fn test_update_preset_workflow() {
         None,
         None,
         None,
+        None,
     ).unwrap();
     assert_eq!(updated.description, Some("New description".to_string()));
     
@@ -101,6 +114,7 @@ fn test_update_preset_workflow() {
         Some(vec!["tag1".to_string(), "tag2".to_string()]),
         None,
         None,
+        None,
     ).unwrap();
     assert_eq!(updated.tags.len(), 2);
     
@@ -112,6 +126,7 @@ fn test_update_preset_workflow() {
         None,
         None,
         Some(serde_json::json!({"activity": 100, "mix": 80})),
+        None,
     ).unwrap();
     assert_eq!(updated.parameters["activity"], 100);
     assert_eq!(updated.parameters["mix"], 80);
@@ -126,7 +141,7 @@ fn test_delete_preset_workflow() {
         "To Delete".to_string(),
         "Microcosm".to_string(),
         None,
-        serde_json::json!({}),
+        microcosm_parameters(serde_json::json!({})),
         vec![],
     ).unwrap();
     
@@ -159,7 +174,7 @@ fn test_bank_management_workflow() {
         "Preset 1".to_string(),
         "Microcosm".to_string(),
         None,
-        serde_json::json!({}),
+        microcosm_parameters(serde_json::json!({})),
         vec![],
     ).unwrap();
     
@@ -167,7 +182,7 @@ fn test_bank_management_workflow() {
         "Preset 2".to_string(),
         "Microcosm".to_string(),
         None,
-        serde_json::json!({}),
+        microcosm_parameters(serde_json::json!({})),
         vec![],
     ).unwrap();
     
@@ -175,7 +190,7 @@ fn test_bank_management_workflow() {
         "Preset 3".to_string(),
         "Microcosm".to_string(),
         None,
-        serde_json::json!({}),
+        microcosm_parameters(serde_json::json!({})),
         vec![],
     ).unwrap();
     
@@ -217,7 +232,7 @@ fn test_preset_library_with_multiple_pedal_types() {
         "Microcosm Preset".to_string(),
         "Microcosm".to_string(),
         None,
-        serde_json::json!({"activity": 64}),
+        microcosm_parameters(serde_json::json!({"activity": 64})),
         vec![],
     ).unwrap();
     
@@ -233,7 +248,7 @@ fn test_preset_library_with_multiple_pedal_types() {
         "Chroma Preset".to_string(),
         "ChromaConsole".to_string(),
         None,
-        serde_json::json!({"drive": 100}),
+        serde_json::to_value(ChromaConsoleState::default()).unwrap(),
         vec![],
     ).unwrap();
     
@@ -259,6 +274,67 @@ fn test_preset_library_with_multiple_pedal_types() {
     assert_eq!(retrieved_chroma.pedal_type, "ChromaConsole");
 }
 
+#[test]
+fn test_truncated_parameters_rejected_on_save() {
+    let (library, _temp_dir) = create_test_library();
+    
+    // A partially written blob is missing fields
+    let mut parameters = microcosm_parameters(serde_json::json!({}));
+    parameters.as_object_mut().unwrap().remove("looper_speed_stepped");
+    
+    let result = library.save_preset(
+        "Truncated".to_string(),
+        "Microcosm".to_string(),
+        None,
+        parameters,
+        vec![],
+    );
+    match result {
+        Err(PresetError::InvalidParameters { pedal_type, reason }) => {
+            assert_eq!(pedal_type, "Microcosm");
+            assert!(reason.contains("missing field `looper_speed_stepped`"), "{}", reason);
+            assert!(reason.contains("line 1 column"), "{}", reason);
+        }
+        other => panic!("Expected InvalidParameters, got {:?}", other),
+    }
+    assert!(library.list_presets(Default::default()).unwrap().is_empty());
+}
+
+#[test]
+fn test_parameters_for_other_pedal_rejected_on_save() {
+    let (library, _temp_dir) = create_test_library();
+    
+    let gen_loss = serde_json::to_value(GenLossMkiiState::default()).unwrap();
+    let result = library.save_preset(
+        "Wrong Pedal".to_string(),
+        "Microcosm".to_string(),
+        None,
+        gen_loss,
+        vec![],
+    );
+    assert!(matches!(result, Err(PresetError::InvalidParameters { .. })));
+}
+
+#[test]
+fn test_unknown_parameter_fields_saved_with_warnings() {
+    let (library, _temp_dir) = create_test_library();
+    
+    let preset = library.save_preset(
+        "Extra Field".to_string(),
+        "Microcosm".to_string(),
+        None,
+        microcosm_parameters(serde_json::json!({"future_knob": 12})),
+        vec![],
+    ).unwrap();
+    assert_eq!(preset.warnings.len(), 1);
+    assert!(preset.warnings[0].contains("future_knob"));
+    
+    // Extra fields are kept, and warnings aren't stored
+    let retrieved = library.get_preset(&preset.id).unwrap();
+    assert_eq!(retrieved.parameters["future_knob"], 12);
+    assert!(retrieved.warnings.is_empty());
+}
+
 #[test]
 fn test_validation_constraints() {
     let (library, _temp_dir) = create_test_library();
@@ -268,7 +344,7 @@ fn test_validation_constraints() {
         "".to_string(),
         "Microcosm".to_string(),
         None,
-        serde_json::json!({}),
+        microcosm_parameters(serde_json::json!({})),
         vec![],
     );
     assert!(result.is_err());
@@ -278,7 +354,7 @@ fn test_validation_constraints() {
         "   ".to_string(),
         "Microcosm".to_string(),
         None,
-        serde_json::json!({}),
+        microcosm_parameters(serde_json::json!({})),
         vec![],
     );
     assert!(result.is_err());
@@ -288,7 +364,7 @@ fn test_validation_constraints() {
         "Unique Name".to_string(),
         "Microcosm".to_string(),
         None,
-        serde_json::json!({}),
+        microcosm_parameters(serde_json::json!({})),
         vec![],
     ).unwrap();
     
@@ -296,7 +372,7 @@ fn test_validation_constraints() {
         "Unique Name".to_string(),
         "Microcosm".to_string(),
         None,
-        serde_json::json!({}),
+        microcosm_parameters(serde_json::json!({})),
         vec![],
     );
     assert!(result.is_err());
@@ -306,7 +382,7 @@ fn test_validation_constraints() {
         "Test".to_string(),
         "Microcosm".to_string(),
         None,
-        serde_json::json!({}),
+        microcosm_parameters(serde_json::json!({})),
         vec![],
     ).unwrap();
     
@@ -317,3 +393,927 @@ fn test_validation_constraints() {
     let result = library.assign_to_bank("Microcosm", 61, &preset.id);
     assert!(result.is_err());
 }
+
+#[test]
+fn test_export_bank_layout() {
+    let (library, _temp_dir) = create_test_library();
+    
+    let preset = library.save_preset(
+        "Board | Main".to_string(),
+        "Microcosm".to_string(),
+        None,
+        microcosm_parameters(serde_json::json!({})),
+        vec![],
+    ).unwrap();
+    library.assign_to_bank("Microcosm", 45, &preset.id).unwrap();
+    library.assign_to_bank("Microcosm", 50, &preset.id).unwrap();
+    
+    let layout = library.export_bank_layout().unwrap();
+    
+    // Every pedal type is present, even with nothing assigned
+    assert_eq!(layout.pedals.len(), librarian_lib::presets::bank_config::PEDAL_TYPES.len());
+    assert!(layout.pedals["Lossy"].is_empty());
+    
+    let microcosm = &layout.pedals["Microcosm"];
+    assert_eq!(microcosm.len(), 2);
+    assert_eq!(microcosm[0].bank_number, 45);
+    assert_eq!(microcosm[0].preset_id, preset.id);
+    assert_eq!(microcosm[0].preset_name, "Board | Main");
+    assert!(microcosm[0].synced_at.is_some());
+    
+    // JSON is keyed directly by pedal type
+    let json = serde_json::to_value(&layout).unwrap();
+    assert_eq!(json["Microcosm"][1]["bankNumber"], 50);
+    assert_eq!(json["Clean"], serde_json::json!([]));
+    
+    let markdown = layout.to_markdown_table();
+    assert!(markdown.starts_with("| Pedal | Bank | Preset | Last Synced |"));
+    assert!(markdown.contains("| Microcosm | Bank 1A | Board \\| Main |"));
+    assert!(markdown.contains("| Lossy | - | _(no banks assigned)_ | - |"));
+}
+
+#[test]
+fn test_same_name_allowed_across_pedal_types() {
+    let (library, _temp_dir) = create_test_library();
+    
+    library.save_preset(
+        "Lead".to_string(),
+        "Microcosm".to_string(),
+        None,
+        microcosm_parameters(serde_json::json!({})),
+        vec![],
+    ).unwrap();
+    
+    let chroma_lead = library.save_preset(
+        "Lead".to_string(),
+        "ChromaConsole".to_string(),
+        None,
+        serde_json::to_value(ChromaConsoleState::default()).unwrap(),
+        vec![],
+    );
+    assert!(chroma_lead.is_ok());
+}
+
+#[test]
+fn test_clone_bank_layout_matches_presets_by_name() {
+    let (library, _temp_dir) = create_test_library();
+    
+    let save = |name: &str, pedal_type: &str, parameters: serde_json::Value| {
+        library.save_preset(name.to_string(), pedal_type.to_string(), None, parameters, vec![]).unwrap()
+    };
+    let chroma = || serde_json::to_value(ChromaConsoleState::default()).unwrap();
+    
+    let micro_lead = save("Lead", "Microcosm", microcosm_parameters(serde_json::json!({})));
+    let micro_pad = save("Pad", "Microcosm", microcosm_parameters(serde_json::json!({})));
+    let micro_solo = save("Solo", "Microcosm", microcosm_parameters(serde_json::json!({})));
+    library.assign_to_bank("Microcosm", 45, &micro_lead.id).unwrap();
+    library.assign_to_bank("Microcosm", 46, &micro_pad.id).unwrap();
+    library.assign_to_bank("Microcosm", 47, &micro_solo.id).unwrap();
+    
+    // No Chroma Console "Solo", and bank 48 already holds something unrelated
+    let chroma_lead = save("Lead", "ChromaConsole", chroma());
+    let chroma_pad = save("Pad", "ChromaConsole", chroma());
+    let chroma_other = save("Other", "ChromaConsole", chroma());
+    library.assign_to_bank("ChromaConsole", 48, &chroma_other.id).unwrap();
+    
+    assert_eq!(library.clone_bank_layout("Microcosm", "ChromaConsole").unwrap(), 2);
+    
+    let banks = library.get_bank_state("ChromaConsole").unwrap();
+    let preset_in = |bank: u8| {
+        banks.iter().find(|b| b.bank_number == bank).unwrap().preset.as_ref().map(|p| p.id.clone())
+    };
+    assert_eq!(preset_in(45), Some(chroma_lead.id));
+    assert_eq!(preset_in(46), Some(chroma_pad.id));
+    assert_eq!(preset_in(47), None);
+    assert_eq!(preset_in(48), Some(chroma_other.id));
+    
+    // The source layout is untouched
+    let micro_45 = library.get_bank_preset("Microcosm", 45).unwrap().unwrap();
+    assert_eq!(micro_45.id, micro_lead.id);
+}
+
+#[test]
+fn test_clone_bank_layout_rejects_unknown_pedal() {
+    let (library, _temp_dir) = create_test_library();
+    
+    assert!(library.clone_bank_layout("Microcosm", "NotAPedal").is_err());
+}
+
+#[test]
+fn test_bank_state_covers_each_pedals_program_range() {
+    let (library, _temp_dir) = create_test_library();
+    
+    let expected = [
+        ("Microcosm", 45, 60),
+        ("PreampMk2", 0, 29),
+        ("Cxm1978", 0, 29),
+        ("ChromaConsole", 40, 79),
+        ("GenLossMkii", 1, 122),
+    ];
+    for (pedal_type, first, last) in expected {
+        let banks = library.get_bank_state(pedal_type).unwrap();
+        let numbers: Vec<u8> = banks.iter().map(|slot| slot.bank_number).collect();
+        assert_eq!(numbers, (first..=last).collect::<Vec<u8>>(), "{} bank range", pedal_type);
+        assert!(banks.iter().all(|slot| slot.preset.is_none()));
+    }
+    
+    // Every pedal with a bank config gets its slots
+    for pedal_type in librarian_lib::presets::bank_config::PEDAL_TYPES {
+        assert!(!library.get_bank_state(pedal_type).unwrap().is_empty(), "{} has no banks", pedal_type);
+    }
+    
+    assert!(matches!(
+        library.get_bank_state("NotAPedal"),
+        Err(PresetError::NoBankConfig { .. })
+    ));
+}
+
+#[test]
+fn test_cxm1978_bank_assignment_shows_in_bank_state() {
+    let (library, _temp_dir) = create_test_library();
+    
+    let preset = library.save_preset(
+        "Plate".to_string(),
+        "Cxm1978".to_string(),
+        None,
+        serde_json::to_value(Cxm1978State::default()).unwrap(),
+        vec![],
+    ).unwrap();
+    library.assign_to_bank("Cxm1978", 0, &preset.id).unwrap();
+    assert!(library.assign_to_bank("Cxm1978", 30, &preset.id).is_err());
+    
+    let banks = library.get_bank_state("Cxm1978").unwrap();
+    assert_eq!(banks[0].preset.as_ref().unwrap().id, preset.id);
+    assert_eq!(banks[0].bank_label, "Bank 1-1");
+}
+
+fn save_gen_loss(library: &PresetLibrary, name: &str) -> librarian_lib::presets::PresetId {
+    library.save_preset(
+        name.to_string(),
+        "GenLossMkii".to_string(),
+        None,
+        serde_json::to_value(GenLossMkiiState::default()).unwrap(),
+        vec![],
+    ).unwrap().id
+}
+
+#[test]
+fn test_multi_bank_pedal_allows_preset_in_several_banks() {
+    let (library, _temp_dir) = create_test_library();
+    
+    let preset = library.save_preset(
+        "Everywhere".to_string(),
+        "Microcosm".to_string(),
+        None,
+        microcosm_parameters(serde_json::json!({})),
+        vec![],
+    ).unwrap();
+    library.assign_to_bank("Microcosm", 45, &preset.id).unwrap();
+    library.assign_to_bank("Microcosm", 46, &preset.id).unwrap();
+    
+    let with_banks = library.get_presets_with_banks("Microcosm").unwrap();
+    assert_eq!(with_banks[0].bank_numbers, vec![45, 46]);
+}
+
+#[test]
+fn test_single_bank_pedal_rejects_second_bank() {
+    let (library, _temp_dir) = create_test_library();
+    
+    let id = save_gen_loss(&library, "Canonical");
+    library.assign_to_bank("GenLossMkii", 3, &id).unwrap();
+    // Re-assigning the same slot is fine
+    library.assign_to_bank("GenLossMkii", 3, &id).unwrap();
+    
+    assert!(matches!(
+        library.assign_to_bank("GenLossMkii", 7, &id),
+        Err(PresetError::AlreadyAssigned { existing_bank: 3 })
+    ));
+    let with_banks = library.get_presets_with_banks("GenLossMkii").unwrap();
+    assert_eq!(with_banks[0].bank_numbers, vec![3]);
+}
+
+#[test]
+fn test_single_bank_pedal_move_clears_old_slot() {
+    let (library, _temp_dir) = create_test_library();
+    
+    let moved = save_gen_loss(&library, "Moved");
+    let displaced = save_gen_loss(&library, "Displaced");
+    library.assign_to_bank("GenLossMkii", 3, &moved).unwrap();
+    library.assign_to_bank("GenLossMkii", 7, &displaced).unwrap();
+    
+    library.move_to_bank("GenLossMkii", 7, &moved).unwrap();
+    
+    let banks = library.get_bank_state("GenLossMkii").unwrap();
+    let slot = |number: u8| banks.iter().find(|slot| slot.bank_number == number).unwrap();
+    assert!(slot(3).preset.is_none());
+    assert_eq!(slot(7).preset.as_ref().unwrap().id, moved);
+    
+    // The list form is unchanged: one bank for the moved preset, none for the displaced one
+    let with_banks = library.get_presets_with_banks("GenLossMkii").unwrap();
+    let banks_of = |name: &str| with_banks.iter().find(|p| p.preset.name == name).unwrap().bank_numbers.clone();
+    assert_eq!(banks_of("Moved"), vec![7]);
+    assert!(banks_of("Displaced").is_empty());
+}
+
+#[test]
+fn test_get_preset_by_name() {
+    let (library, _temp_dir) = create_test_library();
+    
+    let saved = library.save_preset(
+        "Shimmer".to_string(),
+        "Microcosm".to_string(),
+        None,
+        microcosm_parameters(serde_json::json!({"mix": 90})),
+        vec![],
+    ).unwrap();
+    
+    let found = library.get_preset_by_name("Microcosm", " Shimmer ").unwrap();
+    assert_eq!(found.id, saved.id);
+    assert_eq!(found.parameters["mix"], 90);
+    
+    // Names are per pedal type, and trashed presets don't match
+    assert!(matches!(library.get_preset_by_name("Lossy", "Shimmer"), Err(PresetError::NotFound { .. })));
+    library.delete_preset(&saved.id).unwrap();
+    assert!(matches!(library.get_preset_by_name("Microcosm", "Shimmer"), Err(PresetError::NotFound { .. })));
+}
+
+#[test]
+fn test_tag_preset_collection_appends_and_dedupes() {
+    let (library, _temp_dir) = create_test_library();
+    
+    let tagged = library.save_preset(
+        "Tagged".to_string(),
+        "Microcosm".to_string(),
+        None,
+        microcosm_parameters(serde_json::json!({})),
+        vec!["ambient".to_string(), "live-set".to_string()],
+    ).unwrap();
+    let untagged = library.save_preset(
+        "Untagged".to_string(),
+        "Lossy".to_string(),
+        None,
+        serde_json::to_value(LossyState::default()).unwrap(),
+        vec![],
+    ).unwrap();
+    
+    let updated = library.tag_preset_collection(
+        &[tagged.id.clone(), untagged.id.clone()],
+        vec!["live-set".to_string(), " imported-2024 ".to_string(), "imported-2024".to_string(), "".to_string()],
+    ).unwrap();
+    assert_eq!(updated, 2);
+    
+    // Existing tags are kept, new ones appended once
+    assert_eq!(library.get_preset(&tagged.id).unwrap().tags, vec!["ambient", "live-set", "imported-2024"]);
+    assert_eq!(library.get_preset(&untagged.id).unwrap().tags, vec!["live-set", "imported-2024"]);
+    
+    // Presets that already have every tag aren't counted
+    let updated = library.tag_preset_collection(std::slice::from_ref(&tagged.id), vec!["ambient".to_string()]).unwrap();
+    assert_eq!(updated, 0);
+}
+
+#[test]
+fn test_tag_preset_collection_missing_preset_changes_nothing() {
+    let (library, _temp_dir) = create_test_library();
+    
+    let preset = library.save_preset(
+        "Real".to_string(),
+        "Microcosm".to_string(),
+        None,
+        microcosm_parameters(serde_json::json!({})),
+        vec![],
+    ).unwrap();
+    
+    let missing = librarian_lib::presets::PresetId::new("does-not-exist".to_string());
+    let result = library.tag_preset_collection(&[preset.id.clone(), missing], vec!["live-set".to_string()]);
+    assert!(result.is_err());
+    
+    // The whole batch rolled back
+    assert!(library.get_preset(&preset.id).unwrap().tags.is_empty());
+}
+
+#[test]
+fn test_bank_history_written_only_after_save_sequence_completes() {
+    let (library, _temp_dir) = create_test_library();
+    
+    let original = library.save_preset(
+        "Original".to_string(),
+        "Microcosm".to_string(),
+        None,
+        microcosm_parameters(serde_json::json!({"mix": 10})),
+        vec![],
+    ).unwrap();
+    let replacement = library.save_preset(
+        "Replacement".to_string(),
+        "Microcosm".to_string(),
+        None,
+        microcosm_parameters(serde_json::json!({"mix": 120})),
+        vec![],
+    ).unwrap();
+    library.assign_to_bank("Microcosm", 45, &original.id).unwrap();
+    
+    // Save sequence failed: the snapshot is dropped and nothing is recorded
+    let snapshot = library.snapshot_bank("Microcosm", 45).unwrap();
+    assert_eq!(snapshot.previous.as_ref().unwrap().id, original.id);
+    drop(snapshot);
+    assert!(library.get_bank_history("Microcosm", 45).unwrap().is_empty());
+    
+    // Save sequence completed: the previous occupant is recorded
+    let snapshot = library.snapshot_bank("Microcosm", 45).unwrap();
+    let entry = library
+        .record_bank_overwrite(&snapshot, "Microcosm MIDI", &replacement.parameters)
+        .unwrap()
+        .unwrap();
+    library.assign_to_bank("Microcosm", 45, &replacement.id).unwrap();
+    
+    assert_eq!(entry.preset_id, original.id);
+    assert_eq!(entry.preset_name, "Original");
+    assert_eq!(entry.device_name, "Microcosm MIDI");
+    assert_eq!(entry.parameters, original.parameters);
+    
+    let history = library.get_bank_history("Microcosm", 45).unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(library.get_bank_history_entry(entry.id).unwrap().preset_id, original.id);
+    assert!(library.get_bank_history("Microcosm", 46).unwrap().is_empty());
+}
+
+#[test]
+fn test_bank_history_skips_empty_and_unchanged_banks() {
+    let (library, _temp_dir) = create_test_library();
+    
+    let preset = library.save_preset(
+        "Only".to_string(),
+        "Microcosm".to_string(),
+        None,
+        microcosm_parameters(serde_json::json!({})),
+        vec![],
+    ).unwrap();
+    
+    // Nothing was in the bank
+    let snapshot = library.snapshot_bank("Microcosm", 45).unwrap();
+    assert!(library.record_bank_overwrite(&snapshot, "Microcosm", &preset.parameters).unwrap().is_none());
+    library.assign_to_bank("Microcosm", 45, &preset.id).unwrap();
+    
+    // Re-saving the same state loses nothing
+    let snapshot = library.snapshot_bank("Microcosm", 45).unwrap();
+    assert!(library.record_bank_overwrite(&snapshot, "Microcosm", &preset.parameters).unwrap().is_none());
+    
+    assert!(library.get_bank_history("Microcosm", 45).unwrap().is_empty());
+    assert!(matches!(
+        library.get_bank_history_entry(1),
+        Err(PresetError::HistoryEntryNotFound { id: 1 })
+    ));
+}
+
+#[test]
+fn test_bank_history_capped_per_bank() {
+    let (library, _temp_dir) = create_test_library();
+    
+    let incoming = microcosm_parameters(serde_json::json!({"mix": 127}));
+    for i in 0..BANK_HISTORY_LIMIT + 2 {
+        let preset = library.save_preset(
+            format!("Preset {}", i),
+            "Microcosm".to_string(),
+            None,
+            microcosm_parameters(serde_json::json!({"mix": i})),
+            vec![],
+        ).unwrap();
+        library.assign_to_bank("Microcosm", 45, &preset.id).unwrap();
+        
+        let snapshot = library.snapshot_bank("Microcosm", 45).unwrap();
+        library.record_bank_overwrite(&snapshot, "Microcosm", &incoming).unwrap();
+    }
+    
+    let history = library.get_bank_history("Microcosm", 45).unwrap();
+    assert_eq!(history.len(), BANK_HISTORY_LIMIT);
+    assert_eq!(history[0].preset_name, format!("Preset {}", BANK_HISTORY_LIMIT + 1));
+    assert_eq!(history.last().unwrap().preset_name, "Preset 2");
+}
+
+#[test]
+fn test_save_preset_and_assign() {
+    let (library, _temp_dir) = create_test_library();
+    
+    let (preset, slot) = library.save_preset_and_assign(
+        "Bank Seven".to_string(),
+        "Microcosm".to_string(),
+        None,
+        microcosm_parameters(serde_json::json!({"mix": 90})),
+        vec!["live".to_string()],
+        51,
+    ).unwrap();
+    
+    assert_eq!(slot.bank_number, 51);
+    assert_eq!(slot.preset.as_ref().unwrap().id, preset.id);
+    let stored = library.get_bank_state("Microcosm").unwrap()
+        .into_iter()
+        .find(|slot| slot.bank_number == 51)
+        .and_then(|slot| slot.preset)
+        .unwrap();
+    assert_eq!(stored.name, "Bank Seven");
+}
+
+#[test]
+fn test_save_preset_and_assign_rolls_back_on_failure() {
+    let (library, temp_dir) = create_test_library();
+    let count = |table: &str| -> i64 {
+        let conn = rusqlite::Connection::open(temp_dir.path().join("test.db")).unwrap();
+        conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get(0)).unwrap()
+    };
+    let attempt = |name: &str, parameters: serde_json::Value, bank_number: u8| library.save_preset_and_assign(
+        name.to_string(),
+        "Microcosm".to_string(),
+        None,
+        parameters,
+        vec![],
+        bank_number,
+    );
+    
+    // Rejected before anything is written
+    assert!(matches!(
+        attempt("Bad Bank", microcosm_parameters(serde_json::json!({})), 200),
+        Err(PresetError::InvalidBankNumber { .. })
+    ));
+    assert!(matches!(
+        attempt("Bad Params", microcosm_parameters(serde_json::json!({"mix": 300})), 45),
+        Err(PresetError::InvalidParameters { .. })
+    ));
+    
+    // The bank write fails after the preset row went in: the preset must go too
+    let conn = rusqlite::Connection::open(temp_dir.path().join("test.db")).unwrap();
+    conn.execute_batch(
+        "CREATE TRIGGER fail_bank_write BEFORE INSERT ON pedal_banks
+         BEGIN SELECT RAISE(ABORT, 'bank write failed'); END;",
+    ).unwrap();
+    assert!(matches!(
+        attempt("Orphan", microcosm_parameters(serde_json::json!({})), 45),
+        Err(PresetError::Database(_))
+    ));
+    
+    assert_eq!(count("presets"), 0);
+    assert_eq!(count("pedal_banks"), 0);
+}
+
+#[test]
+fn test_backup_to_file_copies_live_database() {
+    let (library, temp_dir) = create_test_library();
+    let preset = library.save_preset(
+        "Backed Up".to_string(),
+        "Microcosm".to_string(),
+        None,
+        microcosm_parameters(serde_json::json!({"mix": 80})),
+        vec![],
+    ).unwrap();
+    library.assign_to_bank("Microcosm", 45, &preset.id).unwrap();
+    
+    let backup_path = temp_dir.path().join("backup.db");
+    library.backup_to_file(backup_path.clone()).unwrap();
+    
+    // The original stays open and usable
+    library.toggle_favorite(&preset.id).unwrap();
+    
+    let restored = PresetLibrary::new(backup_path).unwrap();
+    let copy = restored.get_preset(&preset.id).unwrap();
+    assert_eq!(copy.name, "Backed Up");
+    assert!(!copy.is_favorite);
+    let slot = restored.get_bank_state("Microcosm").unwrap()
+        .into_iter()
+        .find(|slot| slot.bank_number == 45)
+        .and_then(|slot| slot.preset)
+        .unwrap();
+    assert_eq!(slot.id, preset.id);
+}
+
+#[test]
+fn test_maintenance_shrinks_database_after_bulk_delete_and_purge() {
+    let (library, _temp_dir) = create_test_library();
+    let ids: Vec<_> = (0..300)
+        .map(|i| library.save_preset(
+            format!("Bulk {}", i),
+            "Microcosm".to_string(),
+            Some("x".repeat(1000)),
+            microcosm_parameters(serde_json::json!({})),
+            vec![],
+        ).unwrap().id)
+        .collect();
+    library.delete_presets(&ids, false).unwrap();
+    library.purge_deleted(0).unwrap();
+    assert!(library.needs_maintenance(0.5).unwrap());
+    
+    let report = library.run_maintenance().unwrap();
+    assert!(report.size_after < report.size_before, "{:?}", report);
+    assert!(!library.needs_maintenance(0.0).unwrap());
+}
+
+#[test]
+fn test_maintain_database_runs_without_the_shared_library_lock() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let shared = librarian_lib::presets::create_shared_library(db_path.clone()).unwrap();
+    let library = shared.lock().unwrap();
+    let ids: Vec<_> = (0..300)
+        .map(|i| library.save_preset(
+            format!("Bulk {}", i),
+            "Microcosm".to_string(),
+            Some("x".repeat(1000)),
+            microcosm_parameters(serde_json::json!({})),
+            vec![],
+        ).unwrap().id)
+        .collect();
+    library.delete_presets(&ids, false).unwrap();
+    library.purge_deleted(0).unwrap();
+    
+    // The lock is still held here; maintenance goes through its own connection regardless
+    let report = librarian_lib::presets::maintain_database(&db_path, 0.5).unwrap().unwrap();
+    assert!(report.size_after < report.size_before, "{:?}", report);
+    assert_eq!(librarian_lib::presets::maintain_database(&db_path, 0.5).unwrap(), None);
+    assert!(library.list_presets(PresetFilter::default()).unwrap().is_empty());
+}
+
+#[test]
+fn test_search_fulltext_ranks_and_tracks_edits() {
+    let (library, _temp_dir) = create_test_library();
+    let save = |name: &str, description: Option<&str>, tags: &[&str]| {
+        library.save_preset(
+            name.to_string(),
+            "Microcosm".to_string(),
+            description.map(str::to_string),
+            microcosm_parameters(serde_json::json!({})),
+            tags.iter().map(|tag| tag.to_string()).collect(),
+        ).unwrap()
+    };
+    let warm_pad = save("Warm Pad", Some("warm, slow and warm"), &["ambient"]);
+    let glitch = save("Glitch Lead", Some("a little warmth"), &["warm"]);
+    let shimmer = save("Shimmer", None, &["ambient"]);
+    
+    let names = |query: &str| -> Vec<String> {
+        library.search_fulltext(query).unwrap().into_iter().map(|preset| preset.name).collect()
+    };
+    
+    // Prefix match on every word, most relevant first
+    assert_eq!(names("warm"), vec!["Warm Pad", "Glitch Lead"]);
+    assert_eq!(names("ambient warm"), vec!["Warm Pad"]);
+    assert!(names("").is_empty());
+    // FTS5 syntax in the query is taken literally rather than failing: AND is just a word
+    assert_eq!(names("\"warm AND ("), vec!["Warm Pad"]);
+    
+    // Renames and tag edits are picked up by the triggers
+    library.update_preset(&shimmer.id, Some("Warm Shimmer".to_string()), None, None, None, None, None).unwrap();
+    assert!(names("shimmer").contains(&"Warm Shimmer".to_string()));
+    library.update_preset(&glitch.id, None, Some("harsh".to_string()), Some(vec![]), None, None, None).unwrap();
+    assert_eq!(names("warm"), vec!["Warm Pad", "Warm Shimmer"]);
+    
+    // Trashed presets drop out
+    library.delete_preset(&warm_pad.id).unwrap();
+    assert_eq!(names("warm"), vec!["Warm Shimmer"]);
+}
+
+#[test]
+fn test_chroma_console_banks_are_user_presets_only() {
+    let (library, _temp_dir) = create_test_library();
+    let preset = library.save_preset(
+        "Chroma User".to_string(),
+        "ChromaConsole".to_string(),
+        None,
+        serde_json::to_value(ChromaConsoleState::default()).unwrap(),
+        vec![],
+    ).unwrap();
+    
+    // Factory presets (PC 0-39) can't be overwritten
+    assert!(matches!(
+        library.assign_to_bank("ChromaConsole", 39, &preset.id),
+        Err(PresetError::InvalidBankNumber { min: 40, max: 79, .. })
+    ));
+    library.assign_to_bank("ChromaConsole", 40, &preset.id).unwrap();
+    
+    let banks = library.get_bank_state("ChromaConsole").unwrap();
+    assert_eq!(banks.len(), 40);
+    assert_eq!(banks.first().unwrap().bank_number, 40);
+    assert_eq!(banks.last().unwrap().bank_number, 79);
+}
+
+/// Save `count` Microcosm presets named "{prefix} 00".. carrying `tag`
+fn save_tagged_microcosm_presets(library: &PresetLibrary, prefix: &str, count: usize, tag: &str) -> Vec<librarian_lib::presets::Preset> {
+    (0..count)
+        .map(|i| {
+            library.save_preset(
+                format!("{} {:02}", prefix, i),
+                "Microcosm".to_string(),
+                None,
+                microcosm_parameters(serde_json::json!({})),
+                vec![tag.to_string()],
+            ).unwrap()
+        })
+        .collect()
+}
+
+fn tag_filter(tag: &str) -> PresetFilter {
+    PresetFilter { tags: vec![tag.to_string()], ..Default::default() }
+}
+
+fn alphabetical(clear_remainder: bool) -> FillStrategy {
+    FillStrategy { order: FillOrder::Alphabetical, clear_remainder }
+}
+
+#[test]
+fn test_autofill_truncates_to_the_pedals_banks() {
+    let (library, _temp_dir) = create_test_library();
+    save_tagged_microcosm_presets(&library, "Tour", 18, "june-tour");
+    save_tagged_microcosm_presets(&library, "Other", 2, "studio");
+    
+    let plan = library.plan_autofill("Microcosm", tag_filter("june-tour"), &alphabetical(false)).unwrap();
+    assert_eq!(plan.slots.len(), 16);
+    assert_eq!(plan.truncated, 2);
+    // Planning writes nothing
+    assert!(library.get_bank_state("Microcosm").unwrap().iter().all(|slot| slot.preset.is_none()));
+    
+    let report = library.apply_autofill(plan);
+    assert!(report.is_complete());
+    let banks = library.get_bank_state("Microcosm").unwrap();
+    assert_eq!(banks[0].preset.as_ref().unwrap().name, "Tour 00");
+    assert_eq!(banks[15].preset.as_ref().unwrap().name, "Tour 15");
+}
+
+#[test]
+fn test_autofill_clear_remainder() {
+    let (library, _temp_dir) = create_test_library();
+    let tour = save_tagged_microcosm_presets(&library, "Tour", 3, "june-tour");
+    let other = save_tagged_microcosm_presets(&library, "Other", 1, "studio");
+    library.assign_to_bank("Microcosm", 60, &other[0].id).unwrap();
+    
+    // Fewer matches than banks: the rest are left alone...
+    let report = library.apply_autofill(library.plan_autofill("Microcosm", tag_filter("june-tour"), &alphabetical(false)).unwrap());
+    assert_eq!(report.results.len(), 3);
+    assert_eq!(library.get_bank_preset("Microcosm", 47).unwrap().unwrap().id, tour[2].id);
+    assert_eq!(library.get_bank_preset("Microcosm", 60).unwrap().unwrap().id, other[0].id);
+    
+    // ...or cleared
+    let report = library.apply_autofill(library.plan_autofill("Microcosm", tag_filter("june-tour"), &alphabetical(true)).unwrap());
+    assert_eq!(report.results.len(), 16);
+    assert_eq!(report.results[15].status, AutofillSlotStatus::Cleared);
+    assert!(library.get_bank_preset("Microcosm", 60).unwrap().is_none());
+    assert_eq!(library.get_bank_preset("Microcosm", 45).unwrap().unwrap().id, tour[0].id);
+}
+
+#[test]
+fn test_autofill_failure_keeps_earlier_slots() {
+    let (library, _temp_dir) = create_test_library();
+    let tour = save_tagged_microcosm_presets(&library, "Tour", 3, "june-tour");
+    let other = save_tagged_microcosm_presets(&library, "Other", 1, "studio");
+    library.assign_to_bank("Microcosm", 47, &other[0].id).unwrap();
+    
+    let plan = library.plan_autofill("Microcosm", tag_filter("june-tour"), &alphabetical(false)).unwrap();
+    // Gone by the time the plan is applied
+    library.permanently_delete_preset(&tour[1].id).unwrap();
+    let report = library.apply_autofill(plan);
+    
+    assert!(!report.is_complete());
+    assert_eq!(report.results[0].status, AutofillSlotStatus::Assigned);
+    assert!(matches!(report.results[1].status, AutofillSlotStatus::Failed { .. }));
+    assert_eq!(report.results[2].status, AutofillSlotStatus::Skipped);
+    
+    assert_eq!(library.get_bank_preset("Microcosm", 45).unwrap().unwrap().id, tour[0].id);
+    assert!(library.get_bank_preset("Microcosm", 46).unwrap().is_none());
+    // Skipped slots keep what they held
+    assert_eq!(library.get_bank_preset("Microcosm", 47).unwrap().unwrap().id, other[0].id);
+}
+
+#[test]
+fn test_autofill_hook_failure_keeps_earlier_slots() {
+    let (library, _temp_dir) = create_test_library();
+    let tour = save_tagged_microcosm_presets(&library, "Tour", 3, "june-tour");
+    
+    let plan = library.plan_autofill("Microcosm", tag_filter("june-tour"), &alphabetical(false)).unwrap();
+    let mut saved = Vec::new();
+    let report = library.apply_autofill_with(plan, |_, index, slot| {
+        saved.push(index);
+        match index {
+            0 => Ok(true),
+            _ => Err(format!("pedal didn't answer for bank {}", slot.bank_number)),
+        }
+    });
+    
+    assert_eq!(saved, vec![0, 1]);
+    assert_eq!(report.results[0].status, AutofillSlotStatus::SavedToPedal);
+    assert!(matches!(report.results[1].status, AutofillSlotStatus::Failed { .. }));
+    assert_eq!(report.results[2].status, AutofillSlotStatus::Skipped);
+    assert_eq!(library.get_bank_preset("Microcosm", 45).unwrap().unwrap().id, tour[0].id);
+    // A slot the pedal didn't take isn't assigned in the library either
+    assert!(library.get_bank_preset("Microcosm", 46).unwrap().is_none());
+}
+
+#[test]
+fn test_autofill_manual_order() {
+    let (library, _temp_dir) = create_test_library();
+    let tour = save_tagged_microcosm_presets(&library, "Tour", 3, "june-tour");
+    let strategy = FillStrategy {
+        order: FillOrder::Manual { ids: vec![tour[2].id.clone(), tour[0].id.clone()] },
+        clear_remainder: false,
+    };
+    
+    let plan = library.plan_autofill("Microcosm", tag_filter("june-tour"), &strategy).unwrap();
+    let ids: Vec<_> = plan.slots.iter().map(|slot| slot.preset_id.clone().unwrap()).collect();
+    assert_eq!(ids, vec![tour[2].id.clone(), tour[0].id.clone()]);
+}
+
+/// Everything published so far on `changes`
+fn drain(changes: &mut tokio::sync::broadcast::Receiver<PresetChangeEvent>) -> Vec<PresetChangeEvent> {
+    std::iter::from_fn(|| changes.try_recv().ok()).collect()
+}
+
+fn bank_changed(pedal_type: &str, bank_number: u8) -> PresetChangeEvent {
+    PresetChangeEvent::BankChanged { pedal_type: pedal_type.to_string(), bank_number }
+}
+
+#[test]
+fn test_watch_changes_reports_each_mutation() {
+    let (library, _temp_dir) = create_test_library();
+    let mut changes = library.watch_changes();
+    
+    let preset = save_tagged_microcosm_presets(&library, "Watched", 1, "live").remove(0);
+    library.toggle_favorite(&preset.id).unwrap();
+    library.assign_to_bank("Microcosm", 45, &preset.id).unwrap();
+    library.clear_bank("Microcosm", 45).unwrap();
+    library.delete_preset(&preset.id).unwrap();
+    library.restore_preset(&preset.id).unwrap();
+    
+    assert_eq!(
+        drain(&mut changes),
+        vec![
+            PresetChangeEvent::Created(preset.id.clone()),
+            PresetChangeEvent::Updated(preset.id.clone()),
+            bank_changed("Microcosm", 45),
+            bank_changed("Microcosm", 45),
+            PresetChangeEvent::Deleted(preset.id.clone()),
+            PresetChangeEvent::Created(preset.id.clone()),
+        ]
+    );
+}
+
+#[test]
+fn test_watch_changes_skips_failed_and_read_only_calls() {
+    let (library, _temp_dir) = create_test_library();
+    let preset = save_tagged_microcosm_presets(&library, "Quiet", 1, "live").remove(0);
+    let mut changes = library.watch_changes();
+    
+    library.get_preset(&preset.id).unwrap();
+    library.list_presets(PresetFilter::default()).unwrap();
+    assert!(library.assign_to_bank("Microcosm", 200, &preset.id).is_err());
+    assert!(library.save_preset(
+        "Quiet 00".to_string(),
+        "Microcosm".to_string(),
+        None,
+        microcosm_parameters(serde_json::json!({})),
+        vec![],
+    ).is_err());
+    
+    assert!(drain(&mut changes).is_empty());
+}
+
+#[test]
+fn test_watch_changes_move_reports_both_slots() {
+    let (library, _temp_dir) = create_test_library();
+    let moved = save_gen_loss(&library, "Moved");
+    library.assign_to_bank("GenLossMkii", 3, &moved).unwrap();
+    let mut changes = library.watch_changes();
+    
+    library.move_to_bank("GenLossMkii", 7, &moved).unwrap();
+    
+    assert_eq!(drain(&mut changes), vec![bank_changed("GenLossMkii", 3), bank_changed("GenLossMkii", 7)]);
+}
+
+#[test]
+fn test_watch_changes_merge_reports_where_each_preset_went() {
+    let (library, _temp_dir) = create_test_library();
+    let presets = save_tagged_microcosm_presets(&library, "Merged", 2, "live");
+    let mut changes = library.watch_changes();
+    
+    library.merge_presets(&presets[0].id, std::slice::from_ref(&presets[1].id)).unwrap();
+    
+    assert_eq!(
+        drain(&mut changes),
+        vec![
+            PresetChangeEvent::Updated(presets[0].id.clone()),
+            PresetChangeEvent::Merged { from: presets[1].id.clone(), into: presets[0].id.clone() },
+            PresetChangeEvent::Deleted(presets[1].id.clone()),
+        ]
+    );
+}
+
+#[test]
+fn test_watch_changes_bulk_operations_report_applied_presets_only() {
+    let (library, _temp_dir) = create_test_library();
+    let presets = save_tagged_microcosm_presets(&library, "Bulk", 2, "live");
+    let missing = librarian_lib::presets::PresetId::generate();
+    let mut changes = library.watch_changes();
+    
+    library.set_favorite_bulk(&[presets[0].id.clone(), missing.clone()], true, true).unwrap();
+    library.delete_presets(&[presets[1].id.clone(), missing], true).unwrap();
+    
+    assert_eq!(
+        drain(&mut changes),
+        vec![PresetChangeEvent::Updated(presets[0].id.clone()), PresetChangeEvent::Deleted(presets[1].id.clone())]
+    );
+}
+
+#[test]
+fn test_watch_changes_tagging_reports_presets_that_gained_a_tag() {
+    let (library, _temp_dir) = create_test_library();
+    let live = save_tagged_microcosm_presets(&library, "Live", 1, "live").remove(0);
+    let studio = save_tagged_microcosm_presets(&library, "Studio", 1, "studio").remove(0);
+    let mut changes = library.watch_changes();
+    
+    let tagged = library.tag_preset_collection(&[live.id.clone(), studio.id.clone()], vec!["live".to_string()]).unwrap();
+    
+    assert_eq!(tagged, 1);
+    assert_eq!(drain(&mut changes), vec![PresetChangeEvent::Updated(studio.id.clone())]);
+}
+
+#[test]
+fn test_watch_changes_reports_purges_and_repairs() {
+    let (library, temp_dir) = create_test_library();
+    let presets = save_tagged_microcosm_presets(&library, "Gone", 2, "live");
+    library.assign_to_bank("Microcosm", 46, &presets[1].id).unwrap();
+    library.delete_preset(&presets[0].id).unwrap();
+    
+    // Deleted behind the library's back, leaving its bank row pointing at nothing
+    let conn = rusqlite::Connection::open(temp_dir.path().join("test.db")).unwrap();
+    conn.execute("DELETE FROM presets WHERE id = ?1", [presets[1].id.as_str()]).unwrap();
+    let mut changes = library.watch_changes();
+    
+    assert_eq!(library.purge_deleted(0).unwrap(), 1);
+    assert_eq!(library.repair_orphaned_banks().unwrap(), 1);
+    
+    assert_eq!(
+        drain(&mut changes),
+        vec![PresetChangeEvent::Deleted(presets[0].id.clone()), bank_changed("Microcosm", 46)]
+    );
+}
+
+#[test]
+fn test_watch_changes_deletes_report_emptied_bank_slots() {
+    let (library, _temp_dir) = create_test_library();
+    let presets = save_tagged_microcosm_presets(&library, "Slotted", 3, "live");
+    library.assign_to_bank("Microcosm", 45, &presets[0].id).unwrap();
+    library.assign_to_bank("Microcosm", 46, &presets[1].id).unwrap();
+    library.assign_to_bank("Microcosm", 47, &presets[2].id).unwrap();
+    let mut changes = library.watch_changes();
+    
+    library.delete_preset(&presets[0].id).unwrap();
+    library.delete_presets(&[presets[1].id.clone()], false).unwrap();
+    library.permanently_delete_preset(&presets[2].id).unwrap();
+    
+    assert_eq!(
+        drain(&mut changes),
+        vec![
+            PresetChangeEvent::Deleted(presets[0].id.clone()),
+            bank_changed("Microcosm", 45),
+            PresetChangeEvent::Deleted(presets[1].id.clone()),
+            bank_changed("Microcosm", 46),
+            PresetChangeEvent::Deleted(presets[2].id.clone()),
+            bank_changed("Microcosm", 47),
+        ]
+    );
+}
+
+#[test]
+fn test_preset_change_event_serializes_camel_case() {
+    assert_eq!(
+        serde_json::to_value(bank_changed("Microcosm", 45)).unwrap(),
+        serde_json::json!({"bankChanged": {"pedalType": "Microcosm", "bankNumber": 45}})
+    );
+    let id = librarian_lib::presets::PresetId::new("abc".to_string());
+    assert_eq!(serde_json::to_value(PresetChangeEvent::Deleted(id)).unwrap(), serde_json::json!({"deleted": "abc"}));
+}
+
+// Rows saved when ChromaConsole slots started at 0 are reported, not silently hidden
+#[test]
+fn test_stranded_chroma_console_banks_are_reported() {
+    let (library, temp_dir) = create_test_library();
+    let preset = library.save_preset(
+        "Old Slot".to_string(),
+        "ChromaConsole".to_string(),
+        None,
+        serde_json::to_value(ChromaConsoleState::default()).unwrap(),
+        vec![],
+    ).unwrap();
+    library.assign_to_bank("ChromaConsole", 40, &preset.id).unwrap();
+    assert!(library.stranded_bank_assignments().unwrap().is_empty());
+    
+    let conn = rusqlite::Connection::open(temp_dir.path().join("test.db")).unwrap();
+    conn.execute(
+        "INSERT INTO pedal_banks (pedal_type, bank_number, preset_id) VALUES ('ChromaConsole', 5, ?1)",
+        [preset.id.as_str()],
+    ).unwrap();
+    drop(library);
+    
+    let library = PresetLibrary::new(temp_dir.path().join("test.db")).unwrap();
+    let stranded = library.stranded_bank_assignments().unwrap();
+    assert_eq!(stranded, vec![StrandedBankAssignment {
+        pedal_type: "ChromaConsole".to_string(),
+        bank_number: 5,
+        preset_id: preset.id.clone(),
+        preset_name: "Old Slot".to_string(),
+    }]);
+    // The row is left in place for the user to reassign
+    assert_eq!(library.get_bank_state("ChromaConsole").unwrap().len(), 40);
+}
@@ -0,0 +1,86 @@
+// Benchmark-style test: listing a large library must stay responsive
+// The UI lists presets on every navigation, so this guards against regressions
+// in query shape (e.g. loading parameters for metadata-only views).
+
+use librarian_lib::midi::pedals::gen_loss_mkii::GenLossMkiiState;
+use librarian_lib::midi::pedals::microcosm::MicrocosmState;
+use librarian_lib::presets::{PresetFilter, PresetLibrary};
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+
+const PRESET_COUNT: usize = 2000;
+
+// Generous for unoptimized test builds on slow CI machines
+const LIST_BUDGET: Duration = Duration::from_millis(750);
+
+fn seed_library() -> (PresetLibrary, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let library = PresetLibrary::new(temp_dir.path().join("bench.db")).unwrap();
+
+    // Real pedal states - presets are validated on save
+    let microcosm = serde_json::to_value(MicrocosmState::default()).unwrap();
+    let gen_loss = serde_json::to_value(GenLossMkiiState::default()).unwrap();
+
+    for i in 0..PRESET_COUNT {
+        let (pedal_type, parameters) = if i % 2 == 0 { ("Microcosm", &microcosm) } else { ("GenLossMkii", &gen_loss) };
+        library.save_preset(
+            format!("Preset {:04}", i),
+            pedal_type.to_string(),
+            Some("Seeded for list benchmarks".to_string()),
+            parameters.clone(),
+            vec!["bench".to_string()],
+        ).unwrap();
+    }
+
+    (library, temp_dir)
+}
+
+#[test]
+fn test_list_2000_presets_within_budget() {
+    let (library, _temp_dir) = seed_library();
+
+    // Warm the statement cache once, then time
+    library.list_presets(PresetFilter::default()).unwrap();
+
+    let start = Instant::now();
+    let presets = library.list_presets(PresetFilter::default()).unwrap();
+    let full_elapsed = start.elapsed();
+    assert_eq!(presets.len(), PRESET_COUNT);
+
+    let start = Instant::now();
+    let summaries = library.list_preset_summaries(PresetFilter::default()).unwrap();
+    let summary_elapsed = start.elapsed();
+    assert_eq!(summaries.len(), PRESET_COUNT);
+
+    let filter = PresetFilter {
+        pedal_type: Some("Microcosm".to_string()),
+        ..Default::default()
+    };
+    let start = Instant::now();
+    let filtered = library.list_preset_summaries(filter).unwrap();
+    let filtered_elapsed = start.elapsed();
+    assert_eq!(filtered.len(), PRESET_COUNT / 2);
+
+    println!(
+        "list: {:?}, summaries: {:?}, filtered summaries: {:?}",
+        full_elapsed, summary_elapsed, filtered_elapsed
+    );
+    assert!(full_elapsed < LIST_BUDGET, "list took {:?}", full_elapsed);
+    assert!(summary_elapsed < LIST_BUDGET, "summaries took {:?}", summary_elapsed);
+    assert!(filtered_elapsed < LIST_BUDGET, "filtered summaries took {:?}", filtered_elapsed);
+}
+
+#[test]
+fn test_summaries_match_full_list() {
+    let (library, _temp_dir) = seed_library();
+
+    let presets = library.list_presets(PresetFilter::default()).unwrap();
+    let summaries = library.list_preset_summaries(PresetFilter::default()).unwrap();
+
+    for (preset, summary) in presets.iter().zip(summaries.iter()) {
+        assert_eq!(preset.id, summary.id);
+        assert_eq!(preset.name, summary.name);
+        assert_eq!(preset.tags, summary.tags);
+        assert_eq!(preset.updated_at, summary.updated_at);
+    }
+}
@@ -1,7 +1,10 @@
 // Integration tests for PresetRepository
 // Tests CRUD operations against in-memory SQLite database
 
-use librarian_lib::presets::{Preset, PresetId, PresetFilter};
+mod common;
+
+use common::microcosm_parameters;
+use librarian_lib::presets::{Preset, PresetError, PresetId, PresetFilter};
 use librarian_lib::presets::PresetLibrary;
 use std::path::PathBuf;
 use tempfile::TempDir;
@@ -23,7 +26,7 @@ fn test_save_and_get_preset() {
         "Test Preset".to_string(),
         "Microcosm".to_string(),
         Some("A test preset".to_string()),
-        serde_json::json!({"activity": 64}),
+        microcosm_parameters(serde_json::json!({"activity": 64})),
         vec!["ambient".to_string()],
     ).unwrap();
     
@@ -45,7 +48,7 @@ fn test_save_preset_with_empty_name_fails() {
         "".to_string(),
         "Microcosm".to_string(),
         None,
-        serde_json::json!({}),
+        microcosm_parameters(serde_json::json!({})),
         vec![],
     );
     
@@ -60,7 +63,7 @@ fn test_save_preset_with_whitespace_name_fails() {
         "   ".to_string(),
         "Microcosm".to_string(),
         None,
-        serde_json::json!({}),
+        microcosm_parameters(serde_json::json!({})),
         vec![],
     );
     
@@ -76,7 +79,7 @@ fn test_save_preset_with_duplicate_name_fails() {
         "Duplicate Name".to_string(),
         "Microcosm".to_string(),
         None,
-        serde_json::json!({}),
+        microcosm_parameters(serde_json::json!({})),
         vec![],
     ).unwrap();
     
@@ -85,7 +88,7 @@ fn test_save_preset_with_duplicate_name_fails() {
         "Duplicate Name".to_string(),
         "Microcosm".to_string(),
         None,
-        serde_json::json!({}),
+        microcosm_parameters(serde_json::json!({})),
         vec![],
     );
     
@@ -101,7 +104,7 @@ fn test_update_preset() {
         "Original Name".to_string(),
         "Microcosm".to_string(),
         Some("Original description".to_string()),
-        serde_json::json!({"activity": 64}),
+        microcosm_parameters(serde_json::json!({"activity": 64})),
         vec!["tag1".to_string()],
     ).unwrap();
     
@@ -113,6 +116,7 @@ fn test_update_preset() {
         Some(vec!["tag1".to_string(), "tag2".to_string()]),
         Some(true),
         Some(serde_json::json!({"activity": 100})),
+        None,
     ).unwrap();
     
     assert_eq!(updated.name, "Updated Name");
@@ -134,6 +138,7 @@ fn test_update_nonexistent_preset_fails() {
         None,
         None,
         None,
+        None,
     );
     
     assert!(result.is_err());
@@ -148,7 +153,7 @@ fn test_delete_preset() {
         "To Delete".to_string(),
         "Microcosm".to_string(),
         None,
-        serde_json::json!({}),
+        microcosm_parameters(serde_json::json!({})),
         vec![],
     ).unwrap();
     
@@ -169,7 +174,7 @@ fn test_list_presets_no_filter() {
         "Preset 1".to_string(),
         "Microcosm".to_string(),
         None,
-        serde_json::json!({}),
+        microcosm_parameters(serde_json::json!({})),
         vec![],
     ).unwrap();
     
@@ -185,7 +190,7 @@ fn test_list_presets_no_filter() {
         "Preset 3".to_string(),
         "Microcosm".to_string(),
         None,
-        serde_json::json!({}),
+        microcosm_parameters(serde_json::json!({})),
         vec![],
     ).unwrap();
     
@@ -203,7 +208,7 @@ fn test_list_presets_filtered_by_pedal_type() {
         "Microcosm 1".to_string(),
         "Microcosm".to_string(),
         None,
-        serde_json::json!({}),
+        microcosm_parameters(serde_json::json!({})),
         vec![],
     ).unwrap();
     
@@ -219,7 +224,7 @@ fn test_list_presets_filtered_by_pedal_type() {
         "Microcosm 2".to_string(),
         "Microcosm".to_string(),
         None,
-        serde_json::json!({}),
+        microcosm_parameters(serde_json::json!({})),
         vec![],
     ).unwrap();
     
@@ -234,6 +239,40 @@ fn test_list_presets_filtered_by_pedal_type() {
     assert!(presets.iter().all(|p| p.pedal_type == "Microcosm"));
 }
 
+#[test]
+fn test_list_presets_filtered_by_created_date() {
+    let (library, _temp_dir) = create_test_library();
+    
+    let preset = library.save_preset(
+        "Microcosm 1".to_string(),
+        "Microcosm".to_string(),
+        None,
+        microcosm_parameters(serde_json::json!({})),
+        vec![],
+    ).unwrap();
+    let created_at = preset.created_at;
+    
+    let count = |created_after: Option<i64>, created_before: Option<i64>| {
+        library.list_presets(PresetFilter {
+            created_after,
+            created_before,
+            ..Default::default()
+        }).unwrap().len()
+    };
+    
+    // Both bounds are inclusive
+    assert_eq!(count(Some(created_at), Some(created_at)), 1);
+    assert_eq!(count(Some(created_at + 1), None), 0);
+    assert_eq!(count(None, Some(created_at - 1)), 0);
+    
+    // The same clause applies to summaries
+    let summaries = library.list_preset_summaries(PresetFilter {
+        created_after: Some(created_at + 1),
+        ..Default::default()
+    }).unwrap();
+    assert!(summaries.is_empty());
+}
+
 #[test]
 fn test_toggle_favorite() {
     let (library, _temp_dir) = create_test_library();
@@ -243,7 +282,7 @@ fn test_toggle_favorite() {
         "Test".to_string(),
         "Microcosm".to_string(),
         None,
-        serde_json::json!({}),
+        microcosm_parameters(serde_json::json!({})),
         vec![],
     ).unwrap();
     
@@ -279,7 +318,7 @@ fn test_assign_to_bank() {
         "Bank Test".to_string(),
         "Microcosm".to_string(),
         None,
-        serde_json::json!({}),
+        microcosm_parameters(serde_json::json!({})),
         vec![],
     ).unwrap();
     
@@ -303,7 +342,7 @@ fn test_assign_to_invalid_bank_fails() {
         "Test".to_string(),
         "Microcosm".to_string(),
         None,
-        serde_json::json!({}),
+        microcosm_parameters(serde_json::json!({})),
         vec![],
     ).unwrap();
     
@@ -321,7 +360,7 @@ fn test_get_presets_with_banks() {
         "Preset 1".to_string(),
         "Microcosm".to_string(),
         None,
-        serde_json::json!({}),
+        microcosm_parameters(serde_json::json!({})),
         vec![],
     ).unwrap();
     
@@ -329,7 +368,7 @@ fn test_get_presets_with_banks() {
         "Preset 2".to_string(),
         "Microcosm".to_string(),
         None,
-        serde_json::json!({}),
+        microcosm_parameters(serde_json::json!({})),
         vec![],
     ).unwrap();
     
@@ -354,3 +393,175 @@ fn test_get_presets_with_banks() {
     assert_eq!(p2.bank_numbers.len(), 1);
     assert!(p2.bank_numbers.contains(&47));
 }
+
+#[test]
+fn test_repair_orphaned_banks() {
+    let (library, temp_dir) = create_test_library();
+    let save = |name: &str| library.save_preset(
+        name.to_string(),
+        "Microcosm".to_string(),
+        None,
+        microcosm_parameters(serde_json::json!({})),
+        vec![],
+    ).unwrap();
+    let kept = save("Kept");
+    let nulled = save("Nulled");
+    let missing = save("Missing");
+    library.assign_to_bank("Microcosm", 45, &kept.id).unwrap();
+    library.assign_to_bank("Microcosm", 46, &nulled.id).unwrap();
+    library.assign_to_bank("Microcosm", 47, &missing.id).unwrap();
+    
+    // Delete presets behind the library's back: once with foreign keys enforced
+    // (the bank row is nulled), once without (the bank row points at nothing)
+    let conn = rusqlite::Connection::open(temp_dir.path().join("test.db")).unwrap();
+    conn.pragma_update(None, "foreign_keys", "ON").unwrap();
+    conn.execute("DELETE FROM presets WHERE id = ?1", [nulled.id.as_str()]).unwrap();
+    conn.pragma_update(None, "foreign_keys", "OFF").unwrap();
+    conn.execute("DELETE FROM presets WHERE id = ?1", [missing.id.as_str()]).unwrap();
+    
+    assert_eq!(library.repair_orphaned_banks().unwrap(), 2);
+    assert_eq!(library.repair_orphaned_banks().unwrap(), 0);
+    
+    let assigned: Vec<u8> = library.get_bank_state("Microcosm").unwrap()
+        .into_iter()
+        .filter(|slot| slot.preset.is_some())
+        .map(|slot| slot.bank_number)
+        .collect();
+    assert_eq!(assigned, vec![45]);
+}
+
+#[test]
+fn test_metadata_over_size_cap_is_rejected() {
+    let (library, _temp_dir) = create_test_library();
+    let oversized = serde_json::json!({ "notes": "x".repeat(8 * 1024) });
+    
+    let result = library.save_preset_with_metadata(
+        "Too Much".to_string(),
+        "Microcosm".to_string(),
+        None,
+        microcosm_parameters(serde_json::json!({})),
+        vec![],
+        oversized.clone(),
+    );
+    assert!(matches!(result, Err(PresetError::InvalidMetadata { .. })));
+    
+    let preset = library.save_preset(
+        "Plain".to_string(),
+        "Microcosm".to_string(),
+        None,
+        microcosm_parameters(serde_json::json!({})),
+        vec![],
+    ).unwrap();
+    let result = library.update_preset(&preset.id, None, None, None, None, None, Some(oversized));
+    assert!(matches!(result, Err(PresetError::InvalidMetadata { .. })));
+    assert_eq!(library.get_preset(&preset.id).unwrap().metadata, serde_json::json!({}));
+}
+
+#[test]
+fn test_filter_by_metadata_key() {
+    let (library, _temp_dir) = create_test_library();
+    let save = |name: &str, metadata: serde_json::Value| library.save_preset_with_metadata(
+        name.to_string(),
+        "Microcosm".to_string(),
+        None,
+        microcosm_parameters(serde_json::json!({})),
+        vec![],
+        metadata,
+    ).unwrap();
+    save("Tempo", serde_json::json!({ "bpm": 92, "key": "D minor" }));
+    save("Credits", serde_json::json!({ "artist": "Someone", "notes": { "bpm": 120 } }));
+    save("Bare", serde_json::json!({}));
+    
+    let filter = PresetFilter {
+        metadata_key: Some("bpm".to_string()),
+        ..Default::default()
+    };
+    let names: Vec<String> = library.list_presets(filter.clone()).unwrap()
+        .into_iter()
+        .map(|p| p.name)
+        .collect();
+    assert_eq!(names, vec!["Tempo"]);
+    
+    let summaries = library.list_preset_summaries(filter).unwrap();
+    assert_eq!(summaries.len(), 1);
+    assert_eq!(summaries[0].metadata["key"], "D minor");
+}
+
+#[test]
+fn test_filter_excludes_ids() {
+    let (library, _temp_dir) = create_test_library();
+    let save = |name: &str| library.save_preset(
+        name.to_string(),
+        "Microcosm".to_string(),
+        None,
+        microcosm_parameters(serde_json::json!({})),
+        vec![],
+    ).unwrap();
+    let loaded = save("Loaded");
+    let other = save("Other");
+    let similar = save("Similar");
+    
+    let names = |exclude_ids: Vec<PresetId>| -> Vec<String> {
+        let mut names: Vec<String> = library.list_presets(PresetFilter { exclude_ids, ..Default::default() })
+            .unwrap()
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+        names.sort();
+        names
+    };
+    
+    // An empty list filters nothing (and must not produce `NOT IN ()`)
+    assert_eq!(names(vec![]), vec!["Loaded", "Other", "Similar"]);
+    assert_eq!(names(vec![loaded.id.clone()]), vec!["Other", "Similar"]);
+    assert_eq!(names(vec![loaded.id.clone(), other.id.clone()]), vec!["Similar"]);
+    
+    let summaries = library.list_preset_summaries(PresetFilter {
+        exclude_ids: vec![loaded.id, other.id, similar.id],
+        ..Default::default()
+    }).unwrap();
+    assert!(summaries.is_empty());
+}
+
+#[test]
+fn test_paged_listing_is_stable_when_presets_share_updated_at() {
+    let (library, temp_dir) = create_test_library();
+    for i in 0..7 {
+        library.save_preset(
+            format!("Preset {}", i),
+            "Microcosm".to_string(),
+            None,
+            microcosm_parameters(serde_json::json!({})),
+            if i % 2 == 0 { vec!["even".to_string()] } else { vec![] },
+        ).unwrap();
+    }
+    // Same second for all of them, as with a bulk import
+    rusqlite::Connection::open(temp_dir.path().join("test.db")).unwrap()
+        .execute("UPDATE presets SET updated_at = 1000", [])
+        .unwrap();
+    
+    let page = |offset: usize| {
+        library.list_presets_page(PresetFilter { limit: Some(3), offset: Some(offset), ..Default::default() }, false).unwrap()
+    };
+    let mut seen = Vec::new();
+    for offset in [0, 3, 6] {
+        let result = page(offset);
+        assert_eq!(result.total_count, 7);
+        assert!(result.presets.iter().all(|preset| preset.parameters.is_null()));
+        seen.extend(result.presets.into_iter().map(|preset| preset.id));
+    }
+    
+    // Every preset exactly once, in the same order as an unpaged listing
+    let all: Vec<PresetId> = library.list_presets(PresetFilter::default()).unwrap().into_iter().map(|p| p.id).collect();
+    assert_eq!(seen, all);
+    assert_eq!(page(3).presets.into_iter().map(|p| p.id).collect::<Vec<_>>(), all[3..6]);
+    
+    // Tags are filtered in SQL, so the count and page agree
+    let even = library.list_presets_page(
+        PresetFilter { tags: vec!["even".to_string()], limit: Some(2), ..Default::default() },
+        true,
+    ).unwrap();
+    assert_eq!(even.total_count, 4);
+    assert_eq!(even.presets.len(), 2);
+    assert!(even.presets.iter().all(|preset| preset.parameters.is_object()));
+}
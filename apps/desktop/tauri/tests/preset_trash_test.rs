@@ -0,0 +1,254 @@
+// Integration tests for the preset trash (soft delete, restore, purge) and archiving
+
+mod common;
+
+use common::microcosm_parameters;
+use librarian_lib::presets::{PresetError, PresetFilter, PresetLibrary};
+use tempfile::TempDir;
+
+/// Helper to create a temporary database for testing
+fn create_test_library() -> (PresetLibrary, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let library = PresetLibrary::new(db_path).unwrap();
+    (library, temp_dir)
+}
+
+fn save(library: &PresetLibrary, name: &str) -> librarian_lib::presets::Preset {
+    library.save_preset(
+        name.to_string(),
+        "Microcosm".to_string(),
+        None,
+        microcosm_parameters(serde_json::json!({})),
+        vec![],
+    ).unwrap()
+}
+
+#[test]
+fn test_delete_moves_preset_to_trash() {
+    let (library, _temp_dir) = create_test_library();
+
+    let preset = save(&library, "Trash Me");
+    library.assign_to_bank("Microcosm", 46, &preset.id).unwrap();
+    library.assign_to_bank("Microcosm", 45, &preset.id).unwrap();
+
+    library.delete_preset(&preset.id).unwrap();
+
+    // Hidden from list/find/bank queries
+    assert!(library.get_preset(&preset.id).is_err());
+    assert!(library.list_presets(PresetFilter::default()).unwrap().is_empty());
+    assert!(library.get_presets_with_banks("Microcosm").unwrap().is_empty());
+    let banks = library.get_bank_state("Microcosm").unwrap();
+    assert!(banks.iter().all(|b| b.preset.is_none()));
+
+    // But still in the trash, remembering its bank slots
+    let trashed = library.list_deleted_presets().unwrap();
+    assert_eq!(trashed.len(), 1);
+    assert_eq!(trashed[0].preset.id, preset.id);
+    assert_eq!(trashed[0].previous_bank_numbers, vec![45, 46]);
+
+    // Deleting twice is an error
+    assert!(library.delete_preset(&preset.id).is_err());
+}
+
+#[test]
+fn test_save_with_name_of_trashed_preset_succeeds() {
+    let (library, _temp_dir) = create_test_library();
+
+    let trashed = save(&library, "Shimmer");
+    library.delete_preset(&trashed.id).unwrap();
+
+    let replacement = save(&library, "Shimmer");
+    assert_ne!(replacement.id, trashed.id);
+
+    // Renaming another preset onto a trashed name also works
+    let other = save(&library, "Other");
+    library.delete_preset(&replacement.id).unwrap();
+    let renamed = library
+        .update_preset(&other.id, Some("Shimmer".to_string()), None, None, None, None, None)
+        .unwrap();
+    assert_eq!(renamed.name, "Shimmer");
+
+    assert_eq!(library.list_deleted_presets().unwrap().len(), 2);
+}
+
+#[test]
+fn test_restore_preset() {
+    let (library, _temp_dir) = create_test_library();
+
+    let preset = save(&library, "Come Back");
+    library.assign_to_bank("Microcosm", 50, &preset.id).unwrap();
+    library.delete_preset(&preset.id).unwrap();
+
+    let restored = library.restore_preset(&preset.id).unwrap();
+    assert_eq!(restored.preset.id, preset.id);
+    assert_eq!(restored.previous_bank_numbers, vec![50]);
+
+    // Visible again, out of the trash, and not silently re-assigned
+    assert_eq!(library.get_preset(&preset.id).unwrap().name, "Come Back");
+    assert!(library.list_deleted_presets().unwrap().is_empty());
+    let bank_50 = library.get_bank_preset("Microcosm", 50).unwrap();
+    assert!(bank_50.is_none());
+
+    // Nothing left to restore
+    assert!(library.restore_preset(&preset.id).is_err());
+}
+
+#[test]
+fn test_restore_conflicts_with_live_name() {
+    let (library, _temp_dir) = create_test_library();
+
+    let trashed = save(&library, "Taken");
+    library.delete_preset(&trashed.id).unwrap();
+    save(&library, "Taken");
+
+    let result = library.restore_preset(&trashed.id);
+    assert!(matches!(result, Err(PresetError::DuplicateName { .. })));
+
+    // Still in the trash
+    assert_eq!(library.list_deleted_presets().unwrap().len(), 1);
+}
+
+#[test]
+fn test_purge_deleted() {
+    let (library, _temp_dir) = create_test_library();
+
+    let kept = save(&library, "Keep");
+    let trashed = save(&library, "Purge");
+    library.assign_to_bank("Microcosm", 47, &trashed.id).unwrap();
+    library.delete_preset(&trashed.id).unwrap();
+
+    // Deleted just now - not old enough for the retention window
+    assert_eq!(library.purge_deleted(30).unwrap(), 0);
+    assert_eq!(library.list_deleted_presets().unwrap().len(), 1);
+
+    // Zero days purges everything in the trash, but never live presets
+    assert_eq!(library.purge_deleted(0).unwrap(), 1);
+    assert!(library.list_deleted_presets().unwrap().is_empty());
+    assert!(library.restore_preset(&trashed.id).is_err());
+    assert!(library.get_preset(&kept.id).is_ok());
+}
+
+#[test]
+fn test_migrates_database_with_unique_names() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("legacy.db");
+
+    // Schema as it was before soft delete
+    {
+        let conn = rusqlite::Connection::open(&db_path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE presets (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL UNIQUE,
+                pedal_type TEXT NOT NULL,
+                description TEXT,
+                parameters TEXT NOT NULL,
+                tags TEXT,
+                is_favorite INTEGER NOT NULL DEFAULT 0,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            );
+            INSERT INTO presets VALUES ('legacy-id', 'Legacy', 'Microcosm', NULL, '{}', '[]', 1, 0, 0);",
+        ).unwrap();
+    }
+
+    let library = PresetLibrary::new(db_path.clone()).unwrap();
+    let legacy = library.list_presets(PresetFilter::default()).unwrap();
+    assert_eq!(legacy.len(), 1);
+    assert_eq!(legacy[0].name, "Legacy");
+    assert!(legacy[0].is_favorite);
+
+    // Live names are still unique, trashed ones are not
+    let id = legacy[0].id.clone();
+    assert!(library.save_preset("Legacy".to_string(), "Microcosm".to_string(), None, microcosm_parameters(serde_json::json!({})), vec![]).is_err());
+    library.delete_preset(&id).unwrap();
+    save(&library, "Legacy");
+
+    // Reopening an already-migrated database is a no-op
+    drop(library);
+    let library = PresetLibrary::new(db_path).unwrap();
+    assert_eq!(library.list_presets(PresetFilter::default()).unwrap().len(), 1);
+    assert_eq!(library.list_deleted_presets().unwrap().len(), 1);
+}
+
+#[test]
+fn test_archive_hides_preset_but_keeps_data() {
+    let (library, _temp_dir) = create_test_library();
+
+    let preset = save(&library, "Archive Me");
+    let other = save(&library, "Keep Me");
+    library.assign_to_bank("Microcosm", 45, &preset.id).unwrap();
+
+    let archived = library.archive_preset(&preset.id).unwrap();
+    assert!(archived.is_archived);
+
+    // Excluded from lists by default
+    let listed = library.list_presets(PresetFilter::default()).unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].id, other.id);
+    assert_eq!(library.list_preset_summaries(PresetFilter::default()).unwrap().len(), 1);
+
+    // ...but can be asked for explicitly
+    let archived_only = library.list_presets(PresetFilter {
+        is_archived: Some(true),
+        ..Default::default()
+    }).unwrap();
+    assert_eq!(archived_only.len(), 1);
+    assert_eq!(archived_only[0].id, preset.id);
+
+    // Still fetchable, still holding its bank slot, not in the trash
+    assert_eq!(library.get_preset(&preset.id).unwrap().parameters, preset.parameters);
+    let banks = library.get_bank_state("Microcosm").unwrap();
+    let slot = banks.iter().find(|b| b.bank_number == 45).unwrap();
+    assert_eq!(slot.preset.as_ref().unwrap().id, preset.id);
+    assert!(library.list_deleted_presets().unwrap().is_empty());
+
+    let unarchived = library.unarchive_preset(&preset.id).unwrap();
+    assert!(!unarchived.is_archived);
+    assert_eq!(library.list_presets(PresetFilter::default()).unwrap().len(), 2);
+}
+
+#[test]
+fn test_delete_archived_preset_is_permanent() {
+    let (library, _temp_dir) = create_test_library();
+
+    let live = save(&library, "Live");
+    let archived = save(&library, "Archived");
+    library.assign_to_bank("Microcosm", 45, &archived.id).unwrap();
+    library.archive_preset(&archived.id).unwrap();
+
+    // A live preset still goes to the trash
+    library.delete_preset(&live.id).unwrap();
+    assert_eq!(library.list_deleted_presets().unwrap().len(), 1);
+
+    // An archived one is gone for good, along with its bank slot
+    library.delete_preset(&archived.id).unwrap();
+    assert!(matches!(library.get_preset(&archived.id), Err(PresetError::NotFound { .. })));
+    assert_eq!(library.list_deleted_presets().unwrap().len(), 1);
+    let banks = library.get_bank_state("Microcosm").unwrap();
+    assert!(banks.iter().all(|b| b.preset.is_none()));
+
+    // The name is free again
+    save(&library, "Archived");
+}
+
+#[test]
+fn test_permanently_delete_preset() {
+    let (library, _temp_dir) = create_test_library();
+
+    let live = save(&library, "Live");
+    let trashed = save(&library, "Trashed");
+    library.delete_preset(&trashed.id).unwrap();
+
+    library.permanently_delete_preset(&live.id).unwrap();
+    library.permanently_delete_preset(&trashed.id).unwrap();
+
+    assert!(library.list_presets(PresetFilter::default()).unwrap().is_empty());
+    assert!(library.list_deleted_presets().unwrap().is_empty());
+    assert!(matches!(
+        library.permanently_delete_preset(&live.id),
+        Err(PresetError::NotFound { .. })
+    ));
+    assert!(library.archive_preset(&live.id).is_err());
+}